@@ -271,8 +271,13 @@ pub fn server(
 pub fn tool(
     name: String,
     server: String,
+    from_scenario: Option<String>,
     global_flags: &crate::commands::GlobalFlags,
 ) -> Result<()> {
+    if let Some(scenario) = from_scenario {
+        return tool_from_scenario(&name, &server, &scenario, global_flags);
+    }
+
     if global_flags.should_output() {
         println!("\n{}", "Adding tool".bright_cyan().bold());
         println!("{}", "────────────────".bright_cyan());
@@ -290,6 +295,142 @@ pub fn tool(
     Ok(())
 }
 
+/// Generate a tool module for `server` from the scenario steps that call it.
+fn tool_from_scenario(
+    name: &str,
+    server: &str,
+    scenario_path: &str,
+    global_flags: &crate::commands::GlobalFlags,
+) -> Result<()> {
+    let not_quiet = global_flags.should_output();
+    if not_quiet {
+        println!("\n{}", "Adding tool from scenario".bright_cyan().bold());
+        println!("{}", "─────────────────────────".bright_cyan());
+    }
+
+    if !PathBuf::from("Cargo.toml").exists() {
+        anyhow::bail!("Not in a workspace directory. Run 'cargo-pmcp new <name>' first.");
+    }
+    let config = WorkspaceConfig::load()?;
+    if !config.has_server(server) {
+        anyhow::bail!(
+            "Server '{}' not found. Add it first with 'cargo pmcp add server {}'.",
+            server,
+            server
+        );
+    }
+
+    let scenario = mcp_tester::scenario::TestScenario::from_file(scenario_path)?;
+    let generated = templates::scenario_tool::generate(name, &scenario)?;
+
+    let core_dir = PathBuf::from(format!("crates/mcp-{}-core", server));
+    let lib_rs = core_dir.join("src/lib.rs");
+    let module_file = core_dir.join(format!("src/{}.rs", generated.module));
+    if module_file.exists() {
+        anyhow::bail!(
+            "{} already exists; remove it to regenerate the tool",
+            module_file.display()
+        );
+    }
+    let lib = fs::read_to_string(&lib_rs)
+        .with_context(|| format!("Failed to read {}", lib_rs.display()))?;
+
+    fs::write(&module_file, &generated.source)
+        .with_context(|| format!("Failed to write {}", module_file.display()))?;
+    fs::write(&lib_rs, declare_module(&lib, &generated.module))
+        .with_context(|| format!("Failed to update {}", lib_rs.display()))?;
+
+    if not_quiet {
+        println!(
+            "  {} Inferred {} and {} from '{}'",
+            "ok".green(),
+            generated.input_type.bright_yellow(),
+            generated.output_type.bright_yellow(),
+            scenario.name
+        );
+        println!(
+            "  {} Wrote {} with {} failing test(s)",
+            "ok".green(),
+            module_file.display(),
+            generated.tests
+        );
+        println!("  {} Declared module in {}", "ok".green(), lib_rs.display());
+
+        println!("\n{}", "Next steps:".bright_white().bold());
+        println!();
+        println!(
+            "  {} Register the tool in build_{}_server():",
+            "1.".bright_cyan().bold(),
+            server.replace('-', "_")
+        );
+        println!(
+            "     {}",
+            format!(
+                ".tool(\"{}\", TypedToolWithOutput::new(\"{}\", |input: {}::{}, _extra| Box::pin({}::handle(input))))",
+                generated.tool, generated.tool, generated.module, generated.input_type, generated.module
+            )
+            .bright_yellow()
+        );
+        println!();
+        println!(
+            "  {} Watch the tests fail, then implement {}::handle:",
+            "2.".bright_cyan().bold(),
+            generated.module
+        );
+        println!(
+            "     {}",
+            format!("cargo test -p mcp-{}-core {}::", server, generated.module).bright_yellow()
+        );
+        println!();
+        println!(
+            "  {} Run the scenario against the live server:",
+            "3.".bright_cyan().bold()
+        );
+        println!(
+            "     {}",
+            format!(
+                "cargo pmcp test run --server {} --scenarios {}",
+                server, scenario_path
+            )
+            .bright_yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Add `pub mod <module>;` after the last module declaration in `lib`.
+fn declare_module(lib: &str, module: &str) -> String {
+    let declaration = format!("pub mod {};", module);
+    if lib.lines().any(|line| line.trim() == declaration) {
+        return lib.to_string();
+    }
+
+    let lines: Vec<&str> = lib.lines().collect();
+    let insert_at = lines
+        .iter()
+        .rposition(|line| {
+            let line = line.trim_start();
+            (line.starts_with("mod ") || line.starts_with("pub mod ")) && line.ends_with(';')
+        })
+        .map(|i| i + 1)
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|line| !line.starts_with("//!"))
+                .map(|i| i + 1)
+        })
+        .unwrap_or(lines.len());
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.insert(insert_at.min(out.len()), declaration);
+    let mut result = out.join("\n");
+    if lib.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
 pub fn workflow(
     name: String,
     server: String,
@@ -311,3 +452,23 @@ pub fn workflow(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declare_module_after_existing_modules() {
+        let lib = "//! Core\n\nmod types;\n\nuse pmcp::Server;\n";
+        assert_eq!(
+            declare_module(lib, "convert"),
+            "//! Core\n\nmod types;\npub mod convert;\n\nuse pmcp::Server;\n"
+        );
+    }
+
+    #[test]
+    fn test_declare_module_is_idempotent() {
+        let lib = "mod types;\npub mod convert;\n";
+        assert_eq!(declare_module(lib, "convert"), lib);
+    }
+}
@@ -0,0 +1,237 @@
+//! Workspace artifact and cache cleanup.
+//!
+//! Removes caches and build leftovers that cargo-pmcp tooling writes outside
+//! of `cargo clean`'s reach:
+//!
+//! - `target/wasm-bridge/` — WASM bridge build cache from `cargo pmcp preview`
+//! - `.pmcp/reports/` — load test JSON reports
+//! - `deploy/.build*/`, `deploy/cdk.out/`, `deploy/cloudflare/build/`,
+//!   `target/lambda/` — deploy build artifacts (regenerated on every deploy)
+//! - `$TMPDIR/mcp-e2e-chromium/` — Chromium downloaded for e2e tests and
+//!   preview screenshots
+//! - `$TMPDIR/pmcp-assets/`, `$TMPDIR/pmcp-template-*/` — extracted assets
+//!   and landing page template checkouts
+//! - `$TMPDIR/pmcp-widget-build/` — cached TypeScript widget bundles
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use super::GlobalFlags;
+
+/// A directory eligible for cleanup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CleanTarget {
+    /// Human-readable category shown in the report
+    category: &'static str,
+    path: PathBuf,
+}
+
+/// Remove cargo-pmcp caches and build artifacts, reporting reclaimed space.
+pub fn execute(dry_run: bool, global_flags: &GlobalFlags) -> Result<()> {
+    let project_root = find_project_root()?;
+    let targets = collect_targets(&project_root, &std::env::temp_dir());
+
+    if global_flags.should_output() {
+        println!();
+        println!(
+            "  {} {}",
+            "cargo pmcp clean".bright_white().bold(),
+            if dry_run {
+                "(dry run)".dimmed()
+            } else {
+                "".normal()
+            }
+        );
+        println!("  {}", "─".repeat(40).dimmed());
+        println!();
+    }
+
+    if targets.is_empty() {
+        if global_flags.should_output() {
+            println!("  {} Nothing to clean", "✓".green());
+            println!();
+        }
+        return Ok(());
+    }
+
+    let mut total = 0u64;
+    let mut failures = 0usize;
+    for target in &targets {
+        let size = dir_size(&target.path);
+        let removed = if dry_run {
+            Ok(())
+        } else {
+            std::fs::remove_dir_all(&target.path)
+                .with_context(|| format!("Failed to remove {}", target.path.display()))
+        };
+
+        match removed {
+            Ok(()) => {
+                total += size;
+                if global_flags.should_output() {
+                    println!(
+                        "  {} {:>10}  {:<18} {}",
+                        if dry_run { "-".dimmed() } else { "✓".green() },
+                        format_size(size),
+                        target.category,
+                        target.path.display().to_string().dimmed()
+                    );
+                }
+            },
+            Err(e) => {
+                failures += 1;
+                eprintln!("  {} {:#}", "✗".red(), e);
+            },
+        }
+    }
+
+    if global_flags.should_output() {
+        println!();
+        let verb = if dry_run { "Would free" } else { "Freed" };
+        println!("  {} {}", verb, format_size(total).bright_white().bold());
+        if dry_run {
+            println!("  Run without {} to delete.", "--dry-run".bright_cyan());
+        }
+        println!();
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} location(s) could not be removed", failures);
+    }
+    Ok(())
+}
+
+/// Find the nearest directory with a `Cargo.toml`, falling back to the current directory.
+fn find_project_root() -> Result<PathBuf> {
+    let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    Ok(current_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").exists())
+        .unwrap_or(current_dir.as_path())
+        .to_path_buf())
+}
+
+/// List the cleanup locations that currently exist.
+fn collect_targets(project_root: &Path, temp_dir: &Path) -> Vec<CleanTarget> {
+    let mut candidates = vec![
+        ("preview cache", project_root.join("target/wasm-bridge")),
+        ("loadtest reports", project_root.join(".pmcp/reports")),
+        ("deploy build", project_root.join("deploy/cdk.out")),
+        ("deploy build", project_root.join("deploy/cloudflare/build")),
+        ("deploy build", project_root.join("target/lambda")),
+        ("e2e chromium", temp_dir.join("mcp-e2e-chromium")),
+        ("asset temp dir", temp_dir.join("pmcp-assets")),
+        ("widget build cache", temp_dir.join("pmcp-widget-build")),
+    ];
+    // deploy/.build and deploy/.build-{lambda_type}
+    candidates.extend(
+        prefixed_dirs(&project_root.join("deploy"), ".build")
+            .into_iter()
+            .map(|path| ("deploy build", path)),
+    );
+    // Landing page template checkouts: pmcp-template-{timestamp}
+    candidates.extend(
+        prefixed_dirs(temp_dir, "pmcp-template-")
+            .into_iter()
+            .map(|path| ("asset temp dir", path)),
+    );
+
+    let mut targets: Vec<CleanTarget> = candidates
+        .into_iter()
+        .filter(|(_, path)| path.is_dir())
+        .map(|(category, path)| CleanTarget { category, path })
+        .collect();
+    targets.sort_by(|a, b| a.path.cmp(&b.path));
+    targets.dedup();
+    targets
+}
+
+/// Subdirectories of `dir` whose names start with `prefix`.
+fn prefixed_dirs(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Total size of regular files under `path`, without following symlinks.
+fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, len: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn collects_existing_locations_only() {
+        let project = tempfile::tempdir().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        write_file(&project.path().join(".pmcp/reports/run.json"), 10);
+        write_file(&project.path().join("deploy/.build/bootstrap"), 10);
+        write_file(&project.path().join("deploy/.build-arm64/bootstrap"), 10);
+        write_file(&tmp.path().join("mcp-e2e-chromium/chrome"), 10);
+        write_file(&tmp.path().join("pmcp-template-1700000000/README.md"), 10);
+        // Unrelated directories are left alone
+        write_file(&project.path().join("deploy/lib/stack.ts"), 10);
+        write_file(&tmp.path().join("other/file"), 10);
+
+        let targets = collect_targets(project.path(), tmp.path());
+        let paths: Vec<&Path> = targets.iter().map(|t| t.path.as_path()).collect();
+
+        assert_eq!(targets.len(), 5);
+        assert!(paths.contains(&project.path().join(".pmcp/reports").as_path()));
+        assert!(paths.contains(&project.path().join("deploy/.build").as_path()));
+        assert!(paths.contains(&project.path().join("deploy/.build-arm64").as_path()));
+        assert!(paths.contains(&tmp.path().join("mcp-e2e-chromium").as_path()));
+        assert!(paths.contains(&tmp.path().join("pmcp-template-1700000000").as_path()));
+    }
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(&dir.path().join("a"), 100);
+        write_file(&dir.path().join("nested/b"), 24);
+
+        assert_eq!(dir_size(dir.path()), 124);
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn format_size_uses_binary_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -975,15 +975,12 @@ async fn start_http_in_background(default_port: u16, server_name: &str) -> pmcp:
 
     let addr = SocketAddr::new(host, port);
 
-    // Create and start HTTP server
-    let config = pmcp::server::streamable_http_server::StreamableHttpServerConfig {{
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-    }};
+    // Create and start HTTP server. The serverless profile keeps the server
+    // stateless and bounds concurrency, body size, and caching so small
+    // Lambda memory configurations don't run out of memory under load.
+    let config = pmcp::server::streamable_http_server::StreamableHttpServerConfig::from_profile(
+        pmcp::server::resource_profile::ResourceProfile::serverless(),
+    );
 
     let http_server = pmcp::server::streamable_http_server::StreamableHttpServer::with_config(
         addr,
@@ -93,6 +93,20 @@ pub struct DeployCommand {
     #[arg(long)]
     no_oauth: bool,
 
+    /// Start over instead of resuming an unfinished previous deploy.
+    ///
+    /// By default, a deploy that was interrupted after building or uploading
+    /// reuses the build and continues the remote deployment on the next run,
+    /// as long as the project sources have not changed.
+    #[arg(long)]
+    fresh: bool,
+
+    /// Take the deploy lock even if another deploy appears to hold it.
+    ///
+    /// Use this only when a previous deploy crashed and left its lock behind.
+    #[arg(long)]
+    force_unlock: bool,
+
     #[command(subcommand)]
     action: Option<DeployAction>,
 }
@@ -143,9 +157,13 @@ pub enum DeployAction {
 
     /// View deployment metrics
     Metrics {
-        /// Time period (1h, 24h, 7d, 30d)
+        /// Time period (1h, 24h, 7d)
         #[arg(long, default_value = "24h")]
         period: String,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text")]
+        output: String,
     },
 
     /// Test the deployment
@@ -430,11 +448,17 @@ impl DeployCommand {
                         let config = crate::deployment::DeployConfig::load(&project_root)?;
                         target.logs(&config, *tail, *lines).await
                     },
-                    DeployAction::Metrics { period } => {
+                    DeployAction::Metrics { period, output } => {
                         let config = crate::deployment::DeployConfig::load(&project_root)?;
                         let metrics = target.metrics(&config, period).await?;
                         // Requested data -- always show
-                        println!("Metrics for {}: {}", target.name(), metrics.period);
+                        match output.as_str() {
+                            "json" => println!("{}", serde_json::to_string_pretty(&metrics)?),
+                            "text" => metrics.display(),
+                            other => {
+                                bail!("Unknown output format '{}' (expected text or json)", other)
+                            },
+                        }
                         Ok(())
                     },
                     DeployAction::Test {} => {
@@ -639,14 +663,42 @@ impl DeployCommand {
                     );
                 }
 
-                let artifact = target.build(&config).await?;
-                let outputs = target.deploy(&config, artifact).await?;
+                let lock = crate::deployment::DeployLock::acquire(
+                    &project_root,
+                    &target_id,
+                    self.force_unlock,
+                )?;
+                let mut state = crate::deployment::DeployState::begin(
+                    &project_root,
+                    &target_id,
+                    &config.server.name,
+                    self.fresh,
+                )?;
+                if state.is_resumed() && global_flags.should_output() {
+                    println!(
+                        "♻️  Resuming unfinished deploy (attempt {}, use --fresh to start over)",
+                        state.attempts
+                    );
+                }
+                target
+                    .acquire_lock(&config, lock.info(), &state, self.force_unlock)
+                    .await?;
+
+                let deployed = Self::deploy_resumable(target.as_ref(), &config, &mut state).await;
+                if let Err(e) = target.release_lock(&config, lock.info()).await {
+                    eprintln!("⚠️  Failed to release the remote deploy lock: {}", e);
+                }
+                let outputs = deployed?;
+                state.finish()?;
+                drop(lock);
 
                 if global_flags.should_output() {
                     println!();
                     outputs.display();
                 }
 
+                Self::notify_webhooks(&config, &target_id, &outputs, global_flags).await;
+
                 // Save deployment info for pmcp-run target (for landing page integration)
                 if target_id == "pmcp-run" {
                     Self::save_deployment_info(&project_root, &outputs)?;
@@ -746,6 +798,77 @@ impl DeployCommand {
         }
     }
 
+    /// Build (or reuse the build of an unfinished attempt) and deploy, checkpointing in `state`.
+    async fn deploy_resumable(
+        target: &dyn crate::deployment::r#trait::DeploymentTarget,
+        config: &crate::deployment::DeployConfig,
+        state: &mut crate::deployment::DeployState,
+    ) -> Result<crate::deployment::DeploymentOutputs> {
+        let artifact = match state.reusable_artifact() {
+            Some(artifact) => {
+                println!("♻️  Reusing build from the previous attempt");
+                artifact
+            },
+            None => {
+                let artifact = target.build(config).await?;
+                state.record_artifact(&artifact)?;
+                artifact
+            },
+        };
+        target.deploy_resumable(config, artifact, state).await
+    }
+
+    /// Post a `deploy.completed` event to the webhooks configured in `.pmcp/deploy.toml`.
+    ///
+    /// Delivery failures are reported as warnings; the deployment itself has
+    /// already succeeded at this point.
+    async fn notify_webhooks(
+        config: &crate::deployment::DeployConfig,
+        target_id: &str,
+        outputs: &crate::deployment::DeploymentOutputs,
+        global_flags: &crate::commands::GlobalFlags,
+    ) {
+        use pmcp::server::webhooks::{events, WebhookEndpoint, WebhookEvent, WebhookSink};
+
+        if config.webhooks.is_empty() {
+            return;
+        }
+
+        let mut sink = WebhookSink::new();
+        for hook in &config.webhooks {
+            let mut endpoint = WebhookEndpoint::new(&hook.url);
+            if let Some(var) = &hook.secret_env {
+                match std::env::var(var) {
+                    Ok(secret) => endpoint = endpoint.secret(secret),
+                    Err(_) => eprintln!(
+                        "Warning: webhook secret variable {} is not set; sending unsigned",
+                        var
+                    ),
+                }
+            }
+            if !hook.events.is_empty() {
+                endpoint = endpoint.events(hook.events.iter().cloned());
+            }
+            sink = sink.endpoint(endpoint);
+        }
+
+        let event = WebhookEvent::new(
+            events::DEPLOY_COMPLETED,
+            serde_json::json!({
+                "server": config.server.name,
+                "target": target_id,
+                "outputs": outputs,
+            }),
+        );
+        match sink.send(&event).await {
+            Ok(()) if global_flags.should_output() => {
+                println!("Webhooks notified: {}", events::DEPLOY_COMPLETED);
+            },
+            Ok(()) => {},
+            Err(e) => eprintln!("Warning: webhook delivery failed: {}", e),
+        }
+    }
+
     /// Save deployment info to .pmcp/deployment.toml for landing page integration
     fn save_deployment_info(
         project_root: &PathBuf,
@@ -19,7 +19,7 @@ const LAMBDA_BINARIES: &[&str] = &["bootstrap"];
 ///
 /// Excludes Lambda-only binaries (e.g. `bootstrap`) that cannot run locally.
 /// Uses `cargo metadata` to discover available binary targets.
-fn resolve_server_binary(server: &str) -> Result<String> {
+pub(crate) fn resolve_server_binary(server: &str) -> Result<String> {
     let candidates = [format!("{}-server", server), server.to_string()];
 
     // Use cargo metadata to find available binary targets
@@ -21,7 +21,8 @@ pub enum LoadtestCommand {
     ///
     /// Executes a load test using the scenario defined in .pmcp/loadtest.toml
     /// (or a custom config path). Reports results to the terminal and writes
-    /// a JSON report to .pmcp/reports/.
+    /// JSON and HTML reports to .pmcp/reports/. Exits with an error if any
+    /// configured [thresholds] fail.
     Run {
         /// Target MCP server URL
         url: String,
@@ -42,7 +43,7 @@ pub enum LoadtestCommand {
         #[arg(long)]
         iterations: Option<u64>,
 
-        /// Disable JSON report output
+        /// Disable JSON and HTML report output
         #[arg(long)]
         no_report: bool,
 
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use cargo_pmcp::loadtest::config::LoadTestConfig;
 use cargo_pmcp::loadtest::engine::LoadTestEngine;
+use cargo_pmcp::loadtest::html::write_html_report;
 use cargo_pmcp::loadtest::report::{write_report, LoadTestReport};
 use cargo_pmcp::loadtest::summary::render_summary;
 
@@ -15,7 +16,8 @@ use crate::commands::GlobalFlags;
 /// Execute the `loadtest run` command.
 ///
 /// Loads config (via explicit path or auto-discovery), applies CLI overrides,
-/// builds and runs the load test engine, and prints a basic results summary.
+/// builds and runs the load test engine, prints a results summary, and writes
+/// the JSON and HTML reports. Fails if any configured threshold is not met.
 pub async fn execute_run(
     url: String,
     config_path: Option<PathBuf>,
@@ -91,9 +93,21 @@ pub async fn execute_run(
     let summary = render_summary(&result, engine.config(), &url);
     println!("{summary}");
 
-    // Step 5: Write JSON report (unless --no-report)
+    let report = LoadTestReport::from_result(&result, engine.config(), &url);
+    if !report.thresholds.is_empty() {
+        println!();
+        println!("  thresholds:");
+        for outcome in &report.thresholds {
+            let mark = if outcome.passed { "PASS" } else { "FAIL" };
+            println!(
+                "    {mark} {} (limit {}, actual {:.2})",
+                outcome.metric, outcome.limit, outcome.actual
+            );
+        }
+    }
+
+    // Step 5: Write JSON and HTML reports (unless --no-report)
     if !no_report {
-        let report = LoadTestReport::from_result(&result, engine.config(), &url);
         let cwd = std::env::current_dir()?;
         match write_report(&report, &cwd) {
             Ok(path) => {
@@ -101,6 +115,18 @@ pub async fn execute_run(
                     eprintln!();
                     eprintln!("Report written to: {}", path.display());
                 }
+                match write_html_report(&report, &path.with_extension("html")) {
+                    Ok(html_path) => {
+                        if global_flags.should_output() {
+                            eprintln!("HTML report written to: {}", html_path.display());
+                        }
+                    },
+                    Err(e) => {
+                        if global_flags.should_output() {
+                            eprintln!("Warning: Failed to write HTML report: {}", e);
+                        }
+                    },
+                }
             },
             Err(e) => {
                 if global_flags.should_output() {
@@ -112,6 +138,16 @@ pub async fn execute_run(
         }
     }
 
+    if !report.thresholds_passed() {
+        let failed: Vec<&str> = report
+            .thresholds
+            .iter()
+            .filter(|t| !t.passed)
+            .map(|t| t.metric.as_str())
+            .collect();
+        anyhow::bail!("Load test thresholds failed: {}", failed.join(", "));
+    }
+
     Ok(())
 }
 
@@ -163,7 +199,7 @@ fn discover_config() -> Option<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cargo_pmcp::loadtest::config::{LoadTestConfig, ScenarioStep, Settings};
+    use cargo_pmcp::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Thresholds};
 
     #[test]
     fn test_discover_config_returns_none_when_no_config() {
@@ -189,6 +225,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
 
         let gf = GlobalFlags {
@@ -217,6 +254,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
 
         let gf = GlobalFlags {
@@ -245,6 +283,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
 
         let gf = GlobalFlags {
@@ -273,6 +312,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
 
         let gf = GlobalFlags {
@@ -0,0 +1,285 @@
+//! Port MCP servers written with other SDKs to pmcp.
+//!
+//! `cargo pmcp migrate --from typescript ./my-ts-server` reads the server's
+//! tool, resource and prompt registrations and scaffolds an equivalent pmcp
+//! workspace: one typed handler stub per tool, resources and prompts
+//! registered with their original URIs and arguments, and `TODO(migrate)`
+//! markers wherever the original logic still has to be ported.
+//!
+//! Registrations come from a schema export when one is given with `--schema`
+//! (the output of `cargo pmcp schema export`, or a raw `tools/list` response),
+//! and otherwise from source heuristics over the project's TypeScript files.
+
+mod scaffold;
+mod typescript;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+use super::schema::{McpSchema, PromptSchema, ResourceSchema, ToolSchema};
+use super::GlobalFlags;
+use crate::templates;
+use crate::utils::config::WorkspaceConfig;
+
+/// Port assigned to the migrated server in `.pmcp-config.toml`.
+const DEFAULT_PORT: u16 = 3000;
+
+/// SDK the server is migrated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MigrateSource {
+    /// The official TypeScript SDK (`@modelcontextprotocol/sdk`)
+    Typescript,
+}
+
+/// A registration found in the original server.
+#[derive(Debug, Clone)]
+pub struct Migrated<T> {
+    /// What the original server registers.
+    pub item: T,
+    /// `file:line` of the registration, when read from source.
+    pub origin: Option<String>,
+    /// Parts of the registration that could not be translated.
+    pub todos: Vec<String>,
+}
+
+impl<T> Migrated<T> {
+    fn exported(item: T) -> Self {
+        Self {
+            item,
+            origin: None,
+            todos: Vec::new(),
+        }
+    }
+}
+
+/// Everything the original server registers.
+#[derive(Debug, Default)]
+pub struct Inventory {
+    pub tools: Vec<Migrated<ToolSchema>>,
+    pub resources: Vec<Migrated<ResourceSchema>>,
+    pub prompts: Vec<Migrated<PromptSchema>>,
+    /// Shared schema definitions referenced by tool schemas.
+    pub definitions: Map<String, Value>,
+    /// Problems that are not tied to a single registration.
+    pub warnings: Vec<String>,
+}
+
+impl Inventory {
+    fn is_empty(&self) -> bool {
+        self.tools.is_empty() && self.resources.is_empty() && self.prompts.is_empty()
+    }
+}
+
+/// Scaffold a pmcp workspace from the server at `source_dir`.
+pub fn execute(
+    from: MigrateSource,
+    source_dir: String,
+    name: Option<String>,
+    schema: Option<String>,
+    path: Option<String>,
+    global_flags: &GlobalFlags,
+) -> Result<()> {
+    let not_quiet = global_flags.should_output();
+    let source_dir = PathBuf::from(source_dir);
+    if !source_dir.is_dir() {
+        anyhow::bail!("'{}' is not a directory", source_dir.display());
+    }
+
+    let name = match name {
+        Some(name) => name,
+        None => default_name(&source_dir)?,
+    };
+    let workspace_dir = match path {
+        Some(p) => PathBuf::from(p).join(&name),
+        None => PathBuf::from(&name),
+    };
+    if workspace_dir.exists() {
+        anyhow::bail!("Directory '{}' already exists", workspace_dir.display());
+    }
+
+    if not_quiet {
+        println!(
+            "\n{}",
+            format!("Migrating {} from {}", name, from.label())
+                .bright_cyan()
+                .bold()
+        );
+        println!("{}", "────────────────────────────────────".bright_cyan());
+    }
+
+    let inventory = match &schema {
+        Some(schema) => load_schema(Path::new(schema))?,
+        None => match from {
+            MigrateSource::Typescript => typescript::scan(&source_dir),
+        },
+    };
+    if inventory.is_empty() {
+        anyhow::bail!(
+            "No tool, resource or prompt registrations found in '{}'. \
+             Export the schema from the running server and pass it with --schema.",
+            source_dir.display()
+        );
+    }
+    if not_quiet {
+        println!(
+            "  {} Found {} tools, {} resources, {} prompts{}",
+            "✓".green(),
+            inventory.tools.len(),
+            inventory.resources.len(),
+            inventory.prompts.len(),
+            match &schema {
+                Some(schema) => format!(" in {}", schema),
+                None => String::new(),
+            }
+        );
+    }
+
+    std::fs::create_dir_all(workspace_dir.join("crates"))
+        .context("Failed to create workspace directory")?;
+    std::fs::create_dir_all(workspace_dir.join("scenarios"))
+        .context("Failed to create scenarios directory")?;
+    templates::workspace::generate(&workspace_dir, &name)?;
+    templates::server_common::generate(&workspace_dir)?;
+    let todos = scaffold::generate(&workspace_dir, &name, &inventory, &source_dir)?;
+
+    let mut config = WorkspaceConfig::default();
+    config.add_server(name.clone(), DEFAULT_PORT, "migrated".to_string());
+    config
+        .save_in(&workspace_dir)
+        .context("Failed to save workspace config")?;
+
+    if not_quiet {
+        println!(
+            "  {} Created mcp-{}-core and {}-server",
+            "✓".green(),
+            name,
+            name
+        );
+        println!("  {} Wrote MIGRATION.md", "✓".green());
+        for warning in &inventory.warnings {
+            println!("  {} {}", "⚠".yellow(), warning);
+        }
+        println!(
+            "\n{} Workspace created with {} TODO(migrate) markers",
+            "✓".green().bold(),
+            todos
+        );
+        println!("\nNext steps:");
+        println!("  cd {}", workspace_dir.display());
+        println!("  grep -rn 'TODO(migrate)' crates/   # port each handler");
+        println!("  cargo pmcp dev --server {}", name);
+    }
+
+    Ok(())
+}
+
+impl MigrateSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Typescript => "TypeScript",
+        }
+    }
+}
+
+/// Workspace name from `package.json`, else the directory name.
+fn default_name(source_dir: &Path) -> Result<String> {
+    let from_package = std::fs::read_to_string(source_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|package| package.get("name")?.as_str().map(str::to_string));
+    let raw = match from_package {
+        Some(name) => name,
+        None => source_dir
+            .canonicalize()
+            .context("Failed to resolve source directory")?
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    let name = crate_name(&raw);
+    if name.is_empty() {
+        anyhow::bail!("Could not derive a server name from '{}'; pass --name", raw);
+    }
+    Ok(name)
+}
+
+/// Kebab-case crate name for an npm package name (`@acme/Weather_MCP` -> `weather-mcp`).
+fn crate_name(package: &str) -> String {
+    let unscoped = package.rsplit('/').next().unwrap_or(package);
+    let mut name = String::new();
+    for c in unscoped.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    name.trim_end_matches('-').to_string()
+}
+
+/// Read registrations from a `cargo pmcp schema export` file or a raw
+/// `tools/list` response (optionally wrapped in a JSON-RPC envelope).
+fn load_schema(path: &Path) -> Result<Inventory> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read schema {}", path.display()))?;
+    let mut value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse schema {}", path.display()))?;
+    if let Some(result) = value.get_mut("result") {
+        value = result.take();
+    }
+    let object = value
+        .as_object_mut()
+        .with_context(|| format!("Schema {} is not a JSON object", path.display()))?;
+    object
+        .entry("server_id")
+        .or_insert_with(|| Value::String(String::new()));
+    object
+        .entry("name")
+        .or_insert_with(|| Value::String(String::new()));
+    let schema: McpSchema = serde_json::from_value(value)
+        .with_context(|| format!("Invalid schema {}", path.display()))?;
+
+    Ok(Inventory {
+        tools: schema.tools.into_iter().map(Migrated::exported).collect(),
+        resources: schema
+            .resources
+            .into_iter()
+            .map(Migrated::exported)
+            .collect(),
+        prompts: schema.prompts.into_iter().map(Migrated::exported).collect(),
+        definitions: schema.definitions,
+        warnings: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(crate_name("@acme/Weather_MCP"), "weather-mcp");
+        assert_eq!(crate_name("my-server"), "my-server");
+        assert_eq!(crate_name("--x--"), "x");
+    }
+
+    #[test]
+    fn test_load_tools_list_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tools.json");
+        std::fs::write(
+            &path,
+            r#"{"jsonrpc":"2.0","id":1,"result":{"tools":[{"name":"echo","inputSchema":{"type":"object","properties":{"text":{"type":"string"}}}}]}}"#,
+        )
+        .unwrap();
+
+        let inventory = load_schema(&path).unwrap();
+        assert_eq!(inventory.tools.len(), 1);
+        assert_eq!(inventory.tools[0].item.name, "echo");
+        assert!(inventory.tools[0].origin.is_none());
+        assert!(inventory.resources.is_empty());
+    }
+}
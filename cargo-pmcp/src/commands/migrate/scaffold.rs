@@ -0,0 +1,676 @@
+//! Workspace scaffolding for migrated servers.
+//!
+//! Writes `mcp-<name>-core` with one module per tool under `src/tools/`,
+//! `src/resources.rs` and `src/prompts.rs` when the original server has
+//! them, the `<name>-server` binary crate, and `MIGRATION.md` listing every
+//! registration, where it came from, and what still has to be ported.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use super::{Inventory, Migrated};
+use crate::commands::schema::bindings::{field_ident, handler_types};
+use crate::commands::schema::{PromptSchema, ResourceSchema, ToolSchema};
+use crate::templates::server::{generate_server_crate, update_workspace_members};
+
+/// Marker left wherever generated code needs a human.
+const TODO: &str = "TODO(migrate)";
+
+/// Write the core and server crates and the migration report.
+///
+/// Returns the number of `TODO(migrate)` markers in the generated code.
+pub fn generate(
+    workspace_dir: &Path,
+    name: &str,
+    inventory: &Inventory,
+    source_dir: &Path,
+) -> Result<usize> {
+    let core_name = format!("mcp-{}-core", name);
+    let server_name = format!("{}-server", name);
+    let core_dir = workspace_dir.join("crates").join(&core_name);
+    let tools_dir = core_dir.join("src").join("tools");
+    fs::create_dir_all(&tools_dir).context("Failed to create core src/tools directory")?;
+
+    let mut todos = 0;
+    let templated = inventory.resources.iter().any(|r| is_template(&r.item.uri));
+    fs::write(
+        core_dir.join("Cargo.toml"),
+        core_cargo_toml(&core_name, templated),
+    )
+    .context("Failed to create core Cargo.toml")?;
+
+    let mut modules = HashSet::new();
+    let mut registrations = Vec::new();
+    let mut mod_rs = String::from("//! Tool handlers migrated from the original server.\n\n");
+    for tool in &inventory.tools {
+        let module = unique_module(&mut modules, &tool.item.name);
+        let (source, registration) = tool_module(&module, tool, inventory);
+        todos += source.matches(TODO).count();
+        fs::write(tools_dir.join(format!("{}.rs", module)), source)
+            .with_context(|| format!("Failed to create tools/{}.rs", module))?;
+        let _ = writeln!(mod_rs, "pub mod {};", module);
+        registrations.push(registration);
+    }
+    fs::write(tools_dir.join("mod.rs"), mod_rs).context("Failed to create tools/mod.rs")?;
+
+    if !inventory.resources.is_empty() {
+        let source = resources_module(&inventory.resources);
+        todos += source.matches(TODO).count();
+        fs::write(core_dir.join("src/resources.rs"), source)
+            .context("Failed to create resources.rs")?;
+    }
+    if !inventory.prompts.is_empty() {
+        let source = prompts_module(&inventory.prompts);
+        todos += source.matches(TODO).count();
+        fs::write(core_dir.join("src/prompts.rs"), source)
+            .context("Failed to create prompts.rs")?;
+    }
+
+    fs::write(
+        core_dir.join("src/lib.rs"),
+        lib_rs(name, inventory, &registrations),
+    )
+    .context("Failed to create core lib.rs")?;
+
+    generate_server_crate(&workspace_dir.join("crates").join(&server_name), name)?;
+    update_workspace_members(workspace_dir, &core_name, &server_name)?;
+
+    fs::write(
+        workspace_dir.join("MIGRATION.md"),
+        report(name, inventory, source_dir),
+    )
+    .context("Failed to create MIGRATION.md")?;
+
+    Ok(todos)
+}
+
+fn core_cargo_toml(core_name: &str, templated: bool) -> String {
+    let mut out = format!(
+        r#"[package]
+name = "{}"
+version.workspace = true
+edition.workspace = true
+license.workspace = true
+authors.workspace = true
+
+[dependencies]
+pmcp = {{ workspace = true }}
+serde = {{ workspace = true }}
+serde_json = {{ workspace = true }}
+schemars = {{ workspace = true }}
+"#,
+        core_name
+    );
+    if templated {
+        out.push_str("async-trait = { workspace = true }\n");
+    }
+    out.push_str("\n[dev-dependencies]\ntokio = { workspace = true }\n");
+    out
+}
+
+/// Module name for `tool`, suffixed when two tools map to the same one.
+fn unique_module(taken: &mut HashSet<String>, tool: &str) -> String {
+    let base = field_ident(tool).trim_start_matches("r#").to_string();
+    let mut module = base.clone();
+    let mut n = 2;
+    while !taken.insert(module.clone()) {
+        module = format!("{}_{}", base, n);
+        n += 1;
+    }
+    module
+}
+
+/// Source of `tools/<module>.rs` and the builder call that registers it.
+fn tool_module(
+    module: &str,
+    tool: &Migrated<ToolSchema>,
+    inventory: &Inventory,
+) -> (String, String) {
+    let name = &tool.item.name;
+    let types = handler_types(&tool.item, &inventory.definitions);
+    let output = types
+        .output
+        .clone()
+        .unwrap_or_else(|| "serde_json::Value".to_string());
+
+    let mut out = String::new();
+    let _ = writeln!(out, "//! The `{}` tool.", name);
+    out.push_str("//!\n");
+    match &tool.origin {
+        Some(origin) => {
+            let _ = writeln!(
+                out,
+                "//! Migrated by `cargo pmcp migrate` from `{}`.\n",
+                origin
+            );
+        },
+        None => out.push_str("//! Migrated by `cargo pmcp migrate` from a schema export.\n\n"),
+    }
+    out.push_str("use schemars::JsonSchema;\n");
+    out.push_str("use serde::{Deserialize, Serialize};\n\n");
+    out.push_str(&types.source);
+    out.push('\n');
+    match tool.item.description.as_deref() {
+        Some(description) => write_doc(&mut out, description),
+        None => {
+            let _ = writeln!(out, "/// Handle a call to `{}`.", name);
+        },
+    }
+    let _ = writeln!(
+        out,
+        "pub async fn handle(input: {}) -> pmcp::Result<{}> {{",
+        types.input, output
+    );
+    match &tool.origin {
+        Some(origin) => {
+            let _ = writeln!(out, "    // {}: port the handler from {}.", TODO, origin);
+        },
+        None => {
+            let _ = writeln!(out, "    // {}: port the handler.", TODO);
+        },
+    }
+    for todo in &tool.todos {
+        let _ = writeln!(out, "    // {}: {}", TODO, todo);
+    }
+    out.push_str("    let _ = input;\n");
+    let _ = writeln!(
+        out,
+        "    Err(pmcp::Error::internal(\"{} is not implemented yet\"))",
+        name
+    );
+    out.push_str("}\n");
+
+    let constructor = if types.output.is_some() {
+        "TypedToolWithOutput"
+    } else {
+        "TypedTool"
+    };
+    let mut registration = String::new();
+    let _ = writeln!(registration, "        .tool(");
+    let _ = writeln!(registration, "            {:?},", name);
+    let _ = writeln!(
+        registration,
+        "            {}::new({:?}, |input: tools::{}::{}, _extra| {{",
+        constructor, name, module, types.input
+    );
+    let _ = writeln!(
+        registration,
+        "                Box::pin(tools::{}::handle(input))",
+        module
+    );
+    registration.push_str("            })");
+    if let Some(description) = &tool.item.description {
+        let _ = write!(
+            registration,
+            "\n            .with_description({:?})",
+            description
+        );
+    }
+    if let Some(annotations) = &tool.item.annotations {
+        if let Some(title) = &annotations.title {
+            let _ = write!(
+                registration,
+                "\n            .with_annotations(pmcp::types::ToolAnnotations::new().with_title({:?}))",
+                title
+            );
+        }
+        for (hint, method) in [
+            (annotations.read_only_hint, "read_only"),
+            (annotations.destructive_hint, "destructive"),
+            (annotations.idempotent_hint, "idempotent"),
+            (annotations.open_world_hint, "open_world"),
+        ] {
+            if hint == Some(true) {
+                let _ = write!(registration, "\n            .{}()", method);
+            }
+        }
+    }
+    registration.push_str(",\n        )\n");
+
+    (out, registration)
+}
+
+fn is_template(uri: &str) -> bool {
+    uri.contains('{')
+}
+
+/// `resources.rs`: static resources in a `ResourceCollection`, URI templates
+/// in one `DynamicResourceProvider`.
+fn resources_module(resources: &[Migrated<ResourceSchema>]) -> String {
+    let (templates, fixed): (Vec<_>, Vec<_>) =
+        resources.iter().partition(|r| is_template(&r.item.uri));
+
+    let mut out = String::from("//! Resources migrated from the original server.\n\n");
+    if !templates.is_empty() {
+        out.push_str("use async_trait::async_trait;\n");
+        out.push_str(
+            "use pmcp::server::dynamic_resources::{DynamicResourceProvider, RequestContext, UriParams};\n",
+        );
+    }
+    out.push_str("use pmcp::server::simple_resources::{ResourceCollection, StaticResource};\n");
+    if !templates.is_empty() {
+        out.push_str("use pmcp::types::{ReadResourceResult, ResourceTemplate};\n");
+        out.push_str("use std::sync::Arc;\n");
+    }
+
+    out.push_str("\n/// Every resource the server exposes.\n");
+    out.push_str("pub fn collection() -> ResourceCollection {\n");
+    out.push_str("    ResourceCollection::new()");
+    for resource in &fixed {
+        out.push_str("\n        .add_resource(\n");
+        let _ = writeln!(out, "            StaticResource::new_text(");
+        let _ = writeln!(out, "                {:?},", resource.item.uri);
+        let _ = writeln!(
+            out,
+            "                // {}: {}",
+            TODO,
+            port_note("content", resource.origin.as_deref())
+        );
+        let _ = writeln!(
+            out,
+            "                \"{} is not migrated yet\",",
+            resource.item.uri
+        );
+        out.push_str("            )");
+        let _ = write!(out, "\n            .with_name({:?})", resource.item.name);
+        if let Some(description) = &resource.item.description {
+            let _ = write!(out, "\n            .with_description({:?})", description);
+        }
+        if let Some(mime_type) = &resource.item.mime_type {
+            let _ = write!(out, "\n            .with_mime_type({:?})", mime_type);
+        }
+        out.push_str(",\n        )");
+    }
+    if !templates.is_empty() {
+        out.push_str("\n        .add_dynamic_provider(Arc::new(TemplateResources))");
+    }
+    out.push_str("\n}\n");
+
+    if !templates.is_empty() {
+        out.push_str("\n/// Resources addressed through URI templates.\n");
+        out.push_str("struct TemplateResources;\n\n");
+        out.push_str("#[async_trait]\nimpl DynamicResourceProvider for TemplateResources {\n");
+        out.push_str("    fn templates(&self) -> Vec<ResourceTemplate> {\n        vec![\n");
+        for resource in &templates {
+            let _ = write!(
+                out,
+                "            ResourceTemplate::new({:?}, {:?})",
+                resource.item.uri, resource.item.name
+            );
+            if let Some(description) = &resource.item.description {
+                let _ = write!(
+                    out,
+                    "\n                .with_description({:?})",
+                    description
+                );
+            }
+            if let Some(mime_type) = &resource.item.mime_type {
+                let _ = write!(out, "\n                .with_mime_type({:?})", mime_type);
+            }
+            out.push_str(",\n");
+        }
+        out.push_str("        ]\n    }\n\n");
+        out.push_str("    async fn fetch(\n        &self,\n        uri: &str,\n        params: UriParams,\n        _context: RequestContext,\n    ) -> pmcp::Result<ReadResourceResult> {\n");
+        for resource in &templates {
+            let _ = writeln!(
+                out,
+                "        // {}: {} ({})",
+                TODO,
+                port_note("read handler", resource.origin.as_deref()),
+                resource.item.uri
+            );
+        }
+        out.push_str("        let _ = params;\n");
+        out.push_str(
+            "        Err(pmcp::Error::internal(format!(\"{} is not migrated yet\", uri)))\n",
+        );
+        out.push_str("    }\n}\n");
+    }
+    out
+}
+
+/// `prompts.rs`: one constructor per prompt with its original arguments.
+fn prompts_module(prompts: &[Migrated<PromptSchema>]) -> String {
+    let mut out = String::from("//! Prompts migrated from the original server.\n\n");
+    out.push_str("use pmcp::types::GetPromptResult;\n");
+    out.push_str("use pmcp::SyncPrompt;\n");
+    out.push_str("use std::collections::HashMap;\n");
+
+    let mut functions = HashSet::new();
+    for prompt in prompts {
+        let function = unique_module(&mut functions, &prompt.item.name);
+        out.push('\n');
+        match prompt.item.description.as_deref() {
+            Some(description) => write_doc(&mut out, description),
+            None => {
+                let _ = writeln!(out, "/// The `{}` prompt.", prompt.item.name);
+            },
+        }
+        let _ = writeln!(
+            out,
+            "pub fn {}() -> SyncPrompt<impl Fn(HashMap<String, String>) -> pmcp::Result<GetPromptResult> + Send + Sync> {{",
+            function
+        );
+        let _ = writeln!(
+            out,
+            "    SyncPrompt::new({:?}, |args: HashMap<String, String>| {{",
+            prompt.item.name
+        );
+        let _ = writeln!(
+            out,
+            "        // {}: {}",
+            TODO,
+            port_note("messages", prompt.origin.as_deref())
+        );
+        for todo in &prompt.todos {
+            let _ = writeln!(out, "        // {}: {}", TODO, todo);
+        }
+        out.push_str("        let _ = args;\n");
+        let _ = writeln!(
+            out,
+            "        Err(pmcp::Error::internal(\"{} is not implemented yet\"))",
+            prompt.item.name
+        );
+        out.push_str("    })");
+        if let Some(description) = &prompt.item.description {
+            let _ = write!(out, "\n    .with_description({:?})", description);
+        }
+        for argument in &prompt.item.arguments {
+            let _ = write!(
+                out,
+                "\n    .with_argument({:?}, {:?}, {})",
+                argument.name,
+                argument.description.as_deref().unwrap_or_default(),
+                argument.required
+            );
+        }
+        out.push_str("\n}\n");
+    }
+    out
+}
+
+fn port_note(what: &str, origin: Option<&str>) -> String {
+    match origin {
+        Some(origin) => format!("port the {} from {}", what, origin),
+        None => format!("port the {}", what),
+    }
+}
+
+fn lib_rs(name: &str, inventory: &Inventory, registrations: &[String]) -> String {
+    let fn_name = name.replace('-', "_");
+    let has_output = registrations
+        .iter()
+        .any(|r| r.contains("TypedToolWithOutput::new"));
+    let has_input_only = registrations.iter().any(|r| r.contains(" TypedTool::new"));
+
+    let mut out = String::new();
+    let _ = writeln!(out, "//! {} MCP server core.", name);
+    out.push_str("//!\n");
+    out.push_str(
+        "//! Scaffolded by `cargo pmcp migrate` from a TypeScript MCP server. Search for\n",
+    );
+    let _ = writeln!(
+        out,
+        "//! `{}` to find what still has to be ported; MIGRATION.md at the",
+        TODO
+    );
+    out.push_str("//! workspace root lists every registration and where it came from.\n\n");
+    if !inventory.prompts.is_empty() {
+        out.push_str("pub mod prompts;\n");
+    }
+    if !inventory.resources.is_empty() {
+        out.push_str("pub mod resources;\n");
+    }
+    out.push_str("pub mod tools;\n\n");
+    if has_output {
+        out.push_str("use pmcp::server::typed_tool::TypedToolWithOutput;\n");
+    }
+    if has_input_only {
+        out.push_str("use pmcp::{Server, TypedTool};\n");
+    } else {
+        out.push_str("use pmcp::Server;\n");
+    }
+
+    let _ = writeln!(out, "\n/// Build the {} server", name);
+    let _ = writeln!(
+        out,
+        "pub fn build_{}_server() -> pmcp::Result<Server> {{",
+        fn_name
+    );
+    out.push_str("    Server::builder()\n");
+    let _ = writeln!(out, "        .name({:?})", name);
+    out.push_str("        .version(\"1.0.0\")\n");
+    for registration in registrations {
+        out.push_str(registration);
+    }
+    if !inventory.resources.is_empty() {
+        out.push_str("        .resources(resources::collection())\n");
+    }
+    let mut functions = HashSet::new();
+    for prompt in &inventory.prompts {
+        let function = unique_module(&mut functions, &prompt.item.name);
+        let _ = writeln!(
+            out,
+            "        .prompt({:?}, prompts::{}())",
+            prompt.item.name, function
+        );
+    }
+    out.push_str("        .build()\n}\n");
+
+    out.push_str("\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n");
+    out.push_str("    #[test]\n    fn test_server_builds() {\n");
+    let _ = writeln!(out, "        assert!(build_{}_server().is_ok());", fn_name);
+    out.push_str("    }\n}\n");
+    out
+}
+
+fn write_doc(out: &mut String, text: &str) {
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            out.push_str("///\n");
+        } else {
+            let _ = writeln!(out, "/// {}", line);
+        }
+    }
+}
+
+/// `MIGRATION.md`: what was found, where, and what is left to port.
+fn report(name: &str, inventory: &Inventory, source_dir: &Path) -> String {
+    let mut out = format!("# Migrating {} to pmcp\n\n", name);
+    let _ = writeln!(
+        out,
+        "Scaffolded by `cargo pmcp migrate --from typescript {}`.\n",
+        source_dir.display()
+    );
+    out.push_str("Every handler returns an error until it is ported. Search the crates for\n");
+    let _ = writeln!(
+        out,
+        "`{}` and port each one, then check the server against the original with\n`cargo pmcp test`.\n",
+        TODO
+    );
+
+    if !inventory.tools.is_empty() {
+        out.push_str("## Tools\n\n| Tool | Origin | Notes |\n|------|--------|-------|\n");
+        let mut modules = HashSet::new();
+        for tool in &inventory.tools {
+            let module = unique_module(&mut modules, &tool.item.name);
+            let _ = writeln!(
+                out,
+                "| `{}` → `tools/{}.rs` | {} | {} |",
+                tool.item.name,
+                module,
+                origin_cell(tool.origin.as_deref()),
+                notes_cell(&tool.todos)
+            );
+        }
+        out.push('\n');
+    }
+    if !inventory.resources.is_empty() {
+        out.push_str(
+            "## Resources\n\n| URI | Name | Origin | Notes |\n|-----|------|--------|-------|\n",
+        );
+        for resource in &inventory.resources {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                resource.item.uri,
+                resource.item.name,
+                origin_cell(resource.origin.as_deref()),
+                notes_cell(&resource.todos)
+            );
+        }
+        out.push('\n');
+    }
+    if !inventory.prompts.is_empty() {
+        out.push_str("## Prompts\n\n| Prompt | Arguments | Origin | Notes |\n|--------|-----------|--------|-------|\n");
+        for prompt in &inventory.prompts {
+            let arguments: Vec<String> = prompt
+                .item
+                .arguments
+                .iter()
+                .map(|a| {
+                    if a.required {
+                        format!("`{}`", a.name)
+                    } else {
+                        format!("`{}`?", a.name)
+                    }
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                prompt.item.name,
+                arguments.join(", "),
+                origin_cell(prompt.origin.as_deref()),
+                notes_cell(&prompt.todos)
+            );
+        }
+        out.push('\n');
+    }
+    if !inventory.warnings.is_empty() {
+        out.push_str("## Not migrated\n\n");
+        for warning in &inventory.warnings {
+            let _ = writeln!(out, "- {}", warning);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn origin_cell(origin: Option<&str>) -> String {
+    origin
+        .map(|o| format!("`{}`", o))
+        .unwrap_or_else(|| "schema export".to_string())
+}
+
+fn notes_cell(todos: &[String]) -> String {
+    todos.join("<br>").replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Inventory {
+        let mut inventory = Inventory::default();
+        super::super::typescript::scan_source(
+            "src/index.ts",
+            r#"
+server.registerTool("get-forecast", {
+  title: "Forecast",
+  description: "Get the forecast",
+  inputSchema: { city: z.string(), days: z.number().int().optional() },
+  outputSchema: { summary: z.string() },
+  annotations: { readOnlyHint: true },
+}, async () => ({ content: [] }));
+server.tool("ping", async () => ({ content: [] }));
+server.resource("config", "config://app", async () => ({ contents: [] }));
+server.resource("user", new ResourceTemplate("users://{id}", { list: undefined }), async () => ({ contents: [] }));
+server.prompt("summarize", { text: z.string() }, ({ text }) => ({ messages: [] }));
+"#,
+            &mut inventory,
+        );
+        inventory
+    }
+
+    #[test]
+    fn test_tool_module_has_typed_stub() {
+        let inventory = inventory();
+        let (source, registration) = tool_module("get_forecast", &inventory.tools[0], &inventory);
+        assert!(source.contains("pub struct GetForecastInput {"));
+        assert!(source.contains("    pub days: Option<i64>,"));
+        assert!(source.contains(
+            "pub async fn handle(input: GetForecastInput) -> pmcp::Result<GetForecastOutput> {"
+        ));
+        assert!(source.contains("// TODO(migrate): port the handler from src/index.ts:2."));
+        assert!(registration.contains("TypedToolWithOutput::new(\"get-forecast\""));
+        assert!(registration.contains(".with_title(\"Forecast\")"));
+        assert!(registration.contains(".read_only()"));
+    }
+
+    #[test]
+    fn test_tool_without_output_returns_value() {
+        let inventory = inventory();
+        let (source, registration) = tool_module("ping", &inventory.tools[1], &inventory);
+        assert!(source.contains("pub struct PingInput {\n}"));
+        assert!(source.contains("-> pmcp::Result<serde_json::Value>"));
+        assert!(registration.contains("TypedTool::new(\"ping\""));
+    }
+
+    #[test]
+    fn test_resources_split_static_and_templates() {
+        let source = resources_module(&inventory().resources);
+        assert!(source.contains("StaticResource::new_text(\n                \"config://app\","));
+        assert!(source.contains("ResourceTemplate::new(\"users://{id}\", \"user\")"));
+        assert!(source.contains(".add_dynamic_provider(Arc::new(TemplateResources))"));
+    }
+
+    #[test]
+    fn test_lib_registers_everything() {
+        let inventory = inventory();
+        let registrations: Vec<String> = inventory
+            .tools
+            .iter()
+            .map(|tool| tool_module(&field_ident(&tool.item.name), tool, &inventory).1)
+            .collect();
+        let lib = lib_rs("weather", &inventory, &registrations);
+        assert!(lib.contains("pub fn build_weather_server() -> pmcp::Result<Server> {"));
+        assert!(lib.contains("use pmcp::server::typed_tool::TypedToolWithOutput;"));
+        assert!(lib.contains("use pmcp::{Server, TypedTool};"));
+        assert!(lib.contains(".resources(resources::collection())"));
+        assert!(lib.contains(".prompt(\"summarize\", prompts::summarize())"));
+    }
+
+    #[test]
+    fn test_generate_writes_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    # Add server crates here via: cargo pmcp add server <name>\n]\n",
+        )
+        .unwrap();
+
+        let todos = generate(
+            workspace,
+            "weather",
+            &inventory(),
+            Path::new("../weather-ts"),
+        )
+        .unwrap();
+        // One marker per tool, resource and prompt handler
+        assert_eq!(todos, 5);
+        assert!(workspace
+            .join("crates/mcp-weather-core/src/tools/get_forecast.rs")
+            .exists());
+        assert!(workspace.join("crates/weather-server/src/main.rs").exists());
+        let members = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+        assert!(members.contains("\"crates/mcp-weather-core\""));
+        let report = fs::read_to_string(workspace.join("MIGRATION.md")).unwrap();
+        assert!(report.contains("| `get-forecast` → `tools/get_forecast.rs` | `src/index.ts:2` |"));
+    }
+}
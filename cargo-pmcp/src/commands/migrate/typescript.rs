@@ -0,0 +1,1102 @@
+//! Source heuristics for servers built on the TypeScript MCP SDK.
+//!
+//! Recognises the `McpServer` registration calls — `tool`/`registerTool`,
+//! `resource`/`registerResource` and `prompt`/`registerPrompt` — and
+//! translates their Zod shapes to JSON Schema. Shapes passed by variable are
+//! resolved against `const`/`let` declarations in the same file. Anything that
+//! cannot be read statically becomes a TODO on the registration instead of a
+//! guess.
+//!
+//! This is not a TypeScript parser: it matches brackets outside strings and
+//! comments, which is enough for registration calls written the way the SDK
+//! documentation writes them.
+
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+use super::{Inventory, Migrated};
+use crate::commands::schema::{PromptArgSchema, PromptSchema, ResourceSchema, ToolSchema};
+
+/// Source file extensions scanned for registrations.
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "mts", "cts", "tsx", "js", "mjs", "cjs"];
+
+/// Directories that hold dependencies or build output rather than sources.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build", "out", "coverage", ".git"];
+
+/// How many variable indirections to follow when resolving a shape.
+const MAX_RESOLVE_DEPTH: usize = 4;
+
+/// Scan every source file under `root` for registrations.
+pub fn scan(root: &Path) -> Inventory {
+    let mut inventory = Inventory::default();
+    let files = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !name.ends_with(".d.ts")
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+        });
+
+    for entry in files {
+        let Ok(source) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let display = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        scan_source(&display, &source, &mut inventory);
+    }
+    inventory
+}
+
+/// Add the registrations found in one source file to `inventory`.
+pub fn scan_source(file: &str, source: &str, inventory: &mut Inventory) {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let call = CALL.get_or_init(|| {
+        Regex::new(r"\.\s*(registerTool|registerResource|registerPrompt|tool|resource|prompt)\s*\(")
+            .expect("valid regex")
+    });
+
+    let code = code_mask(source);
+    for found in call.captures_iter(source) {
+        let whole = found.get(0).expect("match");
+        if !code[whole.start()] {
+            continue;
+        }
+        let open = whole.end() - 1;
+        let Some(close) = matching_close(source, open) else {
+            continue;
+        };
+        let args = split_top_level(&source[open + 1..close - 1]);
+        let origin = format!("{}:{}", file, line_of(source, whole.start()));
+        let resolver = Resolver { source };
+        match &found[1] {
+            "tool" => tool_call(&args, &resolver, origin, inventory),
+            "registerTool" => register_tool_call(&args, &resolver, origin, inventory),
+            "resource" | "registerResource" => resource_call(&args, &resolver, origin, inventory),
+            "prompt" => prompt_call(&args, &resolver, origin, inventory),
+            "registerPrompt" => register_prompt_call(&args, &resolver, origin, inventory),
+            _ => unreachable!("regex only matches registration methods"),
+        }
+    }
+
+    for marker in [
+        "ListToolsRequestSchema",
+        "ListResourcesRequestSchema",
+        "ListPromptsRequestSchema",
+    ] {
+        if source.contains(marker) {
+            inventory.warnings.push(format!(
+                "{} registers {} with the low-level Server API, which cannot be read from source; \
+                 export the schema from the running server and pass it with --schema",
+                file, marker
+            ));
+        }
+    }
+}
+
+/// `server.tool(name, description?, paramsSchema?, annotations?, callback)`
+fn tool_call<'a>(
+    args: &[&'a str],
+    resolver: &Resolver<'a>,
+    origin: String,
+    inventory: &mut Inventory,
+) {
+    let Some(name) = args.first().and_then(|a| string_literal(a)) else {
+        inventory.warnings.push(format!(
+            "{}: tool name is not a string literal; add it by hand",
+            origin
+        ));
+        return;
+    };
+    let mut tool = empty_tool(name);
+    let mut todos = Vec::new();
+    let middle = args
+        .get(1..args.len().saturating_sub(1))
+        .unwrap_or_default();
+    for &arg in middle {
+        if let Some(description) = string_literal(arg) {
+            tool.description = Some(description);
+            continue;
+        }
+        let resolved = resolver.resolve(arg);
+        match object_entries(resolved) {
+            Some(entries) if !tool_has_input(&tool) && looks_like_shape(&entries) => {
+                tool.input_schema = Some(shape_schema(&entries, resolver, &mut todos));
+            },
+            Some(entries) => tool.annotations = annotations(&entries),
+            None => todos.push(format!("could not read the argument `{}`", abbreviate(arg))),
+        }
+    }
+    inventory.tools.push(Migrated {
+        item: tool,
+        origin: Some(origin),
+        todos,
+    });
+}
+
+/// `server.registerTool(name, { title, description, inputSchema, outputSchema, annotations }, callback)`
+fn register_tool_call<'a>(
+    args: &[&'a str],
+    resolver: &Resolver<'a>,
+    origin: String,
+    inventory: &mut Inventory,
+) {
+    let Some(name) = args.first().and_then(|a| string_literal(a)) else {
+        inventory.warnings.push(format!(
+            "{}: tool name is not a string literal; add it by hand",
+            origin
+        ));
+        return;
+    };
+    let mut tool = empty_tool(name);
+    let mut todos = Vec::new();
+    let config = args
+        .get(1)
+        .map(|&arg| resolver.resolve(arg))
+        .and_then(object_entries)
+        .unwrap_or_default();
+    for (key, value) in &config {
+        match key.as_str() {
+            "description" => tool.description = string_literal(value),
+            "inputSchema" => {
+                tool.input_schema = Some(object_schema(value, resolver, &mut todos));
+            },
+            "outputSchema" => {
+                tool.output_schema = Some(object_schema(value, resolver, &mut todos));
+            },
+            "annotations" => {
+                let title = tool.annotations.as_ref().and_then(|a| a.title.clone());
+                tool.annotations = object_entries(resolver.resolve(value))
+                    .and_then(|entries| annotations(&entries));
+                if let (Some(annotations), Some(title)) = (tool.annotations.as_mut(), title) {
+                    annotations.title.get_or_insert(title);
+                }
+            },
+            "title" => {
+                if let Some(title) = string_literal(value) {
+                    tool.annotations.get_or_insert_with(empty_annotations).title = Some(title);
+                }
+            },
+            _ => {},
+        }
+    }
+    inventory.tools.push(Migrated {
+        item: tool,
+        origin: Some(origin),
+        todos,
+    });
+}
+
+/// `server.resource(name, uriOrTemplate, metadata?, callback)` and
+/// `server.registerResource(name, uriOrTemplate, metadata, callback)`
+fn resource_call<'a>(
+    args: &[&'a str],
+    resolver: &Resolver<'a>,
+    origin: String,
+    inventory: &mut Inventory,
+) {
+    let Some(name) = args.first().and_then(|a| string_literal(a)) else {
+        inventory.warnings.push(format!(
+            "{}: resource name is not a string literal; add it by hand",
+            origin
+        ));
+        return;
+    };
+    let mut todos = Vec::new();
+    let uri = match args.get(1).map(|&arg| resolver.resolve(arg)) {
+        Some(arg) => string_literal(arg).or_else(|| resource_template_uri(arg)),
+        None => None,
+    };
+    let uri = uri.unwrap_or_else(|| {
+        todos.push("could not read the resource URI".to_string());
+        format!("todo://{}", name)
+    });
+    let mut resource = ResourceSchema {
+        uri,
+        name,
+        description: None,
+        mime_type: None,
+    };
+    let metadata = args
+        .get(2..args.len().saturating_sub(1))
+        .unwrap_or_default()
+        .iter()
+        .find_map(|&arg| object_entries(resolver.resolve(arg)))
+        .unwrap_or_default();
+    for (key, value) in &metadata {
+        match key.as_str() {
+            "description" => resource.description = string_literal(value),
+            "mimeType" => resource.mime_type = string_literal(value),
+            _ => {},
+        }
+    }
+    inventory.resources.push(Migrated {
+        item: resource,
+        origin: Some(origin),
+        todos,
+    });
+}
+
+/// `server.prompt(name, description?, argsSchema?, callback)`
+fn prompt_call<'a>(
+    args: &[&'a str],
+    resolver: &Resolver<'a>,
+    origin: String,
+    inventory: &mut Inventory,
+) {
+    let Some(name) = args.first().and_then(|a| string_literal(a)) else {
+        inventory.warnings.push(format!(
+            "{}: prompt name is not a string literal; add it by hand",
+            origin
+        ));
+        return;
+    };
+    let mut prompt = PromptSchema {
+        name,
+        description: None,
+        arguments: Vec::new(),
+    };
+    let mut todos = Vec::new();
+    let middle = args
+        .get(1..args.len().saturating_sub(1))
+        .unwrap_or_default();
+    for &arg in middle {
+        if let Some(description) = string_literal(arg) {
+            prompt.description = Some(description);
+        } else {
+            prompt.arguments = prompt_arguments(arg, resolver, &mut todos);
+        }
+    }
+    inventory.prompts.push(Migrated {
+        item: prompt,
+        origin: Some(origin),
+        todos,
+    });
+}
+
+/// `server.registerPrompt(name, { title, description, argsSchema }, callback)`
+fn register_prompt_call<'a>(
+    args: &[&'a str],
+    resolver: &Resolver<'a>,
+    origin: String,
+    inventory: &mut Inventory,
+) {
+    let Some(name) = args.first().and_then(|a| string_literal(a)) else {
+        inventory.warnings.push(format!(
+            "{}: prompt name is not a string literal; add it by hand",
+            origin
+        ));
+        return;
+    };
+    let mut prompt = PromptSchema {
+        name,
+        description: None,
+        arguments: Vec::new(),
+    };
+    let mut todos = Vec::new();
+    let config = args
+        .get(1)
+        .map(|&arg| resolver.resolve(arg))
+        .and_then(object_entries)
+        .unwrap_or_default();
+    for (key, value) in &config {
+        match key.as_str() {
+            "description" => prompt.description = string_literal(value),
+            "argsSchema" => prompt.arguments = prompt_arguments(value, resolver, &mut todos),
+            _ => {},
+        }
+    }
+    inventory.prompts.push(Migrated {
+        item: prompt,
+        origin: Some(origin),
+        todos,
+    });
+}
+
+fn empty_tool(name: String) -> ToolSchema {
+    ToolSchema {
+        name,
+        description: None,
+        input_schema: None,
+        output_schema: None,
+        annotations: None,
+    }
+}
+
+fn empty_annotations() -> crate::commands::schema::ToolAnnotations {
+    serde_json::from_value(json!({})).expect("all annotation fields are optional")
+}
+
+fn tool_has_input(tool: &ToolSchema) -> bool {
+    tool.input_schema.is_some()
+}
+
+/// `{ title, readOnlyHint, ... }` as tool annotations.
+fn annotations(entries: &[(String, &str)]) -> Option<crate::commands::schema::ToolAnnotations> {
+    let mut object = Map::new();
+    for (key, value) in entries {
+        let value = match value.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            other => match string_literal(other) {
+                Some(s) => Value::String(s),
+                None => continue,
+            },
+        };
+        object.insert(key.clone(), value);
+    }
+    serde_json::from_value(Value::Object(object)).ok()
+}
+
+/// Prompt arguments from a Zod raw shape; prompt arguments are always strings.
+fn prompt_arguments<'a>(
+    expr: &'a str,
+    resolver: &Resolver<'a>,
+    todos: &mut Vec<String>,
+) -> Vec<PromptArgSchema> {
+    let Some(entries) = shape_entries(expr, resolver) else {
+        todos.push(format!(
+            "could not read the prompt arguments `{}`",
+            abbreviate(expr)
+        ));
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .map(|(name, value)| {
+            let field = zod(value, resolver, 0);
+            PromptArgSchema {
+                name: name.clone(),
+                description: field
+                    .schema
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                required: !field.optional,
+            }
+        })
+        .collect()
+}
+
+/// JSON Schema for a tool's `inputSchema`/`outputSchema`, which the SDK
+/// accepts as a Zod raw shape or a `z.object(...)`.
+fn object_schema<'a>(expr: &'a str, resolver: &Resolver<'a>, todos: &mut Vec<String>) -> Value {
+    match shape_entries(expr, resolver) {
+        Some(entries) => shape_schema(&entries, resolver, todos),
+        None => {
+            todos.push(format!("could not read the schema `{}`", abbreviate(expr)));
+            json!({ "type": "object" })
+        },
+    }
+}
+
+/// Entries of a raw shape (`{ a: z.string() }`) or of `z.object({ ... })`.
+fn shape_entries<'a>(expr: &'a str, resolver: &Resolver<'a>) -> Option<Vec<(String, &'a str)>> {
+    let expr = resolver.resolve(expr);
+    if let Some(entries) = object_entries(expr) {
+        return Some(entries);
+    }
+    let chain = zod_chain(expr)?;
+    match chain.first() {
+        Some(("object" | "strictObject" | "looseObject", args)) => args
+            .first()
+            .map(|&a| resolver.resolve(a))
+            .and_then(object_entries),
+        _ => None,
+    }
+}
+
+/// JSON Schema object for a Zod raw shape.
+fn shape_schema<'a>(
+    entries: &[(String, &'a str)],
+    resolver: &Resolver<'a>,
+    todos: &mut Vec<String>,
+) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, expr) in entries {
+        let field = zod(expr, resolver, 0);
+        if !field.understood {
+            todos.push(format!(
+                "`{}: {}` has no JSON Schema equivalent here; typed as serde_json::Value",
+                name,
+                abbreviate(expr)
+            ));
+        }
+        if !field.optional {
+            required.push(Value::String(name.clone()));
+        }
+        properties.insert(name.clone(), field.schema);
+    }
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Keys of the `annotations` argument to `server.tool(...)`.
+const ANNOTATION_KEYS: &[&str] = &[
+    "title",
+    "readOnlyHint",
+    "destructiveHint",
+    "idempotentHint",
+    "openWorldHint",
+];
+
+/// An object argument is a params shape unless it only holds annotation keys.
+fn looks_like_shape(entries: &[(String, &str)]) -> bool {
+    entries
+        .iter()
+        .any(|(key, value)| zod_chain(value).is_some() || !ANNOTATION_KEYS.contains(&key.as_str()))
+}
+
+/// A Zod expression translated to JSON Schema.
+#[derive(Debug)]
+struct ZodField {
+    schema: Value,
+    optional: bool,
+    understood: bool,
+}
+
+/// Translate a Zod expression such as `z.number().int().min(1).optional()`.
+fn zod<'a>(expr: &'a str, resolver: &Resolver<'a>, depth: usize) -> ZodField {
+    let expr = resolver.resolve(expr.trim());
+    let Some(chain) = zod_chain(expr) else {
+        return ZodField {
+            schema: json!({}),
+            optional: false,
+            understood: false,
+        };
+    };
+
+    let mut understood = true;
+    let (base, base_args) = &chain[0];
+    let mut schema = match *base {
+        "string" => json!({ "type": "string" }),
+        "number" => json!({ "type": "number" }),
+        "bigint" => json!({ "type": "integer" }),
+        "boolean" => json!({ "type": "boolean" }),
+        "date" => json!({ "type": "string", "format": "date-time" }),
+        "array" => match base_args.first() {
+            Some(&item) if depth < MAX_RESOLVE_DEPTH => {
+                let item = zod(item, resolver, depth + 1);
+                understood &= item.understood;
+                json!({ "type": "array", "items": item.schema })
+            },
+            _ => json!({ "type": "array" }),
+        },
+        "enum" => {
+            let values: Vec<Value> = base_args
+                .first()
+                .and_then(|list| list.trim().strip_prefix('['))
+                .and_then(|list| list.strip_suffix(']'))
+                .map(|list| {
+                    split_top_level(list)
+                        .into_iter()
+                        .filter_map(string_literal)
+                        .map(Value::String)
+                        .collect()
+                })
+                .unwrap_or_default();
+            understood &= !values.is_empty();
+            json!({ "type": "string", "enum": values })
+        },
+        "literal" => match base_args.first().map(|v| literal_value(v)) {
+            Some(Some(value)) => json!({ "const": value }),
+            _ => {
+                understood = false;
+                json!({})
+            },
+        },
+        "object" | "strictObject" | "looseObject" => {
+            match base_args
+                .first()
+                .map(|&a| resolver.resolve(a))
+                .and_then(object_entries)
+            {
+                Some(entries) if depth < MAX_RESOLVE_DEPTH => {
+                    let mut nested = Vec::new();
+                    let schema = shape_schema(&entries, resolver, &mut nested);
+                    understood &= nested.is_empty();
+                    schema
+                },
+                _ => json!({ "type": "object" }),
+            }
+        },
+        "record" => json!({ "type": "object" }),
+        // Unions and free-form values stay JSON values on the Rust side
+        "union" | "discriminatedUnion" | "any" | "unknown" | "json" => json!({}),
+        _ => {
+            understood = false;
+            json!({})
+        },
+    };
+
+    let mut optional = false;
+    for (method, args) in &chain[1..] {
+        let first = args.first().map(|a| a.trim());
+        match *method {
+            "optional" | "nullish" => optional = true,
+            "default" => {
+                optional = true;
+                if let Some(value) = first.and_then(literal_value) {
+                    schema["default"] = value;
+                }
+            },
+            "nullable" => {
+                if let Some(ty) = schema.get("type").cloned() {
+                    schema["type"] = json!([ty, "null"]);
+                }
+            },
+            "describe" => {
+                if let Some(description) = first.and_then(string_literal) {
+                    schema["description"] = Value::String(description);
+                }
+            },
+            "int" => schema["type"] = json!("integer"),
+            "array" => schema = json!({ "type": "array", "items": schema }),
+            "email" | "url" | "uuid" | "datetime" | "date" | "time" | "ipv4" | "ipv6" => {
+                schema["format"] = Value::String(
+                    match *method {
+                        "url" => "uri",
+                        "datetime" => "date-time",
+                        other => other,
+                    }
+                    .to_string(),
+                );
+            },
+            "min" | "max" | "gte" | "lte" | "gt" | "lt" | "length" | "nonempty" | "positive"
+            | "nonnegative" => {
+                apply_bound(
+                    &mut schema,
+                    method,
+                    first.and_then(|v| v.parse::<f64>().ok()),
+                );
+            },
+            _ => {},
+        }
+    }
+
+    ZodField {
+        schema,
+        optional,
+        understood,
+    }
+}
+
+/// Map a Zod bound to the JSON Schema keyword for the schema's type.
+fn apply_bound(schema: &mut Value, method: &str, value: Option<f64>) {
+    let ty = schema
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if method == "length" {
+        let (min, max) = match ty.as_str() {
+            "array" => ("minItems", "maxItems"),
+            "string" => ("minLength", "maxLength"),
+            _ => return,
+        };
+        if let Some(value) = value {
+            schema[min] = json!(value);
+            schema[max] = json!(value);
+        }
+        return;
+    }
+    let (keyword, value) = match (ty.as_str(), method) {
+        ("string", "min" | "gte") => ("minLength", value),
+        ("string", "max" | "lte") => ("maxLength", value),
+        ("string", "nonempty") => ("minLength", Some(1.0)),
+        ("array", "min" | "gte") => ("minItems", value),
+        ("array", "max" | "lte") => ("maxItems", value),
+        ("array", "nonempty") => ("minItems", Some(1.0)),
+        ("number" | "integer", "min" | "gte") => ("minimum", value),
+        ("number" | "integer", "max" | "lte") => ("maximum", value),
+        ("number" | "integer", "gt") => ("exclusiveMinimum", value),
+        ("number" | "integer", "lt") => ("exclusiveMaximum", value),
+        ("number" | "integer", "positive") => ("exclusiveMinimum", Some(0.0)),
+        ("number" | "integer", "nonnegative") => ("minimum", Some(0.0)),
+        _ => return,
+    };
+    if let Some(value) = value {
+        schema[keyword] = json!(value);
+    }
+}
+
+/// Split `z.string().min(1).describe("x")` into
+/// `[("string", []), ("min", ["1"]), ("describe", ["\"x\""])]`.
+///
+/// Returns `None` when `expr` is not a Zod expression.
+fn zod_chain(expr: &str) -> Option<Vec<(&str, Vec<&str>)>> {
+    let expr = expr.trim();
+    let rest = expr
+        .strip_prefix("z.")
+        .or_else(|| expr.strip_prefix("zod."))?;
+    let rest = rest.strip_prefix("coerce.").unwrap_or(rest);
+    let offset = expr.len() - rest.len();
+
+    let mut chain = Vec::new();
+    let mut pos = offset;
+    let bytes = expr.as_bytes();
+    loop {
+        let start = pos;
+        while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+            pos += 1;
+        }
+        if pos == start {
+            return None;
+        }
+        let method = &expr[start..pos];
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let args = if bytes.get(pos) == Some(&b'(') {
+            let close = matching_close(expr, pos)?;
+            let args = split_top_level(&expr[pos + 1..close - 1]);
+            pos = close;
+            args
+        } else {
+            Vec::new()
+        };
+        chain.push((method, args));
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos) {
+            Some(b'.') => pos += 1,
+            None => break,
+            Some(_) => return None,
+        }
+    }
+    Some(chain)
+}
+
+/// Resolves identifiers to the initializer of their declaration in one file.
+struct Resolver<'a> {
+    source: &'a str,
+}
+
+impl<'a> Resolver<'a> {
+    /// The expression `expr` stands for: the initializer of `const expr = ...`
+    /// when `expr` is a plain identifier declared in the file, else `expr`.
+    fn resolve(&self, expr: &'a str) -> &'a str {
+        let mut expr = expr.trim();
+        for _ in 0..MAX_RESOLVE_DEPTH {
+            let name = expr.strip_suffix(".shape").unwrap_or(expr);
+            if name.is_empty()
+                || !name
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'$')
+            {
+                return expr;
+            }
+            match self.initializer(name) {
+                Some(init) => expr = init,
+                None => return expr,
+            }
+        }
+        expr
+    }
+
+    fn initializer(&self, name: &str) -> Option<&'a str> {
+        let pattern = format!(
+            r"(?:const|let|var)\s+{}\s*(?::[^=]+)?=\s*",
+            regex::escape(name)
+        );
+        let declaration = Regex::new(&pattern).ok()?.find(self.source)?;
+        let rest = &self.source[declaration.end()..];
+        Some(rest[..statement_end(rest)].trim())
+    }
+}
+
+/// Length of the expression statement at the start of `source`: up to a
+/// top-level `;`, or a top-level line break not followed by a `.` chain.
+fn statement_end(source: &str) -> usize {
+    let mask = code_mask(source);
+    let mut depth = 0usize;
+    for (i, b) in source.bytes().enumerate() {
+        if !mask[i] {
+            continue;
+        }
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => return i,
+            b'\n' if depth == 0 && !source[i..].trim_start().starts_with('.') => return i,
+            _ => {},
+        }
+    }
+    source.len()
+}
+
+/// URI of `new ResourceTemplate("users://{id}", { ... })`.
+fn resource_template_uri(expr: &str) -> Option<String> {
+    let rest = expr.trim().strip_prefix("new")?.trim_start();
+    let rest = rest.strip_prefix("ResourceTemplate")?.trim_start();
+    if !rest.starts_with('(') {
+        return None;
+    }
+    let close = matching_close(rest, 0)?;
+    split_top_level(&rest[1..close - 1])
+        .first()
+        .and_then(|uri| string_literal(uri))
+}
+
+/// `key: value` entries of an object literal, or `None` if `expr` is not one.
+///
+/// Shorthand properties map to themselves and spreads are skipped.
+fn object_entries(expr: &str) -> Option<Vec<(String, &str)>> {
+    let expr = expr.trim();
+    if !expr.starts_with('{') || matching_close(expr, 0) != Some(expr.len()) {
+        return None;
+    }
+    let mut entries = Vec::new();
+    for entry in split_top_level(&expr[1..expr.len() - 1]) {
+        if entry.starts_with("...") {
+            continue;
+        }
+        let (key, value) = match first_top_level(entry, |b| b == b':') {
+            Some(colon) => (entry[..colon].trim(), entry[colon + 1..].trim()),
+            None => (entry, entry),
+        };
+        let key = string_literal(key).unwrap_or_else(|| key.to_string());
+        entries.push((key, value));
+    }
+    Some(entries)
+}
+
+/// Value of a string literal without interpolation.
+fn string_literal(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    let quote = expr
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    if expr.len() < 2 || !expr.ends_with(quote) || (quote == '`' && expr.contains("${")) {
+        return None;
+    }
+    let inner = &expr[1..expr.len() - 1];
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            if c == quote {
+                // Concatenations like 'a' + 'b' are not a single literal
+                return None;
+            }
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => {},
+        }
+    }
+    Some(out)
+}
+
+/// JSON value of a string, number or boolean literal.
+fn literal_value(expr: &str) -> Option<Value> {
+    let expr = expr.trim();
+    if let Some(s) = string_literal(expr) {
+        return Some(Value::String(s));
+    }
+    match expr {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        _ => serde_json::from_str::<serde_json::Number>(expr)
+            .ok()
+            .map(Value::Number),
+    }
+}
+
+/// For each byte of `source`, whether it is code rather than a string or comment.
+fn code_mask(source: &str) -> Vec<bool> {
+    let bytes = source.as_bytes();
+    let mut mask = vec![true; bytes.len()];
+    let mut i = 0;
+    while i < bytes.len() {
+        let skip_to = match bytes[i] {
+            quote @ (b'"' | b'\'' | b'`') => {
+                let mut j = i + 1;
+                while j < bytes.len() && bytes[j] != quote {
+                    j += if bytes[j] == b'\\' { 2 } else { 1 };
+                }
+                (j + 1).min(bytes.len())
+            },
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                source[i..].find('\n').map_or(bytes.len(), |n| i + n)
+            },
+            b'/' if bytes.get(i + 1) == Some(&b'*') => source[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |n| i + 2 + n + 2),
+            _ => {
+                i += 1;
+                continue;
+            },
+        };
+        for flag in &mut mask[i..skip_to] {
+            *flag = false;
+        }
+        i = skip_to;
+    }
+    mask
+}
+
+/// Offset just past the bracket that closes the one at `open`.
+fn matching_close(source: &str, open: usize) -> Option<usize> {
+    let mask = code_mask(&source[open..]);
+    let mut depth = 0usize;
+    for (i, b) in source[open..].bytes().enumerate() {
+        if !mask[i] {
+            continue;
+        }
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(open + i + 1);
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Offset of the first byte at bracket depth zero that satisfies `pred`.
+fn first_top_level(source: &str, pred: impl Fn(u8) -> bool) -> Option<usize> {
+    let mask = code_mask(source);
+    let mut depth = 0usize;
+    for (i, b) in source.bytes().enumerate() {
+        if !mask[i] {
+            continue;
+        }
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && pred(b) => return Some(i),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Split a comma-separated list at depth zero, dropping empty trailing items.
+fn split_top_level(source: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = source;
+    while let Some(comma) = first_top_level(rest, |b| b == b',') {
+        parts.push(rest[..comma].trim());
+        rest = &rest[comma + 1..];
+    }
+    parts.push(rest.trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/// First line of `expr`, shortened for messages.
+fn abbreviate(expr: &str) -> String {
+    let line = expr.trim().lines().next().unwrap_or_default();
+    if line.chars().count() > 60 || expr.trim().lines().count() > 1 {
+        format!("{}…", line.chars().take(60).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_str(source: &str) -> Inventory {
+        let mut inventory = Inventory::default();
+        scan_source("src/index.ts", source, &mut inventory);
+        inventory
+    }
+
+    #[test]
+    fn test_tool_with_zod_shape() {
+        let inventory = scan_str(
+            r#"
+const server = new McpServer({ name: "weather", version: "1.0.0" });
+
+server.tool(
+  "get_forecast",
+  "Get the forecast for a location",
+  {
+    latitude: z.number().min(-90).max(90).describe("Latitude"),
+    days: z.number().int().optional(),
+    units: z.enum(["metric", "imperial"]).default("metric"),
+  },
+  async ({ latitude, days }) => ({ content: [] })
+);
+"#,
+        );
+        assert_eq!(inventory.tools.len(), 1);
+        let tool = &inventory.tools[0];
+        assert_eq!(tool.item.name, "get_forecast");
+        assert_eq!(tool.origin.as_deref(), Some("src/index.ts:4"));
+        assert_eq!(
+            tool.item.description.as_deref(),
+            Some("Get the forecast for a location")
+        );
+        let schema = tool.item.input_schema.as_ref().unwrap();
+        assert_eq!(
+            schema["properties"]["latitude"],
+            json!({ "type": "number", "minimum": -90.0, "maximum": 90.0, "description": "Latitude" })
+        );
+        assert_eq!(schema["properties"]["days"]["type"], "integer");
+        assert_eq!(schema["properties"]["units"]["default"], "metric");
+        assert_eq!(schema["required"], json!(["latitude"]));
+        assert!(tool.todos.is_empty());
+    }
+
+    #[test]
+    fn test_register_tool_resolves_shape_variables() {
+        let inventory = scan_str(
+            r#"
+const SearchInput = z.object({
+  query: z.string(),
+  tags: z.array(z.string()).optional(),
+});
+
+server.registerTool("search", {
+  title: "Search",
+  description: "Search the catalog",
+  inputSchema: SearchInput.shape,
+  outputSchema: { total: z.number() },
+  annotations: { readOnlyHint: true },
+}, async (args) => ({ content: [] }));
+"#,
+        );
+        let tool = &inventory.tools[0].item;
+        let input = tool.input_schema.as_ref().unwrap();
+        assert_eq!(
+            input["properties"]["tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(input["required"], json!(["query"]));
+        assert_eq!(
+            tool.output_schema.as_ref().unwrap()["properties"]["total"]["type"],
+            "number"
+        );
+        let annotations = tool.annotations.as_ref().unwrap();
+        assert_eq!(annotations.title.as_deref(), Some("Search"));
+        assert_eq!(annotations.read_only_hint, Some(true));
+    }
+
+    #[test]
+    fn test_unknown_schema_becomes_todo() {
+        let inventory = scan_str(
+            r#"server.tool("save", { doc: DocumentSchema }, async () => ({ content: [] }));"#,
+        );
+        let tool = &inventory.tools[0];
+        assert_eq!(tool.todos.len(), 1);
+        assert!(tool.todos[0].contains("`doc: DocumentSchema`"));
+    }
+
+    #[test]
+    fn test_resources_and_templates() {
+        let inventory = scan_str(
+            r#"
+server.resource("config", "config://app", { mimeType: "application/json" }, async (uri) => ({ contents: [] }));
+server.registerResource(
+  "user-profile",
+  new ResourceTemplate("users://{userId}/profile", { list: undefined }),
+  { title: "User Profile", description: "Profile data" },
+  async (uri, { userId }) => ({ contents: [] })
+);
+"#,
+        );
+        assert_eq!(inventory.resources.len(), 2);
+        assert_eq!(inventory.resources[0].item.uri, "config://app");
+        assert_eq!(
+            inventory.resources[0].item.mime_type.as_deref(),
+            Some("application/json")
+        );
+        assert_eq!(inventory.resources[1].item.uri, "users://{userId}/profile");
+        assert_eq!(
+            inventory.resources[1].item.description.as_deref(),
+            Some("Profile data")
+        );
+    }
+
+    #[test]
+    fn test_prompts() {
+        let inventory = scan_str(
+            r#"
+server.prompt("review-code", "Review code", { code: z.string(), style: z.string().optional() }, ({ code }) => ({ messages: [] }));
+server.registerPrompt("summarize", {
+  description: "Summarize text",
+  argsSchema: { text: z.string().describe("Text to summarize") },
+}, ({ text }) => ({ messages: [] }));
+"#,
+        );
+        assert_eq!(inventory.prompts.len(), 2);
+        let review = &inventory.prompts[0].item;
+        assert_eq!(review.arguments.len(), 2);
+        assert!(review.arguments[0].required);
+        assert!(!review.arguments[1].required);
+        let summarize = &inventory.prompts[1].item;
+        assert_eq!(
+            summarize.arguments[0].description.as_deref(),
+            Some("Text to summarize")
+        );
+    }
+
+    #[test]
+    fn test_ignores_calls_in_comments_and_strings() {
+        let inventory = scan_str(
+            r#"
+// server.tool("commented", async () => ({}));
+const help = "call server.tool(\"quoted\") to register";
+"#,
+        );
+        assert!(inventory.tools.is_empty());
+    }
+
+    #[test]
+    fn test_low_level_server_is_reported() {
+        let inventory = scan_str(
+            "server.setRequestHandler(ListToolsRequestSchema, async () => ({ tools: [] }));",
+        );
+        assert_eq!(inventory.warnings.len(), 1);
+        assert!(inventory.warnings[0].contains("--schema"));
+    }
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!(string_literal(r#""a \"b\"""#).as_deref(), Some("a \"b\""));
+        assert_eq!(string_literal("'it\\'s'").as_deref(), Some("it's"));
+        assert_eq!(string_literal("`x ${y}`"), None);
+        assert_eq!(string_literal("'a' + 'b'"), None);
+        assert_eq!(string_literal("name"), None);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod add;
 pub mod app;
 pub mod auth;
+pub mod clean;
 pub mod connect;
 pub mod deploy;
 pub mod dev;
@@ -8,13 +9,17 @@ pub mod doctor;
 pub mod flags;
 pub mod landing;
 pub mod loadtest;
+pub mod migrate;
 pub mod new;
 pub mod pentest;
 pub mod preview;
 pub mod schema;
 pub mod secret;
+pub mod service;
+pub mod status;
 pub mod test;
 pub mod validate;
+pub mod widget;
 
 /// Global CLI flags shared across all commands.
 ///
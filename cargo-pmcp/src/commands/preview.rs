@@ -15,6 +15,11 @@ pub async fn execute(
     locale: String,
     widgets_dir: Option<String>,
     mode: String,
+    host_profile: String,
+    mocks_dir: Option<String>,
+    session_file: Option<String>,
+    messages_dir: Option<String>,
+    anonymize: bool,
     auth_flags: &AuthFlags,
     global_flags: &crate::commands::GlobalFlags,
 ) -> Result<()> {
@@ -23,6 +28,7 @@ pub async fn execute(
     } else {
         mcp_preview::PreviewMode::Standard
     };
+    let host_profile: mcp_preview::HostProfile = host_profile.parse()?;
 
     if global_flags.should_output() {
         println!("\n{}", "Starting MCP Apps Preview".bright_cyan().bold());
@@ -40,11 +46,34 @@ pub async fn execute(
                 dir.bright_magenta()
             );
         }
+        if let Some(ref dir) = mocks_dir {
+            println!("  {} Mocks Dir:   {}", "→".blue(), dir.bright_magenta());
+        }
+        if let Some(ref file) = session_file {
+            println!("  {} Session:     {}", "→".blue(), file.bright_magenta());
+        }
+        if let Some(ref dir) = messages_dir {
+            println!("  {} Messages:    {}", "→".blue(), dir.bright_magenta());
+        }
+        if anonymize {
+            println!(
+                "  {} Exports:     {}",
+                "→".blue(),
+                "anonymized".bright_magenta()
+            );
+        }
         let mode_display = match preview_mode {
             mcp_preview::PreviewMode::ChatGpt => "ChatGPT Strict".bright_red().bold(),
             mcp_preview::PreviewMode::Standard => "Standard".bright_green().bold(),
         };
         println!("  {} Mode:        {}", "→".blue(), mode_display);
+        if host_profile != mcp_preview::HostProfile::Compat {
+            println!(
+                "  {} Host:        {}",
+                "→".blue(),
+                host_profile.to_string().bright_magenta()
+            );
+        }
         println!();
     }
 
@@ -125,8 +154,14 @@ pub async fn execute(
         locale,
         widgets_dir: widgets_path,
         mode: preview_mode,
+        host_profile,
         auth_header,
         oauth_config,
+        mocks_dir: mocks_dir.map(std::path::PathBuf::from),
+        session_file: session_file.map(std::path::PathBuf::from),
+        messages_dir: messages_dir.map(std::path::PathBuf::from),
+        baselines_dir: None,
+        anonymize,
     };
 
     // Open browser if requested
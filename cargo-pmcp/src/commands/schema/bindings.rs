@@ -0,0 +1,685 @@
+//! Typed Rust bindings for vendored foundation schemas.
+//!
+//! Generates one module per foundation with an input struct per tool, an
+//! output struct per tool that declares an `outputSchema`, and an async
+//! function per tool that calls it through any `FoundationClient`. The module
+//! embeds the vendored schema with `include_str!` and asserts its
+//! `schema_fingerprint` at compile time, so the build fails when the schema
+//! changes without regenerating the bindings.
+//!
+//! Types shared between tools through `{"$ref": "#/definitions/<name>"}`
+//! are generated once, as a struct named after the definition, and reused by
+//! every tool that references them.
+
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use super::{McpSchema, ToolSchema};
+
+/// A struct to emit in the generated module.
+#[derive(Debug)]
+struct StructDef {
+    name: String,
+    doc: String,
+    fields: Vec<FieldDef>,
+}
+
+#[derive(Debug)]
+struct FieldDef {
+    rust_name: String,
+    json_name: String,
+    ty: String,
+    doc: Option<String>,
+    optional: bool,
+}
+
+/// Prefix of references to shared schema definitions.
+const DEFINITIONS_REF_PREFIX: &str = "#/definitions/";
+
+/// Collects struct definitions while mapping JSON Schema to Rust types.
+#[derive(Default)]
+struct TypeRegistry {
+    structs: Vec<StructDef>,
+    names: HashSet<String>,
+    /// Shared definitions that `$ref`s resolve against
+    definitions: Map<String, Value>,
+    /// Rust type already generated for each referenced definition
+    resolved: HashMap<String, String>,
+}
+
+impl TypeRegistry {
+    fn new(definitions: Map<String, Value>) -> Self {
+        Self {
+            definitions,
+            ..Self::default()
+        }
+    }
+
+    /// Rust type for a `#/definitions/<name>` reference, generated on first use.
+    fn ref_type(&mut self, reference: &str) -> String {
+        let Some((name, schema)) = reference
+            .strip_prefix(DEFINITIONS_REF_PREFIX)
+            .and_then(|name| Some((name, self.definitions.get(name)?.clone())))
+        else {
+            return "serde_json::Value".to_string();
+        };
+        if let Some(ty) = self.resolved.get(name) {
+            return ty.clone();
+        }
+        let name = name.to_string();
+        let ty = if has_properties(&schema) {
+            let struct_name = self.reserve(&pascal_case(&name));
+            // Record before generating fields so self-references terminate
+            self.resolved.insert(name.clone(), struct_name.clone());
+            let doc = schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Shared `{}` definition.", name));
+            self.push_struct(&schema, struct_name.clone(), doc);
+            struct_name
+        } else {
+            self.rust_type(&schema, &pascal_case(&name))
+        };
+        self.resolved.insert(name, ty.clone());
+        ty
+    }
+
+    /// Reserve a unique struct name based on `base`.
+    fn reserve(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut n = 2;
+        while !self.names.insert(name.clone()) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+        name
+    }
+
+    /// Rust type for `schema`; object schemas with properties become structs named after `hint`.
+    fn rust_type(&mut self, schema: &Value, hint: &str) -> String {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.ref_type(reference);
+        }
+        let (ty, nullable) = schema_type(schema);
+        let base = match ty.as_deref() {
+            Some("string") => "String".to_string(),
+            Some("integer") => "i64".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => {
+                let item = schema
+                    .get("items")
+                    .map(|items| self.rust_type(items, &format!("{}Item", hint)))
+                    .unwrap_or_else(|| "serde_json::Value".to_string());
+                format!("Vec<{}>", item)
+            },
+            Some("object") if has_properties(schema) => {
+                let doc = schema
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Nested object.")
+                    .to_string();
+                self.object_struct(schema, hint, doc)
+            },
+            _ => "serde_json::Value".to_string(),
+        };
+        if nullable {
+            format!("Option<{}>", base)
+        } else {
+            base
+        }
+    }
+
+    /// Register a struct for an object schema and return its name.
+    fn object_struct(&mut self, schema: &Value, name: &str, doc: String) -> String {
+        let name = self.reserve(name);
+        self.push_struct(schema, name.clone(), doc);
+        name
+    }
+
+    /// Generate the fields of an object schema as the struct `name`.
+    fn push_struct(&mut self, schema: &Value, name: String, doc: String) {
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut fields = Vec::new();
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (json_name, prop) in properties {
+                let ty = self.rust_type(prop, &format!("{}{}", name, pascal_case(json_name)));
+                let optional = !required.contains(json_name.as_str()) && !ty.starts_with("Option<");
+                fields.push(FieldDef {
+                    rust_name: field_ident(json_name),
+                    json_name: json_name.clone(),
+                    ty,
+                    doc: prop
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    optional,
+                });
+            }
+        }
+
+        self.structs.push(StructDef { name, doc, fields });
+    }
+}
+
+/// Generate the bindings module for a vendored schema.
+///
+/// `schema_file` is the vendored JSON file name (relative to the generated
+/// module) and `schema_json` its exact contents, which are fingerprinted.
+pub fn generate(
+    server_id: &str,
+    schema: &McpSchema,
+    schema_file: &str,
+    schema_json: &str,
+) -> String {
+    let fingerprint = pmcp::composition::schema_fingerprint(schema_json.as_bytes());
+    let mut registry = TypeRegistry::new(schema.definitions.clone());
+    let mut functions = String::new();
+
+    let mut tools: Vec<&ToolSchema> = schema.tools.iter().collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    for tool in tools {
+        write_function(&mut functions, &mut registry, tool);
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "//! Typed bindings for the `{}` foundation server.",
+        server_id
+    );
+    out.push_str("//!\n");
+    let _ = writeln!(
+        out,
+        "//! @generated by `cargo pmcp schema vendor {}` from `{}`.",
+        server_id, schema_file
+    );
+    out.push_str("//! Do not edit by hand; re-run the command after updating the schema.\n\n");
+    out.push_str("#![allow(dead_code)]\n\n");
+    out.push_str("use pmcp::composition::{CompositionError, FoundationClient};\n");
+    out.push_str("use serde::{Deserialize, Serialize};\n\n");
+    out.push_str("/// Foundation server ID used for every call.\n");
+    let _ = writeln!(out, "pub const SERVER_ID: &str = {:?};\n", server_id);
+    out.push_str("/// Vendored schema these bindings were generated from.\n");
+    let _ = writeln!(
+        out,
+        "pub const SCHEMA_JSON: &str = include_str!({:?});\n",
+        schema_file
+    );
+    out.push_str("const _: () = assert!(\n");
+    let _ = writeln!(
+        out,
+        "    pmcp::composition::schema_fingerprint(SCHEMA_JSON.as_bytes()) == {:#018x},",
+        fingerprint
+    );
+    let _ = writeln!(
+        out,
+        "    \"{} changed since these bindings were generated; run `cargo pmcp schema vendor {}`\"",
+        schema_file, server_id
+    );
+    out.push_str(");\n");
+
+    for def in &registry.structs {
+        out.push('\n');
+        write_struct(&mut out, def, false);
+    }
+    out.push_str(&functions);
+    out
+}
+
+/// Input and output types for implementing a tool rather than calling it.
+pub(crate) struct HandlerTypes {
+    /// Name of the input struct; tools without arguments get an empty one.
+    pub input: String,
+    /// Name of the output struct, when the tool declares an object `outputSchema`.
+    pub output: Option<String>,
+    /// Struct definitions, deriving `JsonSchema` so the server can advertise them.
+    pub source: String,
+}
+
+/// Generate the structs a handler for `tool` takes and returns.
+pub(crate) fn handler_types(tool: &ToolSchema, definitions: &Map<String, Value>) -> HandlerTypes {
+    let type_base = pascal_case(&tool.name);
+    let mut registry = TypeRegistry::new(definitions.clone());
+
+    let empty = serde_json::json!({ "type": "object" });
+    let input = registry.object_struct(
+        tool.input_schema.as_ref().unwrap_or(&empty),
+        &format!("{}Input", type_base),
+        format!("Arguments for the `{}` tool.", tool.name),
+    );
+    let output = tool
+        .output_schema
+        .as_ref()
+        .filter(|s| has_properties(s))
+        .map(|s| {
+            let name = tool
+                .annotations
+                .as_ref()
+                .and_then(|a| a.output_type_name.clone())
+                .unwrap_or_else(|| format!("{}Output", type_base));
+            registry.object_struct(s, &name, format!("Result of the `{}` tool.", tool.name))
+        });
+
+    let mut source = String::new();
+    for (i, def) in registry.structs.iter().enumerate() {
+        if i > 0 {
+            source.push('\n');
+        }
+        write_struct(&mut source, def, true);
+    }
+    HandlerTypes {
+        input,
+        output,
+        source,
+    }
+}
+
+fn write_function(out: &mut String, registry: &mut TypeRegistry, tool: &ToolSchema) {
+    let fn_name = function_name(&tool.name);
+    let type_base = pascal_case(&tool.name);
+
+    let input = tool
+        .input_schema
+        .as_ref()
+        .filter(|s| has_properties(s))
+        .map(|s| {
+            registry.object_struct(
+                s,
+                &format!("{}Input", type_base),
+                format!("Arguments for the `{}` tool.", tool.name),
+            )
+        });
+
+    let output_name = tool
+        .annotations
+        .as_ref()
+        .and_then(|a| a.output_type_name.clone())
+        .unwrap_or_else(|| format!("{}Output", type_base));
+    let output = match tool.output_schema.as_ref() {
+        Some(s) if has_properties(s) => registry.object_struct(
+            s,
+            &output_name,
+            format!("Result of the `{}` tool.", tool.name),
+        ),
+        Some(s) => registry.rust_type(s, &output_name),
+        None => "serde_json::Value".to_string(),
+    };
+
+    out.push('\n');
+    match tool.description.as_deref() {
+        Some(desc) => write_doc(out, "", desc),
+        None => {
+            let _ = writeln!(out, "/// Call the `{}` tool.", tool.name);
+        },
+    }
+    match &input {
+        Some(input) => {
+            let _ = writeln!(
+                out,
+                "pub async fn {}<C: FoundationClient>(\n    client: &C,\n    input: &{},\n) -> Result<{}, CompositionError> {{",
+                fn_name, input, output
+            );
+            out.push_str(
+                "    let arguments = serde_json::to_value(input)\n        .map_err(|e| CompositionError::Serialization(e.to_string()))?;\n",
+            );
+        },
+        None => {
+            let _ = writeln!(
+                out,
+                "pub async fn {}<C: FoundationClient>(client: &C) -> Result<{}, CompositionError> {{",
+                fn_name, output
+            );
+            out.push_str("    let arguments = serde_json::json!({});\n");
+        },
+    }
+    let _ = writeln!(
+        out,
+        "    client\n        .call_tool_typed(SERVER_ID, {:?}, &arguments)\n        .await\n}}",
+        tool.name
+    );
+}
+
+fn write_struct(out: &mut String, def: &StructDef, json_schema: bool) {
+    write_doc(out, "", &def.doc);
+    if json_schema {
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]\n");
+    } else {
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+    }
+    let _ = writeln!(out, "pub struct {} {{", def.name);
+    for field in &def.fields {
+        if let Some(doc) = &field.doc {
+            write_doc(out, "    ", doc);
+        }
+        if field.rust_name.trim_start_matches("r#") != field.json_name {
+            let _ = writeln!(out, "    #[serde(rename = {:?})]", field.json_name);
+        }
+        if field.optional {
+            out.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+            let _ = writeln!(out, "    pub {}: Option<{}>,", field.rust_name, field.ty);
+        } else {
+            let _ = writeln!(out, "    pub {}: {},", field.rust_name, field.ty);
+        }
+    }
+    out.push_str("}\n");
+}
+
+fn write_doc(out: &mut String, indent: &str, text: &str) {
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            let _ = writeln!(out, "{}///", indent);
+        } else {
+            let _ = writeln!(out, "{}/// {}", indent, line);
+        }
+    }
+}
+
+/// The primary JSON Schema type and whether `null` is allowed.
+fn schema_type(schema: &Value) -> (Option<String>, bool) {
+    match schema.get("type") {
+        Some(Value::String(t)) => (Some(t.clone()), false),
+        Some(Value::Array(types)) => {
+            let names: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+            let nullable = names.contains(&"null");
+            let non_null: Vec<&str> = names.into_iter().filter(|t| *t != "null").collect();
+            let ty = (non_null.len() == 1).then(|| non_null[0].to_string());
+            (ty, nullable)
+        },
+        _ if has_properties(schema) => (Some("object".to_string()), false),
+        _ => (None, false),
+    }
+}
+
+fn has_properties(schema: &Value) -> bool {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|p| !p.is_empty())
+}
+
+/// Split an identifier on `_`, `-`, `.`, spaces, and camelCase boundaries.
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Module name for a foundation server ID (e.g. `my-calc` -> `my_calc`).
+pub fn module_name(server_id: &str) -> String {
+    field_ident(server_id)
+}
+
+/// Name of the generated function that calls `tool`.
+pub fn function_name(tool: &str) -> String {
+    field_ident(tool)
+}
+
+/// PascalCase Rust type name, prefixed when it would start with a digit.
+pub fn pascal_case(s: &str) -> String {
+    let name: String = words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("T{}", name),
+        Some(_) => name,
+        None => "Unnamed".to_string(),
+    }
+}
+
+/// snake_case Rust identifier, raw-escaped when it collides with a keyword.
+pub fn field_ident(s: &str) -> String {
+    let name = words(s)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    match name.as_str() {
+        "" => "field".to_string(),
+        "self" | "super" | "crate" | "Self" => format!("{}_", name),
+        _ if name.starts_with(|c: char| c.is_ascii_digit()) => format!("_{}", name),
+        _ if is_keyword(&name) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+fn is_keyword(s: &str) -> bool {
+    matches!(
+        s,
+        "as" | "async"
+            | "await"
+            | "break"
+            | "const"
+            | "continue"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "static"
+            | "struct"
+            | "trait"
+            | "true"
+            | "type"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+            | "abstract"
+            | "become"
+            | "box"
+            | "do"
+            | "final"
+            | "gen"
+            | "macro"
+            | "override"
+            | "priv"
+            | "try"
+            | "typeof"
+            | "unsized"
+            | "virtual"
+            | "yield"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn calculator() -> McpSchema {
+        serde_json::from_value(json!({
+            "server_id": "calculator",
+            "name": "Calculator",
+            "tools": [
+                {
+                    "name": "add",
+                    "description": "Add two numbers",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "a": { "type": "number", "description": "First operand" },
+                            "b": { "type": "number" },
+                            "roundTo": { "type": "integer" }
+                        },
+                        "required": ["a", "b"]
+                    },
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "result": { "type": "number" },
+                            "steps": { "type": "array", "items": {
+                                "type": "object",
+                                "properties": { "op": { "type": "string" } }
+                            } }
+                        },
+                        "required": ["result"]
+                    }
+                },
+                { "name": "get-history" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn generates_structs_and_functions() {
+        let code = generate("calculator", &calculator(), "calculator.json", "{}");
+
+        assert!(code.contains("pub struct AddInput {"));
+        assert!(code.contains("    /// First operand\n    pub a: f64,"));
+        assert!(code.contains("    #[serde(rename = \"roundTo\")]\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub round_to: Option<i64>,"));
+        assert!(code.contains("pub struct AddOutput {"));
+        assert!(code.contains("pub steps: Option<Vec<AddOutputStepsItem>>,"));
+        assert!(code.contains("pub struct AddOutputStepsItem {"));
+        assert!(code.contains("/// Add two numbers\npub async fn add<C: FoundationClient>(\n    client: &C,\n    input: &AddInput,\n) -> Result<AddOutput, CompositionError> {"));
+        assert!(code.contains("pub async fn get_history<C: FoundationClient>(client: &C) -> Result<serde_json::Value, CompositionError> {"));
+        assert!(code.contains(".call_tool_typed(SERVER_ID, \"get-history\", &arguments)"));
+    }
+
+    #[test]
+    fn pins_schema_fingerprint() {
+        let json = "{\"server_id\":\"calculator\"}\n";
+        let code = generate("calculator", &calculator(), "calculator.json", json);
+
+        assert!(code.contains("include_str!(\"calculator.json\")"));
+        assert!(code.contains(&format!(
+            "{:#018x}",
+            pmcp::composition::schema_fingerprint(json.as_bytes())
+        )));
+    }
+
+    #[test]
+    fn output_type_name_annotation_is_used() {
+        let mut schema = calculator();
+        schema.tools[0].annotations = serde_json::from_value(json!({
+            "pmcp:outputTypeName": "ArithmeticResult"
+        }))
+        .unwrap();
+        let code = generate("calculator", &schema, "calculator.json", "{}");
+
+        assert!(code.contains("pub struct ArithmeticResult {"));
+        assert!(code.contains("Result<ArithmeticResult, CompositionError>"));
+    }
+
+    #[test]
+    fn shared_definitions_generate_one_struct() {
+        let schema: McpSchema = serde_json::from_value(json!({
+            "server_id": "orders",
+            "name": "Orders",
+            "definitions": {
+                "Address": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"]
+                }
+            },
+            "tools": [
+                {
+                    "name": "create_order",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "shipping": { "$ref": "#/definitions/Address", "description": "Ship to" }
+                        },
+                        "required": ["shipping"]
+                    }
+                },
+                {
+                    "name": "update_shipping",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": { "address": { "$ref": "#/definitions/Address" } },
+                        "required": ["address"]
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+        let code = generate("orders", &schema, "orders.json", "{}");
+
+        assert_eq!(code.matches("pub struct Address {").count(), 1);
+        assert!(code.contains("/// Shared `Address` definition.\n"));
+        assert!(code.contains("    /// Ship to\n    pub shipping: Address,"));
+        assert!(code.contains("    pub address: Address,"));
+        assert!(!code.contains("CreateOrderInputShipping"));
+    }
+
+    #[test]
+    fn handler_types_derive_json_schema() {
+        let schema = calculator();
+        let types = handler_types(&schema.tools[0], &schema.definitions);
+
+        assert_eq!(types.input, "AddInput");
+        assert!(types
+            .source
+            .contains("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]\npub struct AddInput {"));
+
+        let no_args: ToolSchema = serde_json::from_value(json!({ "name": "ping" })).unwrap();
+        let types = handler_types(&no_args, &Map::new());
+        assert_eq!(types.input, "PingInput");
+        assert!(types.output.is_none());
+        assert!(types.source.contains("pub struct PingInput {\n}\n"));
+    }
+
+    #[test]
+    fn identifiers_are_valid_rust() {
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("userID"), "user_id");
+        assert_eq!(field_ident("3d-mode"), "_3d_mode");
+        assert_eq!(pascal_case("get-history"), "GetHistory");
+        assert_eq!(pascal_case("list_tables.v2"), "ListTablesV2");
+        assert_eq!(module_name("my-calc"), "my_calc");
+    }
+}
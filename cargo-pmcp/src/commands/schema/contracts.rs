@@ -0,0 +1,454 @@
+//! Contract scenarios for foundation servers, derived from domain usage.
+//!
+//! A domain server depends on a foundation through the tools it calls and the
+//! output fields its typed bindings deserialize. This module finds those calls,
+//! either by scanning the domain's sources for calls into the generated
+//! bindings module or from recorded `tools/call` traffic, and turns them into
+//! an mcp-tester scenario. The foundation runs the scenario in its own CI, so a
+//! change that removes a tool, rejects arguments the domain sends, or drops a
+//! required output field fails there instead of in the domain.
+
+use anyhow::{anyhow, Context, Result};
+use mcp_tester::scenario::{Assertion, Operation, TestScenario, TestStep};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::bindings;
+use super::McpSchema;
+
+/// Most recorded argument sets turned into steps for one tool.
+const MAX_RECORDED_CALLS: usize = 3;
+
+/// Deepest `$ref` chain followed when building example arguments.
+const MAX_REF_DEPTH: usize = 8;
+
+/// How the domain uses one foundation tool.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ToolUsage {
+    /// Source locations (`path:line`) calling the tool's binding.
+    pub call_sites: Vec<String>,
+    /// Distinct argument sets seen in recorded traffic.
+    pub recorded: Vec<Value>,
+}
+
+/// Find calls to the bindings for `schema` in the Rust sources under `src`.
+///
+/// Calls are matched as `<module>::<tool_fn>(`, where `<module>` is the
+/// bindings module for `server_id`. Files under `skip` (the vendored
+/// bindings themselves) are ignored.
+pub fn scan_sources(
+    src: &Path,
+    skip: &Path,
+    server_id: &str,
+    schema: &McpSchema,
+) -> Result<BTreeMap<String, ToolUsage>> {
+    let module = bindings::module_name(server_id);
+    let pattern = format!(
+        r"\b{}::(?:r#)?([A-Za-z_][A-Za-z0-9_]*)\s*\(",
+        regex::escape(module.trim_start_matches("r#"))
+    );
+    let calls = Regex::new(&pattern).context("Invalid bindings call pattern")?;
+    let tools_by_fn: HashMap<String, &str> = schema
+        .tools
+        .iter()
+        .map(|tool| {
+            let function = bindings::function_name(&tool.name);
+            (
+                function.trim_start_matches("r#").to_string(),
+                tool.name.as_str(),
+            )
+        })
+        .collect();
+
+    let mut usage: BTreeMap<String, ToolUsage> = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(src)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.path().starts_with(skip))
+    {
+        let entry = entry.with_context(|| format!("Failed to walk {}", src.display()))?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        for (index, line) in content.lines().enumerate() {
+            for capture in calls.captures_iter(line) {
+                if let Some(tool) = tools_by_fn.get(&capture[1]) {
+                    usage
+                        .entry((*tool).to_string())
+                        .or_default()
+                        .call_sites
+                        .push(format!("{}:{}", path.display(), index + 1));
+                }
+            }
+        }
+    }
+    Ok(usage)
+}
+
+/// Add the `tools/call` requests recorded in `content` to `usage`.
+///
+/// `content` is JSON Lines. Each line is either a JSON-RPC `tools/call`
+/// request or `{"tool": ..., "arguments": ...}`. Lines carrying a
+/// `"foundation"` field for a different server are skipped, as are other
+/// JSON-RPC methods.
+pub fn add_recorded_traffic(
+    usage: &mut BTreeMap<String, ToolUsage>,
+    content: &str,
+    server_id: &str,
+) -> Result<usize> {
+    let mut added = 0;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let record: Value = serde_json::from_str(line)
+            .with_context(|| format!("Traffic line {} is not valid JSON", index + 1))?;
+        if let Some(foundation) = record.get("foundation").and_then(Value::as_str) {
+            if foundation != server_id {
+                continue;
+            }
+        }
+
+        let (tool, arguments) = match record.get("method").and_then(Value::as_str) {
+            Some("tools/call") => {
+                let params = record.get("params").unwrap_or(&Value::Null);
+                (params.get("name"), params.get("arguments"))
+            },
+            Some(_) => continue,
+            None => (record.get("tool"), record.get("arguments")),
+        };
+        let tool = tool.and_then(Value::as_str).ok_or_else(|| {
+            anyhow!(
+                "Traffic line {} has no tool name (expected a tools/call request or a \"tool\" field)",
+                index + 1
+            )
+        })?;
+        let arguments = arguments.cloned().unwrap_or_else(|| json!({}));
+
+        let entry = usage.entry(tool.to_string()).or_default();
+        if !entry.recorded.contains(&arguments) {
+            entry.recorded.push(arguments);
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// Build the contract scenario for the tools `consumer` uses.
+///
+/// Each tool gets one step per recorded argument set (up to
+/// [`MAX_RECORDED_CALLS`]), or a single step with example arguments built
+/// from its input schema. Every step asserts success and the presence of the
+/// output fields the schema marks as required, since the domain's typed
+/// bindings fail to deserialize without them.
+pub fn generate(
+    server_id: &str,
+    consumer: &str,
+    schema: &McpSchema,
+    usage: &BTreeMap<String, ToolUsage>,
+) -> TestScenario {
+    let mut steps = Vec::new();
+    for (tool_name, tool_usage) in usage {
+        let tool = schema.tools.iter().find(|t| &t.name == tool_name);
+        let assertions = tool
+            .and_then(|t| t.output_schema.as_ref())
+            .map(|output| output_assertions(output, &schema.definitions))
+            .unwrap_or_else(|| vec![Assertion::Success]);
+
+        let mut calls: Vec<(String, Value)> = tool_usage
+            .recorded
+            .iter()
+            .take(MAX_RECORDED_CALLS)
+            .enumerate()
+            .map(|(i, args)| (format!("recorded call {}", i + 1), args.clone()))
+            .collect();
+        if calls.is_empty() {
+            let example = tool
+                .and_then(|t| t.input_schema.as_ref())
+                .map(|input| example_value(input, &schema.definitions, 0))
+                .unwrap_or_else(|| json!({}));
+            calls.push(("example arguments".to_string(), example));
+        }
+
+        for (label, arguments) in calls {
+            steps.push(TestStep {
+                name: format!("{} ({})", tool_name, label),
+                operation: Operation::ToolCall {
+                    tool: tool_name.clone(),
+                    arguments,
+                },
+                timeout: Some(30),
+                continue_on_failure: true,
+                store_result: None,
+                assertions: assertions.clone(),
+            });
+        }
+    }
+
+    TestScenario {
+        name: format!("Contract: {} -> {}", consumer, server_id),
+        description: Some(format!(
+            "Tools of the `{}` foundation as used by `{}`. Generated by \
+             `cargo pmcp schema contracts {}`; regenerate instead of editing.",
+            server_id, consumer, server_id
+        )),
+        timeout: 60,
+        stop_on_failure: false,
+        variables: HashMap::new(),
+        setup: Vec::new(),
+        steps,
+        cleanup: Vec::new(),
+    }
+}
+
+/// Success plus an `exists` check for each required top-level output field.
+fn output_assertions(output: &Value, definitions: &Map<String, Value>) -> Vec<Assertion> {
+    let output = resolve(output, definitions, 0);
+    let mut assertions = vec![Assertion::Success];
+    let required = output
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    assertions.extend(required.map(|field| Assertion::Exists {
+        path: format!("parsed.{}", field),
+    }));
+    assertions
+}
+
+/// Follow `$ref`s into the shared definitions.
+fn resolve<'a>(schema: &'a Value, definitions: &'a Map<String, Value>, depth: usize) -> &'a Value {
+    match schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/definitions/"))
+        .and_then(|name| definitions.get(name))
+    {
+        Some(target) if depth < MAX_REF_DEPTH => resolve(target, definitions, depth + 1),
+        _ => schema,
+    }
+}
+
+/// A value satisfying `schema`, filling only required object properties.
+fn example_value(schema: &Value, definitions: &Map<String, Value>, depth: usize) -> Value {
+    let schema = resolve(schema, definitions, 0);
+    for key in ["const", "default", "example"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    if let Some(first) = schema
+        .get("examples")
+        .or_else(|| schema.get("enum"))
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::String(ty)) => Some(ty.as_str()),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    };
+    match ty {
+        Some("string") => json!("example"),
+        Some("integer") => schema.get("minimum").cloned().unwrap_or_else(|| json!(0)),
+        Some("number") => schema.get("minimum").cloned().unwrap_or_else(|| json!(0.0)),
+        Some("boolean") => json!(false),
+        Some("array") => {
+            let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+            match schema.get("items") {
+                Some(items) if min_items > 0 && depth < MAX_REF_DEPTH => {
+                    let item = example_value(items, definitions, depth + 1);
+                    Value::Array(vec![item; min_items as usize])
+                },
+                _ => json!([]),
+            }
+        },
+        Some("object") | None if depth < MAX_REF_DEPTH => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let mut object = Map::new();
+            for field in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                let value = properties
+                    .and_then(|p| p.get(field))
+                    .map(|s| example_value(s, definitions, depth + 1))
+                    .unwrap_or(Value::Null);
+                object.insert(field.to_string(), value);
+            }
+            Value::Object(object)
+        },
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> McpSchema {
+        serde_json::from_value(json!({
+            "server_id": "catalog",
+            "name": "Catalog",
+            "definitions": {
+                "Money": {
+                    "type": "object",
+                    "properties": {
+                        "amount": { "type": "number" },
+                        "currency": { "type": "string", "enum": ["EUR", "USD"] }
+                    },
+                    "required": ["amount", "currency"]
+                }
+            },
+            "tools": [
+                {
+                    "name": "get-product",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "sku": { "type": "string" },
+                            "locale": { "type": "string" },
+                            "budget": { "$ref": "#/definitions/Money" }
+                        },
+                        "required": ["sku", "budget"]
+                    },
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "price": { "$ref": "#/definitions/Money" },
+                            "tags": { "type": "array" }
+                        },
+                        "required": ["name", "price"]
+                    }
+                },
+                { "name": "list_categories" },
+                { "name": "unused" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn scan_finds_binding_calls_outside_vendored_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let foundations = src.join("foundations");
+        std::fs::create_dir_all(&foundations).unwrap();
+        std::fs::write(
+            src.join("tools.rs"),
+            "let p = foundations::catalog::get_product(&client, &input).await?;\n\
+             let c = catalog::list_categories (&client).await?;\n\
+             let other = inventory::get_product(&client, &input).await?;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            foundations.join("catalog.rs"),
+            "pub async fn unused<C>(client: &C) { catalog::unused(client) }\n",
+        )
+        .unwrap();
+
+        let usage = scan_sources(&src, &foundations, "catalog", &catalog()).unwrap();
+
+        assert_eq!(
+            usage.keys().collect::<Vec<_>>(),
+            vec!["get-product", "list_categories"]
+        );
+        assert!(usage["get-product"].call_sites[0].ends_with("tools.rs:1"));
+        assert!(usage["list_categories"].call_sites[0].ends_with("tools.rs:2"));
+    }
+
+    #[test]
+    fn recorded_traffic_is_deduplicated_and_filtered() {
+        let traffic = r#"
+{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"get-product","arguments":{"sku":"A1"}}}
+{"jsonrpc":"2.0","id":2,"method":"tools/list"}
+{"tool":"get-product","arguments":{"sku":"A1"}}
+{"tool":"get-product","arguments":{"sku":"B2"},"foundation":"catalog"}
+{"tool":"reserve","arguments":{},"foundation":"inventory"}
+"#;
+        let mut usage = BTreeMap::new();
+        let added = add_recorded_traffic(&mut usage, traffic, "catalog").unwrap();
+
+        assert_eq!(added, 2);
+        assert_eq!(
+            usage["get-product"].recorded,
+            vec![json!({"sku": "A1"}), json!({"sku": "B2"})]
+        );
+        assert!(!usage.contains_key("reserve"));
+
+        let err = add_recorded_traffic(&mut usage, "{\"arguments\":{}}", "catalog").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn generate_uses_examples_and_asserts_required_output() {
+        let mut usage = BTreeMap::new();
+        usage.insert("get-product".to_string(), ToolUsage::default());
+        let scenario = generate("catalog", "storefront", &catalog(), &usage);
+
+        assert_eq!(scenario.name, "Contract: storefront -> catalog");
+        assert_eq!(scenario.steps.len(), 1);
+        let step = &scenario.steps[0];
+        let Operation::ToolCall { tool, arguments } = &step.operation else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(tool, "get-product");
+        assert_eq!(
+            arguments,
+            &json!({"sku": "example", "budget": {"amount": 0.0, "currency": "EUR"}})
+        );
+
+        let paths: Vec<&str> = step
+            .assertions
+            .iter()
+            .filter_map(|a| match a {
+                Assertion::Exists { path } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(paths, vec!["parsed.name", "parsed.price"]);
+        assert!(matches!(step.assertions[0], Assertion::Success));
+    }
+
+    #[test]
+    fn generate_prefers_recorded_arguments() {
+        let mut usage = BTreeMap::new();
+        usage.insert(
+            "get-product".to_string(),
+            ToolUsage {
+                call_sites: Vec::new(),
+                recorded: (0..5).map(|i| json!({"sku": format!("S{}", i)})).collect(),
+            },
+        );
+        usage.insert("removed-tool".to_string(), ToolUsage::default());
+        let scenario = generate("catalog", "storefront", &catalog(), &usage);
+
+        let names: Vec<&str> = scenario.steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "get-product (recorded call 1)",
+                "get-product (recorded call 2)",
+                "get-product (recorded call 3)",
+                "removed-tool (example arguments)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,1368 @@
+//! Schema discovery and management commands
+//!
+//! - `export`: Export schema from an MCP server endpoint
+//! - `validate`: Validate a local schema file
+//! - `diff`: Compare local schema with live server
+//! - `vendor`: Pin a schema in-repo and generate typed bindings
+//! - `contracts`: Generate foundation contract scenarios from domain usage
+//! - `workflows`: Render the workflows of a server as Mermaid or DOT diagrams
+
+pub(crate) mod bindings;
+mod contracts;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Subcommand;
+use console::style;
+use pmcp::server::workflow::{graph::WORKFLOW_META_KEY, GraphFormat, WorkflowGraph};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::flags::AuthFlags;
+
+#[derive(Subcommand)]
+pub enum SchemaCommand {
+    /// Export schema from an MCP server endpoint
+    Export {
+        /// MCP server URL or --server for pmcp.run
+        #[command(flatten)]
+        server_flags: super::flags::ServerFlags,
+
+        /// Output file path (default: schemas/<server_id>.json)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Authentication flags for the target MCP server
+        #[command(flatten)]
+        auth_flags: AuthFlags,
+    },
+
+    /// Validate a schema file
+    Validate {
+        /// Schema file to validate
+        schema: String,
+    },
+
+    /// Show diff between local schema and live server
+    Diff {
+        /// Local schema file
+        schema: String,
+
+        /// MCP server URL to compare against
+        #[arg(index = 2)]
+        url: String,
+    },
+
+    /// Vendor a foundation schema into the repo and generate typed bindings
+    ///
+    /// Copies the exported schema to <out-dir>/<server_id>.json and writes
+    /// <out-dir>/<server_id>.rs with typed structs and call functions for
+    /// `FoundationClient`. The generated module fails to compile if the
+    /// vendored schema is changed without re-running this command.
+    Vendor {
+        /// Foundation server ID (as configured in foundations.toml)
+        server_id: String,
+
+        /// Exported schema to vendor (default: schemas/<server_id>.json)
+        #[arg(long)]
+        schema: Option<String>,
+
+        /// Directory for the vendored schema and generated bindings
+        #[arg(long, default_value = "src/foundations")]
+        out_dir: String,
+
+        /// Verify the vendored files are up to date without writing (for CI)
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Generate contract test scenarios for a foundation from this domain's usage
+    ///
+    /// Finds the foundation tools this domain calls, from calls into the
+    /// vendored bindings under --src and/or recorded tools/call traffic, and
+    /// writes an mcp-tester scenario that calls each one and asserts the
+    /// output fields the bindings rely on. Run it in the foundation's CI with
+    /// `cargo pmcp test run --scenarios <file>`.
+    Contracts {
+        /// Foundation server ID (as configured in foundations.toml)
+        server_id: String,
+
+        /// Foundation schema (default: vendored copy in --bindings-dir, then schemas/<server_id>.json)
+        #[arg(long)]
+        schema: Option<String>,
+
+        /// Domain sources to scan for binding calls
+        #[arg(long, default_value = "src")]
+        src: String,
+
+        /// Directory holding the vendored bindings (excluded from the scan)
+        #[arg(long, default_value = "src/foundations")]
+        bindings_dir: String,
+
+        /// Recorded tools/call traffic as JSON Lines
+        #[arg(long)]
+        traffic: Option<String>,
+
+        /// Name of the consuming domain server (default: package name in Cargo.toml)
+        #[arg(long)]
+        consumer: Option<String>,
+
+        /// Output scenario file (default: contracts/<server_id>.yaml)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Render every workflow a server registers as a diagram
+    ///
+    /// Reads the step graphs pmcp servers publish with their workflow
+    /// prompts and renders them as Mermaid (a Markdown document with one
+    /// section per workflow) or Graphviz DOT.
+    Workflows {
+        /// MCP server URL or --server for pmcp.run
+        #[command(flatten)]
+        server_flags: super::flags::ServerFlags,
+
+        /// Diagram format: mermaid or dot
+        #[arg(long, default_value = "mermaid")]
+        format: GraphFormat,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Authentication flags for the target MCP server
+        #[command(flatten)]
+        auth_flags: AuthFlags,
+    },
+}
+
+impl SchemaCommand {
+    pub fn execute(self, global_flags: &crate::commands::GlobalFlags) -> Result<()> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        let quiet = global_flags.quiet;
+        runtime.block_on(async {
+            match self {
+                SchemaCommand::Export {
+                    server_flags,
+                    output,
+                    auth_flags,
+                } => {
+                    export(
+                        server_flags.url,
+                        server_flags.server,
+                        output,
+                        quiet,
+                        &auth_flags,
+                    )
+                    .await
+                },
+                SchemaCommand::Validate { schema } => validate(&schema, quiet).await,
+                SchemaCommand::Diff { schema, url } => diff(&schema, &url, quiet).await,
+                SchemaCommand::Vendor {
+                    server_id,
+                    schema,
+                    out_dir,
+                    check,
+                } => vendor(
+                    &server_id,
+                    schema.as_deref(),
+                    Path::new(&out_dir),
+                    check,
+                    quiet,
+                ),
+                SchemaCommand::Contracts {
+                    server_id,
+                    schema,
+                    src,
+                    bindings_dir,
+                    traffic,
+                    consumer,
+                    output,
+                } => generate_contracts(
+                    &server_id,
+                    schema.as_deref(),
+                    Path::new(&src),
+                    Path::new(&bindings_dir),
+                    traffic.as_deref(),
+                    consumer,
+                    output,
+                    quiet,
+                ),
+                SchemaCommand::Workflows {
+                    server_flags,
+                    format,
+                    output,
+                    auth_flags,
+                } => {
+                    workflows(
+                        server_flags.url,
+                        server_flags.server,
+                        format,
+                        output,
+                        quiet,
+                        &auth_flags,
+                    )
+                    .await
+                },
+            }
+        })
+    }
+}
+
+/// MCP server schema for code generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpSchema {
+    /// JSON Schema identifier
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema_url: Option<String>,
+
+    /// Server ID (used for module naming)
+    pub server_id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Server description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Server version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Endpoint URL (for reference)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// Server tier (foundation/domain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+
+    /// Tool definitions
+    #[serde(default)]
+    pub tools: Vec<ToolSchema>,
+
+    /// Shared schema definitions referenced from tool schemas via
+    /// `{"$ref": "#/definitions/<name>"}`
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub definitions: serde_json::Map<String, Value>,
+
+    /// Resource definitions
+    #[serde(default)]
+    pub resources: Vec<ResourceSchema>,
+
+    /// Prompt definitions
+    #[serde(default)]
+    pub prompts: Vec<PromptSchema>,
+
+    /// Export metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Input schema - deserializes from MCP's "inputSchema" or our "input_schema"
+    #[serde(
+        default,
+        alias = "inputSchema",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub input_schema: Option<Value>,
+    /// Output schema - top-level per MCP spec 2025-06-18
+    #[serde(
+        default,
+        alias = "outputSchema",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_schema: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+/// Tool annotations supporting both MCP standard and PMCP extensions.
+///
+/// When deserializing from MCP responses, this maps:
+/// - `readOnlyHint` -> `read_only`
+/// - `destructiveHint` -> `destructive`
+/// - `idempotentHint` -> `idempotent`
+/// - `openWorldHint` -> `open_world`
+/// - `pmcp:outputTypeName` -> `output_type_name` (PMCP extension)
+///
+/// Note: `outputSchema` is now a top-level field on `ToolSchema` per MCP spec 2025-06-18.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// Human-readable title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Tool does not modify state
+    #[serde(alias = "read_only", skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+
+    /// Tool may perform destructive operations
+    #[serde(alias = "destructive", skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+
+    /// Tool is idempotent (same args = same result)
+    #[serde(alias = "idempotent", skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+
+    /// Tool interacts with external systems
+    #[serde(alias = "open_world", skip_serializing_if = "Option::is_none")]
+    pub open_world_hint: Option<bool>,
+
+    // -------------------------------------------------------------------------
+    // PMCP Extensions for Type-Safe Composition
+    // -------------------------------------------------------------------------
+    /// Name of the output type for code generation (PMCP extension).
+    ///
+    /// Example: "QueryResult" generates `struct QueryResult { ... }`
+    #[serde(
+        rename = "pmcp:outputTypeName",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_type_name: Option<String>,
+
+    // Legacy/simplified fields for internal use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSchema {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// MCP JSON-RPC request
+#[derive(Debug, Serialize)]
+struct McpRequest {
+    jsonrpc: &'static str,
+    id: u64,
+    method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+}
+
+/// MCP JSON-RPC response
+#[derive(Debug, Deserialize)]
+struct McpResponse {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    #[allow(dead_code)]
+    id: u64,
+    result: Option<Value>,
+    error: Option<McpError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct McpError {
+    code: i32,
+    message: String,
+}
+
+/// Export schema from an MCP server
+async fn export(
+    endpoint: Option<String>,
+    server: Option<String>,
+    output: Option<String>,
+    quiet: bool,
+    auth_flags: &AuthFlags,
+) -> Result<()> {
+    let endpoint_url = endpoint_url(&endpoint, &server, "export")?;
+
+    // Resolve authentication
+    let auth_method = auth_flags.resolve();
+    let auth_header_value = super::auth::resolve_auth_header(&endpoint_url, &auth_method).await?;
+
+    if !quiet {
+        println!(
+            "{} Exporting schema from {}",
+            style("->").cyan().bold(),
+            style(&endpoint_url).yellow()
+        );
+    }
+
+    // Create HTTP client
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    // Initialize MCP session
+    if !quiet {
+        println!("  {} Initializing MCP session...", style("*").dim());
+    }
+    let init_response = send_mcp_request(
+        &client,
+        &endpoint_url,
+        "initialize",
+        Some(json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {
+                "name": "cargo-pmcp",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        })),
+        auth_header_value.as_deref(),
+    )
+    .await?;
+
+    // Extract server info
+    let server_info = init_response
+        .get("serverInfo")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+
+    let server_name = server_info
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let server_version = server_info
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    // Derive server_id from name or endpoint
+    let server_id = server.clone().unwrap_or_else(|| slugify(&server_name));
+
+    // Send initialized notification (fire and forget, with auth)
+    let mut notif_builder = client
+        .post(&endpoint_url)
+        .header("Content-Type", "application/json");
+    if let Some(ref auth) = auth_header_value {
+        notif_builder = notif_builder.header("Authorization", auth);
+    }
+    let _ = notif_builder
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        }))
+        .send()
+        .await;
+
+    // Fetch tools
+    if !quiet {
+        println!("  {} Fetching tools...", style("*").dim());
+    }
+    let tools_response = send_mcp_request(
+        &client,
+        &endpoint_url,
+        "tools/list",
+        None,
+        auth_header_value.as_deref(),
+    )
+    .await?;
+    let tools: Vec<ToolSchema> = tools_response
+        .get("tools")
+        .and_then(|t| serde_json::from_value(t.clone()).ok())
+        .unwrap_or_default();
+    let definitions: serde_json::Map<String, Value> = tools_response
+        .get("definitions")
+        .and_then(|d| serde_json::from_value(d.clone()).ok())
+        .unwrap_or_default();
+    if !quiet {
+        println!(
+            "    {} Found {} tools",
+            style("OK").green(),
+            style(tools.len()).bold()
+        );
+    }
+
+    // Fetch resources
+    if !quiet {
+        println!("  {} Fetching resources...", style("*").dim());
+    }
+    let resources_response = send_mcp_request(
+        &client,
+        &endpoint_url,
+        "resources/list",
+        None,
+        auth_header_value.as_deref(),
+    )
+    .await;
+    let resources: Vec<ResourceSchema> = resources_response
+        .ok()
+        .and_then(|r| r.get("resources").cloned())
+        .and_then(|r| serde_json::from_value(r).ok())
+        .unwrap_or_default();
+    if !quiet {
+        println!(
+            "    {} Found {} resources",
+            style("OK").green(),
+            style(resources.len()).bold()
+        );
+    }
+
+    // Fetch prompts
+    if !quiet {
+        println!("  {} Fetching prompts...", style("*").dim());
+    }
+    let prompts_response = send_mcp_request(
+        &client,
+        &endpoint_url,
+        "prompts/list",
+        None,
+        auth_header_value.as_deref(),
+    )
+    .await;
+    let prompts: Vec<PromptSchema> = prompts_response
+        .ok()
+        .and_then(|r| r.get("prompts").cloned())
+        .and_then(|r| serde_json::from_value(r).ok())
+        .unwrap_or_default();
+    if !quiet {
+        println!(
+            "    {} Found {} prompts",
+            style("OK").green(),
+            style(prompts.len()).bold()
+        );
+    }
+
+    // Build schema
+    let schema = McpSchema {
+        schema_url: Some("https://pmcp.run/schemas/mcp-foundation-v1.json".to_string()),
+        server_id: server_id.clone(),
+        name: server_name,
+        description: None,
+        version: server_version,
+        endpoint: Some(endpoint_url),
+        tier: Some("foundation".to_string()),
+        tools,
+        definitions,
+        resources,
+        prompts,
+        exported_at: Some(chrono::Utc::now().to_rfc3339()),
+    };
+
+    // Determine output path
+    let output_path = output.unwrap_or_else(|| format!("schemas/{}.json", server_id));
+
+    // Create parent directories
+    if let Some(parent) = Path::new(&output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    // Write schema
+    let schema_json =
+        serde_json::to_string_pretty(&schema).context("Failed to serialize schema")?;
+    std::fs::write(&output_path, &schema_json)
+        .with_context(|| format!("Failed to write schema to {}", output_path))?;
+
+    if !quiet {
+        println!();
+        println!(
+            "{} Schema exported to {}",
+            style("OK").green().bold(),
+            style(&output_path).cyan()
+        );
+        println!();
+        println!(
+            "  Server: {} v{}",
+            style(&schema.name).bold(),
+            style(schema.version.as_deref().unwrap_or("?")).dim()
+        );
+        println!("  Tools: {}", style(schema.tools.len()).bold());
+        println!("  Resources: {}", style(schema.resources.len()).bold());
+        println!("  Prompts: {}", style(schema.prompts.len()).bold());
+        println!();
+        println!("Next steps:");
+        println!(
+            "  1. Review and customize: {}",
+            style(&output_path).yellow()
+        );
+        println!(
+            "  2. Generate typed client: {}",
+            style(format!("cargo pmcp schema vendor {}", schema.server_id)).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Resolve the endpoint of `cargo pmcp schema <command>` from a URL or a pmcp.run server ID.
+fn endpoint_url(
+    endpoint: &Option<String>,
+    server: &Option<String>,
+    command: &str,
+) -> Result<String> {
+    match (endpoint, server) {
+        (Some(url), _) => Ok(url.clone()),
+        // Construct pmcp.run endpoint
+        (None, Some(server_id)) => Ok(format!("https://api.pmcp.run/{}/mcp", server_id)),
+        (None, None) => Err(anyhow!(
+            "Either a URL or --server must be specified\n\n\
+             Examples:\n  \
+             cargo pmcp schema {command} https://mcp.example.com\n  \
+             cargo pmcp schema {command} --server db-demo"
+        )),
+    }
+}
+
+/// Render the workflows of a server as diagrams
+async fn workflows(
+    endpoint: Option<String>,
+    server: Option<String>,
+    format: GraphFormat,
+    output: Option<String>,
+    quiet: bool,
+    auth_flags: &AuthFlags,
+) -> Result<()> {
+    let endpoint_url = endpoint_url(&endpoint, &server, "workflows")?;
+    let auth_method = auth_flags.resolve();
+    let auth_header_value = super::auth::resolve_auth_header(&endpoint_url, &auth_method).await?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    send_mcp_request(
+        &client,
+        &endpoint_url,
+        "initialize",
+        Some(json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "cargo-pmcp", "version": env!("CARGO_PKG_VERSION") }
+        })),
+        auth_header_value.as_deref(),
+    )
+    .await?;
+
+    let mut graphs = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = cursor.as_ref().map(|c| json!({ "cursor": c }));
+        let page = send_mcp_request(
+            &client,
+            &endpoint_url,
+            "prompts/list",
+            params,
+            auth_header_value.as_deref(),
+        )
+        .await?;
+        graphs.extend(workflow_graphs(&page)?);
+        cursor = page
+            .get("nextCursor")
+            .and_then(Value::as_str)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string);
+        if cursor.is_none() {
+            break;
+        }
+    }
+    if graphs.is_empty() {
+        return Err(anyhow!(
+            "{} publishes no workflow graphs; workflows registered with a pmcp server \
+             carry them in their prompt metadata",
+            endpoint_url
+        ));
+    }
+
+    let rendered = render_workflows(&graphs, format);
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered).with_context(|| format!("Failed to write {}", path))?;
+            if !quiet {
+                println!(
+                    "{} Rendered {} workflows to {}",
+                    style("OK").green().bold(),
+                    style(graphs.len()).bold(),
+                    style(&path).cyan()
+                );
+            }
+        },
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+/// The workflow graphs published in a `prompts/list` result.
+fn workflow_graphs(prompts_list: &Value) -> Result<Vec<WorkflowGraph>> {
+    let prompts = prompts_list
+        .get("prompts")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    prompts
+        .iter()
+        .filter_map(|prompt| prompt.get("_meta")?.get(WORKFLOW_META_KEY))
+        .map(|graph| {
+            serde_json::from_value(graph.clone()).context("Failed to parse workflow graph")
+        })
+        .collect()
+}
+
+/// Mermaid renders as a Markdown document with one section per workflow;
+/// DOT as one digraph per workflow.
+fn render_workflows(graphs: &[WorkflowGraph], format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Mermaid => {
+            let mut out = String::from("# Workflows\n");
+            for graph in graphs {
+                out.push_str(&format!("\n## {}\n\n", graph.name));
+                if !graph.description.is_empty() {
+                    out.push_str(&format!("{}\n\n", graph.description));
+                }
+                out.push_str(&format!("```mermaid\n{}```\n", graph.to_mermaid()));
+            }
+            out
+        },
+        GraphFormat::Dot => graphs
+            .iter()
+            .map(WorkflowGraph::to_dot)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Validate a schema file
+async fn validate(schema_path: &str, quiet: bool) -> Result<()> {
+    if !quiet {
+        println!(
+            "{} Validating schema: {}",
+            style("->").cyan().bold(),
+            style(schema_path).yellow()
+        );
+    }
+
+    // Read schema file
+    let content = std::fs::read_to_string(schema_path)
+        .with_context(|| format!("Failed to read schema file: {}", schema_path))?;
+
+    // Parse as JSON
+    let schema: McpSchema =
+        serde_json::from_str(&content).with_context(|| "Failed to parse schema JSON")?;
+
+    // Validate required fields
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+
+    if schema.server_id.is_empty() {
+        errors.push("server_id is required".to_string());
+    }
+    if schema.name.is_empty() {
+        errors.push("name is required".to_string());
+    }
+    if schema.tools.is_empty() && schema.resources.is_empty() && schema.prompts.is_empty() {
+        warnings.push("Schema has no tools, resources, or prompts".to_string());
+    }
+
+    // Check tools
+    for (i, tool) in schema.tools.iter().enumerate() {
+        if tool.name.is_empty() {
+            errors.push(format!("tools[{}].name is required", i));
+        }
+    }
+
+    // Report results -- validation errors are important output (always show)
+    if !warnings.is_empty() {
+        for warning in &warnings {
+            println!("  {} {}", style("WARN").yellow(), warning);
+        }
+    }
+
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("  {} {}", style("ERR").red(), error);
+        }
+        return Err(anyhow!(
+            "Schema validation failed with {} errors",
+            errors.len()
+        ));
+    }
+
+    // Success output is decorative
+    if !quiet {
+        println!("{} Schema is valid", style("OK").green().bold());
+        println!(
+            "  Server: {} ({})",
+            style(&schema.name).bold(),
+            schema.server_id
+        );
+        println!(
+            "  Tools: {}, Resources: {}, Prompts: {}",
+            schema.tools.len(),
+            schema.resources.len(),
+            schema.prompts.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Compare local schema with live server
+/// List the tool names a live server exposes (unauthenticated).
+pub(crate) async fn fetch_tool_names(endpoint: &str) -> Result<Vec<String>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    send_mcp_request(
+        &client,
+        endpoint,
+        "initialize",
+        Some(json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "cargo-pmcp", "version": env!("CARGO_PKG_VERSION") }
+        })),
+        None,
+    )
+    .await?;
+
+    let tools_response = send_mcp_request(&client, endpoint, "tools/list", None, None).await?;
+    let tools: Vec<ToolSchema> = tools_response
+        .get("tools")
+        .and_then(|t| serde_json::from_value(t.clone()).ok())
+        .unwrap_or_default();
+
+    Ok(tools.into_iter().map(|t| t.name).collect())
+}
+
+/// Tools added on the remote side and tools removed from it, each sorted.
+pub(crate) fn diff_tool_names(local: &[String], remote: &[String]) -> (Vec<String>, Vec<String>) {
+    let local: std::collections::BTreeSet<&String> = local.iter().collect();
+    let remote: std::collections::BTreeSet<&String> = remote.iter().collect();
+
+    let added = remote.difference(&local).map(|s| s.to_string()).collect();
+    let removed = local.difference(&remote).map(|s| s.to_string()).collect();
+    (added, removed)
+}
+
+async fn diff(schema_path: &str, endpoint: &str, quiet: bool) -> Result<()> {
+    if !quiet {
+        println!(
+            "{} Comparing {} with {}",
+            style("->").cyan().bold(),
+            style(schema_path).yellow(),
+            style(endpoint).yellow()
+        );
+    }
+
+    // Read local schema
+    let local_content = std::fs::read_to_string(schema_path)
+        .with_context(|| format!("Failed to read schema file: {}", schema_path))?;
+    let local: McpSchema =
+        serde_json::from_str(&local_content).with_context(|| "Failed to parse local schema")?;
+
+    let local_tools: Vec<String> = local.tools.iter().map(|t| t.name.clone()).collect();
+    let remote_tools = fetch_tool_names(endpoint).await?;
+    let (added, removed) = diff_tool_names(&local_tools, &remote_tools);
+
+    println!();
+    if added.is_empty() && removed.is_empty() {
+        println!("{} No differences found", style("OK").green().bold());
+    } else {
+        if !added.is_empty() {
+            println!("{} Added tools:", style("+").green());
+            for name in added {
+                println!("  {} {}", style("+").green(), name);
+            }
+        }
+        if !removed.is_empty() {
+            println!("{} Removed tools:", style("-").red());
+            for name in removed {
+                println!("  {} {}", style("-").red(), name);
+            }
+        }
+        if !quiet {
+            println!();
+            println!(
+                "Run {} to update local schema",
+                style(format!(
+                    "cargo pmcp schema export {} --output {}",
+                    endpoint, schema_path
+                ))
+                .yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Vendor a schema into `out_dir` and generate typed bindings next to it.
+///
+/// With `check`, compares what would be written against the files on disk
+/// and fails if they differ. When the exported schema is not present (e.g.
+/// in CI), the vendored copy itself is used as the source.
+fn vendor(
+    server_id: &str,
+    schema_path: Option<&str>,
+    out_dir: &Path,
+    check: bool,
+    quiet: bool,
+) -> Result<()> {
+    let module = bindings::module_name(server_id);
+    let json_file = format!("{}.json", module);
+    let json_path = out_dir.join(&json_file);
+    let rs_path = out_dir.join(format!("{}.rs", module));
+
+    let default_source = format!("schemas/{}.json", server_id);
+    let source = match schema_path {
+        Some(path) => Path::new(path).to_path_buf(),
+        None if Path::new(&default_source).exists() => default_source.into(),
+        None if check && json_path.exists() => json_path.clone(),
+        None => {
+            return Err(anyhow!(
+                "Schema not found: {}\n\n\
+                 Export it first:\n  \
+                 cargo pmcp schema export <URL> --output {}",
+                default_source,
+                default_source
+            ))
+        },
+    };
+
+    let content = std::fs::read_to_string(&source)
+        .with_context(|| format!("Failed to read schema file: {}", source.display()))?;
+    let mut schema: McpSchema = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse schema JSON: {}", source.display()))?;
+
+    // The export timestamp would make every re-vendor a diff
+    schema.exported_at = None;
+    let schema_json = format!(
+        "{}\n",
+        serde_json::to_string_pretty(&schema).context("Failed to serialize schema")?
+    );
+    let code = bindings::generate(server_id, &schema, &json_file, &schema_json);
+
+    if check {
+        let stale: Vec<String> = [(&json_path, &schema_json), (&rs_path, &code)]
+            .into_iter()
+            .filter(|(path, expected)| {
+                std::fs::read_to_string(path)
+                    .map(|actual| actual.replace("\r\n", "\n") != **expected)
+                    .unwrap_or(true)
+            })
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !stale.is_empty() {
+            for path in &stale {
+                println!("  {} {} is out of date", style("ERR").red(), path);
+            }
+            return Err(anyhow!(
+                "Vendored schema for '{}' is out of date; run `cargo pmcp schema vendor {}`",
+                server_id,
+                server_id
+            ));
+        }
+        if !quiet {
+            println!(
+                "{} Vendored schema for {} is up to date",
+                style("OK").green().bold(),
+                style(server_id).bold()
+            );
+        }
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create directory: {}", out_dir.display()))?;
+    std::fs::write(&json_path, &schema_json)
+        .with_context(|| format!("Failed to write {}", json_path.display()))?;
+    std::fs::write(&rs_path, &code)
+        .with_context(|| format!("Failed to write {}", rs_path.display()))?;
+    let mod_path = out_dir.join("mod.rs");
+    let mod_created = register_module(&mod_path, &module)?;
+
+    if !quiet {
+        println!(
+            "{} Vendored {} ({} tools)",
+            style("OK").green().bold(),
+            style(server_id).bold(),
+            schema.tools.len()
+        );
+        println!("  Schema:   {}", style(json_path.display()).cyan());
+        println!("  Bindings: {}", style(rs_path.display()).cyan());
+        println!();
+        println!("Next steps:");
+        if mod_created {
+            println!(
+                "  1. Declare the module: {} in main.rs or lib.rs",
+                style("mod foundations;").yellow()
+            );
+        }
+        println!(
+            "  {}. Call tools: {}",
+            if mod_created { 2 } else { 1 },
+            style(format!(
+                "foundations::{}::<tool>(&client, &input).await?",
+                module
+            ))
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Write the contract scenario for `server_id` as used by this domain.
+#[allow(clippy::too_many_arguments)]
+fn generate_contracts(
+    server_id: &str,
+    schema_path: Option<&str>,
+    src: &Path,
+    bindings_dir: &Path,
+    traffic: Option<&str>,
+    consumer: Option<String>,
+    output: Option<String>,
+    quiet: bool,
+) -> Result<()> {
+    let vendored = bindings_dir.join(format!("{}.json", bindings::module_name(server_id)));
+    let exported = Path::new("schemas").join(format!("{}.json", server_id));
+    let source = match schema_path {
+        Some(path) => Path::new(path).to_path_buf(),
+        None if vendored.exists() => vendored,
+        None if exported.exists() => exported,
+        None => {
+            return Err(anyhow!(
+                "Schema for '{}' not found in {} or {}
+
+                 Vendor it first:
+                   cargo pmcp schema vendor {}",
+                server_id,
+                vendored.display(),
+                exported.display(),
+                server_id
+            ))
+        },
+    };
+    let content = std::fs::read_to_string(&source)
+        .with_context(|| format!("Failed to read schema file: {}", source.display()))?;
+    let schema: McpSchema = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse schema JSON: {}", source.display()))?;
+
+    let mut usage = if src.exists() {
+        contracts::scan_sources(src, bindings_dir, server_id, &schema)?
+    } else {
+        Default::default()
+    };
+    let scanned = usage.len();
+    let mut recorded = 0;
+    if let Some(traffic) = traffic {
+        let content = std::fs::read_to_string(traffic)
+            .with_context(|| format!("Failed to read traffic file: {}", traffic))?;
+        recorded = contracts::add_recorded_traffic(&mut usage, &content, server_id)?;
+    }
+    if usage.is_empty() {
+        return Err(anyhow!(
+            "No calls to '{}' found in {}{}",
+            server_id,
+            src.display(),
+            if traffic.is_some() {
+                " or the recorded traffic"
+            } else {
+                "; pass --traffic to use recorded calls"
+            }
+        ));
+    }
+
+    let consumer = consumer
+        .or_else(package_name)
+        .unwrap_or_else(|| "domain".to_string());
+    let scenario = contracts::generate(server_id, &consumer, &schema, &usage);
+
+    let output = output.unwrap_or_else(|| format!("contracts/{}.yaml", server_id));
+    let output = Path::new(&output);
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+    }
+    scenario.to_file(output)?;
+
+    if !quiet {
+        println!(
+            "{} Contract for {} ({} tools, {} steps)",
+            style("OK").green().bold(),
+            style(server_id).bold(),
+            usage.len(),
+            scenario.steps.len()
+        );
+        println!(
+            "  Sources:  {} tools called from {}",
+            scanned,
+            style(src.display()).cyan()
+        );
+        if traffic.is_some() {
+            println!("  Traffic:  {} recorded argument sets", recorded);
+        }
+        for tool in usage.keys() {
+            if !schema.tools.iter().any(|t| &t.name == tool) {
+                println!(
+                    "  {} '{}' is not in the foundation schema; its step will fail",
+                    style("WARN").yellow(),
+                    tool
+                );
+            }
+        }
+        println!("  Scenario: {}", style(output.display()).cyan());
+        println!();
+        println!("Next steps:");
+        println!("  1. Review the example arguments in the scenario");
+        println!(
+            "  2. In the foundation's CI: {}",
+            style(format!(
+                "cargo pmcp test run <foundation-url> --scenarios {}",
+                output.display()
+            ))
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Package name from `Cargo.toml` in the current directory.
+fn package_name() -> Option<String> {
+    let content = std::fs::read_to_string("Cargo.toml").ok()?;
+    let manifest: toml::Value = toml::from_str(&content).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Add `pub mod <module>;` to the foundations `mod.rs`, creating it if needed.
+///
+/// Returns `true` if the file was created.
+fn register_module(mod_path: &Path, module: &str) -> Result<bool> {
+    let declaration = format!("pub mod {};", module);
+    let existing = match std::fs::read_to_string(mod_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", mod_path.display())),
+    };
+
+    let (content, created) = match existing {
+        Some(content) if content.lines().any(|l| l.trim() == declaration) => return Ok(false),
+        Some(mut content) => {
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(&declaration);
+            content.push('\n');
+            (content, false)
+        },
+        None => (
+            format!(
+                "//! Typed bindings for vendored foundation schemas.\n//!\n//! Managed by `cargo pmcp schema vendor`.\n\n{}\n",
+                declaration
+            ),
+            true,
+        ),
+    };
+    std::fs::write(mod_path, content)
+        .with_context(|| format!("Failed to write {}", mod_path.display()))?;
+    Ok(created)
+}
+
+/// Send an MCP JSON-RPC request with optional authentication.
+///
+/// When `auth_header` is `Some`, it is attached as the `Authorization` header
+/// on the outbound HTTP request.
+async fn send_mcp_request(
+    client: &reqwest::Client,
+    endpoint: &str,
+    method: &str,
+    params: Option<Value>,
+    auth_header: Option<&str>,
+) -> Result<Value> {
+    let request = McpRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: method.to_string(),
+        params,
+    };
+
+    let mut builder = client
+        .post(endpoint)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json");
+    if let Some(auth) = auth_header {
+        builder = builder.header("Authorization", auth);
+    }
+
+    let response = builder
+        .json(&request)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to {}", endpoint))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Server returned error: {} {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        ));
+    }
+
+    let mcp_response: McpResponse = response
+        .json()
+        .await
+        .with_context(|| "Failed to parse MCP response")?;
+
+    if let Some(error) = mcp_response.error {
+        return Err(anyhow!("MCP error {}: {}", error.code, error.message));
+    }
+
+    mcp_response
+        .result
+        .ok_or_else(|| anyhow!("Empty result from MCP server"))
+}
+
+/// Convert a string to a URL-safe slug
+fn slugify(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "server_id": "calculator",
+        "name": "Calculator",
+        "exported_at": "2026-01-01T00:00:00Z",
+        "tools": [{
+            "name": "add",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "a": { "type": "number" }, "b": { "type": "number" } },
+                "required": ["a", "b"]
+            }
+        }]
+    }"#;
+
+    #[test]
+    fn vendor_writes_bindings_and_detects_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("calculator.export.json");
+        std::fs::write(&source, SCHEMA).unwrap();
+        let out_dir = dir.path().join("foundations");
+        let source = source.to_str().unwrap();
+
+        vendor("calculator", Some(source), &out_dir, false, true).unwrap();
+        let vendored = std::fs::read_to_string(out_dir.join("calculator.json")).unwrap();
+        assert!(!vendored.contains("exported_at"));
+        let code = std::fs::read_to_string(out_dir.join("calculator.rs")).unwrap();
+        assert!(code.contains("pub struct AddInput {"));
+        assert_eq!(
+            std::fs::read_to_string(out_dir.join("mod.rs"))
+                .unwrap()
+                .matches("pub mod calculator;")
+                .count(),
+            1
+        );
+
+        // Re-vendoring is idempotent, including the mod.rs declaration
+        vendor("calculator", Some(source), &out_dir, false, true).unwrap();
+        vendor("calculator", Some(source), &out_dir, true, true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(out_dir.join("mod.rs"))
+                .unwrap()
+                .matches("pub mod calculator;")
+                .count(),
+            1
+        );
+
+        // Editing the vendored schema by hand is drift
+        std::fs::write(
+            out_dir.join("calculator.json"),
+            vendored.replace("\"add\"", "\"sum\""),
+        )
+        .unwrap();
+        assert!(vendor("calculator", Some(source), &out_dir, true, true).is_err());
+    }
+
+    #[test]
+    fn register_module_appends_to_existing_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        let mod_path = dir.path().join("mod.rs");
+        std::fs::write(&mod_path, "pub mod database;").unwrap();
+
+        assert!(!register_module(&mod_path, "calculator").unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&mod_path).unwrap(),
+            "pub mod database;\npub mod calculator;\n"
+        );
+    }
+
+    #[test]
+    fn renders_workflow_graphs_from_prompt_metadata() {
+        use pmcp::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+
+        let graph = SequentialWorkflow::new("summarize", "Summarize a page")
+            .step(WorkflowStep::new("fetch", ToolHandle::new("fetch")))
+            .graph();
+        let prompts_list = json!({
+            "prompts": [
+                { "name": "plain" },
+                { "name": "summarize", "_meta": { (WORKFLOW_META_KEY): graph } }
+            ]
+        });
+
+        let graphs = workflow_graphs(&prompts_list).unwrap();
+        assert_eq!(graphs, vec![graph]);
+
+        let markdown = render_workflows(&graphs, GraphFormat::Mermaid);
+        assert!(markdown.contains("## summarize\n\nSummarize a page\n\n```mermaid\nflowchart TD\n"));
+        assert!(markdown.ends_with("```\n"));
+        let dot = render_workflows(&graphs, GraphFormat::Dot);
+        assert!(dot.starts_with("digraph \"summarize\" {"));
+    }
+
+    #[test]
+    fn diff_tool_names_reports_added_and_removed() {
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let (added, removed) = diff_tool_names(
+            &names(&["add", "divide"]),
+            &names(&["multiply", "add", "abs"]),
+        );
+
+        assert_eq!(added, names(&["abs", "multiply"]));
+        assert_eq!(removed, names(&["divide"]));
+    }
+}
@@ -5,12 +5,18 @@
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use std::collections::{BTreeSet, HashMap};
 use std::io::{self, IsTerminal, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use crate::commands::flags::FormatValue;
 use crate::secrets::{
     config::{detect_target, SecretTarget, SecretsConfig},
+    drift::{collect_references, compute_drift, DriftEntry, DriftStatus, SecretReference},
+    load_dotenv,
+    provider::SecretProvider,
+    value::SecretEntry,
     ListOptions, ProviderRegistry, SecretCharset, SecretValue, SetOptions,
 };
 
@@ -161,17 +167,32 @@ pub enum SecretAction {
         check: bool,
     },
 
-    /// Sync secrets from configuration
+    /// Compare configured secrets with the target and fix drift.
+    ///
+    /// Shows which referenced secrets are missing from the target, which
+    /// target secrets are no longer referenced, and which differ from the
+    /// version pinned in configuration (`secret:server/NAME@3`).
+    ///
+    /// # Examples
+    ///
+    /// Drift table against pmcp.run:
+    ///   cargo pmcp secret sync --target pmcp
+    ///
+    /// Fail CI when the deployment has drifted:
+    ///   cargo pmcp secret sync --target pmcp --check
+    ///
+    /// Walk through fixes one secret at a time:
+    ///   cargo pmcp secret sync --target pmcp --interactive
     Sync {
         /// TOML file to analyze
         #[arg(long, default_value = "pmcp.toml")]
         file: PathBuf,
 
-        /// Check only, no changes
-        #[arg(long)]
+        /// Report only; exit with an error if anything drifted
+        #[arg(long, conflicts_with = "interactive")]
         check: bool,
 
-        /// Prompt for each missing secret
+        /// Offer a fix for each drifted secret
         #[arg(long)]
         interactive: bool,
     },
@@ -428,61 +449,171 @@ impl SecretCommand {
                 check,
                 interactive,
             } => {
-                // Parse the TOML file to find secret references
-                if !file.exists() {
-                    anyhow::bail!("Configuration file not found: {}", file.display());
-                }
+                self.sync(&registry, provider, file, *check, *interactive, quiet)
+                    .await?;
+            },
+        }
 
-                let content = std::fs::read_to_string(file)?;
-                let secrets_refs = parse_secret_references(&content);
+        Ok(())
+    }
 
-                if secrets_refs.is_empty() {
-                    println!("No secret references found in {}", file.display());
-                    return Ok(());
-                }
+    /// Compare configured secret references with the target and offer fixes.
+    async fn sync(
+        &self,
+        registry: &ProviderRegistry,
+        provider: Arc<dyn SecretProvider>,
+        file: &Path,
+        check: bool,
+        interactive: bool,
+        quiet: bool,
+    ) -> Result<()> {
+        if !file.exists() {
+            anyhow::bail!("Configuration file not found: {}", file.display());
+        }
+
+        let mut content = std::fs::read_to_string(file)?;
+        let refs = collect_references(&content, self.server.as_deref());
+
+        if refs.is_empty() {
+            println!("No secret references found in {}", file.display());
+            return Ok(());
+        }
+
+        let deployed = list_referenced_secrets(provider.as_ref(), &refs).await?;
+        let drift = compute_drift(&refs, &deployed);
+        let drifted: Vec<&DriftEntry> = drift
+            .iter()
+            .filter(|e| e.status != DriftStatus::InSync)
+            .collect();
+
+        if matches!(self.format, FormatValue::Json) {
+            println!("{}", serde_json::to_string_pretty(&drift)?);
+        } else {
+            println!("{} vs {}:", file.display(), provider.name());
+            print_drift_table(&drift);
+        }
 
-                println!("Secrets referenced in configuration:");
+        if drifted.is_empty() {
+            if !quiet && !matches!(self.format, FormatValue::Json) {
+                println!();
+                println!("All secrets are in sync.");
+            }
+            return Ok(());
+        }
 
-                // Check which secrets exist
-                let list_result = provider.list(ListOptions::default()).await?;
-                let existing: std::collections::HashSet<_> =
-                    list_result.secrets.iter().map(|s| &s.name).collect();
+        if check {
+            anyhow::bail!(
+                "{} secret(s) drifted from {}",
+                drifted.len(),
+                provider.name()
+            );
+        }
 
-                let mut missing = Vec::new();
-                for secret_ref in &secrets_refs {
-                    if existing.contains(secret_ref) {
-                        println!("  ✓ {}  exists", secret_ref);
+        if !interactive {
+            if !quiet && !matches!(self.format, FormatValue::Json) {
+                println!();
+                println!(
+                    "{} secret(s) drifted. Run with --interactive for guided fixes.",
+                    drifted.len()
+                );
+            }
+            return Ok(());
+        }
+
+        // Values can be copied from the local store or environment when syncing a remote target
+        let local = if provider.id() == "local" {
+            None
+        } else {
+            Some(registry.get("local")?)
+        };
+        let dotenv = load_dotenv(&std::env::current_dir()?);
+        let mut pins_updated = false;
+
+        for entry in drifted {
+            println!();
+            match entry.status {
+                DriftStatus::Missing | DriftStatus::Stale => {
+                    let mut choices = Vec::new();
+                    if entry.status == DriftStatus::Stale {
+                        println!(
+                            "{}: {} has version {}, {} pins version {}",
+                            entry.name,
+                            provider.name(),
+                            entry.deployed_version.unwrap_or_default(),
+                            file.display(),
+                            entry.expected_version.unwrap_or_default()
+                        );
+                        choices.push("[p]in deployed version");
                     } else {
-                        println!("  ✗ {}  MISSING", secret_ref);
-                        missing.push(secret_ref.clone());
+                        println!("{}: missing from {}", entry.name, provider.name());
                     }
-                }
 
-                if missing.is_empty() {
-                    println!();
-                    println!("All secrets are configured.");
-                } else {
-                    println!();
-                    println!("{} secret(s) missing.", missing.len());
-
-                    if *check {
-                        // Just report, don't create
-                        println!("Run with --interactive to create missing secrets.");
-                    } else if *interactive {
-                        // Prompt for each missing secret
-                        for name in &missing {
-                            println!();
-                            println!("Creating secret: {}", name);
-                            let value = rpassword::prompt_password("  Enter value (hidden): ")?;
-
-                            provider
-                                .set(name, SecretValue::new(value), SetOptions::default())
-                                .await?;
-                            println!("  ✓ Created");
-                        }
+                    let local_value =
+                        local_secret_value(local.as_deref(), &dotenv, &entry.name).await;
+                    if local_value.is_some() {
+                        choices.push("[c]opy local value");
                     }
-                }
-            },
+                    choices.extend(["[e]nter value", "[g]enerate", "[s]kip"]);
+
+                    let value = match prompt_choice(&choices.join(", "))?.as_str() {
+                        "p" if entry.status == DriftStatus::Stale => {
+                            if let (Some(expected), Some(actual)) =
+                                (entry.expected_version, entry.deployed_version)
+                            {
+                                content = content.replace(
+                                    &format!("secret:{}@{}", entry.name, expected),
+                                    &format!("secret:{}@{}", entry.name, actual),
+                                );
+                                pins_updated = true;
+                                println!("  ✓ Pinned version {}", actual);
+                            }
+                            continue;
+                        },
+                        "c" => local_value,
+                        "e" => Some(SecretValue::new(rpassword::prompt_password(
+                            "  Enter value (hidden): ",
+                        )?)),
+                        "g" => Some(SecretValue::generate(32, SecretCharset::Alphanumeric)),
+                        _ => None,
+                    };
+
+                    match value {
+                        Some(value) => {
+                            let options = SetOptions {
+                                server_id: entry.name.split_once('/').map(|(s, _)| s.to_string()),
+                                ..Default::default()
+                            };
+                            let metadata = provider.set(&entry.name, value, options).await?;
+                            match metadata.version {
+                                Some(version) => println!("  ✓ Set (version {})", version),
+                                None => println!("  ✓ Set"),
+                            }
+                        },
+                        None => println!("  - Skipped"),
+                    }
+                },
+                DriftStatus::Extra => {
+                    println!(
+                        "{}: in {} but not referenced by {}",
+                        entry.name,
+                        provider.name(),
+                        file.display()
+                    );
+                    if prompt_choice("[d]elete, [k]eep")? == "d" {
+                        provider.delete(&entry.name, true).await?;
+                        println!("  ✓ Deleted");
+                    } else {
+                        println!("  - Kept");
+                    }
+                },
+                DriftStatus::InSync => {},
+            }
+        }
+
+        if pins_updated {
+            std::fs::write(file, content)?;
+            println!();
+            println!("Updated version pins in {}", file.display());
         }
 
         Ok(())
@@ -505,16 +636,77 @@ impl SecretCommand {
     }
 }
 
-/// Parse secret references from TOML content.
-fn parse_secret_references(content: &str) -> Vec<String> {
-    let mut refs = Vec::new();
-    let re = regex::Regex::new(r#"secret:([a-zA-Z0-9_\-/]+)"#).unwrap();
+/// List the target's secrets for every server that `refs` mention.
+///
+/// pmcp.run lists one server at a time, so each referenced server is listed
+/// separately.
+pub(crate) async fn list_referenced_secrets(
+    provider: &dyn SecretProvider,
+    refs: &[SecretReference],
+) -> Result<Vec<SecretEntry>> {
+    let servers: BTreeSet<&str> = refs.iter().map(|r| r.server_id()).collect();
+    let mut deployed = Vec::new();
+    for server in servers {
+        let options = ListOptions {
+            filter: None,
+            server_id: Some(server.to_string()),
+            include_metadata: true,
+        };
+        let result = provider.list(options).await.with_context(|| {
+            format!("Failed to list '{}' secrets in {}", server, provider.name())
+        })?;
+        deployed.extend(result.secrets);
+    }
+    Ok(deployed)
+}
+
+/// Print the drift table for `secret sync`.
+fn print_drift_table(drift: &[DriftEntry]) {
+    println!(
+        "{:<40} {:<10} {:<8} {}",
+        "NAME", "STATUS", "PINNED", "DEPLOYED"
+    );
+    for entry in drift {
+        let marker = if entry.status == DriftStatus::InSync {
+            "✓"
+        } else {
+            "✗"
+        };
+        let version = |v: Option<u32>| v.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+        println!(
+            "{} {:<38} {:<10} {:<8} {}",
+            marker,
+            entry.name,
+            entry.status.label(),
+            version(entry.expected_version),
+            version(entry.deployed_version)
+        );
+    }
+}
 
-    for cap in re.captures_iter(content) {
-        if let Some(m) = cap.get(1) {
-            refs.push(m.as_str().to_string());
+/// Find a value for `name` in the local store, the shell environment, or `.env`.
+async fn local_secret_value(
+    local: Option<&dyn SecretProvider>,
+    dotenv: &HashMap<String, String>,
+    name: &str,
+) -> Option<SecretValue> {
+    if let Some(local) = local {
+        if let Ok(value) = local.get(name).await {
+            return Some(value);
         }
     }
+    let env_name = name.rsplit('/').next().unwrap_or(name);
+    std::env::var(env_name)
+        .ok()
+        .or_else(|| dotenv.get(env_name).cloned())
+        .map(SecretValue::new)
+}
 
-    refs
+/// Prompt for a single-letter choice, returned lowercased.
+fn prompt_choice(choices: &str) -> Result<String> {
+    print!("  {}: ", choices);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_lowercase().chars().take(1).collect())
 }
@@ -0,0 +1,529 @@
+//! Register local MCP servers as always-on background services.
+//!
+//! `cargo pmcp install-service --server X` builds the server in release mode,
+//! copies the binary out of `target/` (so `cargo clean` does not break the
+//! service), and registers it with the platform's service manager:
+//!
+//! - **macOS** — a launchd agent at `~/Library/LaunchAgents/run.pmcp.<server>.plist`,
+//!   loaded with `launchctl load -w`. Logs go to `~/Library/Logs/pmcp/<server>.log`.
+//! - **Windows** — a Task Scheduler task `pmcp\<server>` that starts at logon and
+//!   runs a `run.cmd` wrapper. MCP servers do not speak the Service Control
+//!   Manager protocol, so a logon task is used instead of an SCM service; the
+//!   wrapper implements the restart policy and redirects output to
+//!   `%LOCALAPPDATA%\pmcp\logs\<server>.log`.
+//!
+//! `cargo pmcp uninstall-service --server X` stops the service and removes
+//! everything `install-service` created except the log file.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::GlobalFlags;
+use crate::secrets::resolve::load_dotenv;
+use crate::utils::config::WorkspaceConfig;
+
+/// Port used when the server is not listed in `.pmcp-config.toml`.
+const DEFAULT_PORT: u16 = 3000;
+
+/// Seconds to wait before restarting a server that exited.
+const RESTART_DELAY_SECS: u32 = 5;
+
+/// When the service manager restarts the server after it exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RestartPolicy {
+    /// Restart whenever the server exits
+    Always,
+    /// Restart only when the server exits with an error
+    OnFailure,
+    /// Never restart; the server runs once per login
+    Never,
+}
+
+/// Everything needed to render a service definition.
+#[derive(Debug, Clone)]
+struct ServiceSpec {
+    server: String,
+    binary: PathBuf,
+    working_dir: PathBuf,
+    env: Vec<(String, String)>,
+    log_file: PathBuf,
+    restart: RestartPolicy,
+}
+
+/// Build the server and register it as a launchd agent or Windows logon task.
+pub fn install(
+    server: String,
+    port: Option<u16>,
+    restart: RestartPolicy,
+    global_flags: &GlobalFlags,
+) -> Result<()> {
+    let platform = Platform::current()?;
+
+    if global_flags.should_output() {
+        println!();
+        println!(
+            "  {} {}",
+            "cargo pmcp install-service".bright_white().bold(),
+            server.bright_cyan()
+        );
+        println!("  {}", "─".repeat(40).dimmed());
+        println!();
+    }
+
+    if !PathBuf::from("Cargo.toml").exists() {
+        anyhow::bail!("Not in a workspace directory. Run 'cargo pmcp new <name>' first.");
+    }
+    let working_dir = std::env::current_dir().context("Failed to get current directory")?;
+
+    let port = match port {
+        Some(port) => port,
+        None => WorkspaceConfig::load()?
+            .get_server(&server)
+            .map(|config| config.port)
+            .unwrap_or(DEFAULT_PORT),
+    };
+
+    let binary_name = super::dev::resolve_server_binary(&server)?;
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--bin", &binary_name])
+        .status()
+        .context("Failed to build server")?;
+    if !status.success() {
+        anyhow::bail!("Server build failed");
+    }
+    if global_flags.should_output() {
+        println!("  {} Built {} (release)", "✓".green(), binary_name);
+    }
+
+    // Copy the binary out of target/ so rebuilding or cleaning the workspace
+    // does not pull it from under the running service.
+    let service_dir = service_dir(&server)?;
+    std::fs::create_dir_all(&service_dir)
+        .with_context(|| format!("Failed to create {}", service_dir.display()))?;
+    let built = working_dir.join("target/release").join(format!(
+        "{}{}",
+        binary_name,
+        std::env::consts::EXE_SUFFIX
+    ));
+    let binary = service_dir.join(built.file_name().expect("binary path has a file name"));
+    // Stop a previous install first so the copy does not race a running binary
+    match platform {
+        Platform::MacOs => {
+            let _ = Command::new("launchctl")
+                .args(["unload", "-w"])
+                .arg(launchd_plist_path(&server)?)
+                .output();
+        },
+        Platform::Windows => stop_windows_task(&server, &service_dir),
+    }
+    std::fs::copy(&built, &binary)
+        .with_context(|| format!("Failed to copy {} to {}", built.display(), binary.display()))?;
+
+    let log_file = platform.log_dir()?.join(format!("{}.log", server));
+    if let Some(parent) = log_file.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut env = vec![
+        ("MCP_HTTP_PORT".to_string(), port.to_string()),
+        ("RUST_LOG".to_string(), "info".to_string()),
+    ];
+    let mut dotenv: Vec<(String, String)> = load_dotenv(&working_dir).into_iter().collect();
+    dotenv.sort();
+    env.extend(dotenv);
+
+    let spec = ServiceSpec {
+        server: server.clone(),
+        binary,
+        working_dir,
+        env,
+        log_file,
+        restart,
+    };
+
+    let registered = match platform {
+        Platform::MacOs => install_launchd(&spec)?,
+        Platform::Windows => install_windows_task(&spec, &service_dir)?,
+    };
+
+    if global_flags.should_output() {
+        println!("  {} Registered {}", "✓".green(), registered.display());
+        println!();
+        println!(
+            "  {:<10} http://localhost:{}",
+            "URL".dimmed(),
+            port.to_string().bright_yellow()
+        );
+        println!("  {:<10} {}", "Logs".dimmed(), spec.log_file.display());
+        println!("  {:<10} {:?}", "Restart".dimmed(), spec.restart);
+        println!();
+        println!(
+            "  Remove with: {}",
+            format!("cargo pmcp uninstall-service --server {}", server).bright_cyan()
+        );
+        println!();
+    }
+    Ok(())
+}
+
+/// Stop the service and remove its registration and installed binary.
+pub fn uninstall(server: String, global_flags: &GlobalFlags) -> Result<()> {
+    let platform = Platform::current()?;
+
+    let removed = match platform {
+        Platform::MacOs => uninstall_launchd(&server)?,
+        Platform::Windows => uninstall_windows_task(&server)?,
+    };
+
+    let service_dir = service_dir(&server)?;
+    if service_dir.exists() {
+        std::fs::remove_dir_all(&service_dir)
+            .with_context(|| format!("Failed to remove {}", service_dir.display()))?;
+    }
+
+    if !removed {
+        anyhow::bail!("No service installed for server '{}'", server);
+    }
+    if global_flags.should_output() {
+        println!(
+            "  {} Removed service for {}",
+            "✓".green(),
+            server.bright_cyan()
+        );
+        println!(
+            "  {} Logs kept at {}",
+            "→".blue(),
+            platform
+                .log_dir()?
+                .join(format!("{}.log", server))
+                .display()
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    fn current() -> Result<Self> {
+        match std::env::consts::OS {
+            "macos" => Ok(Self::MacOs),
+            "windows" => Ok(Self::Windows),
+            other => anyhow::bail!(
+                "Service installation is supported on macOS (launchd) and Windows; \
+                 on {} use your init system (e.g. a systemd user unit) to run the binary \
+                 from 'cargo build --release'",
+                other
+            ),
+        }
+    }
+
+    fn log_dir(self) -> Result<PathBuf> {
+        match self {
+            Self::MacOs => Ok(home_dir()?.join("Library/Logs/pmcp")),
+            Self::Windows => Ok(local_data_dir()?.join("pmcp").join("logs")),
+        }
+    }
+}
+
+fn home_dir() -> Result<PathBuf> {
+    dirs::home_dir().context("Could not determine home directory")
+}
+
+fn local_data_dir() -> Result<PathBuf> {
+    dirs::data_local_dir().context("Could not determine local data directory")
+}
+
+/// Where the installed binary (and the Windows wrapper script) live.
+fn service_dir(server: &str) -> Result<PathBuf> {
+    Ok(local_data_dir()?.join("pmcp").join("services").join(server))
+}
+
+fn launchd_label(server: &str) -> String {
+    format!("run.pmcp.{}", server)
+}
+
+fn launchd_plist_path(server: &str) -> Result<PathBuf> {
+    Ok(home_dir()?
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", launchd_label(server))))
+}
+
+fn windows_task_name(server: &str) -> String {
+    format!("pmcp\\{}", server)
+}
+
+fn install_launchd(spec: &ServiceSpec) -> Result<PathBuf> {
+    let plist_path = launchd_plist_path(&spec.server)?;
+    if let Some(parent) = plist_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&plist_path, launchd_plist(spec))
+        .with_context(|| format!("Failed to write {}", plist_path.display()))?;
+    // The plist may carry secrets from .env
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&plist_path, std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    run(Command::new("launchctl")
+        .args(["load", "-w"])
+        .arg(&plist_path))?;
+    Ok(plist_path)
+}
+
+fn uninstall_launchd(server: &str) -> Result<bool> {
+    let plist_path = launchd_plist_path(server)?;
+    if !plist_path.exists() {
+        return Ok(false);
+    }
+    // Unloading fails if the agent is not loaded; removing the plist is what matters
+    let _ = Command::new("launchctl")
+        .args(["unload", "-w"])
+        .arg(&plist_path)
+        .output();
+    std::fs::remove_file(&plist_path)
+        .with_context(|| format!("Failed to remove {}", plist_path.display()))?;
+    Ok(true)
+}
+
+fn install_windows_task(spec: &ServiceSpec, service_dir: &Path) -> Result<PathBuf> {
+    let script = service_dir.join("run.cmd");
+    std::fs::write(&script, windows_run_script(spec))
+        .with_context(|| format!("Failed to write {}", script.display()))?;
+
+    let task_name = windows_task_name(&spec.server);
+    // conhost --headless keeps the wrapper from opening a console window at logon
+    let action = format!("conhost.exe --headless \"{}\"", script.display());
+    run(Command::new("schtasks").args([
+        "/Create", "/F", "/TN", &task_name, "/SC", "ONLOGON", "/RL", "LIMITED", "/TR", &action,
+    ]))?;
+    run(Command::new("schtasks").args(["/Run", "/TN", &task_name]))?;
+    Ok(script)
+}
+
+fn uninstall_windows_task(server: &str) -> Result<bool> {
+    let task_name = windows_task_name(server);
+    let exists = Command::new("schtasks")
+        .args(["/Query", "/TN", &task_name])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !exists {
+        return Ok(false);
+    }
+    stop_windows_task(server, &service_dir(server)?);
+    run(Command::new("schtasks").args(["/Delete", "/F", "/TN", &task_name]))?;
+    Ok(true)
+}
+
+/// End the logon task and the server binary it launched, if running.
+fn stop_windows_task(server: &str, service_dir: &Path) {
+    let _ = Command::new("schtasks")
+        .args(["/End", "/TN", &windows_task_name(server)])
+        .output();
+
+    // Ending the task stops the wrapper; stop the server it launched as well
+    if let Ok(entries) = std::fs::read_dir(service_dir) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "exe") {
+                let _ = Command::new("taskkill")
+                    .args(["/F", "/IM"])
+                    .arg(entry.file_name())
+                    .output();
+            }
+        }
+    }
+}
+
+/// Run a service-manager command, surfacing its stderr on failure.
+fn run(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Render the launchd agent definition.
+fn launchd_plist(spec: &ServiceSpec) -> String {
+    let keep_alive = match spec.restart {
+        RestartPolicy::Always => "<true/>".to_string(),
+        RestartPolicy::OnFailure => {
+            "<dict>\n        <key>SuccessfulExit</key>\n        <false/>\n    </dict>".to_string()
+        },
+        RestartPolicy::Never => "<false/>".to_string(),
+    };
+    let log = xml_escape(&spec.log_file.display().to_string());
+
+    let mut env = String::new();
+    for (key, value) in &spec.env {
+        let _ = write!(
+            env,
+            "\n        <key>{}</key>\n        <string>{}</string>",
+            xml_escape(key),
+            xml_escape(value)
+        );
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{binary}</string>
+    </array>
+    <key>WorkingDirectory</key>
+    <string>{working_dir}</string>
+    <key>EnvironmentVariables</key>
+    <dict>{env}
+    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    {keep_alive}
+    <key>ThrottleInterval</key>
+    <integer>{delay}</integer>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        label = xml_escape(&launchd_label(&spec.server)),
+        binary = xml_escape(&spec.binary.display().to_string()),
+        working_dir = xml_escape(&spec.working_dir.display().to_string()),
+        delay = RESTART_DELAY_SECS,
+    )
+}
+
+/// Render the Windows wrapper script that sets the environment, redirects
+/// output to the log file, and applies the restart policy.
+fn windows_run_script(spec: &ServiceSpec) -> String {
+    let mut script = String::from("@echo off\r\n");
+    let _ = write!(
+        script,
+        "rem Generated by cargo pmcp install-service for {}\r\n",
+        spec.server
+    );
+    let _ = write!(script, "cd /d \"{}\"\r\n", spec.working_dir.display());
+    for (key, value) in &spec.env {
+        // %% keeps cmd from expanding values that contain percent signs
+        let _ = write!(script, "set \"{}={}\"\r\n", key, value.replace('%', "%%"));
+    }
+    script.push_str(":start\r\n");
+    let _ = write!(
+        script,
+        "\"{}\" >> \"{}\" 2>&1\r\n",
+        spec.binary.display(),
+        spec.log_file.display()
+    );
+    let restart = format!(
+        "timeout /t {} /nobreak > nul\r\ngoto start",
+        RESTART_DELAY_SECS
+    );
+    match spec.restart {
+        RestartPolicy::Always => {
+            let _ = write!(script, "{}\r\n", restart);
+        },
+        RestartPolicy::OnFailure => {
+            let _ = write!(
+                script,
+                "if errorlevel 1 (\r\n{}\r\n)\r\n",
+                restart.replace("\r\n", " & ")
+            );
+        },
+        RestartPolicy::Never => {},
+    }
+    script
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(restart: RestartPolicy) -> ServiceSpec {
+        ServiceSpec {
+            server: "chess".to_string(),
+            binary: PathBuf::from("/svc/chess-server"),
+            working_dir: PathBuf::from("/work/chess"),
+            env: vec![
+                ("MCP_HTTP_PORT".to_string(), "3000".to_string()),
+                ("API_KEY".to_string(), "a&b<c>%PATH%".to_string()),
+            ],
+            log_file: PathBuf::from("/logs/chess.log"),
+            restart,
+        }
+    }
+
+    #[test]
+    fn plist_contains_program_env_and_logs() {
+        let plist = launchd_plist(&spec(RestartPolicy::Always));
+
+        assert!(plist.contains("<string>run.pmcp.chess</string>"));
+        assert!(plist.contains("<string>/svc/chess-server</string>"));
+        assert!(plist.contains("<key>WorkingDirectory</key>\n    <string>/work/chess</string>"));
+        assert!(plist.contains("<key>MCP_HTTP_PORT</key>\n        <string>3000</string>"));
+        assert!(plist.contains("<string>a&amp;b&lt;c&gt;%PATH%</string>"));
+        assert!(
+            plist.contains("<key>StandardErrorPath</key>\n    <string>/logs/chess.log</string>")
+        );
+        assert!(plist.contains("<key>KeepAlive</key>\n    <true/>"));
+    }
+
+    #[test]
+    fn plist_keep_alive_follows_restart_policy() {
+        let on_failure = launchd_plist(&spec(RestartPolicy::OnFailure));
+        assert!(on_failure.contains("<key>SuccessfulExit</key>\n        <false/>"));
+
+        let never = launchd_plist(&spec(RestartPolicy::Never));
+        assert!(never.contains("<key>KeepAlive</key>\n    <false/>"));
+    }
+
+    #[test]
+    fn windows_script_sets_env_and_restarts() {
+        let always = windows_run_script(&spec(RestartPolicy::Always));
+        assert!(always.contains("cd /d \"/work/chess\"\r\n"));
+        assert!(always.contains("set \"API_KEY=a&b<c>%%PATH%%\"\r\n"));
+        assert!(always.contains("\"/svc/chess-server\" >> \"/logs/chess.log\" 2>&1\r\n"));
+        assert!(always.ends_with("timeout /t 5 /nobreak > nul\r\ngoto start\r\n"));
+
+        let on_failure = windows_run_script(&spec(RestartPolicy::OnFailure));
+        assert!(on_failure
+            .contains("if errorlevel 1 (\r\ntimeout /t 5 /nobreak > nul & goto start\r\n)"));
+
+        let never = windows_run_script(&spec(RestartPolicy::Never));
+        assert!(!never.contains("goto start"));
+    }
+}
@@ -0,0 +1,489 @@
+//! Project health dashboard — one view of local servers, deployments, tests, secrets, and schema.
+//!
+//! Each section is collected independently. A section that does not apply
+//! (nothing deployed, not logged in to pmcp.run, no exported schema) is
+//! reported as skipped, and a section that fails does not hide the others.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::net::{SocketAddr, TcpStream};
+use std::path::Path;
+use std::time::Duration;
+
+use super::flags::FormatValue;
+use super::GlobalFlags;
+use crate::deployment::targets::pmcp_run::{auth, graphql};
+use crate::deployment::{DeployConfig, TargetRegistry};
+use crate::secrets::config::{detect_target, SecretTarget, SecretsConfig};
+use crate::secrets::drift::{
+    collect_references, compute_drift, DriftEntry, DriftStatus, SecretReference,
+};
+use crate::secrets::ProviderRegistry;
+use crate::utils::config::WorkspaceConfig;
+
+/// How long to wait when probing a local server port.
+const PORT_PROBE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Outcome of collecting one dashboard section.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Section<T> {
+    Ok(T),
+    Skipped(String),
+    Failed(String),
+}
+
+impl<T> Section<T> {
+    fn from_result(result: Result<T>) -> Self {
+        match result {
+            Ok(value) => Section::Ok(value),
+            Err(e) => Section::Failed(format!("{:#}", e)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    local_servers: Section<Vec<LocalServer>>,
+    deployments: Section<Vec<Deployment>>,
+    scheduled_tests: Section<TestSummary>,
+    secret_drift: Section<SecretSummary>,
+    schema: Section<SchemaSummary>,
+}
+
+#[derive(Debug, Serialize)]
+struct LocalServer {
+    name: String,
+    port: u16,
+    running: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Deployment {
+    target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct TestSummary {
+    server_id: String,
+    scenarios: usize,
+    passed: usize,
+    failing: Vec<String>,
+    never_run: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_run: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SecretSummary {
+    target: String,
+    referenced: usize,
+    drifted: Vec<DriftEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct SchemaSummary {
+    schema: String,
+    endpoint: String,
+    /// Tools in the local schema that the deployment does not expose
+    not_deployed: Vec<String>,
+    /// Tools the deployment exposes that the local schema does not list
+    only_deployed: Vec<String>,
+}
+
+/// Show the project health dashboard.
+pub fn execute(format: FormatValue, global_flags: &GlobalFlags) -> Result<()> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let report = tokio::runtime::Runtime::new()?.block_on(collect(&project_root));
+
+    match format {
+        FormatValue::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        FormatValue::Text => print_report(&report, global_flags),
+    }
+    Ok(())
+}
+
+async fn collect(project_root: &Path) -> StatusReport {
+    let local_servers = local_servers();
+    let (deployments, server_id) = deployments(project_root).await;
+
+    let endpoint = match &deployments {
+        Section::Ok(list) => list.iter().find_map(|d| d.url.clone()),
+        _ => None,
+    };
+
+    let (scheduled_tests, secret_drift, schema) = tokio::join!(
+        scheduled_tests(server_id.as_deref()),
+        secret_drift(project_root),
+        schema_drift(project_root, server_id.as_deref(), endpoint.as_deref()),
+    );
+
+    StatusReport {
+        local_servers,
+        deployments,
+        scheduled_tests,
+        secret_drift,
+        schema,
+    }
+}
+
+fn local_servers() -> Section<Vec<LocalServer>> {
+    let config = match WorkspaceConfig::load() {
+        Ok(config) => config,
+        Err(e) => return Section::Failed(format!("{:#}", e)),
+    };
+    if config.servers.is_empty() {
+        return Section::Skipped("no servers in .pmcp-config.toml".to_string());
+    }
+
+    let mut servers: Vec<LocalServer> = config
+        .servers
+        .into_iter()
+        .map(|(name, server)| LocalServer {
+            running: port_is_listening(server.port),
+            name,
+            port: server.port,
+        })
+        .collect();
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+    Section::Ok(servers)
+}
+
+fn port_is_listening(port: u16) -> bool {
+    TcpStream::connect_timeout(
+        &SocketAddr::from(([127, 0, 0, 1], port)),
+        PORT_PROBE_TIMEOUT,
+    )
+    .is_ok()
+}
+
+/// Deployments from `.pmcp/deploy.toml` and `.pmcp/deployment.toml`, plus the server ID.
+async fn deployments(project_root: &Path) -> (Section<Vec<Deployment>>, Option<String>) {
+    let mut list = Vec::new();
+    let mut server_id = None;
+
+    if let Ok(config) = DeployConfig::load(project_root) {
+        let target_id = config.target.target_type.clone();
+        server_id = Some(config.server.name.clone());
+
+        let outputs = match TargetRegistry::new().get(&target_id) {
+            Ok(target) => target.outputs(&config).await,
+            Err(e) => Err(e),
+        };
+        list.push(match outputs {
+            Ok(outputs) => Deployment {
+                target: target_id,
+                url: outputs.url,
+                error: None,
+            },
+            Err(e) => Deployment {
+                target: target_id,
+                url: None,
+                error: Some(format!("{:#}", e)),
+            },
+        });
+    }
+
+    // Written by `cargo pmcp deploy` for pmcp.run; its server ID is the one
+    // pmcp.run knows the server by
+    if let Some((id, endpoint)) = crate::landing::config::load_deployment_info(project_root) {
+        match list.iter_mut().find(|d| d.target == "pmcp-run") {
+            Some(existing) if existing.url.is_none() => existing.url = Some(endpoint),
+            Some(_) => {},
+            None => list.push(Deployment {
+                target: "pmcp-run".to_string(),
+                url: Some(endpoint),
+                error: None,
+            }),
+        }
+        server_id = Some(id);
+    }
+
+    if list.is_empty() {
+        let reason = "not deployed (run: cargo pmcp deploy init)".to_string();
+        return (Section::Skipped(reason), server_id);
+    }
+    (Section::Ok(list), server_id)
+}
+
+async fn scheduled_tests(server_id: Option<&str>) -> Section<TestSummary> {
+    let Some(server_id) = server_id else {
+        return Section::Skipped("no deployed server ID".to_string());
+    };
+    let Ok(credentials) = auth::get_credentials().await else {
+        return Section::Skipped("not logged in to pmcp.run".to_string());
+    };
+
+    let result = graphql::list_test_scenarios(&credentials.access_token, server_id)
+        .await
+        .context("Failed to list scenarios");
+    Section::from_result(result.map(|r| summarize_scenarios(server_id, &r.scenarios)))
+}
+
+/// Summarize the last run of each enabled scenario.
+fn summarize_scenarios(server_id: &str, scenarios: &[graphql::ScenarioInfo]) -> TestSummary {
+    let mut summary = TestSummary {
+        server_id: server_id.to_string(),
+        ..TestSummary::default()
+    };
+
+    for scenario in scenarios.iter().filter(|s| s.enabled) {
+        summary.scenarios += 1;
+        match scenario.last_execution_status.as_deref() {
+            None => summary.never_run += 1,
+            Some(status) if is_passing(status) => summary.passed += 1,
+            Some(_) => summary.failing.push(scenario.name.clone()),
+        }
+        // RFC 3339 timestamps order lexicographically
+        if scenario.last_executed_at > summary.last_run {
+            summary.last_run = scenario.last_executed_at.clone();
+        }
+    }
+    summary
+}
+
+fn is_passing(status: &str) -> bool {
+    matches!(
+        status.to_ascii_lowercase().as_str(),
+        "passed" | "pass" | "success" | "succeeded"
+    )
+}
+
+async fn secret_drift(project_root: &Path) -> Section<SecretSummary> {
+    let file = project_root.join("pmcp.toml");
+    let Ok(content) = std::fs::read_to_string(&file) else {
+        return Section::Skipped("no pmcp.toml".to_string());
+    };
+    let refs = collect_references(&content, None);
+    if refs.is_empty() {
+        return Section::Skipped("no secret references in pmcp.toml".to_string());
+    }
+
+    Section::from_result(secret_summary(project_root, &refs).await)
+}
+
+async fn secret_summary(project_root: &Path, refs: &[SecretReference]) -> Result<SecretSummary> {
+    let config = SecretsConfig::load(project_root)?;
+    let target = match config.get_target(None) {
+        SecretTarget::Local => detect_target(),
+        target => target,
+    };
+    let provider = ProviderRegistry::new(project_root, &config).get_for_target(target.clone())?;
+    let deployed = super::secret::list_referenced_secrets(provider.as_ref(), refs).await?;
+
+    let drifted = compute_drift(refs, &deployed)
+        .into_iter()
+        .filter(|e| e.status != DriftStatus::InSync)
+        .collect();
+    Ok(SecretSummary {
+        target: target.to_string(),
+        referenced: refs.len(),
+        drifted,
+    })
+}
+
+async fn schema_drift(
+    project_root: &Path,
+    server_id: Option<&str>,
+    endpoint: Option<&str>,
+) -> Section<SchemaSummary> {
+    let (Some(server_id), Some(endpoint)) = (server_id, endpoint) else {
+        return Section::Skipped("no deployed endpoint".to_string());
+    };
+    let schema = format!("schemas/{}.json", server_id);
+    let Ok(content) = std::fs::read_to_string(project_root.join(&schema)) else {
+        return Section::Skipped(format!(
+            "no {} (run: cargo pmcp schema export {})",
+            schema, endpoint
+        ));
+    };
+
+    Section::from_result(schema_summary(&schema, &content, endpoint).await)
+}
+
+async fn schema_summary(schema: &str, content: &str, endpoint: &str) -> Result<SchemaSummary> {
+    let local: super::schema::McpSchema =
+        serde_json::from_str(content).with_context(|| format!("Failed to parse {}", schema))?;
+    let local_tools: Vec<String> = local.tools.into_iter().map(|t| t.name).collect();
+    let remote_tools = super::schema::fetch_tool_names(endpoint)
+        .await
+        .with_context(|| format!("Failed to list tools at {}", endpoint))?;
+
+    let (only_deployed, not_deployed) = super::schema::diff_tool_names(&local_tools, &remote_tools);
+    Ok(SchemaSummary {
+        schema: schema.to_string(),
+        endpoint: endpoint.to_string(),
+        not_deployed,
+        only_deployed,
+    })
+}
+
+fn print_report(report: &StatusReport, global_flags: &GlobalFlags) {
+    if global_flags.should_output() {
+        println!();
+        println!(
+            "  {} Project Status",
+            "cargo pmcp status".bright_white().bold()
+        );
+        println!("  {}", "─".repeat(40).dimmed());
+    }
+
+    print_section("Local servers", &report.local_servers, |servers| {
+        for server in servers {
+            let state = if server.running {
+                format!("{} running", "●".green())
+            } else {
+                format!("{} stopped", "○".dimmed())
+            };
+            println!("    {:<24} :{:<6} {}", server.name, server.port, state);
+        }
+    });
+
+    print_section("Deployments", &report.deployments, |deployments| {
+        for deployment in deployments {
+            match (&deployment.url, &deployment.error) {
+                (Some(url), _) => println!("    {:<24} {}", deployment.target, url.cyan()),
+                (None, Some(error)) => {
+                    println!("    {:<24} {} {}", deployment.target, "✗".red(), error)
+                },
+                (None, None) => println!("    {:<24} {}", deployment.target, "no URL".dimmed()),
+            }
+        }
+    });
+
+    print_section("Scheduled tests", &report.scheduled_tests, |tests| {
+        let mark = if tests.failing.is_empty() {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "    {} {} scenario(s) for {}: {} passed, {} failing, {} never run",
+            mark,
+            tests.scenarios,
+            tests.server_id,
+            tests.passed,
+            tests.failing.len(),
+            tests.never_run
+        );
+        for name in &tests.failing {
+            println!("      {} {}", "✗".red(), name);
+        }
+        if let Some(last_run) = &tests.last_run {
+            println!("      last run {}", last_run.dimmed());
+        }
+    });
+
+    print_section("Secret drift", &report.secret_drift, |secrets| {
+        if secrets.drifted.is_empty() {
+            println!(
+                "    {} {} referenced secret(s) in sync with {}",
+                "✓".green(),
+                secrets.referenced,
+                secrets.target
+            );
+            return;
+        }
+        println!(
+            "    {} {} of {} secret(s) drifted from {} (run: cargo pmcp secret sync)",
+            "!".yellow(),
+            secrets.drifted.len(),
+            secrets.referenced,
+            secrets.target
+        );
+        for entry in &secrets.drifted {
+            println!("      {:<8} {}", entry.status.label(), entry.name);
+        }
+    });
+
+    print_section("Schema vs deployment", &report.schema, |schema| {
+        if schema.not_deployed.is_empty() && schema.only_deployed.is_empty() {
+            println!(
+                "    {} {} matches {}",
+                "✓".green(),
+                schema.schema,
+                schema.endpoint
+            );
+            return;
+        }
+        println!(
+            "    {} {} differs from {}",
+            "!".yellow(),
+            schema.schema,
+            schema.endpoint
+        );
+        for name in &schema.not_deployed {
+            println!("      {} {} (not deployed)", "-".red(), name);
+        }
+        for name in &schema.only_deployed {
+            println!("      {} {} (only in deployment)", "+".green(), name);
+        }
+    });
+
+    println!();
+}
+
+fn print_section<T>(title: &str, section: &Section<T>, print: impl FnOnce(&T)) {
+    println!();
+    println!("  {}", title.bright_white().bold());
+    match section {
+        Section::Ok(value) => print(value),
+        Section::Skipped(reason) => println!("    {}", format!("skipped: {}", reason).dimmed()),
+        Section::Failed(message) => println!("    {} {}", "✗".red(), message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(
+        name: &str,
+        enabled: bool,
+        status: Option<&str>,
+        at: Option<&str>,
+    ) -> graphql::ScenarioInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": name,
+            "name": name,
+            "source": "generated",
+            "enabled": enabled,
+            "version": 1,
+            "lastExecutionStatus": status,
+            "lastExecutedAt": at,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_summarize_scenarios() {
+        let scenarios = vec![
+            scenario("smoke", true, Some("PASSED"), Some("2026-03-02T06:00:00Z")),
+            scenario("login", true, Some("FAILED"), Some("2026-03-02T07:00:00Z")),
+            scenario("new", true, None, None),
+            scenario("old", false, Some("FAILED"), Some("2026-03-03T00:00:00Z")),
+        ];
+
+        let summary = summarize_scenarios("chess", &scenarios);
+
+        assert_eq!(summary.scenarios, 3);
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.failing, vec!["login".to_string()]);
+        assert_eq!(summary.never_run, 1);
+        assert_eq!(summary.last_run.as_deref(), Some("2026-03-02T07:00:00Z"));
+    }
+
+    #[test]
+    fn test_section_serialization() {
+        let skipped: Section<Vec<LocalServer>> = Section::Skipped("nothing".to_string());
+        assert_eq!(
+            serde_json::to_value(&skipped).unwrap(),
+            serde_json::json!({ "skipped": "nothing" })
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Test impact analysis for `cargo pmcp test run --changed-since <ref>`.
+//!
+//! Maps files changed since a git ref to the tools they implement and
+//! selects only the scenarios that call those tools. Tools are found by
+//! scanning Rust sources for handler registrations:
+//!
+//! - `.tool("name", Handler)` and the other `tool*` builder methods, which
+//!   map the tool to the registering file and to the file implementing
+//!   `ToolHandler` for `Handler`
+//! - `TypedTool::new("name", ...)` and the other `*Tool::new("name", ...)`
+//!   constructors
+//! - `#[mcp_tool]` / `#[pmcp::tool]` functions, named by `name = "..."` or
+//!   the function name
+//!
+//! The analysis is conservative: a changed Rust file that defines no tools
+//! (shared helpers, `lib.rs`), or a change to `Cargo.toml`, `Cargo.lock`, or
+//! `build.rs`, selects every scenario.
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files whose changes can affect every tool.
+const BUILD_FILES: &[&str] = &["Cargo.toml", "Cargo.lock", "build.rs"];
+
+/// Directories whose Rust files never change server behavior.
+const NON_SERVER_DIRS: &[&str] = &["tests", "examples", "benches", "target"];
+
+/// Which scenarios a set of changes affects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Impact {
+    /// Run every scenario, with the reason.
+    All(String),
+    /// Run scenarios calling these tools, plus changed scenario files.
+    Tools {
+        /// Tools implemented by the changed files.
+        tools: BTreeSet<String>,
+        /// Changed files (absolute), used to select edited scenarios.
+        changed: Vec<PathBuf>,
+    },
+}
+
+impl Impact {
+    /// Returns `true` if the scenario at `path`, calling `scenario_tools`,
+    /// should run.
+    pub fn selects(&self, path: &Path, scenario_tools: &BTreeSet<String>) -> bool {
+        match self {
+            Impact::All(_) => true,
+            Impact::Tools { tools, changed } => {
+                changed.iter().any(|c| same_file(c, path))
+                    || scenario_tools.iter().any(|t| tools.contains(t))
+            },
+        }
+    }
+}
+
+/// Index from Rust source file to the tools it implements or registers.
+#[derive(Debug, Default)]
+pub struct ToolIndex {
+    tools_by_file: HashMap<PathBuf, BTreeSet<String>>,
+}
+
+impl ToolIndex {
+    /// Scan every Rust file under `root`, skipping `target/` and hidden dirs.
+    pub fn scan(root: &Path) -> Result<Self> {
+        let mut sources = Vec::new();
+        for entry in walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                e.depth() == 0 || !(name == "target" || name.starts_with('.'))
+            })
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("rs") {
+                let source = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                sources.push((absolute(path), source));
+            }
+        }
+        Ok(Self::from_sources(&sources))
+    }
+
+    /// Build the index from `(path, source)` pairs.
+    pub fn from_sources(sources: &[(PathBuf, String)]) -> Self {
+        let registration = Regex::new(
+            r#"\.tool(?:_arc|_typed\w*|_with_widget)?\s*\(\s*"([^"]+)"\s*,\s*(?:Arc::new\(\s*)?([A-Za-z_][\w:]*)"#,
+        )
+        .unwrap();
+        let constructor = Regex::new(r#"\b\w*Tool\w*::new\w*\(\s*"([^"]+)""#).unwrap();
+        let attribute = Regex::new(
+            r#"#\[(?:pmcp::)?(?:mcp_tool|tool)(?:\(([^)]*)\))?\]\s*(?:#\[[^\]]*\]\s*|///[^\n]*\n\s*)*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(\w+)"#,
+        )
+        .unwrap();
+        let name_arg = Regex::new(r#"name\s*=\s*"([^"]+)""#).unwrap();
+        let handler_impl =
+            Regex::new(r"impl(?:<[^>]*>)?\s+(?:[\w:]+::)?ToolHandler\s+for\s+(\w+)").unwrap();
+
+        let mut index = Self::default();
+        let mut handler_files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for (path, source) in sources {
+            for caps in handler_impl.captures_iter(source) {
+                handler_files
+                    .entry(caps[1].to_string())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+
+        for (path, source) in sources {
+            for caps in registration.captures_iter(source) {
+                let tool = caps[1].to_string();
+                index.add(path, &tool);
+                let handler = handler_type(&caps[2]);
+                for file in handler_files.get(handler).into_iter().flatten() {
+                    index.add(file, &tool);
+                }
+            }
+            for caps in constructor.captures_iter(source) {
+                index.add(path, &caps[1]);
+            }
+            for caps in attribute.captures_iter(source) {
+                let explicit = caps
+                    .get(1)
+                    .and_then(|args| name_arg.captures(args.as_str()))
+                    .map(|c| c[1].to_string());
+                index.add(path, &explicit.unwrap_or_else(|| caps[2].to_string()));
+            }
+        }
+        index
+    }
+
+    fn add(&mut self, path: &Path, tool: &str) {
+        self.tools_by_file
+            .entry(path.to_path_buf())
+            .or_default()
+            .insert(tool.to_string());
+    }
+
+    /// Tools implemented or registered in `path`, if it is an indexed file.
+    pub fn tools_for(&self, path: &Path) -> Option<&BTreeSet<String>> {
+        self.tools_by_file
+            .get(path)
+            .or_else(|| self.tools_by_file.get(&absolute(path)))
+    }
+}
+
+/// Pick the handler type name out of a registration argument such as
+/// `AddTool`, `AddTool::new`, or `tools::AddTool`.
+fn handler_type(expr: &str) -> &str {
+    let mut segments: Vec<&str> = expr.split("::").collect();
+    while segments.len() > 1
+        && segments
+            .last()
+            .is_some_and(|s| s.chars().next().is_some_and(char::is_lowercase))
+    {
+        segments.pop();
+    }
+    segments.last().copied().unwrap_or(expr)
+}
+
+/// Work out which scenarios `changed` files affect for the project at `root`.
+pub fn analyze(changed: &[PathBuf], index: &ToolIndex, root: &Path) -> Impact {
+    let mut tools = BTreeSet::new();
+    for path in changed {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if BUILD_FILES.contains(&name) {
+            return Impact::All(format!("{} changed", path.display()));
+        }
+        if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            continue;
+        }
+        if let Some(found) = index.tools_for(path) {
+            tools.extend(found.iter().cloned());
+        } else if !is_non_server(path, root) {
+            return Impact::All(format!("{} changed and defines no tools", path.display()));
+        }
+    }
+    Impact::Tools {
+        tools,
+        changed: changed.to_vec(),
+    }
+}
+
+/// Analyze changes since `git_ref` for the project rooted at `root`.
+pub fn since(git_ref: &str, root: &Path) -> Result<Impact> {
+    let changed = changed_files(git_ref)?;
+    let index = ToolIndex::scan(root)?;
+    Ok(analyze(&changed, &index, &absolute(root)))
+}
+
+/// Tools a scenario calls across its setup, steps, and cleanup.
+pub fn scenario_tools(scenario: &mcp_tester::TestScenario) -> BTreeSet<String> {
+    scenario
+        .setup
+        .iter()
+        .chain(&scenario.steps)
+        .chain(&scenario.cleanup)
+        .filter_map(|step| match &step.operation {
+            mcp_tester::scenario::Operation::ToolCall { tool, .. } => Some(tool.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Files changed since `git_ref`, including uncommitted and untracked files.
+///
+/// Returned paths are absolute.
+pub fn changed_files(git_ref: &str) -> Result<Vec<PathBuf>> {
+    let toplevel = git(&["rev-parse", "--show-toplevel"])?;
+    let toplevel = PathBuf::from(toplevel.trim());
+
+    let mut files: BTreeSet<PathBuf> = BTreeSet::new();
+    let diff = git(&["diff", "--name-only", git_ref, "--"])
+        .with_context(|| format!("Failed to diff against '{}'", git_ref))?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+    for line in diff.lines().chain(untracked.lines()) {
+        let line = line.trim();
+        if !line.is_empty() {
+            files.insert(toplevel.join(line));
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns `true` for files under `root`'s tests, examples, or benches.
+fn is_non_server(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root).is_ok_and(|relative| {
+        relative
+            .components()
+            .any(|c| NON_SERVER_DIRS.contains(&c.as_os_str().to_str().unwrap_or("")))
+    })
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir().unwrap_or_default().join(path)
+        }
+    })
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || absolute(a) == absolute(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> ToolIndex {
+        ToolIndex::from_sources(&[
+            (
+                PathBuf::from("/p/src/main.rs"),
+                r#"
+                let server = Server::builder()
+                    .tool("add", AddTool)
+                    .tool_typed("search", tools::Search::new)
+                    .tool("echo", SimpleTool::new("echo", |args, _| Ok(args)))
+                    .build()?;
+                "#
+                .to_string(),
+            ),
+            (
+                PathBuf::from("/p/src/tools/add.rs"),
+                "pub struct AddTool;\n#[async_trait]\nimpl ToolHandler for AddTool {}".to_string(),
+            ),
+            (
+                PathBuf::from("/p/src/tools/weather.rs"),
+                r#"
+                /// Get the weather
+                #[mcp_tool(name = "get_weather")]
+                async fn weather(args: WeatherArgs) -> Result<Value> { todo!() }
+
+                #[pmcp::tool]
+                pub async fn forecast(args: ForecastArgs) -> Result<Value> { todo!() }
+                "#
+                .to_string(),
+            ),
+            (
+                PathBuf::from("/p/src/util.rs"),
+                "pub fn helper() {}".to_string(),
+            ),
+        ])
+    }
+
+    fn set(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_index_maps_handlers_and_registrations() {
+        let index = index();
+        assert_eq!(
+            index.tools_for(Path::new("/p/src/tools/add.rs")),
+            Some(&set(&["add"]))
+        );
+        assert_eq!(
+            index.tools_for(Path::new("/p/src/main.rs")),
+            Some(&set(&["add", "echo", "search"]))
+        );
+        assert_eq!(
+            index.tools_for(Path::new("/p/src/tools/weather.rs")),
+            Some(&set(&["forecast", "get_weather"]))
+        );
+        assert_eq!(index.tools_for(Path::new("/p/src/util.rs")), None);
+    }
+
+    #[test]
+    fn test_handler_type() {
+        assert_eq!(handler_type("AddTool"), "AddTool");
+        assert_eq!(handler_type("tools::Search::new"), "Search");
+        assert_eq!(handler_type("AddTool::default"), "AddTool");
+    }
+
+    #[test]
+    fn test_analyze_selects_changed_tools() {
+        let changed = vec![
+            PathBuf::from("/p/src/tools/add.rs"),
+            PathBuf::from("/p/README.md"),
+            PathBuf::from("/p/tests/integration.rs"),
+        ];
+        let impact = analyze(&changed, &index(), Path::new("/p"));
+        let Impact::Tools { tools, .. } = &impact else {
+            panic!("expected tool selection, got {:?}", impact);
+        };
+        assert_eq!(tools, &set(&["add"]));
+
+        assert!(impact.selects(Path::new("/p/scenarios/add.yaml"), &set(&["add", "echo"])));
+        assert!(!impact.selects(
+            Path::new("/p/scenarios/weather.yaml"),
+            &set(&["get_weather"])
+        ));
+    }
+
+    #[test]
+    fn test_analyze_selects_changed_scenarios() {
+        let changed = vec![PathBuf::from("/p/scenarios/weather.yaml")];
+        let impact = analyze(&changed, &index(), Path::new("/p"));
+        assert!(impact.selects(
+            Path::new("/p/scenarios/weather.yaml"),
+            &set(&["get_weather"])
+        ));
+        assert!(!impact.selects(Path::new("/p/scenarios/add.yaml"), &set(&["add"])));
+    }
+
+    #[test]
+    fn test_analyze_falls_back_to_all() {
+        let index = index();
+        assert!(matches!(
+            analyze(&[PathBuf::from("/p/src/util.rs")], &index, Path::new("/p")),
+            Impact::All(_)
+        ));
+        assert!(matches!(
+            analyze(&[PathBuf::from("/p/Cargo.lock")], &index, Path::new("/p")),
+            Impact::All(_)
+        ));
+    }
+}
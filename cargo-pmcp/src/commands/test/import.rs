@@ -0,0 +1,76 @@
+//! Import test scenarios from Postman/Insomnia collections
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use mcp_tester::{import_collection, CollectionFormat};
+use std::path::PathBuf;
+
+use crate::commands::GlobalFlags;
+
+/// Convert an HTTP request collection into an mcp-tester scenario file
+pub fn execute(
+    from: String,
+    collection: PathBuf,
+    output: Option<PathBuf>,
+    global_flags: &GlobalFlags,
+) -> Result<()> {
+    let format: CollectionFormat = from.parse()?;
+
+    if global_flags.should_output() {
+        println!(
+            "\n{}",
+            format!("Importing {:?} collection", format)
+                .bright_cyan()
+                .bold()
+        );
+        println!("{}", "─────────────────────────────────────".bright_cyan());
+    }
+
+    let content = std::fs::read_to_string(&collection)
+        .with_context(|| format!("Failed to read collection {}", collection.display()))?;
+    let result = import_collection(&content, format)?;
+
+    // Default: scenarios/<collection file stem>.yaml
+    let output_path = output.unwrap_or_else(|| {
+        let stem = collection
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("imported");
+        PathBuf::from("scenarios").join(format!("{}.yaml", stem))
+    });
+
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    result.scenario.to_file(&output_path)?;
+
+    if global_flags.should_output() {
+        for skipped in &result.skipped {
+            println!(
+                "  {} Skipped '{}': {}",
+                "⚠".yellow(),
+                skipped.name,
+                skipped.reason
+            );
+        }
+        println!(
+            "\n{} Imported {} step(s) from '{}' to {}",
+            "✓".green().bold(),
+            result.scenario.steps.len(),
+            result.scenario.name,
+            output_path.display()
+        );
+        println!();
+        println!("Next steps:");
+        println!("  1. Review the generated scenario and add assertions");
+        println!(
+            "  2. Run: cargo pmcp test run <server-url> --scenarios {}",
+            output_path.display()
+        );
+    }
+
+    Ok(())
+}
@@ -2,18 +2,26 @@
 //!
 //! This module provides commands for testing MCP servers both locally and remotely:
 //! - `check`: Quick sanity check of MCP server connectivity and compliance
-//! - `run`: Run test scenarios against local or deployed servers
+//! - `run`: Run test scenarios against local or deployed servers, optionally
+//!   only those affected by changes since a git ref
 //! - `generate`: Generate test scenarios from server capabilities
+//! - `import`: Convert Postman/Insomnia collections into test scenarios
+//! - `new`: Author a scenario interactively against a running server
 //! - `upload`: Upload scenarios to pmcp.run for scheduled testing
 //! - `download`: Download scenarios from pmcp.run
 //! - `list`: List scenarios on pmcp.run
+//! - `results`: Show scheduled test results and trends from pmcp.run
 
 mod apps;
 mod check;
 mod conformance;
 mod download;
 mod generate;
+mod impact;
+mod import;
 mod list;
+mod new;
+mod results;
 mod run;
 mod upload;
 
@@ -133,6 +141,11 @@ pub enum TestCommand {
         #[arg(long)]
         transport: Option<String>,
 
+        /// Only run scenarios calling tools changed since this git ref
+        /// (e.g. main, HEAD~1). Runs everything if shared code changed.
+        #[arg(long, value_name = "GIT_REF")]
+        changed_since: Option<String>,
+
         #[command(flatten)]
         auth_flags: AuthFlags,
     },
@@ -175,6 +188,72 @@ pub enum TestCommand {
         auth_flags: AuthFlags,
     },
 
+    /// Import test scenarios from an HTTP request collection
+    ///
+    /// Converts Postman (v2.x) or Insomnia (v4 export) collections that POST raw
+    /// JSON-RPC bodies to an MCP endpoint into a test scenario. Each JSON-RPC
+    /// message becomes a step; non-JSON-RPC requests are skipped and listed.
+    Import {
+        /// Collection format: postman or insomnia
+        #[arg(long, default_value = "postman")]
+        from: String,
+
+        /// Path to the exported collection JSON file
+        collection: PathBuf,
+
+        /// Output file path (.yaml or .json; default: scenarios/<collection>.yaml)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Author a new test scenario
+    ///
+    /// With --interactive, connects to a running server, lets you pick tools,
+    /// fill in their arguments from the input schema, preview the live
+    /// responses and choose assertions from the observed output, then writes
+    /// the scenario YAML. Without it, writes a skeleton scenario.
+    New {
+        /// MCP server URL or --server for local testing
+        #[command(flatten)]
+        server_flags: ServerFlags,
+
+        /// Port to connect to (default: 3000)
+        #[arg(long, default_value = "3000")]
+        port: u16,
+
+        /// Build the scenario step by step against the live server
+        #[arg(long, short)]
+        interactive: bool,
+
+        /// Scenario name (prompted for in interactive mode)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Output file path (default: scenarios/[<server>/]<name>.yaml)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Transport type: http (SSE streaming), jsonrpc (simple POST), or stdio
+        /// Auto-detected by default based on URL patterns
+        #[arg(long)]
+        transport: Option<String>,
+
+        /// Connection timeout in seconds
+        #[arg(long, default_value = "30")]
+        timeout: u64,
+
+        /// Anonymize recorded arguments and expected values before writing
+        ///
+        /// Names, email addresses and identifying numbers are replaced with
+        /// stable fake values so the scenario can be shared; it then no
+        /// longer matches the live server's data.
+        #[arg(long)]
+        anonymize: bool,
+
+        #[command(flatten)]
+        auth_flags: AuthFlags,
+    },
+
     /// Upload test scenarios to pmcp.run
     ///
     /// Upload local scenario files to pmcp.run for scheduled testing
@@ -226,6 +305,24 @@ pub enum TestCommand {
         #[arg(long)]
         all: bool,
     },
+
+    /// Show scheduled test results from pmcp.run
+    ///
+    /// Fetch recent scheduled runs for every scenario of a server and print
+    /// pass/fail trends and latency percentiles (p50/p95/p99) per scenario.
+    Results {
+        /// Server name (deployment ID) on pmcp.run
+        #[arg(long, alias = "server-id")]
+        server: String,
+
+        /// Number of most recent runs to include per scenario
+        #[arg(long, default_value = "20")]
+        last: usize,
+
+        /// Also export the fetched runs to a CSV file
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
 }
 
 impl TestCommand {
@@ -294,12 +391,14 @@ impl TestCommand {
                 port,
                 scenarios,
                 transport,
+                changed_since,
                 auth_flags,
             } => run::execute(
                 server_flags,
                 port,
                 scenarios,
                 transport,
+                changed_since.as_deref(),
                 &auth_flags,
                 global_flags,
             ),
@@ -325,6 +424,38 @@ impl TestCommand {
                 global_flags,
             ),
 
+            TestCommand::Import {
+                from,
+                collection,
+                output,
+            } => import::execute(from, collection, output, global_flags),
+
+            TestCommand::New {
+                server_flags,
+                port,
+                interactive,
+                name,
+                output,
+                transport,
+                timeout,
+                anonymize,
+                auth_flags,
+            } => {
+                let runtime = tokio::runtime::Runtime::new()?;
+                runtime.block_on(new::execute(
+                    server_flags,
+                    port,
+                    interactive,
+                    name,
+                    output,
+                    transport,
+                    timeout,
+                    anonymize,
+                    &auth_flags,
+                    global_flags,
+                ))
+            },
+
             TestCommand::Upload {
                 server,
                 paths,
@@ -354,6 +485,11 @@ impl TestCommand {
                 let runtime = tokio::runtime::Runtime::new()?;
                 runtime.block_on(list::execute(server, all, global_flags))
             },
+
+            TestCommand::Results { server, last, csv } => {
+                let runtime = tokio::runtime::Runtime::new()?;
+                runtime.block_on(results::execute(server, last, csv, global_flags))
+            },
         }
     }
 }
@@ -0,0 +1,788 @@
+//! Author test scenarios
+//!
+//! With `--interactive`, connects to a running server and builds a scenario
+//! one tool call at a time: pick a tool, fill in its arguments from the input
+//! schema, preview the live response, and keep the assertions that match what
+//! came back. Without it, writes a skeleton scenario to edit by hand.
+//!
+//! `--anonymize` replaces names, email addresses and identifying numbers in
+//! the recorded arguments and expected values with stable fake ones, so a
+//! scenario built against real customer data can be shared.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use mcp_tester::scenario::{Assertion, Comparison, Operation, TestStep};
+use mcp_tester::{ScenarioExecutor, ServerTester, TestScenario, TestStatus};
+use pmcp::data::anonymize::Anonymizer;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::commands::auth;
+use crate::commands::flags::{AuthFlags, ServerFlags};
+use crate::commands::GlobalFlags;
+
+/// Longest text snippet offered as a `contains` assertion
+const MAX_SNIPPET_LEN: usize = 40;
+
+/// Create a new test scenario
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    server_flags: ServerFlags,
+    port: u16,
+    interactive: bool,
+    name: Option<String>,
+    output: Option<PathBuf>,
+    transport: Option<String>,
+    timeout: u64,
+    anonymize: bool,
+    auth_flags: &AuthFlags,
+    global_flags: &GlobalFlags,
+) -> Result<()> {
+    if !interactive {
+        let name = name.unwrap_or_else(|| "new_scenario".to_string());
+        let output_path = output_path(output, server_flags.server.as_deref(), &name);
+        write_scenario(&skeleton(&name), &output_path)?;
+        if global_flags.should_output() {
+            println!(
+                "{} Wrote skeleton scenario to {}",
+                "✓".green(),
+                output_path.display()
+            );
+            println!("  Run with --interactive to build steps against a live server");
+        }
+        return Ok(());
+    }
+
+    let (url, server) = server_flags.resolve_url(port)?;
+
+    println!();
+    println!("{}", "New test scenario".bright_cyan().bold());
+    println!("{}", "─────────────────────────────────────".bright_cyan());
+    println!("  {} Connecting to {}...", "→".blue(), url);
+
+    let auth_method = auth_flags.resolve();
+    let middleware = auth::resolve_auth_middleware(&url, &auth_method).await?;
+    let mut tester = ServerTester::new(
+        &url,
+        Duration::from_secs(timeout),
+        false, // insecure
+        None,  // api_key -- auth handled via middleware
+        transport.as_deref(),
+        middleware,
+    )
+    .context("Failed to create server tester")?;
+
+    let init = tester.test_initialize().await;
+    if init.status != TestStatus::Passed {
+        anyhow::bail!(
+            "Failed to initialize server: {}",
+            init.error.unwrap_or_default()
+        );
+    }
+    let tools = tester.list_tools().await?.tools;
+    if tools.is_empty() {
+        anyhow::bail!("Server at {} has no tools to test", url);
+    }
+
+    let default_name = tester
+        .get_server_name()
+        .map(|server_name| format!("{} scenario", server_name))
+        .unwrap_or_else(|| "new_scenario".to_string());
+    let name = match name {
+        Some(name) => name,
+        None => ask_with_default("Scenario name", &default_name)?,
+    };
+
+    println!();
+    println!("{}", "Tools:".bright_white().bold());
+    for (idx, tool) in tools.iter().enumerate() {
+        println!(
+            "  {:>2}. {} {}",
+            idx + 1,
+            tool.name.bright_white(),
+            tool.description
+                .as_deref()
+                .unwrap_or_default()
+                .bright_black()
+        );
+    }
+
+    let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+    let mut steps = Vec::new();
+    loop {
+        println!();
+        let input = ask("Tool to call (number, name or prefix; empty to finish)")?;
+        if input.is_empty() {
+            break;
+        }
+        let tool = match complete(&input, &tool_names) {
+            Ok(tool_name) => &tools[tool_names.iter().position(|n| *n == tool_name).unwrap()],
+            Err(matches) => {
+                report_no_completion(&input, &matches);
+                continue;
+            },
+        };
+
+        let arguments = fill_arguments(&tool.input_schema)?;
+        let mut step = TestStep {
+            name: format!("Call {}", tool.name),
+            operation: Operation::ToolCall {
+                tool: tool.name.clone(),
+                arguments,
+            },
+            timeout: None,
+            continue_on_failure: false,
+            store_result: None,
+            assertions: Vec::new(),
+        };
+
+        println!("  {} Calling {}...", "→".blue(), tool.name);
+        let response = preview(&mut tester, &step).await?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response)
+                .unwrap_or_default()
+                .lines()
+                .map(|line| format!("    {}", line))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .bright_black()
+        );
+
+        let suggestions = suggest_assertions(&response);
+        println!("  {}", "Suggested assertions:".bright_white());
+        for (idx, assertion) in suggestions.iter().enumerate() {
+            println!("    {:>2}. {}", idx + 1, describe_assertion(assertion));
+        }
+        step.assertions = loop {
+            let input = ask_with_default("Keep (e.g. 1,3; all; none)", "all")?;
+            match select(&input, &suggestions) {
+                Some(selected) => break selected,
+                None => println!("    {} Not a valid selection: {}", "✗".red(), input),
+            }
+        };
+        step.name = ask_with_default("Step name", &step.name)?;
+        steps.push(step);
+    }
+
+    if steps.is_empty() {
+        println!("{} No steps added, nothing written", "○".yellow());
+        return Ok(());
+    }
+
+    let mut scenario = skeleton(&name);
+    scenario.steps = steps;
+    if anonymize {
+        let anonymizer = Anonymizer::new();
+        for step in &mut scenario.steps {
+            anonymize_step(&anonymizer, step);
+        }
+        println!(
+            "  {} Anonymized recorded arguments and expected values",
+            "→".blue()
+        );
+    }
+    let output_path = output_path(output, server.as_deref(), &name);
+    write_scenario(&scenario, &output_path)?;
+
+    println!();
+    println!(
+        "{} Wrote {} step(s) to {}",
+        "✓".green().bold(),
+        scenario.steps.len(),
+        output_path.display()
+    );
+    println!(
+        "  Run with: cargo pmcp test run {} --scenarios {}",
+        url,
+        output_path
+            .parent()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// A scenario with a single `list_tools` step
+fn skeleton(name: &str) -> TestScenario {
+    TestScenario {
+        name: name.to_string(),
+        description: None,
+        timeout: 60,
+        stop_on_failure: true,
+        variables: HashMap::new(),
+        setup: Vec::new(),
+        steps: vec![TestStep {
+            name: "List tools".to_string(),
+            operation: Operation::ListTools,
+            timeout: None,
+            continue_on_failure: false,
+            store_result: None,
+            assertions: vec![
+                Assertion::Success,
+                Assertion::Exists {
+                    path: "tools".to_string(),
+                },
+            ],
+        }],
+        cleanup: Vec::new(),
+    }
+}
+
+/// Default: scenarios/[<server>/]<name>.yaml
+fn output_path(output: Option<PathBuf>, server: Option<&str>, name: &str) -> PathBuf {
+    output.unwrap_or_else(|| {
+        let file = format!("{}.yaml", slug(name));
+        match server {
+            Some(server) => PathBuf::from("scenarios").join(server).join(file),
+            None => PathBuf::from("scenarios").join(file),
+        }
+    })
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('_').to_string();
+    if slug.is_empty() {
+        "scenario".to_string()
+    } else {
+        slug
+    }
+}
+
+fn write_scenario(scenario: &TestScenario, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    scenario.to_file(path)
+}
+
+/// Run `step` on its own and return the response assertions are evaluated against
+async fn preview(tester: &mut ServerTester, step: &TestStep) -> Result<Value> {
+    let mut scenario = skeleton("preview");
+    scenario.steps = vec![step.clone()];
+    let result = ScenarioExecutor::new(tester, false)
+        .execute(scenario)
+        .await?;
+    Ok(result
+        .step_results
+        .into_iter()
+        .next()
+        .and_then(|step| step.response)
+        .unwrap_or(Value::Null))
+}
+
+/// Prompt for each property of a tool's input schema, required ones first
+fn fill_arguments(schema: &Value) -> Result<Value> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Ok(Value::Object(Map::new()));
+    };
+    let mut names: Vec<&String> = properties.keys().collect();
+    names.sort_by_key(|name| !required.contains(&name.as_str()));
+
+    let mut arguments = Map::new();
+    for name in names {
+        let property = &properties[name.as_str()];
+        let is_required = required.contains(&name.as_str());
+        if let Some(description) = property.get("description").and_then(Value::as_str) {
+            println!("    {}", description.bright_black());
+        }
+        let label = format!(
+            "{} ({}{})",
+            name,
+            describe_schema(property),
+            if is_required { ", required" } else { "" }
+        );
+        let default = property.get("default");
+        loop {
+            let input = match default {
+                Some(default) => ask_with_default(&label, &default.to_string())?,
+                None => ask(&label)?,
+            };
+            if input.is_empty() {
+                if is_required {
+                    println!("    {} {} is required", "✗".red(), name);
+                    continue;
+                }
+                break;
+            }
+            match parse_value(property, &input) {
+                Ok(value) => {
+                    arguments.insert(name.clone(), value);
+                    break;
+                },
+                Err(e) => println!("    {} {}", "✗".red(), e),
+            }
+        }
+    }
+    Ok(Value::Object(arguments))
+}
+
+/// The JSON type named by a schema, ignoring `null` in type unions
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(name) => Some(name),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|name| *name != "null"),
+        _ => None,
+    }
+}
+
+/// Short description of a property's type for the prompt label
+fn describe_schema(schema: &Value) -> String {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let values: Vec<String> = values.iter().map(Value::to_string).collect();
+        return format!("one of {}", values.join(", "));
+    }
+    match schema_type(schema) {
+        Some(kind @ ("object" | "array")) => format!("{} as JSON", kind),
+        Some(name) => name.to_string(),
+        None => "any JSON".to_string(),
+    }
+}
+
+/// Convert user input to a JSON value of the type the schema asks for
+///
+/// Enum values complete from a unique prefix.
+fn parse_value(schema: &Value, input: &str) -> Result<Value> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let labels: Vec<String> = values
+            .iter()
+            .map(|value| match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        return match complete(input, &labels) {
+            Ok(label) => Ok(values[labels.iter().position(|l| *l == label).unwrap()].clone()),
+            Err(matches) if matches.is_empty() => {
+                anyhow::bail!("Expected one of {}", labels.join(", "))
+            },
+            Err(matches) => anyhow::bail!("Ambiguous, could be {}", matches.join(", ")),
+        };
+    }
+
+    match schema_type(schema) {
+        Some("string") => Ok(Value::String(input.to_string())),
+        Some("integer") => input
+            .parse::<i64>()
+            .map(Value::from)
+            .with_context(|| format!("'{}' is not an integer", input)),
+        Some("number") => input
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .with_context(|| format!("'{}' is not a number", input)),
+        Some("boolean") => match input.to_lowercase().as_str() {
+            "y" | "yes" | "true" | "1" => Ok(Value::Bool(true)),
+            "n" | "no" | "false" | "0" => Ok(Value::Bool(false)),
+            _ => anyhow::bail!("'{}' is not a boolean (y/n)", input),
+        },
+        Some(kind @ ("object" | "array")) => {
+            let value: Value = serde_json::from_str(input)
+                .with_context(|| format!("'{}' is not valid JSON", input))?;
+            let matches = if kind == "object" {
+                value.is_object()
+            } else {
+                value.is_array()
+            };
+            if !matches {
+                anyhow::bail!("Expected a JSON {}", kind);
+            }
+            Ok(value)
+        },
+        // Untyped: JSON if it parses, a string otherwise
+        _ => Ok(serde_json::from_str(input).unwrap_or_else(|_| Value::String(input.to_string()))),
+    }
+}
+
+/// Resolve `input` to a candidate by 1-based number, exact name or unique prefix
+///
+/// Returns the matching candidates when there isn't exactly one.
+fn complete<'a>(input: &str, candidates: &[&'a str]) -> std::result::Result<&'a str, Vec<&'a str>> {
+    if let Some(candidate) = input
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|idx| candidates.get(idx))
+    {
+        return Ok(candidate);
+    }
+    if let Some(candidate) = candidates.iter().find(|c| **c == input) {
+        return Ok(candidate);
+    }
+    let matches: Vec<&str> = candidates
+        .iter()
+        .filter(|c| c.starts_with(input))
+        .copied()
+        .collect();
+    match matches.as_slice() {
+        [candidate] => Ok(candidate),
+        _ => Err(matches),
+    }
+}
+
+fn report_no_completion(input: &str, matches: &[&str]) {
+    if matches.is_empty() {
+        println!("    {} No tool matches '{}'", "✗".red(), input);
+    } else {
+        println!(
+            "    {} '{}' could be: {}",
+            "?".yellow(),
+            input,
+            matches.join(", ")
+        );
+    }
+}
+
+/// Assertions that hold for an observed tool call response
+///
+/// Responses have the shape the scenario executor asserts against:
+/// `success`, the raw `result` text, `parsed` JSON and `error`.
+fn suggest_assertions(response: &Value) -> Vec<Assertion> {
+    let succeeded = response.get("success").and_then(Value::as_bool) == Some(true);
+    if !succeeded {
+        let mut assertions = vec![Assertion::Failure];
+        if let Some(snippet) = response
+            .get("error")
+            .and_then(Value::as_str)
+            .and_then(snippet)
+        {
+            assertions.push(Assertion::Contains {
+                path: "error".to_string(),
+                value: snippet,
+                ignore_case: false,
+            });
+        }
+        return assertions;
+    }
+
+    let mut assertions = vec![Assertion::Success];
+    match response.get("parsed") {
+        Some(Value::Object(fields)) => {
+            for (field, value) in fields {
+                let path = format!("parsed.{}", field);
+                match value {
+                    Value::Null => {},
+                    Value::Array(items) => assertions.push(Assertion::ArrayLength {
+                        path,
+                        comparison: Comparison::Equals(items.len() as f64),
+                    }),
+                    Value::Object(_) => assertions.push(Assertion::Exists { path }),
+                    scalar => assertions.push(Assertion::Equals {
+                        path,
+                        value: scalar.clone(),
+                        ignore_case: false,
+                    }),
+                }
+            }
+        },
+        Some(Value::Array(items)) => assertions.push(Assertion::ArrayLength {
+            path: "parsed".to_string(),
+            comparison: Comparison::Equals(items.len() as f64),
+        }),
+        _ => {
+            if let Some(snippet) = response
+                .get("result")
+                .and_then(Value::as_str)
+                .and_then(snippet)
+            {
+                assertions.push(Assertion::Contains {
+                    path: "result".to_string(),
+                    value: snippet,
+                    ignore_case: false,
+                });
+            }
+        },
+    }
+    assertions
+}
+
+/// The first line of `text`, cut to at most [`MAX_SNIPPET_LEN`] characters
+fn snippet(text: &str) -> Option<String> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    if line.chars().count() <= MAX_SNIPPET_LEN {
+        return Some(line.to_string());
+    }
+    let cut: String = line.chars().take(MAX_SNIPPET_LEN).collect();
+    // Don't end the snippet mid-word
+    Some(match cut.rfind(' ') {
+        Some(end) => cut[..end].to_string(),
+        None => cut,
+    })
+}
+
+fn describe_assertion(assertion: &Assertion) -> String {
+    match assertion {
+        Assertion::Success => "call succeeds".to_string(),
+        Assertion::Failure => "call fails".to_string(),
+        Assertion::Equals { path, value, .. } => format!("{} == {}", path, value),
+        Assertion::Contains { path, value, .. } => format!("{} contains {:?}", path, value),
+        Assertion::Exists { path } => format!("{} exists", path),
+        Assertion::ArrayLength {
+            path,
+            comparison: Comparison::Equals(len),
+        } => format!("{} has {} item(s)", path, len),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Anonymize the tool arguments and expected values recorded in `step`
+///
+/// Expected values are anonymized by the last field of their path, so
+/// `parsed.email` gets the same fake email as an `email` argument.
+fn anonymize_step(anonymizer: &Anonymizer, step: &mut TestStep) {
+    if let Operation::ToolCall { arguments, .. } = &mut step.operation {
+        *arguments = anonymizer.value(arguments);
+    }
+    for assertion in &mut step.assertions {
+        match assertion {
+            Assertion::Equals { path, value, .. } => {
+                *value = anonymizer.field(last_field(path), value);
+            },
+            Assertion::Contains { path, value, .. } => {
+                let anonymized = anonymizer.field(last_field(path), &Value::String(value.clone()));
+                if let Value::String(anonymized) = anonymized {
+                    *value = anonymized;
+                }
+            },
+            Assertion::JsonPath {
+                expression,
+                expected: Some(expected),
+            } => {
+                *expected = anonymizer.field(last_field(expression), expected);
+            },
+            _ => {},
+        }
+    }
+}
+
+/// The last field name of an assertion path (`parsed.items[0].email` -> `email`)
+fn last_field(path: &str) -> &str {
+    let field = path.rsplit('.').next().unwrap_or(path);
+    field.split('[').next().unwrap_or(field)
+}
+
+/// Parse a selection like `1,3`, `all` or `none` from `suggestions`
+fn select(input: &str, suggestions: &[Assertion]) -> Option<Vec<Assertion>> {
+    match input.trim().to_lowercase().as_str() {
+        "all" => return Some(suggestions.to_vec()),
+        "none" => return Some(Vec::new()),
+        _ => {},
+    }
+    input
+        .split(',')
+        .map(|part| {
+            let idx = part.trim().parse::<usize>().ok()?.checked_sub(1)?;
+            suggestions.get(idx).cloned()
+        })
+        .collect()
+}
+
+/// Read a trimmed line after printing `label`
+fn ask(label: &str) -> Result<String> {
+    print!("  {}: ", label);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        anyhow::bail!("Input closed");
+    }
+    Ok(input.trim().to_string())
+}
+
+/// Like [`ask`], returning `default` for empty input
+fn ask_with_default(label: &str, default: &str) -> Result<String> {
+    let input = ask(&format!("{} [{}]", label, default))?;
+    Ok(if input.is_empty() {
+        default.to_string()
+    } else {
+        input
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_complete() {
+        let tools = ["get_weather", "get_forecast", "search"];
+        assert_eq!(complete("2", &tools), Ok("get_forecast"));
+        assert_eq!(complete("search", &tools), Ok("search"));
+        assert_eq!(complete("get_w", &tools), Ok("get_weather"));
+        assert_eq!(
+            complete("get", &tools),
+            Err(vec!["get_weather", "get_forecast"])
+        );
+        assert_eq!(complete("nope", &tools), Err(vec![]));
+        assert_eq!(complete("9", &tools), Err(vec![]));
+    }
+
+    #[test]
+    fn test_parse_value_follows_schema() {
+        let parse = |schema: Value, input: &str| parse_value(&schema, input);
+        assert_eq!(parse(json!({"type": "integer"}), "42").unwrap(), json!(42));
+        assert!(parse(json!({"type": "integer"}), "4.2").is_err());
+        assert_eq!(parse(json!({"type": "number"}), "4.5").unwrap(), json!(4.5));
+        assert_eq!(parse(json!({"type": "boolean"}), "Y").unwrap(), json!(true));
+        assert_eq!(
+            parse(json!({"type": ["string", "null"]}), "42").unwrap(),
+            json!("42")
+        );
+        assert_eq!(
+            parse(json!({"type": "array"}), "[1, 2]").unwrap(),
+            json!([1, 2])
+        );
+        assert!(parse(json!({"type": "object"}), "[1, 2]").is_err());
+        assert_eq!(parse(json!({}), "{\"a\": 1}").unwrap(), json!({"a": 1}));
+        assert_eq!(parse(json!({}), "plain").unwrap(), json!("plain"));
+
+        let units = json!({"type": "string", "enum": ["celsius", "fahrenheit"]});
+        assert_eq!(parse(units.clone(), "f").unwrap(), json!("fahrenheit"));
+        assert!(parse(units, "kelvin").is_err());
+        assert_eq!(parse(json!({"enum": [1, 2]}), "2").unwrap(), json!(2));
+    }
+
+    #[test]
+    fn test_suggest_assertions_from_parsed_output() {
+        let response = json!({
+            "success": true,
+            "result": "{...}",
+            "parsed": {"city": "Lisbon", "temp": 21, "days": [1, 2, 3], "meta": {}, "note": null},
+            "error": null
+        });
+        let described: Vec<String> = suggest_assertions(&response)
+            .iter()
+            .map(describe_assertion)
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                "call succeeds",
+                "parsed.city == \"Lisbon\"",
+                "parsed.temp == 21",
+                "parsed.days has 3 item(s)",
+                "parsed.meta exists",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggest_assertions_from_text_and_errors() {
+        let text = json!({
+            "success": true,
+            "result": "\nThe forecast for Lisbon is sunny all week long with mild winds",
+            "parsed": null
+        });
+        let described: Vec<String> = suggest_assertions(&text)
+            .iter()
+            .map(describe_assertion)
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                "call succeeds",
+                "result contains \"The forecast for Lisbon is sunny all\"",
+            ]
+        );
+
+        let failed = json!({"success": false, "error": "Unknown city"});
+        let described: Vec<String> = suggest_assertions(&failed)
+            .iter()
+            .map(describe_assertion)
+            .collect();
+        assert_eq!(
+            described,
+            vec!["call fails", "error contains \"Unknown city\""]
+        );
+    }
+
+    #[test]
+    fn test_anonymize_step() {
+        let anonymizer = Anonymizer::new();
+        let mut step = TestStep {
+            name: "Call lookup_customer".to_string(),
+            operation: Operation::ToolCall {
+                tool: "lookup_customer".to_string(),
+                arguments: json!({"email": "jane@acme.com", "limit": 5}),
+            },
+            timeout: None,
+            continue_on_failure: false,
+            store_result: None,
+            assertions: vec![
+                Assertion::Success,
+                Assertion::Equals {
+                    path: "parsed.customers[0].email".to_string(),
+                    value: json!("jane@acme.com"),
+                    ignore_case: false,
+                },
+                Assertion::Contains {
+                    path: "result".to_string(),
+                    value: "Found jane@acme.com".to_string(),
+                    ignore_case: false,
+                },
+            ],
+        };
+
+        anonymize_step(&anonymizer, &mut step);
+        let fake = anonymizer.email("jane@acme.com");
+        match &step.operation {
+            Operation::ToolCall { tool, arguments } => {
+                assert_eq!(tool, "lookup_customer");
+                assert_eq!(arguments, &json!({"email": fake, "limit": 5}));
+            },
+            other => panic!("unexpected operation {:?}", other),
+        }
+        assert_eq!(
+            describe_assertion(&step.assertions[1]),
+            format!("parsed.customers[0].email == \"{}\"", fake)
+        );
+        assert_eq!(
+            describe_assertion(&step.assertions[2]),
+            format!("result contains \"Found {}\"", fake)
+        );
+    }
+
+    #[test]
+    fn test_select_and_output_path() {
+        let suggestions = vec![Assertion::Success, Assertion::Failure];
+        assert_eq!(select("all", &suggestions).map(|s| s.len()), Some(2));
+        assert_eq!(select("none", &suggestions).map(|s| s.len()), Some(0));
+        assert_eq!(select("2", &suggestions).map(|s| s.len()), Some(1));
+        assert!(select("3", &suggestions).is_none());
+        assert!(select("x", &suggestions).is_none());
+
+        assert_eq!(
+            output_path(None, Some("weather"), "Weather Scenario!"),
+            PathBuf::from("scenarios/weather/weather_scenario.yaml")
+        );
+        assert_eq!(
+            output_path(Some(PathBuf::from("a.yaml")), None, "x"),
+            PathBuf::from("a.yaml")
+        );
+    }
+}
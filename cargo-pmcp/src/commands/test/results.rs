@@ -0,0 +1,308 @@
+//! Show scheduled test results from pmcp.run
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::commands::GlobalFlags;
+use crate::deployment::targets::pmcp_run::graphql::ScenarioExecution;
+use crate::deployment::targets::pmcp_run::{auth, graphql};
+
+/// Show pass/fail trends and latency percentiles for scheduled test runs
+pub async fn execute(
+    server_id: String,
+    last: usize,
+    csv: Option<PathBuf>,
+    global_flags: &GlobalFlags,
+) -> Result<()> {
+    if last == 0 {
+        bail!("--last must be at least 1");
+    }
+
+    if global_flags.should_output() {
+        println!("\n{}", "Test results on pmcp.run".bright_cyan().bold());
+        println!("{}", "─────────────────────────────────────".bright_cyan());
+    }
+
+    // Get credentials
+    let credentials = auth::get_credentials().await?;
+
+    if global_flags.should_output() {
+        println!("  {} Server ID: {}", "→".blue(), server_id);
+    }
+
+    let executions = graphql::list_test_executions(&credentials.access_token, &server_id, last)
+        .await
+        .context("Failed to fetch test results")?;
+
+    let trends = summarize(&executions, last);
+
+    println!();
+
+    if trends.is_empty() {
+        println!("{}", "No scheduled test results found".yellow());
+        if global_flags.should_output() {
+            println!();
+            println!("Upload scenarios to schedule them:");
+            println!("  cargo pmcp test upload --server {} scenarios/", server_id);
+        }
+        return Ok(());
+    }
+
+    // Requested output: per-scenario trend table
+    println!(
+        "  {:<36} {:>5} {:>7}  {:<w$}  {:>7} {:>7} {:>7}",
+        "SCENARIO".bright_white().bold(),
+        "RUNS".bright_white().bold(),
+        "PASS".bright_white().bold(),
+        "TREND (oldest → newest)".bright_white().bold(),
+        "P50".bright_white().bold(),
+        "P95".bright_white().bold(),
+        "P99".bright_white().bold(),
+        w = last.max(23)
+    );
+    println!("  {}", "─".repeat(80 + last.saturating_sub(23)));
+
+    for trend in &trends {
+        let pass_rate = format!("{:.0}%", trend.pass_rate() * 100.0);
+        let pass_rate = if trend.passed == trend.runs() {
+            pass_rate.green().to_string()
+        } else if trend.passed == 0 {
+            pass_rate.red().to_string()
+        } else {
+            pass_rate.yellow().to_string()
+        };
+
+        println!(
+            "  {:<36} {:>5} {:>7}  {}{}  {:>7} {:>7} {:>7}",
+            truncate_string(&trend.name, 34),
+            trend.runs(),
+            pass_rate,
+            colored_trend(&trend.statuses),
+            " ".repeat(last.max(23) - trend.statuses.len()),
+            format_ms(trend.p50_ms),
+            format_ms(trend.p95_ms),
+            format_ms(trend.p99_ms)
+        );
+
+        if let Some(error) = &trend.last_error {
+            println!("    {}", truncate_string(error, 76).bright_black());
+        }
+    }
+
+    if let Some(path) = csv {
+        std::fs::write(&path, to_csv(&executions))
+            .with_context(|| format!("Failed to write CSV to {}", path.display()))?;
+        if global_flags.should_output() {
+            println!();
+            println!(
+                "{} Exported {} run(s) to {}",
+                "✓".green().bold(),
+                executions.len(),
+                path.display()
+            );
+        }
+    }
+
+    if global_flags.should_output() {
+        println!();
+        println!(
+            "  {} = passed, {} = failed, {} = error",
+            "✓".green(),
+            "✗".red(),
+            "!".red()
+        );
+    }
+
+    Ok(())
+}
+
+/// Pass/fail history and latency summary for one scenario
+#[derive(Debug)]
+struct ScenarioTrend {
+    name: String,
+    /// Completed run statuses, oldest first
+    statuses: Vec<String>,
+    passed: usize,
+    p50_ms: Option<u64>,
+    p95_ms: Option<u64>,
+    p99_ms: Option<u64>,
+    /// Error message of the newest run, if it did not pass
+    last_error: Option<String>,
+}
+
+impl ScenarioTrend {
+    fn runs(&self) -> usize {
+        self.statuses.len()
+    }
+
+    fn pass_rate(&self) -> f64 {
+        self.passed as f64 / self.runs() as f64
+    }
+}
+
+/// Group completed executions by scenario, keeping the newest `last` of each
+fn summarize(executions: &[ScenarioExecution], last: usize) -> Vec<ScenarioTrend> {
+    let mut by_scenario: BTreeMap<&str, Vec<&ScenarioExecution>> = BTreeMap::new();
+    for execution in executions.iter().filter(|e| e.status != "running") {
+        by_scenario
+            .entry(execution.scenario_id.as_str())
+            .or_default()
+            .push(execution);
+    }
+
+    let mut trends: Vec<ScenarioTrend> = by_scenario
+        .into_values()
+        .map(|mut runs| {
+            // RFC 3339 timestamps sort chronologically as strings
+            runs.sort_by(|a, b| a.executed_at.cmp(&b.executed_at));
+            let runs = &runs[runs.len().saturating_sub(last)..];
+
+            let mut durations: Vec<u64> = runs.iter().filter_map(|r| r.duration_ms).collect();
+            durations.sort_unstable();
+
+            let newest = runs[runs.len() - 1];
+            ScenarioTrend {
+                name: newest.scenario_name.clone(),
+                statuses: runs.iter().map(|r| r.status.clone()).collect(),
+                passed: runs.iter().filter(|r| r.status == "passed").count(),
+                p50_ms: percentile(&durations, 50.0),
+                p95_ms: percentile(&durations, 95.0),
+                p99_ms: percentile(&durations, 99.0),
+                last_error: if newest.status == "passed" {
+                    None
+                } else {
+                    newest.error_message.clone()
+                },
+            }
+        })
+        .collect();
+
+    trends.sort_by(|a, b| a.name.cmp(&b.name));
+    trends
+}
+
+/// Nearest-rank percentile of an ascending-sorted slice
+fn percentile(sorted: &[u64], p: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Render a status history as one colored symbol per run
+fn colored_trend(statuses: &[String]) -> String {
+    statuses
+        .iter()
+        .map(|status| match status.as_str() {
+            "passed" => "✓".green().to_string(),
+            "failed" => "✗".red().to_string(),
+            _ => "!".red().to_string(),
+        })
+        .collect()
+}
+
+fn format_ms(ms: Option<u64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{ms}ms"))
+}
+
+/// Serialize executions as CSV, one row per run
+fn to_csv(executions: &[ScenarioExecution]) -> String {
+    let mut out =
+        String::from("scenario_id,scenario_name,executed_at,status,duration_ms,error_message\n");
+    for e in executions {
+        let fields = [
+            csv_field(&e.scenario_id),
+            csv_field(&e.scenario_name),
+            csv_field(&e.executed_at),
+            csv_field(&e.status),
+            e.duration_ms.map(|ms| ms.to_string()).unwrap_or_default(),
+            csv_field(e.error_message.as_deref().unwrap_or("")),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Truncate string to max length, adding ellipsis if needed
+fn truncate_string(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(max_len - 3).collect();
+        format!("{kept}...")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, at: &str, status: &str, ms: u64) -> ScenarioExecution {
+        ScenarioExecution {
+            scenario_id: id.to_string(),
+            scenario_name: format!("{id} scenario"),
+            status: status.to_string(),
+            executed_at: at.to_string(),
+            duration_ms: Some(ms),
+            error_message: (status != "passed").then(|| "tool returned an error".to_string()),
+        }
+    }
+
+    #[test]
+    fn summarize_keeps_newest_runs_in_order() {
+        let executions = vec![
+            run("a", "2026-01-03T00:00:00Z", "failed", 300),
+            run("a", "2026-01-01T00:00:00Z", "passed", 100),
+            run("a", "2026-01-02T00:00:00Z", "passed", 200),
+            run("b", "2026-01-01T00:00:00Z", "running", 0),
+        ];
+
+        let trends = summarize(&executions, 2);
+        assert_eq!(trends.len(), 1, "running-only scenarios are omitted");
+        let trend = &trends[0];
+        assert_eq!(trend.statuses, vec!["passed", "failed"]);
+        assert_eq!(trend.passed, 1);
+        assert_eq!(trend.p50_ms, Some(200));
+        assert_eq!(trend.p99_ms, Some(300));
+        assert_eq!(trend.last_error.as_deref(), Some("tool returned an error"));
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50.0), Some(50));
+        assert_eq!(percentile(&sorted, 95.0), Some(95));
+        assert_eq!(percentile(&[7], 99.0), Some(7));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_delimiters() {
+        let mut execution = run("a", "2026-01-01T00:00:00Z", "failed", 42);
+        execution.error_message = Some("expected \"ok\", got error".to_string());
+
+        let csv = to_csv(&[execution]);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("scenario_id,scenario_name,executed_at,status,duration_ms,error_message")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("a,a scenario,2026-01-01T00:00:00Z,failed,42,\"expected \"\"ok\"\", got error\"")
+        );
+    }
+}
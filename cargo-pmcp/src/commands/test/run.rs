@@ -3,8 +3,9 @@
 use anyhow::Result;
 use colored::Colorize;
 use mcp_tester::run_scenario_with_transport;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::impact::{self, Impact};
 use crate::commands::flags::{AuthFlags, AuthMethod, ServerFlags};
 use crate::commands::GlobalFlags;
 
@@ -14,6 +15,7 @@ pub fn execute(
     port: u16,
     scenarios: Option<PathBuf>,
     transport: Option<String>,
+    changed_since: Option<&str>,
     auth_flags: &AuthFlags,
     global_flags: &GlobalFlags,
 ) -> Result<()> {
@@ -64,17 +66,31 @@ pub fn execute(
     // Try scenario-based testing if scenarios exist
     let test_result = if scenarios_dir.exists() && scenarios_dir.read_dir()?.next().is_some() {
         // Find YAML scenarios
-        let scenarios: Vec<_> = std::fs::read_dir(&scenarios_dir)?
+        let mut scenarios: Vec<_> = std::fs::read_dir(&scenarios_dir)?
             .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
+            .map(|e| e.path())
+            .filter(|path| {
+                path.extension()
                     .and_then(|s| s.to_str())
                     .map(|s| s == "yaml" || s == "yml")
                     .unwrap_or(false)
             })
             .collect();
 
+        if let Some(git_ref) = changed_since {
+            scenarios = select_changed(scenarios, git_ref, global_flags)?;
+            if scenarios.is_empty() {
+                if global_flags.should_output() {
+                    println!(
+                        "  {} No scenarios affected by changes since {}",
+                        "✓".green(),
+                        git_ref
+                    );
+                }
+                return Ok(());
+            }
+        }
+
         if !scenarios.is_empty() {
             if global_flags.should_output() {
                 println!(
@@ -87,8 +103,7 @@ pub fn execute(
 
             // Run each scenario using library
             let mut all_passed = true;
-            for scenario in scenarios {
-                let scenario_path = scenario.path();
+            for scenario_path in scenarios {
                 if global_flags.should_output() {
                     println!("\n  Testing: {}", scenario_path.display());
                 }
@@ -167,3 +182,52 @@ pub fn execute(
         Err(e) => Err(e),
     }
 }
+
+/// Keep only the scenarios affected by changes since `git_ref`.
+fn select_changed(
+    scenarios: Vec<PathBuf>,
+    git_ref: &str,
+    global_flags: &GlobalFlags,
+) -> Result<Vec<PathBuf>> {
+    let impact = impact::since(git_ref, Path::new("."))?;
+    if let Impact::All(reason) = &impact {
+        if global_flags.should_output() {
+            println!("  {} Running all scenarios: {}", "→".blue(), reason);
+        }
+        return Ok(scenarios);
+    }
+
+    let total = scenarios.len();
+    let selected: Vec<PathBuf> = scenarios
+        .into_iter()
+        .filter(|path| {
+            // Scenarios that fail to parse are kept so the run reports the error
+            mcp_tester::TestScenario::from_file(path)
+                .map(|scenario| impact.selects(path, &impact::scenario_tools(&scenario)))
+                .unwrap_or(true)
+        })
+        .collect();
+
+    if global_flags.should_output() {
+        if let Impact::Tools { tools, .. } = &impact {
+            let tools: Vec<&str> = tools.iter().map(String::as_str).collect();
+            println!(
+                "  {} Changed tools since {}: {}",
+                "→".blue(),
+                git_ref,
+                if tools.is_empty() {
+                    "none".to_string()
+                } else {
+                    tools.join(", ")
+                }
+            );
+        }
+        println!(
+            "  {} Selected {} of {} scenario file(s)",
+            "→".blue(),
+            selected.len(),
+            total
+        );
+    }
+    Ok(selected)
+}
@@ -0,0 +1,355 @@
+//! Widget commands for MCP Apps servers.
+//!
+//! Provides `cargo pmcp widget manifest <URL>`, which connects to a running
+//! server and generates the ChatGPT Apps submission manifest from the
+//! metadata the server reports (server info, tool-to-widget links,
+//! `WidgetMeta`, and auth requirements).
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use pmcp::types::mcp_apps::WidgetMeta;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::flags::AuthFlags;
+use crate::publishing::chatgpt::{self, ServerMetadata, WidgetResource};
+
+/// Widget commands.
+#[derive(Subcommand)]
+pub enum WidgetCommand {
+    /// Generate the ChatGPT Apps submission manifest from a running server
+    ///
+    /// Reads name, description, and icons from the server info, tool to
+    /// widget mappings from tool metadata, widget details from each widget
+    /// resource's WidgetMeta, and auth requirements from the server's OAuth
+    /// protected-resource metadata.
+    #[command(after_long_help = "Examples:
+  cargo pmcp widget manifest http://localhost:3000
+  cargo pmcp widget manifest https://chess.example.com/mcp --output app.json
+  cargo pmcp widget manifest http://localhost:3000 --check")]
+    Manifest {
+        /// URL of the running MCP server
+        url: String,
+
+        /// Output file path
+        #[arg(long, short, default_value = "dist/chatgpt-app.json")]
+        output: PathBuf,
+
+        /// Verify the manifest at --output is up to date without writing (for CI)
+        #[arg(long)]
+        check: bool,
+
+        /// Authentication flags for the target MCP server
+        #[command(flatten)]
+        auth_flags: AuthFlags,
+    },
+}
+
+impl WidgetCommand {
+    /// Execute the widget subcommand.
+    pub fn execute(self, global_flags: &crate::commands::GlobalFlags) -> Result<()> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        match self {
+            WidgetCommand::Manifest {
+                url,
+                output,
+                check,
+                auth_flags,
+            } => runtime.block_on(run_manifest(
+                &url,
+                &output,
+                check,
+                &auth_flags,
+                global_flags,
+            )),
+        }
+    }
+}
+
+/// Generate (or check) the ChatGPT Apps manifest for the server at `url`.
+async fn run_manifest(
+    url: &str,
+    output: &Path,
+    check: bool,
+    auth_flags: &AuthFlags,
+    global_flags: &crate::commands::GlobalFlags,
+) -> Result<()> {
+    if global_flags.should_output() {
+        println!(
+            "\n{}",
+            "Generating ChatGPT Apps manifest".bright_cyan().bold()
+        );
+        println!("{}", "------------------------------------".bright_cyan());
+    }
+
+    let auth_header = super::auth::resolve_auth_header(url, &auth_flags.resolve()).await?;
+    let metadata = fetch_server_metadata(url, auth_header.as_deref()).await?;
+
+    if global_flags.should_output() {
+        println!(
+            "  {} Found {} tool(s), {} widget(s)",
+            "ok".green(),
+            metadata.tools.len(),
+            metadata.widgets.len()
+        );
+        if metadata.server_info.get("icons").is_none() {
+            println!(
+                "  {} Server info has no icons; set them on the server builder",
+                "warn".yellow()
+            );
+        }
+    }
+
+    let manifest = chatgpt::generate_app_manifest(&metadata, url)?;
+
+    if check {
+        let existing = fs::read_to_string(output)
+            .with_context(|| format!("Failed to read {}", output.display()))?;
+        if existing.trim_end() != manifest.trim_end() {
+            bail!(
+                "{} is out of date with the server. Re-run `cargo pmcp widget manifest {}` to update it.",
+                output.display(),
+                url
+            );
+        }
+        if global_flags.should_output() {
+            println!(
+                "\n{} {} is up to date",
+                "ok".green().bold(),
+                output.display()
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(output, format!("{manifest}\n"))
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    if global_flags.should_output() {
+        println!(
+            "\n{} Manifest written to {}",
+            "ok".green().bold(),
+            output.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Collect server info, tools, widget resources, and auth metadata.
+async fn fetch_server_metadata(url: &str, auth_header: Option<&str>) -> Result<ServerMetadata> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+    let mut session = McpSession {
+        client: &client,
+        url,
+        auth_header,
+        session_id: None,
+        next_id: 1,
+    };
+
+    let init = session
+        .request(
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "cargo-pmcp",
+                    "version": env!("CARGO_PKG_VERSION")
+                }
+            }),
+        )
+        .await?;
+    session.notify("notifications/initialized").await;
+
+    let tools: Vec<Value> = session
+        .request("tools/list", json!({}))
+        .await?
+        .get("tools")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let listed: Vec<Value> = match session.request("resources/list", json!({})).await {
+        Ok(result) => result
+            .get("resources")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default(),
+        // Servers without resources still produce a manifest; widgets are
+        // then reported as missing.
+        Err(_) => Vec::new(),
+    };
+
+    let mut widget_uris: Vec<String> = tools.iter().filter_map(chatgpt::tool_widget_uri).collect();
+    widget_uris.sort();
+    widget_uris.dedup();
+
+    let mut widgets = Vec::new();
+    for uri in widget_uris {
+        let Ok(read) = session
+            .request("resources/read", json!({ "uri": uri }))
+            .await
+        else {
+            continue;
+        };
+        let listing = listed
+            .iter()
+            .find(|r| r.get("uri").and_then(Value::as_str) == Some(uri.as_str()));
+        let meta = read
+            .pointer("/contents/0/_meta")
+            .or_else(|| listing.and_then(|r| r.get("_meta")))
+            .and_then(|m| serde_json::from_value::<WidgetMeta>(m.clone()).ok())
+            .unwrap_or_default();
+        widgets.push(WidgetResource {
+            name: listing
+                .and_then(|r| r.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or(&uri)
+                .to_string(),
+            description: listing
+                .and_then(|r| r.get("description"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            uri,
+            meta,
+        });
+    }
+
+    Ok(ServerMetadata {
+        server_info: init.get("serverInfo").cloned().unwrap_or_else(|| json!({})),
+        tools,
+        widgets,
+        protected_resource: fetch_protected_resource(&client, url).await,
+    })
+}
+
+/// Fetch RFC 9728 protected-resource metadata from the server origin.
+///
+/// Returns `None` if the server does not serve it, meaning it is public.
+async fn fetch_protected_resource(client: &reqwest::Client, url: &str) -> Option<Value> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    let well_known = parsed.join("/.well-known/oauth-protected-resource").ok()?;
+    let response = client.get(well_known).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Minimal JSON-RPC session over streamable HTTP that tracks `mcp-session-id`.
+struct McpSession<'a> {
+    client: &'a reqwest::Client,
+    url: &'a str,
+    auth_header: Option<&'a str>,
+    session_id: Option<String>,
+    next_id: u64,
+}
+
+impl McpSession<'_> {
+    fn post(&self, body: &Value) -> reqwest::RequestBuilder {
+        let mut builder = self
+            .client
+            .post(self.url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .json(body);
+        if let Some(auth) = self.auth_header {
+            builder = builder.header("Authorization", auth);
+        }
+        if let Some(session_id) = &self.session_id {
+            builder = builder.header("mcp-session-id", session_id);
+        }
+        builder
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let body = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+        let response = self
+            .post(&body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send {} to {}", method, self.url))?;
+        if let Some(session_id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        if !response.status().is_success() {
+            bail!(
+                "{} failed: {} {}",
+                method,
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+
+        let text = response.text().await?;
+        let message =
+            parse_message(&text).with_context(|| format!("Failed to parse {} response", method))?;
+        if let Some(error) = message.get("error") {
+            bail!(
+                "{} failed: {}",
+                method,
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            );
+        }
+        message
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("Empty {} result from MCP server", method))
+    }
+
+    async fn notify(&self, method: &str) {
+        let _ = self
+            .post(&json!({ "jsonrpc": "2.0", "method": method }))
+            .send()
+            .await;
+    }
+}
+
+/// Parse a JSON-RPC message from a plain JSON or SSE response body.
+fn parse_message(body: &str) -> Result<Value> {
+    let trimmed = body.trim_start();
+    if trimmed.starts_with('{') {
+        return serde_json::from_str(trimmed).map_err(Into::into);
+    }
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .find_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .ok_or_else(|| anyhow!("no JSON-RPC message in response"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message_json_and_sse() {
+        let json_body = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+        assert_eq!(parse_message(json_body).unwrap()["id"], 1);
+
+        let sse_body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{}}\n\n";
+        assert_eq!(parse_message(sse_body).unwrap()["id"], 2);
+
+        assert!(parse_message("event: ping\n\n").is_err());
+    }
+}
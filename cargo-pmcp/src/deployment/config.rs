@@ -23,6 +23,10 @@ pub struct DeployConfig {
     #[serde(default)]
     pub composition: CompositionConfig,
 
+    /// Webhooks notified after a successful deployment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+
     /// Project root directory (not serialized)
     #[serde(skip)]
     pub project_root: PathBuf,
@@ -62,6 +66,33 @@ pub struct CompositionConfig {
     pub description: Option<String>,
 }
 
+/// Webhook notified of deployment events.
+///
+/// Payloads are signed with HMAC-SHA256 when `secret_env` names an
+/// environment variable holding the shared secret.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[webhooks]]
+/// url = "https://hooks.example.com/mcp"
+/// secret_env = "DEPLOY_WEBHOOK_SECRET"
+/// events = ["deploy.completed"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Endpoint URL receiving the JSON payload
+    pub url: String,
+
+    /// Environment variable holding the signing secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_env: Option<String>,
+
+    /// Event names to deliver (all events when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
 fn default_tier() -> String {
     "foundation".to_string()
 }
@@ -545,6 +576,7 @@ impl DeployConfig {
             api_gateway: None,
             assets: AssetsConfig::default(),
             composition: CompositionConfig::default(),
+            webhooks: Vec::new(),
             project_root,
         }
     }
@@ -0,0 +1,191 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Locks older than this are assumed to belong to a crashed deploy
+const STALE_AFTER_HOURS: i64 = 2;
+
+/// Who holds a deployment lock, stored in the local lock file and in remote locks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockInfo {
+    /// Random id of this lock, so a holder only ever releases its own lock
+    pub id: String,
+    /// `user@host` of the machine running the deploy
+    pub holder: String,
+    pub pid: u32,
+    pub target: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+impl LockInfo {
+    pub fn new(target: &str) -> Self {
+        let mut rng = rand::rng();
+        let id = (0..16)
+            .map(|_| format!("{:02x}", rng.random_range(0..=255u8)))
+            .collect();
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        let host = std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| "unknown-host".to_string());
+
+        Self {
+            id,
+            holder: format!("{}@{}", user, host),
+            pid: std::process::id(),
+            target: target.to_string(),
+            acquired_at: Utc::now(),
+        }
+    }
+
+    /// Whether the lock is old enough to have been left behind by a crashed deploy
+    pub fn is_stale(&self) -> bool {
+        Utc::now() - self.acquired_at > Duration::hours(STALE_AFTER_HOURS)
+    }
+
+    /// One-line description for "deploy already in progress" errors
+    pub fn describe(&self) -> String {
+        format!(
+            "{} (pid {}, target {}, since {})",
+            self.holder,
+            self.pid,
+            self.target,
+            self.acquired_at.format("%Y-%m-%d %H:%M:%S UTC")
+        )
+    }
+}
+
+/// Local deployment lock held in `.pmcp/deploy.lock` for the duration of a deploy
+///
+/// The lock file is created atomically, so two deploys started from the same
+/// checkout cannot both proceed. It is removed when the lock is dropped.
+#[derive(Debug)]
+pub struct DeployLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+impl DeployLock {
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".pmcp").join("deploy.lock")
+    }
+
+    /// Take the lock, replacing a stale one or, with `force`, any existing one
+    pub fn acquire(project_root: &Path, target: &str, force: bool) -> Result<Self> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let info = LockInfo::new(target);
+        let contents = serde_json::to_string_pretty(&info)?;
+
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(contents.as_bytes())
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    return Ok(Self { path, info });
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    match Self::read(&path) {
+                        Some(existing) if !force && !existing.is_stale() => {
+                            bail!(
+                                "Another deploy is in progress: {}\n\
+                                 If that deploy is no longer running, retry with --force-unlock",
+                                existing.describe()
+                            );
+                        },
+                        Some(existing) => {
+                            println!("⚠️  Taking over deploy lock from {}", existing.describe());
+                        },
+                        None => {
+                            println!("⚠️  Replacing unreadable deploy lock {}", path.display());
+                        },
+                    }
+                    std::fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                },
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create {}", path.display()))
+                },
+            }
+        }
+
+        bail!(
+            "Another deploy took the lock {} while it was being replaced",
+            path.display()
+        )
+    }
+
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+
+    fn read(path: &Path) -> Option<LockInfo> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+}
+
+impl Drop for DeployLock {
+    fn drop(&mut self) {
+        // Leave the file alone if another deploy forced its way in meanwhile
+        if Self::read(&self.path).is_some_and(|current| current.id == self.info.id) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_acquire_fails_while_held() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = DeployLock::acquire(dir.path(), "pmcp-run", false).unwrap();
+
+        let err = DeployLock::acquire(dir.path(), "pmcp-run", false).unwrap_err();
+        assert!(err.to_string().contains("Another deploy is in progress"));
+
+        drop(lock);
+        assert!(!DeployLock::path(dir.path()).exists());
+        DeployLock::acquire(dir.path(), "pmcp-run", false).unwrap();
+    }
+
+    #[test]
+    fn force_takes_over_and_old_holder_keeps_hands_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = DeployLock::acquire(dir.path(), "aws-lambda", false).unwrap();
+        let new = DeployLock::acquire(dir.path(), "aws-lambda", true).unwrap();
+        assert_ne!(old.info().id, new.info().id);
+
+        drop(old);
+        assert!(DeployLock::path(dir.path()).exists());
+        drop(new);
+        assert!(!DeployLock::path(dir.path()).exists());
+    }
+
+    #[test]
+    fn stale_lock_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut stale = LockInfo::new("pmcp-run");
+        stale.acquired_at = Utc::now() - Duration::hours(STALE_AFTER_HOURS + 1);
+        std::fs::create_dir_all(dir.path().join(".pmcp")).unwrap();
+        std::fs::write(
+            DeployLock::path(dir.path()),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .unwrap();
+
+        let lock = DeployLock::acquire(dir.path(), "pmcp-run", false).unwrap();
+        assert_ne!(lock.info().id, stale.id);
+    }
+}
@@ -1,17 +1,21 @@
 pub mod builder;
 pub mod config;
+pub mod lock;
 pub mod metadata;
 pub mod naming;
 pub mod operations;
 pub mod outputs;
 pub mod registry;
+pub mod state;
 pub mod targets;
 pub mod r#trait;
 
 pub use builder::BinaryBuilder;
 pub use config::DeployConfig;
+pub use lock::DeployLock;
 pub use naming::would_conflict;
 pub use operations::OperationStatus;
 pub use outputs::load_cdk_outputs;
 pub use r#trait::{DeploymentOutputs, SecretsAction};
 pub use registry::TargetRegistry;
+pub use state::DeployState;
@@ -0,0 +1,254 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::r#trait::BuildArtifact;
+
+/// Directories that never affect what gets built
+const FINGERPRINT_SKIP_DIRS: &[&str] = &[
+    "target",
+    ".git",
+    ".pmcp",
+    "node_modules",
+    "cdk.out",
+    ".build",
+];
+
+/// Progress of an unfinished deploy, saved in `.pmcp/deploy-state.json`
+///
+/// Each completed step is checkpointed so that a deploy interrupted by a crash,
+/// network failure or Ctrl-C continues where it stopped on the next run instead
+/// of rebuilding and re-uploading. The file is removed once a deploy succeeds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployState {
+    pub target: String,
+    pub server: String,
+    pub started_at: DateTime<Utc>,
+    /// Number of deploy runs for this state, including the current one
+    pub attempts: u32,
+    /// Hash of the project sources the artifact was built from
+    pub fingerprint: String,
+    /// Artifact built by an earlier attempt
+    pub artifact: Option<BuildArtifact>,
+    /// Remote deployment created by an earlier attempt (pmcp-run)
+    pub deployment_id: Option<String>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl DeployState {
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".pmcp").join("deploy-state.json")
+    }
+
+    /// Start a deploy, picking up the saved state of an unfinished previous attempt
+    ///
+    /// Saved state is discarded when `fresh` is set, when it belongs to another
+    /// target or server, or when the sources changed since it was written.
+    pub fn begin(project_root: &Path, target: &str, server: &str, fresh: bool) -> Result<Self> {
+        let path = Self::path(project_root);
+        let fingerprint = source_fingerprint(project_root)?;
+
+        let previous = if fresh {
+            None
+        } else {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+                .filter(|state| {
+                    state.target == target
+                        && state.server == server
+                        && state.fingerprint == fingerprint
+                })
+        };
+
+        let state = match previous {
+            Some(previous) => Self {
+                attempts: previous.attempts + 1,
+                path,
+                ..previous
+            },
+            None => Self {
+                target: target.to_string(),
+                server: server.to_string(),
+                started_at: Utc::now(),
+                attempts: 1,
+                fingerprint,
+                artifact: None,
+                deployment_id: None,
+                path,
+            },
+        };
+        state.save()?;
+        Ok(state)
+    }
+
+    /// Whether this run continues an earlier, unfinished attempt
+    pub fn is_resumed(&self) -> bool {
+        self.attempts > 1
+    }
+
+    /// The artifact of an earlier attempt, if its files are still on disk
+    pub fn reusable_artifact(&self) -> Option<BuildArtifact> {
+        self.artifact
+            .clone()
+            .filter(|artifact| artifact.files().iter().all(|path| path.exists()))
+    }
+
+    pub fn record_artifact(&mut self, artifact: &BuildArtifact) -> Result<()> {
+        self.artifact = Some(artifact.clone());
+        self.save()
+    }
+
+    pub fn record_deployment_id(&mut self, deployment_id: Option<&str>) -> Result<()> {
+        self.deployment_id = deployment_id.map(str::to_string);
+        self.save()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Forget the saved state after a successful deploy
+    pub fn finish(self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", self.path.display()))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Hash the path, size and modification time of every project source file
+fn source_fingerprint(project_root: &Path) -> Result<String> {
+    let mut files: Vec<(String, u64, i64)> = Vec::new();
+
+    let walker = walkdir::WalkDir::new(project_root)
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && entry.depth() > 0
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| FINGERPRINT_SKIP_DIRS.contains(&name)))
+        });
+    for entry in walker {
+        let entry = entry.context("Failed to scan project sources")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry.metadata().context("Failed to read file metadata")?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .map(|time| {
+                DateTime::<Utc>::from(time)
+                    .timestamp_nanos_opt()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        let relative = entry
+            .path()
+            .strip_prefix(project_root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.push((relative, metadata.len(), modified));
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (path, len, modified) in &files {
+        hasher.update(format!("{}\0{}\0{}\n", path, len, modified).as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        dir
+    }
+
+    fn artifact(dir: &Path) -> BuildArtifact {
+        let path = dir.join("target/bootstrap");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"binary").unwrap();
+        BuildArtifact::Binary {
+            path,
+            size: 6,
+            deployment_package: None,
+        }
+    }
+
+    #[test]
+    fn unfinished_attempt_is_resumed() {
+        let dir = project();
+        let mut state = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        assert!(!state.is_resumed());
+        state.record_artifact(&artifact(dir.path())).unwrap();
+        state.record_deployment_id(Some("dep-1")).unwrap();
+
+        let resumed = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        assert!(resumed.is_resumed());
+        assert_eq!(resumed.attempts, 2);
+        assert_eq!(resumed.deployment_id.as_deref(), Some("dep-1"));
+        assert!(resumed.reusable_artifact().is_some());
+    }
+
+    #[test]
+    fn changed_sources_start_over() {
+        let dir = project();
+        let mut state = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        state.record_deployment_id(Some("dep-1")).unwrap();
+
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn added() {}\n").unwrap();
+        let state = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        assert!(!state.is_resumed());
+        assert!(state.deployment_id.is_none());
+    }
+
+    #[test]
+    fn fresh_other_target_and_finish_discard_state() {
+        let dir = project();
+        let mut state = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        state.record_artifact(&artifact(dir.path())).unwrap();
+
+        let other = DeployState::begin(dir.path(), "aws-lambda", "demo", false).unwrap();
+        assert!(other.artifact.is_none());
+        let fresh = DeployState::begin(dir.path(), "aws-lambda", "demo", true).unwrap();
+        assert_eq!(fresh.attempts, 1);
+
+        fresh.finish().unwrap();
+        assert!(!DeployState::path(dir.path()).exists());
+    }
+
+    #[test]
+    fn missing_artifact_files_are_rebuilt() {
+        let dir = project();
+        let mut state = DeployState::begin(dir.path(), "pmcp-run", "demo", false).unwrap();
+        let built = artifact(dir.path());
+        state.record_artifact(&built).unwrap();
+        std::fs::remove_file(built.files()[0]).unwrap();
+        assert!(state.reusable_artifact().is_none());
+    }
+}
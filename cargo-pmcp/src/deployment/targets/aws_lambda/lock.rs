@@ -0,0 +1,106 @@
+//! Remote deploy lock stored as an SSM parameter in the deployment account.
+//!
+//! `put-parameter --no-overwrite` fails if the parameter exists, which makes it
+//! a simple compare-and-set shared by every machine deploying the same server.
+
+use anyhow::{bail, Result};
+use std::process::Command;
+
+use crate::deployment::{lock::LockInfo, DeployConfig};
+
+fn parameter_name(config: &DeployConfig) -> String {
+    format!("/pmcp/deploy-locks/{}", config.server.name)
+}
+
+pub fn acquire(config: &DeployConfig, lock: &LockInfo, force: bool) -> Result<()> {
+    let name = parameter_name(config);
+    let value = serde_json::to_string(lock)?;
+
+    let output = match put_parameter(config, &name, &value, force) {
+        Ok(output) => output,
+        Err(_) => {
+            println!("⚠️  AWS CLI not found; skipping the remote deploy lock");
+            return Ok(());
+        },
+    };
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.contains("ParameterAlreadyExists") {
+        println!(
+            "⚠️  Could not take the remote deploy lock ({}); continuing without it",
+            stderr.trim()
+        );
+        return Ok(());
+    }
+
+    match read(config) {
+        Some(holder) if !holder.is_stale() => bail!(
+            "Another deploy of {} is in progress: {}\n\
+             If that deploy is no longer running, retry with --force-unlock",
+            config.server.name,
+            holder.describe()
+        ),
+        Some(holder) => println!(
+            "⚠️  Taking over stale deploy lock from {}",
+            holder.describe()
+        ),
+        None => println!("⚠️  Replacing unreadable deploy lock {}", name),
+    }
+    if !put_parameter(config, &name, &value, true)?.status.success() {
+        bail!("Failed to replace the deploy lock {}", name);
+    }
+    Ok(())
+}
+
+pub fn release(config: &DeployConfig, lock: &LockInfo) -> Result<()> {
+    // Only delete the lock if it is still ours
+    if read(config).is_some_and(|holder| holder.id == lock.id) {
+        let output = Command::new("aws")
+            .args(["ssm", "delete-parameter", "--name"])
+            .arg(parameter_name(config))
+            .args(["--region", &config.aws.region])
+            .output()?;
+        if !output.status.success() {
+            bail!(
+                "Failed to release the deploy lock: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn put_parameter(
+    config: &DeployConfig,
+    name: &str,
+    value: &str,
+    overwrite: bool,
+) -> std::io::Result<std::process::Output> {
+    Command::new("aws")
+        .args(["ssm", "put-parameter", "--type", "String", "--name", name])
+        .args(["--value", value])
+        .args(["--region", &config.aws.region])
+        .arg(if overwrite {
+            "--overwrite"
+        } else {
+            "--no-overwrite"
+        })
+        .output()
+}
+
+fn read(config: &DeployConfig) -> Option<LockInfo> {
+    let output = Command::new("aws")
+        .args(["ssm", "get-parameter", "--name"])
+        .arg(parameter_name(config))
+        .args(["--region", &config.aws.region])
+        .args(["--query", "Parameter.Value", "--output", "text"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
@@ -1,15 +1,17 @@
 mod deploy;
 pub mod init;
+mod lock;
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 use std::process::Command;
 
 use crate::deployment::{
+    lock::LockInfo,
     r#trait::{
         BuildArtifact, DeploymentOutputs, DeploymentTarget, MetricsData, SecretsAction, TestResults,
     },
-    BinaryBuilder, DeployConfig,
+    BinaryBuilder, DeployConfig, DeployState,
 };
 
 pub struct AwsLambdaTarget;
@@ -113,6 +115,20 @@ impl DeploymentTarget for AwsLambdaTarget {
         deploy::deploy_aws_lambda(config, config.secrets.clone()).await
     }
 
+    async fn acquire_lock(
+        &self,
+        config: &DeployConfig,
+        lock: &LockInfo,
+        _state: &DeployState,
+        force: bool,
+    ) -> Result<()> {
+        lock::acquire(config, lock, force)
+    }
+
+    async fn release_lock(&self, config: &DeployConfig, lock: &LockInfo) -> Result<()> {
+        lock::release(config, lock)
+    }
+
     async fn destroy(&self, config: &DeployConfig, clean: bool) -> Result<()> {
         let deploy_dir = config.project_root.join("deploy");
 
@@ -582,6 +582,19 @@ async fn main(mut req: Request, _env: Env, _ctx: Context) -> Result<Response> {{
         }}
     }};
 
+    // Reject oversized bodies before buffering them, per the serverless
+    // resource profile, so small Worker memory limits aren't exceeded
+    let profile = pmcp::server::resource_profile::ResourceProfile::serverless();
+    if let Some(limit) = profile.max_request_body_bytes {{
+        let declared = req
+            .headers()
+            .get("Content-Length")?
+            .and_then(|v| v.parse::<usize>().ok());
+        if declared.is_some_and(|len| len > limit) {{
+            return Response::error("Request body too large", 413);
+        }}
+    }}
+
     // Get request body
     let body = match req.text().await {{
         Ok(text) => text,
@@ -5,10 +5,10 @@ use std::time::Duration;
 use crate::deployment::{
     metadata::McpMetadata,
     r#trait::{BuildArtifact, DeploymentOutputs},
-    DeployConfig,
+    DeployConfig, DeployState,
 };
 
-use super::{auth, graphql};
+use super::{auth, graphql, subscription};
 
 /// Extract the server version from the Cargo workspace.
 ///
@@ -77,6 +77,7 @@ fn extract_version_from_cargo(project_root: &Path) -> Option<String> {
 pub async fn deploy_to_pmcp_run(
     config: &DeployConfig,
     artifact: BuildArtifact,
+    state: Option<&mut DeployState>,
 ) -> Result<DeploymentOutputs> {
     println!("🚀 Deploying to pmcp.run...");
     println!();
@@ -84,210 +85,32 @@ pub async fn deploy_to_pmcp_run(
     // Get credentials (OAuth tokens)
     let credentials = auth::get_credentials().await?;
 
-    // Paths
-    let deploy_dir = config.project_root.join("deploy");
-    let cdk_out = deploy_dir.join("cdk.out");
-
-    // Step 0: Extract MCP metadata for the CloudFormation template
-    println!("📋 Extracting MCP server metadata...");
-    let metadata = match McpMetadata::extract(&config.project_root) {
-        Ok(m) => {
-            println!("   Server: {} ({})", m.server_id, m.server_type);
-            if !m.resources.secrets.is_empty() {
-                println!("   Secrets: {}", m.resources.secrets.len());
-            }
-            if !m.capabilities.tools.is_empty() {
-                println!("   Tools: {}", m.capabilities.tools.len());
-            }
-            Some(m)
-        },
-        Err(_) => {
-            println!("   No metadata found (using defaults)");
-            None
-        },
-    };
-
-    // Step 1: Synthesize CloudFormation template with metadata context
-    println!("📝 Synthesizing CloudFormation template...");
-
-    // Use shell to run npx/cdk to ensure PATH is correctly set
-    let shell_cmd = if cfg!(target_os = "windows") {
-        "cmd"
-    } else {
-        "sh"
-    };
-    let shell_arg = if cfg!(target_os = "windows") {
-        "/C"
-    } else {
-        "-c"
-    };
-
-    // Build CDK synth command with metadata context
-    let cdk_context_args = metadata
-        .as_ref()
-        .map(|m| m.to_cdk_context().join(" "))
-        .unwrap_or_default();
-
-    let synth_command = if cdk_context_args.is_empty() {
-        "npx cdk synth --quiet".to_string()
-    } else {
-        format!("npx cdk synth --quiet {}", cdk_context_args)
-    };
-
-    let synth_output = std::process::Command::new(shell_cmd)
-        .current_dir(&deploy_dir)
-        .arg(shell_arg)
-        .arg(&synth_command)
-        .output()
-        .context("Failed to run cdk synth. Make sure Node.js and npm are installed")?;
-
-    if !synth_output.status.success() {
-        let stderr = String::from_utf8_lossy(&synth_output.stderr);
-        bail!("CDK synthesis failed:\n{}", stderr);
-    }
-
-    println!("✅ CloudFormation template synthesized");
-
-    // Step 2: Find the synthesized template
-    let template_path = find_template_file(&cdk_out)?;
-    println!("   Template: {}", template_path.display());
-
-    // Step 3: Extract bootstrap binary path and deployment package from artifact
-    let (bootstrap_path, deployment_package) = match artifact {
-        BuildArtifact::Binary {
-            path,
-            deployment_package,
-            ..
-        } => (path, deployment_package),
-        BuildArtifact::Wasm {
-            path,
-            deployment_package,
-            ..
-        } => (path, deployment_package),
-        BuildArtifact::Custom {
-            path,
-            deployment_package,
-            ..
-        } => (path, deployment_package),
+    // Steps 0-7 are skipped when an earlier attempt already created a deployment
+    // that is still running or has succeeded
+    let previous_id = state.as_ref().and_then(|s| s.deployment_id.clone());
+    let resumed_id = match previous_id {
+        Some(id) => resumable_deployment(&credentials.access_token, id).await,
+        None => None,
     };
-
-    // Determine what to upload: deployment package (zip with assets) or raw binary
-    let (bootstrap_data, bootstrap_content_type, has_assets) = if let Some(ref package_path) =
-        deployment_package
-    {
-        if package_path.exists() {
-            println!("   📦 Using deployment package with assets");
-            println!("   Package: {}", package_path.display());
-            let data = std::fs::read(package_path).context("Failed to read deployment package")?;
-            (data, "application/zip", true)
-        } else {
-            // Fall back to raw binary if package doesn't exist
-            if !bootstrap_path.exists() {
-                bail!("Bootstrap binary not found: {}", bootstrap_path.display());
+    let deployment_id = match resumed_id {
+        Some(id) => {
+            println!("♻️  Resuming deployment {} from the previous attempt", id);
+            println!();
+            id
+        },
+        None => {
+            let id = start_deployment(config, artifact, &credentials.access_token).await?;
+            if let Some(state) = state {
+                state.record_deployment_id(Some(&id))?;
             }
-            println!("   Bootstrap: {}", bootstrap_path.display());
-            let data = std::fs::read(&bootstrap_path).context("Failed to read bootstrap binary")?;
-            (data, "application/octet-stream", false)
-        }
-    } else {
-        if !bootstrap_path.exists() {
-            bail!("Bootstrap binary not found: {}", bootstrap_path.display());
-        }
-        println!("   Bootstrap: {}", bootstrap_path.display());
-        let data = std::fs::read(&bootstrap_path).context("Failed to read bootstrap binary")?;
-        (data, "application/octet-stream", false)
-    };
-
-    println!();
-
-    // Step 4: Read template file
-    let template = std::fs::read_to_string(&template_path)
-        .context("Failed to read CloudFormation template")?;
-
-    println!("📦 Template size: {} KB", template.len() / 1024);
-    if has_assets {
-        println!(
-            "📦 Deployment package size: {} KB",
-            bootstrap_data.len() / 1024
-        );
-    } else {
-        println!("📦 Bootstrap size: {} KB", bootstrap_data.len() / 1024);
-    }
-    println!();
-
-    // Step 5: Get presigned S3 URLs from GraphQL
-    println!("🔑 Getting upload URLs from pmcp.run...");
-    let urls = graphql::get_upload_urls(
-        &credentials.access_token,
-        &config.server.name,
-        template.len(),
-        bootstrap_data.len(),
-    )
-    .await
-    .context("Failed to get upload URLs")?;
-
-    println!("   URLs expire in {} seconds", urls.expires_in);
-    println!();
-
-    // Step 6: Upload files to S3 in parallel
-    println!("⬆️  Uploading files to S3...");
-
-    let template_bytes = template.into_bytes();
-    let bootstrap_label = if has_assets { "Package" } else { "Bootstrap" };
-    let (template_result, bootstrap_result) = tokio::join!(
-        graphql::upload_to_s3(
-            &urls.template_upload_url,
-            template_bytes,
-            "application/json",
-            "Template",
-        ),
-        graphql::upload_to_s3(
-            &urls.bootstrap_upload_url,
-            bootstrap_data,
-            bootstrap_content_type,
-            bootstrap_label,
-        )
-    );
-
-    template_result.context("Template upload to S3 failed")?;
-    bootstrap_result.context("Bootstrap upload to S3 failed")?;
-
-    println!("✅ Files uploaded successfully to S3");
-    println!();
-
-    // Step 7: Create deployment via GraphQL with composition settings and version
-    println!("🚀 Creating deployment...");
-
-    // Extract version from Cargo.toml (supports workspace inheritance)
-    let server_version = extract_version_from_cargo(&config.project_root);
-    if let Some(ref version) = server_version {
-        println!("   Version: {}", version);
-    }
-
-    let composition = graphql::CompositionSettings {
-        tier: config.composition.tier.clone(),
-        allow_composition: config.composition.allow_composition,
-        internal_only: config.composition.internal_only,
-        description: config.composition.description.clone(),
-        server_version,
+            id
+        },
     };
-    let deployment = graphql::create_deployment_from_s3_with_composition(
-        &credentials.access_token,
-        &urls,
-        &config.server.name,
-        composition,
-    )
-    .await
-    .context("Failed to create deployment")?;
-
-    println!("   Deployment ID: {}", deployment.deployment_id);
-    println!();
 
-    // Step 8: Poll deployment status (wait for completion)
-    let deployment_outputs =
-        poll_deployment_status(&credentials.access_token, &deployment.deployment_id)
-            .await
-            .context("Deployment failed")?;
+    // Step 8: Wait for deployment completion (streamed progress or polling)
+    let deployment_outputs = wait_for_deployment(&credentials.access_token, &deployment_id)
+        .await
+        .context("Deployment failed")?;
 
     // Step 9: Configure OAuth if enabled in local config
     let oauth_config = if config.auth.enabled {
@@ -307,7 +130,7 @@ pub async fn deploy_to_pmcp_run(
 
         match graphql::configure_server_oauth(
             &credentials.access_token,
-            &deployment.deployment_id,
+            &deployment_id,
             true,
             scopes,
             Some(config.auth.dcr.enabled),
@@ -324,10 +147,7 @@ pub async fn deploy_to_pmcp_run(
             Err(e) => {
                 eprintln!("⚠️  Failed to configure OAuth: {}", e);
                 eprintln!("   You can manually enable OAuth with:");
-                eprintln!(
-                    "   cargo pmcp oauth enable --server {}",
-                    deployment.deployment_id
-                );
+                eprintln!("   cargo pmcp oauth enable --server {}", deployment_id);
                 println!();
                 None
             },
@@ -375,7 +195,7 @@ pub async fn deploy_to_pmcp_run(
     let mcp_url = deployment_outputs
         .url
         .clone()
-        .unwrap_or_else(|| format!("https://api.pmcp.run/{}/mcp", deployment.deployment_id));
+        .unwrap_or_else(|| format!("https://api.pmcp.run/{}/mcp", deployment_id));
     // Replace only the trailing /mcp path, not /mcp- in subdomains like mcp-reference
     // e.g. https://mcp-reference.us-east.true-mcp.com/mcp → .../health
     let health_url = if let Some(base) = mcp_url.strip_suffix("/mcp") {
@@ -398,7 +218,7 @@ pub async fn deploy_to_pmcp_run(
     println!("📊 Deployment Details:");
     println!("   Name: {}", config.server.name);
     println!("   Server ID: {}", server_id);
-    println!("   Deployment ID: {}", deployment.deployment_id);
+    println!("   Deployment ID: {}", deployment_id);
 
     // Display endpoints based on OAuth status
     if let Some(ref oauth) = oauth_config {
@@ -435,10 +255,7 @@ pub async fn deploy_to_pmcp_run(
         println!("   URL: {}", health_url);
         println!();
         println!("No authentication required. Anyone can access this server.");
-        println!(
-            "To enable OAuth: cargo pmcp oauth enable {}",
-            deployment.deployment_id
-        );
+        println!("To enable OAuth: cargo pmcp oauth enable {}", deployment_id);
     }
 
     println!();
@@ -465,7 +282,7 @@ pub async fn deploy_to_pmcp_run(
     );
     outputs_with_id.custom.insert(
         "deployment_id".to_string(),
-        serde_json::Value::String(deployment.deployment_id.clone()),
+        serde_json::Value::String(deployment_id.clone()),
     );
     outputs_with_id.custom.insert(
         "mcp_endpoint".to_string(),
@@ -502,13 +319,253 @@ pub async fn deploy_to_pmcp_run(
     Ok(outputs_with_id)
 }
 
+/// Upload the template and binary and create a deployment (steps 0-7),
+/// returning the new deployment ID
+async fn start_deployment(
+    config: &DeployConfig,
+    artifact: BuildArtifact,
+    access_token: &str,
+) -> Result<String> {
+    // Paths
+    let deploy_dir = config.project_root.join("deploy");
+    let cdk_out = deploy_dir.join("cdk.out");
+
+    // Step 0: Extract MCP metadata for the CloudFormation template
+    println!("📋 Extracting MCP server metadata...");
+    let metadata = match McpMetadata::extract(&config.project_root) {
+        Ok(m) => {
+            println!("   Server: {} ({})", m.server_id, m.server_type);
+            if !m.resources.secrets.is_empty() {
+                println!("   Secrets: {}", m.resources.secrets.len());
+            }
+            if !m.capabilities.tools.is_empty() {
+                println!("   Tools: {}", m.capabilities.tools.len());
+            }
+            Some(m)
+        },
+        Err(_) => {
+            println!("   No metadata found (using defaults)");
+            None
+        },
+    };
+
+    // Step 1: Synthesize CloudFormation template with metadata context
+    println!("📝 Synthesizing CloudFormation template...");
+
+    // Use shell to run npx/cdk to ensure PATH is correctly set
+    let shell_cmd = if cfg!(target_os = "windows") {
+        "cmd"
+    } else {
+        "sh"
+    };
+    let shell_arg = if cfg!(target_os = "windows") {
+        "/C"
+    } else {
+        "-c"
+    };
+
+    // Build CDK synth command with metadata context
+    let cdk_context_args = metadata
+        .as_ref()
+        .map(|m| m.to_cdk_context().join(" "))
+        .unwrap_or_default();
+
+    let synth_command = if cdk_context_args.is_empty() {
+        "npx cdk synth --quiet".to_string()
+    } else {
+        format!("npx cdk synth --quiet {}", cdk_context_args)
+    };
+
+    let synth_output = std::process::Command::new(shell_cmd)
+        .current_dir(&deploy_dir)
+        .arg(shell_arg)
+        .arg(&synth_command)
+        .output()
+        .context("Failed to run cdk synth. Make sure Node.js and npm are installed")?;
+
+    if !synth_output.status.success() {
+        let stderr = String::from_utf8_lossy(&synth_output.stderr);
+        bail!("CDK synthesis failed:\n{}", stderr);
+    }
+
+    println!("✅ CloudFormation template synthesized");
+
+    // Step 2: Find the synthesized template
+    let template_path = find_template_file(&cdk_out)?;
+    println!("   Template: {}", template_path.display());
+
+    // Step 3: Extract bootstrap binary path and deployment package from artifact
+    let (bootstrap_path, deployment_package) = match artifact {
+        BuildArtifact::Binary {
+            path,
+            deployment_package,
+            ..
+        } => (path, deployment_package),
+        BuildArtifact::Wasm {
+            path,
+            deployment_package,
+            ..
+        } => (path, deployment_package),
+        BuildArtifact::Custom {
+            path,
+            deployment_package,
+            ..
+        } => (path, deployment_package),
+    };
+
+    // Determine what to upload: deployment package (zip with assets) or raw binary
+    let (bootstrap_data, bootstrap_content_type, has_assets) = if let Some(ref package_path) =
+        deployment_package
+    {
+        if package_path.exists() {
+            println!("   📦 Using deployment package with assets");
+            println!("   Package: {}", package_path.display());
+            let data = std::fs::read(package_path).context("Failed to read deployment package")?;
+            (data, "application/zip", true)
+        } else {
+            // Fall back to raw binary if package doesn't exist
+            if !bootstrap_path.exists() {
+                bail!("Bootstrap binary not found: {}", bootstrap_path.display());
+            }
+            println!("   Bootstrap: {}", bootstrap_path.display());
+            let data = std::fs::read(&bootstrap_path).context("Failed to read bootstrap binary")?;
+            (data, "application/octet-stream", false)
+        }
+    } else {
+        if !bootstrap_path.exists() {
+            bail!("Bootstrap binary not found: {}", bootstrap_path.display());
+        }
+        println!("   Bootstrap: {}", bootstrap_path.display());
+        let data = std::fs::read(&bootstrap_path).context("Failed to read bootstrap binary")?;
+        (data, "application/octet-stream", false)
+    };
+
+    println!();
+
+    // Step 4: Read template file
+    let template = std::fs::read_to_string(&template_path)
+        .context("Failed to read CloudFormation template")?;
+
+    println!("📦 Template size: {} KB", template.len() / 1024);
+    if has_assets {
+        println!(
+            "📦 Deployment package size: {} KB",
+            bootstrap_data.len() / 1024
+        );
+    } else {
+        println!("📦 Bootstrap size: {} KB", bootstrap_data.len() / 1024);
+    }
+    println!();
+
+    // Step 5: Get presigned S3 URLs from GraphQL
+    println!("🔑 Getting upload URLs from pmcp.run...");
+    let urls = graphql::get_upload_urls(
+        access_token,
+        &config.server.name,
+        template.len(),
+        bootstrap_data.len(),
+    )
+    .await
+    .context("Failed to get upload URLs")?;
+
+    println!("   URLs expire in {} seconds", urls.expires_in);
+    println!();
+
+    // Step 6: Upload files to S3 in parallel
+    println!("⬆️  Uploading files to S3...");
+
+    let template_bytes = template.into_bytes();
+    let bootstrap_label = if has_assets { "Package" } else { "Bootstrap" };
+    let (template_result, bootstrap_result) = tokio::join!(
+        graphql::upload_to_s3(
+            &urls.template_upload_url,
+            template_bytes,
+            "application/json",
+            "Template",
+        ),
+        graphql::upload_to_s3(
+            &urls.bootstrap_upload_url,
+            bootstrap_data,
+            bootstrap_content_type,
+            bootstrap_label,
+        )
+    );
+
+    template_result.context("Template upload to S3 failed")?;
+    bootstrap_result.context("Bootstrap upload to S3 failed")?;
+
+    println!("✅ Files uploaded successfully to S3");
+    println!();
+
+    // Step 7: Create deployment via GraphQL with composition settings and version
+    println!("🚀 Creating deployment...");
+
+    // Extract version from Cargo.toml (supports workspace inheritance)
+    let server_version = extract_version_from_cargo(&config.project_root);
+    if let Some(ref version) = server_version {
+        println!("   Version: {}", version);
+    }
+
+    let composition = graphql::CompositionSettings {
+        tier: config.composition.tier.clone(),
+        allow_composition: config.composition.allow_composition,
+        internal_only: config.composition.internal_only,
+        description: config.composition.description.clone(),
+        server_version,
+    };
+    let deployment = graphql::create_deployment_from_s3_with_composition(
+        access_token,
+        &urls,
+        &config.server.name,
+        composition,
+    )
+    .await
+    .context("Failed to create deployment")?;
+
+    println!("   Deployment ID: {}", deployment.deployment_id);
+    println!();
+
+    Ok(deployment.deployment_id)
+}
+
+/// The ID of an earlier deployment worth resuming: one that is still in
+/// progress or already succeeded. Failed or unknown deployments start over.
+async fn resumable_deployment(access_token: &str, deployment_id: String) -> Option<String> {
+    let status = graphql::get_deployment(access_token, &deployment_id)
+        .await
+        .ok()?;
+    (graphql::is_in_progress(&status.status) || status.status == "success").then_some(deployment_id)
+}
+
+/// Wait for the deployment to finish, streaming step-by-step progress when the
+/// realtime endpoint is reachable and polling otherwise, including after the
+/// stream drops mid-deployment.
+async fn wait_for_deployment(access_token: &str, deployment_id: &str) -> Result<DeploymentOutputs> {
+    println!("⏳ Waiting for deployment to complete...");
+
+    let mut last_event = None;
+    let streamed = subscription::watch_operation(access_token, deployment_id, |event| {
+        println!("{}", event.display_line());
+        last_event = Some(event.clone());
+    })
+    .await;
+
+    match streamed {
+        // The final outputs (URL, project name) come from the deployment record
+        Ok(_) => poll_deployment_status(access_token, deployment_id).await,
+        Err(e) if super::is_reported_failure(last_event.as_ref()) => Err(e),
+        Err(e) => {
+            super::print_polling_fallback(&e, last_event.as_ref());
+            poll_deployment_status(access_token, deployment_id).await
+        },
+    }
+}
+
 /// Poll deployment status until complete or failed
 async fn poll_deployment_status(
     access_token: &str,
     deployment_id: &str,
 ) -> Result<DeploymentOutputs> {
-    println!("⏳ Waiting for deployment to complete...");
-
     let mut dots = 0;
 
     loop {
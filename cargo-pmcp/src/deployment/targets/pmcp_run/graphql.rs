@@ -6,7 +6,7 @@ use super::auth::{load_cached_config, DEFAULT_GRAPHQL_URL};
 
 /// Resolve GraphQL URL with priority: env var > discovery cache > default.
 /// This is sync to avoid an async call on every GraphQL request.
-fn get_graphql_url() -> String {
+pub(crate) fn get_graphql_url() -> String {
     // 1. Legacy env var (highest priority)
     if let Ok(url) = std::env::var("PMCP_RUN_GRAPHQL_URL") {
         return url;
@@ -598,6 +598,60 @@ pub async fn find_deployment_id_by_name(access_token: &str, project_name: &str)
         .context(format!("No deployment found for project: {}", project_name))
 }
 
+/// IDs of the deployments for a project that are still pending, validating or deploying
+pub async fn find_active_deployment_ids(
+    access_token: &str,
+    project_name: &str,
+) -> Result<Vec<String>> {
+    let query = r#"
+        query ListDeployments {
+            listDeployments {
+                items {
+                    id
+                    projectName
+                    status
+                }
+            }
+        }
+    "#;
+
+    let variables = serde_json::json!({});
+
+    #[derive(Debug, Deserialize)]
+    struct ListDeploymentsResponse {
+        #[serde(rename = "listDeployments")]
+        list_deployments: DeploymentList,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct DeploymentList {
+        items: Vec<DeploymentItem>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct DeploymentItem {
+        id: String,
+        #[serde(rename = "projectName")]
+        project_name: String,
+        status: String,
+    }
+
+    let response: ListDeploymentsResponse = execute_graphql(access_token, query, variables).await?;
+
+    Ok(response
+        .list_deployments
+        .items
+        .into_iter()
+        .filter(|d| d.project_name == project_name && is_in_progress(&d.status))
+        .map(|d| d.id)
+        .collect())
+}
+
+/// Whether a deployment status means the deployment has not finished yet
+pub fn is_in_progress(status: &str) -> bool {
+    matches!(status, "pending" | "validating" | "deploying")
+}
+
 /// Get deployment outputs (for outputs command)
 pub async fn get_deployment_outputs(
     access_token: &str,
@@ -660,6 +714,99 @@ pub async fn get_deployment_outputs(
     })
 }
 
+// ========== Metrics GraphQL Functions ==========
+
+/// Metric periods supported by the pmcp.run metrics API
+pub const METRICS_PERIODS: &[&str] = &["1h", "24h", "7d"];
+
+/// Aggregated server metrics returned by getServerMetrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerMetrics {
+    pub period: String,
+    pub requests: u64,
+    pub errors: u64,
+    #[serde(rename = "coldStarts")]
+    pub cold_starts: u64,
+    #[serde(rename = "avgLatencyMs")]
+    pub avg_latency_ms: Option<f64>,
+    #[serde(rename = "p50LatencyMs")]
+    pub p50_latency_ms: Option<f64>,
+    #[serde(rename = "p95LatencyMs")]
+    pub p95_latency_ms: Option<f64>,
+    #[serde(rename = "p99LatencyMs")]
+    pub p99_latency_ms: Option<f64>,
+    #[serde(default)]
+    pub series: Vec<MetricsPoint>,
+}
+
+/// One bucket of the metrics time series
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsPoint {
+    pub timestamp: String,
+    pub requests: u64,
+    pub errors: u64,
+    #[serde(rename = "coldStarts", default)]
+    pub cold_starts: u64,
+    #[serde(rename = "p99LatencyMs")]
+    pub p99_latency_ms: Option<f64>,
+}
+
+/// Get request, error, latency and cold start metrics for a deployment
+pub async fn get_server_metrics(
+    access_token: &str,
+    deployment_id: &str,
+    period: &str,
+) -> Result<ServerMetrics> {
+    if !METRICS_PERIODS.contains(&period) {
+        bail!(
+            "Unsupported metrics period '{}' (expected one of: {})",
+            period,
+            METRICS_PERIODS.join(", ")
+        );
+    }
+
+    let query = r#"
+        query GetServerMetrics($deploymentId: ID!, $period: String!) {
+            getServerMetrics(deploymentId: $deploymentId, period: $period) {
+                period
+                requests
+                errors
+                coldStarts
+                avgLatencyMs
+                p50LatencyMs
+                p95LatencyMs
+                p99LatencyMs
+                series {
+                    timestamp
+                    requests
+                    errors
+                    coldStarts
+                    p99LatencyMs
+                }
+            }
+        }
+    "#;
+
+    let variables = serde_json::json!({
+        "deploymentId": deployment_id,
+        "period": period,
+    });
+
+    #[derive(Debug, Deserialize)]
+    struct GetServerMetricsResponse {
+        #[serde(rename = "getServerMetrics")]
+        get_server_metrics: Option<ServerMetrics>,
+    }
+
+    let response: GetServerMetricsResponse =
+        execute_graphql(access_token, query, variables).await?;
+
+    response.get_server_metrics.context(format!(
+        "No metrics available for deployment: {}",
+        deployment_id
+    ))
+}
+
 // ========== Landing Page Deployment GraphQL Functions ==========
 
 /// Response from getLandingUploadUrl mutation
@@ -1073,6 +1220,23 @@ pub struct ListScenariosResult {
     pub scenarios: Vec<ScenarioInfo>,
 }
 
+/// One scheduled run of a test scenario, from queryTestExecutionsForServer
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioExecution {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    #[serde(rename = "scenarioName")]
+    pub scenario_name: String,
+    /// `passed`, `failed`, `error`, or `running`
+    pub status: String,
+    #[serde(rename = "executedAt")]
+    pub executed_at: String,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: Option<u64>,
+    #[serde(rename = "errorMessage")]
+    pub error_message: Option<String>,
+}
+
 /// Upload a test scenario to pmcp.run
 pub async fn upload_test_scenario(
     access_token: &str,
@@ -1201,6 +1365,46 @@ pub async fn list_test_scenarios(
     Ok(ListScenariosResult { scenarios })
 }
 
+/// List recent scheduled executions of every test scenario for a server
+///
+/// Returns at most `limit` executions per scenario, newest first.
+pub async fn list_test_executions(
+    access_token: &str,
+    server_id: &str,
+    limit: usize,
+) -> Result<Vec<ScenarioExecution>> {
+    let query = r#"
+        query QueryTestExecutionsForServer($serverId: String!, $limit: Int) {
+            queryTestExecutionsForServer(serverId: $serverId, limit: $limit) {
+                executions
+            }
+        }
+    "#;
+
+    let variables = serde_json::json!({
+        "serverId": server_id,
+        "limit": limit
+    });
+
+    #[derive(Debug, Deserialize)]
+    struct QueryTestExecutionsResponse {
+        #[serde(rename = "queryTestExecutionsForServer")]
+        query_test_executions: ListExecutionsRaw,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ListExecutionsRaw {
+        executions: serde_json::Value,
+    }
+
+    let response: QueryTestExecutionsResponse =
+        execute_graphql(access_token, query, variables).await?;
+
+    // Parse the JSON executions array
+    serde_json::from_value(response.query_test_executions.executions)
+        .context("Failed to parse test executions")
+}
+
 // ========== Loadtest Scenario Upload GraphQL Functions ==========
 
 /// Upload a loadtest scenario to pmcp.run
@@ -1,16 +1,18 @@
 pub mod auth;
 mod deploy;
 pub mod graphql;
+pub mod subscription;
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 
 use crate::deployment::{
+    lock::LockInfo,
     operations::{AsyncOperation, DestroyResult, OperationStatus, OperationType},
     r#trait::{
         BuildArtifact, DeploymentOutputs, DeploymentTarget, MetricsData, SecretsAction, TestResults,
     },
-    DeployConfig,
+    DeployConfig, DeployState,
 };
 
 pub use auth::{login, logout};
@@ -29,9 +31,71 @@ impl Default for PmcpRunTarget {
     }
 }
 
+/// Explain why live progress stopped before falling back to polling.
+///
+/// `last_event` is the last event the subscription delivered, if any.
+pub(crate) fn print_polling_fallback(
+    error: &anyhow::Error,
+    last_event: Option<&subscription::OperationProgressEvent>,
+) {
+    match last_event {
+        Some(event) => println!(
+            "   Live progress interrupted at '{}' ({}), polling for status...",
+            event.status, error
+        ),
+        None => println!(
+            "   Live progress unavailable ({}), polling for status...",
+            error
+        ),
+    }
+}
+
+/// Whether a subscription error reports a failed operation, as opposed to a
+/// dropped or stalled stream that polling can recover from.
+pub(crate) fn is_reported_failure(
+    last_event: Option<&subscription::OperationProgressEvent>,
+) -> bool {
+    last_event.is_some_and(|event| event.phase() == subscription::OperationPhase::Failed)
+}
+
 impl PmcpRunTarget {
-    /// Wait for an async operation to complete by polling
+    /// Wait for an async operation to complete.
+    ///
+    /// Streams live progress over a GraphQL subscription when available, falling
+    /// back to polling if the realtime endpoint cannot be reached or the stream
+    /// drops before the operation finishes.
     async fn wait_for_operation(&self, operation_id: &str) -> Result<()> {
+        let credentials = auth::get_credentials().await?;
+        let mut last_event = None;
+
+        let streamed =
+            subscription::watch_operation(&credentials.access_token, operation_id, |event| {
+                println!("{}", event.display_line());
+                last_event = Some(event.clone());
+            })
+            .await;
+
+        match streamed {
+            Ok(event) => {
+                println!(
+                    "✅ {}",
+                    event
+                        .message
+                        .unwrap_or_else(|| "Operation completed".to_string())
+                );
+                Ok(())
+            },
+            // Only a streamed failure status is authoritative
+            Err(e) if is_reported_failure(last_event.as_ref()) => Err(e),
+            Err(e) => {
+                print_polling_fallback(&e, last_event.as_ref());
+                self.poll_operation(operation_id).await
+            },
+        }
+    }
+
+    /// Wait for an async operation to complete by polling
+    async fn poll_operation(&self, operation_id: &str) -> Result<()> {
         use std::time::Duration;
 
         let mut dots = 0;
@@ -194,7 +258,53 @@ impl DeploymentTarget for PmcpRunTarget {
         config: &DeployConfig,
         artifact: BuildArtifact,
     ) -> Result<DeploymentOutputs> {
-        deploy::deploy_to_pmcp_run(config, artifact).await
+        deploy::deploy_to_pmcp_run(config, artifact, None).await
+    }
+
+    async fn deploy_resumable(
+        &self,
+        config: &DeployConfig,
+        artifact: BuildArtifact,
+        state: &mut DeployState,
+    ) -> Result<DeploymentOutputs> {
+        deploy::deploy_to_pmcp_run(config, artifact, Some(state)).await
+    }
+
+    async fn acquire_lock(
+        &self,
+        config: &DeployConfig,
+        _lock: &LockInfo,
+        state: &DeployState,
+        force: bool,
+    ) -> Result<()> {
+        // pmcp.run has no lock API; a deployment of this server that is still
+        // running (other than the one being resumed) means another deploy is active.
+        let credentials = auth::get_credentials().await?;
+        let active =
+            graphql::find_active_deployment_ids(&credentials.access_token, &config.server.name)
+                .await
+                .context("Failed to check for deployments in progress")?;
+        let others: Vec<String> = active
+            .into_iter()
+            .filter(|id| state.deployment_id.as_deref() != Some(id.as_str()))
+            .collect();
+
+        if others.is_empty() {
+            return Ok(());
+        }
+        if force {
+            println!(
+                "⚠️  Deploying despite deployment(s) in progress on pmcp.run: {}",
+                others.join(", ")
+            );
+            return Ok(());
+        }
+        bail!(
+            "Another deploy of {} is in progress on pmcp.run (deployment {})\n\
+             Wait for it to finish, or retry with --force-unlock",
+            config.server.name,
+            others.join(", ")
+        )
     }
 
     async fn destroy(&self, config: &DeployConfig, clean: bool) -> Result<()> {
@@ -347,16 +457,36 @@ impl DeploymentTarget for PmcpRunTarget {
         Ok(())
     }
 
-    async fn metrics(&self, _config: &DeployConfig, period: &str) -> Result<MetricsData> {
-        println!("📊 pmcp.run metrics coming soon!");
-        println!("   View metrics at: https://pmcp.run/dashboard");
+    async fn metrics(&self, config: &DeployConfig, period: &str) -> Result<MetricsData> {
+        let credentials = auth::get_credentials().await?;
+        let deployment_id =
+            graphql::find_deployment_id_by_name(&credentials.access_token, &config.server.name)
+                .await?;
+        let metrics =
+            graphql::get_server_metrics(&credentials.access_token, &deployment_id, period).await?;
+
+        let mut custom = std::collections::HashMap::new();
+        custom.insert(
+            "p50_latency_ms".to_string(),
+            serde_json::json!(metrics.p50_latency_ms),
+        );
+        custom.insert(
+            "p95_latency_ms".to_string(),
+            serde_json::json!(metrics.p95_latency_ms),
+        );
+        custom.insert(
+            "cold_starts".to_string(),
+            serde_json::json!(metrics.cold_starts),
+        );
+        custom.insert("series".to_string(), serde_json::to_value(&metrics.series)?);
+
         Ok(MetricsData {
-            period: period.to_string(),
-            requests: None,
-            errors: None,
-            avg_latency_ms: None,
-            p99_latency_ms: None,
-            custom: std::collections::HashMap::new(),
+            period: metrics.period,
+            requests: Some(metrics.requests),
+            errors: Some(metrics.errors),
+            avg_latency_ms: metrics.avg_latency_ms,
+            p99_latency_ms: metrics.p99_latency_ms,
+            custom,
         })
     }
 
@@ -403,3 +533,30 @@ impl DeploymentTarget for PmcpRunTarget {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::subscription::OperationProgressEvent;
+    use super::*;
+
+    #[test]
+    fn test_only_failed_status_is_reported_failure() {
+        let mut event = OperationProgressEvent {
+            id: "op".to_string(),
+            status: "deploying".to_string(),
+            step: Some("cloudformation".to_string()),
+            message: None,
+            resource_type: None,
+            logical_resource_id: None,
+            resource_status: None,
+            timestamp: None,
+        };
+
+        // No events or a stream lost mid-operation fall back to polling
+        assert!(!is_reported_failure(None));
+        assert!(!is_reported_failure(Some(&event)));
+
+        event.status = "failed".to_string();
+        assert!(is_reported_failure(Some(&event)));
+    }
+}
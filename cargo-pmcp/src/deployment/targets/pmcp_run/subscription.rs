@@ -0,0 +1,379 @@
+//! Live operation status over GraphQL subscriptions.
+//!
+//! Deploy and destroy operations on pmcp.run can take several minutes. Instead of
+//! polling `getDeploymentOperationStatus` every few seconds, this module opens a
+//! WebSocket using the `graphql-transport-ws` protocol and streams step-by-step
+//! progress events (building, uploading, CloudFormation resource events) as the
+//! backend emits them.
+//!
+//! Callers should treat the subscription as best-effort: if the WebSocket cannot be
+//! established, fall back to the polling path.
+
+use anyhow::{bail, Context, Result};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use super::graphql::get_graphql_url;
+
+/// WebSocket subprotocol spoken by the pmcp.run realtime endpoint
+const GRAPHQL_WS_PROTOCOL: &str = "graphql-transport-ws";
+
+/// How long to wait for `connection_ack` after `connection_init`
+const CONNECTION_ACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum silence on the socket before the subscription is considered stalled
+const IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+const OPERATION_PROGRESS_SUBSCRIPTION: &str = r#"
+    subscription OnDeploymentOperationProgress($id: ID!) {
+        onDeploymentOperationProgress(id: $id) {
+            id
+            status
+            step
+            message
+            resourceType
+            logicalResourceId
+            resourceStatus
+            timestamp
+        }
+    }
+"#;
+
+/// A single progress event for a deploy/destroy operation
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct OperationProgressEvent {
+    pub id: String,
+    pub status: String,
+    /// Current step (e.g. "building", "uploading", "cloudformation")
+    pub step: Option<String>,
+    pub message: Option<String>,
+    /// CloudFormation resource type for `cloudformation` steps
+    #[serde(rename = "resourceType")]
+    pub resource_type: Option<String>,
+    #[serde(rename = "logicalResourceId")]
+    pub logical_resource_id: Option<String>,
+    /// CloudFormation resource status (e.g. `CREATE_IN_PROGRESS`)
+    #[serde(rename = "resourceStatus")]
+    pub resource_status: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+/// Coarse phase of an operation, derived from the server status string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationPhase {
+    InProgress,
+    Succeeded,
+    Failed,
+}
+
+impl OperationProgressEvent {
+    /// Classify the event's status into a terminal or non-terminal phase
+    pub fn phase(&self) -> OperationPhase {
+        match self.status.as_str() {
+            "deleted" | "success" | "completed" => OperationPhase::Succeeded,
+            "failed" | "delete_failed" | "error" => OperationPhase::Failed,
+            _ => OperationPhase::InProgress,
+        }
+    }
+
+    /// Render a single human-readable progress line for this event
+    pub fn display_line(&self) -> String {
+        let step = self.step.as_deref().unwrap_or(&self.status);
+        let icon = match step {
+            "building" => "🔨",
+            "uploading" => "📤",
+            "cloudformation" => "☁️ ",
+            "validating" => "🔍",
+            "deleting" => "🗑️ ",
+            _ => "⏳",
+        };
+
+        if let (Some(resource), Some(resource_status)) =
+            (&self.logical_resource_id, &self.resource_status)
+        {
+            let resource_type = self.resource_type.as_deref().unwrap_or("resource");
+            return format!(
+                "   {} {} {} ({})",
+                icon, resource_status, resource, resource_type
+            );
+        }
+
+        match &self.message {
+            Some(message) => format!("   {} [{}] {}", icon, step, message),
+            None => format!("   {} [{}]", icon, step),
+        }
+    }
+}
+
+/// Derive the realtime WebSocket URL.
+///
+/// Priority: `PMCP_RUN_GRAPHQL_WS_URL` env var > GraphQL URL with `ws(s)://` scheme.
+pub fn get_graphql_ws_url() -> String {
+    if let Ok(url) = std::env::var("PMCP_RUN_GRAPHQL_WS_URL") {
+        return url;
+    }
+    http_to_ws_url(&get_graphql_url())
+}
+
+fn http_to_ws_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
+
+/// Messages received from the server under `graphql-transport-ws`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    ConnectionAck,
+    Ping,
+    Pong,
+    Next { payload: serde_json::Value },
+    Error { payload: serde_json::Value },
+    Complete,
+}
+
+fn parse_server_message(text: &str) -> Result<ServerMessage> {
+    serde_json::from_str(text).with_context(|| format!("Invalid subscription message: {}", text))
+}
+
+/// Extract the progress event from a `next` payload, surfacing GraphQL errors
+fn parse_progress_payload(payload: serde_json::Value) -> Result<OperationProgressEvent> {
+    if let Some(errors) = payload.get("errors").and_then(|e| e.as_array()) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+            .collect();
+        if !messages.is_empty() {
+            bail!("GraphQL subscription errors: {}", messages.join(", "));
+        }
+    }
+
+    let event = payload
+        .get("data")
+        .and_then(|d| d.get("onDeploymentOperationProgress"))
+        .cloned()
+        .context("Subscription payload missing onDeploymentOperationProgress")?;
+
+    serde_json::from_value(event).context("Failed to parse operation progress event")
+}
+
+type GraphQLSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Consume messages until `connection_ack`, answering keep-alive pings
+async fn await_connection_ack(socket: &mut GraphQLSocket) -> Result<()> {
+    while let Some(message) = socket.next().await {
+        if let Message::Text(text) = message? {
+            match parse_server_message(&text)? {
+                ServerMessage::ConnectionAck => return Ok(()),
+                ServerMessage::Ping => {
+                    socket
+                        .send(Message::text(r#"{"type":"pong"}"#.to_string()))
+                        .await?;
+                },
+                _ => {},
+            }
+        }
+    }
+    bail!("Subscription connection closed before acknowledgement")
+}
+
+/// Subscribe to progress for `operation_id`, invoking `on_event` for every update.
+///
+/// Returns the terminal event once the operation succeeds, or an error if it
+/// fails or the stream ends prematurely. A failed operation is always reported
+/// through a final `on_event` call with a [`OperationPhase::Failed`] event, so
+/// callers can tell it apart from a lost stream and fall back to polling.
+pub async fn watch_operation<F>(
+    access_token: &str,
+    operation_id: &str,
+    mut on_event: F,
+) -> Result<OperationProgressEvent>
+where
+    F: FnMut(&OperationProgressEvent),
+{
+    let ws_url = get_graphql_ws_url();
+    let mut request = ws_url
+        .as_str()
+        .into_client_request()
+        .context("Invalid GraphQL WebSocket URL")?;
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static(GRAPHQL_WS_PROTOCOL),
+    );
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .context("Failed to connect to GraphQL subscription endpoint")?;
+
+    let init = serde_json::json!({
+        "type": "connection_init",
+        "payload": { "Authorization": access_token },
+    });
+    socket.send(Message::text(init.to_string())).await?;
+
+    // Wait for the server to acknowledge the connection
+    match tokio::time::timeout(CONNECTION_ACK_TIMEOUT, await_connection_ack(&mut socket)).await {
+        Ok(result) => result?,
+        Err(_) => bail!("Timed out waiting for subscription acknowledgement"),
+    }
+
+    let subscribe = serde_json::json!({
+        "id": "1",
+        "type": "subscribe",
+        "payload": {
+            "query": OPERATION_PROGRESS_SUBSCRIPTION,
+            "variables": { "id": operation_id },
+        },
+    });
+    socket.send(Message::text(subscribe.to_string())).await?;
+
+    loop {
+        let message = match tokio::time::timeout(IDLE_TIMEOUT, socket.next()).await {
+            Ok(Some(message)) => message.context("Subscription stream error")?,
+            Ok(None) => bail!("Subscription stream closed before operation finished"),
+            Err(_) => bail!(
+                "No progress received for {}s; subscription stalled",
+                IDLE_TIMEOUT.as_secs()
+            ),
+        };
+
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => bail!("Subscription closed before operation finished"),
+            _ => continue,
+        };
+
+        match parse_server_message(&text)? {
+            ServerMessage::Next { payload } => {
+                let event = parse_progress_payload(payload)?;
+                on_event(&event);
+                match event.phase() {
+                    OperationPhase::InProgress => {},
+                    OperationPhase::Succeeded => {
+                        let _ = socket.close(None).await;
+                        return Ok(event);
+                    },
+                    OperationPhase::Failed => {
+                        let _ = socket.close(None).await;
+                        bail!(
+                            "Operation failed: {}",
+                            event.message.unwrap_or_else(|| event.status.clone())
+                        );
+                    },
+                }
+            },
+            ServerMessage::Ping => {
+                socket
+                    .send(Message::text(r#"{"type":"pong"}"#.to_string()))
+                    .await?;
+            },
+            ServerMessage::Error { payload } => {
+                bail!("Subscription rejected by server: {}", payload);
+            },
+            ServerMessage::Complete => {
+                bail!("Subscription completed before operation finished");
+            },
+            ServerMessage::ConnectionAck | ServerMessage::Pong => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_to_ws_url() {
+        assert_eq!(
+            http_to_ws_url("https://api.pmcp.run/graphql"),
+            "wss://api.pmcp.run/graphql"
+        );
+        assert_eq!(
+            http_to_ws_url("http://localhost:4000/graphql"),
+            "ws://localhost:4000/graphql"
+        );
+        assert_eq!(http_to_ws_url("wss://already/ws"), "wss://already/ws");
+    }
+
+    #[test]
+    fn test_parse_server_messages() {
+        assert!(matches!(
+            parse_server_message(r#"{"type":"connection_ack"}"#).unwrap(),
+            ServerMessage::ConnectionAck
+        ));
+        assert!(matches!(
+            parse_server_message(r#"{"type":"ping"}"#).unwrap(),
+            ServerMessage::Ping
+        ));
+        assert!(matches!(
+            parse_server_message(r#"{"id":"1","type":"complete"}"#).unwrap(),
+            ServerMessage::Complete
+        ));
+    }
+
+    #[test]
+    fn test_parse_progress_payload() {
+        let payload = serde_json::json!({
+            "data": {
+                "onDeploymentOperationProgress": {
+                    "id": "dep-1",
+                    "status": "deploying",
+                    "step": "cloudformation",
+                    "message": null,
+                    "resourceType": "AWS::Lambda::Function",
+                    "logicalResourceId": "McpFunction",
+                    "resourceStatus": "CREATE_IN_PROGRESS",
+                    "timestamp": "2025-01-01T00:00:00Z"
+                }
+            }
+        });
+
+        let event = parse_progress_payload(payload).unwrap();
+        assert_eq!(event.phase(), OperationPhase::InProgress);
+        assert!(event.display_line().contains("McpFunction"));
+        assert!(event.display_line().contains("CREATE_IN_PROGRESS"));
+    }
+
+    #[test]
+    fn test_parse_progress_payload_errors() {
+        let payload = serde_json::json!({
+            "errors": [{ "message": "Unauthorized" }]
+        });
+        let err = parse_progress_payload(payload).unwrap_err();
+        assert!(err.to_string().contains("Unauthorized"));
+    }
+
+    #[test]
+    fn test_event_phase() {
+        let mut event = OperationProgressEvent {
+            id: "op".to_string(),
+            status: "success".to_string(),
+            step: None,
+            message: Some("done".to_string()),
+            resource_type: None,
+            logical_resource_id: None,
+            resource_status: None,
+            timestamp: None,
+        };
+        assert_eq!(event.phase(), OperationPhase::Succeeded);
+
+        event.status = "delete_failed".to_string();
+        assert_eq!(event.phase(), OperationPhase::Failed);
+
+        event.status = "uploading".to_string();
+        assert_eq!(event.phase(), OperationPhase::InProgress);
+        assert_eq!(event.display_line(), "   📤 [uploading] done");
+    }
+}
@@ -4,10 +4,12 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use super::config::DeployConfig;
+use super::lock::LockInfo;
 use super::operations::{AsyncOperation, DestroyResult};
+use super::state::DeployState;
 
 /// Represents a built artifact ready for deployment
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum BuildArtifact {
     /// Native binary (e.g., ARM64 Linux for Lambda)
@@ -33,6 +35,30 @@ pub enum BuildArtifact {
     },
 }
 
+impl BuildArtifact {
+    /// Files on disk that make up the artifact
+    pub fn files(&self) -> Vec<&PathBuf> {
+        let (path, deployment_package) = match self {
+            Self::Binary {
+                path,
+                deployment_package,
+                ..
+            }
+            | Self::Wasm {
+                path,
+                deployment_package,
+                ..
+            }
+            | Self::Custom {
+                path,
+                deployment_package,
+                ..
+            } => (path, deployment_package),
+        };
+        std::iter::once(path).chain(deployment_package).collect()
+    }
+}
+
 /// Deployment outputs from a successful deployment
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DeploymentOutputs {
@@ -109,6 +135,84 @@ pub struct MetricsData {
     pub custom: std::collections::HashMap<String, serde_json::Value>,
 }
 
+impl MetricsData {
+    /// Display metrics in human-readable format, with sparklines for any time series
+    pub fn display(&self) {
+        println!("📊 Metrics ({}):", self.period);
+        println!();
+
+        if let Some(requests) = self.requests {
+            println!("   📨 Requests: {}", requests);
+        }
+
+        if let Some(errors) = self.errors {
+            match self.requests.filter(|r| *r > 0) {
+                Some(requests) => println!(
+                    "   ❌ Errors: {} ({:.2}%)",
+                    errors,
+                    errors as f64 * 100.0 / requests as f64
+                ),
+                None => println!("   ❌ Errors: {}", errors),
+            }
+        }
+
+        let percentiles: Vec<String> = [
+            ("avg", self.avg_latency_ms),
+            ("p50", self.custom_f64("p50_latency_ms")),
+            ("p95", self.custom_f64("p95_latency_ms")),
+            ("p99", self.p99_latency_ms),
+        ]
+        .iter()
+        .filter_map(|(label, value)| value.map(|v| format!("{} {:.0}ms", label, v)))
+        .collect();
+        if !percentiles.is_empty() {
+            println!("   ⏱️  Latency: {}", percentiles.join("  "));
+        }
+
+        if let Some(cold_starts) = self.custom.get("cold_starts").and_then(|v| v.as_u64()) {
+            println!("   🧊 Cold starts: {}", cold_starts);
+        }
+
+        if let Some(series) = self.custom.get("series").and_then(|v| v.as_array()) {
+            let column = |key: &str| -> Vec<f64> {
+                series
+                    .iter()
+                    .map(|point| point.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0))
+                    .collect()
+            };
+            if !series.is_empty() {
+                println!();
+                println!("   requests  {}", sparkline(&column("requests")));
+                println!("   errors    {}", sparkline(&column("errors")));
+            }
+        }
+
+        println!();
+    }
+
+    fn custom_f64(&self, key: &str) -> Option<f64> {
+        self.custom.get(key).and_then(|v| v.as_f64())
+    }
+}
+
+/// Render values as a compact unicode sparkline, scaled to the largest value
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max = values.iter().cloned().fold(0.0_f64, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max <= 0.0 || !v.is_finite() || *v <= 0.0 {
+                BARS[0]
+            } else {
+                let index = ((v / max) * (BARS.len() - 1) as f64).round() as usize;
+                BARS[index.min(BARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
 /// Test results from deployment testing
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -179,6 +283,39 @@ pub trait DeploymentTarget: Send + Sync {
         artifact: BuildArtifact,
     ) -> Result<DeploymentOutputs>;
 
+    /// Deploy, continuing from the checkpoints of an unfinished earlier attempt
+    ///
+    /// Targets that can pick up a partially completed deployment record their
+    /// progress in `state`. By default, this calls `deploy`.
+    async fn deploy_resumable(
+        &self,
+        config: &DeployConfig,
+        artifact: BuildArtifact,
+        _state: &mut DeployState,
+    ) -> Result<DeploymentOutputs> {
+        self.deploy(config, artifact).await
+    }
+
+    /// Take the target's remote deployment lock
+    ///
+    /// Guards against deploys of the same server from other machines, which the
+    /// local lock file cannot see. `force` replaces a lock held by someone else.
+    /// By default, targets have no remote lock.
+    async fn acquire_lock(
+        &self,
+        _config: &DeployConfig,
+        _lock: &LockInfo,
+        _state: &DeployState,
+        _force: bool,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Release the remote deployment lock taken by `acquire_lock`
+    async fn release_lock(&self, _config: &DeployConfig, _lock: &LockInfo) -> Result<()> {
+        Ok(())
+    }
+
     /// Destroy deployment and optionally clean up local files
     ///
     /// This is the legacy synchronous destroy method. For targets that support
@@ -229,3 +366,20 @@ pub trait DeploymentTarget: Send + Sync {
     /// Rollback to a previous version
     async fn rollback(&self, config: &DeployConfig, version: Option<&str>) -> Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_largest_value() {
+        assert_eq!(sparkline(&[0.0, 1.0, 7.0]), "▁▂█");
+        assert_eq!(sparkline(&[3.0, 3.0]), "██");
+    }
+
+    #[test]
+    fn sparkline_handles_empty_and_zero_series() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0.0, 0.0, 0.0]), "▁▁▁");
+    }
+}
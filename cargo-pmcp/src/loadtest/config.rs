@@ -32,6 +32,10 @@
 //! weight = 10
 //! prompt = "summarize"
 //! arguments = { text = "Hello world" }
+//!
+//! [thresholds]
+//! p95_ms = 500
+//! error_rate = 0.01
 //! ```
 //!
 //! Note: The target server URL is NOT part of the config file. It is provided
@@ -95,6 +99,50 @@ pub struct LoadTestConfig {
     /// array-of-tables syntax creates a key called `stage`.
     #[serde(default)]
     pub stage: Vec<Stage>,
+    /// Optional pass/fail criteria checked against the final results.
+    #[serde(default)]
+    pub thresholds: Thresholds,
+}
+
+/// Pass/fail criteria for a load test run.
+///
+/// Every field is optional; unset fields are not checked. Outcomes are
+/// shown in the terminal and in the JSON and HTML reports, and any failed
+/// threshold makes `cargo pmcp loadtest run` exit with an error.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [thresholds]
+/// p95_ms = 500
+/// p99_ms = 1000
+/// error_rate = 0.01
+/// min_rps = 50.0
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Thresholds {
+    /// Maximum allowed P95 success latency in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p95_ms: Option<u64>,
+    /// Maximum allowed P99 success latency in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p99_ms: Option<u64>,
+    /// Maximum allowed error rate as a fraction (0.0..=1.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_rate: Option<f64>,
+    /// Minimum required throughput in requests per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rps: Option<f64>,
+}
+
+impl Thresholds {
+    /// Returns `true` if no threshold is configured.
+    pub fn is_empty(&self) -> bool {
+        self.p95_ms.is_none()
+            && self.p99_ms.is_none()
+            && self.error_rate.is_none()
+            && self.min_rps.is_none()
+    }
 }
 
 /// General load test settings controlling execution parameters.
@@ -240,6 +288,7 @@ impl LoadTestConfig {
     /// - Total weight across all steps is greater than zero
     /// - If stages present: each stage must have `duration_secs > 0`
     /// - If stages absent: require valid `virtual_users` and `duration_secs`
+    /// - `thresholds.error_rate`, if set, is within `0.0..=1.0`
     pub fn validate(&self) -> Result<(), LoadTestError> {
         if self.scenario.is_empty() {
             return Err(LoadTestError::ConfigValidation {
@@ -276,6 +325,16 @@ impl LoadTestConfig {
             }
         }
 
+        if let Some(rate) = self.thresholds.error_rate {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(LoadTestError::ConfigValidation {
+                    message: format!(
+                        "thresholds.error_rate={rate} must be a fraction between 0.0 and 1.0"
+                    ),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -475,6 +534,50 @@ tool = "ping"
         assert_eq!(config.settings.expected_interval_ms, 50);
     }
 
+    #[test]
+    fn test_parse_thresholds() {
+        let toml_str = r#"
+[settings]
+virtual_users = 5
+duration_secs = 30
+timeout_ms = 2000
+
+[[scenario]]
+type = "tools/call"
+weight = 1
+tool = "ping"
+
+[thresholds]
+p95_ms = 500
+error_rate = 0.01
+"#;
+        let config = LoadTestConfig::from_toml(toml_str).unwrap();
+        assert_eq!(config.thresholds.p95_ms, Some(500));
+        assert_eq!(config.thresholds.error_rate, Some(0.01));
+        assert_eq!(config.thresholds.p99_ms, None);
+        assert!(!config.thresholds.is_empty());
+    }
+
+    #[test]
+    fn test_thresholds_default_empty_and_validated() {
+        let toml_str = r#"
+[settings]
+virtual_users = 5
+duration_secs = 30
+timeout_ms = 2000
+
+[[scenario]]
+type = "tools/call"
+weight = 1
+tool = "ping"
+"#;
+        let config = LoadTestConfig::from_toml(toml_str).unwrap();
+        assert!(config.thresholds.is_empty());
+
+        let invalid = format!("{toml_str}\n[thresholds]\nerror_rate = 5.0\n");
+        assert!(LoadTestConfig::from_toml(&invalid).is_err());
+    }
+
     #[test]
     fn test_validate_empty_scenario_fails() {
         let config = LoadTestConfig {
@@ -487,6 +590,7 @@ tool = "ping"
             },
             scenario: vec![],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -518,6 +622,7 @@ tool = "ping"
                 },
             ],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -543,6 +648,7 @@ tool = "ping"
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         assert!(config.validate().is_ok());
     }
@@ -669,6 +775,7 @@ tool = "echo"
                     duration_secs: 0,
                 },
             ],
+            thresholds: Thresholds::default(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -698,6 +805,7 @@ tool = "echo"
                 target_vus: 50,
                 duration_secs: 60,
             }],
+            thresholds: Thresholds::default(),
         };
         assert!(config.validate().is_ok());
     }
@@ -718,6 +826,7 @@ tool = "echo"
                 arguments: serde_json::Value::Null,
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         assert!(!config_no_stages.has_stages());
 
@@ -738,6 +847,7 @@ tool = "echo"
                 target_vus: 10,
                 duration_secs: 30,
             }],
+            thresholds: Thresholds::default(),
         };
         assert!(config_with_stages.has_stages());
     }
@@ -771,6 +881,7 @@ tool = "echo"
                     duration_secs: 20,
                 },
             ],
+            thresholds: Thresholds::default(),
         };
         assert_eq!(config.total_stage_duration(), 110);
 
@@ -788,6 +899,7 @@ tool = "echo"
                 arguments: serde_json::Value::Null,
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         assert_eq!(config_no_stages.total_stage_duration(), 0);
     }
@@ -818,6 +930,7 @@ tool = "echo"
                     duration_secs: 60,
                 },
             ],
+            thresholds: Thresholds::default(),
         };
         assert_eq!(config_with_stages.effective_duration_secs(), 90);
 
@@ -836,6 +949,7 @@ tool = "echo"
                 arguments: serde_json::Value::Null,
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         assert_eq!(config_no_stages.effective_duration_secs(), 120);
     }
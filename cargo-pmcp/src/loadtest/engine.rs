@@ -14,7 +14,9 @@ use crate::loadtest::breaking::{BreakingPoint, BreakingPointDetector};
 use crate::loadtest::config::LoadTestConfig;
 use crate::loadtest::display::display_loop;
 use crate::loadtest::error::LoadTestError;
-use crate::loadtest::metrics::{MetricsRecorder, MetricsSnapshot, RequestSample};
+use crate::loadtest::metrics::{
+    MetricsRecorder, MetricsSnapshot, RequestSample, Timeline, TimelineBucket,
+};
 use crate::loadtest::vu::{vu_loop, ActiveVuCounter};
 
 use pmcp::client::http_middleware::HttpMiddlewareChain;
@@ -226,6 +228,7 @@ impl LoadTestEngine {
             display_tx,
             aggregator_cancel,
             expected_interval_ms,
+            test_start,
             ramp_up_end,
             None, // No stage label for flat mode
             bp_holder_clone,
@@ -268,7 +271,7 @@ impl LoadTestEngine {
         tracker.wait().await;
 
         // Wait for aggregator to finish processing remaining samples
-        let _ = aggregator_handle.await;
+        let timeline = aggregator_handle.await.unwrap_or_default();
 
         // Wait for display to render final state
         let _ = display_handle.await;
@@ -282,6 +285,7 @@ impl LoadTestEngine {
             elapsed: test_start.elapsed(),
             final_active_vus: active_vus.get(),
             breaking_point,
+            timeline,
         })
     }
 
@@ -341,6 +345,7 @@ impl LoadTestEngine {
             display_tx,
             aggregator_cancel,
             expected_interval_ms,
+            test_start,
             ramp_up_end,
             stage_label_clone,
             bp_holder_clone,
@@ -470,7 +475,7 @@ impl LoadTestEngine {
         tracker.wait().await;
 
         // Wait for aggregator and display to finish
-        let _ = aggregator_handle.await;
+        let timeline = aggregator_handle.await.unwrap_or_default();
         let _ = display_handle.await;
 
         // Propagate any scheduler error
@@ -485,6 +490,7 @@ impl LoadTestEngine {
             elapsed: test_start.elapsed(),
             final_active_vus: active_vus.get(),
             breaking_point,
+            timeline,
         })
     }
 }
@@ -500,6 +506,8 @@ pub struct LoadTestResult {
     pub final_active_vus: u32,
     /// Breaking point event, if degradation was detected during the run.
     pub breaking_point: Option<BreakingPoint>,
+    /// Per-second request counts over the whole run, including ramp-up.
+    pub timeline: Vec<TimelineBucket>,
 }
 
 /// Metrics aggregator task for flat load mode.
@@ -520,12 +528,14 @@ async fn metrics_aggregator(
     display_tx: watch::Sender<DisplayState>,
     cancel: CancellationToken,
     expected_interval_ms: u64,
+    test_start: Instant,
     ramp_up_end: Instant,
     stage_label: Option<String>,
     bp_holder: Arc<std::sync::Mutex<Option<BreakingPoint>>>,
     active_vus: ActiveVuCounter,
-) {
+) -> Vec<TimelineBucket> {
     let mut live = MetricsRecorder::new(expected_interval_ms);
+    let mut timeline = Timeline::new(test_start);
     let mut report = MetricsRecorder::new(expected_interval_ms);
     let mut detector = BreakingPointDetector::with_default_window();
     let mut bp_warning: Option<String> = None;
@@ -543,6 +553,7 @@ async fn metrics_aggregator(
                         report.record(&sample);
                     }
                     live.record(&sample);
+                    timeline.record(&sample, active_vus.get());
                 }
                 let snapshot = live.snapshot();
                 // Run breaking point detection on each tick
@@ -567,6 +578,7 @@ async fn metrics_aggregator(
                             report.record(&sample);
                         }
                         live.record(&sample);
+                        timeline.record(&sample, active_vus.get());
                     }
                     None => {
                         // All senders dropped -- VUs are done
@@ -586,6 +598,7 @@ async fn metrics_aggregator(
                         report.record(&sample);
                     }
                     live.record(&sample);
+                    timeline.record(&sample, active_vus.get());
                 }
                 let _ = display_tx.send(DisplayState {
                     snapshot: report.snapshot(),
@@ -596,6 +609,7 @@ async fn metrics_aggregator(
             }
         }
     }
+    timeline.into_buckets()
 }
 
 /// Metrics aggregator task for staged load mode.
@@ -609,12 +623,14 @@ async fn metrics_aggregator_with_label(
     display_tx: watch::Sender<DisplayState>,
     cancel: CancellationToken,
     expected_interval_ms: u64,
+    test_start: Instant,
     ramp_up_end: Instant,
     stage_label: Arc<std::sync::Mutex<Option<String>>>,
     bp_holder: Arc<std::sync::Mutex<Option<BreakingPoint>>>,
     active_vus: ActiveVuCounter,
-) {
+) -> Vec<TimelineBucket> {
     let mut live = MetricsRecorder::new(expected_interval_ms);
+    let mut timeline = Timeline::new(test_start);
     let mut report = MetricsRecorder::new(expected_interval_ms);
     let mut detector = BreakingPointDetector::with_default_window();
     let mut bp_warning: Option<String> = None;
@@ -631,6 +647,7 @@ async fn metrics_aggregator_with_label(
                         report.record(&sample);
                     }
                     live.record(&sample);
+                    timeline.record(&sample, active_vus.get());
                 }
                 let label = stage_label.lock().unwrap().clone();
                 let snapshot = live.snapshot();
@@ -656,6 +673,7 @@ async fn metrics_aggregator_with_label(
                             report.record(&sample);
                         }
                         live.record(&sample);
+                        timeline.record(&sample, active_vus.get());
                     }
                     None => {
                         let label = stage_label.lock().unwrap().clone();
@@ -674,6 +692,7 @@ async fn metrics_aggregator_with_label(
                         report.record(&sample);
                     }
                     live.record(&sample);
+                    timeline.record(&sample, active_vus.get());
                 }
                 let label = stage_label.lock().unwrap().clone();
                 let _ = display_tx.send(DisplayState {
@@ -685,6 +704,7 @@ async fn metrics_aggregator_with_label(
             }
         }
     }
+    timeline.into_buckets()
 }
 
 /// Ctrl+C handler with two-phase shutdown.
@@ -709,7 +729,7 @@ async fn handle_ctrl_c(cancel: CancellationToken) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Stage};
+    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Stage, Thresholds};
     use crate::loadtest::metrics::{OperationType, RequestSample};
 
     fn minimal_config() -> LoadTestConfig {
@@ -727,6 +747,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         }
     }
 
@@ -774,6 +795,7 @@ mod tests {
             cancel,
             100,
             ramp_up_end,
+            ramp_up_end,
             None,
             bp_holder,
             ActiveVuCounter::new(),
@@ -802,6 +824,7 @@ mod tests {
         let bp_holder = Arc::new(std::sync::Mutex::new(None));
 
         // Set ramp_up_end 50ms in the future
+        let test_start = Instant::now();
         let ramp_up_end = Instant::now() + Duration::from_millis(50);
 
         // Send 3 samples immediately (before ramp_up_end -- ramp-up period)
@@ -825,11 +848,12 @@ mod tests {
         drop(sample_tx);
 
         // Run aggregator -- final snapshot uses the report recorder (post-ramp-up only)
-        metrics_aggregator(
+        let timeline = metrics_aggregator(
             sample_rx,
             display_tx,
             cancel,
             10_000,
+            test_start,
             ramp_up_end,
             None,
             bp_holder,
@@ -844,6 +868,9 @@ mod tests {
             "Expected 2 post-ramp-up requests, got {}",
             state.snapshot.total_requests
         );
+        // The timeline covers the whole run, including ramp-up
+        let timeline_requests: u64 = timeline.iter().map(|b| b.requests).sum();
+        assert_eq!(timeline_requests, 5);
     }
 
     #[tokio::test]
@@ -865,6 +892,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         let engine = LoadTestEngine::new(config, "http://127.0.0.1:1".to_string())
             .with_no_color(true)
@@ -890,6 +918,7 @@ mod tests {
             elapsed: Duration::from_secs(30),
             final_active_vus: 5,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         assert_eq!(result.elapsed, Duration::from_secs(30));
         assert_eq!(result.final_active_vus, 5);
@@ -925,6 +954,7 @@ mod tests {
                     duration_secs: 10,
                 },
             ],
+            thresholds: Thresholds::default(),
         };
         let engine =
             LoadTestEngine::new(config, "http://localhost:3000".to_string()).with_no_color(true);
@@ -0,0 +1,507 @@
+//! Standalone HTML report for load test results.
+//!
+//! Renders a [`LoadTestReport`] as a single self-contained HTML file with
+//! inline CSS and SVG charts -- no scripts, fonts, or network requests -- so
+//! it can be attached to a ticket or emailed to stakeholders as-is.
+//!
+//! Sections:
+//! - Summary cards (requests, throughput, error rate, P95, VUs)
+//! - Threshold outcomes from the config's `[thresholds]` section
+//! - Latency percentiles (P50/P95/P99) overall and per tool
+//! - Requests per second over time, with errors overlaid
+//! - Error breakdown per tool and category
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::loadtest::report::{LoadTestReport, TimelinePoint};
+
+/// Chart colors for P50, P95, and P99 bars.
+const PERCENTILE_COLORS: [&str; 3] = ["#4e79a7", "#f28e2b", "#e15759"];
+
+/// Width of the SVG charts in pixels.
+const CHART_WIDTH: f64 = 760.0;
+
+/// Write the HTML report for `report` to `path`.
+///
+/// Usually called with the JSON report path and an `.html` extension, so
+/// both files share a timestamped name.
+pub fn write_html_report(report: &LoadTestReport, path: &Path) -> Result<PathBuf, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, render_html(report))?;
+    Ok(path.to_path_buf())
+}
+
+/// Render `report` as a standalone HTML document.
+pub fn render_html(report: &LoadTestReport) -> String {
+    let mut body = String::new();
+    body.push_str(&summary_section(report));
+    body.push_str(&thresholds_section(report));
+    body.push_str(&latency_section(report));
+    body.push_str(&throughput_section(&report.timeline));
+    body.push_str(&errors_section(report));
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Load test report - {target}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Load test report</h1>
+<p class="meta">{target} &middot; {timestamp} &middot; {duration:.1}s</p>
+{body}
+</body>
+</html>
+"#,
+        target = escape(&report.target_url),
+        timestamp = escape(&report.timestamp),
+        duration = report.duration_secs,
+    )
+}
+
+const STYLE: &str = "\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:2rem auto;max-width:820px;color:#222}\
+h1{margin-bottom:.2rem}h2{margin-top:2rem;border-bottom:1px solid #ddd;padding-bottom:.3rem}\
+.meta{color:#666;margin-top:0}\
+.cards{display:flex;flex-wrap:wrap;gap:.8rem}\
+.card{border:1px solid #ddd;border-radius:6px;padding:.6rem 1rem;min-width:120px}\
+.card .value{font-size:1.4rem;font-weight:600}.card .label{color:#666;font-size:.85rem}\
+table{border-collapse:collapse;width:100%}th,td{text-align:left;padding:.35rem .6rem;border-bottom:1px solid #eee}\
+td.num,th.num{text-align:right}\
+.pass{color:#1a7f37;font-weight:600}.fail{color:#cf222e;font-weight:600}\
+.warn{background:#fff4e5;border:1px solid #f0b35a;border-radius:6px;padding:.6rem 1rem}\
+.legend span{display:inline-block;margin-right:1rem}.swatch{display:inline-block;width:.8rem;height:.8rem;margin-right:.3rem;vertical-align:middle}\
+.empty{color:#666;font-style:italic}";
+
+fn summary_section(report: &LoadTestReport) -> String {
+    let metrics = &report.metrics;
+    let peak_vus = report
+        .timeline
+        .iter()
+        .map(|p| p.active_vus)
+        .max()
+        .unwrap_or(report.config.virtual_users);
+    let cards = [
+        (metrics.total_requests.to_string(), "requests"),
+        (format!("{:.1}", metrics.throughput_rps), "requests/sec"),
+        (format!("{:.2}%", metrics.error_rate * 100.0), "error rate"),
+        (format!("{} ms", metrics.latency.p95_ms), "P95 latency"),
+        (peak_vus.to_string(), "peak VUs"),
+    ];
+
+    let mut html = String::from("<h2>Summary</h2>\n<div class=\"cards\">\n");
+    for (value, label) in cards {
+        html.push_str(&format!(
+            "<div class=\"card\"><div class=\"value\">{value}</div><div class=\"label\">{label}</div></div>\n"
+        ));
+    }
+    html.push_str("</div>\n");
+
+    if report.breaking_point.detected {
+        html.push_str(&format!(
+            "<p class=\"warn\">Breaking point detected at {} VUs: {}</p>\n",
+            report.breaking_point.vus.unwrap_or_default(),
+            escape(report.breaking_point.detail.as_deref().unwrap_or("")),
+        ));
+    }
+    html
+}
+
+fn thresholds_section(report: &LoadTestReport) -> String {
+    let mut html = String::from("<h2>Thresholds</h2>\n");
+    if report.thresholds.is_empty() {
+        html.push_str(
+            "<p class=\"empty\">No thresholds configured. Add a [thresholds] section to the loadtest config to check results automatically.</p>\n",
+        );
+        return html;
+    }
+
+    html.push_str(
+        "<table>\n<tr><th>Threshold</th><th class=\"num\">Limit</th><th class=\"num\">Actual</th><th>Result</th></tr>\n",
+    );
+    for outcome in &report.thresholds {
+        let (class, label) = if outcome.passed {
+            ("pass", "PASS")
+        } else {
+            ("fail", "FAIL")
+        };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"{class}\">{label}</td></tr>\n",
+            escape(&outcome.metric),
+            format_threshold_value(&outcome.metric, outcome.limit),
+            format_threshold_value(&outcome.metric, outcome.actual),
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn format_threshold_value(metric: &str, value: f64) -> String {
+    match metric {
+        "error_rate" => format!("{:.2}%", value * 100.0),
+        "min_rps" => format!("{value:.1} rps"),
+        _ => format!("{value:.0} ms"),
+    }
+}
+
+fn latency_section(report: &LoadTestReport) -> String {
+    let latency = &report.metrics.latency;
+    let mut rows = vec![(
+        "All requests".to_string(),
+        [latency.p50_ms, latency.p95_ms, latency.p99_ms],
+    )];
+    let mut tools: Vec<_> = report.per_tool.iter().collect();
+    tools.sort_by(|a, b| a.0.cmp(b.0));
+    rows.extend(tools.into_iter().map(|(name, tool)| {
+        (
+            name.clone(),
+            [
+                tool.latency.p50_ms,
+                tool.latency.p95_ms,
+                tool.latency.p99_ms,
+            ],
+        )
+    }));
+
+    let mut html = String::from("<h2>Latency percentiles</h2>\n");
+    html.push_str(&legend(&[
+        ("P50", PERCENTILE_COLORS[0]),
+        ("P95", PERCENTILE_COLORS[1]),
+        ("P99", PERCENTILE_COLORS[2]),
+    ]));
+    html.push_str(&latency_chart(&rows));
+    html
+}
+
+/// Horizontal grouped bar chart: one group of P50/P95/P99 bars per row.
+fn latency_chart(rows: &[(String, [u64; 3])]) -> String {
+    const LABEL_WIDTH: f64 = 180.0;
+    const BAR_HEIGHT: f64 = 10.0;
+    const GROUP_GAP: f64 = 14.0;
+
+    let max = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().copied())
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let bar_space = CHART_WIDTH - LABEL_WIDTH - 70.0;
+    let group_height = BAR_HEIGHT * 3.0 + GROUP_GAP;
+    let height = group_height * rows.len() as f64;
+
+    let mut svg = format!(
+        "<svg width=\"{CHART_WIDTH}\" height=\"{height}\" viewBox=\"0 0 {CHART_WIDTH} {height}\" role=\"img\" aria-label=\"Latency percentiles\">\n"
+    );
+    for (i, (name, values)) in rows.iter().enumerate() {
+        let top = i as f64 * group_height;
+        svg.push_str(&format!(
+            "<text x=\"0\" y=\"{:.1}\" font-size=\"12\">{}</text>\n",
+            top + BAR_HEIGHT * 2.0,
+            escape(&truncate(name, 28)),
+        ));
+        for (j, value) in values.iter().enumerate() {
+            let y = top + BAR_HEIGHT * j as f64;
+            let width = (*value as f64 / max * bar_space).max(1.0);
+            svg.push_str(&format!(
+                "<rect x=\"{LABEL_WIDTH}\" y=\"{y:.1}\" width=\"{width:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                BAR_HEIGHT - 1.0,
+                PERCENTILE_COLORS[j],
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\">{value} ms</text>\n",
+                LABEL_WIDTH + width + 4.0,
+                y + BAR_HEIGHT - 2.0,
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn throughput_section(timeline: &[TimelinePoint]) -> String {
+    let mut html = String::from("<h2>Requests per second</h2>\n");
+    if timeline.is_empty() {
+        html.push_str("<p class=\"empty\">No requests were recorded.</p>\n");
+        return html;
+    }
+    html.push_str(&legend(&[("requests", "#4e79a7"), ("errors", "#e15759")]));
+    html.push_str(&throughput_chart(timeline));
+    html
+}
+
+/// Line chart of requests and errors per second over the run.
+fn throughput_chart(timeline: &[TimelinePoint]) -> String {
+    const HEIGHT: f64 = 220.0;
+    const LEFT: f64 = 50.0;
+    const BOTTOM: f64 = 24.0;
+
+    let plot_width = CHART_WIDTH - LEFT - 10.0;
+    let plot_height = HEIGHT - BOTTOM - 10.0;
+    let max = timeline
+        .iter()
+        .map(|p| p.requests)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let last_second = timeline.last().map_or(0, |p| p.second).max(1) as f64;
+
+    let point = |second: u64, value: u64| {
+        let x = LEFT + second as f64 / last_second * plot_width;
+        let y = 10.0 + plot_height - value as f64 / max * plot_height;
+        format!("{x:.1},{y:.1}")
+    };
+    let requests: Vec<String> = timeline
+        .iter()
+        .map(|p| point(p.second, p.requests))
+        .collect();
+    let errors: Vec<String> = timeline.iter().map(|p| point(p.second, p.errors)).collect();
+    let axis_y = 10.0 + plot_height;
+
+    format!(
+        "<svg width=\"{CHART_WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} {HEIGHT}\" role=\"img\" aria-label=\"Requests per second over time\">\n\
+<line x1=\"{LEFT}\" y1=\"10\" x2=\"{LEFT}\" y2=\"{axis_y:.1}\" stroke=\"#999\"/>\n\
+<line x1=\"{LEFT}\" y1=\"{axis_y:.1}\" x2=\"{right:.1}\" y2=\"{axis_y:.1}\" stroke=\"#999\"/>\n\
+<text x=\"{label_x:.1}\" y=\"16\" font-size=\"10\" text-anchor=\"end\">{max}</text>\n\
+<text x=\"{label_x:.1}\" y=\"{axis_y:.1}\" font-size=\"10\" text-anchor=\"end\">0</text>\n\
+<text x=\"{LEFT}\" y=\"{time_y:.1}\" font-size=\"10\">0s</text>\n\
+<text x=\"{right:.1}\" y=\"{time_y:.1}\" font-size=\"10\" text-anchor=\"end\">{last_second}s</text>\n\
+<polyline fill=\"none\" stroke=\"#4e79a7\" stroke-width=\"1.5\" points=\"{requests}\"/>\n\
+<polyline fill=\"none\" stroke=\"#e15759\" stroke-width=\"1.5\" points=\"{errors}\"/>\n\
+</svg>\n",
+        right = LEFT + plot_width,
+        label_x = LEFT - 4.0,
+        time_y = HEIGHT - 6.0,
+        requests = requests.join(" "),
+        errors = errors.join(" "),
+    )
+}
+
+fn errors_section(report: &LoadTestReport) -> String {
+    let mut html = String::from("<h2>Errors</h2>\n");
+    if report.metrics.error_count == 0 {
+        html.push_str("<p class=\"empty\">No errors.</p>\n");
+        return html;
+    }
+
+    let categories: BTreeSet<&str> = report
+        .errors
+        .keys()
+        .chain(report.per_tool.values().flat_map(|t| t.errors.keys()))
+        .map(String::as_str)
+        .collect();
+
+    html.push_str("<table>\n<tr><th>Tool</th><th class=\"num\">Requests</th><th class=\"num\">Error rate</th>");
+    for category in &categories {
+        html.push_str(&format!("<th class=\"num\">{}</th>", escape(category)));
+    }
+    html.push_str("</tr>\n");
+
+    let mut tools: Vec<_> = report.per_tool.iter().collect();
+    tools.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, tool) in tools {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.2}%</td>",
+            escape(name),
+            tool.total_requests,
+            tool.error_rate * 100.0,
+        ));
+        for category in &categories {
+            let count = tool.errors.get(*category).copied().unwrap_or(0);
+            html.push_str(&format!("<td class=\"num\">{count}</td>"));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str(&format!(
+        "<tr><th>All requests</th><th class=\"num\">{}</th><th class=\"num\">{:.2}%</th>",
+        report.metrics.total_requests,
+        report.metrics.error_rate * 100.0,
+    ));
+    for category in &categories {
+        let count = report.errors.get(*category).copied().unwrap_or(0);
+        html.push_str(&format!("<th class=\"num\">{count}</th>"));
+    }
+    html.push_str("</tr>\n</table>\n");
+    html
+}
+
+fn legend(entries: &[(&str, &str)]) -> String {
+    let mut html = String::from("<p class=\"legend\">");
+    for (label, color) in entries {
+        html.push_str(&format!(
+            "<span><span class=\"swatch\" style=\"background:{color}\"></span>{label}</span>"
+        ));
+    }
+    html.push_str("</p>\n");
+    html
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().take(max_chars - 1).collect();
+        format!("{kept}…")
+    }
+}
+
+/// Escape text for safe inclusion in HTML content and attributes.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loadtest::report::{
+        BreakingPointReport, LatencyMetrics, ReportConfig, ReportMetrics, ThresholdOutcome,
+        ToolLatencyMetrics, ToolReportMetrics,
+    };
+    use std::collections::HashMap;
+
+    fn test_report() -> LoadTestReport {
+        LoadTestReport {
+            schema_version: "1.1".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            target_url: "http://localhost:3000/mcp?a=1&b=<2>".to_string(),
+            duration_secs: 3.0,
+            config: ReportConfig {
+                virtual_users: 2,
+                duration_secs: 3,
+                timeout_ms: 5000,
+                expected_interval_ms: 100,
+                scenario: Vec::new(),
+            },
+            metrics: ReportMetrics {
+                total_requests: 30,
+                success_count: 27,
+                error_count: 3,
+                error_rate: 0.1,
+                throughput_rps: 10.0,
+                latency: LatencyMetrics {
+                    p50_ms: 20,
+                    p95_ms: 80,
+                    p99_ms: 120,
+                    error_p50_ms: 0,
+                    error_p95_ms: 0,
+                    error_p99_ms: 0,
+                },
+                operation_counts: HashMap::new(),
+                operation_errors: HashMap::new(),
+            },
+            errors: HashMap::from([("timeout".to_string(), 3)]),
+            per_tool: HashMap::from([(
+                "search".to_string(),
+                ToolReportMetrics {
+                    total_requests: 30,
+                    success_count: 27,
+                    error_count: 3,
+                    error_rate: 0.1,
+                    latency: ToolLatencyMetrics {
+                        p50_ms: 20,
+                        p95_ms: 80,
+                        p99_ms: 120,
+                        min_ms: 5,
+                        max_ms: 150,
+                        mean_ms: 30.0,
+                    },
+                    errors: HashMap::from([("timeout".to_string(), 3)]),
+                },
+            )]),
+            breaking_point: BreakingPointReport {
+                detected: false,
+                vus: None,
+                reason: None,
+                detail: None,
+                timestamp: None,
+            },
+            timeline: (0..3)
+                .map(|second| TimelinePoint {
+                    second,
+                    requests: 10,
+                    errors: 1,
+                    active_vus: 2,
+                })
+                .collect(),
+            thresholds: vec![ThresholdOutcome {
+                metric: "p95_ms".to_string(),
+                limit: 50.0,
+                actual: 80.0,
+                passed: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_contains_all_sections() {
+        let html = render_html(&test_report());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        for heading in [
+            "Summary",
+            "Thresholds",
+            "Latency percentiles",
+            "Requests per second",
+            "Errors",
+        ] {
+            assert!(html.contains(&format!("<h2>{heading}</h2>")), "{heading}");
+        }
+        assert!(html.contains("<polyline"));
+        assert!(html.contains("search"));
+        assert!(!html.contains("<script"), "report must not need scripts");
+    }
+
+    #[test]
+    fn test_render_threshold_failures() {
+        let html = render_html(&test_report());
+        assert!(html.contains("class=\"fail\">FAIL"));
+        assert!(html.contains("50 ms"));
+    }
+
+    #[test]
+    fn test_render_escapes_text() {
+        let html = render_html(&test_report());
+        assert!(html.contains("a=1&amp;b=&lt;2&gt;"));
+        assert!(!html.contains("b=<2>"));
+    }
+
+    #[test]
+    fn test_render_empty_run() {
+        let mut report = test_report();
+        report.timeline.clear();
+        report.thresholds.clear();
+        report.per_tool.clear();
+        report.errors.clear();
+        report.metrics.error_count = 0;
+        let html = render_html(&report);
+        assert!(html.contains("No requests were recorded."));
+        assert!(html.contains("No thresholds configured."));
+        assert!(html.contains("No errors."));
+    }
+
+    #[test]
+    fn test_write_html_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports").join("loadtest.html");
+        let written = write_html_report(&test_report(), &path).unwrap();
+        assert_eq!(written, path);
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .contains("Load test report"));
+    }
+}
@@ -553,11 +553,105 @@ impl MetricsRecorder {
     }
 }
 
+/// Requests completed during one second of a load test.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimelineBucket {
+    /// Seconds since the test started.
+    pub second: u64,
+    /// Requests completed during this second.
+    pub requests: u64,
+    /// Failed requests completed during this second.
+    pub errors: u64,
+    /// Highest active VU count observed during this second.
+    pub active_vus: u32,
+}
+
+/// Per-second request counts over a whole run, for throughput-over-time charts.
+///
+/// Unlike the report [`MetricsRecorder`], the timeline includes ramp-up so the
+/// chart shows the full load shape. Seconds without samples are kept as empty
+/// buckets so gaps stay visible.
+#[derive(Debug)]
+pub struct Timeline {
+    start: Instant,
+    buckets: Vec<TimelineBucket>,
+}
+
+impl Timeline {
+    /// Create an empty timeline measured from `start`.
+    pub fn new(start: Instant) -> Self {
+        Self {
+            start,
+            buckets: Vec::new(),
+        }
+    }
+
+    /// Count `sample` in the bucket for the second it completed in.
+    pub fn record(&mut self, sample: &RequestSample, active_vus: u32) {
+        let second = sample
+            .timestamp
+            .saturating_duration_since(self.start)
+            .as_secs();
+        while self.buckets.len() as u64 <= second {
+            let next = self.buckets.len() as u64;
+            self.buckets.push(TimelineBucket {
+                second: next,
+                ..TimelineBucket::default()
+            });
+        }
+        let bucket = &mut self.buckets[second as usize];
+        bucket.requests += 1;
+        if sample.result.is_err() {
+            bucket.errors += 1;
+        }
+        bucket.active_vus = bucket.active_vus.max(active_vus);
+    }
+
+    /// Consume the timeline, returning its buckets in time order.
+    pub fn into_buckets(self) -> Vec<TimelineBucket> {
+        self.buckets
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::loadtest::error::McpError;
 
+    #[test]
+    fn test_timeline_buckets_by_second() {
+        let start = Instant::now();
+        let mut timeline = Timeline::new(start);
+
+        let mut first = RequestSample::success(OperationType::ToolsCall, Duration::ZERO, None);
+        first.timestamp = start;
+        let mut late = RequestSample::error(
+            OperationType::ToolsCall,
+            Duration::ZERO,
+            McpError::Timeout,
+            None,
+        );
+        late.timestamp = start + Duration::from_millis(2500);
+        timeline.record(&first, 3);
+        timeline.record(&late, 5);
+        timeline.record(&late, 4);
+
+        let buckets = timeline.into_buckets();
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].requests, 1);
+        assert_eq!(buckets[0].active_vus, 3);
+        assert_eq!(
+            buckets[1],
+            TimelineBucket {
+                second: 1,
+                ..TimelineBucket::default()
+            }
+        );
+        assert_eq!(buckets[2].requests, 2);
+        assert_eq!(buckets[2].errors, 2);
+        assert_eq!(buckets[2].active_vus, 5);
+    }
+
     #[test]
     fn test_new_recorder_has_zero_counts() {
         let recorder = MetricsRecorder::new(100);
@@ -1,7 +1,7 @@
 //! Load testing engine for MCP servers.
 //!
 //! Provides typed TOML configuration, an MCP-aware HTTP client,
-//! error classification, and HdrHistogram-based metrics.
+//! error classification, HdrHistogram-based metrics, and JSON/HTML reports.
 
 pub mod breaking;
 pub mod client;
@@ -9,6 +9,7 @@ pub mod config;
 pub mod display;
 pub mod engine;
 pub mod error;
+pub mod html;
 pub mod metrics;
 pub mod report;
 pub mod summary;
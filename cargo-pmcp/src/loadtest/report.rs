@@ -1,14 +1,16 @@
 //! JSON report serialization for load test results.
 //!
 //! Produces a schema-versioned JSON report file containing latency percentiles,
-//! throughput, error classification, and the full resolved config for
-//! reproducibility. Designed for CI/CD pipeline consumption.
+//! throughput, error classification, threshold outcomes, and the full resolved
+//! config for reproducibility. Designed for CI/CD pipeline consumption. The
+//! same [`LoadTestReport`] also drives the HTML report in
+//! [`crate::loadtest::html`].
 
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::loadtest::config::LoadTestConfig;
+use crate::loadtest::config::{LoadTestConfig, Thresholds};
 use crate::loadtest::engine::LoadTestResult;
 
 /// Schema version for the JSON report format.
@@ -43,6 +45,36 @@ pub struct LoadTestReport {
     pub per_tool: HashMap<String, ToolReportMetrics>,
     /// Breaking point detection result.
     pub breaking_point: BreakingPointReport,
+    /// Per-second throughput over the whole run, including ramp-up.
+    pub timeline: Vec<TimelinePoint>,
+    /// Outcome of each configured `[thresholds]` entry (empty if none).
+    pub thresholds: Vec<ThresholdOutcome>,
+}
+
+/// Requests completed during one second of the run.
+#[derive(Debug, Serialize)]
+pub struct TimelinePoint {
+    /// Seconds since the test started.
+    pub second: u64,
+    /// Requests completed during this second (the RPS for this second).
+    pub requests: u64,
+    /// Failed requests completed during this second.
+    pub errors: u64,
+    /// Highest active VU count observed during this second.
+    pub active_vus: u32,
+}
+
+/// Result of checking one configured threshold.
+#[derive(Debug, Serialize)]
+pub struct ThresholdOutcome {
+    /// Threshold name as written in the config (e.g., `"p95_ms"`).
+    pub metric: String,
+    /// Configured limit.
+    pub limit: f64,
+    /// Measured value.
+    pub actual: f64,
+    /// Whether the measured value is within the limit.
+    pub passed: bool,
 }
 
 /// Breaking point detection result for the JSON report.
@@ -240,6 +272,36 @@ impl LoadTestReport {
             },
         };
 
+        let timeline = result
+            .timeline
+            .iter()
+            .map(|bucket| TimelinePoint {
+                second: bucket.second,
+                requests: bucket.requests,
+                errors: bucket.errors,
+                active_vus: bucket.active_vus,
+            })
+            .collect();
+
+        let metrics = ReportMetrics {
+            total_requests: snap.total_requests,
+            success_count: snap.success_count,
+            error_count: snap.error_count,
+            error_rate: snap.error_rate,
+            throughput_rps,
+            latency: LatencyMetrics {
+                p50_ms: snap.p50,
+                p95_ms: snap.p95,
+                p99_ms: snap.p99,
+                error_p50_ms: snap.error_p50,
+                error_p95_ms: snap.error_p95,
+                error_p99_ms: snap.error_p99,
+            },
+            operation_counts,
+            operation_errors,
+        };
+        let thresholds = evaluate_thresholds(&config.thresholds, &metrics);
+
         Self {
             schema_version: SCHEMA_VERSION.to_string(),
             timestamp,
@@ -252,28 +314,55 @@ impl LoadTestReport {
                 expected_interval_ms: config.settings.expected_interval_ms,
                 scenario: scenario_values,
             },
-            metrics: ReportMetrics {
-                total_requests: snap.total_requests,
-                success_count: snap.success_count,
-                error_count: snap.error_count,
-                error_rate: snap.error_rate,
-                throughput_rps,
-                latency: LatencyMetrics {
-                    p50_ms: snap.p50,
-                    p95_ms: snap.p95,
-                    p99_ms: snap.p99,
-                    error_p50_ms: snap.error_p50,
-                    error_p95_ms: snap.error_p95,
-                    error_p99_ms: snap.error_p99,
-                },
-                operation_counts,
-                operation_errors,
-            },
+            metrics,
             errors: snap.error_category_counts.clone(),
             per_tool,
             breaking_point: breaking_point_report,
+            timeline,
+            thresholds,
         }
     }
+
+    /// Returns `true` if every configured threshold passed.
+    pub fn thresholds_passed(&self) -> bool {
+        self.thresholds.iter().all(|t| t.passed)
+    }
+}
+
+/// Check the configured thresholds against the aggregate metrics.
+///
+/// Latency and error-rate thresholds are upper bounds; `min_rps` is a lower
+/// bound. Unset thresholds produce no outcome.
+pub fn evaluate_thresholds(
+    thresholds: &Thresholds,
+    metrics: &ReportMetrics,
+) -> Vec<ThresholdOutcome> {
+    let upper = |metric: &str, limit: f64, actual: f64| ThresholdOutcome {
+        metric: metric.to_string(),
+        limit,
+        actual,
+        passed: actual <= limit,
+    };
+
+    let mut outcomes = Vec::new();
+    if let Some(limit) = thresholds.p95_ms {
+        outcomes.push(upper("p95_ms", limit as f64, metrics.latency.p95_ms as f64));
+    }
+    if let Some(limit) = thresholds.p99_ms {
+        outcomes.push(upper("p99_ms", limit as f64, metrics.latency.p99_ms as f64));
+    }
+    if let Some(limit) = thresholds.error_rate {
+        outcomes.push(upper("error_rate", limit, metrics.error_rate));
+    }
+    if let Some(limit) = thresholds.min_rps {
+        outcomes.push(ThresholdOutcome {
+            metric: "min_rps".to_string(),
+            limit,
+            actual: metrics.throughput_rps,
+            passed: metrics.throughput_rps >= limit,
+        });
+    }
+    outcomes
 }
 
 /// Write a JSON report file to the `.pmcp/reports/` directory.
@@ -316,9 +405,9 @@ pub fn report_filename(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings};
+    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Thresholds};
     use crate::loadtest::engine::LoadTestResult;
-    use crate::loadtest::metrics::{MetricsSnapshot, OperationType, ToolSnapshot};
+    use crate::loadtest::metrics::{MetricsSnapshot, OperationType, TimelineBucket, ToolSnapshot};
     use std::collections::HashMap;
     use std::time::Duration;
 
@@ -337,6 +426,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         }
     }
 
@@ -369,6 +459,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         }
     }
 
@@ -653,6 +744,55 @@ mod tests {
         assert_eq!(parsed["breaking_point"]["reason"], "error_rate_spike");
     }
 
+    #[test]
+    fn test_report_thresholds() {
+        let mut config = test_config();
+        config.thresholds = Thresholds {
+            p95_ms: Some(100),
+            error_rate: Some(0.1),
+            min_rps: Some(10.0),
+            ..Thresholds::default()
+        };
+        let report =
+            LoadTestReport::from_result(&test_result(), &config, "http://localhost:3000/mcp");
+
+        // p95 120ms > 100ms fails; 5% errors and 16.7 rps pass
+        let outcomes: Vec<(&str, bool)> = report
+            .thresholds
+            .iter()
+            .map(|t| (t.metric.as_str(), t.passed))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![("p95_ms", false), ("error_rate", true), ("min_rps", true)]
+        );
+        assert!(!report.thresholds_passed());
+
+        let report = LoadTestReport::from_result(
+            &test_result(),
+            &test_config(),
+            "http://localhost:3000/mcp",
+        );
+        assert!(report.thresholds.is_empty());
+        assert!(report.thresholds_passed());
+    }
+
+    #[test]
+    fn test_report_timeline() {
+        let mut result = test_result();
+        result.timeline = vec![TimelineBucket {
+            second: 0,
+            requests: 12,
+            errors: 1,
+            active_vus: 4,
+        }];
+        let report =
+            LoadTestReport::from_result(&result, &test_config(), "http://localhost:3000/mcp");
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["timeline"][0]["requests"], 12);
+        assert_eq!(json["timeline"][0]["active_vus"], 4);
+    }
+
     #[test]
     fn test_report_breaking_point_when_not_detected() {
         let report = LoadTestReport::from_result(
@@ -250,7 +250,7 @@ fn format_metric_row(name: &str, value: &str, pad_width: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings};
+    use crate::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Thresholds};
     use crate::loadtest::engine::LoadTestResult;
     use crate::loadtest::metrics::{MetricsSnapshot, ToolSnapshot};
     use std::collections::HashMap;
@@ -276,6 +276,7 @@ mod tests {
                 arguments: serde_json::json!({"text": "hello"}),
             }],
             stage: vec![],
+            thresholds: Thresholds::default(),
         }
     }
 
@@ -310,6 +311,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -333,6 +335,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -350,6 +353,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -384,6 +388,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -410,6 +415,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -426,6 +432,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -452,6 +459,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -489,6 +497,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -517,6 +526,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -542,6 +552,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             final_active_vus: 10,
             breaking_point: None,
+            timeline: Vec::new(),
         };
         let config = minimal_config();
         let output = render_summary(&result, &config, "http://localhost:3000/mcp");
@@ -93,6 +93,36 @@ enum Commands {
         component: AddCommands,
     },
 
+    /// Scaffold a pmcp workspace from an existing MCP server
+    ///
+    /// Reads the tool, resource and prompt registrations of a server built
+    /// with another SDK and generates a workspace with typed handler stubs,
+    /// TODO(migrate) markers, and a MIGRATION.md listing what is left to port.
+    #[command(after_long_help = "Examples:
+  cargo pmcp migrate --from typescript ./my-ts-server
+  cargo pmcp migrate --from typescript ./my-ts-server --name weather --path ..
+  cargo pmcp migrate --from typescript ./my-ts-server --schema tools.json")]
+    Migrate {
+        /// Directory of the server to migrate
+        source: String,
+
+        /// SDK the server is built with
+        #[arg(long, value_enum)]
+        from: commands::migrate::MigrateSource,
+
+        /// Name of the workspace and server (defaults to the package.json name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Schema export or tools/list response to read instead of the sources
+        #[arg(long)]
+        schema: Option<String>,
+
+        /// Directory to create the workspace in (defaults to current directory)
+        #[arg(long)]
+        path: Option<String>,
+    },
+
     /// Test MCP servers with mcp-tester
     ///
     /// Run tests locally, generate scenarios, or manage scenarios on pmcp.run
@@ -208,6 +238,15 @@ enum Commands {
         command: commands::app::AppCommand,
     },
 
+    /// Widget tooling for MCP Apps servers
+    ///
+    /// Generate ChatGPT Apps submission metadata from a running server's
+    /// tools, widgets, and auth configuration.
+    Widget {
+        #[command(subcommand)]
+        command: commands::widget::WidgetCommand,
+    },
+
     /// Diagnose workspace and server health
     ///
     /// Validates project structure (Cargo.toml, pmcp dependency), Rust toolchain,
@@ -220,6 +259,69 @@ enum Commands {
         url: Option<String>,
     },
 
+    /// Show a project health dashboard
+    ///
+    /// Summarizes local servers and whether their ports are listening,
+    /// deployments with their URLs, scheduled test results from pmcp.run,
+    /// secret drift, and tool differences between the exported schema and
+    /// the deployed server.
+    #[command(after_long_help = "Examples:
+  cargo pmcp status
+  cargo pmcp status --format json")]
+    Status {
+        /// Output format (text, json)
+        #[arg(long, value_enum, default_value = "text")]
+        format: commands::flags::FormatValue,
+    },
+
+    /// Remove cargo-pmcp caches and build artifacts
+    ///
+    /// Deletes the preview WASM cache, load test reports, deploy build
+    /// output, downloaded e2e Chromium, and extracted asset temp dirs,
+    /// reporting how much space each one used.
+    #[command(after_long_help = "Examples:
+  cargo pmcp clean --dry-run
+  cargo pmcp clean")]
+    Clean {
+        /// List what would be removed and its size without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Run a local server as an always-on background service
+    ///
+    /// Builds the server in release mode and registers it as a launchd agent
+    /// (macOS) or a logon task (Windows) that starts automatically, writes
+    /// to a log file, and restarts according to the restart policy.
+    #[command(after_long_help = "Examples:
+  cargo pmcp install-service --server chess
+  cargo pmcp install-service --server chess --port 3100 --restart always")]
+    InstallService {
+        /// Name of the server to install
+        #[arg(long)]
+        server: String,
+
+        /// Port to serve on (defaults to the port in .pmcp-config.toml)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// When to restart the server after it exits
+        #[arg(long, value_enum, default_value = "on-failure")]
+        restart: commands::service::RestartPolicy,
+    },
+
+    /// Remove a service registered with install-service
+    ///
+    /// Stops the server, removes the launchd agent or logon task, and deletes
+    /// the installed binary. Log files are kept.
+    #[command(after_long_help = "Examples:
+  cargo pmcp uninstall-service --server chess")]
+    UninstallService {
+        /// Name of the server to uninstall
+        #[arg(long)]
+        server: String,
+    },
+
     /// Generate shell completions
     ///
     /// Outputs shell completion scripts for bash, zsh, fish, or powershell.
@@ -248,7 +350,9 @@ enum Commands {
     #[command(after_long_help = "Examples:
   cargo pmcp preview http://localhost:3000 --open
   cargo pmcp preview http://localhost:3000 --mode chatgpt --open
-  cargo pmcp preview http://localhost:3000 --widgets-dir ./widgets")]
+  cargo pmcp preview http://localhost:3000 --widgets-dir ./widgets
+  cargo pmcp preview http://localhost:3000 --mocks ./mocks
+  cargo pmcp preview http://localhost:3000 --session bug-report.json")]
     Preview {
         /// URL of the running MCP server
         url: String,
@@ -285,6 +389,42 @@ enum Commands {
         #[arg(long, default_value = "standard")]
         mode: String,
 
+        /// Widget host to emulate: compat (default), chatgpt-apps, mcp-apps, or mcp-ui
+        ///
+        /// Restricts the bridge surface widgets see to what that host
+        /// actually provides (`window.openai`, the `ui/*` postMessage
+        /// protocol, or MCP-UI messages).
+        #[arg(long, default_value = "compat")]
+        host_profile: String,
+
+        /// Directory of tool-call fixtures (`{tool_name}.json`)
+        ///
+        /// Calls to tools with a fixture are answered locally instead of
+        /// hitting the MCP server, for offline work or simulated errors.
+        #[arg(long)]
+        mocks: Option<String>,
+
+        /// Session snapshot to replay on load (exported from the preview UI)
+        ///
+        /// Restores the environment, widget state, and tool-call log so a bug
+        /// report renders exactly as the reporter saw it.
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Directory of message catalogs (`{locale}.json`) for i18n testing
+        ///
+        /// The catalog for the selected locale is injected into the widget
+        /// host context and `window.mcpBridge.i18n`.
+        #[arg(long)]
+        messages: Option<String>,
+
+        /// Anonymize tool-call arguments and results in the Inspector export
+        ///
+        /// Names, email addresses and identifying numbers are replaced with
+        /// stable fake values so the export can be attached to bug reports.
+        #[arg(long)]
+        anonymize: bool,
+
         /// Authentication flags for the target MCP server
         #[command(flatten)]
         auth_flags: AuthFlags,
@@ -319,6 +459,10 @@ enum AddCommands {
         /// Server to add the tool to
         #[arg(long)]
         server: String,
+
+        /// Generate the typed handler and failing tests from an mcp-tester scenario
+        #[arg(long, value_name = "SCENARIO")]
+        from_scenario: Option<String>,
     },
 
     /// Add a workflow to an existing server
@@ -402,13 +546,26 @@ fn execute_command(command: Commands, global_flags: &GlobalFlags) -> Result<()>
             } => {
                 commands::add::server(name, template, port, replace, global_flags)?;
             },
-            AddCommands::Tool { name, server } => {
-                commands::add::tool(name, server, global_flags)?;
+            AddCommands::Tool {
+                name,
+                server,
+                from_scenario,
+            } => {
+                commands::add::tool(name, server, from_scenario, global_flags)?;
             },
             AddCommands::Workflow { name, server } => {
                 commands::add::workflow(name, server, global_flags)?;
             },
         },
+        Commands::Migrate {
+            source,
+            from,
+            name,
+            schema,
+            path,
+        } => {
+            commands::migrate::execute(from, source, name, schema, path, global_flags)?;
+        },
         Commands::Test { command } => {
             command.execute(global_flags)?;
         },
@@ -450,9 +607,28 @@ fn execute_command(command: Commands, global_flags: &GlobalFlags) -> Result<()>
         Commands::App { command } => {
             command.execute(global_flags)?;
         },
+        Commands::Widget { command } => {
+            command.execute(global_flags)?;
+        },
         Commands::Doctor { url } => {
             commands::doctor::execute(url.as_deref(), global_flags)?;
         },
+        Commands::Status { format } => {
+            commands::status::execute(format, global_flags)?;
+        },
+        Commands::Clean { dry_run } => {
+            commands::clean::execute(dry_run, global_flags)?;
+        },
+        Commands::InstallService {
+            server,
+            port,
+            restart,
+        } => {
+            commands::service::install(server, port, restart, global_flags)?;
+        },
+        Commands::UninstallService { server } => {
+            commands::service::uninstall(server, global_flags)?;
+        },
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
             clap_complete::generate(shell, &mut cmd, "cargo pmcp", &mut std::io::stdout());
@@ -469,6 +645,11 @@ fn execute_command(command: Commands, global_flags: &GlobalFlags) -> Result<()>
             locale,
             widgets_dir,
             mode,
+            host_profile,
+            mocks,
+            session,
+            messages,
+            anonymize,
             auth_flags,
         } => {
             let runtime = tokio::runtime::Runtime::new()?;
@@ -481,6 +662,11 @@ fn execute_command(command: Commands, global_flags: &GlobalFlags) -> Result<()>
                 locale,
                 widgets_dir,
                 mode,
+                host_profile,
+                mocks,
+                session,
+                messages,
+                anonymize,
                 &auth_flags,
                 global_flags,
             ))?;
@@ -0,0 +1,389 @@
+//! ChatGPT Apps submission manifest generation.
+//!
+//! Builds the app metadata a ChatGPT Apps submission needs from what a
+//! running server reports about itself: the `serverInfo` set on the server
+//! builder, tool `_meta` linking tools to widgets, the [`WidgetMeta`] on
+//! each widget resource, and the server's OAuth protected-resource
+//! metadata. Because everything is read from the server, regenerating the
+//! manifest keeps it in sync with code.
+//!
+//! The output is deterministic (tools and widgets are sorted, no
+//! timestamps) so a committed manifest can be checked for drift in CI.
+
+use anyhow::{Context, Result};
+use pmcp::types::mcp_apps::WidgetMeta;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Manifest schema version emitted in `schema_version`.
+pub const APP_MANIFEST_VERSION: &str = "v1";
+
+/// A widget resource as served by the MCP server.
+#[derive(Debug, Clone)]
+pub struct WidgetResource {
+    /// Resource URI (e.g. "ui://chess/board.html").
+    pub uri: String,
+    /// Resource name from `resources/list`.
+    pub name: String,
+    /// Resource description from `resources/list`.
+    pub description: Option<String>,
+    /// Widget metadata parsed from the resource content `_meta`.
+    pub meta: WidgetMeta,
+}
+
+/// Everything the manifest is generated from.
+#[derive(Debug, Clone, Default)]
+pub struct ServerMetadata {
+    /// `serverInfo` from the `initialize` response.
+    pub server_info: Value,
+    /// Raw tool definitions from `tools/list`.
+    pub tools: Vec<Value>,
+    /// Widget resources referenced by tools.
+    pub widgets: Vec<WidgetResource>,
+    /// OAuth protected-resource metadata (RFC 9728), if the server serves it.
+    pub protected_resource: Option<Value>,
+}
+
+/// Resolve the widget URI a tool renders its results with.
+///
+/// Checks the MCP Apps standard nested `ui.resourceUri` key first, then the
+/// legacy flat `ui/resourceUri` key, then ChatGPT's `openai/outputTemplate`.
+pub fn tool_widget_uri(tool: &Value) -> Option<String> {
+    let meta = tool.get("_meta")?;
+    meta.get("ui")
+        .and_then(|ui| ui.get("resourceUri"))
+        .or_else(|| meta.get("ui/resourceUri"))
+        .or_else(|| meta.get("openai/outputTemplate"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Generate the ChatGPT Apps manifest JSON string.
+///
+/// Fails if the server exposes no widget-backed tools, since such a server
+/// has nothing to submit as an app.
+pub fn generate_app_manifest(metadata: &ServerMetadata, server_url: &str) -> Result<String> {
+    let info = &metadata.server_info;
+    let name = info
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("unnamed");
+    let title = info.get("title").and_then(Value::as_str).unwrap_or(name);
+    let description = info
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    let mut tools: Vec<&Value> = metadata.tools.iter().collect();
+    tools.sort_by(|a, b| tool_name(a).cmp(tool_name(b)));
+
+    let mut tools_by_widget: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let tool_entries: Vec<Value> = tools
+        .iter()
+        .map(|tool| {
+            let tool_name = tool_name(tool);
+            let widget = tool_widget_uri(tool);
+            if let Some(uri) = &widget {
+                tools_by_widget
+                    .entry(uri.clone())
+                    .or_default()
+                    .push(tool_name.to_string());
+            }
+            let mut entry = json!({
+                "name": tool_name,
+                "description": tool.get("description").and_then(Value::as_str).unwrap_or_default(),
+                "widget": widget,
+                "read_only": tool
+                    .pointer("/annotations/readOnlyHint")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            });
+            if let Some(schemes) = tool_security_schemes(tool) {
+                entry["security_schemes"] = schemes.clone();
+            }
+            entry
+        })
+        .collect();
+
+    if tools_by_widget.is_empty() {
+        anyhow::bail!(
+            "Server '{}' has no tools linked to widgets. Link a tool to a widget \
+             (e.g. with WidgetMeta::resource_uri) before generating an app manifest.",
+            name
+        );
+    }
+
+    let resources: BTreeMap<&str, &WidgetResource> = metadata
+        .widgets
+        .iter()
+        .map(|w| (w.uri.as_str(), w))
+        .collect();
+    let widget_entries: Vec<Value> = tools_by_widget
+        .iter()
+        .map(|(uri, tool_names)| {
+            widget_entry(uri, resources.get(uri.as_str()).copied(), tool_names)
+        })
+        .collect();
+
+    let manifest = json!({
+        "schema_version": APP_MANIFEST_VERSION,
+        "name": title,
+        "name_for_model": name.replace(['-', ' '], "_"),
+        "description": description,
+        "version": info.get("version").and_then(Value::as_str).unwrap_or_default(),
+        "website_url": info.get("websiteUrl").and_then(Value::as_str),
+        "icons": icons(info),
+        "server_url": server_url.trim_end_matches('/'),
+        "auth": auth_requirements(metadata),
+        "tools": tool_entries,
+        "widgets": widget_entries,
+    });
+
+    serde_json::to_string_pretty(&manifest).context("Failed to serialize app manifest JSON")
+}
+
+fn tool_name(tool: &Value) -> &str {
+    tool.get("name").and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Build the manifest entry for one widget from its resource metadata.
+fn widget_entry(uri: &str, resource: Option<&WidgetResource>, tool_names: &[String]) -> Value {
+    let mut entry = Map::new();
+    entry.insert("uri".to_string(), json!(uri));
+    entry.insert("tools".to_string(), json!(tool_names));
+
+    let Some(resource) = resource else {
+        // Referenced by a tool but not served; surface it rather than hide it.
+        entry.insert("missing".to_string(), json!(true));
+        return Value::Object(entry);
+    };
+
+    let meta = &resource.meta;
+    entry.insert("name".to_string(), json!(resource.name));
+    entry.insert(
+        "description".to_string(),
+        json!(meta.description.as_ref().or(resource.description.as_ref())),
+    );
+    if let Some(prefers_border) = meta.prefers_border {
+        entry.insert("prefers_border".to_string(), json!(prefers_border));
+    }
+    if let Some(domain) = &meta.domain {
+        entry.insert("domain".to_string(), json!(domain));
+    }
+    if let Some(csp) = &meta.csp {
+        entry.insert("csp".to_string(), json!(csp));
+    }
+    if let Some(sandbox) = &meta.sandbox {
+        entry.insert("sandbox".to_string(), json!(sandbox));
+    }
+    Value::Object(entry)
+}
+
+/// Normalize `serverInfo.icons` to `{url, mime_type, sizes}` entries.
+fn icons(info: &Value) -> Vec<Value> {
+    info.get("icons")
+        .and_then(Value::as_array)
+        .map(|icons| {
+            icons
+                .iter()
+                .filter_map(|icon| {
+                    let url = icon.get("src").or_else(|| icon.get("url"))?.as_str()?;
+                    Some(json!({
+                        "url": url,
+                        "mime_type": icon.get("mimeType"),
+                        "sizes": icon.get("sizes"),
+                    }))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Per-tool security schemes, from the tool itself or its `_meta`.
+fn tool_security_schemes(tool: &Value) -> Option<&Value> {
+    tool.get("securitySchemes")
+        .or_else(|| tool.pointer("/_meta/securitySchemes"))
+        .filter(|s| s.is_array())
+}
+
+/// Derive auth requirements from protected-resource metadata or tool schemes.
+///
+/// Protected-resource metadata is authoritative. Without it, any tool that
+/// declares an `oauth2` security scheme makes the app require OAuth with the
+/// union of the declared scopes.
+fn auth_requirements(metadata: &ServerMetadata) -> Value {
+    if let Some(prm) = &metadata.protected_resource {
+        return json!({
+            "type": "oauth2",
+            "authorization_servers": prm.get("authorization_servers").cloned().unwrap_or_else(|| json!([])),
+            "scopes": prm.get("scopes_supported").cloned().unwrap_or_else(|| json!([])),
+        });
+    }
+
+    let mut oauth = false;
+    let mut scopes = BTreeSet::new();
+    for scheme in metadata
+        .tools
+        .iter()
+        .filter_map(tool_security_schemes)
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter(|s| s.get("type").and_then(Value::as_str) == Some("oauth2"))
+    {
+        oauth = true;
+        if let Some(declared) = scheme.get("scopes").and_then(Value::as_array) {
+            scopes.extend(declared.iter().filter_map(Value::as_str));
+        }
+    }
+
+    if oauth {
+        json!({ "type": "oauth2", "authorization_servers": [], "scopes": scopes })
+    } else {
+        json!({ "type": "none" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmcp::types::mcp_apps::WidgetCSP;
+
+    fn sample_metadata() -> ServerMetadata {
+        ServerMetadata {
+            server_info: json!({
+                "name": "chess-app",
+                "title": "Chess",
+                "version": "1.2.0",
+                "description": "Play chess",
+                "icons": [{ "src": "https://example.com/chess.png", "mimeType": "image/png" }]
+            }),
+            tools: vec![
+                json!({
+                    "name": "show_board",
+                    "description": "Show the board",
+                    "_meta": { "ui": { "resourceUri": "ui://chess/board.html" } }
+                }),
+                json!({
+                    "name": "move",
+                    "_meta": { "openai/outputTemplate": "ui://chess/board.html" },
+                    "securitySchemes": [{ "type": "oauth2", "scopes": ["games.write"] }]
+                }),
+                json!({ "name": "hint", "annotations": { "readOnlyHint": true } }),
+            ],
+            widgets: vec![WidgetResource {
+                uri: "ui://chess/board.html".to_string(),
+                name: "board".to_string(),
+                description: Some("Resource description".to_string()),
+                meta: WidgetMeta::new()
+                    .description("Interactive chess board")
+                    .prefers_border(true)
+                    .csp(WidgetCSP::new().connect("https://api.chess.com")),
+            }],
+            protected_resource: None,
+        }
+    }
+
+    fn generate(metadata: &ServerMetadata) -> Value {
+        let json_str = generate_app_manifest(metadata, "https://chess.example.com/").unwrap();
+        serde_json::from_str(&json_str).unwrap()
+    }
+
+    #[test]
+    fn test_app_manifest_uses_server_info() {
+        let manifest = generate(&sample_metadata());
+
+        assert_eq!(manifest["schema_version"], "v1");
+        assert_eq!(manifest["name"], "Chess");
+        assert_eq!(manifest["name_for_model"], "chess_app");
+        assert_eq!(manifest["description"], "Play chess");
+        assert_eq!(manifest["version"], "1.2.0");
+        assert_eq!(manifest["server_url"], "https://chess.example.com");
+        assert_eq!(manifest["icons"][0]["url"], "https://example.com/chess.png");
+    }
+
+    #[test]
+    fn test_app_manifest_maps_tools_to_widgets() {
+        let manifest = generate(&sample_metadata());
+
+        let tools = manifest["tools"].as_array().unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["hint", "move", "show_board"]);
+        assert_eq!(tools[0]["widget"], Value::Null);
+        assert_eq!(tools[0]["read_only"], true);
+        assert_eq!(tools[1]["widget"], "ui://chess/board.html");
+
+        let widget = &manifest["widgets"][0];
+        assert_eq!(widget["uri"], "ui://chess/board.html");
+        assert_eq!(widget["tools"], json!(["move", "show_board"]));
+        assert_eq!(widget["description"], "Interactive chess board");
+        assert_eq!(widget["prefers_border"], true);
+        assert_eq!(
+            widget["csp"]["connect_domains"],
+            json!(["https://api.chess.com"])
+        );
+    }
+
+    #[test]
+    fn test_app_manifest_auth_from_tool_schemes() {
+        let manifest = generate(&sample_metadata());
+        assert_eq!(manifest["auth"]["type"], "oauth2");
+        assert_eq!(manifest["auth"]["scopes"], json!(["games.write"]));
+    }
+
+    #[test]
+    fn test_app_manifest_auth_from_protected_resource() {
+        let mut metadata = sample_metadata();
+        metadata.protected_resource = Some(json!({
+            "resource": "https://chess.example.com",
+            "authorization_servers": ["https://auth.example.com"],
+            "scopes_supported": ["openid", "games.read"]
+        }));
+
+        let manifest = generate(&metadata);
+        assert_eq!(
+            manifest["auth"]["authorization_servers"],
+            json!(["https://auth.example.com"])
+        );
+        assert_eq!(manifest["auth"]["scopes"], json!(["openid", "games.read"]));
+    }
+
+    #[test]
+    fn test_app_manifest_auth_none_without_schemes() {
+        let mut metadata = sample_metadata();
+        metadata.tools[1]
+            .as_object_mut()
+            .unwrap()
+            .remove("securitySchemes");
+
+        let manifest = generate(&metadata);
+        assert_eq!(manifest["auth"], json!({ "type": "none" }));
+    }
+
+    #[test]
+    fn test_app_manifest_flags_unserved_widget() {
+        let mut metadata = sample_metadata();
+        metadata.widgets.clear();
+
+        let manifest = generate(&metadata);
+        assert_eq!(manifest["widgets"][0]["missing"], true);
+    }
+
+    #[test]
+    fn test_app_manifest_requires_widget_tools() {
+        let mut metadata = sample_metadata();
+        metadata.tools.clear();
+        metadata.tools.push(json!({ "name": "plain" }));
+
+        let err = generate_app_manifest(&metadata, "https://chess.example.com").unwrap_err();
+        assert!(err.to_string().contains("no tools linked to widgets"));
+    }
+
+    #[test]
+    fn test_app_manifest_is_deterministic() {
+        let mut metadata = sample_metadata();
+        let first = generate_app_manifest(&metadata, "https://chess.example.com").unwrap();
+        metadata.tools.reverse();
+        let second = generate_app_manifest(&metadata, "https://chess.example.com").unwrap();
+        assert_eq!(first, second);
+    }
+}
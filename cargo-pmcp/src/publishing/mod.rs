@@ -3,6 +3,7 @@
 //! Provides project detection and manifest generation for distributing
 //! MCP Apps through ChatGPT-compatible app directories.
 
+pub mod chatgpt;
 pub mod detect;
 pub mod landing;
 pub mod manifest;
@@ -0,0 +1,291 @@
+//! Drift detection between configured secret references and a deployed target.
+//!
+//! `cargo pmcp secret sync` collects the secrets a project references and
+//! compares them with what the deployment target (pmcp.run, AWS, or the local
+//! store) actually holds:
+//!
+//! - **missing**: referenced but not present in the target
+//! - **extra**: present in the target for a referenced server, but no longer referenced
+//! - **stale**: the reference pins a version (`secret:chess/API_KEY@3`) and the
+//!   target holds a different one
+//!
+//! # References
+//!
+//! References come from two places in the configuration file:
+//!
+//! ```toml
+//! [server]
+//! id = "chess"
+//!
+//! # Declared secrets, qualified with the server ID
+//! [[secrets.definitions]]
+//! name = "ANTHROPIC_API_KEY"
+//!
+//! # Inline references, optionally pinned to a version
+//! [database]
+//! url = "secret:chess/DATABASE_URL@2"
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use super::value::SecretEntry;
+
+/// A secret referenced by the project configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretReference {
+    /// Fully-qualified name (`server-id/SECRET_NAME`)
+    pub name: String,
+    /// Version pinned with an `@N` suffix, if any
+    pub version: Option<u32>,
+}
+
+impl SecretReference {
+    /// Server ID part of the name.
+    pub fn server_id(&self) -> &str {
+        self.name.split_once('/').map_or("", |(server, _)| server)
+    }
+}
+
+/// How a secret differs between configuration and the deployed target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    /// Referenced and present (at the pinned version, if pinned)
+    InSync,
+    /// Referenced but not present in the target
+    Missing,
+    /// Present in the target but not referenced
+    Extra,
+    /// Present in the target at a different version than pinned
+    Stale,
+}
+
+impl DriftStatus {
+    /// Label used in the drift table.
+    pub fn label(self) -> &'static str {
+        match self {
+            DriftStatus::InSync => "in sync",
+            DriftStatus::Missing => "MISSING",
+            DriftStatus::Extra => "extra",
+            DriftStatus::Stale => "STALE",
+        }
+    }
+}
+
+/// One row of the drift table.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftEntry {
+    /// Fully-qualified secret name
+    pub name: String,
+    /// Drift status
+    pub status: DriftStatus,
+    /// Version pinned in configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<u32>,
+    /// Version reported by the target (if the provider supports versioning)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployed_version: Option<u32>,
+}
+
+/// Collect secret references from configuration file content.
+///
+/// Includes every `secret:server/NAME[@version]` string and every
+/// `[[secrets.definitions]]` entry. Definitions are qualified with
+/// `server_override`, or else `server.id` / `server.name` from the file.
+/// References are deduplicated by name; a pinned version wins over an
+/// unpinned mention.
+pub fn collect_references(content: &str, server_override: Option<&str>) -> Vec<SecretReference> {
+    let mut refs: Vec<SecretReference> = Vec::new();
+    let mut add =
+        |name: String, version: Option<u32>| match refs.iter_mut().find(|r| r.name == name) {
+            Some(existing) => existing.version = existing.version.or(version),
+            None => refs.push(SecretReference { name, version }),
+        };
+
+    let re = regex::Regex::new(r"secret:([a-zA-Z0-9_\-/]+)(?:@(\d+))?").unwrap();
+    for cap in re.captures_iter(content) {
+        let version = cap.get(2).and_then(|v| v.as_str().parse().ok());
+        add(cap[1].to_string(), version);
+    }
+
+    if let Ok(doc) = content.parse::<toml::Table>() {
+        let server_id = server_override.map(str::to_string).or_else(|| {
+            let server = doc.get("server")?;
+            server
+                .get("id")
+                .or_else(|| server.get("name"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        });
+        let definitions = doc
+            .get("secrets")
+            .and_then(|s| s.get("definitions"))
+            .and_then(|d| d.as_array());
+        if let (Some(server_id), Some(definitions)) = (server_id, definitions) {
+            for definition in definitions {
+                if let Some(name) = definition.get("name").and_then(|n| n.as_str()) {
+                    add(format!("{}/{}", server_id, name), None);
+                }
+            }
+        }
+    }
+
+    refs
+}
+
+/// Compare references with the secrets listed by the target.
+///
+/// `deployed` should cover the servers that appear in `refs`; deployed secrets
+/// of other servers are ignored rather than reported as extra. Rows are
+/// sorted by name.
+pub fn compute_drift(refs: &[SecretReference], deployed: &[SecretEntry]) -> Vec<DriftEntry> {
+    let servers: BTreeSet<&str> = refs.iter().map(SecretReference::server_id).collect();
+    let deployed_by_name: HashMap<&str, &SecretEntry> =
+        deployed.iter().map(|s| (s.name.as_str(), s)).collect();
+
+    let mut entries: Vec<DriftEntry> = refs
+        .iter()
+        .map(|r| {
+            let deployed_version = deployed_by_name
+                .get(r.name.as_str())
+                .and_then(|s| s.metadata.version);
+            let status = if !deployed_by_name.contains_key(r.name.as_str()) {
+                DriftStatus::Missing
+            } else {
+                match (r.version, deployed_version) {
+                    (Some(expected), Some(actual)) if expected != actual => DriftStatus::Stale,
+                    _ => DriftStatus::InSync,
+                }
+            };
+            DriftEntry {
+                name: r.name.clone(),
+                status,
+                expected_version: r.version,
+                deployed_version,
+            }
+        })
+        .collect();
+
+    for secret in deployed {
+        let server = secret.name.split_once('/').map_or("", |(s, _)| s);
+        if servers.contains(server) && !refs.iter().any(|r| r.name == secret.name) {
+            entries.push(DriftEntry {
+                name: secret.name.clone(),
+                status: DriftStatus::Extra,
+                expected_version: None,
+                deployed_version: secret.metadata.version,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries.dedup_by(|a, b| a.name == b.name);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::value::SecretMetadata;
+
+    fn deployed(name: &str, version: Option<u32>) -> SecretEntry {
+        let mut metadata = SecretMetadata::new(name.split_once('/').unwrap().1);
+        metadata.version = version;
+        SecretEntry {
+            name: name.to_string(),
+            metadata,
+        }
+    }
+
+    fn reference(name: &str, version: Option<u32>) -> SecretReference {
+        SecretReference {
+            name: name.to_string(),
+            version,
+        }
+    }
+
+    #[test]
+    fn test_collect_references_inline_and_definitions() {
+        let content = r#"
+[server]
+id = "chess"
+
+[[secrets.definitions]]
+name = "ANTHROPIC_API_KEY"
+
+[[secrets.definitions]]
+name = "DATABASE_URL"
+
+[database]
+url = "secret:chess/DATABASE_URL@2"
+token = "secret:other/TOKEN"
+"#;
+        let refs = collect_references(content, None);
+
+        assert_eq!(
+            refs,
+            vec![
+                reference("chess/DATABASE_URL", Some(2)),
+                reference("other/TOKEN", None),
+                reference("chess/ANTHROPIC_API_KEY", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_references_server_override() {
+        let content = "[server]\nname = \"chess\"\n\n[[secrets.definitions]]\nname = \"KEY\"\n";
+
+        assert_eq!(
+            collect_references(content, None),
+            vec![reference("chess/KEY", None)]
+        );
+        assert_eq!(
+            collect_references(content, Some("prod-chess")),
+            vec![reference("prod-chess/KEY", None)]
+        );
+    }
+
+    #[test]
+    fn test_compute_drift_statuses() {
+        let refs = vec![
+            reference("chess/API_KEY", None),
+            reference("chess/DB_URL", Some(3)),
+            reference("chess/NEW_KEY", None),
+            reference("chess/TOKEN", Some(1)),
+        ];
+        let target = vec![
+            deployed("chess/API_KEY", Some(5)),
+            deployed("chess/DB_URL", Some(2)),
+            deployed("chess/OLD_KEY", None),
+            deployed("chess/TOKEN", Some(1)),
+            deployed("other/UNRELATED", None),
+        ];
+
+        let drift = compute_drift(&refs, &target);
+        let statuses: Vec<(&str, DriftStatus)> =
+            drift.iter().map(|e| (e.name.as_str(), e.status)).collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("chess/API_KEY", DriftStatus::InSync),
+                ("chess/DB_URL", DriftStatus::Stale),
+                ("chess/NEW_KEY", DriftStatus::Missing),
+                ("chess/OLD_KEY", DriftStatus::Extra),
+                ("chess/TOKEN", DriftStatus::InSync),
+            ]
+        );
+        assert_eq!(drift[1].expected_version, Some(3));
+        assert_eq!(drift[1].deployed_version, Some(2));
+    }
+
+    #[test]
+    fn test_compute_drift_unversioned_target_is_not_stale() {
+        let refs = vec![reference("chess/DB_URL", Some(3))];
+        let drift = compute_drift(&refs, &[deployed("chess/DB_URL", None)]);
+        assert_eq!(drift[0].status, DriftStatus::InSync);
+    }
+}
@@ -51,6 +51,7 @@
 //! ```
 
 pub mod config;
+pub mod drift;
 pub mod error;
 pub mod provider;
 pub mod providers;
@@ -237,6 +237,13 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {{
         .build()
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
+    // `--self-test`: check the server can serve traffic, then exit
+    if std::env::args().any(|arg| arg == "--self-test") {{
+        let report = server.run_self_test().await;
+        println!("{{report}}");
+        std::process::exit(report.exit_code());
+    }}
+
     let server = Arc::new(Mutex::new(server));
 
     // Configure HTTP server
@@ -246,14 +253,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {{
         .unwrap_or(3000u16);
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
 
-    let config = StreamableHttpServerConfig {{
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-    }};
+    let config = StreamableHttpServerConfig::stateless();
 
     let http_server = StreamableHttpServer::with_config(addr, server, config);
     let (bound_addr, server_handle) = http_server
@@ -2,6 +2,7 @@ pub mod calculator;
 pub mod complete_calculator;
 pub mod mcp_app;
 pub mod oauth;
+pub mod scenario_tool;
 pub mod server;
 pub mod server_common;
 pub mod sqlite_explorer;
@@ -0,0 +1,630 @@
+//! Tool handler skeletons generated from mcp-tester scenarios.
+//!
+//! `cargo pmcp add tool --from-scenario` supports writing the scenario first.
+//! The steps that call the new tool supply its input type, inferred from their
+//! arguments. The assertions on `parsed.*` supply the output type. The
+//! generated module holds both types, a handler that returns an error until it
+//! is implemented, and one test per scenario step. The tests fail until the
+//! handler satisfies the scenario.
+
+use anyhow::{bail, Result};
+use mcp_tester::scenario::{Assertion, Comparison, Operation, TestScenario, TestStep};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use crate::commands::schema::bindings::{field_ident, pascal_case};
+
+/// Type of a field inferred from the values and assertions seen for it.
+#[derive(Debug, Clone, PartialEq)]
+enum FieldType {
+    Bool,
+    Integer,
+    Number,
+    String,
+    Array(Box<FieldType>),
+    Any,
+}
+
+impl FieldType {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Bool),
+            Value::Number(n) if n.is_i64() || n.is_u64() => Some(Self::Integer),
+            Value::Number(_) => Some(Self::Number),
+            Value::String(_) => Some(Self::String),
+            Value::Array(items) => {
+                let item = items
+                    .iter()
+                    .filter_map(Self::of)
+                    .reduce(Self::merge)
+                    .unwrap_or(Self::Any);
+                Some(Self::Array(Box::new(item)))
+            },
+            Value::Object(_) => Some(Self::Any),
+        }
+    }
+
+    /// Widen two observations of the same field to a type that holds both.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Integer, Self::Number) | (Self::Number, Self::Integer) => Self::Number,
+            (Self::Array(a), Self::Array(b)) => Self::Array(Box::new(a.merge(*b))),
+            _ => Self::Any,
+        }
+    }
+
+    fn rust_type(&self) -> String {
+        match self {
+            Self::Bool => "bool".to_string(),
+            Self::Integer => "i64".to_string(),
+            Self::Number => "f64".to_string(),
+            Self::String => "String".to_string(),
+            Self::Array(item) => format!("Vec<{}>", item.rust_type()),
+            Self::Any => "serde_json::Value".to_string(),
+        }
+    }
+}
+
+/// What the scenario says about one field.
+#[derive(Debug, Default)]
+struct FieldInfo {
+    ty: Option<FieldType>,
+    optional: bool,
+}
+
+impl FieldInfo {
+    fn observe(&mut self, ty: Option<FieldType>) {
+        self.ty = match (self.ty.take(), ty) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// A tool module inferred from a scenario.
+#[derive(Debug)]
+pub struct ScenarioTool {
+    /// Tool name as called by the scenario.
+    pub tool: String,
+    /// Rust module (and file stem) for the generated code.
+    pub module: String,
+    /// Name of the generated input struct.
+    pub input_type: String,
+    /// Name of the generated output struct.
+    pub output_type: String,
+    /// Number of scenario steps turned into tests.
+    pub tests: usize,
+    /// Generated module source.
+    pub source: String,
+}
+
+/// Generate the module for `tool` from the steps of `scenario` that call it.
+pub fn generate(tool: &str, scenario: &TestScenario) -> Result<ScenarioTool> {
+    let steps: Vec<(&TestStep, &Value)> = scenario
+        .setup
+        .iter()
+        .chain(&scenario.steps)
+        .chain(&scenario.cleanup)
+        .filter_map(|step| match &step.operation {
+            Operation::ToolCall {
+                tool: called,
+                arguments,
+            } if called == tool => Some((step, arguments)),
+            _ => None,
+        })
+        .collect();
+
+    if steps.is_empty() {
+        let called: Vec<String> = scenario
+            .steps
+            .iter()
+            .filter_map(|step| match &step.operation {
+                Operation::ToolCall { tool, .. } => Some(format!("'{}'", tool)),
+                _ => None,
+            })
+            .collect();
+        if called.is_empty() {
+            bail!("Scenario '{}' has no tool_call steps", scenario.name);
+        }
+        bail!(
+            "Scenario '{}' never calls '{}' (it calls {})",
+            scenario.name,
+            tool,
+            called.join(", ")
+        );
+    }
+
+    let module = field_ident(tool).trim_start_matches("r#").to_string();
+    let type_base = pascal_case(tool);
+    let input_type = format!("{}Input", type_base);
+    let output_type = format!("{}Output", type_base);
+
+    // Failure steps usually send arguments the tool must reject, so they
+    // only shape the input type when no step is expected to succeed.
+    let succeeding: Vec<&Map<String, Value>> = steps
+        .iter()
+        .filter(|(step, _)| !expects_failure(step))
+        .filter_map(|(_, args)| args.as_object())
+        .collect();
+    let calls = if succeeding.is_empty() {
+        steps
+            .iter()
+            .filter_map(|(_, args)| args.as_object())
+            .collect()
+    } else {
+        succeeding
+    };
+    let input_fields = infer_input(&calls);
+    let output_fields = infer_output(
+        steps
+            .iter()
+            .filter(|(step, _)| !expects_failure(step))
+            .flat_map(|(step, _)| &step.assertions),
+    );
+
+    let mut out = String::new();
+    let _ = writeln!(out, "//! The `{}` tool.", tool);
+    out.push_str("//!\n");
+    let _ = writeln!(
+        out,
+        "//! Generated by `cargo pmcp add tool {} --from-scenario` from scenario",
+        tool
+    );
+    let _ = writeln!(
+        out,
+        "//! \"{}\". The tests below fail until `handle` satisfies it.\n",
+        scenario.name
+    );
+    out.push_str("use schemars::JsonSchema;\n");
+    out.push_str("use serde::{Deserialize, Serialize};\n\n");
+
+    let _ = writeln!(out, "/// Arguments for the `{}` tool.", tool);
+    write_struct(&mut out, &input_type, &input_fields, true);
+    out.push('\n');
+    let _ = writeln!(out, "/// Result of the `{}` tool.", tool);
+    write_struct(&mut out, &output_type, &output_fields, false);
+
+    out.push('\n');
+    let _ = writeln!(out, "/// Handle a call to `{}`.", tool);
+    let _ = writeln!(
+        out,
+        "pub async fn handle(input: {}) -> pmcp::Result<{}> {{",
+        input_type, output_type
+    );
+    out.push_str("    // TODO: implement the tool so the scenario tests below pass.\n");
+    out.push_str("    let _ = input;\n");
+    let _ = writeln!(
+        out,
+        "    Err(pmcp::Error::internal(\"{} is not implemented yet\"))",
+        tool
+    );
+    out.push_str("}\n");
+
+    out.push_str("\n#[cfg(test)]\nmod tests {\n");
+    out.push_str("    use super::*;\n");
+    out.push_str("    use serde_json::json;\n");
+    let mut names = HashSet::new();
+    for (step, arguments) in &steps {
+        out.push('\n');
+        write_test(&mut out, &mut names, step, arguments, &input_type);
+    }
+    out.push_str("}\n");
+
+    Ok(ScenarioTool {
+        tool: tool.to_string(),
+        module,
+        input_type,
+        output_type,
+        tests: steps.len(),
+        source: out,
+    })
+}
+
+fn expects_failure(step: &TestStep) -> bool {
+    step.assertions
+        .iter()
+        .any(|a| matches!(a, Assertion::Failure))
+}
+
+/// A field is optional when some call omits it or passes `null`.
+fn infer_input(calls: &[&Map<String, Value>]) -> BTreeMap<String, FieldInfo> {
+    let mut fields: BTreeMap<String, FieldInfo> = BTreeMap::new();
+    for args in calls {
+        for (key, value) in *args {
+            let field = fields.entry(key.clone()).or_default();
+            field.optional |= value.is_null();
+            field.observe(FieldType::of(value));
+        }
+    }
+    for (key, field) in fields.iter_mut() {
+        field.optional |= calls.iter().any(|args| !args.contains_key(key));
+    }
+    fields
+}
+
+/// Output fields are the top-level keys that assertions reach under `parsed`.
+fn infer_output<'a>(
+    assertions: impl Iterator<Item = &'a Assertion>,
+) -> BTreeMap<String, FieldInfo> {
+    let mut fields: BTreeMap<String, FieldInfo> = BTreeMap::new();
+    for assertion in assertions {
+        let path = match assertion {
+            Assertion::Equals { path, .. }
+            | Assertion::Contains { path, .. }
+            | Assertion::Matches { path, .. }
+            | Assertion::Exists { path }
+            | Assertion::NotExists { path }
+            | Assertion::ArrayLength { path, .. }
+            | Assertion::Numeric { path, .. } => path,
+            _ => continue,
+        };
+        let Some(segments) = output_segments(path) else {
+            continue;
+        };
+        let (key, index) = split_index(segments[0]);
+        let field = fields.entry(key.to_string()).or_default();
+
+        // Only a whole top-level field carries its own type; anything nested
+        // stays a JSON value in the skeleton.
+        if segments.len() > 1 || index.is_some() {
+            field.observe(Some(if segments.len() == 1 {
+                FieldType::Array(Box::new(FieldType::Any))
+            } else {
+                FieldType::Any
+            }));
+            continue;
+        }
+        match assertion {
+            Assertion::Equals { value, .. } => {
+                field.optional |= value.is_null();
+                field.observe(FieldType::of(value));
+            },
+            Assertion::Contains { .. } | Assertion::Matches { .. } => {
+                field.observe(Some(FieldType::String));
+            },
+            Assertion::NotExists { .. } => field.optional = true,
+            Assertion::ArrayLength { .. } => {
+                field.observe(Some(FieldType::Array(Box::new(FieldType::Any))));
+            },
+            Assertion::Numeric { .. } => field.observe(Some(FieldType::Number)),
+            _ => {},
+        }
+    }
+    fields
+}
+
+/// Path segments below `parsed`, or `None` for paths on the raw response.
+fn output_segments(path: &str) -> Option<Vec<&str>> {
+    let rest = path.strip_prefix("parsed.")?;
+    let segments: Vec<&str> = rest.split('.').collect();
+    (!segments.iter().any(|s| s.is_empty())).then_some(segments)
+}
+
+/// Split `items[0]` into `("items", Some(0))`.
+fn split_index(segment: &str) -> (&str, Option<usize>) {
+    match segment.find('[') {
+        Some(pos) if segment.ends_with(']') => (
+            &segment[..pos],
+            segment[pos + 1..segment.len() - 1].parse().ok(),
+        ),
+        _ => (segment, None),
+    }
+}
+
+/// JSON pointer into the serialized output for a `parsed.*` path.
+fn json_pointer(segments: &[&str]) -> String {
+    let mut pointer = String::new();
+    for segment in segments {
+        let (key, index) = split_index(segment);
+        pointer.push('/');
+        pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+        if let Some(index) = index {
+            let _ = write!(pointer, "/{}", index);
+        }
+    }
+    pointer
+}
+
+fn write_struct(
+    out: &mut String,
+    name: &str,
+    fields: &BTreeMap<String, FieldInfo>,
+    deny_unknown: bool,
+) {
+    out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]\n");
+    if deny_unknown {
+        out.push_str("#[schemars(deny_unknown_fields)]\n");
+    }
+    let _ = writeln!(out, "pub struct {} {{", name);
+    for (key, field) in fields {
+        let rust_name = field_ident(key);
+        if rust_name.trim_start_matches("r#") != key {
+            let _ = writeln!(out, "    #[serde(rename = {:?})]", key);
+        }
+        let ty = field.ty.as_ref().unwrap_or(&FieldType::Any).rust_type();
+        if field.optional {
+            out.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+            let _ = writeln!(out, "    pub {}: Option<{}>,", rust_name, ty);
+        } else {
+            let _ = writeln!(out, "    pub {}: {},", rust_name, ty);
+        }
+    }
+    out.push_str("}\n");
+}
+
+fn write_test(
+    out: &mut String,
+    names: &mut HashSet<String>,
+    step: &TestStep,
+    arguments: &Value,
+    input_type: &str,
+) {
+    let base = format!("test_{}", field_ident(&step.name).trim_start_matches("r#"));
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while !names.insert(name.clone()) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    let arguments = match arguments {
+        Value::Null => "{}".to_string(),
+        other => other.to_string(),
+    };
+
+    out.push_str("    #[tokio::test]\n");
+    let _ = writeln!(out, "    async fn {}() {{", name);
+    if expects_failure(step) {
+        let _ = writeln!(
+            out,
+            "        let rejected = match serde_json::from_value::<{}>(json!({})) {{",
+            input_type, arguments
+        );
+        out.push_str("            Ok(input) => handle(input).await.is_err(),\n");
+        out.push_str("            Err(_) => true,\n");
+        out.push_str("        };\n");
+        let _ = writeln!(
+            out,
+            "        assert!(rejected, \"{{}} should fail\", {:?});",
+            step.name
+        );
+        out.push_str("    }\n");
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "        let input: {} = serde_json::from_value(json!({})).unwrap();",
+        input_type, arguments
+    );
+    let _ = writeln!(
+        out,
+        "        let output = handle(input).await.expect({:?});",
+        step.name
+    );
+    out.push_str("        let output = serde_json::to_value(output).unwrap();\n");
+    for assertion in &step.assertions {
+        if let Some(line) = assertion_line(assertion) {
+            let _ = writeln!(out, "        {}", line);
+        }
+    }
+    out.push_str("    }\n");
+}
+
+/// Rust statement checking `assertion` against the serialized `output`.
+fn assertion_line(assertion: &Assertion) -> Option<String> {
+    let (path, check) = match assertion {
+        Assertion::Equals {
+            path,
+            value,
+            ignore_case,
+        } => {
+            let check = match value {
+                Value::Number(n) => format!(
+                    "v.as_f64().is_some_and(|v| (v - {:?}).abs() < f64::EPSILON)",
+                    n.as_f64().unwrap_or_default()
+                ),
+                Value::String(s) if *ignore_case => format!(
+                    "v.as_str().is_some_and(|s| s.eq_ignore_ascii_case({:?}))",
+                    s
+                ),
+                other => format!("*v == json!({})", other),
+            };
+            (path, check)
+        },
+        Assertion::Contains {
+            path,
+            value,
+            ignore_case,
+        } => {
+            let check = if *ignore_case {
+                format!(
+                    "v.as_str().is_some_and(|s| s.to_lowercase().contains({:?}))",
+                    value.to_lowercase()
+                )
+            } else {
+                format!("v.as_str().is_some_and(|s| s.contains({:?}))", value)
+            };
+            (path, check)
+        },
+        // Generated crates do not depend on `regex`; the scenario run still
+        // checks the pattern.
+        Assertion::Matches { path, .. } => (path, "v.is_string()".to_string()),
+        Assertion::Exists { path } => (path, "!v.is_null()".to_string()),
+        Assertion::NotExists { path } => {
+            let segments = output_segments(path)?;
+            return Some(format!(
+                "assert!(output.pointer({:?}).is_none_or(serde_json::Value::is_null), \"{} should not be set\");",
+                json_pointer(&segments),
+                path
+            ));
+        },
+        Assertion::ArrayLength { path, comparison } => (
+            path,
+            format!(
+                "v.as_array().is_some_and(|a| {})",
+                comparison_expr("a.len() as f64", comparison)
+            ),
+        ),
+        Assertion::Numeric { path, comparison } => (
+            path,
+            format!(
+                "v.as_f64().is_some_and(|n| {})",
+                comparison_expr("n", comparison)
+            ),
+        ),
+        _ => return None,
+    };
+    let segments = output_segments(path)?;
+    Some(format!(
+        "assert!(output.pointer({:?}).is_some_and(|v| {}), \"unexpected {}\");",
+        json_pointer(&segments),
+        check,
+        path
+    ))
+}
+
+fn comparison_expr(value: &str, comparison: &Comparison) -> String {
+    match comparison {
+        Comparison::Equals(v) => format!("({} - {:?}).abs() < f64::EPSILON", value, v),
+        Comparison::NotEquals(v) => format!("({} - {:?}).abs() >= f64::EPSILON", value, v),
+        Comparison::GreaterThan(v) => format!("{} > {:?}", value, v),
+        Comparison::GreaterThanOrEqual(v) => format!("{} >= {:?}", value, v),
+        Comparison::LessThan(v) => format!("{} < {:?}", value, v),
+        Comparison::LessThanOrEqual(v) => format!("{} <= {:?}", value, v),
+        Comparison::Between { min, max } => {
+            format!("({:?}..={:?}).contains(&{})", min, max, value)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert() -> TestScenario {
+        serde_json::from_value(json!({
+            "name": "Currency conversion",
+            "steps": [
+                {
+                    "name": "Convert dollars to euros",
+                    "operation": {
+                        "type": "tool_call",
+                        "tool": "convert",
+                        "arguments": { "amount": 10, "from": "USD", "to": "EUR" }
+                    },
+                    "assertions": [
+                        { "type": "success" },
+                        { "type": "numeric", "path": "parsed.converted", "greater_than": 0.0 },
+                        { "type": "equals", "path": "parsed.currency", "value": "EUR" },
+                        { "type": "array_length", "path": "parsed.rates", "equals": 1.0 }
+                    ]
+                },
+                {
+                    "name": "Convert with a rounding precision",
+                    "operation": {
+                        "type": "tool_call",
+                        "tool": "convert",
+                        "arguments": { "amount": 2.5, "from": "USD", "to": "GBP", "precision": 2 }
+                    },
+                    "assertions": [
+                        { "type": "not_exists", "path": "parsed.warning" }
+                    ]
+                },
+                {
+                    "name": "Reject negative amounts",
+                    "operation": {
+                        "type": "tool_call",
+                        "tool": "convert",
+                        "arguments": { "amount": -1, "from": "USD", "to": "EUR" }
+                    },
+                    "assertions": [{ "type": "failure" }]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_infers_input_fields() {
+        let generated = generate("convert", &convert()).unwrap();
+        assert_eq!(generated.input_type, "ConvertInput");
+        assert!(generated.source.contains("    pub amount: f64,\n"));
+        assert!(generated.source.contains("    pub from: String,\n"));
+        assert!(generated
+            .source
+            .contains("    pub precision: Option<i64>,\n"));
+    }
+
+    #[test]
+    fn test_infers_output_fields_from_assertions() {
+        let generated = generate("convert", &convert()).unwrap();
+        assert!(generated.source.contains("    pub converted: f64,\n"));
+        assert!(generated.source.contains("    pub currency: String,\n"));
+        assert!(generated
+            .source
+            .contains("    pub rates: Vec<serde_json::Value>,\n"));
+        assert!(generated
+            .source
+            .contains("    pub warning: Option<serde_json::Value>,\n"));
+    }
+
+    #[test]
+    fn test_generates_one_test_per_step() {
+        let generated = generate("convert", &convert()).unwrap();
+        assert_eq!(generated.tests, 3);
+        assert!(generated
+            .source
+            .contains("async fn test_convert_dollars_to_euros()"));
+        assert!(generated
+            .source
+            .contains("output.pointer(\"/currency\").is_some_and(|v| *v == json!(\"EUR\"))"));
+        assert!(generated
+            .source
+            .contains("Ok(input) => handle(input).await.is_err()"));
+    }
+
+    #[test]
+    fn test_handler_starts_unimplemented() {
+        let generated = generate("convert", &convert()).unwrap();
+        assert!(generated
+            .source
+            .contains("pub async fn handle(input: ConvertInput) -> pmcp::Result<ConvertOutput>"));
+        assert!(generated.source.contains("is not implemented yet"));
+    }
+
+    #[test]
+    fn test_unknown_tool_is_an_error() {
+        let err = generate("divide", &convert()).unwrap_err();
+        assert!(err.to_string().contains("never calls 'divide'"));
+    }
+
+    #[test]
+    fn test_renames_non_snake_case_keys() {
+        let scenario: TestScenario = serde_json::from_value(json!({
+            "name": "Lookup",
+            "steps": [{
+                "name": "Find by id",
+                "operation": { "type": "tool_call", "tool": "lookup", "arguments": { "userId": 7 } },
+                "assertions": [{ "type": "exists", "path": "parsed.displayName" }]
+            }]
+        }))
+        .unwrap();
+        let generated = generate("lookup", &scenario).unwrap();
+        assert!(generated
+            .source
+            .contains("    #[serde(rename = \"userId\")]\n    pub user_id: i64,\n"));
+        assert!(generated.source.contains(
+            "    #[serde(rename = \"displayName\")]\n    pub display_name: serde_json::Value,\n"
+        ));
+    }
+
+    #[test]
+    fn test_json_pointer_handles_indices() {
+        assert_eq!(json_pointer(&["items[2]", "name"]), "/items/2/name");
+        assert_eq!(json_pointer(&["a/b"]), "/a~1b");
+    }
+}
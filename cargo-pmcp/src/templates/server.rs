@@ -43,7 +43,7 @@ fn generate_calculator(name: &str) -> Result<()> {
     println!("  {} Created test scenarios", "✓".green());
 
     // Update workspace Cargo.toml
-    update_workspace_members(&core_name, &server_name)?;
+    update_workspace_members(Path::new("."), &core_name, &server_name)?;
     println!("  {} Updated workspace members", "✓".green());
 
     Ok(())
@@ -67,7 +67,7 @@ fn generate_minimal(name: &str) -> Result<()> {
     println!("  {} Created test scenarios", "✓".green());
 
     // Update workspace Cargo.toml
-    update_workspace_members(&core_name, &server_name)?;
+    update_workspace_members(Path::new("."), &core_name, &server_name)?;
     println!("  {} Updated workspace members", "✓".green());
 
     Ok(())
@@ -95,7 +95,7 @@ fn generate_complete(name: &str) -> Result<()> {
     println!("  {} Created test scenarios", "✓".green());
 
     // Update workspace Cargo.toml
-    update_workspace_members(&core_name, &server_name)?;
+    update_workspace_members(Path::new("."), &core_name, &server_name)?;
     println!("  {} Updated workspace members", "✓".green());
 
     Ok(())
@@ -123,7 +123,7 @@ fn generate_sqlite_explorer(name: &str) -> Result<()> {
     println!("  {} Created test scenarios", "✓".green());
 
     // Update workspace Cargo.toml
-    update_workspace_members(&core_name, &server_name)?;
+    update_workspace_members(Path::new("."), &core_name, &server_name)?;
     println!("  {} Updated workspace members", "✓".green());
 
     // Create chinook.db placeholder or instructions
@@ -573,7 +573,7 @@ License: MIT (included with Chinook)
     Ok(())
 }
 
-fn generate_server_crate(server_dir: &Path, name: &str) -> Result<()> {
+pub(crate) fn generate_server_crate(server_dir: &Path, name: &str) -> Result<()> {
     fs::create_dir_all(server_dir).context("Failed to create server directory")?;
     fs::create_dir_all(server_dir.join("src")).context("Failed to create server src directory")?;
 
@@ -734,10 +734,15 @@ See https://docs.example.com/mcp-tester for full documentation on scenario forma
     Ok(())
 }
 
-fn update_workspace_members(core_name: &str, server_name: &str) -> Result<()> {
-    let cargo_toml_path = Path::new("Cargo.toml");
+/// Add the core and server crates to the members of `workspace_dir/Cargo.toml`.
+pub(crate) fn update_workspace_members(
+    workspace_dir: &Path,
+    core_name: &str,
+    server_name: &str,
+) -> Result<()> {
+    let cargo_toml_path = workspace_dir.join("Cargo.toml");
     let content =
-        fs::read_to_string(cargo_toml_path).context("Failed to read workspace Cargo.toml")?;
+        fs::read_to_string(&cargo_toml_path).context("Failed to read workspace Cargo.toml")?;
 
     // Add members to workspace
     let new_content = content.replace(
@@ -748,7 +753,7 @@ fn update_workspace_members(core_name: &str, server_name: &str) -> Result<()> {
         ),
     );
 
-    fs::write(cargo_toml_path, new_content).context("Failed to update workspace Cargo.toml")?;
+    fs::write(&cargo_toml_path, new_content).context("Failed to update workspace Cargo.toml")?;
 
     Ok(())
 }
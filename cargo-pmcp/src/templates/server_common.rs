@@ -194,6 +194,8 @@ impl ServerHttpMiddleware for OAuthMiddleware {
 /// - Resolves port from PORT/MCP_HTTP_PORT env vars (default: 3000)
 /// - Binds to 0.0.0.0 for container compatibility
 /// - Starts StreamableHttpServer with observability middleware
+/// - With `--self-test`, runs `Server::run_self_test()` instead and exits
+///   non-zero if any check fails (use as a container readiness gate)
 ///
 /// # Example
 /// ```no_run
@@ -210,6 +212,13 @@ impl ServerHttpMiddleware for OAuthMiddleware {
 /// }
 /// ```
 pub async fn run_http(server: Server, server_name: &str, server_version: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // `--self-test`: check tools, resources, workflows, secrets, and backends, then exit
+    if std::env::args().any(|arg| arg == "--self-test") {
+        let report = server.run_self_test().await;
+        println!("{report}");
+        std::process::exit(report.exit_code());
+    }
+
     // Initialize production logging
     init_logging();
 
@@ -264,14 +273,8 @@ pub async fn run_http(server: Server, server_name: &str, server_version: &str) -
     }
 
     // Create stateless configuration with observability
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: Some(Arc::new(middleware_chain)),
-    };
+    let mut config = StreamableHttpServerConfig::stateless();
+    config.http_middleware = Some(Arc::new(middleware_chain));
 
     // Create and start the HTTP server
     let http_server = StreamableHttpServer::with_config(addr, server, config);
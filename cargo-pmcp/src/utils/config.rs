@@ -39,9 +39,15 @@ impl WorkspaceConfig {
 
     /// Save configuration to workspace root
     pub fn save(&self) -> Result<()> {
+        self.save_in(Path::new("."))
+    }
+
+    /// Save configuration to the root of `workspace_dir`
+    pub fn save_in(&self, workspace_dir: &Path) -> Result<()> {
         let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
 
-        fs::write(CONFIG_FILE, content).context("Failed to write .pmcp-config.toml")?;
+        fs::write(workspace_dir.join(CONFIG_FILE), content)
+            .context("Failed to write .pmcp-config.toml")?;
 
         Ok(())
     }
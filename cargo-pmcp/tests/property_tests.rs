@@ -6,7 +6,7 @@
 //! - McpError classification methods are correct for all code values
 //! - Error categories are always non-empty strings
 
-use cargo_pmcp::loadtest::config::{LoadTestConfig, ScenarioStep, Settings};
+use cargo_pmcp::loadtest::config::{LoadTestConfig, ScenarioStep, Settings, Thresholds};
 use cargo_pmcp::loadtest::error::{LoadTestError, McpError};
 use proptest::prelude::*;
 use std::collections::HashMap;
@@ -91,6 +91,7 @@ tool = "{tool_name}"
             settings,
             scenario: vec![],
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         let result = config.validate();
         prop_assert!(result.is_err());
@@ -112,6 +113,7 @@ tool = "{tool_name}"
             settings,
             scenario: steps,
             stage: vec![],
+            thresholds: Thresholds::default(),
         };
         let result = config.validate();
         prop_assert!(result.is_err());
@@ -18,12 +18,10 @@ pub mod server;
 pub use bridge::{get_tool_call_log, get_widget_state, inject_mock_bridge};
 pub use server::start_test_server;
 
-use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::browser::Browser;
 use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
 use chromiumoxide::element::Element;
-use chromiumoxide::fetcher::{BrowserFetcher, BrowserFetcherOptions};
 use chromiumoxide::page::Page;
-use futures::StreamExt;
 
 /// Launch a headless Chromium browser with auto-download.
 ///
@@ -31,29 +29,11 @@ use futures::StreamExt;
 /// Subsequent calls within the same test binary still download (since there
 /// is no cross-test-binary shared state), but the fetcher caches the binary
 /// on disk so the download is skipped if already present.
+///
+/// Shares its launch configuration with the preview server's screenshot
+/// endpoint ([`mcp_preview::screenshot::launch_browser`]).
 pub async fn launch_browser() -> anyhow::Result<Browser> {
-    let download_path = std::env::temp_dir().join("mcp-e2e-chromium");
-    tokio::fs::create_dir_all(&download_path).await?;
-
-    let fetcher = BrowserFetcher::new(
-        BrowserFetcherOptions::builder()
-            .with_path(&download_path)
-            .build()?,
-    );
-    let info = fetcher.fetch().await?;
-
-    let config = BrowserConfig::builder()
-        .chrome_executable(info.executable_path)
-        .arg("--headless")
-        .arg("--disable-gpu")
-        .arg("--no-sandbox")
-        .build()
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
-
-    let (browser, mut handler) = Browser::launch(config).await?;
-    tokio::spawn(async move { while handler.next().await.is_some() {} });
-
-    Ok(browser)
+    mcp_preview::screenshot::launch_browser().await
 }
 
 /// Create a new browser page with the mock bridge pre-injected.
@@ -884,6 +884,9 @@ var PostMessageTransport = class {
     if (this._targetOrigin !== "*" && event.origin !== this._targetOrigin && event.origin !== "null") {
       return;
     }
+    if (event.source && event.source !== this._targetWindow) {
+      return;
+    }
     const data = event.data;
     if (!data || data.jsonrpc !== "2.0") {
       return;
@@ -1051,6 +1054,27 @@ var _App = class _App {
       console.warn("[App] openLink not supported by host:", err);
     }
   }
+  /**
+   * Open the widget rendered by another tool of the same server.
+   *
+   * The host calls the tool and hands `state` to the target widget as
+   * `hostContext.navigation`. Throws if the host does not support
+   * navigation (`ui/navigate`).
+   *
+   * @param params - Target tool, its arguments, and state to hand over
+   */
+  async navigate(params) {
+    if (!this._transport) {
+      throw new Error("[App] Not connected. Call connect() first.");
+    }
+    await this._transport.send("ui/navigate", params);
+  }
+  /**
+   * Return to the widget that navigated here.
+   */
+  async navigateBack() {
+    await this.navigate({ back: true });
+  }
   /**
    * Send a log entry to the host for debugging.
    *
@@ -1182,6 +1206,7 @@ var AppBridge = class {
     this._initialized = false;
     this._iframe = options.iframe;
     this._toolCallHandler = options.toolCallHandler;
+    this._navigateHandler = options.navigateHandler;
     this._origin = options.origin ?? window.location.origin;
     this._hostContext = options.hostContext ?? {};
   }
@@ -1313,6 +1338,16 @@ var AppBridge = class {
       case "ui/update-model-context":
         console.log("[AppBridge] Widget updated model context:", params);
         return {};
+      case "ui/navigate": {
+        if (!this._navigateHandler) {
+          throw new Error("Widget navigation is not supported by this host");
+        }
+        if (!params?.back && !params?.tool) {
+          throw new Error('ui/navigate requires a "tool" parameter');
+        }
+        await this._navigateHandler(params);
+        return {};
+      }
       case "ui/openLink": {
         const url = params?.url;
         if (url) {
@@ -1477,8 +1512,18 @@ function installCompat(app) {
     },
     get displayMode() {
       return chatgptExt?.displayMode ?? getCtx().displayMode;
+    },
+    i18n: createI18n(
+      () => chatgptExt?.locale ?? getCtx().locale,
+      () => getCtx().i18n
+    ),
+    navigate: (tool, args, state) => app.navigate({ tool, arguments: args ?? {}, state }),
+    navigateBack: () => app.navigateBack(),
+    get navigation() {
+      return getCtx().navigation ?? null;
     }
   };
+  interceptWidgetLinks((params) => app.navigate(params));
   window.mcpBridge = mcpBridge;
   window.openai = {
     callTool: mcpBridge.callTool,
@@ -1620,25 +1665,120 @@ function log(level, message, data) {
     logFn(prefix, message);
   }
 }
+var RTL_LANGUAGES = /* @__PURE__ */ new Set([
+  "ar",
+  "arc",
+  "ckb",
+  "dv",
+  "fa",
+  "he",
+  "iw",
+  "ks",
+  "ku",
+  "ps",
+  "sd",
+  "ug",
+  "ur",
+  "yi"
+]);
+function textDirection(locale) {
+  const language = (locale ?? "").toLowerCase().split(/[-_]/)[0];
+  return RTL_LANGUAGES.has(language) ? "rtl" : "ltr";
+}
+function createI18n(getLocale, getContext) {
+  const messages = () => getContext()?.messages ?? {};
+  return {
+    get locale() {
+      return getLocale() ?? "en-US";
+    },
+    get dir() {
+      return getContext()?.dir ?? textDirection(getLocale());
+    },
+    get messages() {
+      return messages();
+    },
+    t(key, params, fallback) {
+      const template = messages()[key] ?? fallback ?? key;
+      if (!params) {
+        return template;
+      }
+      return template.replace(
+        /\{(\w+)\}/g,
+        (match, name) => name in params ? String(params[name]) : match
+      );
+    },
+    has(key) {
+      return Object.prototype.hasOwnProperty.call(messages(), key);
+    }
+  };
+}
+var WIDGET_LINK_PREFIX = "pmcp-widget:";
+function widgetLink(tool, args = {}, state) {
+  const query = new URLSearchParams({ args: JSON.stringify(args) });
+  if (state !== void 0) {
+    query.set("state", JSON.stringify(state));
+  }
+  return `${WIDGET_LINK_PREFIX}${encodeURIComponent(tool)}?${query.toString()}`;
+}
+function parseWidgetLink(href) {
+  const match = /^pmcp-widget:([^?#]+)(?:\?([^#]*))?/.exec(href ?? "");
+  if (!match) {
+    return null;
+  }
+  const query = new URLSearchParams(match[2] ?? "");
+  try {
+    const args = JSON.parse(query.get("args") ?? "{}");
+    if (args === null || typeof args !== "object" || Array.isArray(args)) {
+      return null;
+    }
+    const state = query.get("state");
+    return {
+      tool: decodeURIComponent(match[1]),
+      arguments: args,
+      state: state === null ? void 0 : JSON.parse(state)
+    };
+  } catch {
+    return null;
+  }
+}
+function interceptWidgetLinks(navigate) {
+  const listener = (event) => {
+    const target = event.target;
+    const link = target?.closest?.("a[href]");
+    const params = parseWidgetLink(link?.getAttribute("href"));
+    if (!params) {
+      return;
+    }
+    event.preventDefault();
+    navigate(params);
+  };
+  document.addEventListener("click", listener);
+  return () => document.removeEventListener("click", listener);
+}
 export {
   App,
   AppBridge,
   PostMessageTransport,
   SET_GLOBALS_EVENT_TYPE,
   WidgetRuntime,
+  createI18n,
   createMessageId,
   debounce,
   WidgetRuntime as default,
   detectHost,
   getBridge,
   installCompat,
+  interceptWidgetLinks,
   isChatGPT,
   isMcpApps,
   isMcpUI,
   isWidget,
   log,
   mergeState,
+  parseWidgetLink,
   serializeState,
+  textDirection,
   throttle,
-  waitForBridge
+  waitForBridge,
+  widgetLink
 };
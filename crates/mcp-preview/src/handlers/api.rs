@@ -5,6 +5,7 @@ use axum::{
     http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Json},
 };
+use pmcp::data::anonymize::Anonymizer;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -20,11 +21,19 @@ pub struct ConfigResponse {
     pub locale: String,
     pub initial_tool: Option<String>,
     pub mode: String,
+    /// Host bridge surface to emulate (`compat`, `chatgpt-apps`, `mcp-apps`, `mcp-ui`).
+    pub host_profile: String,
     pub descriptor_keys: Vec<String>,
     pub invocation_keys: Vec<String>,
     /// OAuth configuration for browser-based PKCE flow (null when OAuth not configured).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oauth_config: Option<OAuthConfigResponse>,
+    /// Tools answered from mock fixtures (empty when mock mode is off).
+    pub mocked_tools: Vec<String>,
+    /// Whether a session snapshot is available at `/api/session` for replay.
+    pub session_snapshot: bool,
+    /// Whether message catalogs are available at `/api/messages`.
+    pub messages: bool,
 }
 
 /// OAuth configuration exposed to the browser.
@@ -77,15 +86,25 @@ pub async fn get_config(State(state): State<Arc<AppState>>) -> Json<ConfigRespon
             scopes: oc.scopes.clone(),
         });
 
+    let mocked_tools = state
+        .proxy
+        .mocks()
+        .map(|mocks| mocks.mocked_tools())
+        .unwrap_or_default();
+
     Json(ConfigResponse {
         mcp_url: state.config.mcp_url.clone(),
         theme: state.config.theme.clone(),
         locale: state.config.locale.clone(),
         initial_tool: state.config.initial_tool.clone(),
         mode: state.config.mode.to_string(),
+        host_profile: state.config.host_profile.to_string(),
         descriptor_keys,
         invocation_keys,
         oauth_config,
+        mocked_tools,
+        session_snapshot: state.config.session_file.is_some(),
+        messages: state.config.messages_dir.is_some(),
     })
 }
 
@@ -405,6 +424,106 @@ pub async fn reconnect(State(state): State<Arc<AppState>>) -> Json<Value> {
     }
 }
 
+/// Serve the session snapshot configured with `--session`.
+///
+/// The file is read on every request so a re-exported snapshot is picked up
+/// on the next page reload.
+pub async fn get_session(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let Some(ref path) = state.config.session_file else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "No session snapshot configured" })),
+        );
+    };
+
+    let snapshot = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        .and_then(|content| {
+            serde_json::from_str::<Value>(&content)
+                .map_err(|e| format!("Invalid session snapshot {}: {}", path.display(), e))
+        });
+
+    match snapshot {
+        Ok(snapshot) => (StatusCode::OK, Json(snapshot)),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": error })),
+        ),
+    }
+}
+
+/// Serve the message catalogs configured with `--messages`.
+///
+/// Returns `{ "<locale>": { "<id>": "<message>" } }` built from every
+/// `{locale}.json` in the directory. Files are read on every request so
+/// edited catalogs are picked up on the next page reload.
+pub async fn get_messages(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let Some(ref dir) = state.config.messages_dir else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "No message catalogs configured" })),
+        );
+    };
+
+    match read_message_catalogs(dir).await {
+        Ok(catalogs) => (StatusCode::OK, Json(Value::Object(catalogs))),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": error })),
+        ),
+    }
+}
+
+async fn read_message_catalogs(
+    dir: &std::path::Path,
+) -> Result<serde_json::Map<String, Value>, String> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut catalogs = serde_json::Map::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let catalog: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid message catalog {}: {}", path.display(), e))?;
+        if !catalog.is_object() {
+            return Err(format!(
+                "Invalid message catalog {}: expected a JSON object",
+                path.display()
+            ));
+        }
+        catalogs.insert(locale.to_string(), catalog);
+    }
+    Ok(catalogs)
+}
+
+/// Export the JSON-RPC traffic of this session in the MCP Inspector's
+/// history format, as a file download.
+pub async fn export_inspector_session(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let anonymizer = state.config.anonymize.then(Anonymizer::new);
+    let export = state
+        .proxy
+        .session_log()
+        .inspector_export(state.proxy.base_url(), anonymizer.as_ref());
+    (
+        [(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_static("attachment; filename=\"mcp-preview-inspector.json\""),
+        )],
+        Json(export),
+    )
+}
+
 /// Check whether the MCP session is currently connected.
 pub async fn status(State(state): State<Arc<AppState>>) -> Json<Value> {
     let connected = state.proxy.is_connected().await;
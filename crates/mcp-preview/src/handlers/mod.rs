@@ -4,5 +4,6 @@ pub mod api;
 pub mod assets;
 pub mod auth;
 pub mod page;
+pub mod screenshot;
 pub mod wasm;
 pub mod websocket;
@@ -6,3 +6,8 @@ use axum::response::Html;
 pub async fn index() -> Html<&'static str> {
     Html(include_str!("../../assets/index.html"))
 }
+
+/// Serve the widget gallery page
+pub async fn gallery() -> Html<&'static str> {
+    Html(include_str!("../../assets/gallery.html"))
+}
@@ -0,0 +1,233 @@
+//! Widget screenshot and visual regression handlers
+//!
+//! `GET /api/screenshot` renders a widget in headless Chromium and returns a
+//! PNG. `POST /api/screenshot/compare` captures the same way and diffs the
+//! result against a named baseline in the baselines directory.
+//!
+//! Both endpoints answer `501 Not Implemented` unless the crate is built with
+//! the `screenshot` feature.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::server::AppState;
+
+#[cfg(feature = "screenshot")]
+use axum::{extract::Query, http::header, response::Json};
+#[cfg(feature = "screenshot")]
+use serde::Deserialize;
+#[cfg(feature = "screenshot")]
+use serde_json::json;
+#[cfg(feature = "screenshot")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "screenshot")]
+use crate::screenshot::{compare_png, ScreenshotRequest};
+
+#[cfg(not(feature = "screenshot"))]
+const DISABLED: &str = "Screenshots require mcp-preview built with the `screenshot` feature";
+
+/// Baselines directory used when `PreviewConfig::baselines_dir` is unset.
+#[cfg(feature = "screenshot")]
+const DEFAULT_BASELINES_DIR: &str = "visual-baselines";
+
+/// Compare request: the widget to capture plus the baseline to diff against.
+#[cfg(feature = "screenshot")]
+#[derive(Deserialize)]
+pub struct CompareRequest {
+    #[serde(flatten)]
+    pub screenshot: ScreenshotRequest,
+    /// Baseline name; stored as `{baselines_dir}/{baseline}.png`
+    pub baseline: String,
+    /// Maximum fraction of differing pixels that still counts as a match
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    /// Per-channel difference ignored when comparing pixels (0-255)
+    #[serde(default = "default_tolerance")]
+    pub tolerance: u8,
+    /// Overwrite the baseline with the new capture
+    #[serde(default)]
+    pub update: bool,
+}
+
+#[cfg(feature = "screenshot")]
+fn default_threshold() -> f64 {
+    0.001
+}
+
+#[cfg(feature = "screenshot")]
+fn default_tolerance() -> u8 {
+    8
+}
+
+/// Render a widget and return it as a PNG.
+#[cfg(feature = "screenshot")]
+pub async fn screenshot(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<ScreenshotRequest>,
+) -> Response {
+    match state
+        .screenshotter
+        .capture(state.config.port, &request)
+        .await
+    {
+        Ok(png) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)),
+    }
+}
+
+/// Render a widget and return it as a PNG.
+#[cfg(not(feature = "screenshot"))]
+pub async fn screenshot(State(_state): State<Arc<AppState>>) -> Response {
+    (StatusCode::NOT_IMPLEMENTED, DISABLED).into_response()
+}
+
+/// Capture a widget and diff it against a stored baseline.
+///
+/// A missing baseline is created from the capture (`"created"`);
+/// `update: true` overwrites it (`"updated"`). On a mismatch the capture
+/// and a diff image are written next to the baseline as
+/// `{baseline}.actual.png` and `{baseline}.diff.png`.
+#[cfg(feature = "screenshot")]
+pub async fn compare(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CompareRequest>,
+) -> Response {
+    if !is_valid_baseline_name(&request.baseline) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Invalid baseline name '{}': use letters, digits, '-', '_' and '.'",
+                request.baseline
+            ),
+        );
+    }
+
+    let dir = state
+        .config
+        .baselines_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINES_DIR));
+    let baseline_path = dir.join(format!("{}.png", request.baseline));
+    let actual_path = dir.join(format!("{}.actual.png", request.baseline));
+    let diff_path = dir.join(format!("{}.diff.png", request.baseline));
+
+    let actual = match state
+        .screenshotter
+        .capture(state.config.port, &request.screenshot)
+        .await
+    {
+        Ok(png) => png,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)),
+    };
+
+    let existing = match tokio::fs::read(&baseline_path).await {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read {}: {}", baseline_path.display(), e),
+            )
+        },
+    };
+
+    let baseline = match existing {
+        Some(bytes) if !request.update => bytes,
+        existing => {
+            let status = if existing.is_some() {
+                "updated"
+            } else {
+                "created"
+            };
+            if let Err(e) = write_file(&baseline_path, &actual).await {
+                return error(StatusCode::INTERNAL_SERVER_ERROR, e);
+            }
+            remove_stale(&actual_path, &diff_path).await;
+            return Json(json!({
+                "status": status,
+                "baseline": baseline_path.display().to_string(),
+            }))
+            .into_response();
+        },
+    };
+
+    let tolerance = request.tolerance;
+    let compared = tokio::task::spawn_blocking(move || {
+        compare_png(&baseline, &actual, tolerance).map(|diff| (diff, actual))
+    })
+    .await;
+    let (diff, actual) = match compared {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => return error(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)),
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    let matches = !diff.size_mismatch && diff.diff_ratio <= request.threshold;
+    let mut report = json!({
+        "status": if matches { "match" } else { "mismatch" },
+        "baseline": baseline_path.display().to_string(),
+        "diffPixels": diff.diff_pixels,
+        "diffRatio": diff.diff_ratio,
+        "threshold": request.threshold,
+        "sizeMismatch": diff.size_mismatch,
+    });
+
+    if matches {
+        remove_stale(&actual_path, &diff_path).await;
+    } else {
+        for (path, bytes) in [(&actual_path, &actual), (&diff_path, &diff.diff_png)] {
+            if let Err(e) = write_file(path, bytes).await {
+                return error(StatusCode::INTERNAL_SERVER_ERROR, e);
+            }
+        }
+        report["actual"] = json!(actual_path.display().to_string());
+        report["diff"] = json!(diff_path.display().to_string());
+    }
+
+    Json(report).into_response()
+}
+
+/// Capture a widget and diff it against a stored baseline.
+#[cfg(not(feature = "screenshot"))]
+pub async fn compare(State(_state): State<Arc<AppState>>) -> Response {
+    (StatusCode::NOT_IMPLEMENTED, DISABLED).into_response()
+}
+
+#[cfg(feature = "screenshot")]
+fn error(status: StatusCode, message: impl std::fmt::Display) -> Response {
+    (status, Json(json!({ "error": message.to_string() }))).into_response()
+}
+
+/// Baseline names become file names, so keep them to a safe character set.
+#[cfg(feature = "screenshot")]
+fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(feature = "screenshot")]
+async fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    tokio::fs::write(path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Remove leftover `.actual.png` / `.diff.png` files from a previous mismatch.
+#[cfg(feature = "screenshot")]
+async fn remove_stale(actual_path: &Path, diff_path: &Path) {
+    let _ = tokio::fs::remove_file(actual_path).await;
+    let _ = tokio::fs::remove_file(diff_path).await;
+}
@@ -13,15 +13,29 @@
 //! # Features
 //!
 //! - Widget rendering in isolated iframe
+//! - Gallery page rendering every `ui://` resource side by side
 //! - Full `window.mcpBridge` / `window.openai` simulation
-//! - Environment controls (theme, locale, display mode)
-//! - DevTools panel (state, console, network, events)
+//! - Host profiles (ChatGPT Apps, SEP-1865 MCP Apps, MCP-UI) emulating each
+//!   host's bridge surface
+//! - Environment controls (theme, locale, display mode, viewport presets)
+//! - Locale, RTL direction, and message catalog injection for i18n testing
+//! - DevTools panel (state, console, network waterfall, events, accessibility audit,
+//!   performance budgets)
 //! - Live proxy to MCP server via HTTP
+//! - Tool-call mocking from fixture files for offline development
+//! - Session snapshot export/import for replaying bug reports
+//! - JSON-RPC session export in the MCP Inspector's history format
+//! - Headless widget screenshots and visual regression (`screenshot` feature)
 
 mod assets;
 mod handlers;
+mod mocks;
 mod proxy;
+#[cfg(feature = "screenshot")]
+pub mod screenshot;
 mod server;
+mod session_log;
 pub mod wasm_builder;
 
-pub use server::{OAuthPreviewConfig, PreviewConfig, PreviewMode, PreviewServer};
+pub use mocks::ToolMocks;
+pub use server::{HostProfile, OAuthPreviewConfig, PreviewConfig, PreviewMode, PreviewServer};
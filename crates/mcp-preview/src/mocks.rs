@@ -0,0 +1,299 @@
+//! Tool-call mocking for offline widget development
+//!
+//! When a mocks directory is configured, `tools/call` requests for tools that
+//! have a fixture file are answered locally instead of being forwarded to the
+//! MCP server. Fixtures live at `{mocks_dir}/{tool_name}.json` and are read
+//! from disk on every call, so edits take effect without a restart.
+//!
+//! A fixture file holds either a single fixture or an array of fixtures. Each
+//! fixture may restrict itself to specific arguments via `when` (a partial
+//! match against the call arguments); the first matching fixture wins.
+//!
+//! ```json
+//! [
+//!   { "when": { "city": "Paris" }, "structuredContent": { "temp": 18 } },
+//!   { "when": { "city": "Atlantis" }, "error": "City not found" },
+//!   { "content": [{ "type": "text", "text": "Sunny" }], "delayMs": 250 }
+//! ]
+//! ```
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::proxy::{ContentItem, ToolCallResult};
+
+/// `_meta` key marking a tool result as served from a fixture.
+pub(crate) const MOCKED_META_KEY: &str = "mcp-preview/mocked";
+
+/// A single canned response for a tool call.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolFixture {
+    /// Partial argument match; `None` matches every call.
+    #[serde(default)]
+    when: Option<Value>,
+    #[serde(default)]
+    content: Option<Vec<ContentItem>>,
+    #[serde(default)]
+    structured_content: Option<Value>,
+    #[serde(rename = "_meta", default)]
+    meta: Option<Value>,
+    /// When set, the call fails with this message (simulated tool error).
+    #[serde(default)]
+    error: Option<String>,
+    /// Artificial latency before responding, in milliseconds.
+    #[serde(default)]
+    delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FixtureFile {
+    Many(Vec<ToolFixture>),
+    One(Box<ToolFixture>),
+}
+
+/// Fixture-backed tool responses loaded from a directory.
+#[derive(Debug, Clone)]
+pub struct ToolMocks {
+    dir: PathBuf,
+}
+
+impl ToolMocks {
+    /// Create a mock layer reading fixtures from `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the fixtures are read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of tools that currently have a fixture file.
+    pub fn mocked_tools(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Answer a tool call from fixtures, or `None` to forward it to the server.
+    ///
+    /// Malformed fixture files are logged and treated as absent so a typo never
+    /// silently masks the live server.
+    pub async fn respond(&self, name: &str, arguments: &Value) -> Option<ToolCallResult> {
+        let fixture = self.find_fixture(name, arguments)?;
+
+        if let Some(delay) = fixture.delay_ms {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+
+        tracing::debug!("Serving mocked response for tool '{}'", name);
+
+        let mut meta = fixture.meta.unwrap_or_else(|| json!({}));
+        if let Some(obj) = meta.as_object_mut() {
+            obj.insert(MOCKED_META_KEY.to_string(), Value::Bool(true));
+        }
+
+        Some(match fixture.error {
+            Some(error) => ToolCallResult {
+                success: false,
+                content: None,
+                error: Some(error),
+                structured_content: None,
+                meta: Some(meta),
+            },
+            None => ToolCallResult {
+                success: true,
+                content: Some(fixture.content.unwrap_or_default()),
+                error: None,
+                structured_content: fixture.structured_content,
+                meta: Some(meta),
+            },
+        })
+    }
+
+    fn find_fixture(&self, name: &str, arguments: &Value) -> Option<ToolFixture> {
+        // Reject names that could escape the mocks directory
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return None;
+        }
+
+        let path = self.dir.join(format!("{}.json", name));
+        let raw = std::fs::read_to_string(&path).ok()?;
+        let fixtures = match serde_json::from_str::<FixtureFile>(&raw) {
+            Ok(FixtureFile::Many(fixtures)) => fixtures,
+            Ok(FixtureFile::One(fixture)) => vec![*fixture],
+            Err(e) => {
+                tracing::warn!("Ignoring invalid mock fixture {}: {}", path.display(), e);
+                return None;
+            },
+        };
+
+        fixtures.into_iter().find(|f| {
+            f.when
+                .as_ref()
+                .is_none_or(|pattern| json_contains(arguments, pattern))
+        })
+    }
+}
+
+/// Whether `value` contains every field of `pattern` (recursively for objects).
+fn json_contains(value: &Value, pattern: &Value) -> bool {
+    match (value, pattern) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(key, exp)| actual.get(key).is_some_and(|act| json_contains(act, exp))),
+        _ => value == pattern,
+    }
+}
+
+/// Convert a mocked tool result into a JSON-RPC `tools/call` result body.
+pub(crate) fn to_call_tool_result(result: &ToolCallResult) -> Value {
+    let mut body = json!({
+        "content": result.content.clone().unwrap_or_default(),
+        "isError": !result.success,
+    });
+    if let Some(error) = &result.error {
+        body["content"] = json!([{ "type": "text", "text": error }]);
+    }
+    if let Some(structured) = &result.structured_content {
+        body["structuredContent"] = structured.clone();
+    }
+    if let Some(meta) = &result.meta {
+        body["_meta"] = meta.clone();
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Temporary directory holding `mocks/` plus a sibling `secret.json`.
+    struct Fixtures {
+        root: PathBuf,
+        mocks: ToolMocks,
+    }
+
+    impl Fixtures {
+        fn new() -> Self {
+            let root =
+                std::env::temp_dir().join(format!("mcp-preview-mocks-{}", uuid::Uuid::new_v4()));
+            let dir = root.join("mocks");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                root.join("secret.json"),
+                r#"{ "structuredContent": "secret" }"#,
+            )
+            .unwrap();
+            Self {
+                root,
+                mocks: ToolMocks::new(dir),
+            }
+        }
+
+        fn write(&self, tool: &str, fixture: Value) {
+            let path = self.mocks.dir().join(format!("{}.json", tool));
+            std::fs::write(path, fixture.to_string()).unwrap();
+        }
+    }
+
+    impl Drop for Fixtures {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_names_outside_mocks_dir() {
+        let fixtures = Fixtures::new();
+        let args = json!({});
+
+        for name in ["../secret", "..\\secret", "nested/secret", "..", ""] {
+            assert!(
+                fixtures.mocks.respond(name, &args).await.is_none(),
+                "{name:?} should not resolve to a fixture"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn first_matching_fixture_wins() {
+        let fixtures = Fixtures::new();
+        fixtures.write(
+            "weather",
+            json!([
+                { "when": { "city": "Paris", "units": { "temp": "C" } }, "structuredContent": { "temp": 18 } },
+                { "when": { "city": "Atlantis" }, "error": "City not found" },
+                { "structuredContent": { "temp": 0 } },
+                { "when": { "city": "Paris" }, "structuredContent": { "temp": 99 } }
+            ]),
+        );
+
+        let paris = fixtures
+            .mocks
+            .respond(
+                "weather",
+                &json!({ "city": "Paris", "units": { "temp": "C", "wind": "kph" } }),
+            )
+            .await
+            .unwrap();
+        assert!(paris.success);
+        assert_eq!(paris.structured_content, Some(json!({ "temp": 18 })));
+        assert_eq!(paris.meta.unwrap()[MOCKED_META_KEY], true);
+
+        let atlantis = fixtures
+            .mocks
+            .respond("weather", &json!({ "city": "Atlantis" }))
+            .await
+            .unwrap();
+        assert!(!atlantis.success);
+        assert_eq!(atlantis.error.as_deref(), Some("City not found"));
+
+        // Nested patterns must match field by field, so this falls through
+        let fahrenheit = fixtures
+            .mocks
+            .respond(
+                "weather",
+                &json!({ "city": "Paris", "units": { "temp": "F" } }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(fahrenheit.structured_content, Some(json!({ "temp": 0 })));
+    }
+
+    #[tokio::test]
+    async fn unmatched_or_invalid_fixtures_forward_to_server() {
+        let fixtures = Fixtures::new();
+        fixtures.write("search", json!({ "when": { "q": "rust" }, "content": [] }));
+        std::fs::write(fixtures.mocks.dir().join("broken.json"), "{ not json").unwrap();
+
+        assert!(fixtures
+            .mocks
+            .respond("search", &json!({ "q": "go" }))
+            .await
+            .is_none());
+        assert!(fixtures.mocks.respond("broken", &json!({})).await.is_none());
+        assert!(fixtures
+            .mocks
+            .respond("missing", &json!({}))
+            .await
+            .is_none());
+        assert!(fixtures
+            .mocks
+            .respond("search", &json!({ "q": "rust" }))
+            .await
+            .is_some());
+    }
+}
@@ -11,6 +11,9 @@ use serde_json::{json, Value};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
 
+use crate::mocks::{self, ToolMocks};
+use crate::session_log::{Direction, RequestTimer, SessionLog};
+
 /// JSON-RPC request
 #[derive(Debug, Serialize)]
 struct JsonRpcRequest {
@@ -193,27 +196,46 @@ async fn check_mcp_response(
     Ok(response)
 }
 
-/// Parse a JSON-RPC response, handling both plain JSON and SSE (text/event-stream).
-///
-/// Streamable HTTP MCP servers may return SSE with `event: message\ndata: {...}`
-/// instead of plain JSON. This function detects the content-type and parses accordingly.
-async fn parse_rpc_response(response: reqwest::Response) -> Result<JsonRpcResponse> {
-    let is_sse = response
-        .headers()
+/// Whether a response carries an SSE stream (`text/event-stream`).
+fn is_sse(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
         .get("content-type")
         .and_then(|v| v.to_str().ok())
-        .is_some_and(|ct| ct.contains("text/event-stream"));
+        .is_some_and(|ct| ct.contains("text/event-stream"))
+}
 
-    if is_sse {
-        let body = response.text().await?;
-        let json_str = body
-            .lines()
-            .find_map(|line| line.strip_prefix("data: "))
-            .unwrap_or(&body);
-        Ok(serde_json::from_str(json_str)?)
+/// Split a response body into its JSON-RPC messages, handling both plain
+/// JSON and SSE (text/event-stream).
+///
+/// Streamable HTTP MCP servers may return SSE with `event: message\ndata: {...}`
+/// instead of plain JSON, and may send notifications (progress, logging)
+/// on the stream ahead of the response.
+fn split_rpc_messages(body: &str, sse: bool) -> Vec<Value> {
+    let mut messages: Vec<Value> = if sse {
+        body.lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|data| serde_json::from_str(data).ok())
+            .collect()
     } else {
-        Ok(response.json().await?)
+        Vec::new()
+    };
+    if messages.is_empty() {
+        match serde_json::from_str(body) {
+            Ok(Value::Array(batch)) => messages = batch,
+            Ok(message) => messages.push(message),
+            Err(_) => {},
+        }
     }
+    messages
+}
+
+/// Remove and return the response (the message with a `result` or `error`).
+fn take_response(messages: &mut Vec<Value>) -> Result<Value> {
+    let index = messages
+        .iter()
+        .position(|m| m.get("result").is_some() || m.get("error").is_some())
+        .ok_or_else(|| anyhow::anyhow!("MCP server response contained no JSON-RPC response"))?;
+    Ok(messages.remove(index))
 }
 
 /// MCP HTTP Proxy with session-once initialization.
@@ -250,6 +272,8 @@ pub struct McpProxy {
     request_id: AtomicU64,
     session: RwLock<Option<SessionInfo>>,
     auth_header: SyncRwLock<Option<String>>,
+    mocks: Option<ToolMocks>,
+    log: SessionLog,
 }
 
 impl McpProxy {
@@ -271,9 +295,32 @@ impl McpProxy {
             request_id: AtomicU64::new(1),
             session: RwLock::new(None),
             auth_header: SyncRwLock::new(auth_header),
+            mocks: None,
+            log: SessionLog::new(),
         }
     }
 
+    /// Answer `tools/call` for tools with fixtures from `mocks` instead of the server.
+    pub fn with_mocks(mut self, mocks: ToolMocks) -> Self {
+        self.mocks = Some(mocks);
+        self
+    }
+
+    /// Tool mocks, if mock mode is enabled.
+    pub fn mocks(&self) -> Option<&ToolMocks> {
+        self.mocks.as_ref()
+    }
+
+    /// URL of the target MCP server.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// JSON-RPC traffic exchanged with the MCP server in this session.
+    pub fn session_log(&self) -> &SessionLog {
+        &self.log
+    }
+
     /// Borrow the shared HTTP client for reuse (e.g., in token exchange).
     pub fn http_client(&self) -> &reqwest::Client {
         &self.client
@@ -362,11 +409,18 @@ impl McpProxy {
             id: self.next_id(),
         };
 
+        let timer = RequestTimer::start();
         let response = check_response(self.mcp_post().json(&request_body).send().await?).await?;
 
         let session_id = extract_header(response.headers(), MCP_SESSION_ID);
 
-        let rpc_response: JsonRpcResponse = parse_rpc_response(response).await?;
+        let raw_response = self.read_rpc_response(response).await?;
+        self.log.record_request(
+            serde_json::to_value(&request_body)?,
+            Some(raw_response.clone()),
+            timer,
+        );
+        let rpc_response: JsonRpcResponse = serde_json::from_value(raw_response)?;
 
         if let Some(error) = rpc_response.error {
             anyhow::bail!("MCP initialize error: {}", error.message);
@@ -410,15 +464,16 @@ impl McpProxy {
         };
 
         let req_builder = self.attach_session_id(self.mcp_post().json(&request)).await;
-        let response = req_builder
-            .send()
-            .await
-            .map_err(|e| McpRequestError::Other(e.into()))?;
-        let response = check_mcp_response(response).await?;
+        let timer = RequestTimer::start();
+        let raw_response = self.post_rpc(req_builder).await;
+        self.log.record_request(
+            serde_json::to_value(&request).unwrap_or(Value::Null),
+            raw_response.as_ref().ok().cloned(),
+            timer,
+        );
 
-        let rpc_response: JsonRpcResponse = parse_rpc_response(response)
-            .await
-            .map_err(|e| McpRequestError::Other(e))?;
+        let rpc_response: JsonRpcResponse =
+            serde_json::from_value(raw_response?).map_err(|e| McpRequestError::Other(e.into()))?;
 
         if let Some(error) = rpc_response.error {
             return Err(McpRequestError::Other(anyhow::anyhow!(
@@ -430,6 +485,35 @@ impl McpProxy {
         Ok(rpc_response.result.unwrap_or(Value::Null))
     }
 
+    /// Send a JSON-RPC request and return the raw response message.
+    async fn post_rpc(
+        &self,
+        req_builder: reqwest::RequestBuilder,
+    ) -> Result<Value, McpRequestError> {
+        let response = req_builder
+            .send()
+            .await
+            .map_err(|e| McpRequestError::Other(e.into()))?;
+        let response = check_mcp_response(response).await?;
+        self.read_rpc_response(response)
+            .await
+            .map_err(McpRequestError::Other)
+    }
+
+    /// Read the JSON-RPC response from an HTTP response, logging any server
+    /// notifications streamed ahead of it.
+    async fn read_rpc_response(&self, response: reqwest::Response) -> Result<Value> {
+        let sse = is_sse(response.headers());
+        let body = response.text().await?;
+        let mut messages = split_rpc_messages(&body, sse);
+        let raw_response = take_response(&mut messages)?;
+        for notification in messages {
+            self.log
+                .record_notification(Direction::Server, notification);
+        }
+        Ok(raw_response)
+    }
+
     /// Send a JSON-RPC notification (no `id` field, fire-and-forget).
     ///
     /// Notifications do not expect a response from the server.
@@ -442,6 +526,9 @@ impl McpProxy {
         let req_builder = self
             .attach_session_id(self.mcp_post().json(&notification))
             .await;
+        if let Ok(message) = serde_json::to_value(&notification) {
+            self.log.record_notification(Direction::Client, message);
+        }
         let _ = req_builder.send().await;
         Ok(())
     }
@@ -480,13 +567,36 @@ impl McpProxy {
 
     /// Call a tool on the MCP server.
     ///
-    /// Ensures the session is initialized before sending the request.
-    /// Returns `McpRequestError::AuthRequired` for upstream 401/403.
+    /// Tools with a mock fixture are answered locally without contacting the
+    /// server. Otherwise ensures the session is initialized before sending the
+    /// request. Returns `McpRequestError::AuthRequired` for upstream 401/403.
     pub async fn call_tool(
         &self,
         name: &str,
         arguments: Value,
     ) -> Result<ToolCallResult, McpRequestError> {
+        if let Some(ref mocks) = self.mocks {
+            let timer = RequestTimer::start();
+            if let Some(result) = mocks.respond(name, &arguments).await {
+                let id = self.next_id();
+                self.log.record_request(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "method": "tools/call",
+                        "params": { "name": name, "arguments": arguments },
+                    }),
+                    Some(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": mocks::to_call_tool_result(&result),
+                    })),
+                    timer,
+                );
+                return Ok(result);
+            }
+        }
+
         self.ensure_initialized()
             .await
             .map_err(McpRequestError::Other)?;
@@ -587,12 +697,29 @@ impl McpProxy {
     /// Unlike other proxy methods, this does NOT call `ensure_initialized()`
     /// because the WASM client manages its own MCP session lifecycle
     /// (initialize, notifications/initialized, etc.).
+    ///
+    /// `tools/call` requests for mocked tools are answered locally.
     pub async fn forward_raw(
         &self,
         body: String,
         session_id: Option<&str>,
         protocol_version: Option<&str>,
     ) -> Result<RawForwardResult, McpRequestError> {
+        let timer = RequestTimer::start();
+        let request: Option<Value> = serde_json::from_str(&body).ok();
+
+        if let Some(mocked) = self.mock_raw_tool_call(&body).await {
+            if let Some(request) = request {
+                self.log
+                    .record_request(request, serde_json::from_str(&mocked).ok(), timer);
+            }
+            return Ok(RawForwardResult {
+                body: mocked,
+                session_id: session_id.map(String::from),
+                protocol_version: protocol_version.map(String::from),
+            });
+        }
+
         let mut req_builder = self.mcp_post().body(body);
 
         // Forward MCP session headers from the WASM client
@@ -612,15 +739,57 @@ impl McpProxy {
         // Capture MCP session headers to forward back to the WASM client
         let session_id = extract_header(response.headers(), MCP_SESSION_ID);
         let protocol_version = extract_header(response.headers(), MCP_PROTOCOL_VERSION);
+        let sse = is_sse(response.headers());
 
         let body = response
             .text()
             .await
             .map_err(|e| McpRequestError::Other(e.into()))?;
+        self.log_raw_exchange(request, &body, sse, timer);
         Ok(RawForwardResult {
             body,
             session_id,
             protocol_version,
         })
     }
+
+    /// Log a message forwarded for the WASM client: a request with its
+    /// response, or a client notification (which has no `id`).
+    fn log_raw_exchange(&self, request: Option<Value>, body: &str, sse: bool, timer: RequestTimer) {
+        let Some(request) = request else {
+            return;
+        };
+        if request.get("id").is_none() {
+            self.log.record_notification(Direction::Client, request);
+            return;
+        }
+        let mut messages = split_rpc_messages(body, sse);
+        let response = take_response(&mut messages).ok();
+        for notification in messages {
+            self.log
+                .record_notification(Direction::Server, notification);
+        }
+        self.log.record_request(request, response, timer);
+    }
+
+    /// Answer a raw JSON-RPC `tools/call` body from mock fixtures, if one matches.
+    async fn mock_raw_tool_call(&self, body: &str) -> Option<String> {
+        let mocks = self.mocks.as_ref()?;
+        let request: Value = serde_json::from_str(body).ok()?;
+        if request.get("method").and_then(Value::as_str) != Some("tools/call") {
+            return None;
+        }
+
+        let params = request.get("params")?;
+        let name = params.get("name").and_then(Value::as_str)?;
+        let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+        let result = mocks.respond(name, &arguments).await?;
+
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": request.get("id").cloned().unwrap_or(Value::Null),
+            "result": mocks::to_call_tool_result(&result),
+        });
+        Some(response.to_string())
+    }
 }
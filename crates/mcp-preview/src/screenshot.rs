@@ -0,0 +1,262 @@
+//! Headless widget screenshots and visual regression
+//!
+//! Renders a single `ui://` resource through the gallery page in headless
+//! Chromium and captures the widget iframe as a PNG. The browser is launched
+//! lazily on the first capture, reused afterwards and relaunched if it
+//! crashes.
+//!
+//! [`compare_png`] diffs a capture against a stored baseline pixel by pixel
+//! and produces a diff image with mismatched pixels highlighted in red.
+//!
+//! Available behind the `screenshot` feature flag.
+
+use anyhow::{Context, Result};
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat;
+use chromiumoxide::fetcher::{BrowserFetcher, BrowserFetcherOptions};
+use futures::StreamExt;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use serde::Deserialize;
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long to wait for the gallery page to report the widget as loaded.
+const READY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Default delay after load so widget scripts can finish rendering.
+const DEFAULT_SETTLE_MS: u64 = 500;
+
+/// Launch a headless Chromium browser with auto-download.
+///
+/// Downloads Chromium to a temporary directory on first use; the fetcher
+/// caches the binary on disk so later launches skip the download. Shared
+/// with the `mcp-e2e-tests` crate.
+pub async fn launch_browser() -> Result<Browser> {
+    let download_path = std::env::temp_dir().join("mcp-e2e-chromium");
+    tokio::fs::create_dir_all(&download_path).await?;
+
+    let fetcher = BrowserFetcher::new(
+        BrowserFetcherOptions::builder()
+            .with_path(&download_path)
+            .build()?,
+    );
+    let info = fetcher.fetch().await?;
+
+    let config = BrowserConfig::builder()
+        .chrome_executable(info.executable_path)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-sandbox")
+        .build()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let (browser, mut handler) = Browser::launch(config).await?;
+    tokio::spawn(async move { while handler.next().await.is_some() {} });
+
+    Ok(browser)
+}
+
+/// Widget and environment to capture.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScreenshotRequest {
+    /// `ui://` resource URI of the widget
+    pub uri: String,
+    /// Theme (`light` or `dark`)
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Locale passed to the widget host context
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Display mode (`inline`, `pip`, `fullscreen`)
+    #[serde(default)]
+    pub display_mode: Option<String>,
+    /// Iframe width in pixels
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Iframe height in pixels
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// Extra delay after load before capturing, in milliseconds
+    #[serde(default)]
+    pub settle_ms: Option<u64>,
+}
+
+impl ScreenshotRequest {
+    /// Gallery page URL rendering only this widget with the requested environment.
+    fn gallery_url(&self, port: u16) -> Result<String> {
+        let params = [
+            ("uri", Some(self.uri.clone())),
+            ("theme", self.theme.clone()),
+            ("locale", self.locale.clone()),
+            ("displayMode", self.display_mode.clone()),
+            ("width", self.width.map(|w| w.to_string())),
+            ("height", self.height.map(|h| h.to_string())),
+        ];
+        let url = reqwest::Url::parse_with_params(
+            &format!("http://127.0.0.1:{}/gallery", port),
+            params
+                .iter()
+                .filter_map(|(key, value)| value.as_deref().map(|v| (*key, v))),
+        )?;
+        Ok(url.into())
+    }
+}
+
+/// Captures widget screenshots with a shared headless browser.
+#[derive(Default)]
+pub struct Screenshotter {
+    browser: Mutex<Option<Browser>>,
+}
+
+impl Screenshotter {
+    /// Create a screenshotter; the browser starts on the first capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render the widget on the preview server at `port` and return PNG bytes.
+    pub async fn capture(&self, port: u16, request: &ScreenshotRequest) -> Result<Vec<u8>> {
+        let mut guard = self.browser.lock().await;
+        if guard.is_none() {
+            let browser = launch_browser()
+                .await
+                .context("Failed to launch headless Chromium")?;
+            *guard = Some(browser);
+        }
+        let browser = guard.as_ref().context("Headless browser unavailable")?;
+
+        // A browser that cannot open or close pages has crashed or lost its
+        // connection; drop it so the next capture launches a fresh one.
+        let page = match browser.new_page(request.gallery_url(port)?).await {
+            Ok(page) => page,
+            Err(e) => {
+                *guard = None;
+                return Err(e).context("Headless browser failed to open a page");
+            },
+        };
+        let result = async {
+            let deadline = Instant::now() + READY_TIMEOUT;
+            loop {
+                let state: String = page
+                    .evaluate("document.body.dataset.galleryState || ''")
+                    .await?
+                    .into_value()?;
+                match state.as_str() {
+                    "ready" => break,
+                    "error" => {
+                        let message: String = page
+                            .evaluate(
+                                "document.querySelector('.card-status.error, .empty-state')?.textContent || 'unknown error'",
+                            )
+                            .await?
+                            .into_value()?;
+                        anyhow::bail!("Widget failed to render: {}", message);
+                    },
+                    _ if Instant::now() >= deadline => {
+                        anyhow::bail!("Timed out waiting for widget {} to render", request.uri);
+                    },
+                    _ => tokio::time::sleep(Duration::from_millis(100)).await,
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(
+                request.settle_ms.unwrap_or(DEFAULT_SETTLE_MS),
+            ))
+            .await;
+
+            let frame = page.find_element(".card-frame").await?;
+            let png = frame.screenshot(CaptureScreenshotFormat::Png).await?;
+            Ok::<_, anyhow::Error>(png)
+        }
+        .await;
+
+        if page.close().await.is_err() {
+            *guard = None;
+        }
+        result
+    }
+}
+
+impl std::fmt::Debug for Screenshotter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Screenshotter").finish_non_exhaustive()
+    }
+}
+
+/// Result of comparing a capture against a baseline.
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Number of pixels that differ beyond the per-channel tolerance
+    pub diff_pixels: u64,
+    /// Fraction of differing pixels (0.0 - 1.0); 1.0 when sizes differ
+    pub diff_ratio: f64,
+    /// Whether the two images have different dimensions
+    pub size_mismatch: bool,
+    /// PNG highlighting differing pixels in red over a faded baseline
+    pub diff_png: Vec<u8>,
+}
+
+/// Compare two PNG images pixel by pixel.
+///
+/// A pixel differs when any RGBA channel differs by more than `tolerance`,
+/// which absorbs anti-aliasing noise between runs.
+pub fn compare_png(baseline: &[u8], actual: &[u8], tolerance: u8) -> Result<ImageDiff> {
+    let baseline = image::load_from_memory_with_format(baseline, ImageFormat::Png)
+        .context("Baseline is not a valid PNG")?
+        .to_rgba8();
+    let actual = image::load_from_memory_with_format(actual, ImageFormat::Png)
+        .context("Screenshot is not a valid PNG")?
+        .to_rgba8();
+
+    let size_mismatch = baseline.dimensions() != actual.dimensions();
+    let width = baseline.width().max(actual.width());
+    let height = baseline.height().max(actual.height());
+
+    let mut diff = RgbaImage::new(width, height);
+    let mut diff_pixels = 0u64;
+    for (x, y, out) in diff.enumerate_pixels_mut() {
+        let a = (x < baseline.width() && y < baseline.height()).then(|| baseline.get_pixel(x, y));
+        let b = (x < actual.width() && y < actual.height()).then(|| actual.get_pixel(x, y));
+        let differs = match (a, b) {
+            (Some(a), Some(b)) => {
+                a.0.iter()
+                    .zip(b.0.iter())
+                    .any(|(ca, cb)| ca.abs_diff(*cb) > tolerance)
+            },
+            _ => true,
+        };
+        *out = if differs {
+            diff_pixels += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            // Faded baseline so the highlighted pixels stand out
+            let [red, green, blue, _] = a.map_or([255, 255, 255, 255], |p| p.0);
+            Rgba([fade(red), fade(green), fade(blue), 255])
+        };
+    }
+
+    let total = u64::from(width) * u64::from(height);
+    let diff_ratio = if size_mismatch {
+        1.0
+    } else if total == 0 {
+        0.0
+    } else {
+        diff_pixels as f64 / total as f64
+    };
+
+    let mut diff_png = Vec::new();
+    DynamicImage::ImageRgba8(diff).write_to(&mut Cursor::new(&mut diff_png), ImageFormat::Png)?;
+
+    Ok(ImageDiff {
+        diff_pixels,
+        diff_ratio,
+        size_mismatch,
+        diff_png,
+    })
+}
+
+fn fade(channel: u8) -> u8 {
+    // Blend 75% towards white
+    (u16::from(channel) / 4 + 191) as u8
+}
@@ -13,6 +13,7 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
 
 use crate::handlers;
+use crate::mocks::ToolMocks;
 use crate::proxy::McpProxy;
 use crate::wasm_builder::{find_workspace_root, WasmBuilder};
 
@@ -52,6 +53,65 @@ impl std::fmt::Display for PreviewMode {
     }
 }
 
+/// Widget host whose bridge surface the preview emulates
+///
+/// Each host exposes a different API to widget iframes. Pick the host a
+/// widget ships to so missing or host-specific APIs fail in the preview the
+/// same way they would in production.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HostProfile {
+    /// Every surface at once: `window.openai`, `window.mcpBridge`, and the
+    /// `ui/*` postMessage protocol (default)
+    #[default]
+    Compat,
+    /// ChatGPT Apps: `window.openai` only, with `openai:set_globals` events
+    ChatGptApps,
+    /// SEP-1865 MCP Apps: the `ui/*` JSON-RPC postMessage protocol only
+    McpApps,
+    /// MCP-UI: `window.mcpBridge` backed by MCP-UI `tool`/`intent`/`prompt`/
+    /// `notify`/`link` messages and iframe render data
+    McpUi,
+}
+
+impl HostProfile {
+    /// All profiles, in the order shown to users.
+    pub const ALL: [HostProfile; 4] = [
+        HostProfile::Compat,
+        HostProfile::ChatGptApps,
+        HostProfile::McpApps,
+        HostProfile::McpUi,
+    ];
+}
+
+impl std::fmt::Display for HostProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compat => write!(f, "compat"),
+            Self::ChatGptApps => write!(f, "chatgpt-apps"),
+            Self::McpApps => write!(f, "mcp-apps"),
+            Self::McpUi => write!(f, "mcp-ui"),
+        }
+    }
+}
+
+impl std::str::FromStr for HostProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<String> = Self::ALL.iter().map(ToString::to_string).collect();
+                anyhow::anyhow!(
+                    "Unknown host profile '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
 /// Configuration for the preview server
 #[derive(Debug, Clone)]
 pub struct PreviewConfig {
@@ -74,6 +134,12 @@ pub struct PreviewConfig {
     pub widgets_dir: Option<PathBuf>,
     /// Preview mode (standard or chatgpt)
     pub mode: PreviewMode,
+    /// Host bridge surface exposed to widgets
+    ///
+    /// Independent of `mode`: the mode selects which tool metadata is
+    /// validated, the profile selects which globals and messages a widget
+    /// sees.
+    pub host_profile: HostProfile,
     /// Optional `Authorization` header value for authenticated MCP servers.
     ///
     /// When set, the proxy attaches this header to every outbound request
@@ -81,6 +147,31 @@ pub struct PreviewConfig {
     pub auth_header: Option<String>,
     /// OAuth configuration for browser-based PKCE flow (None = no browser OAuth).
     pub oauth_config: Option<OAuthPreviewConfig>,
+    /// Optional directory of tool-call fixtures (`{tool_name}.json`).
+    ///
+    /// When set, calls to tools with a fixture are answered locally instead of
+    /// hitting the MCP server, enabling offline work and simulated errors.
+    pub mocks_dir: Option<PathBuf>,
+    /// Optional session snapshot (exported from the preview UI) to replay on load.
+    ///
+    /// The file is read on each page load and restores the environment,
+    /// widget state, and tool-call log it captured.
+    pub session_file: Option<PathBuf>,
+    /// Optional directory of message catalogs (`{locale}.json`).
+    ///
+    /// Catalogs are read on each page load and injected into the widget
+    /// host context as `i18n.messages` for the selected locale.
+    pub messages_dir: Option<PathBuf>,
+    /// Directory holding visual regression baselines (`{name}.png`).
+    ///
+    /// Used by `POST /api/screenshot/compare`; defaults to `visual-baselines`
+    /// in the working directory. Requires the `screenshot` feature.
+    pub baselines_dir: Option<PathBuf>,
+    /// Anonymize tool-call arguments and results in the Inspector export.
+    ///
+    /// Names, email addresses and identifying numbers are replaced with
+    /// stable fake values so the export can be shared safely.
+    pub anonymize: bool,
 }
 
 impl Default for PreviewConfig {
@@ -93,8 +184,14 @@ impl Default for PreviewConfig {
             locale: "en-US".to_string(),
             widgets_dir: None,
             mode: PreviewMode::default(),
+            host_profile: HostProfile::default(),
             auth_header: None,
             oauth_config: None,
+            mocks_dir: None,
+            session_file: None,
+            messages_dir: None,
+            baselines_dir: None,
+            anonymize: false,
         }
     }
 }
@@ -104,6 +201,8 @@ pub struct AppState {
     pub config: PreviewConfig,
     pub proxy: McpProxy,
     pub wasm_builder: WasmBuilder,
+    #[cfg(feature = "screenshot")]
+    pub screenshotter: crate::screenshot::Screenshotter,
 }
 
 /// MCP Preview Server
@@ -112,7 +211,10 @@ pub struct PreviewServer;
 impl PreviewServer {
     /// Start the preview server
     pub async fn start(config: PreviewConfig) -> Result<()> {
-        let proxy = McpProxy::new_with_auth(&config.mcp_url, config.auth_header.clone());
+        let mut proxy = McpProxy::new_with_auth(&config.mcp_url, config.auth_header.clone());
+        if let Some(ref mocks_dir) = config.mocks_dir {
+            proxy = proxy.with_mocks(ToolMocks::new(mocks_dir));
+        }
 
         // Locate the workspace root to find the WASM client source
         let cwd = std::env::current_dir().unwrap_or_default();
@@ -125,6 +227,8 @@ impl PreviewServer {
             config: config.clone(),
             proxy,
             wasm_builder,
+            #[cfg(feature = "screenshot")]
+            screenshotter: crate::screenshot::Screenshotter::new(),
         });
 
         // Build CORS layer
@@ -137,6 +241,7 @@ impl PreviewServer {
         let app = Router::new()
             // Main preview page
             .route("/", get(handlers::page::index))
+            .route("/gallery", get(handlers::page::gallery))
             // API endpoints - tools
             .route("/api/config", get(handlers::api::get_config))
             .route("/api/tools", get(handlers::api::list_tools))
@@ -147,6 +252,20 @@ impl PreviewServer {
             // API endpoints - session management
             .route("/api/reconnect", post(handlers::api::reconnect))
             .route("/api/status", get(handlers::api::status))
+            // API endpoints - session snapshot replay
+            .route("/api/session", get(handlers::api::get_session))
+            .route(
+                "/api/session/inspector",
+                get(handlers::api::export_inspector_session),
+            )
+            // API endpoints - i18n message catalogs
+            .route("/api/messages", get(handlers::api::get_messages))
+            // API endpoints - headless screenshots and visual regression
+            .route("/api/screenshot", get(handlers::screenshot::screenshot))
+            .route(
+                "/api/screenshot/compare",
+                post(handlers::screenshot::compare),
+            )
             // API endpoints - MCP proxy (same-origin forward for WASM client)
             .route("/api/mcp", post(handlers::api::forward_mcp))
             // API endpoints - WASM bridge
@@ -175,6 +294,10 @@ impl PreviewServer {
             "\x1b[1;36m║\x1b[0m  Preview:    \x1b[1;33mhttp://localhost:{:<5}\x1b[0m             \x1b[1;36m║\x1b[0m",
             config.port
         );
+        println!(
+            "\x1b[1;36m║\x1b[0m  Gallery:    \x1b[1;33mhttp://localhost:{:<5}/gallery\x1b[0m     \x1b[1;36m║\x1b[0m",
+            config.port
+        );
         println!(
             "\x1b[1;36m║\x1b[0m  MCP Server: \x1b[1;32m{:<30}\x1b[0m   \x1b[1;36m║\x1b[0m",
             truncate_url(&config.mcp_url, 30)
@@ -189,6 +312,20 @@ impl PreviewServer {
                 widgets_dir.display()
             );
         }
+        if let Some(ref mocks_dir) = config.mocks_dir {
+            println!(
+                "\x1b[1;36m║\x1b[0m  Mocks:      \x1b[1;35m{:<30}\x1b[0m   \x1b[1;36m║\x1b[0m",
+                truncate_url(&mocks_dir.display().to_string(), 30)
+            );
+            info!("Mocks directory: {}", mocks_dir.display());
+        }
+        if let Some(ref session_file) = config.session_file {
+            println!(
+                "\x1b[1;36m║\x1b[0m  Session:    \x1b[1;35m{:<30}\x1b[0m   \x1b[1;36m║\x1b[0m",
+                truncate_url(&session_file.display().to_string(), 30)
+            );
+            info!("Replaying session snapshot: {}", session_file.display());
+        }
         println!(
             "\x1b[1;36m║\x1b[0m  Mode:       {:<30}   \x1b[1;36m║\x1b[0m",
             match config.mode {
@@ -0,0 +1,212 @@
+//! JSON-RPC session capture
+//!
+//! Records every JSON-RPC message the preview exchanges with the MCP server
+//! (requests with their responses and timings, plus notifications in both
+//! directions) so a session can be exported in the MCP Inspector's history
+//! format and opened in Inspector tooling.
+//!
+//! The Inspector keeps its history as `{ request, response }` pairs of
+//! serialized JSON-RPC messages and lists server notifications separately;
+//! the export uses the same shape, adding `timestamp` and `durationMs` to each
+//! entry.
+//!
+//! Exports can be anonymized so they can be attached to bug reports: tool-call
+//! arguments and results have names, email addresses and identifying numbers
+//! replaced with stable fake values (see [`pmcp::data::anonymize`]).
+
+use parking_lot::Mutex;
+use pmcp::data::anonymize::Anonymizer;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Oldest entries are dropped beyond this many, to bound memory in long sessions.
+const MAX_ENTRIES: usize = 2000;
+
+/// Format identifier written to exports.
+const EXPORT_FORMAT: &str = "mcp-inspector-history";
+
+/// Bump when the export shape changes incompatibly.
+const EXPORT_VERSION: u32 = 1;
+
+/// Which side sent a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent by the preview to the MCP server.
+    Client,
+    /// Sent by the MCP server (e.g. progress or logging in an SSE response).
+    Server,
+}
+
+/// Start time of a request, taken before it is sent.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestTimer {
+    timestamp: u64,
+    started: Instant,
+}
+
+impl RequestTimer {
+    pub fn start() -> Self {
+        Self {
+            timestamp: now_millis(),
+            started: Instant::now(),
+        }
+    }
+}
+
+enum Entry {
+    Request {
+        request: Value,
+        response: Option<Value>,
+        timestamp: u64,
+        duration_ms: u64,
+    },
+    Notification {
+        direction: Direction,
+        message: Value,
+        timestamp: u64,
+    },
+}
+
+/// Bounded, thread-safe log of the JSON-RPC traffic of a preview session.
+pub struct SessionLog {
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl Default for SessionLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionLog {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a request and its response (`None` if the request failed).
+    pub fn record_request(&self, request: Value, response: Option<Value>, timer: RequestTimer) {
+        let duration_ms = u64::try_from(timer.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.push(Entry::Request {
+            request,
+            response,
+            timestamp: timer.timestamp,
+            duration_ms,
+        });
+    }
+
+    /// Record a notification sent by either side.
+    pub fn record_notification(&self, direction: Direction, message: Value) {
+        self.push(Entry::Notification {
+            direction,
+            message,
+            timestamp: now_millis(),
+        });
+    }
+
+    /// Export the session in the MCP Inspector's history format.
+    ///
+    /// `requestHistory` holds serialized request/response pairs in the order
+    /// the requests were sent; `serverNotifications` and
+    /// `clientNotifications` hold notification messages as objects.
+    ///
+    /// With an `anonymizer`, the arguments and results of `tools/call`
+    /// requests are anonymized before serialization.
+    pub fn inspector_export(&self, server_url: &str, anonymizer: Option<&Anonymizer>) -> Value {
+        let entries = self.entries.lock();
+        let mut history = Vec::new();
+        let mut server_notifications = Vec::new();
+        let mut client_notifications = Vec::new();
+
+        for entry in entries.iter() {
+            match entry {
+                Entry::Request {
+                    request,
+                    response,
+                    timestamp,
+                    duration_ms,
+                } => {
+                    let (request, response) = match anonymizer {
+                        Some(anonymizer) => {
+                            anonymize_tool_call(anonymizer, request, response.as_ref())
+                        },
+                        None => (request.clone(), response.clone()),
+                    };
+                    let mut item = json!({
+                        "request": request.to_string(),
+                        "timestamp": timestamp,
+                        "durationMs": duration_ms,
+                    });
+                    if let Some(response) = response {
+                        item["response"] = Value::String(response.to_string());
+                    }
+                    history.push(item);
+                },
+                Entry::Notification {
+                    direction,
+                    message,
+                    timestamp,
+                } => {
+                    let item = json!({ "notification": message, "timestamp": timestamp });
+                    match direction {
+                        Direction::Server => server_notifications.push(item),
+                        Direction::Client => client_notifications.push(item),
+                    }
+                },
+            }
+        }
+
+        json!({
+            "format": EXPORT_FORMAT,
+            "version": EXPORT_VERSION,
+            "exportedAt": now_millis(),
+            "transport": "streamable-http",
+            "serverUrl": server_url,
+            "requestHistory": history,
+            "serverNotifications": server_notifications,
+            "clientNotifications": client_notifications,
+        })
+    }
+
+    fn push(&self, entry: Entry) {
+        let mut entries = self.entries.lock();
+        if entries.len() == MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Anonymize the arguments and result of a `tools/call` exchange.
+///
+/// Other requests are returned unchanged: their payloads (tool lists,
+/// resource metadata) describe the server, not its users.
+fn anonymize_tool_call(
+    anonymizer: &Anonymizer,
+    request: &Value,
+    response: Option<&Value>,
+) -> (Value, Option<Value>) {
+    if request.get("method").and_then(Value::as_str) != Some("tools/call") {
+        return (request.clone(), response.cloned());
+    }
+
+    let mut request = request.clone();
+    if let Some(arguments) = request.pointer_mut("/params/arguments") {
+        *arguments = anonymizer.value(arguments);
+    }
+    let response = response.cloned().map(|mut response| {
+        if let Some(result) = response.get_mut("result") {
+            *result = anonymizer.value(result);
+        }
+        response
+    });
+    (request, response)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
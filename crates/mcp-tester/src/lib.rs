@@ -53,6 +53,7 @@ pub mod report;
 pub mod scenario;
 pub mod scenario_executor;
 pub mod scenario_generator;
+pub mod scenario_import;
 pub mod tester;
 pub mod validators;
 
@@ -65,6 +66,7 @@ pub use report::{OutputFormat, TestCategory, TestReport, TestResult, TestStatus}
 pub use scenario::TestScenario;
 pub use scenario_executor::ScenarioExecutor;
 pub use scenario_generator::ScenarioGenerator;
+pub use scenario_import::{import_collection, CollectionFormat, ImportResult};
 pub use tester::ServerTester;
 
 use anyhow::{Context, Result};
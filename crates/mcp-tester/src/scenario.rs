@@ -260,6 +260,18 @@ impl TestScenario {
         }
     }
 
+    /// Write the scenario to a file, as JSON for `.json` paths and YAML otherwise
+    #[allow(dead_code)] // Used by cargo-pmcp through the library
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path_ref = path.as_ref();
+        let content = match path_ref.extension().and_then(|s| s.to_str()) {
+            Some("json") => serde_json::to_string_pretty(self)?,
+            _ => serde_yaml::to_string(self)?,
+        };
+        fs::write(path_ref, content)
+            .with_context(|| format!("Failed to write scenario file: {:?}", path_ref))
+    }
+
     /// Validate the scenario structure
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() {
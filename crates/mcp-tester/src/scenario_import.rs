@@ -0,0 +1,563 @@
+//! Import test scenarios from HTTP request collections.
+//!
+//! Converts Postman (v2.x) and Insomnia (export format v4) collections whose
+//! requests POST raw JSON-RPC bodies to an MCP endpoint into a [`TestScenario`].
+//! Each JSON-RPC message becomes one step; batch bodies become one step per
+//! message. Requests that are not JSON-RPC (wrong method, non-JSON body) are
+//! skipped and reported back so they can be migrated by hand.
+//!
+//! Collection variables (`{{name}}`) are rewritten to scenario variables
+//! (`${name}`), and their values are copied into the scenario's `variables`.
+
+use crate::scenario::{Assertion, Operation, TestScenario, TestStep};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// JSON-RPC methods handled by the tester's own connection handshake.
+const HANDSHAKE_METHODS: &[&str] = &["initialize", "notifications/initialized"];
+
+/// Collection template variables: `{{name}}` and Insomnia's `{{ _.name }}`.
+static TEMPLATE_VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*(?:_\.)?([A-Za-z0-9_.\-]+)\s*\}\}").unwrap());
+
+/// Supported collection formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionFormat {
+    /// Postman collection v2.0 / v2.1.
+    Postman,
+    /// Insomnia export (format version 4).
+    Insomnia,
+}
+
+impl FromStr for CollectionFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "postman" => Ok(Self::Postman),
+            "insomnia" => Ok(Self::Insomnia),
+            other => bail!(
+                "Unsupported collection format '{}' (expected postman or insomnia)",
+                other
+            ),
+        }
+    }
+}
+
+/// A collection request that could not be converted.
+#[derive(Debug, Clone)]
+pub struct SkippedRequest {
+    /// Request name in the collection.
+    pub name: String,
+    /// Why it was skipped.
+    pub reason: String,
+}
+
+/// Result of importing a collection.
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    /// The converted scenario.
+    pub scenario: TestScenario,
+    /// Requests that were not converted.
+    pub skipped: Vec<SkippedRequest>,
+}
+
+/// A request extracted from a collection, independent of its format.
+struct CollectionRequest {
+    name: String,
+    method: String,
+    url: String,
+    body: Option<String>,
+}
+
+/// Import a collection from its JSON text.
+pub fn import_collection(content: &str, format: CollectionFormat) -> Result<ImportResult> {
+    let root: Value = serde_json::from_str(content).context("Collection is not valid JSON")?;
+
+    let (name, description, variables, requests) = match format {
+        CollectionFormat::Postman => parse_postman(&root)?,
+        CollectionFormat::Insomnia => parse_insomnia(&root)?,
+    };
+
+    let mut steps = Vec::new();
+    let mut skipped = Vec::new();
+    let mut endpoints = Vec::new();
+
+    for request in requests {
+        match request_to_steps(&request) {
+            Ok(request_steps) if request_steps.is_empty() => skipped.push(SkippedRequest {
+                name: request.name,
+                reason: "only contains initialize handshake messages".to_string(),
+            }),
+            Ok(request_steps) => {
+                if !endpoints.contains(&request.url) {
+                    endpoints.push(request.url.clone());
+                }
+                steps.extend(request_steps);
+            },
+            Err(reason) => skipped.push(SkippedRequest {
+                name: request.name,
+                reason,
+            }),
+        }
+    }
+
+    if steps.is_empty() {
+        bail!("No JSON-RPC requests found in collection '{}'", name);
+    }
+
+    let mut description = description.unwrap_or_default();
+    if !endpoints.is_empty() {
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&format!(
+            "Imported from {:?} collection (endpoint: {})",
+            format,
+            endpoints.join(", ")
+        ));
+    }
+
+    Ok(ImportResult {
+        scenario: TestScenario {
+            name,
+            description: Some(description),
+            timeout: 60,
+            stop_on_failure: false,
+            variables,
+            setup: Vec::new(),
+            steps,
+            cleanup: Vec::new(),
+        },
+        skipped,
+    })
+}
+
+type ParsedCollection = (
+    String,
+    Option<String>,
+    HashMap<String, Value>,
+    Vec<CollectionRequest>,
+);
+
+fn parse_postman(root: &Value) -> Result<ParsedCollection> {
+    let info = root
+        .get("info")
+        .context("Not a Postman collection: missing 'info'")?;
+    let name = info
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("Imported Postman collection")
+        .to_string();
+    let description = info
+        .get("description")
+        .and_then(|d| {
+            d.as_str()
+                .or_else(|| d.get("content").and_then(Value::as_str))
+        })
+        .map(String::from);
+
+    let variables = root
+        .get("variable")
+        .and_then(Value::as_array)
+        .map(|vars| {
+            vars.iter()
+                .filter_map(|v| {
+                    let key = v.get("key")?.as_str()?;
+                    let value = v.get("value").cloned().unwrap_or(Value::Null);
+                    Some((key.to_string(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut requests = Vec::new();
+    if let Some(items) = root.get("item").and_then(Value::as_array) {
+        collect_postman_items(items, "", &mut requests);
+    }
+
+    Ok((name, description, variables, requests))
+}
+
+/// Walk Postman items depth-first; folders prefix their children's names.
+fn collect_postman_items(items: &[Value], prefix: &str, out: &mut Vec<CollectionRequest>) {
+    for item in items {
+        let item_name = item
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("request");
+        let full_name = if prefix.is_empty() {
+            item_name.to_string()
+        } else {
+            format!("{} / {}", prefix, item_name)
+        };
+
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            collect_postman_items(children, &full_name, out);
+            continue;
+        }
+
+        let Some(request) = item.get("request") else {
+            continue;
+        };
+        // A request may be a bare URL string
+        let (method, url, body) = match request {
+            Value::String(url) => ("GET".to_string(), url.clone(), None),
+            _ => {
+                let method = request
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("GET")
+                    .to_string();
+                let url = match request.get("url") {
+                    Some(Value::String(url)) => url.clone(),
+                    Some(url) => url
+                        .get("raw")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    None => String::new(),
+                };
+                let body = request
+                    .get("body")
+                    .filter(|b| b.get("mode").and_then(Value::as_str) == Some("raw"))
+                    .and_then(|b| b.get("raw"))
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                (method, url, body)
+            },
+        };
+
+        out.push(CollectionRequest {
+            name: full_name,
+            method,
+            url,
+            body,
+        });
+    }
+}
+
+fn parse_insomnia(root: &Value) -> Result<ParsedCollection> {
+    let resources = root
+        .get("resources")
+        .and_then(Value::as_array)
+        .context("Not an Insomnia export: missing 'resources'")?;
+
+    let of_type = |ty: &'static str| {
+        resources
+            .iter()
+            .filter(move |r| r.get("_type").and_then(Value::as_str) == Some(ty))
+    };
+
+    let workspace = of_type("workspace").next();
+    let name = workspace
+        .and_then(|w| w.get("name"))
+        .and_then(Value::as_str)
+        .unwrap_or("Imported Insomnia collection")
+        .to_string();
+    let description = workspace
+        .and_then(|w| w.get("description"))
+        .and_then(Value::as_str)
+        .filter(|d| !d.is_empty())
+        .map(String::from);
+
+    let mut variables = HashMap::new();
+    for environment in of_type("environment") {
+        if let Some(data) = environment.get("data").and_then(Value::as_object) {
+            for (key, value) in data {
+                variables.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    let groups: HashMap<&str, (&str, Option<&str>)> = of_type("request_group")
+        .filter_map(|g| {
+            let id = g.get("_id")?.as_str()?;
+            let name = g.get("name").and_then(Value::as_str).unwrap_or("group");
+            let parent = g.get("parentId").and_then(Value::as_str);
+            Some((id, (name, parent)))
+        })
+        .collect();
+
+    let requests = of_type("request")
+        .map(|r| {
+            let mut name = r
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("request")
+                .to_string();
+            let mut parent = r.get("parentId").and_then(Value::as_str);
+            let mut visited = HashSet::new();
+            while let Some((id, (group_name, grandparent))) =
+                parent.and_then(|id| groups.get_key_value(id))
+            {
+                if !visited.insert(*id) {
+                    bail!("Insomnia request group '{}' is its own ancestor", id);
+                }
+                name = format!("{} / {}", group_name, name);
+                parent = *grandparent;
+            }
+
+            Ok(CollectionRequest {
+                name,
+                method: r
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("GET")
+                    .to_string(),
+                url: r
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                body: r
+                    .get("body")
+                    .and_then(|b| b.get("text"))
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((name, description, variables, requests))
+}
+
+/// Rewrite collection template variables (`{{name}}`, Insomnia's `{{ _.name }}`)
+/// to scenario variables (`${name}`).
+fn rewrite_variables(text: &str) -> String {
+    TEMPLATE_VARIABLE.replace_all(text, "$${$1}").into_owned()
+}
+
+/// Convert one collection request into scenario steps.
+///
+/// Returns `Err` with a human-readable reason when the request is not a
+/// JSON-RPC call.
+fn request_to_steps(request: &CollectionRequest) -> std::result::Result<Vec<TestStep>, String> {
+    if !request.method.eq_ignore_ascii_case("POST") {
+        return Err(format!(
+            "{} request (MCP calls are POST)",
+            request.method.to_uppercase()
+        ));
+    }
+    let body = request
+        .body
+        .as_deref()
+        .filter(|b| !b.trim().is_empty())
+        .ok_or_else(|| "no raw body".to_string())?;
+
+    let body: Value = serde_json::from_str(&rewrite_variables(body))
+        .map_err(|e| format!("body is not JSON ({})", e))?;
+
+    let messages = match body {
+        Value::Array(batch) => batch,
+        single => vec![single],
+    };
+    let batched = messages.len() > 1;
+
+    let mut steps = Vec::new();
+    for (index, message) in messages.iter().enumerate() {
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .filter(|_| message.get("jsonrpc").is_some())
+            .ok_or_else(|| "body is not a JSON-RPC request".to_string())?;
+        if HANDSHAKE_METHODS.contains(&method) {
+            continue;
+        }
+
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let name = if batched {
+            format!("{} [{}]", request.name, index + 1)
+        } else {
+            request.name.clone()
+        };
+
+        steps.push(TestStep {
+            name,
+            operation: jsonrpc_to_operation(method, params),
+            timeout: None,
+            continue_on_failure: false,
+            store_result: None,
+            assertions: vec![Assertion::Success],
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Map a JSON-RPC method to the closest typed scenario operation.
+fn jsonrpc_to_operation(method: &str, params: Value) -> Operation {
+    let str_param = |key: &str| params.get(key).and_then(Value::as_str).map(String::from);
+    let arguments = || params.get("arguments").cloned().unwrap_or(Value::Null);
+
+    match method {
+        "tools/list" => Operation::ListTools,
+        "resources/list" => Operation::ListResources,
+        "prompts/list" => Operation::ListPrompts,
+        "tools/call" => match str_param("name") {
+            Some(tool) => Operation::ToolCall {
+                tool,
+                arguments: arguments(),
+            },
+            None => custom(method, params),
+        },
+        "resources/read" => match str_param("uri") {
+            Some(uri) => Operation::ReadResource { uri },
+            None => custom(method, params),
+        },
+        "prompts/get" => match str_param("name") {
+            Some(name) => Operation::GetPrompt {
+                name,
+                arguments: arguments(),
+            },
+            None => custom(method, params),
+        },
+        _ => custom(method, params),
+    }
+}
+
+fn custom(method: &str, params: Value) -> Operation {
+    Operation::Custom {
+        method: method.to_string(),
+        params,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_import_postman_collection() {
+        let collection = json!({
+            "info": { "name": "Weather API", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+            "variable": [{ "key": "city", "value": "London" }],
+            "item": [
+                {
+                    "name": "Handshake",
+                    "request": {
+                        "method": "POST",
+                        "url": { "raw": "{{baseUrl}}/mcp" },
+                        "body": { "mode": "raw", "raw": "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\",\"params\":{}}" }
+                    }
+                },
+                {
+                    "name": "Tools",
+                    "item": [{
+                        "name": "Get weather",
+                        "request": {
+                            "method": "POST",
+                            "url": "http://localhost:3000/mcp",
+                            "body": { "mode": "raw", "raw": "{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"tools/call\",\"params\":{\"name\":\"get_weather\",\"arguments\":{\"city\":\"{{city}}\"}}}" }
+                        }
+                    }]
+                },
+                {
+                    "name": "Health",
+                    "request": { "method": "GET", "url": "http://localhost:3000/health" }
+                }
+            ]
+        });
+
+        let result = import_collection(&collection.to_string(), CollectionFormat::Postman).unwrap();
+        let scenario = result.scenario;
+
+        assert_eq!(scenario.name, "Weather API");
+        assert_eq!(scenario.variables.get("city"), Some(&json!("London")));
+        assert_eq!(scenario.steps.len(), 1);
+        assert_eq!(scenario.steps[0].name, "Tools / Get weather");
+        match &scenario.steps[0].operation {
+            Operation::ToolCall { tool, arguments } => {
+                assert_eq!(tool, "get_weather");
+                assert_eq!(arguments, &json!({ "city": "${city}" }));
+            },
+            other => panic!("unexpected operation: {:?}", other),
+        }
+
+        assert_eq!(result.skipped.len(), 2);
+        assert_eq!(result.skipped[0].name, "Handshake");
+        assert_eq!(result.skipped[1].name, "Health");
+        scenario.validate().unwrap();
+    }
+
+    #[test]
+    fn test_import_insomnia_batch_request() {
+        let export = json!({
+            "_type": "export",
+            "__export_format": 4,
+            "resources": [
+                { "_id": "wrk_1", "_type": "workspace", "name": "Docs Server" },
+                { "_id": "env_1", "_type": "environment", "parentId": "wrk_1", "data": { "doc": "readme" } },
+                { "_id": "fld_1", "_type": "request_group", "parentId": "wrk_1", "name": "Smoke" },
+                {
+                    "_id": "req_1",
+                    "_type": "request",
+                    "parentId": "fld_1",
+                    "name": "List and read",
+                    "method": "POST",
+                    "url": "http://localhost:3000/mcp",
+                    "body": {
+                        "mimeType": "application/json",
+                        "text": "[{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"resources/list\"},{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"resources/read\",\"params\":{\"uri\":\"docs://{{ _.doc }}\"}},{\"jsonrpc\":\"2.0\",\"id\":3,\"method\":\"logging/setLevel\",\"params\":{\"level\":\"debug\"}}]"
+                    }
+                }
+            ]
+        });
+
+        let result = import_collection(&export.to_string(), CollectionFormat::Insomnia).unwrap();
+        let steps = &result.scenario.steps;
+
+        assert_eq!(result.scenario.name, "Docs Server");
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].name, "Smoke / List and read [1]");
+        assert!(matches!(steps[0].operation, Operation::ListResources));
+        assert!(
+            matches!(&steps[1].operation, Operation::ReadResource { uri } if uri == "docs://${doc}")
+        );
+        assert!(
+            matches!(&steps[2].operation, Operation::Custom { method, .. } if method == "logging/setLevel")
+        );
+        assert!(result.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_import_insomnia_group_cycle_fails() {
+        let export = json!({
+            "_type": "export",
+            "__export_format": 4,
+            "resources": [
+                { "_id": "fld_1", "_type": "request_group", "parentId": "fld_2", "name": "A" },
+                { "_id": "fld_2", "_type": "request_group", "parentId": "fld_1", "name": "B" },
+                {
+                    "_id": "req_1",
+                    "_type": "request",
+                    "parentId": "fld_1",
+                    "name": "Ping",
+                    "method": "POST",
+                    "url": "http://localhost:3000/mcp",
+                    "body": { "text": "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}" }
+                }
+            ]
+        });
+
+        let error = import_collection(&export.to_string(), CollectionFormat::Insomnia).unwrap_err();
+        assert!(
+            error.to_string().contains("its own ancestor"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn test_import_without_jsonrpc_requests_fails() {
+        let collection = json!({
+            "info": { "name": "REST only" },
+            "item": [{ "name": "Health", "request": "http://localhost:3000/health" }]
+        });
+        assert!(import_collection(&collection.to_string(), CollectionFormat::Postman).is_err());
+    }
+}
@@ -56,15 +56,9 @@ async fn start_http_in_background(server: pmcp::Server) -> Result<SocketAddr, Er
     let server = std::sync::Arc::new(tokio::sync::Mutex::new(server));
     let addr: SocketAddr = "127.0.0.1:8080".parse().expect("valid addr");
 
-    let config = pmcp::server::streamable_http_server::StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = pmcp::server::streamable_http_server::StreamableHttpServerConfig::from_profile(
+        pmcp::server::resource_profile::ResourceProfile::serverless(),
+    );
 
     let http_server = pmcp::server::streamable_http_server::StreamableHttpServer::with_config(
         addr, server, config,
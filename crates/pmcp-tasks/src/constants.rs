@@ -33,6 +33,9 @@ pub const METHOD_TASKS_LIST: &str = "tasks/list";
 /// JSON-RPC method name for cancelling a task.
 pub const METHOD_TASKS_CANCEL: &str = "tasks/cancel";
 
+/// JSON-RPC method name for answering a task's pending elicitation (PMCP extension).
+pub const METHOD_TASKS_PROVIDE_INPUT: &str = "tasks/provide_input";
+
 /// JSON-RPC method name for task status change notifications.
 pub const METHOD_TASKS_STATUS_NOTIFICATION: &str = "notifications/tasks/status";
 
@@ -58,6 +61,7 @@ mod tests {
         assert_eq!(METHOD_TASKS_RESULT, "tasks/result");
         assert_eq!(METHOD_TASKS_LIST, "tasks/list");
         assert_eq!(METHOD_TASKS_CANCEL, "tasks/cancel");
+        assert_eq!(METHOD_TASKS_PROVIDE_INPUT, "tasks/provide_input");
         assert_eq!(
             METHOD_TASKS_STATUS_NOTIFICATION,
             "notifications/tasks/status"
@@ -49,6 +49,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde_json::Value;
@@ -56,6 +57,10 @@ use serde_json::Value;
 use crate::domain::TaskRecord;
 use crate::error::TaskError;
 use crate::store::TaskStore;
+use crate::types::input::{
+    ElicitRequestParams, ElicitResult, TASK_INPUT_KEY, TASK_INPUT_REQUEST_KEY,
+};
+use crate::types::progress::{TaskProgress, TaskStepStatus, TASK_PROGRESS_KEY};
 use crate::types::task::TaskStatus;
 
 /// Ergonomic wrapper that scopes all task operations to a single task.
@@ -392,6 +397,89 @@ impl TaskContext {
             .await
     }
 
+    // --- Progress reporting ---
+
+    /// Returns the task's structured progress, if any has been reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    pub async fn progress(&self) -> Result<Option<TaskProgress>, TaskError> {
+        Ok(self.get().await?.progress())
+    }
+
+    /// Replaces the task's structured progress.
+    ///
+    /// The progress is stored under [`TASK_PROGRESS_KEY`] and returned in
+    /// the `_meta` of `tasks/get` responses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    /// Returns [`TaskError::Expired`] if the task has expired.
+    pub async fn set_progress(&self, progress: &TaskProgress) -> Result<TaskRecord, TaskError> {
+        let value =
+            serde_json::to_value(progress).map_err(|e| TaskError::StoreError(e.to_string()))?;
+        self.set_variable(TASK_PROGRESS_KEY, value).await
+    }
+
+    /// Reports `current` out of `total` units of work done, keeping the
+    /// sub-steps reported so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    /// Returns [`TaskError::Expired`] if the task has expired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use pmcp_tasks::context::TaskContext;
+    /// # use pmcp_tasks::store::memory::InMemoryTaskStore;
+    /// # use pmcp_tasks::store::TaskStore;
+    /// # use pmcp_tasks::security::TaskSecurityConfig;
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # let store = Arc::new(InMemoryTaskStore::new().with_security(TaskSecurityConfig::default().with_allow_anonymous(true)));
+    /// # let record = store.create("local", "tools/call", None).await.unwrap();
+    /// # let ctx = TaskContext::new(store, record.task.task_id.clone(), "local".to_string());
+    /// ctx.report_progress(40, Some(200), Some("Indexing documents".to_string()))
+    ///     .await
+    ///     .unwrap();
+    /// let progress = ctx.progress().await.unwrap().unwrap();
+    /// assert_eq!(progress.percent(), Some(20.0));
+    /// # });
+    /// ```
+    pub async fn report_progress(
+        &self,
+        current: u64,
+        total: Option<u64>,
+        message: Option<String>,
+    ) -> Result<TaskRecord, TaskError> {
+        let mut progress = self.progress().await?.unwrap_or_default();
+        progress.current = current;
+        progress.total = total;
+        progress.message = message;
+        self.set_progress(&progress).await
+    }
+
+    /// Sets the status of a named sub-step, adding it if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    /// Returns [`TaskError::Expired`] if the task has expired.
+    pub async fn update_step(
+        &self,
+        name: impl Into<String>,
+        status: TaskStepStatus,
+        message: Option<String>,
+    ) -> Result<TaskRecord, TaskError> {
+        let mut progress = self.progress().await?.unwrap_or_default();
+        progress.set_step(name, status, message);
+        self.set_progress(&progress).await
+    }
+
     // --- Status transition convenience methods ---
 
     /// Completes the task with a result value.
@@ -523,6 +611,129 @@ impl TaskContext {
             .await
     }
 
+    // --- Input elicitation ---
+
+    /// Attaches an elicitation request to the task and moves it to
+    /// `InputRequired`.
+    ///
+    /// The request is stored under [`TASK_INPUT_REQUEST_KEY`], so clients
+    /// see it in the `_meta` of `tasks/get` and answer it with
+    /// `tasks/provide_input`. Any earlier answer is discarded. The status
+    /// message is the request's message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::InvalidTransition`] if the task is in a terminal state.
+    /// Returns [`TaskError::Expired`] if the task has expired.
+    pub async fn request_input(
+        &self,
+        request: ElicitRequestParams,
+    ) -> Result<TaskRecord, TaskError> {
+        let message = match &request {
+            ElicitRequestParams::Form { message, .. }
+            | ElicitRequestParams::Url { message, .. } => message.clone(),
+        };
+        let request =
+            serde_json::to_value(request).map_err(|e| TaskError::StoreError(e.to_string()))?;
+        self.set_variables(HashMap::from([
+            (TASK_INPUT_REQUEST_KEY.to_string(), request),
+            (TASK_INPUT_KEY.to_string(), Value::Null),
+        ]))
+        .await?;
+        self.require_input(message).await
+    }
+
+    /// Returns the pending elicitation request, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    pub async fn input_request(&self) -> Result<Option<ElicitRequestParams>, TaskError> {
+        self.get_typed(TASK_INPUT_REQUEST_KEY).await
+    }
+
+    /// Takes the client's answer once the task is back in `Working`.
+    ///
+    /// Returns `Ok(None)` while no answer has been provided. A returned
+    /// answer is removed from the task so it is only seen once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::NotFound`] if the task does not exist.
+    pub async fn take_input(&self) -> Result<Option<ElicitResult>, TaskError> {
+        let record = self.get().await?;
+        self.take_input_from(&record).await
+    }
+
+    async fn take_input_from(
+        &self,
+        record: &TaskRecord,
+    ) -> Result<Option<ElicitResult>, TaskError> {
+        if record.task.status != TaskStatus::Working {
+            return Ok(None);
+        }
+        let Some(value) = record.variables.get(TASK_INPUT_KEY).cloned() else {
+            return Ok(None);
+        };
+        let answer: ElicitResult = serde_json::from_value(value)
+            .map_err(|e| TaskError::StoreError(format!("invalid task input: {e}")))?;
+        self.delete_variable(TASK_INPUT_KEY).await?;
+        Ok(Some(answer))
+    }
+
+    /// Waits for the client's answer to the pending elicitation, checking
+    /// the store every `poll_interval`.
+    ///
+    /// Wrap the call in `tokio::time::timeout` to bound the wait.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::InvalidTransition`] if the task reaches a
+    /// terminal state (e.g. is cancelled) while waiting.
+    /// Returns [`TaskError::Expired`] if the task expires while waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// ctx.request_input(ElicitRequestParams::Form {
+    ///     message: "Which environment should be migrated?".to_string(),
+    ///     requested_schema: json!({
+    ///         "type": "object",
+    ///         "properties": {"env": {"type": "string", "enum": ["staging", "prod"]}},
+    ///         "required": ["env"]
+    ///     }),
+    /// })
+    /// .await?;
+    ///
+    /// let answer = ctx.wait_for_input(Duration::from_secs(1)).await?;
+    /// if !answer.is_accepted() {
+    ///     return ctx.fail("migration declined").await;
+    /// }
+    /// ```
+    pub async fn wait_for_input(&self, poll_interval: Duration) -> Result<ElicitResult, TaskError> {
+        loop {
+            let record = self.get().await?;
+            if let Some(answer) = self.take_input_from(&record).await? {
+                return Ok(answer);
+            }
+            if record.task.status.is_terminal() {
+                return Err(TaskError::InvalidTransition {
+                    task_id: self.task_id.clone(),
+                    from: record.task.status,
+                    to: TaskStatus::Working,
+                    suggested_action: Some("task ended while waiting for input".to_string()),
+                });
+            }
+            if record.is_expired() {
+                return Err(TaskError::Expired {
+                    task_id: self.task_id.clone(),
+                    expired_at: record.expires_at.map(|at| at.to_rfc3339()),
+                });
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     /// Cancels the task.
     ///
     /// Delegates to [`TaskStore::cancel`] which transitions the task to
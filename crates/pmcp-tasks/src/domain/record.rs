@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::types::notification::TaskStatusNotification;
+use crate::types::progress::{TaskProgress, TASK_PROGRESS_KEY};
 use crate::types::task::{Task, TaskStatus};
 
 /// Internal storage representation of a task.
@@ -63,6 +65,12 @@ pub struct TaskRecord {
     /// chrono's serde support.
     pub expires_at: Option<DateTime<Utc>>,
 
+    /// When the task entered its current status. Unlike
+    /// `task.last_updated_at`, variable and result writes do not move it.
+    /// `None` for records stored before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_changed_at: Option<DateTime<Utc>>,
+
     /// Monotonic version for CAS operations. Not part of the serialized
     /// record -- managed by the storage backend.
     #[serde(skip)]
@@ -131,10 +139,35 @@ impl TaskRecord {
             result: None,
             request_method,
             expires_at,
+            status_changed_at: Some(now),
             version: 0,
         }
     }
 
+    /// Returns how long the task has been in its current status at `now`.
+    ///
+    /// Falls back to the creation time for records without
+    /// `status_changed_at`. Returns `None` if neither timestamp is usable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp_tasks::domain::TaskRecord;
+    ///
+    /// let record = TaskRecord::new("owner".to_string(), "tools/call".to_string(), None);
+    /// let later = chrono::Utc::now() + chrono::Duration::seconds(5);
+    /// assert!(record.time_in_status(later).unwrap() >= chrono::Duration::seconds(5));
+    /// ```
+    pub fn time_in_status(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let since = match self.status_changed_at {
+            Some(since) => since,
+            None => DateTime::parse_from_rfc3339(&self.task.created_at)
+                .ok()?
+                .with_timezone(&Utc),
+        };
+        Some(now.signed_duration_since(since))
+    }
+
     /// Returns `true` if the task has expired based on its TTL.
     ///
     /// A task with no `expires_at` (unlimited TTL) never expires.
@@ -208,6 +241,63 @@ impl TaskRecord {
 
         task
     }
+
+    /// Returns the task's structured progress, if any has been reported.
+    ///
+    /// Progress is read from the [`TASK_PROGRESS_KEY`] variable; a value
+    /// that does not deserialize as [`TaskProgress`] is treated as absent.
+    pub fn progress(&self) -> Option<TaskProgress> {
+        self.variables
+            .get(TASK_PROGRESS_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Builds the `notifications/tasks/status` params for the task's
+    /// current state.
+    ///
+    /// Reported progress is included in `_meta` under [`TASK_PROGRESS_KEY`]
+    /// so clients can update progress bars from notifications alone. Other
+    /// variables are left out to keep notifications small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp_tasks::domain::TaskRecord;
+    /// use pmcp_tasks::types::progress::{TaskProgress, TASK_PROGRESS_KEY};
+    ///
+    /// let mut record = TaskRecord::new(
+    ///     "owner".to_string(),
+    ///     "tools/call".to_string(),
+    ///     None,
+    /// );
+    /// record.variables.insert(
+    ///     TASK_PROGRESS_KEY.to_string(),
+    ///     serde_json::to_value(TaskProgress::new(2, Some(8))).unwrap(),
+    /// );
+    ///
+    /// let notification = record.to_status_notification();
+    /// let meta = notification._meta.expect("progress in _meta");
+    /// assert_eq!(meta[TASK_PROGRESS_KEY]["total"], 8);
+    /// ```
+    pub fn to_status_notification(&self) -> TaskStatusNotification {
+        let task = &self.task;
+        let _meta = self.variables.get(TASK_PROGRESS_KEY).map(|progress| {
+            let mut meta = serde_json::Map::new();
+            meta.insert(TASK_PROGRESS_KEY.to_string(), progress.clone());
+            meta
+        });
+
+        TaskStatusNotification {
+            task_id: task.task_id.clone(),
+            status: task.status,
+            status_message: task.status_message.clone(),
+            created_at: task.created_at.clone(),
+            last_updated_at: task.last_updated_at.clone(),
+            ttl: task.ttl,
+            poll_interval: task.poll_interval,
+            _meta,
+        }
+    }
 }
 
 /// Validates that a JSON value does not exceed the maximum nesting depth.
@@ -66,6 +66,13 @@ pub enum TaskError {
         task_id: String,
     },
 
+    /// Caller lacks the capability required for an operation (e.g., admin
+    /// listing without being configured as an admin owner).
+    PermissionDenied {
+        /// The operation that was rejected.
+        operation: String,
+    },
+
     /// Resource limits exceeded (e.g., too many active tasks).
     ResourceExhausted {
         /// Optional suggestion for the caller.
@@ -133,6 +140,9 @@ impl fmt::Display for TaskError {
             Self::OwnerMismatch { task_id } => {
                 write!(f, "owner mismatch for task {task_id}")
             }
+            Self::PermissionDenied { operation } => {
+                write!(f, "permission denied for {operation}")
+            }
             Self::ResourceExhausted { .. } => write!(f, "resource exhausted"),
             Self::VariableSizeExceeded {
                 limit_bytes,
@@ -161,7 +171,7 @@ impl TaskError {
     /// Maps this error to a JSON-RPC error code per the MCP specification.
     ///
     /// - `-32602` (Invalid params): `InvalidTransition`, `NotFound`, `Expired`,
-    ///   `NotReady`, `OwnerMismatch`, `VariableSizeExceeded`
+    ///   `NotReady`, `OwnerMismatch`, `PermissionDenied`, `VariableSizeExceeded`
     /// - `-32603` (Internal error): `ResourceExhausted`, `ConcurrentModification`,
     ///   `StorageFull`, `StoreError`
     ///
@@ -188,6 +198,7 @@ impl TaskError {
             | Self::Expired { .. }
             | Self::NotReady { .. }
             | Self::OwnerMismatch { .. }
+            | Self::PermissionDenied { .. }
             | Self::VariableSizeExceeded { .. } => -32602,
             Self::ResourceExhausted { .. }
             | Self::ConcurrentModification { .. }
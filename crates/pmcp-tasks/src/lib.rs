@@ -22,8 +22,10 @@
 //! - [`domain`] - Internal domain types (`TaskRecord`, `TaskWithVariables`)
 //! - [`store`] - `TaskStore` async trait, supporting types, and backend implementations
 //! - [`security`] - Security configuration and owner resolution
+//! - [`worker`] - Background worker pool for task work spawned by tool handlers
 //! - [`error`] - Rich error types with JSON-RPC error code mapping
 //! - [`constants`] - Meta key and method name constants
+//! - `widget_state` - Redis-backed widget state store (`redis` feature)
 
 pub mod constants;
 pub mod error;
@@ -44,22 +46,39 @@ pub mod context;
 /// TaskRouter implementation bridging pmcp's TaskRouter trait to TaskStore.
 pub mod router;
 
+/// Background worker pool for spawning task work from tool handlers.
+pub mod worker;
+
+/// Redis-backed implementation of pmcp's widget state store.
+#[cfg(feature = "redis")]
+pub mod widget_state;
+
 // Re-exports for ergonomic access
 pub use constants::*;
 pub use context::TaskContext;
 pub use domain::{TaskRecord, TaskWithVariables};
 pub use error::TaskError;
 pub use router::TaskRouterImpl;
-pub use security::{resolve_owner_id, TaskSecurityConfig, DEFAULT_LOCAL_OWNER};
+pub use security::{
+    api_key_owner_id, resolve_owner_id, OwnerResolver, OwnerStrategy, TaskRouterConfig,
+    TaskSecurityConfig, DEFAULT_LOCAL_OWNER,
+};
 #[cfg(feature = "dynamodb")]
 pub use store::dynamodb::DynamoDbBackend;
 pub use store::generic::GenericTaskStore;
 pub use store::memory::InMemoryBackend;
 pub use store::memory::InMemoryTaskStore;
+pub use store::metrics::TaskMetrics;
 #[cfg(feature = "redis")]
 pub use store::redis::RedisBackend;
+pub use store::retention::{CleanupStats, FileSystemArchiver, RetentionPolicy, TaskArchiver};
+#[cfg(feature = "sql")]
+pub use store::sql::SqlBackend;
 pub use store::{
-    ListTasksOptions, StorageBackend, StorageError, StoreConfig, TaskPage, TaskStore,
-    VersionedRecord,
+    AdminListOptions, ListTasksOptions, StorageBackend, StorageError, StoreConfig, TaskPage,
+    TaskStore, TenantTaskCounts, VersionedRecord,
 };
 pub use types::*;
+#[cfg(feature = "redis")]
+pub use widget_state::RedisWidgetStateStore;
+pub use worker::{SpawnTaskExt, TaskWorker};
@@ -3,7 +3,8 @@
 //! [`TaskRouterImpl`] is the concrete implementation of the [`pmcp::server::tasks::TaskRouter`]
 //! trait. It owns a [`TaskStore`] and [`TaskSecurityConfig`], handling all task lifecycle
 //! operations: creation (via task-augmented `tools/call`), status retrieval, result
-//! retrieval, listing, and cancellation.
+//! retrieval, listing, cancellation, and answering a task's pending elicitation
+//! (`tasks/provide_input`).
 //!
 //! # Design
 //!
@@ -25,17 +26,22 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use pmcp::error::{Error as PmcpError, Result as PmcpResult};
+use pmcp::server::auth::AuthContext;
 use pmcp::server::tasks::TaskRouter;
 
 use crate::error::TaskError;
-use crate::security::{resolve_owner_id, TaskSecurityConfig};
-use crate::store::{ListTasksOptions, TaskStore};
+use crate::security::{TaskRouterConfig, TaskSecurityConfig};
+use crate::store::{AdminListOptions, ListTasksOptions, TaskStore};
 use crate::types::capabilities::ServerTaskCapabilities;
 use crate::types::execution::{TaskSupport, ToolExecution};
+use crate::types::input::{
+    missing_required_fields, ElicitRequestParams, TASK_INPUT_KEY, TASK_INPUT_REQUEST_KEY,
+};
 use crate::types::params::{
-    TaskCancelParams, TaskGetParams, TaskListParams, TaskParams, TaskResultParams,
+    TaskCancelParams, TaskGetParams, TaskListParams, TaskParams, TaskProvideInputParams,
+    TaskResultParams,
 };
-use crate::types::task::{related_task_meta, CreateTaskResult};
+use crate::types::task::{related_task_meta, CreateTaskResult, TaskStatus};
 
 /// Implementation of pmcp's `TaskRouter` trait using a `TaskStore` backend.
 ///
@@ -56,6 +62,7 @@ use crate::types::task::{related_task_meta, CreateTaskResult};
 pub struct TaskRouterImpl {
     store: Arc<dyn TaskStore>,
     security_config: TaskSecurityConfig,
+    config: TaskRouterConfig,
 }
 
 impl TaskRouterImpl {
@@ -68,6 +75,7 @@ impl TaskRouterImpl {
         Self {
             store,
             security_config: TaskSecurityConfig::default(),
+            config: TaskRouterConfig::default(),
         }
     }
 
@@ -77,13 +85,32 @@ impl TaskRouterImpl {
     ///
     /// * `store` - The task store backend (shared via `Arc`).
     /// * `config` - Security configuration for owner-specific limits.
+    ///
+    /// Limits and admin owners are enforced by the store, so they must also
+    /// be set on the store (e.g. `InMemoryTaskStore::with_security`). Admin
+    /// owners given only here would never be honored, and are logged as a
+    /// warning.
     pub fn with_security(store: Arc<dyn TaskStore>, config: TaskSecurityConfig) -> Self {
+        if !config.admin_owners.is_empty() {
+            tracing::warn!(
+                "admin owners on the task router's security config are ignored; \
+                 configure them on the task store"
+            );
+        }
         Self {
             store,
             security_config: config,
+            config: TaskRouterConfig::default(),
         }
     }
 
+    /// Sets the router configuration (owner strategy and cross-owner
+    /// listing).
+    pub fn with_config(mut self, config: TaskRouterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Returns a reference to the underlying task store.
     ///
     /// Useful for direct store access in tests or advanced use cases.
@@ -95,6 +122,11 @@ impl TaskRouterImpl {
     pub fn security_config(&self) -> &TaskSecurityConfig {
         &self.security_config
     }
+
+    /// Returns a reference to the router configuration.
+    pub fn config(&self) -> &TaskRouterConfig {
+        &self.config
+    }
 }
 
 /// Converts a [`TaskError`] into a [`pmcp::error::Error`] using the error code
@@ -209,19 +241,37 @@ impl TaskRouter for TaskRouterImpl {
 
     /// Handle `tasks/list` request.
     ///
-    /// Returns a paginated list of tasks for the given owner.
+    /// Returns a paginated list of tasks for the given owner. With
+    /// `"allOwners": true`, admins list tasks across all owners; this
+    /// requires [`TaskRouterConfig::admin_listing`] and an owner the store
+    /// recognizes as admin.
     async fn handle_tasks_list(&self, params: Value, owner_id: &str) -> PmcpResult<Value> {
         let list_params: TaskListParams = serde_json::from_value(params)
             .map_err(|e| PmcpError::invalid_params(format!("invalid tasks/list params: {e}")))?;
 
-        let options = ListTasksOptions {
-            owner_id: owner_id.to_string(),
-            cursor: list_params.cursor,
-            limit: None,
+        let page = if list_params.all_owners {
+            if !self.config.admin_listing {
+                return Err(PmcpError::invalid_params(
+                    "listing tasks across owners is not enabled on this server",
+                ));
+            }
+            let options = AdminListOptions {
+                cursor: list_params.cursor,
+                ..Default::default()
+            };
+            self.store
+                .list_all(owner_id, options)
+                .await
+                .map_err(task_error_to_pmcp)?
+        } else {
+            let options = ListTasksOptions {
+                owner_id: owner_id.to_string(),
+                cursor: list_params.cursor,
+                limit: None,
+            };
+            self.store.list(options).await.map_err(task_error_to_pmcp)?
         };
 
-        let page = self.store.list(options).await.map_err(task_error_to_pmcp)?;
-
         // Convert TaskPage to JSON response with tasks array and optional nextCursor
         let tasks_json: Vec<Value> = page
             .tasks
@@ -276,17 +326,98 @@ impl TaskRouter for TaskRouterImpl {
             .map_err(|e| PmcpError::internal(format!("failed to serialize CancelTaskResult: {e}")))
     }
 
+    /// Handle `tasks/provide_input` request.
+    ///
+    /// Only accepted while the task is `input_required`. An accepted answer
+    /// must contain every `required` field of the pending form request. The
+    /// task moves back to `working`, the answer is stored under
+    /// [`TASK_INPUT_KEY`], and the pending request is cleared.
+    async fn handle_tasks_provide_input(&self, params: Value, owner_id: &str) -> PmcpResult<Value> {
+        let input_params: TaskProvideInputParams = serde_json::from_value(params).map_err(|e| {
+            PmcpError::invalid_params(format!("invalid tasks/provide_input params: {e}"))
+        })?;
+        let task_id = &input_params.task_id;
+
+        let record = self
+            .store
+            .get(task_id, owner_id)
+            .await
+            .map_err(task_error_to_pmcp)?;
+        if record.task.status != TaskStatus::InputRequired {
+            return Err(task_error_to_pmcp(TaskError::InvalidTransition {
+                task_id: task_id.clone(),
+                from: record.task.status,
+                to: TaskStatus::Working,
+                suggested_action: Some("the task is not waiting for input".to_string()),
+            }));
+        }
+
+        let pending: Option<ElicitRequestParams> = record
+            .variables
+            .get(TASK_INPUT_REQUEST_KEY)
+            .and_then(|request| serde_json::from_value(request.clone()).ok());
+        if let Some(pending) = pending {
+            let missing = missing_required_fields(&pending, &input_params.input);
+            if !missing.is_empty() {
+                return Err(PmcpError::invalid_params(format!(
+                    "missing required input fields: {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+
+        let answer = serde_json::to_value(&input_params.input)
+            .map_err(|e| PmcpError::internal(format!("failed to serialize task input: {e}")))?;
+
+        // Resume first so a concurrent cancel wins before the answer is stored
+        self.store
+            .update_status(task_id, owner_id, TaskStatus::Working, None)
+            .await
+            .map_err(task_error_to_pmcp)?;
+        let record = self
+            .store
+            .set_variables(
+                task_id,
+                owner_id,
+                HashMap::from([
+                    (TASK_INPUT_KEY.to_string(), answer),
+                    (TASK_INPUT_REQUEST_KEY.to_string(), Value::Null),
+                ]),
+            )
+            .await
+            .map_err(task_error_to_pmcp)?;
+
+        let wire_task = record.to_wire_task_with_variables();
+        serde_json::to_value(wire_task)
+            .map_err(|e| PmcpError::internal(format!("failed to serialize task: {e}")))
+    }
+
     /// Resolve owner ID from authentication context fields.
     ///
-    /// Delegates to [`resolve_owner_id`] with the given subject, client ID,
-    /// and session ID.
+    /// Applies the configured [`OwnerStrategy`](crate::security::OwnerStrategy)
+    /// to an auth context built from the subject and client ID.
     fn resolve_owner(
         &self,
         subject: Option<&str>,
         client_id: Option<&str>,
         session_id: Option<&str>,
     ) -> String {
-        resolve_owner_id(subject, client_id, session_id)
+        let auth = (subject.is_some() || client_id.is_some()).then(|| AuthContext {
+            subject: subject.unwrap_or_default().to_string(),
+            client_id: client_id.map(str::to_string),
+            ..Default::default()
+        });
+        self.config.resolve_owner(auth.as_ref(), session_id)
+    }
+
+    /// Resolve owner ID from the full authentication context using the
+    /// configured [`OwnerStrategy`](crate::security::OwnerStrategy).
+    fn resolve_owner_from_auth(
+        &self,
+        auth_context: Option<&AuthContext>,
+        session_id: Option<&str>,
+    ) -> String {
+        self.config.resolve_owner(auth_context, session_id)
     }
 
     /// Check if a tool requires task augmentation (`taskSupport: required`).
@@ -358,6 +489,20 @@ impl TaskRouter for TaskRouterImpl {
             .map_err(|e| PmcpError::internal(format!("failed to serialize CreateTaskResult: {e}")))
     }
 
+    /// Load a workflow task for resumption.
+    ///
+    /// Returns the wire task with its variables merged into `_meta`.
+    async fn get_workflow_task(&self, task_id: &str, owner_id: &str) -> PmcpResult<Value> {
+        let record = self
+            .store
+            .get(task_id, owner_id)
+            .await
+            .map_err(task_error_to_pmcp)?;
+
+        serde_json::to_value(record.to_wire_task_with_variables())
+            .map_err(|e| PmcpError::internal(format!("failed to serialize task: {e}")))
+    }
+
     /// Update task variables with workflow step results.
     ///
     /// Deserializes `variables` as a JSON object and sets each key-value
@@ -514,6 +659,7 @@ impl TaskRouter for TaskRouterImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::security::OwnerStrategy;
     use crate::store::memory::InMemoryTaskStore;
 
     fn make_router() -> TaskRouterImpl {
@@ -780,6 +926,58 @@ mod tests {
         assert_eq!(router.resolve_owner(None, None, None), "local");
     }
 
+    #[test]
+    fn resolve_owner_from_auth_uses_configured_strategy() {
+        let store = Arc::new(InMemoryTaskStore::new());
+        let router = TaskRouterImpl::new(store).with_config(
+            TaskRouterConfig::default().with_owner_strategy(OwnerStrategy::TenantUser),
+        );
+        let mut auth = AuthContext::new("user-1");
+        auth.claims
+            .insert("tenant_id".to_string(), serde_json::json!("acme"));
+
+        assert_eq!(
+            router.resolve_owner_from_auth(Some(&auth), None),
+            "acme/user-1"
+        );
+        assert_eq!(router.resolve_owner(Some("user-1"), None, None), "local");
+    }
+
+    #[tokio::test]
+    async fn tasks_list_all_owners_requires_flag_and_admin() {
+        let store = Arc::new(
+            InMemoryTaskStore::new().with_security(
+                TaskSecurityConfig::default()
+                    .with_allow_anonymous(true)
+                    .with_admin_owner("admin"),
+            ),
+        );
+        store.create("alice", "tools/call", None).await.unwrap();
+        store.create("bob", "tools/call", None).await.unwrap();
+        let all_owners = serde_json::json!({ "allOwners": true });
+
+        let disabled = TaskRouterImpl::new(store.clone());
+        assert!(disabled
+            .handle_tasks_list(all_owners.clone(), "admin")
+            .await
+            .is_err());
+
+        let router = TaskRouterImpl::new(store)
+            .with_config(TaskRouterConfig::default().with_admin_listing(true));
+        let result = router
+            .handle_tasks_list(all_owners.clone(), "admin")
+            .await
+            .unwrap();
+        assert_eq!(result["tasks"].as_array().unwrap().len(), 2);
+
+        assert!(router.handle_tasks_list(all_owners, "alice").await.is_err());
+        let own = router
+            .handle_tasks_list(serde_json::json!({}), "alice")
+            .await
+            .unwrap();
+        assert_eq!(own["tasks"].as_array().unwrap().len(), 1);
+    }
+
     #[test]
     fn tool_requires_task_with_required() {
         let router = make_router();
@@ -978,6 +1176,38 @@ mod tests {
         assert!(err.to_string().contains("not found"));
     }
 
+    #[tokio::test]
+    async fn get_workflow_task_includes_variables() {
+        let router = make_router();
+
+        let record = router
+            .store()
+            .create("owner-1", "tools/call", None)
+            .await
+            .unwrap();
+        let task_id = record.task.task_id.clone();
+        router
+            .set_task_variables(
+                &task_id,
+                "owner-1",
+                serde_json::json!({ "_workflow.binding.raw_data": { "rows": 3 } }),
+            )
+            .await
+            .unwrap();
+
+        let task = router.get_workflow_task(&task_id, "owner-1").await.unwrap();
+
+        assert_eq!(task["taskId"], task_id);
+        assert_eq!(task["status"], "working");
+        assert_eq!(task["_meta"]["_workflow.binding.raw_data"]["rows"], 3);
+
+        let err = router
+            .get_workflow_task(&task_id, "owner-2")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
     #[tokio::test]
     async fn complete_workflow_task_rejects_already_terminal() {
         let router = make_router();
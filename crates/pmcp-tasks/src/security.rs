@@ -13,12 +13,44 @@
 //! 3. Session ID
 //! 4. [`DEFAULT_LOCAL_OWNER`] ("local") -- used for single-user servers
 //!
+//! # Owner Strategies
+//!
+//! Multi-tenant servers can pick a different [`OwnerStrategy`] through
+//! [`TaskRouterConfig::with_owner_strategy`]: the session ID, the OAuth
+//! subject alone, a tenant+user composite, a hash of the caller's API key,
+//! or a custom [`OwnerResolver`]. The router and the task worker resolve
+//! owners through the configured strategy; [`resolve_owner_id`] remains the
+//! default ([`OwnerStrategy::PriorityChain`]).
+//!
 //! # Security Model
 //!
 //! Owner isolation is structural: every task operation receives an `owner_id`
 //! parameter, and the store enforces that callers can only access their own
 //! tasks. On owner mismatch, the store returns `NotFound` (never revealing
 //! that a task exists but belongs to someone else).
+//!
+//! # Admin Access
+//!
+//! Operators can inspect and cancel tasks across owners through the store's
+//! admin methods (`list_all`, `tenant_counts`, `admin_cancel`). These require
+//! the caller's owner ID to be listed in
+//! [`TaskSecurityConfig::admin_owners`]; everyone else gets
+//! [`TaskError::PermissionDenied`](crate::error::TaskError::PermissionDenied).
+//! Normal owner-scoped operations are unaffected.
+//!
+//! Admins can also list across owners over the protocol with
+//! `tasks/list` and `"allOwners": true`, once
+//! [`TaskRouterConfig::with_admin_listing`] turns that capability on.
+//!
+//! [`TaskSecurityConfig`] is enforced by the store and [`TaskRouterConfig`]
+//! by the router, so each setting lives where it takes effect.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use pmcp::server::auth::AuthContext;
+use sha2::{Digest, Sha256};
 
 /// Default owner ID used when no authentication is configured.
 ///
@@ -46,6 +78,7 @@ pub const DEFAULT_LOCAL_OWNER: &str = "local";
 /// |-----------------------|---------|------------------------------------------|
 /// | `max_tasks_per_owner` | 100     | Maximum active tasks per owner           |
 /// | `allow_anonymous`     | false   | Whether anonymous/local access is allowed|
+/// | `admin_owners`        | empty   | Owner IDs allowed to use admin methods   |
 ///
 /// # Examples
 ///
@@ -71,7 +104,13 @@ pub struct TaskSecurityConfig {
     /// When this limit is reached, `create()` returns
     /// [`TaskError::ResourceExhausted`](crate::error::TaskError::ResourceExhausted)
     /// with a suggestion to cancel or wait for existing tasks to expire.
-    /// There is no auto-eviction -- the limit is a hard reject.
+    ///
+    /// This limit never evicts anything on its own. If the store's
+    /// [`RetentionPolicy`](crate::store::retention::RetentionPolicy) also sets
+    /// `max_tasks_per_owner`, `create()` first evicts the owner's oldest
+    /// terminal tasks down to that cap, then applies this limit to the tasks
+    /// that remain. Working tasks are never evicted, so this limit still
+    /// caps how many working tasks an owner can hold.
     pub max_tasks_per_owner: usize,
 
     /// Whether anonymous (unauthenticated) access is allowed.
@@ -80,6 +119,13 @@ pub struct TaskSecurityConfig {
     /// [`DEFAULT_LOCAL_OWNER`] value are rejected. Set to `true` for
     /// local single-user servers that operate without OAuth.
     pub allow_anonymous: bool,
+
+    /// Owner IDs granted the admin capability (cross-owner listing,
+    /// per-tenant counts, and cancellation).
+    ///
+    /// Empty by default, which disables admin access entirely. Anonymous
+    /// owners are never treated as admins, even if listed.
+    pub admin_owners: HashSet<String>,
 }
 
 impl Default for TaskSecurityConfig {
@@ -87,6 +133,7 @@ impl Default for TaskSecurityConfig {
         Self {
             max_tasks_per_owner: 100,
             allow_anonymous: false,
+            admin_owners: HashSet::new(),
         }
     }
 }
@@ -123,6 +170,227 @@ impl TaskSecurityConfig {
         self.allow_anonymous = allow;
         self
     }
+
+    /// Grants the admin capability to an owner ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp_tasks::security::TaskSecurityConfig;
+    ///
+    /// let config = TaskSecurityConfig::default()
+    ///     .with_admin_owner("ops@example.com");
+    /// assert!(config.is_admin("ops@example.com"));
+    /// assert!(!config.is_admin("user-123"));
+    /// ```
+    pub fn with_admin_owner(mut self, owner_id: impl Into<String>) -> Self {
+        self.admin_owners.insert(owner_id.into());
+        self
+    }
+
+    /// Returns whether the given owner holds the admin capability.
+    ///
+    /// Empty and [`DEFAULT_LOCAL_OWNER`] owner IDs are never admins, so
+    /// unauthenticated callers cannot reach admin methods.
+    pub fn is_admin(&self, owner_id: &str) -> bool {
+        !owner_id.is_empty()
+            && owner_id != DEFAULT_LOCAL_OWNER
+            && self.admin_owners.contains(owner_id)
+    }
+}
+
+/// Router-level task configuration: how requests map to owners and which
+/// protocol capabilities are exposed.
+///
+/// Store-level limits and the admin owner list belong in
+/// [`TaskSecurityConfig`], which the store enforces.
+///
+/// # Defaults
+///
+/// | Setting          | Default         | Description                              |
+/// |------------------|-----------------|------------------------------------------|
+/// | `admin_listing`  | false           | Whether `tasks/list` accepts `allOwners` |
+/// | `owner_strategy` | `PriorityChain` | How owner IDs are resolved               |
+#[derive(Debug, Clone, Default)]
+pub struct TaskRouterConfig {
+    /// Whether admins may list tasks across owners through `tasks/list`
+    /// with `"allOwners": true`.
+    ///
+    /// Off by default. The store's admin methods are available to admins
+    /// regardless; this flag only gates the protocol-level capability.
+    pub admin_listing: bool,
+
+    /// How the owner ID of a request is resolved.
+    pub owner_strategy: OwnerStrategy,
+}
+
+impl TaskRouterConfig {
+    /// Enables or disables cross-owner listing through `tasks/list`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp_tasks::security::TaskRouterConfig;
+    ///
+    /// let config = TaskRouterConfig::default()
+    ///     .with_admin_listing(true);
+    /// assert!(config.admin_listing);
+    /// ```
+    pub fn with_admin_listing(mut self, enabled: bool) -> Self {
+        self.admin_listing = enabled;
+        self
+    }
+
+    /// Sets the owner resolution strategy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp::server::auth::AuthContext;
+    /// use pmcp_tasks::security::{OwnerStrategy, TaskRouterConfig};
+    ///
+    /// let config = TaskRouterConfig::default().with_owner_strategy(OwnerStrategy::TenantUser);
+    ///
+    /// let mut auth = AuthContext::new("user-1");
+    /// auth.claims.insert("tid".to_string(), "acme".into());
+    /// assert_eq!(config.resolve_owner(Some(&auth), None), "acme/user-1");
+    /// ```
+    pub fn with_owner_strategy(mut self, strategy: OwnerStrategy) -> Self {
+        self.owner_strategy = strategy;
+        self
+    }
+
+    /// Resolves the owner ID of a request with the configured strategy.
+    pub fn resolve_owner(&self, auth: Option<&AuthContext>, session_id: Option<&str>) -> String {
+        self.owner_strategy.resolve(auth, session_id)
+    }
+}
+
+/// Pluggable owner resolution for [`OwnerStrategy::Custom`].
+///
+/// Return `None` when the request carries no usable identity; the request
+/// then falls back to [`DEFAULT_LOCAL_OWNER`] (and is rejected unless
+/// anonymous access is allowed).
+pub trait OwnerResolver: Send + Sync {
+    /// Derives the owner ID from the request's auth context and session.
+    fn resolve_owner(&self, auth: Option<&AuthContext>, session_id: Option<&str>)
+        -> Option<String>;
+}
+
+/// Strategy for deriving a task owner ID from a request.
+///
+/// Every strategy falls back to [`DEFAULT_LOCAL_OWNER`] when its identity
+/// source is missing, so the anonymous-access check still applies.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp::server::auth::AuthContext;
+/// use pmcp_tasks::security::{OwnerStrategy, DEFAULT_LOCAL_OWNER};
+///
+/// let mut auth = AuthContext::new("user-1");
+/// auth.client_id = Some("client-a".to_string());
+///
+/// assert_eq!(OwnerStrategy::PriorityChain.resolve(Some(&auth), None), "user-1");
+/// assert_eq!(OwnerStrategy::SessionId.resolve(Some(&auth), Some("sess-9")), "sess-9");
+/// assert_eq!(OwnerStrategy::Subject.resolve(None, Some("sess-9")), DEFAULT_LOCAL_OWNER);
+/// ```
+#[derive(Clone, Default)]
+pub enum OwnerStrategy {
+    /// OAuth subject, then client ID, then session ID
+    /// (see [`resolve_owner_id`]).
+    #[default]
+    PriorityChain,
+
+    /// The transport session ID. Tasks are private to one connection.
+    SessionId,
+
+    /// The OAuth subject only. Tasks follow the user across clients.
+    Subject,
+
+    /// `{tenant}/{subject}`, with the tenant taken from
+    /// [`AuthContext::tenant_id`]. Keeps equal subjects in different
+    /// tenants apart.
+    TenantUser,
+
+    /// `key:` followed by a truncated SHA-256 of the caller's token (see
+    /// [`api_key_owner_id`]). For API-key auth, where there is no subject;
+    /// the key itself is never stored.
+    ApiKeyHash,
+
+    /// A custom resolver.
+    Custom(Arc<dyn OwnerResolver>),
+}
+
+impl fmt::Debug for OwnerStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PriorityChain => f.write_str("PriorityChain"),
+            Self::SessionId => f.write_str("SessionId"),
+            Self::Subject => f.write_str("Subject"),
+            Self::TenantUser => f.write_str("TenantUser"),
+            Self::ApiKeyHash => f.write_str("ApiKeyHash"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl OwnerStrategy {
+    /// Resolves the owner ID for a request.
+    pub fn resolve(&self, auth: Option<&AuthContext>, session_id: Option<&str>) -> String {
+        let subject = auth
+            .map(|auth| auth.subject.as_str())
+            .filter(|subject| !subject.is_empty());
+
+        let owner = match self {
+            Self::PriorityChain => {
+                return resolve_owner_id(
+                    subject,
+                    auth.and_then(|auth| auth.client_id.as_deref()),
+                    session_id,
+                )
+            },
+            Self::SessionId => session_id.map(str::to_string),
+            Self::Subject => subject.map(str::to_string),
+            Self::TenantUser => auth
+                .and_then(AuthContext::tenant_id)
+                .filter(|tenant| !tenant.is_empty())
+                .zip(subject)
+                .map(|(tenant, subject)| format!("{tenant}/{subject}")),
+            Self::ApiKeyHash => auth
+                .and_then(|auth| auth.token.as_deref())
+                .filter(|token| !token.is_empty())
+                .map(api_key_owner_id),
+            Self::Custom(resolver) => resolver.resolve_owner(auth, session_id),
+        };
+
+        owner
+            .filter(|owner| !owner.is_empty())
+            .unwrap_or_else(|| DEFAULT_LOCAL_OWNER.to_string())
+    }
+}
+
+/// Owner ID for an API key: `key:` and the first 32 hex digits of its
+/// SHA-256.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::security::api_key_owner_id;
+///
+/// let owner = api_key_owner_id("sk-live-123");
+/// assert!(owner.starts_with("key:"));
+/// assert_eq!(owner.len(), 36);
+/// assert_eq!(owner, api_key_owner_id("sk-live-123"));
+/// ```
+pub fn api_key_owner_id(api_key: &str) -> String {
+    let digest = Sha256::digest(api_key.as_bytes());
+    let hex: String = digest
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("key:{hex}")
 }
 
 /// Resolves the owner ID from available identity sources.
@@ -141,14 +409,8 @@ impl TaskSecurityConfig {
 /// * `client_id` - The OAuth client ID, if available.
 /// * `session_id` - The transport session ID, if available.
 ///
-/// # Design Note
-///
-/// This function takes `&str` slices rather than an `AuthContext` reference
-/// to avoid coupling `pmcp-tasks` to the `pmcp` crate's auth types. Phase 3
-/// middleware will bridge the two:
-/// ```text
-/// resolve_owner_id(Some(&auth.subject), auth.client_id.as_deref(), session_id)
-/// ```
+/// This is the chain behind [`OwnerStrategy::PriorityChain`]; call it
+/// directly when the identity does not come from an [`AuthContext`].
 ///
 /// # Examples
 ///
@@ -248,6 +510,106 @@ mod tests {
         assert!(debug.contains("100"));
     }
 
+    #[test]
+    fn admin_disabled_by_default() {
+        let config = TaskSecurityConfig::default();
+        assert!(config.admin_owners.is_empty());
+        assert!(!config.is_admin("anyone"));
+    }
+
+    #[test]
+    fn anonymous_owners_are_never_admin() {
+        let config = TaskSecurityConfig::default()
+            .with_admin_owner(DEFAULT_LOCAL_OWNER)
+            .with_admin_owner("");
+        assert!(!config.is_admin(DEFAULT_LOCAL_OWNER));
+        assert!(!config.is_admin(""));
+    }
+
+    // --- OwnerStrategy tests ---
+
+    fn auth(subject: &str) -> AuthContext {
+        let mut auth = AuthContext::new(subject);
+        auth.client_id = Some("client-a".to_string());
+        auth.token = Some("sk-test".to_string());
+        auth
+    }
+
+    #[test]
+    fn strategies_pick_their_identity_source() {
+        let auth = auth("user-1");
+
+        assert_eq!(
+            OwnerStrategy::PriorityChain.resolve(Some(&auth), Some("sess-1")),
+            "user-1"
+        );
+        assert_eq!(
+            OwnerStrategy::SessionId.resolve(Some(&auth), Some("sess-1")),
+            "sess-1"
+        );
+        assert_eq!(
+            OwnerStrategy::Subject.resolve(Some(&auth), Some("sess-1")),
+            "user-1"
+        );
+        assert_eq!(
+            OwnerStrategy::ApiKeyHash.resolve(Some(&auth), None),
+            api_key_owner_id("sk-test")
+        );
+        assert_ne!(api_key_owner_id("sk-test"), api_key_owner_id("sk-other"));
+    }
+
+    #[test]
+    fn tenant_user_separates_tenants_and_requires_both_parts() {
+        let mut acme = auth("user-1");
+        acme.claims.insert("tenant_id".to_string(), "acme".into());
+        let mut globex = auth("user-1");
+        globex.claims.insert("org_id".to_string(), "globex".into());
+
+        assert_eq!(
+            OwnerStrategy::TenantUser.resolve(Some(&acme), None),
+            "acme/user-1"
+        );
+        assert_eq!(
+            OwnerStrategy::TenantUser.resolve(Some(&globex), None),
+            "globex/user-1"
+        );
+        assert_eq!(
+            OwnerStrategy::TenantUser.resolve(Some(&auth("user-1")), None),
+            DEFAULT_LOCAL_OWNER
+        );
+    }
+
+    #[test]
+    fn custom_strategy_falls_back_to_local() {
+        struct EmailOwner;
+        impl OwnerResolver for EmailOwner {
+            fn resolve_owner(
+                &self,
+                auth: Option<&AuthContext>,
+                _session_id: Option<&str>,
+            ) -> Option<String> {
+                auth.and_then(AuthContext::email).map(str::to_string)
+            }
+        }
+
+        let config = TaskRouterConfig::default()
+            .with_owner_strategy(OwnerStrategy::Custom(Arc::new(EmailOwner)));
+        let mut with_email = auth("user-1");
+        with_email
+            .claims
+            .insert("email".to_string(), "ada@example.com".into());
+
+        assert_eq!(
+            config.resolve_owner(Some(&with_email), None),
+            "ada@example.com"
+        );
+        assert_eq!(
+            config.resolve_owner(Some(&auth("user-1")), None),
+            DEFAULT_LOCAL_OWNER
+        );
+        assert!(format!("{config:?}").contains("Custom(..)"));
+    }
+
     // --- DEFAULT_LOCAL_OWNER tests ---
 
     #[test]
@@ -244,6 +244,41 @@ pub trait StorageBackend: Send + Sync {
         prefix: &str,
     ) -> Result<Vec<(String, VersionedRecord)>, StorageError>;
 
+    /// Lists every record in the backend, across all owners.
+    ///
+    /// Used only by the admin listing path
+    /// ([`GenericTaskStore::list_all`](crate::store::generic::GenericTaskStore::list_all))
+    /// and by retention-aware cleanup, never by owner-scoped operations. Keys are in `{owner_id}:{task_id}`
+    /// form, as with [`list_by_prefix`](StorageBackend::list_by_prefix).
+    ///
+    /// The default implementation returns [`StorageError::Backend`] for
+    /// backends that cannot enumerate across partitions.
+    ///
+    /// # Errors
+    ///
+    /// - [`StorageError::Backend`] on I/O failures or if unsupported.
+    async fn list_all(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        Err(StorageError::Backend {
+            message: "this backend does not support listing tasks across owners".to_string(),
+            source: None,
+        })
+    }
+
+    /// Lists records that have expired but are still stored.
+    ///
+    /// Cleanup uses this to archive expired tasks before
+    /// [`cleanup_expired`](StorageBackend::cleanup_expired) deletes them.
+    /// The default implementation returns nothing, which suits backends
+    /// whose records expire natively (Redis, `DynamoDB`): once a record's
+    /// TTL passes it is gone and there is nothing left to archive.
+    ///
+    /// # Errors
+    ///
+    /// - [`StorageError::Backend`] on I/O or backend-specific failures.
+    async fn list_expired(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        Ok(Vec::new())
+    }
+
     /// Removes records that have expired, using backend-specific criteria.
     ///
     /// This is a best-effort operation. Different backends handle TTL
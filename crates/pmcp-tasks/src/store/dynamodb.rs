@@ -1,9 +1,10 @@
 //! DynamoDB storage backend for task persistence.
 //!
 //! [`DynamoDbBackend`] implements [`StorageBackend`] using Amazon DynamoDB as
-//! the underlying key-value store. It maps the 6 trait methods to DynamoDB API
+//! the underlying key-value store. It maps the trait methods to DynamoDB API
 //! calls: `GetItem`, `PutItem` (with `ConditionExpression` for CAS),
-//! `DeleteItem`, and `Query` (for `list_by_prefix`).
+//! `DeleteItem`, `Query` (for `list_by_prefix`), and `Scan` (for the admin
+//! `list_all`).
 //!
 //! # Single-Table Design
 //!
@@ -56,6 +57,8 @@
 //! # }
 //! ```
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use aws_sdk_dynamodb::types::AttributeValue;
 use aws_sdk_dynamodb::Client;
@@ -225,6 +228,25 @@ fn extract_ttl_epoch(data: &[u8]) -> Option<i64> {
     Some(dt.timestamp())
 }
 
+/// Converts a DynamoDB item into a `({owner_id}:{task_id}, record)` entry.
+///
+/// Returns `None` for items that do not follow the single-table layout.
+fn item_to_entry(item: &HashMap<String, AttributeValue>) -> Option<(String, VersionedRecord)> {
+    let pk = item.get("PK")?.as_s().ok()?;
+    let sk = item.get("SK")?.as_s().ok()?;
+    let version = item.get("version")?.as_n().ok()?.parse::<u64>().ok()?;
+    let data = item.get("data")?.as_s().ok()?;
+    let (owner, task_id) = (parse_pk(pk)?, parse_sk(sk)?);
+
+    Some((
+        format!("{owner}:{task_id}"),
+        VersionedRecord {
+            data: data.as_bytes().to_vec(),
+            version,
+        },
+    ))
+}
+
 /// Maps an AWS SDK error to a [`StorageError::Backend`].
 fn map_sdk_error(err: impl std::error::Error + Send + Sync + 'static, key: &str) -> StorageError {
     StorageError::Backend {
@@ -404,33 +426,34 @@ impl StorageBackend for DynamoDbBackend {
 
             let output = query.send().await.map_err(|e| map_sdk_error(e, prefix))?;
 
-            for item in output.items() {
-                let item_pk = item.get("PK").and_then(|v: &AttributeValue| v.as_s().ok());
-                let item_sk = item.get("SK").and_then(|v: &AttributeValue| v.as_s().ok());
-                let version_str = item
-                    .get("version")
-                    .and_then(|v: &AttributeValue| v.as_n().ok());
-                let data_str = item
-                    .get("data")
-                    .and_then(|v: &AttributeValue| v.as_s().ok());
-
-                if let (Some(pk_val), Some(sk_val), Some(ver), Some(data)) =
-                    (item_pk, item_sk, version_str, data_str)
-                {
-                    if let (Some(owner), Some(task_id)) = (parse_pk(pk_val), parse_sk(sk_val)) {
-                        if let Ok(version) = ver.parse::<u64>() {
-                            let composite_key = format!("{owner}:{task_id}");
-                            results.push((
-                                composite_key,
-                                VersionedRecord {
-                                    data: data.as_bytes().to_vec(),
-                                    version,
-                                },
-                            ));
-                        }
-                    }
-                }
+            results.extend(output.items().iter().filter_map(item_to_entry));
+
+            match output.last_evaluated_key() {
+                Some(last_key) if !last_key.is_empty() => {
+                    exclusive_start_key = Some(last_key.clone());
+                },
+                _ => break,
             }
+        }
+
+        Ok(results)
+    }
+
+    /// Scans the whole table. Intended for occasional admin inspection, not
+    /// request paths: a `Scan` reads every item and consumes capacity
+    /// accordingly.
+    async fn list_all(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let mut results = Vec::new();
+        let mut exclusive_start_key = None;
+
+        loop {
+            let mut scan = self.client.scan().table_name(&self.table_name);
+            if let Some(start_key) = exclusive_start_key.take() {
+                scan = scan.set_exclusive_start_key(Some(start_key));
+            }
+
+            let output = scan.send().await.map_err(|e| map_sdk_error(e, "*"))?;
+            results.extend(output.items().iter().filter_map(item_to_entry));
 
             match output.last_evaluated_key() {
                 Some(last_key) if !last_key.is_empty() => {
@@ -32,16 +32,49 @@
 //!
 //! Owner mismatch on any operation returns [`TaskError::NotFound`] -- the
 //! store never reveals that a task exists for a different owner.
+//!
+//! # Admin Access
+//!
+//! Owners listed in [`TaskSecurityConfig::admin_owners`] may call
+//! [`list_all`](GenericTaskStore::list_all),
+//! [`tenant_counts`](GenericTaskStore::tenant_counts), and
+//! [`admin_cancel`](GenericTaskStore::admin_cancel) to work across owners.
+//! Any other caller gets [`TaskError::PermissionDenied`]; the regular
+//! operations keep their owner isolation.
+//!
+//! # Retention
+//!
+//! A [`RetentionPolicy`] set with
+//! [`with_retention`](GenericTaskStore::with_retention) evicts an owner's
+//! oldest terminal tasks once they reach a cap (on `create` and during
+//! cleanup) and removes terminal tasks after a retention window. A
+//! [`TaskArchiver`] set with [`with_archiver`](GenericTaskStore::with_archiver)
+//! sees every task before it is deleted. Cleanup activity is counted in
+//! [`cleanup_stats`](GenericTaskStore::cleanup_stats).
+//!
+//! # Metrics
+//!
+//! With [`with_observability`](GenericTaskStore::with_observability) the
+//! store emits lifecycle metrics (creations, terminal outcomes, time in each
+//! status, variable payload sizes) to an [`ObservabilityBackend`]; see
+//! [`metrics`](crate::store::metrics) for the full list.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use chrono::Utc;
+use pmcp::server::observability::ObservabilityBackend;
 use serde_json::Value;
 
 use crate::domain::record::{validate_variables, TaskRecord};
 use crate::error::TaskError;
 use crate::security::{TaskSecurityConfig, DEFAULT_LOCAL_OWNER};
-use crate::store::backend::{make_key, make_prefix, StorageBackend, StorageError};
-use crate::store::{ListTasksOptions, StoreConfig, TaskPage};
+use crate::store::backend::{make_key, make_prefix, StorageBackend, StorageError, VersionedRecord};
+use crate::store::metrics::TaskMetricsRecorder;
+use crate::store::retention::{
+    CleanupMetrics, CleanupStats, RemovalReason, RetentionPolicy, TaskArchiver,
+};
+use crate::store::{AdminListOptions, ListTasksOptions, StoreConfig, TaskPage, TenantTaskCounts};
 use crate::types::task::TaskStatus;
 
 /// Generic task store that delegates all storage to a [`StorageBackend`].
@@ -60,6 +93,10 @@ pub struct GenericTaskStore<B: StorageBackend> {
     config: StoreConfig,
     security: TaskSecurityConfig,
     default_poll_interval: u64,
+    retention: RetentionPolicy,
+    archiver: Option<Arc<dyn TaskArchiver>>,
+    cleanup_metrics: CleanupMetrics,
+    metrics: Option<TaskMetricsRecorder>,
 }
 
 impl<B: StorageBackend> GenericTaskStore<B> {
@@ -75,6 +112,10 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             config: StoreConfig::default(),
             security: TaskSecurityConfig::default(),
             default_poll_interval: 500,
+            retention: RetentionPolicy::default(),
+            archiver: None,
+            cleanup_metrics: CleanupMetrics::default(),
+            metrics: None,
         }
     }
 
@@ -96,6 +137,39 @@ impl<B: StorageBackend> GenericTaskStore<B> {
         self
     }
 
+    /// Sets the retention policy applied on top of TTL expiry.
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Sets the hook that archives tasks before they are deleted.
+    pub fn with_archiver(mut self, archiver: Arc<dyn TaskArchiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+
+    /// Emits task lifecycle metrics to `backend`.
+    pub fn with_observability(mut self, backend: Arc<dyn ObservabilityBackend>) -> Self {
+        self.metrics = Some(TaskMetricsRecorder::new(backend));
+        self
+    }
+
+    /// Adds a dimension (e.g. `ServerName`) to every task metric.
+    ///
+    /// Has no effect unless [`with_observability`](Self::with_observability)
+    /// was called first.
+    pub fn with_metric_dimension(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.add_dimension(key.into(), value.into());
+        }
+        self
+    }
+
     // ---- Serialization helpers (private) ----
 
     fn serialize_record(record: &TaskRecord) -> Result<Vec<u8>, TaskError> {
@@ -141,12 +215,162 @@ impl<B: StorageBackend> GenericTaskStore<B> {
         Ok(())
     }
 
+    /// Validates that the caller holds the admin capability.
+    fn check_admin(&self, admin_id: &str, operation: &str) -> Result<(), TaskError> {
+        if !self.security.is_admin(admin_id) {
+            tracing::warn!(
+                owner_id = %admin_id,
+                operation = %operation,
+                "admin operation denied"
+            );
+            return Err(TaskError::PermissionDenied {
+                operation: operation.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Deserializes backend entries, skipping records that fail to parse.
+    fn deserialize_entries<'a>(
+        entries: impl IntoIterator<Item = &'a VersionedRecord>,
+    ) -> Vec<TaskRecord> {
+        entries
+            .into_iter()
+            .filter_map(|versioned| {
+                let mut record = Self::deserialize_record(&versioned.data).ok()?;
+                record.version = versioned.version;
+                Some(record)
+            })
+            .collect()
+    }
+
+    /// Deserializes backend entries keeping their keys, skipping records
+    /// that fail to parse.
+    fn deserialize_keyed(entries: Vec<(String, VersionedRecord)>) -> Vec<(String, TaskRecord)> {
+        entries
+            .into_iter()
+            .filter_map(|(key, versioned)| {
+                let mut record = Self::deserialize_record(&versioned.data).ok()?;
+                record.version = versioned.version;
+                Some((key, record))
+            })
+            .collect()
+    }
+
+    /// Archives (if configured) and deletes one task.
+    ///
+    /// Returns `false` without deleting if archiving fails, so the task is
+    /// retried by a later cleanup.
+    async fn remove_record(
+        &self,
+        key: &str,
+        record: &TaskRecord,
+        reason: RemovalReason,
+    ) -> Result<bool, TaskError> {
+        if let Some(archiver) = &self.archiver {
+            if let Err(e) = archiver.archive(record, reason).await {
+                self.cleanup_metrics.record_archive_failure();
+                tracing::warn!(
+                    task_id = %record.task.task_id,
+                    owner_id = %record.owner_id,
+                    error = %e,
+                    "failed to archive task; keeping it"
+                );
+                return Ok(false);
+            }
+            self.cleanup_metrics.record_archived();
+        }
+
+        let deleted = self
+            .backend
+            .delete(key)
+            .await
+            .map_err(|e| Self::map_storage_error(e, &record.task.task_id))?;
+        if deleted {
+            self.cleanup_metrics.record_removed(reason, 1);
+        }
+        Ok(deleted)
+    }
+
+    /// Evicts up to `count` of the oldest terminal tasks in `records`.
+    ///
+    /// Returns the number of tasks actually removed.
+    async fn evict_oldest_terminal(
+        &self,
+        records: Vec<(String, TaskRecord)>,
+        count: usize,
+    ) -> Result<usize, TaskError> {
+        if count == 0 {
+            return Ok(0);
+        }
+        let mut terminal: Vec<(String, TaskRecord)> = records
+            .into_iter()
+            .filter(|(_, record)| record.task.status.is_terminal())
+            .collect();
+        terminal.sort_by(|(_, a), (_, b)| a.task.created_at.cmp(&b.task.created_at));
+
+        let mut evicted = 0;
+        for (key, record) in terminal.iter().take(count) {
+            if self
+                .remove_record(key, record, RemovalReason::Evicted)
+                .await?
+            {
+                evicted += 1;
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Sorts tasks newest first and slices out one page.
+    ///
+    /// The cursor is the task ID of the last item in the previous page.
+    fn paginate(
+        mut tasks: Vec<TaskRecord>,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> TaskPage {
+        tasks.sort_by(|a, b| b.task.created_at.cmp(&a.task.created_at));
+
+        let start_idx = if let Some(cursor) = cursor {
+            tasks
+                .iter()
+                .position(|t| t.task.task_id == cursor)
+                .map_or(0, |i| i + 1)
+        } else {
+            0
+        };
+
+        let limit = limit.unwrap_or(50);
+        let page_tasks: Vec<TaskRecord> = tasks
+            .get(start_idx..)
+            .unwrap_or(&[])
+            .iter()
+            .take(limit)
+            .cloned()
+            .collect();
+
+        let next_cursor = if start_idx + limit < tasks.len() {
+            page_tasks.last().map(|t| t.task.task_id.clone())
+        } else {
+            None
+        };
+
+        TaskPage {
+            tasks: page_tasks,
+            next_cursor,
+        }
+    }
+
     // ---- Domain operations (public) ----
 
     /// Creates a new task in the `Working` state.
     ///
     /// Enforces anonymous access check, max tasks per owner, TTL maximum
     /// (hard reject, no silent clamping), and default TTL application.
+    ///
+    /// If the retention policy caps tasks per owner and the owner is at the
+    /// cap, their oldest terminal tasks are evicted first. The security
+    /// limit still applies to whatever remains.
     pub async fn create(
         &self,
         owner_id: &str,
@@ -163,7 +387,16 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .list_by_prefix(&prefix)
             .await
             .map_err(|e| Self::map_storage_error(e, ""))?;
-        if owner_records.len() >= self.security.max_tasks_per_owner {
+        let mut owner_count = owner_records.len();
+        if let Some(max) = self.retention.max_tasks_per_owner {
+            if owner_count >= max {
+                let excess = owner_count + 1 - max;
+                owner_count -= self
+                    .evict_oldest_terminal(Self::deserialize_keyed(owner_records), excess)
+                    .await?;
+            }
+        }
+        if owner_count >= self.security.max_tasks_per_owner {
             return Err(TaskError::ResourceExhausted {
                 suggested_action: Some("Cancel or wait for existing tasks to expire".to_string()),
             });
@@ -199,6 +432,10 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .map_err(|e| Self::map_storage_error(e, &record.task.task_id))?;
         record.version = version;
 
+        if let Some(metrics) = &self.metrics {
+            metrics.record_created(&record).await;
+        }
+
         Ok(record)
     }
 
@@ -282,10 +519,12 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .validate_transition(task_id, &new_status)?;
 
         // Apply transition
+        let now = Utc::now();
+        let (from, time_in_state) = (record.task.status, record.time_in_status(now));
         record.task.status = new_status;
         record.task.status_message = status_message;
-        record.task.last_updated_at =
-            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        record.task.last_updated_at = now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        record.status_changed_at = Some(now);
 
         // CAS write
         let bytes = Self::serialize_record(&record)?;
@@ -296,6 +535,12 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .map_err(|e| Self::map_storage_error(e, task_id))?;
         record.version = new_version;
 
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .record_transition(&record, from, time_in_state)
+                .await;
+        }
+
         Ok(record)
     }
 
@@ -370,6 +615,7 @@ impl<B: StorageBackend> GenericTaskStore<B> {
         }
 
         // Commit the merged variables
+        let variables_size = serialized.len();
         record.variables = merged;
         record.task.last_updated_at =
             chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
@@ -383,6 +629,10 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .map_err(|e| Self::map_storage_error(e, task_id))?;
         record.version = new_version;
 
+        if let Some(metrics) = &self.metrics {
+            metrics.record_variables_size(&record, variables_size).await;
+        }
+
         Ok(record)
     }
 
@@ -505,11 +755,13 @@ impl<B: StorageBackend> GenericTaskStore<B> {
         record.task.status.validate_transition(task_id, &status)?;
 
         // Apply atomically: status + result in a single CAS write
+        let now = Utc::now();
+        let (from, time_in_state) = (record.task.status, record.time_in_status(now));
         record.task.status = status;
         record.task.status_message = status_message;
         record.result = Some(result);
-        record.task.last_updated_at =
-            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        record.task.last_updated_at = now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        record.status_changed_at = Some(now);
 
         // CAS write
         let bytes = Self::serialize_record(&record)?;
@@ -520,6 +772,12 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .map_err(|e| Self::map_storage_error(e, task_id))?;
         record.version = new_version;
 
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .record_transition(&record, from, time_in_state)
+                .await;
+        }
+
         Ok(record)
     }
 
@@ -535,48 +793,104 @@ impl<B: StorageBackend> GenericTaskStore<B> {
             .await
             .map_err(|e| Self::map_storage_error(e, ""))?;
 
-        // Deserialize all records
-        let mut tasks: Vec<TaskRecord> = entries
-            .iter()
-            .filter_map(|(_, versioned)| {
-                let mut record = Self::deserialize_record(&versioned.data).ok()?;
-                record.version = versioned.version;
-                Some(record)
-            })
-            .collect();
+        let tasks = Self::deserialize_entries(entries.iter().map(|(_, versioned)| versioned));
+        Ok(Self::paginate(
+            tasks,
+            options.cursor.as_deref(),
+            options.limit,
+        ))
+    }
 
-        // Sort by creation time, newest first
-        tasks.sort_by(|a, b| b.task.created_at.cmp(&a.task.created_at));
+    /// Lists tasks across all owners (admin only).
+    ///
+    /// Applies the optional owner and status filters, then paginates like
+    /// [`list`](Self::list). Expired tasks are included so operators can
+    /// see what cleanup has not yet removed.
+    pub async fn list_all(
+        &self,
+        admin_id: &str,
+        options: AdminListOptions,
+    ) -> Result<TaskPage, TaskError> {
+        self.check_admin(admin_id, "list_all")?;
 
-        // Cursor-based pagination: cursor = task_id of last item in previous page
-        let start_idx = if let Some(ref cursor) = options.cursor {
-            tasks
-                .iter()
-                .position(|t| t.task.task_id == *cursor)
-                .map_or(0, |i| i + 1)
-        } else {
-            0
-        };
+        let entries = self
+            .backend
+            .list_all()
+            .await
+            .map_err(|e| Self::map_storage_error(e, ""))?;
 
-        let limit = options.limit.unwrap_or(50);
-        let page_tasks: Vec<TaskRecord> = tasks
-            .get(start_idx..)
-            .unwrap_or(&[])
-            .iter()
-            .take(limit)
-            .cloned()
-            .collect();
+        let tasks: Vec<TaskRecord> =
+            Self::deserialize_entries(entries.iter().map(|(_, versioned)| versioned))
+                .into_iter()
+                .filter(|t| {
+                    options
+                        .owner_id
+                        .as_ref()
+                        .is_none_or(|owner| t.owner_id == *owner)
+                })
+                .filter(|t| options.status.is_none_or(|status| t.task.status == status))
+                .collect();
+
+        Ok(Self::paginate(
+            tasks,
+            options.cursor.as_deref(),
+            options.limit,
+        ))
+    }
 
-        let next_cursor = if start_idx + limit < tasks.len() {
-            page_tasks.last().map(|t| t.task.task_id.clone())
-        } else {
-            None
-        };
+    /// Returns task counts per owner (admin only), sorted by owner ID.
+    pub async fn tenant_counts(&self, admin_id: &str) -> Result<Vec<TenantTaskCounts>, TaskError> {
+        self.check_admin(admin_id, "tenant_counts")?;
 
-        Ok(TaskPage {
-            tasks: page_tasks,
-            next_cursor,
-        })
+        let entries = self
+            .backend
+            .list_all()
+            .await
+            .map_err(|e| Self::map_storage_error(e, ""))?;
+
+        let mut counts: HashMap<String, TenantTaskCounts> = HashMap::new();
+        for record in Self::deserialize_entries(entries.iter().map(|(_, versioned)| versioned)) {
+            let entry = counts
+                .entry(record.owner_id.clone())
+                .or_insert_with(|| TenantTaskCounts {
+                    owner_id: record.owner_id.clone(),
+                    total: 0,
+                    active: 0,
+                    expired: 0,
+                    by_status: HashMap::new(),
+                });
+            entry.total += 1;
+            if record.is_expired() {
+                entry.expired += 1;
+            } else if !record.task.status.is_terminal() {
+                entry.active += 1;
+            }
+            *entry.by_status.entry(record.task.status).or_insert(0) += 1;
+        }
+
+        let mut counts: Vec<TenantTaskCounts> = counts.into_values().collect();
+        counts.sort_by(|a, b| a.owner_id.cmp(&b.owner_id));
+        Ok(counts)
+    }
+
+    /// Cancels another owner's task (admin only).
+    ///
+    /// The cancellation is logged with the admin's ID for auditing, then
+    /// follows the same rules as [`cancel`](Self::cancel).
+    pub async fn admin_cancel(
+        &self,
+        admin_id: &str,
+        task_id: &str,
+        owner_id: &str,
+    ) -> Result<TaskRecord, TaskError> {
+        self.check_admin(admin_id, "admin_cancel")?;
+        tracing::info!(
+            admin_id = %admin_id,
+            task_id = %task_id,
+            owner_id = %owner_id,
+            "admin cancelling task"
+        );
+        self.cancel(task_id, owner_id).await
     }
 
     /// Cancels a non-terminal task.
@@ -590,12 +904,104 @@ impl<B: StorageBackend> GenericTaskStore<B> {
 
     /// Removes expired tasks from storage.
     ///
-    /// Delegates to [`StorageBackend::cleanup_expired`].
+    /// Without a retention policy or archiver this delegates to
+    /// [`StorageBackend::cleanup_expired`]. With an archiver, expired tasks
+    /// from [`StorageBackend::list_expired`] are archived and deleted first.
+    /// With an active retention policy, live tasks are then scanned (requires
+    /// [`StorageBackend::list_all`]) to remove terminal tasks past their
+    /// retention window and each owner's oldest terminal tasks beyond the
+    /// per-owner cap, archiving each one first. Finally the backend's own
+    /// expiry cleanup runs, unless an expired task failed to archive and
+    /// must be kept for the next run.
+    ///
+    /// Returns the number of tasks removed.
     pub async fn cleanup_expired(&self) -> Result<usize, TaskError> {
-        self.backend
-            .cleanup_expired()
+        self.cleanup_metrics.record_run();
+
+        let mut removed = 0;
+        let mut archive_failed = false;
+        if self.archiver.is_some() {
+            let expired = self
+                .backend
+                .list_expired()
+                .await
+                .map_err(|e| Self::map_storage_error(e, ""))?;
+            for (key, record) in Self::deserialize_keyed(expired) {
+                if self
+                    .remove_record(&key, &record, RemovalReason::Expired)
+                    .await?
+                {
+                    removed += 1;
+                } else {
+                    archive_failed = true;
+                }
+            }
+        }
+
+        if self.retention.is_active() {
+            removed += self.apply_retention().await?;
+        }
+
+        if !archive_failed {
+            let expired = self
+                .backend
+                .cleanup_expired()
+                .await
+                .map_err(|e| Self::map_storage_error(e, ""))?;
+            self.cleanup_metrics
+                .record_removed(RemovalReason::Expired, expired as u64);
+            removed += expired;
+        }
+
+        tracing::debug!(removed, "task cleanup finished");
+        Ok(removed)
+    }
+
+    /// Removes live tasks that the retention policy no longer keeps.
+    ///
+    /// Returns the number of tasks removed.
+    async fn apply_retention(&self) -> Result<usize, TaskError> {
+        let entries = self
+            .backend
+            .list_all()
             .await
-            .map_err(|e| Self::map_storage_error(e, ""))
+            .map_err(|e| Self::map_storage_error(e, ""))?;
+
+        let now = Utc::now();
+        let mut removed = 0;
+        let mut by_owner: HashMap<String, Vec<(String, TaskRecord)>> = HashMap::new();
+        for (key, record) in Self::deserialize_keyed(entries) {
+            if record.is_expired() {
+                // Left to the expiry pass
+                continue;
+            }
+            if self.retention.terminal_retention_elapsed(&record, now) {
+                if self
+                    .remove_record(&key, &record, RemovalReason::RetentionElapsed)
+                    .await?
+                {
+                    removed += 1;
+                }
+            } else {
+                by_owner
+                    .entry(record.owner_id.clone())
+                    .or_default()
+                    .push((key, record));
+            }
+        }
+
+        if let Some(max) = self.retention.max_tasks_per_owner {
+            for records in by_owner.into_values() {
+                let excess = records.len().saturating_sub(max);
+                removed += self.evict_oldest_terminal(records, excess).await?;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Returns counters describing cleanup activity so far.
+    pub fn cleanup_stats(&self) -> CleanupStats {
+        self.cleanup_metrics.snapshot()
     }
 
     /// Returns a reference to the store's configuration.
@@ -612,7 +1018,6 @@ impl<B: StorageBackend> GenericTaskStore<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::store::backend::VersionedRecord;
     use crate::store::memory::InMemoryBackend;
     use async_trait::async_trait;
     use serde_json::json;
@@ -884,14 +1289,10 @@ mod tests {
     #[tokio::test]
     async fn cas_conflict_returns_concurrent_modification() {
         let backend = Arc::new(InMemoryBackend::new());
-        let store = GenericTaskStore {
-            backend: CasConflictBackend {
-                inner: backend.clone(),
-            },
-            config: StoreConfig::default(),
-            security: TaskSecurityConfig::default().with_allow_anonymous(true),
-            default_poll_interval: 500,
-        };
+        let store = GenericTaskStore::new(CasConflictBackend {
+            inner: backend.clone(),
+        })
+        .with_security(TaskSecurityConfig::default().with_allow_anonymous(true));
 
         let created = store.create("owner-1", "tools/call", None).await.unwrap();
 
@@ -1056,6 +1457,117 @@ mod tests {
         assert!(page.tasks.iter().all(|t| t.owner_id == "owner-a"));
     }
 
+    // ---- Admin access tests ----
+
+    /// Helper: creates a store where `admin` holds the admin capability.
+    fn admin_store() -> GenericTaskStore<InMemoryBackend> {
+        GenericTaskStore::new(InMemoryBackend::new()).with_security(
+            TaskSecurityConfig::default()
+                .with_allow_anonymous(true)
+                .with_admin_owner("admin"),
+        )
+    }
+
+    #[tokio::test]
+    async fn admin_operations_reject_non_admin() {
+        let store = admin_store();
+        let created = store.create("owner-a", "tools/call", None).await.unwrap();
+
+        let result = store.list_all("owner-a", AdminListOptions::default()).await;
+        assert!(matches!(result, Err(TaskError::PermissionDenied { .. })));
+        let result = store.tenant_counts("owner-a").await;
+        assert!(matches!(result, Err(TaskError::PermissionDenied { .. })));
+        let result = store
+            .admin_cancel("owner-b", &created.task.task_id, "owner-a")
+            .await;
+        assert!(matches!(result, Err(TaskError::PermissionDenied { .. })));
+    }
+
+    #[tokio::test]
+    async fn list_all_spans_owners_with_filters() {
+        let store = admin_store();
+        let a = store.create("owner-a", "tools/call", None).await.unwrap();
+        store.create("owner-a", "tools/call", None).await.unwrap();
+        store.create("owner-b", "tools/call", None).await.unwrap();
+        store.cancel(&a.task.task_id, "owner-a").await.unwrap();
+
+        let page = store
+            .list_all("admin", AdminListOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(page.tasks.len(), 3);
+
+        let page = store
+            .list_all(
+                "admin",
+                AdminListOptions {
+                    owner_id: Some("owner-a".to_string()),
+                    status: Some(TaskStatus::Working),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.tasks.len(), 1);
+        assert_eq!(page.tasks[0].owner_id, "owner-a");
+        assert_eq!(page.tasks[0].task.status, TaskStatus::Working);
+
+        let page = store
+            .list_all(
+                "admin",
+                AdminListOptions {
+                    limit: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.tasks.len(), 2);
+        assert!(page.next_cursor.is_some());
+    }
+
+    #[tokio::test]
+    async fn tenant_counts_aggregates_per_owner() {
+        let store = admin_store();
+        let a = store.create("owner-a", "tools/call", None).await.unwrap();
+        store.create("owner-a", "tools/call", None).await.unwrap();
+        store.create("owner-b", "tools/call", None).await.unwrap();
+        store
+            .update_status(&a.task.task_id, "owner-a", TaskStatus::Completed, None)
+            .await
+            .unwrap();
+
+        let counts = store.tenant_counts("admin").await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].owner_id, "owner-a");
+        assert_eq!(counts[0].total, 2);
+        assert_eq!(counts[0].active, 1);
+        assert_eq!(counts[0].expired, 0);
+        assert_eq!(counts[0].by_status.get(&TaskStatus::Completed), Some(&1));
+        assert_eq!(counts[0].by_status.get(&TaskStatus::Working), Some(&1));
+        assert_eq!(counts[1].owner_id, "owner-b");
+        assert_eq!(counts[1].total, 1);
+    }
+
+    #[tokio::test]
+    async fn admin_cancel_cancels_other_owners_task() {
+        let store = admin_store();
+        let created = store.create("owner-a", "tools/call", None).await.unwrap();
+
+        let cancelled = store
+            .admin_cancel("admin", &created.task.task_id, "owner-a")
+            .await
+            .unwrap();
+        assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+
+        // Wrong owner still surfaces as NotFound, even for admins
+        let other = store.create("owner-b", "tools/call", None).await.unwrap();
+        let result = store
+            .admin_cancel("admin", &other.task.task_id, "owner-a")
+            .await;
+        assert!(matches!(result, Err(TaskError::NotFound { .. })));
+    }
+
     // ---- Cleanup expired test ----
 
     #[tokio::test]
@@ -1083,6 +1595,217 @@ mod tests {
         assert_eq!(removed, 1);
     }
 
+    // ---- Retention tests ----
+
+    #[derive(Debug, Default)]
+    struct RecordingArchiver {
+        fail: bool,
+        archived: parking_lot::Mutex<Vec<(String, RemovalReason)>>,
+    }
+
+    #[async_trait]
+    impl TaskArchiver for RecordingArchiver {
+        async fn archive(
+            &self,
+            record: &TaskRecord,
+            reason: RemovalReason,
+        ) -> Result<(), TaskError> {
+            if self.fail {
+                return Err(TaskError::StoreError("archive offline".to_string()));
+            }
+            self.archived
+                .lock()
+                .push((record.task.task_id.clone(), reason));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn create_evicts_oldest_terminal_task_at_retention_cap() {
+        let archiver = Arc::new(RecordingArchiver::default());
+        let store = test_store()
+            .with_retention(RetentionPolicy::default().with_max_tasks_per_owner(2))
+            .with_archiver(archiver.clone());
+
+        let first = store.create("owner-1", "tools/call", None).await.unwrap();
+        let second = store.create("owner-1", "tools/call", None).await.unwrap();
+        store
+            .update_status(&first.task.task_id, "owner-1", TaskStatus::Completed, None)
+            .await
+            .unwrap();
+
+        store.create("owner-1", "tools/call", None).await.unwrap();
+        assert!(matches!(
+            store.get(&first.task.task_id, "owner-1").await,
+            Err(TaskError::NotFound { .. })
+        ));
+        assert_eq!(
+            *archiver.archived.lock(),
+            vec![(first.task.task_id.clone(), RemovalReason::Evicted)]
+        );
+
+        // Working tasks are never evicted; the security limit still governs
+        store.create("owner-1", "tools/call", None).await.unwrap();
+        assert!(store.get(&second.task.task_id, "owner-1").await.is_ok());
+
+        let stats = store.cleanup_stats();
+        assert_eq!(stats.evicted, 1);
+        assert_eq!(stats.archived, 1);
+    }
+
+    #[tokio::test]
+    async fn cleanup_removes_terminal_tasks_past_retention() {
+        let archiver = Arc::new(RecordingArchiver::default());
+        let store = test_store()
+            .with_retention(
+                RetentionPolicy::default().with_terminal_retention(std::time::Duration::ZERO),
+            )
+            .with_archiver(archiver.clone());
+
+        let done = store.create("owner-1", "tools/call", None).await.unwrap();
+        let working = store.create("owner-1", "tools/call", None).await.unwrap();
+        store
+            .update_status(&done.task.task_id, "owner-1", TaskStatus::Failed, None)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        assert_eq!(store.cleanup_expired().await.unwrap(), 1);
+        assert!(store.get(&working.task.task_id, "owner-1").await.is_ok());
+        assert_eq!(
+            *archiver.archived.lock(),
+            vec![(done.task.task_id.clone(), RemovalReason::RetentionElapsed)]
+        );
+
+        let stats = store.cleanup_stats();
+        assert_eq!(stats.runs, 1);
+        assert_eq!(stats.retention_elapsed, 1);
+        assert_eq!(stats.removed(), 1);
+    }
+
+    #[tokio::test]
+    async fn cleanup_archives_expired_tasks_without_retention() {
+        let archiver = Arc::new(RecordingArchiver::default());
+        let store = test_store().with_archiver(archiver.clone());
+
+        let expired = store
+            .create("owner-1", "tools/call", Some(60_000))
+            .await
+            .unwrap();
+        let live = store.create("owner-1", "tools/call", None).await.unwrap();
+        let key = make_key("owner-1", &expired.task.task_id);
+        let mut record = expired.clone();
+        record.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        store
+            .backend()
+            .put(&key, &serde_json::to_vec(&record).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(store.cleanup_expired().await.unwrap(), 1);
+        assert_eq!(
+            *archiver.archived.lock(),
+            vec![(expired.task.task_id.clone(), RemovalReason::Expired)]
+        );
+        assert!(store.get(&live.task.task_id, "owner-1").await.is_ok());
+        assert_eq!(store.cleanup_stats().expired, 1);
+    }
+
+    #[tokio::test]
+    async fn failed_archive_keeps_task() {
+        let store = test_store()
+            .with_retention(
+                RetentionPolicy::default().with_terminal_retention(std::time::Duration::ZERO),
+            )
+            .with_archiver(Arc::new(RecordingArchiver {
+                fail: true,
+                ..RecordingArchiver::default()
+            }));
+
+        let done = store.create("owner-1", "tools/call", None).await.unwrap();
+        store.cancel(&done.task.task_id, "owner-1").await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        assert_eq!(store.cleanup_expired().await.unwrap(), 0);
+        assert!(store.get(&done.task.task_id, "owner-1").await.is_ok());
+        let stats = store.cleanup_stats();
+        assert_eq!(stats.archive_failures, 1);
+        assert_eq!(stats.removed(), 0);
+    }
+
+    // ---- Metrics tests ----
+
+    #[derive(Default)]
+    struct RecordingBackend {
+        metrics: parking_lot::Mutex<Vec<pmcp::server::observability::McpMetric>>,
+    }
+
+    #[async_trait]
+    impl ObservabilityBackend for RecordingBackend {
+        async fn record_request(&self, _event: &pmcp::server::observability::McpRequestEvent) {}
+        async fn record_response(&self, _event: &pmcp::server::observability::McpResponseEvent) {}
+        async fn emit_metric(&self, metric: &pmcp::server::observability::McpMetric) {
+            self.metrics.lock().push(metric.clone());
+        }
+        async fn flush(&self) {}
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+    }
+
+    #[tokio::test]
+    async fn lifecycle_emits_task_metrics() {
+        use crate::store::metrics::TaskMetrics;
+
+        let backend = Arc::new(RecordingBackend::default());
+        let store = test_store()
+            .with_observability(backend.clone())
+            .with_metric_dimension("ServerName", "reports");
+
+        let task = store.create("owner-1", "tools/call", None).await.unwrap();
+        let id = task.task.task_id.as_str();
+        store
+            .set_variables(id, "owner-1", HashMap::from([("k".into(), json!("v"))]))
+            .await
+            .unwrap();
+        store
+            .update_status(id, "owner-1", TaskStatus::InputRequired, None)
+            .await
+            .unwrap();
+        store
+            .update_status(id, "owner-1", TaskStatus::Working, None)
+            .await
+            .unwrap();
+        store
+            .complete_with_result(id, "owner-1", TaskStatus::Failed, None, json!(null))
+            .await
+            .unwrap();
+        // Rejected transitions emit nothing
+        assert!(store.cancel(id, "owner-1").await.is_err());
+
+        let metrics = backend.metrics.lock();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                TaskMetrics::CREATED,
+                TaskMetrics::VARIABLES_SIZE,
+                TaskMetrics::TIME_IN_STATE,
+                TaskMetrics::INPUT_REQUIRED,
+                TaskMetrics::TIME_IN_STATE,
+                TaskMetrics::TIME_IN_STATE,
+                TaskMetrics::FAILED,
+            ]
+        );
+        assert!(metrics
+            .iter()
+            .all(|m| m.dimensions["Method"] == "tools/call"
+                && m.dimensions["ServerName"] == "reports"));
+        assert_eq!(metrics[1].value, 9.0); // {"k":"v"}
+        assert_eq!(metrics[4].dimensions["State"], "input_required");
+        assert_eq!(metrics[4].dimensions["NextState"], "working");
+    }
+
     // ---- Serialization round-trip test ----
 
     #[tokio::test]
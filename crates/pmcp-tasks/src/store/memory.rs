@@ -38,9 +38,11 @@
 //! ```
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use dashmap::DashMap;
+use pmcp::server::observability::ObservabilityBackend;
 use serde_json::Value;
 
 use crate::domain::TaskRecord;
@@ -48,9 +50,12 @@ use crate::error::TaskError;
 use crate::security::TaskSecurityConfig;
 use crate::store::backend::{StorageBackend, StorageError, VersionedRecord};
 use crate::store::generic::GenericTaskStore;
+use crate::store::retention::{CleanupStats, RetentionPolicy, TaskArchiver};
 use crate::types::task::TaskStatus;
 
-use super::{ListTasksOptions, StoreConfig, TaskPage, TaskStore};
+use super::{
+    AdminListOptions, ListTasksOptions, StoreConfig, TaskPage, TaskStore, TenantTaskCounts,
+};
 
 // ---- InMemoryBackend: dumb KV store using DashMap ----
 
@@ -203,6 +208,32 @@ impl StorageBackend for InMemoryBackend {
         Ok(results)
     }
 
+    async fn list_all(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        self.list_by_prefix("").await
+    }
+
+    async fn list_expired(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let results = self
+            .data
+            .iter()
+            .filter(|entry| {
+                serde_json::from_slice::<TaskRecord>(&entry.value().0)
+                    .is_ok_and(|record| record.is_expired())
+            })
+            .map(|entry| {
+                let (data, version) = entry.value();
+                (
+                    entry.key().clone(),
+                    VersionedRecord {
+                        data: data.clone(),
+                        version: *version,
+                    },
+                )
+            })
+            .collect();
+        Ok(results)
+    }
+
     async fn cleanup_expired(&self) -> Result<usize, StorageError> {
         let keys_to_remove: Vec<String> = self
             .data
@@ -326,6 +357,51 @@ impl InMemoryTaskStore {
         self
     }
 
+    /// Sets the retention policy applied on top of TTL expiry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pmcp_tasks::store::memory::InMemoryTaskStore;
+    /// use pmcp_tasks::store::retention::RetentionPolicy;
+    ///
+    /// let store = InMemoryTaskStore::new()
+    ///     .with_retention(RetentionPolicy::default().with_max_tasks_per_owner(20));
+    /// ```
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.inner = self.inner.with_retention(retention);
+        self
+    }
+
+    /// Sets the hook that archives tasks before they are deleted.
+    pub fn with_archiver(mut self, archiver: Arc<dyn TaskArchiver>) -> Self {
+        self.inner = self.inner.with_archiver(archiver);
+        self
+    }
+
+    /// Emits task lifecycle metrics to `backend`.
+    ///
+    /// See [`metrics`](crate::store::metrics) for the metrics emitted.
+    pub fn with_observability(mut self, backend: Arc<dyn ObservabilityBackend>) -> Self {
+        self.inner = self.inner.with_observability(backend);
+        self
+    }
+
+    /// Adds a dimension (e.g. `ServerName`) to every task metric.
+    pub fn with_metric_dimension(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.inner = self.inner.with_metric_dimension(key, value);
+        self
+    }
+
+    /// Returns counters describing cleanup activity so far.
+    pub fn cleanup_stats(&self) -> CleanupStats {
+        self.inner.cleanup_stats()
+    }
+
     /// Returns a reference to the underlying backend.
     ///
     /// Useful for test code that needs to inspect backend state (e.g., record
@@ -418,6 +494,27 @@ impl TaskStore for InMemoryTaskStore {
         self.inner.cleanup_expired().await
     }
 
+    async fn list_all(
+        &self,
+        admin_id: &str,
+        options: AdminListOptions,
+    ) -> Result<TaskPage, TaskError> {
+        self.inner.list_all(admin_id, options).await
+    }
+
+    async fn tenant_counts(&self, admin_id: &str) -> Result<Vec<TenantTaskCounts>, TaskError> {
+        self.inner.tenant_counts(admin_id).await
+    }
+
+    async fn admin_cancel(
+        &self,
+        admin_id: &str,
+        task_id: &str,
+        owner_id: &str,
+    ) -> Result<TaskRecord, TaskError> {
+        self.inner.admin_cancel(admin_id, task_id, owner_id).await
+    }
+
     fn config(&self) -> &StoreConfig {
         self.inner.config()
     }
@@ -0,0 +1,169 @@
+//! Task lifecycle metrics emitted through pmcp's observability backends.
+//!
+//! A [`GenericTaskStore`](crate::store::generic::GenericTaskStore) configured
+//! with [`with_observability`](crate::store::generic::GenericTaskStore::with_observability)
+//! emits an [`McpMetric`] for every lifecycle event, so the same
+//! [`ObservabilityBackend`] that records requests (console, `CloudWatch`, or a
+//! custom backend) also covers tasks:
+//!
+//! | Metric                      | Unit         | When                                  |
+//! |-----------------------------|--------------|---------------------------------------|
+//! | `mcp.tasks.created`         | Count        | A task is created                     |
+//! | `mcp.tasks.completed`       | Count        | A task reaches `completed`            |
+//! | `mcp.tasks.failed`          | Count        | A task reaches `failed`               |
+//! | `mcp.tasks.cancelled`       | Count        | A task reaches `cancelled`            |
+//! | `mcp.tasks.input_required`  | Count        | A task moves to `input_required`      |
+//! | `mcp.tasks.time_in_state`   | Milliseconds | A task leaves a status                |
+//! | `mcp.tasks.variables.size`  | Bytes        | A task's variables are written        |
+//!
+//! Every metric carries a `Method` dimension (the request method that
+//! created the task). `mcp.tasks.time_in_state` adds `State` (the status
+//! being left) and `NextState`. Owner and task IDs are never used as
+//! dimensions, to keep metric cardinality bounded.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//!
+//! use pmcp::server::observability::{CloudWatchBackend, CloudWatchConfig};
+//! use pmcp_tasks::store::memory::InMemoryTaskStore;
+//!
+//! let backend = Arc::new(CloudWatchBackend::new(CloudWatchConfig::default()));
+//! let store = InMemoryTaskStore::new()
+//!     .with_observability(backend)
+//!     .with_metric_dimension("ServerName", "reports");
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Duration;
+use pmcp::server::observability::{McpMetric, MetricUnit, ObservabilityBackend};
+
+use crate::domain::TaskRecord;
+use crate::types::task::TaskStatus;
+
+/// Names of the task metrics.
+#[derive(Debug, Clone, Copy)]
+pub struct TaskMetrics;
+
+impl TaskMetrics {
+    /// Metric name for created tasks.
+    pub const CREATED: &'static str = "mcp.tasks.created";
+
+    /// Metric name for tasks that completed.
+    pub const COMPLETED: &'static str = "mcp.tasks.completed";
+
+    /// Metric name for tasks that failed.
+    pub const FAILED: &'static str = "mcp.tasks.failed";
+
+    /// Metric name for tasks that were cancelled.
+    pub const CANCELLED: &'static str = "mcp.tasks.cancelled";
+
+    /// Metric name for tasks that started waiting for input.
+    pub const INPUT_REQUIRED: &'static str = "mcp.tasks.input_required";
+
+    /// Metric name for the time a task spent in a status.
+    pub const TIME_IN_STATE: &'static str = "mcp.tasks.time_in_state";
+
+    /// Metric name for the serialized size of a task's variables.
+    pub const VARIABLES_SIZE: &'static str = "mcp.tasks.variables.size";
+
+    /// Returns the count metric for entering `status`, if it has one.
+    pub fn for_status(status: TaskStatus) -> Option<&'static str> {
+        match status {
+            TaskStatus::Completed => Some(Self::COMPLETED),
+            TaskStatus::Failed => Some(Self::FAILED),
+            TaskStatus::Cancelled => Some(Self::CANCELLED),
+            TaskStatus::InputRequired => Some(Self::INPUT_REQUIRED),
+            TaskStatus::Working => None,
+        }
+    }
+}
+
+/// Emits task metrics to an observability backend.
+#[derive(Clone)]
+pub(crate) struct TaskMetricsRecorder {
+    backend: Arc<dyn ObservabilityBackend>,
+    dimensions: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for TaskMetricsRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskMetricsRecorder")
+            .field("backend", &self.backend.name())
+            .field("dimensions", &self.dimensions)
+            .finish()
+    }
+}
+
+impl TaskMetricsRecorder {
+    pub(crate) fn new(backend: Arc<dyn ObservabilityBackend>) -> Self {
+        Self {
+            backend,
+            dimensions: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn add_dimension(&mut self, key: String, value: String) {
+        self.dimensions.insert(key, value);
+    }
+
+    fn metric(&self, name: &str, value: f64, unit: MetricUnit, record: &TaskRecord) -> McpMetric {
+        McpMetric::new(name, value, unit)
+            .with_dimensions(self.dimensions.clone())
+            .with_dimension("Method", record.request_method.clone())
+    }
+
+    async fn emit(&self, metric: McpMetric) {
+        if self.backend.is_enabled() {
+            self.backend.emit_metric(&metric).await;
+        }
+    }
+
+    /// Records a newly created task.
+    pub(crate) async fn record_created(&self, record: &TaskRecord) {
+        self.emit(self.metric(TaskMetrics::CREATED, 1.0, MetricUnit::Count, record))
+            .await;
+    }
+
+    /// Records a status transition of `record` (already in its new status)
+    /// out of `from`, where it spent `time_in_state`.
+    #[allow(clippy::cast_precision_loss)] // millisecond durations fit comfortably in f64
+    pub(crate) async fn record_transition(
+        &self,
+        record: &TaskRecord,
+        from: TaskStatus,
+        time_in_state: Option<Duration>,
+    ) {
+        if let Some(elapsed) = time_in_state {
+            let millis = elapsed.num_milliseconds().max(0);
+            let metric = self
+                .metric(
+                    TaskMetrics::TIME_IN_STATE,
+                    millis as f64,
+                    MetricUnit::Milliseconds,
+                    record,
+                )
+                .with_dimension("State", from.to_string())
+                .with_dimension("NextState", record.task.status.to_string());
+            self.emit(metric).await;
+        }
+
+        if let Some(name) = TaskMetrics::for_status(record.task.status) {
+            self.emit(self.metric(name, 1.0, MetricUnit::Count, record))
+                .await;
+        }
+    }
+
+    /// Records the serialized size of a task's variables after a write.
+    #[allow(clippy::cast_precision_loss)] // variable payloads are capped far below 2^53 bytes
+    pub(crate) async fn record_variables_size(&self, record: &TaskRecord, bytes: usize) {
+        self.emit(self.metric(
+            TaskMetrics::VARIABLES_SIZE,
+            bytes as f64,
+            MetricUnit::Bytes,
+            record,
+        ))
+        .await;
+    }
+}
@@ -13,7 +13,7 @@
 //!    mutations, canonical serialization). Has a blanket `TaskStore` impl.
 //!
 //! 3. **[`StorageBackend`]** -- Dumb KV trait that backends implement
-//!    (in-memory, DynamoDB, Redis). No domain logic.
+//!    (in-memory, DynamoDB, Redis, SQL). No domain logic.
 //!
 //! To create a store: `GenericTaskStore::new(backend)` and wrap in
 //! `Arc<dyn TaskStore>` for use with `TaskContext` and `TaskRouterImpl`.
@@ -27,20 +27,32 @@
 //!   deployments. Available behind the `dynamodb` feature flag.
 //! - `RedisBackend` -- Redis backend for long-running server deployments.
 //!   Available behind the `redis` feature flag.
+//! - `SqlBackend` -- SQLite or Postgres backend for self-hosted deployments,
+//!   with schema migration helpers. Available behind the `sql` feature flag.
 //!
 //! # Supporting Types
 //!
 //! - [`StoreConfig`] - Configurable limits for variable size and TTL.
 //! - [`ListTasksOptions`] - Parameters for cursor-based task listing.
 //! - [`TaskPage`] - A page of task results with optional next cursor.
+//! - [`AdminListOptions`] - Filters for the admin cross-owner listing.
+//! - [`TenantTaskCounts`] - Per-owner task counts for operators.
+//!
+//! Retention beyond TTL expiry (per-owner caps, terminal retention windows,
+//! archive-before-delete hooks, cleanup metrics) lives in [`retention`].
+//! Task lifecycle metrics for observability backends live in [`metrics`].
 
 pub mod backend;
 #[cfg(feature = "dynamodb")]
 pub mod dynamodb;
 pub mod generic;
 pub mod memory;
+pub mod metrics;
 #[cfg(feature = "redis")]
 pub mod redis;
+pub mod retention;
+#[cfg(feature = "sql")]
+pub mod sql;
 
 use std::collections::HashMap;
 
@@ -191,6 +203,61 @@ pub struct TaskPage {
     pub next_cursor: Option<String>,
 }
 
+/// Options for the admin cross-owner listing
+/// ([`TaskStore::list_all`]).
+///
+/// Unlike [`ListTasksOptions`], the owner is an optional filter rather than
+/// a scope.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::store::AdminListOptions;
+/// use pmcp_tasks::TaskStatus;
+///
+/// // Every working task, across all owners
+/// let options = AdminListOptions {
+///     status: Some(TaskStatus::Working),
+///     ..AdminListOptions::default()
+/// };
+/// assert!(options.owner_id.is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AdminListOptions {
+    /// Only include tasks owned by this owner.
+    pub owner_id: Option<String>,
+
+    /// Only include tasks in this status.
+    pub status: Option<TaskStatus>,
+
+    /// Opaque cursor for pagination. `None` for the first page.
+    pub cursor: Option<String>,
+
+    /// Maximum number of tasks to return. `None` uses the store default.
+    pub limit: Option<usize>,
+}
+
+/// Task counts for a single owner (tenant), as returned by
+/// [`TaskStore::tenant_counts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantTaskCounts {
+    /// The owner these counts belong to.
+    pub owner_id: String,
+
+    /// Total stored tasks, including expired ones not yet cleaned up.
+    pub total: usize,
+
+    /// Unexpired tasks in a non-terminal status -- the candidates for
+    /// "stuck" tasks.
+    pub active: usize,
+
+    /// Tasks past their TTL.
+    pub expired: usize,
+
+    /// Tasks per status.
+    pub by_status: HashMap<TaskStatus, usize>,
+}
+
 /// Type-erasure interface for task storage.
 ///
 /// This trait serves as the dynamic dispatch interface for
@@ -404,6 +471,65 @@ pub trait TaskStore: Send + Sync {
     /// - [`TaskError::StoreError`] on backend failures.
     async fn cleanup_expired(&self) -> Result<usize, TaskError>;
 
+    /// Lists tasks across all owners (admin only).
+    ///
+    /// `admin_id` is the caller's resolved owner ID; it must hold the admin
+    /// capability ([`TaskSecurityConfig::is_admin`](crate::security::TaskSecurityConfig::is_admin)).
+    /// Results are ordered by creation time (newest first).
+    ///
+    /// The default implementation rejects every caller, so stores that do
+    /// not support admin access stay closed.
+    ///
+    /// # Errors
+    ///
+    /// - [`TaskError::PermissionDenied`] if `admin_id` is not an admin.
+    /// - [`TaskError::StoreError`] on backend failures, or if the backend
+    ///   cannot enumerate across owners.
+    async fn list_all(
+        &self,
+        admin_id: &str,
+        options: AdminListOptions,
+    ) -> Result<TaskPage, TaskError> {
+        let _ = (admin_id, options);
+        Err(TaskError::PermissionDenied {
+            operation: "list_all".to_string(),
+        })
+    }
+
+    /// Returns task counts per owner (admin only), sorted by owner ID.
+    ///
+    /// # Errors
+    ///
+    /// - [`TaskError::PermissionDenied`] if `admin_id` is not an admin.
+    /// - [`TaskError::StoreError`] on backend failures.
+    async fn tenant_counts(&self, admin_id: &str) -> Result<Vec<TenantTaskCounts>, TaskError> {
+        let _ = admin_id;
+        Err(TaskError::PermissionDenied {
+            operation: "tenant_counts".to_string(),
+        })
+    }
+
+    /// Cancels another owner's task (admin only).
+    ///
+    /// Same state machine rules as [`cancel`](TaskStore::cancel); only the
+    /// owner check is replaced by the admin capability check.
+    ///
+    /// # Errors
+    ///
+    /// - [`TaskError::PermissionDenied`] if `admin_id` is not an admin.
+    /// - Any error [`cancel`](TaskStore::cancel) can return.
+    async fn admin_cancel(
+        &self,
+        admin_id: &str,
+        task_id: &str,
+        owner_id: &str,
+    ) -> Result<TaskRecord, TaskError> {
+        let _ = (admin_id, task_id, owner_id);
+        Err(TaskError::PermissionDenied {
+            operation: "admin_cancel".to_string(),
+        })
+    }
+
     /// Returns a reference to the store's configuration.
     ///
     /// This method is synchronous (not async) since it returns a reference
@@ -485,6 +611,27 @@ impl<B: StorageBackend + 'static> TaskStore for generic::GenericTaskStore<B> {
         self.cleanup_expired().await
     }
 
+    async fn list_all(
+        &self,
+        admin_id: &str,
+        options: AdminListOptions,
+    ) -> Result<TaskPage, TaskError> {
+        self.list_all(admin_id, options).await
+    }
+
+    async fn tenant_counts(&self, admin_id: &str) -> Result<Vec<TenantTaskCounts>, TaskError> {
+        self.tenant_counts(admin_id).await
+    }
+
+    async fn admin_cancel(
+        &self,
+        admin_id: &str,
+        task_id: &str,
+        owner_id: &str,
+    ) -> Result<TaskRecord, TaskError> {
+        self.admin_cancel(admin_id, task_id, owner_id).await
+    }
+
     fn config(&self) -> &StoreConfig {
         self.config()
     }
@@ -1,8 +1,8 @@
 //! Redis storage backend for task persistence.
 //!
 //! [`RedisBackend`] implements [`StorageBackend`] using Redis as the underlying
-//! key-value store. It maps the 6 trait methods to Redis operations: `HGETALL`
-//! for reads, and Lua scripts (`redis::Script`) for atomic writes that update
+//! key-value store. It maps the trait methods to Redis operations: `HGETALL`
+//! for reads, `SCAN` over owner indexes for the admin `list_all`, and Lua scripts (`redis::Script`) for atomic writes that update
 //! hash fields, sorted set indexes, and TTL in a single round-trip.
 //!
 //! # Key Schema
@@ -556,6 +556,48 @@ impl StorageBackend for RedisBackend {
         Ok(results)
     }
 
+    /// Enumerates owner indexes with `SCAN` and lists each owner's tasks.
+    ///
+    /// `SCAN` is non-blocking but walks the whole keyspace, so this is meant
+    /// for occasional admin inspection only.
+    async fn list_all(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let idx_prefix = format!("{}:idx:", self.key_prefix);
+        let pattern = format!("{idx_prefix}*");
+        let mut conn = self.conn.clone();
+        let mut cursor: u64 = 0;
+        let mut owners = Vec::new();
+
+        loop {
+            let (next, keys): (u64, Vec<String>) = ::redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| map_redis_error(e, &pattern))?;
+            owners.extend(
+                keys.iter()
+                    .filter_map(|k| k.strip_prefix(&idx_prefix))
+                    .map(String::from),
+            );
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        owners.sort();
+        owners.dedup();
+
+        let mut results = Vec::new();
+        for owner_id in owners {
+            results.extend(self.list_by_prefix(&format!("{owner_id}:")).await?);
+        }
+        Ok(results)
+    }
+
     /// No-op for Redis: `EXPIRE`/`EXPIREAT` handles expired key cleanup
     /// automatically.
     ///
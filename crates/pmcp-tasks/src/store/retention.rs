@@ -0,0 +1,372 @@
+//! Retention policies, archival hooks, and cleanup metrics.
+//!
+//! TTL expiry alone keeps every finished task around until its TTL runs out.
+//! A [`RetentionPolicy`] adds two more ways for
+//! [`GenericTaskStore`](crate::store::generic::GenericTaskStore) to reclaim
+//! space:
+//!
+//! - **Per-owner cap** -- keep at most N tasks per owner. When an owner hits
+//!   the cap, their oldest terminal tasks are evicted to make room; working
+//!   tasks are never evicted.
+//! - **Terminal retention** -- remove terminal tasks a fixed time after they
+//!   finished, even if their TTL has not elapsed.
+//!
+//! A [`TaskArchiver`] is called with each task before the store deletes it,
+//! so finished work can be kept outside the store (S3, a data warehouse, the
+//! local filesystem via [`FileSystemArchiver`]). If archiving fails the task
+//! is kept and retried on the next cleanup run.
+//!
+//! Every cleanup run updates [`CleanupStats`], available from
+//! [`GenericTaskStore::cleanup_stats`](crate::store::generic::GenericTaskStore::cleanup_stats).
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! use pmcp_tasks::store::generic::GenericTaskStore;
+//! use pmcp_tasks::store::memory::InMemoryBackend;
+//! use pmcp_tasks::store::retention::{FileSystemArchiver, RetentionPolicy};
+//!
+//! let store = GenericTaskStore::new(InMemoryBackend::new())
+//!     .with_retention(
+//!         RetentionPolicy::default()
+//!             .with_max_tasks_per_owner(20)
+//!             .with_terminal_retention(Duration::from_secs(600)),
+//!     )
+//!     .with_archiver(Arc::new(FileSystemArchiver::new("/var/lib/mcp/task-archive")));
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::TaskRecord;
+use crate::error::TaskError;
+
+/// Retention rules applied on top of TTL expiry.
+///
+/// The default policy adds nothing: tasks are only removed once their TTL
+/// has elapsed.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use pmcp_tasks::store::retention::RetentionPolicy;
+///
+/// let policy = RetentionPolicy::default()
+///     .with_max_tasks_per_owner(10)
+///     .with_terminal_retention(Duration::from_secs(300));
+/// assert_eq!(policy.max_tasks_per_owner, Some(10));
+/// assert!(!RetentionPolicy::default().is_active());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Maximum tasks kept per owner. Oldest terminal tasks beyond this are
+    /// evicted. `None` disables eviction.
+    pub max_tasks_per_owner: Option<usize>,
+
+    /// How long a terminal task is kept after its last update. `None` keeps
+    /// terminal tasks until their TTL elapses.
+    pub terminal_retention: Option<Duration>,
+}
+
+impl RetentionPolicy {
+    /// Sets the per-owner task cap.
+    pub fn with_max_tasks_per_owner(mut self, max: usize) -> Self {
+        self.max_tasks_per_owner = Some(max);
+        self
+    }
+
+    /// Sets how long terminal tasks are kept after they finish.
+    pub fn with_terminal_retention(mut self, retention: Duration) -> Self {
+        self.terminal_retention = Some(retention);
+        self
+    }
+
+    /// Returns `true` if the policy adds any rule beyond TTL expiry.
+    pub fn is_active(&self) -> bool {
+        self.max_tasks_per_owner.is_some() || self.terminal_retention.is_some()
+    }
+
+    /// Returns `true` if `record` is terminal and older than the terminal
+    /// retention window at `now`.
+    pub fn terminal_retention_elapsed(&self, record: &TaskRecord, now: DateTime<Utc>) -> bool {
+        let Some(retention) = self.terminal_retention else {
+            return false;
+        };
+        if !record.task.status.is_terminal() {
+            return false;
+        }
+        let Ok(updated) = DateTime::parse_from_rfc3339(&record.task.last_updated_at) else {
+            return false;
+        };
+        let retention = chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX);
+        updated
+            .with_timezone(&Utc)
+            .checked_add_signed(retention)
+            .is_some_and(|deadline| now > deadline)
+    }
+}
+
+/// Why a task is being removed from the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    /// The task's TTL elapsed.
+    Expired,
+    /// The task was terminal for longer than the terminal retention window.
+    RetentionElapsed,
+    /// The task was evicted to keep its owner under the per-owner cap.
+    Evicted,
+}
+
+impl RemovalReason {
+    /// Returns the reason as a lowercase string, e.g. for file names or logs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Expired => "expired",
+            Self::RetentionElapsed => "retention_elapsed",
+            Self::Evicted => "evicted",
+        }
+    }
+}
+
+/// Hook called with each task before the store deletes it.
+///
+/// Returning an error keeps the task in the store; cleanup retries it on
+/// the next run.
+#[async_trait]
+pub trait TaskArchiver: Send + Sync + std::fmt::Debug {
+    /// Archives `record`, which is about to be removed for `reason`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record could not be archived.
+    async fn archive(&self, record: &TaskRecord, reason: RemovalReason) -> Result<(), TaskError>;
+}
+
+/// Archiver that writes each task as a JSON file under a directory.
+///
+/// Files are written to `{dir}/{owner}/{task_id}.json`. The owner ID is
+/// percent-encoded: every byte outside `[A-Za-z0-9_-]`, including `.` and
+/// `%`, becomes `%XX`, so distinct owners never share a directory and no
+/// owner can name `.` or `..`. An empty owner ID is stored under `%`.
+#[derive(Debug, Clone)]
+pub struct FileSystemArchiver {
+    dir: PathBuf,
+}
+
+impl FileSystemArchiver {
+    /// Creates an archiver writing under `dir`. The directory is created on
+    /// first use.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the archive directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the file path a record is archived to.
+    pub fn path_for(&self, record: &TaskRecord) -> PathBuf {
+        self.dir
+            .join(encode_owner(&record.owner_id))
+            .join(format!("{}.json", record.task.task_id))
+    }
+}
+
+/// Percent-encodes an owner ID into a single, never dot-only path component.
+fn encode_owner(owner_id: &str) -> String {
+    if owner_id.is_empty() {
+        return "%".to_string();
+    }
+    let mut encoded = String::with_capacity(owner_id.len());
+    for byte in owner_id.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[async_trait]
+impl TaskArchiver for FileSystemArchiver {
+    async fn archive(&self, record: &TaskRecord, reason: RemovalReason) -> Result<(), TaskError> {
+        let path = self.path_for(record);
+        let mut entry = serde_json::to_value(record)
+            .map_err(|e| TaskError::StoreError(format!("failed to serialize task: {e}")))?;
+        entry["archiveReason"] = serde_json::Value::from(reason.as_str());
+        let bytes = serde_json::to_vec_pretty(&entry)
+            .map_err(|e| TaskError::StoreError(format!("failed to serialize task: {e}")))?;
+
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, bytes)
+        })
+        .await
+        .map_err(|e| TaskError::StoreError(format!("archive task panicked: {e}")))?
+        .map_err(|e| TaskError::StoreError(format!("failed to archive task: {e}")))
+    }
+}
+
+/// Counters describing cleanup activity since the store was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanupStats {
+    /// Number of cleanup runs.
+    pub runs: u64,
+    /// Tasks removed because their TTL elapsed.
+    pub expired: u64,
+    /// Tasks removed because their terminal retention window elapsed.
+    pub retention_elapsed: u64,
+    /// Tasks evicted to enforce the per-owner cap.
+    pub evicted: u64,
+    /// Tasks successfully archived before removal.
+    pub archived: u64,
+    /// Archive attempts that failed (the task was kept).
+    pub archive_failures: u64,
+}
+
+impl CleanupStats {
+    /// Total number of tasks removed.
+    pub fn removed(&self) -> u64 {
+        self.expired + self.retention_elapsed + self.evicted
+    }
+}
+
+/// Lock-free cleanup counters shared by a store.
+#[derive(Debug, Default)]
+pub(crate) struct CleanupMetrics {
+    runs: AtomicU64,
+    expired: AtomicU64,
+    retention_elapsed: AtomicU64,
+    evicted: AtomicU64,
+    archived: AtomicU64,
+    archive_failures: AtomicU64,
+}
+
+impl CleanupMetrics {
+    pub(crate) fn record_run(&self) {
+        self.runs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_removed(&self, reason: RemovalReason, count: u64) {
+        let counter = match reason {
+            RemovalReason::Expired => &self.expired,
+            RemovalReason::RetentionElapsed => &self.retention_elapsed,
+            RemovalReason::Evicted => &self.evicted,
+        };
+        counter.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_archived(&self) {
+        self.archived.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_archive_failure(&self) {
+        self.archive_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> CleanupStats {
+        CleanupStats {
+            runs: self.runs.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            retention_elapsed: self.retention_elapsed.load(Ordering::Relaxed),
+            evicted: self.evicted.load(Ordering::Relaxed),
+            archived: self.archived.load(Ordering::Relaxed),
+            archive_failures: self.archive_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::task::TaskStatus;
+    use std::path::Component;
+
+    fn finished(minutes_ago: i64) -> TaskRecord {
+        let mut record = TaskRecord::new("owner".into(), "tools/call".into(), None);
+        record.task.status = TaskStatus::Completed;
+        record.task.last_updated_at = (Utc::now() - chrono::Duration::minutes(minutes_ago))
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        record
+    }
+
+    #[test]
+    fn terminal_retention_only_applies_to_old_terminal_tasks() {
+        let policy = RetentionPolicy::default().with_terminal_retention(Duration::from_secs(600));
+        let now = Utc::now();
+
+        assert!(policy.terminal_retention_elapsed(&finished(30), now));
+        assert!(!policy.terminal_retention_elapsed(&finished(5), now));
+
+        let mut working = finished(30);
+        working.task.status = TaskStatus::Working;
+        assert!(!policy.terminal_retention_elapsed(&working, now));
+        assert!(!RetentionPolicy::default().terminal_retention_elapsed(&finished(30), now));
+    }
+
+    #[tokio::test]
+    async fn filesystem_archiver_writes_encoded_path() {
+        let dir = std::env::temp_dir().join(format!("pmcp-archive-{}", uuid::Uuid::new_v4()));
+        let archiver = FileSystemArchiver::new(&dir);
+        let mut record = finished(1);
+        record.owner_id = "oauth:user/1".into();
+
+        archiver
+            .archive(&record, RemovalReason::Evicted)
+            .await
+            .unwrap();
+
+        let path = dir
+            .join("oauth%3Auser%2F1")
+            .join(format!("{}.json", record.task.task_id));
+        assert_eq!(archiver.path_for(&record), path);
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written["archiveReason"], "evicted");
+        assert_eq!(written["ownerId"], "oauth:user/1");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn filesystem_archiver_stays_inside_dir() {
+        let archiver = FileSystemArchiver::new("/archive");
+        let mut record = finished(1);
+        let file = format!("{}.json", record.task.task_id);
+
+        for (owner, component) in [("..", "%2E%2E"), (".", "%2E"), ("", "%")] {
+            record.owner_id = owner.into();
+            let path = archiver.path_for(&record);
+            assert_eq!(path, Path::new("/archive").join(component).join(&file));
+            assert!(path
+                .components()
+                .all(|c| !matches!(c, Component::ParentDir | Component::CurDir)));
+        }
+    }
+
+    #[test]
+    fn filesystem_archiver_keeps_owners_apart() {
+        let archiver = FileSystemArchiver::new("/archive");
+        let owners = ["a/b", "a_b", "a%2Fb", "a.b", "a%2Eb", "A", "a"];
+
+        let dirs: std::collections::HashSet<PathBuf> = owners
+            .iter()
+            .map(|owner| {
+                let mut record = finished(1);
+                record.owner_id = (*owner).into();
+                archiver.path_for(&record).parent().unwrap().to_path_buf()
+            })
+            .collect();
+        assert_eq!(dirs.len(), owners.len());
+    }
+}
@@ -0,0 +1,719 @@
+//! SQL storage backend for task persistence (SQLite and Postgres).
+//!
+//! [`SqlBackend`] implements [`StorageBackend`] over a `sqlx` [`AnyPool`], so
+//! the same backend serves SQLite for single-node deployments and Postgres
+//! for self-hosted clusters. The driver is chosen by the connection URL
+//! (`sqlite://...` or `postgres://...`).
+//!
+//! # Schema
+//!
+//! All records live in one table (default `pmcp_tasks`):
+//!
+//! | Column | Type | Description |
+//! |--------|------|-------------|
+//! | `owner_id` | TEXT | Owner part of the `{owner_id}:{task_id}` key |
+//! | `task_id` | TEXT | Task part of the key |
+//! | `data` | TEXT | Serialized `TaskRecord` JSON blob |
+//! | `version` | BIGINT | Monotonic CAS version, starts at 1 |
+//! | `created_at` | BIGINT | Unix epoch milliseconds, for listing order |
+//! | `expires_at` | BIGINT NULL | Unix epoch seconds (only present with TTL) |
+//!
+//! `(owner_id, task_id)` is the primary key, and `expires_at` is indexed for
+//! [`cleanup_expired`](StorageBackend::cleanup_expired).
+//!
+//! # Migrations
+//!
+//! [`SqlBackend::migrate`] creates the table and indexes, recording applied
+//! migrations in a `{table}_migrations` table so it is safe to call on every
+//! start. Deployments that manage their schema with their own tooling can
+//! take the statements from [`migration_sql`] instead.
+//!
+//! # Relationship to GenericTaskStore
+//!
+//! This backend is a **dumb KV adapter**. It stores and retrieves opaque
+//! JSON blobs. All domain logic -- state machine validation, owner checking,
+//! variable merge, TTL policy -- lives in
+//! [`GenericTaskStore`](crate::store::generic::GenericTaskStore). The backend
+//! never interprets the data it stores, except for extracting the
+//! `expiresAt` field into `expires_at` and `task.createdAt` into
+//! `created_at`.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use pmcp_tasks::store::generic::GenericTaskStore;
+//! use pmcp_tasks::store::sql::SqlBackend;
+//!
+//! # async fn example() {
+//! let backend = SqlBackend::new("postgres://localhost/mcp").await.unwrap();
+//! backend.migrate().await.unwrap();
+//! let store = GenericTaskStore::new(backend);
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use sqlx::any::{AnyPool, AnyPoolOptions};
+use sqlx::Row;
+
+use crate::store::backend::{StorageBackend, StorageError, VersionedRecord};
+
+/// Default table name for task records.
+const DEFAULT_TABLE: &str = "pmcp_tasks";
+
+/// Schema migrations, in order. `{table}` is replaced with the table name.
+///
+/// Each entry is applied once, in its own transaction; append new entries
+/// rather than editing existing ones.
+const MIGRATIONS: &[&[&str]] = &[&[
+    "CREATE TABLE IF NOT EXISTS {table} (
+        owner_id TEXT NOT NULL,
+        task_id TEXT NOT NULL,
+        data TEXT NOT NULL,
+        version BIGINT NOT NULL,
+        created_at BIGINT NOT NULL,
+        expires_at BIGINT,
+        PRIMARY KEY (owner_id, task_id)
+    )",
+    "CREATE INDEX IF NOT EXISTS {table}_expires_at_idx ON {table} (expires_at)",
+    "CREATE INDEX IF NOT EXISTS {table}_owner_created_idx ON {table} (owner_id, created_at)",
+]];
+
+/// Returns every schema statement for a task table named `table`, in order.
+///
+/// Use this to run the migrations with external tooling; [`SqlBackend::migrate`]
+/// applies the same statements and tracks which have run.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::store::sql::migration_sql;
+///
+/// let statements = migration_sql("tasks");
+/// assert!(statements[0].starts_with("CREATE TABLE IF NOT EXISTS tasks"));
+/// ```
+pub fn migration_sql(table: &str) -> Vec<String> {
+    MIGRATIONS
+        .iter()
+        .flat_map(|migration| migration.iter())
+        .map(|statement| statement.replace("{table}", table))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// SqlBackend struct
+// ---------------------------------------------------------------------------
+
+/// SQL storage backend for task persistence.
+///
+/// Stores one row per task with the serialized record, its CAS version and
+/// its expiry. Writes are single statements (`INSERT ... ON CONFLICT` and
+/// conditional `UPDATE ... RETURNING`), so CAS semantics hold across
+/// concurrent servers sharing the database.
+///
+/// This backend is a thin adapter -- it contains **no domain logic**. All
+/// intelligence (state machine validation, owner isolation, variable merge,
+/// TTL enforcement) lives in
+/// [`GenericTaskStore`](crate::store::generic::GenericTaskStore).
+///
+/// # Connection Model
+///
+/// `SqlBackend` holds an [`AnyPool`], which is cheap to clone; all clones
+/// share the same connections.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmcp_tasks::store::generic::GenericTaskStore;
+/// use pmcp_tasks::store::sql::SqlBackend;
+///
+/// # async fn example() {
+/// // SQLite file, created if missing:
+/// let backend = SqlBackend::new("sqlite://tasks.db?mode=rwc").await.unwrap();
+/// backend.migrate().await.unwrap();
+/// let store = GenericTaskStore::new(backend);
+///
+/// // Postgres with a custom table:
+/// let backend = SqlBackend::new("postgres://localhost/mcp")
+///     .await
+///     .unwrap()
+///     .with_table("my_app_tasks");
+/// backend.migrate().await.unwrap();
+/// let store = GenericTaskStore::new(backend);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqlBackend {
+    pool: AnyPool,
+    table: String,
+}
+
+impl SqlBackend {
+    /// Creates a backend by connecting to the database at the given URL.
+    ///
+    /// Supports `sqlite://` and `postgres://` URLs. Uses the default table
+    /// `"pmcp_tasks"`. Fails fast if the connection cannot be established.
+    /// Does not create the schema; call [`migrate`](Self::migrate) for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Backend`] if the URL is not supported or the
+    /// connection cannot be established.
+    pub async fn new(url: &str) -> Result<Self, StorageError> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .connect(url)
+            .await
+            .map_err(|e| StorageError::Backend {
+                message: format!("failed to connect to database: {e}"),
+                source: Some(Box::new(e)),
+            })?;
+        Ok(Self::with_pool(pool))
+    }
+
+    /// Creates a backend from an existing connection pool.
+    ///
+    /// Use this to share a pool with the rest of the application or to
+    /// configure pool limits. Call [`sqlx::any::install_default_drivers`]
+    /// before creating the pool.
+    pub fn with_pool(pool: AnyPool) -> Self {
+        Self {
+            pool,
+            table: DEFAULT_TABLE.to_string(),
+        }
+    }
+
+    /// Sets the table that stores task records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` is not a plain SQL identifier (ASCII letters,
+    /// digits and underscores, not starting with a digit), since it is
+    /// interpolated into every statement.
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        let table = table.into();
+        assert!(
+            is_identifier(&table),
+            "invalid task table name {table:?}: use ASCII letters, digits and underscores"
+        );
+        self.table = table;
+        self
+    }
+
+    /// Applies any schema migrations that have not run yet.
+    ///
+    /// Applied migrations are recorded in `{table}_migrations`, so calling
+    /// this on every start is cheap and safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Backend`] if a statement fails. Migrations
+    /// applied before the failure stay applied.
+    pub async fn migrate(&self) -> Result<(), StorageError> {
+        let history = format!("{}_migrations", self.table);
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {history} (version BIGINT PRIMARY KEY)"
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, &history))?;
+
+        let applied: Option<i64> =
+            sqlx::query_scalar(&format!("SELECT MAX(version) FROM {history}"))
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| map_sql_error(e, &history))?;
+        let applied = usize::try_from(applied.unwrap_or(0)).unwrap_or(0);
+
+        for (index, statements) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let mut tx = self
+                .pool
+                .begin()
+                .await
+                .map_err(|e| map_sql_error(e, &self.table))?;
+            for statement in *statements {
+                sqlx::query(&statement.replace("{table}", &self.table))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| map_sql_error(e, &self.table))?;
+            }
+            sqlx::query(&format!("INSERT INTO {history} (version) VALUES ($1)"))
+                .bind(index as i64 + 1)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| map_sql_error(e, &history))?;
+            tx.commit()
+                .await
+                .map_err(|e| map_sql_error(e, &self.table))?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+impl SqlBackend {
+    /// Lists live records matching an optional owner, oldest first.
+    async fn list(
+        &self,
+        owner_id: Option<&str>,
+    ) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let live = "expires_at IS NULL OR expires_at > $";
+        let sql = match owner_id {
+            Some(_) => format!(
+                "SELECT owner_id, task_id, data, version FROM {} \
+                 WHERE owner_id = $1 AND ({live}2) \
+                 ORDER BY created_at, task_id",
+                self.table
+            ),
+            None => format!(
+                "SELECT owner_id, task_id, data, version FROM {} \
+                 WHERE {live}1 \
+                 ORDER BY owner_id, created_at, task_id",
+                self.table
+            ),
+        };
+        let mut query = sqlx::query(&sql);
+        if let Some(owner_id) = owner_id {
+            query = query.bind(owner_id);
+        }
+        let query = query.bind(now_epoch());
+        let context = owner_id.unwrap_or(&self.table);
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| map_sql_error(e, context))?;
+        keyed_records(&rows, context)
+    }
+
+    /// The stored version of `key`, live or expired, if the row exists.
+    async fn current_version(
+        &self,
+        owner_id: &str,
+        task_id: &str,
+        key: &str,
+    ) -> Result<Option<u64>, StorageError> {
+        let version: Option<i64> = sqlx::query_scalar(&format!(
+            "SELECT version FROM {} WHERE owner_id = $1 AND task_id = $2",
+            self.table
+        ))
+        .bind(owner_id)
+        .bind(task_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, key))?;
+        Ok(version.map(|v| v as u64))
+    }
+}
+
+/// Maps `owner_id, task_id, data, version` rows to keyed records.
+fn keyed_records(
+    rows: &[sqlx::any::AnyRow],
+    context: &str,
+) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+    rows.iter()
+        .map(|row| -> Result<_, StorageError> {
+            let owner_id: String = row
+                .try_get("owner_id")
+                .map_err(|e| map_sql_error(e, context))?;
+            let task_id: String = row
+                .try_get("task_id")
+                .map_err(|e| map_sql_error(e, context))?;
+            let record = versioned_record(row, context)?;
+            Ok((format!("{owner_id}:{task_id}"), record))
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn split_key(key: &str) -> Result<(&str, &str), StorageError> {
+    key.split_once(':').ok_or_else(|| StorageError::Backend {
+        message: format!("invalid key format (missing ':'): {key}"),
+        source: None,
+    })
+}
+
+fn split_prefix(prefix: &str) -> Result<&str, StorageError> {
+    prefix
+        .strip_suffix(':')
+        .ok_or_else(|| StorageError::Backend {
+            message: format!("invalid prefix format (missing trailing ':'): {prefix}"),
+            source: None,
+        })
+}
+
+fn data_str(data: &[u8]) -> Result<&str, StorageError> {
+    std::str::from_utf8(data).map_err(|e| StorageError::Backend {
+        message: format!("data is not valid UTF-8: {e}"),
+        source: Some(Box::new(e)),
+    })
+}
+
+fn extract_ttl_epoch(data: &[u8]) -> Option<i64> {
+    let value: serde_json::Value = serde_json::from_slice(data).ok()?;
+    let expires_at_str = value.get("expiresAt")?.as_str()?;
+    let dt = chrono::DateTime::parse_from_rfc3339(expires_at_str).ok()?;
+    Some(dt.timestamp())
+}
+
+fn extract_created_at_ms(data: &[u8]) -> Option<i64> {
+    let value: serde_json::Value = serde_json::from_slice(data).ok()?;
+    let created_at_str = value.get("task")?.get("createdAt")?.as_str()?;
+    let dt = chrono::DateTime::parse_from_rfc3339(created_at_str).ok()?;
+    Some(dt.timestamp_millis())
+}
+
+fn now_epoch() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn versioned_record(row: &sqlx::any::AnyRow, key: &str) -> Result<VersionedRecord, StorageError> {
+    let data: String = row.try_get("data").map_err(|e| map_sql_error(e, key))?;
+    let version: i64 = row.try_get("version").map_err(|e| map_sql_error(e, key))?;
+    Ok(VersionedRecord {
+        data: data.into_bytes(),
+        version: version as u64,
+    })
+}
+
+fn map_sql_error(err: sqlx::Error, key: &str) -> StorageError {
+    StorageError::Backend {
+        message: format!("SQL error for key {key}: {err}"),
+        source: Some(Box::new(err)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// StorageBackend implementation
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl StorageBackend for SqlBackend {
+    async fn get(&self, key: &str) -> Result<VersionedRecord, StorageError> {
+        let (owner_id, task_id) = split_key(key)?;
+        let row = sqlx::query(&format!(
+            "SELECT data, version FROM {} \
+             WHERE owner_id = $1 AND task_id = $2 \
+             AND (expires_at IS NULL OR expires_at > $3)",
+            self.table
+        ))
+        .bind(owner_id)
+        .bind(task_id)
+        .bind(now_epoch())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, key))?;
+
+        match row {
+            Some(row) => versioned_record(&row, key),
+            None => Err(StorageError::NotFound {
+                key: key.to_string(),
+            }),
+        }
+    }
+
+    async fn put(&self, key: &str, data: &[u8]) -> Result<u64, StorageError> {
+        let (owner_id, task_id) = split_key(key)?;
+        let version: i64 = sqlx::query_scalar(&format!(
+            "INSERT INTO {table} (owner_id, task_id, data, version, created_at, expires_at) \
+             VALUES ($1, $2, $3, 1, $4, $5) \
+             ON CONFLICT (owner_id, task_id) DO UPDATE \
+             SET data = excluded.data, version = {table}.version + 1, \
+             expires_at = excluded.expires_at \
+             RETURNING version",
+            table = self.table
+        ))
+        .bind(owner_id)
+        .bind(task_id)
+        .bind(data_str(data)?)
+        .bind(extract_created_at_ms(data).unwrap_or(0))
+        .bind(extract_ttl_epoch(data))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, key))?;
+
+        Ok(version as u64)
+    }
+
+    async fn put_if_version(
+        &self,
+        key: &str,
+        data: &[u8],
+        expected_version: u64,
+    ) -> Result<u64, StorageError> {
+        let (owner_id, task_id) = split_key(key)?;
+        let version: Option<i64> = sqlx::query_scalar(&format!(
+            "UPDATE {} SET data = $1, version = version + 1, expires_at = $2 \
+             WHERE owner_id = $3 AND task_id = $4 AND version = $5 \
+             RETURNING version",
+            self.table
+        ))
+        .bind(data_str(data)?)
+        .bind(extract_ttl_epoch(data))
+        .bind(owner_id)
+        .bind(task_id)
+        .bind(expected_version as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, key))?;
+
+        if let Some(version) = version {
+            return Ok(version as u64);
+        }
+        // A missing row is a conflict with version 0, as in the other backends.
+        let actual = self
+            .current_version(owner_id, task_id, key)
+            .await?
+            .unwrap_or(0);
+        Err(StorageError::VersionConflict {
+            key: key.to_string(),
+            expected: expected_version,
+            actual,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, StorageError> {
+        let (owner_id, task_id) = split_key(key)?;
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE owner_id = $1 AND task_id = $2",
+            self.table
+        ))
+        .bind(owner_id)
+        .bind(task_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, key))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_by_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let owner_id = split_prefix(prefix)?;
+        self.list(Some(owner_id)).await
+    }
+
+    async fn list_all(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        self.list(None).await
+    }
+
+    async fn list_expired(&self) -> Result<Vec<(String, VersionedRecord)>, StorageError> {
+        let rows = sqlx::query(&format!(
+            "SELECT owner_id, task_id, data, version FROM {} \
+             WHERE expires_at IS NOT NULL AND expires_at <= $1 \
+             ORDER BY owner_id, created_at, task_id",
+            self.table
+        ))
+        .bind(now_epoch())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, &self.table))?;
+        keyed_records(&rows, &self.table)
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize, StorageError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE expires_at IS NOT NULL AND expires_at <= $1",
+            self.table
+        ))
+        .bind(now_epoch())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| map_sql_error(e, &self.table))?;
+
+        Ok(usize::try_from(result.rows_affected()).unwrap_or(usize::MAX))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Integration tests -- SQL backend contract tests
+// ---------------------------------------------------------------------------
+
+/// Runs against an in-memory SQLite database, or against the database at
+/// `SQL_TEST_URL` (e.g. a Postgres instance) when it is set.
+#[cfg(all(test, feature = "sql"))]
+mod integration_tests {
+    use super::*;
+    use crate::domain::TaskRecord;
+    use crate::error::TaskError;
+    use crate::store::generic::GenericTaskStore;
+    use crate::store::retention::{RemovalReason, TaskArchiver};
+    use std::sync::Arc;
+
+    async fn test_backend() -> SqlBackend {
+        sqlx::any::install_default_drivers();
+        let url = std::env::var("SQL_TEST_URL").unwrap_or_else(|_| "sqlite::memory:".to_string());
+        // A single connection keeps every query on the same in-memory database.
+        let pool = AnyPoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect(&url)
+            .await
+            .expect("database connection failed -- is SQL_TEST_URL reachable?");
+        let table = format!("test_{}", uuid::Uuid::new_v4().simple());
+        let backend = SqlBackend::with_pool(pool).with_table(table);
+        backend.migrate().await.unwrap();
+        backend
+    }
+
+    fn record_json(expires_at: Option<&str>) -> Vec<u8> {
+        let mut record = TaskRecord::new("owner".to_string(), "tool".to_string(), None);
+        if let Some(expires_at) = expires_at {
+            record.expires_at = Some(expires_at.parse().unwrap());
+        }
+        serde_json::to_vec(&record).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sql_get_missing_key_returns_not_found() {
+        let backend = test_backend().await;
+        let result = backend.get("owner:missing").await;
+        assert!(
+            matches!(&result, Err(StorageError::NotFound { key }) if key == "owner:missing"),
+            "expected NotFound, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn sql_put_assigns_increasing_versions() {
+        let backend = test_backend().await;
+        assert_eq!(backend.put("owner:task-1", b"first").await.unwrap(), 1);
+        assert_eq!(backend.put("owner:task-1", b"second").await.unwrap(), 2);
+
+        let record = backend.get("owner:task-1").await.unwrap();
+        assert_eq!(record.data, b"second");
+        assert_eq!(record.version, 2);
+    }
+
+    #[tokio::test]
+    async fn sql_put_if_version_checks_the_stored_version() {
+        let backend = test_backend().await;
+        backend.put("owner:task-1", b"v1").await.unwrap();
+
+        assert_eq!(
+            backend
+                .put_if_version("owner:task-1", b"v2", 1)
+                .await
+                .unwrap(),
+            2
+        );
+        let result = backend.put_if_version("owner:task-1", b"stale", 1).await;
+        assert!(
+            matches!(
+                result,
+                Err(StorageError::VersionConflict {
+                    expected: 1,
+                    actual: 2,
+                    ..
+                })
+            ),
+            "expected VersionConflict, got: {result:?}"
+        );
+        let result = backend.put_if_version("owner:missing", b"x", 1).await;
+        assert!(matches!(
+            result,
+            Err(StorageError::VersionConflict { actual: 0, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn sql_delete_is_idempotent() {
+        let backend = test_backend().await;
+        backend.put("owner:task-1", b"data").await.unwrap();
+        assert!(backend.delete("owner:task-1").await.unwrap());
+        assert!(!backend.delete("owner:task-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn sql_list_by_prefix_is_owner_scoped() {
+        let backend = test_backend().await;
+        backend.put("alice:task-1", b"a1").await.unwrap();
+        backend.put("alice:task-2", b"a2").await.unwrap();
+        backend.put("bob:task-3", b"b1").await.unwrap();
+
+        let alice = backend.list_by_prefix("alice:").await.unwrap();
+        let keys: Vec<&str> = alice.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["alice:task-1", "alice:task-2"]);
+        assert_eq!(backend.list_all().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn sql_expired_records_are_hidden_and_cleaned_up() {
+        let backend = test_backend().await;
+        let expired = record_json(Some("2000-01-01T00:00:00Z"));
+        let live = record_json(Some("2999-01-01T00:00:00Z"));
+        backend.put("owner:expired", &expired).await.unwrap();
+        backend.put("owner:live", &live).await.unwrap();
+        backend
+            .put("owner:forever", &record_json(None))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            backend.get("owner:expired").await,
+            Err(StorageError::NotFound { .. })
+        ));
+        assert_eq!(backend.list_by_prefix("owner:").await.unwrap().len(), 2);
+        assert_eq!(backend.cleanup_expired().await.unwrap(), 1);
+        assert_eq!(backend.cleanup_expired().await.unwrap(), 0);
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingArchiver(parking_lot::Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl TaskArchiver for RecordingArchiver {
+        async fn archive(
+            &self,
+            record: &TaskRecord,
+            _reason: RemovalReason,
+        ) -> Result<(), TaskError> {
+            self.0.lock().push(record.task.task_id.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn sql_cleanup_archives_expired_tasks_then_deletes_them() {
+        let backend = test_backend().await;
+        let mut expired = TaskRecord::new("owner".to_string(), "tool".to_string(), Some(60_000));
+        expired.expires_at = Some("2000-01-01T00:00:00Z".parse().unwrap());
+        let key = format!("owner:{}", expired.task.task_id);
+        backend
+            .put(&key, &serde_json::to_vec(&expired).unwrap())
+            .await
+            .unwrap();
+        backend.put("owner:live", &record_json(None)).await.unwrap();
+
+        let listed = backend.list_expired().await.unwrap();
+        let keys: Vec<&str> = listed.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, [key.as_str()]);
+
+        let archiver = Arc::new(RecordingArchiver::default());
+        let store = GenericTaskStore::new(backend).with_archiver(archiver.clone());
+        assert_eq!(store.cleanup_expired().await.unwrap(), 1);
+        assert_eq!(*archiver.0.lock(), vec![expired.task.task_id.clone()]);
+        assert!(store.backend().list_expired().await.unwrap().is_empty());
+        assert_eq!(store.backend().list_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sql_migrate_is_idempotent() {
+        let backend = test_backend().await;
+        backend.migrate().await.unwrap();
+        backend.put("owner:task-1", b"data").await.unwrap();
+        backend.migrate().await.unwrap();
+        assert_eq!(backend.get("owner:task-1").await.unwrap().version, 1);
+    }
+}
@@ -0,0 +1,125 @@
+//! Elicitation bridge for tasks in `input_required` status.
+//!
+//! When a task needs input from the user it stores an elicitation request
+//! (an [`ElicitRequestParams`], the same payload as `elicitation/create`)
+//! under [`TASK_INPUT_REQUEST_KEY`] and moves to `input_required`. Clients
+//! see the request in the `_meta` of `tasks/get` and answer it with
+//! `tasks/provide_input`; the answer is stored as an [`ElicitResult`] under
+//! [`TASK_INPUT_KEY`] and the task moves back to `working`.
+//!
+//! Tool handlers drive this through
+//! [`TaskContext::request_input`](crate::context::TaskContext::request_input)
+//! and [`TaskContext::wait_for_input`](crate::context::TaskContext::wait_for_input).
+
+pub use pmcp::types::elicitation::{ElicitAction, ElicitRequestParams, ElicitResult};
+use serde_json::Value;
+
+/// Task variable key holding the pending [`ElicitRequestParams`].
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::input::TASK_INPUT_REQUEST_KEY;
+///
+/// assert_eq!(TASK_INPUT_REQUEST_KEY, "_task.input_request");
+/// ```
+pub const TASK_INPUT_REQUEST_KEY: &str = "_task.input_request";
+
+/// Task variable key holding the client's [`ElicitResult`] answer.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::input::TASK_INPUT_KEY;
+///
+/// assert_eq!(TASK_INPUT_KEY, "_task.input");
+/// ```
+pub const TASK_INPUT_KEY: &str = "_task.input";
+
+/// Returns the `required` fields of a form request's schema that are
+/// missing from an accepted answer.
+///
+/// Declined or cancelled answers, URL-mode requests, and schemas without a
+/// `required` list never report missing fields.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::input::{missing_required_fields, ElicitRequestParams, ElicitResult};
+/// use serde_json::json;
+///
+/// let request = ElicitRequestParams::Form {
+///     message: "Which region?".to_string(),
+///     requested_schema: json!({
+///         "type": "object",
+///         "properties": {"region": {"type": "string"}},
+///         "required": ["region"]
+///     }),
+/// };
+///
+/// let empty = ElicitResult::accept(Default::default());
+/// assert_eq!(missing_required_fields(&request, &empty), vec!["region"]);
+/// assert!(missing_required_fields(&request, &ElicitResult::decline()).is_empty());
+/// ```
+pub fn missing_required_fields(
+    request: &ElicitRequestParams,
+    answer: &ElicitResult,
+) -> Vec<String> {
+    let ElicitRequestParams::Form {
+        requested_schema, ..
+    } = request
+    else {
+        return Vec::new();
+    };
+    if !answer.is_accepted() {
+        return Vec::new();
+    }
+
+    let content = answer.content.as_ref();
+    requested_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|field| {
+            content
+                .and_then(|content| content.get(*field))
+                .is_none_or(Value::is_null)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn url_requests_and_partial_schemas_have_no_missing_fields() {
+        let url = ElicitRequestParams::Url {
+            message: "Sign in".to_string(),
+            elicitation_id: "e-1".to_string(),
+            url: "https://example.com/login".to_string(),
+        };
+        let answer = ElicitResult::accept(Default::default());
+        assert!(missing_required_fields(&url, &answer).is_empty());
+
+        let form = ElicitRequestParams::Form {
+            message: "Details".to_string(),
+            requested_schema: json!({
+                "type": "object",
+                "required": ["name", "age"]
+            }),
+        };
+        let answer = ElicitResult::accept(
+            [
+                ("name".to_string(), json!("Ada")),
+                ("age".to_string(), json!(null)),
+            ]
+            .into(),
+        );
+        assert_eq!(missing_required_fields(&form, &answer), vec!["age"]);
+    }
+}
@@ -6,14 +6,18 @@
 
 pub mod capabilities;
 pub mod execution;
+pub mod input;
 pub mod notification;
 pub mod params;
+pub mod progress;
 pub mod task;
 pub mod workflow;
 
 pub use capabilities::*;
 pub use execution::*;
+pub use input::*;
 pub use notification::*;
 pub use params::*;
+pub use progress::*;
 pub use task::*;
 pub use workflow::*;
@@ -6,6 +6,7 @@
 //! it appears in notification params, not as a result.
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::types::task::TaskStatus;
 
@@ -17,7 +18,7 @@ use crate::types::task::TaskStatus;
 /// # Serialization
 ///
 /// - `ttl` is required but nullable: serializes as `null` when `None`.
-/// - `poll_interval` and `status_message` are optional, omitted when `None`.
+/// - `poll_interval`, `status_message` and `_meta` are optional, omitted when `None`.
 ///
 /// # Examples
 ///
@@ -33,6 +34,7 @@ use crate::types::task::TaskStatus;
 ///     last_updated_at: "2025-11-25T10:35:00Z".to_string(),
 ///     ttl: None,
 ///     poll_interval: None,
+///     _meta: None,
 /// };
 ///
 /// let json = serde_json::to_value(&notification).unwrap();
@@ -67,6 +69,12 @@ pub struct TaskStatusNotification {
     /// Suggested polling interval in milliseconds. Omitted when `None`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub poll_interval: Option<u64>,
+
+    /// Optional metadata map (e.g., structured task progress).
+    #[serde(rename = "_meta", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::pub_underscore_fields)]
+    pub _meta: Option<Map<String, Value>>,
 }
 
 #[cfg(test)]
@@ -84,6 +92,7 @@ mod tests {
             last_updated_at: "2025-11-25T10:32:00Z".to_string(),
             ttl: Some(60000),
             poll_interval: Some(5000),
+            _meta: None,
         };
 
         let json = serde_json::to_value(&notification).unwrap();
@@ -106,6 +115,7 @@ mod tests {
             last_updated_at: "2025-11-25T10:35:00Z".to_string(),
             ttl: None,
             poll_interval: None,
+            _meta: None,
         };
 
         let json = serde_json::to_value(&notification).unwrap();
@@ -127,6 +137,7 @@ mod tests {
             last_updated_at: "2025-11-25T10:33:00Z".to_string(),
             ttl: Some(120000),
             poll_interval: None,
+            _meta: None,
         };
 
         let json_str = serde_json::to_string(&original).unwrap();
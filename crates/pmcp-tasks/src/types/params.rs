@@ -1,7 +1,8 @@
 //! Request parameter types for MCP Tasks protocol methods.
 //!
 //! These types correspond to the `params` field in JSON-RPC requests
-//! for `tasks/get`, `tasks/result`, `tasks/list`, and `tasks/cancel`.
+//! for `tasks/get`, `tasks/result`, `tasks/list`, `tasks/cancel`, and
+//! `tasks/provide_input`.
 //! The [`TaskParams`] struct is used in the `task` field of `tools/call`
 //! requests when task augmentation is enabled.
 
@@ -109,23 +110,31 @@ pub struct TaskResultParams {
 /// use serde_json;
 ///
 /// // First page (no cursor)
-/// let params = TaskListParams { cursor: None };
+/// let params = TaskListParams::default();
 /// let json = serde_json::to_value(&params).unwrap();
 /// assert!(json.get("cursor").is_none());
 ///
 /// // Subsequent page
 /// let params = TaskListParams {
 ///     cursor: Some("page-2-token".to_string()),
+///     ..Default::default()
 /// };
 /// let json = serde_json::to_value(&params).unwrap();
 /// assert_eq!(json["cursor"], "page-2-token");
+/// assert!(json.get("allOwners").is_none());
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskListParams {
     /// Pagination cursor for the next page. `None` for the first page.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+
+    /// List tasks of every owner instead of only the caller's. Requires an
+    /// admin caller and admin listing enabled on the server. Omitted when
+    /// `false`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub all_owners: bool,
 }
 
 /// Parameters for `tasks/cancel` requests.
@@ -173,6 +182,37 @@ pub struct TaskCancelParams {
     pub result: Option<serde_json::Value>,
 }
 
+/// Parameters for `tasks/provide_input` requests.
+///
+/// Answers the elicitation attached to a task in `input_required` status.
+/// The elicitation result fields sit next to `taskId`.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::TaskProvideInputParams;
+/// use serde_json::json;
+///
+/// let params: TaskProvideInputParams = serde_json::from_value(json!({
+///     "taskId": "task-7",
+///     "action": "accept",
+///     "content": {"region": "eu-west-1"}
+/// }))
+/// .unwrap();
+/// assert_eq!(params.task_id, "task-7");
+/// assert!(params.input.is_accepted());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProvideInputParams {
+    /// The task ID waiting for input.
+    pub task_id: String,
+
+    /// The user's answer to the task's elicitation.
+    #[serde(flatten)]
+    pub input: crate::types::input::ElicitResult,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,7 +263,7 @@ mod tests {
 
     #[test]
     fn task_list_params_no_cursor() {
-        let params = TaskListParams { cursor: None };
+        let params = TaskListParams::default();
         let json = serde_json::to_value(&params).unwrap();
         assert!(json.get("cursor").is_none());
     }
@@ -232,6 +272,7 @@ mod tests {
     fn task_list_params_with_cursor() {
         let params = TaskListParams {
             cursor: Some("next-page".to_string()),
+            ..Default::default()
         };
         let json = serde_json::to_value(&params).unwrap();
         assert_eq!(json["cursor"], "next-page");
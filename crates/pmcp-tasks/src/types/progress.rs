@@ -0,0 +1,199 @@
+//! Structured progress reporting for long-running tasks.
+//!
+//! A [`TaskProgress`] records how far a task has come (`current` out of an
+//! optional `total`), a human-readable message, and the status of named
+//! sub-steps. It is stored in the task's variable store under
+//! [`TASK_PROGRESS_KEY`], so it is returned in the `_meta` of `tasks/get`
+//! responses like any other variable and carried in the `_meta` of status
+//! notifications built with
+//! [`TaskRecord::to_status_notification`](crate::domain::TaskRecord::to_status_notification).
+//!
+//! Tool handlers update it through
+//! [`TaskContext::report_progress`](crate::context::TaskContext::report_progress)
+//! and [`TaskContext::update_step`](crate::context::TaskContext::update_step).
+
+use serde::{Deserialize, Serialize};
+
+/// Task variable key for the structured [`TaskProgress`] object.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::progress::TASK_PROGRESS_KEY;
+///
+/// assert_eq!(TASK_PROGRESS_KEY, "_task.progress");
+/// ```
+pub const TASK_PROGRESS_KEY: &str = "_task.progress";
+
+/// Progress of a long-running task.
+///
+/// # Serialization
+///
+/// Fields use `camelCase`; `total` and `message` are omitted when `None` and
+/// `steps` is omitted when empty.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::progress::{TaskProgress, TaskStepStatus};
+///
+/// let mut progress = TaskProgress::new(30, Some(120)).with_message("Importing rows");
+/// progress.set_step("download", TaskStepStatus::Completed, None);
+/// progress.set_step("import", TaskStepStatus::Running, None);
+///
+/// assert_eq!(progress.percent(), Some(25.0));
+///
+/// let json = serde_json::to_value(&progress).unwrap();
+/// assert_eq!(json["current"], 30);
+/// assert_eq!(json["total"], 120);
+/// assert_eq!(json["steps"][1]["status"], "running");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    /// Units of work done so far.
+    pub current: u64,
+
+    /// Total units of work, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// Human-readable description of the current activity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Named sub-steps, in the order they were first reported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<TaskProgressStep>,
+}
+
+impl TaskProgress {
+    /// Creates progress at `current` out of an optional `total`.
+    pub fn new(current: u64, total: Option<u64>) -> Self {
+        Self {
+            current,
+            total,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the progress message.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Completion percentage (0-100), or `None` when the total is unknown
+    /// or zero.
+    #[allow(clippy::cast_precision_loss)] // progress bars don't need 53+ bits
+    pub fn percent(&self) -> Option<f64> {
+        match self.total {
+            Some(total) if total > 0 => {
+                Some((self.current.min(total) as f64 / total as f64) * 100.0)
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the sub-step with the given name.
+    pub fn step(&self, name: &str) -> Option<&TaskProgressStep> {
+        self.steps.iter().find(|step| step.name == name)
+    }
+
+    /// Updates the sub-step with the given name, appending it if new.
+    pub fn set_step(
+        &mut self,
+        name: impl Into<String>,
+        status: TaskStepStatus,
+        message: Option<String>,
+    ) {
+        let name = name.into();
+        match self.steps.iter_mut().find(|step| step.name == name) {
+            Some(step) => {
+                step.status = status;
+                step.message = message;
+            },
+            None => self.steps.push(TaskProgressStep {
+                name,
+                status,
+                message,
+            }),
+        }
+    }
+}
+
+/// A named sub-step of a task's progress.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgressStep {
+    /// Step name, unique within the task.
+    pub name: String,
+
+    /// Current status of the step.
+    pub status: TaskStepStatus,
+
+    /// Optional detail, such as an error for a failed step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Status of a progress sub-step.
+///
+/// Variants serialize as `snake_case` strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStepStatus {
+    /// Step has not started yet.
+    #[default]
+    Pending,
+    /// Step is in progress.
+    Running,
+    /// Step finished successfully.
+    Completed,
+    /// Step failed.
+    Failed,
+    /// Step was skipped.
+    Skipped,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn percent_handles_unknown_zero_and_overshoot() {
+        assert_eq!(TaskProgress::new(5, None).percent(), None);
+        assert_eq!(TaskProgress::new(5, Some(0)).percent(), None);
+        assert_eq!(TaskProgress::new(1, Some(4)).percent(), Some(25.0));
+        assert_eq!(TaskProgress::new(9, Some(4)).percent(), Some(100.0));
+    }
+
+    #[test]
+    fn set_step_updates_in_place() {
+        let mut progress = TaskProgress::default();
+        progress.set_step("fetch", TaskStepStatus::Running, None);
+        progress.set_step("parse", TaskStepStatus::Pending, None);
+        progress.set_step("fetch", TaskStepStatus::Failed, Some("timeout".into()));
+
+        assert_eq!(progress.steps.len(), 2);
+        assert_eq!(progress.steps[0].name, "fetch");
+        assert_eq!(
+            progress.step("fetch").unwrap().status,
+            TaskStepStatus::Failed
+        );
+        assert_eq!(
+            progress.step("fetch").unwrap().message.as_deref(),
+            Some("timeout")
+        );
+    }
+
+    #[test]
+    fn minimal_progress_serialization() {
+        let json = serde_json::to_value(TaskProgress::new(3, None)).unwrap();
+        assert_eq!(json, json!({"current": 3}));
+
+        let back: TaskProgress = serde_json::from_value(json!({"current": 3})).unwrap();
+        assert!(back.steps.is_empty());
+    }
+}
@@ -11,6 +11,7 @@
 //! Workflow-related task variables use the `_workflow.` prefix:
 //! - `_workflow.progress` -- the full [`WorkflowProgress`] struct
 //! - `_workflow.result.<step_name>` -- per-step tool result (raw JSON)
+//! - `_workflow.binding.<binding>` -- per-step output binding, used to resume
 //! - `_workflow.pause_reason` -- the [`PauseReason`] when execution pauses
 //!
 //! # Schema Versioning
@@ -59,6 +60,13 @@ pub const WORKFLOW_PAUSE_REASON_KEY: &str = "_workflow.pause_reason";
 /// the full key.
 pub const WORKFLOW_RESULT_PREFIX: &str = "_workflow.result.";
 
+/// Prefix for per-step output binding variable keys.
+///
+/// Each completed step with a binding stores its output under
+/// `_workflow.binding.<binding>` so execution can resume from the last
+/// completed step. Use [`workflow_binding_key`] to build the full key.
+pub const WORKFLOW_BINDING_PREFIX: &str = "_workflow.binding.";
+
 /// Prefix for extra tool call variable keys (unmatched tools).
 ///
 /// When a tool call with `_task_id` does not match any workflow step,
@@ -87,6 +95,19 @@ pub fn workflow_result_key(step_name: &str) -> String {
     format!("{WORKFLOW_RESULT_PREFIX}{step_name}")
 }
 
+/// Builds the task variable key for a step output binding.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp_tasks::types::workflow::workflow_binding_key;
+///
+/// assert_eq!(workflow_binding_key("raw_data"), "_workflow.binding.raw_data");
+/// ```
+pub fn workflow_binding_key(binding: &str) -> String {
+    format!("{WORKFLOW_BINDING_PREFIX}{binding}")
+}
+
 /// Builds the task variable key for an unmatched tool call result.
 ///
 /// # Examples
@@ -0,0 +1,229 @@
+//! Redis-backed widget state store.
+//!
+//! [`RedisWidgetStateStore`] implements the SDK's
+//! [`WidgetStateStore`](pmcp::server::widget_state::WidgetStateStore) so
+//! widget state survives server restarts and is shared across instances.
+//!
+//! # Key Schema
+//!
+//! | Key Pattern | Type | Purpose |
+//! |-------------|------|---------|
+//! | `{prefix}:widget-state:{session_id}:{widget_uri}` | String (JSON) | Widget state |
+//!
+//! When a TTL is configured, every save refreshes the key's expiry with
+//! `SET ... EX`, so state lives for the TTL after its last write.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use pmcp_tasks::widget_state::RedisWidgetStateStore;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let store = RedisWidgetStateStore::new("redis://127.0.0.1:6379")
+//!     .await
+//!     .unwrap()
+//!     .with_ttl(Duration::from_secs(24 * 3600));
+//! let server = pmcp::Server::builder()
+//!     .name("board")
+//!     .version("1.0.0")
+//!     .widget_state_store(Arc::new(store));
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use ::redis::aio::MultiplexedConnection;
+use ::redis::AsyncCommands;
+use async_trait::async_trait;
+use pmcp::server::widget_state::{encode_state, WidgetStateStore, DEFAULT_MAX_STATE_BYTES};
+use serde_json::Value;
+
+use crate::store::backend::StorageError;
+
+/// Redis store for widget state, keyed by session and widget URI.
+///
+/// Holds a [`MultiplexedConnection`] which is cheap to clone; each method
+/// clones it for concurrent safety, as [`RedisBackend`](crate::store::redis::RedisBackend) does.
+#[derive(Debug, Clone)]
+pub struct RedisWidgetStateStore {
+    conn: MultiplexedConnection,
+    key_prefix: String,
+    ttl: Option<Duration>,
+    max_state_bytes: usize,
+}
+
+impl RedisWidgetStateStore {
+    /// Creates a store by connecting to Redis at the given URL.
+    ///
+    /// Uses the default key prefix `"pmcp"`, no expiry, and the SDK's
+    /// default state size limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Backend`] if the Redis client cannot be created
+    /// or the connection cannot be established.
+    pub async fn new(url: &str) -> Result<Self, StorageError> {
+        let client = ::redis::Client::open(url).map_err(|e| StorageError::Backend {
+            message: format!("failed to create Redis client: {e}"),
+            source: Some(Box::new(e)),
+        })?;
+        let conn = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| StorageError::Backend {
+                message: format!("failed to connect to Redis: {e}"),
+                source: Some(Box::new(e)),
+            })?;
+        Ok(Self::with_connection(conn))
+    }
+
+    /// Creates a store with a pre-built multiplexed connection.
+    pub fn with_connection(conn: MultiplexedConnection) -> Self {
+        Self {
+            conn,
+            key_prefix: "pmcp".to_string(),
+            ttl: None,
+            max_state_bytes: DEFAULT_MAX_STATE_BYTES,
+        }
+    }
+
+    /// Sets a custom key prefix (builder pattern).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Expire saved state `ttl` after it was last written.
+    ///
+    /// Redis expiry has one-second granularity; shorter TTLs round up.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Override the maximum serialized size of a single widget state.
+    pub fn with_max_state_bytes(mut self, max_state_bytes: usize) -> Self {
+        self.max_state_bytes = max_state_bytes;
+        self
+    }
+
+    /// Constructs the Redis key for a widget's state in a session.
+    fn state_key(&self, session_id: &str, widget_uri: &str) -> String {
+        format!(
+            "{}:widget-state:{}:{}",
+            self.key_prefix, session_id, widget_uri
+        )
+    }
+}
+
+/// Maps a Redis error to a `pmcp` internal error.
+fn map_redis_error(err: ::redis::RedisError, key: &str) -> pmcp::Error {
+    pmcp::Error::internal(format!("Redis error for key {key}: {err}"))
+}
+
+#[async_trait]
+impl WidgetStateStore for RedisWidgetStateStore {
+    async fn load(&self, session_id: &str, widget_uri: &str) -> pmcp::Result<Option<Value>> {
+        let key = self.state_key(session_id, widget_uri);
+        let raw: Option<String> = self
+            .conn
+            .clone()
+            .get(&key)
+            .await
+            .map_err(|e| map_redis_error(e, &key))?;
+        raw.map(|raw| serde_json::from_str(&raw))
+            .transpose()
+            .map_err(|e| pmcp::Error::internal(format!("corrupt widget state at {key}: {e}")))
+    }
+
+    async fn save(&self, session_id: &str, widget_uri: &str, state: Value) -> pmcp::Result<()> {
+        let encoded = encode_state(&state, self.max_state_bytes)?;
+        let key = self.state_key(session_id, widget_uri);
+        let mut cmd = ::redis::cmd("SET");
+        cmd.arg(&key).arg(encoded);
+        if let Some(ttl) = self.ttl {
+            let secs = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+            cmd.arg("EX").arg(secs.max(1));
+        }
+        let () = cmd
+            .query_async(&mut self.conn.clone())
+            .await
+            .map_err(|e| map_redis_error(e, &key))?;
+        Ok(())
+    }
+
+    async fn clear(&self, session_id: &str, widget_uri: &str) -> pmcp::Result<bool> {
+        let key = self.state_key(session_id, widget_uri);
+        let removed: i64 = self
+            .conn
+            .clone()
+            .del(&key)
+            .await
+            .map_err(|e| map_redis_error(e, &key))?;
+        Ok(removed > 0)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Integration tests (require a running Redis instance)
+// ---------------------------------------------------------------------------
+
+/// Integration tests against a real Redis instance.
+///
+/// Run with:
+/// ```bash
+/// cargo test -p pmcp-tasks --features redis-tests -- redis_widget_state --test-threads=1
+/// ```
+#[cfg(all(test, feature = "redis-tests"))]
+mod integration_tests {
+    use super::*;
+    use serde_json::json;
+
+    const WIDGET: &str = "ui://board/main.html";
+
+    async fn test_store() -> RedisWidgetStateStore {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        RedisWidgetStateStore::new(&url)
+            .await
+            .expect("Redis connection failed -- is Redis running?")
+            .with_prefix(format!("test-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn redis_widget_state_roundtrip() {
+        let store = test_store().await;
+        assert_eq!(store.load("s1", WIDGET).await.unwrap(), None);
+
+        store.save("s1", WIDGET, json!({"page": 2})).await.unwrap();
+        assert_eq!(
+            store.load("s1", WIDGET).await.unwrap(),
+            Some(json!({"page": 2}))
+        );
+        assert_eq!(store.load("s2", WIDGET).await.unwrap(), None);
+
+        assert!(store.clear("s1", WIDGET).await.unwrap());
+        assert!(!store.clear("s1", WIDGET).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn redis_widget_state_sets_ttl() {
+        let store = test_store().await.with_ttl(Duration::from_secs(60));
+        store.save("s1", WIDGET, json!(1)).await.unwrap();
+
+        let key = store.state_key("s1", WIDGET);
+        let ttl: i64 = store.conn.clone().ttl(&key).await.unwrap();
+        assert!(ttl > 0 && ttl <= 60, "unexpected TTL {ttl}");
+    }
+
+    #[tokio::test]
+    async fn redis_widget_state_rejects_oversized_state() {
+        let store = test_store().await.with_max_state_bytes(8);
+        assert!(store
+            .save("s1", WIDGET, json!({"notes": "too long"}))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! Background worker pool for running task work off the request path.
+//!
+//! [`TaskWorker`] lets a tool handler create a task, hand the actual work to
+//! a bounded pool of tokio tasks, and return the task to the client
+//! immediately. When the work finishes, the worker moves the task from
+//! `working` to `completed` (storing the result atomically with the status
+//! change) or to `failed` with the error message.
+//!
+//! Register the worker as server state and spawn from handlers through
+//! [`SpawnTaskExt`]:
+//!
+//! ```rust,ignore
+//! use pmcp_tasks::worker::{SpawnTaskExt, TaskWorker};
+//!
+//! let worker = TaskWorker::new(store.clone(), 8);
+//! let server = Server::builder()
+//!     .with_state(worker)
+//!     // ...
+//!     .build()?;
+//!
+//! // Inside a tool handler:
+//! let task = extra.spawn_task(async move {
+//!     let report = build_report(&args).await?;
+//!     Ok(json!({ "report": report }))
+//! }).await?;
+//! Ok(task)
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+
+use pmcp::error::Error as PmcpError;
+use pmcp::RequestHandlerExtra;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+
+use crate::context::TaskContext;
+use crate::domain::TaskRecord;
+use crate::error::TaskError;
+use crate::security::OwnerStrategy;
+use crate::store::TaskStore;
+use crate::types::task::TaskStatus;
+
+/// Default number of task bodies allowed to run at the same time.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 16;
+
+/// Bounded pool that runs task work in the background.
+///
+/// At most `max_concurrency` task bodies run at once; tasks spawned beyond
+/// that stay `working` until a slot frees up. Cloning a worker is cheap and
+/// shares the same pool.
+#[derive(Clone)]
+pub struct TaskWorker {
+    store: Arc<dyn TaskStore>,
+    permits: Arc<Semaphore>,
+    owner_strategy: OwnerStrategy,
+}
+
+impl std::fmt::Debug for TaskWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskWorker")
+            .field("available_permits", &self.permits.available_permits())
+            .field("owner_strategy", &self.owner_strategy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TaskWorker {
+    /// Creates a worker backed by `store` that runs at most
+    /// `max_concurrency` task bodies concurrently (minimum 1).
+    pub fn new(store: Arc<dyn TaskStore>, max_concurrency: usize) -> Self {
+        Self {
+            store,
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            owner_strategy: OwnerStrategy::default(),
+        }
+    }
+
+    /// Sets how [`SpawnTaskExt::spawn_task`] resolves the owner of new tasks.
+    ///
+    /// Use the same strategy as the server's
+    /// [`TaskSecurityConfig`](crate::security::TaskSecurityConfig) so spawned
+    /// tasks are visible to their caller through `tasks/get`.
+    pub fn with_owner_strategy(mut self, strategy: OwnerStrategy) -> Self {
+        self.owner_strategy = strategy;
+        self
+    }
+
+    /// Returns the store the worker creates and updates tasks in.
+    pub fn store(&self) -> &Arc<dyn TaskStore> {
+        &self.store
+    }
+
+    /// Creates a task and runs `work` for it in the background.
+    ///
+    /// Returns the freshly created task record (status `working`) without
+    /// waiting for the work. `work` receives a [`TaskContext`] scoped to the
+    /// new task, e.g. for storing intermediate variables.
+    ///
+    /// When `work` returns `Ok(value)` the task is completed with `value` as
+    /// its result; on `Err` (or a panic) it is marked failed. If the task has
+    /// already reached a terminal state by then, for example because the
+    /// client cancelled it, the outcome is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns the store error if the task cannot be created.
+    pub async fn spawn<F, Fut>(
+        &self,
+        owner_id: &str,
+        ttl: Option<u64>,
+        work: F,
+    ) -> Result<TaskRecord, TaskError>
+    where
+        F: FnOnce(TaskContext) -> Fut + Send + 'static,
+        Fut: Future<Output = pmcp::Result<Value>> + Send + 'static,
+    {
+        let record = self.store.create(owner_id, "tools/call", ttl).await?;
+        let ctx = TaskContext::new(
+            self.store.clone(),
+            record.task.task_id.clone(),
+            owner_id.to_string(),
+        );
+        let store = self.store.clone();
+        let permits = self.permits.clone();
+
+        tokio::spawn(async move {
+            // The semaphore is never closed, so acquiring can only succeed
+            let Ok(_permit) = permits.acquire_owned().await else {
+                return;
+            };
+            let task_id = ctx.task_id().to_string();
+            let owner_id = ctx.owner_id().to_string();
+
+            // Run the body in its own tokio task so a panic fails the task
+            // instead of leaving it `working` forever
+            let outcome = match tokio::spawn(work(ctx)).await {
+                Ok(outcome) => outcome,
+                Err(join_error) => Err(PmcpError::internal(format!(
+                    "task worker panicked: {join_error}"
+                ))),
+            };
+
+            let stored = match outcome {
+                Ok(result) => {
+                    store
+                        .complete_with_result(
+                            &task_id,
+                            &owner_id,
+                            TaskStatus::Completed,
+                            None,
+                            result,
+                        )
+                        .await
+                },
+                Err(e) => {
+                    store
+                        .update_status(&task_id, &owner_id, TaskStatus::Failed, Some(e.to_string()))
+                        .await
+                },
+            };
+            if let Err(e) = stored {
+                tracing::debug!(task_id = %task_id, error = %e, "discarding task worker outcome");
+            }
+        });
+
+        Ok(record)
+    }
+}
+
+/// Spawns background task work from a tool handler's [`RequestHandlerExtra`].
+///
+/// Requires a [`TaskWorker`] registered as server state
+/// (`with_state(worker)`).
+pub trait SpawnTaskExt {
+    /// Creates a task owned by the caller and runs `work` for it on the
+    /// registered [`TaskWorker`].
+    ///
+    /// Returns the wire-format task as a JSON value; returning it from the
+    /// tool handler makes the server answer the `tools/call` with a
+    /// `CreateTaskResult`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no [`TaskWorker`] is registered or the task
+    /// cannot be created.
+    fn spawn_task<Fut>(&self, work: Fut) -> impl Future<Output = pmcp::Result<Value>> + Send
+    where
+        Fut: Future<Output = pmcp::Result<Value>> + Send + 'static;
+}
+
+impl SpawnTaskExt for RequestHandlerExtra {
+    async fn spawn_task<Fut>(&self, work: Fut) -> pmcp::Result<Value>
+    where
+        Fut: Future<Output = pmcp::Result<Value>> + Send + 'static,
+    {
+        let worker = self.extensions.get_arc::<TaskWorker>().ok_or_else(|| {
+            PmcpError::internal("no TaskWorker registered; add one with `with_state(worker)`")
+        })?;
+
+        let owner_id = worker
+            .owner_strategy
+            .resolve(self.auth_context.as_ref(), self.session_id.as_deref());
+        let ttl = self
+            .task_request
+            .as_ref()
+            .and_then(|task| task.get("ttl"))
+            .and_then(Value::as_u64);
+
+        let record = worker
+            .spawn(&owner_id, ttl, move |_| work)
+            .await
+            .map_err(|e| PmcpError::internal(e.to_string()))?;
+
+        serde_json::to_value(record.to_wire_task())
+            .map_err(|e| PmcpError::internal(format!("failed to serialize task: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use serde_json::json;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::security::TaskSecurityConfig;
+    use crate::store::memory::InMemoryTaskStore;
+
+    fn store() -> Arc<dyn TaskStore> {
+        Arc::new(
+            InMemoryTaskStore::new()
+                .with_security(TaskSecurityConfig::default().with_allow_anonymous(true)),
+        )
+    }
+
+    async fn wait_for_terminal(store: &Arc<dyn TaskStore>, task_id: &str) -> TaskRecord {
+        for _ in 0..200 {
+            let record = store.get(task_id, "local").await.unwrap();
+            if record.task.status.is_terminal() {
+                return record;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("task {task_id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn successful_work_completes_task_with_result() {
+        let store = store();
+        let worker = TaskWorker::new(store.clone(), 2);
+
+        let record = worker
+            .spawn("local", None, |ctx| async move {
+                ctx.set_variable("step", json!("done")).await.ok();
+                Ok(json!({"answer": 42}))
+            })
+            .await
+            .unwrap();
+        assert_eq!(record.task.status, TaskStatus::Working);
+
+        let done = wait_for_terminal(&store, &record.task.task_id).await;
+        assert_eq!(done.task.status, TaskStatus::Completed);
+        assert_eq!(done.result, Some(json!({"answer": 42})));
+        assert_eq!(done.variables.get("step"), Some(&json!("done")));
+    }
+
+    #[tokio::test]
+    async fn failing_or_panicking_work_fails_task() {
+        let store = store();
+        let worker = TaskWorker::new(store.clone(), 2);
+
+        let failed = worker
+            .spawn("local", None, |_| async {
+                Err(PmcpError::internal("upstream unavailable"))
+            })
+            .await
+            .unwrap();
+        let panicked = worker
+            .spawn("local", None, |_| async { panic!("boom") })
+            .await
+            .unwrap();
+
+        let failed = wait_for_terminal(&store, &failed.task.task_id).await;
+        assert_eq!(failed.task.status, TaskStatus::Failed);
+        assert!(failed
+            .task
+            .status_message
+            .unwrap()
+            .contains("upstream unavailable"));
+
+        let panicked = wait_for_terminal(&store, &panicked.task.task_id).await;
+        assert_eq!(panicked.task.status, TaskStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_bounded() {
+        let store = store();
+        let worker = TaskWorker::new(store.clone(), 2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let mut ids = Vec::new();
+        for _ in 0..6 {
+            let running = running.clone();
+            let peak = peak.clone();
+            let record = worker
+                .spawn("local", None, move |_| async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(json!(null))
+                })
+                .await
+                .unwrap();
+            ids.push(record.task.task_id);
+        }
+
+        for id in &ids {
+            wait_for_terminal(&store, id).await;
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn spawn_task_uses_registered_worker() {
+        let store = store();
+        let mut extensions = pmcp::server::extensions::Extensions::new();
+        extensions.insert(TaskWorker::new(store.clone(), 1));
+        let extra = RequestHandlerExtra::new("req-1".to_string(), CancellationToken::new())
+            .with_extensions(extensions);
+
+        let task = extra.spawn_task(async { Ok(json!("ok")) }).await.unwrap();
+        let task_id = task["taskId"].as_str().unwrap();
+        assert_eq!(task["status"], "working");
+
+        let done = wait_for_terminal(&store, task_id).await;
+        assert_eq!(done.result, Some(json!("ok")));
+
+        let missing = RequestHandlerExtra::new("req-2".to_string(), CancellationToken::new())
+            .spawn_task(async { Ok(json!(null)) })
+            .await;
+        assert!(missing.is_err());
+    }
+}
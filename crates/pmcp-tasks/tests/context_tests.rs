@@ -368,6 +368,73 @@ mod transition_tests {
     }
 }
 
+mod progress_tests {
+    use super::*;
+    use pmcp_tasks::types::progress::{TaskStepStatus, TASK_PROGRESS_KEY};
+
+    #[tokio::test]
+    async fn test_report_progress_keeps_steps() {
+        let (_store, ctx) = create_store_and_context().await;
+        assert!(ctx.progress().await.unwrap().is_none());
+
+        ctx.update_step("download", TaskStepStatus::Completed, None)
+            .await
+            .unwrap();
+        ctx.update_step("import", TaskStepStatus::Running, None)
+            .await
+            .unwrap();
+        ctx.report_progress(50, Some(200), Some("Importing rows".to_string()))
+            .await
+            .unwrap();
+
+        let progress = ctx.progress().await.unwrap().unwrap();
+        assert_eq!(progress.current, 50);
+        assert_eq!(progress.percent(), Some(25.0));
+        assert_eq!(progress.message.as_deref(), Some("Importing rows"));
+        assert_eq!(progress.steps.len(), 2);
+        assert_eq!(
+            progress.step("import").unwrap().status,
+            TaskStepStatus::Running
+        );
+
+        ctx.update_step(
+            "import",
+            TaskStepStatus::Failed,
+            Some("bad row 17".to_string()),
+        )
+        .await
+        .unwrap();
+        let progress = ctx.progress().await.unwrap().unwrap();
+        assert_eq!(progress.current, 50);
+        assert_eq!(
+            progress.step("import").unwrap().message.as_deref(),
+            Some("bad row 17")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_in_wire_task_and_notification() {
+        let (_store, ctx) = create_store_and_context().await;
+        ctx.report_progress(3, Some(4), None).await.unwrap();
+
+        let record = ctx.get().await.unwrap();
+        let wire = record.to_wire_task_with_variables();
+        assert_eq!(wire._meta.unwrap()[TASK_PROGRESS_KEY]["current"], 3);
+
+        ctx.update_step("verify", TaskStepStatus::Running, None)
+            .await
+            .unwrap();
+        let notification = ctx.get().await.unwrap().to_status_notification();
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["taskId"], ctx.task_id());
+        assert_eq!(json["_meta"][TASK_PROGRESS_KEY]["total"], 4);
+        assert_eq!(
+            json["_meta"][TASK_PROGRESS_KEY]["steps"][0]["name"],
+            "verify"
+        );
+    }
+}
+
 mod identity_tests {
     use super::*;
     use pretty_assertions::assert_eq;
@@ -454,3 +521,116 @@ mod identity_tests {
         assert!(matches!(result.unwrap_err(), TaskError::NotFound { .. }));
     }
 }
+
+mod input_tests {
+    use super::*;
+    use std::time::Duration;
+
+    use pmcp::server::tasks::TaskRouter;
+    use pmcp_tasks::router::TaskRouterImpl;
+    use pmcp_tasks::types::input::{ElicitRequestParams, TASK_INPUT_REQUEST_KEY};
+
+    fn region_request() -> ElicitRequestParams {
+        ElicitRequestParams::Form {
+            message: "Which region should be used?".to_string(),
+            requested_schema: json!({
+                "type": "object",
+                "properties": {"region": {"type": "string"}},
+                "required": ["region"]
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_input_round_trip_through_router() {
+        let (store, ctx) = create_store_and_context().await;
+        let router = TaskRouterImpl::with_security(
+            store.clone(),
+            TaskSecurityConfig::default().with_allow_anonymous(true),
+        );
+
+        let waiting = ctx.request_input(region_request()).await.unwrap();
+        assert_eq!(waiting.task.status, TaskStatus::InputRequired);
+        assert_eq!(
+            waiting.task.status_message.as_deref(),
+            Some("Which region should be used?")
+        );
+
+        // The client sees the elicitation in tasks/get
+        let task = router
+            .handle_tasks_get(json!({"taskId": ctx.task_id()}), "test-owner")
+            .await
+            .unwrap();
+        assert_eq!(
+            task["_meta"][TASK_INPUT_REQUEST_KEY]["requestedSchema"]["required"][0],
+            "region"
+        );
+
+        let waiter = {
+            let ctx = ctx.clone();
+            tokio::spawn(async move { ctx.wait_for_input(Duration::from_millis(5)).await })
+        };
+
+        // Missing required fields are rejected and the task keeps waiting
+        let err = router
+            .handle_tasks_provide_input(
+                json!({"taskId": ctx.task_id(), "action": "accept", "content": {}}),
+                "test-owner",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("region"));
+
+        let resumed = router
+            .handle_tasks_provide_input(
+                json!({
+                    "taskId": ctx.task_id(),
+                    "action": "accept",
+                    "content": {"region": "eu-west-1"}
+                }),
+                "test-owner",
+            )
+            .await
+            .unwrap();
+        assert_eq!(resumed["status"], "working");
+
+        let answer = waiter.await.unwrap().unwrap();
+        assert!(answer.is_accepted());
+        assert_eq!(answer.content.unwrap()["region"], "eu-west-1");
+        assert!(ctx.input_request().await.unwrap().is_none());
+        assert!(ctx.take_input().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_provide_input_requires_input_required_status() {
+        let (store, ctx) = create_store_and_context().await;
+        let router = TaskRouterImpl::with_security(
+            store,
+            TaskSecurityConfig::default().with_allow_anonymous(true),
+        );
+
+        let result = router
+            .handle_tasks_provide_input(
+                json!({"taskId": ctx.task_id(), "action": "decline"}),
+                "test-owner",
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_input_stops_when_cancelled() {
+        let (_store, ctx) = create_store_and_context().await;
+        ctx.request_input(region_request()).await.unwrap();
+        ctx.cancel().await.unwrap();
+
+        let result = ctx.wait_for_input(Duration::from_millis(5)).await;
+        assert!(matches!(
+            result,
+            Err(TaskError::InvalidTransition {
+                from: TaskStatus::Cancelled,
+                ..
+            })
+        ));
+    }
+}
@@ -6,6 +6,7 @@
 
 use async_trait::async_trait;
 use pmcp::server::builder::ServerCoreBuilder;
+use pmcp::server::cancellation::with_request_session;
 use pmcp::server::core::ProtocolHandler;
 use pmcp::types::jsonrpc::ResponsePayload;
 use pmcp::types::tasks::{
@@ -15,7 +16,10 @@ use pmcp::types::tools::{TaskSupport, ToolExecution};
 use pmcp::types::{CallToolRequest, ClientRequest, Request, RequestId, ToolInfo};
 use pmcp::RequestHandlerExtra;
 use pmcp_tasks::task::TaskStatus;
-use pmcp_tasks::{InMemoryTaskStore, TaskRouterImpl, TaskSecurityConfig, TaskStore};
+use pmcp_tasks::{
+    InMemoryTaskStore, OwnerStrategy, TaskRouterConfig, TaskRouterImpl, TaskSecurityConfig,
+    TaskStore,
+};
 use serde_json::{json, Value};
 use std::sync::Arc;
 
@@ -608,3 +612,54 @@ async fn task_call_stores_tool_context_variables() {
         "should store arguments variable"
     );
 }
+
+// --------------------------------------------------------------------------
+// Test: Session owner strategy keeps each transport session's tasks apart
+// --------------------------------------------------------------------------
+
+#[tokio::test]
+async fn session_strategy_gives_each_session_its_own_tasks() {
+    let store = Arc::new(
+        InMemoryTaskStore::new()
+            .with_security(TaskSecurityConfig::default().with_allow_anonymous(true)),
+    );
+    let router = Arc::new(
+        TaskRouterImpl::new(store.clone())
+            .with_config(TaskRouterConfig::default().with_owner_strategy(OwnerStrategy::SessionId)),
+    );
+    let server = ServerCoreBuilder::new()
+        .name("test-tasks")
+        .version("1.0.0")
+        .tool(
+            "long_running_tool",
+            TestTool {
+                store: store.clone(),
+            },
+        )
+        .with_task_store(router)
+        .stateless_mode(true)
+        .build()
+        .unwrap();
+
+    let req = task_call_request("long_running_tool", json!({}), json!({ "ttl": 60000 }));
+    let response = with_request_session(
+        Some("session-a".to_string()),
+        server.handle_request(RequestId::from(1i64), req, None),
+    )
+    .await;
+    let task_id = unwrap_result(response)["task"]["taskId"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(store.get(&task_id, "session-a").await.is_ok());
+
+    for (session, expected) in [("session-a", 1), ("session-b", 0)] {
+        let response = with_request_session(
+            Some(session.to_string()),
+            server.handle_request(RequestId::from(2i64), tasks_list_request(), None),
+        )
+        .await;
+        let tasks = unwrap_result(response)["tasks"].as_array().unwrap().len();
+        assert_eq!(tasks, expected, "tasks visible to {session}");
+    }
+}
@@ -408,7 +408,7 @@ fn test_task_result_params_round_trip() {
 
 #[test]
 fn test_task_list_params_cursor_omitted_when_none() {
-    let params = TaskListParams { cursor: None };
+    let params = TaskListParams::default();
     let json = serde_json::to_value(&params).unwrap();
     assert!(json.get("cursor").is_none());
 }
@@ -417,12 +417,27 @@ fn test_task_list_params_cursor_omitted_when_none() {
 fn test_task_list_params_cursor_included_when_some() {
     let params = TaskListParams {
         cursor: Some("page-2-token".to_string()),
+        ..Default::default()
     };
     let json = serde_json::to_value(&params).unwrap();
     assert_eq!(json["cursor"], "page-2-token");
 
     let back: TaskListParams = serde_json::from_value(json).unwrap();
     assert_eq!(back.cursor.as_deref(), Some("page-2-token"));
+    assert!(!back.all_owners);
+}
+
+#[test]
+fn test_task_list_params_all_owners() {
+    let params = TaskListParams {
+        all_owners: true,
+        ..Default::default()
+    };
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(json, json!({"allOwners": true}));
+
+    let back: TaskListParams = serde_json::from_value(json).unwrap();
+    assert!(back.all_owners);
 }
 
 #[test]
@@ -507,6 +522,7 @@ fn test_notification_camel_case_keys() {
         last_updated_at: "2025-11-25T10:35:00Z".to_string(),
         ttl: Some(60000),
         poll_interval: Some(3000),
+        _meta: None,
     };
 
     let json = serde_json::to_value(&notification).unwrap();
@@ -529,6 +545,7 @@ fn test_notification_ttl_null_not_omitted() {
         last_updated_at: "2025-11-25T10:30:00Z".to_string(),
         ttl: None,
         poll_interval: None,
+        _meta: None,
     };
 
     let json = serde_json::to_value(&notification).unwrap();
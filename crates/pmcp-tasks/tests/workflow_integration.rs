@@ -9,6 +9,7 @@
 //!   `GetPromptResult` without `_meta`
 //! - **INTG-04**: Full create-execute-handoff-continue-complete lifecycle
 //!   through real `ServerCore`, and cancel-with-result transitions to `Completed`
+//! - Resuming a paused workflow task on a fresh server from its checkpoint
 
 use async_trait::async_trait;
 use pmcp::server::builder::ServerCoreBuilder;
@@ -22,6 +23,7 @@ use pmcp::RequestHandlerExtra;
 use pmcp_tasks::{InMemoryTaskStore, TaskRouterImpl, TaskSecurityConfig, TaskStore};
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 // ---------------------------------------------------------------------------
@@ -166,6 +168,35 @@ impl pmcp::ToolHandler for StoreDataTool {
     }
 }
 
+/// A data-fetching tool that counts its calls (for resume tests).
+struct CountingFetchDataTool(Arc<AtomicUsize>);
+
+#[async_trait]
+impl pmcp::ToolHandler for CountingFetchDataTool {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        FetchDataTool.handle(args, extra).await
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        FetchDataTool.metadata()
+    }
+}
+
+/// A storage tool that always fails (to pause after the first two steps).
+struct FailingStoreDataTool;
+
+#[async_trait]
+impl pmcp::ToolHandler for FailingStoreDataTool {
+    async fn handle(&self, _args: Value, _extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+        Err(pmcp::Error::internal("storage unavailable"))
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        StoreDataTool.metadata()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Workflow builders
 // ---------------------------------------------------------------------------
@@ -291,6 +322,26 @@ fn build_conditional_test_server() -> (pmcp::server::core::ServerCore, Arc<InMem
     (server, store)
 }
 
+/// Build a task workflow server over an existing store, as after a restart.
+fn build_server_with_store(
+    store: Arc<InMemoryTaskStore>,
+    fetch: impl pmcp::ToolHandler + 'static,
+    store_tool: impl pmcp::ToolHandler + 'static,
+) -> pmcp::server::core::ServerCore {
+    ServerCoreBuilder::new()
+        .name("test-workflow-resume")
+        .version("1.0.0")
+        .tool("fetch_data", fetch)
+        .tool("transform_data", TransformDataTool)
+        .tool("store_data", store_tool)
+        .with_task_store(Arc::new(TaskRouterImpl::new(store)))
+        .prompt_workflow(task_workflow())
+        .expect("task workflow should register")
+        .stateless_mode(true)
+        .build()
+        .expect("server should build")
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -786,3 +837,82 @@ async fn test_continuation_with_task_id() {
         "task should still be working"
     );
 }
+
+// A paused workflow resumes from its checkpoint on a fresh server without
+// re-running completed steps.
+#[tokio::test]
+async fn test_resume_workflow_from_checkpoint() {
+    let store = Arc::new(
+        InMemoryTaskStore::new()
+            .with_security(TaskSecurityConfig::default().with_allow_anonymous(true)),
+    );
+
+    // First run: fetch and transform complete, store fails
+    let server = build_server_with_store(store.clone(), FetchDataTool, FailingStoreDataTool);
+    let args = HashMap::from([("source".to_string(), "endpoint".to_string())]);
+    let response = server
+        .handle_request(
+            RequestId::from(1i64),
+            get_prompt_request("data_pipeline", args.clone()),
+            None,
+        )
+        .await;
+    let result = unwrap_result(response);
+    let task_id = result["_meta"]["task_id"]
+        .as_str()
+        .expect("should have task_id")
+        .to_string();
+    assert_eq!(result["_meta"]["task_status"], "working");
+
+    let record = store.get(&task_id, "local").await.unwrap();
+    assert_eq!(
+        record.variables["_workflow.binding.raw_data"]["source"],
+        "endpoint"
+    );
+    assert_eq!(
+        record.variables["_workflow.binding.transformed"]["transformed"],
+        true
+    );
+    assert!(record.variables.contains_key("_workflow.pause_reason"));
+
+    // Second run on a new server: only the store step executes
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let server = build_server_with_store(
+        store.clone(),
+        CountingFetchDataTool(fetches.clone()),
+        StoreDataTool,
+    );
+    let req = Request::Client(Box::new(ClientRequest::GetPrompt(GetPromptRequest {
+        name: "data_pipeline".to_string(),
+        arguments: args,
+        _meta: Some(RequestMeta::new().with_task_id(task_id.as_str())),
+    })));
+    let response = server
+        .handle_request(RequestId::from(2i64), req, None)
+        .await;
+    let result = unwrap_result(response);
+
+    assert_eq!(fetches.load(Ordering::SeqCst), 0, "fetch should not re-run");
+    assert_eq!(result["_meta"]["task_id"], task_id.as_str());
+    assert_eq!(result["_meta"]["task_status"], "completed");
+    let text = serde_json::to_string(&result["messages"]).unwrap();
+    assert!(text.contains("Resuming workflow: 2 of 3 steps already completed."));
+
+    let record = store.get(&task_id, "local").await.unwrap();
+    assert_eq!(record.task.status, pmcp_tasks::task::TaskStatus::Completed);
+    assert!(!record.variables.contains_key("_workflow.pause_reason"));
+
+    // A completed task cannot be resumed again
+    let req = Request::Client(Box::new(ClientRequest::GetPrompt(GetPromptRequest {
+        name: "data_pipeline".to_string(),
+        arguments: HashMap::new(),
+        _meta: Some(RequestMeta::new().with_task_id(task_id.as_str())),
+    })));
+    let response = server
+        .handle_request(RequestId::from(3i64), req, None)
+        .await;
+    match response.payload {
+        ResponsePayload::Error(e) => assert!(e.message.contains("cannot be resumed")),
+        ResponsePayload::Result(v) => panic!("expected error, got: {v}"),
+    }
+}
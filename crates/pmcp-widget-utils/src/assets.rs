@@ -0,0 +1,672 @@
+//! Local asset inlining and fingerprinting for widget HTML.
+//!
+//! Widgets are served as a single MCP resource, so stylesheets, scripts, and
+//! images next to the HTML file are not reachable from the host's iframe.
+//! These functions resolve local references relative to a base directory and
+//! either:
+//!
+//! - **inline** them: stylesheets become `<style>` blocks, scripts become
+//!   inline `<script>` blocks, and everything else (images, fonts, icons)
+//!   becomes a `data:` URI; or
+//! - **fingerprint** them: references are rewritten to content-hashed URIs
+//!   such as `ui://app/assets/logo.3f9a1c2e.png`, and the assets are returned
+//!   so the server can register each one as a resource.
+//!
+//! Handled references: `<link href>` (stylesheets, icons, preloads),
+//! `<script src>`, `src`/`poster` on media elements, and `url(...)` inside
+//! `<style>` blocks and linked stylesheets (resolved relative to the
+//! stylesheet). Absolute paths (`/assets/...`), remote URLs, `data:` URIs,
+//! and fragments are left untouched. Scripts are inlined as-is; bundle
+//! module imports before inlining.
+
+use std::path::{Path, PathBuf};
+
+/// A local asset referenced by widget HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WidgetAsset {
+    /// URI the reference was rewritten to (`data:` URI when inlining).
+    pub uri: String,
+    /// File the asset was read from.
+    pub path: PathBuf,
+    /// MIME type guessed from the file extension.
+    pub mime_type: &'static str,
+    /// Asset content (stylesheets with their `url(...)` references rewritten).
+    pub content: Vec<u8>,
+}
+
+/// Result of [`inline_assets`] or [`fingerprint_assets`].
+#[derive(Debug, Clone, Default)]
+pub struct BundledWidget {
+    /// Rewritten widget HTML.
+    pub html: String,
+    /// Assets to register as resources. Empty when inlining.
+    pub assets: Vec<WidgetAsset>,
+    /// Local references that could not be read; they are left unchanged.
+    pub missing: Vec<String>,
+}
+
+/// Inline local CSS, JS, and other assets into widget HTML.
+///
+/// References are resolved relative to `base_dir` (usually the directory
+/// of the HTML file).
+///
+/// # Example
+///
+/// ```no_run
+/// use pmcp_widget_utils::inline_assets;
+/// use std::path::Path;
+///
+/// let html = std::fs::read_to_string("widgets/board.html").unwrap();
+/// let bundled = inline_assets(&html, Path::new("widgets"));
+/// for reference in &bundled.missing {
+///     eprintln!("missing widget asset: {reference}");
+/// }
+/// ```
+pub fn inline_assets(html: &str, base_dir: &Path) -> BundledWidget {
+    Bundler::new(base_dir, None).run(html)
+}
+
+/// Rewrite local asset references to fingerprinted URIs under `uri_prefix`.
+///
+/// Each asset is named `<stem>.<hash>.<ext>`, where `hash` is derived from its
+/// content, so the URI changes whenever the file does and hosts can cache
+/// assets indefinitely. Register every returned [`WidgetAsset`] as a resource
+/// at its `uri`.
+///
+/// # Example
+///
+/// ```
+/// use pmcp_widget_utils::fingerprint_assets;
+/// use std::path::Path;
+///
+/// let bundled = fingerprint_assets(
+///     r#"<img src="https://example.com/remote.png">"#,
+///     Path::new("widgets"),
+///     "ui://app/assets",
+/// );
+/// // Remote references are left alone
+/// assert!(bundled.assets.is_empty());
+/// ```
+pub fn fingerprint_assets(html: &str, base_dir: &Path, uri_prefix: &str) -> BundledWidget {
+    Bundler::new(base_dir, Some(uri_prefix.trim_end_matches('/'))).run(html)
+}
+
+/// Encode bytes as a `data:` URI.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(
+///     pmcp_widget_utils::data_uri("text/plain", b"hi"),
+///     "data:text/plain;base64,aGk="
+/// );
+/// ```
+pub fn data_uri(mime_type: &str, content: &[u8]) -> String {
+    format!("data:{};base64,{}", mime_type, base64_encode(content))
+}
+
+/// Guess a MIME type from a file extension.
+pub fn mime_type_for(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "html" | "htm" => "text/html",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "wasm" => "application/wasm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Elements whose `src`/`poster` attributes are rewritten.
+const MEDIA_ELEMENTS: [&str; 7] = ["img", "source", "video", "audio", "track", "input", "embed"];
+
+struct Bundler<'a> {
+    base_dir: &'a Path,
+    /// `None` inlines assets; `Some(prefix)` fingerprints them.
+    prefix: Option<&'a str>,
+    assets: Vec<WidgetAsset>,
+    missing: Vec<String>,
+}
+
+impl<'a> Bundler<'a> {
+    fn new(base_dir: &'a Path, prefix: Option<&'a str>) -> Self {
+        Self {
+            base_dir,
+            prefix,
+            assets: Vec::new(),
+            missing: Vec::new(),
+        }
+    }
+
+    fn run(mut self, html: &str) -> BundledWidget {
+        let html = self.html(html);
+        BundledWidget {
+            html,
+            assets: self.assets,
+            missing: self.missing,
+        }
+    }
+
+    fn html(&mut self, html: &str) -> String {
+        let base_dir = self.base_dir;
+        let mut out = String::with_capacity(html.len());
+        let mut rest = html;
+
+        while let Some(lt) = rest.find('<') {
+            out.push_str(&rest[..lt]);
+            rest = &rest[lt..];
+
+            if rest.starts_with("<!--") {
+                let end = rest.find("-->").map_or(rest.len(), |i| i + 3);
+                out.push_str(&rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+            let Some(tag) = Tag::parse(rest) else {
+                out.push('<');
+                rest = &rest[1..];
+                continue;
+            };
+            rest = &rest[tag.source.len()..];
+
+            match tag.name.as_str() {
+                "script" => {
+                    let close = find_ignore_case(rest, "</script").unwrap_or(rest.len());
+                    let body = &rest[..close];
+                    rest = &rest[close..];
+                    match tag.attr("src").filter(|src| is_local(src)) {
+                        Some(src) if self.prefix.is_none() => match self.read(base_dir, src) {
+                            Some((_, content)) => {
+                                out.push_str(&tag.without("src"));
+                                out.push_str(
+                                    &String::from_utf8_lossy(&content)
+                                        .replace("</script", "<\\/script"),
+                                );
+                            },
+                            None => {
+                                out.push_str(tag.source);
+                                out.push_str(body);
+                            },
+                        },
+                        Some(src) => {
+                            out.push_str(&self.rewrite_attr(&tag, "src", src, base_dir));
+                            out.push_str(body);
+                        },
+                        None => {
+                            out.push_str(tag.source);
+                            out.push_str(body);
+                        },
+                    }
+                },
+                "style" => {
+                    let close = find_ignore_case(rest, "</style").unwrap_or(rest.len());
+                    out.push_str(tag.source);
+                    out.push_str(&self.css(&rest[..close], base_dir));
+                    rest = &rest[close..];
+                },
+                "link" => {
+                    let is_stylesheet = tag
+                        .attr("rel")
+                        .is_some_and(|rel| rel.to_ascii_lowercase().contains("stylesheet"));
+                    match tag.attr("href").filter(|href| is_local(href)) {
+                        Some(href) if is_stylesheet => out.push_str(&self.stylesheet(&tag, href)),
+                        Some(href) => {
+                            out.push_str(&self.rewrite_attr(&tag, "href", href, base_dir))
+                        },
+                        None => out.push_str(tag.source),
+                    }
+                },
+                name if MEDIA_ELEMENTS.contains(&name) => {
+                    let mut source = tag.source.to_string();
+                    for attr in ["src", "poster"] {
+                        let tag = Tag::parse(&source).expect("re-parsing a parsed tag");
+                        if let Some(value) = tag.attr(attr).filter(|v| is_local(v)) {
+                            source = self.rewrite_attr(&tag, attr, value, base_dir);
+                        }
+                    }
+                    out.push_str(&source);
+                },
+                _ => out.push_str(tag.source),
+            }
+        }
+
+        out.push_str(rest);
+        out
+    }
+
+    /// Inline a linked stylesheet, or rewrite its `href` to a fingerprinted URI.
+    fn stylesheet(&mut self, tag: &Tag<'_>, href: &str) -> String {
+        let Some((path, content)) = self.read(self.base_dir, href) else {
+            return tag.source.to_string();
+        };
+        let css_dir = path.parent().unwrap_or(self.base_dir).to_path_buf();
+        let css = self.css(&String::from_utf8_lossy(&content), &css_dir);
+
+        match self.prefix {
+            None => {
+                let media = tag
+                    .attr("media")
+                    .map(|media| format!(" media=\"{}\"", media))
+                    .unwrap_or_default();
+                format!(
+                    "<style{}>\n{}\n</style>",
+                    media,
+                    css.replace("</style", "<\\/style")
+                )
+            },
+            Some(_) => {
+                let uri = self.register(path, css.into_bytes());
+                tag.with("href", &uri)
+            },
+        }
+    }
+
+    /// Rewrite `url(...)` references in CSS resolved relative to `dir`.
+    fn css(&mut self, css: &str, dir: &Path) -> String {
+        let mut out = String::with_capacity(css.len());
+        let mut rest = css;
+
+        while let Some(start) = find_ignore_case(rest, "url(") {
+            let open = start + 4;
+            let Some(len) = rest[open..].find(')') else {
+                break;
+            };
+            let inner = &rest[open..open + len];
+            let reference = inner.trim().trim_matches(|c| c == '"' || c == '\'');
+
+            out.push_str(&rest[..open]);
+            match self.asset_uri(dir, reference) {
+                Some(uri) => out.push_str(&format!("\"{}\"", uri)),
+                None => out.push_str(inner),
+            }
+            out.push(')');
+            rest = &rest[open + len + 1..];
+        }
+
+        out.push_str(rest);
+        out
+    }
+
+    /// Replace an attribute value with the asset's URI, if the asset exists.
+    fn rewrite_attr(&mut self, tag: &Tag<'_>, name: &str, reference: &str, dir: &Path) -> String {
+        match self.asset_uri(dir, reference) {
+            Some(uri) => tag.with(name, &uri),
+            None => tag.source.to_string(),
+        }
+    }
+
+    /// URI for a local reference: a `data:` URI or a fingerprinted URI.
+    fn asset_uri(&mut self, dir: &Path, reference: &str) -> Option<String> {
+        if !is_local(reference) {
+            return None;
+        }
+        let (path, content) = self.read(dir, reference)?;
+        Some(match self.prefix {
+            None => data_uri(mime_type_for(&path), &content),
+            Some(_) => self.register(path, content),
+        })
+    }
+
+    /// Record a fingerprinted asset and return its URI.
+    fn register(&mut self, path: PathBuf, content: Vec<u8>) -> String {
+        let prefix = self.prefix.unwrap_or_default();
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("asset");
+        let hash = (fnv1a(&content) >> 32) as u32;
+        let uri = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{}/{}.{:08x}.{}", prefix, stem, hash, ext),
+            None => format!("{}/{}.{:08x}", prefix, stem, hash),
+        };
+
+        if !self.assets.iter().any(|asset| asset.uri == uri) {
+            self.assets.push(WidgetAsset {
+                uri: uri.clone(),
+                mime_type: mime_type_for(&path),
+                path,
+                content,
+            });
+        }
+        uri
+    }
+
+    /// Read a local reference relative to `dir`, recording it as missing on failure.
+    fn read(&mut self, dir: &Path, reference: &str) -> Option<(PathBuf, Vec<u8>)> {
+        let file = reference.split(['?', '#']).next().unwrap_or(reference);
+        let path = dir.join(file);
+        match std::fs::read(&path) {
+            Ok(content) => Some((path, content)),
+            Err(_) => {
+                if !self.missing.iter().any(|m| m == reference) {
+                    self.missing.push(reference.to_string());
+                }
+                None
+            },
+        }
+    }
+}
+
+/// Whether a reference points at a file relative to the widget.
+fn is_local(reference: &str) -> bool {
+    let reference = reference.trim();
+    !(reference.is_empty()
+        || reference.starts_with('#')
+        || reference.starts_with('/')
+        || reference.starts_with('{')
+        || reference.contains(':'))
+}
+
+/// A parsed start tag.
+pub(crate) struct Tag<'a> {
+    /// Lowercased element name.
+    pub(crate) name: String,
+    /// The tag as written, from `<` through `>`.
+    pub(crate) source: &'a str,
+    attrs: Vec<Attr>,
+}
+
+/// An attribute in [`Tag::source`].
+struct Attr {
+    /// Lowercased attribute name.
+    name: String,
+    /// Byte range of the whole attribute, including its value.
+    span: (usize, usize),
+    /// Byte range of the value, including any quotes.
+    value: Option<(usize, usize)>,
+}
+
+impl<'a> Tag<'a> {
+    /// Parse a start tag at the beginning of `html`.
+    pub(crate) fn parse(html: &'a str) -> Option<Self> {
+        let bytes = html.as_bytes();
+        if bytes.first() != Some(&b'<') || !bytes.get(1)?.is_ascii_alphabetic() {
+            return None;
+        }
+
+        let mut i = 1;
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+            i += 1;
+        }
+        let name = html[1..i].to_ascii_lowercase();
+        let mut attrs = Vec::new();
+
+        loop {
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            match bytes.get(i)? {
+                b'>' => break,
+                b'/' => {
+                    i += 1;
+                    continue;
+                },
+                _ => {},
+            }
+
+            let start = i;
+            while i < bytes.len()
+                && !bytes[i].is_ascii_whitespace()
+                && !matches!(bytes[i], b'=' | b'>' | b'/')
+            {
+                i += 1;
+            }
+            let attr_name = html[start..i].to_ascii_lowercase();
+
+            let mut j = i;
+            while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                j += 1;
+            }
+            let mut value = None;
+            if bytes.get(j) == Some(&b'=') {
+                j += 1;
+                while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                let value_start = j;
+                match bytes.get(j)? {
+                    quote @ (b'"' | b'\'') => {
+                        let len = html[j + 1..].find(*quote as char)?;
+                        j += len + 2;
+                    },
+                    _ => {
+                        while j < bytes.len() && !bytes[j].is_ascii_whitespace() && bytes[j] != b'>'
+                        {
+                            j += 1;
+                        }
+                    },
+                }
+                value = Some((value_start, j));
+                i = j;
+            }
+
+            attrs.push(Attr {
+                name: attr_name,
+                span: (start, i),
+                value,
+            });
+        }
+
+        Some(Self {
+            name,
+            source: &html[..=i],
+            attrs,
+        })
+    }
+
+    /// Unquoted value of an attribute.
+    pub(crate) fn attr(&self, name: &str) -> Option<&'a str> {
+        let (start, end) = self.attrs.iter().find(|a| a.name == name)?.value?;
+        let value = &self.source[start..end];
+        Some(
+            value
+                .strip_prefix(['"', '\''])
+                .and_then(|v| v.strip_suffix(['"', '\'']))
+                .unwrap_or(value),
+        )
+    }
+
+    /// The tag with an attribute's value replaced.
+    fn with(&self, name: &str, value: &str) -> String {
+        match self
+            .attrs
+            .iter()
+            .find(|a| a.name == name)
+            .and_then(|a| a.value)
+        {
+            Some((start, end)) => format!(
+                "{}\"{}\"{}",
+                &self.source[..start],
+                value.replace('"', "&quot;"),
+                &self.source[end..]
+            ),
+            None => self.source.to_string(),
+        }
+    }
+
+    /// The tag with attributes appended before its closing `>` (or `/>`).
+    pub(crate) fn with_added(&self, attrs: &[(&str, &str)]) -> String {
+        let close = self.source.len() - 1;
+        // A trailing `/` inside an unquoted value is not a self-closing slash
+        let self_closing = self.source[..close].ends_with('/')
+            && self.attrs.last().is_none_or(|a| a.span.1 < close);
+        let end = if self_closing { close - 1 } else { close };
+
+        let mut out = self.source[..end].trim_end().to_string();
+        for (name, value) in attrs {
+            out.push_str(&format!(" {}=\"{}\"", name, value.replace('"', "&quot;")));
+        }
+        if self_closing {
+            out.push_str(" /");
+        }
+        out.push('>');
+        out
+    }
+
+    /// The tag with an attribute removed.
+    fn without(&self, name: &str) -> String {
+        match self.attrs.iter().find(|a| a.name == name) {
+            Some(attr) => {
+                let before = self.source[..attr.span.0].trim_end();
+                format!("{}{}", before, &self.source[attr.span.1..])
+            },
+            None => self.source.to_string(),
+        }
+    }
+}
+
+/// Case-insensitive ASCII substring search.
+pub(crate) fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// 64-bit FNV-1a hash, used for asset fingerprints.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Standard base64 with padding.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn widget_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("img")).unwrap();
+        fs::write(dir.path().join("img/logo.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        fs::write(
+            dir.path().join("app.css"),
+            "body { background: url('img/logo.png'); }",
+        )
+        .unwrap();
+        fs::write(dir.path().join("app.js"), "console.log('</script>');").unwrap();
+        dir
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn inlines_stylesheets_scripts_and_images() {
+        let dir = widget_dir();
+        let html = r#"<html><head><link rel="stylesheet" href="app.css" media="screen"></head>
+<body><img alt="logo" src="img/logo.png"><script type="module" src="app.js"></script></body></html>"#;
+
+        let bundled = inline_assets(html, dir.path());
+
+        assert!(bundled.missing.is_empty());
+        assert!(bundled.assets.is_empty());
+        assert!(bundled.html.contains("<style media=\"screen\">"));
+        assert!(bundled
+            .html
+            .contains("background: url(\"data:image/png;base64,iVBORw==\")"));
+        assert!(bundled
+            .html
+            .contains(r#"<img alt="logo" src="data:image/png;base64,iVBORw==">"#));
+        assert!(bundled
+            .html
+            .contains("<script type=\"module\">console.log('<\\/script>');</script>"));
+        assert!(!bundled.html.contains("app.css"));
+    }
+
+    #[test]
+    fn leaves_remote_absolute_and_missing_references() {
+        let dir = widget_dir();
+        let html = concat!(
+            r#"<script src="/assets/widget-runtime.mjs"></script>"#,
+            r#"<img src="https://example.com/a.png"><img src="data:image/png;base64,AA==">"#,
+            r#"<link rel="stylesheet" href="missing.css"><!-- <img src="img/logo.png"> -->"#,
+        );
+
+        let bundled = inline_assets(html, dir.path());
+
+        assert_eq!(bundled.html, html);
+        assert_eq!(bundled.missing, vec!["missing.css".to_string()]);
+    }
+
+    #[test]
+    fn fingerprints_assets_with_content_hash() {
+        let dir = widget_dir();
+        let html = r#"<link rel="stylesheet" href="app.css"><img src='img/logo.png?v=1'>"#;
+
+        let bundled = fingerprint_assets(html, dir.path(), "ui://app/assets/");
+
+        let uris: Vec<&str> = bundled.assets.iter().map(|a| a.uri.as_str()).collect();
+        assert_eq!(uris.len(), 2);
+        let logo = uris.iter().find(|u| u.contains("/logo.")).unwrap();
+        let css = uris.iter().find(|u| u.ends_with(".css")).unwrap();
+        assert!(logo.starts_with("ui://app/assets/logo.") && logo.ends_with(".png"));
+        assert!(bundled.html.contains(&format!("href=\"{}\"", css)));
+        assert!(bundled.html.contains(&format!("src=\"{}\"", logo)));
+
+        // The stylesheet's own url() points at the fingerprinted image
+        let css_asset = bundled.assets.iter().find(|a| a.uri == *css).unwrap();
+        assert_eq!(css_asset.mime_type, "text/css");
+        assert!(String::from_utf8_lossy(&css_asset.content).contains(logo));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_content() {
+        let dir = widget_dir();
+        let html = r#"<img src="img/logo.png">"#;
+        let before = fingerprint_assets(html, dir.path(), "ui://app").assets[0]
+            .uri
+            .clone();
+
+        fs::write(dir.path().join("img/logo.png"), b"changed").unwrap();
+        let after = fingerprint_assets(html, dir.path(), "ui://app").assets[0]
+            .uri
+            .clone();
+
+        assert_ne!(before, after);
+    }
+}
@@ -2,6 +2,25 @@
 //!
 //! Provides common HTML manipulation functions used by both the core `pmcp` crate
 //! and the `mcp-preview` crate, eliminating code duplication.
+//!
+//! - [`inject_bridge_script`] adds the widget runtime bridge to widget HTML.
+//! - [`inline_assets`] and [`fingerprint_assets`] turn a widget and the local
+//!   CSS/JS/images it references into a single self-contained resource, or into
+//!   HTML plus content-hashed `ui://` assets.
+//! - [`render_widget_template`] and [`json_script`] inject server-side data
+//!   (such as initial widget state) into widget HTML.
+//! - [`add_subresource_integrity`] pins CDN scripts and stylesheets with
+//!   `integrity` hashes.
+
+mod assets;
+mod sri;
+mod template;
+
+pub use assets::{
+    data_uri, fingerprint_assets, inline_assets, mime_type_for, BundledWidget, WidgetAsset,
+};
+pub use sri::{add_subresource_integrity, external_resources, sri_hash};
+pub use template::{json_script, render_widget_template, script_safe_json};
 
 /// Inject a bridge script tag into widget HTML.
 ///
@@ -0,0 +1,206 @@
+//! Subresource Integrity (SRI) for scripts and stylesheets loaded from CDNs.
+//!
+//! Widgets often pull libraries such as Chart.js from a CDN. Adding an
+//! `integrity` attribute makes the browser refuse a file whose content has
+//! changed, so a compromised CDN cannot inject code into the widget.
+//!
+//! Handled tags: `<script src>` and `<link href>` with `rel` `stylesheet`,
+//! `preload`, or `modulepreload`, when the URL is remote (`https://`,
+//! `http://`, or protocol-relative `//`). Tags that already carry an
+//! `integrity` attribute are left alone. `crossorigin="anonymous"` is added
+//! when missing, because browsers only check integrity on CORS requests.
+
+use sha2::{Digest, Sha384};
+
+use crate::assets::{base64_encode, find_ignore_case, Tag};
+
+/// Compute an SRI value (`sha384-<base64>`) for a resource's content.
+///
+/// # Example
+///
+/// ```
+/// use pmcp_widget_utils::sri_hash;
+///
+/// assert_eq!(
+///     sri_hash(b"alert('Hello, world.');"),
+///     "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+/// );
+/// ```
+pub fn sri_hash(content: &[u8]) -> String {
+    format!("sha384-{}", base64_encode(&Sha384::digest(content)))
+}
+
+/// Remote script and stylesheet URLs in widget HTML that lack an `integrity`
+/// attribute, in document order and without duplicates.
+///
+/// Use this to find the files to download and hash with [`sri_hash`].
+pub fn external_resources(html: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    scan(html, |url| {
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+        None
+    });
+    urls
+}
+
+/// Add `integrity` and `crossorigin` attributes to remote scripts and stylesheets.
+///
+/// `integrity` is called with each remote URL and returns its SRI value
+/// (for example from a lock file, or by hashing a downloaded copy with
+/// [`sri_hash`]). Tags for which it returns `None` are left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use pmcp_widget_utils::add_subresource_integrity;
+///
+/// let html = r#"<script src="https://cdn.example.com/chart.js"></script>"#;
+/// let result = add_subresource_integrity(html, |url| {
+///     (url == "https://cdn.example.com/chart.js").then(|| "sha384-abc".to_string())
+/// });
+/// assert_eq!(
+///     result,
+///     r#"<script src="https://cdn.example.com/chart.js" integrity="sha384-abc" crossorigin="anonymous"></script>"#
+/// );
+/// ```
+pub fn add_subresource_integrity(
+    html: &str,
+    integrity: impl FnMut(&str) -> Option<String>,
+) -> String {
+    scan(html, integrity)
+}
+
+/// Walk the start tags in `html`, rewriting SRI-eligible tags for which
+/// `integrity` returns a value.
+fn scan(html: &str, mut integrity: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |i| i + 3);
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        let Some(tag) = Tag::parse(rest) else {
+            out.push('<');
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[tag.source.len()..];
+
+        let url = match tag.name.as_str() {
+            "script" => tag.attr("src"),
+            "link" if tag.attr("rel").is_some_and(is_sri_rel) => tag.attr("href"),
+            _ => None,
+        }
+        .map(str::trim)
+        .filter(|url| is_remote(url) && tag.attr("integrity").is_none());
+
+        match url.and_then(&mut integrity) {
+            Some(hash) => {
+                let mut attrs = vec![("integrity", hash.as_str())];
+                if tag.attr("crossorigin").is_none() {
+                    attrs.push(("crossorigin", "anonymous"));
+                }
+                out.push_str(&tag.with_added(&attrs));
+            },
+            None => out.push_str(tag.source),
+        }
+
+        // Script bodies are not markup
+        if tag.name == "script" {
+            let close = find_ignore_case(rest, "</script").unwrap_or(rest.len());
+            out.push_str(&rest[..close]);
+            rest = &rest[close..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn is_sri_rel(rel: &str) -> bool {
+    rel.split_ascii_whitespace().any(|r| {
+        ["stylesheet", "preload", "modulepreload"]
+            .iter()
+            .any(|k| r.eq_ignore_ascii_case(k))
+    })
+}
+
+fn is_remote(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://") || lower.starts_with("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = r#"<head>
+<link rel="stylesheet" href="https://cdn.example.com/app.css">
+<link rel="icon" href="https://cdn.example.com/favicon.ico">
+<link rel="stylesheet" href="local.css">
+<script src="//cdn.example.com/lib.js" crossorigin="use-credentials"></script>
+<script src="https://cdn.example.com/pinned.js" integrity="sha384-old"></script>
+<script>const s = '<script src="https://evil.example.com/x.js">';</script>
+<!-- <script src="https://cdn.example.com/commented.js"></script> -->
+</head>"#;
+
+    #[test]
+    fn sri_hash_matches_known_vector() {
+        // Example from the SRI specification
+        assert_eq!(
+            sri_hash(b"alert('Hello, world.');"),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+    }
+
+    #[test]
+    fn lists_remote_resources_without_integrity() {
+        assert_eq!(
+            external_resources(HTML),
+            vec![
+                "https://cdn.example.com/app.css".to_string(),
+                "//cdn.example.com/lib.js".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn adds_integrity_and_crossorigin() {
+        let result = add_subresource_integrity(HTML, |url| Some(format!("sha384-{}", url.len())));
+
+        assert!(result.contains(
+            r#"<link rel="stylesheet" href="https://cdn.example.com/app.css" integrity="sha384-31" crossorigin="anonymous">"#
+        ));
+        // An existing crossorigin attribute is kept
+        assert!(result.contains(
+            r#"<script src="//cdn.example.com/lib.js" crossorigin="use-credentials" integrity="sha384-24">"#
+        ));
+        assert!(result.contains(r#"integrity="sha384-old"></script>"#));
+        assert!(!result.contains("evil.example.com/x.js\" integrity"));
+        assert!(!result.contains("commented.js\" integrity"));
+        assert!(result.contains(r#"<link rel="icon" href="https://cdn.example.com/favicon.ico">"#));
+    }
+
+    #[test]
+    fn leaves_unresolved_urls_unchanged() {
+        assert_eq!(add_subresource_integrity(HTML, |_| None), HTML);
+    }
+
+    #[test]
+    fn keeps_self_closing_slash() {
+        let html = r#"<link rel="preload" href="https://cdn.example.com/font.woff2" />"#;
+        assert_eq!(
+            add_subresource_integrity(html, |_| Some("sha384-x".to_string())),
+            r#"<link rel="preload" href="https://cdn.example.com/font.woff2" integrity="sha384-x" crossorigin="anonymous" />"#
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Server-side data injection for widget HTML.
+//!
+//! Widgets can ship with initial state rendered in by the server, avoiding a
+//! tool round trip before the first paint. Placeholders use a small
+//! mustache-like syntax:
+//!
+//! | Placeholder | Output |
+//! |-------------|--------|
+//! | `{{ path }}` | Value as HTML-escaped text |
+//! | `{{ path \| json }}` | Value as JSON that is safe inside a `<script>` element |
+//! | `{{{ path }}}` | Value as raw, unescaped text (trusted data only) |
+//!
+//! Paths are dot-separated object keys and array indices (`game.moves.0`);
+//! `.` is the whole data value. Missing values and `null` render as an empty
+//! string (or `null` with `| json`). Unterminated placeholders are left as is.
+//!
+//! For state that widget scripts read, prefer a JSON data block over
+//! interpolating into JavaScript:
+//!
+//! ```html
+//! <script type="application/json" id="initial-data">{{ state | json }}</script>
+//! <script>
+//!   const state = JSON.parse(document.getElementById('initial-data').textContent);
+//! </script>
+//! ```
+
+use serde_json::Value;
+
+/// Render `{{ }}` placeholders in widget HTML with values from `data`.
+///
+/// # Example
+///
+/// ```
+/// use pmcp_widget_utils::render_widget_template;
+/// use serde_json::json;
+///
+/// let html = r#"<h1>{{ title }}</h1><script type="application/json" id="initial-data">{{ board | json }}</script>"#;
+/// let data = json!({ "title": "Chess <live>", "board": { "turn": "white" } });
+///
+/// assert_eq!(
+///     render_widget_template(html, &data),
+///     r#"<h1>Chess &lt;live&gt;</h1><script type="application/json" id="initial-data">{"turn":"white"}</script>"#
+/// );
+/// ```
+pub fn render_widget_template(html: &str, data: &Value) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let (raw, open, close) = if rest.starts_with("{{{") {
+            (true, "{{{", "}}}")
+        } else {
+            (false, "{{", "}}")
+        };
+        let Some(end) = rest[open.len()..].find(close) else {
+            break;
+        };
+        let expression = rest[open.len()..open.len() + end].trim();
+        rest = &rest[open.len() + end + close.len()..];
+
+        let (path, filter) = match expression.split_once('|') {
+            Some((path, filter)) => (path.trim(), Some(filter.trim())),
+            None => (expression, None),
+        };
+        let value = lookup(data, path);
+
+        match filter {
+            Some("json") => out.push_str(&script_safe_json(value.unwrap_or(&Value::Null))),
+            _ if raw => out.push_str(&to_text(value)),
+            _ => out.push_str(&escape_html(&to_text(value))),
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Build a `<script type="application/json">` element holding `data`.
+///
+/// The JSON is escaped so it cannot close the element or open a comment,
+/// whatever strings it contains.
+///
+/// # Example
+///
+/// ```
+/// use pmcp_widget_utils::json_script;
+/// use serde_json::json;
+///
+/// let tag = json_script("initial-data", &json!({ "note": "</script>" }));
+/// assert_eq!(
+///     tag,
+///     r#"<script type="application/json" id="initial-data">{"note":"\u003c/script\u003e"}</script>"#
+/// );
+/// ```
+pub fn json_script(id: &str, data: &Value) -> String {
+    format!(
+        r#"<script type="application/json" id="{}">{}</script>"#,
+        escape_html(id),
+        script_safe_json(data)
+    )
+}
+
+/// Serialize `value` as JSON that is safe to embed in an HTML `<script>`.
+///
+/// `<`, `>`, and `&` are written as `\u` escapes, as are U+2028 and U+2029,
+/// which are line terminators in older JavaScript engines. The result is
+/// still valid JSON and parses to the same value.
+pub fn script_safe_json(value: &Value) -> String {
+    let json = value.to_string();
+    let mut out = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => out.push_str("\\u003c"),
+            '>' => out.push_str("\\u003e"),
+            '&' => out.push_str("\\u0026"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Resolve a dot-separated path of object keys and array indices.
+fn lookup<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() || path == "." {
+        return Some(data);
+    }
+    path.split('.')
+        .try_fold(data, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Text form of a value: strings without quotes, `null` as empty, the rest as JSON.
+fn to_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_escaped_text() {
+        let data = json!({ "user": { "name": "<b>Ann & \"Bo\"</b>" }, "count": 3, "ok": true });
+        let html = "<p title=\"{{ user.name }}\">{{count}} / {{ ok }}</p>";
+        assert_eq!(
+            render_widget_template(html, &data),
+            "<p title=\"&lt;b&gt;Ann &amp; &quot;Bo&quot;&lt;/b&gt;\">3 / true</p>"
+        );
+    }
+
+    #[test]
+    fn renders_raw_triple_braces() {
+        let data = json!({ "snippet": "<em>hi</em>" });
+        assert_eq!(
+            render_widget_template("<div>{{{ snippet }}}</div>", &data),
+            "<div><em>hi</em></div>"
+        );
+    }
+
+    #[test]
+    fn resolves_array_indices_and_root() {
+        let data = json!({ "moves": ["e4", "e5"] });
+        assert_eq!(render_widget_template("{{ moves.1 }}", &data), "e5");
+        assert_eq!(
+            render_widget_template("{{ . | json }}", &data),
+            r#"{"moves":["e4","e5"]}"#
+        );
+    }
+
+    #[test]
+    fn missing_values_render_empty_or_null() {
+        let data = json!({ "a": null });
+        assert_eq!(
+            render_widget_template("[{{ a }}][{{ b.c }}]", &data),
+            "[][]"
+        );
+        assert_eq!(
+            render_widget_template("{{ missing | json }}", &data),
+            "null"
+        );
+    }
+
+    #[test]
+    fn json_filter_cannot_break_out_of_script() {
+        let data = json!({ "s": "</script><!-- \u{2028}" });
+        let rendered = render_widget_template("<script>{{ s | json }}</script>", &data);
+        assert_eq!(rendered.matches("</script>").count(), 1);
+        assert!(!rendered.contains("<!--"));
+        let embedded = rendered
+            .trim_start_matches("<script>")
+            .trim_end_matches("</script>");
+        assert_eq!(serde_json::from_str::<Value>(embedded).unwrap(), data["s"]);
+    }
+
+    #[test]
+    fn leaves_unterminated_placeholders() {
+        let data = json!({ "a": 1 });
+        assert_eq!(
+            render_widget_template("{{ a }} and {{ b", &data),
+            "1 and {{ b"
+        );
+    }
+}
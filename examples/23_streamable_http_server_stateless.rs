@@ -175,16 +175,9 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     info!("Creating stateless HTTP server on {}", addr);
 
-    // Create stateless configuration
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,   // STATELESS MODE - no session IDs
-        enable_json_response: true,   // Use simple JSON responses (no streaming)
-        event_store: None,            // No event store needed
-        on_session_initialized: None, // No session callbacks
-        on_session_closed: None,
-        http_middleware: None, // No HTTP middleware
-        allowed_origins: None,
-    };
+    // Create stateless configuration: no session IDs, simple JSON responses
+    // (no streaming), and no event store
+    let config = StreamableHttpServerConfig::stateless();
 
     // Create the streamable HTTP server in stateless mode
     let http_server = StreamableHttpServer::with_config(addr, server, config);
@@ -17,7 +17,9 @@
 //!
 //! Make sure to start the corresponding server example first!
 
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::{Client, ClientCapabilities};
 use serde_json::json;
 use tracing::info;
@@ -49,15 +51,11 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Configure the HTTP transport
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse(server_url).map_err(|e| pmcp::Error::Internal(e.to_string()))?,
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,           // Will be set by stateful server if applicable
-        enable_json_response: true, // Use simple JSON responses
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config = StreamableHttpTransportConfigBuilder::new(
+        Url::parse(server_url).map_err(|e| pmcp::Error::Internal(e.to_string()))?,
+    )
+    .enable_json_response()
+    .build();
 
     // Create the transport - it's already Clone so we can share it
     let transport = StreamableHttpTransport::new(config);
@@ -1,4 +1,6 @@
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::{Client, ClientCapabilities};
 use serde_json::json;
 use url::Url;
@@ -20,15 +22,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Configure the HTTP transport
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse(server_url).map_err(|e| pmcp::Error::Internal(e.to_string()))?,
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config = StreamableHttpTransportConfigBuilder::new(
+        Url::parse(server_url).map_err(|e| pmcp::Error::Internal(e.to_string()))?,
+    )
+    .enable_json_response()
+    .build();
 
     // Create the transport
     let transport = StreamableHttpTransport::new(config);
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use pmcp::{
     shared::{
-        streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig},
+        streamable_http::{
+            StreamableHttpTransport, StreamableHttpTransportConfig,
+            StreamableHttpTransportConfigBuilder,
+        },
         StdioTransport,
     },
     types::{
@@ -95,15 +98,11 @@ impl ServerTester {
                     "HTTP middleware chain present: {}",
                     http_middleware_chain.is_some()
                 );
-                let config = StreamableHttpTransportConfig {
-                    url: parsed_url,
-                    extra_headers,
-                    auth_provider: None,
-                    session_id: None,
-                    enable_json_response: true,
-                    on_resumption_token: None,
-                    http_middleware_chain: http_middleware_chain.clone(),
-                };
+                let mut config = StreamableHttpTransportConfigBuilder::new(parsed_url)
+                    .enable_json_response()
+                    .build();
+                config.extra_headers = extra_headers;
+                config.http_middleware_chain = http_middleware_chain.clone();
                 (TransportType::Http, Some(config), None)
             },
             Some("jsonrpc") => {
@@ -155,15 +154,11 @@ impl ServerTester {
                             "HTTP middleware chain (jsonrpc path) present: {}",
                             http_middleware_chain.is_some()
                         );
-                        let config = StreamableHttpTransportConfig {
-                            url: parsed_url,
-                            extra_headers,
-                            auth_provider: None,
-                            session_id: None,
-                            enable_json_response: true,
-                            on_resumption_token: None,
-                            http_middleware_chain: http_middleware_chain.clone(),
-                        };
+                        let mut config = StreamableHttpTransportConfigBuilder::new(parsed_url)
+                            .enable_json_response()
+                            .build();
+                        config.extra_headers = extra_headers;
+                        config.http_middleware_chain = http_middleware_chain.clone();
                         (TransportType::Http, Some(config), None)
                     }
                 }
@@ -119,14 +119,12 @@ async fn main() -> Result<()> {
         .build()?;
 
     // Step 3: Create HTTP server config (middleware retrieved from server)
-    let config = StreamableHttpServerConfig {
-        http_middleware: server.http_middleware(),
-        session_id_generator: Some(Box::new(|| {
-            format!("demo-session-{}", uuid::Uuid::new_v4())
-        })),
-        enable_json_response: true,
-        ..Default::default()
-    };
+    let mut config = StreamableHttpServerConfig::default();
+    config.http_middleware = server.http_middleware();
+    config.session_id_generator = Some(Box::new(|| {
+        format!("demo-session-{}", uuid::Uuid::new_v4())
+    }));
+    config.enable_json_response = true;
 
     let server = Arc::new(Mutex::new(server));
 
@@ -497,7 +497,10 @@ impl ResourceHandler for ChessResources {
             .and_then(|s| s.strip_suffix(".html").or(Some(s)));
 
         if let Some(widget_name) = name {
-            let html = self.widget_dir.read_widget(widget_name);
+            // Ship the starting position with the HTML so the board paints
+            // without waiting for a chess_new_game round trip
+            let data = serde_json::json!({ "initialState": GameState::new() });
+            let html = self.widget_dir.render_widget(widget_name, &data);
             let transformed = self.adapter.transform(uri, widget_name, &html);
 
             Ok(ReadResourceResult::new(vec![Content::Resource {
@@ -578,14 +581,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         .unwrap_or(3000u16);
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let http_server = StreamableHttpServer::with_config(addr, server, config);
     let (bound_addr, server_handle) = http_server
@@ -33,6 +33,7 @@
 use async_trait::async_trait;
 use pmcp::server::mcp_apps::{McpAppsAdapter, UIAdapter, WidgetDir};
 use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::tower_layers::CompressionConfig;
 use pmcp::server::typed_tool::TypedToolWithOutput;
 use pmcp::server::ServerBuilder;
 use pmcp::types::mcp_apps::{ExtendedUIMimeType, HostType};
@@ -331,14 +332,8 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         .unwrap_or(3002u16);
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-    };
+    // Query results can be hundreds of KB of JSON; gzip/zstd shrinks them
+    let config = StreamableHttpServerConfig::stateless().with_compression(CompressionConfig::new());
 
     let http_server = StreamableHttpServer::with_config(addr, server, config);
     let (bound_addr, server_handle) = http_server
@@ -483,14 +483,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         .unwrap_or(3001u16);
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let http_server = StreamableHttpServer::with_config(addr, server, config);
     let (bound_addr, server_handle) = http_server
@@ -7,7 +7,7 @@
  */
 
 import { PostMessageTransport } from './transport';
-import type { CallToolResult, HostContext, AppBridgeOptions } from './types';
+import type { CallToolResult, HostContext, AppBridgeOptions, NavigateParams } from './types';
 
 // =============================================================================
 // AppBridge Class
@@ -40,6 +40,7 @@ import type { CallToolResult, HostContext, AppBridgeOptions } from './types';
 export class AppBridge {
   private _iframe: HTMLIFrameElement;
   private _toolCallHandler: (name: string, args?: Record<string, unknown>) => Promise<CallToolResult>;
+  private _navigateHandler: ((params: NavigateParams) => Promise<unknown>) | undefined;
   private _origin: string;
   private _transport: PostMessageTransport | null = null;
   private _hostContext: HostContext;
@@ -48,6 +49,7 @@ export class AppBridge {
   constructor(options: AppBridgeOptions) {
     this._iframe = options.iframe;
     this._toolCallHandler = options.toolCallHandler;
+    this._navigateHandler = options.navigateHandler;
     this._origin = options.origin ?? window.location.origin;
     this._hostContext = options.hostContext ?? {};
   }
@@ -200,6 +202,17 @@ export class AppBridge {
         console.log('[AppBridge] Widget updated model context:', params);
         return {};
 
+      case 'ui/navigate': {
+        if (!this._navigateHandler) {
+          throw new Error('Widget navigation is not supported by this host');
+        }
+        if (!params?.back && !params?.tool) {
+          throw new Error('ui/navigate requires a "tool" parameter');
+        }
+        await this._navigateHandler(params as NavigateParams);
+        return {};
+      }
+
       case 'ui/openLink': {
         const url = params?.url as string | undefined;
         if (url) {
@@ -6,7 +6,14 @@
  */
 
 import { PostMessageTransport } from './transport';
-import type { CallToolParams, CallToolResult, HostContext, AppOptions, AppCapabilities } from './types';
+import type {
+  CallToolParams,
+  CallToolResult,
+  HostContext,
+  AppOptions,
+  AppCapabilities,
+  NavigateParams,
+} from './types';
 
 // =============================================================================
 // Lifecycle callback types
@@ -188,6 +195,29 @@ export class App {
     }
   }
 
+  /**
+   * Open the widget rendered by another tool of the same server.
+   *
+   * The host calls the tool and hands `state` to the target widget as
+   * `hostContext.navigation`. Throws if the host does not support
+   * navigation (`ui/navigate`).
+   *
+   * @param params - Target tool, its arguments, and state to hand over
+   */
+  async navigate(params: NavigateParams): Promise<void> {
+    if (!this._transport) {
+      throw new Error('[App] Not connected. Call connect() first.');
+    }
+    await this._transport.send('ui/navigate', params as unknown as Record<string, unknown>);
+  }
+
+  /**
+   * Return to the widget that navigated here.
+   */
+  async navigateBack(): Promise<void> {
+    await this.navigate({ back: true });
+  }
+
   /**
    * Send a log entry to the host for debugging.
    *
@@ -199,7 +229,7 @@ export class App {
     }
 
     // Notifications are fire-and-forget
-    this._transport.notify('ui/log', params as Record<string, unknown>);
+    this._transport.notify('ui/log', params as unknown as Record<string, unknown>);
   }
 
   /**
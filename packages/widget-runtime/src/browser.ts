@@ -53,6 +53,11 @@ export type {
   CallToolParams,
   CallToolResult,
   HostContext,
+  I18nContext,
+  I18n,
+  TextDirection,
+  NavigateParams,
+  NavigationContext,
   AppOptions,
   AppBridgeOptions,
 } from './types';
@@ -78,6 +83,11 @@ export {
   debounce,
   throttle,
   log,
+  textDirection,
+  createI18n,
+  widgetLink,
+  parseWidgetLink,
+  interceptWidgetLinks,
 } from './utils';
 
 // Default export for convenience
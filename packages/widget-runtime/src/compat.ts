@@ -10,7 +10,14 @@
  */
 
 import { App } from './app';
-import type { CallToolResult, ChatGptExtensions, HostContext, McpBridgeExtensions } from './types';
+import type {
+  CallToolResult,
+  ChatGptExtensions,
+  HostContext,
+  McpBridgeExtensions,
+  NavigationContext,
+} from './types';
+import { createI18n, interceptWidgetLinks } from './utils';
 
 // Track whether the deprecation warning has been logged
 let deprecationWarned = false;
@@ -215,8 +222,25 @@ export function installCompat(app: App): void {
     get displayMode(): string | undefined {
       return chatgptExt?.displayMode ?? getCtx().displayMode;
     },
+
+    i18n: createI18n(
+      () => chatgptExt?.locale ?? getCtx().locale,
+      () => getCtx().i18n
+    ),
+
+    navigate: (tool: string, args?: Record<string, unknown>, state?: unknown): Promise<void> =>
+      app.navigate({ tool, arguments: args ?? {}, state }),
+
+    navigateBack: (): Promise<void> => app.navigateBack(),
+
+    get navigation(): NavigationContext | null {
+      return getCtx().navigation ?? null;
+    },
   };
 
+  // Follow pmcp-widget: deep links
+  interceptWidgetLinks((params) => app.navigate(params));
+
   // Install window.mcpBridge
   (window as unknown as Record<string, unknown>).mcpBridge = mcpBridge;
 
@@ -213,6 +213,12 @@ export class PostMessageTransport {
       return;
     }
 
+    // Source validation: with several bridged iframes on one page, each
+    // transport must only handle messages from its own target window.
+    if (event.source && event.source !== this._targetWindow) {
+      return;
+    }
+
     const data = event.data;
     if (!data || data.jsonrpc !== '2.0') {
       return;
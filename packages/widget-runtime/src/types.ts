@@ -433,6 +433,16 @@ export interface McpBridge {
   sendIntent?(action: string, data?: unknown): Promise<unknown>;
   /** Open link (MCP-UI) */
   openLink?(url: string): void;
+  /** Current locale */
+  readonly locale?: string;
+  /** Message catalog lookup for the current locale */
+  readonly i18n?: I18n;
+  /** Open the widget of another tool on the same server */
+  navigate?(tool: string, args?: Record<string, unknown>, state?: unknown): Promise<unknown>;
+  /** Return to the widget that navigated here */
+  navigateBack?(): Promise<unknown>;
+  /** Handoff from the widget that navigated here, if any */
+  readonly navigation?: NavigationContext | null;
 
   // Host-specific extensions namespace
   /** Host-specific capabilities (ChatGPT, Claude, etc.) */
@@ -571,6 +581,68 @@ export interface HostContext {
     width: number;
     height: number;
   };
+  /** Localization data (PMCP extension, supplied by mcp-preview) */
+  i18n?: I18nContext;
+  /** Handoff from the widget that navigated here (PMCP extension) */
+  navigation?: NavigationContext;
+}
+
+/**
+ * Parameters of a `ui/navigate` request: open the widget rendered by `tool`,
+ * or return to the previous widget with `back: true`.
+ */
+export interface NavigateParams {
+  /** Tool whose widget to open */
+  tool?: string;
+  /** Arguments to call the tool with */
+  arguments?: Record<string, unknown>;
+  /** State handed to the target widget */
+  state?: unknown;
+  /** Return to the previous widget instead */
+  back?: boolean;
+}
+
+/**
+ * Navigation handoff delivered to the target widget, in `hostContext` and
+ * under `pmcp/navigation` in the tool result's `_meta`.
+ */
+export interface NavigationContext {
+  /** Tool whose widget navigated here */
+  from?: string;
+  /** State handed over by the previous widget */
+  state?: unknown;
+}
+
+/** Text direction of the widget's locale. */
+export type TextDirection = 'ltr' | 'rtl';
+
+/**
+ * Localization data a host can supply alongside `locale`.
+ */
+export interface I18nContext {
+  /** Message catalog for the current locale, keyed by message ID */
+  messages?: Record<string, string>;
+  /** Text direction; derived from the locale when omitted */
+  dir?: TextDirection;
+}
+
+/**
+ * Message lookup API available on `window.mcpBridge.i18n`.
+ */
+export interface I18n {
+  /** Current locale (e.g., 'ar-SA') */
+  readonly locale: string;
+  /** Text direction for the current locale */
+  readonly dir: TextDirection;
+  /** Message catalog supplied by the host (empty when none) */
+  readonly messages: Record<string, string>;
+  /**
+   * Look up a message, replacing `{name}` placeholders from `params`.
+   * Returns `fallback` (or the key itself) when the message is missing.
+   */
+  t(key: string, params?: Record<string, string | number>, fallback?: string): string;
+  /** Whether the catalog contains `key` */
+  has(key: string): boolean;
 }
 
 /**
@@ -607,4 +679,6 @@ export interface AppBridgeOptions {
   origin?: string;
   /** Initial host context to send to the widget on initialization */
   hostContext?: HostContext;
+  /** Handler for `ui/navigate` requests; navigation is rejected without one */
+  navigateHandler?: (params: NavigateParams) => Promise<unknown>;
 }
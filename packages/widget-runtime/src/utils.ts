@@ -2,7 +2,14 @@
  * Utility functions for MCP widgets.
  */
 
-import type { HostType, McpBridge } from './types';
+import type {
+  HostType,
+  I18n,
+  I18nContext,
+  McpBridge,
+  NavigateParams,
+  TextDirection,
+} from './types';
 
 /**
  * Detect the current host platform.
@@ -272,3 +279,130 @@ export function log(
     logFn(prefix, message);
   }
 }
+
+/** Primary language subtags written right-to-left. */
+const RTL_LANGUAGES = new Set([
+  'ar', 'arc', 'ckb', 'dv', 'fa', 'he', 'iw', 'ks', 'ku', 'ps', 'sd', 'ug', 'ur', 'yi',
+]);
+
+/**
+ * Text direction for a BCP 47 locale.
+ *
+ * @example
+ * ```typescript
+ * textDirection('ar-SA'); // 'rtl'
+ * textDirection('en-US'); // 'ltr'
+ * ```
+ */
+export function textDirection(locale: string | undefined): TextDirection {
+  const language = (locale ?? '').toLowerCase().split(/[-_]/)[0];
+  return RTL_LANGUAGES.has(language) ? 'rtl' : 'ltr';
+}
+
+/**
+ * Create an i18n helper backed by live host context.
+ *
+ * The getters are re-evaluated on every access, so the helper follows
+ * locale and catalog changes pushed by the host.
+ *
+ * @param getLocale - Returns the current locale
+ * @param getContext - Returns the host's i18n context, if any
+ */
+export function createI18n(
+  getLocale: () => string | undefined,
+  getContext: () => I18nContext | undefined
+): I18n {
+  const messages = (): Record<string, string> => getContext()?.messages ?? {};
+
+  return {
+    get locale() {
+      return getLocale() ?? 'en-US';
+    },
+    get dir() {
+      return getContext()?.dir ?? textDirection(getLocale());
+    },
+    get messages() {
+      return messages();
+    },
+    t(key: string, params?: Record<string, string | number>, fallback?: string): string {
+      const template = messages()[key] ?? fallback ?? key;
+      if (!params) {
+        return template;
+      }
+      return template.replace(/\{(\w+)\}/g, (match, name: string) =>
+        name in params ? String(params[name]) : match
+      );
+    },
+    has(key: string): boolean {
+      return Object.prototype.hasOwnProperty.call(messages(), key);
+    },
+  };
+}
+
+/** Scheme of widget deep links. */
+const WIDGET_LINK_PREFIX = 'pmcp-widget:';
+
+/**
+ * Build a deep link to the widget rendered by `tool`.
+ *
+ * Matches `WidgetLink::href` on the server:
+ * `pmcp-widget:<tool>?args=<json>&state=<json>`.
+ */
+export function widgetLink(
+  tool: string,
+  args: Record<string, unknown> = {},
+  state?: unknown
+): string {
+  const query = new URLSearchParams({ args: JSON.stringify(args) });
+  if (state !== undefined) {
+    query.set('state', JSON.stringify(state));
+  }
+  return `${WIDGET_LINK_PREFIX}${encodeURIComponent(tool)}?${query.toString()}`;
+}
+
+/**
+ * Parse a deep link built with `widgetLink` (or `WidgetLink::href`).
+ *
+ * @returns The navigation target, or null if `href` is not a valid link
+ */
+export function parseWidgetLink(href: string | null | undefined): NavigateParams | null {
+  const match = /^pmcp-widget:([^?#]+)(?:\?([^#]*))?/.exec(href ?? '');
+  if (!match) {
+    return null;
+  }
+  const query = new URLSearchParams(match[2] ?? '');
+  try {
+    const args = JSON.parse(query.get('args') ?? '{}');
+    if (args === null || typeof args !== 'object' || Array.isArray(args)) {
+      return null;
+    }
+    const state = query.get('state');
+    return {
+      tool: decodeURIComponent(match[1]),
+      arguments: args as Record<string, unknown>,
+      state: state === null ? undefined : JSON.parse(state),
+    };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Follow clicks on `pmcp-widget:` deep links with `navigate`.
+ *
+ * @returns A function that removes the listener
+ */
+export function interceptWidgetLinks(navigate: (params: NavigateParams) => unknown): () => void {
+  const listener = (event: MouseEvent): void => {
+    const target = event.target as Element | null;
+    const link = target?.closest?.('a[href]');
+    const params = parseWidgetLink(link?.getAttribute('href'));
+    if (!params) {
+      return;
+    }
+    event.preventDefault();
+    navigate(params);
+  };
+  document.addEventListener('click', listener);
+  return () => document.removeEventListener('click', listener);
+}
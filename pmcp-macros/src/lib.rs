@@ -115,7 +115,8 @@ pub fn tool(args: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// # Attributes
 ///
-/// - `description` - Tool description (required, enforced at compile time)
+/// - `description` - Tool description (required, enforced at compile time;
+///   defaults to the function's `///` doc comment)
 /// - `name` - Override tool name (defaults to function name)
 /// - `annotations(...)` - MCP standard annotations (`read_only`, `destructive`,
 ///   `idempotent`, `open_world`)
@@ -144,6 +145,18 @@ pub fn tool(args: TokenStream, input: TokenStream) -> TokenStream {
 /// // Register: server_builder.tool("query", query().with_state(shared_db))
 /// ```
 ///
+/// Via the `pmcp::tool` re-export, with the description taken from the doc
+/// comment. Errors that have no `pmcp::Error` conversion (`String`, `&str`,
+/// `Box<dyn Error>`, `anyhow::Error`) are mapped to internal errors:
+///
+/// ```rust,ignore
+/// /// Look up a user by id
+/// #[pmcp::tool]
+/// async fn get_user(args: GetUserArgs) -> Result<User, String> {
+///     find_user(args.id).ok_or_else(|| format!("no user {}", args.id))
+/// }
+/// ```
+///
 /// With annotations:
 ///
 /// ```rust,ignore
@@ -5,7 +5,7 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{FnArg, GenericParam, Generics, Type, TypePath};
+use syn::{Attribute, Expr, ExprLit, FnArg, GenericParam, Generics, Lit, Meta, Type, TypePath};
 
 /// Parameter slot for call-site argument ordering.
 ///
@@ -108,6 +108,94 @@ pub fn extract_result_ok_type(ty: &Type) -> Option<Type> {
     None
 }
 
+/// Extract the error type from `Result<T, E>`.
+///
+/// Returns `None` for the single-parameter `pmcp::Result<T>` alias.
+pub fn extract_result_err_type(ty: &Type) -> Option<Type> {
+    if let Type::Path(TypePath { path, .. }) = ty {
+        if let Some(segment) = path.segments.last() {
+            if segment.ident == "Result" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(err_type)) = args.args.iter().nth(1) {
+                        return Some(err_type.clone());
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Check if an error type has no `Into<pmcp::Error>` conversion and must be
+/// mapped through its `Display` output instead.
+///
+/// Covers `String`, `&str`, `Box<dyn Error>`, and `anyhow::Error`, none of
+/// which can implement `From` for `pmcp::Error` in user code. Other error
+/// types keep flowing through `?` so their `From` impls (and error codes)
+/// are preserved.
+pub fn is_display_mapped_error(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => type_name_matches(&reference.elem, "str"),
+        Type::Path(TypePath { path, .. }) => {
+            let last = path.segments.last().map(|s| s.ident.to_string());
+            let first = path.segments.first().map(|s| s.ident.to_string());
+            match last.as_deref() {
+                Some("String" | "Box") => true,
+                Some("Error") => first.as_deref() == Some("anyhow"),
+                _ => false,
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Generate the error mapping applied to a handler call before `?`.
+///
+/// Errors without a `pmcp::Error` conversion become internal errors carrying
+/// their message; everything else is left to `?` and its `From` impls.
+pub fn error_mapping_tokens(return_type: Option<&Type>) -> TokenStream {
+    match return_type.and_then(extract_result_err_type) {
+        Some(err_type) if is_display_mapped_error(&err_type) => {
+            quote! { .map_err(|e| pmcp::Error::internal(e.to_string())) }
+        },
+        _ => quote! {},
+    }
+}
+
+/// Build a tool description from a function's `///` doc comment.
+///
+/// Consecutive lines are joined with spaces and blank lines are kept as
+/// paragraph breaks. Returns `None` if there is no doc comment.
+pub fn doc_description(attrs: &[Attribute]) -> Option<String> {
+    let mut description = String::new();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("doc")) {
+        let Meta::NameValue(name_value) = &attr.meta else {
+            continue;
+        };
+        let Expr::Lit(ExprLit {
+            lit: Lit::Str(text),
+            ..
+        }) = &name_value.value
+        else {
+            continue;
+        };
+        let line = text.value();
+        let line = line.trim();
+        if line.is_empty() {
+            if !description.is_empty() && !description.ends_with("\n\n") {
+                description.push_str("\n\n");
+            }
+        } else {
+            if !description.is_empty() && !description.ends_with('\n') {
+                description.push(' ');
+            }
+            description.push_str(line);
+        }
+    }
+    let description = description.trim_end();
+    (!description.is_empty()).then(|| description.to_string())
+}
+
 /// Generate input schema code for a given args type.
 ///
 /// Uses `schemars::schema_for!` and `normalize_schema`.
@@ -183,6 +271,44 @@ mod tests {
     use super::*;
     use syn::parse_quote;
 
+    #[test]
+    fn test_doc_description_joins_lines_and_paragraphs() {
+        let item: syn::ItemFn = parse_quote! {
+            /// Add two numbers
+            /// and return the sum.
+            ///
+            /// Overflow wraps.
+            fn add() {}
+        };
+        assert_eq!(
+            doc_description(&item.attrs).as_deref(),
+            Some("Add two numbers and return the sum.\n\nOverflow wraps.")
+        );
+
+        let undocumented: syn::ItemFn = parse_quote! { fn add() {} };
+        assert_eq!(doc_description(&undocumented.attrs), None);
+    }
+
+    #[test]
+    fn test_is_display_mapped_error() {
+        assert!(is_display_mapped_error(&parse_quote!(String)));
+        assert!(is_display_mapped_error(&parse_quote!(&'static str)));
+        assert!(is_display_mapped_error(&parse_quote!(anyhow::Error)));
+        assert!(is_display_mapped_error(&parse_quote!(
+            Box<dyn std::error::Error + Send + Sync>
+        )));
+        assert!(!is_display_mapped_error(&parse_quote!(pmcp::Error)));
+        assert!(!is_display_mapped_error(&parse_quote!(MyError)));
+    }
+
+    #[test]
+    fn test_extract_result_err_type() {
+        let ty: Type = parse_quote!(Result<i32, String>);
+        assert!(extract_result_err_type(&ty).is_some());
+        let ty: Type = parse_quote!(pmcp::Result<i32>);
+        assert!(extract_result_err_type(&ty).is_none());
+    }
+
     #[test]
     fn test_classify_param_state() {
         let param: FnArg = parse_quote!(db: State<Database>);
@@ -47,7 +47,7 @@ struct ToolMethodInfo {
     method_name: syn::Ident,
     /// The resolved tool name (from `name = "..."` or method name).
     tool_name: String,
-    /// Tool description (mandatory).
+    /// Tool description (from the attribute or the doc comment).
     description: String,
     /// Whether the method is async.
     is_async: bool,
@@ -151,10 +151,11 @@ pub fn expand_mcp_server(_args: TokenStream, mut input: ItemImpl) -> syn::Result
             .collect();
 
         // Generate function call (async vs sync).
+        let map_err = mcp_common::error_mapping_tokens(method_info.return_type.as_ref());
         let fn_call = if method_info.is_async {
-            quote! { let result = self.server.#method_ident(#(#call_args),*).await?; }
+            quote! { let result = self.server.#method_ident(#(#call_args),*).await #map_err?; }
         } else {
-            quote! { let result = self.server.#method_ident(#(#call_args),*)?; }
+            quote! { let result = self.server.#method_ident(#(#call_args),*) #map_err?; }
         };
 
         // Extra parameter name in handle() signature.
@@ -371,6 +372,11 @@ fn collect_tool_methods(impl_block: &ItemImpl) -> syn::Result<Vec<ToolMethodInfo
 
         // Parse the attribute arguments using darling.
         let macro_args = parse_mcp_tool_attr(attr, method)?;
+        let description = crate::mcp_tool::resolve_description(
+            macro_args.description,
+            &method.attrs,
+            &method.sig.ident,
+        )?;
         let tool_name = macro_args
             .name
             .unwrap_or_else(|| method.sig.ident.to_string());
@@ -433,7 +439,7 @@ fn collect_tool_methods(impl_block: &ItemImpl) -> syn::Result<Vec<ToolMethodInfo
         methods.push(ToolMethodInfo {
             method_name: method.sig.ident.clone(),
             tool_name,
-            description: macro_args.description,
+            description,
             is_async: method.sig.asyncness.is_some(),
             args_type,
             has_extra,
@@ -451,12 +457,8 @@ fn collect_tool_methods(impl_block: &ItemImpl) -> syn::Result<Vec<ToolMethodInfo
 fn parse_mcp_tool_attr(attr: &syn::Attribute, method: &ImplItemFn) -> syn::Result<McpToolArgs> {
     let tokens = match &attr.meta {
         syn::Meta::List(list) => list.tokens.clone(),
-        syn::Meta::Path(_) => {
-            return Err(syn::Error::new_spanned(
-                &method.sig.ident,
-                "mcp_tool requires at least `description = \"...\"` attribute",
-            ));
-        },
+        // Bare `#[mcp_tool]` takes its description from the doc comment.
+        syn::Meta::Path(_) => TokenStream::new(),
         syn::Meta::NameValue(_) => {
             return Err(syn::Error::new_spanned(
                 attr,
@@ -668,6 +670,32 @@ mod tests {
         assert!(methods[1].args_type.is_none());
     }
 
+    #[test]
+    fn test_collect_tool_methods_doc_comment_description() {
+        let impl_block: ItemImpl = parse_quote! {
+            impl MyServer {
+                /// Count rows in a table
+                #[mcp_tool]
+                async fn count(&self, args: CountArgs) -> Result<Value> {
+                    Ok(serde_json::json!({}))
+                }
+
+                #[mcp_tool]
+                async fn undocumented(&self) -> Result<Value> {
+                    Ok(serde_json::json!({}))
+                }
+            }
+        };
+
+        let err = collect_tool_methods(&impl_block).err().unwrap();
+        assert!(err.to_string().contains("or a doc comment"));
+
+        let mut impl_block = impl_block;
+        impl_block.items.truncate(1);
+        let methods = collect_tool_methods(&impl_block).unwrap();
+        assert_eq!(methods[0].description, "Count rows in a table");
+    }
+
     #[test]
     fn test_collect_tool_methods_empty_errors() {
         let impl_block: ItemImpl = parse_quote! {
@@ -21,6 +21,17 @@
 //!
 //! // Register: server_builder.tool("add", add())
 //! ```
+//!
+//! The description may instead come from the doc comment, and the macro is
+//! re-exported as `pmcp::tool`:
+//!
+//! ```rust,ignore
+//! /// Add two numbers
+//! #[pmcp::tool]
+//! async fn add(args: AddArgs) -> Result<AddResult, String> {
+//!     Ok(AddResult { sum: args.a + args.b })
+//! }
+//! ```
 
 use crate::mcp_common;
 use darling::FromMeta;
@@ -33,8 +44,9 @@ use syn::{ItemFn, ReturnType, Type};
 /// Parsed attributes for `#[mcp_tool(...)]`.
 #[derive(Debug, FromMeta)]
 pub struct McpToolArgs {
-    /// Tool description (mandatory per D-05).
-    pub(crate) description: String,
+    /// Tool description (mandatory per D-05; falls back to the doc comment).
+    #[darling(default)]
+    pub(crate) description: Option<String>,
     /// Override tool name (defaults to function name per D-06).
     #[darling(default)]
     pub(crate) name: Option<String>,
@@ -68,21 +80,16 @@ pub fn expand_mcp_tool(args: TokenStream, input: &ItemFn) -> syn::Result<TokenSt
     use mcp_common::ParamSlot;
 
     // Parse macro attributes via darling.
-    let nested_metas = if args.is_empty() {
-        return Err(syn::Error::new_spanned(
-            &input.sig.ident,
-            "mcp_tool requires at least `description = \"...\"` attribute",
-        ));
-    } else {
-        let parser = syn::punctuated::Punctuated::<darling::ast::NestedMeta, syn::Token![,]>::parse_terminated;
-        parser
-            .parse2(args)
-            .map(|p| p.into_iter().collect::<Vec<_>>())
-            .unwrap_or_default()
-    };
+    let parser =
+        syn::punctuated::Punctuated::<darling::ast::NestedMeta, syn::Token![,]>::parse_terminated;
+    let nested_metas = parser
+        .parse2(args)
+        .map(|p| p.into_iter().collect::<Vec<_>>())
+        .unwrap_or_default();
 
     let macro_args = McpToolArgs::from_list(&nested_metas)
         .map_err(|e| syn::Error::new_spanned(&input.sig.ident, e.to_string()))?;
+    let description = resolve_description(macro_args.description, &input.attrs, &input.sig.ident)?;
 
     // Extract function info.
     let fn_name = &input.sig.ident;
@@ -90,7 +97,6 @@ pub fn expand_mcp_tool(args: TokenStream, input: &ItemFn) -> syn::Result<TokenSt
     let tool_name = macro_args.name.unwrap_or_else(|| fn_name_str.clone());
     let is_async = input.sig.asyncness.is_some();
     let struct_name = format_ident!("{}Tool", fn_name_str.to_upper_camel_case());
-    let description = &macro_args.description;
 
     // Rename the original function to an internal name to avoid conflict
     // with the constructor function that uses the same name.
@@ -228,10 +234,11 @@ pub fn expand_mcp_tool(args: TokenStream, input: &ItemFn) -> syn::Result<TokenSt
 
     // Generate the function call (async vs sync).
     // Calls the renamed internal function, not the public constructor.
+    let map_err = extract_error_mapping(input);
     let fn_call = if is_async {
-        quote! { let result = #impl_fn_name(#(#call_args),*).await?; }
+        quote! { let result = #impl_fn_name(#(#call_args),*).await #map_err?; }
     } else {
-        quote! { let result = #impl_fn_name(#(#call_args),*)?; }
+        quote! { let result = #impl_fn_name(#(#call_args),*) #map_err?; }
     };
 
     // Generate result serialization.
@@ -262,7 +269,7 @@ pub fn expand_mcp_tool(args: TokenStream, input: &ItemFn) -> syn::Result<TokenSt
     // Generate ToolInfo construction (branching on annotations presence).
     let tool_info_code = generate_tool_info_code(
         &tool_name,
-        description,
+        &description,
         macro_args.annotations.as_ref(),
         macro_args.ui.as_ref(),
     );
@@ -337,6 +344,36 @@ fn extract_output_schema_code(input: &ItemFn) -> TokenStream {
     mcp_common::output_schema_tokens(return_type)
 }
 
+/// Extract the error mapping for the function's return type.
+///
+/// Delegates to `mcp_common::error_mapping_tokens`.
+fn extract_error_mapping(input: &ItemFn) -> TokenStream {
+    let return_type = match &input.sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => Some(ty.as_ref()),
+    };
+    mcp_common::error_mapping_tokens(return_type)
+}
+
+/// Resolve a tool description from the attribute or the doc comment.
+///
+/// An explicit `description = "..."` wins; otherwise the `///` doc comment is
+/// used. Having neither is a compile error (D-05).
+pub fn resolve_description(
+    explicit: Option<String>,
+    attrs: &[syn::Attribute],
+    ident: &Ident,
+) -> syn::Result<String> {
+    explicit
+        .or_else(|| mcp_common::doc_description(attrs))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                ident,
+                "mcp_tool requires `description = \"...\"` or a doc comment",
+            )
+        })
+}
+
 /// Generate `ToolInfo` construction code, branching on annotations presence.
 ///
 /// Per the plan: `ToolInfo` has NO `set_annotations()` method, so we must branch:
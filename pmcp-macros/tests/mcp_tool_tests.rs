@@ -191,6 +191,55 @@ fn test_builder_registration() {
     drop(builder);
 }
 
+// === Test 10: `#[pmcp::tool]` with doc-comment description ===
+
+/// Reverse a message
+/// character by character.
+#[pmcp::tool]
+async fn reverse(args: EchoArgs) -> pmcp::Result<EchoResult> {
+    Ok(EchoResult {
+        echoed: args.message.chars().rev().collect(),
+    })
+}
+
+#[tokio::test]
+async fn test_doc_comment_description() {
+    let tool = reverse();
+    let meta = tool.metadata().unwrap();
+    assert_eq!(meta.name, "reverse");
+    assert_eq!(
+        meta.description.as_deref(),
+        Some("Reverse a message character by character.")
+    );
+    assert!(meta.output_schema.is_some());
+
+    let extra = pmcp::RequestHandlerExtra::default();
+    let result = tool
+        .handle(serde_json::json!({"message": "abc"}), extra)
+        .await
+        .unwrap();
+    assert_eq!(result["echoed"], "cba");
+}
+
+// === Test 11: Error mapping for errors without a pmcp::Error conversion ===
+
+/// Fail with a plain string error
+#[pmcp::tool]
+fn string_error(args: EchoArgs) -> Result<serde_json::Value, String> {
+    Err(format!("cannot echo '{}'", args.message))
+}
+
+#[tokio::test]
+async fn test_string_error_is_mapped() {
+    let tool = string_error();
+    let extra = pmcp::RequestHandlerExtra::default();
+    let err = tool
+        .handle(serde_json::json!({"message": "x"}), extra)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot echo 'x'"));
+}
+
 // === Compile-fail tests (trybuild) ===
 
 #[test]
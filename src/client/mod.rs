@@ -4,9 +4,10 @@ use crate::error::{Error, Result};
 use crate::shared::{
     EnhancedMiddlewareChain, MiddlewareContext, Protocol, ProtocolOptions, Transport,
 };
+use crate::types::elicitation::ElicitResult;
 use crate::types::tasks::{
     CancelTaskRequest, CancelTaskResult, CreateTaskResult, GetTaskPayloadRequest, GetTaskRequest,
-    GetTaskResult, ListTasksRequest, ListTasksResult, Task, TaskStatus,
+    GetTaskResult, ListTasksRequest, ListTasksResult, ProvideTaskInputRequest, Task, TaskStatus,
 };
 use crate::types::{
     CallToolRequest, CallToolResult, CancelledNotification, ClientCapabilities, ClientNotification,
@@ -15,9 +16,11 @@ use crate::types::{
     ListPromptsRequest, ListPromptsResult, ListResourceTemplatesRequest,
     ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
     ListToolsResult, LoggingLevel, Notification, ProgressNotification, ReadResourceRequest,
-    ReadResourceResult, Request, RequestId, ServerCapabilities, SubscribeRequest,
+    ReadResourceResult, Request, RequestId, ServerCapabilities, SubscribeRequest, ToolInfo,
     UnsubscribeRequest,
 };
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -39,7 +42,12 @@ pub mod http_middleware;
 #[cfg(all(not(target_arch = "wasm32"), feature = "oauth"))]
 pub mod oauth;
 pub mod oauth_middleware;
+pub mod pagination;
 pub mod transport;
+pub mod typed_call;
+
+pub use pagination::Paginated;
+pub use typed_call::SchemaViolation;
 
 /// Response from a task-augmented `tools/call`.
 ///
@@ -68,6 +76,10 @@ pub struct Client<T: Transport> {
     info: Implementation,
     notification_tx: Option<mpsc::Sender<Notification>>,
     active_requests: Arc<RwLock<HashMap<RequestId, oneshot::Sender<()>>>>,
+    /// Tools seen in `tools/list` responses, used by `call_tool_typed`.
+    tool_cache: Arc<RwLock<HashMap<String, ToolInfo>>>,
+    /// `list_changed` notifications seen, used to restart [`Paginated`] streams.
+    list_changes: Arc<pagination::ListChanges>,
 }
 
 impl<T: Transport> std::fmt::Debug for Client<T> {
@@ -131,6 +143,8 @@ impl<T: Transport> Client<T> {
             info: client_info,
             notification_tx: None,
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            tool_cache: Arc::new(RwLock::new(HashMap::new())),
+            list_changes: Arc::default(),
         }
     }
 
@@ -173,6 +187,8 @@ impl<T: Transport> Client<T> {
             info: client_info,
             notification_tx: None,
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            tool_cache: Arc::new(RwLock::new(HashMap::new())),
+            list_changes: Arc::default(),
         }
     }
 
@@ -346,14 +362,57 @@ impl<T: Transport> Client<T> {
         let request_id = RequestId::String(Uuid::new_v4().to_string());
         let response = self.send_request(request_id, request).await?;
 
-        match response.payload {
+        let result: ListToolsResult = match response.payload {
             crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))?
             },
             crate::types::jsonrpc::ResponsePayload::Error(error) => {
-                Err(Error::from_jsonrpc_error(error))
+                return Err(Error::from_jsonrpc_error(error));
             },
+        };
+
+        let mut cache = self.tool_cache.write().await;
+        for tool in &result.tools {
+            cache.insert(tool.name.clone(), tool.clone());
         }
+        Ok(result)
+    }
+
+    /// Stream every tools on the server, following `nextCursor` across pages.
+    ///
+    /// The stream restarts from the first page if the server sends
+    /// `notifications/tools/list_changed` during iteration. See
+    /// [`Paginated`] for prefetching and restart limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let mut tools = client.iter_tools().prefetch(1);
+    /// while let Some(tool) = tools.try_next().await? {
+    ///     println!("{}", tool.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_tools(&self) -> Paginated<'_, ToolInfo> {
+        Paginated::new(
+            "tools",
+            &self.list_changes.tools,
+            |tool| &tool.name,
+            Box::new(move |cursor| {
+                Box::pin(async move {
+                    let page = self.list_tools(cursor).await?;
+                    Ok((page.tools, page.next_cursor))
+                })
+            }),
+        )
     }
 
     /// Call a tool.
@@ -440,6 +499,88 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Call a tool with typed input and output, checked against its schemas.
+    ///
+    /// `input` is serialized and validated against the tool's advertised
+    /// `inputSchema` before the request is sent. The result's
+    /// `structuredContent` is validated against the tool's `outputSchema`
+    /// and deserialized into `O`. Tools without an output schema may return
+    /// their result as JSON text instead.
+    ///
+    /// Schemas come from earlier [`list_tools`](Self::list_tools) responses;
+    /// if the tool has not been listed yet, the tool list is fetched first.
+    /// Schema validation requires the `validation` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize)]
+    /// struct AddInput { a: i64, b: i64 }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct AddOutput { result: i64 }
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let sum: AddOutput = client.call_tool_typed("add", &AddInput { a: 2, b: 3 }).await?;
+    /// assert_eq!(sum.result, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors from [`call_tool`](Self::call_tool), returns
+    /// an error listing every mismatch if:
+    /// - The server does not advertise the tool
+    /// - `input` does not match the input schema (nothing is sent)
+    /// - The tool returns an error result
+    /// - The result does not match the output schema or does not deserialize
+    ///   into `O`
+    pub async fn call_tool_typed<I, O>(&self, name: impl Into<String>, input: &I) -> Result<O>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        let name = name.into();
+        let arguments = serde_json::to_value(input).map_err(|e| {
+            Error::validation(format!("Failed to serialize arguments for '{name}': {e}"))
+        })?;
+        let tool = self.tool_info(&name).await?;
+        typed_call::check_input(&tool, &arguments)?;
+
+        let result = self.call_tool(name, arguments).await?;
+        typed_call::decode_output(&tool, result)
+    }
+
+    /// Look up a tool's advertised definition, listing tools if it is not cached.
+    async fn tool_info(&self, name: &str) -> Result<ToolInfo> {
+        if let Some(tool) = self.tool_cache.read().await.get(name) {
+            return Ok(tool.clone());
+        }
+
+        let mut cursor = None;
+        loop {
+            let page = self.list_tools(cursor).await?;
+            if let Some(tool) = page.tools.into_iter().find(|t| t.name == name) {
+                return Ok(tool);
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => {
+                    return Err(Error::not_found(format!(
+                        "Tool '{name}' is not advertised by the server"
+                    )))
+                },
+            }
+        }
+    }
+
     // =========================================================================
     // MCP Tasks (2025-11-25)
     // =========================================================================
@@ -601,6 +742,40 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Answer the elicitation of a task waiting in `input_required`.
+    ///
+    /// The task's pending request is returned by [`tasks_get`](Self::tasks_get)
+    /// in `_meta`. On success the task is back in `working` and the updated
+    /// task is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The server doesn't support tasks or task input
+    /// - The task is not waiting for input
+    pub async fn tasks_provide_input(&self, task_id: &str, input: ElicitResult) -> Result<Task> {
+        self.ensure_initialized()?;
+        self.assert_capability("tasks", "tasks/provide_input")?;
+
+        let request = Request::Client(Box::new(ClientRequest::TasksProvideInput(
+            ProvideTaskInputRequest {
+                task_id: task_id.to_string(),
+                input,
+            },
+        )));
+        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let response = self.send_request(request_id, request).await?;
+
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Result(result) => {
+                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+            },
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                Err(Error::from_jsonrpc_error(error))
+            },
+        }
+    }
+
     /// Call a tool and automatically poll until the task completes.
     ///
     /// This is a high-level convenience method that encapsulates the full
@@ -644,7 +819,7 @@ impl<T: Transport> Client<T> {
 
                     if task.status == TaskStatus::InputRequired {
                         return Err(Error::internal(format!(
-                            "Task {} requires input — handle interactively via tasks_get/tasks_cancel",
+                            "Task {} requires input — handle interactively via tasks_get/tasks_provide_input",
                             task_id
                         )));
                     }
@@ -747,18 +922,87 @@ impl<T: Transport> Client<T> {
     /// - The server doesn't support prompts
     /// - Network or protocol errors occur
     pub async fn list_prompts(&self, cursor: Option<String>) -> Result<ListPromptsResult> {
+        self.request_prompt_list(ListPromptsRequest {
+            cursor,
+            prefix: None,
+        })
+        .await
+    }
+
+    /// Stream every prompts on the server, following `nextCursor` across pages.
+    ///
+    /// The stream restarts from the first page if the server sends
+    /// `notifications/prompts/list_changed` during iteration. See
+    /// [`Paginated`] for prefetching and restart limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let mut prompts = client.iter_prompts().prefetch(1);
+    /// while let Some(prompt) = prompts.try_next().await? {
+    ///     println!("{}", prompt.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_prompts(&self) -> Paginated<'_, crate::types::PromptInfo> {
+        Paginated::new(
+            "prompts",
+            &self.list_changes.prompts,
+            |prompt| &prompt.name,
+            Box::new(move |cursor| {
+                Box::pin(async move {
+                    let page = self.list_prompts(cursor).await?;
+                    Ok((page.prompts, page.next_cursor))
+                })
+            }),
+        )
+    }
+
+    /// List the prompts in one namespace of a server with hierarchical
+    /// prompt names (`reporting/weekly`, `admin/cleanup`).
+    ///
+    /// `namespace` also matches nested namespaces, so `admin` lists
+    /// `admin/users/purge`. The filter is applied again to the response,
+    /// so servers that ignore it still only yield prompts in the namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Client::list_prompts`].
+    pub async fn list_prompts_in(
+        &self,
+        namespace: impl Into<String>,
+        cursor: Option<String>,
+    ) -> Result<ListPromptsResult> {
+        self.request_prompt_list(ListPromptsRequest {
+            cursor,
+            prefix: Some(namespace.into()),
+        })
+        .await
+    }
+
+    async fn request_prompt_list(&self, request: ListPromptsRequest) -> Result<ListPromptsResult> {
         self.ensure_initialized()?;
         self.assert_capability("prompts", "prompts/list")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
-            cursor,
-        })));
+        let filter = request.clone();
+        let request = Request::Client(Box::new(ClientRequest::ListPrompts(request)));
         let request_id = RequestId::String(Uuid::new_v4().to_string());
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
             crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+                let mut result: ListPromptsResult =
+                    serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))?;
+                result.prompts.retain(|prompt| filter.matches(&prompt.name));
+                Ok(result)
             },
             crate::types::jsonrpc::ResponsePayload::Error(error) => {
                 Err(Error::from_jsonrpc_error(error))
@@ -908,6 +1152,43 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Stream every resources on the server, following `nextCursor` across pages.
+    ///
+    /// The stream restarts from the first page if the server sends
+    /// `notifications/resources/list_changed` during iteration. See
+    /// [`Paginated`] for prefetching and restart limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let mut resources = client.iter_resources().prefetch(1);
+    /// while let Some(resource) = resources.try_next().await? {
+    ///     println!("{}", resource.uri);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_resources(&self) -> Paginated<'_, crate::types::ResourceInfo> {
+        Paginated::new(
+            "resources",
+            &self.list_changes.resources,
+            |resource| &resource.uri,
+            Box::new(move |cursor| {
+                Box::pin(async move {
+                    let page = self.list_resources(cursor).await?;
+                    Ok((page.resources, page.next_cursor))
+                })
+            }),
+        )
+    }
+
     /// List resource templates.
     ///
     /// Retrieves information about all resource templates available on the server.
@@ -1013,13 +1294,116 @@ impl<T: Transport> Client<T> {
     /// - Access to the resource is denied
     /// - Network or protocol errors occur
     pub async fn read_resource(&self, uri: String) -> Result<ReadResourceResult> {
+        self.read_resource_with(ReadResourceRequest::new(uri)).await
+    }
+
+    /// Read a byte range of a resource's text.
+    ///
+    /// Sends `_meta["pmcp/range"]` with the `resources/read` request (a PMCP
+    /// extension), so only the requested slice of a large resource (a log, a
+    /// dataset) is transferred. The result's [`ReadResourceResult::range_info`] reports the
+    /// actual slice, the total length, and where the next slice starts. Servers
+    /// without range support return the whole resource and no range info.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// // First 64 KiB of a log
+    /// let head = client
+    ///     .read_resource_range("file:///var/log/app.log", 0, Some(64 * 1024))
+    ///     .await?;
+    /// if let Some(info) = head.range_info() {
+    ///     println!("{} of {} bytes", info.length, info.total_length);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`read_resource`](Self::read_resource), plus an
+    /// invalid-params error if the range is empty or starts past the end.
+    pub async fn read_resource_range(
+        &self,
+        uri: impl Into<String>,
+        offset: u64,
+        length: Option<u64>,
+    ) -> Result<ReadResourceResult> {
+        self.read_resource_with(ReadResourceRequest::new(uri).with_range(offset, length))
+            .await
+    }
+
+    /// Stream a resource's text in sequential ranges of up to `chunk_size` bytes.
+    ///
+    /// Each item is one slice of text; the stream ends after the last slice.
+    /// If the resource changes between slices (its etag differs), the stream
+    /// yields an error instead of mixing old and new content. Servers without
+    /// range support yield the whole text as a single item.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let chunks = client.read_resource_chunks("file:///var/log/app.log", 256 * 1024);
+    /// futures::pin_mut!(chunks);
+    /// while let Some(chunk) = chunks.next().await {
+    ///     print!("{}", chunk?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_resource_chunks(
+        &self,
+        uri: impl Into<String>,
+        chunk_size: u64,
+    ) -> impl futures::Stream<Item = Result<String>> + '_ {
+        let uri = uri.into();
+        // State: next offset (None when done) and the etag of the first slice
+        futures::stream::try_unfold((Some(0_u64), None::<String>), move |(offset, etag)| {
+            let uri = uri.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                if chunk_size == 0 {
+                    return Err(Error::validation("chunk_size must be greater than 0"));
+                }
+                let result = self
+                    .read_resource_range(uri.clone(), offset, Some(chunk_size))
+                    .await?;
+                let text = result.text().unwrap_or_default().to_string();
+                let Some(info) = result.range_info() else {
+                    return Ok(Some((text, (None, etag))));
+                };
+                if etag.as_ref().is_some_and(|etag| *etag != info.etag) {
+                    return Err(Error::validation(format!(
+                        "Resource '{}' changed during ranged read",
+                        uri
+                    )));
+                }
+                Ok(Some((text, (info.next_offset, Some(info.etag)))))
+            }
+        })
+    }
+
+    /// Send a `resources/read` request.
+    async fn read_resource_with(&self, req: ReadResourceRequest) -> Result<ReadResourceResult> {
         self.ensure_initialized()?;
         self.assert_capability("resources", "resources/read")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-            uri,
-            _meta: None,
-        })));
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(req)));
         let request_id = RequestId::String(Uuid::new_v4().to_string());
         let response = self.send_request(request_id, request).await?;
 
@@ -1178,6 +1562,7 @@ impl<T: Transport> Client<T> {
     ///         name: "function_name".to_string(),
     ///         value: "calc_".to_string(),
     ///     },
+    ///     context: None,
     /// };
     ///
     /// let completions = client.complete(completion_request).await?;
@@ -1614,6 +1999,8 @@ impl<T: Transport> Client<T> {
                         );
                     }
 
+                    self.list_changes.record(&notification);
+
                     // Forward to notification handler if registered
                     if let Some(tx) = &self.notification_tx {
                         // Clone the sender because send() requires &mut self
@@ -1826,6 +2213,8 @@ impl<T: Transport> Clone for Client<T> {
             info: self.info.clone(),
             notification_tx: self.notification_tx.clone(),
             active_requests: self.active_requests.clone(),
+            tool_cache: self.tool_cache.clone(),
+            list_changes: self.list_changes.clone(),
         }
     }
 }
@@ -2026,6 +2415,115 @@ mod tests {
         assert_eq!(tools.tools[0].name, "test-tool");
     }
 
+    #[tokio::test]
+    async fn test_iter_tools_restarts_on_list_changed() {
+        use futures::TryStreamExt;
+
+        let response = |id: i64, result: serde_json::Value| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(result),
+            })
+        };
+        let tool = |name: &str| json!({ "name": name, "inputSchema": {} });
+        let init_response = response(
+            1,
+            json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": { "tools": { "listChanged": true } },
+                "serverInfo": { "name": "test-server", "version": "1.0.0" }
+            }),
+        );
+        // Responses are popped from the end.
+        let transport = MockTransport::with_responses(vec![
+            response(5, json!({ "tools": [tool("a"), tool("c")] })),
+            response(4, json!({ "tools": [tool("b")] })),
+            TransportMessage::Notification(Notification::Server(
+                crate::types::ServerNotification::ToolsChanged,
+            )),
+            response(3, json!({ "tools": [tool("a")], "nextCursor": "2" })),
+            init_response,
+        ]);
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::minimal())
+            .await
+            .unwrap();
+
+        let names: Vec<String> = client
+            .iter_tools()
+            .map_ok(|tool| tool.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(names, ["a", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_typed() {
+        #[derive(serde::Deserialize)]
+        struct AddOutput {
+            result: i64,
+        }
+
+        let init_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(1i64),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {
+                    "tools": {}
+                },
+                "serverInfo": {
+                    "name": "test-server",
+                    "version": "1.0.0"
+                }
+            })),
+        });
+
+        let tools_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(2i64),
+            payload: ResponsePayload::Result(json!({
+                "tools": [{
+                    "name": "add",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}},
+                        "required": ["a", "b"]
+                    },
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": {"result": {"type": "integer"}},
+                        "required": ["result"]
+                    }
+                }]
+            })),
+        });
+
+        let call_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(3i64),
+            payload: ResponsePayload::Result(json!({
+                "content": [{"type": "text", "text": "5"}],
+                "structuredContent": {"result": 5}
+            })),
+        });
+
+        let transport =
+            MockTransport::with_responses(vec![call_response, tools_response, init_response]);
+        let mut client = Client::new(transport);
+        let _ = client.initialize(ClientCapabilities::minimal()).await;
+
+        let output: AddOutput = client
+            .call_tool_typed("add", &json!({"a": 2, "b": 3}))
+            .await
+            .unwrap();
+        assert_eq!(output.result, 5);
+        assert!(client.tool_cache.read().await.contains_key("add"));
+    }
+
     #[tokio::test]
     async fn test_error_response() {
         let init_response = TransportMessage::Response(JSONRPCResponse {
@@ -2180,6 +2678,7 @@ mod tests {
                     name: "test".to_string(),
                     value: "t".to_string(),
                 },
+                context: None,
             })
             .await;
         assert!(result.is_ok());
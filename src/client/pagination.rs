@@ -0,0 +1,322 @@
+//! Streams over paginated `*/list` results.
+//!
+//! [`Client::iter_tools`](super::Client::iter_tools),
+//! [`Client::iter_resources`](super::Client::iter_resources) and
+//! [`Client::iter_prompts`](super::Client::iter_prompts) return a
+//! [`Paginated`] stream that follows `nextCursor` until the server runs out
+//! of pages, so callers do not have to write the cursor loop themselves.
+//!
+//! If the server sends the matching `notifications/*/list_changed` while a
+//! stream is being consumed, the stream starts again from the first page and
+//! skips items it has already yielded. Items removed by the change may
+//! already have been yielded. After [`Paginated::max_restarts`] restarts the
+//! stream yields an error instead of starting over again.
+//!
+//! Cursors make pagination sequential, so at most one page request is in
+//! flight per stream. [`Paginated::prefetch`] lets that request run while
+//! earlier pages are still being consumed.
+
+use crate::error::{Error, Result};
+use crate::types::{Notification, ServerNotification};
+use futures::Stream;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+/// Restarts allowed by default before a changing list is reported as an error.
+const DEFAULT_MAX_RESTARTS: usize = 3;
+
+/// One page of a list: its items and the cursor of the next page.
+pub(crate) type Page<I> = (Vec<I>, Option<String>);
+
+#[cfg(not(target_arch = "wasm32"))]
+type PageFuture<'a, I> = Pin<Box<dyn Future<Output = Result<Page<I>>> + Send + 'a>>;
+#[cfg(target_arch = "wasm32")]
+type PageFuture<'a, I> = Pin<Box<dyn Future<Output = Result<Page<I>>> + 'a>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+type Fetch<'a, I> = Box<dyn FnMut(Option<String>) -> PageFuture<'a, I> + Send + 'a>;
+#[cfg(target_arch = "wasm32")]
+type Fetch<'a, I> = Box<dyn FnMut(Option<String>) -> PageFuture<'a, I> + 'a>;
+
+/// Counts the `list_changed` notifications a client has received, per list.
+#[derive(Debug, Default)]
+pub(crate) struct ListChanges {
+    pub(crate) tools: AtomicU64,
+    pub(crate) resources: AtomicU64,
+    pub(crate) prompts: AtomicU64,
+}
+
+impl ListChanges {
+    /// Count `notification` if it announces a list change.
+    pub(crate) fn record(&self, notification: &Notification) {
+        let counter = match notification {
+            Notification::Server(ServerNotification::ToolsChanged) => &self.tools,
+            Notification::Server(ServerNotification::ResourcesChanged) => &self.resources,
+            Notification::Server(ServerNotification::PromptsChanged) => &self.prompts,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A stream of every item in a paginated server list.
+///
+/// Yields `Err` and ends if a page request fails or the list keeps changing.
+pub struct Paginated<'a, I> {
+    list: &'static str,
+    fetch: Fetch<'a, I>,
+    key: fn(&I) -> &str,
+    changes: &'a AtomicU64,
+    generation: u64,
+    prefetch: usize,
+    max_restarts: usize,
+    restarts: usize,
+    seen: HashSet<String>,
+    pages: VecDeque<VecDeque<I>>,
+    in_flight: Option<PageFuture<'a, I>>,
+    /// Cursor of the next page to request; `None` once the last page is known.
+    next: Option<Option<String>>,
+    done: bool,
+}
+
+impl<'a, I> Paginated<'a, I> {
+    pub(crate) fn new(
+        list: &'static str,
+        changes: &'a AtomicU64,
+        key: fn(&I) -> &str,
+        fetch: Fetch<'a, I>,
+    ) -> Self {
+        Self {
+            list,
+            fetch,
+            key,
+            changes,
+            generation: changes.load(Ordering::SeqCst),
+            prefetch: 0,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            restarts: 0,
+            seen: HashSet::new(),
+            pages: VecDeque::new(),
+            in_flight: None,
+            next: Some(None),
+            done: false,
+        }
+    }
+
+    /// Request up to `pages` pages ahead of the one being consumed.
+    ///
+    /// The default of 0 requests a page only once the previous one is used up.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Allow the list to change `restarts` times during iteration (default 3).
+    pub fn max_restarts(mut self, restarts: usize) -> Self {
+        self.max_restarts = restarts;
+        self
+    }
+
+    fn wants_page(&self) -> bool {
+        self.in_flight.is_none()
+            && self.next.is_some()
+            && self.pages.iter().filter(|page| !page.is_empty()).count() <= self.prefetch
+    }
+
+    /// Start again from the first page after a `list_changed` notification.
+    fn restart(&mut self, generation: u64) -> Result<()> {
+        self.restarts += 1;
+        if self.restarts > self.max_restarts {
+            return Err(Error::internal(format!(
+                "The server's {} list changed {} times during iteration",
+                self.list, self.restarts
+            )));
+        }
+        self.generation = generation;
+        self.pages.clear();
+        self.in_flight = None;
+        self.next = Some(None);
+        Ok(())
+    }
+
+    fn fail(&mut self, error: Error) -> Poll<Option<Result<I>>> {
+        self.done = true;
+        self.pages.clear();
+        self.in_flight = None;
+        Poll::Ready(Some(Err(error)))
+    }
+}
+
+impl<I> Unpin for Paginated<'_, I> {}
+
+impl<I> Stream for Paginated<'_, I> {
+    type Item = Result<I>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            if this.wants_page() {
+                let cursor = this.next.take().flatten();
+                this.in_flight = Some((this.fetch)(cursor));
+            }
+            let Some(fetch) = this.in_flight.as_mut() else {
+                break;
+            };
+            let Poll::Ready(page) = fetch.as_mut().poll(cx) else {
+                break;
+            };
+            this.in_flight = None;
+            let (items, cursor) = match page {
+                Ok(page) => page,
+                Err(e) => return this.fail(e),
+            };
+            let generation = this.changes.load(Ordering::SeqCst);
+            if generation != this.generation {
+                if let Err(e) = this.restart(generation) {
+                    return this.fail(e);
+                }
+                continue;
+            }
+            this.next = cursor.filter(|c| !c.is_empty()).map(Some);
+            let key = this.key;
+            let seen = &this.seen;
+            this.pages.push_back(
+                items
+                    .into_iter()
+                    .filter(|item| !seen.contains(key(item)))
+                    .collect(),
+            );
+        }
+
+        while let Some(page) = this.pages.front_mut() {
+            if let Some(item) = page.pop_front() {
+                this.seen.insert((this.key)(&item).to_string());
+                return Poll::Ready(Some(Ok(item)));
+            }
+            this.pages.pop_front();
+        }
+        if this.in_flight.is_some() {
+            return Poll::Pending;
+        }
+        this.done = true;
+        Poll::Ready(None)
+    }
+}
+
+impl<I> fmt::Debug for Paginated<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginated")
+            .field("list", &self.list)
+            .field("prefetch", &self.prefetch)
+            .field("max_restarts", &self.max_restarts)
+            .field("restarts", &self.restarts)
+            .field("yielded", &self.seen.len())
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    /// Serves `pages` in order, bumping `changes` before serving the pages listed in `change_at`.
+    fn paginated<'a>(
+        pages: Vec<Page<String>>,
+        changes: &'a AtomicU64,
+        change_at: Vec<usize>,
+        requests: Arc<Mutex<Vec<Option<String>>>>,
+    ) -> Paginated<'a, String> {
+        let mut served = 0;
+        Paginated::new(
+            "tools",
+            changes,
+            String::as_str,
+            Box::new(move |cursor| {
+                requests.lock().unwrap().push(cursor);
+                if change_at.contains(&served) {
+                    changes.fetch_add(1, Ordering::SeqCst);
+                }
+                let page = pages[served % pages.len()].clone();
+                served += 1;
+                Box::pin(async move { Ok(page) })
+            }),
+        )
+    }
+
+    fn pages() -> Vec<Page<String>> {
+        vec![
+            (vec!["a".into(), "b".into()], Some("2".into())),
+            (vec!["c".into()], Some("3".into())),
+            (vec!["d".into()], None),
+        ]
+    }
+
+    #[tokio::test]
+    async fn follows_cursors_to_the_last_page() {
+        let changes = AtomicU64::new(0);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let items: Vec<String> = paginated(pages(), &changes, vec![], requests.clone())
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(items, ["a", "b", "c", "d"]);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [None, Some("2".to_string()), Some("3".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn restarts_on_list_changed_without_repeating_items() {
+        let changes = AtomicU64::new(0);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let [first, second, third] = <[Page<String>; 3]>::try_from(pages()).unwrap();
+        let changed = (vec!["a".into(), "b".into(), "e".into()], Some("2".into()));
+        // The change arrives while the second page is fetched.
+        let served = vec![first, second.clone(), changed, second, third];
+        let items: Vec<String> = paginated(served, &changes, vec![1], requests.clone())
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(items, ["a", "b", "e", "c", "d"]);
+        assert_eq!(requests.lock().unwrap()[2], None);
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_the_list_keeps_changing() {
+        let changes = AtomicU64::new(0);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let results: Vec<Result<String>> =
+            paginated(pages(), &changes, (0..10).collect(), requests)
+                .max_restarts(2)
+                .collect()
+                .await;
+        assert_eq!(results.len(), 1);
+        let err = results[0].as_ref().unwrap_err().to_string();
+        assert!(err.contains("tools list changed 3 times"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn prefetch_requests_the_next_page_before_it_is_needed() {
+        let changes = AtomicU64::new(0);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut stream = paginated(pages(), &changes, vec![], requests.clone()).prefetch(1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), "a");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut stream = paginated(pages(), &changes, vec![], requests.clone());
+        assert_eq!(stream.next().await.unwrap().unwrap(), "a");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,221 @@
+//! Schema checks for [`Client::call_tool_typed`](super::Client::call_tool_typed).
+//!
+//! Arguments are checked against the tool's advertised `inputSchema` before
+//! the request is sent, and the structured result is checked against its
+//! `outputSchema` before being deserialized. Schema checks need the
+//! `validation` feature; without it only deserialization is checked.
+
+use crate::error::{Error, Result};
+use crate::types::{CallToolResult, Content, ToolInfo};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+
+/// A single place where a value does not match a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (empty for the root).
+    pub path: String,
+    /// What was wrong with the value.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Check `value` against `schema`, returning every violation found.
+///
+/// Returns an empty list when the `validation` feature is disabled.
+pub fn schema_violations(schema: &Value, value: &Value) -> Result<Vec<SchemaViolation>> {
+    #[cfg(feature = "validation")]
+    {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| Error::validation(format!("Schema does not compile: {e}")))?;
+        Ok(validator
+            .iter_errors(value)
+            .map(|error| SchemaViolation {
+                path: error.instance_path().to_string(),
+                message: error.to_string(),
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "validation"))]
+    {
+        let _ = (schema, value);
+        Ok(Vec::new())
+    }
+}
+
+/// Check tool arguments against the tool's input schema.
+pub(crate) fn check_input(tool: &ToolInfo, arguments: &Value) -> Result<()> {
+    let violations = schema_violations(&tool.input_schema, arguments)?;
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatch(
+            format!(
+                "Arguments for tool '{}' do not match its input schema",
+                tool.name
+            ),
+            &violations,
+        ))
+    }
+}
+
+/// Extract, check, and deserialize the structured output of a tool call.
+///
+/// Uses `structuredContent` when present. Tools without an output schema
+/// may instead return their result as JSON text content.
+pub(crate) fn decode_output<O: DeserializeOwned>(
+    tool: &ToolInfo,
+    mut result: CallToolResult,
+) -> Result<O> {
+    if result.is_error {
+        return Err(Error::internal(format!(
+            "Tool '{}' returned an error: {}",
+            tool.name,
+            text_content(&result).unwrap_or("no details")
+        )));
+    }
+
+    let output = match (result.structured_content.take(), &tool.output_schema) {
+        (Some(structured), _) => structured,
+        (None, Some(_)) => {
+            return Err(Error::validation(format!(
+                "Tool '{}' declares an output schema but returned no structured content",
+                tool.name
+            )));
+        },
+        (None, None) => {
+            let text = text_content(&result).ok_or_else(|| {
+                Error::validation(format!(
+                    "Tool '{}' returned no structured or text content",
+                    tool.name
+                ))
+            })?;
+            serde_json::from_str(text).map_err(|e| {
+                Error::validation(format!(
+                    "Tool '{}' returned text that is not JSON: {e}",
+                    tool.name
+                ))
+            })?
+        },
+    };
+
+    if let Some(schema) = &tool.output_schema {
+        let violations = schema_violations(schema, &output)?;
+        if !violations.is_empty() {
+            return Err(mismatch(
+                format!(
+                    "Result of tool '{}' does not match its output schema",
+                    tool.name
+                ),
+                &violations,
+            ));
+        }
+    }
+
+    serde_json::from_value(output).map_err(|e| {
+        Error::validation(format!(
+            "Result of tool '{}' does not match the expected type: {e}",
+            tool.name
+        ))
+    })
+}
+
+fn text_content(result: &CallToolResult) -> Option<&str> {
+    result.content.iter().find_map(|c| match c {
+        Content::Text { text } => Some(text.as_str()),
+        _ => None,
+    })
+}
+
+fn mismatch(summary: String, violations: &[SchemaViolation]) -> Error {
+    let details: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    Error::validation(format!("{summary}:\n  - {}", details.join("\n  - ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sum {
+        result: i64,
+    }
+
+    fn add_tool() -> ToolInfo {
+        ToolInfo::new(
+            "add",
+            None,
+            json!({
+                "type": "object",
+                "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}},
+                "required": ["a", "b"]
+            }),
+        )
+        .with_output_schema(json!({
+            "type": "object",
+            "properties": {"result": {"type": "integer"}},
+            "required": ["result"]
+        }))
+    }
+
+    #[test]
+    fn test_decode_structured_output() {
+        let result = CallToolResult::new(vec![]).with_structured_content(json!({"result": 3}));
+        let sum: Sum = decode_output(&add_tool(), result).unwrap();
+        assert_eq!(sum, Sum { result: 3 });
+    }
+
+    #[test]
+    fn test_missing_structured_output_is_rejected() {
+        let result = CallToolResult::new(vec![Content::text("3")]);
+        let err = decode_output::<Sum>(&add_tool(), result).unwrap_err();
+        assert!(err.to_string().contains("no structured content"));
+    }
+
+    #[test]
+    fn test_text_output_without_schema() {
+        let tool = ToolInfo::new("add", None, json!({"type": "object"}));
+        let result = CallToolResult::new(vec![Content::text(r#"{"result": 5}"#)]);
+        let sum: Sum = decode_output(&tool, result).unwrap();
+        assert_eq!(sum, Sum { result: 5 });
+    }
+
+    #[test]
+    fn test_error_result_is_surfaced() {
+        let result = CallToolResult::error(vec![Content::text("division by zero")]);
+        let err = decode_output::<Sum>(&add_tool(), result).unwrap_err();
+        assert!(err.to_string().contains("division by zero"));
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_input_violations_list_each_path() {
+        let err = check_input(&add_tool(), &json!({"a": "one"})).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("input schema"));
+        assert!(message.contains("/a"));
+        assert!(message.contains("\"b\""));
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_output_drift_is_reported() {
+        let result =
+            CallToolResult::new(vec![]).with_structured_content(json!({"result": "three"}));
+        let err = decode_output::<Sum>(&add_tool(), result).unwrap_err();
+        assert!(err.to_string().contains("/result"));
+    }
+}
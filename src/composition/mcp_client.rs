@@ -13,6 +13,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use url::Url;
 
+use crate::client::http_middleware::{
+    HttpMiddleware, HttpMiddlewareChain, HttpMiddlewareContext, HttpRequest,
+};
+use crate::server::observability::TraceContext;
+use crate::shared::http_constants::{TRACEPARENT, TRACESTATE};
 use crate::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
 use crate::types::ClientCapabilities;
 use crate::Client;
@@ -25,11 +30,37 @@ struct FoundationConnection {
     initialized: bool,
 }
 
+/// Injects the current [`TraceContext`] as W3C `traceparent`/`tracestate`
+/// headers on outbound requests.
+struct TracePropagation;
+
+#[async_trait]
+impl HttpMiddleware for TracePropagation {
+    async fn on_request(
+        &self,
+        request: &mut HttpRequest,
+        _context: &HttpMiddlewareContext,
+    ) -> crate::Result<()> {
+        if let Some(trace) = TraceContext::current() {
+            request.add_header(TRACEPARENT, &trace.traceparent());
+            if let Some(tracestate) = &trace.tracestate {
+                request.add_header(TRACESTATE, tracestate);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// MCP-based foundation client that connects to servers over HTTP.
 ///
 /// This client maintains persistent connections to foundation servers,
 /// initializing them once and reusing them for subsequent calls.
 ///
+/// Calls made while a trace is current (inside a tool handler with
+/// observability enabled, or a request that arrived with a `traceparent`
+/// header) carry W3C `traceparent`/`tracestate` headers, so foundation
+/// servers join the caller's distributed trace.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -144,6 +175,10 @@ impl McpFoundationClient {
             CompositionError::Configuration(format!("Invalid URL for {}: {}", server_id, e))
         })?;
 
+        // Propagate the current trace on every request
+        let mut middleware = HttpMiddlewareChain::new();
+        middleware.add(Arc::new(TracePropagation));
+
         // Build transport configuration
         let mut transport_config = StreamableHttpTransportConfig {
             url,
@@ -156,7 +191,8 @@ impl McpFoundationClient {
             session_id: None,
             enable_json_response: endpoint.enable_json_response,
             on_resumption_token: None,
-            http_middleware_chain: None,
+            on_resume: None,
+            http_middleware_chain: Some(Arc::new(middleware)),
         };
 
         // Add auth header if configured
@@ -45,16 +45,25 @@
 //! [foundations.database]
 //! url = "http://localhost:8081"
 //! ```
+//!
+//! # Workflows
+//!
+//! Workflow steps can call foundation tools directly. Build the step with
+//! [`ToolHandle::remote`](crate::server::workflow::ToolHandle::remote), e.g.
+//! `ToolHandle::remote("calculator", "add")`, and pass the client to the server
+//! builder with `.foundation_client(...)` before registering the workflow.
 
 mod config;
 mod error;
 mod mcp_client;
 mod types;
+mod vendor;
 
 pub use config::{FoundationConfig, FoundationEndpoint};
 pub use error::CompositionError;
 pub use mcp_client::McpFoundationClient;
 pub use types::{EmbeddedResource, PromptContent, PromptMessage, PromptResult, ResourceContent};
+pub use vendor::schema_fingerprint;
 
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
@@ -129,7 +138,10 @@ pub trait FoundationClient: Send + Sync {
         server_id: &str,
         tool_name: &str,
         arguments: &serde_json::Value,
-    ) -> Result<T, CompositionError> {
+    ) -> Result<T, CompositionError>
+    where
+        Self: Sized,
+    {
         let result = self.call_tool(server_id, tool_name, arguments).await?;
         serde_json::from_str(&result).map_err(|e| {
             CompositionError::Deserialization(format!(
@@ -0,0 +1,68 @@
+//! Support for vendored foundation schemas.
+//!
+//! `cargo pmcp schema vendor <server_id>` copies a foundation's exported
+//! schema into the domain server's repository and generates typed bindings
+//! next to it (`src/foundations/<server_id>.rs`). The generated module pins
+//! the schema it was generated from with a compile-time check:
+//!
+//! ```rust,ignore
+//! pub const SCHEMA_JSON: &str = include_str!("calculator.json");
+//!
+//! const _: () = assert!(
+//!     pmcp::composition::schema_fingerprint(SCHEMA_JSON.as_bytes()) == 0x9f3c_0a4e_1d2b_7765,
+//!     "calculator.json changed since bindings were generated; run `cargo pmcp schema vendor calculator`"
+//! );
+//! ```
+//!
+//! Editing or re-exporting the vendored JSON without regenerating the
+//! bindings therefore fails the build instead of failing at call time.
+
+/// Fingerprint of a vendored schema file, usable in `const` context.
+///
+/// 64-bit FNV-1a over the file bytes with `\r` skipped, so checkouts with
+/// CRLF line endings fingerprint the same as LF ones. This is a drift
+/// check, not a cryptographic hash.
+pub const fn schema_fingerprint(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\r' {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+        i += 1;
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_known_values() {
+        assert_eq!(schema_fingerprint(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(schema_fingerprint(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_carriage_returns() {
+        assert_eq!(
+            schema_fingerprint(b"{\r\n  \"a\": 1\r\n}\r\n"),
+            schema_fingerprint(b"{\n  \"a\": 1\n}\n")
+        );
+        assert_ne!(
+            schema_fingerprint(b"{\"a\":1}"),
+            schema_fingerprint(b"{\"a\":2}")
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_is_const() {
+        const PINNED: u64 = schema_fingerprint(b"schema");
+        assert_eq!(PINNED, schema_fingerprint(b"schema"));
+    }
+}
@@ -0,0 +1,383 @@
+//! Replace personal data with realistic, stable fake values.
+//!
+//! [`Anonymizer`] maps real names, email addresses and numbers to fake ones
+//! derived from a salted SHA-256 hash of the original. The same input always
+//! maps to the same output (for a given salt), so relationships survive
+//! anonymization: a customer that appears in a tool's arguments and again in
+//! its result is replaced by the same fake customer in both places.
+//!
+//! Use it to turn captured traffic, recorded scenarios or demo resources into
+//! fixtures and bug reports that can be shared without leaking real data.
+//!
+//! # Examples
+//!
+//! ```
+//! use pmcp::data::anonymize::Anonymizer;
+//! use serde_json::json;
+//!
+//! let anonymizer = Anonymizer::new();
+//!
+//! let customer = json!({
+//!     "name": "Jane Doe",
+//!     "email": "jane.doe@acme.com",
+//!     "phone": "+1 (555) 010-4477",
+//!     "plan": "enterprise",
+//! });
+//! let shared = anonymizer.value(&customer);
+//!
+//! assert_ne!(shared["name"], "Jane Doe");
+//! assert!(shared["email"].as_str().unwrap().ends_with("@example.com"));
+//! assert_eq!(shared["plan"], "enterprise");
+//!
+//! // Stable: the same input always maps to the same fake value
+//! assert_eq!(anonymizer.name("Jane Doe"), shared["name"]);
+//! ```
+//!
+//! # Security
+//!
+//! The mapping is one-way but not encryption: anyone who knows the salt can
+//! confirm a guessed value by anonymizing it. Use [`Anonymizer::with_salt`]
+//! with a private salt when the fixtures leave your organisation.
+
+use regex::Regex;
+use serde_json::{Number, Value};
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
+
+/// Salt used by [`Anonymizer::new`].
+pub const DEFAULT_SALT: &str = "pmcp-anonymize";
+
+/// Email addresses embedded in free text.
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bruno", "Chloe", "Dmitri", "Elena", "Farid", "Grace", "Hiro", "Ines", "Jonas",
+    "Keiko", "Liam", "Maya", "Nikhil", "Olivia", "Pablo", "Quinn", "Rosa", "Samir", "Tara", "Uma",
+    "Victor", "Wen", "Yusuf",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Andersen", "Barros", "Castillo", "Dubois", "Eriksen", "Fischer", "Garcia", "Haddad", "Ito",
+    "Jensen", "Kowalski", "Larsen", "Moreau", "Nakamura", "Okafor", "Petrov", "Quint", "Rossi",
+    "Schmidt", "Tanaka", "Ueda", "Varga", "Weber", "Zhang",
+];
+
+/// Kind of personal data a JSON field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A person's name (`name`, `first_name`, `customerName`, ...).
+    Name,
+    /// An email address (`email`, `contact_email`, ...).
+    Email,
+    /// A number that identifies someone (`phone`, `ssn`, `account_number`, ...).
+    Number,
+}
+
+impl FieldKind {
+    /// Guesses the kind of data stored under a JSON object key.
+    ///
+    /// Matching ignores case, `_`, `-` and spaces, so `first_name`,
+    /// `firstName` and `First-Name` are all recognised.
+    pub fn for_key(key: &str) -> Option<Self> {
+        let key: String = key
+            .chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        match key.as_str() {
+            "name" | "firstname" | "lastname" | "middlename" | "fullname" | "givenname"
+            | "familyname" | "surname" | "displayname" | "customername" | "contactname" => {
+                Some(Self::Name)
+            },
+            "mail" | "emailaddress" => Some(Self::Email),
+            "mobile"
+            | "telephone"
+            | "fax"
+            | "ssn"
+            | "socialsecuritynumber"
+            | "taxid"
+            | "accountnumber"
+            | "iban"
+            | "cardnumber"
+            | "creditcard"
+            | "creditcardnumber"
+            | "routingnumber"
+            | "passportnumber"
+            | "licensenumber" => Some(Self::Number),
+            key if key.ends_with("email") => Some(Self::Email),
+            key if key.ends_with("phone") || key.ends_with("phonenumber") => Some(Self::Number),
+            _ => None,
+        }
+    }
+}
+
+/// Maps personal data to stable fake values.
+///
+/// See the [module documentation](self) for an overview.
+#[derive(Debug, Clone)]
+pub struct Anonymizer {
+    salt: String,
+}
+
+impl Default for Anonymizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Anonymizer {
+    /// Creates an anonymizer with the [default salt](DEFAULT_SALT).
+    pub fn new() -> Self {
+        Self::with_salt(DEFAULT_SALT)
+    }
+
+    /// Creates an anonymizer with a custom salt.
+    ///
+    /// Different salts produce unrelated mappings for the same input.
+    pub fn with_salt(salt: impl Into<String>) -> Self {
+        Self { salt: salt.into() }
+    }
+
+    /// Replaces a person's name with a fake one.
+    ///
+    /// Single-word names map to a first name, anything longer to a first and
+    /// last name. Case and surrounding whitespace do not affect the result.
+    pub fn name(&self, real: &str) -> String {
+        let normalized = real.split_whitespace().collect::<Vec<_>>().join(" ");
+        let hash = self.hash("name", &normalized.to_lowercase());
+        let first = pick(FIRST_NAMES, hash[0]);
+        if normalized.contains(' ') {
+            format!("{} {}", first, pick(LAST_NAMES, hash[1]))
+        } else {
+            first.to_string()
+        }
+    }
+
+    /// Replaces an email address with a fake one at `example.com`.
+    pub fn email(&self, real: &str) -> String {
+        let hash = self.hash("email", &real.trim().to_lowercase());
+        format!(
+            "{}.{}{}@example.com",
+            pick(FIRST_NAMES, hash[0]).to_lowercase(),
+            pick(LAST_NAMES, hash[1]).to_lowercase(),
+            u16::from_be_bytes([hash[2], hash[3]]) % 100
+        )
+    }
+
+    /// Replaces a number with a fake one with the same sign and digit count.
+    pub fn number(&self, real: i64) -> i64 {
+        if real == 0 {
+            return 0;
+        }
+        let magnitude = real.unsigned_abs().to_string();
+        let hash = self.hash("number", &real.to_string());
+        let digit_count = magnitude.len();
+
+        let mut fake: u64 = 0;
+        for (idx, byte) in hash.iter().take(digit_count).enumerate() {
+            let digit = match idx {
+                // Keep the digit count; 19-digit values must stay below i64::MAX
+                0 if digit_count == 19 => 1 + byte % 8,
+                0 => 1 + byte % 9,
+                _ => byte % 10,
+            };
+            fake = fake * 10 + u64::from(digit);
+        }
+
+        let fake = i64::try_from(fake).unwrap_or(i64::MAX);
+        if real < 0 {
+            -fake
+        } else {
+            fake
+        }
+    }
+
+    /// Replaces every ASCII digit in `real`, keeping all other characters.
+    ///
+    /// Suited to formatted identifiers such as phone or card numbers, where
+    /// the formatting is useful in a fixture but the digits are not.
+    pub fn digits(&self, real: &str) -> String {
+        let mut hash = self.hash("digits", real);
+        let mut block = 0;
+        let mut used = 0;
+
+        real.chars()
+            .map(|c| {
+                if !c.is_ascii_digit() {
+                    return c;
+                }
+                if used == hash.len() {
+                    block += 1;
+                    hash = self.hash("digits", &format!("{real}\0{block}"));
+                    used = 0;
+                }
+                let digit = hash[used] % 10;
+                used += 1;
+                char::from(b'0' + digit)
+            })
+            .collect()
+    }
+
+    /// Replaces the email addresses found in free text.
+    pub fn text(&self, real: &str) -> String {
+        EMAIL
+            .replace_all(real, |caps: &regex::Captures<'_>| self.email(&caps[0]))
+            .into_owned()
+    }
+
+    /// Anonymizes a value stored under an object key.
+    ///
+    /// Keys recognised by [`FieldKind::for_key`] are anonymized by kind; any
+    /// other value is passed through [`value`](Self::value).
+    pub fn field(&self, key: &str, value: &Value) -> Value {
+        match FieldKind::for_key(key) {
+            Some(kind) => self.kind(kind, value),
+            None => self.value(value),
+        }
+    }
+
+    /// Anonymizes a JSON document.
+    ///
+    /// Object fields are anonymized according to their key (see
+    /// [`FieldKind::for_key`]), and email addresses are replaced in every
+    /// other string. Other values are kept unchanged.
+    pub fn value(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), self.field(key, value)))
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.value(v)).collect()),
+            Value::String(s) => Value::String(self.text(s)),
+            other => other.clone(),
+        }
+    }
+
+    fn kind(&self, kind: FieldKind, value: &Value) -> Value {
+        match (kind, value) {
+            (FieldKind::Name, Value::String(s)) => Value::String(self.name(s)),
+            (FieldKind::Email, Value::String(s)) => Value::String(self.email(s)),
+            (FieldKind::Number, Value::String(s)) => Value::String(self.digits(s)),
+            (FieldKind::Number, Value::Number(n)) => match n.as_i64() {
+                Some(n) => Value::from(self.number(n)),
+                None => self
+                    .digits(&n.to_string())
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map_or(Value::Null, Value::Number),
+            },
+            (_, Value::Array(items)) => {
+                Value::Array(items.iter().map(|v| self.kind(kind, v)).collect())
+            },
+            (_, other) => self.value(other),
+        }
+    }
+
+    fn hash(&self, kind: &str, input: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update([0]);
+        hasher.update(kind.as_bytes());
+        hasher.update([0]);
+        hasher.update(input.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+fn pick(items: &'static [&'static str], byte: u8) -> &'static str {
+    items[usize::from(byte) % items.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn mappings_are_stable_and_salted() {
+        let anonymizer = Anonymizer::new();
+        assert_eq!(
+            anonymizer.name("Jane Doe"),
+            anonymizer.name("  jane   DOE ")
+        );
+        assert_eq!(
+            anonymizer.email("Jane@Acme.com"),
+            anonymizer.email("jane@acme.com")
+        );
+        assert_ne!(anonymizer.name("Jane Doe"), "Jane Doe");
+        assert_eq!(anonymizer.name("Jane").split(' ').count(), 1);
+        assert_eq!(anonymizer.name("Jane Q Doe").split(' ').count(), 2);
+
+        let other = Anonymizer::with_salt("secret");
+        let differs = ["a@b.io", "c@d.io", "e@f.io", "g@h.io"]
+            .iter()
+            .any(|email| anonymizer.email(email) != other.email(email));
+        assert!(differs);
+    }
+
+    #[test]
+    fn numbers_keep_shape() {
+        let anonymizer = Anonymizer::new();
+        for real in [0, 7, 42, -1234, 987_654_321, i64::MAX, i64::MIN + 1] {
+            let fake = anonymizer.number(real);
+            assert_eq!(fake.signum(), real.signum());
+            assert_eq!(
+                fake.unsigned_abs().to_string().len(),
+                real.unsigned_abs().to_string().len()
+            );
+        }
+
+        let phone = anonymizer.digits("+1 (555) 010-4477");
+        assert_eq!(phone.len(), "+1 (555) 010-4477".len());
+        assert!(phone.starts_with("+"));
+        assert_eq!(&phone[2..4], " (");
+        assert_eq!(anonymizer.digits("no digits"), "no digits");
+    }
+
+    #[test]
+    fn field_kinds_follow_key_conventions() {
+        assert_eq!(FieldKind::for_key("firstName"), Some(FieldKind::Name));
+        assert_eq!(FieldKind::for_key("customer_name"), Some(FieldKind::Name));
+        assert_eq!(FieldKind::for_key("billing-email"), Some(FieldKind::Email));
+        assert_eq!(FieldKind::for_key("mobilePhone"), Some(FieldKind::Number));
+        assert_eq!(
+            FieldKind::for_key("account_number"),
+            Some(FieldKind::Number)
+        );
+        assert_eq!(FieldKind::for_key("filename"), None);
+        assert_eq!(FieldKind::for_key("amount"), None);
+    }
+
+    #[test]
+    fn documents_are_anonymized_consistently() {
+        let anonymizer = Anonymizer::new();
+        let doc = json!({
+            "customers": [
+                {"name": "Jane Doe", "email": "jane@acme.com", "ssn": 123_456_789, "balance": 10.5},
+                {"full_name": "Jane Doe", "notes": "Reached at jane@acme.com on Monday"},
+            ],
+            "status": "active",
+        });
+
+        let out = anonymizer.value(&doc);
+        let fake_email = anonymizer.email("jane@acme.com");
+
+        assert_eq!(
+            out["customers"][0]["name"],
+            out["customers"][1]["full_name"]
+        );
+        assert_eq!(out["customers"][0]["email"], fake_email.as_str());
+        assert_eq!(
+            out["customers"][1]["notes"],
+            format!("Reached at {fake_email} on Monday")
+        );
+        assert_ne!(out["customers"][0]["ssn"], 123_456_789);
+        assert_eq!(out["customers"][0]["balance"], 10.5);
+        assert_eq!(out["status"], "active");
+    }
+}
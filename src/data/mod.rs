@@ -0,0 +1,5 @@
+//! Helpers for the data MCP servers expose as demo resources and fixtures.
+//!
+//! - [`anonymize`] - Replace names, emails and numbers with stable fake values
+
+pub mod anonymize;
@@ -81,11 +81,14 @@ pub mod client;
 #[cfg(feature = "composition")]
 #[cfg_attr(docsrs, doc(cfg(feature = "composition")))]
 pub mod composition;
+pub mod data;
 pub mod error;
 pub mod runtime;
 pub mod secrets;
 pub mod server;
 pub mod shared;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod storage;
 pub mod types;
 pub mod utils;
 
@@ -110,20 +113,24 @@ pub use client::{Client, ClientBuilder, ToolCallResponse};
 pub use error::{Error, ErrorCode, Result};
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::cancellation::RequestHandlerExtra;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
+pub use server::simple_resources::{DynamicResourceHandler, ResourceCollection, StaticResource};
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
 pub use server::task_store::{InMemoryTaskStore, StoreConfig, TaskStore, TaskStoreError};
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::{
     auth,
-    simple_prompt::{SimplePrompt, SyncPrompt},
-    simple_resources::{DynamicResourceHandler, ResourceCollection, StaticResource},
     simple_tool::{SimpleTool, SyncTool},
     state::State,
-    typed_prompt::TypedPrompt,
     typed_tool::{SimpleToolExt, SyncToolExt, TypedSyncTool, TypedTool, TypedToolWithOutput},
     ui::UIResourceBuilder,
     McpServer, PromptHandler, ResourceHandler, SamplingHandler, Server, ServerBuilder, ToolHandler,
 };
+#[cfg(all(not(target_arch = "wasm32"), feature = "prompts"))]
+pub use server::{
+    simple_prompt::{SimplePrompt, SyncPrompt},
+    typed_prompt::TypedPrompt,
+};
 #[cfg(target_arch = "wasm32")]
 pub use server::{
     wasm_server::{
@@ -140,6 +147,9 @@ pub use server::wasm_typed_tool::WasmTypedTool as TypedTool;
 // instead of adding pmcp-macros as a separate dependency.
 #[cfg(feature = "macros")]
 pub use pmcp_macros::{mcp_prompt, mcp_server, mcp_tool};
+// `#[pmcp::tool]` is `#[mcp_tool]`; the description may come from the doc comment.
+#[cfg(feature = "macros")]
+pub use pmcp_macros::mcp_tool as tool;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use shared::StdioTransport;
@@ -167,13 +177,13 @@ pub use shared::{WasmHttpClient, WasmHttpConfig, WasmHttpTransport};
 pub use shared::{HttpConfig, HttpTransport};
 pub use types::{
     AuthInfo, AuthScheme, CallToolRequest, CallToolResult, ClientCapabilities, ClientNotification,
-    ClientRequest, CompleteRequest, CompleteResult, CompletionArgument, CompletionReference,
-    Content, CreateMessageParams, CreateMessageResult, GetPromptResult, Implementation,
-    IncludeContext, ListResourcesResult, ListToolsResult, LoggingLevel, ModelPreferences,
-    ProgressNotification, ProgressToken, PromptMessage, ProtocolVersion, ReadResourceResult,
-    RequestId, ResourceInfo, Role, RootsCapabilities, SamplingCapabilities, SamplingMessage,
-    ServerCapabilities, ServerNotification, ServerRequest, TokenUsage, ToolCapabilities, ToolInfo,
-    UIMimeType, UIResource, UIResourceContents,
+    ClientRequest, CompleteRequest, CompleteResult, CompletionArgument, CompletionContext,
+    CompletionReference, Content, CreateMessageParams, CreateMessageResult, GetPromptResult,
+    Implementation, IncludeContext, ListResourcesResult, ListToolsResult, LoggingLevel,
+    ModelPreferences, ProgressNotification, ProgressToken, PromptMessage, ProtocolVersion,
+    ReadResourceResult, RequestId, ResourceInfo, Role, RootsCapabilities, SamplingCapabilities,
+    SamplingMessage, ServerCapabilities, ServerNotification, ServerRequest, TokenUsage,
+    ToolCapabilities, ToolInfo, UIMimeType, UIResource, UIResourceContents,
 };
 
 /// Type alias for [`CallToolResult`] - provides convenient access to tool execution results
@@ -521,4 +521,112 @@ mod tests {
             }
         }
     }
+
+    /// Transport fed from a channel, so the adapter waits for input the
+    /// way it does on stdio.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[derive(Debug)]
+    struct ChannelTransport {
+        incoming: tokio::sync::mpsc::UnboundedReceiver<TransportMessage>,
+        sent: tokio::sync::mpsc::UnboundedSender<TransportMessage>,
+        connected: bool,
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[async_trait]
+    impl TransportTrait for ChannelTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            let _ = self.sent.send(message);
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            if let Some(message) = self.incoming.recv().await {
+                Ok(message)
+            } else {
+                self.connected = false;
+                Err(crate::error::Error::internal("Client disconnected"))
+            }
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            self.connected = false;
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            self.connected
+        }
+
+        fn transport_type(&self) -> &'static str {
+            "channel"
+        }
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    #[tokio::test]
+    async fn test_generic_adapter_forwards_scheduled_task_status() {
+        use crate::server::task_store::InMemoryTaskStore;
+        use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+        use crate::types::tasks::TaskStatus;
+        use tokio::sync::mpsc;
+
+        let digest = SequentialWorkflow::new("digest", "Nightly digest")
+            .step(WorkflowStep::new("collect", ToolHandle::new("echo")));
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("echo", EchoTool)
+            .task_store(Arc::new(InMemoryTaskStore::new()))
+            .scheduled_workflow("digest", "0 3 * * *", digest)
+            .unwrap()
+            .build()
+            .unwrap();
+        let scheduler = server.scheduler().unwrap().clone();
+
+        let (client_tx, incoming) = mpsc::unbounded_channel();
+        let (sent, mut client_rx) = mpsc::unbounded_channel();
+        let adapter = GenericTransportAdapter::new(ChannelTransport {
+            incoming,
+            sent,
+            connected: true,
+        });
+        let serving = tokio::spawn(async move { adapter.serve(Arc::new(server)).await });
+
+        client_tx
+            .send(TransportMessage::Request {
+                id: RequestId::from(1i64),
+                request: Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+                    protocol_version: "2025-11-25".to_string(),
+                    capabilities: ClientCapabilities {
+                        tasks: Some(ClientTasksCapability::default()),
+                        ..Default::default()
+                    },
+                    client_info: Implementation::new("test-client", "1.0.0"),
+                }))),
+            })
+            .unwrap();
+        assert!(matches!(
+            client_rx.recv().await,
+            Some(TransportMessage::Response(_))
+        ));
+
+        let task = scheduler.run_now("digest").await.unwrap();
+        let mut statuses = Vec::new();
+        while statuses.len() < 2 {
+            match client_rx.recv().await {
+                Some(TransportMessage::Notification(Notification::Server(
+                    ServerNotification::TaskStatus(notification),
+                ))) => {
+                    assert_eq!(notification.task.task_id, task.task_id);
+                    statuses.push(notification.task.status);
+                },
+                other => panic!("Expected a task status notification, got {:?}", other),
+            }
+        }
+        assert_eq!(statuses, [TaskStatus::Working, TaskStatus::Completed]);
+
+        drop(client_tx);
+        serving.await.unwrap().unwrap();
+    }
 }
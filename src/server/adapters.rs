@@ -5,11 +5,13 @@
 
 use crate::error::Result;
 use crate::shared::{Transport as TransportTrait, TransportMessage};
+use crate::types::{ClientRequest, Notification, Request, ServerNotification};
 // Types are re-exported through TransportMessage
 #[cfg(test)]
-use crate::types::{JSONRPCResponse, Request, RequestId};
+use crate::types::{JSONRPCResponse, RequestId};
 use async_trait::async_trait;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[cfg(target_arch = "wasm32")]
 use futures::lock::RwLock;
@@ -64,10 +66,16 @@ impl<T: TransportTrait> GenericTransportAdapter<T> {
     }
 
     /// Process messages from the transport.
+    ///
+    /// While waiting for the next message, notifications pushed by the
+    /// handler are sent to the client. Task status notifications are only
+    /// sent once the client has declared the `tasks` capability.
     async fn process_messages(
         transport: Arc<RwLock<T>>,
         handler: Arc<dyn ProtocolHandler>,
     ) -> Result<()> {
+        let mut notifications = handler.notifications();
+        let mut tasks_declared = false;
         loop {
             // Receive message from transport
             let message = {
@@ -75,7 +83,32 @@ impl<T: TransportTrait> GenericTransportAdapter<T> {
                 if !t.is_connected() {
                     break;
                 }
-                if let Ok(msg) = t.receive().await {
+                let received = match notifications.as_mut() {
+                    Some(pushed) => tokio::select! {
+                        received = t.receive() => received,
+                        notification = pushed.recv() => {
+                            match notification {
+                                Ok(notification) => {
+                                    let is_task_status =
+                                        matches!(notification, ServerNotification::TaskStatus(_));
+                                    if tasks_declared || !is_task_status {
+                                        t.send(TransportMessage::Notification(
+                                            Notification::Server(notification),
+                                        ))
+                                        .await?;
+                                    }
+                                },
+                                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                    tracing::warn!(skipped, "Server notifications skipped for slow transport");
+                                },
+                                Err(broadcast::error::RecvError::Closed) => notifications = None,
+                            }
+                            continue;
+                        },
+                    },
+                    None => t.receive().await,
+                };
+                if let Ok(msg) = received {
                     msg
                 } else {
                     // Connection likely closed, check and break if so
@@ -89,6 +122,11 @@ impl<T: TransportTrait> GenericTransportAdapter<T> {
             // Process the message
             match message {
                 TransportMessage::Request { id, request } => {
+                    if let Request::Client(client_request) = &request {
+                        if let ClientRequest::Initialize(init) = client_request.as_ref() {
+                            tasks_declared = init.capabilities.tasks.is_some();
+                        }
+                    }
                     let response = handler.handle_request(id, request, None).await;
                     let mut t = transport.write().await;
                     t.send(TransportMessage::Response(response)).await?;
@@ -0,0 +1,225 @@
+//! Schema-driven coercion of stringly-typed tool arguments.
+//!
+//! LLM clients often send `"5"` for an integer or `"true"` for a boolean.
+//! Typed tools reject those by default. A tool in lenient-input mode first
+//! runs its arguments through [`coerce_arguments`], which converts strings to
+//! the number or boolean the input schema asks for:
+//!
+//! - `"5"` becomes `5` where the schema says `integer` or `number`
+//! - `"2.5"` becomes `2.5` where the schema says `number`
+//! - `"true"` / `"false"` (any case) become booleans where the schema says `boolean`
+//!
+//! Strings are left alone when the schema also accepts a string, when the
+//! text does not parse, or when the schema is ambiguous (`anyOf` with several
+//! non-null alternatives). Nested objects and arrays are coerced recursively.
+//!
+//! ```rust
+//! use pmcp::server::argument_coercion::coerce_arguments;
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "limit": { "type": "integer" },
+//!         "verbose": { "type": "boolean" },
+//!         "query": { "type": "string" }
+//!     }
+//! });
+//!
+//! let args = coerce_arguments(json!({ "limit": "5", "verbose": "true", "query": "42" }), &schema);
+//! assert_eq!(args, json!({ "limit": 5, "verbose": true, "query": "42" }));
+//! ```
+
+use serde_json::{Number, Value};
+
+use super::argument_error::resolve;
+
+/// Coerce string-encoded numbers and booleans in `args` to the types declared
+/// by `input_schema`.
+pub fn coerce_arguments(args: Value, input_schema: &Value) -> Value {
+    coerce(args, input_schema, input_schema)
+}
+
+fn coerce(value: Value, root: &Value, schema: &Value) -> Value {
+    let Some(schema) = resolve(root, schema) else {
+        return value;
+    };
+
+    match value {
+        Value::String(text) => coerce_string(text, schema),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let field_schema = schema
+                        .get("properties")
+                        .and_then(|p| p.get(&key))
+                        .or_else(|| schema.get("additionalProperties").filter(|v| v.is_object()));
+                    let value = match field_schema {
+                        Some(field_schema) => coerce(value, root, field_schema),
+                        None => value,
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let item_schema = schema
+                        .get("prefixItems")
+                        .and_then(|p| p.get(index))
+                        .or_else(|| match schema.get("items") {
+                            Some(Value::Array(items)) => items.get(index),
+                            Some(items) => Some(items),
+                            None => None,
+                        });
+                    match item_schema {
+                        Some(item_schema) => coerce(item, root, item_schema),
+                        None => item,
+                    }
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn coerce_string(text: String, schema: &Value) -> Value {
+    let types = schema_types(schema);
+    if types.is_empty() || types.contains(&"string") {
+        return Value::String(text);
+    }
+
+    let trimmed = text.trim();
+    let coerced = types
+        .contains(&"integer")
+        .then(|| parse_integer(trimmed))
+        .flatten()
+        .or_else(|| {
+            types
+                .contains(&"number")
+                .then(|| parse_number(trimmed))
+                .flatten()
+        })
+        .or_else(|| {
+            types
+                .contains(&"boolean")
+                .then(|| parse_boolean(trimmed))
+                .flatten()
+        });
+
+    coerced.unwrap_or(Value::String(text))
+}
+
+/// The `type` keyword as a list, e.g. `["integer", "null"]`.
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_integer(text: &str) -> Option<Value> {
+    text.parse::<i64>()
+        .map(Value::from)
+        .or_else(|_| text.parse::<u64>().map(Value::from))
+        .ok()
+}
+
+fn parse_number(text: &str) -> Option<Value> {
+    parse_integer(text).or_else(|| {
+        text.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+    })
+}
+
+fn parse_boolean(text: &str) -> Option<Value> {
+    if text.eq_ignore_ascii_case("true") {
+        Some(Value::Bool(true))
+    } else if text.eq_ignore_ascii_case("false") {
+        Some(Value::Bool(false))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": ["integer", "null"] },
+                "ratio": { "type": "number" },
+                "dry_run": { "type": "boolean" },
+                "label": { "type": "string" },
+                "code": { "type": ["string", "integer"] },
+                "filters": { "type": "array", "items": { "$ref": "#/$defs/Filter" } },
+                "weights": { "type": "object", "additionalProperties": { "type": "number" } },
+                "page": { "anyOf": [{ "type": "integer" }, { "type": "null" }] }
+            },
+            "$defs": {
+                "Filter": {
+                    "type": "object",
+                    "properties": { "min": { "type": "integer" } }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_coerces_scalars_to_schema_types() {
+        let args = json!({
+            "limit": " 5 ",
+            "ratio": "2.5",
+            "dry_run": "TRUE",
+            "label": "7",
+            "page": "3"
+        });
+        assert_eq!(
+            coerce_arguments(args, &schema()),
+            json!({ "limit": 5, "ratio": 2.5, "dry_run": true, "label": "7", "page": 3 })
+        );
+    }
+
+    #[test]
+    fn test_coerces_nested_values() {
+        let args = json!({
+            "filters": [{ "min": "1" }, { "min": 2 }],
+            "weights": { "a": "0.5", "b": 1 }
+        });
+        assert_eq!(
+            coerce_arguments(args, &schema()),
+            json!({
+                "filters": [{ "min": 1 }, { "min": 2 }],
+                "weights": { "a": 0.5, "b": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_leaves_unparseable_and_string_typed_values() {
+        let args = json!({
+            "limit": "five",
+            "ratio": "NaN",
+            "dry_run": "yes",
+            "code": "12",
+            "unknown": "1"
+        });
+        assert_eq!(coerce_arguments(args.clone(), &schema()), args);
+    }
+
+    #[test]
+    fn test_integer_schema_does_not_accept_fractions() {
+        let args = json!({ "limit": "2.5" });
+        assert_eq!(coerce_arguments(args.clone(), &schema()), args);
+    }
+}
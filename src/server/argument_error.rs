@@ -0,0 +1,418 @@
+//! Structured errors for tool arguments that fail to deserialize.
+//!
+//! Typed tools deserialize their arguments into a Rust type. When that fails,
+//! a bare serde message ("invalid type: string, expected f64") does not say
+//! which field was wrong. [`deserialize_arguments`] tracks the path to the
+//! failing field and returns an `INVALID_PARAMS` error whose `data` carries:
+//!
+//! - `pointer`: the JSON pointer of the offending field (`/items/2/price`)
+//! - `code`: a [`ValidationErrorCode`] such as `missing_field` or `type_mismatch`
+//! - `expected`: what the field should hold
+//! - `schema`: the fragment of the tool's input schema for that field
+//!
+//! ```rust
+//! use pmcp::server::argument_error::deserialize_arguments;
+//! use serde::Deserialize;
+//! use serde_json::json;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Order {
+//!     items: Vec<Item>,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Item {
+//!     price: f64,
+//! }
+//!
+//! let err = deserialize_arguments::<Order>(
+//!     "place_order",
+//!     json!({ "items": [{ "price": 1.5 }, { "price": "free" }] }),
+//!     &json!({}),
+//! )
+//! .unwrap_err();
+//!
+//! let pmcp::Error::Protocol { data: Some(data), .. } = err else {
+//!     panic!("expected a protocol error");
+//! };
+//! assert_eq!(data["pointer"], "/items/1/price");
+//! assert_eq!(data["code"], "type_mismatch");
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use super::error_codes::ValidationErrorCode;
+use crate::error::ErrorCode;
+use crate::{Error, Result};
+
+/// Deepest `$ref` chain followed when looking up a schema fragment.
+const MAX_REF_DEPTH: usize = 16;
+
+/// A tool argument that failed to deserialize.
+#[derive(Debug, Clone)]
+pub struct ArgumentError {
+    /// Tool whose arguments were rejected
+    pub tool: String,
+    /// JSON pointer to the offending field (empty for the arguments object)
+    pub pointer: String,
+    /// Kind of failure
+    pub code: ValidationErrorCode,
+    /// What the field should hold, from the schema or the deserializer
+    pub expected: Option<String>,
+    /// Deserializer message
+    pub message: String,
+    /// Input schema fragment describing the field
+    pub schema: Option<Value>,
+}
+
+impl ArgumentError {
+    /// Describe a deserialization failure against the tool's input schema.
+    pub fn new(
+        tool: impl Into<String>,
+        error: &serde_path_to_error::Error<serde_json::Error>,
+        input_schema: &Value,
+    ) -> Self {
+        let message = error.inner().to_string();
+        let mut segments = path_segments(error.path());
+
+        let code = if let Some(field) = quoted_field(&message, "missing field ") {
+            segments.push(field);
+            ValidationErrorCode::MissingField
+        } else if let Some(field) = quoted_field(&message, "unknown field ") {
+            if segments.last() != Some(&field) {
+                segments.push(field);
+            }
+            ValidationErrorCode::NotAllowed
+        } else if message.starts_with("invalid type") {
+            ValidationErrorCode::TypeMismatch
+        } else if message.starts_with("unknown variant") {
+            ValidationErrorCode::NotAllowed
+        } else if message.starts_with("invalid length") {
+            ValidationErrorCode::TooFewItems
+        } else if message.starts_with("invalid value") {
+            ValidationErrorCode::InvalidFormat
+        } else {
+            ValidationErrorCode::CustomValidation
+        };
+
+        let schema = schema_fragment(input_schema, &segments);
+        let expected = schema
+            .as_ref()
+            .and_then(describe_schema)
+            .or_else(|| serde_expected(&message));
+
+        Self {
+            tool: tool.into(),
+            pointer: json_pointer(&segments),
+            code,
+            expected,
+            message,
+            schema,
+        }
+    }
+
+    /// Machine-readable details sent as the error's `data`.
+    pub fn to_json(&self) -> Value {
+        let mut data = json!({
+            "tool": self.tool,
+            "pointer": self.pointer,
+            "code": self.code.as_str(),
+            "message": self.message,
+        });
+        if let Some(expected) = &self.expected {
+            data["expected"] = json!(expected);
+        }
+        if let Some(schema) = &self.schema {
+            data["schema"] = schema.clone();
+        }
+        data
+    }
+
+    /// Convert to an `INVALID_PARAMS` protocol error carrying [`to_json`](Self::to_json).
+    pub fn into_error(self) -> Error {
+        let location = if self.pointer.is_empty() {
+            String::new()
+        } else {
+            format!(" at {}", self.pointer)
+        };
+        let detail = match &self.expected {
+            Some(expected) if self.code == ValidationErrorCode::MissingField => {
+                format!("missing required field (expected {})", expected)
+            },
+            _ => self.message.clone(),
+        };
+        Error::Protocol {
+            code: ErrorCode::INVALID_PARAMS,
+            message: format!(
+                "Invalid arguments for tool '{}'{}: {}",
+                self.tool, location, detail
+            ),
+            data: Some(self.to_json()),
+        }
+    }
+}
+
+/// Deserialize tool arguments, reporting failures as [`ArgumentError`]s.
+///
+/// `input_schema` is the tool's advertised input schema. It supplies the
+/// `expected` description and `schema` fragment; pass `&json!({})` when the
+/// tool has none.
+pub fn deserialize_arguments<T: DeserializeOwned>(
+    tool: &str,
+    args: Value,
+    input_schema: &Value,
+) -> Result<T> {
+    serde_path_to_error::deserialize(args)
+        .map_err(|e| ArgumentError::new(tool, &e, input_schema).into_error())
+}
+
+fn path_segments(path: &serde_path_to_error::Path) -> Vec<String> {
+    use serde_path_to_error::Segment;
+
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } | Segment::Enum { variant: key } => Some(key.clone()),
+            Segment::Unknown => None,
+        })
+        .collect()
+}
+
+/// The backquoted name after `prefix`, as in "missing field `query`".
+fn quoted_field(message: &str, prefix: &str) -> Option<String> {
+    let rest = message.strip_prefix(prefix)?.strip_prefix('`')?;
+    rest.split_once('`').map(|(field, _)| field.to_string())
+}
+
+/// The deserializer's own description, as in "invalid type: string, expected f64".
+fn serde_expected(message: &str) -> Option<String> {
+    message
+        .rsplit_once(", expected ")
+        .map(|(_, expected)| expected.to_string())
+}
+
+fn json_pointer(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Walk `root` along `segments` through `properties`, `items` and `$ref`s.
+fn schema_fragment(root: &Value, segments: &[String]) -> Option<Value> {
+    let mut current = resolve(root, root)?;
+    for segment in segments {
+        let next = current
+            .get("properties")
+            .and_then(|p| p.get(segment))
+            .or_else(|| match current.get("items") {
+                Some(Value::Array(items)) => {
+                    segment.parse::<usize>().ok().and_then(|i| items.get(i))
+                },
+                Some(items) if segment.parse::<usize>().is_ok() => Some(items),
+                _ => None,
+            })
+            .or_else(|| {
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| current.get("prefixItems")?.get(i))
+            })
+            .or_else(|| {
+                current
+                    .get("additionalProperties")
+                    .filter(|v| v.is_object())
+            })?;
+        current = resolve(root, next)?;
+    }
+    current
+        .as_object()
+        .is_some_and(|o| !o.is_empty())
+        .then(|| current.clone())
+}
+
+/// Follow local `$ref`s and unwrap `Option` schemas (`anyOf: [T, null]`).
+pub(crate) fn resolve<'a>(root: &'a Value, mut schema: &'a Value) -> Option<&'a Value> {
+    for _ in 0..MAX_REF_DEPTH {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let pointer = reference.strip_prefix('#')?;
+            schema = root.pointer(pointer)?;
+            continue;
+        }
+        let alternatives = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(Value::as_array);
+        if let Some(alternatives) = alternatives {
+            let non_null: Vec<&Value> = alternatives
+                .iter()
+                .filter(|alt| alt.get("type").and_then(Value::as_str) != Some("null"))
+                .collect();
+            if let [single] = non_null.as_slice() {
+                schema = single;
+                continue;
+            }
+        }
+        return Some(schema);
+    }
+    None
+}
+
+/// Short description of a schema, e.g. "integer", "string (email)" or
+/// "one of: low, high".
+fn describe_schema(schema: &Value) -> Option<String> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let values: Vec<String> = values
+            .iter()
+            .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+            .collect();
+        return Some(format!("one of: {}", values.join(", ")));
+    }
+    let ty = match schema.get("type")? {
+        Value::String(ty) => ty.clone(),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|t| *t != "null")
+            .collect::<Vec<_>>()
+            .join(" or "),
+        _ => return None,
+    };
+    Some(match schema.get("format").and_then(Value::as_str) {
+        Some(format) => format!("{} ({})", ty, format),
+        None => ty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct Search {
+        query: String,
+        limit: Option<u32>,
+        filters: Vec<Filter>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Filter {
+        field: String,
+        priority: Priority,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Priority {
+        Low,
+        High,
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": ["integer", "null"], "format": "uint32" },
+                "filters": { "type": "array", "items": { "$ref": "#/$defs/Filter" } }
+            },
+            "required": ["query", "filters"],
+            "$defs": {
+                "Filter": {
+                    "type": "object",
+                    "properties": {
+                        "field": { "type": "string" },
+                        "priority": { "type": "string", "enum": ["low", "high"] }
+                    }
+                }
+            }
+        })
+    }
+
+    fn data(args: Value) -> Value {
+        match deserialize_arguments::<Search>("search", args, &schema()).unwrap_err() {
+            Error::Protocol {
+                code,
+                data: Some(data),
+                ..
+            } => {
+                assert_eq!(code, ErrorCode::INVALID_PARAMS);
+                data
+            },
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_field_points_at_field() {
+        let data = data(json!({ "filters": [] }));
+        assert_eq!(data["pointer"], "/query");
+        assert_eq!(data["code"], "missing_field");
+        assert_eq!(data["expected"], "string");
+        assert_eq!(data["schema"], json!({ "type": "string" }));
+    }
+
+    #[test]
+    fn test_type_mismatch_in_nested_array() {
+        let data = data(json!({
+            "query": "rust",
+            "filters": [
+                { "field": "lang", "priority": "low" },
+                { "field": 7, "priority": "high" }
+            ]
+        }));
+        assert_eq!(data["pointer"], "/filters/1/field");
+        assert_eq!(data["code"], "type_mismatch");
+        assert_eq!(data["expected"], "string");
+    }
+
+    #[test]
+    fn test_unknown_variant_lists_schema_enum() {
+        let data = data(json!({
+            "query": "rust",
+            "filters": [{ "field": "lang", "priority": "urgent" }]
+        }));
+        assert_eq!(data["pointer"], "/filters/0/priority");
+        assert_eq!(data["code"], "not_allowed");
+        assert_eq!(data["expected"], "one of: low, high");
+    }
+
+    #[test]
+    fn test_optional_field_uses_non_null_type() {
+        let data = data(json!({ "query": "rust", "filters": [], "limit": "ten" }));
+        assert_eq!(data["pointer"], "/limit");
+        assert_eq!(data["expected"], "integer (uint32)");
+    }
+
+    #[test]
+    fn test_unknown_field_is_not_allowed() {
+        let data = data(json!({ "query": "rust", "filters": [], "sort": "asc" }));
+        assert_eq!(data["pointer"], "/sort");
+        assert_eq!(data["code"], "not_allowed");
+    }
+
+    #[test]
+    fn test_without_schema_falls_back_to_serde_expectation() {
+        let err = deserialize_arguments::<Search>(
+            "search",
+            json!({ "query": 1, "filters": [] }),
+            &json!({}),
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Invalid arguments for tool 'search' at /query"));
+        let Error::Protocol {
+            data: Some(data), ..
+        } = err
+        else {
+            panic!("expected a protocol error");
+        };
+        assert_eq!(data["expected"], "a string");
+        assert!(data.get("schema").is_none());
+    }
+}
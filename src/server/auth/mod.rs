@@ -60,8 +60,8 @@ pub mod traits;
 
 // Re-export core traits and types
 pub use traits::{
-    AuthContext, AuthProvider, ClaimMappings, ScopeBasedAuthorizer, SessionManager, TokenValidator,
-    ToolAuthorizer,
+    AuthContext, AuthProvider, ClaimMappings, NamespaceAuthorizer, PromptAuthorizer,
+    ScopeBasedAuthorizer, SessionManager, TokenValidator, ToolAuthorizer,
 };
 
 // Re-export configuration types
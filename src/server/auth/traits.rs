@@ -592,3 +592,81 @@ impl Default for ScopeBasedAuthorizer {
         Self::new()
     }
 }
+
+/// Prompt authorization trait for per-prompt or per-namespace access control.
+///
+/// Unauthorized prompts are hidden from `prompts/list` and rejected by
+/// `prompts/get`.
+#[async_trait]
+pub trait PromptAuthorizer: Send + Sync {
+    /// Check if the authenticated context can access a specific prompt.
+    async fn can_access_prompt(&self, auth: &AuthContext, prompt_name: &str) -> Result<bool>;
+}
+
+/// Scope-based prompt authorizer keyed by prompt namespace.
+///
+/// A prompt needs the scopes of the most specific namespace containing it,
+/// so `admin/users` can require more than `admin`. Prompts outside every
+/// configured namespace need the default scopes, which are empty unless set.
+///
+/// ```rust
+/// use pmcp::server::auth::NamespaceAuthorizer;
+///
+/// let authorizer = NamespaceAuthorizer::new()
+///     .require_scopes("admin", ["mcp:admin"])
+///     .require_scopes("reporting", ["reports:read"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceAuthorizer {
+    namespace_scopes: HashMap<String, Vec<String>>,
+    default_scopes: Vec<String>,
+}
+
+impl NamespaceAuthorizer {
+    /// Create an authorizer that allows every prompt.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require scopes for every prompt in `namespace` (e.g. `admin`).
+    pub fn require_scopes<S, I>(mut self, namespace: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let namespace = namespace
+            .into()
+            .trim_end_matches(crate::types::prompts::PROMPT_NAMESPACE_SEPARATOR)
+            .to_string();
+        let scopes_vec = scopes.into_iter().map(|s| s.as_ref().to_string()).collect();
+        self.namespace_scopes.insert(namespace, scopes_vec);
+        self
+    }
+
+    /// Set the scopes required for prompts outside every configured namespace.
+    pub fn default_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.default_scopes = scopes;
+        self
+    }
+
+    /// Scopes required for `prompt_name`.
+    pub fn required_scopes_for_prompt(&self, prompt_name: &str) -> &[String] {
+        self.namespace_scopes
+            .iter()
+            .filter(|(namespace, _)| crate::types::prompts::in_namespace(prompt_name, namespace))
+            .max_by_key(|(namespace, _)| namespace.len())
+            .map_or(&self.default_scopes, |(_, scopes)| scopes)
+    }
+}
+
+#[async_trait]
+impl PromptAuthorizer for NamespaceAuthorizer {
+    async fn can_access_prompt(&self, auth: &AuthContext, prompt_name: &str) -> Result<bool> {
+        let scope_refs: Vec<&str> = self
+            .required_scopes_for_prompt(prompt_name)
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        Ok(auth.has_all_scopes(&scope_refs))
+    }
+}
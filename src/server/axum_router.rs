@@ -29,7 +29,7 @@
 //! ```
 
 use crate::server::streamable_http_server::{
-    build_mcp_router, make_server_state, StreamableHttpServerConfig,
+    build_compressed_router, make_server_state, StreamableHttpServerConfig,
 };
 use crate::server::tower_layers::{DnsRebindingLayer, SecurityHeadersLayer};
 use crate::server::Server;
@@ -99,10 +99,11 @@ pub fn router_with_config(server: Arc<tokio::sync::Mutex<Server>>, config: Route
     server_config.allowed_origins = Some(allowed.clone());
 
     let state = make_server_state(server, server_config);
-    let base_router = build_mcp_router(state);
+    let base_router = build_compressed_router(state);
     let cors = crate::server::tower_layers::build_mcp_cors_layer(&allowed);
 
-    // Layer ordering: CORS (outermost) -> DnsRebinding -> SecurityHeaders -> handler
+    // Layer ordering: CORS (outermost) -> DnsRebinding -> SecurityHeaders ->
+    // Compression (if configured) -> handler
     base_router
         .layer(config.security_headers)
         .layer(DnsRebindingLayer::new(allowed))
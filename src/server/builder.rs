@@ -2,19 +2,24 @@
 
 use crate::error::{Error, Result};
 use crate::runtime::RwLock;
-use crate::server::auth::{AuthProvider, ToolAuthorizer};
+use crate::server::auth::{AuthProvider, PromptAuthorizer, ToolAuthorizer};
 use crate::server::core::ServerCore;
+use crate::server::event_bus::{EventBus, ServerEvent};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::server::observability::{
-    CloudWatchBackend, ConsoleBackend, McpObservabilityMiddleware, NullBackend,
-    ObservabilityBackend, ObservabilityConfig,
+    build_backend, McpObservabilityMiddleware, ObservabilityBackend, ObservabilityConfig,
 };
+use crate::server::schema_compat::{SchemaBaseline, SchemaCheckMode, SchemaGuard};
+use crate::server::schema_registry::SchemaRegistry;
+use crate::server::self_test::{BackendCheck, SelfTestPlan};
+#[cfg(feature = "tasks")]
 use crate::server::tasks::TaskRouter;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::server::tool_middleware::{ToolMiddleware, ToolMiddlewareChain};
 use crate::server::{PromptHandler, ResourceHandler, SamplingHandler, ToolHandler};
 use crate::shared::middleware::EnhancedMiddlewareChain;
 use crate::types::{Implementation, PromptInfo, ServerCapabilities, ToolInfo};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -66,24 +71,81 @@ pub struct ServerCoreBuilder {
     sampling: Option<Arc<dyn SamplingHandler>>,
     auth_provider: Option<Arc<dyn AuthProvider>>,
     tool_authorizer: Option<Arc<dyn ToolAuthorizer>>,
+    prompt_authorizer: Option<Arc<dyn PromptAuthorizer>>,
     protocol_middleware: Arc<RwLock<EnhancedMiddlewareChain>>,
     #[cfg(not(target_arch = "wasm32"))]
     tool_middlewares: Vec<Arc<dyn ToolMiddleware>>,
     /// Task router for experimental MCP Tasks support (optional)
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     task_router: Option<Arc<dyn TaskRouter>>,
     /// Task store for MCP Tasks with polling (optional, standard capability path)
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     task_store: Option<Arc<dyn crate::server::task_store::TaskStore>>,
+    /// Workflows run on a cron schedule (require a task store)
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    scheduled_workflows: Vec<crate::server::scheduler::ScheduledWorkflow>,
+    /// Client for workflow steps on foundation servers
+    #[cfg(all(
+        not(target_arch = "wasm32"),
+        feature = "workflow",
+        feature = "composition"
+    ))]
+    foundation_client: Option<Arc<dyn crate::composition::FoundationClient>>,
     /// Stateless mode for serverless deployments (None = auto-detect)
     stateless_mode: Option<bool>,
     /// Host-specific metadata layers (e.g., `ChatGpt` for openai/* keys)
     #[cfg(feature = "mcp-apps")]
     host_layers: Vec<crate::types::mcp_apps::HostType>,
+    /// Widgets that tool `_meta` references are validated against
+    #[cfg(feature = "mcp-apps")]
+    widget_manifest: Option<crate::server::mcp_apps::WidgetManifest>,
+    /// Tools registered with `tool_with_form`
+    #[cfg(feature = "mcp-apps")]
+    form_tools: Vec<String>,
     /// Optional website URL for the server implementation (MCP 2025-11-25)
     website_url: Option<String>,
     /// Optional icons for the server implementation (MCP 2025-11-25)
     icons: Option<Vec<crate::types::protocol::IconInfo>>,
+    /// Event bus shared with the built server
+    events: EventBus,
+    /// Shared definitions referenced from tool schemas
+    schema_registry: SchemaRegistry,
+    /// Workflows, secrets, and backends checked by `run_self_test`
+    self_test: SelfTestPlan,
+    /// `(tool, widget URI)` pairs registered with `tool_with_widget`
+    tool_widgets: Vec<(String, String)>,
+    /// Prompt directories registered with `prompt_dir`
+    #[cfg(feature = "prompt-dir")]
+    prompt_dirs: Vec<crate::server::prompt_dir::PromptDir>,
+    /// Last exported schema registered with `schema_baseline`
+    schema_baseline: Option<(SchemaBaseline, SchemaCheckMode)>,
+    /// Widget state store exposed to handlers via `RequestHandlerExtra`
+    widget_state: Option<Arc<dyn crate::server::widget_state::WidgetStateStore>>,
+    /// Key-value store exposed to handlers via `RequestHandlerExtra::kv`
+    kv_store: Option<Arc<dyn crate::storage::KvStore>>,
+    /// Feature flags exposed to handlers via `RequestHandlerExtra::flags`
+    flag_provider: Option<Arc<dyn crate::server::flags::FlagProvider>>,
+    /// Deadlines for tool handlers
+    #[cfg(not(target_arch = "wasm32"))]
+    tool_timeouts: crate::server::tool_timeout::ToolTimeouts,
+    /// Deadlines for workflow steps, set from `tool_timeouts` on `build()`
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    workflow_tool_timeouts: crate::server::tool_timeout::DeferredToolTimeouts,
+    /// Tools per `tools/list` page (all tools in one page when `None`)
+    #[cfg(not(target_arch = "wasm32"))]
+    tools_page_size: Option<usize>,
+    /// Codec for `tools/list` cursors (a random key when `None`)
+    #[cfg(not(target_arch = "wasm32"))]
+    cursor_codec: Option<crate::server::pagination::CursorCodec>,
+    /// Typed state exposed to handlers via `RequestHandlerExtra::state`
+    state: crate::server::extensions::Extensions,
+    /// Change sources for resource subscriptions
+    #[cfg(feature = "resources")]
+    resource_watchers: Vec<Arc<dyn crate::server::subscriptions::ResourceWatcher>>,
+    /// Argument completion providers
+    completions: crate::server::completion::CompletionRegistry,
+    /// Subsystems switched off with `disable`
+    disabled: crate::server::subsystem::DisabledSubsystems,
 }
 
 impl Default for ServerCoreBuilder {
@@ -107,18 +169,54 @@ impl ServerCoreBuilder {
             sampling: None,
             auth_provider: None,
             tool_authorizer: None,
+            prompt_authorizer: None,
             protocol_middleware: Arc::new(RwLock::new(EnhancedMiddlewareChain::new())),
             #[cfg(not(target_arch = "wasm32"))]
             tool_middlewares: Vec::new(),
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             task_router: None,
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             task_store: None,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+            scheduled_workflows: Vec::new(),
+            #[cfg(all(
+                not(target_arch = "wasm32"),
+                feature = "workflow",
+                feature = "composition"
+            ))]
+            foundation_client: None,
             stateless_mode: None, // Auto-detect by default
             #[cfg(feature = "mcp-apps")]
             host_layers: Vec::new(),
+            #[cfg(feature = "mcp-apps")]
+            widget_manifest: None,
+            #[cfg(feature = "mcp-apps")]
+            form_tools: Vec::new(),
             website_url: None,
             icons: None,
+            events: EventBus::new(),
+            schema_registry: SchemaRegistry::new(),
+            self_test: SelfTestPlan::default(),
+            tool_widgets: Vec::new(),
+            #[cfg(feature = "prompt-dir")]
+            prompt_dirs: Vec::new(),
+            schema_baseline: None,
+            widget_state: None,
+            kv_store: None,
+            flag_provider: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            tool_timeouts: crate::server::tool_timeout::ToolTimeouts::new(),
+            #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+            workflow_tool_timeouts: Arc::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            tools_page_size: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cursor_codec: None,
+            state: crate::server::extensions::Extensions::new(),
+            #[cfg(feature = "resources")]
+            resource_watchers: Vec::new(),
+            completions: crate::server::completion::CompletionRegistry::default(),
+            disabled: crate::server::subsystem::DisabledSubsystems::default(),
         }
     }
 
@@ -183,6 +281,69 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Add a tool whose results render in the widget at `widget_uri`.
+    ///
+    /// Sets the tool's `_meta.ui.resourceUri` (plus the legacy
+    /// `ui/resourceUri` key) so `_meta` does not have to be written by hand;
+    /// host layers such as [`HostType::ChatGpt`](crate::types::mcp_apps::HostType::ChatGpt)
+    /// add `openai/outputTemplate` from it. [`build`](Self::build) fails if
+    /// `widget_uri` is not a `ui://` URI or no resource handler is
+    /// registered, and [`ServerCore::run_self_test`] reads the widget to
+    /// confirm it is served.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use pmcp::server::builder::ServerCoreBuilder;
+    /// # use pmcp::server::simple_resources::ResourceCollection;
+    /// # use pmcp::{RequestHandlerExtra, ToolHandler};
+    /// # use async_trait::async_trait;
+    /// # use serde_json::Value;
+    /// # struct ChessMove;
+    /// # #[async_trait]
+    /// # impl ToolHandler for ChessMove {
+    /// #     async fn handle(&self, args: Value, _: RequestHandlerExtra) -> pmcp::Result<Value> { Ok(args) }
+    /// # }
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("chess")
+    ///     .version("1.0.0")
+    ///     .tool_with_widget("chess_move", ChessMove, "ui://app/board")
+    ///     .resources(ResourceCollection::new())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn tool_with_widget(
+        self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+        widget_uri: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        let mut builder = self.tool(name.clone(), handler);
+        builder.tool_widgets.push((name, widget_uri.into()));
+        builder
+    }
+
+    /// Add a tool with a generated form widget.
+    ///
+    /// The widget at `ui://pmcp/form/<name>` renders the tool's input schema
+    /// as a form, calls the tool when submitted and shows the result. It is
+    /// served alongside the resources of the handler passed to
+    /// [`resources`](Self::resources), if any. See
+    /// [`render_form_widget`](crate::server::mcp_apps::render_form_widget).
+    #[cfg(feature = "mcp-apps")]
+    pub fn tool_with_form(
+        self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+    ) -> Self {
+        let name = name.into();
+        let uri = crate::server::mcp_apps::form_widget_uri(&name);
+        let mut builder = self.tool_with_widget(name.clone(), handler, uri);
+        builder.form_tools.push(name);
+        builder
+    }
+
     /// Add a tool handler with an Arc.
     ///
     /// This variant is useful when you need to share the handler across multiple servers.
@@ -234,6 +395,41 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Register one prompt per markdown or YAML file in a directory.
+    ///
+    /// Files are discovered and parsed when the server is built, and re-read
+    /// on every request so prompt text can be edited without a restart. See
+    /// [`PromptDir`](crate::server::prompt_dir::PromptDir) for the file format.
+    ///
+    /// [`build`](Self::build) fails if the directory cannot be read, a file
+    /// does not parse, or a prompt name is already registered.
+    ///
+    /// `prompts/list` entries are cached at build time like other prompts;
+    /// edits to a file's front matter show there after a restart, while
+    /// `prompts/get` always uses the current file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::prompt_dir::PromptDir;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("reviewer")
+    ///     .version("1.0.0")
+    ///     .prompt_dir(PromptDir::new("prompts"))
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "prompt-dir")]
+    pub fn prompt_dir(mut self, dir: crate::server::prompt_dir::PromptDir) -> Self {
+        self.prompt_dirs.push(dir);
+        if self.capabilities.prompts.is_none() {
+            self.capabilities.prompts = Some(crate::types::PromptCapabilities {
+                list_changed: Some(false),
+            });
+        }
+        self
+    }
+
     /// Add a prompt handler with an Arc.
     ///
     /// This variant is useful when you need to share the handler across multiple servers.
@@ -352,6 +548,17 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Set the prompt authorizer.
+    ///
+    /// Prompts the authenticated caller may not access are hidden from
+    /// `prompts/list` and rejected by `prompts/get`. Use
+    /// [`NamespaceAuthorizer`](crate::server::auth::NamespaceAuthorizer) to
+    /// require scopes per prompt namespace (`admin/cleanup`).
+    pub fn prompt_authorizer(mut self, authorizer: impl PromptAuthorizer + 'static) -> Self {
+        self.prompt_authorizer = Some(Arc::new(authorizer) as Arc<dyn PromptAuthorizer>);
+        self
+    }
+
     /// Set the protocol middleware chain.
     ///
     /// Protocol middleware processes JSON-RPC requests, responses, and notifications
@@ -426,6 +633,33 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Subscribe to server events.
+    ///
+    /// The listener is called for every [`ServerEvent`]: sessions starting,
+    /// tool calls, resource reads, and task transitions (tasks created by a
+    /// tool call or cancelled via `tasks/cancel`). It runs on the dispatch
+    /// path, so keep it cheap or hand the event off to a task.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::event_bus::ServerEvent;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("my-server")
+    ///     .version("1.0.0")
+    ///     .on_event(|event| {
+    ///         if let ServerEvent::TaskTransitioned { task_id, status } = event {
+    ///             println!("task {} is now {:?}", task_id, status);
+    ///         }
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn on_event(self, listener: impl Fn(&ServerEvent) + Send + Sync + 'static) -> Self {
+        self.events.on_event(listener);
+        self
+    }
+
     /// Enable observability for this server.
     ///
     /// This adds observability middleware that provides:
@@ -476,11 +710,7 @@ impl ServerCoreBuilder {
         }
 
         // Create backend based on configuration
-        let backend: Arc<dyn ObservabilityBackend> = match config.backend.as_str() {
-            "cloudwatch" => Arc::new(CloudWatchBackend::new(config.cloudwatch.clone())),
-            "null" => Arc::new(NullBackend),
-            _ => Arc::new(ConsoleBackend::new(config.console.pretty)),
-        };
+        let backend = build_backend(&config);
 
         // Get server name for middleware (use placeholder if not yet set)
         let server_name = self.name.clone().unwrap_or_else(|| "unknown".to_string());
@@ -569,6 +799,83 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Validate tool widget references against a [`WidgetManifest`] at build time.
+    ///
+    /// [`build`](Self::build) fails if a tool's `_meta` points at a widget URI
+    /// the manifest does not list, or a widget depends on a tool that is not
+    /// registered.
+    ///
+    /// [`WidgetManifest`]: crate::server::mcp_apps::WidgetManifest
+    #[cfg(feature = "mcp-apps")]
+    pub fn widget_manifest(mut self, manifest: crate::server::mcp_apps::WidgetManifest) -> Self {
+        self.widget_manifest = Some(manifest);
+        self
+    }
+
+    /// Register a schema definition shared between tools.
+    ///
+    /// Subschemas of tool input and output schemas that match `schema` are
+    /// replaced with `{"$ref": "#/definitions/<name>"}`, and the definition
+    /// is sent once, under `definitions` in the `tools/list` result. See
+    /// [`SchemaRegistry`] for the matching rules.
+    ///
+    /// ```rust,ignore
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("orders")
+    ///     .version("1.0.0")
+    ///     .schema_definition("Address", address_schema)
+    ///     .tool("create_order", CreateOrder)
+    ///     .tool("update_shipping", UpdateShipping)
+    ///     .build()?;
+    /// ```
+    pub fn schema_definition(mut self, name: impl Into<String>, schema: Value) -> Self {
+        self.schema_registry.insert(name, schema);
+        self
+    }
+
+    /// Register the schema generated for `T` as a shared definition named
+    /// after the type.
+    ///
+    /// See [`schema_definition`](Self::schema_definition).
+    #[cfg(feature = "schema-generation")]
+    pub fn schema_type<T: schemars::JsonSchema>(mut self) -> Self {
+        self.schema_registry.insert_type::<T>();
+        self
+    }
+
+    /// Require the environment variable `name` to be set and non-empty when
+    /// [`ServerCore::run_self_test`] runs.
+    pub fn self_test_secret(mut self, name: impl Into<String>) -> Self {
+        self.self_test.add_secret(name);
+        self
+    }
+
+    /// Add a reachability check for an external backend (a database, an API,
+    /// a foundation server) to [`ServerCore::run_self_test`].
+    ///
+    /// The backend counts as reachable when `check` returns `Ok`.
+    pub fn self_test_backend<F, Fut>(mut self, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.self_test.add_backend(BackendCheck::new(name, check));
+        self
+    }
+
+    /// Check the server against the last exported schema.
+    ///
+    /// Tools and prompts are compared in [`build`](Self::build), resources
+    /// in [`ServerCore::run_self_test`]. Breaking changes without a breaking
+    /// version bump fail both in [`SchemaCheckMode::Strict`] and are logged
+    /// in [`SchemaCheckMode::Warn`]. See
+    /// [`schema_compat`](crate::server::schema_compat) for what counts as
+    /// breaking.
+    pub fn schema_baseline(mut self, baseline: SchemaBaseline, mode: SchemaCheckMode) -> Self {
+        self.schema_baseline = Some((baseline, mode));
+        self
+    }
+
     /// Enable or disable stateless mode for serverless deployments.
     ///
     /// Stateless mode skips initialization state checking, allowing the server
@@ -638,7 +945,8 @@ impl ServerCoreBuilder {
     ///     .with_task_store(Arc::new(task_router))
     ///     .build()?;
     /// ```
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
     pub fn with_task_store(mut self, router: Arc<dyn TaskRouter>) -> Self {
         // Auto-configure experimental.tasks capability
         let experimental = self
@@ -674,7 +982,8 @@ impl ServerCoreBuilder {
     ///     .task_store(store)
     ///     .build()?;
     /// ```
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
     pub fn task_store(mut self, store: Arc<dyn crate::server::task_store::TaskStore>) -> Self {
         // Set ServerCapabilities.tasks (standard, not experimental)
         self.capabilities.tasks = Some(crate::types::capabilities::ServerTasksCapability {
@@ -691,103 +1000,385 @@ impl ServerCoreBuilder {
         self
     }
 
-    /// Detect if running in a stateless/serverless environment.
+    /// Register a store for persisting widget state across reloads.
     ///
-    /// Checks for environment variables that indicate serverless platforms:
-    /// - AWS Lambda
-    /// - Vercel Functions
-    /// - Deno Deploy
-    /// - Cloudflare Workers
-    /// - Azure Functions
-    fn detect_stateless_environment() -> bool {
-        std::env::var("AWS_LAMBDA_FUNCTION_NAME").is_ok()
-            || std::env::var("VERCEL").is_ok()
-            || std::env::var("DENO_DEPLOYMENT_ID").is_ok()
-            || std::env::var("CLOUDFLARE_WORKER").is_ok()
-            || std::env::var("FUNCTIONS_WORKER_RUNTIME").is_ok()
+    /// Tool and resource handlers reach the store through
+    /// [`RequestHandlerExtra::load_widget_state`] and
+    /// [`RequestHandlerExtra::save_widget_state`], keyed by the request's
+    /// session and the widget URI.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::widget_state::InMemoryWidgetStateStore;
+    /// use std::sync::Arc;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("board")
+    ///     .version("1.0.0")
+    ///     .widget_state_store(Arc::new(InMemoryWidgetStateStore::new()))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::load_widget_state`]: crate::server::cancellation::RequestHandlerExtra::load_widget_state
+    /// [`RequestHandlerExtra::save_widget_state`]: crate::server::cancellation::RequestHandlerExtra::save_widget_state
+    pub fn widget_state_store(
+        mut self,
+        store: Arc<dyn crate::server::widget_state::WidgetStateStore>,
+    ) -> Self {
+        self.widget_state = Some(store);
+        self
     }
 
-    /// Register a workflow as a prompt with automatic middleware support.
+    /// Register a key-value store for handler state.
     ///
-    /// This method provides the easiest way to register workflows with middleware:
-    /// - Validates the workflow
-    /// - Builds tool registry from registered tools
-    /// - Creates workflow handler with middleware executor
-    /// - Ensures OAuth, logging, and other middleware applies to workflow tool calls
+    /// Tool and resource handlers reach the store through
+    /// [`RequestHandlerExtra::kv`]. See [`crate::storage`] for the available
+    /// backends.
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```rust,ignore
-    /// use pmcp::server::builder::ServerCoreBuilder;
-    /// use pmcp::server::workflow::{SequentialWorkflow, WorkflowStep, ToolHandle};
-    /// use pmcp::server::tool_middleware::ToolMiddleware;
+    /// use pmcp::storage::MemoryKvStore;
+    /// use std::sync::Arc;
     ///
-    /// let workflow = SequentialWorkflow::new("my_workflow", "Description")
-    ///     .step(WorkflowStep::new("fetch_data", ToolHandle::new("my_tool")));
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("notes")
+    ///     .version("1.0.0")
+    ///     .kv_store(Arc::new(MemoryKvStore::new()))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::kv`]: crate::server::cancellation::RequestHandlerExtra::kv
+    pub fn kv_store(mut self, store: Arc<dyn crate::storage::KvStore>) -> Self {
+        self.kv_store = Some(store);
+        self
+    }
+
+    /// Evaluate feature flags for the caller before each tool call.
+    ///
+    /// Handlers read the result with [`RequestHandlerExtra::flags`], and
+    /// widget tools return it in the result `_meta` for the widget bridge.
+    /// See [`flags`](crate::server::flags) for the built-in providers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::flags::StaticFlags;
+    /// use std::sync::Arc;
     ///
     /// let server = ServerCoreBuilder::new()
-    ///     .name("my-server")
+    ///     .name("search")
     ///     .version("1.0.0")
-    ///     .tool("my_tool", MyTool)
-    ///     .tool_middleware(Arc::new(OAuthMiddleware::new())) // ✅ Applies to workflows!
-    ///     .prompt_workflow(workflow)?  // ✅ Simple one-line registration
+    ///     .feature_flags(Arc::new(StaticFlags::new().rollout("search-v2", 10)))
     ///     .build()?;
     /// ```
     ///
-    /// # Benefits
+    /// [`RequestHandlerExtra::flags`]: crate::server::cancellation::RequestHandlerExtra::flags
+    pub fn feature_flags(mut self, provider: Arc<dyn crate::server::flags::FlagProvider>) -> Self {
+        self.flag_provider = Some(provider);
+        self
+    }
+
+    /// Set the deadline for tool handlers without a per-tool timeout.
     ///
-    /// - **One-Line Registration**: No manual tool registry building required
-    /// - **Automatic Middleware**: OAuth and other middleware applies automatically
-    /// - **No Boilerplate**: No need to manually create `WorkflowPromptHandler`
-    /// - **Builder Pattern**: Follows the same pattern as `.tool()` and `.prompt()`
+    /// A call that runs longer is cancelled and the client gets a
+    /// `REQUEST_TIMEOUT` error. Without a default, tools run without a
+    /// deadline. See [`tool_timeout`](crate::server::tool_timeout) for details.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_tool_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.tool_timeouts = self.tool_timeouts.with_default(timeout);
+        self
+    }
+
+    /// Set the deadline for one tool's handler, overriding the default.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// Returns an error if workflow validation fails.
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("warehouse")
+    ///     .version("1.0.0")
+    ///     .default_tool_timeout(Duration::from_secs(30))
+    ///     .tool_timeout("execute_query", Duration::from_secs(120))
+    ///     .build()?;
+    /// ```
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn prompt_workflow(
-        mut self,
-        workflow: crate::server::workflow::SequentialWorkflow,
-    ) -> Result<Self> {
-        use crate::server::builder_middleware_executor::BuilderMiddlewareExecutor;
-        use crate::server::middleware_executor::MiddlewareExecutor;
-        use crate::server::workflow;
+    pub fn tool_timeout(mut self, name: impl Into<String>, timeout: std::time::Duration) -> Self {
+        self.tool_timeouts = self.tool_timeouts.with_tool(name, timeout);
+        self
+    }
 
-        // Validate workflow
-        workflow
-            .validate()
-            .map_err(|e| Error::validation(format!("Workflow validation failed: {}", e)))?;
+    /// Split `tools/list` results into pages of `page_size` tools.
+    ///
+    /// Tools are listed by name, and each page's `nextCursor` is a signed
+    /// [`CursorCodec`](crate::server::pagination::CursorCodec) cursor. By
+    /// default all tools are returned at once.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tools_page_size(mut self, page_size: usize) -> Self {
+        self.tools_page_size = Some(page_size);
+        self
+    }
 
-        // Build tool registry from cached metadata (avoids per-request handler.metadata() calls)
-        let mut tool_registry = std::collections::HashMap::new();
-        for (name, info) in &self.tool_infos {
-            tool_registry.insert(
-                Arc::from(name.as_str()),
-                workflow::conversion::ToolInfo {
-                    name: info.name.clone(),
-                    description: info.description.clone().unwrap_or_default(),
-                    input_schema: info.input_schema.clone(),
-                },
-            );
-        }
+    /// Sign `tools/list` cursors with `codec` instead of a random per-process key.
+    ///
+    /// Needed when several server instances serve the same clients, so a
+    /// cursor issued by one instance is accepted by the others.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cursor_codec(mut self, codec: crate::server::pagination::CursorCodec) -> Self {
+        self.cursor_codec = Some(codec);
+        self
+    }
 
-        // Create builder-scoped middleware executor
-        let middleware_executor = Arc::new(BuilderMiddlewareExecutor::new(
-            self.tools.clone(),
-            self.tool_middlewares.clone(),
-        )) as Arc<dyn MiddlewareExecutor>;
+    /// Switch off a subsystem for this server.
+    ///
+    /// The subsystem's capability is not advertised and its requests are
+    /// answered with `METHOD_NOT_FOUND`. `build()` fails if handlers for a
+    /// disabled subsystem are registered. See
+    /// [`subsystem`](crate::server::subsystem) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::subsystem::Subsystem;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("calculator")
+    ///     .version("1.0.0")
+    ///     .tool("add", AddTool)
+    ///     .disable(Subsystem::Prompts)
+    ///     .disable(Subsystem::Sampling)
+    ///     .build()?;
+    /// ```
+    pub fn disable(mut self, subsystem: crate::server::subsystem::Subsystem) -> Self {
+        self.disabled.insert(subsystem);
+        self
+    }
 
-        // Get workflow name and task support flag before moving
-        let name = workflow.name().to_string();
-        let has_task_support = workflow.has_task_support();
+    /// Register shared state for handlers, one value per type.
+    ///
+    /// Tool, prompt, resource and completion handlers read it with
+    /// [`RequestHandlerExtra::state`]. Registering a second value of the same
+    /// type replaces the first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// struct Db(sqlx::PgPool);
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("orders")
+    ///     .version("1.0.0")
+    ///     .with_state(Db(pool))
+    ///     .build()?;
+    ///
+    /// // In a handler:
+    /// let Db(pool) = extra.state::<Db>()?;
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::state`]: crate::server::cancellation::RequestHandlerExtra::state
+    pub fn with_state<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+        self.state.insert(state);
+        self
+    }
 
-        // Create workflow handler with middleware
-        let handler = workflow::WorkflowPromptHandler::with_middleware_executor(
-            workflow.clone(),
-            tool_registry,
-            middleware_executor,
-            self.resources.clone(),
-        );
+    /// Register a source of resource changes for `resources/subscribe`.
+    ///
+    /// Enables the `resources.subscribe` capability. Call
+    /// [`ServerCore::start_resource_watchers`] with the transport's
+    /// notification sender to start them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::resource_watcher::FileResourceWatcher;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("docs")
+    ///     .version("1.0.0")
+    ///     .resource_watcher(FileResourceWatcher::new("./docs"))
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
+    pub fn resource_watcher(
+        mut self,
+        watcher: impl crate::server::subscriptions::ResourceWatcher + 'static,
+    ) -> Self {
+        self.resource_watchers.push(Arc::new(watcher));
+        self.capabilities
+            .resources
+            .get_or_insert(crate::types::ResourceCapabilities {
+                subscribe: Some(false),
+                list_changed: Some(false),
+            })
+            .subscribe = Some(true);
+        self
+    }
+
+    /// Register autocompletion for an argument of a prompt.
+    ///
+    /// Advertises the `completions` capability; `provider` answers
+    /// `completion/complete` requests with a `ref/prompt` reference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::completion::StaticCompletions;
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("deploy")
+    ///     .version("1.0.0")
+    ///     .prompt_completion("deploy", "environment", StaticCompletions::new(["dev", "prod"]))
+    ///     .build()?;
+    /// ```
+    pub fn prompt_completion(
+        mut self,
+        prompt: impl Into<String>,
+        argument: impl Into<String>,
+        provider: impl crate::server::completion::CompletionProvider + 'static,
+    ) -> Self {
+        self.completions
+            .add_prompt(prompt, argument, Arc::new(provider));
+        self.capabilities.completions = Some(crate::types::CompletionCapabilities::default());
+        self
+    }
+
+    /// Register autocompletion for a variable of a resource template.
+    ///
+    /// `uri_template` must match the template's `uriTemplate` exactly, since
+    /// clients send it back as the `ref/resource` reference.
+    pub fn resource_completion(
+        mut self,
+        uri_template: impl Into<String>,
+        variable: impl Into<String>,
+        provider: impl crate::server::completion::CompletionProvider + 'static,
+    ) -> Self {
+        self.completions
+            .add_resource_template(uri_template, variable, Arc::new(provider));
+        self.capabilities.completions = Some(crate::types::CompletionCapabilities::default());
+        self
+    }
+
+    /// Detect if running in a stateless/serverless environment.
+    ///
+    /// Checks for environment variables that indicate serverless platforms:
+    /// - AWS Lambda
+    /// - Vercel Functions
+    /// - Deno Deploy
+    /// - Cloudflare Workers
+    /// - Azure Functions
+    fn detect_stateless_environment() -> bool {
+        std::env::var("AWS_LAMBDA_FUNCTION_NAME").is_ok()
+            || std::env::var("VERCEL").is_ok()
+            || std::env::var("DENO_DEPLOYMENT_ID").is_ok()
+            || std::env::var("CLOUDFLARE_WORKER").is_ok()
+            || std::env::var("FUNCTIONS_WORKER_RUNTIME").is_ok()
+    }
+
+    /// Set the client for workflow steps on foundation servers.
+    ///
+    /// Steps built with [`ToolHandle::remote`] call their tool through this
+    /// client. Set it before registering workflows that use remote steps.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pmcp::composition::{FoundationConfig, McpFoundationClient};
+    /// use pmcp::server::workflow::{DataSource, SequentialWorkflow, ToolHandle, WorkflowStep};
+    ///
+    /// let client = McpFoundationClient::new(FoundationConfig::from_file("foundations.toml")?);
+    /// let workflow = SequentialWorkflow::new("quote", "Price an order")
+    ///     .argument("qty", "Quantity", true)
+    ///     .step(
+    ///         WorkflowStep::new("total", ToolHandle::remote("calculator", "multiply"))
+    ///             .arg("a", DataSource::prompt_arg("qty"))
+    ///             .arg("b", DataSource::constant(serde_json::json!(9.5)))
+    ///             .bind("total"),
+    ///     );
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("orders")
+    ///     .version("1.0.0")
+    ///     .foundation_client(Arc::new(client))
+    ///     .prompt_workflow(workflow)?
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`ToolHandle::remote`]: crate::server::workflow::ToolHandle::remote
+    #[cfg(all(
+        not(target_arch = "wasm32"),
+        feature = "workflow",
+        feature = "composition"
+    ))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "workflow", feature = "composition"))))]
+    pub fn foundation_client(
+        mut self,
+        client: Arc<dyn crate::composition::FoundationClient>,
+    ) -> Self {
+        self.foundation_client = Some(client);
+        self
+    }
+
+    /// Register a workflow as a prompt with automatic middleware support.
+    ///
+    /// This method provides the easiest way to register workflows with middleware:
+    /// - Validates the workflow
+    /// - Builds tool registry from registered tools
+    /// - Creates workflow handler with middleware executor
+    /// - Ensures OAuth, logging, and other middleware applies to workflow tool calls
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::builder::ServerCoreBuilder;
+    /// use pmcp::server::workflow::{SequentialWorkflow, WorkflowStep, ToolHandle};
+    /// use pmcp::server::tool_middleware::ToolMiddleware;
+    ///
+    /// let workflow = SequentialWorkflow::new("my_workflow", "Description")
+    ///     .step(WorkflowStep::new("fetch_data", ToolHandle::new("my_tool")));
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("my-server")
+    ///     .version("1.0.0")
+    ///     .tool("my_tool", MyTool)
+    ///     .tool_middleware(Arc::new(OAuthMiddleware::new())) // ✅ Applies to workflows!
+    ///     .prompt_workflow(workflow)?  // ✅ Simple one-line registration
+    ///     .build()?;
+    /// ```
+    ///
+    /// # Benefits
+    ///
+    /// - **One-Line Registration**: No manual tool registry building required
+    /// - **Automatic Middleware**: OAuth and other middleware applies automatically
+    /// - **No Boilerplate**: No need to manually create `WorkflowPromptHandler`
+    /// - **Builder Pattern**: Follows the same pattern as `.tool()` and `.prompt()`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if workflow validation fails.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
+    pub fn prompt_workflow(
+        mut self,
+        workflow: crate::server::workflow::SequentialWorkflow,
+    ) -> Result<Self> {
+        use crate::server::workflow;
+
+        // Validate workflow
+        workflow
+            .validate()
+            .map_err(|e| Error::validation(format!("Workflow validation failed: {}", e)))?;
+
+        // Get workflow name and task support flag before moving
+        let name = workflow.name().to_string();
+        let has_task_support = workflow.has_task_support();
+        self.self_test.add_workflow(workflow.clone());
+
+        // Create workflow handler with middleware
+        let handler = self.workflow_handler(workflow.clone())?;
 
         // Wrap in TaskWorkflowPromptHandler if task support is enabled
         if has_task_support {
@@ -831,13 +1422,199 @@ impl ServerCoreBuilder {
         Ok(self)
     }
 
+    /// Run a workflow on a cron schedule, server-side.
+    ///
+    /// The workflow executes without any client request, with the tools
+    /// and tool middleware registered so far (register them first). Each
+    /// run is recorded as a task owned by
+    /// [`SCHEDULED_TASK_OWNER`](crate::server::scheduler::SCHEDULED_TASK_OWNER),
+    /// so it shows up in `tasks/list`, and the workflow output is served by
+    /// `tasks/result`. The workflow is not exposed as a prompt.
+    ///
+    /// `cron` is a five-field expression evaluated in UTC; see
+    /// [`CronSchedule`](crate::server::scheduler::CronSchedule). A task store
+    /// must be registered with [`Self::task_store`] before `build()`, and the
+    /// schedule starts once [`WorkflowScheduler::spawn`] is called on
+    /// [`ServerCore::scheduler`].
+    ///
+    /// [`WorkflowScheduler::spawn`]: crate::server::scheduler::WorkflowScheduler::spawn
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::builder::ServerCoreBuilder;
+    /// use pmcp::server::task_store::InMemoryTaskStore;
+    /// use pmcp::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+    /// use std::sync::Arc;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let workflow = SequentialWorkflow::new("nightly_digest", "Summarize the day")
+    ///     .step(WorkflowStep::new("collect", ToolHandle::new("collect_activity")));
+    ///
+    /// let server = ServerCoreBuilder::new()
+    ///     .name("automation")
+    ///     .version("1.0.0")
+    ///     .task_store(Arc::new(InMemoryTaskStore::new()))
+    ///     .scheduled_workflow("nightly_digest", "0 3 * * *", workflow)?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cron expression is invalid, the workflow
+    /// fails validation or declares required arguments, or another
+    /// scheduled workflow already uses `name`.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
+    pub fn scheduled_workflow(
+        mut self,
+        name: impl Into<String>,
+        cron: &str,
+        workflow: crate::server::workflow::SequentialWorkflow,
+    ) -> Result<Self> {
+        use crate::server::scheduler::{CronSchedule, ScheduledWorkflow};
+
+        let name = name.into();
+        let schedule = CronSchedule::parse(cron)
+            .map_err(|e| Error::validation(format!("Scheduled workflow '{}': {}", name, e)))?;
+        workflow
+            .validate()
+            .map_err(|e| Error::validation(format!("Workflow validation failed: {}", e)))?;
+        if let Some(arg) = workflow.arguments().iter().find(|(_, spec)| spec.required) {
+            return Err(Error::validation(format!(
+                "Scheduled workflow '{}' declares required argument '{}', but scheduled runs have no arguments",
+                name, arg.0
+            )));
+        }
+        if self.scheduled_workflows.iter().any(|w| w.name == name) {
+            return Err(Error::validation(format!(
+                "Scheduled workflow '{}' is already registered",
+                name
+            )));
+        }
+
+        let handler: Arc<dyn PromptHandler> = Arc::new(self.workflow_handler(workflow)?);
+        self.scheduled_workflows
+            .push(ScheduledWorkflow::new(name, schedule, handler));
+        Ok(self)
+    }
+
+    /// Create a workflow handler that executes tools through the builder's
+    /// tool middleware.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    fn workflow_handler(
+        &self,
+        workflow: crate::server::workflow::SequentialWorkflow,
+    ) -> Result<crate::server::workflow::WorkflowPromptHandler> {
+        use crate::server::builder_middleware_executor::BuilderMiddlewareExecutor;
+        use crate::server::middleware_executor::MiddlewareExecutor;
+        use crate::server::tool_timeout::DeferredTimedHandler;
+        use crate::server::workflow;
+
+        // Build tool registry from cached metadata (avoids per-request handler.metadata() calls)
+        let mut tool_registry = std::collections::HashMap::new();
+        for (name, info) in &self.tool_infos {
+            tool_registry.insert(
+                Arc::from(name.as_str()),
+                workflow::conversion::ToolInfo {
+                    name: info.name.clone(),
+                    description: info.description.clone().unwrap_or_default(),
+                    input_schema: info.input_schema.clone(),
+                },
+            );
+        }
+
+        // Steps run each tool under the deadline a direct call would get
+        let tools = self
+            .tools
+            .iter()
+            .map(|(name, handler)| {
+                let handler = DeferredTimedHandler::new(
+                    name.clone(),
+                    handler.clone(),
+                    self.workflow_tool_timeouts.clone(),
+                    self.events.clone(),
+                );
+                (name.clone(), Arc::new(handler) as Arc<dyn ToolHandler>)
+            })
+            .collect();
+
+        // Create builder-scoped middleware executor
+        let middleware_executor = Arc::new(BuilderMiddlewareExecutor::new(
+            tools,
+            self.tool_middlewares.clone(),
+        )) as Arc<dyn MiddlewareExecutor>;
+
+        let handler = workflow::WorkflowPromptHandler::with_middleware_executor(
+            workflow,
+            tool_registry,
+            middleware_executor,
+            self.resources.clone(),
+        );
+        #[cfg(feature = "composition")]
+        let handler = match &self.foundation_client {
+            Some(client) => handler.with_foundation_client(client.clone()),
+            None => handler,
+        };
+        handler
+            .check_remote_steps()
+            .map_err(|e| Error::validation(format!("Workflow validation failed: {}", e)))?;
+        Ok(handler)
+    }
+
+    /// Whether `tool_with_form` registered widgets that need the resources subsystem.
+    fn has_form_widgets(&self) -> bool {
+        #[cfg(feature = "mcp-apps")]
+        return !self.form_tools.is_empty();
+        #[cfg(not(feature = "mcp-apps"))]
+        false
+    }
+
     /// Build the `ServerCore` instance.
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields (name, version) are not set.
+    /// Returns an error if required fields (name, version) are not set, or
+    /// if scheduled workflows are registered without a task store.
     #[allow(unused_mut)]
     pub fn build(mut self) -> Result<ServerCore> {
+        #[cfg(feature = "prompt-dir")]
+        for dir in std::mem::take(&mut self.prompt_dirs) {
+            for (prompt_name, prompt) in dir.prompts()? {
+                if self.prompts.contains_key(&prompt_name) {
+                    return Err(Error::validation(format!(
+                        "Prompt '{}' from {} is already registered",
+                        prompt_name,
+                        dir.path().display()
+                    )));
+                }
+                self = self.prompt(prompt_name, prompt);
+            }
+        }
+
+        {
+            use crate::server::subsystem::Subsystem;
+            let disabled = &self.disabled;
+            disabled.check_unused(Subsystem::Prompts, !self.prompts.is_empty())?;
+            #[cfg(feature = "resources")]
+            let watched = !self.resource_watchers.is_empty();
+            #[cfg(not(feature = "resources"))]
+            let watched = false;
+            disabled.check_unused(
+                Subsystem::Resources,
+                self.resources.is_some() || watched || self.has_form_widgets(),
+            )?;
+            #[cfg(feature = "tasks")]
+            disabled.check_unused(
+                Subsystem::Tasks,
+                self.task_router.is_some() || self.task_store.is_some(),
+            )?;
+            disabled.check_unused(Subsystem::Sampling, self.sampling.is_some())?;
+            disabled.apply(&mut self.capabilities);
+        }
+
         let name = self
             .name
             .ok_or_else(|| Error::validation("Server name is required"))?;
@@ -864,6 +1641,40 @@ impl ServerCoreBuilder {
             Arc::new(RwLock::new(tool_middleware_chain))
         };
 
+        #[cfg(feature = "mcp-apps")]
+        if !self.form_tools.is_empty() {
+            let mut forms = crate::server::mcp_apps::FormWidgets::new(self.resources.take());
+            for tool in &self.form_tools {
+                forms.add(tool, &self.tool_infos)?;
+            }
+            self.resources = Some(Arc::new(forms));
+            self.capabilities
+                .resources
+                .get_or_insert(crate::types::ResourceCapabilities {
+                    subscribe: Some(false),
+                    list_changed: Some(false),
+                });
+        }
+
+        crate::server::core::link_tool_widgets(
+            &mut self.tool_infos,
+            &self.tool_widgets,
+            self.resources.is_some(),
+        )?;
+
+        #[cfg(feature = "workflow")]
+        for workflow in self.self_test.workflows() {
+            workflow
+                .validate_tools(&self.tool_infos)
+                .map_err(|e| Error::validation(format!("Workflow validation failed: {}", e)))?;
+        }
+
+        if let Some((baseline, mode)) = self.schema_baseline.take() {
+            let guard = SchemaGuard::new(baseline, mode, info.version.as_str());
+            guard.check_build(&self.tool_infos, self.prompt_infos.values())?;
+            self.self_test.set_schema_guard(guard);
+        }
+
         // Enrich tool _meta with host-specific keys (e.g., openai/* for ChatGPT)
         #[cfg(feature = "mcp-apps")]
         {
@@ -874,14 +1685,34 @@ impl ServerCoreBuilder {
                     }
                 }
             }
+            if let Some(manifest) = &self.widget_manifest {
+                manifest.validate(&self.tool_infos)?;
+            }
         }
 
+        #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+        let scheduler = if self.scheduled_workflows.is_empty() {
+            None
+        } else {
+            let store = self.task_store.clone().ok_or_else(|| {
+                Error::validation(
+                    "Scheduled workflows record their runs as tasks; \
+                     call .task_store() on the builder",
+                )
+            })?;
+            Some(crate::server::scheduler::WorkflowScheduler::new(
+                std::mem::take(&mut self.scheduled_workflows),
+                store,
+                self.events.clone(),
+            ))
+        };
+
         // Determine stateless mode: use explicit setting or auto-detect
         let stateless_mode = self
             .stateless_mode
             .unwrap_or_else(Self::detect_stateless_environment);
 
-        Ok(ServerCore::new(
+        let core = ServerCore::new(
             info,
             self.capabilities,
             self.tools,
@@ -895,12 +1726,41 @@ impl ServerCoreBuilder {
             self.protocol_middleware,
             #[cfg(not(target_arch = "wasm32"))]
             tool_middleware,
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             self.task_router,
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             self.task_store,
             stateless_mode,
-        ))
+        )
+        .with_events(self.events)
+        .with_schema_registry(&self.schema_registry)
+        .with_self_test(self.self_test)
+        .with_widget_state(self.widget_state)
+        .with_kv_store(self.kv_store)
+        .with_flag_provider(self.flag_provider)
+        .with_state(self.state)
+        .with_completions(self.completions)
+        .with_prompt_authorizer(self.prompt_authorizer);
+
+        #[cfg(feature = "resources")]
+        let core = core.with_resource_watchers(self.resource_watchers);
+
+        let core = core.with_disabled_subsystems(self.disabled);
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+        let _ = self.workflow_tool_timeouts.set(self.tool_timeouts.clone());
+        #[cfg(not(target_arch = "wasm32"))]
+        let tool_pages = self.tools_page_size.map(|page_size| {
+            crate::server::pagination::ListPages::new("tools/list", page_size, self.cursor_codec)
+        });
+        #[cfg(not(target_arch = "wasm32"))]
+        let core = core
+            .with_tool_timeouts(self.tool_timeouts)
+            .with_tool_pages(tool_pages);
+        #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+        let core = core.with_scheduler(scheduler);
+
+        Ok(core)
     }
 }
 
@@ -1132,6 +1992,46 @@ mod tests {
         assert!(caps.tools.is_some());
     }
 
+    #[cfg(feature = "mcp-apps")]
+    #[test]
+    fn test_builder_widget_manifest_validates_tool_references() {
+        use crate::server::mcp_apps::{WidgetManifest, WidgetManifestEntry};
+
+        struct UiTool;
+
+        #[async_trait]
+        impl ToolHandler for UiTool {
+            async fn handle(&self, _args: Value, _extra: RequestHandlerExtra) -> Result<Value> {
+                Ok(Value::Null)
+            }
+            fn metadata(&self) -> Option<ToolInfo> {
+                Some(ToolInfo::with_ui(
+                    "ui-tool",
+                    None,
+                    serde_json::json!({"type": "object"}),
+                    "ui://chess/board",
+                ))
+            }
+        }
+
+        let build = |manifest: WidgetManifest| {
+            ServerCoreBuilder::new()
+                .name("test")
+                .version("1.0.0")
+                .tool("ui-tool", UiTool)
+                .widget_manifest(manifest)
+                .build()
+        };
+
+        let board = WidgetManifestEntry::new("board", "ui://chess/board");
+        assert!(build(WidgetManifest::new().with_widget(board)).is_ok());
+
+        let err = build(WidgetManifest::new())
+            .err()
+            .expect("unresolved widget reference should fail the build");
+        assert!(err.to_string().contains("ui://chess/board"));
+    }
+
     #[cfg(feature = "mcp-apps")]
     #[test]
     fn test_builder_without_host_layer_no_openai_keys() {
@@ -1215,9 +2115,59 @@ mod tests {
         assert!(server.capabilities().experimental.is_none());
     }
 
+    #[test]
+    fn test_disable_tasks_rejects_task_store() {
+        use crate::server::subsystem::Subsystem;
+
+        let err = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .task_store(Arc::new(crate::server::task_store::InMemoryTaskStore::new()))
+            .disable(Subsystem::Tasks)
+            .build()
+            .err()
+            .expect("build should fail");
+        assert!(err.to_string().contains("tasks subsystem is disabled"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_subsystem_returns_method_not_found() {
+        use crate::server::subsystem::Subsystem;
+        use crate::types::tasks::ListTasksRequest;
+        use crate::types::{ClientRequest, Request, RequestId};
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool("test_tool", TestTool)
+            .disable(Subsystem::Tasks)
+            .disable(Subsystem::Resources)
+            .build()
+            .unwrap();
+        assert!(server.capabilities().tasks.is_none());
+        assert!(server.capabilities().resources.is_none());
+
+        let request = Request::Client(Box::new(ClientRequest::TasksList(
+            ListTasksRequest::default(),
+        )));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                assert_eq!(error.code, -32601);
+                assert!(error.message.contains("tasks subsystem is disabled"));
+            },
+            other => panic!("Expected method not found, got {:?}", other),
+        }
+    }
+
     /// Shared mock task router for workflow task tests.
+    #[cfg(feature = "workflow")]
     struct WorkflowMockTaskRouter;
 
+    #[cfg(feature = "workflow")]
     #[async_trait]
     impl crate::server::tasks::TaskRouter for WorkflowMockTaskRouter {
         async fn handle_task_call(
@@ -1261,6 +2211,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "workflow")]
     #[test]
     fn test_workflow_without_task_support_registers_normally() {
         use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
@@ -1281,6 +2232,45 @@ mod tests {
         assert!(server.capabilities().prompts.is_some());
     }
 
+    #[cfg(feature = "workflow")]
+    #[test]
+    fn test_workflow_argument_mismatch_fails_build() {
+        use crate::server::simple_tool::SimpleTool;
+        use crate::server::workflow::{DataSource, SequentialWorkflow, ToolHandle, WorkflowStep};
+        use serde_json::json;
+
+        let tool = SimpleTool::new("fetch", |_args, _extra| {
+            Box::pin(async { Ok(json!({})) })
+                as std::pin::Pin<Box<dyn std::future::Future<Output = Result<Value>> + Send>>
+        })
+        .with_schema(json!({
+            "type": "object",
+            "properties": { "limit": { "type": "integer" } }
+        }));
+        let err = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .tool("fetch", tool)
+            .prompt_workflow(
+                SequentialWorkflow::new("report", "Build a report")
+                    .argument("limit", "How many rows", true)
+                    .step(
+                        WorkflowStep::new("fetch", ToolHandle::new("fetch"))
+                            .arg("limit", DataSource::prompt_arg("limit")),
+                    ),
+            )
+            .unwrap()
+            .build()
+            .err()
+            .expect("build should fail");
+        assert!(
+            err.to_string()
+                .contains("expects integer for argument 'limit'"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "workflow")]
     #[test]
     fn test_workflow_with_task_support_and_router_wraps_in_task_handler() {
         use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
@@ -1305,6 +2295,7 @@ mod tests {
         assert!(server.capabilities().prompts.is_some());
     }
 
+    #[cfg(feature = "workflow")]
     #[test]
     fn test_workflow_with_task_support_but_no_router_errors() {
         use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
@@ -1330,4 +2321,638 @@ mod tests {
             err_msg
         );
     }
+
+    #[cfg(feature = "workflow")]
+    fn digest_workflow() -> crate::server::workflow::SequentialWorkflow {
+        use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+        SequentialWorkflow::new("digest", "Nightly digest")
+            .step(WorkflowStep::new("collect", ToolHandle::new("test_tool")))
+    }
+
+    #[cfg(feature = "workflow")]
+    #[test]
+    fn test_scheduled_workflow_validation() {
+        let bad_cron =
+            ServerCoreBuilder::new().scheduled_workflow("digest", "0 3 * *", digest_workflow());
+        assert!(bad_cron.is_err());
+
+        let with_args = ServerCoreBuilder::new().scheduled_workflow(
+            "digest",
+            "0 3 * * *",
+            digest_workflow().argument("date", "Day to summarize", true),
+        );
+        assert!(with_args.is_err());
+
+        let no_store = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .scheduled_workflow("digest", "0 3 * * *", digest_workflow())
+            .unwrap()
+            .build();
+        assert!(no_store.is_err());
+    }
+
+    #[cfg(feature = "workflow")]
+    #[tokio::test]
+    async fn test_scheduled_workflow_result_via_tasks_result() {
+        use crate::types::tasks::{GetTaskPayloadRequest, TaskStatus};
+        use crate::types::{ClientRequest, Request, RequestId};
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool("test_tool", TestTool)
+            .task_store(Arc::new(crate::server::task_store::InMemoryTaskStore::new()))
+            .scheduled_workflow("digest", "0 3 * * *", digest_workflow())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let scheduler = server.scheduler().expect("scheduler should be configured");
+        let task = scheduler.run_now("digest").await.unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+
+        let request = Request::Client(Box::new(ClientRequest::TasksResult(
+            GetTaskPayloadRequest {
+                task_id: task.task_id.clone(),
+            },
+        )));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Result(value) => {
+                assert!(
+                    value["messages"].is_array(),
+                    "expected workflow messages, got {}",
+                    value
+                );
+            },
+            other => panic!("Expected scheduled run result, got {:?}", other),
+        }
+    }
+    #[tokio::test]
+    async fn test_schema_definitions_shared_in_tools_list() {
+        use crate::server::simple_tool::SimpleTool;
+        use crate::types::{ClientRequest, ListToolsRequest, ListToolsResult, Request, RequestId};
+        use serde_json::json;
+
+        let address = json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"]
+        });
+        let tool = |name: &str| {
+            SimpleTool::new(name, |_args, _extra| {
+                Box::pin(async { Ok(json!({})) })
+                    as std::pin::Pin<Box<dyn std::future::Future<Output = Result<Value>> + Send>>
+            })
+            .with_schema(json!({
+                "type": "object",
+                "properties": { "address": address.clone() }
+            }))
+        };
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .schema_definition("Address", address.clone())
+            .tool("create_order", tool("create_order"))
+            .tool("update_shipping", tool("update_shipping"))
+            .build()
+            .unwrap();
+
+        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+            cursor: None,
+        })));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(value) = response.payload else {
+            panic!("Expected tools/list result");
+        };
+        let result: ListToolsResult = serde_json::from_value(value).unwrap();
+
+        assert_eq!(result.tools.len(), 2);
+        for tool in &result.tools {
+            assert_eq!(
+                tool.input_schema["properties"]["address"],
+                json!({ "$ref": "#/definitions/Address" })
+            );
+        }
+        assert_eq!(result.definitions.unwrap()["Address"], address);
+    }
+
+    #[tokio::test]
+    async fn test_run_self_test() {
+        use crate::server::self_test::CheckKind;
+        use crate::server::task_store::InMemoryTaskStore;
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .tool("test-tool", TestTool)
+            .task_store(Arc::new(InMemoryTaskStore::new()))
+            .self_test_secret("PMCP_BUILDER_SELF_TEST_UNSET")
+            .self_test_backend("database", || async { Ok(()) })
+            .build()
+            .unwrap();
+
+        let report = server.run_self_test().await;
+
+        let kinds: Vec<_> = report.checks.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CheckKind::ToolSchema,
+                CheckKind::Secret,
+                CheckKind::Backend,
+                CheckKind::Backend,
+            ]
+        );
+        let failures: Vec<_> = report.failures().map(|c| c.name.as_str()).collect();
+        assert_eq!(failures, vec!["PMCP_BUILDER_SELF_TEST_UNSET"]);
+    }
+
+    #[tokio::test]
+    async fn test_schema_baseline() {
+        use crate::server::self_test::CheckKind;
+
+        let baseline = SchemaBaseline::from_json(
+            &serde_json::json!({
+                "version": "1.0.0",
+                "tools": [{
+                    "name": "test-tool",
+                    "input_schema": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
+                    }
+                }],
+                "resources": [{ "uri": "test://removed" }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let builder = |version: &str, mode| {
+            ServerCoreBuilder::new()
+                .name("test")
+                .version(version)
+                .tool("test-tool", TestTool)
+                .schema_baseline(baseline.clone(), mode)
+        };
+
+        let Err(err) = builder("1.1.0", SchemaCheckMode::Strict).build() else {
+            panic!("breaking change without a major version bump");
+        };
+        assert!(err
+            .to_string()
+            .contains("tool 'test-tool': input property 'id' removed"));
+
+        let server = builder("2.0.0", SchemaCheckMode::Strict).build().unwrap();
+        let report = server.run_self_test().await;
+        let schema = report
+            .checks
+            .iter()
+            .find(|c| c.kind == CheckKind::Schema)
+            .unwrap();
+        assert!(schema.passed());
+
+        let server = builder("1.1.0", SchemaCheckMode::Warn).build().unwrap();
+        assert!(server.run_self_test().await.passed());
+    }
+
+    #[tokio::test]
+    async fn test_tool_with_widget_links_meta() {
+        use crate::server::self_test::CheckKind;
+        use crate::server::simple_resources::{ResourceCollection, StaticResource};
+
+        let board = StaticResource::new_text("ui://app/board", "<html></html>");
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool_with_widget("chess_move", TestTool, "ui://app/board")
+            .tool_with_widget("chess_undo", TestTool, "ui://app/missing")
+            .resources(ResourceCollection::new().add_resource(board))
+            .build()
+            .unwrap();
+
+        let tools = list_tools(&server).await;
+        let info = tools.iter().find(|t| t.name == "chess_move").unwrap();
+        assert_eq!(info.widget_uris(), vec!["ui://app/board"]);
+
+        let report = server.run_self_test().await;
+        let widgets: Vec<_> = report
+            .checks
+            .iter()
+            .filter(|c| c.kind == CheckKind::Widget)
+            .map(|c| (c.name.as_str(), c.passed()))
+            .collect();
+        assert_eq!(
+            widgets,
+            vec![
+                ("ui://app/board (tool chess_move)", true),
+                ("ui://app/missing (tool chess_undo)", false),
+            ]
+        );
+    }
+
+    #[cfg(feature = "mcp-apps")]
+    #[tokio::test]
+    async fn test_tool_with_form_serves_generated_widget() {
+        use crate::server::self_test::CheckKind;
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool_with_form("chess_move", TestTool)
+            .build()
+            .unwrap();
+
+        let tools = list_tools(&server).await;
+        let info = tools.iter().find(|t| t.name == "chess_move").unwrap();
+        assert_eq!(info.widget_uris(), vec!["ui://pmcp/form/chess_move"]);
+        assert!(server.capabilities().resources.is_some());
+
+        let report = server.run_self_test().await;
+        assert!(report
+            .checks
+            .iter()
+            .filter(|c| c.kind == CheckKind::Widget)
+            .all(|c| c.passed()));
+    }
+
+    #[test]
+    fn test_tool_with_widget_validates_at_build() {
+        use crate::server::simple_resources::ResourceCollection;
+
+        let err = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .tool_with_widget("chess_move", TestTool, "ui://app/board")
+            .build()
+            .err()
+            .expect("widget without a resource handler should fail the build");
+        assert!(err.to_string().contains("no resource handler"));
+
+        let err = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .tool_with_widget("chess_move", TestTool, "https://example.com/board")
+            .resources(ResourceCollection::new())
+            .build()
+            .err()
+            .expect("non-ui:// widget URI should fail the build");
+        assert!(err.to_string().contains("ui://"));
+    }
+
+    #[cfg(feature = "mcp-apps")]
+    #[tokio::test]
+    async fn test_tool_with_widget_chatgpt_output_template() {
+        use crate::server::simple_resources::ResourceCollection;
+        use crate::types::mcp_apps::HostType;
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .with_host_layer(HostType::ChatGpt)
+            .tool_with_widget("chess_move", TestTool, "ui://app/board")
+            .resources(ResourceCollection::new())
+            .build()
+            .unwrap();
+
+        let tools = list_tools(&server).await;
+        let meta = tools[0]._meta.as_ref().unwrap();
+        assert_eq!(meta["openai/outputTemplate"], "ui://app/board");
+        assert_eq!(meta["ui"]["resourceUri"], "ui://app/board");
+    }
+
+    #[tokio::test]
+    async fn test_widget_state_store_reaches_handlers() {
+        use crate::server::auth::AuthContext;
+        use crate::server::widget_state::{InMemoryWidgetStateStore, WidgetStateStore};
+        use crate::types::{CallToolRequest, ClientRequest, Request, RequestId};
+
+        struct CounterTool;
+
+        #[async_trait]
+        impl ToolHandler for CounterTool {
+            async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+                let count = extra
+                    .load_widget_state("ui://app/counter")
+                    .await?
+                    .and_then(|state| state["count"].as_u64())
+                    .unwrap_or(0)
+                    + 1;
+                extra
+                    .save_widget_state("ui://app/counter", serde_json::json!({"count": count}))
+                    .await?;
+                Ok(serde_json::json!({"count": count}))
+            }
+        }
+
+        let store = Arc::new(InMemoryWidgetStateStore::new());
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool("counter", CounterTool)
+            .widget_state_store(store.clone())
+            .build()
+            .unwrap();
+
+        for subject in ["alice", "alice", "bob"] {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "counter",
+                serde_json::json!({}),
+            ))));
+            server
+                .handle_request(
+                    RequestId::from(1i64),
+                    request,
+                    Some(AuthContext::new(subject)),
+                )
+                .await;
+        }
+
+        let alice = store.load("alice", "ui://app/counter").await.unwrap();
+        assert_eq!(alice, Some(serde_json::json!({"count": 2})));
+        let bob = store.load("bob", "ui://app/counter").await.unwrap();
+        assert_eq!(bob, Some(serde_json::json!({"count": 1})));
+    }
+
+    #[tokio::test]
+    async fn test_kv_store_reaches_handlers() {
+        use crate::storage::{KvStore, MemoryKvStore};
+        use crate::types::{CallToolRequest, ClientRequest, Request, RequestId};
+
+        struct VisitsTool;
+
+        #[async_trait]
+        impl ToolHandler for VisitsTool {
+            async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+                let visits = extra.kv()?.increment("visits", 1).await?;
+                Ok(serde_json::json!({"visits": visits}))
+            }
+        }
+
+        let store = Arc::new(MemoryKvStore::new());
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool("visits", VisitsTool)
+            .kv_store(store.clone())
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "visits",
+                serde_json::json!({}),
+            ))));
+            server
+                .handle_request(RequestId::from(1i64), request, None)
+                .await;
+        }
+
+        assert_eq!(store.get("visits").await.unwrap(), Some(b"3".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_state_reaches_handlers() {
+        use crate::types::{CallToolRequest, ClientRequest, Request, RequestId};
+
+        struct Greeting(&'static str);
+
+        struct GreetTool;
+
+        #[async_trait]
+        impl ToolHandler for GreetTool {
+            async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+                let greeting = extra.state::<Greeting>()?;
+                assert!(extra.state::<u64>().is_err());
+                Ok(serde_json::json!({"greeting": greeting.0}))
+            }
+        }
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool("greet", GreetTool)
+            .with_state(Greeting("hello"))
+            .build()
+            .unwrap();
+
+        let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+            "greet",
+            serde_json::json!({}),
+        ))));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(value) = response.payload else {
+            panic!("Expected tools/call result");
+        };
+        assert!(
+            value.to_string().contains("hello"),
+            "expected greeting from state, got {}",
+            value
+        );
+    }
+
+    #[tokio::test]
+    async fn test_feature_flags_reach_handlers_and_widgets() {
+        use crate::server::auth::AuthContext;
+        use crate::server::flags::{StaticFlags, FLAGS_META_KEY};
+        use crate::server::simple_resources::ResourceCollection;
+        use crate::types::{CallToolRequest, ClientRequest, Request, RequestId};
+
+        struct SearchTool;
+
+        #[async_trait]
+        impl ToolHandler for SearchTool {
+            async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+                let engine = if extra.flags().is_enabled("search-v2") {
+                    "v2"
+                } else {
+                    "v1"
+                };
+                Ok(serde_json::json!({ "engine": engine }))
+            }
+        }
+
+        let flags =
+            StaticFlags::new()
+                .flag("search-v2", false)
+                .tenant_flag("acme", "search-v2", true);
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .tool_with_widget("search", SearchTool, "ui://app/results")
+            .resources(ResourceCollection::new())
+            .feature_flags(Arc::new(flags))
+            .build()
+            .unwrap();
+
+        let mut acme_user = AuthContext::new("alice");
+        acme_user
+            .claims
+            .insert("tenant_id".to_string(), serde_json::json!("acme"));
+        for (auth, engine) in [(Some(acme_user), "v2"), (None, "v1")] {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "search",
+                serde_json::json!({}),
+            ))));
+            let response = server
+                .handle_request(RequestId::from(1i64), request, auth)
+                .await;
+            let crate::types::jsonrpc::ResponsePayload::Result(value) = response.payload else {
+                panic!("Expected tools/call result");
+            };
+            assert_eq!(value["structuredContent"]["engine"], engine);
+            assert_eq!(
+                value["_meta"][FLAGS_META_KEY]["search-v2"],
+                serde_json::json!(engine == "v2")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resource_subscriptions_receive_watcher_changes() {
+        use crate::server::subscriptions::{ResourceChangeNotifier, ResourceWatcher};
+        use crate::types::{
+            ClientRequest, Request, RequestId, ServerNotification, SubscribeRequest,
+            UnsubscribeRequest,
+        };
+
+        struct ManualWatcher(Arc<std::sync::Mutex<Option<ResourceChangeNotifier>>>);
+
+        #[async_trait]
+        impl ResourceWatcher for ManualWatcher {
+            async fn start(&self, notifier: ResourceChangeNotifier) -> Result<()> {
+                *self.0.lock().unwrap() = Some(notifier);
+                Ok(())
+            }
+        }
+
+        let slot = Arc::new(std::sync::Mutex::new(None));
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .resource_watcher(ManualWatcher(slot.clone()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            server.capabilities().resources.as_ref().unwrap().subscribe,
+            Some(true)
+        );
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        server
+            .start_resource_watchers(move |notification| {
+                let _ = tx.send(notification);
+            })
+            .await
+            .unwrap();
+
+        let subscribe = Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest {
+            uri: "ui://app/board".to_string(),
+        })));
+        server
+            .handle_request(RequestId::from(1i64), subscribe, None)
+            .await;
+
+        let notifier = slot.lock().unwrap().clone().unwrap();
+        notifier.notify("ui://app/other");
+        notifier.notify("ui://app/board");
+        let notification = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match notification {
+            ServerNotification::ResourceUpdated(params) => assert_eq!(params.uri, "ui://app/board"),
+            other => panic!("unexpected notification: {:?}", other),
+        }
+
+        let unsubscribe =
+            Request::Client(Box::new(ClientRequest::Unsubscribe(UnsubscribeRequest {
+                uri: "ui://app/board".to_string(),
+            })));
+        server
+            .handle_request(RequestId::from(2i64), unsubscribe, None)
+            .await;
+        assert_eq!(
+            server
+                .notify_resource_updated("ui://app/board")
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prompt_completion() {
+        use crate::server::completion::StaticCompletions;
+        use crate::types::{
+            ClientRequest, CompleteRequest, CompleteResult, CompletionArgument,
+            CompletionReference, Request, RequestId,
+        };
+
+        let server = ServerCoreBuilder::new()
+            .name("test")
+            .version("1.0.0")
+            .stateless_mode(true)
+            .prompt_completion(
+                "deploy",
+                "environment",
+                StaticCompletions::new(["development", "staging", "production"]),
+            )
+            .build()
+            .unwrap();
+        assert!(server.capabilities().completions.is_some());
+
+        let request = Request::Client(Box::new(ClientRequest::Complete(CompleteRequest {
+            r#ref: CompletionReference::Prompt {
+                name: "deploy".to_string(),
+            },
+            argument: CompletionArgument {
+                name: "environment".to_string(),
+                value: "st".to_string(),
+            },
+            context: None,
+        })));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(value) = response.payload else {
+            panic!("Expected completion/complete result");
+        };
+        let result: CompleteResult = serde_json::from_value(value).unwrap();
+        assert_eq!(result.completion.values, vec!["staging"]);
+    }
+
+    async fn list_tools(server: &ServerCore) -> Vec<ToolInfo> {
+        use crate::types::{ClientRequest, ListToolsRequest, ListToolsResult, Request, RequestId};
+
+        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+            cursor: None,
+        })));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(value) = response.payload else {
+            panic!("Expected tools/list result");
+        };
+        serde_json::from_value::<ListToolsResult>(value)
+            .unwrap()
+            .tools
+    }
 }
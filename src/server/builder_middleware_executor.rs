@@ -7,7 +7,7 @@
 use crate::error::Result;
 use crate::server::cancellation::RequestHandlerExtra;
 use crate::server::middleware_executor::MiddlewareExecutor;
-use crate::server::tool_middleware::{ToolContext, ToolMiddleware};
+use crate::server::tool_middleware::{ToolContext, ToolMiddleware, ToolMiddlewareChain};
 use crate::server::ToolHandler;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -55,14 +55,14 @@ pub struct BuilderMiddlewareExecutor {
     #[allow(dead_code)] // Tools are accessed via get() method
     tools: HashMap<String, Arc<dyn ToolHandler>>,
     /// Middleware chain (captured from builder)
-    middlewares: Vec<Arc<dyn ToolMiddleware>>,
+    chain: ToolMiddlewareChain,
 }
 
 impl std::fmt::Debug for BuilderMiddlewareExecutor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BuilderMiddlewareExecutor")
             .field("tools", &self.tools.keys().collect::<Vec<_>>())
-            .field("middlewares_count", &self.middlewares.len())
+            .field("middlewares_count", &self.chain.len())
             .finish()
     }
 }
@@ -75,7 +75,11 @@ impl BuilderMiddlewareExecutor {
         tools: HashMap<String, Arc<dyn ToolHandler>>,
         middlewares: Vec<Arc<dyn ToolMiddleware>>,
     ) -> Self {
-        Self { tools, middlewares }
+        let mut chain = ToolMiddlewareChain::new();
+        for middleware in middlewares {
+            chain.add(middleware);
+        }
+        Self { tools, chain }
     }
 }
 
@@ -84,10 +88,9 @@ impl MiddlewareExecutor for BuilderMiddlewareExecutor {
     async fn execute_tool_with_middleware(
         &self,
         tool_name: &str,
-        mut args: Value,
-        mut extra: RequestHandlerExtra,
+        args: Value,
+        extra: RequestHandlerExtra,
     ) -> Result<Value> {
-        // Debug: Check auth_context at middleware executor entry
         tracing::debug!(
             "BuilderMiddlewareExecutor.execute_tool_with_middleware() - Entry: auth_context present: {}, has_token: {}, tool: {}",
             extra.auth_context.is_some(),
@@ -101,52 +104,11 @@ impl MiddlewareExecutor for BuilderMiddlewareExecutor {
             .get(tool_name)
             .ok_or_else(|| crate::Error::internal(format!("Tool '{}' not found", tool_name)))?;
 
-        // Create tool context for middleware
-        let context = ToolContext::new(tool_name, &extra.request_id);
-
-        // Process request through middleware chain
-        for middleware in &self.middlewares {
-            // Debug: Log before middleware processing
-            tracing::debug!(
-                "BuilderMiddlewareExecutor - Before middleware: auth_context present: {}, has_token: {}",
-                extra.auth_context.is_some(),
-                extra.auth_context.as_ref().and_then(|ctx| ctx.token.as_ref()).is_some()
-            );
-
-            middleware
-                .on_request(tool_name, &mut args, &mut extra, &context)
-                .await?;
-
-            // Debug: Log after middleware processing
-            tracing::debug!(
-                "BuilderMiddlewareExecutor - After middleware: auth_context present: {}, has_token: {}",
-                extra.auth_context.is_some(),
-                extra.auth_context.as_ref().and_then(|ctx| ctx.token.as_ref()).is_some()
-            );
-        }
-
-        // Execute the tool
-        let mut result = handler.handle(args, extra.clone()).await;
-
-        // Process response through middleware chain
-        for middleware in &self.middlewares {
-            if let Err(e) = middleware
-                .on_response(tool_name, &mut result, &context)
-                .await
-            {
-                // Log error but continue with original result
-                tracing::warn!("Tool response middleware processing failed: {}", e);
-            }
-        }
-
-        // If tool execution failed, call on_error hooks
-        if let Err(ref e) = result {
-            for middleware in &self.middlewares {
-                let _ = middleware.on_error(tool_name, e, &context).await;
-            }
-        }
-
-        result
+        // Run the tool through the same lifecycle as the built server
+        let context = ToolContext::for_call(tool_name, &extra);
+        self.chain
+            .execute(tool_name, args, extra, &context, handler.as_ref())
+            .await
     }
 }
 
@@ -1,15 +1,65 @@
 //! Request cancellation support for MCP server.
 
-use crate::error::Result;
+use crate::error::{Error, ErrorCode, Result};
+use crate::server::elicitation::ElicitInput;
+use crate::server::extensions::Extensions;
+use crate::server::flags::Flags;
 use crate::server::progress::ProgressReporter;
+use crate::server::roots::{ClientRoots, Root};
+#[cfg(feature = "sampling")]
+use crate::server::sampling::{ClientSampling, SamplingRequestBuilder};
+use crate::server::widget_state::WidgetStateStore;
+use crate::storage::KvStore;
+use crate::types::elicitation::{ElicitRequestParams, ElicitResult};
+#[cfg(feature = "sampling")]
+use crate::types::CreateMessageResult;
 use crate::types::{CancelledNotification, Notification};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::RwLock;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio_util::sync::CancellationToken;
 
+tokio::task_local! {
+    static REQUEST_SESSION: Option<String>;
+}
+
+/// Run `future` with `session_id` as the transport session of its request.
+///
+/// Request dispatch reads it to fill [`RequestHandlerExtra::session_id`],
+/// the tool middleware context, and the task owner. The streamable HTTP
+/// server does this for every request; custom transports serving a
+/// [`ServerCore`](crate::server::core::ServerCore) wrap `handle_request` in
+/// it to give each of their sessions its own identity.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use pmcp::server::cancellation::with_request_session;
+/// # use pmcp::server::core::{ProtocolHandler, ServerCore};
+/// # use pmcp::types::{Request, RequestId};
+/// # async fn example(core: &ServerCore, id: RequestId, request: Request) {
+/// let response = with_request_session(
+///     Some("session-42".to_string()),
+///     core.handle_request(id, request, None),
+/// )
+/// .await;
+/// # }
+/// ```
+pub async fn with_request_session<F: Future>(session_id: Option<String>, future: F) -> F::Output {
+    REQUEST_SESSION.scope(session_id, future).await
+}
+
+/// The session set by [`with_request_session`], if any.
+///
+/// Single-client transports such as stdio have no session.
+pub(crate) fn request_session() -> Option<String> {
+    REQUEST_SESSION.try_with(Clone::clone).ok().flatten()
+}
+
 /// Manages cancellation tokens for requests.
 pub struct CancellationManager {
     tokens: Arc<RwLock<HashMap<String, CancellationToken>>>,
@@ -144,6 +194,46 @@ pub struct RequestHandlerExtra {
     /// When `None`, the client does not support tasks or did not request
     /// task mode — the tool should return results synchronously.
     pub task_request: Option<serde_json::Value>,
+    /// Workflow task referenced by the request's `_meta._task_id` (PMCP extension).
+    ///
+    /// Task-backed workflow prompts resume this task from its last completed
+    /// step instead of starting a new one.
+    pub task_id: Option<String>,
+    /// Widget state store registered on the server, if any.
+    ///
+    /// Use [`load_widget_state`](Self::load_widget_state) and
+    /// [`save_widget_state`](Self::save_widget_state) rather than calling the
+    /// store directly so the session key is resolved consistently.
+    pub widget_state: Option<Arc<dyn WidgetStateStore>>,
+    /// Key-value store registered on the server, if any.
+    pub kv_store: Option<Arc<dyn KvStore>>,
+    /// Channel for eliciting user input, present when the client supports it.
+    ///
+    /// Use [`elicit`](Self::elicit) rather than calling it directly so the
+    /// request is abandoned when the tool call is cancelled.
+    pub elicitation: Option<Arc<dyn ElicitInput>>,
+    /// The client's roots, present when the client supports them.
+    ///
+    /// Use [`roots`](Self::roots) and [`resolve_path`](Self::resolve_path)
+    /// rather than calling it directly.
+    pub roots: Option<Arc<ClientRoots>>,
+    /// Channel for sampling the client's model, present when the client
+    /// supports it.
+    ///
+    /// Use [`sample`](Self::sample) rather than calling it directly.
+    #[cfg(feature = "sampling")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+    pub sampling: Option<Arc<ClientSampling>>,
+    /// Feature flags evaluated for the caller of this request.
+    ///
+    /// Empty unless the server has a flag provider. Read them with
+    /// [`flags`](Self::flags).
+    pub flags: Flags,
+    /// Typed state registered on the server with `with_state`.
+    ///
+    /// Each request gets its own copy, so middleware may insert
+    /// request-scoped values. Read it with [`state`](Self::state).
+    pub extensions: Extensions,
 }
 
 impl RequestHandlerExtra {
@@ -158,6 +248,15 @@ impl RequestHandlerExtra {
             metadata: HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            #[cfg(feature = "sampling")]
+            sampling: None,
+            flags: Flags::new(),
+            extensions: Extensions::new(),
         }
     }
 
@@ -201,6 +300,271 @@ impl RequestHandlerExtra {
         self
     }
 
+    /// Set the workflow task referenced by the request's `_meta`.
+    pub fn with_task_id(mut self, task_id: Option<String>) -> Self {
+        self.task_id = task_id;
+        self
+    }
+
+    /// Attach the server's widget state store.
+    pub fn with_widget_state(mut self, widget_state: Option<Arc<dyn WidgetStateStore>>) -> Self {
+        self.widget_state = widget_state;
+        self
+    }
+
+    /// Attach the server's key-value store.
+    pub fn with_kv_store(mut self, kv_store: Option<Arc<dyn KvStore>>) -> Self {
+        self.kv_store = kv_store;
+        self
+    }
+
+    /// The server's key-value store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an internal error if no store was registered with
+    /// `kv_store` on the server builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let visits = extra.kv()?.increment("visits", 1).await?;
+    /// ```
+    pub fn kv(&self) -> Result<&dyn KvStore> {
+        self.kv_store.as_deref().ok_or_else(|| {
+            crate::Error::internal("no key-value store is configured on this server")
+        })
+    }
+
+    /// Attach the elicitation channel to the connected client.
+    pub fn with_elicitation(mut self, elicitation: Option<Arc<dyn ElicitInput>>) -> Self {
+        self.elicitation = elicitation;
+        self
+    }
+
+    /// Ask the user for structured input and wait for the answer.
+    ///
+    /// Sends an `elicitation/create` request with `requested_schema` (a flat
+    /// JSON Schema object of primitive fields) and `message`, and suspends
+    /// the handler until the client responds. Check the returned action:
+    /// the user may decline or cancel, in which case
+    /// [`ElicitResult::content_as`] returns `None`.
+    ///
+    /// # Errors
+    ///
+    /// - `UNSUPPORTED_CAPABILITY` if the client did not declare the
+    ///   `elicitation` capability or the transport cannot carry server requests
+    /// - `REQUEST_TIMEOUT` if the client does not answer within the server's
+    ///   elicitation timeout
+    /// - [`Error::Cancelled`] if the tool call is cancelled while waiting; the
+    ///   client is sent `notifications/cancelled` for the elicitation
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let answer = extra
+    ///     .elicit(
+    ///         json!({
+    ///             "type": "object",
+    ///             "properties": { "confirm": { "type": "boolean" } },
+    ///             "required": ["confirm"]
+    ///         }),
+    ///         "Delete 42 files?",
+    ///     )
+    ///     .await?;
+    ///
+    /// match answer.content_as::<Confirm>()? {
+    ///     Some(Confirm { confirm: true }) => delete_files().await,
+    ///     _ => Ok(json!({ "deleted": 0 })),
+    /// }
+    /// ```
+    pub async fn elicit(
+        &self,
+        requested_schema: Value,
+        message: impl Into<String>,
+    ) -> Result<ElicitResult> {
+        let elicitation = self.elicitation.as_ref().ok_or_else(|| {
+            Error::protocol(
+                ErrorCode::UNSUPPORTED_CAPABILITY,
+                "Elicitation is not available: the client did not declare the elicitation capability",
+            )
+        })?;
+
+        let request = ElicitRequestParams::Form {
+            message: message.into(),
+            requested_schema,
+        };
+
+        // Dropping the elicitation on cancellation withdraws it from the client
+        tokio::select! {
+            result = elicitation.elicit_input(request) => result,
+            () = self.cancellation_token.cancelled() => Err(Error::cancelled()),
+        }
+    }
+
+    /// Attach the connected client's roots.
+    pub fn with_roots(mut self, roots: Option<Arc<ClientRoots>>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// The directories the client has exposed to the server.
+    ///
+    /// Fetched with `roots/list` on first use and cached until the client
+    /// sends `notifications/roots/list_changed`.
+    ///
+    /// # Errors
+    ///
+    /// - `UNSUPPORTED_CAPABILITY` if the client did not declare the `roots`
+    ///   capability or the transport cannot carry server requests
+    /// - the client's error, or `REQUEST_TIMEOUT` if it does not answer
+    /// - [`Error::Cancelled`] if the tool call is cancelled while waiting
+    pub async fn roots(&self) -> Result<Vec<Root>> {
+        let roots = self.roots.as_ref().ok_or_else(|| {
+            Error::protocol(
+                ErrorCode::UNSUPPORTED_CAPABILITY,
+                "Roots are not available: the client did not declare the roots capability",
+            )
+        })?;
+
+        tokio::select! {
+            result = roots.list() => result,
+            () = self.cancellation_token.cancelled() => Err(Error::cancelled()),
+        }
+    }
+
+    /// Resolve a user-supplied path and check it is inside the client's roots.
+    ///
+    /// Relative paths are resolved against the first root. See
+    /// [`roots::resolve_path`](crate::server::roots::resolve_path).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`roots`](Self::roots), or `INVALID_PARAMS` if
+    /// the path is outside every root.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let path = extra.resolve_path(&input.path).await?;
+    /// let content = tokio::fs::read_to_string(path).await?;
+    /// ```
+    pub async fn resolve_path(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<std::path::PathBuf> {
+        let roots = self.roots().await?;
+        crate::server::roots::resolve_path(&roots, path)
+    }
+
+    /// Attach the sampling channel to the connected client.
+    #[cfg(feature = "sampling")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+    pub fn with_sampling(mut self, sampling: Option<Arc<ClientSampling>>) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Ask the client's model for a completion.
+    ///
+    /// Sends the request built with `request` as `sampling/createMessage`
+    /// and suspends the handler until the client answers. The client may
+    /// show the request to the user, pick a different model than hinted, or
+    /// refuse.
+    ///
+    /// # Errors
+    ///
+    /// - `INVALID_PARAMS` if the request is incomplete (see
+    ///   [`SamplingRequestBuilder::build`])
+    /// - `UNSUPPORTED_CAPABILITY` if the client did not declare the
+    ///   `sampling` capability or the transport cannot carry server requests
+    /// - the client's error if it refuses, or `REQUEST_TIMEOUT` if it does
+    ///   not answer within the server's sampling timeout
+    /// - [`Error::Cancelled`] if the tool call is cancelled while waiting
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let reply = extra
+    ///     .sample(
+    ///         SamplingRequestBuilder::new()
+    ///             .user(format!("Classify the sentiment of: {text}. Reply with JSON {{\"score\": number}}"))
+    ///             .intelligence_priority(0.3)
+    ///             .max_tokens(50),
+    ///     )
+    ///     .await?;
+    /// let sentiment: Sentiment = reply.parse_json()?;
+    /// ```
+    #[cfg(feature = "sampling")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+    pub async fn sample(&self, request: SamplingRequestBuilder) -> Result<CreateMessageResult> {
+        let params = request.build()?;
+        let sampling = self.sampling.as_ref().ok_or_else(|| {
+            Error::protocol(
+                ErrorCode::UNSUPPORTED_CAPABILITY,
+                "Sampling is not available: the client did not declare the sampling capability",
+            )
+        })?;
+
+        tokio::select! {
+            result = sampling.create_message(params) => result,
+            () = self.cancellation_token.cancelled() => Err(Error::cancelled()),
+        }
+    }
+
+    /// Set the feature flags evaluated for this request.
+    pub fn with_flags(mut self, flags: Flags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Feature flags evaluated for the caller of this request.
+    ///
+    /// Evaluated before the handler runs by the provider registered with
+    /// `feature_flags` on the server builder; empty when there is none. See
+    /// [`flags`](crate::server::flags).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// if extra.flags().is_enabled("search-v2") {
+    ///     return search_v2(&input).await;
+    /// }
+    /// let page_size = extra.flags().get_as::<usize>("page-size")?.unwrap_or(20);
+    /// ```
+    pub fn flags(&self) -> &Flags {
+        &self.flags
+    }
+
+    /// Attach the server's typed state.
+    pub fn with_extensions(mut self, extensions: Extensions) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// The value of type `T` registered with `with_state` on the server
+    /// builder, or inserted into [`extensions`](Self::extensions) by
+    /// middleware.
+    ///
+    /// # Errors
+    ///
+    /// Returns an internal error if no value of type `T` is available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let pool = extra.state::<PgPool>()?;
+    /// let row = sqlx::query("SELECT 1").fetch_one(pool).await?;
+    /// ```
+    pub fn state<T: Send + Sync + 'static>(&self) -> Result<&T> {
+        self.extensions.get::<T>().ok_or_else(|| {
+            crate::Error::internal(format!(
+                "no state of type `{}` is registered on this server",
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
     /// Returns `true` if the client requested task-augmented behavior.
     pub fn is_task_request(&self) -> bool {
         self.task_request.is_some()
@@ -225,6 +589,87 @@ impl RequestHandlerExtra {
         self.metadata.insert(key, value);
     }
 
+    /// The trace context of this request, for correlating handler logs with
+    /// observability events.
+    ///
+    /// Available when the observability middleware is enabled or the request
+    /// arrived with a W3C `traceparent` header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// if let Some(trace) = extra.trace_context() {
+    ///     tracing::info!(trace_id = %trace.trace_id, span_id = %trace.span_id, "looking up order");
+    /// }
+    /// ```
+    pub fn trace_context(&self) -> Option<crate::server::observability::TraceContext> {
+        crate::server::observability::tool_trace(self)
+            .or_else(crate::server::observability::TraceContext::current)
+    }
+
+    /// Session key used for widget state.
+    ///
+    /// This is the transport session ID when one is set, otherwise the
+    /// subject of an authenticated caller. Returns `None` for anonymous
+    /// sessionless requests, which have no stable identity to key state by.
+    pub fn widget_state_session(&self) -> Option<&str> {
+        self.session_id.as_deref().or_else(|| {
+            self.auth_context
+                .as_ref()
+                .filter(|ctx| ctx.authenticated)
+                .map(|ctx| ctx.subject.as_str())
+        })
+    }
+
+    /// Load the state saved for `widget_uri` in the current session.
+    ///
+    /// # Errors
+    ///
+    /// Fails if no widget state store is configured, the request has no
+    /// session key (see [`widget_state_session`](Self::widget_state_session)),
+    /// or the store itself fails.
+    pub async fn load_widget_state(&self, widget_uri: &str) -> Result<Option<Value>> {
+        let (store, session_id) = self.widget_state_scope()?;
+        store.load(session_id, widget_uri).await
+    }
+
+    /// Save the state for `widget_uri` in the current session.
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as
+    /// [`load_widget_state`](Self::load_widget_state), or if the store
+    /// rejects the state (for example because it is too large).
+    pub async fn save_widget_state(&self, widget_uri: &str, state: Value) -> Result<()> {
+        let (store, session_id) = self.widget_state_scope()?;
+        store.save(session_id, widget_uri, state).await
+    }
+
+    /// Remove the state for `widget_uri` in the current session.
+    ///
+    /// Returns `true` if state existed and was removed.
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as
+    /// [`load_widget_state`](Self::load_widget_state).
+    pub async fn clear_widget_state(&self, widget_uri: &str) -> Result<bool> {
+        let (store, session_id) = self.widget_state_scope()?;
+        store.clear(session_id, widget_uri).await
+    }
+
+    fn widget_state_scope(&self) -> Result<(&Arc<dyn WidgetStateStore>, &str)> {
+        let store = self.widget_state.as_ref().ok_or_else(|| {
+            crate::Error::internal("No widget state store configured on this server")
+        })?;
+        let session_id = self.widget_state_session().ok_or_else(|| {
+            crate::Error::validation(
+                "Widget state requires a session ID or an authenticated caller",
+            )
+        })?;
+        Ok((store, session_id))
+    }
+
     /// Check if the request has been cancelled.
     pub fn is_cancelled(&self) -> bool {
         self.cancellation_token.is_cancelled()
@@ -271,6 +716,29 @@ impl RequestHandlerExtra {
             Ok(())
         }
     }
+
+    /// Stream a piece of partial output to the client, if it asked for progress.
+    ///
+    /// Chunks are sent as progress notifications carrying the chunk text as
+    /// their message, so long-running tools can show output as it is
+    /// produced. Chunks are dropped when the request has no progress token,
+    /// so the tool's final result should still contain the complete output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// for row in rows {
+    ///     extra.stream_chunk(format!("{row}\n")).await?;
+    ///     output.push(row);
+    /// }
+    /// ```
+    pub async fn stream_chunk(&self, chunk: impl Into<String>) -> crate::Result<()> {
+        if let Some(rep) = &self.progress_reporter {
+            rep.stream_chunk(chunk.into()).await
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Default for RequestHandlerExtra {
@@ -289,6 +757,15 @@ impl Default for RequestHandlerExtra {
             metadata: HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            #[cfg(feature = "sampling")]
+            sampling: None,
+            flags: Flags::new(),
+            extensions: Extensions::new(),
         }
     }
 }
@@ -323,7 +800,8 @@ impl std::fmt::Debug for RequestHandlerExtra {
             })
             .collect();
 
-        f.debug_struct("RequestHandlerExtra")
+        let mut debug = f.debug_struct("RequestHandlerExtra");
+        debug
             .field("cancellation_token", &self.cancellation_token)
             .field("request_id", &self.request_id)
             .field("session_id", &self.session_id)
@@ -331,6 +809,16 @@ impl std::fmt::Debug for RequestHandlerExtra {
             .field("auth_context", &self.auth_context)
             .field("metadata", &redacted_metadata)
             .field("task_request", &self.task_request.is_some())
+            .field("task_id", &self.task_id)
+            .field("widget_state", &self.widget_state.is_some())
+            .field("kv_store", &self.kv_store.is_some())
+            .field("elicitation", &self.elicitation.is_some())
+            .field("roots", &self.roots.is_some());
+        #[cfg(feature = "sampling")]
+        debug.field("sampling", &self.sampling.is_some());
+        debug
+            .field("flags", &self.flags)
+            .field("extensions", &self.extensions)
             .finish()
     }
 }
@@ -471,6 +959,37 @@ mod tests {
         assert!(extra.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_cancelled_elicitation_is_withdrawn() {
+        use crate::server::elicitation::{ElicitationContext, ElicitationManager};
+
+        let (request_tx, mut request_rx) = tokio::sync::mpsc::channel(10);
+        let (notification_tx, mut notification_rx) = tokio::sync::mpsc::channel(10);
+        let mut manager = ElicitationManager::new();
+        manager.set_request_channel(request_tx);
+        manager.set_notification_channel(notification_tx);
+        let manager = Arc::new(manager);
+
+        let token = CancellationToken::new();
+        let extra = RequestHandlerExtra::new("test-req".to_string(), token.clone())
+            .with_elicitation(Some(Arc::new(ElicitationContext::new(manager.clone()))));
+
+        let elicit = extra.elicit(serde_json::json!({"type": "object"}), "Continue?");
+        let cancel = async {
+            let (request_id, _) = request_rx.recv().await.unwrap();
+            token.cancel();
+            request_id
+        };
+        let (result, request_id) = tokio::join!(elicit, cancel);
+        assert!(matches!(result, Err(Error::Cancelled)));
+
+        match notification_rx.recv().await.unwrap() {
+            Notification::Cancelled(cancelled) => assert_eq!(cancelled.request_id, request_id),
+            other => panic!("expected a cancelled notification, got {other:?}"),
+        }
+        assert_eq!(manager.pending_count().await, 0);
+    }
+
     #[tokio::test]
     async fn test_metadata_redaction_in_debug() {
         let token = CancellationToken::new();
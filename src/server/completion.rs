@@ -0,0 +1,279 @@
+//! Argument autocompletion (`completion/complete`).
+//!
+//! A [`CompletionProvider`] supplies suggestions for one argument of a prompt
+//! or one variable of a resource template. Providers are registered on the
+//! server builder with `prompt_completion` and `resource_completion`, which
+//! also advertise the `completions` capability so clients such as Claude
+//! Desktop offer suggestions as the user types.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::completion::StaticCompletions;
+//! use pmcp::Server;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("deploy")
+//!     .version("1.0.0")
+//!     .prompt_completion(
+//!         "deploy",
+//!         "environment",
+//!         StaticCompletions::new(["development", "staging", "production"]),
+//!     )
+//!     .resource_completion("repo://{owner}/{name}", "owner", |value: &str| {
+//!         vec![format!("{value}-org")]
+//!     })
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::types::{CompleteRequest, CompleteResult, CompletionReference, CompletionResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Most values a `completion/complete` response may carry, per the MCP spec.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Supplies completion suggestions for a single argument.
+#[async_trait]
+pub trait CompletionProvider: Send + Sync {
+    /// Suggest values for the argument given its partial `value`.
+    ///
+    /// `arguments` holds values the client has already resolved for the other
+    /// arguments of the same prompt or template. Results longer than
+    /// [`MAX_COMPLETION_VALUES`] are truncated and marked `has_more`.
+    async fn complete(
+        &self,
+        value: &str,
+        arguments: &HashMap<String, String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<Vec<String>>;
+}
+
+#[async_trait]
+impl<F> CompletionProvider for F
+where
+    F: Fn(&str) -> Vec<String> + Send + Sync,
+{
+    async fn complete(
+        &self,
+        value: &str,
+        _arguments: &HashMap<String, String>,
+        _extra: RequestHandlerExtra,
+    ) -> Result<Vec<String>> {
+        Ok(self(value))
+    }
+}
+
+/// Completes from a fixed list of values, matching by case-insensitive prefix.
+#[derive(Debug, Clone)]
+pub struct StaticCompletions {
+    values: Vec<String>,
+}
+
+impl StaticCompletions {
+    /// Create a provider suggesting `values` in the given order.
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for StaticCompletions {
+    async fn complete(
+        &self,
+        value: &str,
+        _arguments: &HashMap<String, String>,
+        _extra: RequestHandlerExtra,
+    ) -> Result<Vec<String>> {
+        let prefix = value.to_lowercase();
+        Ok(self
+            .values
+            .iter()
+            .filter(|v| v.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+/// What a completion provider is registered against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CompletionTarget {
+    Prompt(String),
+    ResourceTemplate(String),
+}
+
+impl From<&CompletionReference> for CompletionTarget {
+    fn from(reference: &CompletionReference) -> Self {
+        match reference {
+            CompletionReference::Prompt { name } => Self::Prompt(name.clone()),
+            CompletionReference::Resource { uri } => Self::ResourceTemplate(uri.clone()),
+        }
+    }
+}
+
+/// Completion providers keyed by prompt or resource template and argument.
+#[derive(Clone, Default)]
+pub(crate) struct CompletionRegistry {
+    providers: HashMap<(CompletionTarget, String), Arc<dyn CompletionProvider>>,
+}
+
+impl std::fmt::Debug for CompletionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompletionRegistry")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+impl CompletionRegistry {
+    pub(crate) fn add_prompt(
+        &mut self,
+        prompt: impl Into<String>,
+        argument: impl Into<String>,
+        provider: Arc<dyn CompletionProvider>,
+    ) {
+        self.providers.insert(
+            (CompletionTarget::Prompt(prompt.into()), argument.into()),
+            provider,
+        );
+    }
+
+    pub(crate) fn add_resource_template(
+        &mut self,
+        uri_template: impl Into<String>,
+        variable: impl Into<String>,
+        provider: Arc<dyn CompletionProvider>,
+    ) {
+        self.providers.insert(
+            (
+                CompletionTarget::ResourceTemplate(uri_template.into()),
+                variable.into(),
+            ),
+            provider,
+        );
+    }
+
+    /// Answer a `completion/complete` request.
+    ///
+    /// Arguments without a registered provider get an empty result rather
+    /// than an error, so clients can ask about any argument.
+    pub(crate) async fn complete(
+        &self,
+        request: &CompleteRequest,
+        extra: RequestHandlerExtra,
+    ) -> Result<CompleteResult> {
+        let key = (
+            CompletionTarget::from(&request.r#ref),
+            request.argument.name.clone(),
+        );
+        let Some(provider) = self.providers.get(&key) else {
+            return Ok(CompleteResult::new(CompletionResult::default()));
+        };
+
+        let arguments = request
+            .context
+            .as_ref()
+            .map(|c| c.arguments.clone())
+            .unwrap_or_default();
+        let mut values = provider
+            .complete(&request.argument.value, &arguments, extra)
+            .await?;
+
+        let total = values.len();
+        values.truncate(MAX_COMPLETION_VALUES);
+        Ok(CompleteResult::new(
+            CompletionResult::new(values)
+                .with_total(total)
+                .with_has_more(total > MAX_COMPLETION_VALUES),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CompletionArgument;
+    use tokio_util::sync::CancellationToken;
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    fn request(reference: CompletionReference, name: &str, value: &str) -> CompleteRequest {
+        CompleteRequest {
+            r#ref: reference,
+            argument: CompletionArgument {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+            context: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prompt_argument_completion() {
+        let mut registry = CompletionRegistry::default();
+        registry.add_prompt(
+            "deploy",
+            "environment",
+            Arc::new(StaticCompletions::new(["development", "staging", "demo"])),
+        );
+
+        let reference = CompletionReference::Prompt {
+            name: "deploy".to_string(),
+        };
+        let result = registry
+            .complete(&request(reference, "environment", "De"), extra())
+            .await
+            .unwrap();
+        assert_eq!(result.completion.values, vec!["development", "demo"]);
+        assert_eq!(result.completion.total, Some(2));
+        assert!(!result.completion.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_argument_is_empty() {
+        let registry = CompletionRegistry::default();
+        let reference = CompletionReference::Resource {
+            uri: "repo://{owner}".to_string(),
+        };
+        let result = registry
+            .complete(&request(reference, "owner", "a"), extra())
+            .await
+            .unwrap();
+        assert!(result.completion.values.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_results_are_capped() {
+        let mut registry = CompletionRegistry::default();
+        registry.add_resource_template(
+            "item://{id}",
+            "id",
+            Arc::new(|_: &str| (0..150).map(|i| i.to_string()).collect::<Vec<_>>()),
+        );
+
+        let reference = CompletionReference::Resource {
+            uri: "item://{id}".to_string(),
+        };
+        let result = registry
+            .complete(&request(reference, "id", ""), extra())
+            .await
+            .unwrap();
+        assert_eq!(result.completion.values.len(), MAX_COMPLETION_VALUES);
+        assert_eq!(result.completion.total, Some(150));
+        assert!(result.completion.has_more);
+    }
+}
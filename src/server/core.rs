@@ -14,7 +14,7 @@ use crate::types::{
     JSONRPCResponse, ListPromptsRequest, ListPromptsResult, ListResourceTemplatesRequest,
     ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
     ListToolsResult, Notification, PromptInfo, ProtocolVersion, ReadResourceRequest,
-    ReadResourceResult, Request, RequestId, ServerCapabilities, ToolInfo,
+    ReadResourceResult, Request, RequestId, ServerCapabilities, ServerNotification, ToolInfo,
 };
 use async_trait::async_trait;
 use serde_json::Value;
@@ -24,21 +24,22 @@ use std::sync::Arc;
 use crate::runtime::RwLock;
 
 #[cfg(not(target_arch = "wasm32"))]
-use super::auth::{AuthContext, AuthProvider, ToolAuthorizer};
+use super::auth::{AuthContext, AuthProvider, PromptAuthorizer, ToolAuthorizer};
 #[cfg(not(target_arch = "wasm32"))]
-use super::cancellation::{CancellationManager, RequestHandlerExtra};
+use super::cancellation::{request_session, CancellationManager, RequestHandlerExtra};
+use super::event_bus::{EventBus, ServerEvent};
 #[cfg(not(target_arch = "wasm32"))]
 use super::roots::RootsManager;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
 use super::subscriptions::SubscriptionManager;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
 use super::tasks::TaskRouter;
 #[cfg(not(target_arch = "wasm32"))]
 use super::tool_middleware::{ToolContext, ToolMiddlewareChain};
 use super::{PromptHandler, ResourceHandler, SamplingHandler, ToolHandler};
-#[cfg(not(target_arch = "wasm32"))]
-use crate::types::tasks::RELATED_TASK_META_KEY;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+use crate::types::tasks::{TaskStatus, RELATED_TASK_META_KEY};
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
 use crate::types::tools::TaskSupport;
 
 /// Protocol-agnostic request handler trait.
@@ -84,6 +85,15 @@ pub trait ProtocolHandler: Send + Sync {
     ///
     /// Returns metadata about the server implementation.
     fn info(&self) -> &Implementation;
+
+    /// Subscribe to notifications the server sends without a client request.
+    ///
+    /// Transport adapters call this once per connection and forward every
+    /// notification received to the client. Returns `None` (the default)
+    /// if the handler never pushes notifications.
+    fn notifications(&self) -> Option<tokio::sync::broadcast::Receiver<ServerNotification>> {
+        None
+    }
 }
 
 /// Protocol handler trait for WASM environments (single-threaded).
@@ -103,6 +113,45 @@ pub trait ProtocolHandler {
     fn info(&self) -> &Implementation;
 }
 
+/// Point each linked tool's `_meta` at its widget.
+///
+/// `links` holds `(tool, widget URI)` pairs from `tool_with_widget`. Adds the
+/// standard `ui.resourceUri` and legacy `ui/resourceUri` keys; host layers
+/// derive their own keys from these afterwards.
+///
+/// # Errors
+///
+/// Fails if a widget URI is not a `ui://` URI, or no resource handler is
+/// registered to serve the widget.
+pub(crate) fn link_tool_widgets(
+    tool_infos: &mut HashMap<String, ToolInfo>,
+    links: &[(String, String)],
+    has_resources: bool,
+) -> Result<()> {
+    for (tool, uri) in links {
+        if !uri.starts_with("ui://") {
+            return Err(Error::validation(format!(
+                "Tool '{}' links widget '{}', which does not start with 'ui://'",
+                tool, uri
+            )));
+        }
+        if !has_resources {
+            return Err(Error::validation(format!(
+                "Tool '{}' links widget '{}', but no resource handler is registered to serve it",
+                tool, uri
+            )));
+        }
+        if let Some(info) = tool_infos.get_mut(tool) {
+            let meta = info._meta.get_or_insert_with(serde_json::Map::new);
+            crate::types::ui::deep_merge(
+                meta,
+                crate::types::ui::ToolUIMetadata::build_meta_map(uri),
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Enrich a tool's `_meta` with host-specific keys.
 ///
 /// Reads the standard `ui.resourceUri` and adds host-specific aliases.
@@ -237,6 +286,7 @@ pub struct ServerCore {
     roots_manager: Arc<RwLock<RootsManager>>,
 
     /// Subscription manager for resource subscriptions
+    #[cfg(feature = "resources")]
     subscription_manager: Arc<RwLock<SubscriptionManager>>,
 
     /// Authentication provider (optional)
@@ -245,6 +295,9 @@ pub struct ServerCore {
     /// Tool authorizer for fine-grained access control (optional)
     tool_authorizer: Option<Arc<dyn ToolAuthorizer>>,
 
+    /// Prompt authorizer for per-prompt and per-namespace access (optional)
+    prompt_authorizer: Option<Arc<dyn PromptAuthorizer>>,
+
     /// Protocol middleware chain for request/response/notification processing
     protocol_middleware: Arc<RwLock<EnhancedMiddlewareChain>>,
 
@@ -253,13 +306,20 @@ pub struct ServerCore {
     tool_middleware: Arc<RwLock<ToolMiddlewareChain>>,
 
     /// Task router for experimental MCP Tasks support (optional)
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     task_router: Option<Arc<dyn TaskRouter>>,
 
     /// Task store for MCP Tasks with polling (standard capability path)
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     task_store: Option<Arc<dyn crate::server::task_store::TaskStore>>,
 
+    /// Runs cron-scheduled workflows (set by the builder)
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    scheduler: Option<crate::server::scheduler::WorkflowScheduler>,
+
+    /// Subsystems switched off with `ServerCoreBuilder::disable`
+    disabled: crate::server::subsystem::DisabledSubsystems,
+
     /// Stateless mode flag for serverless deployments
     ///
     /// When true, the server skips initialization state checking, allowing
@@ -270,6 +330,42 @@ pub struct ServerCore {
     ///
     /// Default: false (maintains backward compatibility)
     stateless_mode: bool,
+
+    /// Event bus for session, tool, resource, and task events
+    events: EventBus,
+
+    /// Shared definitions referenced from tool schemas (sent with `tools/list`)
+    schema_definitions: Option<serde_json::Map<String, Value>>,
+
+    /// Workflows, secrets, and backends checked by `run_self_test`
+    self_test: crate::server::self_test::SelfTestPlan,
+
+    /// Widget state store handed to tool calls and resource reads
+    widget_state: Option<Arc<dyn crate::server::widget_state::WidgetStateStore>>,
+
+    /// Key-value store handed to tool calls and resource reads
+    kv_store: Option<Arc<dyn crate::storage::KvStore>>,
+
+    /// Feature flags evaluated before each tool call
+    flag_provider: Option<Arc<dyn crate::server::flags::FlagProvider>>,
+
+    /// Deadlines for tool handlers
+    #[cfg(not(target_arch = "wasm32"))]
+    tool_timeouts: crate::server::tool_timeout::ToolTimeouts,
+
+    /// Pages for `tools/list`, when the builder set a page size
+    #[cfg(not(target_arch = "wasm32"))]
+    tool_pages: Option<crate::server::pagination::ListPages>,
+
+    /// Typed state handed to tool calls, prompts, resources and completions
+    state: crate::server::extensions::Extensions,
+
+    /// Change sources for resource subscriptions
+    #[cfg(feature = "resources")]
+    resource_watchers: Vec<Arc<dyn crate::server::subscriptions::ResourceWatcher>>,
+
+    /// Argument completion providers for `completion/complete`
+    completions: crate::server::completion::CompletionRegistry,
 }
 
 /// Outcome of a tool handler call — either a normal result or a task creation.
@@ -277,10 +373,21 @@ enum ToolCallOutcome {
     /// Standard tool result wrapped as `CallToolResult`
     Result(CallToolResult),
     /// Tool returned a Task-shaped value — returned as `CreateTaskResult` with `_meta`
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     TaskCreated { task_id: String, task_value: Value },
 }
 
+impl ToolCallOutcome {
+    /// Whether the tool reported a failure in its result.
+    fn is_error(&self) -> bool {
+        match self {
+            Self::Result(result) => result.is_error,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+            Self::TaskCreated { .. } => false,
+        }
+    }
+}
+
 impl ServerCore {
     /// Create a new `ServerCore` with the given configuration.
     #[allow(clippy::too_many_arguments)]
@@ -297,8 +404,10 @@ impl ServerCore {
         tool_authorizer: Option<Arc<dyn ToolAuthorizer>>,
         protocol_middleware: Arc<RwLock<EnhancedMiddlewareChain>>,
         #[cfg(not(target_arch = "wasm32"))] tool_middleware: Arc<RwLock<ToolMiddlewareChain>>,
-        #[cfg(not(target_arch = "wasm32"))] task_router: Option<Arc<dyn TaskRouter>>,
-        #[cfg(not(target_arch = "wasm32"))] task_store: Option<
+        #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))] task_router: Option<
+            Arc<dyn TaskRouter>,
+        >,
+        #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))] task_store: Option<
             Arc<dyn crate::server::task_store::TaskStore>,
         >,
         stateless_mode: bool,
@@ -318,20 +427,256 @@ impl ServerCore {
             initialized: Arc::new(RwLock::new(false)),
             cancellation_manager: CancellationManager::new(),
             roots_manager: Arc::new(RwLock::new(RootsManager::new())),
+            #[cfg(feature = "resources")]
             subscription_manager: Arc::new(RwLock::new(SubscriptionManager::new())),
             auth_provider,
             tool_authorizer,
+            prompt_authorizer: None,
             protocol_middleware,
             #[cfg(not(target_arch = "wasm32"))]
             tool_middleware,
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             task_router,
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
             task_store,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+            scheduler: None,
+            disabled: crate::server::subsystem::DisabledSubsystems::default(),
             stateless_mode,
+            events: EventBus::new(),
+            schema_definitions: None,
+            self_test: crate::server::self_test::SelfTestPlan::default(),
+            widget_state: None,
+            kv_store: None,
+            flag_provider: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            tool_timeouts: crate::server::tool_timeout::ToolTimeouts::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            tool_pages: None,
+            state: crate::server::extensions::Extensions::new(),
+            #[cfg(feature = "resources")]
+            resource_watchers: Vec::new(),
+            completions: crate::server::completion::CompletionRegistry::default(),
         }
     }
 
+    /// Replace the event bus, sharing listeners registered on the builder.
+    pub(crate) fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Replace tool subschemas that match a shared definition with `$ref`s,
+    /// and publish the definitions with `tools/list`.
+    pub(crate) fn with_schema_registry(
+        mut self,
+        registry: &crate::server::schema_registry::SchemaRegistry,
+    ) -> Self {
+        if registry.is_empty() {
+            return self;
+        }
+        for info in self.tool_infos.values_mut() {
+            info.input_schema = registry.compact(&info.input_schema);
+            if let Some(output_schema) = info.output_schema.as_mut() {
+                *output_schema = registry.compact(output_schema);
+            }
+        }
+        self.schema_definitions = Some(registry.definitions());
+        self
+    }
+
+    /// Set the workflows, secrets, and backends checked by `run_self_test`.
+    pub(crate) fn with_self_test(mut self, plan: crate::server::self_test::SelfTestPlan) -> Self {
+        self.self_test = plan;
+        self
+    }
+
+    /// Set the widget state store exposed to handlers via `RequestHandlerExtra`.
+    pub(crate) fn with_widget_state(
+        mut self,
+        store: Option<Arc<dyn crate::server::widget_state::WidgetStateStore>>,
+    ) -> Self {
+        self.widget_state = store;
+        self
+    }
+
+    /// Set the key-value store exposed to handlers via `RequestHandlerExtra::kv`.
+    pub(crate) fn with_kv_store(mut self, store: Option<Arc<dyn crate::storage::KvStore>>) -> Self {
+        self.kv_store = store;
+        self
+    }
+
+    /// Set the feature flag provider evaluated before each tool call.
+    pub(crate) fn with_flag_provider(
+        mut self,
+        provider: Option<Arc<dyn crate::server::flags::FlagProvider>>,
+    ) -> Self {
+        self.flag_provider = provider;
+        self
+    }
+
+    /// Set the deadlines for tool handlers.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_tool_timeouts(
+        mut self,
+        timeouts: crate::server::tool_timeout::ToolTimeouts,
+    ) -> Self {
+        self.tool_timeouts = timeouts;
+        self
+    }
+
+    /// Split `tools/list` into pages.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_tool_pages(
+        mut self,
+        pages: Option<crate::server::pagination::ListPages>,
+    ) -> Self {
+        self.tool_pages = pages;
+        self
+    }
+
+    /// Set the typed state exposed to handlers via `RequestHandlerExtra::state`.
+    pub(crate) fn with_state(mut self, state: crate::server::extensions::Extensions) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Set the resource watchers registered with
+    /// `ServerCoreBuilder::resource_watcher`.
+    #[cfg(feature = "resources")]
+    pub(crate) fn with_resource_watchers(
+        mut self,
+        watchers: Vec<Arc<dyn crate::server::subscriptions::ResourceWatcher>>,
+    ) -> Self {
+        self.resource_watchers = watchers;
+        self
+    }
+
+    /// Set the completion providers registered on the builder.
+    pub(crate) fn with_completions(
+        mut self,
+        completions: crate::server::completion::CompletionRegistry,
+    ) -> Self {
+        self.completions = completions;
+        self
+    }
+
+    /// Set the subsystems switched off on the builder.
+    pub(crate) fn with_disabled_subsystems(
+        mut self,
+        disabled: crate::server::subsystem::DisabledSubsystems,
+    ) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set the prompt authorizer registered on the builder.
+    pub(crate) fn with_prompt_authorizer(
+        mut self,
+        authorizer: Option<Arc<dyn PromptAuthorizer>>,
+    ) -> Self {
+        self.prompt_authorizer = authorizer;
+        self
+    }
+
+    /// Whether the caller may see and get `prompt_name`.
+    ///
+    /// As with tools, the authorizer only applies to authenticated requests.
+    async fn can_access_prompt(
+        &self,
+        auth_context: Option<&AuthContext>,
+        prompt_name: &str,
+    ) -> Result<bool> {
+        match (&self.prompt_authorizer, auth_context) {
+            (Some(authorizer), Some(auth_ctx)) => {
+                authorizer.can_access_prompt(auth_ctx, prompt_name).await
+            },
+            _ => Ok(true),
+        }
+    }
+
+    /// Start the registered resource watchers.
+    ///
+    /// `sender` receives a `notifications/resources/updated` notification for
+    /// every change to a resource that a client has subscribed to; forward
+    /// these to the transport. Call once, after connecting the transport.
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
+    pub async fn start_resource_watchers<F>(&self, sender: F) -> Result<()>
+    where
+        F: Fn(ServerNotification) + Send + Sync + 'static,
+    {
+        self.subscription_manager
+            .write()
+            .await
+            .set_notification_sender(sender);
+        crate::server::subscriptions::start_watchers(
+            &self.resource_watchers,
+            self.subscription_manager.clone(),
+        )
+        .await
+    }
+
+    /// Notify subscribers that a resource has changed.
+    ///
+    /// Returns the number of subscribers notified. Notifications are only
+    /// delivered after [`start_resource_watchers`](Self::start_resource_watchers)
+    /// has installed a sender.
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
+    pub async fn notify_resource_updated(&self, uri: impl Into<String>) -> Result<usize> {
+        self.subscription_manager
+            .read()
+            .await
+            .notify_resource_updated(uri.into())
+            .await
+    }
+
+    /// Check that the server is ready to serve traffic.
+    ///
+    /// Compiles every tool schema, reads every listed resource, re-validates
+    /// workflows, probes the task store, and checks the secrets and backends
+    /// declared with `ServerCoreBuilder::self_test_secret` and
+    /// `ServerCoreBuilder::self_test_backend`. See
+    /// [`self_test`](crate::server::self_test) for details.
+    pub async fn run_self_test(&self) -> crate::server::self_test::SelfTestReport {
+        self.self_test
+            .run(crate::server::self_test::SelfTestTarget {
+                tools: &self.tool_infos,
+                definitions: self.schema_definitions.as_ref(),
+                resources: self.resources.as_ref(),
+                #[cfg(feature = "tasks")]
+                task_store: self.task_store.as_ref(),
+            })
+            .await
+    }
+
+    /// Get the server's event bus for subscribing to [`ServerEvent`]s.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Attach the scheduler for workflows registered with
+    /// `ServerCoreBuilder::scheduled_workflow`.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    pub(crate) fn with_scheduler(
+        mut self,
+        scheduler: Option<crate::server::scheduler::WorkflowScheduler>,
+    ) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Get the scheduler for cron-scheduled workflows, if any are registered.
+    ///
+    /// Call [`spawn`](crate::server::scheduler::WorkflowScheduler::spawn) on
+    /// it to start running the schedules.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
+    pub fn scheduler(&self) -> Option<&crate::server::scheduler::WorkflowScheduler> {
+        self.scheduler.as_ref()
+    }
+
     /// Check if the server is initialized.
     pub async fn is_initialized(&self) -> bool {
         *self.initialized.read().await
@@ -350,6 +695,12 @@ impl ServerCore {
 
         let negotiated_version = crate::negotiate_protocol_version(&init_req.protocol_version);
 
+        self.events.emit(ServerEvent::SessionStarted {
+            session_id: request_session(),
+            client: init_req.client_info.clone(),
+            protocol_version: negotiated_version.to_string(),
+        });
+
         Ok(InitializeResult {
             protocol_version: ProtocolVersion(negotiated_version.to_string()),
             capabilities: self.capabilities.clone(),
@@ -359,15 +710,44 @@ impl ServerCore {
     }
 
     /// Handle list tools request.
-    async fn handle_list_tools(&self, _req: &ListToolsRequest) -> Result<ListToolsResult> {
+    async fn handle_list_tools(&self, req: &ListToolsRequest) -> Result<ListToolsResult> {
         let tools: Vec<ToolInfo> = self.tool_infos.values().cloned().collect();
+        let (tools, next_cursor) = self.tool_page(tools, req.cursor.as_deref())?;
 
         Ok(ListToolsResult {
             tools,
-            next_cursor: None,
+            next_cursor,
+            definitions: self.schema_definitions.clone(),
         })
     }
 
+    /// The `tools/list` page `cursor` points at, and the next page's cursor.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn tool_page(
+        &self,
+        mut tools: Vec<ToolInfo>,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<ToolInfo>, Option<String>)> {
+        let Some(pages) = &self.tool_pages else {
+            return Ok((tools, None));
+        };
+        // Pages need the same order on every call
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        let page = pages.page(tools, cursor)?;
+        Ok((page.items, page.next_cursor))
+    }
+
+    /// WASM servers list all tools in one page.
+    #[cfg(target_arch = "wasm32")]
+    #[allow(clippy::unnecessary_wraps)]
+    fn tool_page(
+        &self,
+        tools: Vec<ToolInfo>,
+        _cursor: Option<&str>,
+    ) -> Result<(Vec<ToolInfo>, Option<String>)> {
+        Ok((tools, None))
+    }
+
     /// Handle call tool request.
     async fn handle_call_tool(
         &self,
@@ -393,58 +773,32 @@ impl ServerCore {
 
         // Create request handler extra data with auth_context and task request
         let request_id = format!("tool_{}", req.name);
-        let mut extra = RequestHandlerExtra::new(
+        let extra = RequestHandlerExtra::new(
             request_id.clone(),
             self.cancellation_manager
                 .create_token(request_id.clone())
                 .await,
         )
+        .with_session_id(request_session())
         .with_auth_context(auth_context)
-        .with_task_request(req.task.clone());
+        .with_task_request(req.task.clone())
+        .with_widget_state(self.widget_state.clone())
+        .with_kv_store(self.kv_store.clone())
+        .with_extensions(self.state.clone());
+        let extra = match &self.flag_provider {
+            Some(provider) => {
+                let flags = crate::server::flags::evaluate_for(provider.as_ref(), &extra).await;
+                extra.with_flags(flags)
+            },
+            None => extra,
+        };
+        let widget_flags = extra.flags.clone();
 
         // Execute tool with or without middleware depending on platform
         #[cfg(not(target_arch = "wasm32"))]
-        let result = {
-            // Create tool context for middleware
-            let context = ToolContext::new(&req.name, &request_id);
-
-            // Clone arguments for middleware processing
-            let mut args = req.arguments.clone();
-
-            // Process request through tool middleware chain
-            // Middleware rejection short-circuits tool execution (on_error already called by chain)
-            self.tool_middleware
-                .read()
-                .await
-                .process_request(&req.name, &mut args, &mut extra, &context)
-                .await?;
-
-            // Execute the tool with potentially modified args and extra
-            let mut result = handler.handle(args, extra).await;
-
-            // Process response through tool middleware chain
-            if let Err(e) = self
-                .tool_middleware
-                .read()
-                .await
-                .process_response(&req.name, &mut result, &context)
-                .await
-            {
-                // Log error but continue with original result
-                tracing::warn!("Tool response middleware processing failed: {}", e);
-            }
-
-            // If tool execution failed, call handle_tool_error
-            if let Err(ref e) = result {
-                self.tool_middleware
-                    .read()
-                    .await
-                    .handle_tool_error(&req.name, e, &context)
-                    .await;
-            }
-
-            result
-        };
+        let result = self
+            .run_tool(&req.name, handler.as_ref(), req.arguments.clone(), extra)
+            .await;
 
         #[cfg(target_arch = "wasm32")]
         let result = {
@@ -464,7 +818,7 @@ impl ServerCore {
         // 4. Tool returned a Task-shaped Value (has taskId + status)
         // When the client doesn't send `task`, fall through to CallToolResult
         // so non-task-aware clients (like ChatGPT) get normal tool output.
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
         {
             let tool_task_support = tool_info
                 .as_ref()
@@ -507,18 +861,36 @@ impl ServerCore {
             // Widget tool: structured data goes in structuredContent,
             // text is a brief summary to avoid duplication in `ChatGPT`
             let summary = summarize_structured_output(&value);
-            CallToolResult::new(vec![Content::text(summary)]).with_widget_enrichment(info, value)
+            let mut call_result = CallToolResult::new(vec![Content::text(summary)])
+                .with_widget_enrichment(info, value);
+            crate::server::flags::attach_to_result(&mut call_result, &widget_flags);
+            call_result
         } else {
             let text = serde_json::to_string_pretty(&value)?;
-            CallToolResult::new(vec![Content::text(text)])
+            let call_result = CallToolResult::new(vec![Content::text(text)]);
+            match tool_info {
+                Some(info) => call_result.with_structured_output(info, value),
+                None => call_result,
+            }
         };
 
         Ok(ToolCallOutcome::Result(call_result))
     }
 
     /// Handle list prompts request.
-    async fn handle_list_prompts(&self, _req: &ListPromptsRequest) -> Result<ListPromptsResult> {
-        let prompts: Vec<PromptInfo> = self.prompt_infos.values().cloned().collect();
+    async fn handle_list_prompts(
+        &self,
+        req: &ListPromptsRequest,
+        auth_context: Option<&AuthContext>,
+    ) -> Result<ListPromptsResult> {
+        let mut prompts = Vec::new();
+        for info in self.prompt_infos.values() {
+            if req.matches(&info.name) && self.can_access_prompt(auth_context, &info.name).await? {
+                prompts.push(info.clone());
+            }
+        }
+        // Sorting keeps each namespace's prompts together
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
 
         tracing::debug!(
             target: "mcp.prompts",
@@ -538,6 +910,18 @@ impl ServerCore {
         req: &GetPromptRequest,
         auth_context: Option<AuthContext>,
     ) -> Result<GetPromptResult> {
+        // Authorize before the lookup so a denied caller cannot tell whether
+        // the prompt exists
+        if !self
+            .can_access_prompt(auth_context.as_ref(), &req.name)
+            .await?
+        {
+            return Err(Error::authentication(format!(
+                "User not authorized to get prompt '{}'",
+                req.name
+            )));
+        }
+
         let handler = self
             .prompts
             .get(&req.name)
@@ -551,7 +935,10 @@ impl ServerCore {
                 .create_token(request_id.clone())
                 .await,
         )
-        .with_auth_context(auth_context);
+        .with_session_id(request_session())
+        .with_auth_context(auth_context)
+        .with_task_id(req._meta.as_ref().and_then(|m| m._task_id.clone()))
+        .with_extensions(self.state.clone());
 
         handler.handle(req.arguments.clone(), extra).await
     }
@@ -571,6 +958,7 @@ impl ServerCore {
                         .create_token(request_id.clone())
                         .await,
                 )
+                .with_session_id(request_session())
                 .with_auth_context(auth_context);
                 handler.list(req.cursor.clone(), extra).await?
             },
@@ -613,7 +1001,11 @@ impl ServerCore {
                 .create_token(request_id.clone())
                 .await,
         )
-        .with_auth_context(auth_context);
+        .with_session_id(request_session())
+        .with_auth_context(auth_context)
+        .with_widget_state(self.widget_state.clone())
+        .with_kv_store(self.kv_store.clone())
+        .with_extensions(self.state.clone());
 
         let mut result = handler.read(&req.uri, extra).await?;
 
@@ -632,6 +1024,8 @@ impl ServerCore {
             }
         }
 
+        #[cfg(feature = "resources")]
+        let result = super::resource_range::apply(req, result)?;
         Ok(result)
     }
 
@@ -646,6 +1040,26 @@ impl ServerCore {
         })
     }
 
+    /// Run a tool through the middleware chain under its deadline.
+    ///
+    /// Shared by `tools/call` and workflow steps so both get the same
+    /// middleware, timeout, and [`ServerEvent::ToolTimedOut`] handling.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn run_tool(
+        &self,
+        name: &str,
+        handler: &dyn ToolHandler,
+        args: Value,
+        extra: RequestHandlerExtra,
+    ) -> Result<Value> {
+        let context = ToolContext::for_call(name, &extra);
+
+        // Middleware rejection short-circuits tool execution (on_error already called by chain)
+        let chain = self.tool_middleware.read().await.clone();
+        let handler = self.tool_timeouts.guard(name, &self.events, handler);
+        chain.execute(name, args, extra, &context, &handler).await
+    }
+
     /// Create an error response.
     fn error_response(id: RequestId, code: i32, message: String) -> JSONRPCResponse {
         JSONRPCResponse {
@@ -677,8 +1091,8 @@ impl crate::server::middleware_executor::MiddlewareExecutor for ServerCore {
     async fn execute_tool_with_middleware(
         &self,
         tool_name: &str,
-        mut args: Value,
-        mut extra: RequestHandlerExtra,
+        args: Value,
+        extra: RequestHandlerExtra,
     ) -> Result<Value> {
         // Get the tool handler
         let handler = self
@@ -698,42 +1112,8 @@ impl crate::server::middleware_executor::MiddlewareExecutor for ServerCore {
             }
         }
 
-        // Create tool context for middleware
-        let context = ToolContext::new(tool_name, &extra.request_id);
-
-        // Process request through tool middleware chain
-        // Middleware rejection short-circuits tool execution (on_error already called by chain)
-        self.tool_middleware
-            .read()
-            .await
-            .process_request(tool_name, &mut args, &mut extra, &context)
-            .await?;
-
-        // Execute the tool with potentially modified args and extra
-        let mut result = handler.handle(args, extra).await;
-
-        // Process response through tool middleware chain
-        if let Err(e) = self
-            .tool_middleware
-            .read()
+        self.run_tool(tool_name, handler.as_ref(), args, extra)
             .await
-            .process_response(tool_name, &mut result, &context)
-            .await
-        {
-            // Log error but continue with original result
-            tracing::warn!("Tool response middleware processing failed: {}", e);
-        }
-
-        // If tool execution failed, call handle_tool_error
-        if let Err(ref e) = result {
-            self.tool_middleware
-                .read()
-                .await
-                .handle_tool_error(tool_name, e, &context)
-                .await;
-        }
-
-        result
     }
 }
 
@@ -815,33 +1195,37 @@ impl ProtocolHandler for ServerCore {
     fn info(&self) -> &Implementation {
         &self.info
     }
+
+    /// The `notifications/tasks/status` of scheduled workflow runs.
+    fn notifications(&self) -> Option<tokio::sync::broadcast::Receiver<ServerNotification>> {
+        #[cfg(feature = "workflow")]
+        if let Some(scheduler) = &self.scheduler {
+            return Some(scheduler.subscribe());
+        }
+        None
+    }
 }
 
 impl ServerCore {
     /// Resolve the owner ID from the authentication context using the task router.
     ///
     /// Returns `None` if no task router is configured. When a task router is
-    /// available, it delegates to [`TaskRouter::resolve_owner`] which uses
-    /// the priority chain: OAuth subject > client ID > session ID > "local".
+    /// available, it delegates to [`TaskRouter::resolve_owner_from_auth`],
+    /// which applies the router's owner strategy (by default the priority
+    /// chain: OAuth subject > client ID > session ID > "local").
     /// When only a [`TaskStore`] is configured (no [`TaskRouter`]), derives
     /// the owner from the auth context directly.
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
     fn resolve_task_owner(&self, auth_context: Option<&AuthContext>) -> Option<String> {
         // Legacy path: TaskRouter has its own resolve_owner logic
         if let Some(ref router) = self.task_router {
-            return Some(match auth_context {
-                Some(ctx) => {
-                    router.resolve_owner(Some(&ctx.subject), ctx.client_id.as_deref(), None)
-                },
-                None => router.resolve_owner(None, None, None),
-            });
+            return Some(
+                router.resolve_owner_from_auth(auth_context, request_session().as_deref()),
+            );
         }
         // Standard path: derive owner from auth context when task_store is configured
         if self.task_store.is_some() {
-            return Some(match auth_context {
-                Some(ctx) => ctx.client_id.clone().unwrap_or_else(|| ctx.subject.clone()),
-                None => "local".to_string(),
-            });
+            return Some(crate::server::task_store::owner_for(auth_context));
         }
         None
     }
@@ -878,16 +1262,25 @@ impl ServerCore {
                     );
                 }
 
+                if let Some(subsystem) = self.disabled.rejects(boxed_req) {
+                    return Self::error_response(id, -32601, subsystem.disabled_message());
+                }
+
                 match boxed_req.as_ref() {
                     ClientRequest::ListTools(req) => match self.handle_list_tools(req).await {
                         Ok(result) => {
                             Self::success_response(id, serde_json::to_value(result).unwrap())
                         },
-                        Err(e) => Self::error_response(id, -32603, e.to_string()),
+                        // Keep the `Invalid params` code of rejected cursors
+                        Err(e) => JSONRPCResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id,
+                            payload: ResponsePayload::Error(JSONRPCError::from(e)),
+                        },
                     },
                     ClientRequest::CallTool(req) => {
                         // Check for task-augmented call: explicit task field or tool requires task
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                         if let Some(ref task_router) = self.task_router {
                             // Determine if this tool requires task augmentation
                             let tool_execution = self
@@ -928,7 +1321,7 @@ impl ServerCore {
                         }
                         // Normal tool call path (no task augmentation)
                         // Extract continuation context before the handler call
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                         #[allow(clippy::used_underscore_binding)]
                         let continuation_ctx = req
                             ._meta
@@ -936,13 +1329,25 @@ impl ServerCore {
                             .and_then(|m| m._task_id.clone())
                             .map(|task_id| (task_id, req.name.clone()));
 
-                        match self.handle_call_tool(req, auth_context.clone()).await {
+                        let started = std::time::Instant::now();
+                        let outcome = self.handle_call_tool(req, auth_context.clone()).await;
+                        self.events.emit(ServerEvent::ToolCalled {
+                            name: req.name.clone(),
+                            duration: started.elapsed(),
+                            is_error: outcome.as_ref().map_or(true, ToolCallOutcome::is_error),
+                        });
+
+                        match outcome {
                             Ok(outcome) => match outcome {
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                                 ToolCallOutcome::TaskCreated {
                                     task_id,
                                     task_value,
                                 } => {
+                                    self.events.emit(ServerEvent::TaskTransitioned {
+                                        task_id: task_id.clone(),
+                                        status: TaskStatus::Working,
+                                    });
                                     let result_value = serde_json::json!({
                                         "task": task_value,
                                         "_meta": {
@@ -953,7 +1358,7 @@ impl ServerCore {
                                 },
                                 ToolCallOutcome::Result(result) => {
                                     // Fire-and-forget workflow continuation recording
-                                    #[cfg(not(target_arch = "wasm32"))]
+                                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                                     if let (Some((task_id, tool_name)), Some(ref task_router)) =
                                         (continuation_ctx, &self.task_router)
                                     {
@@ -984,14 +1389,22 @@ impl ServerCore {
                                     )
                                 },
                             },
-                            Err(e) => Self::error_response(id, -32603, e.to_string()),
+                            // Keep the code and data of protocol errors raised by
+                            // tool middleware (e.g. rate limit retry metadata)
+                            Err(e) => JSONRPCResponse {
+                                jsonrpc: "2.0".to_string(),
+                                id,
+                                payload: ResponsePayload::Error(JSONRPCError::from(e)),
+                            },
                         }
                     },
-                    ClientRequest::ListPrompts(req) => match self.handle_list_prompts(req).await {
-                        Ok(result) => {
-                            Self::success_response(id, serde_json::to_value(result).unwrap())
-                        },
-                        Err(e) => Self::error_response(id, -32603, e.to_string()),
+                    ClientRequest::ListPrompts(req) => {
+                        match self.handle_list_prompts(req, auth_context.as_ref()).await {
+                            Ok(result) => {
+                                Self::success_response(id, serde_json::to_value(result).unwrap())
+                            },
+                            Err(e) => Self::error_response(id, -32603, e.to_string()),
+                        }
                     },
                     ClientRequest::GetPrompt(req) => {
                         match self.handle_get_prompt(req, auth_context.clone()).await {
@@ -1010,7 +1423,14 @@ impl ServerCore {
                         }
                     },
                     ClientRequest::ReadResource(req) => {
-                        match self.handle_read_resource(req, auth_context.clone()).await {
+                        let started = std::time::Instant::now();
+                        let result = self.handle_read_resource(req, auth_context.clone()).await;
+                        self.events.emit(ServerEvent::ResourceRead {
+                            uri: req.uri.clone(),
+                            duration: started.elapsed(),
+                            is_error: result.is_err(),
+                        });
+                        match result {
                             Ok(result) => {
                                 Self::success_response(id, serde_json::to_value(result).unwrap())
                             },
@@ -1025,8 +1445,44 @@ impl ServerCore {
                             Err(e) => Self::error_response(id, -32603, e.to_string()),
                         }
                     },
+                    #[cfg(feature = "resources")]
+                    ClientRequest::Subscribe(req) => {
+                        let subscriber =
+                            crate::server::subscriptions::subscriber_id(auth_context.as_ref());
+                        let manager = self.subscription_manager.read().await;
+                        match manager.subscribe(req.uri.clone(), subscriber).await {
+                            Ok(()) => Self::success_response(id, serde_json::json!({})),
+                            Err(e) => Self::error_response(id, -32603, e.to_string()),
+                        }
+                    },
+                    #[cfg(feature = "resources")]
+                    ClientRequest::Unsubscribe(req) => {
+                        let subscriber =
+                            crate::server::subscriptions::subscriber_id(auth_context.as_ref());
+                        let manager = self.subscription_manager.read().await;
+                        match manager.unsubscribe(req.uri.clone(), subscriber).await {
+                            Ok(()) => Self::success_response(id, serde_json::json!({})),
+                            Err(e) => Self::error_response(id, -32603, e.to_string()),
+                        }
+                    },
+                    ClientRequest::Complete(req) => {
+                        let request_id = format!("complete_{}", req.argument.name);
+                        let extra = RequestHandlerExtra::new(
+                            request_id.clone(),
+                            self.cancellation_manager.create_token(request_id).await,
+                        )
+                        .with_session_id(request_session())
+                        .with_auth_context(auth_context.clone())
+                        .with_extensions(self.state.clone());
+                        match self.completions.complete(req, extra).await {
+                            Ok(result) => {
+                                Self::success_response(id, serde_json::to_value(result).unwrap())
+                            },
+                            Err(e) => Self::error_response(id, -32603, e.to_string()),
+                        }
+                    },
                     // Task endpoint routing (TaskStore preferred, TaskRouter fallback)
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                     ClientRequest::TasksGet(params) => {
                         if let Some(ref store) = self.task_store {
                             let owner_id = self
@@ -1060,10 +1516,41 @@ impl ServerCore {
                             Self::error_response(id, -32601, "Tasks not enabled".to_string())
                         }
                     },
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                     ClientRequest::TasksResult(params) => {
-                        // tasks/result is a PMCP extension -- delegate to TaskRouter only
-                        if let Some(ref task_router) = self.task_router {
+                        // tasks/result is a PMCP extension -- scheduled workflow runs
+                        // keep their own results, everything else goes to the TaskRouter
+                        #[cfg(feature = "workflow")]
+                        let scheduled = match (&self.scheduler, &self.task_store) {
+                            (Some(scheduler), Some(store)) => {
+                                match scheduler.result(&params.task_id) {
+                                    Some(result) => {
+                                        let owner_id = self
+                                            .resolve_task_owner(auth_context.as_ref())
+                                            .unwrap_or_else(|| "local".to_string());
+                                        // Owner check: never reveal another owner's run
+                                        Some(
+                                            store
+                                                .get(&params.task_id, &owner_id)
+                                                .await
+                                                .map(|_| result),
+                                        )
+                                    },
+                                    None => None,
+                                }
+                            },
+                            _ => None,
+                        };
+                        #[cfg(not(feature = "workflow"))]
+                        let scheduled: Option<
+                            std::result::Result<Value, crate::server::task_store::TaskStoreError>,
+                        > = None;
+                        if let Some(outcome) = scheduled {
+                            match outcome {
+                                Ok(result) => Self::success_response(id, result),
+                                Err(e) => Self::error_response(id, -32603, e.to_string()),
+                            }
+                        } else if let Some(ref task_router) = self.task_router {
                             let owner_id = self
                                 .resolve_task_owner(auth_context.as_ref())
                                 .unwrap_or_else(|| "local".to_string());
@@ -1085,7 +1572,7 @@ impl ServerCore {
                             )
                         }
                     },
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                     ClientRequest::TasksList(params) => {
                         if let Some(ref store) = self.task_store {
                             let owner_id = self
@@ -1103,6 +1590,11 @@ impl ServerCore {
                                         serde_json::to_value(result).unwrap(),
                                     )
                                 },
+                                Err(
+                                    e @ crate::server::task_store::TaskStoreError::InvalidCursor {
+                                        ..
+                                    },
+                                ) => Self::error_response(id, -32602, e.to_string()),
                                 Err(e) => Self::error_response(id, -32603, e.to_string()),
                             }
                         } else if let Some(ref task_router) = self.task_router {
@@ -1123,7 +1615,7 @@ impl ServerCore {
                             Self::error_response(id, -32601, "Tasks not enabled".to_string())
                         }
                     },
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
                     ClientRequest::TasksCancel(params) => {
                         if let Some(ref store) = self.task_store {
                             let owner_id = self
@@ -1131,6 +1623,14 @@ impl ServerCore {
                                 .unwrap_or_else(|| "local".to_string());
                             match store.cancel(&params.task_id, &owner_id).await {
                                 Ok(task) => {
+                                    #[cfg(feature = "workflow")]
+                                    if let Some(ref scheduler) = self.scheduler {
+                                        scheduler.cancel(&task.task_id);
+                                    }
+                                    self.events.emit(ServerEvent::TaskTransitioned {
+                                        task_id: task.task_id.clone(),
+                                        status: task.status,
+                                    });
                                     let result = crate::types::tasks::CancelTaskResult::new(task);
                                     Self::success_response(
                                         id,
@@ -1157,6 +1657,31 @@ impl ServerCore {
                             Self::error_response(id, -32601, "Tasks not enabled".to_string())
                         }
                     },
+                    // tasks/provide_input is a PMCP extension served by the TaskRouter
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+                    ClientRequest::TasksProvideInput(params) => {
+                        if let Some(ref task_router) = self.task_router {
+                            let owner_id = self
+                                .resolve_task_owner(auth_context.as_ref())
+                                .unwrap_or_else(|| "local".to_string());
+                            match task_router
+                                .handle_tasks_provide_input(
+                                    serde_json::to_value(params).unwrap_or_default(),
+                                    &owner_id,
+                                )
+                                .await
+                            {
+                                Ok(result) => Self::success_response(id, result),
+                                Err(e) => Self::error_response(id, -32603, e.to_string()),
+                            }
+                        } else {
+                            Self::error_response(
+                                id,
+                                -32601,
+                                "tasks/provide_input not supported".to_string(),
+                            )
+                        }
+                    },
                     _ => Self::error_response(id, -32601, "Method not supported".to_string()),
                 }
             },
@@ -254,6 +254,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_tool_listing_pages() {
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("tool3", MockTool::new())
+            .tool("tool1", MockTool::new())
+            .tool("tool5", MockTool::new())
+            .tool("tool2", MockTool::new())
+            .tool("tool4", MockTool::new())
+            .tools_page_size(2)
+            .build()
+            .unwrap();
+        server
+            .handle_request(RequestId::from(1i64), create_init_request(), None)
+            .await;
+
+        let mut names = Vec::new();
+        let mut cursor = None;
+        for id in 2i64.. {
+            let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                cursor: cursor.take(),
+            })));
+            let response = server
+                .handle_request(RequestId::from(id), request, None)
+                .await;
+            let crate::types::jsonrpc::ResponsePayload::Result(result) = response.payload else {
+                panic!("Expected successful tools list");
+            };
+            let page: ListToolsResult = serde_json::from_value(result).unwrap();
+            assert!(page.tools.len() <= 2);
+            names.extend(page.tools.into_iter().map(|t| t.name));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(names, ["tool1", "tool2", "tool3", "tool4", "tool5"]);
+
+        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+            cursor: Some("not a cursor".to_string()),
+        })));
+        let response = server
+            .handle_request(RequestId::from(10i64), request, None)
+            .await;
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                assert_eq!(error.code, crate::ErrorCode::INVALID_PARAMS.as_i32());
+            },
+            _ => panic!("Expected an invalid cursor to be rejected"),
+        }
+    }
+
     #[tokio::test]
     async fn test_tool_schema_in_list() {
         use crate::server::simple_tool::SyncTool;
@@ -440,6 +493,80 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "prompt-dir")]
+    #[tokio::test]
+    async fn test_prompt_dir_prompts() {
+        use crate::server::prompt_dir::PromptDir;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("review.md"),
+            "---\ndescription: Review code\narguments:\n  - name: language\n    required: true\n---\nReview this {{language}} code.",
+        )
+        .unwrap();
+
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .prompt_dir(PromptDir::new(dir.path()))
+            .build()
+            .unwrap();
+        server
+            .handle_request(RequestId::from(1i64), create_init_request(), None)
+            .await;
+
+        let list_request =
+            Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
+                cursor: None,
+                prefix: None,
+            })));
+        let list_response = server
+            .handle_request(RequestId::from(2i64), list_request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(result) = list_response.payload else {
+            panic!("Expected successful prompts list");
+        };
+        let prompts_result: ListPromptsResult = serde_json::from_value(result).unwrap();
+        assert_eq!(prompts_result.prompts[0].name, "review");
+        assert_eq!(
+            prompts_result.prompts[0].description.as_deref(),
+            Some("Review code")
+        );
+
+        // Edits apply to the next prompts/get without rebuilding the server
+        std::fs::write(
+            dir.path().join("review.md"),
+            "---\narguments:\n  - name: language\n---\nCarefully review this {{language}} code.",
+        )
+        .unwrap();
+        let get_request = Request::Client(Box::new(ClientRequest::GetPrompt(GetPromptRequest {
+            name: "review".to_string(),
+            arguments: HashMap::from([("language".to_string(), "Rust".to_string())]),
+            _meta: None,
+        })));
+        let get_response = server
+            .handle_request(RequestId::from(3i64), get_request, None)
+            .await;
+        let crate::types::jsonrpc::ResponsePayload::Result(result) = get_response.payload else {
+            panic!("Expected successful prompt get");
+        };
+        let prompt_result: GetPromptResult = serde_json::from_value(result).unwrap();
+        assert!(matches!(
+            &prompt_result.messages[0].content,
+            Content::Text { text } if text == "Carefully review this Rust code."
+        ));
+
+        let err = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .prompt("review", MockPromptHandler::new())
+            .prompt_dir(PromptDir::new(dir.path()))
+            .build()
+            .err()
+            .expect("duplicate prompt name should fail the build");
+        assert!(err.to_string().contains("already registered"));
+    }
+
     #[tokio::test]
     async fn test_prompt_handling() {
         let prompt = MockPromptHandler::new();
@@ -460,6 +587,7 @@ mod tests {
         let list_request =
             Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
                 cursor: None,
+                prefix: None,
             })));
 
         let list_response = server
@@ -496,6 +624,93 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_prompt_namespaces_filter_and_authorize() {
+        use crate::server::auth::{AuthContext, NamespaceAuthorizer};
+
+        async fn list(
+            server: &ServerCore,
+            prefix: Option<&str>,
+            auth: Option<AuthContext>,
+        ) -> Vec<String> {
+            let request = ListPromptsRequest {
+                cursor: None,
+                prefix: prefix.map(str::to_string),
+            };
+            let response = server
+                .handle_request(
+                    RequestId::from(2i64),
+                    Request::Client(Box::new(ClientRequest::ListPrompts(request))),
+                    auth,
+                )
+                .await;
+            match response.payload {
+                crate::types::jsonrpc::ResponsePayload::Result(result) => {
+                    serde_json::from_value::<ListPromptsResult>(result)
+                        .unwrap()
+                        .prompts
+                        .into_iter()
+                        .map(|p| p.name)
+                        .collect()
+                },
+                _ => panic!("Expected successful prompts list"),
+            }
+        }
+
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .prompt("reporting/weekly", MockPromptHandler::new())
+            .prompt("reporting/monthly", MockPromptHandler::new())
+            .prompt("admin/cleanup", MockPromptHandler::new())
+            .prompt("summarize", MockPromptHandler::new())
+            .prompt_authorizer(NamespaceAuthorizer::new().require_scopes("admin", ["mcp:admin"]))
+            .build()
+            .unwrap();
+        server
+            .handle_request(RequestId::from(1i64), create_init_request(), None)
+            .await;
+
+        let mut reader = AuthContext::new("reader");
+        reader.scopes = vec!["mcp:read".to_string()];
+        let mut admin = AuthContext::new("admin");
+        admin.scopes = vec!["mcp:admin".to_string()];
+
+        assert_eq!(
+            list(&server, Some("reporting"), None).await,
+            vec!["reporting/monthly", "reporting/weekly"]
+        );
+        assert_eq!(
+            list(&server, None, Some(reader.clone())).await,
+            vec!["reporting/monthly", "reporting/weekly", "summarize"]
+        );
+        assert_eq!(
+            list(&server, Some("admin/"), Some(admin)).await,
+            vec!["admin/cleanup"]
+        );
+
+        // A denied prompt and a missing one in a denied namespace look the same
+        let mut denials = Vec::new();
+        for name in ["admin/cleanup", "admin/missing"] {
+            let get_request =
+                Request::Client(Box::new(ClientRequest::GetPrompt(GetPromptRequest {
+                    name: name.to_string(),
+                    arguments: HashMap::new(),
+                    _meta: None,
+                })));
+            let response = server
+                .handle_request(RequestId::from(3i64), get_request, Some(reader.clone()))
+                .await;
+            match response.payload {
+                crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                    denials.push((error.code, error.message.replace(name, "<prompt>")));
+                },
+                _ => panic!("Expected {} to be denied", name),
+            }
+        }
+        assert_eq!(denials[0], denials[1]);
+    }
+
     #[tokio::test]
     async fn test_resource_handling() {
         let resources = MockResourceHandler::new();
@@ -531,11 +746,9 @@ mod tests {
         }
 
         // Read resource
-        let read_request =
-            Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-                uri: "test://resource1".to_string(),
-                _meta: None,
-            })));
+        let read_request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("test://resource1"),
+        )));
 
         let read_response = server
             .handle_request(RequestId::from(3i64), read_request, None)
@@ -569,10 +782,9 @@ mod tests {
             .await;
 
         // Read non-existent resource
-        let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-            uri: "test://nonexistent".to_string(),
-            _meta: None,
-        })));
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("test://nonexistent"),
+        )));
 
         let response = server
             .handle_request(RequestId::from(2i64), request, None)
@@ -984,4 +1196,270 @@ mod tests {
             _ => panic!("Expected successful tool call with CallToolResult"),
         }
     }
+
+    #[tokio::test]
+    async fn test_event_bus_reports_session_and_tool_calls() {
+        use crate::server::event_bus::ServerEvent;
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("ok_tool", MockTool::new())
+            .tool("failing_tool", MockTool::failing())
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()))
+            .build()
+            .unwrap();
+
+        crate::server::cancellation::with_request_session(
+            Some("session-1".to_string()),
+            server.handle_request(RequestId::from(1i64), create_init_request(), None),
+        )
+        .await;
+        for (id, name) in [(2i64, "ok_tool"), (3i64, "failing_tool")] {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                name,
+                json!({}),
+            ))));
+            server
+                .handle_request(RequestId::from(id), request, None)
+                .await;
+        }
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            ServerEvent::SessionStarted {
+                session_id, client, ..
+            } => {
+                assert_eq!(session_id.as_deref(), Some("session-1"));
+                assert_eq!(client.name, "test-client");
+            },
+            other => panic!("Expected SessionStarted, got {:?}", other),
+        }
+        match (&events[1], &events[2]) {
+            (
+                ServerEvent::ToolCalled {
+                    name: ok_name,
+                    is_error: false,
+                    ..
+                },
+                ServerEvent::ToolCalled {
+                    name: failed_name,
+                    is_error: true,
+                    ..
+                },
+            ) => {
+                assert_eq!(ok_name, "ok_tool");
+                assert_eq!(failed_name, "failing_tool");
+            },
+            other => panic!("Expected two ToolCalled events, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_timeout_cancels_slow_handler() {
+        use crate::server::event_bus::ServerEvent;
+        use crate::server::tool_middleware::{ToolContext, ToolMiddleware};
+        use crate::SimpleTool;
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        type ErrorLog = Arc<Mutex<Vec<(String, Option<i32>)>>>;
+
+        /// Records the error codes tool middleware sees.
+        struct ErrorCodes(ErrorLog);
+
+        #[async_trait]
+        impl ToolMiddleware for ErrorCodes {
+            async fn on_error(
+                &self,
+                tool_name: &str,
+                error: &Error,
+                _context: &ToolContext,
+            ) -> Result<()> {
+                let code = error.error_code().map(|code| code.as_i32());
+                self.0.lock().unwrap().push((tool_name.to_string(), code));
+                Ok(())
+            }
+        }
+
+        let errors = ErrorLog::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let slow = SimpleTool::new("execute_query", |_args, extra| {
+            Box::pin(async move {
+                tokio::select! {
+                    () = tokio::time::sleep(Duration::from_secs(3600)) => Ok(json!({})),
+                    () = extra.cancellation_token.cancelled() => Ok(json!({"cancelled": true})),
+                }
+            })
+        });
+
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("execute_query", slow)
+            .tool("ok_tool", MockTool::new())
+            .default_tool_timeout(Duration::from_millis(20))
+            .tool_timeout("ok_tool", Duration::from_secs(5))
+            .tool_middleware(Arc::new(ErrorCodes(errors.clone())))
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()))
+            .build()
+            .unwrap();
+        server
+            .handle_request(RequestId::from(1i64), create_init_request(), None)
+            .await;
+
+        let call = |name: &str| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                name,
+                json!({}),
+            ))))
+        };
+        let response = server
+            .handle_request(RequestId::from(2i64), call("execute_query"), None)
+            .await;
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                assert_eq!(error.code, -32001);
+                assert!(error
+                    .message
+                    .contains("Tool 'execute_query' timed out after 20ms"));
+            },
+            _ => panic!("Expected timeout error"),
+        }
+
+        let response = server
+            .handle_request(RequestId::from(3i64), call("ok_tool"), None)
+            .await;
+        assert!(matches!(
+            response.payload,
+            crate::types::jsonrpc::ResponsePayload::Result(_)
+        ));
+
+        let events = events.lock().unwrap();
+        let timed_out: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ServerEvent::ToolTimedOut { name, timeout } => Some((name.as_str(), *timeout)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            timed_out,
+            vec![("execute_query", Duration::from_millis(20))]
+        );
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec![("execute_query".to_string(), Some(-32001))]
+        );
+    }
+
+    #[cfg(feature = "workflow")]
+    #[tokio::test]
+    async fn test_workflow_step_tool_timeout() {
+        use crate::server::event_bus::ServerEvent;
+        use crate::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+        use crate::SimpleTool;
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let slow = SimpleTool::new("execute_query", |_args, _extra| {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                Ok(json!({}))
+            })
+        });
+        let workflow = SequentialWorkflow::new("report", "Run the report query")
+            .step(WorkflowStep::new("query", ToolHandle::new("execute_query")));
+
+        // The deadline is set after the workflow captured its tools
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("execute_query", slow)
+            .prompt_workflow(workflow)
+            .unwrap()
+            .tool_timeout("execute_query", Duration::from_millis(20))
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()))
+            .build()
+            .unwrap();
+        server
+            .handle_request(RequestId::from(1i64), create_init_request(), None)
+            .await;
+
+        let request = Request::Client(Box::new(ClientRequest::GetPrompt(GetPromptRequest {
+            name: "report".to_string(),
+            arguments: HashMap::new(),
+            _meta: None,
+        })));
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            server.handle_request(RequestId::from(2i64), request, None),
+        )
+        .await
+        .expect("workflow step should time out");
+
+        let events = events.lock().unwrap();
+        assert!(
+            events.iter().any(|event| matches!(
+                event,
+                ServerEvent::ToolTimedOut { name, timeout }
+                    if name == "execute_query" && *timeout == Duration::from_millis(20)
+            )),
+            "expected ToolTimedOut, got {:?}",
+            *events
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_tool_call_reports_retry_after() {
+        use crate::server::rate_limit::{Quota, RateLimiter};
+
+        let tool = MockTool::new();
+        let invocations = tool.invocation_count.clone();
+        let server = ServerCoreBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("limited", tool)
+            .tool_middleware(Arc::new(RateLimiter::new(Quota::per_minute(1))))
+            .build()
+            .unwrap();
+
+        server
+            .handle_request(RequestId::from(0i64), create_init_request(), None)
+            .await;
+        let mut responses = Vec::new();
+        for id in 1..=2i64 {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "limited",
+                json!({}),
+            ))));
+            responses.push(
+                server
+                    .handle_request(RequestId::from(id), request, None)
+                    .await,
+            );
+        }
+
+        assert!(matches!(
+            responses[0].payload,
+            crate::types::jsonrpc::ResponsePayload::Result(_)
+        ));
+        match &responses[1].payload {
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                assert_eq!(error.code, crate::ErrorCode::RATE_LIMITED.as_i32());
+                let data = error.data.as_ref().expect("retry metadata");
+                assert_eq!(data["retryAfter"], 60);
+            },
+            other => panic!("Expected rate limit error, got {:?}", other),
+        }
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+    }
 }
@@ -2,10 +2,20 @@
 //!
 //! This module provides the `ElicitationManager` for handling elicitation
 //! requests using the spec-compliant `elicitation/create` method.
+//!
+//! Tool handlers normally elicit through
+//! [`RequestHandlerExtra::elicit`](crate::server::cancellation::RequestHandlerExtra::elicit),
+//! which is available when the server runs on a transport and the client
+//! declared the `elicitation` capability. The tool is suspended until the
+//! client answers, the request times out, or the tool call is cancelled.
+//! A cancelled elicitation is withdrawn with `notifications/cancelled`.
 
 use crate::error::{Error, ErrorCode, Result};
 use crate::types::elicitation::{ElicitRequestParams, ElicitResult};
-use crate::types::ServerRequest;
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{
+    CancelledNotification, JSONRPCResponse, Notification, RequestId, ServerRequest,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
@@ -18,11 +28,17 @@ use tracing::{debug, warn};
 static ELICITATION_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
 
 /// Manager for handling input elicitation requests.
+///
+/// Each elicitation is sent to the client as an `elicitation/create` request
+/// whose JSON-RPC ID is the elicitation ID; the client's response is routed
+/// back with [`handle_jsonrpc_response`](Self::handle_jsonrpc_response).
 pub struct ElicitationManager {
     /// Pending elicitation requests waiting for responses.
-    pending: Arc<RwLock<HashMap<String, oneshot::Sender<ElicitResult>>>>,
+    pending: Arc<RwLock<HashMap<String, oneshot::Sender<Result<ElicitResult>>>>>,
     /// Channel for sending requests to the client.
-    request_tx: Option<mpsc::Sender<ServerRequest>>,
+    request_tx: Option<mpsc::Sender<(RequestId, ServerRequest)>>,
+    /// Channel for withdrawing abandoned requests from the client.
+    notification_tx: Option<mpsc::Sender<Notification>>,
     /// Default timeout for elicitation requests.
     timeout_duration: Duration,
 }
@@ -31,6 +47,7 @@ impl std::fmt::Debug for ElicitationManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ElicitationManager")
             .field("has_request_tx", &self.request_tx.is_some())
+            .field("has_notification_tx", &self.notification_tx.is_some())
             .field("timeout_duration", &self.timeout_duration)
             .finish()
     }
@@ -42,15 +59,24 @@ impl ElicitationManager {
         Self {
             pending: Arc::new(RwLock::new(HashMap::new())),
             request_tx: None,
+            notification_tx: None,
             timeout_duration: Duration::from_secs(300), // 5 minutes default
         }
     }
 
     /// Set the request channel for sending elicitation requests.
-    pub fn set_request_channel(&mut self, tx: mpsc::Sender<ServerRequest>) {
+    ///
+    /// Each request is paired with the JSON-RPC ID it must be sent with.
+    pub fn set_request_channel(&mut self, tx: mpsc::Sender<(RequestId, ServerRequest)>) {
         self.request_tx = Some(tx);
     }
 
+    /// Set the channel used to send `notifications/cancelled` for elicitations
+    /// the server stops waiting on.
+    pub fn set_notification_channel(&mut self, tx: mpsc::Sender<Notification>) {
+        self.notification_tx = Some(tx);
+    }
+
     /// Set the timeout duration for elicitation requests.
     pub fn set_timeout(&mut self, duration: Duration) {
         self.timeout_duration = duration;
@@ -63,6 +89,9 @@ impl ElicitationManager {
     }
 
     /// Request input from the user using the spec-compliant elicitation/create method.
+    ///
+    /// Dropping the returned future before the client answers removes the
+    /// pending request and sends the client `notifications/cancelled` for it.
     #[allow(clippy::cognitive_complexity)]
     pub async fn elicit_input(&self, request: ElicitRequestParams) -> Result<ElicitResult> {
         let request_tx = self.request_tx.as_ref().ok_or_else(|| {
@@ -80,10 +109,18 @@ impl ElicitationManager {
             let mut pending = self.pending.write().await;
             pending.insert(elicitation_id.clone(), tx);
         }
+        let mut abandoned = AbandonedElicitation {
+            id: Some(elicitation_id.clone()),
+            pending: self.pending.clone(),
+            notification_tx: self.notification_tx.clone(),
+        };
 
-        // Send elicitation request
+        // Send elicitation request, using the elicitation ID as the JSON-RPC ID
         let server_request = ServerRequest::ElicitationCreate(Box::new(request));
-        if let Err(e) = request_tx.send(server_request).await {
+        if let Err(e) = request_tx
+            .send((RequestId::from(elicitation_id.as_str()), server_request))
+            .await
+        {
             // Remove from pending on send error
             self.pending.write().await.remove(&elicitation_id);
             return Err(Error::protocol(
@@ -95,10 +132,12 @@ impl ElicitationManager {
         debug!("Sent elicitation request: {}", elicitation_id);
 
         // Wait for response with timeout
-        match timeout(self.timeout_duration, rx).await {
+        let outcome = timeout(self.timeout_duration, rx).await;
+        abandoned.id = None;
+        match outcome {
             Ok(Ok(response)) => {
                 debug!("Received elicitation response: {}", elicitation_id);
-                Ok(response)
+                response
             },
             Ok(Err(_)) => {
                 warn!("Elicitation channel closed: {}", elicitation_id);
@@ -130,7 +169,7 @@ impl ElicitationManager {
         let mut pending = self.pending.write().await;
 
         if let Some(tx) = pending.remove(elicitation_id) {
-            if tx.send(response).is_err() {
+            if tx.send(Ok(response)).is_err() {
                 warn!("Failed to deliver elicitation response - receiver dropped");
             }
             Ok(())
@@ -146,16 +185,45 @@ impl ElicitationManager {
         }
     }
 
+    /// Route a JSON-RPC response from the client to its pending elicitation.
+    ///
+    /// Returns `false` if the response does not belong to a pending
+    /// elicitation. A JSON-RPC error response fails the elicitation with that
+    /// error; a result that is not a valid `ElicitResult` fails it with
+    /// `INVALID_PARAMS`.
+    pub async fn handle_jsonrpc_response(&self, response: JSONRPCResponse) -> bool {
+        let Some(tx) = self.pending.write().await.remove(&response.id.to_string()) else {
+            return false;
+        };
+
+        let result = match response.payload {
+            ResponsePayload::Result(value) => serde_json::from_value(value).map_err(|e| {
+                Error::protocol(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("Invalid elicitation result: {e}"),
+                )
+            }),
+            ResponsePayload::Error(error) => {
+                Err(Error::protocol(ErrorCode::other(error.code), error.message))
+            },
+        };
+
+        if tx.send(result).is_err() {
+            debug!(
+                "Elicitation {} was abandoned before the response",
+                response.id
+            );
+        }
+        true
+    }
+
     /// Cancel a pending elicitation request.
     pub async fn cancel(&self, elicitation_id: &str) -> Result<()> {
         let mut pending = self.pending.write().await;
 
         if let Some(tx) = pending.remove(elicitation_id) {
             // Send cancellation response
-            let response = ElicitResult {
-                action: crate::types::elicitation::ElicitAction::Cancel,
-                content: None,
-            };
+            let response = Ok(ElicitResult::cancel());
 
             if tx.send(response).is_err() {
                 debug!("Elicitation already completed: {}", elicitation_id);
@@ -174,10 +242,7 @@ impl ElicitationManager {
         let mut pending = self.pending.write().await;
 
         for (_id, tx) in pending.drain() {
-            let response = ElicitResult {
-                action: crate::types::elicitation::ElicitAction::Cancel,
-                content: None,
-            };
+            let response = Ok(ElicitResult::cancel());
 
             let _ = tx.send(response);
         }
@@ -195,9 +260,48 @@ impl Default for ElicitationManager {
     }
 }
 
+/// Withdraws an elicitation whose caller stopped waiting for the answer.
+///
+/// Requests that were answered, timed out or failed to send are already
+/// removed from `pending`, so only abandoned ones reach the client.
+struct AbandonedElicitation {
+    /// Set while the caller is waiting for the answer.
+    id: Option<String>,
+    pending: Arc<RwLock<HashMap<String, oneshot::Sender<Result<ElicitResult>>>>>,
+    notification_tx: Option<mpsc::Sender<Notification>>,
+}
+
+impl Drop for AbandonedElicitation {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let pending = self.pending.clone();
+        let notification_tx = self.notification_tx.clone();
+        runtime.spawn(async move {
+            if pending.write().await.remove(&id).is_none() {
+                return;
+            }
+            debug!("Elicitation abandoned: {}", id);
+            if let Some(tx) = notification_tx {
+                let cancelled = CancelledNotification::new(RequestId::from(id.as_str()))
+                    .with_reason("Elicitation no longer needed");
+                let _ = tx.send(Notification::Cancelled(cancelled)).await;
+            }
+        });
+    }
+}
+
 /// Extension trait for tool handlers to elicit input.
+///
+/// [`RequestHandlerExtra::elicit`](crate::server::cancellation::RequestHandlerExtra::elicit)
+/// drops the `elicit_input` future when the tool call is cancelled, so
+/// implementations should release any pending request on drop.
 #[async_trait::async_trait]
-pub trait ElicitInput {
+pub trait ElicitInput: Send + Sync {
     /// Request input from the user.
     async fn elicit_input(&self, request: ElicitRequestParams) -> Result<ElicitResult>;
 }
@@ -255,4 +359,29 @@ mod tests {
         let result = manager.elicit_input(request).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_abandoned_elicitation_is_withdrawn() {
+        let (request_tx, mut request_rx) = mpsc::channel(10);
+        let (notification_tx, mut notification_rx) = mpsc::channel(10);
+        let mut manager = ElicitationManager::new();
+        manager.set_request_channel(request_tx);
+        manager.set_notification_channel(notification_tx);
+
+        let request = ElicitRequestParams::Form {
+            message: "Test prompt".to_string(),
+            requested_schema: serde_json::json!({"type": "object"}),
+        };
+
+        // Stop waiting before the client answers
+        let result = timeout(Duration::from_millis(50), manager.elicit_input(request)).await;
+        assert!(result.is_err());
+
+        let (request_id, _) = request_rx.recv().await.unwrap();
+        match notification_rx.recv().await.unwrap() {
+            Notification::Cancelled(cancelled) => assert_eq!(cancelled.request_id, request_id),
+            other => panic!("expected a cancelled notification, got {other:?}"),
+        }
+        assert_eq!(manager.pending_count().await, 0);
+    }
 }
@@ -0,0 +1,249 @@
+//! Typed event bus for server lifecycle and dispatch events.
+//!
+//! The server publishes a [`ServerEvent`] when it starts serving, whenever a
+//! session starts or closes, a tool is called or times out, a resource is
+//! read, or a task changes state. Audit logs, metrics exporters, and
+//! webhooks can subscribe to these events instead
+//! of being wired into the dispatch code itself.
+//!
+//! There are two ways to subscribe:
+//!
+//! - **Listeners** registered with [`ServerBuilder::on_event`](crate::ServerBuilder::on_event)
+//!   run synchronously on the dispatch path. Keep them cheap, or spawn a task.
+//! - **Receivers** returned by [`EventBus::subscribe`] get a copy of every event
+//!   over a broadcast channel, which suits background jobs. Slow receivers lag
+//!   and skip events rather than block the server.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pmcp::server::event_bus::ServerEvent;
+//! use pmcp::Server;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("audited-server")
+//!     .version("1.0.0")
+//!     .on_event(|event| {
+//!         if let ServerEvent::ToolCalled { name, is_error, .. } = event {
+//!             println!("tool {} called (error: {})", name, is_error);
+//!         }
+//!     })
+//!     .build()?;
+//!
+//! // Background jobs can subscribe after the server is built.
+//! let mut events = server.events().subscribe();
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         println!("{:?}", event);
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::tasks::TaskStatus;
+use crate::types::Implementation;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Buffered events per broadcast receiver before it starts lagging.
+const BROADCAST_CAPACITY: usize = 256;
+
+/// An event published by the server.
+///
+/// New variants may be added in minor releases, so match with a wildcard arm.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ServerEvent {
+    /// The server started serving a transport.
+    ServerStarted {
+        /// Server implementation info.
+        server: Implementation,
+    },
+    /// A client completed `initialize`.
+    SessionStarted {
+        /// Session ID, when the transport assigns one.
+        session_id: Option<String>,
+        /// Client implementation info sent in `initialize`.
+        client: Implementation,
+        /// Negotiated protocol version.
+        protocol_version: String,
+    },
+    /// A session ended (HTTP `DELETE` or transport closed).
+    SessionClosed {
+        /// Session ID, when the transport assigns one.
+        session_id: Option<String>,
+    },
+    /// A `tools/call` request finished.
+    ToolCalled {
+        /// Tool name.
+        name: String,
+        /// Time spent in the handler, including tool middleware.
+        duration: Duration,
+        /// Whether the call failed or its result has `isError` set.
+        is_error: bool,
+    },
+    /// A `tools/call` request exceeded its deadline and was cancelled.
+    ///
+    /// Followed by the call's [`ToolCalled`](Self::ToolCalled) event.
+    ToolTimedOut {
+        /// Tool name.
+        name: String,
+        /// The deadline that was exceeded.
+        timeout: Duration,
+    },
+    /// A `resources/read` request finished.
+    ResourceRead {
+        /// Resource URI.
+        uri: String,
+        /// Time spent in the resource handler.
+        duration: Duration,
+        /// Whether the read returned an error.
+        is_error: bool,
+    },
+    /// A task was created or changed status through the server.
+    TaskTransitioned {
+        /// Task ID.
+        task_id: String,
+        /// Status after the transition.
+        status: TaskStatus,
+    },
+}
+
+/// Callback invoked synchronously for every published event.
+pub type EventListener = Arc<dyn Fn(&ServerEvent) + Send + Sync>;
+
+/// Publishes [`ServerEvent`]s to listeners and broadcast subscribers.
+///
+/// Cloning is cheap; clones share the same listeners and channel.
+#[derive(Clone)]
+pub struct EventBus {
+    listeners: Arc<RwLock<Vec<EventListener>>>,
+    sender: broadcast::Sender<ServerEvent>,
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("listeners", &self.listeners.read().len())
+            .field("subscribers", &self.sender.receiver_count())
+            .finish()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Create an event bus with no listeners.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            listeners: Arc::new(RwLock::new(Vec::new())),
+            sender,
+        }
+    }
+
+    /// Register a listener called for every event.
+    pub fn on_event(&self, listener: impl Fn(&ServerEvent) + Send + Sync + 'static) {
+        self.listeners.write().push(Arc::new(listener));
+    }
+
+    /// Subscribe to events over a broadcast channel.
+    pub fn subscribe(&self) -> broadcast::Receiver<ServerEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Whether anyone is listening; lets callers skip building events.
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0 || !self.listeners.read().is_empty()
+    }
+
+    /// Publish an event to all listeners and subscribers.
+    pub fn emit(&self, event: ServerEvent) {
+        // Snapshot so a listener can register another listener without deadlocking
+        let listeners = self.listeners.read().clone();
+        for listener in &listeners {
+            listener(&event);
+        }
+        // No receivers is not an error; the event is simply dropped.
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn tool_event(name: &str) -> ServerEvent {
+        ServerEvent::ToolCalled {
+            name: name.to_string(),
+            duration: Duration::from_millis(5),
+            is_error: false,
+        }
+    }
+
+    #[test]
+    fn test_listeners_receive_events() {
+        let bus = EventBus::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let seen = count.clone();
+        bus.on_event(move |event| {
+            if matches!(event, ServerEvent::ToolCalled { name, .. } if name == "echo") {
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        bus.emit(tool_event("echo"));
+        bus.emit(tool_event("other"));
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_clones_share_listeners() {
+        let bus = EventBus::new();
+        assert!(!bus.has_subscribers());
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let seen = count.clone();
+        bus.clone().on_event(move |_| {
+            seen.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(bus.has_subscribers());
+        bus.emit(tool_event("echo"));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        bus.emit(ServerEvent::TaskTransitioned {
+            task_id: "t-1".to_string(),
+            status: TaskStatus::Completed,
+        });
+
+        match rx.recv().await.unwrap() {
+            ServerEvent::TaskTransitioned { task_id, status } => {
+                assert_eq!(task_id, "t-1");
+                assert_eq!(status, TaskStatus::Completed);
+            },
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_emit_without_subscribers_is_noop() {
+        EventBus::new().emit(ServerEvent::SessionClosed { session_id: None });
+    }
+}
@@ -0,0 +1,187 @@
+//! Typed values shared with request handlers.
+//!
+//! [`Extensions`] is a map keyed by type, in the spirit of axum's
+//! `Extensions`. Servers register long-lived state such as database pools,
+//! configuration and caches once with `with_state` on the server builder, and
+//! every handler reads it back through
+//! [`RequestHandlerExtra::state`](crate::server::cancellation::RequestHandlerExtra::state)
+//! instead of capturing `Arc` clones in each closure.
+//!
+//! Each request gets its own copy of the map (values are shared behind
+//! `Arc`), so middleware can add request-scoped values without affecting
+//! other requests.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::extensions::Extensions;
+//!
+//! struct Config {
+//!     region: String,
+//! }
+//!
+//! let mut extensions = Extensions::new();
+//! extensions.insert(Config {
+//!     region: "eu-west-1".to_string(),
+//! });
+//!
+//! assert_eq!(extensions.get::<Config>().unwrap().region, "eu-west-1");
+//! assert!(extensions.get::<String>().is_none());
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A map holding at most one value of each type.
+///
+/// Cloning is cheap: values are reference counted and shared between clones.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `value`, replacing any previous value of the same type.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.insert_arc(Arc::new(value));
+    }
+
+    /// Insert an already shared value, replacing any previous value of the
+    /// same type.
+    pub fn insert_arc<T: Send + Sync + 'static>(&mut self, value: Arc<T>) {
+        self.values.insert(TypeId::of::<T>(), value);
+    }
+
+    /// Get a reference to the value of type `T`, if present.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Get a shared handle to the value of type `T`, if present.
+    ///
+    /// Useful when the value must outlive the request, e.g. when it is moved
+    /// into a spawned task.
+    pub fn get_arc<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Returns `true` if a value of type `T` is present.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Remove the value of type `T`, returning `true` if one was present.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Copy every value from `other` into this map, replacing values of the
+    /// same type.
+    pub fn extend(&mut self, other: &Self) {
+        self.values
+            .extend(other.values.iter().map(|(id, value)| (*id, value.clone())));
+    }
+
+    /// Number of values in the map.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Pool(u32);
+
+    #[test]
+    fn test_insert_and_get_by_type() {
+        let mut extensions = Extensions::new();
+        assert!(extensions.is_empty());
+
+        extensions.insert(Pool(4));
+        extensions.insert("config".to_string());
+
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions.get::<Pool>(), Some(&Pool(4)));
+        assert_eq!(
+            extensions.get::<String>().map(String::as_str),
+            Some("config")
+        );
+        assert!(extensions.get::<u64>().is_none());
+    }
+
+    #[test]
+    fn test_insert_replaces_same_type() {
+        let mut extensions = Extensions::new();
+        extensions.insert(Pool(1));
+        extensions.insert(Pool(2));
+
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(extensions.get::<Pool>(), Some(&Pool(2)));
+    }
+
+    #[test]
+    fn test_clones_share_values() {
+        let shared = Arc::new(Pool(7));
+        let mut extensions = Extensions::new();
+        extensions.insert_arc(shared.clone());
+
+        let clone = extensions.clone();
+        let from_clone = clone.get_arc::<Pool>().unwrap();
+        assert!(Arc::ptr_eq(&shared, &from_clone));
+    }
+
+    #[test]
+    fn test_clone_is_independent_map() {
+        let mut server = Extensions::new();
+        server.insert(Pool(1));
+
+        let mut request = server.clone();
+        request.insert(42u64);
+        assert!(request.remove::<Pool>());
+
+        assert!(server.contains::<Pool>());
+        assert!(!server.contains::<u64>());
+    }
+
+    #[test]
+    fn test_extend_overrides() {
+        let mut base = Extensions::new();
+        base.insert(Pool(1));
+        base.insert(1u8);
+
+        let mut overlay = Extensions::new();
+        overlay.insert(Pool(2));
+
+        base.extend(&overlay);
+        assert_eq!(base.get::<Pool>(), Some(&Pool(2)));
+        assert_eq!(base.get::<u8>(), Some(&1));
+    }
+}
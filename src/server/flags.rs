@@ -0,0 +1,760 @@
+//! Feature flags evaluated per request for tools and widgets.
+//!
+//! A [`FlagProvider`] registered with `feature_flags` on the server builder
+//! is evaluated before each tool call against a [`FlagContext`] built from
+//! the caller's identity (auth subject, tenant, session). Handlers read the
+//! result with [`RequestHandlerExtra::flags`], and widget tools also return
+//! it in the result `_meta` under [`FLAGS_META_KEY`], where the widget
+//! bridges expose it as `window.mcpBridge.flags` / `window.mcpFlags`.
+//!
+//! Built-in providers:
+//! - [`StaticFlags`] - values from config, with per-tenant and per-user
+//!   overrides and percentage rollouts
+//! - [`EnvFlags`] - values from prefixed environment variables
+//! - `LaunchDarklyFlags` - LaunchDarkly client-side evaluation
+//!   (`http-client` feature)
+//!
+//! A provider error never fails the tool call: the call runs with no flags
+//! set and the error is logged.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::flags::StaticFlags;
+//! use pmcp::Server;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let flags = StaticFlags::new()
+//!     .flag("search-v2", false)
+//!     .tenant_flag("acme", "search-v2", true)
+//!     .rollout("compact-widget", 25);
+//!
+//! let server = Server::builder()
+//!     .name("search")
+//!     .version("1.0.0")
+//!     .feature_flags(Arc::new(flags))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```rust,ignore
+//! if extra.flags().is_enabled("search-v2") {
+//!     return search_v2(&input).await;
+//! }
+//! ```
+//!
+//! [`RequestHandlerExtra::flags`]: crate::server::cancellation::RequestHandlerExtra::flags
+
+use crate::error::Result;
+use crate::server::cancellation::RequestHandlerExtra;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// `_meta` key under which widget tool results carry the evaluated flags.
+pub const FLAGS_META_KEY: &str = "pmcp/flags";
+
+/// Who a flag is evaluated for.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlagContext {
+    /// Authenticated user (the auth subject).
+    pub user_id: Option<String>,
+    /// Tenant of the authenticated user.
+    pub tenant_id: Option<String>,
+    /// MCP session, when the transport has one.
+    pub session_id: Option<String>,
+    /// Extra attributes for providers that target on them.
+    pub attributes: HashMap<String, Value>,
+}
+
+impl FlagContext {
+    /// Context for the caller of a request.
+    pub fn from_extra(extra: &RequestHandlerExtra) -> Self {
+        let auth = extra
+            .auth_context
+            .as_ref()
+            .filter(|auth| auth.authenticated);
+        Self {
+            user_id: auth.map(|auth| auth.subject.clone()),
+            tenant_id: auth.and_then(|auth| auth.tenant_id().map(str::to_string)),
+            session_id: extra.session_id.clone(),
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Stable key used to bucket percentage rollouts: the user, else the
+    /// session, else the tenant.
+    pub fn rollout_key(&self) -> Option<&str> {
+        self.user_id
+            .as_deref()
+            .or(self.session_id.as_deref())
+            .or(self.tenant_id.as_deref())
+    }
+}
+
+/// Evaluated flag values for one request.
+///
+/// Values are JSON so that providers can serve booleans, string variants
+/// and structured configuration alike.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Flags(BTreeMap<String, Value>);
+
+impl Flags {
+    /// No flags set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `name` to `value`.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    /// Whether `name` is set to `true`.
+    ///
+    /// Unknown flags and non-boolean values are off.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::flags::Flags;
+    ///
+    /// let mut flags = Flags::new();
+    /// flags.set("search-v2", true);
+    /// flags.set("theme", "dark");
+    ///
+    /// assert!(flags.is_enabled("search-v2"));
+    /// assert!(!flags.is_enabled("theme"));
+    /// assert!(!flags.is_enabled("unknown"));
+    /// ```
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.0.get(name).and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    /// The raw value of `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    /// The value of `name` when it is a string variant.
+    pub fn variant(&self, name: &str) -> Option<&str> {
+        self.0.get(name).and_then(Value::as_str)
+    }
+
+    /// Deserialize the value of `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the value does not match `T`.
+    pub fn get_as<T: serde::de::DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        self.0
+            .get(name)
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|e| {
+                    crate::Error::validation(format!(
+                        "Flag '{}' has an unexpected value: {}",
+                        name, e
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Whether no flags are set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// All flags, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for Flags {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Source of feature flag values.
+#[async_trait]
+pub trait FlagProvider: Send + Sync {
+    /// Evaluate every flag for `context`.
+    async fn evaluate(&self, context: &FlagContext) -> Result<Flags>;
+}
+
+/// Flags from static configuration.
+///
+/// Evaluation order, later wins: defaults, rollouts, tenant overrides, user
+/// overrides.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFlags {
+    defaults: Flags,
+    rollouts: BTreeMap<String, u8>,
+    tenants: HashMap<String, Flags>,
+    users: HashMap<String, Flags>,
+}
+
+impl StaticFlags {
+    /// No flags configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `name` to `value` for everyone.
+    pub fn flag(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.defaults.set(name, value);
+        self
+    }
+
+    /// Set `name` to `value` for one tenant.
+    pub fn tenant_flag(
+        mut self,
+        tenant_id: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.tenants
+            .entry(tenant_id.into())
+            .or_default()
+            .set(name, value);
+        self
+    }
+
+    /// Set `name` to `value` for one user.
+    pub fn user_flag(
+        mut self,
+        user_id: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.users
+            .entry(user_id.into())
+            .or_default()
+            .set(name, value);
+        self
+    }
+
+    /// Enable `name` for `percent` of callers.
+    ///
+    /// Callers are bucketed by [`FlagContext::rollout_key`], so the same
+    /// user keeps the same value while the percentage stays unchanged, and
+    /// raising the percentage only adds callers. Callers without any key
+    /// get `false`. Percentages above 100 are treated as 100.
+    pub fn rollout(mut self, name: impl Into<String>, percent: u8) -> Self {
+        self.rollouts.insert(name.into(), percent.min(100));
+        self
+    }
+}
+
+/// Bucket in `0..100` for `key` within flag `name`.
+fn rollout_bucket(name: &str, key: &str) -> u8 {
+    let digest = Sha256::digest(format!("{}:{}", name, key).as_bytes());
+    (u16::from_be_bytes([digest[0], digest[1]]) % 100) as u8
+}
+
+#[async_trait]
+impl FlagProvider for StaticFlags {
+    async fn evaluate(&self, context: &FlagContext) -> Result<Flags> {
+        let mut flags = self.defaults.clone();
+        for (name, percent) in &self.rollouts {
+            let enabled = context
+                .rollout_key()
+                .is_some_and(|key| rollout_bucket(name, key) < *percent);
+            flags.set(name.clone(), enabled);
+        }
+        let overrides = [
+            context.tenant_id.as_ref().and_then(|t| self.tenants.get(t)),
+            context.user_id.as_ref().and_then(|u| self.users.get(u)),
+        ];
+        for (name, value) in overrides.into_iter().flatten().flat_map(Flags::iter) {
+            flags.set(name, value.clone());
+        }
+        Ok(flags)
+    }
+}
+
+/// Flags from environment variables.
+///
+/// `{PREFIX}SEARCH_V2=true` sets flag `search-v2`: the prefix is stripped,
+/// the rest lowercased and `_` replaced with `-`. Values are parsed as JSON
+/// and fall back to plain strings, so `true`, `42` and `dark` all work.
+/// Variables are read when the provider is created.
+#[derive(Debug, Clone)]
+pub struct EnvFlags {
+    flags: Flags,
+}
+
+impl EnvFlags {
+    /// Read flags from variables starting with `prefix` (e.g. `"PMCP_FLAG_"`).
+    pub fn new(prefix: &str) -> Self {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    /// Read flags from `vars` instead of the process environment.
+    pub fn from_vars(prefix: &str, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let flags = vars
+            .into_iter()
+            .filter_map(|(key, raw)| {
+                let name = key.strip_prefix(prefix)?;
+                if name.is_empty() {
+                    return None;
+                }
+                let value = serde_json::from_str(&raw).unwrap_or(Value::String(raw));
+                Some((name.to_lowercase().replace('_', "-"), value))
+            })
+            .collect();
+        Self { flags }
+    }
+}
+
+#[async_trait]
+impl FlagProvider for EnvFlags {
+    async fn evaluate(&self, _context: &FlagContext) -> Result<Flags> {
+        Ok(self.flags.clone())
+    }
+}
+
+/// Flags evaluated by LaunchDarkly.
+///
+/// Uses the client-side evaluation endpoint with the environment's
+/// client-side ID, so no server SDK key is needed on the MCP server. The
+/// caller is sent as a `user` context keyed by the auth subject, an
+/// `organization` context keyed by the tenant, or both; anonymous callers
+/// are keyed by session. Context attributes are added to the user context.
+///
+/// Results are cached per context for [`with_cache_ttl`](Self::with_cache_ttl)
+/// (one minute by default) so a tool call does not wait on LaunchDarkly
+/// every time. Requests time out after [`with_timeout`](Self::with_timeout)
+/// (five seconds by default); when a refresh fails, the last flags fetched
+/// for the context are reused. At most 10,000 contexts are cached, dropping
+/// expired entries first and then the oldest.
+#[cfg(feature = "http-client")]
+#[derive(Debug)]
+pub struct LaunchDarklyFlags {
+    client_side_id: String,
+    base_url: String,
+    cache_ttl: std::time::Duration,
+    cache: tokio::sync::RwLock<HashMap<String, (std::time::Instant, Flags)>>,
+    client: reqwest::Client,
+}
+
+/// Maximum number of LaunchDarkly contexts with cached flags.
+#[cfg(feature = "http-client")]
+const MAX_CACHED_CONTEXTS: usize = 10_000;
+
+/// Time allowed to connect to LaunchDarkly.
+#[cfg(feature = "http-client")]
+const LAUNCHDARKLY_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Default time allowed for a LaunchDarkly evaluation request.
+#[cfg(feature = "http-client")]
+const LAUNCHDARKLY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[cfg(feature = "http-client")]
+impl LaunchDarklyFlags {
+    /// Evaluate flags in the environment with `client_side_id`.
+    pub fn new(client_side_id: impl Into<String>) -> Self {
+        Self {
+            client_side_id: client_side_id.into(),
+            base_url: "https://clientsdk.launchdarkly.com".to_string(),
+            cache_ttl: std::time::Duration::from_secs(60),
+            cache: tokio::sync::RwLock::new(HashMap::new()),
+            client: Self::http_client(LAUNCHDARKLY_TIMEOUT),
+        }
+    }
+
+    fn http_client(timeout: std::time::Duration) -> reqwest::Client {
+        reqwest::Client::builder()
+            .connect_timeout(LAUNCHDARKLY_CONNECT_TIMEOUT.min(timeout))
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default()
+    }
+
+    /// Send requests to `base_url` instead, e.g. a LaunchDarkly Relay Proxy.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// How long evaluated flags are reused for the same context.
+    pub fn with_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// How long a tool call waits for LaunchDarkly before giving up.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = Self::http_client(timeout);
+        self
+    }
+
+    /// Fetch the flags for an encoded LaunchDarkly context.
+    async fn fetch(&self, ld_context: &str) -> Result<Flags> {
+        use base64::Engine as _;
+
+        let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(ld_context);
+        let url = format!(
+            "{}/sdk/evalx/{}/contexts/{}",
+            self.base_url, self.client_side_id, encoded
+        );
+        let response =
+            self.client.get(&url).send().await.map_err(|e| {
+                crate::Error::internal(format!("LaunchDarkly request failed: {}", e))
+            })?;
+        if !response.status().is_success() {
+            return Err(crate::Error::internal(format!(
+                "LaunchDarkly returned {}",
+                response.status()
+            )));
+        }
+        let body: HashMap<String, Value> = response
+            .json()
+            .await
+            .map_err(|e| crate::Error::internal(format!("Invalid LaunchDarkly response: {}", e)))?;
+        Ok(body
+            .into_iter()
+            .filter_map(|(name, mut evaluation)| Some((name, evaluation.get_mut("value")?.take())))
+            .collect())
+    }
+
+    /// The LaunchDarkly context for `context`.
+    fn ld_context(context: &FlagContext) -> Value {
+        let mut user = serde_json::Map::new();
+        user.insert("kind".into(), "user".into());
+        match (&context.user_id, &context.session_id) {
+            (Some(user_id), _) => {
+                user.insert("key".into(), user_id.clone().into());
+            },
+            (None, session_id) => {
+                let key = session_id
+                    .clone()
+                    .unwrap_or_else(|| "anonymous".to_string());
+                user.insert("key".into(), key.into());
+                user.insert("anonymous".into(), true.into());
+            },
+        }
+        for (name, value) in &context.attributes {
+            user.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+
+        match &context.tenant_id {
+            Some(tenant_id) if context.user_id.is_some() => {
+                user.remove("kind");
+                serde_json::json!({
+                    "kind": "multi",
+                    "user": user,
+                    "organization": { "key": tenant_id },
+                })
+            },
+            Some(tenant_id) => serde_json::json!({ "kind": "organization", "key": tenant_id }),
+            None => Value::Object(user),
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+#[async_trait]
+impl FlagProvider for LaunchDarklyFlags {
+    async fn evaluate(&self, context: &FlagContext) -> Result<Flags> {
+        let ld_context = Self::ld_context(context).to_string();
+        let cached = self.cache.read().await.get(&ld_context).cloned();
+        if let Some((fetched, flags)) = &cached {
+            if fetched.elapsed() < self.cache_ttl {
+                return Ok(flags.clone());
+            }
+        }
+
+        let flags = match self.fetch(&ld_context).await {
+            Ok(flags) => flags,
+            Err(e) => {
+                let (_, stale) = cached.ok_or(e)?;
+                tracing::warn!("LaunchDarkly refresh failed, reusing cached flags");
+                return Ok(stale);
+            },
+        };
+
+        let mut cache = self.cache.write().await;
+        insert_bounded(
+            &mut cache,
+            ld_context,
+            flags.clone(),
+            self.cache_ttl,
+            MAX_CACHED_CONTEXTS,
+        );
+        Ok(flags)
+    }
+}
+
+/// Cache `flags` for `key`, keeping at most `max` entries.
+///
+/// When full, expired entries are dropped first, then the oldest.
+#[cfg(feature = "http-client")]
+fn insert_bounded(
+    cache: &mut HashMap<String, (std::time::Instant, Flags)>,
+    key: String,
+    flags: Flags,
+    ttl: std::time::Duration,
+    max: usize,
+) {
+    if cache.len() >= max && !cache.contains_key(&key) {
+        cache.retain(|_, (fetched, _)| fetched.elapsed() < ttl);
+        if cache.len() >= max {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, (fetched, _))| *fetched)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+    }
+    cache.insert(key, (std::time::Instant::now(), flags));
+}
+
+/// Evaluate `provider` for the caller of `extra`, logging failures.
+pub(crate) async fn evaluate_for(
+    provider: &dyn FlagProvider,
+    extra: &RequestHandlerExtra,
+) -> Flags {
+    match provider.evaluate(&FlagContext::from_extra(extra)).await {
+        Ok(flags) => flags,
+        Err(e) => {
+            tracing::warn!(
+                "Feature flag evaluation failed, running without flags: {}",
+                e
+            );
+            Flags::new()
+        },
+    }
+}
+
+/// Add `flags` to a widget tool result's `_meta`.
+#[allow(clippy::used_underscore_binding)] // _meta is part of MCP protocol spec
+pub(crate) fn attach_to_result(result: &mut crate::types::CallToolResult, flags: &Flags) {
+    if flags.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(flags) {
+        result
+            ._meta
+            .get_or_insert_with(serde_json::Map::new)
+            .insert(FLAGS_META_KEY.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user(user_id: &str, tenant_id: Option<&str>) -> FlagContext {
+        FlagContext {
+            user_id: Some(user_id.to_string()),
+            tenant_id: tenant_id.map(str::to_string),
+            ..FlagContext::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_static_flags_overrides() {
+        let provider = StaticFlags::new()
+            .flag("search-v2", false)
+            .flag("theme", "light")
+            .tenant_flag("acme", "search-v2", true)
+            .user_flag("bob", "theme", "dark");
+
+        let alice = provider.evaluate(&user("alice", None)).await.unwrap();
+        assert!(!alice.is_enabled("search-v2"));
+        assert_eq!(alice.variant("theme"), Some("light"));
+
+        let bob = provider.evaluate(&user("bob", Some("acme"))).await.unwrap();
+        assert!(bob.is_enabled("search-v2"));
+        assert_eq!(bob.variant("theme"), Some("dark"));
+    }
+
+    #[tokio::test]
+    async fn test_rollout_is_sticky_and_proportional() {
+        let provider = StaticFlags::new().rollout("new-ui", 30);
+        let mut enabled = 0;
+        for i in 0..1000 {
+            let context = user(&format!("user-{}", i), None);
+            let first = provider.evaluate(&context).await.unwrap();
+            let again = provider.evaluate(&context).await.unwrap();
+            assert_eq!(first, again);
+            if first.is_enabled("new-ui") {
+                enabled += 1;
+            }
+        }
+        assert!((250..350).contains(&enabled), "{} of 1000 enabled", enabled);
+
+        let anonymous = provider.evaluate(&FlagContext::default()).await.unwrap();
+        assert!(!anonymous.is_enabled("new-ui"));
+        let everyone = StaticFlags::new().rollout("new-ui", 100);
+        assert!(everyone
+            .evaluate(&user("x", None))
+            .await
+            .unwrap()
+            .is_enabled("new-ui"));
+    }
+
+    #[tokio::test]
+    async fn test_env_flags() {
+        let provider = EnvFlags::from_vars(
+            "PMCP_FLAG_",
+            [
+                ("PMCP_FLAG_SEARCH_V2".to_string(), "true".to_string()),
+                ("PMCP_FLAG_THEME".to_string(), "dark".to_string()),
+                ("PMCP_FLAG_LIMITS".to_string(), r#"{"max": 5}"#.to_string()),
+                ("PATH".to_string(), "/usr/bin".to_string()),
+            ],
+        );
+        let flags = provider.evaluate(&FlagContext::default()).await.unwrap();
+        assert!(flags.is_enabled("search-v2"));
+        assert_eq!(flags.variant("theme"), Some("dark"));
+        assert_eq!(flags.get("limits"), Some(&json!({ "max": 5 })));
+        assert_eq!(flags.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_get_as() {
+        let flags: Flags = [("limit", json!(5)), ("theme", json!("dark"))]
+            .into_iter()
+            .collect();
+        assert_eq!(flags.get_as::<u32>("limit").unwrap(), Some(5));
+        assert_eq!(flags.get_as::<u32>("missing").unwrap(), None);
+        assert!(flags.get_as::<u32>("theme").is_err());
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_launchdarkly_context() {
+        assert_eq!(
+            LaunchDarklyFlags::ld_context(&user("alice", Some("acme"))),
+            json!({
+                "kind": "multi",
+                "user": { "key": "alice" },
+                "organization": { "key": "acme" },
+            })
+        );
+        let anonymous = FlagContext {
+            session_id: Some("s1".to_string()),
+            ..FlagContext::default()
+        };
+        assert_eq!(
+            LaunchDarklyFlags::ld_context(&anonymous),
+            json!({ "kind": "user", "key": "s1", "anonymous": true })
+        );
+    }
+
+    #[cfg(feature = "http-client")]
+    #[tokio::test]
+    async fn test_launchdarkly_evaluates_and_caches() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex("^/sdk/evalx/env-1/contexts/.+$".to_string()),
+            )
+            .with_body(
+                json!({
+                    "search-v2": { "value": true, "variation": 0, "version": 3 },
+                    "theme": { "value": "dark", "variation": 1, "version": 1 }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let provider = LaunchDarklyFlags::new("env-1").with_base_url(server.url());
+        let flags = provider.evaluate(&user("alice", None)).await.unwrap();
+        assert!(flags.is_enabled("search-v2"));
+        assert_eq!(flags.variant("theme"), Some("dark"));
+
+        let cached = provider.evaluate(&user("alice", None)).await.unwrap();
+        assert_eq!(cached, flags);
+        mock.assert_async().await;
+    }
+
+    #[cfg(feature = "http-client")]
+    #[tokio::test]
+    async fn test_launchdarkly_reuses_stale_flags_on_failure() {
+        let mut server = mockito::Server::new_async().await;
+        let ok = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(json!({ "search-v2": { "value": true } }).to_string())
+            .create_async()
+            .await;
+
+        let provider = LaunchDarklyFlags::new("env-1")
+            .with_base_url(server.url())
+            .with_cache_ttl(std::time::Duration::ZERO);
+        let flags = provider.evaluate(&user("alice", None)).await.unwrap();
+        assert!(flags.is_enabled("search-v2"));
+
+        ok.remove_async().await;
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(503)
+            .create_async()
+            .await;
+        let stale = provider.evaluate(&user("alice", None)).await.unwrap();
+        assert_eq!(stale, flags);
+        assert!(provider.evaluate(&user("bob", None)).await.is_err());
+    }
+
+    #[cfg(feature = "http-client")]
+    #[tokio::test]
+    async fn test_launchdarkly_times_out() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _server = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let provider = LaunchDarklyFlags::new("env-1")
+            .with_base_url(format!("http://{}", addr))
+            .with_timeout(std::time::Duration::from_millis(100));
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            provider.evaluate(&user("alice", None)),
+        )
+        .await
+        .expect("evaluation should time out on its own");
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_launchdarkly_cache_is_bounded() {
+        let ttl = std::time::Duration::from_secs(60);
+        let mut cache = HashMap::new();
+        for i in 0..10 {
+            insert_bounded(&mut cache, format!("session-{}", i), Flags::new(), ttl, 4);
+            assert!(cache.len() <= 4);
+        }
+        assert!(cache.contains_key("session-9"));
+
+        let expired = std::time::Instant::now() - 2 * ttl;
+        cache.insert("expired".to_string(), (expired, Flags::new()));
+        insert_bounded(&mut cache, "fresh".to_string(), Flags::new(), ttl, 4);
+        assert!(!cache.contains_key("expired"));
+        assert_eq!(cache.len(), 4);
+    }
+}
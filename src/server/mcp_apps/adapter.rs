@@ -3,7 +3,9 @@
 
 //! UI Adapter implementations for different MCP host platforms.
 
-use crate::types::mcp_apps::{ExtendedUIMimeType, HostType, WidgetCSP, WidgetMeta};
+use crate::types::mcp_apps::{
+    ExtendedUIMimeType, HostType, RemoteDomFramework, WidgetCSP, WidgetMeta,
+};
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -158,11 +160,16 @@ fn find_cdn_import(html: &str, markers: &[&str]) -> Option<(usize, usize)> {
 /// - `ui/initialize` handshake with `hostContext` delivery
 /// - `ui/toolResult` and `ui/hostContextChanged` notifications
 /// - `tools/call` proxy for widget-initiated tool calls
+/// - `window.mcpFlags` from the `pmcp/flags` key of each tool result's `_meta`
+/// - `app.navigate()` / `app.navigateBack()`, `pmcp-widget:` link clicks, and
+///   `window.mcpNavigation` (see [`WidgetLink`](super::WidgetLink))
 const EXT_APPS_SHIM: &str = r"
 // Inline ext-apps App shim (replaces CDN import for hosts that block external scripts)
 const _extPending = new Map();
 let _extNextId = 1;
 let _extApp = null;
+window.mcpFlags = window.mcpFlags || {};
+window.mcpNavigation = window.mcpNavigation || null;
 
 function _extSend(method, params) {
   return new Promise((resolve, reject) => {
@@ -183,6 +190,10 @@ window.addEventListener('message', (e) => {
   }
   if (!_extApp) return;
   if (d.method === 'ui/toolResult' || d.method === 'ui/notifications/tool-result') {
+    const flags = d.params && d.params._meta && d.params._meta['pmcp/flags'];
+    if (flags) window.mcpFlags = flags;
+    const nav = d.params && d.params._meta && d.params._meta['pmcp/navigation'];
+    if (nav) window.mcpNavigation = nav;
     if (_extApp.ontoolresult) _extApp.ontoolresult(d.params);
   } else if (d.method === 'ui/hostContextChanged' || d.method === 'ui/notifications/host-context-changed') {
     if (d.params) _extApp._hc = Object.assign(_extApp._hc || {}, d.params);
@@ -190,6 +201,23 @@ window.addEventListener('message', (e) => {
   }
 });
 
+function _extParseLink(href) {
+  const m = /^pmcp-widget:([^?#]+)(?:\?([^#]*))?/.exec(href || '');
+  if (!m) return null;
+  const q = new URLSearchParams(m[2] || '');
+  try {
+    return { tool: decodeURIComponent(m[1]), arguments: JSON.parse(q.get('args') || '{}'), state: q.has('state') ? JSON.parse(q.get('state')) : undefined };
+  } catch (_) { return null; }
+}
+
+document.addEventListener('click', (e) => {
+  const a = e.target && e.target.closest && e.target.closest('a[href]');
+  const p = a && _extParseLink(a.getAttribute('href'));
+  if (!p || !_extApp) return;
+  e.preventDefault();
+  _extApp.navigate(p);
+});
+
 class App {
   constructor(info, caps) {
     this._info = info || {};
@@ -207,13 +235,51 @@ class App {
         new Promise(resolve => setTimeout(() => resolve(null), 2000))
       ]);
       if (r && r.hostContext) this._hc = r.hostContext;
+      if (this._hc && this._hc.navigation) window.mcpNavigation = this._hc.navigation;
     } catch (_) { /* host may not support ui/initialize */ }
   }
   getHostContext() { return this._hc; }
   async callServerTool(params) { return _extSend('tools/call', params); }
+  async navigate(params) { return _extSend('ui/navigate', params); }
+  async navigateBack() { return _extSend('ui/navigate', { back: true }); }
 }
 ";
 
+/// Follows clicks on `pmcp-widget:` deep links with `window.mcpBridge.navigate`.
+///
+/// Injected after the ChatGPT and MCP-UI bridges; the ext-apps shim carries
+/// its own copy. See [`WidgetLink`](super::WidgetLink) for the link format.
+const WIDGET_LINK_SCRIPT: &str = r#"
+<script>
+(function() {
+    'use strict';
+
+    function parseWidgetLink(href) {
+        var m = /^pmcp-widget:([^?#]+)(?:\?([^#]*))?/.exec(href || '');
+        if (!m) return null;
+        var query = new URLSearchParams(m[2] || '');
+        try {
+            return {
+                tool: decodeURIComponent(m[1]),
+                arguments: JSON.parse(query.get('args') || '{}'),
+                state: query.has('state') ? JSON.parse(query.get('state')) : undefined
+            };
+        } catch (e) {
+            return null;
+        }
+    }
+
+    document.addEventListener('click', function(event) {
+        var link = event.target && event.target.closest && event.target.closest('a[href]');
+        var params = link && parseWidgetLink(link.getAttribute('href'));
+        if (!params || !window.mcpBridge?.navigate) return;
+        event.preventDefault();
+        window.mcpBridge.navigate(params.tool, params.arguments, params.state);
+    });
+})();
+</script>
+"#;
+
 /// Inject a script block into HTML, preferring before `</head>`.
 ///
 /// Tries three strategies in order:
@@ -231,6 +297,24 @@ fn inject_script_into_head(html: &str, script: &str) -> String {
     }
 }
 
+/// Script-free page shown in place of a widget rejected by strict CSP.
+fn csp_error_page(uri: &str, message: &str) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    format!(
+        "<!DOCTYPE html><html><head><meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'\"></head>\
+         <body><h1>Widget blocked by Content-Security-Policy</h1><p>{}</p><pre>{}</pre></body></html>",
+        escape(uri),
+        escape(message)
+    )
+}
+
+/// Resource `_meta` keys carrying a widget's CSP and iframe sandbox policy.
+const WIDGET_SECURITY_KEYS: &[&str] = &["openai/widgetCSP", "openai/widgetSandbox"];
+
 /// Adapter for ChatGPT Apps (OpenAI Apps SDK).
 ///
 /// Transforms resources to use `text/html;profile=mcp-app` MIME type and
@@ -239,6 +323,12 @@ fn inject_script_into_head(html: &str, script: &str) -> String {
 pub struct ChatGptAdapter {
     /// Optional widget metadata for ChatGPT.
     pub widget_meta: Option<WidgetMeta>,
+    /// Stamp a fresh CSP nonce on every transform and reject widgets that
+    /// need `'unsafe-inline'`. See [`ChatGptAdapter::with_strict_csp`].
+    pub strict_csp: bool,
+    /// SRI values by remote script/stylesheet URL. Empty disables SRI.
+    /// See [`ChatGptAdapter::with_subresource_integrity`].
+    pub integrity: HashMap<String, String>,
 }
 
 impl ChatGptAdapter {
@@ -254,19 +344,92 @@ impl ChatGptAdapter {
         self.widget_meta = Some(meta);
         self
     }
-}
 
-impl UIAdapter for ChatGptAdapter {
-    fn host_type(&self) -> HostType {
-        HostType::ChatGpt
+    /// Serve widgets under a strict, nonce-based Content-Security-Policy.
+    ///
+    /// Each transform generates a new nonce, stamps it on every `<script>`
+    /// and `<style>` (including the injected bridge), and adds a matching
+    /// `<meta http-equiv="Content-Security-Policy">` built from the widget's
+    /// declared [`WidgetCSP`] domains.
+    ///
+    /// Widgets with inline event handlers, `style` attributes, or
+    /// `javascript:` URLs cannot run under this policy. [`Self::try_transform`]
+    /// reports them as an error; [`UIAdapter::transform`] logs the error and
+    /// serves an error page listing the offending markup instead of a widget
+    /// that would silently break in the host.
+    ///
+    /// Nonces are per response, so call the adapter for each `resources/read`
+    /// rather than caching its output (for example in `MultiPlatformResource`).
+    #[must_use]
+    pub fn with_strict_csp(mut self) -> Self {
+        self.strict_csp = true;
+        self
     }
 
-    fn mime_type(&self) -> ExtendedUIMimeType {
-        ExtendedUIMimeType::HtmlSkybridge
+    /// Pin remote scripts and stylesheets with Subresource Integrity hashes.
+    ///
+    /// Each `<script src>` and stylesheet/preload `<link href>` whose URL has
+    /// an entry gets `integrity` and `crossorigin="anonymous"` attributes, so
+    /// the browser refuses the file if the CDN serves different content.
+    /// Compute values with [`sri_hash`](super::sri_hash) from a vetted copy of
+    /// each file; remote URLs without an entry are logged and left unpinned.
+    ///
+    /// ```rust
+    /// use pmcp::server::mcp_apps::ChatGptAdapter;
+    ///
+    /// let adapter = ChatGptAdapter::new().with_subresource_integrity([(
+    ///     "https://cdn.jsdelivr.net/npm/chart.js@4.4.1",
+    ///     "sha384-...",
+    /// )]);
+    /// ```
+    #[must_use]
+    pub fn with_subresource_integrity<U, H>(
+        mut self,
+        hashes: impl IntoIterator<Item = (U, H)>,
+    ) -> Self
+    where
+        U: Into<String>,
+        H: Into<String>,
+    {
+        self.integrity
+            .extend(hashes.into_iter().map(|(u, h)| (u.into(), h.into())));
+        self
     }
 
-    fn transform(&self, uri: &str, name: &str, html: &str) -> TransformedResource {
-        let injected_html = self.inject_bridge(html);
+    /// Transform `html`, failing if strict CSP is enabled and the widget
+    /// requires `'unsafe-inline'`.
+    ///
+    /// Without strict CSP this is equivalent to [`UIAdapter::transform`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error listing every construct that needs
+    /// `'unsafe-inline'`.
+    pub fn try_transform(
+        &self,
+        uri: &str,
+        name: &str,
+        html: &str,
+    ) -> crate::Result<TransformedResource> {
+        let mut transformed = self.transform_unchecked(uri, name, html);
+        if self.strict_csp {
+            let csp = self.widget_meta.as_ref().and_then(|wm| wm.csp.as_ref());
+            transformed.content = super::csp::apply_csp_nonce(
+                &transformed.content,
+                &super::csp::generate_nonce(),
+                csp,
+            )?
+            .html;
+        }
+        Ok(transformed)
+    }
+
+    fn transform_unchecked(&self, uri: &str, name: &str, html: &str) -> TransformedResource {
+        let injected_html = if self.integrity.is_empty() {
+            self.inject_bridge(html)
+        } else {
+            self.inject_bridge(&self.apply_integrity(uri, html))
+        };
 
         // Build ChatGPT descriptor metadata.
         // Start with any descriptor keys from widget_meta (e.g., openai/widgetAccessible),
@@ -281,6 +444,16 @@ impl UIAdapter for ChatGptAdapter {
             })
             .unwrap_or_default();
 
+        // Security policy keys let ChatGPT enforce the widget's CSP and sandbox.
+        if let Some(wm) = &self.widget_meta {
+            let meta = wm.to_meta_map();
+            for key in WIDGET_SECURITY_KEYS {
+                if let Some(value) = meta.get(*key) {
+                    metadata.insert((*key).to_string(), value.clone());
+                }
+            }
+        }
+
         // ChatGptAdapter always emits openai/outputTemplate from the resource URI
         metadata
             .entry("openai/outputTemplate".to_string())
@@ -295,6 +468,42 @@ impl UIAdapter for ChatGptAdapter {
         }
     }
 
+    fn apply_integrity(&self, uri: &str, html: &str) -> String {
+        pmcp_widget_utils::add_subresource_integrity(html, |url| {
+            let hash = self.integrity.get(url).cloned();
+            if hash.is_none() {
+                tracing::warn!(
+                    uri,
+                    url,
+                    "No SRI hash configured for remote widget resource"
+                );
+            }
+            hash
+        })
+    }
+}
+
+impl UIAdapter for ChatGptAdapter {
+    fn host_type(&self) -> HostType {
+        HostType::ChatGpt
+    }
+
+    fn mime_type(&self) -> ExtendedUIMimeType {
+        ExtendedUIMimeType::HtmlSkybridge
+    }
+
+    fn transform(&self, uri: &str, name: &str, html: &str) -> TransformedResource {
+        match self.try_transform(uri, name, html) {
+            Ok(transformed) => transformed,
+            Err(e) => {
+                tracing::error!(uri, "{}", e);
+                let mut transformed = self.transform_unchecked(uri, name, "");
+                transformed.content = csp_error_page(uri, &e.to_string());
+                transformed
+            },
+        }
+    }
+
     fn inject_bridge(&self, html: &str) -> String {
         // ChatGPT Apps bridge script - Full OpenAI Apps SDK alignment
         let bridge_script = r#"
@@ -401,6 +610,16 @@ impl UIAdapter for ChatGptAdapter {
             return window.openai?.toolResponseMetadata ?? {};
         },
 
+        // Feature flags evaluated for this user (pmcp/flags in _meta)
+        get flags() {
+            return window.openai?.toolResponseMetadata?.['pmcp/flags'] ?? {};
+        },
+
+        // Whether a feature flag is set to true
+        isFlagEnabled: (name) => {
+            return window.mcpBridge.flags[name] === true;
+        },
+
         // ========================================
         // Communication
         // ========================================
@@ -415,6 +634,34 @@ impl UIAdapter for ChatGptAdapter {
             window.openai?.openExternal?.({ href: url });
         },
 
+        // ========================================
+        // Navigation
+        // ========================================
+
+        // Open the widget of another tool on this server. ChatGPT has no
+        // widget navigation, so ask the model to call the tool instead.
+        navigate: async (tool, args, state) => {
+            var params = { tool: tool, arguments: args || {}, state: state };
+            if (window.openai?.pmcpNavigate) {
+                return window.openai.pmcpNavigate(params);
+            }
+            window.openai?.sendFollowUpMessage?.({
+                prompt: 'Call the ' + tool + ' tool with ' + JSON.stringify(params.arguments)
+            });
+        },
+
+        // Return to the widget that navigated here
+        navigateBack: async () => {
+            if (window.openai?.pmcpNavigate) {
+                return window.openai.pmcpNavigate({ back: true });
+            }
+        },
+
+        // Handoff from the widget that navigated here (pmcp/navigation in _meta)
+        get navigation() {
+            return window.openai?.toolResponseMetadata?.['pmcp/navigation'] ?? null;
+        },
+
         // ========================================
         // File Operations
         // ========================================
@@ -501,13 +748,19 @@ impl UIAdapter for ChatGptAdapter {
         }
     };
 
+    Object.defineProperty(window, 'mcpFlags', {
+        get: () => window.mcpBridge.flags,
+        configurable: true
+    });
+
     // Dispatch ready event for widgets waiting on bridge
     window.dispatchEvent(new Event('mcpBridgeReady'));
 })();
 </script>
 "#;
 
-        inject_script_into_head(html, bridge_script)
+        let html = inject_script_into_head(html, bridge_script);
+        inject_script_into_head(&html, WIDGET_LINK_SCRIPT)
     }
 
     fn required_csp(&self) -> Option<WidgetCSP> {
@@ -524,6 +777,8 @@ impl UIAdapter for ChatGptAdapter {
 pub struct McpAppsAdapter {
     /// Optional Content Security Policy.
     pub csp: Option<WidgetCSP>,
+    /// Iframe sandbox flags the widget needs.
+    pub sandbox: Vec<String>,
 }
 
 impl McpAppsAdapter {
@@ -539,6 +794,36 @@ impl McpAppsAdapter {
         self.csp = Some(csp);
         self
     }
+
+    /// Add an iframe sandbox flag (e.g. `allow-forms`) the widget needs.
+    #[must_use]
+    pub fn with_sandbox(mut self, flag: impl Into<String>) -> Self {
+        let flag = flag.into();
+        if !self.sandbox.contains(&flag) {
+            self.sandbox.push(flag);
+        }
+        self
+    }
+
+    /// Resource `_meta` declaring the widget's security policy as `ui.csp`
+    /// and `ui.sandbox`.
+    fn security_meta(&self) -> HashMap<String, Value> {
+        let mut ui = serde_json::Map::new();
+        if let Some(csp) = &self.csp {
+            let csp = csp.to_spec_map();
+            if !csp.is_empty() {
+                ui.insert("csp".to_string(), Value::Object(csp));
+            }
+        }
+        if !self.sandbox.is_empty() {
+            ui.insert("sandbox".to_string(), serde_json::json!(self.sandbox));
+        }
+        let mut metadata = HashMap::new();
+        if !ui.is_empty() {
+            metadata.insert("ui".to_string(), Value::Object(ui));
+        }
+        metadata
+    }
 }
 
 impl UIAdapter for McpAppsAdapter {
@@ -561,7 +846,7 @@ impl UIAdapter for McpAppsAdapter {
             name: name.to_string(),
             mime_type: self.mime_type(),
             content: inline_ext_apps_shim(html).into_owned(),
-            metadata: HashMap::new(),
+            metadata: self.security_meta(),
         }
     }
 
@@ -572,13 +857,53 @@ impl UIAdapter for McpAppsAdapter {
     }
 }
 
+/// `_meta` key for the frame size an MCP-UI host should render the resource at.
+const MCP_UI_FRAME_SIZE_KEY: &str = "mcpui.dev/ui-preferred-frame-size";
+
+/// `_meta` key for data an MCP-UI host passes to the resource on first render.
+const MCP_UI_INITIAL_DATA_KEY: &str = "mcpui.dev/ui-initial-render-data";
+
 /// Adapter for MCP-UI (community standard).
 ///
-/// Supports multiple output formats including HTML, URLs, and Remote DOM.
+/// Supports the three MCP-UI content forms:
+///
+/// | Format | MIME type | Content |
+/// |--------|-----------|---------|
+/// | [`McpUiFormat::Html`] | `text/html` | HTML with the postMessage bridge |
+/// | [`McpUiFormat::Url`] | `text/uri-list` | External URL loaded in an iframe |
+/// | [`McpUiFormat::RemoteDom`] | `application/vnd.mcp-ui.remote-dom+javascript` | Remote DOM script |
+///
+/// For the URL and Remote DOM formats, the content passed to
+/// [`transform`](UIAdapter::transform) is used as the URL or script, unless
+/// one was set with [`with_external_url`](Self::with_external_url) or
+/// [`with_remote_dom`](Self::with_remote_dom).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pmcp::server::mcp_apps::{McpUiAdapter, UIAdapter};
+/// use pmcp::types::mcp_apps::RemoteDomFramework;
+///
+/// let script = "const button = document.createElement('ui-button'); root.appendChild(button);";
+/// let adapter = McpUiAdapter::new()
+///     .with_remote_dom(script, RemoteDomFramework::React)
+///     .with_preferred_frame_size("800px", "600px");
+/// let transformed = adapter.transform("ui://counter", "Counter", "");
+/// ```
 #[derive(Debug, Clone, Default)]
 pub struct McpUiAdapter {
     /// Preferred output format.
     pub preferred_format: McpUiFormat,
+    /// URL served in [`McpUiFormat::Url`] form.
+    pub external_url: Option<String>,
+    /// Script served in [`McpUiFormat::RemoteDom`] form.
+    pub remote_dom_script: Option<String>,
+    /// Component framework the Remote DOM script targets.
+    pub remote_dom_framework: RemoteDomFramework,
+    /// Preferred frame width and height as CSS lengths (e.g. `"800px"`).
+    pub preferred_frame_size: Option<(String, String)>,
+    /// Data passed to the resource on first render.
+    pub initial_render_data: Option<Value>,
 }
 
 /// MCP-UI output formats.
@@ -587,9 +912,9 @@ pub enum McpUiFormat {
     /// HTML with postMessage bridge.
     #[default]
     Html,
-    /// URL reference (for CDN-hosted widgets).
+    /// External URL (`externalUrl`), for widgets hosted elsewhere.
     Url,
-    /// Remote DOM (for non-iframe rendering).
+    /// Remote DOM script (`remoteDom`), rendered with the host's own components.
     RemoteDom,
 }
 
@@ -606,6 +931,59 @@ impl McpUiAdapter {
         self.preferred_format = format;
         self
     }
+
+    /// Serve an external URL (`text/uri-list`) instead of inline HTML.
+    #[must_use]
+    pub fn with_external_url(mut self, url: impl Into<String>) -> Self {
+        self.preferred_format = McpUiFormat::Url;
+        self.external_url = Some(url.into());
+        self
+    }
+
+    /// Serve a Remote DOM script for `framework` instead of inline HTML.
+    #[must_use]
+    pub fn with_remote_dom(
+        mut self,
+        script: impl Into<String>,
+        framework: RemoteDomFramework,
+    ) -> Self {
+        self.preferred_format = McpUiFormat::RemoteDom;
+        self.remote_dom_script = Some(script.into());
+        self.remote_dom_framework = framework;
+        self
+    }
+
+    /// Ask the host to render the resource at this size (CSS lengths).
+    #[must_use]
+    pub fn with_preferred_frame_size(
+        mut self,
+        width: impl Into<String>,
+        height: impl Into<String>,
+    ) -> Self {
+        self.preferred_frame_size = Some((width.into(), height.into()));
+        self
+    }
+
+    /// Pass `data` to the resource when the host first renders it.
+    #[must_use]
+    pub fn with_initial_render_data(mut self, data: Value) -> Self {
+        self.initial_render_data = Some(data);
+        self
+    }
+
+    fn metadata(&self) -> HashMap<String, Value> {
+        let mut metadata = HashMap::new();
+        if let Some((width, height)) = &self.preferred_frame_size {
+            metadata.insert(
+                MCP_UI_FRAME_SIZE_KEY.to_string(),
+                serde_json::json!([width, height]),
+            );
+        }
+        if let Some(data) = &self.initial_render_data {
+            metadata.insert(MCP_UI_INITIAL_DATA_KEY.to_string(), data.clone());
+        }
+        metadata
+    }
 }
 
 impl UIAdapter for McpUiAdapter {
@@ -614,23 +992,29 @@ impl UIAdapter for McpUiAdapter {
     }
 
     fn mime_type(&self) -> ExtendedUIMimeType {
-        match self.preferred_format {
-            McpUiFormat::Html => ExtendedUIMimeType::HtmlPlain,
-            McpUiFormat::Url => ExtendedUIMimeType::UriList,
-            McpUiFormat::RemoteDom => ExtendedUIMimeType::RemoteDom,
+        match (&self.preferred_format, self.remote_dom_framework) {
+            (McpUiFormat::Html, _) => ExtendedUIMimeType::HtmlPlain,
+            (McpUiFormat::Url, _) => ExtendedUIMimeType::UriList,
+            (McpUiFormat::RemoteDom, RemoteDomFramework::WebComponents) => {
+                ExtendedUIMimeType::RemoteDom
+            },
+            (McpUiFormat::RemoteDom, RemoteDomFramework::React) => {
+                ExtendedUIMimeType::RemoteDomReact
+            },
         }
     }
 
     fn transform(&self, uri: &str, name: &str, html: &str) -> TransformedResource {
         let content = match self.preferred_format {
             McpUiFormat::Html => self.inject_bridge(html),
-            McpUiFormat::Url => uri.to_string(),
-            McpUiFormat::RemoteDom => {
-                format!(
-                    r#"{{"type":"remote-dom","uri":"{}","name":"{}"}}"#,
-                    uri, name
-                )
-            },
+            McpUiFormat::Url => self
+                .external_url
+                .clone()
+                .unwrap_or_else(|| html.trim().to_string()),
+            McpUiFormat::RemoteDom => self
+                .remote_dom_script
+                .clone()
+                .unwrap_or_else(|| html.to_string()),
         };
 
         TransformedResource {
@@ -638,7 +1022,7 @@ impl UIAdapter for McpUiAdapter {
             name: name.to_string(),
             mime_type: self.mime_type(),
             content,
-            metadata: HashMap::new(),
+            metadata: self.metadata(),
         }
     }
 
@@ -666,6 +1050,8 @@ impl UIAdapter for McpUiAdapter {
         }
 
         if (msg.method && !msg.id) {
+            const nav = msg.params && msg.params._meta && msg.params._meta['pmcp/navigation'];
+            if (nav) window.mcpBridge.navigation = nav;
             window.dispatchEvent(new CustomEvent('mcpNotification', { detail: msg }));
         }
     });
@@ -690,6 +1076,12 @@ impl UIAdapter for McpUiAdapter {
         readResource: (uri) => sendRequest('resources/read', { uri }),
         getPrompt: (name, args) => sendRequest('prompts/get', { name, arguments: args }),
 
+        // Widget navigation
+        navigate: (tool, args, state) =>
+            sendRequest('ui/navigate', { tool, arguments: args || {}, state }),
+        navigateBack: () => sendRequest('ui/navigate', { back: true }),
+        navigation: null,
+
         // MCP-UI specific
         sendIntent: (action, data) => sendRequest('ui/intent', { action, data }),
         notify: (level, message) => {
@@ -713,7 +1105,8 @@ impl UIAdapter for McpUiAdapter {
 </script>
 ";
 
-        inject_script_into_head(html, bridge_script)
+        let html = inject_script_into_head(html, bridge_script);
+        inject_script_into_head(&html, WIDGET_LINK_SCRIPT)
     }
 
     fn required_csp(&self) -> Option<WidgetCSP> {
@@ -737,6 +1130,125 @@ mod tests {
         assert!(transformed.content.contains("window.openai"));
     }
 
+    #[test]
+    fn test_bridges_expose_feature_flags() {
+        let html = "<html><head></head><body>Hello</body></html>";
+        let transformed = ChatGptAdapter::new().transform("ui://test/widget.html", "Test", html);
+        assert!(transformed.content.contains("get flags()"));
+        assert!(transformed.content.contains("isFlagEnabled"));
+        assert!(transformed.content.contains("'pmcp/flags'"));
+
+        let shim = inline_ext_apps_shim(
+            r#"<script type="module">import { App } from "https://esm.sh/@modelcontextprotocol/ext-apps";</script>"#,
+        );
+        assert!(shim.contains("window.mcpFlags = flags"));
+    }
+
+    #[test]
+    fn test_bridges_support_widget_navigation() {
+        let html = "<html><head></head><body>Hello</body></html>";
+        for content in [
+            ChatGptAdapter::new()
+                .transform("ui://test/map.html", "Map", html)
+                .content,
+            McpUiAdapter::new()
+                .transform("ui://test/map.html", "Map", html)
+                .content,
+        ] {
+            assert!(content.contains("navigate: "));
+            assert!(content.contains("navigateBack"));
+            assert!(content.contains("parseWidgetLink(link.getAttribute('href'))"));
+        }
+
+        let shim = inline_ext_apps_shim(
+            r#"<script type="module">import { App } from "https://esm.sh/@modelcontextprotocol/ext-apps";</script>"#,
+        );
+        assert!(shim.contains("_extSend('ui/navigate', params)"));
+        assert!(shim.contains("window.mcpNavigation = nav"));
+    }
+
+    #[test]
+    fn test_chatgpt_adapter_subresource_integrity() {
+        let html = r#"<html><head><script src="https://cdn.example.com/chart.js"></script><script src="https://cdn.example.com/other.js"></script></head><body></body></html>"#;
+
+        let plain = ChatGptAdapter::new().transform("ui://test/widget.html", "Test", html);
+        assert!(!plain.content.contains("integrity="));
+
+        let adapter = ChatGptAdapter::new()
+            .with_subresource_integrity([("https://cdn.example.com/chart.js", "sha384-abc")]);
+        let transformed = adapter.transform("ui://test/widget.html", "Test", html);
+
+        assert!(transformed.content.contains(
+            r#"<script src="https://cdn.example.com/chart.js" integrity="sha384-abc" crossorigin="anonymous"></script>"#
+        ));
+        assert!(transformed
+            .content
+            .contains(r#"<script src="https://cdn.example.com/other.js"></script>"#));
+    }
+
+    #[test]
+    fn test_chatgpt_adapter_strict_csp_nonces_bridge() {
+        let adapter = ChatGptAdapter::new().with_strict_csp();
+        let html = "<html><head><style>p{}</style></head><body><script>go()</script></body></html>";
+
+        let first = adapter
+            .try_transform("ui://test/widget.html", "Test Widget", html)
+            .unwrap();
+        let second = adapter
+            .try_transform("ui://test/widget.html", "Test Widget", html)
+            .unwrap();
+
+        let nonce = first
+            .content
+            .split("'nonce-")
+            .nth(1)
+            .and_then(|rest| rest.split('\'').next())
+            .unwrap();
+        // Bridge and deep-link scripts, widget style and widget script
+        assert_eq!(
+            first
+                .content
+                .matches(&format!("nonce=\"{}\"", nonce))
+                .count(),
+            4
+        );
+        assert!(!first.content.contains("unsafe-inline"));
+        assert!(!second.content.contains(nonce));
+    }
+
+    #[test]
+    fn test_chatgpt_adapter_strict_csp_rejects_inline_handlers() {
+        let adapter = ChatGptAdapter::new().with_strict_csp();
+        let html = "<html><body><button onclick=\"go()\">Go</button></body></html>";
+
+        let err = adapter
+            .try_transform("ui://test/widget.html", "Test Widget", html)
+            .unwrap_err();
+        assert!(err.to_string().contains("onclick"));
+
+        let fallback = adapter.transform("ui://test/widget.html", "Test Widget", html);
+        assert!(fallback
+            .content
+            .contains("blocked by Content-Security-Policy"));
+        assert!(!fallback.content.contains("<script"));
+        assert_eq!(
+            fallback.metadata.get("openai/outputTemplate"),
+            Some(&Value::String("ui://test/widget.html".into()))
+        );
+    }
+
+    #[test]
+    fn test_chatgpt_adapter_without_strict_csp_allows_inline() {
+        let adapter = ChatGptAdapter::new();
+        let html = "<html><body><button onclick=\"go()\">Go</button></body></html>";
+
+        let transformed = adapter
+            .try_transform("ui://test/widget.html", "Test Widget", html)
+            .unwrap();
+        assert!(transformed.content.contains("onclick"));
+        assert!(!transformed.content.contains("Content-Security-Policy"));
+    }
+
     #[test]
     fn test_mcp_apps_adapter_transform() {
         let adapter = McpAppsAdapter::new();
@@ -747,6 +1259,28 @@ mod tests {
         assert_eq!(transformed.mime_type, ExtendedUIMimeType::HtmlMcpApp);
     }
 
+    #[test]
+    fn test_mcp_apps_adapter_emits_security_meta() {
+        let adapter = McpAppsAdapter::new()
+            .with_csp(
+                WidgetCSP::new()
+                    .connect("https://api.example.com")
+                    .frame_ancestor("https://claude.ai"),
+            )
+            .with_sandbox("allow-forms");
+        let html = "<html><body></body></html>";
+
+        let transformed = adapter.transform("ui://test/widget.html", "Test Widget", html);
+
+        let ui = &transformed.metadata["ui"];
+        assert_eq!(ui["csp"]["connectDomains"][0], "https://api.example.com");
+        assert_eq!(ui["csp"]["frameAncestors"][0], "https://claude.ai");
+        assert_eq!(ui["sandbox"], serde_json::json!(["allow-forms"]));
+
+        let plain = McpAppsAdapter::new().transform("ui://test/widget.html", "Test Widget", html);
+        assert!(plain.metadata.is_empty());
+    }
+
     #[test]
     fn test_mcp_ui_adapter_transform() {
         let adapter = McpUiAdapter::new();
@@ -755,6 +1289,58 @@ mod tests {
         let transformed = adapter.transform("ui://test/widget.html", "Test Widget", html);
 
         assert!(transformed.content.contains("mcpBridge"));
+        assert!(transformed.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_mcp_ui_adapter_external_url() {
+        let adapter = McpUiAdapter::new().with_external_url("https://widgets.example.com/chart");
+        let transformed = adapter.transform("ui://test/chart", "Chart", "<html></html>");
+
+        assert_eq!(transformed.mime_type, ExtendedUIMimeType::UriList);
+        assert_eq!(transformed.content, "https://widgets.example.com/chart");
+
+        // Without a configured URL, the resource content is the URL
+        let adapter = McpUiAdapter::new().with_format(McpUiFormat::Url);
+        let transformed = adapter.transform("ui://test/chart", "Chart", " https://a.example/\n");
+        assert_eq!(transformed.content, "https://a.example/");
+    }
+
+    #[test]
+    fn test_mcp_ui_adapter_remote_dom() {
+        let script = "const b = document.createElement('ui-button'); root.appendChild(b);";
+
+        let adapter = McpUiAdapter::new().with_remote_dom(script, RemoteDomFramework::React);
+        let transformed = adapter.transform("ui://test/button", "Button", "");
+        assert_eq!(transformed.mime_type, ExtendedUIMimeType::RemoteDomReact);
+        assert_eq!(
+            transformed.mime_type.as_str(),
+            "application/vnd.mcp-ui.remote-dom+javascript; framework=react"
+        );
+        assert_eq!(transformed.content, script);
+
+        let adapter = McpUiAdapter::new().with_format(McpUiFormat::RemoteDom);
+        let transformed = adapter.transform("ui://test/button", "Button", script);
+        assert_eq!(transformed.mime_type, ExtendedUIMimeType::RemoteDom);
+        assert_eq!(transformed.content, script);
+    }
+
+    #[test]
+    fn test_mcp_ui_adapter_metadata() {
+        let adapter = McpUiAdapter::new()
+            .with_preferred_frame_size("800px", "600px")
+            .with_initial_render_data(serde_json::json!({ "theme": "dark" }));
+        let mut transformed = adapter.transform("ui://test/widget", "Widget", "<html></html>");
+
+        let meta = transformed.take_meta().unwrap();
+        assert_eq!(
+            meta["mcpui.dev/ui-preferred-frame-size"],
+            serde_json::json!(["800px", "600px"])
+        );
+        assert_eq!(
+            meta["mcpui.dev/ui-initial-render-data"],
+            serde_json::json!({ "theme": "dark" })
+        );
     }
 
     #[test]
@@ -811,6 +1397,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chatgpt_adapter_with_widget_meta_keeps_security_keys() {
+        let meta = WidgetMeta::new()
+            .csp(WidgetCSP::new().frame_ancestor("https://chatgpt.com"))
+            .sandbox("allow-popups");
+
+        let adapter = ChatGptAdapter::new().with_widget_meta(meta);
+        let html = "<html><body></body></html>";
+        let transformed = adapter.transform("ui://test/widget.html", "Test Widget", html);
+
+        assert_eq!(
+            transformed.metadata["openai/widgetCSP"]["frame_ancestors"][0],
+            "https://chatgpt.com"
+        );
+        assert_eq!(
+            transformed.metadata["openai/widgetSandbox"],
+            serde_json::json!(["allow-popups"])
+        );
+    }
+
     #[test]
     fn test_bridge_injection_with_head() {
         let adapter = ChatGptAdapter::new();
@@ -236,6 +236,13 @@ impl UIResourceBuilder {
         self
     }
 
+    /// Add an iframe sandbox flag (e.g. `allow-forms`) the widget needs.
+    #[must_use]
+    pub fn widget_sandbox(mut self, flag: impl Into<String>) -> Self {
+        self.widget_meta = self.widget_meta.sandbox(flag);
+        self
+    }
+
     // =========================================================================
     // CSP configuration
     // =========================================================================
@@ -261,6 +268,13 @@ impl UIResourceBuilder {
         self
     }
 
+    /// Add an origin allowed to embed the widget (`frame-ancestors`).
+    #[must_use]
+    pub fn csp_frame_ancestor(mut self, origin: impl Into<String>) -> Self {
+        self.csp = self.csp.frame_ancestor(origin);
+        self
+    }
+
     // =========================================================================
     // Build methods
     // =========================================================================
@@ -287,26 +301,33 @@ impl UIResourceBuilder {
     /// Build a `MultiPlatformResource` for MCP Apps only.
     #[must_use]
     pub fn build_mcp_apps(self) -> MultiPlatformResource {
-        let csp = self.csp.clone();
+        let adapter = self.mcp_apps_adapter();
         let html = self.html.clone().unwrap_or_default();
 
-        MultiPlatformResource::new(&self.uri, &self.name, html)
-            .with_adapter(McpAppsAdapter::new().with_csp(csp))
+        MultiPlatformResource::new(&self.uri, &self.name, html).with_adapter(adapter)
     }
 
     /// Build a `MultiPlatformResource` for all platforms.
     #[must_use]
     pub fn build_all(self) -> MultiPlatformResource {
         let widget_meta = self.widget_meta.clone();
-        let csp = self.csp.clone();
+        let mcp_apps = self.mcp_apps_adapter();
         let html = self.html.clone().unwrap_or_default();
 
         MultiPlatformResource::new(&self.uri, &self.name, html)
             .with_adapter(ChatGptAdapter::new().with_widget_meta(widget_meta))
-            .with_adapter(McpAppsAdapter::new().with_csp(csp))
+            .with_adapter(mcp_apps)
             .with_adapter(McpUiAdapter::new())
     }
 
+    fn mcp_apps_adapter(&self) -> McpAppsAdapter {
+        let mut adapter = McpAppsAdapter::new().with_csp(self.csp.clone());
+        for flag in self.widget_meta.sandbox.iter().flatten() {
+            adapter = adapter.with_sandbox(flag.clone());
+        }
+        adapter
+    }
+
     /// Get the ChatGPT tool metadata for use in tool definitions.
     #[must_use]
     pub fn chatgpt_tool_meta(&self) -> &ChatGptToolMeta {
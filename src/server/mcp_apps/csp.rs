@@ -0,0 +1,484 @@
+//! Strict Content-Security-Policy support for widget HTML.
+//!
+//! [`apply_csp_nonce`] stamps a per-response nonce on every `<script>`,
+//! `<style>`, and stylesheet `<link>` in a widget (including scripts an
+//! adapter injected), and prepends a matching
+//! `<meta http-equiv="Content-Security-Policy">` to `<head>`. The resulting
+//! policy never needs `'unsafe-inline'`.
+//!
+//! Markup that only works under `'unsafe-inline'` — inline event handler
+//! attributes (`onclick="..."`), `style="..."` attributes, and `javascript:`
+//! URLs — cannot be nonced, so it is rejected up front with
+//! [`find_inline_violations`] instead of silently breaking in the host.
+
+use crate::types::mcp_apps::WidgetCSP;
+use base64::Engine;
+use std::fmt;
+
+/// Generate a fresh CSP nonce (base64 of 128 random bits).
+///
+/// Use a new nonce for every response; a reused nonce lets injected markup
+/// borrow the widget's trust.
+pub fn generate_nonce() -> String {
+    base64::engine::general_purpose::STANDARD.encode(uuid::Uuid::new_v4().as_bytes())
+}
+
+/// Markup that requires `'unsafe-inline'` to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InlineViolationKind {
+    /// Inline event handler attribute such as `onclick`.
+    EventHandler(String),
+    /// `style="..."` attribute.
+    StyleAttribute,
+    /// `javascript:` URL in the named attribute.
+    JavascriptUrl(String),
+}
+
+/// A widget construct incompatible with a nonce-based policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineViolation {
+    /// Lowercase tag name carrying the offending attribute.
+    pub tag: String,
+    /// What requires `'unsafe-inline'`.
+    pub kind: InlineViolationKind,
+    /// 1-based line number of the tag.
+    pub line: usize,
+}
+
+impl fmt::Display for InlineViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            InlineViolationKind::EventHandler(attr) => write!(
+                f,
+                "line {}: <{} {}=...> inline event handler (use addEventListener)",
+                self.line, self.tag, attr
+            ),
+            InlineViolationKind::StyleAttribute => write!(
+                f,
+                "line {}: <{} style=...> inline style attribute (use a class in a <style> block)",
+                self.line, self.tag
+            ),
+            InlineViolationKind::JavascriptUrl(attr) => write!(
+                f,
+                "line {}: <{} {}=\"javascript:...\"> javascript: URL (use a click listener)",
+                self.line, self.tag, attr
+            ),
+        }
+    }
+}
+
+/// Widget HTML rewritten for a strict, nonce-based policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoncedHtml {
+    /// HTML with nonces stamped and the policy `<meta>` tag in `<head>`.
+    pub html: String,
+    /// Nonce used for this response.
+    pub nonce: String,
+    /// Policy string, for hosts that send it as a `Content-Security-Policy` header instead.
+    pub policy: String,
+}
+
+/// Build a strict policy for `nonce`, widening fetch directives with the
+/// domains declared in `csp`.
+///
+/// Scripts are allowed only by nonce (`'strict-dynamic'` extends trust to
+/// scripts they load, such as module imports); styles by nonce or resource
+/// domain.
+pub fn strict_policy(nonce: &str, csp: Option<&WidgetCSP>) -> String {
+    let resources = csp.map_or(&[][..], |c| c.resource_domains.as_slice());
+    let connect = csp.map_or(&[][..], |c| c.connect_domains.as_slice());
+    let frames = csp.and_then(|c| c.frame_domains.as_deref()).unwrap_or(&[]);
+    let base = csp
+        .and_then(|c| c.base_uri_domains.as_deref())
+        .unwrap_or(&[]);
+
+    let nonce_source = format!("'nonce-{}'", nonce);
+
+    [
+        "default-src 'none'".to_string(),
+        directive(
+            "script-src",
+            &[nonce_source.as_str(), "'strict-dynamic'"],
+            &[],
+        ),
+        directive("style-src", &[nonce_source.as_str()], resources),
+        directive("img-src", &["'self'", "data:", "blob:"], resources),
+        directive("font-src", &["'self'", "data:"], resources),
+        directive("media-src", &["'self'", "data:", "blob:"], resources),
+        directive("connect-src", &["'self'"], connect),
+        directive("frame-src", &[], frames),
+        directive("base-uri", &[], base),
+        "object-src 'none'".to_string(),
+    ]
+    .join("; ")
+}
+
+/// Format one directive; an empty source list becomes `'none'`.
+fn directive(name: &str, fixed: &[&str], domains: &[String]) -> String {
+    let mut sources: Vec<&str> = fixed.to_vec();
+    sources.extend(domains.iter().map(String::as_str));
+    if sources.is_empty() {
+        sources.push("'none'");
+    }
+    format!("{} {}", name, sources.join(" "))
+}
+
+/// List markup in `html` that only runs under `'unsafe-inline'`.
+///
+/// Contents of `<script>` and `<style>` elements and HTML comments are not
+/// inspected.
+pub fn find_inline_violations(html: &str) -> Vec<InlineViolation> {
+    let mut violations = Vec::new();
+    for tag in scan_tags(html) {
+        for attr in &tag.attrs {
+            let kind = if attr.name.len() > 2 && attr.name.starts_with("on") {
+                Some(InlineViolationKind::EventHandler(attr.name.clone()))
+            } else if attr.name == "style" {
+                Some(InlineViolationKind::StyleAttribute)
+            } else if matches!(attr.name.as_str(), "href" | "src" | "action" | "formaction")
+                && attr.value.as_deref().is_some_and(|v| {
+                    v.trim_start()
+                        .to_ascii_lowercase()
+                        .starts_with("javascript:")
+                })
+            {
+                Some(InlineViolationKind::JavascriptUrl(attr.name.clone()))
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                violations.push(InlineViolation {
+                    tag: tag.name.clone(),
+                    kind,
+                    line: html[..tag.start].matches('\n').count() + 1,
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Stamp `nonce` on every script and style in `html` and prepend the
+/// matching policy `<meta>` tag to `<head>`.
+///
+/// Existing `nonce` attributes are replaced. Any policy already present in
+/// the document stays in force alongside the new one.
+///
+/// # Errors
+///
+/// Returns a validation error listing every [`InlineViolation`] when the
+/// widget needs `'unsafe-inline'`.
+pub fn apply_csp_nonce(
+    html: &str,
+    nonce: &str,
+    csp: Option<&WidgetCSP>,
+) -> crate::Result<NoncedHtml> {
+    let violations = find_inline_violations(html);
+    if !violations.is_empty() {
+        let details: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        return Err(crate::Error::validation(format!(
+            "Widget requires 'unsafe-inline' and cannot run under a strict CSP:\n  {}",
+            details.join("\n  ")
+        )));
+    }
+
+    let policy = strict_policy(nonce, csp);
+    let meta = format!(
+        r#"<meta http-equiv="Content-Security-Policy" content="{}">"#,
+        policy
+    );
+    let nonce_attr = format!(r#"nonce="{}""#, nonce);
+
+    // (offset, text to insert, bytes to replace)
+    let mut edits: Vec<(usize, String, usize)> = Vec::new();
+    let tags = scan_tags(html);
+
+    if let Some(head) = tags.iter().find(|t| t.name == "head") {
+        edits.push((head.end, meta, 0));
+    } else if let Some(root) = tags.iter().find(|t| t.name == "html") {
+        edits.push((root.end, format!("<head>{}</head>", meta), 0));
+    } else {
+        edits.push((0, meta, 0));
+    }
+
+    for tag in &tags {
+        let stylesheet = tag.name == "link"
+            && tag.attrs.iter().any(|a| {
+                a.name == "rel"
+                    && a.value
+                        .as_deref()
+                        .is_some_and(|v| v.to_ascii_lowercase().contains("stylesheet"))
+            });
+        if !(tag.name == "script" || tag.name == "style" || stylesheet) {
+            continue;
+        }
+        match tag.attrs.iter().find(|a| a.name == "nonce") {
+            Some(existing) => edits.push((
+                existing.start,
+                nonce_attr.clone(),
+                existing.end - existing.start,
+            )),
+            None => edits.push((tag.name_end, format!(" {}", nonce_attr), 0)),
+        }
+    }
+
+    edits.sort_by_key(|(offset, _, _)| *offset);
+    let mut out = String::with_capacity(html.len() + edits.len() * (nonce_attr.len() + 1) + 512);
+    let mut cursor = 0;
+    for (offset, text, replace) in edits {
+        out.push_str(&html[cursor..offset]);
+        out.push_str(&text);
+        cursor = offset + replace;
+    }
+    out.push_str(&html[cursor..]);
+
+    Ok(NoncedHtml {
+        html: out,
+        nonce: nonce.to_string(),
+        policy,
+    })
+}
+
+/// A start tag found by [`scan_tags`]. Offsets are byte positions in the source.
+pub(super) struct Tag {
+    pub(super) name: String,
+    start: usize,
+    name_end: usize,
+    end: usize,
+    pub(super) attrs: Vec<Attr>,
+}
+
+pub(super) struct Attr {
+    pub(super) name: String,
+    pub(super) value: Option<String>,
+    start: usize,
+    end: usize,
+}
+
+/// Minimal start-tag scanner: skips comments, end tags, and the raw text
+/// of `<script>`/`<style>` elements.
+pub(super) fn scan_tags(html: &str) -> Vec<Tag> {
+    let lower = html.to_ascii_lowercase();
+    let bytes = html.as_bytes();
+    let mut tags = Vec::new();
+    let mut i = 0;
+
+    while let Some(rel) = html[i..].find('<') {
+        let start = i + rel;
+        if html[start..].starts_with("<!--") {
+            i = html[start..]
+                .find("-->")
+                .map_or(html.len(), |e| start + e + 3);
+            continue;
+        }
+
+        let name_start = start + 1;
+        let name_end = bytes[name_start..]
+            .iter()
+            .position(|b| !(b.is_ascii_alphanumeric() || *b == b'-'))
+            .map_or(html.len(), |p| name_start + p);
+        if name_end == name_start || !bytes[name_start].is_ascii_alphabetic() {
+            // End tag, doctype, or a stray '<' in text
+            i = name_start;
+            continue;
+        }
+
+        let name = lower[name_start..name_end].to_string();
+        let (attrs, end) = scan_attrs(html, &lower, name_end);
+        i = end;
+        if name == "script" || name == "style" {
+            let close = format!("</{}", name);
+            i = lower[end..].find(&close).map_or(html.len(), |p| end + p);
+        }
+        tags.push(Tag {
+            name,
+            start,
+            name_end,
+            end,
+            attrs,
+        });
+    }
+    tags
+}
+
+/// Parse attributes from `pos` up to the closing `>`; returns them and the
+/// offset just past the tag.
+fn scan_attrs(html: &str, lower: &str, mut pos: usize) -> (Vec<Attr>, usize) {
+    let bytes = html.as_bytes();
+    let mut attrs = Vec::new();
+
+    loop {
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'/') {
+            pos += 1;
+        }
+        if pos >= bytes.len() {
+            return (attrs, bytes.len());
+        }
+        if bytes[pos] == b'>' {
+            return (attrs, pos + 1);
+        }
+
+        let start = pos;
+        while pos < bytes.len()
+            && !bytes[pos].is_ascii_whitespace()
+            && !matches!(bytes[pos], b'=' | b'>' | b'/')
+        {
+            pos += 1;
+        }
+        let name = lower[start..pos].to_string();
+
+        let mut after = pos;
+        while after < bytes.len() && bytes[after].is_ascii_whitespace() {
+            after += 1;
+        }
+        let mut value = None;
+        if after < bytes.len() && bytes[after] == b'=' {
+            pos = after + 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if pos < bytes.len() && matches!(bytes[pos], b'"' | b'\'') {
+                let quote = bytes[pos];
+                let value_start = pos + 1;
+                let value_end = bytes[value_start..]
+                    .iter()
+                    .position(|b| *b == quote)
+                    .map_or(bytes.len(), |p| value_start + p);
+                value = Some(html[value_start..value_end].to_string());
+                pos = (value_end + 1).min(bytes.len());
+            } else {
+                let value_start = pos;
+                while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() && bytes[pos] != b'>' {
+                    pos += 1;
+                }
+                value = Some(html[value_start..pos].to_string());
+            }
+        }
+
+        if name.is_empty() {
+            // Lone '=' or similar junk; skip a byte to guarantee progress
+            pos = pos.max(start + 1);
+            continue;
+        }
+        attrs.push(Attr {
+            name,
+            value,
+            start,
+            end: pos,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONCE: &str = "abc123";
+
+    #[test]
+    fn stamps_scripts_styles_and_stylesheets() {
+        let html = r#"<html><head><style>p{}</style><link rel="stylesheet" href="a.css"><link rel="icon" href="i.png"></head><body><script src="x.js"></script><SCRIPT type="module">go()</SCRIPT></body></html>"#;
+        let nonced = apply_csp_nonce(html, NONCE, None).unwrap();
+
+        assert_eq!(nonced.html.matches(r#"nonce="abc123""#).count(), 4);
+        assert!(nonced.html.contains(r#"<style nonce="abc123">"#));
+        assert!(nonced
+            .html
+            .contains(r#"<link nonce="abc123" rel="stylesheet""#));
+        assert!(nonced.html.contains(r#"<link rel="icon""#));
+        assert!(nonced
+            .html
+            .contains(r#"<SCRIPT nonce="abc123" type="module">"#));
+    }
+
+    #[test]
+    fn meta_precedes_head_scripts() {
+        let html = "<html><head><script>a()</script></head><body></body></html>";
+        let nonced = apply_csp_nonce(html, NONCE, None).unwrap();
+
+        let meta = nonced.html.find("Content-Security-Policy").unwrap();
+        let script = nonced.html.find("<script").unwrap();
+        assert!(meta < script);
+        assert!(nonced
+            .policy
+            .contains("script-src 'nonce-abc123' 'strict-dynamic'"));
+        assert!(!nonced.policy.contains("unsafe-inline"));
+    }
+
+    #[test]
+    fn adds_head_when_missing() {
+        let nonced = apply_csp_nonce("<html><body>hi</body></html>", NONCE, None).unwrap();
+        assert!(nonced
+            .html
+            .starts_with(r#"<html><head><meta http-equiv="Content-Security-Policy""#));
+
+        let fragment = apply_csp_nonce("<div>hi</div>", NONCE, None).unwrap();
+        assert!(fragment.html.starts_with("<meta "));
+    }
+
+    #[test]
+    fn replaces_existing_nonce() {
+        let html = r#"<script nonce="old">a()</script>"#;
+        let nonced = apply_csp_nonce(html, NONCE, None).unwrap();
+        assert!(nonced.html.contains(r#"<script nonce="abc123">"#));
+        assert!(!nonced.html.contains("old"));
+    }
+
+    #[test]
+    fn policy_includes_declared_domains() {
+        let csp = WidgetCSP::new()
+            .connect("https://api.example.com")
+            .resources("https://cdn.example.com");
+        let policy = strict_policy(NONCE, Some(&csp));
+
+        assert!(policy.contains("connect-src 'self' https://api.example.com"));
+        assert!(policy.contains("style-src 'nonce-abc123' https://cdn.example.com"));
+        assert!(policy.contains("img-src 'self' data: blob: https://cdn.example.com"));
+        assert!(policy.contains("frame-src 'none'"));
+    }
+
+    #[test]
+    fn detects_unsafe_inline_markup() {
+        let html = "<div>\n<button onclick=\"go()\">Go</button>\n<p style=\"color:red\">x</p>\n<a href=\" JavaScript:void(0)\">y</a>\n</div>";
+        let violations = find_inline_violations(html);
+
+        assert_eq!(
+            violations,
+            vec![
+                InlineViolation {
+                    tag: "button".into(),
+                    kind: InlineViolationKind::EventHandler("onclick".into()),
+                    line: 2,
+                },
+                InlineViolation {
+                    tag: "p".into(),
+                    kind: InlineViolationKind::StyleAttribute,
+                    line: 3,
+                },
+                InlineViolation {
+                    tag: "a".into(),
+                    kind: InlineViolationKind::JavascriptUrl("href".into()),
+                    line: 4,
+                },
+            ]
+        );
+
+        let err = apply_csp_nonce(html, NONCE, None).unwrap_err();
+        assert!(err.to_string().contains("unsafe-inline"));
+        assert!(err.to_string().contains("onclick"));
+    }
+
+    #[test]
+    fn ignores_script_text_and_comments() {
+        let html = r#"<!-- <b onclick="x"> --><script>el.innerHTML = '<b onclick="x">';</script><style>p[style]{}</style><p class="ok">hi</p>"#;
+        assert!(find_inline_violations(html).is_empty());
+    }
+
+    #[test]
+    fn nonces_are_unique() {
+        let a = generate_nonce();
+        let b = generate_nonce();
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 24);
+    }
+}
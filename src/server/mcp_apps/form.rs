@@ -0,0 +1,631 @@
+//! Built-in form widget generated from a tool's input schema.
+//!
+//! [`ServerBuilder::tool_with_form`](crate::server::ServerBuilder::tool_with_form)
+//! registers a tool together with a generated widget at
+//! `ui://pmcp/form/<tool>`, so a server gets a usable UI without writing
+//! widget HTML. The widget renders the input schema as a form:
+//!
+//! - strings, numbers and integers become inputs carrying the schema's
+//!   `minLength`, `maxLength`, `pattern`, `minimum` and `maximum` as
+//!   browser validation,
+//! - booleans become checkboxes,
+//! - `enum` values and `oneOf` lists of `const` values become selects,
+//! - nested objects become fieldsets,
+//! - anything else (arrays, unions, free-form objects) becomes a JSON text area.
+//!
+//! Submitting the form calls the tool through the host bridge and shows the
+//! structured result, or the text content if the tool returned none.
+//!
+//! [`render_form_widget`] returns the same HTML for servers that serve their
+//! widgets themselves.
+
+use super::inline_ext_apps_shim;
+use crate::error::{Error, ErrorCode, Result};
+use crate::server::argument_error::resolve;
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::ResourceHandler;
+use crate::types::ui::{build_ui_meta, UIMimeType};
+use crate::types::{Content, ListResourcesResult, ReadResourceResult, ResourceInfo, ToolInfo};
+use async_trait::async_trait;
+use pmcp_widget_utils::render_widget_template;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// URI prefix of generated form widgets.
+pub const FORM_WIDGET_URI_PREFIX: &str = "ui://pmcp/form/";
+
+/// Nesting depth after which objects are edited as JSON, so recursive
+/// schemas terminate.
+const MAX_DEPTH: usize = 6;
+
+/// Schema keywords copied onto form inputs as validation attributes.
+const CONSTRAINTS: &[&str] = &[
+    "format",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// The URI of the generated form widget for `tool`.
+pub fn form_widget_uri(tool: &str) -> String {
+    format!("{FORM_WIDGET_URI_PREFIX}{tool}")
+}
+
+/// Render the form widget HTML for `tool`.
+///
+/// The page is self-contained: the ext-apps `App` bridge is inlined, so it
+/// works in hosts that block scripts from CDNs.
+pub fn render_form_widget(tool: &ToolInfo) -> String {
+    let data = json!({
+        "title": tool.title.as_deref().unwrap_or(&tool.name),
+        "description": tool.description,
+        "form": {
+            "tool": tool.name,
+            "fields": object_fields(&tool.input_schema, &tool.input_schema, 0),
+        },
+    });
+    let html = render_widget_template(FORM_TEMPLATE, &data);
+    inline_ext_apps_shim(&html).into_owned()
+}
+
+/// One form control, serialized into the page for the widget script.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct FormField {
+    name: String,
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<Value>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    constraints: Map<String, Value>,
+    #[serde(flatten)]
+    input: Input,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "input", rename_all = "camelCase")]
+enum Input {
+    Text,
+    Number { integer: bool },
+    Checkbox,
+    Select { options: Vec<Value> },
+    Object { fields: Vec<FormField> },
+    Json,
+}
+
+fn object_fields(root: &Value, schema: &Value, depth: usize) -> Vec<FormField> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let schema = resolve(root, property).unwrap_or(property);
+            let text = |key: &str| {
+                property
+                    .get(key)
+                    .or_else(|| schema.get(key))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            FormField {
+                name: name.clone(),
+                label: text("title").unwrap_or_else(|| label(name)),
+                description: text("description"),
+                required: required.contains(&name.as_str()),
+                default: property
+                    .get("default")
+                    .or_else(|| schema.get("default"))
+                    .cloned(),
+                constraints: CONSTRAINTS
+                    .iter()
+                    .filter_map(|key| Some((key.to_string(), schema.get(*key)?.clone())))
+                    .collect(),
+                input: input(root, schema, depth),
+            }
+        })
+        .collect()
+}
+
+fn input(root: &Value, schema: &Value, depth: usize) -> Input {
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        return Input::Select {
+            options: options.clone(),
+        };
+    }
+    if let Some(value) = schema.get("const") {
+        return Input::Select {
+            options: vec![value.clone()],
+        };
+    }
+    if let Some(options) = const_alternatives(root, schema) {
+        return Input::Select { options };
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::String(ty)) => Some(ty.as_str()),
+        Some(Value::Array(types)) => {
+            let types: Vec<&str> = types
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|ty| *ty != "null")
+                .collect();
+            match types.as_slice() {
+                [ty] => Some(*ty),
+                _ => None,
+            }
+        },
+        _ if schema.get("properties").is_some() => Some("object"),
+        _ => None,
+    };
+    match ty {
+        Some("string") => Input::Text,
+        Some("integer") => Input::Number { integer: true },
+        Some("number") => Input::Number { integer: false },
+        Some("boolean") => Input::Checkbox,
+        Some("object") if depth < MAX_DEPTH && schema.get("properties").is_some() => {
+            Input::Object {
+                fields: object_fields(root, schema, depth + 1),
+            }
+        },
+        _ => Input::Json,
+    }
+}
+
+/// The values of a `oneOf`/`anyOf` whose alternatives are all `const`s, as
+/// generated for documented enums.
+fn const_alternatives(root: &Value, schema: &Value) -> Option<Vec<Value>> {
+    let alternatives = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))?
+        .as_array()?;
+    let options: Option<Vec<Value>> = alternatives
+        .iter()
+        .map(|alt| resolve(root, alt)?.get("const").cloned())
+        .collect();
+    options.filter(|options| !options.is_empty())
+}
+
+/// `max_results` and `maxResults` both become "Max results".
+fn label(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            out.push(' ');
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower {
+            out.push(' ');
+        }
+        previous_lower = c.is_lowercase();
+        if out.is_empty() {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+    out
+}
+
+/// Serves the generated form widgets and delegates every other URI to the
+/// server's own resource handler, if it has one.
+pub(crate) struct FormWidgets {
+    forms: HashMap<String, (ResourceInfo, String)>,
+    inner: Option<Arc<dyn ResourceHandler>>,
+}
+
+impl FormWidgets {
+    pub(crate) fn new(inner: Option<Arc<dyn ResourceHandler>>) -> Self {
+        Self {
+            forms: HashMap::new(),
+            inner,
+        }
+    }
+
+    /// Generate the form for `tool`, failing if it is not registered.
+    pub(crate) fn add(&mut self, tool: &str, tools: &HashMap<String, ToolInfo>) -> Result<()> {
+        let info = tools.get(tool).ok_or_else(|| {
+            Error::validation(format!(
+                "Form widget requested for tool '{}', which is not registered",
+                tool
+            ))
+        })?;
+        let uri = form_widget_uri(tool);
+        let resource = ResourceInfo::new(&uri, format!("{} form", tool))
+            .with_mime_type(UIMimeType::HtmlMcpApp.as_str());
+        self.forms.insert(uri, (resource, render_form_widget(info)));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResourceHandler for FormWidgets {
+    async fn read(&self, uri: &str, extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
+        if let Some((_, html)) = self.forms.get(uri) {
+            return Ok(ReadResourceResult::new(vec![Content::Resource {
+                uri: uri.to_string(),
+                text: Some(html.clone()),
+                mime_type: Some(UIMimeType::HtmlMcpApp.as_str().to_string()),
+                meta: build_ui_meta(Some(uri)),
+            }]));
+        }
+        match &self.inner {
+            Some(inner) => inner.read(uri, extra).await,
+            None => Err(Error::protocol(
+                ErrorCode::INVALID_PARAMS,
+                format!("Resource not found: {}", uri),
+            )),
+        }
+    }
+
+    async fn list(
+        &self,
+        cursor: Option<String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ListResourcesResult> {
+        let first_page = cursor.is_none();
+        let mut result = match &self.inner {
+            Some(inner) => inner.list(cursor, extra).await?,
+            None => ListResourcesResult::new(Vec::new()),
+        };
+        if first_page {
+            let mut forms: Vec<ResourceInfo> = self
+                .forms
+                .values()
+                .map(|(resource, _)| resource.clone())
+                .collect();
+            forms.sort_by(|a, b| a.uri.cmp(&b.uri));
+            result.resources.extend(forms);
+        }
+        Ok(result)
+    }
+}
+
+const FORM_TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{ title }}</title>
+<style>
+  body { font: 14px/1.4 system-ui, sans-serif; margin: 16px; color: #1f2328; }
+  h1 { font-size: 18px; margin: 0 0 4px; }
+  p.description, .hint { color: #59636e; margin: 0 0 12px; }
+  .hint { font-size: 12px; margin: 2px 0 0; }
+  .field { margin-bottom: 12px; }
+  label { display: block; font-weight: 600; margin-bottom: 4px; }
+  label.inline { display: flex; gap: 6px; align-items: center; }
+  input:not([type=checkbox]), select, textarea { box-sizing: border-box; width: 100%; padding: 6px; font: inherit; }
+  textarea { font-family: ui-monospace, monospace; min-height: 80px; }
+  fieldset { border: 1px solid #d1d9e0; border-radius: 6px; margin: 0 0 12px; padding: 8px 12px; }
+  .required { color: #d1242f; }
+  button { padding: 6px 16px; font: inherit; cursor: pointer; }
+  pre { background: #f6f8fa; padding: 8px; border-radius: 6px; overflow: auto; white-space: pre-wrap; }
+  pre.error { background: #ffebe9; color: #82071e; }
+</style>
+</head>
+<body>
+<h1>{{ title }}</h1>
+<p class="description">{{ description }}</p>
+<form id="tool-form" novalidate></form>
+<section id="result" hidden>
+  <h2>Result</h2>
+  <pre id="output"></pre>
+</section>
+<script type="application/json" id="form-spec">{{ form | json }}</script>
+<script type="module">
+import { App } from "https://esm.sh/@modelcontextprotocol/ext-apps";
+
+const spec = JSON.parse(document.getElementById('form-spec').textContent);
+const form = document.getElementById('tool-form');
+const result = document.getElementById('result');
+const output = document.getElementById('output');
+const app = new App({ name: 'pmcp-form', version: '1.0.0' });
+
+function el(tag, attrs, children) {
+  const node = document.createElement(tag);
+  Object.entries(attrs || {}).forEach(([key, value]) => {
+    if (value !== undefined && value !== null && value !== false) node.setAttribute(key, value === true ? '' : value);
+  });
+  (children || []).forEach((child) => node.append(child));
+  return node;
+}
+
+function caption(field) {
+  const parts = [field.label];
+  if (field.required) parts.push(el('span', { class: 'required' }, [' *']));
+  return parts;
+}
+
+// Each control returns { node, read } where read() yields the value,
+// undefined to leave the argument out, or throws on invalid input.
+function control(field, path) {
+  const id = 'f-' + path.join('-');
+  const c = field.constraints || {};
+  const hint = field.description ? el('p', { class: 'hint' }, [field.description]) : null;
+  const wrap = (input, inline) => {
+    const label = el('label', { for: id, class: inline ? 'inline' : null }, inline ? [input, ...caption(field)] : caption(field));
+    return el('div', { class: 'field' }, inline ? [label, hint || ''] : [label, input, hint || '']);
+  };
+
+  switch (field.input) {
+    case 'object': {
+      const children = field.fields.map((child) => control(child, path.concat(child.name)));
+      const legend = el('legend', {}, caption(field));
+      const node = el('fieldset', { id }, [legend, hint || '', ...children.map((child) => child.node)]);
+      return {
+        node,
+        read() {
+          const value = {};
+          field.fields.forEach((child, i) => {
+            const v = children[i].read();
+            if (v !== undefined) value[child.name] = v;
+          });
+          return Object.keys(value).length || field.required ? value : undefined;
+        },
+      };
+    }
+    case 'checkbox': {
+      const input = el('input', { id, type: 'checkbox', checked: field.default === true });
+      return { node: wrap(input, true), read: () => input.checked };
+    }
+    case 'select': {
+      const options = field.options.map((option, i) =>
+        el('option', { value: String(i), selected: JSON.stringify(option) === JSON.stringify(field.default) }, [
+          typeof option === 'string' ? option : JSON.stringify(option),
+        ]));
+      if (!field.required) options.unshift(el('option', { value: '' }, ['—']));
+      const input = el('select', { id, required: field.required }, options);
+      return { node: wrap(input), read: () => (input.value === '' ? undefined : field.options[Number(input.value)]) };
+    }
+    case 'number': {
+      const input = el('input', {
+        id, type: 'number', required: field.required,
+        min: c.minimum, max: c.maximum, step: c.multipleOf || (field.integer ? 1 : 'any'),
+        value: field.default,
+      });
+      return {
+        node: wrap(input),
+        read() {
+          if (input.value === '') return undefined;
+          const value = Number(input.value);
+          if ('exclusiveMinimum' in c && value <= c.exclusiveMinimum) throw new Error(field.label + ' must be greater than ' + c.exclusiveMinimum);
+          if ('exclusiveMaximum' in c && value >= c.exclusiveMaximum) throw new Error(field.label + ' must be less than ' + c.exclusiveMaximum);
+          return value;
+        },
+      };
+    }
+    case 'text': {
+      const types = { email: 'email', uri: 'url', url: 'url', date: 'date', 'date-time': 'datetime-local', time: 'time' };
+      const input = el('input', {
+        id, type: types[c.format] || 'text', required: field.required,
+        minlength: c.minLength, maxlength: c.maxLength, pattern: c.pattern,
+        value: field.default,
+      });
+      return { node: wrap(input), read: () => (input.value === '' && !field.required ? undefined : input.value) };
+    }
+    default: {
+      const input = el('textarea', { id, required: field.required, placeholder: 'JSON' }, [
+        field.default === undefined ? '' : JSON.stringify(field.default, null, 2),
+      ]);
+      return {
+        node: wrap(input),
+        read() {
+          if (input.value.trim() === '') return undefined;
+          try {
+            return JSON.parse(input.value);
+          } catch (e) {
+            throw new Error(field.label + ' is not valid JSON: ' + e.message);
+          }
+        },
+      };
+    }
+  }
+}
+
+const controls = spec.fields.map((field) => control(field, [field.name]));
+controls.forEach((c) => form.append(c.node));
+form.append(el('button', { type: 'submit' }, ['Run ' + spec.tool]));
+
+function show(text, isError) {
+  result.hidden = false;
+  output.textContent = text;
+  output.className = isError ? 'error' : '';
+}
+
+function showResult(res) {
+  if (!res) return;
+  if (res.structuredContent !== undefined) {
+    show(JSON.stringify(res.structuredContent, null, 2), res.isError);
+    return;
+  }
+  const text = (res.content || []).filter((c) => c.type === 'text').map((c) => c.text).join('\n');
+  show(text || JSON.stringify(res, null, 2), res.isError);
+}
+
+form.addEventListener('submit', async (event) => {
+  event.preventDefault();
+  if (!form.reportValidity()) return;
+  const args = {};
+  try {
+    spec.fields.forEach((field, i) => {
+      const value = controls[i].read();
+      if (value !== undefined) args[field.name] = value;
+    });
+  } catch (e) {
+    show(e.message, true);
+    return;
+  }
+  const button = form.querySelector('button');
+  button.disabled = true;
+  try {
+    showResult(await app.callServerTool({ name: spec.tool, arguments: args }));
+  } catch (e) {
+    show(e.message, true);
+  } finally {
+    button.disabled = false;
+  }
+});
+
+app.ontoolresult = (params) => showResult(params);
+app.connect();
+</script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> ToolInfo {
+        ToolInfo::new(
+            "search_orders",
+            Some("Find orders".to_string()),
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "minLength": 2 },
+                    "status": { "enum": ["open", "shipped"] },
+                    "maxResults": { "type": ["integer", "null"], "maximum": 100 },
+                    "include_archived": { "type": "boolean", "default": false },
+                    "customer": { "$ref": "#/$defs/Customer" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["query"],
+                "$defs": {
+                    "Customer": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "tier": { "oneOf": [{ "const": "gold" }, { "const": "silver" }] }
+                        },
+                        "required": ["id"]
+                    }
+                }
+            }),
+        )
+    }
+
+    fn field<'a>(fields: &'a [FormField], name: &str) -> &'a FormField {
+        fields.iter().find(|f| f.name == name).unwrap()
+    }
+
+    #[test]
+    fn maps_schema_types_to_inputs() {
+        let info = tool();
+        let fields = object_fields(&info.input_schema, &info.input_schema, 0);
+
+        let query = field(&fields, "query");
+        assert_eq!(query.input, Input::Text);
+        assert!(query.required);
+        assert_eq!(query.constraints["minLength"], 2);
+        assert_eq!(
+            field(&fields, "status").input,
+            Input::Select {
+                options: vec![json!("open"), json!("shipped")]
+            }
+        );
+        let max_results = field(&fields, "maxResults");
+        assert_eq!(max_results.input, Input::Number { integer: true });
+        assert_eq!(max_results.label, "Max results");
+        assert_eq!(field(&fields, "include_archived").input, Input::Checkbox);
+        assert_eq!(field(&fields, "tags").input, Input::Json);
+    }
+
+    #[test]
+    fn nests_referenced_objects() {
+        let info = tool();
+        let fields = object_fields(&info.input_schema, &info.input_schema, 0);
+        let Input::Object { fields: customer } = &field(&fields, "customer").input else {
+            panic!("customer should be a fieldset");
+        };
+        assert!(field(customer, "id").required);
+        assert_eq!(
+            field(customer, "tier").input,
+            Input::Select {
+                options: vec![json!("gold"), json!("silver")]
+            }
+        );
+    }
+
+    #[test]
+    fn recursive_schemas_fall_back_to_json() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "node": { "$ref": "#/$defs/Node" } },
+            "$defs": {
+                "Node": { "type": "object", "properties": { "child": { "$ref": "#/$defs/Node" } } }
+            }
+        });
+        let mut fields = object_fields(&schema, &schema, 0);
+        let mut depth = 0;
+        while let Some(FormField {
+            input: Input::Object { fields: children },
+            ..
+        }) = fields.pop()
+        {
+            fields = children;
+            depth += 1;
+        }
+        assert_eq!(depth, MAX_DEPTH);
+    }
+
+    #[test]
+    fn renders_self_contained_page() {
+        let mut info = tool();
+        info.description = Some("Find </script> orders".to_string());
+        let html = render_form_widget(&info);
+        assert!(!html.contains("esm.sh"));
+        assert!(html.contains("callServerTool"));
+        assert!(html.contains("Find &lt;/script&gt; orders"));
+        assert!(html.contains(r#""tool":"search_orders""#));
+    }
+
+    #[tokio::test]
+    async fn serves_forms_and_delegates_other_uris() {
+        let tools = HashMap::from([("search_orders".to_string(), tool())]);
+        let mut forms = FormWidgets::new(None);
+        forms.add("search_orders", &tools).unwrap();
+        assert!(forms.add("missing", &tools).is_err());
+
+        let uri = form_widget_uri("search_orders");
+        let listed = forms
+            .list(None, RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.resources.len(), 1);
+        assert_eq!(listed.resources[0].uri, uri);
+
+        let read = forms
+            .read(&uri, RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        assert!(matches!(
+            &read.contents[0],
+            Content::Resource { mime_type: Some(mime), .. } if mime == "text/html;profile=mcp-app"
+        ));
+        assert!(forms
+            .read("ui://app/other", RequestHandlerExtra::default())
+            .await
+            .is_err());
+    }
+}
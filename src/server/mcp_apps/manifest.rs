@@ -0,0 +1,379 @@
+//! Widget manifests: what each widget is and what it needs.
+//!
+//! A [`WidgetManifest`] lists every widget a server ships with its resource
+//! URI, preferred size, CSP domains, the tools it calls, and the locales it
+//! supports. [`WidgetManifest::generate`] builds one from a [`WidgetDir`],
+//! reading declarations from `<meta>` tags in each widget's HTML:
+//!
+//! ```html
+//! <html lang="en">
+//! <head>
+//!   <meta name="mcp-widget:size" content="480x320">
+//!   <meta name="mcp-widget:connect-domains" content="https://api.example.com">
+//!   <meta name="mcp-widget:resource-domains" content="https://cdn.example.com">
+//!   <meta name="mcp-widget:tools" content="get_board, make_move">
+//!   <meta name="mcp-widget:locales" content="en, de">
+//! </head>
+//! ```
+//!
+//! Values are comma- or whitespace-separated. Without `mcp-widget:locales`,
+//! the `lang` attribute of `<html>` is used.
+//!
+//! Register the manifest on the server builder with `widget_manifest()` and
+//! the build fails if a tool's `_meta` points at a widget the manifest does
+//! not list, or a widget depends on a tool the server does not register.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use pmcp::server::mcp_apps::{WidgetDir, WidgetManifest};
+//!
+//! let manifest = WidgetManifest::generate(&WidgetDir::new("widgets"))?;
+//! std::fs::write("widgets.json", serde_json::to_string_pretty(&manifest)?)?;
+//!
+//! let server = Server::builder()
+//!     .name("chess")
+//!     .version("1.0.0")
+//!     .tool("chess_board", board_tool) // _meta.ui.resourceUri = "ui://app/board"
+//!     .widget_manifest(manifest)
+//!     .build()?;
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::csp::scan_tags;
+use super::WidgetDir;
+use crate::types::mcp_apps::{UIDimensions, WidgetCSP};
+use crate::types::ToolInfo;
+
+/// Prefix of the `<meta name>` declarations read by [`WidgetManifest::generate`].
+const META_PREFIX: &str = "mcp-widget:";
+
+/// Manifest of the widgets a server ships.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WidgetManifest {
+    /// Widgets, sorted by name when generated.
+    pub widgets: Vec<WidgetManifestEntry>,
+}
+
+/// A single widget in a [`WidgetManifest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetManifestEntry {
+    /// Widget name (the file stem, e.g. `board`).
+    pub name: String,
+    /// MCP resource URI (e.g. `ui://app/board`).
+    pub uri: String,
+    /// Size the widget would like the host to render it at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_size: Option<UIDimensions>,
+    /// Domains the widget needs to connect to or load resources from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub csp: Option<WidgetCSP>,
+    /// Tools the widget calls through the host.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Locales the widget supports (BCP 47 tags).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<String>,
+}
+
+impl WidgetManifestEntry {
+    /// Create an entry with no declared requirements.
+    pub fn new(name: impl Into<String>, uri: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            uri: uri.into(),
+            preferred_size: None,
+            csp: None,
+            tools: Vec::new(),
+            locales: Vec::new(),
+        }
+    }
+
+    /// Set the preferred size in pixels.
+    #[must_use]
+    pub fn with_preferred_size(mut self, width: u32, height: u32) -> Self {
+        self.preferred_size = Some(UIDimensions {
+            width: Some(width),
+            height: Some(height),
+            ..UIDimensions::default()
+        });
+        self
+    }
+
+    /// Set the CSP domains the widget needs.
+    #[must_use]
+    pub fn with_csp(mut self, csp: WidgetCSP) -> Self {
+        self.csp = Some(csp);
+        self
+    }
+
+    /// Declare a tool the widget calls.
+    #[must_use]
+    pub fn with_tool(mut self, tool: impl Into<String>) -> Self {
+        self.tools.push(tool.into());
+        self
+    }
+
+    /// Declare a supported locale.
+    #[must_use]
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locales.push(locale.into());
+        self
+    }
+
+    /// Build an entry from the `mcp-widget:*` declarations in widget HTML.
+    pub fn from_html(name: impl Into<String>, uri: impl Into<String>, html: &str) -> Self {
+        let mut entry = Self::new(name, uri);
+        let mut csp = WidgetCSP::new();
+        let mut lang = None;
+
+        for tag in scan_tags(html) {
+            let attr = |name: &str| {
+                tag.attrs
+                    .iter()
+                    .find(|a| a.name == name)
+                    .and_then(|a| a.value.as_deref())
+            };
+            if tag.name == "html" {
+                lang = attr("lang").map(str::to_string);
+                continue;
+            }
+            if tag.name != "meta" {
+                continue;
+            }
+            let Some(key) = attr("name").and_then(|n| n.strip_prefix(META_PREFIX)) else {
+                continue;
+            };
+            let content = attr("content").unwrap_or_default();
+            match key {
+                "size" => entry.preferred_size = parse_size(content),
+                "connect-domains" => csp.connect_domains.extend(split_list(content)),
+                "resource-domains" => csp.resource_domains.extend(split_list(content)),
+                "frame-domains" => csp
+                    .frame_domains
+                    .get_or_insert_with(Vec::new)
+                    .extend(split_list(content)),
+                "tools" => entry.tools.extend(split_list(content)),
+                "locales" => entry.locales.extend(split_list(content)),
+                other => {
+                    tracing::debug!("Ignoring unknown widget declaration {META_PREFIX}{other}")
+                },
+            }
+        }
+
+        if !csp.is_empty() {
+            entry.csp = Some(csp);
+        }
+        if entry.locales.is_empty() {
+            entry.locales.extend(lang.filter(|l| !l.is_empty()));
+        }
+        entry
+    }
+}
+
+impl WidgetManifest {
+    /// Create an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a widget.
+    #[must_use]
+    pub fn with_widget(mut self, entry: WidgetManifestEntry) -> Self {
+        self.widgets.push(entry);
+        self
+    }
+
+    /// Generate a manifest for every widget in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read.
+    pub fn generate(dir: &WidgetDir) -> std::io::Result<Self> {
+        let widgets = dir
+            .discover()?
+            .into_iter()
+            .map(|widget| {
+                let html = dir.read_widget(&widget.filename);
+                WidgetManifestEntry::from_html(widget.filename, widget.uri, &html)
+            })
+            .collect();
+        Ok(Self { widgets })
+    }
+
+    /// Look up a widget by resource URI.
+    pub fn get(&self, uri: &str) -> Option<&WidgetManifestEntry> {
+        self.widgets.iter().find(|w| w.uri == uri)
+    }
+
+    /// Check the manifest against the tools a server registers.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error listing every tool whose `_meta` refers to
+    /// a widget URI missing from the manifest, and every widget that depends
+    /// on an unregistered tool.
+    pub fn validate(&self, tools: &HashMap<String, ToolInfo>) -> crate::Result<()> {
+        let mut problems = Vec::new();
+
+        let mut tool_names: Vec<&String> = tools.keys().collect();
+        tool_names.sort();
+        for name in tool_names {
+            for uri in tools[name].widget_uris() {
+                if self.get(uri).is_none() {
+                    problems.push(format!(
+                        "tool '{}' references widget '{}', which is not in the manifest",
+                        name, uri
+                    ));
+                }
+            }
+        }
+
+        for widget in &self.widgets {
+            for tool in &widget.tools {
+                if !tools.contains_key(tool) {
+                    problems.push(format!(
+                        "widget '{}' depends on tool '{}', which is not registered",
+                        widget.name, tool
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::Error::validation(format!(
+                "Widget manifest does not match the registered tools:\n  {}",
+                problems.join("\n  ")
+            )))
+        }
+    }
+}
+
+/// Parse `WIDTHxHEIGHT` (e.g. `480x320`).
+fn parse_size(value: &str) -> Option<UIDimensions> {
+    let (width, height) = value.trim().split_once(['x', 'X'])?;
+    Some(UIDimensions {
+        width: Some(width.trim().parse().ok()?),
+        height: Some(height.trim().parse().ok()?),
+        ..UIDimensions::default()
+    })
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const BOARD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <meta name="mcp-widget:size" content="480x320">
+  <meta name="mcp-widget:connect-domains" content="https://api.example.com">
+  <meta name="mcp-widget:tools" content="get_board, make_move">
+  <meta name="mcp-widget:locales" content="en de">
+</head>
+<body></body>
+</html>"#;
+
+    fn widget_tool(uri: &str) -> ToolInfo {
+        ToolInfo::new("tool", None, json!({"type": "object"}))
+            .with_meta_entry("ui", json!({ "resourceUri": uri }))
+    }
+
+    fn tools(entries: &[(&str, ToolInfo)]) -> HashMap<String, ToolInfo> {
+        entries
+            .iter()
+            .map(|(name, info)| (name.to_string(), info.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_entry_from_html_declarations() {
+        let entry = WidgetManifestEntry::from_html("board", "ui://app/board", BOARD);
+
+        let size = entry.preferred_size.unwrap();
+        assert_eq!((size.width, size.height), (Some(480), Some(320)));
+        assert_eq!(
+            entry.csp.unwrap().connect_domains,
+            vec!["https://api.example.com"]
+        );
+        assert_eq!(entry.tools, vec!["get_board", "make_move"]);
+        assert_eq!(entry.locales, vec!["en", "de"]);
+    }
+
+    #[test]
+    fn test_html_lang_is_default_locale() {
+        let entry =
+            WidgetManifestEntry::from_html("x", "ui://app/x", r#"<html lang="fr"><body></body>"#);
+        assert_eq!(entry.locales, vec!["fr"]);
+        assert!(entry.csp.is_none());
+        assert!(entry.preferred_size.is_none());
+    }
+
+    #[test]
+    fn test_generate_from_widget_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("board.html"), BOARD).unwrap();
+        std::fs::write(dir.path().join("about.html"), "<p>About</p>").unwrap();
+
+        let manifest = WidgetManifest::generate(&WidgetDir::new(dir.path())).unwrap();
+        let names: Vec<&str> = manifest.widgets.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["about", "board"]);
+        assert_eq!(
+            manifest.get("ui://app/board").unwrap().tools,
+            vec!["get_board", "make_move"]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_unresolved_references() {
+        let manifest = WidgetManifest::new().with_widget(
+            WidgetManifestEntry::new("board", "ui://app/board").with_tool("make_move"),
+        );
+
+        let ok = tools(&[("make_move", widget_tool("ui://app/board"))]);
+        assert!(manifest.validate(&ok).is_ok());
+
+        let broken = tools(&[("show", widget_tool("ui://app/missing"))]);
+        let err = manifest.validate(&broken).unwrap_err().to_string();
+        assert!(err.contains("tool 'show' references widget 'ui://app/missing'"));
+        assert!(err.contains("widget 'board' depends on tool 'make_move'"));
+    }
+
+    #[test]
+    fn test_manifest_serialization() {
+        let manifest = WidgetManifest::new().with_widget(
+            WidgetManifestEntry::new("board", "ui://app/board")
+                .with_preferred_size(480, 320)
+                .with_locale("en"),
+        );
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "widgets": [{
+                    "name": "board",
+                    "uri": "ui://app/board",
+                    "preferredSize": { "width": 480, "height": 320 },
+                    "locales": ["en"]
+                }]
+            })
+        );
+        let back: WidgetManifest = serde_json::from_value(value).unwrap();
+        assert_eq!(back, manifest);
+    }
+}
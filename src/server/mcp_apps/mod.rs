@@ -30,6 +30,9 @@
 //!
 //! **Widget development:** Widget HTML should use the `@modelcontextprotocol/ext-apps`
 //! SDK (`App` class) for host communication. See `GUIDE.md` in this directory.
+//! Widgets of one server can open each other with deep links; see [`WidgetLink`].
+//! Tools registered with `tool_with_form` get a generated form widget; see
+//! [`render_form_widget`].
 //!
 //! # Example
 //!
@@ -61,9 +64,28 @@
 
 mod adapter;
 mod builder;
+mod csp;
+mod form;
+mod manifest;
+mod navigation;
+#[cfg(feature = "widget-build")]
+mod widget_build;
 mod widget_fs;
 
 pub use adapter::inline_ext_apps_shim;
-pub use adapter::{ChatGptAdapter, McpAppsAdapter, McpUiAdapter, UIAdapter};
+pub use adapter::{ChatGptAdapter, McpAppsAdapter, McpUiAdapter, McpUiFormat, UIAdapter};
 pub use builder::{MultiPlatformResource, UIResourceBuilder};
+pub use csp::{
+    apply_csp_nonce, find_inline_violations, generate_nonce, strict_policy, InlineViolation,
+    InlineViolationKind, NoncedHtml,
+};
+pub(crate) use form::FormWidgets;
+pub use form::{form_widget_uri, render_form_widget, FORM_WIDGET_URI_PREFIX};
+pub use manifest::{WidgetManifest, WidgetManifestEntry};
+pub use navigation::{NavigationContext, WidgetLink, NAVIGATION_META_KEY, WIDGET_LINK_SCHEME};
+pub use pmcp_widget_utils::{
+    add_subresource_integrity, external_resources, json_script, render_widget_template, sri_hash,
+};
+#[cfg(feature = "widget-build")]
+pub use widget_build::{WatchHandle, WidgetBuilder};
 pub use widget_fs::{WidgetDir, WidgetEntry};
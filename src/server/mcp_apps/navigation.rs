@@ -0,0 +1,251 @@
+//! Navigation and deep links between the widgets of one server.
+//!
+//! A widget opens another widget of the same server by naming the tool that
+//! renders it, the arguments to call it with, and optional state to hand
+//! over (a map widget opening the detail widget for the selected city, with
+//! the map's zoom level to restore on the way back):
+//!
+//! ```javascript
+//! await window.mcpBridge.navigate('city_detail', { city_id: 42 }, { zoom: 7 });
+//! window.mcpBridge.navigateBack();
+//! ```
+//!
+//! The bridges send this to the host as a `ui/navigate` request. The host
+//! calls the tool, renders its widget, and delivers the handoff as
+//! `hostContext.navigation` and under [`NAVIGATION_META_KEY`] in the tool
+//! result's `_meta`, where the target widget reads it as
+//! `window.mcpBridge.navigation` (`window.mcpNavigation` with the inlined
+//! ext-apps shim). mcp-preview implements `ui/navigate`, including
+//! `navigateBack()`. On ChatGPT, which has no widget-to-widget navigation,
+//! the bridge asks the model to call the tool with a follow-up message and
+//! the state is not handed over.
+//!
+//! Links need no script: the bridges intercept clicks on anchors whose
+//! `href` is a deep link built with [`WidgetLink::href`]:
+//!
+//! ```rust
+//! use pmcp::server::mcp_apps::WidgetLink;
+//! use serde_json::json;
+//!
+//! let link = WidgetLink::new("city_detail")
+//!     .argument("city_id", json!(42))
+//!     .state(json!({ "zoom": 7 }));
+//! let html = format!(r#"<a href="{}">Lisbon</a>"#, link.href());
+//! assert!(html.starts_with(r#"<a href="pmcp-widget:city_detail?args="#));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::types::ToolInfo;
+
+/// Scheme of widget deep links.
+pub const WIDGET_LINK_SCHEME: &str = "pmcp-widget";
+
+/// `_meta` key of the navigation handoff in the target tool's result.
+pub const NAVIGATION_META_KEY: &str = "pmcp/navigation";
+
+/// A navigation target: the tool whose widget to open.
+///
+/// Serializes to the `ui/navigate` request parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WidgetLink {
+    /// Tool that renders the target widget.
+    pub tool: String,
+    /// Arguments to call the tool with.
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+    /// State handed to the target widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<Value>,
+}
+
+impl WidgetLink {
+    /// Link to the widget rendered by `tool`.
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Add an argument for the tool call.
+    #[must_use]
+    pub fn argument(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.arguments.insert(name.into(), value);
+        self
+    }
+
+    /// Set the state handed to the target widget.
+    #[must_use]
+    pub fn state(mut self, state: Value) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// The deep link, `pmcp-widget:<tool>?args=<json>&state=<json>`.
+    ///
+    /// Query values are form-encoded JSON; `state` is omitted when unset.
+    pub fn href(&self) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("args", &Value::Object(self.arguments.clone()).to_string());
+        if let Some(state) = &self.state {
+            query.append_pair("state", &state.to_string());
+        }
+        format!(
+            "{}:{}?{}",
+            WIDGET_LINK_SCHEME,
+            urlencoding::encode(&self.tool),
+            query.finish()
+        )
+    }
+
+    /// Parse a deep link produced by [`href`](Self::href).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `href` is not a widget deep link or its
+    /// query values are not JSON.
+    pub fn parse(href: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| Error::validation(format!("Invalid widget link '{}': {}", href, reason));
+        let rest = href
+            .strip_prefix(WIDGET_LINK_SCHEME)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| invalid("not a pmcp-widget link"))?;
+        let (tool, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let tool = urlencoding::decode(tool)
+            .map_err(|_| invalid("tool name is not UTF-8"))?
+            .into_owned();
+        if tool.is_empty() {
+            return Err(invalid("missing tool name"));
+        }
+
+        let mut link = Self::new(tool);
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            let json = || {
+                serde_json::from_str::<Value>(&value)
+                    .map_err(|e| invalid(&format!("{} is not JSON: {}", key, e)))
+            };
+            match key.as_ref() {
+                "args" => match json()? {
+                    Value::Object(arguments) => link.arguments = arguments,
+                    _ => return Err(invalid("args is not a JSON object")),
+                },
+                "state" => link.state = Some(json()?),
+                _ => {},
+            }
+        }
+        Ok(link)
+    }
+
+    /// Check that the target tool is registered and renders a widget.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error naming the tool otherwise.
+    pub fn validate<'a>(&self, tools: impl IntoIterator<Item = &'a ToolInfo>) -> Result<()> {
+        let tool = tools
+            .into_iter()
+            .find(|t| t.name == self.tool)
+            .ok_or_else(|| {
+                Error::validation(format!("Widget link targets unknown tool '{}'", self.tool))
+            })?;
+        if tool.widget_uris().is_empty() {
+            return Err(Error::validation(format!(
+                "Widget link targets tool '{}', which has no widget",
+                self.tool
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Navigation handoff delivered to the target widget.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NavigationContext {
+    /// Tool whose widget navigated here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// State handed over by the previous widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<Value>,
+}
+
+impl NavigationContext {
+    /// Read the handoff from a tool result's `_meta`.
+    pub fn from_meta(meta: &Map<String, Value>) -> Option<Self> {
+        serde_json::from_value(meta.get(NAVIGATION_META_KEY)?.clone()).ok()
+    }
+
+    /// Add the handoff to a tool result's `_meta`.
+    pub fn insert_into(&self, meta: &mut Map<String, Value>) {
+        if let Ok(value) = serde_json::to_value(self) {
+            meta.insert(NAVIGATION_META_KEY.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_href_round_trip() {
+        let link = WidgetLink::new("city detail")
+            .argument("city_id", json!(42))
+            .argument("name", json!("São Paulo & co"))
+            .state(json!({ "zoom": 7, "center": [1.5, -2] }));
+        let href = link.href();
+        assert!(href.starts_with("pmcp-widget:city%20detail?args="));
+        assert_eq!(WidgetLink::parse(&href).unwrap(), link);
+
+        let bare = WidgetLink::parse("pmcp-widget:list").unwrap();
+        assert_eq!(bare, WidgetLink::new("list"));
+        assert!(!WidgetLink::new("list").href().contains("state="));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_links() {
+        assert!(WidgetLink::parse("https://example.com").is_err());
+        assert!(WidgetLink::parse("pmcp-widget:?args=%7B%7D").is_err());
+        assert!(WidgetLink::parse("pmcp-widget:list?args=%5B%5D").is_err());
+        assert!(WidgetLink::parse("pmcp-widget:list?state=nope").is_err());
+    }
+
+    #[test]
+    fn test_validate_target() {
+        let mut detail = ToolInfo::new("city_detail", None, json!({ "type": "object" }));
+        detail._meta = Some(
+            json!({ "ui": { "resourceUri": "ui://cities/detail.html" } })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let plain = ToolInfo::new("city_stats", None, json!({ "type": "object" }));
+        let tools = [detail, plain];
+
+        assert!(WidgetLink::new("city_detail").validate(&tools).is_ok());
+        let err = WidgetLink::new("city_stats").validate(&tools).unwrap_err();
+        assert!(err.to_string().contains("has no widget"));
+        assert!(WidgetLink::new("missing").validate(&tools).is_err());
+    }
+
+    #[test]
+    fn test_navigation_meta() {
+        let context = NavigationContext {
+            from: Some("city_map".to_string()),
+            state: Some(json!({ "zoom": 7 })),
+        };
+        let mut meta = Map::new();
+        context.insert_into(&mut meta);
+        assert_eq!(
+            meta[NAVIGATION_META_KEY],
+            json!({ "from": "city_map", "state": { "zoom": 7 } })
+        );
+        assert_eq!(NavigationContext::from_meta(&meta), Some(context));
+        assert_eq!(NavigationContext::from_meta(&Map::new()), None);
+    }
+}
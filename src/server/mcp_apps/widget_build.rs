@@ -0,0 +1,539 @@
+//! TypeScript widget build step for [`WidgetDir`](super::WidgetDir).
+//!
+//! With the `widget-build` feature, widget sources in `widgets/src/` are
+//! bundled by [esbuild](https://esbuild.github.io) into self-contained HTML,
+//! so a widget can be written in TypeScript/TSX without a Vite or npm setup.
+//! esbuild ships as a single static binary; no Node.js is needed.
+//!
+//! # Convention
+//!
+//! - Every top-level `widgets/src/<name>.{ts,tsx,js,jsx}` is an entry point
+//!   and becomes the widget `ui://app/<name>`. Files in subdirectories are
+//!   modules the entries import.
+//! - `widgets/src/<name>.html`, if present, is the page template: the bundled
+//!   CSS is inlined before `</head>` and the script before `</body>`.
+//!   Without a template the widget gets a minimal page with a `<div id="root">`.
+//!
+//! # esbuild discovery
+//!
+//! The first of these is used:
+//!
+//! 1. [`WidgetBuilder::esbuild`] or the `PMCP_ESBUILD` environment variable
+//! 2. `ESBUILD_BINARY_PATH` (esbuild's own override)
+//! 3. `node_modules/.bin/esbuild` in the source directory or any parent
+//! 4. `esbuild` on `PATH`
+//!
+//! # Caching
+//!
+//! Bundles are keyed by a fingerprint of every file under the source
+//! directory (path, size, modification time) plus the build options. They
+//! are kept in memory and on disk (`$TMPDIR/pmcp-widget-build/` by default),
+//! so unchanged widgets are served without invoking esbuild, including
+//! after a server restart.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use pmcp::server::mcp_apps::{WidgetBuilder, WidgetDir};
+//!
+//! // widgets/*.html plus widgets/src/*.ts(x), compiled on demand
+//! let widgets = WidgetDir::new("widgets").with_build();
+//! let html = widgets.read_widget("board");
+//!
+//! // Rebuild eagerly as sources change
+//! let builder = WidgetBuilder::new("widgets/src").minify(true);
+//! let _watch = builder.watch(std::time::Duration::from_millis(500), |name, result| {
+//!     if let Err(e) = result {
+//!         eprintln!("{name}: {e}");
+//!     }
+//! });
+//! ```
+
+use crate::error::{Error, Result};
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Source extensions treated as entry points, in lookup order.
+const ENTRY_EXTENSIONS: [&str; 4] = ["tsx", "ts", "jsx", "js"];
+
+/// Compiles widget sources into bundled HTML with esbuild.
+///
+/// Cloning is cheap; clones share the in-memory cache.
+#[derive(Clone)]
+pub struct WidgetBuilder {
+    src_dir: PathBuf,
+    cache_dir: PathBuf,
+    esbuild: Option<PathBuf>,
+    minify: bool,
+    extra_args: Vec<String>,
+    /// Widget name -> (fingerprint, bundled HTML)
+    memory: Arc<Mutex<HashMap<String, (u64, String)>>>,
+    /// Serializes esbuild runs, which share an output directory
+    build_lock: Arc<Mutex<()>>,
+}
+
+impl std::fmt::Debug for WidgetBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WidgetBuilder")
+            .field("src_dir", &self.src_dir)
+            .field("cache_dir", &self.cache_dir)
+            .field("esbuild", &self.esbuild)
+            .field("minify", &self.minify)
+            .field("extra_args", &self.extra_args)
+            .field("cached", &self.memory.lock().len())
+            .finish()
+    }
+}
+
+impl WidgetBuilder {
+    /// Create a builder for the sources in `src_dir`.
+    pub fn new(src_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            src_dir: src_dir.into(),
+            cache_dir: std::env::temp_dir().join("pmcp-widget-build"),
+            esbuild: std::env::var_os("PMCP_ESBUILD").map(PathBuf::from),
+            minify: false,
+            extra_args: Vec::new(),
+            memory: Arc::new(Mutex::new(HashMap::new())),
+            build_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Use this esbuild binary instead of searching for one.
+    #[must_use]
+    pub fn esbuild(mut self, path: impl Into<PathBuf>) -> Self {
+        self.esbuild = Some(path.into());
+        self
+    }
+
+    /// Store cached bundles in `dir` instead of `$TMPDIR/pmcp-widget-build`.
+    #[must_use]
+    pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// Minify bundles (default: off, with inline source maps instead).
+    #[must_use]
+    pub fn minify(mut self, minify: bool) -> Self {
+        self.minify = minify;
+        self
+    }
+
+    /// Pass an extra argument to esbuild, e.g. `--jsx-import-source=preact`
+    /// or `--external:@modelcontextprotocol/ext-apps`.
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.extra_args.push(arg.into());
+        self
+    }
+
+    /// Return the source directory.
+    pub fn src_dir(&self) -> &Path {
+        &self.src_dir
+    }
+
+    /// List entry points as `(widget name, source path)`, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source directory cannot be read.
+    pub fn discover(&self) -> std::io::Result<Vec<(String, PathBuf)>> {
+        let mut entries: Vec<(String, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(&self.src_dir)? {
+            let path = entry?.path();
+            let is_entry = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| ENTRY_EXTENSIONS.contains(&ext));
+            if !is_entry || !path.is_file() {
+                continue;
+            }
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                if !entries.iter().any(|(name, _)| name == stem) {
+                    entries.push((stem.to_string(), path.clone()));
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
+    /// Path of the entry point for `name`, if there is one.
+    pub fn entry(&self, name: &str) -> Option<PathBuf> {
+        ENTRY_EXTENSIONS
+            .iter()
+            .map(|ext| self.src_dir.join(format!("{}.{}", name, ext)))
+            .find(|path| path.is_file())
+    }
+
+    /// Bundle widget `name` into HTML, reusing a cached bundle when no
+    /// source file changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no entry point for `name`, esbuild
+    /// cannot be found, or the build fails (the message carries esbuild's
+    /// diagnostics).
+    pub fn build(&self, name: &str) -> Result<String> {
+        let entry = self.entry(name).ok_or_else(|| {
+            Error::not_found(format!(
+                "No widget source for '{}' in {}",
+                name,
+                self.src_dir.display()
+            ))
+        })?;
+        let fingerprint = self.fingerprint();
+
+        if let Some((cached_fp, html)) = self.memory.lock().get(name) {
+            if *cached_fp == fingerprint {
+                return Ok(html.clone());
+            }
+        }
+
+        let cached_file = self
+            .cache_dir
+            .join(format!("{}-{:016x}.html", name, fingerprint));
+        let html = match std::fs::read_to_string(&cached_file) {
+            Ok(html) => html,
+            Err(_) => {
+                let html = self.compile(name, &entry)?;
+                self.store_on_disk(name, &cached_file, &html);
+                html
+            },
+        };
+
+        self.memory
+            .lock()
+            .insert(name.to_string(), (fingerprint, html.clone()));
+        Ok(html)
+    }
+
+    /// Rebuild every widget whenever a source file changes.
+    ///
+    /// Polls the source directory every `interval` on a background thread
+    /// and calls `on_rebuild` with each widget's build result. Widgets are
+    /// built once up front. Watching stops when the returned handle is
+    /// dropped.
+    pub fn watch<F>(&self, interval: Duration, on_rebuild: F) -> WatchHandle
+    where
+        F: Fn(&str, &Result<String>) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let builder = self.clone();
+        let stopped = stop.clone();
+        std::thread::spawn(move || {
+            let mut last = None;
+            while !stopped.load(Ordering::SeqCst) {
+                let fingerprint = builder.fingerprint();
+                if last != Some(fingerprint) {
+                    last = Some(fingerprint);
+                    for (name, _) in builder.discover().unwrap_or_default() {
+                        on_rebuild(&name, &builder.build(&name));
+                    }
+                }
+                std::thread::sleep(interval);
+            }
+        });
+        WatchHandle { stop }
+    }
+
+    /// Hash of the build options and every source file's path, size, and
+    /// modification time.
+    fn fingerprint(&self) -> u64 {
+        let mut files = Vec::new();
+        collect_files(&self.src_dir, &mut files);
+        files.sort();
+
+        let mut hasher = DefaultHasher::new();
+        std::fs::canonicalize(&self.src_dir)
+            .unwrap_or_else(|_| self.src_dir.clone())
+            .hash(&mut hasher);
+        self.minify.hash(&mut hasher);
+        self.extra_args.hash(&mut hasher);
+        for file in files {
+            file.hash(&mut hasher);
+            if let Ok(meta) = std::fs::metadata(&file) {
+                meta.len().hash(&mut hasher);
+                meta.modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_nanos())
+                    .hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Write a bundle to the disk cache, replacing older bundles of the
+    /// same widget. Failures only cost a rebuild later, so they are logged.
+    fn store_on_disk(&self, name: &str, path: &Path, html: &str) {
+        let prefix = format!("{}-", name);
+        if let Ok(entries) = std::fs::read_dir(&self.cache_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                // `<name>-<16 hex digits>.html`, so `board` never removes `board-v2-...`
+                let is_stale = file_name
+                    .strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(".html"))
+                    .is_some_and(|hash| hash.len() == 16);
+                if is_stale {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+        let written =
+            std::fs::create_dir_all(&self.cache_dir).and_then(|()| std::fs::write(path, html));
+        if let Err(e) = written {
+            tracing::debug!("Could not cache widget bundle {}: {}", path.display(), e);
+        }
+    }
+
+    /// Run esbuild for one entry point and assemble the HTML page.
+    fn compile(&self, name: &str, entry: &Path) -> Result<String> {
+        let esbuild = self.find_esbuild().ok_or_else(|| {
+            Error::not_found(
+                "esbuild not found. Install the standalone binary \
+                 (curl -fsSL https://esbuild.github.io/dl/latest | sh) and put it on PATH, \
+                 or set PMCP_ESBUILD to its path",
+            )
+        })?;
+
+        let _guard = self.build_lock.lock();
+        let out_dir = self.cache_dir.join("out").join(name);
+        let _ = std::fs::remove_dir_all(&out_dir);
+
+        let mut command = Command::new(&esbuild);
+        command
+            .arg(entry)
+            .arg("--bundle")
+            .arg("--format=esm")
+            .arg("--platform=browser")
+            .arg("--target=es2020")
+            .arg("--entry-names=widget")
+            .arg("--log-level=warning")
+            .arg(format!("--outdir={}", out_dir.display()));
+        if self.minify {
+            command.arg("--minify");
+        } else {
+            command.arg("--sourcemap=inline");
+        }
+        command.args(&self.extra_args);
+
+        tracing::debug!("Building widget '{}' with {}", name, esbuild.display());
+        let output = command
+            .output()
+            .map_err(|e| Error::internal(format!("Failed to run {}: {}", esbuild.display(), e)))?;
+        if !output.status.success() {
+            return Err(Error::internal(format!(
+                "esbuild failed for {}:\n{}",
+                entry.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let js = std::fs::read_to_string(out_dir.join("widget.js"))?;
+        let css = std::fs::read_to_string(out_dir.join("widget.css")).ok();
+        let template = std::fs::read_to_string(self.src_dir.join(format!("{}.html", name))).ok();
+        Ok(assemble_html(
+            name,
+            template.as_deref(),
+            &js,
+            css.as_deref(),
+        ))
+    }
+
+    fn find_esbuild(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.esbuild {
+            return Some(path.clone());
+        }
+        if let Some(path) = std::env::var_os("ESBUILD_BINARY_PATH") {
+            return Some(PathBuf::from(path));
+        }
+        let exe = if cfg!(windows) {
+            "esbuild.exe"
+        } else {
+            "esbuild"
+        };
+        let src_dir = std::fs::canonicalize(&self.src_dir).unwrap_or_else(|_| self.src_dir.clone());
+        let local = src_dir
+            .ancestors()
+            .map(|dir| dir.join("node_modules").join(".bin").join(exe))
+            .find(|path| path.is_file());
+        local.or_else(|| {
+            std::env::var_os("PATH").and_then(|paths| {
+                std::env::split_paths(&paths)
+                    .map(|dir| dir.join(exe))
+                    .find(|path| path.is_file())
+            })
+        })
+    }
+}
+
+/// Stops a [`WidgetBuilder::watch`] loop when dropped.
+#[derive(Debug)]
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// All files under `dir`, skipping `node_modules` and hidden directories.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if name != "node_modules" && !name.starts_with('.') {
+                collect_files(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Inline the bundled script and stylesheet into the widget's HTML page.
+fn assemble_html(name: &str, template: Option<&str>, js: &str, css: Option<&str>) -> String {
+    // A literal `</script>` inside the bundle would end the inline script early
+    let script = format!(
+        "<script type=\"module\">\n{}\n</script>",
+        js.replace("</script", "<\\/script")
+    );
+    let style = css
+        .map(|css| format!("<style>\n{}\n</style>", css.replace("</style", "<\\/style")))
+        .unwrap_or_default();
+
+    let Some(template) = template else {
+        return format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>{name}</title>
+{style}
+</head>
+<body>
+<div id="root"></div>
+{script}
+</body>
+</html>"#
+        );
+    };
+
+    let mut html = template.to_string();
+    if !style.is_empty() {
+        match html.find("</head>") {
+            Some(pos) => html.insert_str(pos, &format!("{}\n", style)),
+            None => html.insert_str(0, &format!("{}\n", style)),
+        }
+    }
+    match html.rfind("</body>") {
+        Some(pos) => html.insert_str(pos, &format!("{}\n", script)),
+        None => html.push_str(&script),
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_discover_lists_top_level_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("board.tsx"), "export {}").unwrap();
+        fs::write(dir.path().join("board.html"), "<html></html>").unwrap();
+        fs::write(dir.path().join("map.ts"), "export {}").unwrap();
+        fs::create_dir(dir.path().join("lib")).unwrap();
+        fs::write(dir.path().join("lib/util.ts"), "export {}").unwrap();
+
+        let builder = WidgetBuilder::new(dir.path());
+        let names: Vec<String> = builder
+            .discover()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.0)
+            .collect();
+
+        assert_eq!(names, vec!["board", "map"]);
+        assert_eq!(builder.entry("board"), Some(dir.path().join("board.tsx")));
+        assert_eq!(builder.entry("util"), None);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_nested_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("board.ts"), "export {}").unwrap();
+        let builder = WidgetBuilder::new(dir.path());
+        let before = builder.fingerprint();
+
+        fs::create_dir(dir.path().join("lib")).unwrap();
+        fs::write(dir.path().join("lib/util.ts"), "export const x = 1").unwrap();
+        assert_ne!(builder.fingerprint(), before);
+        assert_ne!(
+            builder.clone().minify(true).fingerprint(),
+            builder.fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_assemble_html_without_template() {
+        let html = assemble_html("board", None, "console.log('</script>')", Some("body{}"));
+        assert!(html.contains("<title>board</title>"));
+        assert!(html.contains("<div id=\"root\"></div>"));
+        assert!(html.contains("<style>\nbody{}\n</style>"));
+        assert!(html.contains("console.log('<\\/script>')"));
+    }
+
+    #[test]
+    fn test_assemble_html_with_template() {
+        let template = "<html><head><title>T</title></head><body><main></main></body></html>";
+        let html = assemble_html("board", Some(template), "run()", Some("main{}"));
+
+        let style = html.find("<style>").unwrap();
+        let script = html.find("<script type=\"module\">").unwrap();
+        assert!(style < html.find("</head>").unwrap());
+        assert!(html.find("<main></main>").unwrap() < script);
+        assert!(script < html.find("</body>").unwrap());
+    }
+
+    #[test]
+    fn test_build_uses_disk_cache_without_esbuild() {
+        let src = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        fs::write(src.path().join("board.ts"), "export {}").unwrap();
+        let builder = WidgetBuilder::new(src.path())
+            .cache_dir(cache.path())
+            .esbuild("/nonexistent/esbuild");
+
+        let cached = cache
+            .path()
+            .join(format!("board-{:016x}.html", builder.fingerprint()));
+        fs::write(&cached, "<html>cached</html>").unwrap();
+
+        assert_eq!(builder.build("board").unwrap(), "<html>cached</html>");
+        // A source change invalidates the cache and needs esbuild again
+        fs::write(src.path().join("board.ts"), "export const changed = true").unwrap();
+        assert!(builder.build("board").is_err());
+        assert!(builder.build("missing").is_err());
+    }
+}
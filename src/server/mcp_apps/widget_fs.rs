@@ -9,6 +9,8 @@
 //! - Filename maps directly to MCP resource URI: `widgets/board.html` -> `ui://app/board`
 //! - Widgets are single self-contained HTML files
 //! - The server auto-injects the bridge script tag via [`WidgetDir::inject_bridge_script`]
+//! - With the `widget-build` feature and [`WidgetDir::with_build`], TypeScript
+//!   sources in `widgets/src/` are bundled on demand (see [`WidgetBuilder`](super::WidgetBuilder))
 //!
 //! # Example
 //!
@@ -50,6 +52,8 @@ pub struct WidgetEntry {
 #[derive(Debug, Clone)]
 pub struct WidgetDir {
     path: PathBuf,
+    #[cfg(feature = "widget-build")]
+    builder: Option<super::WidgetBuilder>,
 }
 
 impl WidgetDir {
@@ -59,7 +63,30 @@ impl WidgetDir {
     /// returned when [`discover`](Self::discover) or [`read_widget`](Self::read_widget)
     /// are called.
     pub fn new(path: impl Into<PathBuf>) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            #[cfg(feature = "widget-build")]
+            builder: None,
+        }
+    }
+
+    /// Bundle TypeScript widget sources from `<dir>/src` with esbuild.
+    ///
+    /// Source widgets are listed by [`discover`](Self::discover) alongside the
+    /// `.html` files and take precedence over an `.html` file of the same name.
+    #[cfg(feature = "widget-build")]
+    #[must_use]
+    pub fn with_build(self) -> Self {
+        let builder = super::WidgetBuilder::new(self.path.join("src"));
+        self.with_builder(builder)
+    }
+
+    /// Bundle TypeScript widget sources with a configured [`WidgetBuilder`](super::WidgetBuilder).
+    #[cfg(feature = "widget-build")]
+    #[must_use]
+    pub fn with_builder(mut self, builder: super::WidgetBuilder) -> Self {
+        self.builder = Some(builder);
+        self
     }
 
     /// Return the directory path this `WidgetDir` points to.
@@ -102,6 +129,25 @@ impl WidgetDir {
             }
         }
 
+        #[cfg(feature = "widget-build")]
+        if let Some(builder) = &self.builder {
+            if builder.src_dir().is_dir() {
+                for (name, source) in builder.discover()? {
+                    entries.retain(|e| e.filename != name);
+                    let abs_path = if source.is_absolute() {
+                        source
+                    } else {
+                        std::env::current_dir().unwrap_or_default().join(&source)
+                    };
+                    entries.push(WidgetEntry {
+                        uri: format!("ui://app/{}", name),
+                        filename: name,
+                        path: abs_path,
+                    });
+                }
+            }
+        }
+
         entries.sort_by(|a, b| a.filename.cmp(&b.filename));
 
         tracing::debug!(
@@ -118,7 +164,24 @@ impl WidgetDir {
     /// Reads from disk on every call (no caching) to enable hot-reload during
     /// development. If the file does not exist or cannot be read, returns a
     /// styled HTML error page with the filename and error details.
+    ///
+    /// With a build step configured, widgets that have a TypeScript source are
+    /// bundled instead (cached until a source file changes), and build errors
+    /// are shown on the error page.
     pub fn read_widget(&self, name: &str) -> String {
+        #[cfg(feature = "widget-build")]
+        if let Some(builder) = &self.builder {
+            if let Some(entry) = builder.entry(name) {
+                return match builder.build(name) {
+                    Ok(html) => html,
+                    Err(err) => {
+                        tracing::warn!("Failed to build widget {}: {}", entry.display(), err);
+                        Self::error_page(name, &entry, &err.to_string())
+                    },
+                };
+            }
+        }
+
         let file_path = self.path.join(format!("{}.html", name));
 
         match std::fs::read_to_string(&file_path) {
@@ -141,6 +204,56 @@ impl WidgetDir {
         }
     }
 
+    /// Read a widget and render its `{{ }}` placeholders with `data`.
+    ///
+    /// Lets the server ship initial state with the widget HTML instead of
+    /// waiting for a tool call. See [`render_widget_template`](super::render_widget_template)
+    /// for the placeholder syntax.
+    pub fn render_widget(&self, name: &str, data: &serde_json::Value) -> String {
+        pmcp_widget_utils::render_widget_template(&self.read_widget(name), data)
+    }
+
+    /// Map a changed file in this directory to the widget URIs it affects.
+    ///
+    /// `widgets/board.html` maps to `ui://app/board`. With a build step, a
+    /// top-level entry point in `widgets/src/` maps to its widget and any other
+    /// source file maps to every widget, since bundles may import it. Paths
+    /// outside the directory map to nothing.
+    pub fn uris_for_path(&self, path: &Path) -> Vec<String> {
+        let dir = absolute_path(&self.path);
+        let path = absolute_path(path);
+        let Ok(relative) = path.strip_prefix(&dir) else {
+            return Vec::new();
+        };
+        let stem = relative.file_stem().and_then(|s| s.to_str());
+        let depth = relative.components().count();
+
+        if depth == 1 && relative.extension().and_then(|e| e.to_str()) == Some("html") {
+            return stem
+                .map(|s| vec![format!("ui://app/{}", s)])
+                .unwrap_or_default();
+        }
+
+        #[cfg(feature = "widget-build")]
+        if self.builder.is_some() && relative.starts_with("src") {
+            let uris: Vec<String> = self
+                .discover()
+                .map(|entries| entries.into_iter().map(|e| e.uri).collect())
+                .unwrap_or_default();
+            if depth == 2 {
+                if let Some(uri) = stem
+                    .map(|s| format!("ui://app/{}", s))
+                    .filter(|uri| uris.contains(uri))
+                {
+                    return vec![uri];
+                }
+            }
+            return uris;
+        }
+
+        Vec::new()
+    }
+
     /// Insert a `<script src="{bridge_url}"></script>` tag into widget HTML.
     ///
     /// Injection strategy:
@@ -202,6 +315,7 @@ impl WidgetDir {
             font-size: 0.85rem;
             color: #ff9999;
             line-height: 1.5;
+            white-space: pre-wrap;
         }}
         .hint {{
             margin-top: 16px;
@@ -221,18 +335,49 @@ impl WidgetDir {
     </div>
 </body>
 </html>"#,
-            name = name,
-            path = path.display(),
-            error = error,
+            name = escape_html(name),
+            path = escape_html(&path.display().to_string()),
+            error = escape_html(error),
         )
     }
 }
 
+/// Resolve `path` against the current directory, as [`WidgetDir::discover`] does.
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    }
+}
+
+/// Escape text for inclusion in HTML (compiler diagnostics contain `<` and `>`).
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn test_uris_for_path_maps_html_files() {
+        let widget_dir = WidgetDir::new("/srv/app/widgets");
+        assert_eq!(
+            widget_dir.uris_for_path(Path::new("/srv/app/widgets/board.html")),
+            vec!["ui://app/board".to_string()]
+        );
+        assert!(widget_dir
+            .uris_for_path(Path::new("/srv/app/widgets/notes.txt"))
+            .is_empty());
+        assert!(widget_dir
+            .uris_for_path(Path::new("/srv/app/other/board.html"))
+            .is_empty());
+    }
+
     #[test]
     fn test_discover_finds_html_files() {
         let dir = tempfile::tempdir().unwrap();
@@ -285,6 +430,23 @@ mod tests {
         assert!(result.contains("nonexistent"));
     }
 
+    #[test]
+    fn test_render_widget_injects_data() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("board.html"),
+            r#"<h1>{{ title }}</h1><script type="application/json" id="initial-data">{{ state | json }}</script>"#,
+        )
+        .unwrap();
+
+        let widget_dir = WidgetDir::new(dir.path());
+        let data = serde_json::json!({ "title": "<Chess>", "state": { "turn": "white" } });
+        let result = widget_dir.render_widget("board", &data);
+
+        assert!(result.contains("<h1>&lt;Chess&gt;</h1>"));
+        assert!(result.contains(r#"id="initial-data">{"turn":"white"}</script>"#));
+    }
+
     #[test]
     fn test_inject_bridge_script_before_head_close() {
         let html = "<html><head><title>Test</title></head><body>Content</body></html>";
@@ -332,4 +494,28 @@ mod tests {
         assert!(page.contains("file not found"));
         assert!(page.contains("board"));
     }
+
+    #[test]
+    fn test_error_page_escapes_error() {
+        let page = WidgetDir::error_page("board", Path::new("board.tsx"), "expected \"<div>\"");
+        assert!(page.contains("expected \"&lt;div&gt;\""));
+    }
+
+    #[cfg(feature = "widget-build")]
+    #[test]
+    fn test_discover_includes_sources_when_building() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("board.html"), "<html>board</html>").unwrap();
+        fs::write(dir.path().join("map.html"), "<html>map</html>").unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/board.tsx"), "export {}").unwrap();
+        fs::write(dir.path().join("src/chart.ts"), "export {}").unwrap();
+
+        let entries = WidgetDir::new(dir.path()).with_build().discover().unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.filename.as_str()).collect();
+
+        assert_eq!(names, vec!["board", "chart", "map"]);
+        assert!(entries[0].path.ends_with("src/board.tsx"));
+        assert!(entries[2].path.ends_with("map.html"));
+    }
 }
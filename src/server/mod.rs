@@ -3,7 +3,7 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::error::{Error, Result};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::shared::{Protocol, ProtocolOptions, TransportMessage};
+use crate::shared::TransportMessage;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::types::{
     CallToolRequest, CallToolResult, ClientCapabilities, ClientRequest, GetPromptRequest,
@@ -44,43 +44,100 @@ pub mod batch;
 pub mod builder_middleware_executor;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod cancellation;
-/// Dynamic resource provider system for pattern-based resource routing.
+/// Argument autocompletion providers for `completion/complete`.
 #[cfg(not(target_arch = "wasm32"))]
+pub mod completion;
+/// Dynamic resource provider system for pattern-based resource routing.
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
 pub mod dynamic_resources;
+/// Typed event bus for session, tool, resource, and task events.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod event_bus;
+/// Typed state shared with request handlers.
+pub mod extensions;
+/// Feature flags evaluated per request for tools and widgets.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod flags;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod http_middleware;
 /// Middleware executor abstraction for consistent tool execution.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod middleware_executor;
+/// Signed, expiring pagination cursors for list results.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod pagination;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod preset;
 /// Progress reporting support for long-running operations.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod progress;
-/// Simple prompt implementations with metadata support.
+/// Prompt discovery from a directory of markdown and YAML files.
+#[cfg(all(not(target_arch = "wasm32"), feature = "prompt-dir"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "prompt-dir")))]
+pub mod prompt_dir;
+/// Token-bucket rate limiting for tool calls.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rate_limit;
+/// Resource limits for memory-constrained (serverless) deployments.
+pub mod resource_profile;
+/// Byte-range and conditional reads for `resources/read`.
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
+pub(crate) mod resource_range;
+/// Cron-scheduled workflows recorded as tasks.
+#[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
+pub mod scheduler;
+/// Startup check for breaking changes against an exported schema.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema_compat;
+/// Shared schema definitions referenced from tool schemas.
+pub mod schema_registry;
+/// Startup self-test for readiness checks.
 #[cfg(not(target_arch = "wasm32"))]
+pub mod self_test;
+/// Simple prompt implementations with metadata support.
+#[cfg(all(not(target_arch = "wasm32"), feature = "prompts"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "prompts")))]
 pub mod simple_prompt;
 /// Simple resource implementations with builder pattern support.
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
 pub mod simple_resources;
 /// Simple tool implementations with schema support.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod simple_tool;
-/// SDK-level task store trait and in-memory implementation.
+/// Per-server toggles for prompts, resources, tasks and sampling.
 #[cfg(not(target_arch = "wasm32"))]
+pub mod subsystem;
+/// SDK-level task store trait and in-memory implementation.
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
 pub mod task_store;
 /// Task routing trait for MCP Tasks integration.
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
 pub mod tasks;
 /// Tool middleware for cross-cutting concerns in tool execution.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod tool_middleware;
+/// Deadlines for tool handlers, with a default and per-tool overrides.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tool_timeout;
+/// Outbound webhooks for server lifecycle and task events.
+#[cfg(all(not(target_arch = "wasm32"), feature = "http-client"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-client")))]
+pub mod webhooks;
+/// Server-side widget state persistence keyed by session and widget URI.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod widget_state;
 
 /// Observability infrastructure for tracing, metrics, and logging.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod observability;
 /// Workflow-based prompt system with type-safe handles and ergonomic builders.
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "workflow"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
 pub mod workflow;
 
 /// State extractor for `#[mcp_tool]` shared state injection.
@@ -92,7 +149,8 @@ pub mod state;
 pub mod typed_tool;
 
 /// Typed prompt implementations with automatic argument schema generation.
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "prompts"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "prompts")))]
 pub mod typed_prompt;
 
 /// UI resource implementations for MCP Apps Extension (SEP-1865).
@@ -118,6 +176,14 @@ pub mod schema_utils;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod error_codes;
 
+/// Structured errors with JSON pointers for tool arguments that fail to deserialize.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod argument_error;
+
+/// Schema-driven coercion of string-encoded numbers and booleans in tool arguments.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod argument_coercion;
+
 /// Cross-platform path validation with security constraints.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod path_validation;
@@ -147,10 +213,17 @@ pub mod notification_debouncer;
 pub mod resource_watcher;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod roots;
+#[cfg(all(not(target_arch = "wasm32"), feature = "sampling"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+pub mod sampling;
 #[cfg(all(not(target_arch = "wasm32"), feature = "streamable-http"))]
 pub mod streamable_http_server;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "resources"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
 pub mod subscriptions;
+/// In-process test client for the streamable HTTP server.
+#[cfg(all(not(target_arch = "wasm32"), feature = "streamable-http"))]
+pub mod test_client;
 /// Tower middleware layers for MCP HTTP security (DNS rebinding, security headers).
 #[cfg(feature = "streamable-http")]
 #[cfg_attr(docsrs, doc(cfg(feature = "streamable-http")))]
@@ -310,19 +383,57 @@ pub struct Server {
     /// Roots manager for directory/URI registration
     roots_manager: Arc<RwLock<roots::RootsManager>>,
     /// Subscription manager for resource subscriptions
+    #[cfg(feature = "resources")]
     subscription_manager: Arc<RwLock<subscriptions::SubscriptionManager>>,
     /// Elicitation manager for user input requests
     elicitation_manager: Option<Arc<elicitation::ElicitationManager>>,
+    /// How long a tool waits for the client to answer an elicitation
+    elicitation_timeout: Option<std::time::Duration>,
+    /// The client's roots, fetched over the transport and cached
+    client_roots: Option<Arc<roots::ClientRoots>>,
+    /// Sends `sampling/createMessage` requests to the client
+    #[cfg(feature = "sampling")]
+    client_sampling: Option<Arc<sampling::ClientSampling>>,
+    /// How long a tool waits for the client's model to answer
+    #[cfg(feature = "sampling")]
+    sampling_timeout: Option<std::time::Duration>,
+    /// Deadlines for tool handlers
+    tool_timeouts: tool_timeout::ToolTimeouts,
+    /// Pages for `tools/list`, when the builder set a page size
+    tool_pages: Option<pagination::ListPages>,
     /// Authentication provider for validating requests
     auth_provider: Option<Arc<dyn auth::AuthProvider>>,
     /// Tool authorizer for fine-grained access control
     tool_authorizer: Option<Arc<dyn auth::ToolAuthorizer>>,
+    /// Prompt authorizer for per-prompt and per-namespace access
+    prompt_authorizer: Option<Arc<dyn auth::PromptAuthorizer>>,
     /// Tool middleware chain for cross-cutting concerns in tool execution
     #[cfg(not(target_arch = "wasm32"))]
     tool_middleware_chain: Arc<RwLock<tool_middleware::ToolMiddlewareChain>>,
     /// HTTP middleware chain for `StreamableHttpServer` (configured via `ServerBuilder`)
     #[cfg(feature = "streamable-http")]
     http_middleware: Option<Arc<http_middleware::ServerHttpMiddlewareChain>>,
+    /// Event bus for session, tool, and resource events
+    events: event_bus::EventBus,
+    /// Shared definitions referenced from tool schemas (sent with `tools/list`)
+    schema_definitions: Option<serde_json::Map<String, Value>>,
+    /// Workflows, secrets, and backends checked by `run_self_test`
+    self_test: self_test::SelfTestPlan,
+    /// Widget state store handed to tool calls and resource reads
+    widget_state: Option<Arc<dyn widget_state::WidgetStateStore>>,
+    /// Key-value store handed to tool calls and resource reads
+    kv_store: Option<Arc<dyn crate::storage::KvStore>>,
+    /// Feature flags evaluated before each tool call
+    flag_provider: Option<Arc<dyn flags::FlagProvider>>,
+    /// Typed state handed to tool calls, prompts and resource reads
+    state: extensions::Extensions,
+    /// Change sources started when the server runs on a transport
+    #[cfg(feature = "resources")]
+    resource_watchers: Vec<Arc<dyn subscriptions::ResourceWatcher>>,
+    /// Argument completion providers for `completion/complete`
+    completions: completion::CompletionRegistry,
+    /// Subsystems switched off with `ServerBuilder::disable`
+    disabled: subsystem::DisabledSubsystems,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -342,6 +453,46 @@ impl std::fmt::Debug for Server {
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Server {
+    /// Elicitation channel for tool calls, when the client supports elicitation.
+    async fn elicitation_channel(&self) -> Option<Arc<dyn elicitation::ElicitInput>> {
+        let manager = self.elicitation_manager.as_ref()?;
+        let supported = self
+            .client_capabilities
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(ClientCapabilities::supports_elicitation);
+        supported.then(|| {
+            Arc::new(elicitation::ElicitationContext::new(manager.clone()))
+                as Arc<dyn elicitation::ElicitInput>
+        })
+    }
+
+    /// The client's roots for tool calls, when the client supports roots.
+    async fn roots_channel(&self) -> Option<Arc<roots::ClientRoots>> {
+        let client_roots = self.client_roots.as_ref()?;
+        let supported = self
+            .client_capabilities
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(ClientCapabilities::supports_roots);
+        supported.then(|| client_roots.clone())
+    }
+
+    /// Sampling channel for tool calls, when the client supports sampling.
+    #[cfg(feature = "sampling")]
+    async fn sampling_channel(&self) -> Option<Arc<sampling::ClientSampling>> {
+        let client_sampling = self.client_sampling.as_ref()?;
+        let supported = self
+            .client_capabilities
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(ClientCapabilities::supports_sampling);
+        supported.then(|| client_sampling.clone())
+    }
+
     /// Check if a tool exists
     pub fn has_tool(&self, name: &str) -> bool {
         self.tools.contains_key(name)
@@ -357,6 +508,25 @@ impl Server {
         self.prompts.get(name)
     }
 
+    /// Check that the server is ready to serve traffic.
+    ///
+    /// Compiles every tool schema, reads every listed resource, re-validates
+    /// workflows, and checks the secrets and backends declared with
+    /// [`ServerBuilder::self_test_secret`] and
+    /// [`ServerBuilder::self_test_backend`]. Every check runs; see
+    /// [`self_test`] for details.
+    pub async fn run_self_test(&self) -> self_test::SelfTestReport {
+        self.self_test
+            .run(self_test::SelfTestTarget {
+                tools: &self.tool_infos,
+                definitions: self.schema_definitions.as_ref(),
+                resources: self.resources.as_ref(),
+                #[cfg(feature = "tasks")]
+                task_store: None,
+            })
+            .await
+    }
+
     /// Get the HTTP middleware chain configured via `ServerBuilder`.
     ///
     /// Returns the HTTP middleware chain that was set using
@@ -378,10 +548,8 @@ impl Server {
     ///     // ... with_http_middleware() called here
     ///     .build()?;
     ///
-    /// let config = StreamableHttpServerConfig {
-    ///     http_middleware: server.http_middleware(),
-    ///     ..Default::default()
-    /// };
+    /// let mut config = StreamableHttpServerConfig::default();
+    /// config.http_middleware = server.http_middleware();
     /// # Ok(())
     /// # }
     /// # }
@@ -400,6 +568,15 @@ impl Server {
         self.auth_provider.clone()
     }
 
+    /// Get the server's event bus.
+    ///
+    /// Use this to subscribe background jobs to [`event_bus::ServerEvent`]s
+    /// after the server is built; listeners that should be in place from the
+    /// first request are better registered with `ServerBuilder::on_event()`.
+    pub fn events(&self) -> &event_bus::EventBus {
+        &self.events
+    }
+
     /// Build tool and resource registries for workflow expansion.
     ///
     /// Creates `HashMap` registries that can be used to build an `ExpansionContext`
@@ -440,6 +617,8 @@ impl Server {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "workflow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
     pub fn build_expansion_registries(
         &self,
     ) -> (
@@ -712,64 +891,133 @@ impl Server {
                 }));
         }
 
+        // Route resource change notifications to subscribed clients
+        #[cfg(feature = "resources")]
+        {
+            if let Some(tx) = &self.notification_tx {
+                let tx = tx.clone();
+                self.subscription_manager
+                    .write()
+                    .await
+                    .set_notification_sender(move |notification| {
+                        let _ = tx.try_send(Notification::Server(notification));
+                    });
+            }
+            subscriptions::start_watchers(
+                &self.resource_watchers,
+                self.subscription_manager.clone(),
+            )
+            .await?;
+        }
+
+        // Elicitation, roots and sampling requests are sent to the client over the same transport
+        let (request_tx, request_rx) = mpsc::channel(32);
+        let mut elicitation_manager = elicitation::ElicitationManager::new();
+        elicitation_manager.set_request_channel(request_tx.clone());
+        if let Some(tx) = &self.notification_tx {
+            elicitation_manager.set_notification_channel(tx.clone());
+        }
+        if let Some(timeout) = self.elicitation_timeout {
+            elicitation_manager.set_timeout(timeout);
+        }
+        self.elicitation_manager = Some(Arc::new(elicitation_manager));
+        self.client_roots = Some(Arc::new(roots::ClientRoots::new(request_tx.clone())));
+        #[cfg(feature = "sampling")]
+        if !self.disabled.contains(subsystem::Subsystem::Sampling) {
+            let mut client_sampling = sampling::ClientSampling::new(request_tx);
+            if let Some(timeout) = self.sampling_timeout {
+                client_sampling.set_timeout(timeout);
+            }
+            self.client_sampling = Some(Arc::new(client_sampling));
+        }
+
+        self.events.emit(event_bus::ServerEvent::ServerStarted {
+            server: self.info.clone(),
+        });
+
         let server = Arc::new(self);
         let transport = Arc::new(RwLock::new(transport));
-        let protocol = Arc::new(RwLock::new(Protocol::new(ProtocolOptions::default())));
 
-        Self::spawn_notification_handler(transport.clone(), notification_rx);
-        Self::spawn_message_handler(server.clone(), transport.clone(), protocol);
+        Self::spawn_message_handler(server, transport, notification_rx, request_rx);
 
         // Keep the main task alive
         Self::run_main_loop().await
     }
 
-    /// Spawn task to handle outgoing notifications.
-    fn spawn_notification_handler(
-        transport: Arc<RwLock<impl crate::shared::Transport + 'static>>,
-        mut notification_rx: mpsc::Receiver<Notification>,
-    ) {
-        tokio::spawn(async move {
-            while let Some(notification) = notification_rx.recv().await {
-                if let Err(e) =
-                    Self::send_notification_through_transport(&transport, notification).await
-                {
-                    Self::log_error(&format!("Failed to send notification: {}", e)).await;
-                }
-            }
-        });
-    }
-
-    /// Spawn task to handle incoming messages.
+    /// Spawn the task that owns the transport.
+    ///
+    /// Incoming requests are handled on their own tasks, so a tool waiting on
+    /// the client (for example for an elicitation) does not block the
+    /// connection. Responses, notifications and server-initiated requests are
+    /// all written by this task; a pending receive is cancelled to write
+    /// them, so the transport's `receive` must be cancel-safe.
     fn spawn_message_handler(
         server: Arc<Self>,
         transport: Arc<RwLock<impl crate::shared::Transport + 'static>>,
-        _protocol: Arc<RwLock<Protocol>>,
+        mut notification_rx: mpsc::Receiver<Notification>,
+        mut request_rx: mpsc::Receiver<(RequestId, crate::types::ServerRequest)>,
     ) {
         tokio::spawn(async move {
+            let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<TransportMessage>(100);
+
             loop {
-                let message = match Self::receive_message_from_transport(&transport).await {
-                    Ok(msg) => msg,
-                    Err(e) => {
-                        Self::log_error(&format!("Transport receive error: {}", e)).await;
-                        break;
+                let outgoing = tokio::select! {
+                    Some(notification) = notification_rx.recv() => {
+                        TransportMessage::Notification(notification)
+                    },
+                    Some((id, request)) = request_rx.recv() => TransportMessage::Request {
+                        id,
+                        request: Request::Server(Box::new(request)),
+                    },
+                    Some(message) = outgoing_rx.recv() => message,
+                    received = Self::receive_message_from_transport(&transport) => {
+                        let message = match received {
+                            Ok(msg) => msg,
+                            Err(e) => {
+                                Self::log_error(&format!("Transport receive error: {}", e)).await;
+                                server
+                                    .events
+                                    .emit(event_bus::ServerEvent::SessionClosed { session_id: None });
+                                break;
+                            },
+                        };
+
+                        if let Err(e) =
+                            Self::handle_transport_message(&server, message, &outgoing_tx).await
+                        {
+                            Self::log_error(&format!("Message handling error: {}", e)).await;
+                            break;
+                        }
+                        continue;
                     },
                 };
 
-                if let Err(e) = Self::handle_transport_message(&server, &transport, message).await {
-                    Self::log_error(&format!("Message handling error: {}", e)).await;
-                    break;
+                if let Err(e) = Self::send_through_transport(&transport, outgoing).await {
+                    Self::log_error(&format!("Failed to send message: {}", e)).await;
                 }
             }
+
+            // Release tools still waiting on the client
+            if let Some(manager) = &server.elicitation_manager {
+                manager.cancel_all().await;
+            }
+            if let Some(client_roots) = &server.client_roots {
+                client_roots.cancel_all().await;
+            }
+            #[cfg(feature = "sampling")]
+            if let Some(client_sampling) = &server.client_sampling {
+                client_sampling.cancel_all().await;
+            }
         });
     }
 
-    /// Send a notification through the transport.
-    async fn send_notification_through_transport(
+    /// Send a message through the transport.
+    async fn send_through_transport(
         transport: &Arc<RwLock<impl crate::shared::Transport>>,
-        notification: Notification,
+        message: TransportMessage,
     ) -> Result<()> {
         let mut t = transport.write().await;
-        t.send(TransportMessage::Notification(notification)).await
+        t.send(message).await
     }
 
     /// Receive a message from the transport.
@@ -783,15 +1031,19 @@ impl Server {
     /// Handle a transport message.
     async fn handle_transport_message(
         server: &Arc<Self>,
-        transport: &Arc<RwLock<impl crate::shared::Transport>>,
         message: TransportMessage,
+        outgoing_tx: &mpsc::Sender<TransportMessage>,
     ) -> Result<()> {
         match message {
             TransportMessage::Request { id, request } => {
-                Self::handle_request_message(server, transport, id, request).await
+                Self::spawn_request_handler(server.clone(), id, request, outgoing_tx.clone());
+                Ok(())
             },
-            TransportMessage::Response(_) => {
-                Self::log_warning("Server received unexpected response message").await;
+            TransportMessage::Response(response) => {
+                let routed = Self::route_client_response(server, response).await;
+                if !routed {
+                    Self::log_warning("Server received unexpected response message").await;
+                }
                 Ok(())
             },
             TransportMessage::Notification(notification) => {
@@ -806,22 +1058,58 @@ impl Server {
                         .await?;
                 }
 
+                // Refetch the client's roots on next use
+                if let (
+                    Notification::Client(crate::types::ClientNotification::RootsListChanged),
+                    Some(client_roots),
+                ) = (&notification, &server.client_roots)
+                {
+                    client_roots.invalidate().await;
+                }
+
                 Self::log_debug("Server received notification").await;
                 Ok(())
             },
         }
     }
 
-    /// Handle a request message.
-    async fn handle_request_message(
-        server: &Arc<Self>,
-        transport: &Arc<RwLock<impl crate::shared::Transport>>,
+    /// Deliver the client's response to the server request waiting for it.
+    ///
+    /// Returns `false` if no request is waiting for this response ID.
+    async fn route_client_response(server: &Arc<Self>, response: JSONRPCResponse) -> bool {
+        let id = response.id.to_string();
+        if id.starts_with("roots-") {
+            if let Some(client_roots) = &server.client_roots {
+                return client_roots.handle_jsonrpc_response(response).await;
+            }
+        } else if id.starts_with("sample-") {
+            #[cfg(feature = "sampling")]
+            if let Some(client_sampling) = &server.client_sampling {
+                return client_sampling.handle_jsonrpc_response(response).await;
+            }
+        } else if let Some(manager) = &server.elicitation_manager {
+            return manager.handle_jsonrpc_response(response).await;
+        }
+        false
+    }
+
+    /// Handle a request on its own task and queue the response for sending.
+    fn spawn_request_handler(
+        server: Arc<Self>,
         id: RequestId,
         request: Request,
-    ) -> Result<()> {
-        let response = server.handle_request(id, request, None).await;
-        let mut t = transport.write().await;
-        t.send(TransportMessage::Response(response)).await
+        outgoing_tx: mpsc::Sender<TransportMessage>,
+    ) {
+        tokio::spawn(async move {
+            let response = server.handle_request(id, request, None).await;
+            if outgoing_tx
+                .send(TransportMessage::Response(response))
+                .await
+                .is_err()
+            {
+                Self::log_warning("Connection closed before the response was sent").await;
+            }
+        });
     }
 
     /// Log an error message.
@@ -861,11 +1149,20 @@ impl Server {
                 };
                 // Store client capabilities
                 *self.client_capabilities.write().await = Some(init_req.capabilities.clone());
+                if let Some(client_roots) = &self.client_roots {
+                    client_roots.invalidate().await;
+                }
                 *self.initialized.write().await = true;
 
                 let negotiated_version =
                     crate::negotiate_protocol_version(&init_req.protocol_version);
 
+                self.events.emit(event_bus::ServerEvent::SessionStarted {
+                    session_id: crate::server::cancellation::request_session(),
+                    client: init_req.client_info.clone(),
+                    protocol_version: negotiated_version.to_string(),
+                });
+
                 let result = InitializeResult {
                     protocol_version: ProtocolVersion(negotiated_version.to_string()),
                     capabilities: self.capabilities.clone(),
@@ -917,6 +1214,12 @@ impl Server {
         request: ClientRequest,
         auth_context: Option<auth::AuthContext>,
     ) -> Result<serde_json::Value> {
+        if let Some(subsystem) = self.disabled.rejects(&request) {
+            return Err(Error::protocol(
+                crate::ErrorCode::METHOD_NOT_FOUND,
+                subsystem.disabled_message(),
+            ));
+        }
         match request {
             ClientRequest::Initialize(_) => {
                 // Already handled above
@@ -924,9 +1227,24 @@ impl Server {
             },
             ClientRequest::ListTools(req) => self.handle_list_tools(req),
             ClientRequest::CallTool(req) => {
-                self.handle_call_tool(request_id, req, auth_context).await
+                let name = req.name.clone();
+                let started = std::time::Instant::now();
+                let result = self.handle_call_tool(request_id, req, auth_context).await;
+                // Tools can also report failure inside the result
+                let is_error = match &result {
+                    Ok(value) => value["isError"].as_bool().unwrap_or(false),
+                    Err(_) => true,
+                };
+                self.events.emit(event_bus::ServerEvent::ToolCalled {
+                    name,
+                    duration: started.elapsed(),
+                    is_error,
+                });
+                result
+            },
+            ClientRequest::ListPrompts(req) => {
+                self.handle_list_prompts(req, auth_context.as_ref()).await
             },
-            ClientRequest::ListPrompts(req) => self.handle_list_prompts(req),
             ClientRequest::GetPrompt(req) => {
                 self.handle_get_prompt(request_id, req, auth_context).await
             },
@@ -935,17 +1253,53 @@ impl Server {
                     .await
             },
             ClientRequest::ReadResource(req) => {
-                self.handle_read_resource(request_id, req, auth_context)
-                    .await
+                let uri = req.uri.clone();
+                let started = std::time::Instant::now();
+                let result = self
+                    .handle_read_resource(request_id, req, auth_context)
+                    .await;
+                self.events.emit(event_bus::ServerEvent::ResourceRead {
+                    uri,
+                    duration: started.elapsed(),
+                    is_error: result.is_err(),
+                });
+                result
             },
             ClientRequest::ListResourceTemplates(req) => {
                 Self::handle_list_resource_templates(self, req)
             },
-            ClientRequest::Subscribe(_)
-            | ClientRequest::Unsubscribe(_)
-            | ClientRequest::Complete(_)
-            | ClientRequest::SetLoggingLevel { level: _ }
-            | ClientRequest::Ping => Ok(serde_json::json!({})),
+            #[cfg(feature = "resources")]
+            ClientRequest::Subscribe(req) => {
+                let subscriber = subscriptions::subscriber_id(auth_context.as_ref());
+                self.subscribe_resource(req.uri, subscriber).await?;
+                Ok(serde_json::json!({}))
+            },
+            #[cfg(feature = "resources")]
+            ClientRequest::Unsubscribe(req) => {
+                let subscriber = subscriptions::subscriber_id(auth_context.as_ref());
+                self.unsubscribe_resource(req.uri, subscriber).await?;
+                Ok(serde_json::json!({}))
+            },
+            #[cfg(not(feature = "resources"))]
+            ClientRequest::Subscribe(_) | ClientRequest::Unsubscribe(_) => Err(Error::protocol(
+                crate::ErrorCode::METHOD_NOT_FOUND,
+                subsystem::Subsystem::Resources.disabled_message(),
+            )),
+            ClientRequest::Complete(req) => {
+                let request_id_str = request_id.to_string();
+                let extra = crate::server::cancellation::RequestHandlerExtra::new(
+                    request_id_str.clone(),
+                    self.cancellation_manager.create_token(request_id_str).await,
+                )
+                .with_session_id(crate::server::cancellation::request_session())
+                .with_auth_context(auth_context);
+                Ok(serde_json::to_value(
+                    self.completions.complete(&req, extra).await?,
+                )?)
+            },
+            ClientRequest::SetLoggingLevel { level: _ } | ClientRequest::Ping => {
+                Ok(serde_json::json!({}))
+            },
             ClientRequest::CreateMessage(req) => self.handle_create_message(request_id, *req).await,
             // Note: Elicitation responses are now handled as the response to
             // ServerRequest::ElicitationCreate in the JSON-RPC response flow,
@@ -954,7 +1308,8 @@ impl Server {
             ClientRequest::TasksGet(_)
             | ClientRequest::TasksResult(_)
             | ClientRequest::TasksList(_)
-            | ClientRequest::TasksCancel(_) => Err(crate::Error::protocol(
+            | ClientRequest::TasksCancel(_)
+            | ClientRequest::TasksProvideInput(_) => Err(crate::Error::protocol(
                 crate::ErrorCode::METHOD_NOT_FOUND,
                 "Tasks not supported: no task router configured",
             )),
@@ -969,26 +1324,54 @@ impl Server {
                 id,
                 payload: crate::types::jsonrpc::ResponsePayload::Result(value),
             },
+            // Protocol errors keep their code and data (e.g. rate limit retry
+            // metadata); everything else is reported as an internal error.
             Err(e) => JSONRPCResponse {
                 jsonrpc: "2.0".to_string(),
                 id,
-                payload: crate::types::jsonrpc::ResponsePayload::Error(
-                    crate::types::jsonrpc::JSONRPCError {
-                        code: -32603,
-                        message: e.to_string(),
-                        data: None,
-                    },
-                ),
+                payload: crate::types::jsonrpc::ResponsePayload::Error(e.into()),
             },
         }
     }
 
-    fn handle_list_tools(&self, _req: ListToolsRequest) -> Result<Value> {
-        let tools: Vec<ToolInfo> = self.tool_infos.values().cloned().collect();
+    /// Create a progress reporter for `token`.
+    ///
+    /// Sends through the request's own notification sink when the transport
+    /// set one (see [`progress::with_request_notifier`]), otherwise through
+    /// the server's notification channel.
+    fn progress_reporter(
+        &self,
+        token: &crate::types::ProgressToken,
+    ) -> Option<Arc<dyn progress::ProgressReporter>> {
+        let sender = progress::request_notifier().or_else(|| {
+            self.notification_tx.as_ref().map(|tx| {
+                let tx = tx.clone();
+                Arc::new(move |notification| {
+                    let _ = tx.try_send(notification);
+                }) as progress::NotificationSender
+            })
+        })?;
+        Some(Arc::new(progress::ServerProgressReporter::new(
+            token.clone(),
+            sender,
+        )))
+    }
+
+    fn handle_list_tools(&self, req: ListToolsRequest) -> Result<Value> {
+        let mut tools: Vec<ToolInfo> = self.tool_infos.values().cloned().collect();
+        let mut next_cursor = None;
+        if let Some(pages) = &self.tool_pages {
+            // Pages need the same order on every call
+            tools.sort_by(|a, b| a.name.cmp(&b.name));
+            let page = pages.page(tools, req.cursor.as_deref())?;
+            tools = page.items;
+            next_cursor = page.next_cursor;
+        }
 
         Ok(serde_json::to_value(ListToolsResult {
             tools,
-            next_cursor: None,
+            next_cursor,
+            definitions: self.schema_definitions.clone(),
         })?)
     }
 
@@ -1041,68 +1424,45 @@ impl Server {
             ._meta
             .as_ref()
             .and_then(|meta| meta.progress_token.as_ref())
-            .and_then(|token| {
-                self.notification_tx.as_ref().map(|tx| {
-                    let tx = tx.clone();
-                    let reporter = crate::server::progress::ServerProgressReporter::new(
-                        token.clone(),
-                        Arc::new(move |notification| {
-                            let _ = tx.try_send(notification);
-                        }),
-                    );
-                    Arc::new(reporter) as Arc<dyn crate::server::progress::ProgressReporter>
-                })
-            });
+            .and_then(|token| self.progress_reporter(token));
 
-        let mut extra = crate::server::cancellation::RequestHandlerExtra::new(
+        let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id.to_string(),
             cancellation_token,
         )
+        .with_session_id(crate::server::cancellation::request_session())
         .with_auth_context(validated_auth_context)
-        .with_progress_reporter(progress_reporter);
+        .with_progress_reporter(progress_reporter)
+        .with_widget_state(self.widget_state.clone())
+        .with_kv_store(self.kv_store.clone())
+        .with_elicitation(self.elicitation_channel().await)
+        .with_roots(self.roots_channel().await)
+        .with_extensions(self.state.clone());
+        #[cfg(feature = "sampling")]
+        let extra = extra.with_sampling(self.sampling_channel().await);
+        let extra = match &self.flag_provider {
+            Some(provider) => {
+                let flags = flags::evaluate_for(provider.as_ref(), &extra).await;
+                extra.with_flags(flags)
+            },
+            None => extra,
+        };
+        let widget_flags = extra.flags.clone();
 
         // Execute tool with middleware (native-only)
         #[cfg(not(target_arch = "wasm32"))]
         let result = {
             // Create tool context for middleware
-            let context = tool_middleware::ToolContext::new(&req.name, &request_id_str);
+            let context = tool_middleware::ToolContext::for_call(&req.name, &extra);
 
-            // Clone arguments for middleware processing
-            let mut args = req.arguments;
-
-            // Process request through tool middleware chain
             // Middleware rejection short-circuits tool execution
-            self.tool_middleware_chain
-                .read()
-                .await
-                .process_request(&req.name, &mut args, &mut extra, &context)
-                .await?;
-
-            // Execute the tool with potentially modified args and extra
-            let mut result = handler.handle(args, extra).await;
-
-            // Process response through tool middleware chain
-            if let Err(e) = self
-                .tool_middleware_chain
-                .read()
-                .await
-                .process_response(&req.name, &mut result, &context)
+            let chain = self.tool_middleware_chain.read().await.clone();
+            let handler = self
+                .tool_timeouts
+                .guard(&req.name, &self.events, handler.as_ref());
+            chain
+                .execute(&req.name, req.arguments, extra, &context, &handler)
                 .await
-            {
-                // Log error but continue with original result
-                tracing::warn!("Tool response middleware processing failed: {}", e);
-            }
-
-            // If tool execution failed, call handle_tool_error
-            if let Err(ref e) = result {
-                self.tool_middleware_chain
-                    .read()
-                    .await
-                    .handle_tool_error(&req.name, e, &context)
-                    .await;
-            }
-
-            result
         };
 
         // On WASM, execute tool directly without middleware
@@ -1123,32 +1483,59 @@ impl Server {
                 Err(e)
             },
         }?;
-        // Build CallToolResult, adding structured_content for widget tools
+        // Build CallToolResult, adding structured_content for widget tools and
+        // tools that declare an outputSchema
         let text = result.to_string();
         let mut call_result = CallToolResult::new(vec![crate::types::Content::text(text)]);
 
         if let Some(info) = self.tool_infos.get(&req.name) {
-            call_result = call_result.with_widget_enrichment(info, result);
+            call_result = if info.widget_meta().is_some() {
+                let mut call_result = call_result.with_widget_enrichment(info, result);
+                flags::attach_to_result(&mut call_result, &widget_flags);
+                call_result
+            } else {
+                call_result.with_structured_output(info, result)
+            };
         }
 
         Ok(serde_json::to_value(call_result)?)
     }
 
-    fn handle_list_prompts(&self, _req: ListPromptsRequest) -> Result<Value> {
-        let prompts = self
-            .prompts
-            .iter()
-            .map(|(name, handler)| {
-                // Use prompt metadata if provided, otherwise use defaults
-                if let Some(mut info) = handler.metadata() {
-                    // Ensure the name matches the registered name
-                    info.name.clone_from(name);
-                    info
-                } else {
-                    crate::types::PromptInfo::new(name)
-                }
-            })
-            .collect::<Vec<_>>();
+    /// Whether the caller may see and get `prompt_name`.
+    async fn can_access_prompt(
+        &self,
+        auth_context: Option<&auth::AuthContext>,
+        prompt_name: &str,
+    ) -> Result<bool> {
+        match (&self.prompt_authorizer, auth_context) {
+            (Some(authorizer), Some(auth_ctx)) => {
+                authorizer.can_access_prompt(auth_ctx, prompt_name).await
+            },
+            _ => Ok(true),
+        }
+    }
+
+    async fn handle_list_prompts(
+        &self,
+        req: ListPromptsRequest,
+        auth_context: Option<&auth::AuthContext>,
+    ) -> Result<Value> {
+        let mut prompts = Vec::new();
+        for (name, handler) in &self.prompts {
+            if !req.matches(name) || !self.can_access_prompt(auth_context, name).await? {
+                continue;
+            }
+            // Use prompt metadata if provided, otherwise use defaults
+            prompts.push(if let Some(mut info) = handler.metadata() {
+                // Ensure the name matches the registered name
+                info.name.clone_from(name);
+                info
+            } else {
+                crate::types::PromptInfo::new(name)
+            });
+        }
+        // Sorting keeps each namespace's prompts together
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
 
         Ok(serde_json::to_value(ListPromptsResult {
             prompts,
@@ -1162,6 +1549,18 @@ impl Server {
         req: GetPromptRequest,
         auth_context: Option<auth::AuthContext>,
     ) -> Result<Value> {
+        // Authorize before the lookup so a denied caller cannot tell whether
+        // the prompt exists
+        if !self
+            .can_access_prompt(auth_context.as_ref(), &req.name)
+            .await?
+        {
+            return Err(Error::protocol(
+                crate::error::ErrorCode::AUTHENTICATION_REQUIRED,
+                format!("Access denied for prompt '{}'", req.name),
+            ));
+        }
+
         let handler = self
             .prompts
             .get(&req.name)
@@ -1179,25 +1578,16 @@ impl Server {
             ._meta
             .as_ref()
             .and_then(|meta| meta.progress_token.as_ref())
-            .and_then(|token| {
-                self.notification_tx.as_ref().map(|tx| {
-                    let tx = tx.clone();
-                    let reporter = crate::server::progress::ServerProgressReporter::new(
-                        token.clone(),
-                        Arc::new(move |notification| {
-                            let _ = tx.try_send(notification);
-                        }),
-                    );
-                    Arc::new(reporter) as Arc<dyn crate::server::progress::ProgressReporter>
-                })
-            });
+            .and_then(|token| self.progress_reporter(token));
 
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id_str.clone(),
             cancellation_token,
         )
+        .with_session_id(crate::server::cancellation::request_session())
         .with_auth_context(auth_context)
-        .with_progress_reporter(progress_reporter);
+        .with_progress_reporter(progress_reporter)
+        .with_extensions(self.state.clone());
         let result = match handler.handle(req.arguments, extra).await {
             Ok(v) => {
                 self.cancellation_manager
@@ -1231,6 +1621,7 @@ impl Server {
                 request_id_str.clone(),
                 cancellation_token,
             )
+            .with_session_id(crate::server::cancellation::request_session())
             .with_auth_context(auth_context);
             let mut result = match handler.list(req.cursor, extra).await {
                 Ok(v) => {
@@ -1287,25 +1678,18 @@ impl Server {
             ._meta
             .as_ref()
             .and_then(|meta| meta.progress_token.as_ref())
-            .and_then(|token| {
-                self.notification_tx.as_ref().map(|tx| {
-                    let tx = tx.clone();
-                    let reporter = crate::server::progress::ServerProgressReporter::new(
-                        token.clone(),
-                        Arc::new(move |notification| {
-                            let _ = tx.try_send(notification);
-                        }),
-                    );
-                    Arc::new(reporter) as Arc<dyn crate::server::progress::ProgressReporter>
-                })
-            });
+            .and_then(|token| self.progress_reporter(token));
 
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id_str.clone(),
             cancellation_token,
         )
+        .with_session_id(crate::server::cancellation::request_session())
         .with_auth_context(auth_context)
-        .with_progress_reporter(progress_reporter);
+        .with_progress_reporter(progress_reporter)
+        .with_widget_state(self.widget_state.clone())
+        .with_kv_store(self.kv_store.clone())
+        .with_extensions(self.state.clone());
         let mut result = match handler.read(&req.uri, extra).await {
             Ok(v) => {
                 self.cancellation_manager
@@ -1331,6 +1715,8 @@ impl Server {
                 }
             }
         }
+        #[cfg(feature = "resources")]
+        let result = resource_range::apply(&req, result)?;
         Ok(serde_json::to_value(result)?)
     }
 
@@ -1469,6 +1855,30 @@ impl Server {
         roots_manager.get_roots().await
     }
 
+    /// Get the roots the connected client has exposed to the server.
+    ///
+    /// Unlike [`get_roots`](Self::get_roots), which lists roots registered by
+    /// this server, this asks the client with `roots/list`. The result is
+    /// cached until the client sends `notifications/roots/list_changed`.
+    /// Tool handlers use [`RequestHandlerExtra::roots`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UNSUPPORTED_CAPABILITY` if the server is not running on a
+    /// transport or the client did not declare the `roots` capability, the
+    /// client's error response, or `REQUEST_TIMEOUT` if it does not answer.
+    ///
+    /// [`RequestHandlerExtra::roots`]: crate::server::cancellation::RequestHandlerExtra::roots
+    pub async fn roots(&self) -> Result<Vec<roots::Root>> {
+        let client_roots = self.roots_channel().await.ok_or_else(|| {
+            Error::protocol(
+                crate::error::ErrorCode::UNSUPPORTED_CAPABILITY,
+                "Roots are not available: the client did not declare the roots capability",
+            )
+        })?;
+        client_roots.list().await
+    }
+
     /// Subscribe a client to resource updates.
     ///
     /// This method allows the server to track which clients are interested
@@ -1499,6 +1909,8 @@ impl Server {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
     pub async fn subscribe_resource(&self, uri: String, client_id: String) -> Result<()> {
         if uri.is_empty() || client_id.is_empty() {
             return Err(Error::invalid_params("URI and client_id must not be empty"));
@@ -1585,6 +1997,8 @@ impl Server {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
     pub async fn unsubscribe_resource(&self, uri: String, client_id: String) -> Result<()> {
         if uri.is_empty() || client_id.is_empty() {
             return Err(Error::invalid_params("URI and client_id must not be empty"));
@@ -1603,6 +2017,8 @@ impl Server {
     /// # Returns
     ///
     /// The number of subscribers that were notified.
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
     pub async fn notify_resource_updated(&self, uri: String) -> Result<usize> {
         let mut subscription_manager = self.subscription_manager.write().await;
         if let Some(tx) = &self.notification_tx {
@@ -1665,6 +2081,8 @@ pub struct ServerBuilder {
     auth_provider: Option<Arc<dyn auth::AuthProvider>>,
     /// Tool authorizer for fine-grained access control
     tool_authorizer: Option<Arc<dyn auth::ToolAuthorizer>>,
+    /// Prompt authorizer for per-prompt and per-namespace access
+    prompt_authorizer: Option<Arc<dyn auth::PromptAuthorizer>>,
     /// Tool protection requirements to be applied at build time
     tool_protections: HashMap<String, Vec<String>>,
     /// Tool middleware chain for cross-cutting concerns
@@ -1676,10 +2094,64 @@ pub struct ServerBuilder {
     /// Host layers for MCP Apps metadata enrichment (e.g., `ChatGPT`)
     #[cfg(feature = "mcp-apps")]
     host_layers: Vec<crate::types::mcp_apps::HostType>,
+    /// Widgets that tool `_meta` references are validated against
+    #[cfg(feature = "mcp-apps")]
+    widget_manifest: Option<mcp_apps::WidgetManifest>,
+    /// Tools registered with `tool_with_form`
+    #[cfg(feature = "mcp-apps")]
+    form_tools: Vec<String>,
     /// Optional website URL for the server implementation (MCP 2025-11-25)
     website_url: Option<String>,
     /// Optional icons for the server implementation (MCP 2025-11-25)
     icons: Option<Vec<crate::types::protocol::IconInfo>>,
+    /// Event bus shared with the built server
+    events: event_bus::EventBus,
+    /// Shared definitions referenced from tool schemas
+    schema_registry: schema_registry::SchemaRegistry,
+    /// Workflows, secrets, and backends checked by `run_self_test`
+    self_test: self_test::SelfTestPlan,
+    /// `(tool, widget URI)` pairs registered with `tool_with_widget`
+    tool_widgets: Vec<(String, String)>,
+    /// Prompt directories registered with `prompt_dir`
+    #[cfg(feature = "prompt-dir")]
+    prompt_dirs: Vec<prompt_dir::PromptDir>,
+    /// Last exported schema registered with `schema_baseline`
+    schema_baseline: Option<(
+        schema_compat::SchemaBaseline,
+        schema_compat::SchemaCheckMode,
+    )>,
+    /// Widget state store exposed to handlers via `RequestHandlerExtra`
+    widget_state: Option<Arc<dyn widget_state::WidgetStateStore>>,
+    /// Key-value store exposed to handlers via `RequestHandlerExtra::kv`
+    kv_store: Option<Arc<dyn crate::storage::KvStore>>,
+    /// Feature flags exposed to handlers via `RequestHandlerExtra::flags`
+    flag_provider: Option<Arc<dyn flags::FlagProvider>>,
+    /// Timeout for `RequestHandlerExtra::elicit`
+    elicitation_timeout: Option<std::time::Duration>,
+    /// Timeout for `RequestHandlerExtra::sample`
+    #[cfg(feature = "sampling")]
+    sampling_timeout: Option<std::time::Duration>,
+    /// Deadlines for tool handlers
+    tool_timeouts: tool_timeout::ToolTimeouts,
+    /// Deadlines for workflow steps, set from `tool_timeouts` on `build()`
+    #[cfg(feature = "workflow")]
+    workflow_tool_timeouts: tool_timeout::DeferredToolTimeouts,
+    /// Tools per `tools/list` page (all tools in one page when `None`)
+    tools_page_size: Option<usize>,
+    /// Codec for `tools/list` cursors (a random key when `None`)
+    cursor_codec: Option<pagination::CursorCodec>,
+    /// Typed state exposed to handlers via `RequestHandlerExtra::state`
+    state: extensions::Extensions,
+    /// Change sources for resource subscriptions
+    #[cfg(feature = "resources")]
+    resource_watchers: Vec<Arc<dyn subscriptions::ResourceWatcher>>,
+    /// Argument completion providers
+    completions: completion::CompletionRegistry,
+    /// Subsystems switched off with `disable`
+    disabled: subsystem::DisabledSubsystems,
+    /// Client for workflow steps on foundation servers
+    #[cfg(all(feature = "workflow", feature = "composition"))]
+    foundation_client: Option<Arc<dyn crate::composition::FoundationClient>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1732,6 +2204,7 @@ impl ServerBuilder {
             roots_manager: roots::RootsManager::new(),
             auth_provider: None,
             tool_authorizer: None,
+            prompt_authorizer: None,
             tool_protections: HashMap::new(),
             #[cfg(not(target_arch = "wasm32"))]
             tool_middlewares: Vec::new(),
@@ -1739,8 +2212,37 @@ impl ServerBuilder {
             http_middleware: None,
             #[cfg(feature = "mcp-apps")]
             host_layers: Vec::new(),
+            #[cfg(feature = "mcp-apps")]
+            widget_manifest: None,
+            #[cfg(feature = "mcp-apps")]
+            form_tools: Vec::new(),
             website_url: None,
             icons: None,
+            events: event_bus::EventBus::new(),
+            schema_registry: schema_registry::SchemaRegistry::new(),
+            self_test: self_test::SelfTestPlan::default(),
+            tool_widgets: Vec::new(),
+            #[cfg(feature = "prompt-dir")]
+            prompt_dirs: Vec::new(),
+            schema_baseline: None,
+            widget_state: None,
+            kv_store: None,
+            flag_provider: None,
+            elicitation_timeout: None,
+            #[cfg(feature = "sampling")]
+            sampling_timeout: None,
+            tool_timeouts: tool_timeout::ToolTimeouts::new(),
+            #[cfg(feature = "workflow")]
+            workflow_tool_timeouts: Arc::default(),
+            tools_page_size: None,
+            cursor_codec: None,
+            state: extensions::Extensions::new(),
+            #[cfg(feature = "resources")]
+            resource_watchers: Vec::new(),
+            completions: completion::CompletionRegistry::default(),
+            disabled: subsystem::DisabledSubsystems::default(),
+            #[cfg(all(feature = "workflow", feature = "composition"))]
+            foundation_client: None,
         }
     }
 
@@ -1886,6 +2388,87 @@ impl ServerBuilder {
         self
     }
 
+    /// Add a tool whose results render in the widget at `widget_uri`.
+    ///
+    /// Sets the tool's `_meta.ui.resourceUri` (plus the legacy
+    /// `ui/resourceUri` key) so `_meta` does not have to be written by hand;
+    /// host layers such as `HostType::ChatGpt` add `openai/outputTemplate`
+    /// from it. [`build`](Self::build) fails if `widget_uri` is not a
+    /// `ui://` URI or no resource handler is registered, and
+    /// [`Server::run_self_test`] reads the widget to confirm it is served.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use pmcp::{Server, RequestHandlerExtra, ToolHandler};
+    /// # use pmcp::server::simple_resources::ResourceCollection;
+    /// # use async_trait::async_trait;
+    /// # use serde_json::Value;
+    /// # struct ChessMove;
+    /// # #[async_trait]
+    /// # impl ToolHandler for ChessMove {
+    /// #     async fn handle(&self, args: Value, _: RequestHandlerExtra) -> pmcp::Result<Value> { Ok(args) }
+    /// # }
+    /// let server = Server::builder()
+    ///     .name("chess")
+    ///     .version("1.0.0")
+    ///     .tool_with_widget("chess_move", ChessMove, "ui://app/board")
+    ///     .resources(ResourceCollection::new())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn tool_with_widget(
+        self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+        widget_uri: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        let mut builder = self.tool(name.clone(), handler);
+        builder.tool_widgets.push((name, widget_uri.into()));
+        builder
+    }
+
+    /// Add a tool with a generated form widget.
+    ///
+    /// The widget at `ui://pmcp/form/<name>` renders the tool's input schema
+    /// as a form, calls the tool when submitted and shows the result, so the
+    /// tool gets a UI without any widget code. It is served alongside the
+    /// resources of the handler passed to [`resources`](Self::resources), if
+    /// any. See [`render_form_widget`](mcp_apps::render_form_widget) for how
+    /// schemas map to form controls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use pmcp::{Server, RequestHandlerExtra, ToolHandler};
+    /// # use async_trait::async_trait;
+    /// # use serde_json::Value;
+    /// # struct SearchOrders;
+    /// # #[async_trait]
+    /// # impl ToolHandler for SearchOrders {
+    /// #     async fn handle(&self, args: Value, _: RequestHandlerExtra) -> pmcp::Result<Value> { Ok(args) }
+    /// # }
+    /// let server = Server::builder()
+    ///     .name("orders")
+    ///     .version("1.0.0")
+    ///     .tool_with_form("search_orders", SearchOrders)
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    #[cfg(feature = "mcp-apps")]
+    pub fn tool_with_form(
+        self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+    ) -> Self {
+        let name = name.into();
+        let uri = mcp_apps::form_widget_uri(&name);
+        let mut builder = self.tool_with_widget(name.clone(), handler, uri);
+        builder.form_tools.push(name);
+        builder
+    }
+
     /// Register all tools and prompts from an `#[mcp_server]` annotated type.
     ///
     /// This is the ergonomic counterpart to individually registering tools and
@@ -2133,6 +2716,11 @@ impl ServerBuilder {
     /// This is a convenience overload that allows setting a description directly
     /// without needing to chain `.with_description()`.
     ///
+    /// The handler may return any `Serialize + JsonSchema` type. When that type
+    /// is a struct (its schema is an object), the tool advertises it as
+    /// `outputSchema` and results are returned as `structuredContent` as well as
+    /// text. Handlers returning `serde_json::Value` get no output schema.
+    ///
     /// # Example
     /// ```no_run
     /// # #[cfg(feature = "schema-generation")]
@@ -2151,6 +2739,12 @@ impl ServerBuilder {
     ///     op: String,
     /// }
     ///
+    /// #[derive(Debug, Serialize, JsonSchema)]
+    /// struct MathResult {
+    ///     /// Result of the operation
+    ///     result: f64,
+    /// }
+    ///
     /// let server = ServerBuilder::new()
     ///     .name("example")
     ///     .tool_typed_sync_with_description(
@@ -2164,13 +2758,13 @@ impl ServerBuilder {
     ///                 "divide" => args.a / args.b,
     ///                 _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
     ///             };
-    ///             Ok(serde_json::json!({ "result": result }))
+    ///             Ok(MathResult { result })
     ///         }
     ///     );
     /// # }
     /// ```
     #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_sync_with_description<T, F>(
+    pub fn tool_typed_sync_with_description<T, TOut, F>(
         mut self,
         name: impl Into<String>,
         description: impl Into<String>,
@@ -2178,14 +2772,20 @@ impl ServerBuilder {
     ) -> Self
     where
         T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<serde_json::Value>
-            + Send
-            + Sync
-            + 'static,
+        TOut: serde::Serialize + schemars::JsonSchema + Send + Sync + 'static,
+        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<TOut> + Send + Sync + 'static,
     {
-        use crate::server::typed_tool::TypedSyncTool;
+        use crate::server::typed_tool::{generate_output_schema, TypedSyncTool};
         let name_str = name.into();
-        let tool = TypedSyncTool::new(name_str.clone(), handler).with_description(description);
+        let to_value = move |args: T, extra: crate::RequestHandlerExtra| -> crate::Result<Value> {
+            let output = handler(args, extra)?;
+            serde_json::to_value(output)
+                .map_err(|e| Error::internal(format!("Failed to serialize result: {}", e)))
+        };
+        let mut tool = TypedSyncTool::new(name_str.clone(), to_value).with_description(description);
+        if let Some(schema) = generate_output_schema::<TOut>() {
+            tool = tool.with_output_schema(schema);
+        }
         self.tools.insert(name_str, Arc::new(tool));
 
         // Update capabilities to include tools
@@ -2404,34 +3004,82 @@ impl ServerBuilder {
         self
     }
 
-    /// Register a workflow-based prompt with automatic validation.
-    ///
-    /// This method validates the workflow before registration and converts it
-    /// to a prompt handler. The workflow's instructions become the prompt messages,
-    /// and the workflow's arguments become the prompt arguments.
-    ///
-    /// # Arguments
-    ///
-    /// * `workflow` - The workflow definition to register as a prompt
+    /// Register one prompt per markdown or YAML file in a directory.
     ///
-    /// # Errors
+    /// Files are discovered and parsed when the server is built, and re-read
+    /// on every request so prompt text can be edited without a restart. See
+    /// [`PromptDir`](crate::server::prompt_dir::PromptDir) for the file format.
     ///
-    /// Returns an error if the workflow validation fails (e.g., undefined bindings,
-    /// undefined prompt arguments, etc.).
+    /// [`build`](Self::build) fails if the directory cannot be read, a file
+    /// does not parse, or a prompt name is already registered.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use pmcp::{Server, ServerBuilder};
-    /// use pmcp::server::workflow::{SequentialWorkflow, InternalPromptMessage};
-    /// use pmcp::types::Role;
+    /// use pmcp::server::prompt_dir::PromptDir;
+    /// use pmcp::Server;
     ///
-    /// # fn main() -> pmcp::Result<()> {
-    /// let workflow = SequentialWorkflow::new(
-    ///     "code_review_workflow",
-    ///     "Review code with multiple steps"
-    /// )
-    /// .argument("code", "Code to review", true)
+    /// let server = Server::builder()
+    ///     .name("reviewer")
+    ///     .version("1.0.0")
+    ///     .prompt_dir(PromptDir::new("prompts"))
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    #[cfg(feature = "prompt-dir")]
+    pub fn prompt_dir(mut self, dir: prompt_dir::PromptDir) -> Self {
+        self.prompt_dirs.push(dir);
+        if self.capabilities.prompts.is_none() {
+            self.capabilities.prompts = Some(crate::types::PromptCapabilities {
+                list_changed: Some(false),
+            });
+        }
+        self
+    }
+
+    /// Set the client for workflow steps on foundation servers.
+    ///
+    /// Steps built with [`ToolHandle::remote`](workflow::ToolHandle::remote)
+    /// call their tool through this client. Set it before registering
+    /// workflows that use remote steps.
+    #[cfg(all(feature = "workflow", feature = "composition"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "workflow", feature = "composition"))))]
+    pub fn foundation_client(
+        mut self,
+        client: Arc<dyn crate::composition::FoundationClient>,
+    ) -> Self {
+        self.foundation_client = Some(client);
+        self
+    }
+
+    /// Register a workflow-based prompt with automatic validation.
+    ///
+    /// This method validates the workflow before registration and converts it
+    /// to a prompt handler. The workflow's instructions become the prompt messages,
+    /// and the workflow's arguments become the prompt arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow` - The workflow definition to register as a prompt
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workflow validation fails (e.g., undefined bindings,
+    /// undefined prompt arguments, etc.).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, ServerBuilder};
+    /// use pmcp::server::workflow::{SequentialWorkflow, InternalPromptMessage};
+    /// use pmcp::types::Role;
+    ///
+    /// # fn main() -> pmcp::Result<()> {
+    /// let workflow = SequentialWorkflow::new(
+    ///     "code_review_workflow",
+    ///     "Review code with multiple steps"
+    /// )
+    /// .argument("code", "Code to review", true)
     /// .instruction(InternalPromptMessage::new(
     ///     Role::System,
     ///     "You are a code reviewer. Review the provided code carefully."
@@ -2445,6 +3093,8 @@ impl ServerBuilder {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "workflow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "workflow")))]
     pub fn prompt_workflow(mut self, workflow: workflow::SequentialWorkflow) -> Result<Self> {
         // Validate the workflow before registration
         workflow
@@ -2467,16 +3117,23 @@ impl ServerBuilder {
             }
         }
 
-        // Build tool handlers map for workflow execution
-        // Clone Arc references for shared ownership
+        // Build tool handlers map for workflow execution; steps run each tool
+        // under the deadline a direct call would get
         let mut tool_handlers: std::collections::HashMap<Arc<str>, Arc<dyn ToolHandler>> =
             std::collections::HashMap::new();
         for (name, handler) in &self.tools {
-            tool_handlers.insert(Arc::from(name.as_str()), Arc::clone(handler));
+            let handler = tool_timeout::DeferredTimedHandler::new(
+                name.clone(),
+                Arc::clone(handler),
+                self.workflow_tool_timeouts.clone(),
+                self.events.clone(),
+            );
+            tool_handlers.insert(Arc::from(name.as_str()), Arc::new(handler));
         }
 
         // Get the workflow name before moving it
         let name = workflow.name().to_string();
+        self.self_test.add_workflow(workflow.clone());
 
         // Create workflow prompt handler with tool execution and resource fetching capability
         // Note: Workflow prompts in ServerBuilder do not currently execute tool middleware.
@@ -2487,6 +3144,14 @@ impl ServerBuilder {
             tool_handlers,
             self.resources.clone(),
         );
+        #[cfg(feature = "composition")]
+        let handler = match &self.foundation_client {
+            Some(client) => handler.with_foundation_client(client.clone()),
+            None => handler,
+        };
+        handler
+            .check_remote_steps()
+            .map_err(|e| Error::Validation(format!("Workflow validation failed: {}", e)))?;
 
         // Register as a prompt
         self.prompts.insert(name, Arc::new(handler));
@@ -2697,6 +3362,29 @@ impl ServerBuilder {
         self
     }
 
+    /// Set a prompt authorizer for per-prompt or per-namespace access control.
+    ///
+    /// Prompts the authenticated caller may not access are hidden from
+    /// `prompts/list` and rejected by `prompts/get`. Requests without an
+    /// auth context are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, auth::NamespaceAuthorizer};
+    ///
+    /// let server = Server::builder()
+    ///     .name("ops-server")
+    ///     .version("1.0.0")
+    ///     .prompt_authorizer(NamespaceAuthorizer::new().require_scopes("admin", ["mcp:admin"]))
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn prompt_authorizer(mut self, authorizer: impl auth::PromptAuthorizer + 'static) -> Self {
+        self.prompt_authorizer = Some(Arc::new(authorizer));
+        self
+    }
+
     /// Protect a specific tool with required scopes.
     ///
     /// This is a convenience method that creates or updates a scope-based authorizer
@@ -2818,18 +3506,55 @@ impl ServerBuilder {
     ///
     /// # Middleware Execution Order
     ///
-    /// Multiple middleware are executed in FIFO order for requests and FIFO for responses:
+    /// Middleware run in priority order for requests (registration order for
+    /// equal priorities) and in reverse order for responses:
     ///
     /// ```text
     /// Request:  Middleware1 → Middleware2 → Tool Handler
-    /// Response: Tool Handler → Middleware1 → Middleware2
+    /// Response: Tool Handler → Middleware2 → Middleware1
     /// ```
+    ///
+    /// Wrap a middleware in
+    /// [`FilteredMiddleware`](tool_middleware::FilteredMiddleware) to apply it
+    /// to some tools only.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn tool_middleware(mut self, middleware: Arc<dyn tool_middleware::ToolMiddleware>) -> Self {
         self.tool_middlewares.push(middleware);
         self
     }
 
+    /// Subscribe to server events.
+    ///
+    /// The listener is called for every [`event_bus::ServerEvent`]: sessions
+    /// starting and closing, tool calls, and resource reads. It runs on the
+    /// dispatch path, so keep it cheap or hand the event off to a task.
+    /// Can be called multiple times; listeners run in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::event_bus::ServerEvent;
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("audited-server")
+    ///     .version("1.0.0")
+    ///     .on_event(|event| {
+    ///         if let ServerEvent::ToolCalled { name, duration, .. } = event {
+    ///             tracing::info!(tool = %name, ?duration, "tool called");
+    ///         }
+    ///     })
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn on_event(
+        self,
+        listener: impl Fn(&event_bus::ServerEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.events.on_event(listener);
+        self
+    }
+
     /// Enable observability for this server.
     ///
     /// This adds observability middleware that provides:
@@ -2882,13 +3607,7 @@ impl ServerBuilder {
         }
 
         // Create backend based on configuration
-        let backend: Arc<dyn observability::ObservabilityBackend> = match config.backend.as_str() {
-            "cloudwatch" => Arc::new(observability::CloudWatchBackend::new(
-                config.cloudwatch.clone(),
-            )),
-            "null" => Arc::new(observability::NullBackend),
-            _ => Arc::new(observability::ConsoleBackend::new(config.console.pretty)),
-        };
+        let backend = observability::build_backend(&config);
 
         // Get server name for middleware (use placeholder if not yet set)
         let server_name = self.name.clone().unwrap_or_else(|| "unknown".to_string());
@@ -3038,10 +3757,8 @@ impl ServerBuilder {
     ///     .build()?;
     ///
     /// // Later when creating StreamableHttpServer:
-    /// // let config = StreamableHttpServerConfig {
-    /// //     http_middleware: server.http_middleware(),
-    /// //     ..Default::default()
-    /// // };
+    /// // let mut config = StreamableHttpServerConfig::default();
+    /// // config.http_middleware = server.http_middleware();
     /// # Ok(())
     /// # }
     /// ```
@@ -3070,129 +3787,658 @@ impl ServerBuilder {
         self
     }
 
-    /// Build the server.
+    /// Validate tool widget references against a [`WidgetManifest`](mcp_apps::WidgetManifest)
+    /// at build time.
     ///
-    /// Constructs the final Server instance from the configured builder.
-    /// This validates that required fields (name and version) are set.
+    /// [`build`](Self::build) fails if a tool's `_meta` points at a widget URI
+    /// the manifest does not list, or a widget depends on a tool that is not
+    /// registered.
+    #[cfg(feature = "mcp-apps")]
+    pub fn widget_manifest(mut self, manifest: mcp_apps::WidgetManifest) -> Self {
+        self.widget_manifest = Some(manifest);
+        self
+    }
+
+    /// Register a schema definition shared between tools.
     ///
-    /// # Errors
+    /// Subschemas of tool input and output schemas that match `schema` are
+    /// replaced with `{"$ref": "#/definitions/<name>"}`, and the definition
+    /// is sent once, under `definitions` in the `tools/list` result. See
+    /// [`SchemaRegistry`](schema_registry::SchemaRegistry) for the matching
+    /// rules.
+    pub fn schema_definition(mut self, name: impl Into<String>, schema: Value) -> Self {
+        self.schema_registry.insert(name, schema);
+        self
+    }
+
+    /// Register the schema generated for `T` as a shared definition named
+    /// after the type.
     ///
-    /// Returns an error if:
-    /// - The server name is not set
-    /// - The server version is not set
-    pub fn build(self) -> Result<Server> {
-        let name = self
-            .name
-            .ok_or_else(|| crate::Error::validation("Server name is required"))?;
-        let version = self
-            .version
-            .ok_or_else(|| crate::Error::validation("Server version is required"))?;
+    /// See [`schema_definition`](Self::schema_definition).
+    #[cfg(feature = "schema-generation")]
+    pub fn schema_type<T: schemars::JsonSchema>(mut self) -> Self {
+        self.schema_registry.insert_type::<T>();
+        self
+    }
 
-        // Apply tool protections
-        let tool_authorizer = if !self.tool_protections.is_empty() {
-            if self.tool_authorizer.is_some() {
-                // If there's an existing authorizer and tool protections are specified,
-                // this is a configuration error
-                return Err(crate::Error::validation(
-                    "Cannot use protect_tool() with a custom tool_authorizer. \
-                     Either use protect_tool() to configure scope-based authorization, \
-                     or provide a custom ToolAuthorizer implementation, but not both.",
-                ));
-            }
-            // Create a ScopeBasedAuthorizer with all the tool protections
-            let mut authorizer = auth::ScopeBasedAuthorizer::new();
-            for (tool_name, scopes) in self.tool_protections {
-                authorizer = authorizer.require_scopes(tool_name, scopes);
-            }
-            Some(Arc::new(authorizer) as Arc<dyn auth::ToolAuthorizer>)
-        } else {
-            self.tool_authorizer
-        };
+    /// Require the environment variable `name` to be set and non-empty when
+    /// [`Server::run_self_test`] runs.
+    pub fn self_test_secret(mut self, name: impl Into<String>) -> Self {
+        self.self_test.add_secret(name);
+        self
+    }
 
-        // Initialize tool middleware chain
-        #[cfg(not(target_arch = "wasm32"))]
-        let tool_middleware_chain = {
-            let mut chain = tool_middleware::ToolMiddlewareChain::new();
-            for middleware in self.tool_middlewares {
-                chain.add(middleware);
-            }
-            Arc::new(RwLock::new(chain))
-        };
+    /// Add a reachability check for an external backend (a database, an API,
+    /// a foundation server) to [`Server::run_self_test`].
+    ///
+    /// The backend counts as reachable when `check` returns `Ok`.
+    pub fn self_test_backend<F, Fut>(mut self, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.self_test
+            .add_backend(self_test::BackendCheck::new(name, check));
+        self
+    }
 
-        // Build tool_infos cache at construction time (mirrors ServerCore pattern)
-        let tool_infos: HashMap<String, ToolInfo> = self
-            .tools
-            .iter()
-            .map(|(name, handler)| {
-                let info = handler.metadata().unwrap_or_else(|| {
-                    ToolInfo::new(
-                        name.clone(),
-                        None,
-                        serde_json::json!({"type": "object", "properties": {}}),
-                    )
-                });
-                (name.clone(), info)
-            })
-            .collect();
+    /// Check the server against the last exported schema.
+    ///
+    /// Tools and prompts are compared in [`build`](Self::build), resources
+    /// in [`Server::run_self_test`]. Breaking changes without a breaking
+    /// version bump fail both in [`SchemaCheckMode::Strict`] and are logged
+    /// in [`SchemaCheckMode::Warn`]. See [`schema_compat`] for what counts
+    /// as breaking.
+    ///
+    /// [`SchemaCheckMode::Strict`]: schema_compat::SchemaCheckMode::Strict
+    /// [`SchemaCheckMode::Warn`]: schema_compat::SchemaCheckMode::Warn
+    pub fn schema_baseline(
+        mut self,
+        baseline: schema_compat::SchemaBaseline,
+        mode: schema_compat::SchemaCheckMode,
+    ) -> Self {
+        self.schema_baseline = Some((baseline, mode));
+        self
+    }
 
-        // Apply host layer enrichment to tool _meta (e.g., ChatGPT openai/* keys)
-        #[cfg(feature = "mcp-apps")]
-        let tool_infos = {
-            let mut infos = tool_infos;
-            for host in &self.host_layers {
-                for info in infos.values_mut() {
-                    if let Some(meta) = info._meta.as_mut() {
-                        core::enrich_meta_for_host(meta, *host);
-                    }
-                }
-            }
-            infos
-        };
+    /// Register a store for persisting widget state across reloads.
+    ///
+    /// Tool and resource handlers reach the store through
+    /// [`RequestHandlerExtra::load_widget_state`] and
+    /// [`RequestHandlerExtra::save_widget_state`], keyed by the request's
+    /// session and the widget URI.
+    ///
+    /// [`RequestHandlerExtra::load_widget_state`]: crate::server::cancellation::RequestHandlerExtra::load_widget_state
+    /// [`RequestHandlerExtra::save_widget_state`]: crate::server::cancellation::RequestHandlerExtra::save_widget_state
+    pub fn widget_state_store(mut self, store: Arc<dyn widget_state::WidgetStateStore>) -> Self {
+        self.widget_state = Some(store);
+        self
+    }
 
-        // Build URI-to-tool-meta index for widget resource _meta propagation
-        let uri_to_tool_meta = core::build_uri_to_tool_meta(&tool_infos);
+    /// Register a key-value store for handler state.
+    ///
+    /// Tool and resource handlers reach the store through
+    /// [`RequestHandlerExtra::kv`]. See [`crate::storage`] for the available
+    /// backends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::storage::MemoryKvStore;
+    /// use pmcp::Server;
+    /// use std::sync::Arc;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("notes")
+    ///     .version("1.0.0")
+    ///     .kv_store(Arc::new(MemoryKvStore::new()))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::kv`]: crate::server::cancellation::RequestHandlerExtra::kv
+    pub fn kv_store(mut self, store: Arc<dyn crate::storage::KvStore>) -> Self {
+        self.kv_store = Some(store);
+        self
+    }
 
-        Ok(Server {
-            info: {
-                let mut info = Implementation::new(&name, &version);
-                if let Some(url) = self.website_url {
-                    info = info.with_website_url(url);
-                }
-                if let Some(icons) = self.icons {
-                    info = info.with_icons(icons);
-                }
-                info
-            },
-            capabilities: self.capabilities,
-            tools: self.tools,
-            tool_infos,
-            uri_to_tool_meta,
-            prompts: self.prompts,
-            resources: self.resources,
-            sampling: self.sampling,
-            client_capabilities: Arc::new(RwLock::new(None)),
-            initialized: Arc::new(RwLock::new(false)),
-            notification_tx: None,
-            cancellation_manager: self.cancellation_manager,
-            roots_manager: Arc::new(RwLock::new(self.roots_manager)),
-            subscription_manager: Arc::new(RwLock::new(subscriptions::SubscriptionManager::new())),
-            elicitation_manager: None,
-            auth_provider: self.auth_provider,
-            tool_authorizer,
-            #[cfg(not(target_arch = "wasm32"))]
-            tool_middleware_chain,
-            #[cfg(feature = "streamable-http")]
-            http_middleware: self.http_middleware,
-        })
+    /// Evaluate feature flags for the caller before each tool call.
+    ///
+    /// Handlers read the result with [`RequestHandlerExtra::flags`], and
+    /// widget tools return it in the result `_meta` for the widget bridge.
+    /// See [`flags`](crate::server::flags) for the built-in providers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::flags::EnvFlags;
+    /// use pmcp::Server;
+    /// use std::sync::Arc;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("search")
+    ///     .version("1.0.0")
+    ///     .feature_flags(Arc::new(EnvFlags::new("PMCP_FLAG_")))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::flags`]: crate::server::cancellation::RequestHandlerExtra::flags
+    pub fn feature_flags(mut self, provider: Arc<dyn flags::FlagProvider>) -> Self {
+        self.flag_provider = Some(provider);
+        self
     }
-}
 
-#[cfg(not(target_arch = "wasm32"))]
-impl Default for ServerBuilder {
-    fn default() -> Self {
-        Self::new()
+    /// Set how long [`RequestHandlerExtra::elicit`] waits for the user's answer.
+    ///
+    /// Defaults to five minutes. When it expires the tool gets a
+    /// `REQUEST_TIMEOUT` error and can fall back or give up.
+    ///
+    /// [`RequestHandlerExtra::elicit`]: crate::server::cancellation::RequestHandlerExtra::elicit
+    pub fn elicitation_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.elicitation_timeout = Some(timeout);
+        self
     }
-}
+
+    /// Set how long [`RequestHandlerExtra::sample`] waits for the client's model.
+    ///
+    /// Defaults to two minutes. When it expires the tool gets a
+    /// `REQUEST_TIMEOUT` error.
+    ///
+    /// [`RequestHandlerExtra::sample`]: crate::server::cancellation::RequestHandlerExtra::sample
+    #[cfg(feature = "sampling")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+    pub fn sampling_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.sampling_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for tool handlers without a per-tool timeout.
+    ///
+    /// A call that runs longer is cancelled and the client gets a
+    /// `REQUEST_TIMEOUT` error. Without a default, tools run without a
+    /// deadline. See [`tool_timeout`] for details.
+    pub fn default_tool_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.tool_timeouts = self.tool_timeouts.with_default(timeout);
+        self
+    }
+
+    /// Set the deadline for one tool's handler, overriding the default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pmcp::Server;
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("warehouse")
+    ///     .version("1.0.0")
+    ///     .default_tool_timeout(Duration::from_secs(30))
+    ///     .tool_timeout("execute_query", Duration::from_secs(120))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tool_timeout(mut self, name: impl Into<String>, timeout: std::time::Duration) -> Self {
+        self.tool_timeouts = self.tool_timeouts.with_tool(name, timeout);
+        self
+    }
+
+    /// Split `tools/list` results into pages of `page_size` tools.
+    ///
+    /// Tools are listed by name, and each page's `nextCursor` is a signed
+    /// [`CursorCodec`](pagination::CursorCodec) cursor. By default all tools
+    /// are returned at once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pmcp::server::pagination::CursorCodec;
+    /// use pmcp::Server;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("catalog")
+    ///     .version("1.0.0")
+    ///     .tools_page_size(50)
+    ///     .cursor_codec(CursorCodec::new(b"a secret shared by all server instances"))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tools_page_size(mut self, page_size: usize) -> Self {
+        self.tools_page_size = Some(page_size);
+        self
+    }
+
+    /// Sign `tools/list` cursors with `codec` instead of a random per-process key.
+    ///
+    /// Needed when several server instances serve the same clients, so a
+    /// cursor issued by one instance is accepted by the others.
+    pub fn cursor_codec(mut self, codec: pagination::CursorCodec) -> Self {
+        self.cursor_codec = Some(codec);
+        self
+    }
+
+    /// Switch off a subsystem for this server.
+    ///
+    /// The subsystem's capability is not advertised and its requests are
+    /// answered with `METHOD_NOT_FOUND`. When sampling is disabled, handlers
+    /// also get no sampling channel to the client. `build()` fails if
+    /// handlers for a disabled subsystem are registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pmcp::server::subsystem::Subsystem;
+    /// use pmcp::Server;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("calculator")
+    ///     .version("1.0.0")
+    ///     .disable(Subsystem::Prompts)
+    ///     .disable(Subsystem::Sampling)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn disable(mut self, subsystem: subsystem::Subsystem) -> Self {
+        self.disabled.insert(subsystem);
+        self
+    }
+
+    /// Register shared state for handlers, one value per type.
+    ///
+    /// Handlers read it with [`RequestHandlerExtra::state`], so database
+    /// pools, configuration and caches do not have to be captured in every
+    /// handler closure. Registering a second value of the same type replaces
+    /// the first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::{RequestHandlerExtra, Server, SimpleTool};
+    /// use serde_json::json;
+    ///
+    /// struct AppConfig {
+    ///     greeting: String,
+    /// }
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("greeter")
+    ///     .version("1.0.0")
+    ///     .with_state(AppConfig {
+    ///         greeting: "Hello".to_string(),
+    ///     })
+    ///     .tool(
+    ///         "greet",
+    ///         SimpleTool::new("greet", |_args, extra: RequestHandlerExtra| {
+    ///             Box::pin(async move {
+    ///                 let config = extra.state::<AppConfig>()?;
+    ///                 Ok(json!({ "message": config.greeting }))
+    ///             })
+    ///         }),
+    ///     )
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RequestHandlerExtra::state`]: crate::server::cancellation::RequestHandlerExtra::state
+    pub fn with_state<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+        self.state.insert(state);
+        self
+    }
+
+    /// Register a source of resource changes for `resources/subscribe`.
+    ///
+    /// Enables the `resources.subscribe` capability. Watchers are started
+    /// when the server runs on a transport or is served over streamable
+    /// HTTP, and every change they report for a subscribed URI is sent as
+    /// `notifications/resources/updated`. Over HTTP, subscriptions belong to
+    /// the session and changes arrive on its standalone SSE stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pmcp::server::mcp_apps::WidgetDir;
+    /// use pmcp::server::resource_watcher::FileResourceWatcher;
+    ///
+    /// let server = Server::builder()
+    ///     .name("chess")
+    ///     .version("1.0.0")
+    ///     .resource_watcher(FileResourceWatcher::for_widget_dir(&WidgetDir::new("widgets")))
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "resources")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resources")))]
+    pub fn resource_watcher(
+        mut self,
+        watcher: impl subscriptions::ResourceWatcher + 'static,
+    ) -> Self {
+        self.resource_watchers.push(Arc::new(watcher));
+        self.capabilities
+            .resources
+            .get_or_insert(crate::types::ResourceCapabilities {
+                subscribe: Some(false),
+                list_changed: Some(false),
+            })
+            .subscribe = Some(true);
+        self
+    }
+
+    /// Register autocompletion for an argument of a prompt.
+    ///
+    /// Advertises the `completions` capability. Clients call
+    /// `completion/complete` with a `ref/prompt` reference as the user types
+    /// the argument, and `provider` supplies the suggestions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::completion::StaticCompletions;
+    /// use pmcp::Server;
+    ///
+    /// # fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("deploy")
+    ///     .version("1.0.0")
+    ///     .prompt_completion(
+    ///         "deploy",
+    ///         "environment",
+    ///         StaticCompletions::new(["development", "staging", "production"]),
+    ///     )
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prompt_completion(
+        mut self,
+        prompt: impl Into<String>,
+        argument: impl Into<String>,
+        provider: impl completion::CompletionProvider + 'static,
+    ) -> Self {
+        self.completions
+            .add_prompt(prompt, argument, Arc::new(provider));
+        self.capabilities.completions = Some(crate::types::CompletionCapabilities::default());
+        self
+    }
+
+    /// Register autocompletion for a variable of a resource template.
+    ///
+    /// `uri_template` must match the template's `uriTemplate` exactly, since
+    /// clients send it back as the `ref/resource` reference.
+    pub fn resource_completion(
+        mut self,
+        uri_template: impl Into<String>,
+        variable: impl Into<String>,
+        provider: impl completion::CompletionProvider + 'static,
+    ) -> Self {
+        self.completions
+            .add_resource_template(uri_template, variable, Arc::new(provider));
+        self.capabilities.completions = Some(crate::types::CompletionCapabilities::default());
+        self
+    }
+
+    /// Whether `tool_with_form` registered widgets that need the resources subsystem.
+    fn has_form_widgets(&self) -> bool {
+        #[cfg(feature = "mcp-apps")]
+        return !self.form_tools.is_empty();
+        #[cfg(not(feature = "mcp-apps"))]
+        false
+    }
+
+    /// Build the server.
+    ///
+    /// Constructs the final Server instance from the configured builder.
+    /// This validates that required fields (name and version) are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The server name is not set
+    /// - The server version is not set
+    #[allow(unused_mut)]
+    pub fn build(mut self) -> Result<Server> {
+        #[cfg(feature = "prompt-dir")]
+        for dir in std::mem::take(&mut self.prompt_dirs) {
+            for (prompt_name, prompt) in dir.prompts()? {
+                if self.prompts.contains_key(&prompt_name) {
+                    return Err(crate::Error::validation(format!(
+                        "Prompt '{}' from {} is already registered",
+                        prompt_name,
+                        dir.path().display()
+                    )));
+                }
+                self = self.prompt(prompt_name, prompt);
+            }
+        }
+
+        let has_form_widgets = self.has_form_widgets();
+        let name = self
+            .name
+            .ok_or_else(|| crate::Error::validation("Server name is required"))?;
+        let version = self
+            .version
+            .ok_or_else(|| crate::Error::validation("Server version is required"))?;
+
+        {
+            use subsystem::Subsystem;
+            let disabled = &self.disabled;
+            disabled.check_unused(Subsystem::Prompts, !self.prompts.is_empty())?;
+            #[cfg(feature = "resources")]
+            let watched = !self.resource_watchers.is_empty();
+            #[cfg(not(feature = "resources"))]
+            let watched = false;
+            disabled.check_unused(
+                Subsystem::Resources,
+                self.resources.is_some() || watched || has_form_widgets,
+            )?;
+            disabled.check_unused(Subsystem::Sampling, self.sampling.is_some())?;
+            disabled.apply(&mut self.capabilities);
+        }
+
+        // Apply tool protections
+        let tool_authorizer = if !self.tool_protections.is_empty() {
+            if self.tool_authorizer.is_some() {
+                // If there's an existing authorizer and tool protections are specified,
+                // this is a configuration error
+                return Err(crate::Error::validation(
+                    "Cannot use protect_tool() with a custom tool_authorizer. \
+                     Either use protect_tool() to configure scope-based authorization, \
+                     or provide a custom ToolAuthorizer implementation, but not both.",
+                ));
+            }
+            // Create a ScopeBasedAuthorizer with all the tool protections
+            let mut authorizer = auth::ScopeBasedAuthorizer::new();
+            for (tool_name, scopes) in self.tool_protections {
+                authorizer = authorizer.require_scopes(tool_name, scopes);
+            }
+            Some(Arc::new(authorizer) as Arc<dyn auth::ToolAuthorizer>)
+        } else {
+            self.tool_authorizer
+        };
+
+        // Initialize tool middleware chain
+        #[cfg(not(target_arch = "wasm32"))]
+        let tool_middleware_chain = {
+            let mut chain = tool_middleware::ToolMiddlewareChain::new();
+            for middleware in self.tool_middlewares {
+                chain.add(middleware);
+            }
+            Arc::new(RwLock::new(chain))
+        };
+
+        // Build tool_infos cache at construction time (mirrors ServerCore pattern)
+        let mut tool_infos: HashMap<String, ToolInfo> = self
+            .tools
+            .iter()
+            .map(|(name, handler)| {
+                let info = handler.metadata().unwrap_or_else(|| {
+                    ToolInfo::new(
+                        name.clone(),
+                        None,
+                        serde_json::json!({"type": "object", "properties": {}}),
+                    )
+                });
+                (name.clone(), info)
+            })
+            .collect();
+
+        #[cfg(feature = "mcp-apps")]
+        if !self.form_tools.is_empty() {
+            let mut forms = mcp_apps::FormWidgets::new(self.resources.take());
+            for tool in &self.form_tools {
+                forms.add(tool, &tool_infos)?;
+            }
+            self.resources = Some(Arc::new(forms));
+            self.capabilities
+                .resources
+                .get_or_insert(crate::types::ResourceCapabilities {
+                    subscribe: Some(false),
+                    list_changed: Some(false),
+                });
+        }
+
+        core::link_tool_widgets(
+            &mut tool_infos,
+            &self.tool_widgets,
+            self.resources.is_some(),
+        )?;
+
+        #[cfg(feature = "workflow")]
+        for workflow in self.self_test.workflows() {
+            workflow
+                .validate_tools(&tool_infos)
+                .map_err(|e| Error::Validation(format!("Workflow validation failed: {}", e)))?;
+        }
+
+        if let Some((baseline, mode)) = self.schema_baseline.take() {
+            let guard = schema_compat::SchemaGuard::new(baseline, mode, &version);
+            let prompt_infos: Vec<crate::types::PromptInfo> = self
+                .prompts
+                .iter()
+                .map(|(name, handler)| {
+                    let mut info = handler
+                        .metadata()
+                        .unwrap_or_else(|| crate::types::PromptInfo::new(name));
+                    info.name.clone_from(name);
+                    info
+                })
+                .collect();
+            guard.check_build(&tool_infos, &prompt_infos)?;
+            self.self_test.set_schema_guard(guard);
+        }
+
+        // Apply host layer enrichment to tool _meta (e.g., ChatGPT openai/* keys)
+        #[cfg(feature = "mcp-apps")]
+        let tool_infos = {
+            let mut infos = tool_infos;
+            for host in &self.host_layers {
+                for info in infos.values_mut() {
+                    if let Some(meta) = info._meta.as_mut() {
+                        core::enrich_meta_for_host(meta, *host);
+                    }
+                }
+            }
+            if let Some(manifest) = &self.widget_manifest {
+                manifest.validate(&infos)?;
+            }
+            infos
+        };
+
+        // Replace subschemas shared between tools with $refs to definitions
+        let mut tool_infos = tool_infos;
+        let schema_definitions = if self.schema_registry.is_empty() {
+            None
+        } else {
+            for info in tool_infos.values_mut() {
+                info.input_schema = self.schema_registry.compact(&info.input_schema);
+                if let Some(output_schema) = info.output_schema.as_mut() {
+                    *output_schema = self.schema_registry.compact(output_schema);
+                }
+            }
+            Some(self.schema_registry.definitions())
+        };
+
+        // Build URI-to-tool-meta index for widget resource _meta propagation
+        let uri_to_tool_meta = core::build_uri_to_tool_meta(&tool_infos);
+
+        #[cfg(feature = "workflow")]
+        let _ = self.workflow_tool_timeouts.set(self.tool_timeouts.clone());
+
+        Ok(Server {
+            info: {
+                let mut info = Implementation::new(&name, &version);
+                if let Some(url) = self.website_url {
+                    info = info.with_website_url(url);
+                }
+                if let Some(icons) = self.icons {
+                    info = info.with_icons(icons);
+                }
+                info
+            },
+            capabilities: self.capabilities,
+            tools: self.tools,
+            tool_infos,
+            uri_to_tool_meta,
+            prompts: self.prompts,
+            resources: self.resources,
+            sampling: self.sampling,
+            client_capabilities: Arc::new(RwLock::new(None)),
+            initialized: Arc::new(RwLock::new(false)),
+            notification_tx: None,
+            cancellation_manager: self.cancellation_manager,
+            roots_manager: Arc::new(RwLock::new(self.roots_manager)),
+            #[cfg(feature = "resources")]
+            subscription_manager: Arc::new(RwLock::new(subscriptions::SubscriptionManager::new())),
+            elicitation_manager: None,
+            elicitation_timeout: self.elicitation_timeout,
+            client_roots: None,
+            #[cfg(feature = "sampling")]
+            client_sampling: None,
+            #[cfg(feature = "sampling")]
+            sampling_timeout: self.sampling_timeout,
+            tool_timeouts: self.tool_timeouts,
+            tool_pages: self.tools_page_size.map(|page_size| {
+                pagination::ListPages::new("tools/list", page_size, self.cursor_codec)
+            }),
+            auth_provider: self.auth_provider,
+            tool_authorizer,
+            prompt_authorizer: self.prompt_authorizer,
+            #[cfg(not(target_arch = "wasm32"))]
+            tool_middleware_chain,
+            #[cfg(feature = "streamable-http")]
+            http_middleware: self.http_middleware,
+            events: self.events,
+            schema_definitions,
+            self_test: self.self_test,
+            widget_state: self.widget_state,
+            kv_store: self.kv_store,
+            flag_provider: self.flag_provider,
+            state: self.state,
+            #[cfg(feature = "resources")]
+            resource_watchers: self.resource_watchers,
+            completions: self.completions,
+            disabled: self.disabled,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for ServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -3411,6 +4657,387 @@ mod tests {
         let _ = timeout(std::time::Duration::from_millis(200), server_handle).await;
     }
 
+    /// Transport backed by channels, standing in for a connected client
+    #[derive(Debug)]
+    struct ChannelTransport {
+        incoming: mpsc::Receiver<TransportMessage>,
+        outgoing: mpsc::Sender<TransportMessage>,
+    }
+
+    #[async_trait]
+    impl Transport for ChannelTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            self.outgoing
+                .send(message)
+                .await
+                .map_err(|_| Error::internal("client gone"))
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            self.incoming
+                .recv()
+                .await
+                .ok_or_else(|| Error::internal("client gone"))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Tool that asks the user to confirm and echoes the answer
+    struct ConfirmTool;
+
+    #[async_trait]
+    impl ToolHandler for ConfirmTool {
+        async fn handle(
+            &self,
+            _args: Value,
+            extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<Value> {
+            let schema = json!({
+                "type": "object",
+                "properties": { "confirm": { "type": "boolean" } }
+            });
+            let answer = extra.elicit(schema, "Proceed?").await?;
+            Ok(json!({ "action": answer.action, "content": answer.content }))
+        }
+    }
+
+    /// Start a server with `ConfirmTool` and initialize it as a client with
+    /// the given capabilities.
+    async fn connect_confirm_server(
+        capabilities: ClientCapabilities,
+        elicitation_timeout: std::time::Duration,
+    ) -> (
+        mpsc::Sender<TransportMessage>,
+        mpsc::Receiver<TransportMessage>,
+    ) {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("confirm", ConfirmTool)
+            .elicitation_timeout(elicitation_timeout)
+            .build()
+            .unwrap();
+        let (client_tx, client_rx) = connect_client(server, capabilities).await;
+        call_tool(&client_tx, 2, "confirm", json!({})).await;
+
+        (client_tx, client_rx)
+    }
+
+    /// Run `server` on a channel transport and initialize it as a client
+    /// with the given capabilities.
+    async fn connect_client(
+        server: Server,
+        capabilities: ClientCapabilities,
+    ) -> (
+        mpsc::Sender<TransportMessage>,
+        mpsc::Receiver<TransportMessage>,
+    ) {
+        let (client_tx, incoming) = mpsc::channel(8);
+        let (outgoing, mut client_rx) = mpsc::channel(8);
+        tokio::spawn(server.run(ChannelTransport { incoming, outgoing }));
+
+        client_tx
+            .send(TransportMessage::Request {
+                id: RequestId::from(1i64),
+                request: Request::Client(Box::new(ClientRequest::Initialize(
+                    InitializeRequest::new(
+                        Implementation::new("test-client", "1.0.0"),
+                        capabilities,
+                    ),
+                ))),
+            })
+            .await
+            .unwrap();
+        let initialized = timeout(std::time::Duration::from_secs(1), client_rx.recv())
+            .await
+            .unwrap();
+        assert!(matches!(initialized, Some(TransportMessage::Response(_))));
+
+        (client_tx, client_rx)
+    }
+
+    async fn call_tool(
+        client_tx: &mpsc::Sender<TransportMessage>,
+        id: i64,
+        name: &str,
+        args: Value,
+    ) {
+        client_tx
+            .send(TransportMessage::Request {
+                id: RequestId::from(id),
+                request: Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                    name, args,
+                )))),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn next_message(rx: &mut mpsc::Receiver<TransportMessage>) -> TransportMessage {
+        timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .expect("server did not send a message")
+            .expect("server closed the connection")
+    }
+
+    #[tokio::test]
+    async fn test_tool_elicits_input_from_client() {
+        let mut capabilities = ClientCapabilities::minimal();
+        capabilities.elicitation = Some(Default::default());
+        let (client_tx, mut client_rx) =
+            connect_confirm_server(capabilities, std::time::Duration::from_secs(5)).await;
+
+        // The tool suspends and the server asks the client
+        let TransportMessage::Request {
+            id,
+            request: Request::Server(server_request),
+        } = next_message(&mut client_rx).await
+        else {
+            panic!("expected an elicitation request");
+        };
+        let crate::types::ServerRequest::ElicitationCreate(params) = *server_request else {
+            panic!("expected elicitation/create");
+        };
+        assert!(matches!(
+            *params,
+            crate::types::elicitation::ElicitRequestParams::Form { ref message, .. } if message == "Proceed?"
+        ));
+
+        client_tx
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                id,
+                json!({ "action": "accept", "content": { "confirm": true } }),
+            )))
+            .await
+            .unwrap();
+
+        // The tool resumes with the user's answer
+        let TransportMessage::Response(response) = next_message(&mut client_rx).await else {
+            panic!("expected the tool response");
+        };
+        assert_eq!(response.id, RequestId::from(2i64));
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("tool call failed: {:?}", response.payload);
+        };
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("accept"), "{text}");
+        assert!(text.contains("confirm"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_elicitation_requires_client_capability() {
+        let (_client_tx, mut client_rx) = connect_confirm_server(
+            ClientCapabilities::minimal(),
+            std::time::Duration::from_secs(5),
+        )
+        .await;
+
+        let TransportMessage::Response(response) = next_message(&mut client_rx).await else {
+            panic!("expected the tool response without an elicitation request");
+        };
+        assert_eq!(response.id, RequestId::from(2i64));
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_elicitation_times_out() {
+        let mut capabilities = ClientCapabilities::minimal();
+        capabilities.elicitation = Some(Default::default());
+        let (_client_tx, mut client_rx) =
+            connect_confirm_server(capabilities, std::time::Duration::from_millis(50)).await;
+
+        assert!(matches!(
+            next_message(&mut client_rx).await,
+            TransportMessage::Request { .. }
+        ));
+        // No answer: the tool call completes once the elicitation times out
+        let TransportMessage::Response(response) = next_message(&mut client_rx).await else {
+            panic!("expected the tool response");
+        };
+        assert_eq!(response.id, RequestId::from(2i64));
+    }
+
+    /// Tool that resolves a path against the client's roots
+    struct ResolvePathTool;
+
+    #[async_trait]
+    impl ToolHandler for ResolvePathTool {
+        async fn handle(
+            &self,
+            args: Value,
+            extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<Value> {
+            let path = extra
+                .resolve_path(args["path"].as_str().unwrap_or_default())
+                .await?;
+            Ok(json!({ "path": path }))
+        }
+    }
+
+    /// Expect a `roots/list` request and answer it with `uri` as the only root.
+    async fn answer_roots_list(
+        client_tx: &mpsc::Sender<TransportMessage>,
+        client_rx: &mut mpsc::Receiver<TransportMessage>,
+        uri: &str,
+    ) {
+        let TransportMessage::Request {
+            id,
+            request: Request::Server(server_request),
+        } = next_message(client_rx).await
+        else {
+            panic!("expected a roots/list request");
+        };
+        assert!(matches!(
+            *server_request,
+            crate::types::ServerRequest::ListRoots
+        ));
+        client_tx
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                id,
+                json!({ "roots": [{ "uri": uri }] }),
+            )))
+            .await
+            .unwrap();
+    }
+
+    async fn tool_response(client_rx: &mut mpsc::Receiver<TransportMessage>, id: i64) -> Value {
+        let TransportMessage::Response(response) = next_message(client_rx).await else {
+            panic!("expected the tool response");
+        };
+        assert_eq!(response.id, RequestId::from(id));
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("tool call failed: {:?}", response.payload);
+        };
+        result
+    }
+
+    #[tokio::test]
+    async fn test_tool_resolves_paths_against_client_roots() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("resolve", ResolvePathTool)
+            .build()
+            .unwrap();
+        let (client_tx, mut client_rx) = connect_client(server, ClientCapabilities::full()).await;
+
+        // The first call fetches the roots from the client
+        call_tool(&client_tx, 2, "resolve", json!({ "path": "notes.md" })).await;
+        answer_roots_list(&client_tx, &mut client_rx, "file:///srv/data").await;
+        let result = tool_response(&mut client_rx, 2).await;
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("/srv/data/notes.md"), "{text}");
+
+        // The second is served from the cache and rejects an escaping path
+        call_tool(&client_tx, 3, "resolve", json!({ "path": "../etc/passwd" })).await;
+        let TransportMessage::Response(response) = next_message(&mut client_rx).await else {
+            panic!("expected the tool response");
+        };
+        assert_eq!(response.id, RequestId::from(3i64));
+        assert!(matches!(response.payload, ResponsePayload::Error(_)));
+
+        // After list_changed the roots are fetched again
+        client_tx
+            .send(TransportMessage::Notification(Notification::Client(
+                crate::types::ClientNotification::RootsListChanged,
+            )))
+            .await
+            .unwrap();
+        call_tool(&client_tx, 4, "resolve", json!({ "path": "notes.md" })).await;
+        answer_roots_list(&client_tx, &mut client_rx, "file:///srv/other").await;
+        let result = tool_response(&mut client_rx, 4).await;
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("/srv/other/notes.md"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_roots_require_client_capability() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("resolve", ResolvePathTool)
+            .build()
+            .unwrap();
+        let (client_tx, mut client_rx) =
+            connect_client(server, ClientCapabilities::minimal()).await;
+
+        call_tool(&client_tx, 2, "resolve", json!({ "path": "notes.md" })).await;
+        let TransportMessage::Response(response) = next_message(&mut client_rx).await else {
+            panic!("expected the tool response without a roots/list request");
+        };
+        assert_eq!(response.id, RequestId::from(2i64));
+    }
+
+    /// Tool that asks the client's model to summarize its input
+    struct SummarizeTool;
+
+    #[async_trait]
+    impl ToolHandler for SummarizeTool {
+        async fn handle(
+            &self,
+            args: Value,
+            extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<Value> {
+            let reply = extra
+                .sample(
+                    crate::server::sampling::SamplingRequestBuilder::new()
+                        .user(format!("Summarize: {}", args["text"]))
+                        .hint("haiku")
+                        .max_tokens(20),
+                )
+                .await?;
+            Ok(json!({ "summary": reply.text(), "model": reply.model }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_samples_client_model() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("summarize", SummarizeTool)
+            .build()
+            .unwrap();
+        let (client_tx, mut client_rx) = connect_client(server, ClientCapabilities::full()).await;
+
+        call_tool(
+            &client_tx,
+            2,
+            "summarize",
+            json!({ "text": "a long story" }),
+        )
+        .await;
+        let TransportMessage::Request {
+            id,
+            request: Request::Server(server_request),
+        } = next_message(&mut client_rx).await
+        else {
+            panic!("expected a sampling request");
+        };
+        let crate::types::ServerRequest::CreateMessage(params) = *server_request else {
+            panic!("expected sampling/createMessage");
+        };
+        assert_eq!(params.max_tokens, Some(20));
+        client_tx
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                id,
+                json!({
+                    "role": "assistant",
+                    "content": { "type": "text", "text": "A story." },
+                    "model": "claude-3-5-haiku"
+                }),
+            )))
+            .await
+            .unwrap();
+
+        let result = tool_response(&mut client_rx, 2).await;
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("A story."), "{text}");
+        assert!(text.contains("claude-3-5-haiku"), "{text}");
+    }
+
     #[tokio::test]
     async fn test_server_capabilities() {
         let server = Server::builder()
@@ -3521,6 +5148,44 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_list_tools_pages() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("b-tool", MockTool::new(json!({})))
+            .tool("c-tool", MockTool::new(json!({})))
+            .tool("a-tool", MockTool::new(json!({})))
+            .tools_page_size(2)
+            .build()
+            .unwrap();
+
+        let list = |cursor: Option<String>| {
+            Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                cursor,
+            })))
+        };
+        let response = server
+            .handle_request(RequestId::from(1i64), list(None), None)
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let first: ListToolsResult = serde_json::from_value(result).unwrap();
+        let names: Vec<&str> = first.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["a-tool", "b-tool"]);
+
+        let response = server
+            .handle_request(RequestId::from(2i64), list(first.next_cursor), None)
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let second: ListToolsResult = serde_json::from_value(result).unwrap();
+        assert_eq!(second.tools[0].name, "c-tool");
+        assert!(second.next_cursor.is_none());
+    }
+
     #[tokio::test]
     async fn test_handle_call_tool() {
         let server = Server::builder()
@@ -3551,6 +5216,71 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "schema-generation")]
+    #[tokio::test]
+    async fn test_typed_sync_tool_output_is_structured() {
+        #[derive(serde::Deserialize, schemars::JsonSchema)]
+        struct AddArgs {
+            a: i64,
+            b: i64,
+        }
+
+        #[derive(serde::Serialize, schemars::JsonSchema)]
+        struct AddResult {
+            sum: i64,
+        }
+
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool_typed_sync_with_description("add", "Adds two numbers", |args: AddArgs, _| {
+                Ok(AddResult {
+                    sum: args.a + args.b,
+                })
+            })
+            .tool_typed_sync_with_description("echo", "Echoes input", |args: AddArgs, _| {
+                Ok(json!({"a": args.a}))
+            })
+            .build()
+            .unwrap();
+
+        let add = server.tool_infos.get("add").unwrap();
+        assert_eq!(add.output_schema.as_ref().unwrap()["type"], "object");
+        assert!(server
+            .tool_infos
+            .get("echo")
+            .unwrap()
+            .output_schema
+            .is_none());
+
+        let call = |name: &str| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
+                name: name.to_string(),
+                arguments: json!({"a": 2, "b": 3}),
+                _meta: None,
+                task: None,
+            })))
+        };
+
+        let response = server
+            .handle_request(RequestId::from(1i64), call("add"), None)
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let call_result: CallToolResult = serde_json::from_value(result).unwrap();
+        assert_eq!(call_result.structured_content, Some(json!({"sum": 5})));
+
+        let response = server
+            .handle_request(RequestId::from(2i64), call("echo"), None)
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let call_result: CallToolResult = serde_json::from_value(result).unwrap();
+        assert!(call_result.structured_content.is_none());
+    }
+
     #[tokio::test]
     async fn test_handle_call_tool_not_found() {
         let server = Server::builder()
@@ -3595,6 +5325,7 @@ mod tests {
 
         let request = Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
             cursor: None,
+            prefix: None,
         })));
         let response = server
             .handle_request(RequestId::from(1i64), request, None)
@@ -3610,6 +5341,53 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_disabled_subsystem_is_hidden_and_rejected() {
+        use crate::server::subsystem::Subsystem;
+
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::prompts_only())
+            .disable(Subsystem::Prompts)
+            .build()
+            .unwrap();
+        assert!(server.capabilities.prompts.is_none());
+
+        let request = Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
+            cursor: None,
+            prefix: None,
+        })));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+
+        match response.payload {
+            ResponsePayload::Error(error) => {
+                assert_eq!(error.code, -32601);
+                assert!(error.message.contains("prompts subsystem is disabled"));
+            },
+            ResponsePayload::Result(_) => panic!("Expected method not found"),
+        }
+    }
+
+    #[test]
+    fn test_disabled_subsystem_rejects_registered_handlers() {
+        use crate::server::subsystem::Subsystem;
+
+        let err = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .prompt(
+                "test-prompt",
+                MockPrompt::new(crate::types::GetPromptResult::new(vec![], None)),
+            )
+            .disable(Subsystem::Prompts)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("prompts subsystem is disabled"));
+    }
+
     #[tokio::test]
     async fn test_handle_get_prompt() {
         let prompt_result = crate::types::GetPromptResult {
@@ -3694,10 +5472,9 @@ mod tests {
             .build()
             .unwrap();
 
-        let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-            uri: "test://uri".to_string(),
-            _meta: None,
-        })));
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("test://uri"),
+        )));
 
         let response = server
             .handle_request(RequestId::from(1i64), request, None)
@@ -3713,6 +5490,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_read_resource_range() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .resources(MockResource::new().with_resource(
+                "test://log".to_string(),
+                crate::types::ReadResourceResult::new(vec![crate::types::Content::text(
+                    "0123456789",
+                )]),
+            ))
+            .build()
+            .unwrap();
+
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("test://log").with_range(4, Some(3)),
+        )));
+        let response = server
+            .handle_request(RequestId::from(1i64), request, None)
+            .await;
+
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let read_result: crate::types::ReadResourceResult =
+                    serde_json::from_value(result).unwrap();
+                assert_eq!(read_result.text(), Some("456"));
+                let info = read_result.range_info().unwrap();
+                assert_eq!(info.total_length, 10);
+                assert_eq!(info.next_offset, Some(7));
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
+        }
+
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("test://log").with_range(11, None),
+        )));
+        let response = server
+            .handle_request(RequestId::from(2i64), request, None)
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Error(_)));
+    }
+
     #[tokio::test]
     async fn test_handle_read_resource_not_found() {
         let server = Server::builder()
@@ -3722,10 +5541,9 @@ mod tests {
             .build()
             .unwrap();
 
-        let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-            uri: "nonexistent://uri".to_string(),
-            _meta: None,
-        })));
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(
+            ReadResourceRequest::new("nonexistent://uri"),
+        )));
 
         let response = server
             .handle_request(RequestId::from(1i64), request, None)
@@ -5,6 +5,9 @@
 //!
 //! - `ConsoleBackend` - Development/debugging output
 //! - `CloudWatchBackend` - AWS `CloudWatch` with EMF support
+//! - `DatadogBackend` - `DogStatsD` metrics to the Datadog Agent
+//! - `HttpJsonBackend` - Batched JSON events over HTTP, e.g. Honeycomb
+//!   (`http-client` feature)
 //! - `CompositeBackend` - Fan-out to multiple backends
 //!
 //! # Implementing Custom Backends
@@ -41,7 +44,11 @@
 //! }
 //! ```
 
-use super::events::{McpMetric, McpRequestEvent, McpResponseEvent, MetricUnit, StandardMetrics};
+use super::config::ObservabilityConfig;
+use super::datadog::DatadogBackend;
+use super::events::{
+    McpLogEvent, McpMetric, McpRequestEvent, McpResponseEvent, MetricUnit, StandardMetrics,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -69,6 +76,12 @@ pub trait ObservabilityBackend: Send + Sync + 'static {
     /// Emit a metric data point.
     async fn emit_metric(&self, metric: &McpMetric);
 
+    /// Record a `tracing` event or span forwarded by
+    /// [`ObservabilityLayer`](super::ObservabilityLayer).
+    ///
+    /// The default implementation discards it.
+    async fn record_log(&self, _event: &McpLogEvent) {}
+
     /// Flush pending data (called on shutdown or periodically).
     async fn flush(&self);
 
@@ -165,6 +178,16 @@ impl ObservabilityBackend for CompositeBackend {
         futures::future::join_all(futures).await;
     }
 
+    async fn record_log(&self, event: &McpLogEvent) {
+        let futures: Vec<_> = self
+            .backends
+            .iter()
+            .filter(|b| b.is_enabled())
+            .map(|b| b.record_log(event))
+            .collect();
+        futures::future::join_all(futures).await;
+    }
+
     async fn flush(&self) {
         let futures: Vec<_> = self.backends.iter().map(|b| b.flush()).collect();
         futures::future::join_all(futures).await;
@@ -292,6 +315,25 @@ impl ObservabilityBackend for ConsoleBackend {
         }
     }
 
+    async fn record_log(&self, event: &McpLogEvent) {
+        if self.pretty {
+            let duration = event
+                .duration_ms
+                .map(|ms| format!(" ({ms}ms)"))
+                .unwrap_or_default();
+            println!(
+                "[{}] {} {} {:>5} {}{duration}",
+                event.trace.short_trace_id(),
+                event.server_name,
+                event.tool_name.as_deref().unwrap_or("-"),
+                event.level,
+                event.message,
+            );
+        } else if let Ok(json) = serde_json::to_string(&event) {
+            println!("{json}");
+        }
+    }
+
     async fn flush(&self) {
         // Console output is immediate, no buffering
     }
@@ -477,6 +519,27 @@ impl ObservabilityBackend for CloudWatchBackend {
         }
     }
 
+    async fn record_log(&self, event: &McpLogEvent) {
+        // A standalone JSON line, so Logs Insights can filter on TraceId
+        let line = json!({
+            "Timestamp": event.timestamp.timestamp_millis(),
+            "Level": event.level,
+            "Target": event.target,
+            "Message": event.message,
+            "ServerName": event.server_name,
+            "Operation": event.tool_name,
+            "TraceId": event.trace.trace_id,
+            "SpanId": event.trace.span_id,
+            "ParentSpanId": event.trace.parent_span_id,
+            "Depth": event.trace.depth,
+            "Duration": event.duration_ms,
+            "Fields": event.fields,
+        });
+        if let Ok(line) = serde_json::to_string(&line) {
+            println!("{line}");
+        }
+    }
+
     async fn flush(&self) {
         // CloudWatch logs are flushed automatically by the Lambda runtime
         // or the tracing subscriber
@@ -518,6 +581,31 @@ impl ObservabilityBackend for NullBackend {
     }
 }
 
+/// Build the backend selected by `config.backend`.
+///
+/// Unknown names fall back to the console backend. `"http"` and
+/// `"honeycomb"` also fall back to it, with a warning, when pmcp is built
+/// without the `http-client` feature.
+pub fn build_backend(config: &ObservabilityConfig) -> Arc<dyn ObservabilityBackend> {
+    match config.backend.as_str() {
+        "cloudwatch" => Arc::new(CloudWatchBackend::new(config.cloudwatch.clone())),
+        "datadog" => Arc::new(DatadogBackend::new(config.datadog.clone())),
+        #[cfg(feature = "http-client")]
+        "http" | "honeycomb" => {
+            Arc::new(super::http_json::HttpJsonBackend::new(config.http.clone()))
+        },
+        #[cfg(not(feature = "http-client"))]
+        "http" | "honeycomb" => {
+            tracing::warn!(
+                "Using console observability backend: pmcp was built without the `http-client` feature"
+            );
+            Arc::new(ConsoleBackend::new(config.console.pretty))
+        },
+        "null" => Arc::new(NullBackend),
+        _ => Arc::new(ConsoleBackend::new(config.console.pretty)),
+    }
+}
+
 /// Helper to emit standard metrics from a response event.
 ///
 /// This is used by the middleware to emit the standard set of metrics
@@ -15,6 +15,16 @@
 //! max_depth = 10
 //! sample_rate = 1.0
 //!
+//! [observability.sampling]
+//! always_sample_errors = true
+//! slow_threshold_ms = 2000
+//! tail_buffer_size = 1000
+//! tail_max_age_ms = 300000
+//!
+//! [observability.sampling.tool_rates]
+//! health_check = 0.01
+//! generate_report = 1.0
+//!
 //! [observability.fields]
 //! capture_tool_name = true
 //! capture_arguments_hash = false
@@ -23,11 +33,32 @@
 //! [observability.cloudwatch]
 //! namespace = "PMCP/Servers"
 //! emf_enabled = true
+//!
+//! [observability.datadog]
+//! agent_addr = "127.0.0.1:8125"
+//! tags = ["env:prod"]
+//!
+//! [observability.http]
+//! endpoint = "https://api.honeycomb.io/1/batch/mcp-servers"
+//!
+//! [observability.errors]
+//! enabled = true
+//!
+//! [[observability.errors.sinks]]
+//! type = "file"
+//! path = "errors.jsonl"
+//!
+//! [[observability.errors.sinks]]
+//! type = "sentry"
+//! dsn = "https://public-key@o0.ingest.sentry.io/42"
 //! ```
 
 use super::backend::CloudWatchConfig;
+use super::datadog::DatadogConfig;
+use super::http_json::HttpJsonConfig;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Main observability configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +67,8 @@ pub struct ObservabilityConfig {
     /// Master switch for observability.
     pub enabled: bool,
 
-    /// Backend selection: "console", "cloudwatch", "null".
+    /// Backend selection: "console", "cloudwatch", "datadog", "http" (or
+    /// "honeycomb"), "null".
     pub backend: String,
 
     /// Maximum composition depth (loop prevention).
@@ -45,6 +77,9 @@ pub struct ObservabilityConfig {
     /// Sampling rate (0.0 - 1.0, for high-volume servers).
     pub sample_rate: f64,
 
+    /// Rules that keep or drop calls on top of `sample_rate`.
+    pub sampling: SamplingConfig,
+
     /// Tracing configuration.
     pub tracing: TracingConfig,
 
@@ -57,8 +92,17 @@ pub struct ObservabilityConfig {
     /// CloudWatch-specific configuration.
     pub cloudwatch: CloudWatchConfig,
 
+    /// Datadog-specific configuration.
+    pub datadog: DatadogConfig,
+
+    /// JSON-over-HTTP (Honeycomb) configuration.
+    pub http: HttpJsonConfig,
+
     /// Console-specific configuration.
     pub console: ConsoleConfig,
+
+    /// Error report configuration.
+    pub errors: ErrorReportingConfig,
 }
 
 impl Default for ObservabilityConfig {
@@ -68,11 +112,15 @@ impl Default for ObservabilityConfig {
             backend: "console".to_string(),
             max_depth: 10,
             sample_rate: 1.0,
+            sampling: SamplingConfig::default(),
             tracing: TracingConfig::default(),
             fields: FieldsConfig::default(),
             metrics: MetricsConfig::default(),
             cloudwatch: CloudWatchConfig::default(),
+            datadog: DatadogConfig::default(),
+            http: HttpJsonConfig::default(),
             console: ConsoleConfig::default(),
+            errors: ErrorReportingConfig::default(),
         }
     }
 }
@@ -151,6 +199,11 @@ impl ObservabilityConfig {
                 self.sample_rate = v;
             }
         }
+        if let Ok(threshold) = std::env::var("PMCP_OBSERVABILITY_SLOW_THRESHOLD_MS") {
+            if let Ok(v) = threshold.parse() {
+                self.sampling.slow_threshold_ms = Some(v);
+            }
+        }
 
         // Field capture overrides
         if let Ok(v) = std::env::var("PMCP_OBSERVABILITY_CAPTURE_TOOL_NAME") {
@@ -184,12 +237,51 @@ impl ObservabilityConfig {
             }
         }
 
+        // Datadog overrides (the Agent's standard variables)
+        if let Ok(host) = std::env::var("DD_AGENT_HOST") {
+            let port = std::env::var("DD_DOGSTATSD_PORT").unwrap_or_else(|_| "8125".to_string());
+            self.datadog.agent_addr = format!("{host}:{port}");
+        } else if let Ok(port) = std::env::var("DD_DOGSTATSD_PORT") {
+            let host = self
+                .datadog
+                .agent_addr
+                .rsplit_once(':')
+                .map_or("127.0.0.1", |(host, _)| host);
+            self.datadog.agent_addr = format!("{host}:{port}");
+        }
+
+        // HTTP backend overrides
+        if let Ok(endpoint) = std::env::var("PMCP_OBSERVABILITY_HTTP_ENDPOINT") {
+            self.http.endpoint = endpoint;
+        }
+        if let Ok(api_key) = std::env::var("PMCP_OBSERVABILITY_HTTP_API_KEY") {
+            self.http.api_key = Some(api_key);
+        }
+
         // Console overrides
         if let Ok(pretty) = std::env::var("PMCP_CONSOLE_PRETTY") {
             if let Ok(v) = pretty.parse() {
                 self.console.pretty = v;
             }
         }
+
+        // Error report overrides (sink variables add to configured sinks)
+        if let Ok(enabled) = std::env::var("PMCP_OBSERVABILITY_ERRORS_ENABLED") {
+            if let Ok(v) = enabled.parse() {
+                self.errors.enabled = v;
+            }
+        }
+        if let Ok(path) = std::env::var("PMCP_OBSERVABILITY_ERRORS_FILE") {
+            self.errors
+                .sinks
+                .push(ErrorSinkConfig::File { path: path.into() });
+        }
+        if let Ok(url) = std::env::var("PMCP_OBSERVABILITY_ERRORS_WEBHOOK") {
+            self.errors.sinks.push(ErrorSinkConfig::Webhook { url });
+        }
+        if let Ok(dsn) = std::env::var("PMCP_OBSERVABILITY_SENTRY_DSN") {
+            self.errors.sinks.push(ErrorSinkConfig::Sentry { dsn });
+        }
     }
 
     /// Check if sampling should capture this request.
@@ -198,20 +290,16 @@ impl ObservabilityConfig {
     /// Uses a simple time-based entropy source to avoid requiring
     /// the `rand` crate.
     pub fn should_sample(&self) -> bool {
-        if self.sample_rate >= 1.0 {
-            return true;
-        }
-        if self.sample_rate <= 0.0 {
-            return false;
-        }
-        // Simple sampling using time-based entropy
-        // This is not cryptographically secure but sufficient for sampling
-        let nanos = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .subsec_nanos();
-        let random_value = (nanos as f64) / (u32::MAX as f64);
-        random_value < self.sample_rate
+        sample(self.sample_rate)
+    }
+
+    /// Check if sampling should capture a call to `tool_name`, using its
+    /// rate from `sampling.tool_rates` or else `sample_rate`.
+    ///
+    /// This is the head decision, made when the call starts. Calls it drops
+    /// can still be kept by the tail rules in [`SamplingConfig`].
+    pub fn should_sample_tool(&self, tool_name: &str) -> bool {
+        sample(self.sampling.rate_for(tool_name, self.sample_rate))
     }
 
     /// Create a disabled configuration.
@@ -246,6 +334,90 @@ impl ObservabilityConfig {
     }
 }
 
+/// Randomly decide whether to sample at `rate`.
+fn sample(rate: f64) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+    if rate <= 0.0 {
+        return false;
+    }
+    // Simple sampling using time-based entropy
+    // This is not cryptographically secure but sufficient for sampling
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    let random_value = f64::from(nanos) / f64::from(u32::MAX);
+    random_value < rate
+}
+
+/// Rule-based and tail-based sampling configuration.
+///
+/// The head decision uses per-tool rates from `tool_rates`, falling back to
+/// `sample_rate`. Calls it drops are held in a bounded tail buffer until
+/// they finish, and are still recorded if they fail (`always_sample_errors`)
+/// or run longer than `slow_threshold_ms`, so interesting traces survive
+/// low base rates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingConfig {
+    /// Record failed calls even when the head decision dropped them.
+    pub always_sample_errors: bool,
+
+    /// Record calls slower than this many milliseconds even when the head
+    /// decision dropped them.
+    pub slow_threshold_ms: Option<u64>,
+
+    /// Per-tool sample rates (0.0 - 1.0) overriding `sample_rate`.
+    pub tool_rates: HashMap<String, f64>,
+
+    /// Maximum number of unsampled in-flight calls held for the tail rules.
+    /// Calls beyond this are dropped as soon as they are not sampled; `0`
+    /// disables tail-based retention.
+    pub tail_buffer_size: usize,
+
+    /// Milliseconds a call may stay in the tail buffer. Calls that have not
+    /// finished by then (typically because they were dropped) are evicted
+    /// without being recorded.
+    pub tail_max_age_ms: u64,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            always_sample_errors: true,
+            slow_threshold_ms: None,
+            tool_rates: HashMap::new(),
+            tail_buffer_size: 1000,
+            tail_max_age_ms: 300_000,
+        }
+    }
+}
+
+impl SamplingConfig {
+    /// The head sample rate for `tool_name`.
+    pub fn rate_for(&self, tool_name: &str, default_rate: f64) -> f64 {
+        self.tool_rates
+            .get(tool_name)
+            .copied()
+            .unwrap_or(default_rate)
+    }
+
+    /// Whether a call the head decision dropped should be retained.
+    pub fn retains(&self, failed: bool, duration_ms: u64) -> bool {
+        (failed && self.always_sample_errors)
+            || self
+                .slow_threshold_ms
+                .is_some_and(|threshold| duration_ms >= threshold)
+    }
+
+    /// Whether any tail rule can retain calls the head decision dropped.
+    pub fn has_tail_rules(&self) -> bool {
+        self.tail_buffer_size > 0 && (self.always_sample_errors || self.slow_threshold_ms.is_some())
+    }
+}
+
 /// Tracing configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -392,6 +564,42 @@ impl Default for ConsoleConfig {
     }
 }
 
+/// Error report configuration.
+///
+/// When enabled, every failed tool call produces an
+/// [`ErrorReport`](super::ErrorReport) that is sent to each configured sink.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorReportingConfig {
+    /// Emit error reports for failed tool calls.
+    pub enabled: bool,
+
+    /// Destinations for error reports.
+    pub sinks: Vec<ErrorSinkConfig>,
+}
+
+/// A configured error report destination.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ErrorSinkConfig {
+    /// Append reports as JSON lines to a file.
+    File {
+        /// Path of the JSON lines file.
+        path: PathBuf,
+    },
+    /// POST each report as JSON to a URL (requires the `http-client` feature).
+    Webhook {
+        /// Webhook URL.
+        url: String,
+    },
+    /// Send reports to a Sentry-compatible store endpoint (requires the
+    /// `http-client` feature).
+    Sentry {
+        /// Project DSN, e.g. `https://key@o0.ingest.sentry.io/42`.
+        dsn: String,
+    },
+}
+
 /// Configuration errors.
 #[derive(Debug)]
 pub enum ConfigError {
@@ -511,6 +719,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sampling_rules_from_toml() {
+        let toml = r#"
+            [observability]
+            sample_rate = 0.1
+
+            [observability.sampling]
+            always_sample_errors = false
+            slow_threshold_ms = 500
+
+            [observability.sampling.tool_rates]
+            health_check = 0.0
+            generate_report = 1.0
+        "#;
+
+        let config = ObservabilityConfig::from_toml(toml).unwrap();
+        let sampling = &config.sampling;
+
+        assert!((sampling.rate_for("search", config.sample_rate) - 0.1).abs() < f64::EPSILON);
+        assert!(config.should_sample_tool("generate_report"));
+        assert!(!config.should_sample_tool("health_check"));
+        assert!(!sampling.retains(true, 10));
+        assert!(sampling.retains(false, 500));
+        assert!(!sampling.retains(false, 499));
+        assert_eq!(sampling.tail_buffer_size, 1000);
+        assert!(sampling.has_tail_rules());
+    }
+
+    #[test]
+    fn test_sampling_defaults_keep_errors_only() {
+        let sampling = SamplingConfig::default();
+
+        assert!(sampling.retains(true, 0));
+        assert!(!sampling.retains(false, u64::MAX));
+        assert!(sampling.has_tail_rules());
+        assert!(!SamplingConfig {
+            tail_buffer_size: 0,
+            ..Default::default()
+        }
+        .has_tail_rules());
+    }
+
     #[test]
     fn test_tracing_config_defaults() {
         let config = TracingConfig::default();
@@ -520,6 +770,69 @@ mod tests {
         assert_eq!(config.trace_field, "_trace");
     }
 
+    #[test]
+    fn test_error_sinks_from_toml() {
+        let toml = r#"
+            [observability.errors]
+            enabled = true
+
+            [[observability.errors.sinks]]
+            type = "file"
+            path = "errors.jsonl"
+
+            [[observability.errors.sinks]]
+            type = "webhook"
+            url = "https://hooks.example.com/mcp"
+        "#;
+
+        let config = ObservabilityConfig::from_toml(toml).unwrap();
+
+        assert!(config.errors.enabled);
+        assert_eq!(
+            config.errors.sinks,
+            vec![
+                ErrorSinkConfig::File {
+                    path: PathBuf::from("errors.jsonl")
+                },
+                ErrorSinkConfig::Webhook {
+                    url: "https://hooks.example.com/mcp".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_datadog_and_http_backends_from_toml() {
+        let toml = r#"
+            [observability]
+            backend = "datadog"
+
+            [observability.datadog]
+            tags = ["env:prod"]
+
+            [observability.http]
+            endpoint = "https://api.honeycomb.io/1/batch/mcp"
+            batch_size = 50
+        "#;
+
+        let config = ObservabilityConfig::from_toml(toml).unwrap();
+
+        assert_eq!(config.backend, "datadog");
+        assert_eq!(config.datadog.agent_addr, "127.0.0.1:8125");
+        assert_eq!(config.datadog.tags, vec!["env:prod".to_string()]);
+        assert_eq!(config.http.endpoint, "https://api.honeycomb.io/1/batch/mcp");
+        assert_eq!(config.http.api_key_header, "X-Honeycomb-Team");
+        assert_eq!(config.http.batch_size, 50);
+        assert!(config.http.api_key.is_none());
+    }
+
+    #[test]
+    fn test_error_reporting_disabled_by_default() {
+        let config = ObservabilityConfig::default();
+        assert!(!config.errors.enabled);
+        assert!(config.errors.sinks.is_empty());
+    }
+
     #[test]
     fn test_metrics_config_defaults() {
         let config = MetricsConfig::default();
@@ -0,0 +1,294 @@
+//! Datadog backend using the `DogStatsD` protocol.
+//!
+//! [`DatadogBackend`] sends metrics as `DogStatsD` datagrams over UDP to the
+//! local Datadog Agent, which forwards them to Datadog. Every response event
+//! becomes the [`StandardMetrics`] duration, count, and error series, tagged
+//! by server, method, and operation; metrics emitted by the middleware or
+//! other components are forwarded with their dimensions as tags.
+//!
+//! Trace and user IDs are never used as tags, to keep metric cardinality
+//! bounded. Handler logs are left to the Agent's log collection.
+//!
+//! ```toml
+//! [observability]
+//! backend = "datadog"
+//!
+//! [observability.datadog]
+//! agent_addr = "127.0.0.1:8125"
+//! tags = ["env:prod", "service:weather"]
+//! ```
+//!
+//! The standard `DD_AGENT_HOST` and `DD_DOGSTATSD_PORT` environment
+//! variables override `agent_addr`.
+
+use super::backend::ObservabilityBackend;
+use super::events::{McpMetric, McpRequestEvent, McpResponseEvent, MetricUnit, StandardMetrics};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+/// Configuration for the Datadog backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatadogConfig {
+    /// `host:port` of the `DogStatsD` listener of the Datadog Agent.
+    pub agent_addr: String,
+
+    /// Tags added to every metric (e.g. `env:prod`).
+    pub tags: Vec<String>,
+}
+
+impl Default for DatadogConfig {
+    fn default() -> Self {
+        Self {
+            agent_addr: "127.0.0.1:8125".to_string(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Datadog backend sending `DogStatsD` metrics to the Datadog Agent.
+///
+/// Datagrams are sent without blocking; metrics are dropped (with a debug
+/// log) if the Agent is unreachable.
+///
+/// # Example
+///
+/// ```rust
+/// use pmcp::server::observability::{DatadogBackend, DatadogConfig};
+///
+/// let backend = DatadogBackend::new(DatadogConfig {
+///     tags: vec!["env:staging".to_string()],
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug)]
+pub struct DatadogBackend {
+    config: DatadogConfig,
+    socket: Option<(UdpSocket, SocketAddr)>,
+}
+
+impl DatadogBackend {
+    /// Create a backend sending to `config.agent_addr`.
+    ///
+    /// An address that cannot be resolved or bound is logged, and the
+    /// backend then discards all metrics.
+    pub fn new(config: DatadogConfig) -> Self {
+        let socket = match Self::connect(&config.agent_addr) {
+            Ok(socket) => Some(socket),
+            Err(e) => {
+                tracing::warn!(
+                    "Datadog backend disabled: cannot reach DogStatsD at {}: {}",
+                    config.agent_addr,
+                    e
+                );
+                None
+            },
+        };
+        Self { config, socket }
+    }
+
+    fn connect(agent_addr: &str) -> std::io::Result<(UdpSocket, SocketAddr)> {
+        let target = agent_addr.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "address did not resolve")
+        })?;
+        let bind: SocketAddr = if target.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(bind)?;
+        socket.set_nonblocking(true)?;
+        Ok((socket, target))
+    }
+
+    /// Format one `DogStatsD` datagram.
+    fn datagram<'a>(
+        &self,
+        name: &str,
+        value: f64,
+        kind: &str,
+        tags: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> String {
+        let mut line = format!("{}:{value}|{kind}", sanitize(name));
+        let mut tags: Vec<String> = tags
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", sanitize(key), sanitize(value)))
+            .collect();
+        tags.sort();
+        tags.extend(self.config.tags.iter().map(|tag| sanitize(tag)));
+        if !tags.is_empty() {
+            let _ = write!(line, "|#{}", tags.join(","));
+        }
+        line
+    }
+
+    fn send(&self, datagram: &str) {
+        if let Some((socket, target)) = &self.socket {
+            if let Err(e) = socket.send_to(datagram.as_bytes(), target) {
+                tracing::debug!("Failed to send DogStatsD metric to {}: {}", target, e);
+            }
+        }
+    }
+}
+
+/// The `DogStatsD` metric type for a unit.
+fn metric_kind(unit: MetricUnit) -> &'static str {
+    match unit {
+        MetricUnit::Count => "c",
+        MetricUnit::Milliseconds => "h",
+        MetricUnit::Bytes | MetricUnit::Percent | MetricUnit::None => "g",
+    }
+}
+
+/// Replace characters that delimit `DogStatsD` fields.
+fn sanitize(value: &str) -> String {
+    value.replace(['|', ',', '#', '\n'], "_")
+}
+
+#[async_trait]
+impl ObservabilityBackend for DatadogBackend {
+    async fn record_request(&self, _event: &McpRequestEvent) {
+        // Requests are counted when their response is recorded
+    }
+
+    #[allow(clippy::cast_precision_loss)] // millisecond durations fit comfortably in f64
+    async fn record_response(&self, event: &McpResponseEvent) {
+        let success = event.success.to_string();
+        let mut tags = vec![
+            ("server", event.server_name.as_str()),
+            ("method", event.operation.method.as_str()),
+            ("success", success.as_str()),
+        ];
+        if let Some(operation) = event.operation.operation_name() {
+            tags.push(("operation", operation));
+        }
+
+        self.send(&self.datagram(
+            StandardMetrics::REQUEST_DURATION,
+            event.duration_ms as f64,
+            "h",
+            tags.iter().copied(),
+        ));
+        self.send(&self.datagram(
+            StandardMetrics::REQUEST_COUNT,
+            1.0,
+            "c",
+            tags.iter().copied(),
+        ));
+        if let Some(code) = event.error_code {
+            let code = code.to_string();
+            self.send(&self.datagram(
+                StandardMetrics::REQUEST_ERRORS,
+                1.0,
+                "c",
+                tags.iter().copied().chain([("error_code", code.as_str())]),
+            ));
+        }
+    }
+
+    async fn emit_metric(&self, metric: &McpMetric) {
+        self.send(
+            &self.datagram(
+                &metric.name,
+                metric.value,
+                metric_kind(metric.unit),
+                metric
+                    .dimensions
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            ),
+        );
+    }
+
+    async fn flush(&self) {
+        // Datagrams are sent immediately
+    }
+
+    fn name(&self) -> &'static str {
+        "datadog"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.socket.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::observability::{McpOperationDetails, TraceContext};
+    use std::time::Duration;
+
+    /// A local `DogStatsD` listener and a backend pointed at it.
+    fn listener(tags: Vec<String>) -> (UdpSocket, DatadogBackend) {
+        let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let backend = DatadogBackend::new(DatadogConfig {
+            agent_addr: agent.local_addr().unwrap().to_string(),
+            tags,
+        });
+        (agent, backend)
+    }
+
+    fn receive(agent: &UdpSocket) -> String {
+        let mut buf = [0; 1024];
+        let len = agent.recv(&mut buf).unwrap();
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_sent_as_dogstatsd() {
+        let (agent, backend) = listener(vec!["env:test".to_string()]);
+        assert!(backend.is_enabled());
+
+        backend
+            .emit_metric(
+                &McpMetric::count("mcp.tool.complete", 1).with_dimension("tool", "get|weather"),
+            )
+            .await;
+
+        assert_eq!(
+            receive(&agent),
+            "mcp.tool.complete:1|c|#tool:get_weather,env:test"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_response_emits_duration_count_and_error() {
+        let (agent, backend) = listener(Vec::new());
+        let event = McpResponseEvent::failure(
+            TraceContext::new_root(),
+            "weather",
+            McpOperationDetails::tool_call("forecast"),
+            42,
+            -32603,
+            "boom",
+        );
+
+        backend.record_response(&event).await;
+
+        let tags = "method:tools/call,operation:forecast,server:weather,success:false";
+        assert_eq!(
+            receive(&agent),
+            format!("mcp.request.duration:42|h|#{tags}")
+        );
+        assert_eq!(receive(&agent), format!("mcp.request.count:1|c|#{tags}"));
+        assert_eq!(
+            receive(&agent),
+            format!("mcp.request.errors:1|c|#error_code:-32603,{tags}")
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_agent_disables_backend() {
+        let backend = DatadogBackend::new(DatadogConfig {
+            agent_addr: "not an address".to_string(),
+            tags: Vec::new(),
+        });
+        assert!(!backend.is_enabled());
+    }
+}
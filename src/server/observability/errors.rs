@@ -0,0 +1,708 @@
+//! Structured error reports for failed tool calls and panics.
+//!
+//! When error reporting is enabled, the observability middleware turns every
+//! failed tool call into an [`ErrorReport`] carrying the request context that
+//! was live when the error happened: tool name, trace context, argument hash,
+//! and user identity. Each report has a stable fingerprint so that sinks can
+//! group repeated occurrences of the same failure.
+//!
+//! Reports are delivered to [`ErrorSink`]s. Built-in sinks:
+//! - [`FileErrorSink`] - JSON lines appended to a file
+//! - `WebhookErrorSink` - JSON POST to a URL (`http-client` feature)
+//! - `SentryErrorSink` - Sentry-compatible store endpoint (`http-client` feature)
+//!
+//! # Panics
+//!
+//! [`install_panic_hook`] reports panics with their code location. If the
+//! panic happens inside a tool call (its middleware or handler, but not
+//! tasks the handler spawns), the report also carries that call's context.
+//!
+//! Reports are delivered in the background, so slow sinks never hold up the
+//! response; HTTP sinks give up after [`SINK_TIMEOUT`].
+
+use super::config::{ErrorReportingConfig, ErrorSinkConfig};
+use super::types::TraceContext;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+/// Variable parts of error messages (UUIDs, long hex IDs, numbers) that
+/// should not split otherwise identical errors into separate fingerprints.
+static VOLATILE_TOKENS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}|\b[0-9a-f]{12,}\b|\d+",
+    )
+    .unwrap()
+});
+
+/// Tool name used for panics that cannot be attributed to a tool call.
+const UNKNOWN_TOOL: &str = "<unknown>";
+
+/// How long HTTP sinks wait for their endpoint before dropping a report.
+pub const SINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+tokio::task_local! {
+    /// The tool call running in this task.
+    static CURRENT_CALL: CallScope;
+}
+
+/// Source of [`call_id`]s.
+static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Per-call state set up by [`scope_call`].
+struct CallScope {
+    /// Process-unique ID of the call.
+    id: u64,
+    /// Error context of the call, if captured.
+    error: Mutex<Option<(String, ErrorContext)>>,
+}
+
+/// Source code location where a panic occurred.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeLocation {
+    /// Source file path.
+    pub file: String,
+    /// Line number.
+    pub line: u32,
+    /// Column number.
+    pub column: u32,
+}
+
+impl From<&std::panic::Location<'_>> for CodeLocation {
+    fn from(location: &std::panic::Location<'_>) -> Self {
+        Self {
+            file: location.file().to_string(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+/// Request context captured when a tool call starts.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ErrorContext {
+    pub(crate) trace: Option<TraceContext>,
+    pub(crate) arguments_hash: Option<String>,
+    pub(crate) user_id: Option<String>,
+    pub(crate) tenant_id: Option<String>,
+}
+
+/// A structured report of a failed tool call or panic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorReport {
+    /// Stable grouping key (see [`fingerprint`]).
+    pub fingerprint: String,
+
+    /// Server name.
+    pub server_name: String,
+
+    /// Tool that failed.
+    pub tool_name: String,
+
+    /// JSON-RPC error code.
+    pub error_code: i32,
+
+    /// Error message.
+    pub message: String,
+
+    /// Whether the report comes from a panic rather than an error result.
+    pub panic: bool,
+
+    /// Trace context of the failing request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TraceContext>,
+
+    /// Hash of the call arguments (never the arguments themselves).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments_hash: Option<String>,
+
+    /// Code location (available for panics).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<CodeLocation>,
+
+    /// User ID from `AuthContext`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    /// Tenant ID from `AuthContext`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+
+    /// When the error occurred.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ErrorReport {
+    /// Create a report for a failed tool call.
+    pub fn new(
+        server_name: impl Into<String>,
+        tool_name: impl Into<String>,
+        error_code: i32,
+        message: impl Into<String>,
+    ) -> Self {
+        let tool_name = tool_name.into();
+        let message = message.into();
+        Self {
+            fingerprint: fingerprint(&tool_name, error_code, &message, None),
+            server_name: server_name.into(),
+            tool_name,
+            error_code,
+            message,
+            panic: false,
+            trace: None,
+            arguments_hash: None,
+            location: None,
+            user_id: None,
+            tenant_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the code location; the fingerprint is recomputed to include it.
+    pub fn with_location(mut self, location: CodeLocation) -> Self {
+        self.fingerprint = fingerprint(
+            &self.tool_name,
+            self.error_code,
+            &self.message,
+            Some(&location),
+        );
+        self.location = Some(location);
+        self
+    }
+
+    /// Set the trace context.
+    pub fn with_trace(mut self, trace: TraceContext) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Set the arguments hash.
+    pub fn with_arguments_hash(mut self, hash: impl Into<String>) -> Self {
+        self.arguments_hash = Some(hash.into());
+        self
+    }
+
+    pub(crate) fn with_context(mut self, context: ErrorContext) -> Self {
+        self.trace = context.trace;
+        self.arguments_hash = context.arguments_hash;
+        self.user_id = context.user_id;
+        self.tenant_id = context.tenant_id;
+        self
+    }
+}
+
+/// Compute a stable fingerprint for an error.
+///
+/// The fingerprint hashes the tool name, error code, code location (when
+/// known), and the message with volatile tokens (numbers, UUIDs, long hex
+/// IDs) masked out, so "user 42 not found" and "user 7 not found" group
+/// together.
+pub fn fingerprint(
+    tool_name: &str,
+    error_code: i32,
+    message: &str,
+    location: Option<&CodeLocation>,
+) -> String {
+    let normalized = VOLATILE_TOKENS.replace_all(message, "#");
+    let mut hasher = Sha256::new();
+    hasher.update(tool_name.as_bytes());
+    hasher.update([0]);
+    hasher.update(error_code.to_be_bytes());
+    hasher.update(normalized.as_bytes());
+    if let Some(location) = location {
+        hasher.update([0]);
+        hasher.update(location.file.as_bytes());
+        hasher.update(location.line.to_be_bytes());
+    }
+    hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Destination for error reports.
+///
+/// Like [`ObservabilityBackend`](super::ObservabilityBackend), sinks never
+/// return errors: reporting must not fail a request. Implementations should
+/// log delivery failures and move on.
+#[async_trait]
+pub trait ErrorSink: Send + Sync + 'static {
+    /// Deliver a report.
+    async fn report(&self, report: &ErrorReport);
+
+    /// Sink name for diagnostics.
+    fn name(&self) -> &'static str;
+}
+
+/// Build the sinks described by an [`ErrorReportingConfig`].
+///
+/// Returns no sinks when reporting is disabled. HTTP sinks are skipped with
+/// a warning when the `http-client` feature is off.
+pub fn build_error_sinks(config: &ErrorReportingConfig) -> Vec<Arc<dyn ErrorSink>> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    config
+        .sinks
+        .iter()
+        .filter_map(|sink| -> Option<Arc<dyn ErrorSink>> {
+            match sink {
+                ErrorSinkConfig::File { path } => Some(Arc::new(FileErrorSink::new(path.clone()))),
+                #[cfg(feature = "http-client")]
+                ErrorSinkConfig::Webhook { url } => Some(Arc::new(WebhookErrorSink::new(url))),
+                #[cfg(feature = "http-client")]
+                ErrorSinkConfig::Sentry { dsn } => match SentryErrorSink::from_dsn(dsn) {
+                    Ok(sink) => Some(Arc::new(sink)),
+                    Err(e) => {
+                        tracing::warn!("Ignoring Sentry error sink: {}", e);
+                        None
+                    },
+                },
+                #[cfg(not(feature = "http-client"))]
+                ErrorSinkConfig::Webhook { .. } | ErrorSinkConfig::Sentry { .. } => {
+                    tracing::warn!(
+                        "Ignoring HTTP error sink: pmcp was built without the `http-client` feature"
+                    );
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Deliver a report to every sink concurrently.
+async fn dispatch(sinks: &[Arc<dyn ErrorSink>], report: &ErrorReport) {
+    let futures: Vec<_> = sinks.iter().map(|s| s.report(report)).collect();
+    futures::future::join_all(futures).await;
+}
+
+/// Deliver a report to every sink in the background.
+///
+/// Reports are dropped when there is no Tokio runtime to deliver them on.
+pub(crate) fn spawn_dispatch(sinks: &[Arc<dyn ErrorSink>], report: ErrorReport) {
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        let sinks = sinks.to_vec();
+        handle.spawn(async move { dispatch(&sinks, &report).await });
+    }
+}
+
+/// Run a tool call (its middleware and handler) under a fresh [`call_id`],
+/// with room for its error context.
+///
+/// The context lives exactly as long as the call, so a call that is dropped
+/// or times out leaves nothing behind.
+pub(crate) async fn scope_call<F: Future>(future: F) -> F::Output {
+    let scope = CallScope {
+        id: NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed),
+        error: Mutex::new(None),
+    };
+    CURRENT_CALL.scope(scope, future).await
+}
+
+/// Unique ID of the running tool call, unlike its request ID, which
+/// concurrent calls can share. `None` outside [`scope_call`].
+pub(crate) fn call_id() -> Option<u64> {
+    CURRENT_CALL.try_with(|c| c.id).ok()
+}
+
+/// Record the error context of the running tool call.
+///
+/// Does nothing outside [`scope_call`].
+pub(crate) fn enter_call(tool_name: &str, context: ErrorContext) {
+    let _ = CURRENT_CALL.try_with(|c| *c.error.lock() = Some((tool_name.to_string(), context)));
+}
+
+/// Take the error context recorded by [`enter_call`].
+pub(crate) fn take_call() -> Option<ErrorContext> {
+    CURRENT_CALL
+        .try_with(|c| c.error.lock().take())
+        .ok()
+        .flatten()
+        .map(|(_, context)| context)
+}
+
+/// Install a panic hook that reports panics to `sinks`.
+///
+/// The previous hook still runs afterwards, so panic messages keep appearing
+/// on stderr. Reports are delivered on the current Tokio runtime; panics
+/// outside a runtime are not reported.
+pub fn install_panic_hook(server_name: impl Into<String>, sinks: Vec<Arc<dyn ErrorSink>>) {
+    let server_name = server_name.into();
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+
+        // `try_lock`: a panic while the slot is locked must not deadlock
+        let current = CURRENT_CALL
+            .try_with(|c| c.error.try_lock().and_then(|call| call.clone()))
+            .ok()
+            .flatten();
+        let (tool_name, context) =
+            current.unwrap_or_else(|| (UNKNOWN_TOOL.to_string(), ErrorContext::default()));
+
+        let mut report = ErrorReport::new(
+            &server_name,
+            tool_name,
+            crate::ErrorCode::INTERNAL_ERROR.as_i32(),
+            message,
+        )
+        .with_context(context);
+        report.panic = true;
+        if let Some(location) = info.location() {
+            report = report.with_location(location.into());
+        }
+
+        spawn_dispatch(&sinks, report);
+
+        previous(info);
+    }));
+}
+
+/// Appends error reports as JSON lines to a file.
+#[derive(Debug)]
+pub struct FileErrorSink {
+    path: PathBuf,
+}
+
+impl FileErrorSink {
+    /// Create a sink writing to `path` (created if missing).
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl ErrorSink for FileErrorSink {
+    async fn report(&self, report: &ErrorReport) {
+        use tokio::io::AsyncWriteExt;
+
+        let Ok(mut line) = serde_json::to_vec(report) else {
+            return;
+        };
+        line.push(b'\n');
+
+        let result = async {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            file.write_all(&line).await?;
+            // tokio writes in the background; flush so the line is on disk
+            file.flush().await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to write error report to {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "file"
+    }
+}
+
+/// HTTP client for sinks, bounded by [`SINK_TIMEOUT`].
+#[cfg(feature = "http-client")]
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(SINK_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// POSTs each error report as JSON to a webhook URL.
+#[cfg(feature = "http-client")]
+#[derive(Debug)]
+pub struct WebhookErrorSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "http-client")]
+impl WebhookErrorSink {
+    /// Create a sink posting to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: http_client(),
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+#[async_trait]
+impl ErrorSink for WebhookErrorSink {
+    async fn report(&self, report: &ErrorReport) {
+        match self.client.post(&self.url).json(report).send().await {
+            Ok(response) if !response.status().is_success() => {
+                tracing::warn!("Error webhook {} returned {}", self.url, response.status());
+            },
+            Ok(_) => {},
+            Err(e) => tracing::warn!("Failed to send error report to {}: {}", self.url, e),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+}
+
+/// Sends error reports to a Sentry-compatible store endpoint.
+///
+/// Works with Sentry and with self-hosted services that accept the Sentry
+/// store API (e.g. GlitchTip). The report fingerprint is passed through so
+/// events group the same way as in other sinks.
+#[cfg(feature = "http-client")]
+#[derive(Debug)]
+pub struct SentryErrorSink {
+    store_url: String,
+    auth_header: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "http-client")]
+impl SentryErrorSink {
+    /// Create a sink from a project DSN (`https://key@host/project_id`).
+    pub fn from_dsn(dsn: &str) -> Result<Self, String> {
+        let url = url::Url::parse(dsn).map_err(|e| format!("invalid DSN: {}", e))?;
+        let key = url.username();
+        if key.is_empty() {
+            return Err("DSN is missing the public key".to_string());
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| "DSN is missing the host".to_string())?;
+        let project_id = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| "DSN is missing the project ID".to_string())?;
+        let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
+
+        Ok(Self {
+            store_url: format!(
+                "{}://{}{}/api/{}/store/",
+                url.scheme(),
+                host,
+                port,
+                project_id
+            ),
+            auth_header: format!(
+                "Sentry sentry_version=7, sentry_key={}, sentry_client=pmcp/{}",
+                key,
+                env!("CARGO_PKG_VERSION")
+            ),
+            client: http_client(),
+        })
+    }
+
+    fn event_body(report: &ErrorReport) -> serde_json::Value {
+        let mut tags = serde_json::json!({
+            "server": report.server_name,
+            "tool": report.tool_name,
+            "error_code": report.error_code.to_string(),
+        });
+        if let Some(trace) = &report.trace {
+            tags["trace_id"] = trace.trace_id.clone().into();
+            tags["span_id"] = trace.span_id.clone().into();
+        }
+
+        serde_json::json!({
+            "event_id": uuid::Uuid::new_v4().simple().to_string(),
+            "timestamp": report.timestamp.to_rfc3339(),
+            "level": if report.panic { "fatal" } else { "error" },
+            "platform": "rust",
+            "logger": "pmcp",
+            "server_name": report.server_name,
+            "message": { "formatted": report.message },
+            "fingerprint": [report.fingerprint],
+            "tags": tags,
+            "user": report.user_id.as_ref().map(|id| serde_json::json!({ "id": id })),
+            "extra": {
+                "arguments_hash": report.arguments_hash,
+                "tenant_id": report.tenant_id,
+                "location": report.location,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "http-client")]
+#[async_trait]
+impl ErrorSink for SentryErrorSink {
+    async fn report(&self, report: &ErrorReport) {
+        let result = self
+            .client
+            .post(&self.store_url)
+            .header("X-Sentry-Auth", &self.auth_header)
+            .json(&Self::event_body(report))
+            .send()
+            .await;
+        match result {
+            Ok(response) if !response.status().is_success() => {
+                tracing::warn!("Sentry store endpoint returned {}", response.status());
+            },
+            Ok(_) => {},
+            Err(e) => tracing::warn!("Failed to send error report to Sentry: {}", e),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "sentry"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_volatile_tokens() {
+        let a = fingerprint("get_user", -32603, "user 42 not found", None);
+        let b = fingerprint("get_user", -32603, "user 1337 not found", None);
+        let c = fingerprint(
+            "get_user",
+            -32603,
+            "user 6f1c2b9e-7a41-4a55-9b5c-0d9a3c2e8f10 not found",
+            None,
+        );
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(a.len(), 32);
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_tool_code_and_location() {
+        let base = fingerprint("get_user", -32603, "boom", None);
+        assert_ne!(base, fingerprint("get_order", -32603, "boom", None));
+        assert_ne!(base, fingerprint("get_user", -32602, "boom", None));
+
+        let location = CodeLocation {
+            file: "src/tools.rs".to_string(),
+            line: 10,
+            column: 5,
+        };
+        assert_ne!(
+            base,
+            fingerprint("get_user", -32603, "boom", Some(&location))
+        );
+    }
+
+    #[test]
+    fn test_report_with_location_updates_fingerprint() {
+        let report = ErrorReport::new("server", "tool", -32603, "boom");
+        let before = report.fingerprint.clone();
+        let report = report.with_location(CodeLocation {
+            file: "src/lib.rs".to_string(),
+            line: 1,
+            column: 1,
+        });
+        assert_ne!(before, report.fingerprint);
+    }
+
+    #[test]
+    fn test_build_error_sinks_respects_enabled() {
+        let mut config = ErrorReportingConfig {
+            enabled: false,
+            sinks: vec![ErrorSinkConfig::File {
+                path: PathBuf::from("errors.jsonl"),
+            }],
+        };
+        assert!(build_error_sinks(&config).is_empty());
+
+        config.enabled = true;
+        let sinks = build_error_sinks(&config);
+        assert_eq!(sinks.len(), 1);
+        assert_eq!(sinks[0].name(), "file");
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("errors.jsonl");
+        let sink = FileErrorSink::new(&path);
+
+        sink.report(&ErrorReport::new("server", "a", -32603, "first"))
+            .await;
+        sink.report(&ErrorReport::new("server", "b", -32603, "second"))
+            .await;
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let reports: Vec<ErrorReport> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].tool_name, "a");
+        assert_eq!(reports[1].message, "second");
+    }
+
+    #[tokio::test]
+    async fn test_call_context_is_scoped_to_the_call() {
+        let context = || ErrorContext {
+            user_id: Some("user-1".to_string()),
+            ..Default::default()
+        };
+
+        // Outside a call scope there is nowhere to keep it
+        enter_call("tool", context());
+        assert!(take_call().is_none());
+
+        let (a, b) = tokio::join!(
+            scope_call(async {
+                enter_call("a", context());
+                tokio::task::yield_now().await;
+                take_call()
+            }),
+            scope_call(async {
+                tokio::task::yield_now().await;
+                take_call()
+            }),
+        );
+        assert_eq!(a.unwrap().user_id.as_deref(), Some("user-1"));
+        assert!(b.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_each_call_gets_its_own_id() {
+        assert!(call_id().is_none());
+        let (a, b) = tokio::join!(
+            scope_call(async { call_id() }),
+            scope_call(async { call_id() })
+        );
+        assert!(a.is_some() && b.is_some());
+        assert_ne!(a, b);
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_sentry_sink_from_dsn() {
+        let sink = SentryErrorSink::from_dsn("https://abc123@o0.ingest.example.io/42").unwrap();
+        assert_eq!(sink.store_url, "https://o0.ingest.example.io/api/42/store/");
+        assert!(sink.auth_header.contains("sentry_key=abc123"));
+
+        assert!(SentryErrorSink::from_dsn("https://o0.ingest.example.io/42").is_err());
+        assert!(SentryErrorSink::from_dsn("not a url").is_err());
+    }
+}
@@ -3,6 +3,7 @@
 //! Events are emitted at key points in request processing:
 //! - `McpRequestEvent` - When a request is received
 //! - `McpResponseEvent` - When a response is sent
+//! - `McpLogEvent` - When a handler emits a `tracing` event or closes a span
 //! - `McpMetric` - For metric data points
 //!
 //! # User Identity
@@ -208,6 +209,84 @@ impl McpResponseEvent {
     }
 }
 
+/// A `tracing` event or span from inside a handler, correlated with its request.
+///
+/// Produced by [`ObservabilityLayer`](super::ObservabilityLayer) so that
+/// existing `tracing::info!` instrumentation reaches the backend with the
+/// trace context of the tool call it was emitted in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpLogEvent {
+    /// Trace context of the request the event belongs to.
+    pub trace: TraceContext,
+
+    /// Server name.
+    pub server_name: String,
+
+    /// Tool being called, if the event was emitted inside a tool call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+
+    /// Level of the event (`ERROR`, `WARN`, `INFO`, `DEBUG` or `TRACE`).
+    pub level: String,
+
+    /// `tracing` target, usually the module path of the emitting code.
+    pub target: String,
+
+    /// The event message, or the span name for closed spans.
+    pub message: String,
+
+    /// How long the span was open, for closed spans; `None` for events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+
+    /// Structured fields of the event and the spans it was emitted in.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, serde_json::Value>,
+
+    /// Timestamp when the event was emitted.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl McpLogEvent {
+    /// Create a new log event.
+    pub fn new(
+        trace: TraceContext,
+        server_name: impl Into<String>,
+        level: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            trace,
+            server_name: server_name.into(),
+            tool_name: None,
+            level: level.into(),
+            target: String::new(),
+            message: message.into(),
+            duration_ms: None,
+            fields: HashMap::new(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the `tracing` target.
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// Set the tool name.
+    pub fn with_tool_name(mut self, tool_name: impl Into<String>) -> Self {
+        self.tool_name = Some(tool_name.into());
+        self
+    }
+
+    /// Add a structured field.
+    pub fn with_field(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.fields.insert(key.into(), value);
+        self
+    }
+}
+
 /// Metric unit for observability metrics.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MetricUnit {
@@ -317,6 +396,9 @@ impl StandardMetrics {
 
     /// Metric name for composition depth.
     pub const COMPOSITION_DEPTH: &'static str = "mcp.composition.depth";
+
+    /// Metric name for tool invocations.
+    pub const TOOL_USAGE: &'static str = "mcp.tool.usage";
 }
 
 /// Tracks the start time of a request for duration calculation.
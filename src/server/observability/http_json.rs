@@ -0,0 +1,486 @@
+//! Generic JSON-over-HTTP backend.
+//!
+//! [`HttpJsonBackend`] buffers events and POSTs them as a JSON array of
+//! `{"time": ..., "data": {...}}` objects, the batch format of Honeycomb's
+//! events API, which most event and log ingestion endpoints also accept.
+//! `data` is flat: response events carry `name`, `service.name`,
+//! `duration_ms`, and `trace.trace_id` / `trace.span_id` /
+//! `trace.parent_id`, so Honeycomb assembles them into traces.
+//!
+//! ```toml
+//! [observability]
+//! backend = "honeycomb"  # or "http"
+//!
+//! [observability.http]
+//! endpoint = "https://api.honeycomb.io/1/batch/mcp-servers"
+//! api_key_header = "X-Honeycomb-Team"
+//! batch_size = 100
+//! flush_interval_ms = 10000
+//! ```
+//!
+//! The API key is best set through `PMCP_OBSERVABILITY_HTTP_API_KEY` rather
+//! than the config file. Sending requires the `http-client` feature.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "http-client")]
+use super::backend::ObservabilityBackend;
+#[cfg(feature = "http-client")]
+use super::events::{McpLogEvent, McpMetric, McpRequestEvent, McpResponseEvent};
+#[cfg(feature = "http-client")]
+use super::types::TraceContext;
+#[cfg(feature = "http-client")]
+use async_trait::async_trait;
+#[cfg(feature = "http-client")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "http-client")]
+use parking_lot::Mutex;
+#[cfg(feature = "http-client")]
+use serde_json::{json, Map, Value};
+#[cfg(feature = "http-client")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "http-client")]
+use std::sync::{Arc, Weak};
+#[cfg(feature = "http-client")]
+use std::time::Duration;
+
+/// How long one batch POST may take before it is abandoned.
+#[cfg(feature = "http-client")]
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configuration for the JSON-over-HTTP backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpJsonConfig {
+    /// URL that batches are POSTed to.
+    pub endpoint: String,
+
+    /// API key sent in `api_key_header`, if the endpoint requires one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Header carrying the API key.
+    pub api_key_header: String,
+
+    /// Number of buffered events that triggers a send.
+    pub batch_size: usize,
+
+    /// Longest time, in milliseconds, an event waits in the buffer before
+    /// it is sent. `0` sends only full batches and explicit flushes.
+    pub flush_interval_ms: u64,
+}
+
+impl Default for HttpJsonConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            api_key: None,
+            api_key_header: "X-Honeycomb-Team".to_string(),
+            batch_size: 100,
+            flush_interval_ms: 10_000,
+        }
+    }
+}
+
+/// Backend that POSTs batches of JSON events to an HTTP endpoint.
+///
+/// A full batch is sent in the background, and a background task sends
+/// whatever is buffered every `flush_interval_ms`.
+/// [`flush`](ObservabilityBackend::flush) sends the buffer and waits for
+/// it; dropping the backend sends the buffer in the background. Failed
+/// sends are logged and their events dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use pmcp::server::observability::{HttpJsonBackend, HttpJsonConfig};
+///
+/// let backend = HttpJsonBackend::new(HttpJsonConfig {
+///     endpoint: "https://api.honeycomb.io/1/batch/mcp-servers".to_string(),
+///     api_key: std::env::var("HONEYCOMB_API_KEY").ok(),
+///     ..Default::default()
+/// });
+/// ```
+#[cfg(feature = "http-client")]
+#[derive(Debug)]
+pub struct HttpJsonBackend {
+    batches: Arc<Batches>,
+    /// Whether the periodic flush task has been started
+    flusher_started: AtomicBool,
+}
+
+/// The buffer and the means to send it, shared with the periodic flush task.
+#[cfg(feature = "http-client")]
+#[derive(Debug)]
+struct Batches {
+    config: HttpJsonConfig,
+    client: reqwest::Client,
+    buffer: Mutex<Vec<Value>>,
+}
+
+#[cfg(feature = "http-client")]
+impl HttpJsonBackend {
+    /// Create a backend posting to `config.endpoint`.
+    pub fn new(config: HttpJsonConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(SEND_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            batches: Arc::new(Batches {
+                config,
+                client,
+                buffer: Mutex::new(Vec::new()),
+            }),
+            flusher_started: AtomicBool::new(false),
+        }
+    }
+
+    /// Buffer an event, sending the batch in the background once full.
+    fn push(&self, time: DateTime<Utc>, data: Map<String, Value>) {
+        let batches = &self.batches;
+        let batch = {
+            let mut buffer = batches.buffer.lock();
+            buffer.push(json!({ "time": time.to_rfc3339(), "data": data }));
+            if buffer.len() < batches.config.batch_size.max(1) {
+                None
+            } else {
+                Some(std::mem::take(&mut *buffer))
+            }
+        };
+        match batch {
+            Some(batch) => batches.send_in_background(batch),
+            None => self.start_flusher(),
+        }
+    }
+
+    /// Start the periodic flush task, once, on the current runtime.
+    fn start_flusher(&self) {
+        let interval = self.batches.config.flush_interval_ms;
+        if interval == 0 || self.flusher_started.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if !self.flusher_started.swap(true, Ordering::Relaxed) {
+            runtime.spawn(flush_periodically(
+                Arc::downgrade(&self.batches),
+                Duration::from_millis(interval),
+            ));
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+impl Drop for HttpJsonBackend {
+    fn drop(&mut self) {
+        let batch = std::mem::take(&mut *self.batches.buffer.lock());
+        if !batch.is_empty() {
+            self.batches.send_in_background(batch);
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+impl Batches {
+    fn request(&self, batch: &[Value]) -> reqwest::RequestBuilder {
+        let request = self.client.post(&self.config.endpoint).json(batch);
+        match &self.config.api_key {
+            Some(key) => request.header(self.config.api_key_header.as_str(), key),
+            None => request,
+        }
+    }
+
+    /// Send everything buffered and wait for the response.
+    async fn flush(&self) {
+        let batch = std::mem::take(&mut *self.buffer.lock());
+        if !batch.is_empty() {
+            send(self.request(&batch), self.config.endpoint.clone()).await;
+        }
+    }
+
+    fn send_in_background(&self, batch: Vec<Value>) {
+        let request = self.request(&batch);
+        let endpoint = self.config.endpoint.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(send(request, endpoint));
+            },
+            Err(_) => tracing::warn!(
+                "Dropping {} observability events: no Tokio runtime to send them on",
+                batch.len()
+            ),
+        }
+    }
+}
+
+/// Flush `batches` every `interval` until the backend is dropped.
+#[cfg(feature = "http-client")]
+async fn flush_periodically(batches: Weak<Batches>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let Some(batches) = batches.upgrade() else {
+            return;
+        };
+        batches.flush().await;
+    }
+}
+
+#[cfg(feature = "http-client")]
+async fn send(request: reqwest::RequestBuilder, endpoint: String) {
+    match request.send().await {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!(
+                "Observability endpoint {} returned {}",
+                endpoint,
+                response.status()
+            );
+        },
+        Ok(_) => {},
+        Err(e) => tracing::warn!("Failed to send observability events to {}: {}", endpoint, e),
+    }
+}
+
+/// The `trace.*` fields Honeycomb uses to assemble traces.
+#[cfg(feature = "http-client")]
+fn trace_fields(data: &mut Map<String, Value>, trace: &TraceContext) {
+    data.insert("trace.trace_id".into(), json!(trace.trace_id));
+    data.insert("trace.span_id".into(), json!(trace.span_id));
+    if let Some(parent) = &trace.parent_span_id {
+        data.insert("trace.parent_id".into(), json!(parent));
+    }
+}
+
+#[cfg(feature = "http-client")]
+#[async_trait]
+impl ObservabilityBackend for HttpJsonBackend {
+    async fn record_request(&self, _event: &McpRequestEvent) {
+        // The response event carries the full span
+    }
+
+    async fn record_response(&self, event: &McpResponseEvent) {
+        let mut data = Map::new();
+        data.insert(
+            "name".into(),
+            json!(event
+                .operation
+                .operation_name()
+                .unwrap_or(&event.operation.method)),
+        );
+        data.insert("service.name".into(), json!(event.server_name));
+        data.insert("mcp.method".into(), json!(event.operation.method));
+        data.insert("duration_ms".into(), json!(event.duration_ms));
+        data.insert("success".into(), json!(event.success));
+        trace_fields(&mut data, &event.trace);
+        if let Some(code) = event.error_code {
+            data.insert("error.code".into(), json!(code));
+        }
+        if let Some(message) = &event.error_message {
+            data.insert("error.message".into(), json!(message));
+        }
+        if let Some(user_id) = &event.user_id {
+            data.insert("user_id".into(), json!(user_id));
+        }
+        if let Some(tenant_id) = &event.tenant_id {
+            data.insert("tenant_id".into(), json!(tenant_id));
+        }
+        if let Some(size) = event.response_size {
+            data.insert("response_size".into(), json!(size));
+        }
+        self.push(event.timestamp, data);
+    }
+
+    async fn emit_metric(&self, metric: &McpMetric) {
+        let mut data: Map<String, Value> = metric
+            .dimensions
+            .iter()
+            .map(|(key, value)| (key.clone(), json!(value)))
+            .collect();
+        data.insert("name".into(), json!(metric.name));
+        data.insert("value".into(), json!(metric.value));
+        data.insert("unit".into(), json!(metric.unit.as_str()));
+        self.push(metric.timestamp, data);
+    }
+
+    async fn record_log(&self, event: &McpLogEvent) {
+        let mut data: Map<String, Value> = event
+            .fields
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        data.insert("name".into(), json!("log"));
+        data.insert("service.name".into(), json!(event.server_name));
+        data.insert("level".into(), json!(event.level));
+        data.insert("target".into(), json!(event.target));
+        data.insert("message".into(), json!(event.message));
+        if let Some(tool) = &event.tool_name {
+            data.insert("mcp.tool".into(), json!(tool));
+        }
+        trace_fields(&mut data, &event.trace);
+        self.push(event.timestamp, data);
+    }
+
+    async fn flush(&self) {
+        self.batches.flush().await;
+    }
+
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.batches.config.endpoint.is_empty()
+    }
+}
+
+#[cfg(all(test, feature = "http-client"))]
+mod tests {
+    use super::*;
+    use crate::server::observability::McpOperationDetails;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Accept one HTTP request and return its raw text.
+    async fn capture_request(listener: TcpListener) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut raw = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    break;
+                }
+            }
+        }
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8(raw).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_flush_posts_batch_with_api_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/1/batch/test", listener.local_addr().unwrap());
+        let server = tokio::spawn(capture_request(listener));
+
+        let backend = HttpJsonBackend::new(HttpJsonConfig {
+            endpoint,
+            api_key: Some("secret".to_string()),
+            ..Default::default()
+        });
+        assert!(backend.is_enabled());
+
+        let trace = TraceContext::new_root().child();
+        let event = McpResponseEvent::failure(
+            trace.clone(),
+            "weather",
+            McpOperationDetails::tool_call("forecast"),
+            42,
+            -32603,
+            "boom",
+        );
+        backend.record_response(&event).await;
+        backend
+            .emit_metric(&McpMetric::count("mcp.tool.usage", 1).with_dimension("tool", "forecast"))
+            .await;
+        backend.flush().await;
+
+        let request = server.await.unwrap();
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("POST /1/batch/test"));
+        assert!(head
+            .to_ascii_lowercase()
+            .contains("x-honeycomb-team: secret"));
+
+        let batch: Vec<Value> = serde_json::from_str(body).unwrap();
+        assert_eq!(batch.len(), 2);
+        let span = &batch[0]["data"];
+        assert_eq!(span["name"], "forecast");
+        assert_eq!(span["service.name"], "weather");
+        assert_eq!(span["duration_ms"], 42);
+        assert_eq!(span["error.code"], -32603);
+        assert_eq!(span["trace.trace_id"], json!(trace.trace_id));
+        assert_eq!(span["trace.parent_id"], json!(trace.parent_span_id));
+        assert!(batch[0]["time"].is_string());
+        assert_eq!(batch[1]["data"]["tool"], "forecast");
+        assert_eq!(batch[1]["data"]["value"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_events_are_buffered_until_batch_is_full() {
+        let backend = HttpJsonBackend::new(HttpJsonConfig {
+            endpoint: "http://127.0.0.1:9/unused".to_string(),
+            batch_size: 10,
+            ..Default::default()
+        });
+        backend.emit_metric(&McpMetric::count("a", 1)).await;
+        backend.emit_metric(&McpMetric::count("b", 1)).await;
+        assert_eq!(backend.batches.buffer.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_partial_batch_is_sent_after_flush_interval() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = tokio::spawn(capture_request(listener));
+
+        let backend = HttpJsonBackend::new(HttpJsonConfig {
+            endpoint,
+            flush_interval_ms: 50,
+            ..Default::default()
+        });
+        backend.emit_metric(&McpMetric::count("a", 1)).await;
+
+        let request = tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .expect("buffered event should be sent without an explicit flush")
+            .unwrap();
+        let batch: Vec<Value> =
+            serde_json::from_str(request.split_once("\r\n\r\n").unwrap().1).unwrap();
+        assert_eq!(batch[0]["data"]["name"], "a");
+    }
+
+    #[tokio::test]
+    async fn test_dropping_backend_sends_buffered_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = tokio::spawn(capture_request(listener));
+
+        let backend = HttpJsonBackend::new(HttpJsonConfig {
+            endpoint,
+            flush_interval_ms: 0,
+            ..Default::default()
+        });
+        backend.emit_metric(&McpMetric::count("a", 1)).await;
+        drop(backend);
+
+        let request = tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .expect("buffered event should be sent on drop")
+            .unwrap();
+        assert!(request.contains("\"name\":\"a\""));
+    }
+
+    #[test]
+    fn test_backend_without_endpoint_is_disabled() {
+        assert!(!HttpJsonBackend::new(HttpJsonConfig::default()).is_enabled());
+    }
+}
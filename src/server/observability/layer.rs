@@ -0,0 +1,613 @@
+//! Bridge from `tracing` to observability backends.
+//!
+//! Tool handlers run inside an `mcp.tool` span that carries the trace
+//! context created by [`McpObservabilityMiddleware`](super::McpObservabilityMiddleware).
+//! [`ObservabilityLayer`] is a `tracing_subscriber` layer that turns events
+//! emitted inside such a span, and spans opened and closed inside it, into
+//! [`McpLogEvent`]s for the backend. Existing `tracing::info!` calls in tool
+//! code then show up in `CloudWatch` (or any other backend) next to the
+//! request and response events of the call, without logging twice.
+//!
+//! Events outside a traced tool call are left to the other layers, as are
+//! events from the SDK itself, which the middleware already records.
+//!
+//! ```rust,ignore
+//! use pmcp::server::observability::{McpObservabilityMiddleware, ObservabilityConfig};
+//! use tracing_subscriber::prelude::*;
+//!
+//! let middleware = McpObservabilityMiddleware::production("my-server", config);
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(middleware.tracing_layer())
+//!     .init();
+//! ```
+//!
+//! A span opened directly with `trace_id` and `span_id` fields (as
+//! [`CorrelatedLogger`](crate::shared::logging::CorrelatedLogger) does) is
+//! correlated the same way.
+//!
+//! For the server's own log output, [`correlated_fmt_layer`] is a `fmt`
+//! layer that prefixes every line emitted inside a handler with the call's
+//! `trace_id` and `span_id` (see [`CorrelatedFormat`]), so log lines can be
+//! matched with the observability events of the same call:
+//!
+//! ```rust,ignore
+//! tracing_subscriber::registry()
+//!     .with(pmcp::server::observability::correlated_fmt_layer())
+//!     .init();
+//! // trace_id=4bf92f3577b34da6a3ce929d0e0e4736 span_id=00f067aa0ba902b7 2026-01-01T00:00:00Z  INFO ...
+//! ```
+
+use super::backend::ObservabilityBackend;
+use super::events::McpLogEvent;
+use super::types::TraceContext;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::fmt::format::{DefaultFields, Format, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+use tracing_subscriber::Layer;
+
+/// Span fields that make up the trace context rather than event fields.
+const TRACE_FIELDS: [&str; 5] = ["trace_id", "span_id", "parent_span_id", "depth", "tool"];
+
+/// Targets of the SDK's own events, which are recorded by the middleware.
+const SDK_TARGETS: [&str; 2] = ["pmcp", "mcp."];
+
+/// A `tracing_subscriber` layer forwarding handler events to an [`ObservabilityBackend`].
+///
+/// By default events and spans up to `INFO` are forwarded; see
+/// [`with_max_level`](Self::with_max_level) and [`without_spans`](Self::without_spans).
+/// Backend calls run on the current Tokio runtime and are dropped when
+/// there is none.
+pub struct ObservabilityLayer {
+    server_name: String,
+    backend: Arc<dyn ObservabilityBackend>,
+    max_level: Level,
+    spans: bool,
+}
+
+impl fmt::Debug for ObservabilityLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservabilityLayer")
+            .field("server_name", &self.server_name)
+            .field("backend", &self.backend.name())
+            .field("max_level", &self.max_level)
+            .field("spans", &self.spans)
+            .finish()
+    }
+}
+
+impl ObservabilityLayer {
+    /// Create a layer forwarding to `backend` on behalf of `server_name`.
+    pub fn new(server_name: impl Into<String>, backend: Arc<dyn ObservabilityBackend>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            backend,
+            max_level: Level::INFO,
+            spans: true,
+        }
+    }
+
+    /// Forward events and spans up to `level` (default `INFO`).
+    pub fn with_max_level(mut self, level: Level) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// Forward events only, not closed spans.
+    pub fn without_spans(mut self) -> Self {
+        self.spans = false;
+        self
+    }
+
+    fn forwards(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.max_level
+            && !SDK_TARGETS
+                .iter()
+                .any(|prefix| metadata.target().starts_with(prefix))
+    }
+
+    /// Build an event correlated through `scope`, innermost span first.
+    fn correlate<'a, S>(
+        &self,
+        metadata: &Metadata<'_>,
+        message: String,
+        mut fields: Map<String, Value>,
+        scope: impl Iterator<Item = SpanRef<'a, S>>,
+    ) -> Option<McpLogEvent>
+    where
+        S: Subscriber + for<'l> LookupSpan<'l>,
+    {
+        let mut trace = None;
+        let mut tool = None;
+        for span in scope {
+            let extensions = span.extensions();
+            let Some(state) = extensions.get::<SpanState>() else {
+                continue;
+            };
+            for (key, value) in &state.fields {
+                if !TRACE_FIELDS.contains(&key.as_str()) && !fields.contains_key(key) {
+                    fields.insert(key.clone(), value.clone());
+                }
+            }
+            if tool.is_none() {
+                tool = state.str_field("tool").map(str::to_string);
+            }
+            if trace.is_none() {
+                trace = state.trace();
+            }
+        }
+
+        let mut event = McpLogEvent::new(
+            trace?,
+            &self.server_name,
+            metadata.level().as_str(),
+            message,
+        )
+        .with_target(metadata.target());
+        event.tool_name = tool;
+        event.fields = fields.into_iter().collect();
+        Some(event)
+    }
+
+    fn dispatch(&self, event: McpLogEvent) {
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let backend = self.backend.clone();
+            runtime.spawn(async move { backend.record_log(&event).await });
+        }
+    }
+}
+
+impl<S> Layer<S> for ObservabilityLayer
+where
+    S: Subscriber + for<'l> LookupSpan<'l>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut state = SpanState {
+            fields: Map::new(),
+            opened: Instant::now(),
+        };
+        attrs.record(&mut FieldVisitor(&mut state.fields));
+        span.extensions_mut().insert(state);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(state) = extensions.get_mut::<SpanState>() {
+            values.record(&mut FieldVisitor(&mut state.fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !self.forwards(metadata) {
+            return;
+        }
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        let mut fields = Map::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        let message = match fields.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        if let Some(event) = self.correlate(metadata, message, fields, scope) {
+            self.dispatch(event);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if !self.spans {
+            return;
+        }
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let metadata = span.metadata();
+        if !self.forwards(metadata) {
+            return;
+        }
+        let opened = {
+            let extensions = span.extensions();
+            match extensions.get::<SpanState>() {
+                // The span of the call itself is recorded by the middleware
+                Some(state) if state.trace().is_none() => state.opened,
+                _ => return,
+            }
+        };
+        let Some(mut event) = self.correlate(
+            metadata,
+            metadata.name().to_string(),
+            Map::new(),
+            span.scope(),
+        ) else {
+            return;
+        };
+        event.duration_ms = Some(u64::try_from(opened.elapsed().as_millis()).unwrap_or(u64::MAX));
+        self.dispatch(event);
+    }
+}
+
+/// Event formatter that prefixes lines emitted inside a traced handler
+/// with the call's `trace_id` and `span_id`.
+///
+/// The IDs come from [`TraceContext::current`], the same context exposed to
+/// handlers by
+/// [`RequestHandlerExtra::trace_context`](crate::server::cancellation::RequestHandlerExtra::trace_context).
+/// Lines outside a traced call are formatted by `inner` unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct CorrelatedFormat<F = Format> {
+    inner: F,
+}
+
+impl<F> CorrelatedFormat<F> {
+    /// Wrap the `inner` event formatter.
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S, N, F> FormatEvent<S, N> for CorrelatedFormat<F>
+where
+    S: Subscriber + for<'l> LookupSpan<'l>,
+    N: for<'w> FormatFields<'w> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        if let Some(trace) = TraceContext::current() {
+            write!(
+                writer,
+                "trace_id={} span_id={} ",
+                trace.trace_id, trace.span_id
+            )?;
+        }
+        self.inner.format_event(ctx, writer, event)
+    }
+}
+
+/// A `tracing_subscriber` `fmt` layer whose lines carry the trace context of
+/// the handler that emitted them. See [`CorrelatedFormat`].
+pub fn correlated_fmt_layer<S>(
+) -> tracing_subscriber::fmt::Layer<S, DefaultFields, CorrelatedFormat>
+where
+    S: Subscriber + for<'l> LookupSpan<'l>,
+{
+    tracing_subscriber::fmt::layer().event_format(CorrelatedFormat::default())
+}
+
+/// Fields and start time of a span, kept in its extensions.
+struct SpanState {
+    fields: Map<String, Value>,
+    opened: Instant,
+}
+
+impl SpanState {
+    fn str_field(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).and_then(Value::as_str)
+    }
+
+    /// The trace context this span carries, if it has `trace_id` and `span_id` fields.
+    fn trace(&self) -> Option<TraceContext> {
+        Some(TraceContext {
+            trace_id: self.str_field("trace_id")?.to_string(),
+            span_id: self.str_field("span_id")?.to_string(),
+            parent_span_id: self.str_field("parent_span_id").map(str::to_string),
+            depth: self
+                .fields
+                .get("depth")
+                .and_then(Value::as_u64)
+                .and_then(|depth| u32::try_from(depth).ok())
+                .unwrap_or(0),
+            tracestate: None,
+        })
+    }
+}
+
+/// Records `tracing` fields as JSON values.
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::cancellation::RequestHandlerExtra;
+    use crate::server::observability::{
+        McpMetric, McpObservabilityMiddleware, McpRequestEvent, McpResponseEvent,
+        ObservabilityConfig,
+    };
+    use crate::server::tool_middleware::{ToolContext, ToolMiddlewareChain};
+    use crate::server::ToolHandler;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use tokio_util::sync::CancellationToken;
+    use tracing_subscriber::prelude::*;
+
+    // Events in these tests name a target, since `pmcp::*` events are not forwarded.
+    #[derive(Default)]
+    struct RecordingBackend {
+        logs: Mutex<Vec<McpLogEvent>>,
+    }
+
+    #[async_trait]
+    impl ObservabilityBackend for RecordingBackend {
+        async fn record_request(&self, _event: &McpRequestEvent) {}
+        async fn record_response(&self, _event: &McpResponseEvent) {}
+        async fn emit_metric(&self, _metric: &McpMetric) {}
+        async fn record_log(&self, event: &McpLogEvent) {
+            self.logs.lock().push(event.clone());
+        }
+        async fn flush(&self) {}
+
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+    }
+
+    /// Run `f` under a subscriber with the layer and return what reached the backend.
+    async fn forwarded(
+        layer: impl FnOnce(ObservabilityLayer) -> ObservabilityLayer,
+        f: impl FnOnce(),
+    ) -> Vec<McpLogEvent> {
+        let backend = Arc::new(RecordingBackend::default());
+        let layer = layer(ObservabilityLayer::new("test-server", backend.clone()));
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), f);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        let logs = backend.logs.lock().clone();
+        logs
+    }
+
+    fn tool_call(trace: &TraceContext) -> tracing::Span {
+        tracing::info_span!(
+            "mcp.tool",
+            tool = "get_weather",
+            trace_id = %trace.trace_id,
+            span_id = %trace.span_id,
+            parent_span_id = trace.parent_span_id.as_deref(),
+            depth = trace.depth,
+        )
+    }
+
+    #[tokio::test]
+    async fn forwards_events_with_the_trace_of_the_tool_call() {
+        let trace = TraceContext::new_root().child();
+        let logs = forwarded(
+            |layer| layer,
+            || {
+                tool_call(&trace).in_scope(|| {
+                    tracing::info!(target: "weather", city = "Paris", cached = false, "fetching forecast");
+                });
+            },
+        )
+        .await;
+
+        assert_eq!(logs.len(), 1);
+        let log = &logs[0];
+        assert_eq!(log.trace, trace);
+        assert_eq!(log.server_name, "test-server");
+        assert_eq!(log.tool_name.as_deref(), Some("get_weather"));
+        assert_eq!(log.level, "INFO");
+        assert_eq!(log.message, "fetching forecast");
+        assert_eq!(log.fields["city"], "Paris");
+        assert_eq!(log.fields["cached"], false);
+        assert!(!log.fields.contains_key("trace_id"));
+    }
+
+    #[tokio::test]
+    async fn ignores_uncorrelated_filtered_and_sdk_events() {
+        let trace = TraceContext::new_root();
+        let logs = forwarded(
+            |layer| layer,
+            || {
+                tracing::info!(target: "weather", "outside any tool call");
+                tool_call(&trace).in_scope(|| {
+                    tracing::debug!(target: "weather", "too verbose");
+                    tracing::info!(target: "pmcp::server", "recorded by the middleware");
+                });
+            },
+        )
+        .await;
+        assert!(logs.is_empty(), "{logs:?}");
+
+        let logs = forwarded(
+            |layer| layer.with_max_level(Level::DEBUG),
+            || tool_call(&trace).in_scope(|| tracing::debug!(target: "weather", "verbose")),
+        )
+        .await;
+        assert_eq!(logs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn forwards_spans_closed_inside_the_tool_call() {
+        let trace = TraceContext::new_root();
+        let logs = forwarded(
+            |layer| layer,
+            || {
+                tool_call(&trace).in_scope(|| {
+                    tracing::info_span!(target: "weather", "query", table = "forecasts").in_scope(
+                        || {
+                            tracing::info!(target: "weather", "querying");
+                        },
+                    );
+                });
+            },
+        )
+        .await;
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].fields["table"], "forecasts");
+        assert_eq!(logs[1].message, "query");
+        assert!(logs[1].duration_ms.is_some());
+        assert_eq!(logs[1].trace.trace_id, trace.trace_id);
+
+        let logs = forwarded(ObservabilityLayer::without_spans, || {
+            tool_call(&trace)
+                .in_scope(|| tracing::info_span!(target: "weather", "query").in_scope(|| {}));
+        })
+        .await;
+        assert!(logs.is_empty(), "{logs:?}");
+    }
+
+    struct Forecast;
+
+    #[async_trait]
+    impl ToolHandler for Forecast {
+        async fn handle(&self, _args: Value, _extra: RequestHandlerExtra) -> crate::Result<Value> {
+            tracing::info!(target: "weather", "inside the handler");
+            Ok(Value::Null)
+        }
+    }
+
+    #[tokio::test]
+    async fn correlates_handler_events_through_the_middleware() {
+        let backend = Arc::new(RecordingBackend::default());
+        let middleware = McpObservabilityMiddleware::new(
+            "test-server",
+            ObservabilityConfig::development(),
+            backend.clone(),
+        );
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(middleware.tracing_layer()),
+        );
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(middleware));
+
+        let extra = RequestHandlerExtra::new("req-1".to_string(), CancellationToken::new());
+        let context = ToolContext::new("forecast", "req-1");
+        chain
+            .execute("forecast", Value::Null, extra, &context, &Forecast)
+            .await
+            .unwrap();
+        tokio::task::yield_now().await;
+
+        let logs = backend.logs.lock().clone();
+        assert_eq!(logs.len(), 1, "{logs:?}");
+        assert_eq!(logs[0].message, "inside the handler");
+        assert_eq!(logs[0].tool_name.as_deref(), Some("forecast"));
+    }
+
+    /// Log output captured in memory.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    struct TracedLookup {
+        seen: Mutex<Option<TraceContext>>,
+    }
+
+    #[async_trait]
+    impl ToolHandler for TracedLookup {
+        async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> crate::Result<Value> {
+            *self.seen.lock() = extra.trace_context();
+            tracing::info!(target: "orders", "looking up order");
+            Ok(Value::Null)
+        }
+    }
+
+    #[tokio::test]
+    async fn correlated_format_prefixes_handler_lines_with_trace_ids() {
+        let output = Captured::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(
+                correlated_fmt_layer()
+                    .with_ansi(false)
+                    .with_writer(output.clone()),
+            ),
+        );
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(McpObservabilityMiddleware::new(
+            "test-server",
+            ObservabilityConfig::development(),
+            Arc::new(RecordingBackend::default()),
+        )));
+        let handler = TracedLookup {
+            seen: Mutex::new(None),
+        };
+
+        tracing::info!(target: "orders", "outside any call");
+        let extra = RequestHandlerExtra::new("req-1".to_string(), CancellationToken::new());
+        let context = ToolContext::new("lookup", "req-1");
+        chain
+            .execute("lookup", Value::Null, extra, &context, &handler)
+            .await
+            .unwrap();
+
+        let trace = handler.seen.lock().clone().expect("trace context exposed");
+        let output = String::from_utf8(output.0.lock().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 2, "{output}");
+        assert!(!lines[0].contains("trace_id="), "{output}");
+        assert!(
+            lines[1].starts_with(&format!(
+                "trace_id={} span_id={} ",
+                trace.trace_id, trace.span_id
+            )),
+            "{output}"
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! - Duration metrics
 //! - Error tracking
 //! - Distributed trace context propagation
+//! - Structured error reports routed to error sinks
 //!
 //! # Design Principles
 //!
@@ -35,7 +36,9 @@
 
 use super::backend::ObservabilityBackend;
 use super::config::ObservabilityConfig;
+use super::errors::{self, ErrorContext, ErrorReport, ErrorSink};
 use super::events::{McpMetric, McpRequestEvent, McpResponseEvent};
+use super::sampling::{HeldCall, TailBuffer};
 use super::types::{McpOperationDetails, RequestMetadata, TraceContext};
 use crate::error::{Error, Result};
 use crate::server::cancellation::RequestHandlerExtra;
@@ -43,7 +46,7 @@ use crate::server::tool_middleware::{ToolContext, ToolMiddleware};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Key used to store the request start time in metadata.
 const REQUEST_START_KEY: &str = "_observability_start_ns";
@@ -51,6 +54,39 @@ const REQUEST_START_KEY: &str = "_observability_start_ns";
 /// Key used to store the trace context in metadata.
 const TRACE_CONTEXT_KEY: &str = "_observability_trace";
 
+/// The span a tool handler runs in, carrying the call's trace context.
+///
+/// Returns a disabled span unless the observability middleware stored a
+/// trace context for this call. [`ObservabilityLayer`](super::ObservabilityLayer)
+/// correlates events emitted inside the span with that context.
+pub(crate) fn tool_span(tool_name: &str, extra: &RequestHandlerExtra) -> tracing::Span {
+    let Some(trace) = tool_trace(extra) else {
+        return tracing::Span::none();
+    };
+    tracing::info_span!(
+        "mcp.tool",
+        tool = tool_name,
+        trace_id = %trace.trace_id,
+        span_id = %trace.span_id,
+        parent_span_id = trace.parent_span_id.as_deref(),
+        depth = trace.depth,
+    )
+}
+
+/// The trace context the observability middleware stored for a tool call.
+pub(crate) fn tool_trace(extra: &RequestHandlerExtra) -> Option<TraceContext> {
+    extra
+        .get_metadata(TRACE_CONTEXT_KEY)
+        .and_then(|json| serde_json::from_str(json).ok())
+}
+
+/// Key of a call in the tail buffer: its unique call ID when it runs
+/// through [`ToolMiddlewareChain::execute`](crate::server::tool_middleware::ToolMiddlewareChain::execute),
+/// else its request ID.
+fn call_key(context: &ToolContext) -> String {
+    errors::call_id().map_or_else(|| context.request_id.clone(), |id| format!("call-{id}"))
+}
+
 /// Observability middleware for MCP tool execution.
 ///
 /// This middleware hooks into the tool execution lifecycle to:
@@ -58,11 +94,14 @@ const TRACE_CONTEXT_KEY: &str = "_observability_trace";
 /// 2. Calculate duration and record response events after execution
 /// 3. Emit metrics for monitoring and alerting
 /// 4. Track errors with full context
+/// 5. Send an [`ErrorReport`] to each configured [`ErrorSink`] when a tool fails
 ///
 /// # Thread Safety
 ///
 /// The middleware is `Send + Sync` and can be safely shared across async tasks.
-/// All state is either immutable or stored in `RequestHandlerExtra.metadata`.
+/// Per-request state is stored in `RequestHandlerExtra.metadata`, except the
+/// error context of a call, which lives in the call's task for as long as
+/// the call runs.
 pub struct McpObservabilityMiddleware {
     /// Server name for event attribution.
     server_name: String,
@@ -72,6 +111,12 @@ pub struct McpObservabilityMiddleware {
 
     /// Backend for emitting events and metrics.
     backend: Arc<dyn ObservabilityBackend>,
+
+    /// Sinks receiving error reports for failed calls.
+    error_sinks: Vec<Arc<dyn ErrorSink>>,
+
+    /// Unsampled calls held for the tail sampling rules.
+    tail: TailBuffer,
 }
 
 impl McpObservabilityMiddleware {
@@ -82,18 +127,64 @@ impl McpObservabilityMiddleware {
     /// * `server_name` - Name of the MCP server (used in events)
     /// * `config` - Observability configuration
     /// * `backend` - Backend for event/metric emission
+    ///
+    /// Error sinks listed in `config.errors` are created here.
     pub fn new(
         server_name: impl Into<String>,
         config: ObservabilityConfig,
         backend: Arc<dyn ObservabilityBackend>,
     ) -> Self {
+        let error_sinks = errors::build_error_sinks(&config.errors);
+        let tail = TailBuffer::new(
+            config.sampling.tail_buffer_size,
+            Duration::from_millis(config.sampling.tail_max_age_ms),
+        );
         Self {
             server_name: server_name.into(),
             config,
             backend,
+            error_sinks,
+            tail,
         }
     }
 
+    /// Add an error sink in addition to those from the configuration.
+    ///
+    /// Sinks added here receive reports even if `config.errors.enabled` is false.
+    pub fn with_error_sink(mut self, sink: Arc<dyn ErrorSink>) -> Self {
+        self.error_sinks.push(sink);
+        self
+    }
+
+    /// A `tracing` layer forwarding handler events to this middleware's backend.
+    ///
+    /// See [`ObservabilityLayer`](super::ObservabilityLayer).
+    #[cfg(feature = "logging")]
+    pub fn tracing_layer(&self) -> super::ObservabilityLayer {
+        super::ObservabilityLayer::new(&self.server_name, self.backend.clone())
+    }
+
+    /// Capture the context attached to error reports for this call.
+    fn capture_error_context(
+        &self,
+        tool_name: &str,
+        args: &Value,
+        extra: &RequestHandlerExtra,
+        trace: Option<TraceContext>,
+    ) {
+        if self.error_sinks.is_empty() {
+            return;
+        }
+
+        let error_context = ErrorContext {
+            trace,
+            arguments_hash: Some(super::types::hash_value(args)),
+            user_id: self.extract_user_id(extra),
+            tenant_id: Self::extract_tenant_id(extra),
+        };
+        errors::enter_call(tool_name, error_context);
+    }
+
     /// Create middleware with development configuration.
     ///
     /// Uses console output with pretty formatting.
@@ -183,23 +274,40 @@ impl McpObservabilityMiddleware {
         details
     }
 
+    /// Record a call released from the tail buffer: its buffered request
+    /// event followed by the response event built by `response`.
+    async fn record_held_call(
+        &self,
+        call: HeldCall,
+        response: impl FnOnce(&McpRequestEvent) -> McpResponseEvent,
+    ) {
+        let mut event = response(&call.request).with_metadata(call.request.metadata.clone());
+        if let Some(user_id) = &call.request.user_id {
+            event = event.with_user_id(user_id);
+        }
+        if let Some(tenant_id) = &call.request.tenant_id {
+            event = event.with_tenant_id(tenant_id);
+        }
+        self.backend.record_request(&call.request).await;
+        self.backend.record_response(&event).await;
+    }
+
     /// Create and store trace context.
     fn create_trace_context(&self, extra: &RequestHandlerExtra) -> TraceContext {
-        // Check if we have an incoming trace context (from composed calls)
-        if let Some(trace_json) = extra.get_metadata(TRACE_CONTEXT_KEY) {
-            if let Ok(parent_trace) = serde_json::from_str::<TraceContext>(trace_json) {
-                // Check depth limit
-                if parent_trace.depth >= self.config.max_depth {
-                    tracing::warn!(
-                        trace_id = %parent_trace.trace_id,
-                        depth = parent_trace.depth,
-                        max_depth = self.config.max_depth,
-                        "Composition depth limit reached, creating new root trace"
-                    );
-                    return TraceContext::new_root();
-                }
-                return parent_trace.child();
+        // Check if we have an incoming trace context (from composed calls or
+        // a `traceparent` header on the transport)
+        if let Some(parent_trace) = extra.trace_context() {
+            // Check depth limit
+            if parent_trace.depth >= self.config.max_depth {
+                tracing::warn!(
+                    trace_id = %parent_trace.trace_id,
+                    depth = parent_trace.depth,
+                    max_depth = self.config.max_depth,
+                    "Composition depth limit reached, creating new root trace"
+                );
+                return TraceContext::new_root();
             }
+            return parent_trace.child();
         }
 
         // Create new root trace
@@ -297,8 +405,17 @@ impl ToolMiddleware for McpObservabilityMiddleware {
         extra: &mut RequestHandlerExtra,
         context: &ToolContext,
     ) -> Result<()> {
-        // Check if observability is enabled and should sample this request
-        if !self.config.enabled || !self.config.should_sample() {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        // Calls the head decision drops are held for the tail rules while
+        // there is room; errors are always reported to error sinks.
+        let sampled = self.config.should_sample_tool(tool_name);
+        let tracked = sampled || (self.config.sampling.has_tail_rules() && self.tail.has_room());
+        if !tracked {
+            let trace = (!self.error_sinks.is_empty()).then(|| self.create_trace_context(extra));
+            self.capture_error_context(tool_name, args, extra, trace);
             return Ok(());
         }
 
@@ -314,6 +431,8 @@ impl ToolMiddleware for McpObservabilityMiddleware {
             extra.set_metadata(TRACE_CONTEXT_KEY.to_string(), trace_json);
         }
 
+        self.capture_error_context(tool_name, args, extra, Some(trace.clone()));
+
         // Build operation details
         let operation = self.build_operation_details(tool_name, args);
 
@@ -332,8 +451,12 @@ impl ToolMiddleware for McpObservabilityMiddleware {
             event = event.with_tenant_id(tenant_id);
         }
 
-        // Emit request event (non-blocking)
-        self.backend.record_request(&event).await;
+        if sampled {
+            // Emit request event (non-blocking)
+            self.backend.record_request(&event).await;
+        } else {
+            self.tail.hold(call_key(context), event);
+        }
 
         Ok(())
     }
@@ -342,7 +465,7 @@ impl ToolMiddleware for McpObservabilityMiddleware {
         &self,
         tool_name: &str,
         result: &mut Result<Value>,
-        _context: &ToolContext,
+        context: &ToolContext,
     ) -> Result<()> {
         // Note: We need the extra to access timing and trace info
         // Since on_response doesn't have extra, we rely on context metadata
@@ -357,6 +480,24 @@ impl ToolMiddleware for McpObservabilityMiddleware {
 
         let success = result.is_ok();
 
+        // Record held calls that turned out slow (failures are left to `on_error`)
+        if success {
+            if let Some(call) = self.tail.release(&call_key(context)) {
+                let duration_ms = call.elapsed_ms();
+                if self.config.sampling.retains(false, duration_ms) {
+                    self.record_held_call(call, |request| {
+                        McpResponseEvent::success(
+                            request.trace.clone(),
+                            &self.server_name,
+                            request.operation.clone(),
+                            duration_ms,
+                        )
+                    })
+                    .await;
+                }
+            }
+        }
+
         // Emit basic metrics
         if self.config.metrics.tool_usage {
             let prefix = &self.config.metrics.prefix;
@@ -371,13 +512,18 @@ impl ToolMiddleware for McpObservabilityMiddleware {
         Ok(())
     }
 
-    async fn on_error(&self, tool_name: &str, error: &Error, _context: &ToolContext) -> Result<()> {
+    async fn on_error(&self, tool_name: &str, error: &Error, context: &ToolContext) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
-        // Create trace context for error event
-        let trace = TraceContext::new_root();
+        let error_context = errors::take_call();
+
+        // Reuse the request's trace context when it was captured
+        let trace = error_context
+            .as_ref()
+            .and_then(|c| c.trace.clone())
+            .unwrap_or_else(TraceContext::new_root);
         let operation = McpOperationDetails::tool_call(tool_name);
 
         // Get error details (convert ErrorCode to i32)
@@ -386,18 +532,39 @@ impl ToolMiddleware for McpObservabilityMiddleware {
             _ => (crate::ErrorCode::INTERNAL_ERROR.as_i32(), error.to_string()),
         };
 
-        // Create failure response event
-        let event = McpResponseEvent::failure(
-            trace,
-            &self.server_name,
-            operation.clone(),
-            0, // Duration unknown in error handler
-            error_code,
-            error_message,
-        );
-
-        // Record the error event
-        self.backend.record_response(&event).await;
+        // Held calls are recorded only if the tail rules retain failures
+        match self.tail.release(&call_key(context)) {
+            Some(call) => {
+                let duration_ms = call.elapsed_ms();
+                if self.config.sampling.retains(true, duration_ms) {
+                    self.record_held_call(call, |request| {
+                        McpResponseEvent::failure(
+                            request.trace.clone(),
+                            &self.server_name,
+                            request.operation.clone(),
+                            duration_ms,
+                            error_code,
+                            error_message.clone(),
+                        )
+                    })
+                    .await;
+                }
+            },
+            None => {
+                // Create failure response event
+                let event = McpResponseEvent::failure(
+                    trace,
+                    &self.server_name,
+                    operation.clone(),
+                    0, // Duration unknown in error handler
+                    error_code,
+                    error_message.clone(),
+                );
+
+                // Record the error event
+                self.backend.record_response(&event).await;
+            },
+        }
 
         // Emit error metrics
         if self.config.metrics.error_rate {
@@ -420,6 +587,12 @@ impl ToolMiddleware for McpObservabilityMiddleware {
             );
         }
 
+        if !self.error_sinks.is_empty() {
+            let report = ErrorReport::new(&self.server_name, tool_name, error_code, error_message)
+                .with_context(error_context.unwrap_or_default());
+            errors::spawn_dispatch(&self.error_sinks, report);
+        }
+
         Ok(())
     }
 
@@ -439,6 +612,14 @@ impl std::fmt::Debug for McpObservabilityMiddleware {
             .field("server_name", &self.server_name)
             .field("enabled", &self.config.enabled)
             .field("backend", &self.backend.name())
+            .field(
+                "error_sinks",
+                &self
+                    .error_sinks
+                    .iter()
+                    .map(|s| s.name())
+                    .collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -447,6 +628,9 @@ impl std::fmt::Debug for McpObservabilityMiddleware {
 mod tests {
     use super::*;
     use crate::server::auth::AuthContext;
+    use crate::server::observability::SamplingConfig;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use tokio_util::sync::CancellationToken;
 
@@ -615,6 +799,289 @@ mod tests {
         assert!(backend.metrics.load(Ordering::SeqCst) > 0);
     }
 
+    /// Test sink that collects reports.
+    #[derive(Default)]
+    struct CollectingSink {
+        reports: Mutex<Vec<ErrorReport>>,
+    }
+
+    #[async_trait]
+    impl ErrorSink for CollectingSink {
+        async fn report(&self, report: &ErrorReport) {
+            self.reports.lock().push(report.clone());
+        }
+
+        fn name(&self) -> &'static str {
+            "collecting"
+        }
+    }
+
+    impl CollectingSink {
+        /// The reports delivered so far, after giving background delivery
+        /// a moment.
+        async fn delivered(&self, expected: usize) -> Vec<ErrorReport> {
+            for _ in 0..100 {
+                if self.reports.lock().len() >= expected {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            self.reports.lock().clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_reports_errors_with_request_context() {
+        let backend = Arc::new(CountingBackend::new());
+        let sink = Arc::new(CollectingSink::default());
+        let middleware = McpObservabilityMiddleware::new(
+            "test-server",
+            ObservabilityConfig::development(),
+            backend,
+        )
+        .with_error_sink(sink.clone());
+
+        let mut args = serde_json::json!({"id": 7});
+        let mut extra = RequestHandlerExtra::new("req-123".to_string(), CancellationToken::new())
+            .with_auth_context(Some(AuthContext::new("user-456")));
+        let context = ToolContext::new("test_tool", "req-123");
+
+        let trace = errors::scope_call(async {
+            middleware
+                .on_request("test_tool", &mut args, &mut extra, &context)
+                .await
+                .unwrap();
+
+            let error = Error::protocol(crate::ErrorCode::INVALID_PARAMS, "order 7 not found");
+            let mut result = Err(Error::protocol(
+                crate::ErrorCode::INVALID_PARAMS,
+                "order 7 not found",
+            ));
+            middleware
+                .on_response("test_tool", &mut result, &context)
+                .await
+                .unwrap();
+            middleware
+                .on_error("test_tool", &error, &context)
+                .await
+                .unwrap();
+            serde_json::from_str::<TraceContext>(extra.get_metadata(TRACE_CONTEXT_KEY).unwrap())
+                .unwrap()
+        })
+        .await;
+
+        let reports = sink.delivered(1).await;
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.tool_name, "test_tool");
+        assert_eq!(report.error_code, crate::ErrorCode::INVALID_PARAMS.as_i32());
+        assert_eq!(report.trace.as_ref().unwrap().trace_id, trace.trace_id);
+        assert_eq!(
+            report.arguments_hash.as_deref(),
+            Some(super::super::types::hash_value(&args).as_str())
+        );
+        assert_eq!(report.user_id.as_deref(), Some("user-456"));
+        assert_eq!(
+            report.fingerprint,
+            errors::fingerprint("test_tool", report.error_code, "order 8 not found", None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_reports_nothing_on_success() {
+        let backend = Arc::new(CountingBackend::new());
+        let sink = Arc::new(CollectingSink::default());
+        let middleware = McpObservabilityMiddleware::new(
+            "test-server",
+            ObservabilityConfig::development(),
+            backend,
+        )
+        .with_error_sink(sink.clone());
+
+        run_call(&middleware, "test_tool", "req-123", None).await;
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(sink.reports.lock().is_empty());
+    }
+
+    /// Run one call through the middleware, failing it if `error` is set.
+    async fn run_call(
+        middleware: &McpObservabilityMiddleware,
+        tool_name: &str,
+        request_id: &str,
+        error: Option<Error>,
+    ) {
+        let mut args = serde_json::json!({});
+        let mut extra = RequestHandlerExtra::new(request_id.to_string(), CancellationToken::new());
+        let context = ToolContext::new(tool_name, request_id);
+
+        errors::scope_call(async {
+            middleware
+                .on_request(tool_name, &mut args, &mut extra, &context)
+                .await
+                .unwrap();
+            // Let concurrent calls interleave
+            tokio::task::yield_now().await;
+            let mut result = match &error {
+                Some(error) => Err(Error::internal(error.to_string())),
+                None => Ok(serde_json::json!({"ok": true})),
+            };
+            middleware
+                .on_response(tool_name, &mut result, &context)
+                .await
+                .unwrap();
+            if let Some(error) = &error {
+                middleware
+                    .on_error(tool_name, error, &context)
+                    .await
+                    .unwrap();
+            }
+        })
+        .await;
+    }
+
+    fn unsampled_config(sampling: SamplingConfig) -> ObservabilityConfig {
+        ObservabilityConfig {
+            sample_rate: 0.0,
+            sampling,
+            ..ObservabilityConfig::development()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampling_keeps_failed_calls() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig::default());
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        run_call(&middleware, "test_tool", "req-ok", None).await;
+        assert_eq!(backend.requests.load(Ordering::SeqCst), 0);
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 0);
+
+        run_call(
+            &middleware,
+            "test_tool",
+            "req-err",
+            Some(Error::internal("boom")),
+        )
+        .await;
+        assert_eq!(backend.requests.load(Ordering::SeqCst), 1);
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 1);
+        assert!(middleware.tail.has_room());
+        assert!(middleware.tail.release("req-ok").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampling_can_drop_failed_calls() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig {
+            always_sample_errors: false,
+            slow_threshold_ms: Some(60_000),
+            ..Default::default()
+        });
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        run_call(
+            &middleware,
+            "test_tool",
+            "req-err",
+            Some(Error::internal("boom")),
+        )
+        .await;
+
+        assert_eq!(backend.requests.load(Ordering::SeqCst), 0);
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampling_keeps_slow_calls() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig {
+            slow_threshold_ms: Some(0),
+            ..Default::default()
+        });
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        run_call(&middleware, "test_tool", "req-1", None).await;
+
+        assert_eq!(backend.requests.load(Ordering::SeqCst), 1);
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampling_keeps_concurrent_calls_apart() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig {
+            slow_threshold_ms: Some(0),
+            ..Default::default()
+        });
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        // Both calls share a request ID, as tools/call through ServerCore do
+        tokio::join!(
+            run_call(&middleware, "test_tool", "tool_test_tool", None),
+            run_call(&middleware, "test_tool", "tool_test_tool", None),
+        );
+
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampling_evicts_dropped_calls() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig {
+            tail_buffer_size: 1,
+            tail_max_age_ms: 10,
+            ..Default::default()
+        });
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        // A call the client abandons never reaches on_response or on_error
+        let abandoned = errors::scope_call(async {
+            let mut args = serde_json::json!({});
+            let mut extra = RequestHandlerExtra::new("req-1".to_string(), CancellationToken::new());
+            middleware
+                .on_request(
+                    "test_tool",
+                    &mut args,
+                    &mut extra,
+                    &ToolContext::new("test_tool", "req-1"),
+                )
+                .await
+                .unwrap();
+            std::future::pending::<()>().await;
+        });
+        assert!(tokio::time::timeout(Duration::from_millis(5), abandoned)
+            .await
+            .is_err());
+        assert!(!middleware.tail.has_room());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        run_call(
+            &middleware,
+            "test_tool",
+            "req-2",
+            Some(Error::internal("boom")),
+        )
+        .await;
+        assert_eq!(backend.responses.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_per_tool_sample_rates() {
+        let backend = Arc::new(CountingBackend::new());
+        let config = unsampled_config(SamplingConfig {
+            tool_rates: HashMap::from([("important".to_string(), 1.0)]),
+            ..Default::default()
+        });
+        let middleware = McpObservabilityMiddleware::new("test-server", config, backend.clone());
+
+        run_call(&middleware, "important", "req-1", None).await;
+        run_call(&middleware, "other", "req-2", None).await;
+
+        assert_eq!(backend.requests.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_middleware_priority() {
         let backend = Arc::new(CountingBackend::new());
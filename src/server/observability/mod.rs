@@ -4,7 +4,7 @@
 //! - **Distributed Tracing**: Trace context propagation across composed servers
 //! - **Event Logging**: Structured request/response events
 //! - **Metrics**: Duration, count, and error rate metrics
-//! - **Multi-Backend Support**: Console, CloudWatch, or custom backends
+//! - **Multi-Backend Support**: Console, CloudWatch, Datadog, HTTP/Honeycomb, or custom backends
 //!
 //! # Design Principles
 //!
@@ -50,7 +50,7 @@
 //! ```toml
 //! [observability]
 //! enabled = true
-//! backend = "console"  # or "cloudwatch"
+//! backend = "console"  # or "cloudwatch", "datadog", "honeycomb"
 //! sample_rate = 1.0
 //!
 //! [observability.fields]
@@ -88,6 +88,33 @@
 //! let backend = CloudWatchBackend::new(config);
 //! ```
 //!
+//! ## Datadog Backend
+//!
+//! Sends `DogStatsD` metrics to the local Datadog Agent:
+//!
+//! ```toml
+//! [observability]
+//! backend = "datadog"
+//!
+//! [observability.datadog]
+//! agent_addr = "127.0.0.1:8125"
+//! tags = ["env:prod"]
+//! ```
+//!
+//! ## HTTP Backend
+//!
+//! With the `http-client` feature, `backend = "http"` (or `"honeycomb"`)
+//! POSTs batched JSON events to any endpoint accepting Honeycomb's batch
+//! format. The API key can come from `PMCP_OBSERVABILITY_HTTP_API_KEY`:
+//!
+//! ```toml
+//! [observability]
+//! backend = "honeycomb"
+//!
+//! [observability.http]
+//! endpoint = "https://api.honeycomb.io/1/batch/mcp-servers"
+//! ```
+//!
 //! ## Composite Backend
 //!
 //! Combine multiple backends:
@@ -117,25 +144,116 @@
 //! assert_eq!(root.trace_id, child.trace_id);
 //! assert_eq!(child.depth, 1);
 //! ```
+//!
+//! # Sampling
+//!
+//! `sample_rate` is the head decision, made when a call starts, and can be
+//! overridden per tool. Calls it drops are held in a bounded tail buffer and
+//! still recorded if they fail or run longer than `slow_threshold_ms`:
+//!
+//! ```toml
+//! [observability]
+//! sample_rate = 0.05
+//!
+//! [observability.sampling]
+//! always_sample_errors = true
+//! slow_threshold_ms = 1000
+//!
+//! [observability.sampling.tool_rates]
+//! health_check = 0.0
+//! ```
+//!
+//! # Prometheus
+//!
+//! [`PrometheusMetrics`] aggregates the [`StandardMetrics`] into Prometheus
+//! counters and histograms. Set it on the streamable HTTP server to expose
+//! them on `GET /metrics`:
+//!
+//! ```rust,ignore
+//! let config = StreamableHttpServerConfig::default().with_metrics(PrometheusMetrics::new());
+//! ```
+//!
+//! # Handler Logs
+//!
+//! With the `logging` feature, [`ObservabilityLayer`] forwards `tracing`
+//! events emitted inside tool handlers to the backend as [`McpLogEvent`]s,
+//! carrying the trace context of the call:
+//!
+//! ```rust,ignore
+//! use tracing_subscriber::prelude::*;
+//!
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(middleware.tracing_layer())
+//!     .init();
+//! ```
+//!
+//! Handlers can read the call's IDs with
+//! [`RequestHandlerExtra::trace_context`](crate::server::cancellation::RequestHandlerExtra::trace_context),
+//! and [`correlated_fmt_layer`] prefixes the server's own log lines with
+//! them.
+//!
+//! # Error Reports
+//!
+//! With `[observability.errors]` enabled, every failed tool call produces an
+//! [`ErrorReport`] with the tool name, trace context, argument hash, and a
+//! stable fingerprint, delivered to the configured [`ErrorSink`]s:
+//!
+//! ```toml
+//! [observability.errors]
+//! enabled = true
+//!
+//! [[observability.errors.sinks]]
+//! type = "sentry"
+//! dsn = "https://key@o0.ingest.sentry.io/42"
+//! ```
+//!
+//! Custom sinks can be added with
+//! [`McpObservabilityMiddleware::with_error_sink`], and panics can be
+//! reported with [`install_panic_hook`].
 
 mod backend;
 mod config;
+mod datadog;
+mod errors;
 mod events;
+mod http_json;
+#[cfg(feature = "logging")]
+mod layer;
 mod middleware;
+mod prometheus;
+mod sampling;
 mod types;
 
 // Re-export public types
 pub use backend::{
-    CloudWatchBackend, CloudWatchConfig, CompositeBackend, ConsoleBackend, NullBackend,
-    ObservabilityBackend,
+    build_backend, CloudWatchBackend, CloudWatchConfig, CompositeBackend, ConsoleBackend,
+    NullBackend, ObservabilityBackend,
 };
 pub use config::{
-    ConfigError, ConsoleConfig, FieldsConfig, MetricsConfig, ObservabilityConfig, TracingConfig,
+    ConfigError, ConsoleConfig, ErrorReportingConfig, ErrorSinkConfig, FieldsConfig, MetricsConfig,
+    ObservabilityConfig, SamplingConfig, TracingConfig,
+};
+pub use datadog::{DatadogBackend, DatadogConfig};
+pub(crate) use errors::scope_call;
+pub use errors::{
+    build_error_sinks, fingerprint, install_panic_hook, CodeLocation, ErrorReport, ErrorSink,
+    FileErrorSink, SINK_TIMEOUT,
 };
+#[cfg(feature = "http-client")]
+pub use errors::{SentryErrorSink, WebhookErrorSink};
 pub use events::{
-    McpMetric, McpRequestEvent, McpResponseEvent, MetricUnit, RequestStart, StandardMetrics,
+    McpLogEvent, McpMetric, McpRequestEvent, McpResponseEvent, MetricUnit, RequestStart,
+    StandardMetrics,
 };
+#[cfg(feature = "http-client")]
+pub use http_json::HttpJsonBackend;
+pub use http_json::HttpJsonConfig;
+#[cfg(feature = "logging")]
+pub use layer::{correlated_fmt_layer, CorrelatedFormat, ObservabilityLayer};
 pub use middleware::McpObservabilityMiddleware;
+pub(crate) use middleware::{tool_span, tool_trace};
+pub use prometheus::{PrometheusMetrics, RequestTimer, DEFAULT_DURATION_BUCKETS};
 pub use types::{hash_value, McpOperationDetails, RequestMetadata, TraceContext};
 
 #[cfg(test)]
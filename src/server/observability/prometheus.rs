@@ -0,0 +1,364 @@
+//! Prometheus exposition of the standard MCP request metrics.
+//!
+//! [`PrometheusMetrics`] aggregates [`McpMetric`]s named after
+//! [`StandardMetrics`] into counters and histograms and renders them in the
+//! Prometheus text format. The streamable HTTP server feeds it every request
+//! and serves it on `GET /metrics` when
+//! `StreamableHttpServerConfig::metrics` is set.
+//!
+//! | Prometheus series                 | Type      | Labels           | Source                              |
+//! |-----------------------------------|-----------|------------------|-------------------------------------|
+//! | `mcp_requests_total`              | counter   | `method`         | [`StandardMetrics::REQUEST_COUNT`]  |
+//! | `mcp_request_duration_seconds`    | histogram | `method`         | [`StandardMetrics::REQUEST_DURATION`] |
+//! | `mcp_request_errors_total`        | counter   | `method`, `code` | [`StandardMetrics::REQUEST_ERRORS`] |
+//! | `mcp_tool_calls_total`            | counter   | `tool`           | [`StandardMetrics::TOOL_USAGE`]     |
+//! | `mcp_requests_in_flight`          | gauge     |                  | [`PrometheusMetrics::start_request`] |
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+
+use super::events::{McpMetric, MetricUnit, StandardMetrics};
+
+/// Default histogram buckets for request durations, in seconds.
+pub const DEFAULT_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Prometheus registry for the standard MCP request metrics.
+///
+/// Cloning is cheap and shares the same counters.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::observability::{McpMetric, PrometheusMetrics, StandardMetrics};
+///
+/// let metrics = PrometheusMetrics::new();
+/// metrics.record(
+///     &McpMetric::count(StandardMetrics::REQUEST_COUNT, 1).with_dimension("method", "tools/list"),
+/// );
+///
+/// let text = metrics.render();
+/// assert!(text.contains("mcp_requests_total{method=\"tools/list\"} 1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrometheusMetrics {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    buckets: Vec<f64>,
+    in_flight: AtomicI64,
+    series: Mutex<Series>,
+}
+
+#[derive(Debug, Default)]
+struct Series {
+    requests: BTreeMap<String, u64>,
+    errors: BTreeMap<(String, String), u64>,
+    tools: BTreeMap<String, u64>,
+    durations: BTreeMap<String, Histogram>,
+}
+
+#[derive(Debug)]
+struct Histogram {
+    /// Per-bucket (non-cumulative) observation counts.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrometheusMetrics {
+    /// Create a registry with [`DEFAULT_DURATION_BUCKETS`].
+    pub fn new() -> Self {
+        Self::with_buckets(DEFAULT_DURATION_BUCKETS.to_vec())
+    }
+
+    /// Create a registry with custom duration buckets (upper bounds in
+    /// seconds). Buckets are sorted; `+Inf` is always added.
+    pub fn with_buckets(mut buckets: Vec<f64>) -> Self {
+        buckets.retain(|b| b.is_finite());
+        buckets.sort_by(f64::total_cmp);
+        buckets.dedup();
+        Self {
+            inner: Arc::new(Inner {
+                buckets,
+                in_flight: AtomicI64::new(0),
+                series: Mutex::new(Series::default()),
+            }),
+        }
+    }
+
+    /// Record a metric.
+    ///
+    /// Metrics named after [`StandardMetrics`] are aggregated; the label
+    /// values come from the `method`, `error_code`, and `tool` dimensions.
+    /// Other metrics are ignored.
+    pub fn record(&self, metric: &McpMetric) {
+        let dimension = |key: &str| metric.dimensions.get(key).cloned().unwrap_or_default();
+        let mut series = self.inner.series.lock();
+        match metric.name.as_str() {
+            StandardMetrics::REQUEST_COUNT => {
+                *series.requests.entry(dimension("method")).or_default() += count(metric);
+            },
+            StandardMetrics::REQUEST_ERRORS => {
+                let key = (dimension("method"), dimension("error_code"));
+                *series.errors.entry(key).or_default() += count(metric);
+            },
+            StandardMetrics::TOOL_USAGE => {
+                *series.tools.entry(dimension("tool")).or_default() += count(metric);
+            },
+            StandardMetrics::REQUEST_DURATION => {
+                let seconds = match metric.unit {
+                    MetricUnit::Milliseconds => metric.value / 1000.0,
+                    _ => metric.value,
+                };
+                let buckets = &self.inner.buckets;
+                let histogram = series
+                    .durations
+                    .entry(dimension("method"))
+                    .or_insert_with(|| Histogram {
+                        counts: vec![0; buckets.len()],
+                        sum: 0.0,
+                        count: 0,
+                    });
+                if let Some(slot) = buckets.iter().position(|bound| seconds <= *bound) {
+                    histogram.counts[slot] += 1;
+                }
+                histogram.sum += seconds;
+                histogram.count += 1;
+            },
+            _ => {},
+        }
+    }
+
+    /// Start timing a request for `method` (and `tool`, for `tools/call`).
+    ///
+    /// The request counts as in flight until the returned timer is finished
+    /// or dropped.
+    pub fn start_request(&self, method: impl Into<String>, tool: Option<String>) -> RequestTimer {
+        self.inner.in_flight.fetch_add(1, Ordering::Relaxed);
+        RequestTimer {
+            metrics: self.clone(),
+            method: method.into(),
+            tool,
+            started: Instant::now(),
+        }
+    }
+
+    /// Number of requests currently in flight.
+    pub fn in_flight(&self) -> i64 {
+        self.inner.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Render all series in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let series = self.inner.series.lock();
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "mcp_requests_total",
+            "counter",
+            "MCP requests handled",
+        );
+        for (method, value) in &series.requests {
+            let _ = writeln!(
+                out,
+                "mcp_requests_total{{method=\"{}\"}} {value}",
+                escape(method)
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_request_errors_total",
+            "counter",
+            "MCP requests that returned a JSON-RPC error",
+        );
+        for ((method, code), value) in &series.errors {
+            let _ = writeln!(
+                out,
+                "mcp_request_errors_total{{method=\"{}\",code=\"{}\"}} {value}",
+                escape(method),
+                escape(code)
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_tool_calls_total",
+            "counter",
+            "Tool invocations",
+        );
+        for (tool, value) in &series.tools {
+            let _ = writeln!(
+                out,
+                "mcp_tool_calls_total{{tool=\"{}\"}} {value}",
+                escape(tool)
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_request_duration_seconds",
+            "histogram",
+            "MCP request duration",
+        );
+        for (method, histogram) in &series.durations {
+            let method = escape(method);
+            let mut cumulative = 0;
+            for (bound, count) in self.inner.buckets.iter().zip(&histogram.counts) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "mcp_request_duration_seconds_bucket{{method=\"{method}\",le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "mcp_request_duration_seconds_bucket{{method=\"{method}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "mcp_request_duration_seconds_sum{{method=\"{method}\"}} {}",
+                histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "mcp_request_duration_seconds_count{{method=\"{method}\"}} {}",
+                histogram.count
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_requests_in_flight",
+            "gauge",
+            "MCP requests currently being handled",
+        );
+        let _ = writeln!(out, "mcp_requests_in_flight {}", self.in_flight());
+
+        out
+    }
+}
+
+/// Times one request; created by [`PrometheusMetrics::start_request`].
+#[derive(Debug)]
+pub struct RequestTimer {
+    metrics: PrometheusMetrics,
+    method: String,
+    tool: Option<String>,
+    started: Instant,
+}
+
+impl RequestTimer {
+    /// Record the request's duration and outcome. `error_code` is the
+    /// JSON-RPC error code, or `None` on success.
+    pub fn finish(self, error_code: Option<i32>) {
+        let duration_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let metrics = &self.metrics;
+
+        metrics.record(
+            &McpMetric::count(StandardMetrics::REQUEST_COUNT, 1)
+                .with_dimension("method", &self.method),
+        );
+        metrics.record(
+            &McpMetric::duration(StandardMetrics::REQUEST_DURATION, duration_ms)
+                .with_dimension("method", &self.method),
+        );
+        if let Some(code) = error_code {
+            metrics.record(
+                &McpMetric::count(StandardMetrics::REQUEST_ERRORS, 1)
+                    .with_dimension("method", &self.method)
+                    .with_dimension("error_code", code.to_string()),
+            );
+        }
+        if let Some(tool) = &self.tool {
+            metrics.record(
+                &McpMetric::count(StandardMetrics::TOOL_USAGE, 1).with_dimension("tool", tool),
+            );
+        }
+    }
+}
+
+impl Drop for RequestTimer {
+    fn drop(&mut self) {
+        self.metrics.inner.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn count(metric: &McpMetric) -> u64 {
+    metric.value.max(0.0) as u64
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Escape a label value per the text exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timers_feed_counters_histograms_and_gauge() {
+        let metrics = PrometheusMetrics::with_buckets(vec![1.0, 0.1]);
+
+        let tool = metrics.start_request("tools/call", Some("search".to_string()));
+        let list = metrics.start_request("tools/list", None);
+        assert_eq!(metrics.in_flight(), 2);
+        tool.finish(Some(-32602));
+        drop(list);
+        assert_eq!(metrics.in_flight(), 0);
+
+        let text = metrics.render();
+        assert!(text.contains("mcp_requests_total{method=\"tools/call\"} 1"));
+        assert!(!text.contains("mcp_requests_total{method=\"tools/list\"}"));
+        assert!(text.contains("mcp_request_errors_total{method=\"tools/call\",code=\"-32602\"} 1"));
+        assert!(text.contains("mcp_tool_calls_total{tool=\"search\"} 1"));
+        assert!(text
+            .contains("mcp_request_duration_seconds_bucket{method=\"tools/call\",le=\"0.1\"} 1"));
+        assert!(text
+            .contains("mcp_request_duration_seconds_bucket{method=\"tools/call\",le=\"+Inf\"} 1"));
+        assert!(text.contains("# TYPE mcp_request_duration_seconds histogram"));
+        assert!(text.contains("mcp_requests_in_flight 0"));
+    }
+
+    #[test]
+    fn durations_and_labels_are_normalized() {
+        let metrics = PrometheusMetrics::with_buckets(vec![0.5]);
+        metrics.record(
+            &McpMetric::duration(StandardMetrics::REQUEST_DURATION, 750)
+                .with_dimension("method", "say \"hi\""),
+        );
+        metrics.record(&McpMetric::count("custom.metric", 1));
+
+        let text = metrics.render();
+        assert!(text.contains("le=\"0.5\"} 0"));
+        assert!(text.contains("mcp_request_duration_seconds_sum{method=\"say \\\"hi\\\"\"} 0.75"));
+        assert!(!text.contains("custom"));
+    }
+}
@@ -0,0 +1,131 @@
+//! Tail-based sampling buffer.
+//!
+//! Calls dropped by the head sampling decision are held here until they
+//! finish, so the rules in [`SamplingConfig`](super::SamplingConfig) can
+//! still record the ones that turn out to be interesting (failed or slow).
+//! Calls that never report back (dropped by the client, say) are evicted
+//! once they exceed the maximum age, so they cannot fill the buffer.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::events::McpRequestEvent;
+
+/// An unsampled call waiting for its outcome.
+#[derive(Debug)]
+pub(crate) struct HeldCall {
+    /// When the call started.
+    pub(crate) started: Instant,
+
+    /// The request event, recorded only if the call is retained.
+    pub(crate) request: McpRequestEvent,
+}
+
+impl HeldCall {
+    /// Milliseconds since the call started.
+    pub(crate) fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+/// Bounded buffer of unsampled in-flight calls, keyed by call ID.
+#[derive(Debug)]
+pub(crate) struct TailBuffer {
+    capacity: usize,
+    max_age: Duration,
+    calls: Mutex<HashMap<String, HeldCall>>,
+}
+
+impl TailBuffer {
+    pub(crate) fn new(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            capacity,
+            max_age,
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether another call can be held.
+    pub(crate) fn has_room(&self) -> bool {
+        let mut calls = self.calls.lock();
+        self.evict_expired(&mut calls);
+        calls.len() < self.capacity
+    }
+
+    /// Hold `request` until the call finishes. Returns `false` (dropping the
+    /// event) if the buffer is full.
+    pub(crate) fn hold(&self, call_id: String, request: McpRequestEvent) -> bool {
+        let mut calls = self.calls.lock();
+        self.evict_expired(&mut calls);
+        if calls.len() >= self.capacity {
+            return false;
+        }
+        calls.insert(
+            call_id,
+            HeldCall {
+                started: Instant::now(),
+                request,
+            },
+        );
+        true
+    }
+
+    /// Release the held call for `call_id`, if any.
+    pub(crate) fn release(&self, call_id: &str) -> Option<HeldCall> {
+        self.calls.lock().remove(call_id)
+    }
+
+    /// Drop calls held longer than the maximum age.
+    fn evict_expired(&self, calls: &mut HashMap<String, HeldCall>) {
+        let before = calls.len();
+        calls.retain(|_, call| call.started.elapsed() < self.max_age);
+        if calls.len() < before {
+            tracing::debug!(
+                evicted = before - calls.len(),
+                "Evicted unfinished calls from the tail sampling buffer"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::observability::{McpOperationDetails, TraceContext};
+
+    fn request() -> McpRequestEvent {
+        McpRequestEvent::new(
+            TraceContext::new_root(),
+            "test-server",
+            McpOperationDetails::tool_call("search"),
+        )
+    }
+
+    #[test]
+    fn buffer_is_bounded() {
+        let buffer = TailBuffer::new(1, Duration::from_secs(60));
+
+        assert!(buffer.hold("a".to_string(), request()));
+        assert!(!buffer.has_room());
+        assert!(!buffer.hold("b".to_string(), request()));
+
+        assert!(buffer.release("b").is_none());
+        assert!(buffer.release("a").is_some());
+        assert!(buffer.has_room());
+    }
+
+    #[test]
+    fn calls_that_never_finish_are_evicted() {
+        let buffer = TailBuffer::new(1, Duration::from_millis(10));
+
+        assert!(buffer.hold("dropped".to_string(), request()));
+        assert!(!buffer.has_room());
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(buffer.has_room());
+        assert!(buffer.hold("next".to_string(), request()));
+        assert!(buffer.release("dropped").is_none());
+    }
+}
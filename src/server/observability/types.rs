@@ -25,6 +25,13 @@ use uuid::Uuid;
 /// end-to-end request tracing. The same `trace_id` is shared across all
 /// servers in a request chain.
 ///
+/// IDs use the W3C Trace Context format (32 hex digits for the trace ID,
+/// 16 for span IDs), so a context can be read from and written to
+/// `traceparent`/`tracestate` HTTP headers with
+/// [`from_traceparent`](Self::from_traceparent) and
+/// [`traceparent`](Self::traceparent), joining MCP hops to existing
+/// distributed traces.
+///
 /// # Important
 ///
 /// This struct contains NO user identity fields (`user_id`, email, `tenant_id`).
@@ -46,11 +53,11 @@ use uuid::Uuid;
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TraceContext {
-    /// Unique trace ID (UUID v4, generated at entry point).
+    /// Unique trace ID (32 lowercase hex digits, generated at entry point).
     /// Same across all servers in a request chain.
     pub trace_id: String,
 
-    /// Span ID for this specific operation (UUID v4).
+    /// Span ID for this specific operation (16 lowercase hex digits).
     /// Unique per operation within a trace.
     pub span_id: String,
 
@@ -62,6 +69,14 @@ pub struct TraceContext {
     /// Depth in composition chain (0 = entry, 1 = first hop, etc.).
     /// Used for loop detection (reject if depth > `max_depth`).
     pub depth: u32,
+
+    /// Vendor-specific W3C `tracestate` carried along with the trace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracestate: Option<String>,
+}
+
+tokio::task_local! {
+    static CURRENT_TRACE: TraceContext;
 }
 
 impl TraceContext {
@@ -70,23 +85,25 @@ impl TraceContext {
     /// Use this when starting a new request chain (at proxy or first server).
     pub fn new_root() -> Self {
         Self {
-            trace_id: Uuid::new_v4().to_string(),
-            span_id: Uuid::new_v4().to_string(),
+            trace_id: Uuid::new_v4().simple().to_string(),
+            span_id: new_span_id(),
             parent_span_id: None,
             depth: 0,
+            tracestate: None,
         }
     }
 
     /// Create a child context for downstream calls.
     ///
-    /// The child inherits the `trace_id` but gets a new `span_id`,
-    /// with the current `span_id` becoming the `parent_span_id`.
+    /// The child inherits the `trace_id` and `tracestate` but gets a new
+    /// `span_id`, with the current `span_id` becoming the `parent_span_id`.
     pub fn child(&self) -> Self {
         Self {
             trace_id: self.trace_id.clone(),
-            span_id: Uuid::new_v4().to_string(),
+            span_id: new_span_id(),
             parent_span_id: Some(self.span_id.clone()),
             depth: self.depth + 1,
+            tracestate: self.tracestate.clone(),
         }
     }
 
@@ -97,20 +114,126 @@ impl TraceContext {
     pub fn from_parent(trace_id: String, parent_span_id: Option<String>, depth: u32) -> Self {
         Self {
             trace_id,
-            span_id: Uuid::new_v4().to_string(),
+            span_id: new_span_id(),
             parent_span_id,
             depth,
+            tracestate: None,
         }
     }
 
+    /// Parse a W3C `traceparent` header (and optional `tracestate`).
+    ///
+    /// The returned context represents the caller's span: its `span_id` is
+    /// the header's parent ID, so [`child`](Self::child) yields the span for
+    /// the local operation. Returns `None` for malformed headers, unknown
+    /// versions, or all-zero IDs.
+    ///
+    /// ```rust
+    /// use pmcp::server::observability::TraceContext;
+    ///
+    /// let parent = TraceContext::from_traceparent(
+    ///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    ///     Some("vendor=abc"),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(parent.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    ///
+    /// let local = parent.child();
+    /// assert_eq!(local.parent_span_id.as_deref(), Some("00f067aa0ba902b7"));
+    /// ```
+    pub fn from_traceparent(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        // Version 00 has exactly four fields; later versions may append more.
+        if !is_hex_id(version, 2) || version == "ff" || (version == "00" && parts.next().is_some())
+        {
+            return None;
+        }
+        if !is_hex_id(trace_id, 32) || !is_hex_id(span_id, 16) || !is_hex_id(flags, 2) {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            parent_span_id: None,
+            depth: 0,
+            tracestate: tracestate
+                .map(str::trim)
+                .filter(|state| !state.is_empty())
+                .map(str::to_string),
+        })
+    }
+
+    /// Format this context as a W3C `traceparent` header value, with this
+    /// span as the parent of the receiving service.
+    ///
+    /// IDs that are not already in W3C form (e.g. UUIDs from older callers)
+    /// are normalized.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-01",
+            to_hex_id(&self.trace_id, 32),
+            to_hex_id(&self.span_id, 16)
+        )
+    }
+
+    /// The trace context of the operation currently running, if any.
+    ///
+    /// Set for the duration of a tool handler when the observability
+    /// middleware is enabled, and for requests that arrived with a
+    /// `traceparent` header. Outbound clients use it to propagate the trace.
+    pub fn current() -> Option<Self> {
+        CURRENT_TRACE.try_with(Clone::clone).ok()
+    }
+
+    /// Run `future` with this context as [`current`](Self::current).
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        CURRENT_TRACE.scope(self, future).await
+    }
+
     /// Get a short version of the `trace_id` (first 8 characters).
     ///
-    /// Useful for logging where full UUID is too verbose.
+    /// Useful for logging where the full ID is too verbose.
     pub fn short_trace_id(&self) -> &str {
         &self.trace_id[..8.min(self.trace_id.len())]
     }
 }
 
+/// Generate a random 16-hex-digit span ID.
+fn new_span_id() -> String {
+    let mut id = Uuid::new_v4().simple().to_string();
+    id.truncate(16);
+    id
+}
+
+/// Whether `id` is `len` lowercase hex digits and not all zeros.
+fn is_hex_id(id: &str, len: usize) -> bool {
+    id.len() == len
+        && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        && (len == 2 || id.bytes().any(|b| b != b'0'))
+}
+
+/// Normalize `id` to `len` lowercase hex digits.
+fn to_hex_id(id: &str, len: usize) -> String {
+    let hex: String = id
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if hex.len() >= len && is_hex_id(&hex[..len], len) {
+        return hex[..len].to_string();
+    }
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    let digest = format!("{:016x}", hasher.finish());
+    digest.repeat(len / 16)
+}
+
 impl Default for TraceContext {
     fn default() -> Self {
         Self::new_root()
@@ -449,6 +572,77 @@ mod tests {
         assert_eq!(ctx, deserialized);
     }
 
+    #[test]
+    fn test_trace_context_ids_are_w3c_format() {
+        let ctx = TraceContext::new_root().child();
+        assert_eq!(ctx.trace_id.len(), 32);
+        assert_eq!(ctx.span_id.len(), 16);
+
+        let traceparent = ctx.traceparent();
+        assert_eq!(
+            traceparent,
+            format!("00-{}-{}-01", ctx.trace_id, ctx.span_id)
+        );
+
+        let parsed = TraceContext::from_traceparent(&traceparent, None).unwrap();
+        assert_eq!(parsed.trace_id, ctx.trace_id);
+        assert_eq!(parsed.span_id, ctx.span_id);
+        assert_eq!(parsed.depth, 0);
+    }
+
+    #[test]
+    fn test_from_traceparent_rejects_malformed_headers() {
+        let valid = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let parsed = TraceContext::from_traceparent(valid, Some(" congo=t61rcWkgMzE ")).unwrap();
+        assert_eq!(parsed.tracestate.as_deref(), Some("congo=t61rcWkgMzE"));
+        assert_eq!(parsed.child().tracestate, parsed.tracestate);
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ] {
+            assert!(
+                TraceContext::from_traceparent(invalid, None).is_none(),
+                "{invalid:?}"
+            );
+        }
+
+        // Future versions may append fields
+        assert!(
+            TraceContext::from_traceparent(&format!("01{}-extra", &valid[2..]), None).is_some()
+        );
+    }
+
+    #[test]
+    fn test_traceparent_normalizes_legacy_ids() {
+        let ctx = TraceContext {
+            trace_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            span_id: "not-hex".to_string(),
+            parent_span_id: None,
+            depth: 0,
+            tracestate: None,
+        };
+        let traceparent = ctx.traceparent();
+        let parsed = TraceContext::from_traceparent(&traceparent, None).unwrap();
+        assert_eq!(parsed.trace_id, "550e8400e29b41d4a716446655440000");
+        assert_eq!(parsed.span_id.len(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_current_is_scoped() {
+        assert!(TraceContext::current().is_none());
+        let ctx = TraceContext::new_root();
+        let seen = ctx.clone().scope(async { TraceContext::current() }).await;
+        assert_eq!(seen, Some(ctx));
+        assert!(TraceContext::current().is_none());
+    }
+
     #[test]
     fn test_short_trace_id() {
         let ctx = TraceContext::new_root();
@@ -0,0 +1,470 @@
+//! Signed, expiring pagination cursors.
+//!
+//! MCP list results carry an opaque `nextCursor` that the client sends back
+//! to fetch the next page. [`CursorCodec`] gives handlers one cursor format
+//! instead of each inventing its own:
+//!
+//! - Cursors carry any serializable state (an offset, a filter, a sort key).
+//! - They are signed with HMAC-SHA256, so clients cannot forge or edit them.
+//! - They can expire, so stale cursors do not live forever.
+//! - They can be bound to one listing, so a `resources/list` cursor is not
+//!   accepted by `tools/list`.
+//! - They hold all the state needed for the next page, so a client can retry
+//!   a request with the same cursor and get the same page back.
+//!
+//! Malformed, tampered, and expired cursors are all rejected with an
+//! `Invalid params` (-32602) error, as the MCP specification requires.
+//!
+//! The format is `<payload>.<signature>`, both base64url-encoded. Treat it
+//! as opaque: only the codec that issued a cursor can read it.
+//!
+//! # Examples
+//!
+//! Paginating a resource list:
+//!
+//! ```rust
+//! use pmcp::server::pagination::CursorCodec;
+//!
+//! # fn main() -> pmcp::Result<()> {
+//! let codec = CursorCodec::new(b"a secret shared by all server instances");
+//! let uris: Vec<String> = (0..25).map(|i| format!("file:///doc-{i}")).collect();
+//!
+//! let first = codec.paginate(uris.clone(), None, 10)?;
+//! assert_eq!(first.items.len(), 10);
+//!
+//! let second = codec.paginate(uris.clone(), first.next_cursor.as_deref(), 10)?;
+//! assert_eq!(second.items[0], "file:///doc-10");
+//!
+//! // Tampered cursors are rejected
+//! assert!(codec.paginate(uris, Some("bm90IGEgY3Vyc29y.AAAA"), 10).is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Custom state, for example a tool that pages through query results:
+//!
+//! ```rust
+//! use pmcp::server::pagination::CursorCodec;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct QueryPage {
+//!     after_id: u64,
+//!     region: String,
+//! }
+//!
+//! # fn main() -> pmcp::Result<()> {
+//! let codec = CursorCodec::random();
+//! let cursor = codec.encode(&QueryPage { after_id: 42, region: "eu".into() })?;
+//!
+//! let page: QueryPage = codec.decode(&cursor)?;
+//! assert_eq!(page.after_id, 42);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use hmac::{Hmac, KeyInit, Mac};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::error::{Error, Result};
+
+/// Default time a cursor stays valid.
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Issues and verifies signed pagination cursors.
+///
+/// Cloning is cheap; clones share the key.
+///
+/// Servers running as several instances (for example behind a load balancer
+/// or on Lambda) must use the same key everywhere, with [`CursorCodec::new`].
+/// [`CursorCodec::random`] suits single-process servers, where cursors only
+/// need to outlive the process.
+#[derive(Clone)]
+pub struct CursorCodec {
+    key: Arc<[u8]>,
+    ttl: Option<Duration>,
+    list: Option<Arc<str>>,
+}
+
+impl fmt::Debug for CursorCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorCodec")
+            .field("key", &"<redacted>")
+            .field("ttl", &self.ttl)
+            .field("list", &self.list)
+            .finish()
+    }
+}
+
+/// Built-in cursor state used by [`CursorCodec::paginate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// Index of the first item on the page.
+    pub offset: usize,
+    /// Filter the listing was made with, if any.
+    ///
+    /// A cursor only continues the listing it was issued for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+/// One page of items and the cursor for the next page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// Items on this page.
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Signed cursor payload.
+#[derive(Serialize, Deserialize)]
+struct Envelope<S> {
+    #[serde(rename = "s")]
+    state: S,
+    /// Expiry as Unix seconds.
+    #[serde(rename = "e", default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<i64>,
+    /// Listing the cursor was issued for.
+    #[serde(rename = "l", default, skip_serializing_if = "Option::is_none")]
+    list: Option<String>,
+}
+
+impl CursorCodec {
+    /// Create a codec with a signing key.
+    ///
+    /// Use at least 32 random bytes, and keep the key secret. Cursors expire
+    /// after one hour by default.
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            ttl: Some(DEFAULT_TTL),
+            list: None,
+        }
+    }
+
+    /// Create a codec with a random key.
+    ///
+    /// Cursors stop working when the process exits.
+    pub fn random() -> Self {
+        let mut key = Vec::with_capacity(32);
+        key.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+        key.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+        Self::new(key)
+    }
+
+    /// Set how long cursors stay valid (default one hour).
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Issue cursors that never expire.
+    #[must_use]
+    pub fn without_expiry(mut self) -> Self {
+        self.ttl = None;
+        self
+    }
+
+    /// Bind cursors to one listing, such as `"resources/list"`.
+    ///
+    /// The listing is signed into every cursor, and a cursor is only accepted
+    /// by a codec bound to the same listing, so a client cannot replay a
+    /// cursor from one list method against another. The key stays shared.
+    #[must_use]
+    pub fn for_list(mut self, list: impl Into<String>) -> Self {
+        self.list = Some(Arc::from(list.into()));
+        self
+    }
+
+    /// Encode and sign cursor state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an internal error if `state` cannot be serialized.
+    pub fn encode<S: Serialize>(&self, state: &S) -> Result<String> {
+        let expires_at = self.ttl.map(|ttl| {
+            let secs = i64::try_from(ttl.as_secs()).unwrap_or(i64::MAX);
+            chrono::Utc::now().timestamp().saturating_add(secs)
+        });
+        let json = serde_json::to_vec(&Envelope {
+            state,
+            expires_at,
+            list: self.list.as_deref().map(str::to_string),
+        })
+        .map_err(|e| Error::internal(format!("Failed to encode cursor: {e}")))?;
+
+        let payload = URL_SAFE_NO_PAD.encode(json);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        Ok(format!("{payload}.{signature}"))
+    }
+
+    /// Verify a cursor and decode its state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Invalid params` error if the cursor is malformed, was not
+    /// signed with this codec's key, was issued for a different listing, has
+    /// expired, or does not hold an `S`.
+    pub fn decode<S: DeserializeOwned>(&self, cursor: &str) -> Result<S> {
+        let (payload, signature) = cursor.split_once('.').ok_or_else(invalid_cursor)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| invalid_cursor())?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| invalid_cursor())?;
+
+        let json = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| invalid_cursor())?;
+        let envelope: Envelope<S> = serde_json::from_slice(&json).map_err(|_| invalid_cursor())?;
+
+        if envelope.list.as_deref() != self.list.as_deref() {
+            return Err(Error::invalid_params(
+                "Cursor was issued for a different listing",
+            ));
+        }
+        if envelope
+            .expires_at
+            .is_some_and(|expires_at| chrono::Utc::now().timestamp() > expires_at)
+        {
+            return Err(Error::invalid_params("Cursor has expired"));
+        }
+        Ok(envelope.state)
+    }
+
+    /// Return the page of `items` that `cursor` points at.
+    ///
+    /// Pass `None` for the first page. `items` must be listed in the same
+    /// order on every call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Invalid params` error for a cursor that [`decode`] rejects.
+    ///
+    /// [`decode`]: CursorCodec::decode
+    pub fn paginate<T>(
+        &self,
+        items: Vec<T>,
+        cursor: Option<&str>,
+        page_size: usize,
+    ) -> Result<Page<T>> {
+        self.paginate_filtered(items, cursor, page_size, None)
+    }
+
+    /// Like [`paginate`], for a listing narrowed by `filter`.
+    ///
+    /// The filter is stored in the cursor, and a cursor issued for one filter
+    /// is rejected for another, so a client cannot mix pages of different
+    /// listings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Invalid params` error for a cursor that [`decode`] rejects
+    /// or that was issued for a different filter.
+    ///
+    /// [`paginate`]: CursorCodec::paginate
+    /// [`decode`]: CursorCodec::decode
+    pub fn paginate_filtered<T>(
+        &self,
+        items: Vec<T>,
+        cursor: Option<&str>,
+        page_size: usize,
+        filter: Option<&str>,
+    ) -> Result<Page<T>> {
+        let offset = match cursor {
+            Some(cursor) => {
+                let state: PageCursor = self.decode(cursor)?;
+                if state.filter.as_deref() != filter {
+                    return Err(Error::invalid_params(
+                        "Cursor was issued for a different filter",
+                    ));
+                }
+                state.offset
+            },
+            None => 0,
+        };
+
+        let page_size = page_size.max(1);
+        let end = offset.saturating_add(page_size);
+        let next_cursor = if end < items.len() {
+            Some(self.encode(&PageCursor {
+                offset: end,
+                filter: filter.map(str::to_string),
+            })?)
+        } else {
+            None
+        };
+        let items = items.into_iter().skip(offset).take(page_size).collect();
+
+        Ok(Page { items, next_cursor })
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(&self.key)
+            .expect("HMAC accepts any key length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// Page size and codec for a listing the server splits into pages itself.
+#[derive(Debug, Clone)]
+pub(crate) struct ListPages {
+    page_size: usize,
+    cursors: CursorCodec,
+}
+
+impl ListPages {
+    /// Pages of `page_size` items for the `list` method, with cursors signed
+    /// by `cursors` or, without one, a random key.
+    pub(crate) fn new(list: &str, page_size: usize, cursors: Option<CursorCodec>) -> Self {
+        Self {
+            page_size,
+            cursors: cursors.unwrap_or_else(CursorCodec::random).for_list(list),
+        }
+    }
+
+    /// The page of `items` that `cursor` points at.
+    pub(crate) fn page<T>(&self, items: Vec<T>, cursor: Option<&str>) -> Result<Page<T>> {
+        self.cursors.paginate(items, cursor, self.page_size)
+    }
+}
+
+fn invalid_cursor() -> Error {
+    Error::invalid_params("Invalid cursor")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorCode;
+
+    fn assert_invalid_params<T: fmt::Debug>(result: Result<T>) {
+        let err = result.unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::INVALID_PARAMS), "{err}");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let codec = CursorCodec::new(b"key");
+        let state = PageCursor {
+            offset: 7,
+            filter: Some("kind=log".to_string()),
+        };
+        let cursor = codec.encode(&state).unwrap();
+        assert_eq!(codec.decode::<PageCursor>(&cursor).unwrap(), state);
+    }
+
+    #[test]
+    fn test_paginate_walks_all_items() {
+        let codec = CursorCodec::random();
+        let items: Vec<u32> = (0..23).collect();
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = codec
+                .paginate(items.clone(), cursor.as_deref(), 10)
+                .unwrap();
+            seen.extend(page.items);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, items);
+    }
+
+    #[test]
+    fn test_retrying_a_cursor_returns_the_same_page() {
+        let codec = CursorCodec::random();
+        let items: Vec<u32> = (0..30).collect();
+        let first = codec.paginate(items.clone(), None, 10).unwrap();
+        let cursor = first.next_cursor.as_deref();
+
+        let a = codec.paginate(items.clone(), cursor, 10).unwrap();
+        let b = codec.paginate(items, cursor, 10).unwrap();
+        assert_eq!(a.items, b.items);
+        assert_eq!(a.items[0], 10);
+    }
+
+    #[test]
+    fn test_rejects_tampered_and_foreign_cursors() {
+        let codec = CursorCodec::new(b"key");
+        let cursor = codec
+            .encode(&PageCursor {
+                offset: 10,
+                filter: None,
+            })
+            .unwrap();
+
+        let (payload, signature) = cursor.split_once('.').unwrap();
+        let forged_payload = URL_SAFE_NO_PAD.encode(br#"{"s":{"offset":0}}"#);
+        assert_invalid_params(codec.decode::<PageCursor>(&format!("{forged_payload}.{signature}")));
+        assert_invalid_params(codec.decode::<PageCursor>(payload));
+        assert_invalid_params(codec.decode::<PageCursor>("not a cursor"));
+        assert_invalid_params(CursorCodec::new(b"other").decode::<PageCursor>(&cursor));
+        // Valid signature, wrong state type
+        assert_invalid_params(codec.decode::<Vec<String>>(&cursor));
+    }
+
+    #[test]
+    fn test_expired_cursor() {
+        let codec = CursorCodec::new(b"key").with_ttl(Duration::ZERO);
+        let cursor = codec.encode(&1u32).unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+        assert_invalid_params(codec.decode::<u32>(&cursor));
+
+        let codec = CursorCodec::new(b"key").without_expiry();
+        let cursor = codec.encode(&1u32).unwrap();
+        assert_eq!(codec.decode::<u32>(&cursor).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_filter_is_bound_to_cursor() {
+        let codec = CursorCodec::random();
+        let items: Vec<u32> = (0..30).collect();
+        let page = codec
+            .paginate_filtered(items.clone(), None, 10, Some("even"))
+            .unwrap();
+        let cursor = page.next_cursor.as_deref();
+
+        assert!(codec
+            .paginate_filtered(items.clone(), cursor, 10, Some("even"))
+            .is_ok());
+        assert_invalid_params(codec.paginate_filtered(items.clone(), cursor, 10, Some("odd")));
+        assert_invalid_params(codec.paginate(items, cursor, 10));
+    }
+
+    #[test]
+    fn test_list_is_bound_to_cursor() {
+        let codec = CursorCodec::new(b"key");
+        let resources = codec.clone().for_list("resources/list");
+        let tools = codec.clone().for_list("tools/list");
+        let items: Vec<u32> = (0..30).collect();
+
+        let page = resources.paginate(items.clone(), None, 10).unwrap();
+        let cursor = page.next_cursor.as_deref();
+
+        assert!(resources.paginate(items.clone(), cursor, 10).is_ok());
+        assert_invalid_params(tools.paginate(items.clone(), cursor, 10));
+        assert_invalid_params(codec.paginate(items.clone(), cursor, 10));
+
+        // Unbound cursors are not accepted by a bound codec either
+        let unbound = codec.paginate(items.clone(), None, 10).unwrap();
+        assert_invalid_params(resources.paginate(items, unbound.next_cursor.as_deref(), 10));
+    }
+}
@@ -32,10 +32,8 @@
 //!     .build()?;
 //!
 //! // Create HTTP server using server's middleware
-//! let config = StreamableHttpServerConfig {
-//!     http_middleware: server.http_middleware(),
-//!     ..Default::default()
-//! };
+//! let mut config = StreamableHttpServerConfig::default();
+//! config.http_middleware = server.http_middleware();
 //!
 //! let http_server = StreamableHttpServer::with_config(
 //!     "127.0.0.1:3000".parse().unwrap(),
@@ -244,10 +242,8 @@ impl ServerPreset {
     ///
     /// let preset = ServerPreset::new("my-service");
     ///
-    /// let config = StreamableHttpServerConfig {
-    ///     http_middleware: preset.http_middleware(),
-    ///     ..Default::default()
-    /// };
+    /// let mut config = StreamableHttpServerConfig::default();
+    /// config.http_middleware = preset.http_middleware();
     /// ```
     pub fn http_middleware(&self) -> Option<Arc<ServerHttpMiddlewareChain>> {
         self.http_chain.clone()
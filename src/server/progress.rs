@@ -2,13 +2,47 @@
 //!
 //! This module provides the infrastructure for tools to report progress during execution,
 //! following the MCP progress notification protocol.
+//!
+//! Tools can also stream partial output with
+//! [`RequestHandlerExtra::stream_chunk`](crate::server::cancellation::RequestHandlerExtra::stream_chunk).
+//! Each chunk is sent as a progress notification whose `message` carries the
+//! chunk text, so any client that displays progress messages shows the output
+//! as it is produced. Over streamable HTTP, such requests are answered with an
+//! SSE stream that delivers the chunks before the final result.
 
 use crate::error::{Error, Result};
 use crate::types::{Notification, ProgressNotification, ProgressToken, ServerNotification};
 use async_trait::async_trait;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Callback that delivers a notification to the client.
+pub(crate) type NotificationSender = Arc<dyn Fn(Notification) + Send + Sync>;
+
+// Notification sink for the request being handled, set by transports that
+// deliver notifications per request (e.g., one SSE stream per HTTP POST).
+tokio::task_local! {
+    static REQUEST_NOTIFIER: NotificationSender;
+}
+
+/// Run `future` with `sender` as the notification sink for its request.
+///
+/// Progress reporters created while handling the request send through
+/// `sender` instead of the server's shared notification channel.
+#[cfg_attr(not(feature = "streamable-http"), allow(dead_code))]
+pub(crate) async fn with_request_notifier<F: Future>(
+    sender: NotificationSender,
+    future: F,
+) -> F::Output {
+    REQUEST_NOTIFIER.scope(sender, future).await
+}
+
+/// The notification sink set by [`with_request_notifier`], if any.
+pub(crate) fn request_notifier() -> Option<NotificationSender> {
+    REQUEST_NOTIFIER.try_with(Clone::clone).ok()
+}
+
 /// Trait for reporting progress during tool execution.
 ///
 /// Implementations of this trait handle the details of sending progress notifications
@@ -54,6 +88,16 @@ pub trait ProgressReporter: Send + Sync {
         self.report_progress(current as f64, Some(total as f64), message)
             .await
     }
+
+    /// Stream a piece of partial output to the client.
+    ///
+    /// Unlike [`report_progress`](Self::report_progress), chunks are never
+    /// rate limited or dropped. The default implementation ignores chunks,
+    /// for reporters that cannot deliver them.
+    async fn stream_chunk(&self, chunk: String) -> Result<()> {
+        let _ = chunk;
+        Ok(())
+    }
 }
 
 /// Server-side progress reporter implementation.
@@ -259,6 +303,22 @@ impl ProgressReporter for ServerProgressReporter {
         (self.notification_sender)(notification);
         Ok(())
     }
+
+    async fn stream_chunk(&self, chunk: String) -> Result<()> {
+        // Each chunk advances progress by one so clients see a strictly
+        // increasing sequence, even when mixed with regular progress reports
+        let progress = {
+            let mut last_progress = self.last_progress.lock().unwrap();
+            let next = last_progress.map_or(1.0, |last| last.floor() + 1.0);
+            *last_progress = Some(next);
+            next
+        };
+        self.update_last_sent();
+
+        let pn = ProgressNotification::new(self.progress_token.clone(), progress, Some(chunk));
+        (self.notification_sender)(Notification::Server(ServerNotification::Progress(pn)));
+        Ok(())
+    }
 }
 
 impl std::fmt::Debug for ServerProgressReporter {
@@ -497,4 +557,45 @@ mod tests {
             .unwrap();
         assert_eq!(counter.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn test_stream_chunks_bypass_rate_limit() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sent_clone = sent.clone();
+
+        let reporter = ServerProgressReporter::with_rate_limit(
+            ProgressToken::String("query".to_string()),
+            Arc::new(move |notification| {
+                if let Notification::Server(ServerNotification::Progress(pn)) = notification {
+                    sent_clone.lock().unwrap().push((pn.progress, pn.message));
+                }
+            }),
+            Duration::from_secs(60),
+        );
+
+        reporter
+            .report_progress(10.0, Some(100.0), None)
+            .await
+            .unwrap();
+        reporter.stream_chunk("row 1".to_string()).await.unwrap();
+        reporter.stream_chunk("row 2".to_string()).await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(
+            *sent,
+            vec![
+                (10.0, None),
+                (11.0, Some("row 1".to_string())),
+                (12.0, Some("row 2".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_notifier_scope() {
+        assert!(request_notifier().is_none());
+        let sender: NotificationSender = Arc::new(|_| {});
+        let inside = with_request_notifier(sender, async { request_notifier().is_some() }).await;
+        assert!(inside);
+    }
 }
@@ -0,0 +1,529 @@
+//! Prompt discovery from a directory of markdown and YAML files.
+//!
+//! Provides a [`PromptDir`] helper, the prompt counterpart of `WidgetDir`,
+//! that registers one prompt per file in a `prompts/` directory. Each prompt
+//! re-reads its file on every request, so prompt text and arguments can be
+//! edited while the server runs.
+//!
+//! # Convention
+//!
+//! - The file stem is the prompt name: `prompts/code-review.md` -> `code-review`
+//! - Markdown files carry metadata in YAML front matter; the body is the
+//!   user message
+//! - YAML files (`.yaml`/`.yml`) hold the same keys plus a `messages` list
+//! - `{{argument}}` placeholders are replaced with the argument values;
+//!   placeholders that do not name a declared argument are left as is
+//!
+//! ```markdown
+//! ---
+//! description: Review code for bugs and style issues
+//! arguments:
+//!   - name: language
+//!     description: Programming language of the code
+//!     required: true
+//!   - name: focus
+//! ---
+//! Review the following {{language}} code. Focus on: {{focus}}
+//! ```
+//!
+//! ```yaml
+//! description: Summarize a document
+//! arguments:
+//!   - name: text
+//!     required: true
+//! messages:
+//!   - role: assistant
+//!     content: I write summaries of at most three sentences.
+//!   - role: user
+//!     content: "Summarize: {{text}}"
+//! ```
+//!
+//! Files are discovered when the server is built; adding or removing a file
+//! needs a restart, but not a recompile.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pmcp::server::prompt_dir::PromptDir;
+//! use pmcp::Server;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("reviewer")
+//!     .version("1.0.0")
+//!     .prompt_dir(PromptDir::new("prompts"))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::PromptHandler;
+use crate::types::{Content, GetPromptResult, PromptArgument, PromptInfo, PromptMessage, Role};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File extensions recognised as prompt files.
+const PROMPT_EXTENSIONS: &[&str] = &["md", "markdown", "yaml", "yml"];
+
+/// A discovered prompt file entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptEntry {
+    /// Prompt name (stem of the file, e.g. "code-review").
+    pub name: String,
+    /// Path to the prompt file.
+    pub path: PathBuf,
+}
+
+/// Prompt directory scanner.
+///
+/// Register it with `prompt_dir` on the server builder, or call
+/// [`prompts`](Self::prompts) to register the handlers yourself.
+#[derive(Debug, Clone)]
+pub struct PromptDir {
+    path: PathBuf,
+}
+
+impl PromptDir {
+    /// Create a new `PromptDir` pointing at the given directory.
+    ///
+    /// The path does not need to exist at construction time; errors are
+    /// returned when [`discover`](Self::discover) is called.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Return the directory path this `PromptDir` points to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Scan the directory for prompt files.
+    ///
+    /// Returns one [`PromptEntry`] per markdown or YAML file, sorted by name.
+    /// Subdirectories are not scanned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, or if two files
+    /// have the same stem (e.g. `review.md` and `review.yaml`).
+    pub fn discover(&self) -> Result<Vec<PromptEntry>> {
+        let dir = std::fs::read_dir(&self.path).map_err(|e| {
+            Error::validation(format!(
+                "Cannot read prompt directory {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+
+        let mut entries: Vec<PromptEntry> = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            let is_prompt = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| PROMPT_EXTENSIONS.contains(&e));
+            if !is_prompt || !path.is_file() {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some(existing) = entries.iter().find(|e| e.name == name) {
+                return Err(Error::validation(format!(
+                    "Prompt '{}' is defined twice: {} and {}",
+                    name,
+                    existing.path.display(),
+                    path.display()
+                )));
+            }
+            entries.push(PromptEntry {
+                name: name.to_string(),
+                path,
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        tracing::debug!(
+            "Discovered {} prompt(s) in {}",
+            entries.len(),
+            self.path.display()
+        );
+
+        Ok(entries)
+    }
+
+    /// Discover the prompts and create a handler for each.
+    ///
+    /// Every file is parsed once here so that mistakes fail the server build
+    /// instead of the first `prompts/get`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`discover`](Self::discover), or a validation
+    /// error naming the first file that does not parse.
+    pub fn prompts(&self) -> Result<Vec<(String, FilePrompt)>> {
+        self.discover()?
+            .into_iter()
+            .map(|entry| {
+                let prompt = FilePrompt::new(&entry.name, entry.path);
+                prompt.load()?;
+                Ok((entry.name, prompt))
+            })
+            .collect()
+    }
+}
+
+/// Front matter of a markdown prompt, or the whole of a YAML prompt.
+#[derive(Debug, Default, Deserialize)]
+struct PromptSpec {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Vec<PromptArgument>,
+    #[serde(default)]
+    messages: Vec<MessageSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageSpec {
+    role: Role,
+    content: String,
+}
+
+/// A parsed prompt file.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    /// Metadata returned by `prompts/list`.
+    pub info: PromptInfo,
+    /// Message templates, in order.
+    pub messages: Vec<(Role, String)>,
+}
+
+impl PromptTemplate {
+    /// Parse a prompt file's content.
+    ///
+    /// `yaml` selects the YAML format; otherwise the content is markdown
+    /// with optional front matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the front matter or YAML is invalid,
+    /// or if the prompt has no messages.
+    pub fn parse(name: &str, content: &str, yaml: bool) -> Result<Self> {
+        let (spec, body) = if yaml {
+            (parse_spec(name, content)?, None)
+        } else {
+            match split_front_matter(content) {
+                Some((front_matter, body)) => (parse_spec(name, front_matter)?, Some(body)),
+                None => (PromptSpec::default(), Some(content)),
+            }
+        };
+
+        let mut messages: Vec<(Role, String)> = spec
+            .messages
+            .into_iter()
+            .map(|m| (m.role, m.content))
+            .collect();
+        if let Some(body) = body.map(str::trim).filter(|b| !b.is_empty()) {
+            messages.push((Role::User, body.to_string()));
+        }
+        if messages.is_empty() {
+            return Err(Error::validation(format!(
+                "Prompt '{}' has no messages",
+                name
+            )));
+        }
+
+        let mut info = PromptInfo::new(name);
+        info.title = spec.title;
+        info.description = spec.description;
+        if !spec.arguments.is_empty() {
+            info.arguments = Some(spec.arguments);
+        }
+        Ok(Self { info, messages })
+    }
+
+    /// Fill in the message templates with `args`.
+    ///
+    /// Missing optional arguments render as empty strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_PARAMS` if a required argument is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::prompt_dir::PromptTemplate;
+    /// use std::collections::HashMap;
+    ///
+    /// let template = PromptTemplate::parse(
+    ///     "greet",
+    ///     "---\narguments:\n  - name: who\n    required: true\n---\nSay hello to {{who}}.",
+    ///     false,
+    /// )?;
+    /// let args = HashMap::from([("who".to_string(), "Ada".to_string())]);
+    /// let result = template.render(&args)?;
+    /// assert_eq!(result.messages.len(), 1);
+    /// assert!(template.render(&HashMap::new()).is_err());
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn render(&self, args: &HashMap<String, String>) -> Result<GetPromptResult> {
+        let declared = self.info.arguments.as_deref().unwrap_or_default();
+        for argument in declared.iter().filter(|a| a.required) {
+            if !args.contains_key(&argument.name) {
+                return Err(Error::invalid_params(format!(
+                    "Missing required argument '{}' for prompt '{}'",
+                    argument.name, self.info.name
+                )));
+            }
+        }
+
+        let messages = self
+            .messages
+            .iter()
+            .map(|(role, template)| {
+                let text = substitute(template, declared, args);
+                PromptMessage::new(*role, Content::text(text))
+            })
+            .collect();
+        Ok(GetPromptResult::new(
+            messages,
+            self.info.description.clone(),
+        ))
+    }
+}
+
+/// Split `---`-delimited front matter from a markdown body.
+fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn parse_spec(name: &str, yaml: &str) -> Result<PromptSpec> {
+    if yaml.trim().is_empty() {
+        return Ok(PromptSpec::default());
+    }
+    serde_yaml::from_str(yaml)
+        .map_err(|e| Error::validation(format!("Invalid front matter in prompt '{}': {}", name, e)))
+}
+
+/// Replace `{{ name }}` placeholders of declared arguments.
+fn substitute(
+    template: &str,
+    declared: &[PromptArgument],
+    args: &HashMap<String, String>,
+) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        out.push_str(&rest[..start]);
+        if declared.iter().any(|a| a.name == key) {
+            out.push_str(args.get(key).map_or("", String::as_str));
+        } else {
+            out.push_str(&rest[start..start + len + 4]);
+        }
+        rest = &rest[start + len + 4..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A prompt backed by a file, re-read on every request.
+#[derive(Debug, Clone)]
+pub struct FilePrompt {
+    name: String,
+    path: PathBuf,
+}
+
+impl FilePrompt {
+    /// Create a prompt named `name` from the file at `path`.
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+        }
+    }
+
+    /// Read and parse the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the file cannot be read or parsed.
+    pub fn load(&self) -> Result<PromptTemplate> {
+        let content = std::fs::read_to_string(&self.path).map_err(|e| {
+            Error::validation(format!(
+                "Cannot read prompt file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+        let yaml = matches!(
+            self.path.extension().and_then(|e| e.to_str()),
+            Some("yaml" | "yml")
+        );
+        PromptTemplate::parse(&self.name, &content, yaml)
+            .map_err(|e| Error::validation(format!("{} ({})", e, self.path.display())))
+    }
+}
+
+#[async_trait]
+impl PromptHandler for FilePrompt {
+    async fn handle(
+        &self,
+        args: HashMap<String, String>,
+        _extra: RequestHandlerExtra,
+    ) -> Result<GetPromptResult> {
+        self.load()?.render(&args)
+    }
+
+    fn metadata(&self) -> Option<PromptInfo> {
+        match self.load() {
+            Ok(template) => Some(template.info),
+            Err(e) => {
+                tracing::warn!("Failed to load prompt '{}': {}", self.name, e);
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(result: &GetPromptResult, index: usize) -> &str {
+        match &result.messages[index].content {
+            Content::Text { text } => text,
+            other => panic!("expected text content, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_markdown_prompt() {
+        let template = PromptTemplate::parse(
+            "review",
+            "---\ndescription: Review code\narguments:\n  - name: language\n    required: true\n  - name: focus\n---\n\nReview this {{ language }} code. Focus: {{focus}}. Keep {{literal}}.\n",
+            false,
+        )
+        .unwrap();
+        assert_eq!(template.info.description.as_deref(), Some("Review code"));
+        let arguments = template.info.arguments.as_ref().unwrap();
+        assert_eq!(arguments.len(), 2);
+        assert!(arguments[0].required);
+        assert!(!arguments[1].required);
+
+        let args = HashMap::from([("language".to_string(), "Rust".to_string())]);
+        let result = template.render(&args).unwrap();
+        assert_eq!(result.messages[0].role, Role::User);
+        assert_eq!(
+            text(&result, 0),
+            "Review this Rust code. Focus: . Keep {{literal}}."
+        );
+
+        let err = template.render(&HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("language"));
+    }
+
+    #[test]
+    fn test_markdown_without_front_matter() {
+        let template = PromptTemplate::parse("plain", "Just say hi.", false).unwrap();
+        assert!(template.info.arguments.is_none());
+        assert_eq!(
+            template.messages,
+            vec![(Role::User, "Just say hi.".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_yaml_prompt() {
+        let template = PromptTemplate::parse(
+            "summarize",
+            "description: Summarize\narguments:\n  - name: text\n    required: true\nmessages:\n  - role: assistant\n    content: I summarize.\n  - role: user\n    content: \"Summarize: {{text}}\"\n",
+            true,
+        )
+        .unwrap();
+        let args = HashMap::from([("text".to_string(), "a long story".to_string())]);
+        let result = template.render(&args).unwrap();
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.messages[0].role, Role::Assistant);
+        assert_eq!(text(&result, 1), "Summarize: a long story");
+    }
+
+    #[test]
+    fn test_invalid_prompts() {
+        let err = PromptTemplate::parse("empty", "---\ndescription: x\n---\n", false).unwrap_err();
+        assert!(err.to_string().contains("no messages"));
+        let err = PromptTemplate::parse("bad", "arguments: 3", true).unwrap_err();
+        assert!(err.to_string().contains("Invalid front matter"));
+    }
+
+    #[tokio::test]
+    async fn test_prompt_dir_discovers_and_hot_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("greet.md"), "Hello {{who}}").unwrap();
+        std::fs::write(
+            dir.path().join("bye.yaml"),
+            "messages:\n  - role: user\n    content: Bye\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let prompts = PromptDir::new(dir.path()).prompts().unwrap();
+        let names: Vec<_> = prompts.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["bye", "greet"]);
+
+        let greet = &prompts[1].1;
+        std::fs::write(
+            dir.path().join("greet.md"),
+            "---\ndescription: Greets\narguments:\n  - name: who\n---\nHi {{who}}!",
+        )
+        .unwrap();
+        assert_eq!(
+            greet.metadata().unwrap().description.as_deref(),
+            Some("Greets")
+        );
+        let args = HashMap::from([("who".to_string(), "Ada".to_string())]);
+        let result = greet
+            .handle(args, RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        assert_eq!(text(&result, 0), "Hi Ada!");
+    }
+
+    #[test]
+    fn test_prompt_dir_rejects_duplicates_and_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("review.md"), "Review").unwrap();
+        std::fs::write(dir.path().join("review.yml"), "messages: []").unwrap();
+        let err = PromptDir::new(dir.path()).discover().unwrap_err();
+        assert!(err.to_string().contains("defined twice"));
+
+        std::fs::remove_file(dir.path().join("review.md")).unwrap();
+        let err = PromptDir::new(dir.path()).prompts().unwrap_err();
+        assert!(err.to_string().contains("review.yml"));
+
+        assert!(PromptDir::new(dir.path().join("missing"))
+            .discover()
+            .is_err());
+    }
+}
@@ -0,0 +1,537 @@
+//! Token-bucket rate limiting for tool calls.
+//!
+//! [`RateLimiter`] is a [`ToolMiddleware`], so it runs inside the server core
+//! and applies the same way over stdio, streamable HTTP, and any other
+//! transport. Calls are counted against token buckets keyed by
+//! [`RateLimitKey`]: one bucket for the whole server, per tool, per session,
+//! or per authenticated user. Tools can get their own quota with
+//! [`RateLimiter::with_tool_limit`].
+//!
+//! A rejected call fails with a `RATE_LIMITED` (-32005) error whose `data`
+//! tells the client when to retry:
+//!
+//! ```json
+//! {"retryAfter": 2, "retryAfterMs": 1500, "limit": 10, "tool": "search"}
+//! ```
+//!
+//! `retryAfter` is in whole seconds, rounded up, like the HTTP `Retry-After`
+//! header. Use [`RateLimiter::with_rejection`] to return a different error.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::rate_limit::{Quota, RateLimitKey, RateLimiter};
+//! use pmcp::Server;
+//! use std::sync::Arc;
+//!
+//! // Each user gets 60 calls per minute with bursts of 10; `search` is
+//! // limited to 5 calls per second per user on top of that.
+//! let limiter = RateLimiter::new(Quota::per_minute(60).with_burst(10))
+//!     .keyed_by(RateLimitKey::User)
+//!     .with_tool_limit("search", Quota::per_second(5));
+//!
+//! let server = Server::builder()
+//!     .name("rate-limited")
+//!     .version("1.0.0")
+//!     .tool_middleware(Arc::new(limiter))
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::tool_middleware::{ToolContext, ToolMiddleware};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Buckets kept before full (idle) buckets are dropped.
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+/// Key used for calls without a session or authenticated user.
+///
+/// Single-client transports such as stdio have neither, so all their calls
+/// share one bucket.
+const LOCAL_KEY: &str = "local";
+
+/// How many calls are allowed, and how fast the allowance refills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    capacity: u32,
+    refill_every: Duration,
+}
+
+impl Quota {
+    /// Allow `calls` calls per `period`, refilled evenly over the period.
+    ///
+    /// The burst size defaults to `calls`. A `calls` value of zero is treated
+    /// as one.
+    pub fn new(calls: u32, period: Duration) -> Self {
+        let calls = calls.max(1);
+        Self {
+            capacity: calls,
+            refill_every: period / calls,
+        }
+    }
+
+    /// Allow `calls` calls per second.
+    pub fn per_second(calls: u32) -> Self {
+        Self::new(calls, Duration::from_secs(1))
+    }
+
+    /// Allow `calls` calls per minute.
+    pub fn per_minute(calls: u32) -> Self {
+        Self::new(calls, Duration::from_secs(60))
+    }
+
+    /// Allow `calls` calls per hour.
+    pub fn per_hour(calls: u32) -> Self {
+        Self::new(calls, Duration::from_secs(3600))
+    }
+
+    /// Set how many calls may be made at once after an idle period.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.capacity = burst.max(1);
+        self
+    }
+
+    /// Maximum number of calls allowed at once.
+    pub fn burst(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Time for one call's allowance to refill.
+    pub fn refill_every(&self) -> Duration {
+        self.refill_every
+    }
+}
+
+/// What calls are counted together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// One bucket for all calls.
+    Global,
+    /// One bucket per tool.
+    Tool,
+    /// One bucket per session ([`RequestHandlerExtra::session_id`]).
+    Session,
+    /// One bucket per authenticated user ([`AuthContext::subject`](crate::server::auth::AuthContext)).
+    User,
+    /// One bucket per session and tool.
+    SessionAndTool,
+    /// One bucket per authenticated user and tool.
+    UserAndTool,
+}
+
+impl RateLimitKey {
+    fn per_tool(self) -> bool {
+        matches!(self, Self::Tool | Self::SessionAndTool | Self::UserAndTool)
+    }
+
+    fn client(self, extra: &RequestHandlerExtra, context: &ToolContext) -> String {
+        match self {
+            Self::Global | Self::Tool => "*".to_string(),
+            Self::Session | Self::SessionAndTool => {
+                let session = extra
+                    .session_id
+                    .as_deref()
+                    .or(context.session_id.as_deref())
+                    .unwrap_or(LOCAL_KEY);
+                format!("session:{}", session)
+            },
+            Self::User | Self::UserAndTool => {
+                let user = extra
+                    .auth_context
+                    .as_ref()
+                    .filter(|ctx| ctx.authenticated)
+                    .map_or(LOCAL_KEY, |ctx| ctx.subject.as_str());
+                format!("user:{}", user)
+            },
+        }
+    }
+}
+
+/// Details of a rejected call, passed to the rejection error factory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitRejection {
+    /// Tool that was called.
+    pub tool: String,
+    /// Session or user the call was counted against (`*` when global).
+    pub key: String,
+    /// Burst size of the exhausted quota.
+    pub limit: u32,
+    /// Time until the next call would be allowed.
+    pub retry_after: Duration,
+}
+
+impl RateLimitRejection {
+    /// `retry_after` in whole seconds, rounded up (at least 1), as used by the
+    /// HTTP `Retry-After` header.
+    pub fn retry_after_secs(&self) -> u64 {
+        let secs = self.retry_after.as_secs();
+        let secs = if self.retry_after.subsec_nanos() > 0 {
+            secs + 1
+        } else {
+            secs
+        };
+        secs.max(1)
+    }
+
+    /// The error returned when no custom rejection is configured.
+    pub fn default_error(&self) -> Error {
+        Error::Protocol {
+            code: ErrorCode::RATE_LIMITED,
+            message: format!(
+                "Rate limit exceeded for tool '{}'; retry after {}s",
+                self.tool,
+                self.retry_after_secs()
+            ),
+            data: Some(self.metadata()),
+        }
+    }
+
+    /// Retry metadata: `retryAfter` (seconds), `retryAfterMs`, `limit` and
+    /// `tool`.
+    pub fn metadata(&self) -> Value {
+        json!({
+            "retryAfter": self.retry_after_secs(),
+            "retryAfterMs": u64::try_from(self.retry_after.as_millis()).unwrap_or(u64::MAX),
+            "limit": self.limit,
+            "tool": self.tool,
+        })
+    }
+}
+
+type RejectionFn = Arc<dyn Fn(&RateLimitRejection) -> Error + Send + Sync>;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn full(quota: &Quota, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(quota.capacity),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, quota: &Quota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        let refilled = elapsed.as_secs_f64() / quota.refill_every.as_secs_f64().max(f64::EPSILON);
+        self.tokens = (self.tokens + refilled).min(f64::from(quota.capacity));
+        self.updated = now;
+    }
+
+    /// Take one token, or return how long until one is available.
+    fn take(&mut self, quota: &Quota, now: Instant) -> std::result::Result<(), Duration> {
+        self.refill(quota, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(quota.refill_every.mul_f64(1.0 - self.tokens))
+        }
+    }
+
+    fn is_full(&self, quota: &Quota, now: Instant) -> bool {
+        let mut bucket = *self;
+        bucket.refill(quota, now);
+        bucket.tokens >= f64::from(quota.capacity)
+    }
+}
+
+/// Tool middleware enforcing token-bucket rate limits.
+///
+/// See the [module documentation](self) for the rejection format.
+pub struct RateLimiter {
+    default_quota: Option<Quota>,
+    tool_quotas: HashMap<String, Quota>,
+    key: RateLimitKey,
+    priority: i32,
+    rejection: Option<RejectionFn>,
+    buckets: Mutex<HashMap<(String, String), Bucket>>,
+}
+
+impl RateLimiter {
+    /// Limit every tool to `quota`, counted globally until
+    /// [`keyed_by`](Self::keyed_by) says otherwise.
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            default_quota: Some(quota),
+            ..Self::unlimited()
+        }
+    }
+
+    /// A limiter that only limits tools given a quota with
+    /// [`with_tool_limit`](Self::with_tool_limit).
+    pub fn unlimited() -> Self {
+        Self {
+            default_quota: None,
+            tool_quotas: HashMap::new(),
+            key: RateLimitKey::Global,
+            priority: 15,
+            rejection: None,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Choose what calls are counted together.
+    pub fn keyed_by(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Give `tool` its own quota and bucket, replacing the default quota for
+    /// that tool.
+    pub fn with_tool_limit(mut self, tool: impl Into<String>, quota: Quota) -> Self {
+        self.tool_quotas.insert(tool.into(), quota);
+        self
+    }
+
+    /// Build the error returned for rejected calls.
+    ///
+    /// ```rust
+    /// use pmcp::server::rate_limit::{Quota, RateLimiter};
+    /// use pmcp::Error;
+    ///
+    /// let limiter = RateLimiter::new(Quota::per_second(1)).with_rejection(|rejection| {
+    ///     Error::validation(format!("Slow down: try again in {}s", rejection.retry_after_secs()))
+    /// });
+    /// ```
+    pub fn with_rejection<F>(mut self, rejection: F) -> Self
+    where
+        F: Fn(&RateLimitRejection) -> Error + Send + Sync + 'static,
+    {
+        self.rejection = Some(Arc::new(rejection));
+        self
+    }
+
+    /// Set the middleware priority (default 15, after authentication).
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    fn quota_for(&self, tool: &str) -> Option<(Quota, bool)> {
+        match self.tool_quotas.get(tool) {
+            Some(quota) => Some((*quota, true)),
+            None => self.default_quota.map(|quota| (quota, self.key.per_tool())),
+        }
+    }
+
+    /// Count one call to `tool` from `client`, rejecting it if its bucket is
+    /// empty.
+    fn check(&self, tool: &str, client: String, now: Instant) -> Result<()> {
+        let Some((quota, per_tool)) = self.quota_for(tool) else {
+            return Ok(());
+        };
+        let bucket_tool = if per_tool { tool } else { "*" };
+
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_IDLE_BUCKETS {
+            buckets.retain(|(_, tool), bucket| {
+                let quota = self
+                    .tool_quotas
+                    .get(tool)
+                    .or(self.default_quota.as_ref())
+                    .copied()
+                    .unwrap_or(quota);
+                !bucket.is_full(&quota, now)
+            });
+        }
+        let bucket = buckets
+            .entry((client.clone(), bucket_tool.to_string()))
+            .or_insert_with(|| Bucket::full(&quota, now));
+
+        bucket.take(&quota, now).map_err(|retry_after| {
+            let rejection = RateLimitRejection {
+                tool: tool.to_string(),
+                key: client,
+                limit: quota.capacity,
+                retry_after,
+            };
+            tracing::warn!(
+                tool = tool,
+                key = rejection.key.as_str(),
+                retry_after_ms = rejection.retry_after.as_millis() as u64,
+                "Tool call rate limited"
+            );
+            match &self.rejection {
+                Some(rejection_fn) => rejection_fn(&rejection),
+                None => rejection.default_error(),
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("default_quota", &self.default_quota)
+            .field("tool_quotas", &self.tool_quotas)
+            .field("key", &self.key)
+            .field("priority", &self.priority)
+            .field("custom_rejection", &self.rejection.is_some())
+            .field("buckets", &self.buckets.lock().len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for RateLimiter {
+    async fn on_request(
+        &self,
+        tool_name: &str,
+        _args: &mut Value,
+        extra: &mut RequestHandlerExtra,
+        context: &ToolContext,
+    ) -> Result<()> {
+        self.check(tool_name, self.key.client(extra, context), Instant::now())
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::auth::AuthContext;
+    use crate::server::tool_middleware::ToolMiddlewareChain;
+    use tokio_util::sync::CancellationToken;
+
+    fn user(subject: &str) -> RequestHandlerExtra {
+        RequestHandlerExtra::new("req".to_string(), CancellationToken::new()).with_auth_context(
+            Some(AuthContext {
+                subject: subject.to_string(),
+                scopes: vec![],
+                claims: HashMap::new(),
+                token: None,
+                client_id: None,
+                expires_at: None,
+                authenticated: true,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let limiter = RateLimiter::new(Quota::per_second(2));
+        let start = Instant::now();
+
+        assert!(limiter.check("echo", "*".into(), start).is_ok());
+        assert!(limiter.check("echo", "*".into(), start).is_ok());
+        assert!(limiter.check("echo", "*".into(), start).is_err());
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check("echo", "*".into(), later).is_ok());
+        assert!(limiter.check("echo", "*".into(), later).is_err());
+    }
+
+    #[test]
+    fn test_rejection_carries_retry_metadata() {
+        let limiter = RateLimiter::new(Quota::per_minute(1));
+        let now = Instant::now();
+        limiter.check("search", "*".into(), now).unwrap();
+
+        let err = limiter.check("search", "*".into(), now).unwrap_err();
+        assert!(err.is_error_code(ErrorCode::RATE_LIMITED));
+        match err {
+            Error::Protocol {
+                data: Some(data), ..
+            } => {
+                assert_eq!(data["retryAfter"], 60);
+                assert_eq!(data["limit"], 1);
+                assert_eq!(data["tool"], "search");
+            },
+            other => panic!("expected protocol error with data, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tool_limit_uses_own_bucket() {
+        let limiter =
+            RateLimiter::new(Quota::per_second(1)).with_tool_limit("search", Quota::per_second(3));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check("search", "*".into(), now).is_ok());
+        }
+        assert!(limiter.check("search", "*".into(), now).is_err());
+        // The default bucket is untouched by `search`.
+        assert!(limiter.check("echo", "*".into(), now).is_ok());
+        assert!(limiter.check("other", "*".into(), now).is_err());
+    }
+
+    #[test]
+    fn test_unlimited_only_limits_configured_tools() {
+        let limiter = RateLimiter::unlimited().with_tool_limit("search", Quota::per_hour(1));
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert!(limiter.check("echo", "*".into(), now).is_ok());
+        }
+        assert!(limiter.check("search", "*".into(), now).is_ok());
+        assert!(limiter.check("search", "*".into(), now).is_err());
+    }
+
+    #[test]
+    fn test_retry_after_secs_rounds_up() {
+        let rejection = RateLimitRejection {
+            tool: "t".into(),
+            key: "*".into(),
+            limit: 1,
+            retry_after: Duration::from_millis(1200),
+        };
+        assert_eq!(rejection.retry_after_secs(), 2);
+
+        let rejection = RateLimitRejection {
+            retry_after: Duration::from_millis(10),
+            ..rejection
+        };
+        assert_eq!(rejection.retry_after_secs(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_users_have_separate_buckets() {
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(
+            RateLimiter::new(Quota::per_hour(1)).keyed_by(RateLimitKey::User),
+        ));
+        let context = ToolContext::new("echo", "req");
+
+        for (subject, allowed) in [("alice", true), ("bob", true), ("alice", false)] {
+            let mut extra = user(subject);
+            let result = chain
+                .process_request("echo", &mut json!({}), &mut extra, &context)
+                .await;
+            assert_eq!(result.is_ok(), allowed, "call by {}", subject);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_rejection_error() {
+        let limiter = RateLimiter::new(Quota::per_hour(1)).with_rejection(|r| {
+            Error::validation(format!("busy, retry in {}s", r.retry_after_secs()))
+        });
+        let context = ToolContext::new("echo", "req");
+        let mut extra = RequestHandlerExtra::new("req".to_string(), CancellationToken::new());
+
+        limiter
+            .on_request("echo", &mut json!({}), &mut extra, &context)
+            .await
+            .unwrap();
+        let err = limiter
+            .on_request("echo", &mut json!({}), &mut extra, &context)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("busy, retry in 3600s"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Resource profiles for memory-constrained deployments.
+//!
+//! A [`ResourceProfile`] bundles the limits that decide how much memory the
+//! HTTP transport holds per request: how many requests are processed at once,
+//! how large a request body may be, and how many responses are kept in the
+//! in-memory event store for resumability.
+//!
+//! [`ResourceProfile::standard()`] matches the long-standing defaults.
+//! [`ResourceProfile::serverless()`] is tuned for small Lambda and Workers
+//! memory configurations, where buffering many large payloads at once is the
+//! usual cause of out-of-memory failures under load.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use pmcp::server::resource_profile::ResourceProfile;
+//! use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+//!
+//! // Explicit serverless tuning
+//! let config = StreamableHttpServerConfig::from_profile(ResourceProfile::serverless());
+//!
+//! // Pick serverless or standard from the runtime environment
+//! let config = StreamableHttpServerConfig::from_profile(ResourceProfile::detect());
+//! ```
+
+/// Environment variables set by the serverless platforms pmcp deploys to.
+const SERVERLESS_ENV_VARS: &[&str] = &[
+    "AWS_LAMBDA_FUNCTION_NAME",
+    "VERCEL",
+    "DENO_DEPLOYMENT_ID",
+    "CLOUDFLARE_WORKER",
+    "FUNCTIONS_WORKER_RUNTIME",
+];
+
+/// Resource limits applied by the HTTP transport.
+///
+/// Limits set to `None` are not enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceProfile {
+    /// Maximum number of requests processed concurrently.
+    ///
+    /// Requests beyond the limit are rejected with `503 Service Unavailable`
+    /// instead of being queued in memory.
+    pub max_concurrent_requests: Option<usize>,
+    /// Maximum accepted request body size in bytes.
+    ///
+    /// Larger bodies are rejected with `413 Payload Too Large` before being
+    /// buffered.
+    pub max_request_body_bytes: Option<usize>,
+    /// Maximum number of events kept in the in-memory event store.
+    ///
+    /// The oldest events are evicted first. `Some(0)` disables the event
+    /// store entirely.
+    pub event_store_max_events: Option<usize>,
+    /// Largest serialized event, in bytes, that the event store will cache.
+    ///
+    /// Larger responses are still delivered but cannot be replayed.
+    pub event_store_max_event_bytes: Option<usize>,
+    /// Whether the transport tracks sessions.
+    pub sessions: bool,
+    /// Whether responses are plain JSON rather than SSE streams.
+    pub json_responses: bool,
+}
+
+impl Default for ResourceProfile {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl ResourceProfile {
+    /// Profile matching the default transport behavior: stateful sessions,
+    /// SSE responses, an unbounded event store, and no request limits.
+    pub const fn standard() -> Self {
+        Self {
+            max_concurrent_requests: None,
+            max_request_body_bytes: None,
+            event_store_max_events: None,
+            event_store_max_event_bytes: None,
+            sessions: true,
+            json_responses: false,
+        }
+    }
+
+    /// Profile for low-memory serverless targets such as AWS Lambda and
+    /// Cloudflare Workers.
+    ///
+    /// Stateless with JSON responses, no event store (there is no long-lived
+    /// process to resume against), request bodies capped at 4 MiB, and at
+    /// most 8 requests in flight per instance.
+    pub const fn serverless() -> Self {
+        Self {
+            max_concurrent_requests: Some(8),
+            max_request_body_bytes: Some(4 * 1024 * 1024),
+            event_store_max_events: Some(0),
+            event_store_max_event_bytes: Some(0),
+            sessions: false,
+            json_responses: true,
+        }
+    }
+
+    /// Select [`serverless()`](Self::serverless) when running on a known
+    /// serverless platform, otherwise [`standard()`](Self::standard).
+    pub fn detect() -> Self {
+        if Self::is_serverless_environment() {
+            Self::serverless()
+        } else {
+            Self::standard()
+        }
+    }
+
+    /// Returns `true` if a serverless platform environment variable is set.
+    pub fn is_serverless_environment() -> bool {
+        SERVERLESS_ENV_VARS
+            .iter()
+            .any(|var| std::env::var_os(var).is_some())
+    }
+
+    /// Set the maximum number of concurrently processed requests.
+    pub const fn with_max_concurrent_requests(mut self, limit: Option<usize>) -> Self {
+        self.max_concurrent_requests = limit;
+        self
+    }
+
+    /// Set the maximum accepted request body size in bytes.
+    pub const fn with_max_request_body_bytes(mut self, limit: Option<usize>) -> Self {
+        self.max_request_body_bytes = limit;
+        self
+    }
+
+    /// Set the event store capacity and per-event size limit.
+    pub const fn with_event_store_limits(
+        mut self,
+        max_events: Option<usize>,
+        max_event_bytes: Option<usize>,
+    ) -> Self {
+        self.event_store_max_events = max_events;
+        self.event_store_max_event_bytes = max_event_bytes;
+        self
+    }
+
+    /// Returns `true` if this profile keeps an event store.
+    pub const fn event_store_enabled(&self) -> bool {
+        !matches!(self.event_store_max_events, Some(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_matches_default_behavior() {
+        let profile = ResourceProfile::standard();
+        assert_eq!(profile, ResourceProfile::default());
+        assert!(profile.sessions);
+        assert!(!profile.json_responses);
+        assert!(profile.event_store_enabled());
+        assert_eq!(profile.max_concurrent_requests, None);
+        assert_eq!(profile.max_request_body_bytes, None);
+    }
+
+    #[test]
+    fn test_serverless_is_bounded() {
+        let profile = ResourceProfile::serverless();
+        assert!(!profile.sessions);
+        assert!(profile.json_responses);
+        assert!(!profile.event_store_enabled());
+        assert_eq!(profile.max_concurrent_requests, Some(8));
+        assert_eq!(profile.max_request_body_bytes, Some(4 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_builder_overrides() {
+        let profile = ResourceProfile::serverless()
+            .with_max_concurrent_requests(Some(2))
+            .with_max_request_body_bytes(None)
+            .with_event_store_limits(Some(100), Some(1024));
+        assert_eq!(profile.max_concurrent_requests, Some(2));
+        assert_eq!(profile.max_request_body_bytes, None);
+        assert!(profile.event_store_enabled());
+        assert_eq!(profile.event_store_max_event_bytes, Some(1024));
+    }
+}
@@ -0,0 +1,233 @@
+//! Byte-range and conditional reads for `resources/read`.
+//!
+//! When a `resources/read` request carries `_meta["pmcp/range"]` or
+//! `_meta["pmcp/ifNoneMatch"]` (PMCP extensions), the server reads the resource through the normal
+//! handler and then slices its text, so every resource handler supports
+//! incremental reads without changes. The result carries
+//! `_meta["pmcp/range"]` ([`ResourceRangeInfo`]) with the slice position,
+//! total length, next offset, and an etag for the whole resource.
+//!
+//! Requests without either key are passed through untouched.
+
+use crate::error::{Error, Result};
+use crate::types::{
+    Content, ReadResourceRequest, ReadResourceResult, ResourceRangeInfo, RESOURCE_RANGE_META_KEY,
+};
+
+/// Apply the request's range and `ifNoneMatch` to a handler result.
+///
+/// # Errors
+///
+/// Returns an invalid-params error if the range is empty or starts past the
+/// end, or if the resource is not a single text content item.
+pub(crate) fn apply(
+    req: &ReadResourceRequest,
+    mut result: ReadResourceResult,
+) -> Result<ReadResourceResult> {
+    if req.range().is_none() && req.if_none_match().is_none() {
+        return Ok(result);
+    }
+
+    let [content] = result.contents.as_mut_slice() else {
+        return Err(Error::invalid_params(format!(
+            "Ranged reads need a single content item, but '{}' has {}",
+            req.uri,
+            result.contents.len()
+        )));
+    };
+    let text = match content {
+        Content::Text { text } => text,
+        Content::Resource {
+            text: Some(text), ..
+        } => text,
+        _ => {
+            return Err(Error::invalid_params(format!(
+                "Ranged reads are only supported for text resources, and '{}' is not one",
+                req.uri
+            )))
+        },
+    };
+
+    let total = text.len();
+    let etag = etag(text);
+
+    if req.if_none_match() == Some(etag.as_str()) {
+        result.contents.clear();
+        return Ok(with_info(
+            result,
+            ResourceRangeInfo {
+                offset: 0,
+                length: 0,
+                total_length: total as u64,
+                next_offset: None,
+                etag,
+                not_modified: true,
+            },
+        ));
+    }
+
+    let (start, end) = match req.range() {
+        Some(range) => {
+            if range.length == Some(0) {
+                return Err(Error::invalid_params("Range length must be greater than 0"));
+            }
+            let offset = usize::try_from(range.offset).unwrap_or(usize::MAX);
+            if offset > total {
+                return Err(Error::invalid_params(format!(
+                    "Range offset {} is past the end of '{}' ({} bytes)",
+                    range.offset, req.uri, total
+                )));
+            }
+            let end = range
+                .length
+                .and_then(|len| usize::try_from(len).ok())
+                .map_or(total, |len| offset.saturating_add(len).min(total));
+            char_bounds(text, offset, end)
+        },
+        None => (0, total),
+    };
+
+    if (start, end) != (0, total) {
+        *text = text[start..end].to_string();
+    }
+
+    Ok(with_info(
+        result,
+        ResourceRangeInfo {
+            offset: start as u64,
+            length: (end - start) as u64,
+            total_length: total as u64,
+            next_offset: (end < total).then_some(end as u64),
+            etag,
+            not_modified: false,
+        },
+    ))
+}
+
+/// Snap a byte range to character boundaries.
+///
+/// The start moves forward and the end moves back, but a non-empty range
+/// always keeps at least one whole character so sequential reads make progress.
+fn char_bounds(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let mut start = start;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let mut snapped = end.max(start);
+    while !text.is_char_boundary(snapped) {
+        snapped -= 1;
+    }
+    if snapped == start && start < end {
+        snapped = start + text[start..].chars().next().map_or(0, char::len_utf8);
+    }
+    (start, snapped)
+}
+
+/// Content validator: 64-bit FNV-1a of the text, hex-encoded.
+fn etag(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn with_info(mut result: ReadResourceResult, info: ResourceRangeInfo) -> ReadResourceResult {
+    if let Ok(value) = serde_json::to_value(info) {
+        result
+            ._meta
+            .get_or_insert_with(serde_json::Map::new)
+            .insert(RESOURCE_RANGE_META_KEY.to_string(), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(req: &ReadResourceRequest, text: &str) -> Result<ReadResourceResult> {
+        apply(req, ReadResourceResult::new(vec![Content::text(text)]))
+    }
+
+    #[test]
+    fn test_no_range_passes_through() {
+        let result = read(&ReadResourceRequest::new("file:///log"), "hello").unwrap();
+        assert_eq!(result.text(), Some("hello"));
+        assert!(result._meta.is_none());
+    }
+
+    #[test]
+    fn test_sequential_ranges_cover_text() {
+        let text = "line 1\nline 2\nline 3\n";
+        let mut offset = 0;
+        let mut collected = String::new();
+        loop {
+            let req = ReadResourceRequest::new("file:///log").with_range(offset, Some(8));
+            let result = read(&req, text).unwrap();
+            let info = result.range_info().unwrap();
+            assert_eq!(info.offset, offset);
+            assert_eq!(info.total_length, text.len() as u64);
+            collected.push_str(result.text().unwrap());
+            match info.next_offset {
+                Some(next) => offset = next,
+                None => break,
+            }
+        }
+        assert_eq!(collected, text);
+    }
+
+    #[test]
+    fn test_range_snaps_to_char_boundaries() {
+        let text = "aé€b"; // 1 + 2 + 3 + 1 bytes
+        let req = ReadResourceRequest::new("x").with_range(0, Some(2));
+        let result = read(&req, text).unwrap();
+        assert_eq!(result.text(), Some("a"));
+        assert_eq!(result.range_info().unwrap().next_offset, Some(1));
+
+        // A length shorter than the next character still returns it whole
+        let req = ReadResourceRequest::new("x").with_range(3, Some(1));
+        let result = read(&req, text).unwrap();
+        assert_eq!(result.text(), Some("€"));
+        assert_eq!(result.range_info().unwrap().next_offset, Some(6));
+
+        // An offset inside a character starts at the next one
+        let req = ReadResourceRequest::new("x").with_range(2, None);
+        let result = read(&req, text).unwrap();
+        assert_eq!(result.text(), Some("€b"));
+        assert_eq!(result.range_info().unwrap().offset, 3);
+    }
+
+    #[test]
+    fn test_range_validation() {
+        let req = ReadResourceRequest::new("x").with_range(10, None);
+        assert!(read(&req, "short").is_err());
+
+        let req = ReadResourceRequest::new("x").with_range(0, Some(0));
+        assert!(read(&req, "short").is_err());
+
+        let req = ReadResourceRequest::new("x").with_range(5, None);
+        let result = read(&req, "short").unwrap();
+        assert_eq!(result.text(), Some(""));
+        assert_eq!(result.range_info().unwrap().next_offset, None);
+
+        let two = ReadResourceResult::new(vec![Content::text("a"), Content::text("b")]);
+        assert!(apply(&ReadResourceRequest::new("x").with_range(0, None), two).is_err());
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let req = ReadResourceRequest::new("x").with_range(0, Some(2));
+        let etag = read(&req, "hello").unwrap().range_info().unwrap().etag;
+
+        let req = ReadResourceRequest::new("x").with_if_none_match(etag.clone());
+        let result = read(&req, "hello").unwrap();
+        assert!(result.contents.is_empty());
+        assert!(result.range_info().unwrap().not_modified);
+
+        let result = read(&req, "hello!").unwrap();
+        let info = result.range_info().unwrap();
+        assert_eq!(result.text(), Some("hello!"));
+        assert!(!info.not_modified);
+        assert_ne!(info.etag, etag);
+    }
+}
@@ -1,4 +1,11 @@
 //! Resource watcher for monitoring file system changes.
+//!
+//! [`FileResourceWatcher`] implements the subscriptions
+//! [`ResourceWatcher`](crate::server::subscriptions::ResourceWatcher) trait and
+//! is registered with `ServerBuilder::resource_watcher`, so subscribed clients
+//! receive `notifications/resources/updated` when a file-backed resource or
+//! widget changes. The standalone [`ResourceWatcher`] struct sends
+//! notifications for explicitly added resources to a channel instead.
 
 use crate::error::{Error, ErrorCode, Result};
 use crate::types::{ResourceInfo, ServerNotification};
@@ -458,6 +465,179 @@ impl Default for ResourceWatcherBuilder {
     }
 }
 
+/// Maps a changed path to the URIs of the resources it affects.
+type UriMapper = dyn Fn(&Path) -> Vec<String> + Send + Sync;
+
+/// Filesystem source of resource changes for `resources/subscribe`.
+///
+/// Watches a directory recursively and reports the URIs affected by each
+/// change. Bursts of events (editors often write a file several times) are
+/// coalesced over the debounce window.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use pmcp::server::resource_watcher::FileResourceWatcher;
+/// use pmcp::server::mcp_apps::WidgetDir;
+///
+/// // File-backed resources reported as file:// URIs
+/// let docs = FileResourceWatcher::new("./docs");
+///
+/// // Widgets reported as ui://app/<name>
+/// let widgets = FileResourceWatcher::for_widget_dir(&WidgetDir::new("widgets"));
+///
+/// let server = Server::builder()
+///     .name("app")
+///     .version("1.0.0")
+///     .resource_watcher(docs)
+///     .resource_watcher(widgets)
+///     .build()?;
+/// ```
+pub struct FileResourceWatcher {
+    root: PathBuf,
+    debounce: Duration,
+    uri_mapper: Arc<UriMapper>,
+    /// Keeps the OS watcher alive once started.
+    watcher: std::sync::Mutex<Option<notify::RecommendedWatcher>>,
+}
+
+impl std::fmt::Debug for FileResourceWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileResourceWatcher")
+            .field("root", &self.root)
+            .field("debounce", &self.debounce)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileResourceWatcher {
+    /// Watch `root`, reporting each changed file as a `file://` URI of its
+    /// absolute path.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self::with_uri_mapper(root, |path| vec![format!("file://{}", path.display())])
+    }
+
+    /// Watch `root`, mapping each changed absolute path to resource URIs.
+    pub fn with_uri_mapper<F>(root: impl AsRef<Path>, mapper: F) -> Self
+    where
+        F: Fn(&Path) -> Vec<String> + Send + Sync + 'static,
+    {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            debounce: Duration::from_millis(200),
+            uri_mapper: Arc::new(mapper),
+            watcher: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Watch a widget directory, reporting changes as `ui://app/<name>` URIs.
+    ///
+    /// See [`WidgetDir::uris_for_path`](crate::server::mcp_apps::WidgetDir::uris_for_path)
+    /// for how files map to widgets.
+    #[cfg(feature = "mcp-apps")]
+    pub fn for_widget_dir(widgets: &crate::server::mcp_apps::WidgetDir) -> Self {
+        let root = widgets.path().to_path_buf();
+        let widgets = widgets.clone();
+        Self::with_uri_mapper(root, move |path| widgets.uris_for_path(path))
+    }
+
+    /// Set how long to wait for further changes before reporting (default 200ms).
+    #[must_use]
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Resolve the watch root to an absolute path.
+    fn absolute_root(&self) -> PathBuf {
+        if self.root.is_absolute() {
+            self.root.clone()
+        } else {
+            std::env::current_dir().unwrap_or_default().join(&self.root)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::server::subscriptions::ResourceWatcher for FileResourceWatcher {
+    async fn start(
+        &self,
+        notifier: crate::server::subscriptions::ResourceChangeNotifier,
+    ) -> Result<()> {
+        use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+        let root = self.absolute_root();
+        // Events may report the canonical path (e.g. /private/var on macOS)
+        let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+        let mut watcher = RecommendedWatcher::new(
+            move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        for path in event.paths {
+                            let _ = tx.send(path);
+                        }
+                    }
+                }
+            },
+            Config::default(),
+        )
+        .map_err(|e| Error::internal(format!("Failed to create watcher: {}", e)))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| Error::internal(format!("Failed to watch {}: {}", root.display(), e)))?;
+        *self
+            .watcher
+            .lock()
+            .map_err(|_| Error::internal("Resource watcher lock poisoned"))? = Some(watcher);
+        info!("Watching {} for resource changes", root.display());
+
+        let mapper = self.uri_mapper.clone();
+        let debounce = self.debounce;
+        tokio::spawn(async move {
+            let affected = |path: &Path| -> Vec<String> {
+                let relative = path
+                    .strip_prefix(&canonical_root)
+                    .or_else(|_| path.strip_prefix(&root));
+                match relative {
+                    Ok(relative) => mapper(&root.join(relative)),
+                    Err(_) => Vec::new(),
+                }
+            };
+
+            while let Some(path) = rx.recv().await {
+                let mut changed: std::collections::BTreeSet<String> =
+                    affected(&path).into_iter().collect();
+
+                let window = tokio::time::sleep(debounce);
+                tokio::pin!(window);
+                loop {
+                    tokio::select! {
+                        () = &mut window => break,
+                        next = rx.recv() => match next {
+                            Some(path) => changed.extend(affected(&path)),
+                            None => break,
+                        },
+                    }
+                }
+
+                for uri in changed {
+                    debug!("Resource changed: {}", uri);
+                    if !notifier.notify(uri) {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -529,4 +709,24 @@ mod tests {
         let resources = watcher.get_resources().await;
         assert_eq!(resources.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_file_resource_watcher_reports_changed_files() {
+        use crate::server::subscriptions::{ResourceChangeNotifier, ResourceWatcher as _};
+
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = FileResourceWatcher::new(dir.path()).debounce(Duration::from_millis(50));
+        let (notifier, mut changes) = ResourceChangeNotifier::channel();
+        watcher.start(notifier).await.unwrap();
+
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let uri = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+            .await
+            .expect("change reported")
+            .unwrap();
+        assert!(uri.starts_with("file://"));
+        assert!(uri.ends_with("notes.txt"));
+    }
 }
@@ -2,14 +2,33 @@
 //!
 //! Roots represent directories or files that the server can operate on.
 //! Servers can register roots to inform clients about their working directories.
+//!
+//! Clients that declare the `roots` capability expose their own roots, the
+//! directories the user has opened. [`ClientRoots`] fetches them with
+//! `roots/list` and caches them until the client sends
+//! `notifications/roots/list_changed`. Filesystem tools read them with
+//! [`RequestHandlerExtra::roots`] and confine user-supplied paths with
+//! [`RequestHandlerExtra::resolve_path`] or [`resolve_path`].
+//!
+//! [`RequestHandlerExtra::roots`]: crate::server::cancellation::RequestHandlerExtra::roots
+//! [`RequestHandlerExtra::resolve_path`]: crate::server::cancellation::RequestHandlerExtra::resolve_path
 
-use crate::error::Result;
-use crate::types::{ServerNotification, ServerRequest};
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{JSONRPCResponse, RequestId, ServerNotification, ServerRequest};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::sync::RwLock;
-use tracing::info;
+use tokio::sync::{mpsc, oneshot, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{timeout, Duration};
+use tracing::{debug, info, warn};
+
+/// Monotonically increasing counter for `roots/list` request IDs.
+static ROOTS_REQUEST_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 /// Represents a root directory or file that the server can operate on.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -21,6 +40,23 @@ pub struct Root {
     pub name: Option<String>,
 }
 
+impl Root {
+    /// The local path of a `file://` root, or `None` for other URIs.
+    pub fn path(&self) -> Option<PathBuf> {
+        url::Url::parse(&self.uri).ok()?.to_file_path().ok()
+    }
+
+    /// Whether `path` lies inside this root.
+    ///
+    /// The check is lexical: `.` and `..` are resolved but symlinks are not
+    /// followed. Use [`resolve_path`] to also follow symlinks of existing
+    /// files.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        self.path()
+            .is_some_and(|root| normalize(path.as_ref()).starts_with(normalize(&root)))
+    }
+}
+
 /// Result of listing roots.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListRootsResult {
@@ -178,6 +214,229 @@ impl RootsManager {
     }
 }
 
+/// Resolve `path` and check that it lies inside one of `roots`.
+///
+/// Relative paths are resolved against the first `file://` root. `.` and `..`
+/// are resolved lexically, and when the path exists it is canonicalized so a
+/// symlink cannot point outside the roots. Returns the resolved path.
+/// For stricter rules (hidden files, depth limits, blocked patterns), pass a
+/// root's [`path`](Root::path) to
+/// [`validate_path`](crate::server::path_validation::validate_path).
+///
+/// # Errors
+///
+/// Returns `INVALID_PARAMS` if the path is outside every root, or if it is
+/// relative and there is no `file://` root to resolve it against.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::roots::{resolve_path, Root};
+///
+/// let roots = vec![Root {
+///     uri: "file:///home/user/project".to_string(),
+///     name: None,
+/// }];
+/// assert!(resolve_path(&roots, "/home/user/project/src/main.rs").is_ok());
+/// assert!(resolve_path(&roots, "/home/user/project/../secrets").is_err());
+/// ```
+pub fn resolve_path(roots: &[Root], path: impl AsRef<Path>) -> Result<PathBuf> {
+    let path = path.as_ref();
+    let root_paths: Vec<PathBuf> = roots.iter().filter_map(Root::path).collect();
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        let base = root_paths.first().ok_or_else(|| {
+            Error::invalid_params(format!(
+                "Cannot resolve relative path '{}': the client has no file roots",
+                path.display()
+            ))
+        })?;
+        base.join(path)
+    };
+
+    let resolved = absolute
+        .canonicalize()
+        .unwrap_or_else(|_| normalize(&absolute));
+    let inside = root_paths.iter().any(|root| {
+        let root = root.canonicalize().unwrap_or_else(|_| normalize(root));
+        resolved.starts_with(root)
+    });
+    if inside {
+        Ok(resolved)
+    } else {
+        Err(Error::invalid_params(format!(
+            "Path '{}' is outside the client's roots",
+            path.display()
+        )))
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir => {
+                normalized.pop();
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// The connected client's roots.
+///
+/// Fetched with `roots/list` on first use and cached until
+/// [`invalidate`](Self::invalidate) is called, which the server does when the
+/// client sends `notifications/roots/list_changed`. Each request is sent with
+/// a `roots-<n>` JSON-RPC ID; the client's response is routed back with
+/// [`handle_jsonrpc_response`](Self::handle_jsonrpc_response).
+pub struct ClientRoots {
+    /// Channel for sending requests to the client.
+    request_tx: mpsc::Sender<(RequestId, ServerRequest)>,
+    /// Roots from the last `roots/list` response.
+    cache: RwLock<Option<Vec<Root>>>,
+    /// Bumped on every invalidation, so a fetch that raced with one is not cached.
+    generation: AtomicU64,
+    /// `roots/list` requests waiting for responses.
+    pending: RwLock<HashMap<String, oneshot::Sender<Result<ListRootsResult>>>>,
+    /// How long to wait for the client to answer.
+    timeout_duration: Duration,
+}
+
+impl std::fmt::Debug for ClientRoots {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientRoots")
+            .field(
+                "cached",
+                &self
+                    .cache
+                    .try_read()
+                    .ok()
+                    .and_then(|c| c.as_ref().map(Vec::len)),
+            )
+            .field("timeout_duration", &self.timeout_duration)
+            .finish()
+    }
+}
+
+impl ClientRoots {
+    /// Create a roots cache that sends `roots/list` requests on `request_tx`.
+    pub fn new(request_tx: mpsc::Sender<(RequestId, ServerRequest)>) -> Self {
+        Self {
+            request_tx,
+            cache: RwLock::new(None),
+            generation: AtomicU64::new(0),
+            pending: RwLock::new(HashMap::new()),
+            timeout_duration: Duration::from_secs(30),
+        }
+    }
+
+    /// Set how long to wait for the client's `roots/list` response.
+    pub fn set_timeout(&mut self, duration: Duration) {
+        self.timeout_duration = duration;
+    }
+
+    /// The client's roots, from the cache or fetched with `roots/list`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error response, `REQUEST_TIMEOUT` if it does not
+    /// answer in time, or an internal error if the connection is closed.
+    pub async fn list(&self) -> Result<Vec<Root>> {
+        if let Some(roots) = self.cache.read().await.as_ref() {
+            return Ok(roots.clone());
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let roots = self.fetch().await?.roots;
+
+        let mut cache = self.cache.write().await;
+        if self.generation.load(Ordering::Acquire) == generation {
+            *cache = Some(roots.clone());
+        }
+        Ok(roots)
+    }
+
+    /// Drop the cached roots so the next [`list`](Self::list) fetches them again.
+    pub async fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        *self.cache.write().await = None;
+        debug!("Client roots invalidated");
+    }
+
+    /// Send a `roots/list` request and wait for the response.
+    async fn fetch(&self) -> Result<ListRootsResult> {
+        let id = format!(
+            "roots-{}",
+            ROOTS_REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let (tx, rx) = oneshot::channel();
+        self.pending.write().await.insert(id.clone(), tx);
+
+        if let Err(e) = self
+            .request_tx
+            .send((RequestId::from(id.as_str()), ServerRequest::ListRoots))
+            .await
+        {
+            self.pending.write().await.remove(&id);
+            return Err(Error::internal(format!(
+                "Failed to send roots/list request: {e}"
+            )));
+        }
+
+        match timeout(self.timeout_duration, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(Error::internal(
+                "Connection closed before roots/list completed",
+            )),
+            Err(_) => {
+                warn!("roots/list request {} timed out", id);
+                self.pending.write().await.remove(&id);
+                Err(Error::protocol(
+                    ErrorCode::REQUEST_TIMEOUT,
+                    "roots/list request timed out",
+                ))
+            },
+        }
+    }
+
+    /// Route a JSON-RPC response from the client to its pending `roots/list`
+    /// request.
+    ///
+    /// Returns `false` if the response does not belong to one.
+    pub async fn handle_jsonrpc_response(&self, response: JSONRPCResponse) -> bool {
+        let Some(tx) = self.pending.write().await.remove(&response.id.to_string()) else {
+            return false;
+        };
+
+        let result = match response.payload {
+            ResponsePayload::Result(value) => serde_json::from_value(value)
+                .map_err(|e| Error::protocol_msg(format!("Invalid roots response: {e}"))),
+            ResponsePayload::Error(error) => {
+                Err(Error::protocol(ErrorCode::other(error.code), error.message))
+            },
+        };
+
+        if tx.send(result).is_err() {
+            debug!(
+                "roots/list {} was abandoned before the response",
+                response.id
+            );
+        }
+        true
+    }
+
+    /// Fail all pending `roots/list` requests, for when the connection closes.
+    pub async fn cancel_all(&self) {
+        self.pending.write().await.clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +599,125 @@ mod tests {
         assert_eq!(roots1, roots2);
         assert_eq!(roots1.len(), 2);
     }
+
+    fn root(uri: &str) -> Root {
+        Root {
+            uri: uri.to_string(),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn test_root_contains() {
+        let project = root("file:///home/user/project");
+        assert_eq!(project.path(), Some(PathBuf::from("/home/user/project")));
+        assert!(project.contains("/home/user/project/src/lib.rs"));
+        assert!(project.contains("/home/user/project"));
+        assert!(!project.contains("/home/user/project-other"));
+        assert!(!project.contains("/home/user/project/../secrets"));
+        assert!(!root("https://example.com/project").contains("/home/user/project"));
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let roots = vec![root("file:///srv/data"), root("file:///srv/shared")];
+
+        assert_eq!(
+            resolve_path(&roots, "reports/q1.csv").unwrap(),
+            PathBuf::from("/srv/data/reports/q1.csv")
+        );
+        assert_eq!(
+            resolve_path(&roots, "/srv/shared/./a/../b.txt").unwrap(),
+            PathBuf::from("/srv/shared/b.txt")
+        );
+        assert!(resolve_path(&roots, "../etc/passwd").is_err());
+        assert!(resolve_path(&roots, "/etc/passwd").is_err());
+        assert!(resolve_path(&[], "relative.txt").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_path_follows_symlinks() {
+        let root_dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root_dir.path().join("escape")).unwrap();
+
+        let roots = vec![Root {
+            uri: url::Url::from_directory_path(root_dir.path())
+                .unwrap()
+                .to_string(),
+            name: None,
+        }];
+        assert!(resolve_path(&roots, "inside.txt").is_ok());
+        assert!(resolve_path(&roots, "escape").is_err());
+    }
+
+    /// Answer the next `roots/list` request on `rx` with `roots`.
+    async fn answer(
+        client_roots: &ClientRoots,
+        rx: &mut mpsc::Receiver<(RequestId, ServerRequest)>,
+        roots: &[&str],
+    ) {
+        let (id, request) = rx.recv().await.unwrap();
+        assert!(matches!(request, ServerRequest::ListRoots));
+        let roots: Vec<Root> = roots.iter().map(|uri| root(uri)).collect();
+        let response = JSONRPCResponse::success(id, serde_json::json!({ "roots": roots }));
+        assert!(client_roots.handle_jsonrpc_response(response).await);
+    }
+
+    #[tokio::test]
+    async fn test_client_roots_cached_until_invalidated() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let client_roots = Arc::new(ClientRoots::new(tx));
+
+        let list = tokio::spawn({
+            let client_roots = client_roots.clone();
+            async move { client_roots.list().await }
+        });
+        answer(&client_roots, &mut rx, &["file:///a"]).await;
+        assert_eq!(list.await.unwrap().unwrap(), vec![root("file:///a")]);
+
+        // Served from the cache without another request
+        assert_eq!(client_roots.list().await.unwrap(), vec![root("file:///a")]);
+        assert!(rx.try_recv().is_err());
+
+        client_roots.invalidate().await;
+        let list = tokio::spawn({
+            let client_roots = client_roots.clone();
+            async move { client_roots.list().await }
+        });
+        answer(&client_roots, &mut rx, &["file:///b"]).await;
+        assert_eq!(list.await.unwrap().unwrap(), vec![root("file:///b")]);
+    }
+
+    #[tokio::test]
+    async fn test_client_roots_error_and_timeout() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut client_roots = ClientRoots::new(tx);
+        client_roots.set_timeout(Duration::from_millis(50));
+        let client_roots = Arc::new(client_roots);
+
+        let list = tokio::spawn({
+            let client_roots = client_roots.clone();
+            async move { client_roots.list().await }
+        });
+        let (id, _) = rx.recv().await.unwrap();
+        let response = JSONRPCResponse::error(
+            id,
+            crate::types::jsonrpc::JSONRPCError::new(-32601, "Method not found"),
+        );
+        assert!(client_roots.handle_jsonrpc_response(response).await);
+        assert!(list.await.unwrap().is_err());
+
+        let err = client_roots.list().await.unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::REQUEST_TIMEOUT));
+        assert!(
+            !client_roots
+                .handle_jsonrpc_response(JSONRPCResponse::success(
+                    RequestId::from("elicit-1"),
+                    serde_json::json!({})
+                ))
+                .await
+        );
+    }
 }
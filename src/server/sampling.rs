@@ -0,0 +1,475 @@
+//! Asking the client's model for completions (`sampling/createMessage`).
+//!
+//! Clients that declare the `sampling` capability let servers borrow their
+//! LLM. Tool handlers build a request with [`SamplingRequestBuilder`] and send
+//! it with
+//! [`RequestHandlerExtra::sample`](crate::server::cancellation::RequestHandlerExtra::sample):
+//!
+//! ```rust,ignore
+//! use pmcp::server::sampling::SamplingRequestBuilder;
+//!
+//! let reply = extra
+//!     .sample(
+//!         SamplingRequestBuilder::new()
+//!             .system_prompt("You are a terse release-notes writer.")
+//!             .user(format!("Summarize these commits:\n{commits}"))
+//!             .hint("claude-3-5-haiku")
+//!             .speed_priority(0.8)
+//!             .max_tokens(400),
+//!     )
+//!     .await?;
+//!
+//! let summary = reply.text().unwrap_or_default();
+//! ```
+//!
+//! The client decides which model actually runs; model preferences and hints
+//! are advisory.
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{
+    CreateMessageParams, CreateMessageResult, IncludeContext, JSONRPCResponse, ModelHint,
+    ModelPreferences, RequestId, Role, SamplingMessage, SamplingMessageContent, ServerRequest,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::sync::{mpsc, oneshot, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{timeout, Duration};
+use tracing::{debug, warn};
+
+/// Monotonically increasing counter for `sampling/createMessage` request IDs.
+static SAMPLING_REQUEST_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Builder for `sampling/createMessage` requests.
+///
+/// At least one message and [`max_tokens`](Self::max_tokens) are required;
+/// everything else is optional.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::sampling::SamplingRequestBuilder;
+///
+/// let params = SamplingRequestBuilder::new()
+///     .system_prompt("Answer in one word.")
+///     .user("What colour is the sky?")
+///     .hint("claude-3-5-sonnet")
+///     .intelligence_priority(0.2)
+///     .max_tokens(10)
+///     .stop_sequence("\n")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(params.max_tokens, Some(10));
+/// assert_eq!(params.stop_sequences, Some(vec!["\n".to_string()]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SamplingRequestBuilder {
+    params: CreateMessageParams,
+}
+
+impl Default for SamplingRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SamplingRequestBuilder {
+    /// Create an empty request.
+    pub fn new() -> Self {
+        Self {
+            params: CreateMessageParams::new(Vec::new()),
+        }
+    }
+
+    /// Append a message.
+    pub fn message(mut self, message: SamplingMessage) -> Self {
+        self.params.messages.push(message);
+        self
+    }
+
+    /// Append a user text message.
+    pub fn user(self, text: impl Into<String>) -> Self {
+        self.message(text_message(Role::User, text))
+    }
+
+    /// Append an assistant text message, for few-shot examples or to continue
+    /// a conversation.
+    pub fn assistant(self, text: impl Into<String>) -> Self {
+        self.message(text_message(Role::Assistant, text))
+    }
+
+    /// Set the system prompt.
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.params.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set the maximum number of tokens to generate (required).
+    pub fn max_tokens(mut self, tokens: u32) -> Self {
+        self.params.max_tokens = Some(tokens);
+        self
+    }
+
+    /// Add a sequence that stops generation.
+    pub fn stop_sequence(mut self, sequence: impl Into<String>) -> Self {
+        self.params
+            .stop_sequences
+            .get_or_insert_with(Vec::new)
+            .push(sequence.into());
+        self
+    }
+
+    /// Set the sampling temperature.
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.params.temperature = Some(temperature);
+        self
+    }
+
+    /// Ask the client to include context from its MCP servers.
+    pub fn include_context(mut self, include: IncludeContext) -> Self {
+        self.params.include_context = include;
+        self
+    }
+
+    /// Set provider-specific metadata passed through to the client.
+    pub fn metadata(mut self, metadata: Value) -> Self {
+        self.params.metadata = Some(metadata);
+        self
+    }
+
+    /// Replace the model preferences.
+    pub fn model_preferences(mut self, preferences: ModelPreferences) -> Self {
+        self.params.model_preferences = Some(preferences);
+        self
+    }
+
+    /// Add a model hint, such as a model name or family (`"claude-3-5-sonnet"`,
+    /// `"sonnet"`). Hints are tried in the order they are added.
+    pub fn hint(mut self, name: impl Into<String>) -> Self {
+        self.preferences()
+            .hints
+            .get_or_insert_with(Vec::new)
+            .push(ModelHint::new(name));
+        self
+    }
+
+    /// How much to prioritize a cheap model (0-1).
+    pub fn cost_priority(mut self, priority: f64) -> Self {
+        self.preferences().cost_priority = Some(priority);
+        self
+    }
+
+    /// How much to prioritize a fast model (0-1).
+    pub fn speed_priority(mut self, priority: f64) -> Self {
+        self.preferences().speed_priority = Some(priority);
+        self
+    }
+
+    /// How much to prioritize a capable model (0-1).
+    pub fn intelligence_priority(mut self, priority: f64) -> Self {
+        self.preferences().intelligence_priority = Some(priority);
+        self
+    }
+
+    fn preferences(&mut self) -> &mut ModelPreferences {
+        self.params
+            .model_preferences
+            .get_or_insert_with(ModelPreferences::new)
+    }
+
+    /// Validate the request and return its parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_PARAMS` if there are no messages, `max_tokens` is
+    /// missing or zero, or a priority is outside 0-1.
+    pub fn build(self) -> Result<CreateMessageParams> {
+        let params = self.params;
+        if params.messages.is_empty() {
+            return Err(Error::invalid_params(
+                "A sampling request needs at least one message",
+            ));
+        }
+        if params.max_tokens.unwrap_or(0) == 0 {
+            return Err(Error::invalid_params(
+                "A sampling request needs max_tokens greater than zero",
+            ));
+        }
+        if let Some(preferences) = &params.model_preferences {
+            for (name, priority) in [
+                ("cost_priority", preferences.cost_priority),
+                ("speed_priority", preferences.speed_priority),
+                ("intelligence_priority", preferences.intelligence_priority),
+            ] {
+                if priority.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+                    return Err(Error::invalid_params(format!(
+                        "{name} must be between 0 and 1"
+                    )));
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+fn text_message(role: Role, text: impl Into<String>) -> SamplingMessage {
+    SamplingMessage::new(
+        role,
+        SamplingMessageContent::Text {
+            text: text.into(),
+            meta: None,
+        },
+    )
+}
+
+/// Parse a `sampling/createMessage` result sent by the client.
+///
+/// # Errors
+///
+/// Returns `INVALID_PARAMS` if `value` is not a valid result.
+pub fn parse_create_message_result(value: Value) -> Result<CreateMessageResult> {
+    serde_json::from_value(value).map_err(|e| {
+        Error::protocol(
+            ErrorCode::INVALID_PARAMS,
+            format!("Invalid sampling result: {e}"),
+        )
+    })
+}
+
+/// Sends `sampling/createMessage` requests to the connected client.
+///
+/// Each request is sent with a `sample-<n>` JSON-RPC ID; the client's
+/// response is routed back with
+/// [`handle_jsonrpc_response`](Self::handle_jsonrpc_response).
+pub struct ClientSampling {
+    /// Channel for sending requests to the client.
+    request_tx: mpsc::Sender<(RequestId, ServerRequest)>,
+    /// Requests waiting for responses.
+    pending: RwLock<HashMap<String, oneshot::Sender<Result<CreateMessageResult>>>>,
+    /// How long to wait for the client's model to answer.
+    timeout_duration: Duration,
+}
+
+impl std::fmt::Debug for ClientSampling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientSampling")
+            .field("timeout_duration", &self.timeout_duration)
+            .finish()
+    }
+}
+
+impl ClientSampling {
+    /// Create a sampler that sends requests on `request_tx`.
+    pub fn new(request_tx: mpsc::Sender<(RequestId, ServerRequest)>) -> Self {
+        Self {
+            request_tx,
+            pending: RwLock::new(HashMap::new()),
+            timeout_duration: Duration::from_secs(120),
+        }
+    }
+
+    /// Set how long to wait for a response (default two minutes).
+    pub fn set_timeout(&mut self, duration: Duration) {
+        self.timeout_duration = duration;
+    }
+
+    /// Send a `sampling/createMessage` request and wait for the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error response (for example when the user
+    /// rejects the request), `REQUEST_TIMEOUT` if it does not answer in time,
+    /// or an internal error if the connection is closed.
+    pub async fn create_message(&self, params: CreateMessageParams) -> Result<CreateMessageResult> {
+        let id = format!(
+            "sample-{}",
+            SAMPLING_REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let (tx, rx) = oneshot::channel();
+        self.pending.write().await.insert(id.clone(), tx);
+
+        let request = ServerRequest::CreateMessage(Box::new(params));
+        if let Err(e) = self
+            .request_tx
+            .send((RequestId::from(id.as_str()), request))
+            .await
+        {
+            self.pending.write().await.remove(&id);
+            return Err(Error::internal(format!(
+                "Failed to send sampling request: {e}"
+            )));
+        }
+        debug!("Sent sampling request: {}", id);
+
+        match timeout(self.timeout_duration, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(Error::internal(
+                "Connection closed before sampling completed",
+            )),
+            Err(_) => {
+                warn!("Sampling request {} timed out", id);
+                self.pending.write().await.remove(&id);
+                Err(Error::protocol(
+                    ErrorCode::REQUEST_TIMEOUT,
+                    "Sampling request timed out",
+                ))
+            },
+        }
+    }
+
+    /// Route a JSON-RPC response from the client to its pending request.
+    ///
+    /// Returns `false` if the response does not belong to one.
+    pub async fn handle_jsonrpc_response(&self, response: JSONRPCResponse) -> bool {
+        let Some(tx) = self.pending.write().await.remove(&response.id.to_string()) else {
+            return false;
+        };
+
+        let result = match response.payload {
+            ResponsePayload::Result(value) => parse_create_message_result(value),
+            ResponsePayload::Error(error) => {
+                Err(Error::protocol(ErrorCode::other(error.code), error.message))
+            },
+        };
+
+        if tx.send(result).is_err() {
+            debug!(
+                "Sampling request {} was abandoned before the response",
+                response.id
+            );
+        }
+        true
+    }
+
+    /// Fail all pending requests, for when the connection closes.
+    pub async fn cancel_all(&self) {
+        self.pending.write().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_builder_serializes_to_spec_shape() {
+        let params = SamplingRequestBuilder::new()
+            .system_prompt("Be brief.")
+            .user("Hi")
+            .assistant("Hello!")
+            .user("Summarize MCP")
+            .hint("claude-3-5-sonnet")
+            .hint("sonnet")
+            .cost_priority(0.3)
+            .intelligence_priority(0.9)
+            .max_tokens(200)
+            .stop_sequence("END")
+            .temperature(0.2)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["systemPrompt"], "Be brief.");
+        assert_eq!(json["maxTokens"], 200);
+        assert_eq!(json["stopSequences"], json!(["END"]));
+        assert_eq!(json["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(json["messages"][1]["role"], "assistant");
+        assert_eq!(json["messages"][2]["content"]["text"], "Summarize MCP");
+        assert_eq!(
+            json["modelPreferences"]["hints"],
+            json!([{ "name": "claude-3-5-sonnet" }, { "name": "sonnet" }])
+        );
+        assert_eq!(json["modelPreferences"]["costPriority"], 0.3);
+        assert!(json["modelPreferences"].get("speedPriority").is_none());
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(SamplingRequestBuilder::new()
+            .max_tokens(10)
+            .build()
+            .is_err());
+        assert!(SamplingRequestBuilder::new().user("hi").build().is_err());
+        assert!(SamplingRequestBuilder::new()
+            .user("hi")
+            .max_tokens(10)
+            .speed_priority(1.5)
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_message_round_trip() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let sampling = Arc::new(ClientSampling::new(tx));
+
+        let pending = tokio::spawn({
+            let sampling = sampling.clone();
+            async move {
+                let params = SamplingRequestBuilder::new()
+                    .user("2+2?")
+                    .max_tokens(5)
+                    .build()
+                    .unwrap();
+                sampling.create_message(params).await
+            }
+        });
+
+        let (id, request) = rx.recv().await.unwrap();
+        let ServerRequest::CreateMessage(params) = request else {
+            panic!("expected sampling/createMessage");
+        };
+        assert_eq!(params.max_tokens, Some(5));
+        let response = JSONRPCResponse::success(
+            id,
+            json!({
+                "role": "assistant",
+                "content": { "type": "text", "text": "4" },
+                "model": "claude-3-5-haiku",
+                "stopReason": "endTurn"
+            }),
+        );
+        assert!(sampling.handle_jsonrpc_response(response).await);
+
+        let result = pending.await.unwrap().unwrap();
+        assert_eq!(result.text(), Some("4"));
+        assert_eq!(result.model, "claude-3-5-haiku");
+        assert_eq!(result.stop_reason.as_deref(), Some("endTurn"));
+    }
+
+    #[tokio::test]
+    async fn test_create_message_rejected_and_timeout() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut sampling = ClientSampling::new(tx);
+        sampling.set_timeout(Duration::from_millis(50));
+        let sampling = Arc::new(sampling);
+        let params = SamplingRequestBuilder::new()
+            .user("hi")
+            .max_tokens(5)
+            .build()
+            .unwrap();
+
+        let pending = tokio::spawn({
+            let sampling = sampling.clone();
+            let params = params.clone();
+            async move { sampling.create_message(params).await }
+        });
+        let (id, _) = rx.recv().await.unwrap();
+        let response = JSONRPCResponse::error(
+            id,
+            crate::types::jsonrpc::JSONRPCError::new(-1, "User rejected sampling request"),
+        );
+        assert!(sampling.handle_jsonrpc_response(response).await);
+        let err = pending.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("rejected"), "{err}");
+
+        let err = sampling.create_message(params).await.unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::REQUEST_TIMEOUT));
+    }
+}
@@ -0,0 +1,716 @@
+//! Cron-scheduled workflows executed by the server itself.
+//!
+//! Workflows registered with
+//! [`ServerCoreBuilder::scheduled_workflow`](crate::server::builder::ServerCoreBuilder::scheduled_workflow)
+//! run on a cron schedule without any client request. Each run is recorded
+//! as a task in the server's [`TaskStore`], so clients see scheduled runs
+//! through the regular `tasks/list` and `tasks/get` requests, and fetch the
+//! workflow output with `tasks/result`.
+//!
+//! Every task transition is published twice:
+//!
+//! - as a [`ServerEvent::TaskTransitioned`] on the server's event bus, and
+//! - as a `notifications/tasks/status` notification to every receiver
+//!   returned by [`WorkflowScheduler::subscribe`]. [`ServerCore`] hands
+//!   these to transport adapters through
+//!   [`ProtocolHandler::notifications`], and adapters such as
+//!   [`StdioAdapter`] send them to clients that declared the `tasks`
+//!   capability.
+//!
+//! [`ServerCore`]: crate::server::core::ServerCore
+//! [`ProtocolHandler::notifications`]: crate::server::core::ProtocolHandler::notifications
+//! [`StdioAdapter`]: crate::server::adapters::StdioAdapter
+//!
+//! Scheduled tasks belong to the [`SCHEDULED_TASK_OWNER`] owner, which is
+//! the owner unauthenticated clients resolve to.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pmcp::server::builder::ServerCoreBuilder;
+//! use pmcp::server::task_store::InMemoryTaskStore;
+//! use pmcp::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+//! use std::sync::Arc;
+//!
+//! # async fn example(digest_tool: impl pmcp::ToolHandler + 'static) -> pmcp::Result<()> {
+//! let digest = SequentialWorkflow::new("nightly_digest", "Summarize yesterday's activity")
+//!     .step(WorkflowStep::new("collect", ToolHandle::new("collect_activity")));
+//!
+//! let server = ServerCoreBuilder::new()
+//!     .name("automation")
+//!     .version("1.0.0")
+//!     .tool("collect_activity", digest_tool)
+//!     .task_store(Arc::new(InMemoryTaskStore::new()))
+//!     .scheduled_workflow("nightly_digest", "0 3 * * *", digest)?
+//!     .build()?;
+//!
+//! // Start the schedule loop once a Tokio runtime is available.
+//! if let Some(scheduler) = server.scheduler() {
+//!     scheduler.spawn();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::event_bus::{EventBus, ServerEvent};
+use crate::server::task_store::{TaskStore, TaskStoreError};
+use crate::server::PromptHandler;
+use crate::types::tasks::{Task, TaskStatus, TaskStatusNotification};
+use crate::types::ServerNotification;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+/// Task owner used for every scheduled run.
+pub const SCHEDULED_TASK_OWNER: &str = "local";
+
+/// Workflow results kept for `tasks/result`; older results are dropped.
+const MAX_RETAINED_RESULTS: usize = 100;
+
+/// Buffered notifications per subscriber before it starts lagging.
+const NOTIFICATION_CAPACITY: usize = 64;
+
+/// How far ahead [`CronSchedule::next_after`] searches before giving up.
+const MAX_LOOKAHEAD_YEARS: i32 = 5;
+
+// ---------------------------------------------------------------------------
+// CronSchedule
+// ---------------------------------------------------------------------------
+
+/// A parsed five-field cron expression, evaluated in UTC.
+///
+/// Fields are `minute hour day-of-month month day-of-week`. Each field
+/// accepts `*`, single values, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `8-18/2`). Months and weekdays also accept three-letter names
+/// (`JAN`, `MON`), and Sunday is both `0` and `7`. The shortcuts
+/// `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` are supported.
+///
+/// As in classic cron, when both day-of-month and day-of-week are
+/// restricted a day matches if either field matches.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use pmcp::server::scheduler::CronSchedule;
+///
+/// let schedule = CronSchedule::parse("0 3 * * *").unwrap();
+/// let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+/// assert_eq!(
+///     schedule.next_after(now),
+///     Some(Utc.with_ymd_and_hms(2025, 1, 2, 3, 0, 0).unwrap())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+impl CronSchedule {
+    /// Parse a cron expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error when the expression does not have five
+    /// fields or a field holds an out-of-range or malformed value.
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(Error::validation(format!(
+                "cron expression '{}' must have 5 fields (minute hour day-of-month month day-of-week)",
+                expression
+            )));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES, 0)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], 0)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// The expression this schedule was parsed from.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The first matching minute strictly after `after`.
+    ///
+    /// Returns `None` when nothing matches within five years, e.g. for
+    /// `0 0 30 2 *`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after.year() + MAX_LOOKAHEAD_YEARS;
+
+        while t.year() <= limit {
+            if !bit(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+                continue;
+            }
+            if !self.day_matches(t) {
+                t = Utc
+                    .with_ymd_and_hms(t.year(), t.month(), t.day(), 0, 0, 0)
+                    .single()?
+                    + Duration::days(1);
+                continue;
+            }
+            if !bit(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let dom = bit(self.days_of_month, t.day());
+        let dow = bit(self.days_of_week, t.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+impl std::fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse one cron field into a bitmask of allowed values.
+///
+/// `names[i]` is an alias for the value `i + name_base`.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], name_base: u32) -> Result<u64> {
+    let invalid = |reason: &str| {
+        Error::validation(format!(
+            "invalid cron field '{}': {} (allowed {}-{})",
+            field, reason, min, max
+        ))
+    };
+    let value = |text: &str| -> Result<u32> {
+        let upper = text.to_ascii_uppercase();
+        let parsed = match names.iter().position(|name| *name == upper) {
+            Some(index) => index as u32 + name_base,
+            None => text.parse().map_err(|_| invalid("not a number"))?,
+        };
+        if parsed < min || parsed > max {
+            return Err(invalid("value out of range"));
+        }
+        Ok(parsed)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid("bad step"))?;
+                if step == 0 {
+                    return Err(invalid("step must be positive"));
+                }
+                (range, step)
+            },
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/10` means every 10th value starting at 5
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                },
+            },
+        };
+        if start > end {
+            return Err(invalid("range start is after its end"));
+        }
+        for v in (start..=end).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+// ---------------------------------------------------------------------------
+// WorkflowScheduler
+// ---------------------------------------------------------------------------
+
+/// A workflow registered to run on a schedule.
+pub(crate) struct ScheduledWorkflow {
+    pub(crate) name: String,
+    pub(crate) schedule: CronSchedule,
+    pub(crate) handler: Arc<dyn PromptHandler>,
+    running: AtomicBool,
+}
+
+impl ScheduledWorkflow {
+    pub(crate) fn new(
+        name: String,
+        schedule: CronSchedule,
+        handler: Arc<dyn PromptHandler>,
+    ) -> Self {
+        Self {
+            name,
+            schedule,
+            handler,
+            running: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Clears a workflow's running flag when its run ends, however it ends.
+struct RunningGuard<'a>(&'a AtomicBool);
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+struct SchedulerInner {
+    workflows: Vec<Arc<ScheduledWorkflow>>,
+    store: Arc<dyn TaskStore>,
+    events: EventBus,
+    notifications: broadcast::Sender<ServerNotification>,
+    /// Cancellation tokens of in-flight runs, keyed by task ID
+    in_flight: DashMap<String, CancellationToken>,
+    /// Serialized `GetPromptResult`s of completed runs, oldest first
+    results: Mutex<VecDeque<(String, Value)>>,
+}
+
+/// Runs a server's scheduled workflows and records each run as a task.
+///
+/// Obtained from [`ServerCore::scheduler`](crate::server::core::ServerCore::scheduler).
+/// Cloning is cheap; clones share the same workflows and state.
+#[derive(Clone)]
+pub struct WorkflowScheduler {
+    inner: Arc<SchedulerInner>,
+}
+
+impl std::fmt::Debug for WorkflowScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkflowScheduler")
+            .field(
+                "workflows",
+                &self
+                    .inner
+                    .workflows
+                    .iter()
+                    .map(|w| (w.name.as_str(), w.schedule.expression()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("in_flight", &self.inner.in_flight.len())
+            .finish()
+    }
+}
+
+impl WorkflowScheduler {
+    pub(crate) fn new(
+        workflows: Vec<ScheduledWorkflow>,
+        store: Arc<dyn TaskStore>,
+        events: EventBus,
+    ) -> Self {
+        let (notifications, _) = broadcast::channel(NOTIFICATION_CAPACITY);
+        Self {
+            inner: Arc::new(SchedulerInner {
+                workflows: workflows.into_iter().map(Arc::new).collect(),
+                store,
+                events,
+                notifications,
+                in_flight: DashMap::new(),
+                results: Mutex::new(VecDeque::new()),
+            }),
+        }
+    }
+
+    /// Names and cron expressions of the scheduled workflows.
+    pub fn workflows(&self) -> Vec<(&str, &CronSchedule)> {
+        self.inner
+            .workflows
+            .iter()
+            .map(|w| (w.name.as_str(), &w.schedule))
+            .collect()
+    }
+
+    /// Receive a `notifications/tasks/status` notification for every
+    /// scheduled task transition.
+    pub fn subscribe(&self) -> broadcast::Receiver<ServerNotification> {
+        self.inner.notifications.subscribe()
+    }
+
+    /// Start the schedule loop on the current Tokio runtime.
+    ///
+    /// Each due workflow runs on its own task. A workflow whose previous
+    /// run is still in progress skips that tick. Abort the returned handle
+    /// to stop scheduling; runs already started finish on their own.
+    pub fn spawn(&self) -> tokio::task::JoinHandle<()> {
+        let scheduler = self.clone();
+        tokio::spawn(async move { scheduler.run_loop().await })
+    }
+
+    async fn run_loop(self) {
+        let now = Utc::now();
+        let mut next: Vec<Option<DateTime<Utc>>> = self
+            .inner
+            .workflows
+            .iter()
+            .map(|w| w.schedule.next_after(now))
+            .collect();
+
+        while let Some(due) = next.iter().flatten().min().copied() {
+            let wait = (due - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            let now = Utc::now();
+            for (workflow, slot) in self.inner.workflows.iter().zip(next.iter_mut()) {
+                if slot.is_some_and(|at| at <= now) {
+                    *slot = workflow.schedule.next_after(now);
+                    let scheduler = self.clone();
+                    let workflow = workflow.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = scheduler.execute(&workflow).await {
+                            tracing::warn!(
+                                workflow = workflow.name.as_str(),
+                                "Scheduled workflow run failed: {}",
+                                e
+                            );
+                        }
+                    });
+                }
+            }
+        }
+        tracing::info!("No scheduled workflow has a future run; scheduler stopped");
+    }
+
+    /// Run a scheduled workflow immediately, outside its schedule.
+    ///
+    /// Waits for the run to finish and returns its final task. A failing
+    /// workflow is not an error here: it yields a task in the `Failed`
+    /// state with the error as its status message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no workflow has this name, a run is already in
+    /// progress, or the task store rejects the task.
+    pub async fn run_now(&self, name: &str) -> Result<Task> {
+        let workflow = self
+            .inner
+            .workflows
+            .iter()
+            .find(|w| w.name == name)
+            .cloned()
+            .ok_or_else(|| Error::not_found(format!("Scheduled workflow '{}' not found", name)))?;
+        self.execute(&workflow).await
+    }
+
+    async fn execute(&self, workflow: &ScheduledWorkflow) -> Result<Task> {
+        if workflow.running.swap(true, Ordering::SeqCst) {
+            return Err(Error::validation(format!(
+                "Scheduled workflow '{}' is already running",
+                workflow.name
+            )));
+        }
+        let _running = RunningGuard(&workflow.running);
+
+        let inner = &self.inner;
+        let task = inner.store.create(SCHEDULED_TASK_OWNER, None).await?;
+        self.publish(&task);
+
+        let token = CancellationToken::new();
+        inner.in_flight.insert(task.task_id.clone(), token.clone());
+        let extra = RequestHandlerExtra::new(
+            format!("schedule_{}_{}", workflow.name, task.task_id),
+            token,
+        );
+        let outcome = workflow.handler.handle(HashMap::new(), extra).await;
+        inner.in_flight.remove(&task.task_id);
+
+        let (status, message) = match outcome {
+            Ok(result) => {
+                self.retain_result(&task.task_id, serde_json::to_value(result)?);
+                (TaskStatus::Completed, None)
+            },
+            Err(e) => (TaskStatus::Failed, Some(e.to_string())),
+        };
+
+        let finished = match inner
+            .store
+            .update_status(&task.task_id, SCHEDULED_TASK_OWNER, status, message)
+            .await
+        {
+            Ok(task) => task,
+            // Cancelled through tasks/cancel while running
+            Err(TaskStoreError::InvalidTransition {
+                from: TaskStatus::Cancelled,
+                ..
+            }) => {
+                return inner
+                    .store
+                    .get(&task.task_id, SCHEDULED_TASK_OWNER)
+                    .await
+                    .map_err(Error::from)
+            },
+            Err(e) => return Err(e.into()),
+        };
+        self.publish(&finished);
+        Ok(finished)
+    }
+
+    /// The workflow output of a completed scheduled run, for `tasks/result`.
+    pub fn result(&self, task_id: &str) -> Option<Value> {
+        self.inner
+            .results
+            .lock()
+            .iter()
+            .find(|(id, _)| id == task_id)
+            .map(|(_, result)| result.clone())
+    }
+
+    /// Signal cancellation to an in-flight run. Returns whether one was found.
+    ///
+    /// The workflow stops before its next step.
+    pub fn cancel(&self, task_id: &str) -> bool {
+        match self.inner.in_flight.get(task_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn retain_result(&self, task_id: &str, result: Value) {
+        let mut results = self.inner.results.lock();
+        if results.len() == MAX_RETAINED_RESULTS {
+            results.pop_front();
+        }
+        results.push_back((task_id.to_string(), result));
+    }
+
+    fn publish(&self, task: &Task) {
+        self.inner.events.emit(ServerEvent::TaskTransitioned {
+            task_id: task.task_id.clone(),
+            status: task.status,
+        });
+        // No subscribers is not an error; the notification is simply dropped.
+        let _ = self
+            .inner
+            .notifications
+            .send(ServerNotification::TaskStatus(TaskStatusNotification {
+                task: task.clone(),
+            }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::task_store::InMemoryTaskStore;
+    use crate::types::{Content, GetPromptResult, PromptMessage};
+    use async_trait::async_trait;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_fields_and_shortcuts() {
+        let schedule = CronSchedule::parse("*/15 8-18/2 1,15 JAN-mar MON-FRI").unwrap();
+        assert!(bit(schedule.minutes, 45) && !bit(schedule.minutes, 50));
+        assert!(bit(schedule.hours, 10) && !bit(schedule.hours, 9));
+        assert!(bit(schedule.months, 3) && !bit(schedule.months, 4));
+        assert!(bit(schedule.days_of_week, 5) && !bit(schedule.days_of_week, 0));
+
+        assert_eq!(
+            CronSchedule::parse("@daily")
+                .unwrap()
+                .next_after(at(2025, 6, 1, 12, 0)),
+            Some(at(2025, 6, 2, 0, 0))
+        );
+        assert!(bit(
+            CronSchedule::parse("0 0 * * 7").unwrap().days_of_week,
+            0
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expr in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "x * * * *",
+        ] {
+            assert!(
+                CronSchedule::parse(expr).is_err(),
+                "{expr:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn next_after_is_strictly_later() {
+        let schedule = CronSchedule::parse("0 3 * * *").unwrap();
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 1, 3, 0)),
+            Some(at(2025, 1, 2, 3, 0))
+        );
+        assert_eq!(
+            schedule.next_after(at(2025, 12, 31, 4, 0)),
+            Some(at(2026, 1, 1, 3, 0))
+        );
+    }
+
+    #[test]
+    fn day_fields_match_either_when_both_restricted() {
+        // 13th of the month or any Friday
+        let schedule = CronSchedule::parse("0 0 13 * FRI").unwrap();
+        // 2025-06-06 is a Friday
+        assert_eq!(
+            schedule.next_after(at(2025, 6, 1, 0, 0)),
+            Some(at(2025, 6, 6, 0, 0))
+        );
+        assert_eq!(
+            schedule.next_after(at(2025, 6, 10, 0, 0)),
+            Some(at(2025, 6, 13, 0, 0))
+        );
+    }
+
+    #[test]
+    fn impossible_dates_never_fire() {
+        let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert_eq!(schedule.next_after(at(2025, 1, 1, 0, 0)), None);
+    }
+
+    struct Digest {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl PromptHandler for Digest {
+        async fn handle(
+            &self,
+            _args: HashMap<String, String>,
+            _extra: RequestHandlerExtra,
+        ) -> Result<GetPromptResult> {
+            if self.fail {
+                return Err(Error::internal("source unavailable"));
+            }
+            Ok(GetPromptResult::new(
+                vec![PromptMessage::assistant(Content::text("3 new items"))],
+                None,
+            ))
+        }
+    }
+
+    fn scheduler(fail: bool) -> WorkflowScheduler {
+        WorkflowScheduler::new(
+            vec![ScheduledWorkflow::new(
+                "nightly_digest".to_string(),
+                CronSchedule::parse("0 3 * * *").unwrap(),
+                Arc::new(Digest { fail }),
+            )],
+            Arc::new(InMemoryTaskStore::new()),
+            EventBus::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn run_stores_result_and_notifies() {
+        let scheduler = scheduler(false);
+        let mut notifications = scheduler.subscribe();
+
+        let task = scheduler.run_now("nightly_digest").await.unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+
+        let stored = scheduler
+            .inner
+            .store
+            .get(&task.task_id, SCHEDULED_TASK_OWNER)
+            .await;
+        assert_eq!(stored.unwrap().status, TaskStatus::Completed);
+        let result = scheduler.result(&task.task_id).unwrap();
+        assert_eq!(result["messages"][0]["content"]["text"], "3 new items");
+
+        let statuses: Vec<TaskStatus> = [notifications.recv().await, notifications.recv().await]
+            .into_iter()
+            .map(|n| match n.unwrap() {
+                ServerNotification::TaskStatus(n) => n.task.status,
+                other => panic!("unexpected notification {other:?}"),
+            })
+            .collect();
+        assert_eq!(statuses, vec![TaskStatus::Working, TaskStatus::Completed]);
+    }
+
+    #[tokio::test]
+    async fn failed_run_records_error() {
+        let scheduler = scheduler(true);
+        let task = scheduler.run_now("nightly_digest").await.unwrap();
+
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert!(task.status_message.unwrap().contains("source unavailable"));
+        assert!(scheduler.result(&task.task_id).is_none());
+        assert!(scheduler.run_now("unknown").await.is_err());
+    }
+}
@@ -0,0 +1,635 @@
+//! Startup check for breaking schema changes.
+//!
+//! Hosts and domain servers depend on a server's contract: its tool names
+//! and argument/output schemas, its prompts and their arguments, and its
+//! static resources. A schema exported with `cargo pmcp schema export` and
+//! bundled into the binary records the last published contract; registering
+//! it with `schema_baseline` on the server builder compares the running
+//! server against it:
+//!
+//! - tools and prompts are compared when the server is built,
+//! - resources are compared when the self-test runs, since they are listed by
+//!   async handlers.
+//!
+//! Breaking changes are removed tools, prompts, prompt arguments and
+//! resources; removed or newly required input properties; removed output
+//! properties or properties no longer guaranteed; a dropped output schema;
+//! and changed property types. Additions are never breaking.
+//!
+//! Breaking changes are accepted when the server version is a breaking
+//! semver bump over the baseline version (a new major version, or a new
+//! minor version below 1.0). Otherwise [`SchemaCheckMode::Strict`] fails the
+//! build (and the self-test), and [`SchemaCheckMode::Warn`] logs each change.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let server = Server::builder()
+//!     .name("orders")
+//!     .version(env!("CARGO_PKG_VERSION"))
+//!     .tool("get_order", GetOrder)
+//!     .schema_baseline(
+//!         SchemaBaseline::from_json(include_str!("../schemas/orders.json"))?,
+//!         SchemaCheckMode::Strict,
+//!     )
+//!     .build()?; // fails if get_order lost an argument without a major bump
+//! ```
+
+use crate::error::{Error, Result};
+use crate::server::schema_registry::DEFINITIONS_REF_PREFIX;
+use crate::types::{PromptInfo, ToolInfo};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Deepest `$ref` chain followed when comparing schemas.
+const MAX_REF_DEPTH: usize = 8;
+
+/// What to do when breaking changes are found without a version bump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaCheckMode {
+    /// Refuse to start: fail the server build and the self-test.
+    Strict,
+    /// Start anyway and log each breaking change as a warning.
+    Warn,
+}
+
+/// The part of the server a breaking change affects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractItem {
+    /// A tool.
+    Tool,
+    /// A prompt.
+    Prompt,
+    /// A resource.
+    Resource,
+}
+
+impl fmt::Display for ContractItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tool => "tool",
+            Self::Prompt => "prompt",
+            Self::Resource => "resource",
+        })
+    }
+}
+
+/// One incompatible difference from the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// What changed.
+    pub item: ContractItem,
+    /// Tool or prompt name, or resource URI.
+    pub name: String,
+    /// How it changed.
+    pub detail: String,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}': {}", self.item, self.name, self.detail)
+    }
+}
+
+/// A schema exported with `cargo pmcp schema export`.
+///
+/// Also accepts a raw `tools/list` result for servers without prompts or
+/// resources.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SchemaBaseline {
+    /// Server version the schema was exported from.
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    tools: Vec<BaselineTool>,
+    #[serde(default)]
+    definitions: Map<String, Value>,
+    #[serde(default)]
+    resources: Vec<BaselineResource>,
+    #[serde(default)]
+    prompts: Vec<BaselinePrompt>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BaselineTool {
+    name: String,
+    #[serde(default, alias = "inputSchema")]
+    input_schema: Option<Value>,
+    #[serde(default, alias = "outputSchema")]
+    output_schema: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BaselineResource {
+    uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BaselinePrompt {
+    name: String,
+    #[serde(default)]
+    arguments: Vec<BaselineArgument>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BaselineArgument {
+    name: String,
+    #[serde(default)]
+    required: bool,
+}
+
+impl SchemaBaseline {
+    /// Parse an exported schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `json` is not an exported schema.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::validation(format!("Invalid schema baseline: {}", e)))
+    }
+
+    /// Breaking changes in `tools` (with the shared `definitions` their
+    /// schemas reference) compared to the baseline, sorted by tool name.
+    pub fn tool_changes(
+        &self,
+        tools: &HashMap<String, ToolInfo>,
+        definitions: Option<&Map<String, Value>>,
+    ) -> Vec<BreakingChange> {
+        let empty = Map::new();
+        let definitions = definitions.unwrap_or(&empty);
+        let mut changes = Vec::new();
+        for old in &self.tools {
+            let change = |detail: String| BreakingChange {
+                item: ContractItem::Tool,
+                name: old.name.clone(),
+                detail,
+            };
+            let Some(new) = tools.get(&old.name) else {
+                changes.push(change("removed".to_string()));
+                continue;
+            };
+            if let Some(old_input) = &old.input_schema {
+                let old_input = Schema::new(old_input, &self.definitions);
+                let new_input = Schema::new(&new.input_schema, definitions);
+                changes.extend(
+                    input_changes(&old_input, &new_input)
+                        .into_iter()
+                        .map(change),
+                );
+            }
+            match (&old.output_schema, &new.output_schema) {
+                (Some(_), None) => changes.push(change("output schema removed".to_string())),
+                (Some(old_output), Some(new_output)) => {
+                    let old_output = Schema::new(old_output, &self.definitions);
+                    let new_output = Schema::new(new_output, definitions);
+                    changes.extend(
+                        output_changes(&old_output, &new_output)
+                            .into_iter()
+                            .map(change),
+                    );
+                },
+                _ => {},
+            }
+        }
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        changes
+    }
+
+    /// Breaking changes in `prompts` compared to the baseline, sorted by
+    /// prompt name.
+    pub fn prompt_changes<'a>(
+        &self,
+        prompts: impl IntoIterator<Item = &'a PromptInfo>,
+    ) -> Vec<BreakingChange> {
+        let prompts: HashMap<&str, &PromptInfo> =
+            prompts.into_iter().map(|p| (p.name.as_str(), p)).collect();
+        let mut changes = Vec::new();
+        for old in &self.prompts {
+            let change = |detail: String| BreakingChange {
+                item: ContractItem::Prompt,
+                name: old.name.clone(),
+                detail,
+            };
+            let Some(new) = prompts.get(old.name.as_str()) else {
+                changes.push(change("removed".to_string()));
+                continue;
+            };
+            let new_arguments = new.arguments.as_deref().unwrap_or_default();
+            for old_argument in &old.arguments {
+                if !new_arguments.iter().any(|a| a.name == old_argument.name) {
+                    changes.push(change(format!("argument '{}' removed", old_argument.name)));
+                }
+            }
+            for new_argument in new_arguments.iter().filter(|a| a.required) {
+                let was_required = old
+                    .arguments
+                    .iter()
+                    .any(|a| a.name == new_argument.name && a.required);
+                if !was_required {
+                    changes.push(change(format!(
+                        "argument '{}' is now required",
+                        new_argument.name
+                    )));
+                }
+            }
+        }
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        changes
+    }
+
+    /// Baseline resources missing from `uris`.
+    pub fn resource_changes<'a>(
+        &self,
+        uris: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<BreakingChange> {
+        let uris: Vec<&str> = uris.into_iter().collect();
+        self.resources
+            .iter()
+            .filter(|old| !uris.contains(&old.uri.as_str()))
+            .map(|old| BreakingChange {
+                item: ContractItem::Resource,
+                name: old.uri.clone(),
+                detail: "no longer listed".to_string(),
+            })
+            .collect()
+    }
+
+    /// Whether `version` may break the baseline's contract under semver.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::schema_compat::SchemaBaseline;
+    ///
+    /// let baseline = SchemaBaseline::from_json(r#"{"version": "1.4.2", "tools": []}"#)?;
+    /// assert!(baseline.allows_breaking_changes("2.0.0"));
+    /// assert!(!baseline.allows_breaking_changes("1.5.0"));
+    ///
+    /// let baseline = SchemaBaseline::from_json(r#"{"version": "0.3.1", "tools": []}"#)?;
+    /// assert!(baseline.allows_breaking_changes("0.4.0"));
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn allows_breaking_changes(&self, version: &str) -> bool {
+        let (Some(old), Some(new)) = (
+            self.version.as_deref().and_then(major_minor),
+            major_minor(version),
+        ) else {
+            return false;
+        };
+        match (old, new) {
+            ((0, old_minor), (0, new_minor)) => new_minor > old_minor,
+            ((old_major, _), (new_major, _)) => new_major > old_major,
+        }
+    }
+}
+
+/// `MAJOR.MINOR` of a semver string.
+fn major_minor(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim_start_matches('v').split(['.', '-', '+']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    Some((major, minor))
+}
+
+/// An object schema with the definitions its `$ref`s point into.
+struct Schema<'a> {
+    schema: &'a Value,
+    definitions: &'a Map<String, Value>,
+}
+
+impl<'a> Schema<'a> {
+    fn new(schema: &'a Value, definitions: &'a Map<String, Value>) -> Self {
+        Self {
+            schema: resolve(schema, definitions),
+            definitions,
+        }
+    }
+
+    fn properties(&self) -> Option<&'a Map<String, Value>> {
+        self.schema.get("properties")?.as_object()
+    }
+
+    fn property_type(&self, name: &str) -> Option<&'a Value> {
+        let property = self.properties()?.get(name)?;
+        resolve(property, self.definitions).get("type")
+    }
+
+    fn is_required(&self, name: &str) -> bool {
+        self.schema
+            .get("required")
+            .and_then(Value::as_array)
+            .is_some_and(|required| required.iter().any(|r| r == name))
+    }
+}
+
+/// Follow `#/definitions/...` references.
+fn resolve<'a>(mut schema: &'a Value, definitions: &'a Map<String, Value>) -> &'a Value {
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix(DEFINITIONS_REF_PREFIX))
+            .and_then(|name| definitions.get(name))
+        else {
+            break;
+        };
+        schema = target;
+    }
+    schema
+}
+
+/// Changes that reject arguments clients built against `old` may send.
+fn input_changes(old: &Schema<'_>, new: &Schema<'_>) -> Vec<String> {
+    let mut changes = Vec::new();
+    let old_properties = old.properties().map(Map::keys).into_iter().flatten();
+    for name in old_properties {
+        match new.properties().and_then(|p| p.get(name)) {
+            None => changes.push(format!("input property '{}' removed", name)),
+            Some(_) => {
+                if let Some(detail) = type_change("input", name, old, new) {
+                    changes.push(detail);
+                }
+                if new.is_required(name) && !old.is_required(name) {
+                    changes.push(format!("input property '{}' is now required", name));
+                }
+            },
+        }
+    }
+    for name in new.properties().map(Map::keys).into_iter().flatten() {
+        let is_new = old.properties().is_none_or(|p| !p.contains_key(name));
+        if is_new && new.is_required(name) {
+            changes.push(format!("new input property '{}' is required", name));
+        }
+    }
+    changes
+}
+
+/// Changes that drop output clients built against `old` may rely on.
+fn output_changes(old: &Schema<'_>, new: &Schema<'_>) -> Vec<String> {
+    let mut changes = Vec::new();
+    let old_properties = old.properties().map(Map::keys).into_iter().flatten();
+    for name in old_properties {
+        match new.properties().and_then(|p| p.get(name)) {
+            None => changes.push(format!("output property '{}' removed", name)),
+            Some(_) => {
+                if let Some(detail) = type_change("output", name, old, new) {
+                    changes.push(detail);
+                }
+                if old.is_required(name) && !new.is_required(name) {
+                    changes.push(format!("output property '{}' is no longer required", name));
+                }
+            },
+        }
+    }
+    changes
+}
+
+fn type_change(which: &str, name: &str, old: &Schema<'_>, new: &Schema<'_>) -> Option<String> {
+    match (old.property_type(name), new.property_type(name)) {
+        (Some(old_type), Some(new_type)) if old_type != new_type => Some(format!(
+            "{} property '{}' changed type from {} to {}",
+            which, name, old_type, new_type
+        )),
+        _ => None,
+    }
+}
+
+/// A baseline and what to do about breaking changes.
+#[derive(Debug, Clone)]
+pub(crate) struct SchemaGuard {
+    baseline: SchemaBaseline,
+    mode: SchemaCheckMode,
+    version: String,
+}
+
+impl SchemaGuard {
+    pub(crate) fn new(baseline: SchemaBaseline, mode: SchemaCheckMode, version: &str) -> Self {
+        Self {
+            baseline,
+            mode,
+            version: version.to_string(),
+        }
+    }
+
+    /// Compare tools and prompts, which are known when the server is built.
+    pub(crate) fn check_build<'a>(
+        &self,
+        tools: &HashMap<String, ToolInfo>,
+        prompts: impl IntoIterator<Item = &'a PromptInfo>,
+    ) -> Result<()> {
+        let mut changes = self.baseline.tool_changes(tools, None);
+        changes.extend(self.baseline.prompt_changes(prompts));
+        self.enforce(&changes)
+    }
+
+    /// Compare the resources listed by a running server.
+    pub(crate) fn check_resources<'a>(
+        &self,
+        uris: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        self.enforce(&self.baseline.resource_changes(uris))
+    }
+
+    /// Apply the mode to `changes`.
+    ///
+    /// Returns an error listing the changes in strict mode, unless the
+    /// version allows them.
+    pub(crate) fn enforce(&self, changes: &[BreakingChange]) -> Result<()> {
+        if changes.is_empty() || self.baseline.allows_breaking_changes(&self.version) {
+            return Ok(());
+        }
+        let baseline_version = self.baseline.version.as_deref().unwrap_or("unknown");
+        match self.mode {
+            SchemaCheckMode::Strict => Err(Error::validation(format!(
+                "Breaking schema changes since version {} without a major version bump (server version {}): {}",
+                baseline_version,
+                self.version,
+                changes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ))),
+            SchemaCheckMode::Warn => {
+                for change in changes {
+                    tracing::warn!(
+                        "Breaking schema change since version {} (server version {}): {}",
+                        baseline_version,
+                        self.version,
+                        change
+                    );
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PromptArgument;
+    use serde_json::json;
+
+    fn baseline() -> SchemaBaseline {
+        SchemaBaseline::from_json(
+            &json!({
+                "server_id": "orders",
+                "name": "orders",
+                "version": "1.2.0",
+                "tools": [
+                    {
+                        "name": "get_order",
+                        "input_schema": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string" },
+                                "verbose": { "type": "boolean" }
+                            },
+                            "required": ["id"]
+                        },
+                        "outputSchema": {
+                            "type": "object",
+                            "properties": { "order": { "$ref": "#/definitions/Order" } },
+                            "required": ["order"]
+                        }
+                    },
+                    { "name": "cancel_order", "input_schema": { "type": "object" } }
+                ],
+                "definitions": { "Order": { "type": "object" } },
+                "resources": [{ "uri": "orders://recent", "name": "Recent" }],
+                "prompts": [{ "name": "summarize", "arguments": [{ "name": "id" }] }]
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    fn tool(input: Value, output: Option<Value>) -> HashMap<String, ToolInfo> {
+        let mut info = ToolInfo::new("get_order", None, input);
+        info.output_schema = output;
+        HashMap::from([("get_order".to_string(), info)])
+    }
+
+    #[test]
+    fn test_tool_changes() {
+        let tools = tool(
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer" },
+                    "region": { "type": "string" },
+                    "limit": { "type": "integer" }
+                },
+                "required": ["id", "region"]
+            }),
+            Some(json!({
+                "type": "object",
+                "properties": { "order": { "$ref": "#/definitions/Order" } }
+            })),
+        );
+        let definitions = Map::from_iter([("Order".to_string(), json!({ "type": "array" }))]);
+        let changes: Vec<String> = baseline()
+            .tool_changes(&tools, Some(&definitions))
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "tool 'cancel_order': removed",
+                "tool 'get_order': input property 'id' changed type from \"string\" to \"integer\"",
+                "tool 'get_order': input property 'verbose' removed",
+                "tool 'get_order': new input property 'region' is required",
+                "tool 'get_order': output property 'order' changed type from \"object\" to \"array\"",
+                "tool 'get_order': output property 'order' is no longer required",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_additions_are_compatible() {
+        let mut tools = tool(
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "verbose": { "type": "boolean" },
+                    "limit": { "type": "integer" }
+                },
+                "required": ["id"]
+            }),
+            Some(json!({
+                "type": "object",
+                "properties": { "order": { "type": "object" }, "eta": { "type": "string" } },
+                "required": ["order", "eta"]
+            })),
+        );
+        tools.insert(
+            "cancel_order".to_string(),
+            ToolInfo::new("cancel_order", None, json!({ "type": "object" })),
+        );
+        tools.insert(
+            "new_tool".to_string(),
+            ToolInfo::new("new_tool", None, json!({ "type": "object" })),
+        );
+        assert!(baseline().tool_changes(&tools, None).is_empty());
+    }
+
+    #[test]
+    fn test_prompt_and_resource_changes() {
+        let prompt = PromptInfo::new("summarize")
+            .with_arguments(vec![PromptArgument::new("style").required()]);
+        let changes: Vec<String> = baseline()
+            .prompt_changes([&prompt])
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "prompt 'summarize': argument 'id' removed",
+                "prompt 'summarize': argument 'style' is now required",
+            ]
+        );
+        assert_eq!(baseline().prompt_changes([]).len(), 1);
+
+        assert!(baseline().resource_changes(["orders://recent"]).is_empty());
+        assert_eq!(
+            baseline().resource_changes(["orders://all"])[0].to_string(),
+            "resource 'orders://recent': no longer listed"
+        );
+    }
+
+    #[test]
+    fn test_guard_modes() {
+        let change = BreakingChange {
+            item: ContractItem::Tool,
+            name: "get_order".to_string(),
+            detail: "removed".to_string(),
+        };
+        let strict = SchemaGuard::new(baseline(), SchemaCheckMode::Strict, "1.3.0");
+        let err = strict.enforce(std::slice::from_ref(&change)).unwrap_err();
+        assert!(err.to_string().contains("tool 'get_order': removed"));
+        assert!(strict.enforce(&[]).is_ok());
+
+        let bumped = SchemaGuard::new(baseline(), SchemaCheckMode::Strict, "2.0.0");
+        assert!(bumped.enforce(std::slice::from_ref(&change)).is_ok());
+
+        let warn = SchemaGuard::new(baseline(), SchemaCheckMode::Warn, "1.3.0");
+        assert!(warn.enforce(&[change]).is_ok());
+    }
+
+    #[test]
+    fn test_version_parsing() {
+        assert_eq!(major_minor("1.2.3"), Some((1, 2)));
+        assert_eq!(major_minor("v2"), Some((2, 0)));
+        assert_eq!(major_minor("0.4.0-beta.1"), Some((0, 4)));
+        assert_eq!(major_minor("latest"), None);
+        let unversioned = SchemaBaseline::default();
+        assert!(!unversioned.allows_breaking_changes("9.0.0"));
+    }
+}
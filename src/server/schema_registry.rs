@@ -0,0 +1,333 @@
+//! Shared schema definitions for tool input and output schemas.
+//!
+//! Typed tools inline every nested type into their schemas, so a type used by
+//! ten tools is repeated ten times in `tools/list`. A [`SchemaRegistry`]
+//! names such types once: when the server is built, every subschema in a
+//! tool schema that matches a registered definition is replaced with
+//! `{"$ref": "#/definitions/<name>"}`, and the definitions are sent once,
+//! under `definitions` in the `tools/list` result.
+//!
+//! Matching is structural and ignores annotations (`title`, `description`,
+//! `default`, `examples`), so a field's own description is kept next to the
+//! `$ref`. The root of a tool schema is never replaced, so every tool keeps
+//! an `object` schema as MCP requires. Definitions of plain scalars (such as
+//! `{"type": "string"}`) are not substituted.
+//!
+//! `definitions` is a PMCP extension. Clients that need self-contained
+//! schemas can call
+//! [`ListToolsResult::inline_definitions`](crate::types::ListToolsResult::inline_definitions).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::schema_registry::SchemaRegistry;
+//! use serde_json::json;
+//!
+//! let address = json!({
+//!     "type": "object",
+//!     "properties": { "city": { "type": "string" } },
+//!     "required": ["city"]
+//! });
+//!
+//! let mut registry = SchemaRegistry::new();
+//! registry.insert("Address", address.clone());
+//!
+//! let tool_schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "shipping": { "description": "Where to ship", "type": "object",
+//!                       "properties": { "city": { "type": "string" } }, "required": ["city"] }
+//!     }
+//! });
+//! assert_eq!(
+//!     registry.compact(&tool_schema)["properties"]["shipping"],
+//!     json!({ "$ref": "#/definitions/Address", "description": "Where to ship" })
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+/// Prefix of references to shared definitions.
+pub const DEFINITIONS_REF_PREFIX: &str = "#/definitions/";
+
+/// Keywords that describe a schema without constraining it.
+const ANNOTATIONS: &[&str] = &["title", "description", "default", "examples"];
+
+/// Keywords that make a definition worth sharing.
+const STRUCTURAL: &[&str] = &[
+    "properties",
+    "items",
+    "enum",
+    "oneOf",
+    "anyOf",
+    "allOf",
+    "additionalProperties",
+];
+
+/// Named schema definitions shared between tools.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    definitions: BTreeMap<String, Value>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `schema` under `name`, replacing any previous definition.
+    ///
+    /// Register the schema in the same form tools use, i.e. with nested
+    /// types inlined.
+    pub fn insert(&mut self, name: impl Into<String>, schema: Value) {
+        self.definitions.insert(name.into(), schema);
+    }
+
+    /// Register the schema generated for `T`, named after the type.
+    ///
+    /// Returns the definition name.
+    #[cfg(feature = "schema-generation")]
+    pub fn insert_type<T: schemars::JsonSchema>(&mut self) -> String {
+        let name = T::schema_name().into_owned();
+        let schema = serde_json::to_value(schemars::schema_for!(T)).unwrap_or_default();
+        self.insert(
+            name.clone(),
+            crate::server::schema_utils::normalize_schema(schema),
+        );
+        name
+    }
+
+    /// Get a registered definition.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.definitions.get(name)
+    }
+
+    /// Whether no definitions are registered.
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Number of registered definitions.
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Replace subschemas of `schema` that match a registered definition with
+    /// a `$ref` to it. The root of `schema` is kept as is.
+    pub fn compact(&self, schema: &Value) -> Value {
+        let mut schema = schema.clone();
+        self.compact_children(&mut schema);
+        schema
+    }
+
+    /// The definitions to publish, with references between them compacted too.
+    pub fn definitions(&self) -> Map<String, Value> {
+        self.definitions
+            .iter()
+            .map(|(name, schema)| (name.clone(), self.compact(schema)))
+            .collect()
+    }
+
+    fn compact_children(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    // Property maps hold schemas, but are not schemas themselves
+                    if key == "properties" || key == "patternProperties" {
+                        if let Value::Object(properties) = child {
+                            for property in properties.values_mut() {
+                                self.compact_node(property);
+                            }
+                        }
+                    } else if !ANNOTATIONS.contains(&key.as_str()) && key != "enum" {
+                        self.compact_node(child);
+                    }
+                }
+            },
+            Value::Array(items) => {
+                for item in items {
+                    self.compact_node(item);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn compact_node(&self, value: &mut Value) {
+        let matched = value.as_object().and_then(|node| {
+            self.definitions
+                .iter()
+                .find(|(_, definition)| matches_definition(node, definition))
+        });
+
+        match matched {
+            Some((name, definition)) => {
+                let mut reference = Map::new();
+                reference.insert(
+                    "$ref".to_string(),
+                    Value::String(format!("{DEFINITIONS_REF_PREFIX}{name}")),
+                );
+                // Keep annotations specific to this use, like a field description
+                if let Value::Object(node) = value {
+                    for key in ANNOTATIONS {
+                        if let Some(annotation) = node.get(*key) {
+                            if definition.get(*key) != Some(annotation) {
+                                reference.insert((*key).to_string(), annotation.clone());
+                            }
+                        }
+                    }
+                }
+                *value = Value::Object(reference);
+            },
+            None => self.compact_children(value),
+        }
+    }
+}
+
+/// Whether `node` is `definition`, ignoring annotations.
+fn matches_definition(node: &Map<String, Value>, definition: &Value) -> bool {
+    let Some(definition) = definition.as_object() else {
+        return false;
+    };
+    if !STRUCTURAL.iter().any(|key| definition.contains_key(*key)) {
+        return false;
+    }
+    let constraints = |map: &Map<String, Value>| {
+        map.iter()
+            .filter(|(key, _)| !ANNOTATIONS.contains(&key.as_str()) && *key != "$schema")
+            .count()
+    };
+    constraints(node) == constraints(definition)
+        && definition
+            .iter()
+            .filter(|(key, _)| !ANNOTATIONS.contains(&key.as_str()) && *key != "$schema")
+            .all(|(key, value)| node.get(key) == Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ListToolsResult, ToolInfo};
+    use serde_json::json;
+
+    fn address() -> Value {
+        json!({
+            "title": "Address",
+            "type": "object",
+            "properties": {
+                "city": { "type": "string" },
+                "zip": { "type": "string" }
+            },
+            "required": ["city"]
+        })
+    }
+
+    fn registry() -> SchemaRegistry {
+        let mut registry = SchemaRegistry::new();
+        registry.insert("Address", address());
+        registry.insert(
+            "Customer",
+            json!({
+                "title": "Customer",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "address": address()
+                }
+            }),
+        );
+        registry.insert("Name", json!({ "type": "string" }));
+        registry
+    }
+
+    #[test]
+    fn test_replaces_inlined_definitions() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "billing": {
+                    "description": "Billing address",
+                    "type": "object",
+                    "properties": {
+                        "city": { "type": "string" },
+                        "zip": { "type": "string" }
+                    },
+                    "required": ["city"]
+                },
+                "backup": { "anyOf": [address(), { "type": "null" }] },
+                "label": { "type": "string" }
+            }
+        });
+
+        let compacted = registry().compact(&schema);
+        assert_eq!(
+            compacted["properties"]["billing"],
+            json!({ "$ref": "#/definitions/Address", "description": "Billing address" })
+        );
+        assert_eq!(
+            compacted["properties"]["backup"]["anyOf"][0],
+            json!({ "$ref": "#/definitions/Address" })
+        );
+        // Scalar definitions are never substituted
+        assert_eq!(
+            compacted["properties"]["label"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn test_different_constraints_do_not_match() {
+        let mut partial = address();
+        partial["required"] = json!(["city", "zip"]);
+        let schema = json!({ "type": "object", "properties": { "a": partial.clone() } });
+
+        assert_eq!(registry().compact(&schema)["properties"]["a"], partial);
+    }
+
+    #[test]
+    fn test_root_is_never_replaced() {
+        let compacted = registry().compact(&address());
+        assert_eq!(compacted, address());
+    }
+
+    #[test]
+    fn test_definitions_reference_each_other() {
+        let definitions = registry().definitions();
+        assert_eq!(
+            definitions["Customer"]["properties"]["address"],
+            json!({ "$ref": "#/definitions/Address" })
+        );
+        assert_eq!(definitions["Address"], address());
+    }
+
+    #[test]
+    fn test_inline_definitions_round_trip() {
+        let registry = registry();
+        let original = json!({
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "title": "Customer",
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "address": address()
+                    }
+                }
+            }
+        });
+        let tool = ToolInfo::new("create_order", None, registry.compact(&original));
+        assert_eq!(
+            tool.input_schema["properties"]["customer"],
+            json!({ "$ref": "#/definitions/Customer" })
+        );
+
+        let mut result = ListToolsResult::new(vec![tool]).with_definitions(registry.definitions());
+        result.inline_definitions();
+        assert!(result.definitions.is_none());
+        assert_eq!(result.tools[0].input_schema, original);
+    }
+}
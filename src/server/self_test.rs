@@ -0,0 +1,588 @@
+//! Startup self-test.
+//!
+//! [`Server::run_self_test`](crate::Server::run_self_test) (and the
+//! [`ServerCore`](crate::server::core::ServerCore) equivalent) checks that a
+//! built server can actually serve traffic before it is put behind a load
+//! balancer:
+//!
+//! - every tool's input and output schema compiles and its `$ref`s resolve,
+//! - every listed resource can be read,
+//! - every widget a tool's `_meta` points at can be read,
+//! - every workflow validates and only calls registered tools,
+//! - every secret declared with `self_test_secret` is set,
+//! - the task store and every backend declared with `self_test_backend`
+//!   respond,
+//! - every resource in the schema baseline registered with `schema_baseline`
+//!   is still listed (see [`schema_compat`](super::schema_compat)).
+//!
+//! All checks run; the [`SelfTestReport`] lists each one with its outcome.
+//! Generated servers run it when started with `--self-test` and exit
+//! non-zero on failure, which makes the binary usable as a container
+//! readiness gate.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn example() -> pmcp::Result<()> {
+//! use pmcp::Server;
+//!
+//! let server = Server::builder()
+//!     .name("orders")
+//!     .version("1.0.0")
+//!     .self_test_secret("DATABASE_URL")
+//!     .self_test_backend("inventory-api", || async {
+//!         // e.g. call a health endpoint or a foundation server tool
+//!         Ok(())
+//!     })
+//!     .build()?;
+//!
+//! let report = server.run_self_test().await;
+//! println!("{report}");
+//! if !report.passed() {
+//!     std::process::exit(1);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use tokio_util::sync::CancellationToken;
+
+use super::cancellation::RequestHandlerExtra;
+use super::schema_compat::SchemaGuard;
+use super::schema_registry::DEFINITIONS_REF_PREFIX;
+#[cfg(feature = "tasks")]
+use super::task_store::TaskStore;
+#[cfg(feature = "workflow")]
+use super::workflow::SequentialWorkflow;
+use super::ResourceHandler;
+use crate::types::ToolInfo;
+use crate::Result;
+
+/// Owner ID used when probing the task store.
+#[cfg(feature = "tasks")]
+const SELF_TEST_OWNER: &str = "pmcp-self-test";
+
+/// Upper bound on `resources/list` pages, in case a handler never stops
+/// returning cursors.
+const MAX_RESOURCE_PAGES: usize = 100;
+
+/// What a self-test check covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    /// A tool's input or output schema.
+    ToolSchema,
+    /// Reading a listed resource.
+    Resource,
+    /// Reading the widget a tool links to.
+    Widget,
+    /// A registered workflow.
+    Workflow,
+    /// A required secret.
+    Secret,
+    /// The task store or a declared backend.
+    Backend,
+    /// Compatibility with the schema baseline.
+    Schema,
+}
+
+impl fmt::Display for CheckKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ToolSchema => "tool schema",
+            Self::Resource => "resource",
+            Self::Widget => "widget",
+            Self::Workflow => "workflow",
+            Self::Secret => "secret",
+            Self::Backend => "backend",
+            Self::Schema => "schema",
+        })
+    }
+}
+
+/// Outcome of one self-test check.
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// What was checked.
+    pub kind: CheckKind,
+    /// Name of the tool, resource URI, workflow, secret, or backend.
+    pub name: String,
+    /// Why the check failed, or `None` if it passed.
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    /// Whether the check passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Results of [`Server::run_self_test`](crate::Server::run_self_test).
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    /// Every check that ran, in order.
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfTestReport {
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(CheckResult::passed)
+    }
+
+    /// The checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|c| !c.passed())
+    }
+
+    /// Process exit code for the report: `0` if every check passed, `1`
+    /// otherwise.
+    pub fn exit_code(&self) -> i32 {
+        i32::from(!self.passed())
+    }
+
+    fn record(&mut self, kind: CheckKind, name: impl Into<String>, outcome: Result<()>) {
+        self.checks.push(CheckResult {
+            kind,
+            name: name.into(),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.error {
+                None => writeln!(f, "ok   {} {}", check.kind, check.name)?,
+                Some(error) => writeln!(f, "FAIL {} {}: {}", check.kind, check.name, error)?,
+            }
+        }
+        let failed = self.failures().count();
+        write!(
+            f,
+            "self-test {}: {} checks, {} failed",
+            if failed == 0 { "passed" } else { "failed" },
+            self.checks.len(),
+            failed
+        )
+    }
+}
+
+type BackendFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// A named reachability check for an external backend.
+#[derive(Clone)]
+pub(crate) struct BackendCheck {
+    name: String,
+    check: Arc<dyn Fn() -> BackendFuture + Send + Sync>,
+}
+
+impl BackendCheck {
+    pub(crate) fn new<F, Fut>(name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            check: Arc::new(move || -> BackendFuture { Box::pin(check()) }),
+        }
+    }
+}
+
+/// What the builder records for the self-test beyond the server's own state.
+#[derive(Clone, Default)]
+pub(crate) struct SelfTestPlan {
+    #[cfg(feature = "workflow")]
+    workflows: Vec<SequentialWorkflow>,
+    secrets: Vec<String>,
+    backends: Vec<BackendCheck>,
+    schema_guard: Option<SchemaGuard>,
+}
+
+impl fmt::Debug for SelfTestPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("SelfTestPlan");
+        #[cfg(feature = "workflow")]
+        f.field("workflows", &self.workflows.len());
+        f.field("secrets", &self.secrets)
+            .field(
+                "backends",
+                &self.backends.iter().map(|b| &b.name).collect::<Vec<_>>(),
+            )
+            .field("schema_guard", &self.schema_guard.is_some())
+            .finish()
+    }
+}
+
+/// The parts of a built server the self-test inspects.
+pub(crate) struct SelfTestTarget<'a> {
+    pub(crate) tools: &'a HashMap<String, ToolInfo>,
+    pub(crate) definitions: Option<&'a Map<String, Value>>,
+    pub(crate) resources: Option<&'a Arc<dyn ResourceHandler>>,
+    #[cfg(feature = "tasks")]
+    pub(crate) task_store: Option<&'a Arc<dyn TaskStore>>,
+}
+
+impl SelfTestPlan {
+    #[cfg(feature = "workflow")]
+    pub(crate) fn add_workflow(&mut self, workflow: SequentialWorkflow) {
+        self.workflows.push(workflow);
+    }
+
+    /// The workflows registered as prompts.
+    #[cfg(feature = "workflow")]
+    pub(crate) fn workflows(&self) -> &[SequentialWorkflow] {
+        &self.workflows
+    }
+
+    pub(crate) fn add_secret(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        if !self.secrets.contains(&name) {
+            self.secrets.push(name);
+        }
+    }
+
+    pub(crate) fn add_backend(&mut self, backend: BackendCheck) {
+        self.backends.push(backend);
+    }
+
+    pub(crate) fn set_schema_guard(&mut self, guard: SchemaGuard) {
+        self.schema_guard = Some(guard);
+    }
+
+    pub(crate) async fn run(&self, target: SelfTestTarget<'_>) -> SelfTestReport {
+        let mut report = SelfTestReport::default();
+
+        let mut tools: Vec<_> = target.tools.values().collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        for tool in tools {
+            report.record(
+                CheckKind::ToolSchema,
+                &tool.name,
+                check_tool_schemas(tool, target.definitions),
+            );
+        }
+
+        let uris = match target.resources {
+            Some(resources) => check_resources(resources.as_ref(), &mut report).await,
+            None => Vec::new(),
+        };
+        if let Some(guard) = &self.schema_guard {
+            report.record(
+                CheckKind::Schema,
+                "resources",
+                guard.check_resources(uris.iter().map(String::as_str)),
+            );
+        }
+
+        for (tool, uri) in widget_links(target.tools) {
+            let outcome = match target.resources {
+                Some(resources) => read_resource(resources.as_ref(), uri).await,
+                None => Err(crate::Error::validation(
+                    "no resource handler is registered to serve it",
+                )),
+            };
+            report.record(CheckKind::Widget, format!("{uri} (tool {tool})"), outcome);
+        }
+
+        #[cfg(feature = "workflow")]
+        for workflow in &self.workflows {
+            report.record(
+                CheckKind::Workflow,
+                workflow.name(),
+                check_workflow(workflow, target.tools),
+            );
+        }
+
+        for secret in &self.secrets {
+            let outcome = match std::env::var(secret) {
+                Ok(value) if !value.is_empty() => Ok(()),
+                Ok(_) => Err(crate::Error::validation("is set but empty")),
+                Err(_) => Err(crate::Error::validation("is not set")),
+            };
+            report.record(CheckKind::Secret, secret, outcome);
+        }
+
+        #[cfg(feature = "tasks")]
+        if let Some(store) = target.task_store {
+            let outcome = store
+                .list(SELF_TEST_OWNER, None)
+                .await
+                .map(drop)
+                .map_err(|e| crate::Error::internal(e.to_string()));
+            report.record(CheckKind::Backend, "task store", outcome);
+        }
+
+        for backend in &self.backends {
+            report.record(CheckKind::Backend, &backend.name, (backend.check)().await);
+        }
+
+        report
+    }
+}
+
+fn check_tool_schemas(tool: &ToolInfo, definitions: Option<&Map<String, Value>>) -> Result<()> {
+    check_schema("input", &tool.input_schema, definitions)?;
+    if let Some(output) = &tool.output_schema {
+        check_schema("output", output, definitions)?;
+    }
+    Ok(())
+}
+
+fn check_schema(
+    which: &str,
+    schema: &Value,
+    definitions: Option<&Map<String, Value>>,
+) -> Result<()> {
+    if !schema.is_object() {
+        return Err(crate::Error::validation(format!(
+            "{which} schema is not a JSON object"
+        )));
+    }
+    let mut unresolved = Vec::new();
+    collect_unresolved_refs(schema, schema, definitions, &mut unresolved);
+    if !unresolved.is_empty() {
+        return Err(crate::Error::validation(format!(
+            "{which} schema has unresolved $ref: {}",
+            unresolved.join(", ")
+        )));
+    }
+    #[cfg(feature = "validation")]
+    {
+        let mut schema = schema.clone();
+        if let (Some(definitions), Some(obj)) = (definitions, schema.as_object_mut()) {
+            obj.entry("definitions")
+                .or_insert_with(|| Value::Object(definitions.clone()));
+        }
+        jsonschema::validator_for(&schema).map_err(|e| {
+            crate::Error::validation(format!("{which} schema does not compile: {e}"))
+        })?;
+    }
+    Ok(())
+}
+
+/// Collect local `$ref`s that resolve neither within `root` nor against the
+/// shared `definitions`.
+fn collect_unresolved_refs(
+    value: &Value,
+    root: &Value,
+    definitions: Option<&Map<String, Value>>,
+    unresolved: &mut Vec<String>,
+) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(reference)) = obj.get("$ref") {
+                let shared = reference
+                    .strip_prefix(DEFINITIONS_REF_PREFIX)
+                    .is_some_and(|name| definitions.is_some_and(|d| d.contains_key(name)));
+                let local = reference
+                    .strip_prefix('#')
+                    .is_some_and(|pointer| root.pointer(pointer).is_some());
+                if reference.starts_with('#') && !shared && !local {
+                    unresolved.push(reference.clone());
+                }
+            }
+            for child in obj.values() {
+                collect_unresolved_refs(child, root, definitions, unresolved);
+            }
+        },
+        Value::Array(items) => {
+            for item in items {
+                collect_unresolved_refs(item, root, definitions, unresolved);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Read every listed resource, returning the listed URIs.
+async fn check_resources(
+    resources: &dyn ResourceHandler,
+    report: &mut SelfTestReport,
+) -> Vec<String> {
+    let mut uris = Vec::new();
+    let mut cursor = None;
+    for _ in 0..MAX_RESOURCE_PAGES {
+        match resources.list(cursor.take(), self_test_extra()).await {
+            Ok(page) => {
+                uris.extend(page.resources.into_iter().map(|r| r.uri));
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            },
+            Err(e) => {
+                report.record(CheckKind::Resource, "resources/list", Err(e));
+                return uris;
+            },
+        }
+    }
+
+    for uri in &uris {
+        let outcome = read_resource(resources, uri).await;
+        report.record(CheckKind::Resource, uri, outcome);
+    }
+    uris
+}
+
+async fn read_resource(resources: &dyn ResourceHandler, uri: &str) -> Result<()> {
+    match resources.read(uri, self_test_extra()).await {
+        Ok(result) if result.contents.is_empty() => {
+            Err(crate::Error::validation("read returned no contents"))
+        },
+        Ok(_) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// `(tool, widget URI)` pairs for every tool that links a widget, sorted by
+/// tool name.
+fn widget_links(tools: &HashMap<String, ToolInfo>) -> Vec<(&str, &str)> {
+    let mut links: Vec<_> = tools
+        .iter()
+        .flat_map(|(name, info)| {
+            info.widget_uris()
+                .into_iter()
+                .map(move |uri| (name.as_str(), uri))
+        })
+        .collect();
+    links.sort_unstable();
+    links
+}
+
+#[cfg(feature = "workflow")]
+fn check_workflow(workflow: &SequentialWorkflow, tools: &HashMap<String, ToolInfo>) -> Result<()> {
+    workflow
+        .validate()
+        .map_err(|e| crate::Error::validation(e.to_string()))?;
+    let missing: Vec<&str> = workflow
+        .steps()
+        .iter()
+        .filter_map(|step| step.tool())
+        .filter(|tool| !tool.is_remote())
+        .map(|tool| tool.name())
+        .filter(|name| !tools.contains_key(*name))
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(crate::Error::validation(format!(
+            "calls unregistered tools: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+fn self_test_extra() -> RequestHandlerExtra {
+    RequestHandlerExtra::new("self-test".to_string(), CancellationToken::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(name: &str, schema: Value) -> (String, ToolInfo) {
+        (name.to_string(), ToolInfo::new(name, None, schema))
+    }
+
+    #[tokio::test]
+    async fn reports_unresolved_schema_refs() {
+        let tools: HashMap<_, _> = [
+            tool("ok", json!({ "type": "object" })),
+            tool(
+                "shared",
+                json!({ "type": "object", "properties": { "a": { "$ref": "#/definitions/Address" } } }),
+            ),
+            tool(
+                "broken",
+                json!({ "type": "object", "properties": { "a": { "$ref": "#/definitions/Missing" } } }),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let mut definitions = Map::new();
+        definitions.insert("Address".to_string(), json!({ "type": "object" }));
+
+        let report = SelfTestPlan::default()
+            .run(SelfTestTarget {
+                tools: &tools,
+                definitions: Some(&definitions),
+                resources: None,
+                #[cfg(feature = "tasks")]
+                task_store: None,
+            })
+            .await;
+
+        assert_eq!(report.checks.len(), 3);
+        let failures: Vec<_> = report.failures().map(|c| c.name.as_str()).collect();
+        assert_eq!(failures, vec!["broken"]);
+        assert_eq!(report.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn checks_secrets_and_backends() {
+        let mut plan = SelfTestPlan::default();
+        plan.add_secret("PMCP_SELF_TEST_SURELY_UNSET_SECRET");
+        plan.add_backend(BackendCheck::new("up", || async { Ok(()) }));
+        plan.add_backend(BackendCheck::new("down", || async {
+            Err(crate::Error::internal("connection refused"))
+        }));
+
+        let report = plan
+            .run(SelfTestTarget {
+                tools: &HashMap::new(),
+                definitions: None,
+                resources: None,
+                #[cfg(feature = "tasks")]
+                task_store: None,
+            })
+            .await;
+
+        let failures: Vec<_> = report
+            .failures()
+            .map(|c| (c.kind, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                (CheckKind::Secret, "PMCP_SELF_TEST_SURELY_UNSET_SECRET"),
+                (CheckKind::Backend, "down"),
+            ]
+        );
+        assert!(report.to_string().contains("2 failed"));
+    }
+
+    #[cfg(feature = "workflow")]
+    #[tokio::test]
+    async fn flags_workflows_calling_unregistered_tools() {
+        use crate::server::workflow::{ToolHandle, WorkflowStep};
+
+        let workflow = SequentialWorkflow::new("report", "Build a report")
+            .step(WorkflowStep::new("fetch", ToolHandle::new("fetch_data")));
+        let mut plan = SelfTestPlan::default();
+        plan.add_workflow(workflow);
+
+        let report = plan
+            .run(SelfTestTarget {
+                tools: &HashMap::new(),
+                definitions: None,
+                resources: None,
+                #[cfg(feature = "tasks")]
+                task_store: None,
+            })
+            .await;
+
+        let error = report.failures().next().unwrap().error.clone().unwrap();
+        assert!(error.contains("fetch_data"), "{error}");
+    }
+}
@@ -12,6 +12,7 @@ use std::sync::Arc;
 
 use super::cancellation::RequestHandlerExtra;
 use super::dynamic_resources::{DynamicResourceProvider, RequestContext, ResourceRouter};
+use super::pagination::CursorCodec;
 use super::ResourceHandler;
 
 /// A static resource that returns fixed content.
@@ -109,6 +110,8 @@ pub struct ResourceCollection {
     resources: HashMap<String, Arc<StaticResource>>,
     router: ResourceRouter,
     ui_resources: HashMap<String, (UIResource, UIResourceContents)>,
+    page_size: Option<usize>,
+    cursors: CursorCodec,
 }
 
 impl fmt::Debug for ResourceCollection {
@@ -119,6 +122,7 @@ impl fmt::Debug for ResourceCollection {
                 &self.resources.keys().collect::<Vec<_>>(),
             )
             .field("dynamic_providers", &self.router.all_templates().len())
+            .field("page_size", &self.page_size)
             .finish()
     }
 }
@@ -144,6 +148,8 @@ impl ResourceCollection {
             resources: HashMap::new(),
             router: ResourceRouter::new(),
             ui_resources: HashMap::new(),
+            page_size: None,
+            cursors: CursorCodec::random().for_list("resources/list"),
         }
     }
 
@@ -274,6 +280,32 @@ impl ResourceCollection {
         self
     }
 
+    /// Split `resources/list` results into pages of `page_size` resources.
+    ///
+    /// Resources are listed in URI order, with signed `nextCursor` values
+    /// (see [`CursorCodec`]). By default all resources are returned at once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::simple_resources::ResourceCollection;
+    ///
+    /// let collection = ResourceCollection::new().with_page_size(50);
+    /// ```
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sign cursors with `codec` instead of a random per-process key.
+    ///
+    /// Needed when several server instances serve the same clients, so a
+    /// cursor issued by one instance is accepted by the others.
+    pub fn with_cursor_codec(mut self, codec: CursorCodec) -> Self {
+        self.cursors = codec.for_list("resources/list");
+        self
+    }
+
     /// Get a static resource by URI.
     pub fn get(&self, uri: &str) -> Option<&Arc<StaticResource>> {
         self.resources.get(uri)
@@ -319,9 +351,7 @@ impl ResourceHandler for ResourceCollection {
     async fn read(&self, uri: &str, extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
         // Try static resources first (fast O(1) lookup)
         if let Some(resource) = self.resources.get(uri) {
-            return Ok(ReadResourceResult {
-                contents: vec![resource.content.clone()],
-            });
+            return Ok(ReadResourceResult::new(vec![resource.content.clone()]));
         }
 
         // Try UI resources (ui:// scheme)
@@ -343,14 +373,12 @@ impl ResourceHandler for ResourceCollection {
                     }
                     m
                 });
-            return Ok(ReadResourceResult {
-                contents: vec![Content::Resource {
-                    uri: contents.uri.clone(),
-                    text: contents.text.clone(),
-                    mime_type: Some(contents.mime_type.clone()),
-                    meta,
-                }],
-            });
+            return Ok(ReadResourceResult::new(vec![Content::Resource {
+                uri: contents.uri.clone(),
+                text: contents.text.clone(),
+                mime_type: Some(contents.mime_type.clone()),
+                meta,
+            }]));
         }
 
         // Try dynamic providers (pattern matching)
@@ -368,12 +396,24 @@ impl ResourceHandler for ResourceCollection {
 
     async fn list(
         &self,
-        _cursor: Option<String>,
+        cursor: Option<String>,
         _extra: RequestHandlerExtra,
     ) -> Result<ListResourcesResult> {
+        let Some(page_size) = self.page_size else {
+            return Ok(ListResourcesResult {
+                resources: self.list(),
+                next_cursor: None,
+            });
+        };
+
+        let mut resources = self.list();
+        resources.sort_by(|a, b| a.uri.cmp(&b.uri));
+        let page = self
+            .cursors
+            .paginate(resources, cursor.as_deref(), page_size)?;
         Ok(ListResourcesResult {
-            resources: self.list(),
-            next_cursor: None,
+            resources: page.items,
+            next_cursor: page.next_cursor,
         })
     }
 }
@@ -640,4 +680,41 @@ mod tests {
             _ => panic!("Expected Content::Resource"),
         }
     }
+
+    #[tokio::test]
+    async fn test_resource_collection_list_pages() {
+        use tokio_util::sync::CancellationToken;
+
+        let collection = (0..5)
+            .fold(ResourceCollection::new(), |c, i| {
+                c.add_resource(StaticResource::new_text(format!("test://doc-{i}"), "text"))
+            })
+            .with_page_size(2);
+        let extra = || RequestHandlerExtra::new("test-req".to_string(), CancellationToken::new());
+
+        let mut uris = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = ResourceHandler::list(&collection, cursor, extra())
+                .await
+                .unwrap();
+            assert!(page.resources.len() <= 2);
+            uris.extend(page.resources.into_iter().map(|r| r.uri));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(
+            uris,
+            (0..5)
+                .map(|i| format!("test://doc-{i}"))
+                .collect::<Vec<_>>()
+        );
+
+        let err = ResourceHandler::list(&collection, Some("bogus".to_string()), extra())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_code(), Some(crate::ErrorCode::INVALID_PARAMS));
+    }
 }
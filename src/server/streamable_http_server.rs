@@ -4,13 +4,25 @@ use crate::server::http_middleware::{
     adapters::{from_axum, into_axum},
     ServerHttpContext, ServerHttpMiddlewareChain, ServerHttpResponse,
 };
-use crate::server::tower_layers::{AllowedOrigins, DnsRebindingLayer, SecurityHeadersLayer};
+use crate::server::observability::{PrometheusMetrics, RequestTimer, TraceContext};
+use crate::server::resource_profile::ResourceProfile;
+#[cfg(feature = "tasks")]
+use crate::server::task_store::{TaskStatusUpdate, TaskStatusUpdates};
+use crate::server::tower_layers::{
+    AllowedOrigins, CompressionConfig, DnsRebindingLayer, SecurityHeadersLayer,
+};
 use crate::server::Server;
 use crate::shared::http_constants::{
     APPLICATION_JSON, LAST_EVENT_ID, MCP_PROTOCOL_VERSION, MCP_SESSION_ID, TEXT_EVENT_STREAM,
+    TRACEPARENT, TRACESTATE,
 };
+use crate::shared::protocol_helpers::client_request_method;
 use crate::shared::TransportMessage;
-use crate::types::{ClientRequest, Request};
+#[cfg(feature = "tasks")]
+use crate::types::tasks::TaskStatusNotification;
+use crate::types::{ClientRequest, Request, RequestId};
+#[cfg(any(feature = "tasks", feature = "resources"))]
+use crate::types::{Notification, ServerNotification};
 use async_trait::async_trait;
 use axum::{
     body::Body,
@@ -23,12 +35,14 @@ use axum::{
 use futures_util::StreamExt;
 use parking_lot::RwLock;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::Arc;
+#[cfg(all(not(target_arch = "wasm32"), feature = "tasks"))]
+use tokio::sync::broadcast;
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Semaphore};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use uuid::Uuid;
 
@@ -67,7 +81,55 @@ pub struct InMemoryEventStore {
     /// Event ID to stream ID mapping
     event_to_stream: Arc<RwLock<HashMap<String, String>>>,
     /// Ordered list of all event IDs
-    event_order: Arc<RwLock<Vec<String>>>,
+    event_order: Arc<RwLock<VecDeque<String>>>,
+    /// Maximum number of retained events (oldest evicted first)
+    max_events: Option<usize>,
+    /// Largest serialized event that will be cached
+    max_event_bytes: Option<usize>,
+}
+
+impl InMemoryEventStore {
+    /// Create an event store that retains at most `max_events` events and
+    /// skips caching events larger than `max_event_bytes` when serialized.
+    ///
+    /// `None` leaves the corresponding limit unbounded.
+    pub fn with_limits(max_events: Option<usize>, max_event_bytes: Option<usize>) -> Self {
+        Self {
+            max_events,
+            max_event_bytes,
+            ..Self::default()
+        }
+    }
+
+    /// Number of events currently retained.
+    pub fn len(&self) -> usize {
+        self.event_order.read().len()
+    }
+
+    /// Returns `true` if no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.event_order.read().is_empty()
+    }
+
+    /// Evict the oldest events until the store is within `max_events`.
+    fn evict_over_capacity(&self, max_events: usize) {
+        let mut events = self.events.write();
+        let mut event_to_stream = self.event_to_stream.write();
+        let mut event_order = self.event_order.write();
+        while event_order.len() > max_events {
+            let Some(oldest) = event_order.pop_front() else {
+                break;
+            };
+            if let Some(stream_id) = event_to_stream.remove(&oldest) {
+                if let Some(stream_events) = events.get_mut(&stream_id) {
+                    stream_events.retain(|(eid, _)| eid != &oldest);
+                    if stream_events.is_empty() {
+                        events.remove(&stream_id);
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -78,14 +140,30 @@ impl EventStore for InMemoryEventStore {
         event_id: &str,
         message: &TransportMessage,
     ) -> Result<()> {
-        let mut events = self.events.write();
-        let stream_events = events.entry(stream_id.to_string()).or_default();
-        stream_events.push((event_id.to_string(), message.clone()));
+        if self.max_events == Some(0) {
+            return Ok(());
+        }
+        if let Some(max_bytes) = self.max_event_bytes {
+            let size = serde_json::to_vec(message).map_or(usize::MAX, |bytes| bytes.len());
+            if size > max_bytes {
+                return Ok(());
+            }
+        }
 
-        self.event_to_stream
-            .write()
-            .insert(event_id.to_string(), stream_id.to_string());
-        self.event_order.write().push(event_id.to_string());
+        {
+            let mut events = self.events.write();
+            let stream_events = events.entry(stream_id.to_string()).or_default();
+            stream_events.push((event_id.to_string(), message.clone()));
+
+            self.event_to_stream
+                .write()
+                .insert(event_id.to_string(), stream_id.to_string());
+            self.event_order.write().push_back(event_id.to_string());
+        }
+
+        if let Some(max_events) = self.max_events {
+            self.evict_over_capacity(max_events);
+        }
 
         Ok(())
     }
@@ -134,40 +212,31 @@ type SessionCallback = Box<dyn Fn(&str) + Send + Sync>;
 
 /// Configuration for the streamable HTTP server.
 ///
+/// This struct is `#[non_exhaustive]`. Start from [`Default::default()`],
+/// [`stateless()`](Self::stateless) or [`from_profile()`](Self::from_profile)
+/// and set fields or use the `with_*` methods to remain forward-compatible.
+///
 /// # Examples
 ///
 /// ```rust
 /// use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
-/// use std::sync::Arc;
 ///
 /// // Stateless configuration (for serverless/Lambda)
-/// let config = StreamableHttpServerConfig {
-///     session_id_generator: None,  // No sessions
-///     enable_json_response: false,
-///     event_store: None,
-///     on_session_initialized: None,
-///     on_session_closed: None,
-///     http_middleware: None,
-///     allowed_origins: None,
-/// };
+/// let config = StreamableHttpServerConfig::stateless();
 ///
 /// // Stateful configuration with custom session IDs
-/// let config = StreamableHttpServerConfig {
-///     session_id_generator: Some(Box::new(|| {
-///         format!("session-{}", uuid::Uuid::new_v4())
-///     })),
-///     enable_json_response: false,
-///     event_store: None,
-///     on_session_initialized: Some(Box::new(|session_id| {
-///         println!("Session started: {}", session_id);
-///     })),
-///     on_session_closed: Some(Box::new(|session_id| {
-///         println!("Session ended: {}", session_id);
-///     })),
-///     http_middleware: None,
-///     allowed_origins: None,
-/// };
+/// let mut config = StreamableHttpServerConfig::default();
+/// config.session_id_generator = Some(Box::new(|| {
+///     format!("session-{}", uuid::Uuid::new_v4())
+/// }));
+/// config.on_session_initialized = Some(Box::new(|session_id| {
+///     println!("Session started: {}", session_id);
+/// }));
+/// config.on_session_closed = Some(Box::new(|session_id| {
+///     println!("Session ended: {}", session_id);
+/// }));
 /// ```
+#[non_exhaustive]
 pub struct StreamableHttpServerConfig {
     /// Function to generate session IDs (None for stateless mode)
     pub session_id_generator: Option<Box<dyn Fn() -> String + Send + Sync>>,
@@ -191,11 +260,43 @@ pub struct StreamableHttpServerConfig {
     /// path uses [`crate::server::axum_router::RouterConfig::allowed_origins`]
     /// instead.
     pub allowed_origins: Option<AllowedOrigins>,
+    /// Negotiated response compression and request decompression.
+    ///
+    /// `None` (the default) sends and accepts uncompressed bodies only.
+    /// SSE streams are never compressed. See [`CompressionConfig`].
+    pub compression: Option<CompressionConfig>,
+    /// Request concurrency and body size limits.
+    ///
+    /// `None` (the default) enforces no limits. Set via
+    /// [`from_profile()`](Self::from_profile) to also apply the profile's
+    /// session, response, and event store settings. See [`ResourceProfile`].
+    pub resource_profile: Option<ResourceProfile>,
+    /// Task transitions to push as `notifications/tasks/status`.
+    ///
+    /// Each update is sent on the standalone SSE stream (`GET`) of the
+    /// sessions that belong to the task's owner and whose client declared the
+    /// `tasks` capability. Use [`TaskStore::status_updates`] to get the
+    /// channel of a store. `None` (the default) pushes nothing; clients poll
+    /// `tasks/get` instead.
+    ///
+    /// [`TaskStore::status_updates`]: crate::server::task_store::TaskStore::status_updates
+    #[cfg(feature = "tasks")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
+    pub task_status_updates: Option<TaskStatusUpdates>,
+    /// Prometheus metrics for the requests this server handles.
+    ///
+    /// When set, every JSON-RPC request is counted and timed, and the
+    /// registry is served in the Prometheus text format on `GET /metrics`.
+    /// The route sits behind the same Host/Origin validation as the MCP
+    /// endpoint, so scrapers must use an allowed host. `None` (the default)
+    /// records nothing and serves no `/metrics` route.
+    pub metrics: Option<PrometheusMetrics>,
 }
 
 impl std::fmt::Debug for StreamableHttpServerConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("StreamableHttpServerConfig")
+        let mut debug = f.debug_struct("StreamableHttpServerConfig");
+        debug
             .field("session_id_generator", &self.session_id_generator.is_some())
             .field("enable_json_response", &self.enable_json_response)
             .field("event_store", &self.event_store.is_some())
@@ -206,7 +307,11 @@ impl std::fmt::Debug for StreamableHttpServerConfig {
             .field("on_session_closed", &self.on_session_closed.is_some())
             .field("http_middleware", &self.http_middleware.is_some())
             .field("allowed_origins", &self.allowed_origins)
-            .finish()
+            .field("compression", &self.compression)
+            .field("resource_profile", &self.resource_profile);
+        #[cfg(feature = "tasks")]
+        debug.field("task_status_updates", &self.task_status_updates.is_some());
+        debug.field("metrics", &self.metrics.is_some()).finish()
     }
 }
 
@@ -220,6 +325,11 @@ impl Default for StreamableHttpServerConfig {
             on_session_closed: None,
             http_middleware: None,
             allowed_origins: None,
+            compression: None,
+            resource_profile: None,
+            #[cfg(feature = "tasks")]
+            task_status_updates: None,
+            metrics: None,
         }
     }
 }
@@ -246,8 +356,96 @@ impl StreamableHttpServerConfig {
             on_session_closed: None,
             http_middleware: None,
             allowed_origins: Some(AllowedOrigins::any()),
+            compression: None,
+            resource_profile: None,
+            #[cfg(feature = "tasks")]
+            task_status_updates: None,
+            metrics: None,
         }
     }
+
+    /// Create a configuration from a [`ResourceProfile`].
+    ///
+    /// Sessions, JSON responses, and the event store follow the profile, and
+    /// its concurrency and body size limits are enforced on `POST` requests.
+    /// Profiles without sessions use [`AllowedOrigins::any()`], as in
+    /// [`stateless()`](Self::stateless).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::resource_profile::ResourceProfile;
+    /// use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+    ///
+    /// let config = StreamableHttpServerConfig::from_profile(ResourceProfile::serverless());
+    /// assert!(config.session_id_generator.is_none());
+    /// assert!(config.event_store.is_none());
+    /// ```
+    pub fn from_profile(profile: ResourceProfile) -> Self {
+        let base = if profile.sessions {
+            Self::default()
+        } else {
+            Self::stateless()
+        };
+        let event_store = profile.event_store_enabled().then(|| {
+            Arc::new(InMemoryEventStore::with_limits(
+                profile.event_store_max_events,
+                profile.event_store_max_event_bytes,
+            ))
+        });
+        Self {
+            enable_json_response: profile.json_responses,
+            event_store,
+            resource_profile: Some(profile),
+            ..base
+        }
+    }
+
+    /// Enable negotiated response compression and request decompression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::tower_layers::CompressionConfig;
+    /// use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+    ///
+    /// let config = StreamableHttpServerConfig::stateless()
+    ///     .with_compression(CompressionConfig::new().without_zstd());
+    /// assert!(config.compression.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Enforce the concurrency and body size limits of a [`ResourceProfile`].
+    ///
+    /// Unlike [`from_profile()`](Self::from_profile), this keeps the
+    /// session, response, and event store settings already configured.
+    #[must_use]
+    pub fn with_resource_profile(mut self, profile: ResourceProfile) -> Self {
+        self.resource_profile = Some(profile);
+        self
+    }
+
+    /// Push the task transitions of `updates` to tasks-capable sessions.
+    ///
+    /// See [`task_status_updates`](Self::task_status_updates).
+    #[cfg(feature = "tasks")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tasks")))]
+    #[must_use]
+    pub fn with_task_status_updates(mut self, updates: TaskStatusUpdates) -> Self {
+        self.task_status_updates = Some(updates);
+        self
+    }
+
+    /// Record request metrics and serve them on `GET /metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: PrometheusMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
 }
 
 /// Session information
@@ -255,6 +453,12 @@ impl StreamableHttpServerConfig {
 struct SessionInfo {
     initialized: bool,
     protocol_version: Option<String>,
+    /// Sequence number of the last SSE event id issued in this session
+    last_event_seq: u64,
+    /// Task owner whose status updates this session receives, if its client
+    /// declared the `tasks` capability
+    #[cfg_attr(not(feature = "tasks"), allow(dead_code))]
+    task_status_owner: Option<String>,
 }
 
 /// Server state shared across routes.
@@ -265,20 +469,112 @@ pub(crate) struct ServerState {
     /// Pre-resolved allowed origins for CORS and DNS rebinding protection.
     allowed_origins: AllowedOrigins,
     /// Active SSE streams by session ID
-    sse_streams: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<TransportMessage>>>>,
+    sse_streams: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<SseEvent>>>>,
     /// Session tracking (session ID -> session info)
     sessions: Arc<RwLock<HashMap<String, SessionInfo>>>,
+    /// Permits bounding concurrent POST requests, if a limit is configured
+    request_permits: Option<Arc<Semaphore>>,
+    /// Set once the server's resource watchers deliver to SSE streams
+    #[cfg(feature = "resources")]
+    resource_watchers: Arc<tokio::sync::OnceCell<()>>,
 }
 
 /// Build the base MCP Router without any Tower layers applied.
 ///
 /// Used by both [`StreamableHttpServer::start()`] and `pmcp::axum::router()`.
 pub(crate) fn build_mcp_router(state: ServerState) -> Router<()> {
-    Router::new()
+    let router = Router::new()
         .route("/", post(handle_post_request))
         .route("/", get(handle_get_sse))
-        .route("/", delete(handle_delete_session))
-        .with_state(state)
+        .route("/", delete(handle_delete_session));
+    let router = if state.config.metrics.is_some() {
+        router.route("/metrics", get(handle_metrics))
+    } else {
+        router
+    };
+    router.with_state(state)
+}
+
+/// Serve the Prometheus metrics registry.
+async fn handle_metrics(State(state): State<ServerState>) -> Response {
+    match &state.config.metrics {
+        Some(metrics) => (
+            [(
+                header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )],
+            metrics.render(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Start timing `request` if Prometheus metrics are enabled.
+///
+/// Tool calls are labelled with the tool name only if `server` registers
+/// the tool; other names share the `unknown` label, so clients cannot add
+/// series by calling made-up tools.
+fn start_request_timer(
+    metrics: Option<&PrometheusMetrics>,
+    server: &Server,
+    request: &Request,
+) -> Option<RequestTimer> {
+    let metrics = metrics?;
+    let Request::Client(request) = request else {
+        return Some(metrics.start_request("unknown", None));
+    };
+    let tool = match request.as_ref() {
+        ClientRequest::CallTool(call) if server.has_tool(&call.name) => Some(call.name.clone()),
+        ClientRequest::CallTool(_) => Some("unknown".to_string()),
+        _ => None,
+    };
+    Some(metrics.start_request(client_request_method(request), tool))
+}
+
+/// Record the outcome of a timed request.
+fn finish_request_timer(timer: Option<RequestTimer>, response: &crate::types::JSONRPCResponse) {
+    if let Some(timer) = timer {
+        let error_code = match &response.payload {
+            crate::types::jsonrpc::ResponsePayload::Error(error) => Some(error.code),
+            crate::types::jsonrpc::ResponsePayload::Result(_) => None,
+        };
+        timer.finish(error_code);
+    }
+}
+
+/// The caller's trace context, from W3C `traceparent`/`tracestate` headers.
+fn incoming_trace(headers: &HeaderMap) -> Option<TraceContext> {
+    let traceparent = headers.get(TRACEPARENT)?.to_str().ok()?;
+    let tracestate = headers.get(TRACESTATE).and_then(|v| v.to_str().ok());
+    TraceContext::from_traceparent(traceparent, tracestate)
+}
+
+/// Run `future` in the scope of one HTTP request: the caller's trace context
+/// becomes the current trace, so the spans it records join the caller's
+/// distributed trace, and `session_id` becomes the request's session.
+async fn with_request_scope<F: std::future::Future>(
+    trace: Option<TraceContext>,
+    session_id: Option<String>,
+    future: F,
+) -> F::Output {
+    let future = crate::server::cancellation::with_request_session(session_id, future);
+    match trace {
+        Some(trace) => trace.scope(future).await,
+        None => future.await,
+    }
+}
+
+/// Build the base MCP Router with the configured compression layers, if any.
+///
+/// Used by both [`StreamableHttpServer::start()`] and `pmcp::axum::router()`.
+pub(crate) fn build_compressed_router(state: ServerState) -> Router<()> {
+    let compression = state.config.compression;
+    let router = build_mcp_router(state);
+    match compression {
+        Some(compression) => compression.apply(router),
+        None => router,
+    }
 }
 
 /// Create a [`ServerState`] for the MCP router.
@@ -293,12 +589,19 @@ pub(crate) fn make_server_state(
         .allowed_origins
         .clone()
         .unwrap_or_else(AllowedOrigins::localhost);
+    let request_permits = config
+        .resource_profile
+        .and_then(|profile| profile.max_concurrent_requests)
+        .map(|limit| Arc::new(Semaphore::new(limit)));
     ServerState {
         server,
         config: Arc::new(config),
         allowed_origins,
         sse_streams: Arc::new(RwLock::new(HashMap::new())),
         sessions: Arc::new(RwLock::new(HashMap::new())),
+        request_permits,
+        #[cfg(feature = "resources")]
+        resource_watchers: Arc::new(tokio::sync::OnceCell::new()),
     }
 }
 
@@ -357,24 +660,79 @@ impl StreamableHttpServer {
     /// - [`CorsLayer`] -- origin-locked CORS (no wildcard `*`)
     /// - [`DnsRebindingLayer`] -- Host/Origin header validation
     /// - [`SecurityHeadersLayer`] -- nosniff, DENY, no-store
+    ///
+    /// Also starts the server's resource watchers, if any. Routers built
+    /// without `start()` start them when the first standalone SSE stream
+    /// opens.
     pub async fn start(self) -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
-        let allowed = self.state.allowed_origins.clone();
-        let cors = crate::server::tower_layers::build_mcp_cors_layer(&allowed);
-
-        // Layer ordering: CORS (outermost) -> DnsRebinding -> SecurityHeaders -> handler
-        let app = build_mcp_router(self.state)
-            .layer(SecurityHeadersLayer::default())
-            .layer(DnsRebindingLayer::new(allowed))
-            .layer(cors);
-
         let listener = tokio::net::TcpListener::bind(self.addr).await?;
         let local_addr = listener.local_addr()?;
+        #[cfg(feature = "resources")]
+        start_resource_watchers(&self.state).await?;
+        {
+            let server = self.state.server.lock().await;
+            server
+                .events()
+                .emit(crate::server::event_bus::ServerEvent::ServerStarted {
+                    server: server.info.clone(),
+                });
+        }
+
+        let app = build_layered_router(self.state);
         let server_task = tokio::spawn(async move {
             axum::serve(listener, app).await.unwrap();
         });
 
         Ok((local_addr, server_task))
     }
+
+    /// Creates an in-process test client for this server.
+    ///
+    /// The client drives the same router and Tower layers as
+    /// [`start()`](Self::start) without binding a port, so HTTP behavior
+    /// (status codes, headers, sessions, SSE) can be tested directly.
+    /// Clients created from the same server share session state.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::streamable_http_server::StreamableHttpServer;
+    /// use pmcp::Server;
+    /// use std::sync::Arc;
+    /// use tokio::sync::Mutex;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder().name("test").version("1.0.0").build()?;
+    /// let http = StreamableHttpServer::new(
+    ///     "127.0.0.1:0".parse().unwrap(),
+    ///     Arc::new(Mutex::new(server)),
+    /// );
+    ///
+    /// let client = http.test_client();
+    /// client.initialize().await.assert_success().await;
+    /// let tools = client.request("tools/list", serde_json::json!({})).await;
+    /// let result = tools.assert_result().await;
+    /// assert!(result["tools"].is_array());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn test_client(&self) -> crate::server::test_client::TestClient {
+        crate::server::test_client::TestClient::new(build_layered_router(self.state.clone()))
+    }
+}
+
+/// Build the MCP router with the full Tower security stack applied.
+///
+/// Layer ordering: CORS (outermost) -> DnsRebinding -> SecurityHeaders ->
+/// Compression (if configured) -> handler
+fn build_layered_router(state: ServerState) -> Router<()> {
+    let allowed = state.allowed_origins.clone();
+    let cors = crate::server::tower_layers::build_mcp_cors_layer(&allowed);
+
+    build_compressed_router(state)
+        .layer(SecurityHeadersLayer::default())
+        .layer(DnsRebindingLayer::new(allowed))
+        .layer(cors)
 }
 
 /// Validate request headers and return appropriate error response.
@@ -474,6 +832,8 @@ fn process_init_session(
                 SessionInfo {
                     initialized: false,
                     protocol_version,
+                    last_event_seq: 0,
+                    task_status_owner: None,
                 },
             );
             if let Some(callback) = &state.config.on_session_initialized {
@@ -542,19 +902,274 @@ fn update_session_after_init(
     state: &ServerState,
     session_id: Option<&String>,
     negotiated_version: Option<String>,
+    task_status_owner: Option<String>,
 ) {
     if let Some(sid) = session_id {
         if let Some(session_info) = state.sessions.write().get_mut(sid) {
             session_info.initialized = true;
             session_info.protocol_version =
                 negotiated_version.or_else(|| Some(crate::DEFAULT_PROTOCOL_VERSION.to_string()));
+            session_info.task_status_owner = task_status_owner;
         }
     }
 }
 
+/// Task owner to push status updates to for an `initialize` request, if
+/// updates are configured and the client declared the `tasks` capability.
+#[cfg(feature = "tasks")]
+fn task_status_owner(
+    state: &ServerState,
+    request: &Request,
+    auth_context: Option<&crate::server::auth::AuthContext>,
+) -> Option<String> {
+    state.config.task_status_updates.as_ref()?;
+    let Request::Client(boxed) = request else {
+        return None;
+    };
+    let ClientRequest::Initialize(init) = boxed.as_ref() else {
+        return None;
+    };
+    init.capabilities
+        .tasks
+        .as_ref()
+        .map(|_| crate::server::task_store::owner_for(auth_context))
+}
+
+/// Without the `tasks` feature there are no status updates to push.
+#[cfg(not(feature = "tasks"))]
+fn task_status_owner(
+    _state: &ServerState,
+    _request: &Request,
+    _auth_context: Option<&crate::server::auth::AuthContext>,
+) -> Option<String> {
+    None
+}
+
+/// Forward task status updates to a session's standalone SSE stream.
+///
+/// Runs until the session is closed, its stream is dropped, or the update
+/// channel closes. Updates for other owners are skipped.
+#[cfg(feature = "tasks")]
+async fn forward_task_status(
+    state: ServerState,
+    session_id: String,
+    mut updates: broadcast::Receiver<TaskStatusUpdate>,
+) {
+    loop {
+        let update = match updates.recv().await {
+            Ok(update) => update,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    session_id = session_id.as_str(),
+                    skipped,
+                    "Task status updates skipped for slow SSE stream"
+                );
+                continue;
+            },
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let owner = match state.sessions.read().get(&session_id) {
+            Some(info) => info.task_status_owner.clone(),
+            None => return,
+        };
+        if owner.as_deref() != Some(update.owner_id.as_str()) {
+            continue;
+        }
+        let Some(sender) = state.sse_streams.read().get(&session_id).cloned() else {
+            return;
+        };
+
+        let message = TransportMessage::Notification(Notification::Server(
+            ServerNotification::TaskStatus(TaskStatusNotification { task: update.task }),
+        ));
+        let event_id = record_event(&state, Some(&session_id), &message).await;
+        if sender.send((event_id, message)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Start the server's resource watchers, once.
+///
+/// Resource subscriptions over HTTP are keyed by session ID, so each change
+/// is delivered to the standalone SSE stream of every subscribed session.
+#[cfg(feature = "resources")]
+async fn start_resource_watchers(state: &ServerState) -> Result<()> {
+    state
+        .resource_watchers
+        .get_or_try_init(|| async {
+            let server = state.server.lock().await;
+            let (tx, rx) = mpsc::unbounded_channel();
+            server
+                .subscription_manager
+                .write()
+                .await
+                .set_subscriber_notification_sender(move |session_id, notification| {
+                    let _ = tx.send((session_id.to_string(), notification));
+                });
+            tokio::spawn(forward_resource_updates(state.clone(), rx));
+            crate::server::subscriptions::start_watchers(
+                &server.resource_watchers,
+                server.subscription_manager.clone(),
+            )
+            .await
+        })
+        .await
+        .map(|_| ())
+}
+
+/// Forward resource change notifications to the standalone SSE streams of
+/// the sessions they are addressed to.
+///
+/// Notifications for sessions without an open stream are dropped; clients
+/// re-read subscribed resources when they reconnect.
+#[cfg(feature = "resources")]
+async fn forward_resource_updates(
+    state: ServerState,
+    mut updates: mpsc::UnboundedReceiver<(String, ServerNotification)>,
+) {
+    while let Some((session_id, notification)) = updates.recv().await {
+        let Some(sender) = state.sse_streams.read().get(&session_id).cloned() else {
+            continue;
+        };
+        let message = TransportMessage::Notification(Notification::Server(notification));
+        let event_id = record_event(&state, Some(&session_id), &message).await;
+        let _ = sender.send((event_id, message));
+    }
+}
+
+/// An SSE event id paired with the message it carries.
+type SseEvent = (String, TransportMessage);
+
+/// Assign `message` its SSE event id and keep it for replay.
+///
+/// Within a session, ids are sequence numbers counting up from 1 (see
+/// [`sequenced_event_id`](crate::shared::streamable_http::sequenced_event_id)),
+/// so a client resuming from `Last-Event-ID` can tell whether the replay
+/// skipped anything. Events outside a session get a random id and are not
+/// stored.
+async fn record_event(
+    state: &ServerState,
+    session_id: Option<&String>,
+    message: &TransportMessage,
+) -> String {
+    let Some(sid) = session_id else {
+        return Uuid::new_v4().to_string();
+    };
+    let seq = state.sessions.write().get_mut(sid).map(|info| {
+        info.last_event_seq += 1;
+        info.last_event_seq
+    });
+    let Some(seq) = seq else {
+        return Uuid::new_v4().to_string();
+    };
+
+    let event_id = crate::shared::streamable_http::sequenced_event_id(sid, seq);
+    if let Some(event_store) = &state.config.event_store {
+        let _ = event_store.store_event(sid, &event_id, message).await;
+    }
+    event_id
+}
+
+/// Stream events from `rx` as an SSE response, ending when `rx` closes.
+fn sse_response(rx: mpsc::UnboundedReceiver<SseEvent>) -> Response {
+    let stream = UnboundedReceiverStream::new(rx);
+    let sse = Sse::new(stream.map(|(event_id, msg)| {
+        // Use JSON-RPC compatibility layer for SSE messages
+        let json_bytes =
+            crate::shared::StdioTransport::serialize_message(&msg).unwrap_or_else(|e| {
+                tracing::error!(target: "mcp.sse", error = %e, "Failed to serialize SSE message");
+                Vec::new()
+            });
+        let json_str = String::from_utf8(json_bytes).unwrap_or_else(|_| "{}".to_string());
+        Ok::<_, Infallible>(
+            Event::default()
+                .id(event_id)
+                .event("message")
+                .data(json_str),
+        )
+    }));
+
+    sse.into_response()
+}
+
+/// Whether `request` should stream its notifications before the response.
+///
+/// Tool calls that carry a progress token get their own SSE stream, so
+/// progress updates and chunks from
+/// [`RequestHandlerExtra::stream_chunk`](crate::server::cancellation::RequestHandlerExtra::stream_chunk)
+/// reach the client while the tool runs. JSON response mode has no stream
+/// to carry them.
+fn streams_notifications(state: &ServerState, request: &Request) -> bool {
+    if state.config.enable_json_response {
+        return false;
+    }
+    #[allow(clippy::used_underscore_binding)] // _meta is part of MCP protocol spec
+    match request {
+        Request::Client(boxed) => match boxed.as_ref() {
+            ClientRequest::CallTool(req) => req
+                ._meta
+                .as_ref()
+                .is_some_and(|meta| meta.progress_token.is_some()),
+            _ => false,
+        },
+        Request::Server(_) => false,
+    }
+}
+
+/// Handle `request` on a background task, answering with an SSE stream.
+///
+/// Notifications raised while the request is handled are sent on the stream
+/// as they happen, followed by the response, after which the stream closes.
+fn stream_request(
+    state: &ServerState,
+    id: RequestId,
+    request: Request,
+    auth_context: Option<crate::server::auth::AuthContext>,
+    session_id: Option<String>,
+    trace: Option<TraceContext>,
+) -> Response {
+    let (message_tx, mut message_rx) = mpsc::unbounded_channel();
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    // Number and store messages in the order they are produced.
+    let request_session = session_id.clone();
+    let forward_state = state.clone();
+    tokio::spawn(async move {
+        while let Some(message) = message_rx.recv().await {
+            let event_id = record_event(&forward_state, session_id.as_ref(), &message).await;
+            if tx.send((event_id, message)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let server = state.server.clone();
+    let metrics = state.config.metrics.clone();
+    tokio::spawn(async move {
+        let notifications = message_tx.clone();
+        let notifier: crate::server::progress::NotificationSender = Arc::new(move |notification| {
+            let _ = notifications.send(TransportMessage::Notification(notification));
+        });
+        let handle = with_request_scope(trace, request_session, async {
+            let server = server.lock().await;
+            let timer = start_request_timer(metrics.as_ref(), &server, &request);
+            let json_response = server.handle_request(id, request, auth_context).await;
+            finish_request_timer(timer, &json_response);
+            json_response
+        });
+        let json_response = crate::server::progress::with_request_notifier(notifier, handle).await;
+
+        let _ = message_tx.send(TransportMessage::Response(json_response));
+    });
+
+    sse_response(rx)
+}
+
 /// Build response with appropriate format (JSON or SSE).
 fn build_response(
     state: &ServerState,
+    event_id: String,
     response: TransportMessage,
     session_id: Option<&String>,
 ) -> Response {
@@ -603,33 +1218,13 @@ fn build_response(
         if let Some(sid) = session_id {
             if let Some(sender) = state.sse_streams.read().get(sid) {
                 // Send to existing SSE stream
-                let _ = sender.send(response);
+                let _ = sender.send((event_id, response));
                 StatusCode::ACCEPTED.into_response()
             } else {
                 // Return as SSE stream
                 let (tx, rx) = mpsc::unbounded_channel();
-                tx.send(response).unwrap();
-
-                let stream = UnboundedReceiverStream::new(rx);
-                let sse = Sse::new(stream.map(|msg| {
-                    let event_id = Uuid::new_v4().to_string();
-                    // Use JSON-RPC compatibility layer for SSE messages
-                    let json_bytes = crate::shared::StdioTransport::serialize_message(&msg)
-                        .unwrap_or_else(|e| {
-                            tracing::error!(target: "mcp.sse", error = %e, "Failed to serialize SSE message");
-                            Vec::new()
-                        });
-                    let json_str =
-                        String::from_utf8(json_bytes).unwrap_or_else(|_| "{}".to_string());
-                    Ok::<_, Infallible>(
-                        Event::default()
-                            .id(event_id)
-                            .event("message")
-                            .data(json_str),
-                    )
-                }));
-
-                sse.into_response()
+                tx.send((event_id, response)).unwrap();
+                sse_response(rx)
             }
         } else {
             // No session, return JSON using JSON-RPC compatibility layer
@@ -707,6 +1302,26 @@ async fn handle_post_request(
     State(state): State<ServerState>,
     request: axum::extract::Request<Body>,
 ) -> impl IntoResponse {
+    // Shed load instead of buffering more requests than the profile allows
+    let _permit = match &state.request_permits {
+        Some(permits) => match permits.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                return create_error_response(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    -32000,
+                    "Server is at its concurrent request limit, retry later",
+                );
+            },
+        },
+        None => None,
+    };
+
+    let request = match limit_request_body(&state, request) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
+
     // Fast path: No HTTP middleware chain
     if state.config.http_middleware.is_none() {
         return handle_post_fast_path(state, request).await;
@@ -716,6 +1331,50 @@ async fn handle_post_request(
     handle_post_with_middleware(state, request).await
 }
 
+/// Enforce the profile's request body limit.
+///
+/// Requests declaring a larger `Content-Length` are rejected up front; bodies
+/// without one are capped while being read.
+fn limit_request_body(
+    state: &ServerState,
+    request: axum::extract::Request<Body>,
+) -> std::result::Result<axum::extract::Request<Body>, Response> {
+    let Some(limit) = state
+        .config
+        .resource_profile
+        .and_then(|profile| profile.max_request_body_bytes)
+    else {
+        return Ok(request);
+    };
+
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > limit) {
+        return Err(create_error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            -32600,
+            &format!("Request body exceeds {} bytes", limit),
+        ));
+    }
+
+    Ok(request.map(|body| Body::new(http_body_util::Limited::new(body, limit))))
+}
+
+/// Returns `true` if reading a body failed because it hit a body size limit.
+fn is_length_limit_error(error: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(err) = source {
+        if err.is::<http_body_util::LengthLimitError>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
 /// Extract and validate authentication from headers.
 async fn extract_and_validate_auth(
     state: &ServerState,
@@ -838,6 +1497,13 @@ async fn handle_post_fast_path(
     // Read body to string
     let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
         Ok(b) => b,
+        Err(e) if is_length_limit_error(&e) => {
+            return create_error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                -32600,
+                "Request body exceeds the configured limit",
+            );
+        },
         Err(e) => {
             return create_error_response(
                 StatusCode::BAD_REQUEST,
@@ -916,37 +1582,64 @@ async fn handle_post_fast_path(
     // Process the message
     match message {
         TransportMessage::Request { id, request } => {
-            let server = state.server.lock().await;
-            let json_response = server.handle_request(id, request, auth_context).await;
+            let (mut response, negotiated_version) = if !is_init_request
+                && streams_notifications(&state, &request)
+            {
+                let response = stream_request(
+                    &state,
+                    id,
+                    request,
+                    auth_context,
+                    response_session_id.clone(),
+                    incoming_trace(&headers),
+                );
+                (response, None)
+            } else {
+                let status_owner = if is_init_request {
+                    task_status_owner(&state, &request, auth_context.as_ref())
+                } else {
+                    None
+                };
+                let server = state.server.lock().await;
+                let timer = start_request_timer(state.config.metrics.as_ref(), &server, &request);
+                let json_response = with_request_scope(
+                    incoming_trace(&headers),
+                    response_session_id.clone(),
+                    server.handle_request(id, request, auth_context),
+                )
+                .await;
+                finish_request_timer(timer, &json_response);
 
-            // Trace response payload (compact for CloudWatch compatibility)
-            tracing::debug!(
-                target: "mcp.http",
-                response = %serde_json::to_string(&json_response).unwrap_or_default(),
-                "StreamableHttpServer response"
-            );
+                // Trace response payload (compact for CloudWatch compatibility)
+                tracing::debug!(
+                    target: "mcp.http",
+                    response = %serde_json::to_string(&json_response).unwrap_or_default(),
+                    "StreamableHttpServer response"
+                );
 
-            let response = TransportMessage::Response(json_response.clone());
+                let response = TransportMessage::Response(json_response.clone());
 
-            // Handle initialization response
-            let negotiated_version = if is_init_request {
-                let version = extract_negotiated_version(&response);
-                update_session_after_init(&state, response_session_id.as_ref(), version.clone());
-                version
-            } else {
-                None
-            };
+                // Handle initialization response
+                let negotiated_version = if is_init_request {
+                    let version = extract_negotiated_version(&response);
+                    update_session_after_init(
+                        &state,
+                        response_session_id.as_ref(),
+                        version.clone(),
+                        status_owner,
+                    );
+                    version
+                } else {
+                    None
+                };
 
-            // Store event if we have an event store
-            if let Some(event_store) = &state.config.event_store {
-                if let Some(sid) = &response_session_id {
-                    let event_id = Uuid::new_v4().to_string();
-                    let _ = event_store.store_event(sid, &event_id, &response).await;
-                }
-            }
+                // Number the event and store it if we have an event store
+                let event_id = record_event(&state, response_session_id.as_ref(), &response).await;
 
-            // Build response with headers
-            let mut response = build_response(&state, response, session_id.as_ref());
+                // Build response with headers
+                let response = build_response(&state, event_id, response, session_id.as_ref());
+                (response, negotiated_version)
+            };
 
             // Always add session header in stateful mode
             if let Some(sid) = &response_session_id {
@@ -1004,6 +1697,23 @@ async fn handle_post_with_middleware(
 
     // Convert from axum request
     let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => Body::from(bytes),
+        Err(e) if is_length_limit_error(&e) => {
+            return create_error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                -32600,
+                "Request body exceeds the configured limit",
+            );
+        },
+        Err(e) => {
+            return create_error_response(
+                StatusCode::BAD_REQUEST,
+                -32700,
+                &format!("Failed to read body: {}", e),
+            );
+        },
+    };
 
     let mut server_request = match from_axum(parts, body).await {
         Ok(req) => req,
@@ -1161,27 +1871,39 @@ async fn handle_post_with_middleware(
     // Process the request
     match message {
         TransportMessage::Request { id, request } => {
+            let status_owner = if is_init_request {
+                task_status_owner(&state, &request, auth_context.as_ref())
+            } else {
+                None
+            };
             let server = state.server.lock().await;
-            let json_response = server.handle_request(id, request, auth_context).await;
+            let timer = start_request_timer(state.config.metrics.as_ref(), &server, &request);
+            let json_response = with_request_scope(
+                incoming_trace(&server_request.headers),
+                response_session_id.clone(),
+                server.handle_request(id, request, auth_context),
+            )
+            .await;
+            finish_request_timer(timer, &json_response);
 
             let response_msg = TransportMessage::Response(json_response.clone());
 
             // Handle initialization response
             let negotiated_version = if is_init_request {
                 let version = extract_negotiated_version(&response_msg);
-                update_session_after_init(&state, response_session_id.as_ref(), version.clone());
+                update_session_after_init(
+                    &state,
+                    response_session_id.as_ref(),
+                    version.clone(),
+                    status_owner,
+                );
                 version
             } else {
                 None
             };
 
-            // Store event if needed
-            if let Some(event_store) = &state.config.event_store {
-                if let Some(sid) = &response_session_id {
-                    let event_id = Uuid::new_v4().to_string();
-                    let _ = event_store.store_event(sid, &event_id, &response_msg).await;
-                }
-            }
+            // Number the event and store it if needed
+            record_event(&state, response_session_id.as_ref(), &response_msg).await;
 
             // Build response with proper headers
             let response_body = match serde_json::to_vec(&response_msg) {
@@ -1276,6 +1998,8 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
             SessionInfo {
                 initialized: true, // GET SSE implicitly initializes
                 protocol_version: None,
+                last_event_seq: 0,
+                task_status_owner: None,
             },
         );
         if let Some(callback) = &state.config.on_session_initialized {
@@ -1311,33 +2035,40 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
     if let Some(last_event_id) = headers.get(LAST_EVENT_ID) {
         if let Ok(last_id) = last_event_id.to_str() {
             if let Some(event_store) = &state.config.event_store {
-                // Replay events after the last event ID
+                // Replay this session's events after the last event ID
                 if let Ok(events) = event_store.replay_events_after(last_id).await {
-                    for (_event_id, msg) in events {
-                        let _ = tx.send(msg);
+                    for (event_id, msg) in events {
+                        let in_session =
+                            crate::shared::streamable_http::parse_sequenced_event_id(&event_id)
+                                .is_some_and(|(stream, _)| stream == session_id);
+                        if in_session {
+                            let _ = tx.send((event_id, msg));
+                        }
                     }
                 }
             }
         }
     }
 
+    #[cfg(feature = "tasks")]
+    if let Some(updates) = &state.config.task_status_updates {
+        tokio::spawn(forward_task_status(
+            state.clone(),
+            session_id.clone(),
+            updates.subscribe(),
+        ));
+    }
+
+    #[cfg(feature = "resources")]
+    if let Err(e) = start_resource_watchers(&state).await {
+        tracing::warn!("Failed to start resource watchers: {}", e);
+    }
+
     let stream = UnboundedReceiverStream::new(rx);
-    let session_id_header = session_id.clone();
-
-    let sse = Sse::new(stream.map(move |msg| {
-        let event_id = Uuid::new_v4().to_string();
-
-        // Store event if we have an event store
-        if let Some(event_store) = &state.config.event_store {
-            let sid = session_id.clone();
-            let msg_clone = msg.clone();
-            let store = event_store.clone();
-            let event_id_clone = event_id.clone();
-            tokio::spawn(async move {
-                let _ = store.store_event(&sid, &event_id_clone, &msg_clone).await;
-            });
-        }
 
+    // Events are numbered and stored by their producers, so replays keep
+    // their original ids.
+    let sse = Sse::new(stream.map(|(event_id, msg)| {
         Ok::<_, Infallible>(
             Event::default()
                 .id(event_id)
@@ -1351,7 +2082,7 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
     // Add session ID header
     response
         .headers_mut()
-        .insert(MCP_SESSION_ID, session_id_header.parse().unwrap());
+        .insert(MCP_SESSION_ID, session_id.parse().unwrap());
 
     // Add SSE-specific headers for hardening
     response.headers_mut().insert(
@@ -1396,6 +2127,21 @@ async fn handle_delete_session(
         if let Some(callback) = &state.config.on_session_closed {
             callback(&sid);
         }
+        let server = state.server.lock().await;
+        #[cfg(feature = "resources")]
+        {
+            let _ = server
+                .subscription_manager
+                .read()
+                .await
+                .unsubscribe_all(&sid)
+                .await;
+        }
+        let events = server.events().clone();
+        drop(server);
+        events.emit(crate::server::event_bus::ServerEvent::SessionClosed {
+            session_id: Some(sid),
+        });
 
         (StatusCode::OK, Json(json!({"status": "ok"}))).into_response()
     } else {
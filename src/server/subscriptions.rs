@@ -1,11 +1,126 @@
 //! Server-side resource subscription management.
+//!
+//! Clients subscribe with `resources/subscribe` and unsubscribe with
+//! `resources/unsubscribe`. Changes are reported by [`ResourceWatcher`]
+//! implementations registered on the server builder; each reported URI with
+//! at least one subscriber produces a `notifications/resources/updated`.
+//!
+//! With the `resource-watcher` feature,
+//! [`FileResourceWatcher`](crate::server::resource_watcher::FileResourceWatcher)
+//! watches a directory of file-backed resources or a `WidgetDir`.
 
 use crate::error::Result;
 use crate::types::{protocol::ResourceUpdatedParams, ServerNotification};
+use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
+
+/// Source of resource change events.
+///
+/// The server calls [`start`](Self::start) once when it begins serving a
+/// transport. Implementations report the URI of every resource that changes
+/// through the [`ResourceChangeNotifier`]; the server only forwards changes
+/// to clients subscribed to that URI, so reporting unsubscribed URIs is cheap.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use pmcp::server::subscriptions::{ResourceChangeNotifier, ResourceWatcher};
+/// use std::time::Duration;
+///
+/// /// Reports the stock price resource as changed every minute.
+/// struct PriceTicker;
+///
+/// #[async_trait]
+/// impl ResourceWatcher for PriceTicker {
+///     async fn start(&self, notifier: ResourceChangeNotifier) -> pmcp::Result<()> {
+///         tokio::spawn(async move {
+///             let mut ticks = tokio::time::interval(Duration::from_secs(60));
+///             loop {
+///                 ticks.tick().await;
+///                 if !notifier.notify("stock://ACME/price") {
+///                     break;
+///                 }
+///             }
+///         });
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ResourceWatcher: Send + Sync {
+    /// Start watching and report changed resource URIs to `notifier`.
+    ///
+    /// Long-running work should be spawned; this method should return once
+    /// watching has been set up.
+    async fn start(&self, notifier: ResourceChangeNotifier) -> Result<()>;
+}
+
+/// Handle a [`ResourceWatcher`] uses to report changed resources.
+#[derive(Debug, Clone)]
+pub struct ResourceChangeNotifier {
+    tx: mpsc::UnboundedSender<String>,
+}
+
+impl ResourceChangeNotifier {
+    /// Create a notifier and the receiver its changes are delivered to.
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<String>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+
+    /// Report that the resource at `uri` changed.
+    ///
+    /// Returns `false` once the server has stopped listening, at which point
+    /// the watcher should stop.
+    pub fn notify(&self, uri: impl Into<String>) -> bool {
+        self.tx.send(uri.into()).is_ok()
+    }
+}
+
+/// Start `watchers` and forward their changes to subscribers in `manager`.
+pub(crate) async fn start_watchers(
+    watchers: &[Arc<dyn ResourceWatcher>],
+    manager: Arc<RwLock<SubscriptionManager>>,
+) -> Result<()> {
+    if watchers.is_empty() {
+        return Ok(());
+    }
+
+    let (notifier, mut changes) = ResourceChangeNotifier::channel();
+    for watcher in watchers {
+        watcher.start(notifier.clone()).await?;
+    }
+
+    tokio::spawn(async move {
+        while let Some(uri) = changes.recv().await {
+            let manager = manager.read().await;
+            if let Err(e) = manager.notify_resource_updated(uri).await {
+                tracing::warn!("Failed to notify resource subscribers: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Identify the subscriber for a `resources/subscribe` request.
+///
+/// Uses the transport session ID when there is one, so each streamable HTTP
+/// session subscribes on its own. Otherwise uses the authenticated client ID
+/// or subject, falling back to `"local"` for unauthenticated single-client
+/// transports such as stdio.
+pub(crate) fn subscriber_id(auth_context: Option<&crate::server::auth::AuthContext>) -> String {
+    if let Some(session_id) = crate::server::cancellation::request_session() {
+        return session_id;
+    }
+    auth_context.map_or_else(
+        || "local".to_string(),
+        |ctx| ctx.client_id.clone().unwrap_or_else(|| ctx.subject.clone()),
+    )
+}
 
 /// Manages resource subscriptions for the server.
 ///
@@ -17,8 +132,13 @@ pub struct SubscriptionManager {
     subscriptions: Arc<RwLock<HashMap<String, HashSet<String>>>>,
     /// Optional callback for sending notifications
     notification_sender: Option<Arc<dyn Fn(ServerNotification) + Send + Sync>>,
+    /// Optional callback for sending a notification to one subscriber
+    subscriber_sender: Option<SubscriberSender>,
 }
 
+/// Callback that delivers a notification to the subscriber with the given ID.
+type SubscriberSender = Arc<dyn Fn(&str, ServerNotification) + Send + Sync>;
+
 impl Default for SubscriptionManager {
     fn default() -> Self {
         Self::new()
@@ -42,6 +162,7 @@ impl SubscriptionManager {
         Self {
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
             notification_sender: None,
+            subscriber_sender: None,
         }
     }
 
@@ -55,6 +176,21 @@ impl SubscriptionManager {
         self.notification_sender = Some(Arc::new(sender));
     }
 
+    /// Set the callback that delivers notifications to each subscriber.
+    ///
+    /// Used by transports that serve several clients at once, such as
+    /// streamable HTTP, where subscriber IDs are session IDs. Unlike the
+    /// [`set_notification_sender`](Self::set_notification_sender) callback,
+    /// which is called once per change, this one is called once per
+    /// subscriber of the changed resource.
+    #[cfg_attr(not(feature = "streamable-http"), allow(dead_code))]
+    pub(crate) fn set_subscriber_notification_sender<F>(&mut self, sender: F)
+    where
+        F: Fn(&str, ServerNotification) + Send + Sync + 'static,
+    {
+        self.subscriber_sender = Some(Arc::new(sender));
+    }
+
     /// Subscribe to a resource.
     ///
     /// # Arguments
@@ -166,24 +302,28 @@ impl SubscriptionManager {
     ///
     /// The number of subscribers notified
     pub async fn notify_resource_updated(&self, uri: String) -> Result<usize> {
-        let subs = self.subscriptions.read().await;
+        let subscribers: Vec<String> = {
+            let subs = self.subscriptions.read().await;
+            subs.get(&uri)
+                .map(|s| s.iter().cloned().collect())
+                .unwrap_or_default()
+        };
+        if subscribers.is_empty() {
+            return Ok(0);
+        }
 
-        if let Some(subscribers) = subs.get(&uri) {
-            let subscriber_count = subscribers.len();
-            drop(subs);
-            if subscriber_count > 0 {
-                // Send notification if sender is available
-                if let Some(sender) = &self.notification_sender {
-                    let notification =
-                        ServerNotification::ResourceUpdated(ResourceUpdatedParams::new(&*uri));
-                    sender(notification);
-                }
-                // Return count regardless of whether notification was sent
-                return Ok(subscriber_count);
+        // Send notification if sender is available
+        let notification = ServerNotification::ResourceUpdated(ResourceUpdatedParams::new(&*uri));
+        if let Some(sender) = &self.subscriber_sender {
+            for subscriber in &subscribers {
+                sender(subscriber, notification.clone());
             }
         }
-
-        Ok(0)
+        if let Some(sender) = &self.notification_sender {
+            sender(notification);
+        }
+        // Return count regardless of whether notification was sent
+        Ok(subscribers.len())
     }
 
     /// Get statistics about current subscriptions.
@@ -389,4 +529,91 @@ mod tests {
             _ => panic!("Wrong notification type"),
         }
     }
+
+    struct ReportOnStart(&'static str);
+
+    #[async_trait]
+    impl ResourceWatcher for ReportOnStart {
+        async fn start(&self, notifier: ResourceChangeNotifier) -> Result<()> {
+            notifier.notify(self.0);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_watchers_notifies_subscribers_only() {
+        use std::sync::Mutex;
+
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = SubscriptionManager::new();
+        let notifications_clone = notifications.clone();
+        manager.set_notification_sender(move |notif| {
+            notifications_clone.lock().unwrap().push(notif);
+        });
+        manager
+            .subscribe("ui://app/board".to_string(), "client1".to_string())
+            .await
+            .unwrap();
+
+        let watchers: Vec<Arc<dyn ResourceWatcher>> = vec![
+            Arc::new(ReportOnStart("ui://app/board")),
+            Arc::new(ReportOnStart("ui://app/unwatched")),
+        ];
+        start_watchers(&watchers, Arc::new(RwLock::new(manager)))
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            if !notifications.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let notifs = notifications.lock().unwrap();
+        assert_eq!(notifs.len(), 1);
+        match &notifs[0] {
+            ServerNotification::ResourceUpdated(n) => assert_eq!(n.uri, "ui://app/board"),
+            _ => panic!("Wrong notification type"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_resource_updated_per_subscriber() {
+        use std::sync::Mutex;
+
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = SubscriptionManager::new();
+        let delivered_clone = delivered.clone();
+        manager.set_subscriber_notification_sender(move |subscriber, _| {
+            delivered_clone.lock().unwrap().push(subscriber.to_string());
+        });
+        for session in ["session-a", "session-b"] {
+            manager
+                .subscribe("file:///log".to_string(), session.to_string())
+                .await
+                .unwrap();
+        }
+
+        let count = manager
+            .notify_resource_updated("file:///log".to_string())
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        let mut delivered = delivered.lock().unwrap().clone();
+        delivered.sort();
+        assert_eq!(delivered, ["session-a", "session-b"]);
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_id() {
+        assert_eq!(subscriber_id(None), "local");
+        let in_session = crate::server::cancellation::with_request_session(
+            Some("session-a".to_string()),
+            async { subscriber_id(None) },
+        )
+        .await;
+        assert_eq!(in_session, "session-a");
+    }
 }
@@ -0,0 +1,235 @@
+//! Per-server toggles for optional subsystems.
+//!
+//! [`ServerBuilder::disable`](crate::server::ServerBuilder::disable) and
+//! [`ServerCoreBuilder::disable`](crate::server::builder::ServerCoreBuilder::disable)
+//! switch a subsystem off for one server. A disabled subsystem is not
+//! advertised in the server capabilities and its requests are answered
+//! with `METHOD_NOT_FOUND`, even if a client sends them anyway.
+//!
+//! Disabling a subsystem while also registering handlers for it is a
+//! configuration mistake, so `build()` rejects it.
+//!
+//! To remove a subsystem from the binary rather than just the server, build
+//! without its cargo feature (`prompts`, `resources`, `tasks`, `sampling`,
+//! or `workflow` for the workflow engine). A subsystem that is compiled out
+//! behaves as if every server had disabled it.
+
+use crate::error::{Error, Result};
+use crate::types::{ClientRequest, ServerCapabilities};
+use std::collections::HashSet;
+use std::fmt;
+
+/// An optional server subsystem that can be switched off per server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// `prompts/list` and `prompts/get`.
+    Prompts,
+    /// `resources/*`, including templates and subscriptions.
+    Resources,
+    /// `tasks/*` and task-augmented tool calls.
+    Tasks,
+    /// `sampling/createMessage`, and sampling requests from handlers to the client.
+    Sampling,
+}
+
+impl Subsystem {
+    /// The subsystem that serves `request`, if it belongs to one.
+    pub(crate) fn of_request(request: &ClientRequest) -> Option<Self> {
+        match request {
+            ClientRequest::ListPrompts(_) | ClientRequest::GetPrompt(_) => Some(Self::Prompts),
+            ClientRequest::ListResources(_)
+            | ClientRequest::ListResourceTemplates(_)
+            | ClientRequest::ReadResource(_)
+            | ClientRequest::Subscribe(_)
+            | ClientRequest::Unsubscribe(_) => Some(Self::Resources),
+            ClientRequest::TasksGet(_)
+            | ClientRequest::TasksResult(_)
+            | ClientRequest::TasksList(_)
+            | ClientRequest::TasksCancel(_)
+            | ClientRequest::TasksProvideInput(_) => Some(Self::Tasks),
+            ClientRequest::CreateMessage(_) => Some(Self::Sampling),
+            _ => None,
+        }
+    }
+
+    /// Whether the subsystem's cargo feature is enabled.
+    fn compiled(self) -> bool {
+        match self {
+            Self::Prompts => cfg!(feature = "prompts"),
+            Self::Resources => cfg!(feature = "resources"),
+            Self::Tasks => cfg!(feature = "tasks"),
+            Self::Sampling => cfg!(feature = "sampling"),
+        }
+    }
+
+    /// The `METHOD_NOT_FOUND` message for a request to this subsystem when it is disabled.
+    pub(crate) fn disabled_message(self) -> String {
+        format!("Method not found: the {self} subsystem is disabled on this server")
+    }
+
+    fn clear_capability(self, capabilities: &mut ServerCapabilities) {
+        match self {
+            Self::Prompts => capabilities.prompts = None,
+            Self::Resources => capabilities.resources = None,
+            Self::Tasks => {
+                capabilities.tasks = None;
+                if let Some(experimental) = capabilities.experimental.as_mut() {
+                    experimental.remove("tasks");
+                    if experimental.is_empty() {
+                        capabilities.experimental = None;
+                    }
+                }
+            },
+            Self::Sampling => capabilities.sampling = None,
+        }
+    }
+}
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Prompts => "prompts",
+            Self::Resources => "resources",
+            Self::Tasks => "tasks",
+            Self::Sampling => "sampling",
+        })
+    }
+}
+
+/// The subsystems a builder has switched off.
+///
+/// Starts with the subsystems whose cargo feature is disabled.
+#[derive(Debug, Clone)]
+pub(crate) struct DisabledSubsystems(HashSet<Subsystem>);
+
+impl Default for DisabledSubsystems {
+    fn default() -> Self {
+        Self(
+            [
+                Subsystem::Prompts,
+                Subsystem::Resources,
+                Subsystem::Tasks,
+                Subsystem::Sampling,
+            ]
+            .into_iter()
+            .filter(|subsystem| !subsystem.compiled())
+            .collect(),
+        )
+    }
+}
+
+impl DisabledSubsystems {
+    pub(crate) fn insert(&mut self, subsystem: Subsystem) {
+        self.0.insert(subsystem);
+    }
+
+    pub(crate) fn contains(&self, subsystem: Subsystem) -> bool {
+        self.0.contains(&subsystem)
+    }
+
+    /// Fail the build if `subsystem` is disabled but handlers were registered for it.
+    pub(crate) fn check_unused(&self, subsystem: Subsystem, registered: bool) -> Result<()> {
+        if registered && !subsystem.compiled() {
+            return Err(Error::validation(format!(
+                "The builder registers {subsystem} handlers but pmcp was built \
+                 without the `{subsystem}` feature"
+            )));
+        }
+        if registered && self.contains(subsystem) {
+            return Err(Error::validation(format!(
+                "The {subsystem} subsystem is disabled but the builder registers \
+                 {subsystem} handlers; remove them or the disable({subsystem:?}) call"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Remove the disabled subsystems from the advertised capabilities.
+    pub(crate) fn apply(&self, capabilities: &mut ServerCapabilities) {
+        for subsystem in &self.0 {
+            subsystem.clear_capability(capabilities);
+        }
+    }
+
+    /// The disabled subsystem `request` belongs to, if any.
+    pub(crate) fn rejects(&self, request: &ClientRequest) -> Option<Subsystem> {
+        Subsystem::of_request(request).filter(|s| self.contains(*s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::capabilities::ServerTasksCapability;
+    use crate::types::{GetPromptRequest, ListToolsRequest};
+    use std::collections::HashMap;
+
+    #[test]
+    fn maps_requests_to_subsystems() {
+        let get = ClientRequest::GetPrompt(GetPromptRequest {
+            name: "p".to_string(),
+            arguments: HashMap::new(),
+            _meta: None,
+        });
+        assert_eq!(Subsystem::of_request(&get), Some(Subsystem::Prompts));
+        assert_eq!(
+            Subsystem::of_request(&ClientRequest::ListTools(ListToolsRequest::default())),
+            None
+        );
+        assert_eq!(Subsystem::of_request(&ClientRequest::Ping), None);
+    }
+
+    #[test]
+    fn apply_clears_standard_and_experimental_tasks() {
+        let mut capabilities = ServerCapabilities {
+            tasks: Some(ServerTasksCapability::default()),
+            experimental: Some(HashMap::from([(
+                "tasks".to_string(),
+                serde_json::json!({}),
+            )])),
+            prompts: Some(Default::default()),
+            ..Default::default()
+        };
+
+        let mut disabled = DisabledSubsystems::default();
+        disabled.insert(Subsystem::Tasks);
+        disabled.apply(&mut capabilities);
+
+        assert!(capabilities.tasks.is_none());
+        assert!(capabilities.experimental.is_none());
+        assert!(capabilities.prompts.is_some());
+    }
+
+    #[test]
+    fn compiled_out_subsystems_start_disabled() {
+        let disabled = DisabledSubsystems::default();
+        assert_eq!(
+            disabled.contains(Subsystem::Prompts),
+            !cfg!(feature = "prompts")
+        );
+        assert_eq!(
+            disabled.contains(Subsystem::Resources),
+            !cfg!(feature = "resources")
+        );
+        assert_eq!(
+            disabled.contains(Subsystem::Tasks),
+            !cfg!(feature = "tasks")
+        );
+        assert_eq!(
+            disabled.contains(Subsystem::Sampling),
+            !cfg!(feature = "sampling")
+        );
+    }
+
+    #[test]
+    fn check_unused_only_fails_for_disabled_subsystems() {
+        let mut disabled = DisabledSubsystems::default();
+        disabled.insert(Subsystem::Sampling);
+        assert!(disabled.check_unused(Subsystem::Sampling, false).is_ok());
+        assert!(disabled.check_unused(Subsystem::Prompts, true).is_ok());
+        let err = disabled
+            .check_unused(Subsystem::Sampling, true)
+            .unwrap_err();
+        assert!(err.to_string().contains("sampling subsystem is disabled"));
+    }
+}
@@ -22,6 +22,13 @@
 //! These PMCP extensions remain in `pmcp-tasks`. The SDK trait covers
 //! the core MCP spec operations only.
 //!
+//! # Status updates
+//!
+//! Stores that return [`TaskStatusUpdates`] from [`TaskStore::status_updates`]
+//! broadcast every task transition. The streamable HTTP server forwards them
+//! as `notifications/tasks/status` to clients that declared the `tasks`
+//! capability, so those clients need not poll `tasks/get`.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -37,7 +44,10 @@
 use async_trait::async_trait;
 use dashmap::DashMap;
 use std::time::Instant;
+use tokio::sync::broadcast;
 
+use crate::server::auth::AuthContext;
+use crate::server::pagination::CursorCodec;
 use crate::types::tasks::{Task, TaskStatus};
 
 // ---------------------------------------------------------------------------
@@ -66,6 +76,11 @@ pub enum TaskStoreError {
         /// The task ID.
         task_id: String,
     },
+    /// The pagination cursor is malformed, forged, or expired.
+    InvalidCursor {
+        /// Why the cursor was rejected.
+        message: String,
+    },
     /// An internal error occurred.
     Internal {
         /// Human-readable error message.
@@ -81,6 +96,7 @@ impl std::fmt::Display for TaskStoreError {
                 write!(f, "invalid transition for task {task_id}: {from} -> {to}")
             },
             Self::Expired { task_id } => write!(f, "task expired: {task_id}"),
+            Self::InvalidCursor { message } => write!(f, "invalid cursor: {message}"),
             Self::Internal { message } => write!(f, "internal error: {message}"),
         }
     }
@@ -95,6 +111,7 @@ impl From<TaskStoreError> for crate::error::Error {
             TaskStoreError::InvalidTransition { .. } => Self::validation(err.to_string()),
             // Expired uses NotFound to avoid leaking existence of expired tasks
             TaskStoreError::Expired { .. } => Self::not_found(err.to_string()),
+            TaskStoreError::InvalidCursor { .. } => Self::invalid_params(err.to_string()),
             TaskStoreError::Internal { .. } => Self::internal(err.to_string()),
         }
     }
@@ -190,7 +207,9 @@ pub trait TaskStore: Send + Sync {
     /// List tasks for an owner with optional cursor-based pagination.
     ///
     /// Returns `(tasks, next_cursor)`. If `next_cursor` is `None`, there
-    /// are no more results.
+    /// are no more results. Implementations should issue cursors with
+    /// [`CursorCodec`] and return [`TaskStoreError::InvalidCursor`] for
+    /// cursors it rejects.
     async fn list(
         &self,
         owner_id: &str,
@@ -205,6 +224,77 @@ pub trait TaskStore: Send + Sync {
 
     /// Returns a reference to the store's configuration.
     fn config(&self) -> &StoreConfig;
+
+    /// Broadcast of this store's task transitions, if the store publishes them.
+    ///
+    /// Defaults to `None`, in which case clients learn about transitions by
+    /// polling `tasks/get`.
+    fn status_updates(&self) -> Option<TaskStatusUpdates> {
+        None
+    }
+}
+
+/// Owner of the tasks created for a request with the given auth context.
+///
+/// The OAuth client ID if present, else the subject; unauthenticated
+/// requests share the `"local"` owner.
+pub fn owner_for(auth_context: Option<&AuthContext>) -> String {
+    match auth_context {
+        Some(ctx) => ctx.client_id.clone().unwrap_or_else(|| ctx.subject.clone()),
+        None => "local".to_string(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TaskStatusUpdates
+// ---------------------------------------------------------------------------
+
+/// Capacity of the status update channel; slower receivers skip older updates.
+const STATUS_UPDATE_CAPACITY: usize = 256;
+
+/// A task transition published by a [`TaskStore`].
+#[derive(Debug, Clone)]
+pub struct TaskStatusUpdate {
+    /// Owner of the task; only this owner's sessions are notified.
+    pub owner_id: String,
+    /// The task after the transition.
+    pub task: Task,
+}
+
+/// Broadcast channel of task transitions.
+///
+/// Cloning shares the channel. Publishing with no receivers is not an error;
+/// the update is simply dropped.
+#[derive(Debug, Clone)]
+pub struct TaskStatusUpdates {
+    sender: broadcast::Sender<TaskStatusUpdate>,
+}
+
+impl TaskStatusUpdates {
+    /// Create a channel with no receivers.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(STATUS_UPDATE_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish `task` as the current state of one of `owner_id`'s tasks.
+    pub fn publish(&self, owner_id: &str, task: &Task) {
+        let _ = self.sender.send(TaskStatusUpdate {
+            owner_id: owner_id.to_string(),
+            task: task.clone(),
+        });
+    }
+
+    /// Receive every update published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskStatusUpdate> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for TaskStatusUpdates {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -242,6 +332,8 @@ struct TaskRecord {
 pub struct InMemoryTaskStore {
     records: DashMap<String, TaskRecord>,
     config: StoreConfig,
+    cursors: CursorCodec,
+    updates: TaskStatusUpdates,
 }
 
 impl InMemoryTaskStore {
@@ -250,6 +342,8 @@ impl InMemoryTaskStore {
         Self {
             records: DashMap::new(),
             config: StoreConfig::default(),
+            cursors: CursorCodec::random().for_list("tasks/list"),
+            updates: TaskStatusUpdates::new(),
         }
     }
 
@@ -258,6 +352,8 @@ impl InMemoryTaskStore {
         Self {
             records: DashMap::new(),
             config,
+            cursors: CursorCodec::random().for_list("tasks/list"),
+            updates: TaskStatusUpdates::new(),
         }
     }
 
@@ -343,6 +439,7 @@ impl TaskStore for InMemoryTaskStore {
         };
 
         self.records.insert(task_id, record);
+        self.updates.publish(owner_id, &task);
         Ok(task)
     }
 
@@ -388,7 +485,10 @@ impl TaskStore for InMemoryTaskStore {
         record.task.last_updated_at = now_str;
         record.task.status_message = message;
 
-        Ok(record.task.clone())
+        let task = record.task.clone();
+        drop(entry);
+        self.updates.publish(owner_id, &task);
+        Ok(task)
     }
 
     async fn list(
@@ -408,19 +508,37 @@ impl TaskStore for InMemoryTaskStore {
             .map(|entry| entry.value().task.clone())
             .collect();
 
-        // Sort by created_at descending (newest first)
-        tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        // Sort by created_at descending (newest first), then by ID so the
+        // order is stable for tasks created in the same instant
+        tasks.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.task_id.cmp(&b.task_id))
+        });
 
-        // Apply cursor-based pagination (cursor = task_id of last item)
-        if let Some(cursor_id) = cursor {
-            if let Some(pos) = tasks.iter().position(|t| t.task_id == cursor_id) {
-                tasks = tasks.into_iter().skip(pos + 1).collect();
-            }
+        // The cursor is the sort key of the last task on the previous page,
+        // so pages stay correct when that task expires in between
+        if let Some(cursor) = cursor {
+            let (created_at, task_id): (String, String) =
+                self.cursors
+                    .decode(cursor)
+                    .map_err(|e| TaskStoreError::InvalidCursor {
+                        message: e.to_string(),
+                    })?;
+            tasks.retain(|t| {
+                t.created_at < created_at || (t.created_at == created_at && t.task_id > task_id)
+            });
         }
 
         if tasks.len() > PAGE_SIZE {
-            let next_cursor = tasks[PAGE_SIZE - 1].task_id.clone();
             tasks.truncate(PAGE_SIZE);
+            let last = &tasks[PAGE_SIZE - 1];
+            let next_cursor = self
+                .cursors
+                .encode(&(&last.created_at, &last.task_id))
+                .map_err(|e| TaskStoreError::Internal {
+                    message: e.to_string(),
+                })?;
             Ok((tasks, Some(next_cursor)))
         } else {
             Ok((tasks, None))
@@ -443,6 +561,10 @@ impl TaskStore for InMemoryTaskStore {
     fn config(&self) -> &StoreConfig {
         &self.config
     }
+
+    fn status_updates(&self) -> Option<TaskStatusUpdates> {
+        Some(self.updates.clone())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -491,6 +613,34 @@ mod tests {
         assert_eq!(config.max_tasks_per_owner, 100);
     }
 
+    #[test]
+    fn owner_prefers_client_id_then_subject() {
+        let mut ctx = AuthContext::new("user-1");
+        assert_eq!(owner_for(Some(&ctx)), "user-1");
+        ctx.client_id = Some("client-1".to_string());
+        assert_eq!(owner_for(Some(&ctx)), "client-1");
+        assert_eq!(owner_for(None), "local");
+    }
+
+    #[tokio::test]
+    async fn transitions_are_published_with_owner() {
+        let store = InMemoryTaskStore::new();
+        let mut updates = store.status_updates().unwrap().subscribe();
+
+        let task = store.create("owner-1", None).await.unwrap();
+        store.cancel(&task.task_id, "owner-1").await.unwrap();
+        // Rejected transitions publish nothing
+        assert!(store.cancel(&task.task_id, "owner-1").await.is_err());
+
+        let created = updates.recv().await.unwrap();
+        assert_eq!(created.owner_id, "owner-1");
+        assert_eq!(created.task.status, TaskStatus::Working);
+        let cancelled = updates.recv().await.unwrap();
+        assert_eq!(cancelled.task.task_id, task.task_id);
+        assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+        assert!(updates.try_recv().is_err());
+    }
+
     // -- Create tests --
 
     #[tokio::test]
@@ -602,6 +752,50 @@ mod tests {
         assert_eq!(tasks[1].task_id, first.task_id);
     }
 
+    #[tokio::test]
+    async fn list_pages_with_signed_cursor() {
+        let store = InMemoryTaskStore::new();
+        for _ in 0..25 {
+            store.create("owner-1", None).await.unwrap();
+        }
+
+        let (first, cursor) = store.list("owner-1", None).await.unwrap();
+        assert_eq!(first.len(), 20);
+        let cursor = cursor.expect("second page");
+
+        let (second, next) = store.list("owner-1", Some(&cursor)).await.unwrap();
+        assert_eq!(second.len(), 5);
+        assert!(next.is_none());
+        assert!(second
+            .iter()
+            .all(|t| first.iter().all(|f| f.task_id != t.task_id)));
+
+        // Retrying with the same cursor returns the same page
+        let (retry, _) = store.list("owner-1", Some(&cursor)).await.unwrap();
+        assert_eq!(
+            retry.iter().map(|t| &t.task_id).collect::<Vec<_>>(),
+            second.iter().map(|t| &t.task_id).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn list_rejects_forged_cursor() {
+        let store = InMemoryTaskStore::new();
+        let task = store.create("owner-1", None).await.unwrap();
+
+        let result = store.list("owner-1", Some(&task.task_id)).await;
+        assert!(matches!(result, Err(TaskStoreError::InvalidCursor { .. })));
+
+        // Cursors from another store instance are rejected too
+        for _ in 0..20 {
+            store.create("owner-1", None).await.unwrap();
+        }
+        let (_, cursor) = store.list("owner-1", None).await.unwrap();
+        let other = InMemoryTaskStore::new();
+        let result = other.list("owner-1", cursor.as_deref()).await;
+        assert!(matches!(result, Err(TaskStoreError::InvalidCursor { .. })));
+    }
+
     // -- Cancel tests --
 
     #[tokio::test]
@@ -800,6 +994,18 @@ mod tests {
         assert!(msg.contains("task not found: t-123"));
     }
 
+    #[test]
+    fn invalid_cursor_converts_to_invalid_params() {
+        let err = TaskStoreError::InvalidCursor {
+            message: "Invalid cursor".to_string(),
+        };
+        let sdk_err: crate::error::Error = err.into();
+        assert_eq!(
+            sdk_err.error_code(),
+            Some(crate::error::ErrorCode::INVALID_PARAMS)
+        );
+    }
+
     // -- Max tasks per owner --
 
     #[tokio::test]
@@ -11,6 +11,7 @@ use async_trait::async_trait;
 use serde_json::Value;
 
 use crate::error::Result;
+use crate::server::auth::AuthContext;
 
 /// Trait for routing MCP task requests.
 ///
@@ -59,6 +60,20 @@ pub trait TaskRouter: Send + Sync {
     /// Requests cancellation of the given task.
     async fn handle_tasks_cancel(&self, params: Value, owner_id: &str) -> Result<Value>;
 
+    /// Handle `tasks/provide_input` request.
+    ///
+    /// Stores the client's answer to the elicitation attached to a task in
+    /// `input_required` status and moves the task back to `working`.
+    ///
+    /// # Default
+    ///
+    /// Returns an error indicating task input is not supported.
+    async fn handle_tasks_provide_input(&self, _params: Value, _owner_id: &str) -> Result<Value> {
+        Err(crate::error::Error::internal(
+            "tasks/provide_input not supported by this router",
+        ))
+    }
+
     /// Resolve owner ID from authentication context fields.
     ///
     /// The owner ID determines task visibility and access control.
@@ -71,6 +86,25 @@ pub trait TaskRouter: Send + Sync {
         session_id: Option<&str>,
     ) -> String;
 
+    /// Resolve owner ID from the full authentication context.
+    ///
+    /// Routers with configurable owner strategies (tenant claims, API-key
+    /// hashes, ...) override this to see the whole [`AuthContext`]. The
+    /// default delegates to [`resolve_owner`](Self::resolve_owner) with the
+    /// subject and client ID.
+    fn resolve_owner_from_auth(
+        &self,
+        auth_context: Option<&AuthContext>,
+        session_id: Option<&str>,
+    ) -> String {
+        match auth_context {
+            Some(ctx) => {
+                self.resolve_owner(Some(&ctx.subject), ctx.client_id.as_deref(), session_id)
+            },
+            None => self.resolve_owner(None, None, session_id),
+        }
+    }
+
     /// Check if a tool requires task augmentation (`taskSupport: required`).
     ///
     /// When a tool has `execution.taskSupport == "required"`, the client
@@ -111,6 +145,26 @@ pub trait TaskRouter: Send + Sync {
         ))
     }
 
+    /// Load a workflow task so its execution can be resumed.
+    ///
+    /// Called by `TaskWorkflowPromptHandler` when a workflow prompt is
+    /// invoked with `_task_id` in `_meta`. Returns the task as `Value`, with
+    /// its variables (progress, step results, bindings) merged into `_meta`.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - ID of the task to load.
+    /// * `owner_id` - Owner identity for authorization.
+    ///
+    /// # Default
+    ///
+    /// Returns an error indicating workflow tasks are not supported.
+    async fn get_workflow_task(&self, _task_id: &str, _owner_id: &str) -> Result<Value> {
+        Err(crate::error::Error::internal(
+            "workflow tasks not supported by this router",
+        ))
+    }
+
     /// Update task variables with workflow step results.
     ///
     /// Called after each step completes to persist the step result
@@ -0,0 +1,574 @@
+//! In-process test harness for the streamable HTTP server.
+//!
+//! [`TestClient`] sends requests straight into the server's axum router with
+//! [`tower::ServiceExt::oneshot`], so tests exercise the real HTTP layer
+//! (headers, status codes, sessions, SSE framing, security layers) without
+//! binding a port. Create one with
+//! [`StreamableHttpServer::test_client()`](crate::server::streamable_http_server::StreamableHttpServer::test_client).
+//!
+//! The client tracks the `mcp-session-id` and negotiated
+//! `mcp-protocol-version` for you, numbers request IDs, and parses both JSON
+//! and SSE response bodies into JSON-RPC messages.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pmcp::server::streamable_http_server::StreamableHttpServer;
+//! use pmcp::Server;
+//! use serde_json::json;
+//! use std::sync::Arc;
+//! use tokio::sync::Mutex;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let server = Server::builder().name("test").version("1.0.0").build()?;
+//! let http = StreamableHttpServer::new("127.0.0.1:0".parse().unwrap(), Arc::new(Mutex::new(server)));
+//! let client = http.test_client();
+//!
+//! client.initialize().await.assert_success().await;
+//! assert!(client.session_id().is_some());
+//!
+//! // JSON-RPC assertions
+//! client.request("ping", json!({})).await.assert_result().await;
+//!
+//! // Server-to-client notifications over the standalone SSE stream
+//! let mut events = client.open_sse().await.into_sse();
+//! if let Some(message) = events.next_message_timeout(std::time::Duration::from_millis(100)).await {
+//!     println!("server sent {}", message);
+//! }
+//!
+//! // Transport errors are reported as JSON-RPC errors too
+//! client.set_session_id(Some("unknown".to_string()));
+//! client.request("ping", json!({})).await.assert_error(-32600).await;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::shared::http_constants::{
+    APPLICATION_JSON, MCP_PROTOCOL_VERSION, MCP_SESSION_ID, TEXT_EVENT_STREAM,
+};
+use crate::shared::sse_parser::{SseEvent, SseParser};
+use axum::body::{Body, BodyDataStream, Bytes};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode};
+use axum::Router;
+use futures_util::StreamExt;
+use http_body_util::BodyExt;
+use parking_lot::RwLock;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+/// `Host` sent when a request does not set one; passes the DNS rebinding layer.
+const DEFAULT_HOST: &str = "localhost";
+
+/// `Accept` header for POST requests, as required by the streamable HTTP spec.
+const POST_ACCEPT: &str = "application/json, text/event-stream";
+
+/// In-process client for a streamable HTTP MCP server.
+///
+/// Cloning is cheap; clones share the session ID, protocol version, and
+/// request ID counter.
+#[derive(Clone)]
+pub struct TestClient {
+    router: Router,
+    session_id: Arc<RwLock<Option<String>>>,
+    protocol_version: Arc<RwLock<Option<String>>>,
+    next_id: Arc<AtomicI64>,
+    default_headers: HeaderMap,
+}
+
+impl std::fmt::Debug for TestClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestClient")
+            .field("session_id", &*self.session_id.read())
+            .field("protocol_version", &*self.protocol_version.read())
+            .field("default_headers", &self.default_headers)
+            .finish()
+    }
+}
+
+impl TestClient {
+    pub(crate) fn new(router: Router) -> Self {
+        Self {
+            router,
+            session_id: Arc::new(RwLock::new(None)),
+            protocol_version: Arc::new(RwLock::new(None)),
+            next_id: Arc::new(AtomicI64::new(1)),
+            default_headers: HeaderMap::new(),
+        }
+    }
+
+    /// Add a header sent with every request (e.g. `Authorization`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not a valid header value.
+    pub fn with_header(mut self, name: HeaderName, value: &str) -> Self {
+        let value = HeaderValue::from_str(value).expect("invalid header value");
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Session ID assigned by the server, if any.
+    pub fn session_id(&self) -> Option<String> {
+        self.session_id.read().clone()
+    }
+
+    /// Override the session ID sent with subsequent requests.
+    pub fn set_session_id(&self, session_id: Option<String>) {
+        *self.session_id.write() = session_id;
+    }
+
+    /// Protocol version negotiated by [`initialize`](Self::initialize).
+    pub fn protocol_version(&self) -> Option<String> {
+        self.protocol_version.read().clone()
+    }
+
+    /// Send a raw HTTP request through the router.
+    ///
+    /// Only the default headers and a `Host` header (if missing) are added;
+    /// use this to test malformed requests.
+    pub async fn send(&self, mut request: Request<Body>) -> TestResponse {
+        let headers = request.headers_mut();
+        for (name, value) in &self.default_headers {
+            headers.entry(name).or_insert_with(|| value.clone());
+        }
+        headers
+            .entry(header::HOST)
+            .or_insert(HeaderValue::from_static(DEFAULT_HOST));
+
+        let response = match self.router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        };
+        let (parts, body) = response.into_parts();
+
+        if let Some(sid) = parts
+            .headers
+            .get(MCP_SESSION_ID)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session_id.write() = Some(sid.to_string());
+        }
+
+        TestResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+
+    /// POST a JSON-RPC message (or batch) with the MCP session headers.
+    pub async fn post(&self, message: Value) -> TestResponse {
+        let builder = self.session_headers(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/")
+                .header(header::CONTENT_TYPE, APPLICATION_JSON)
+                .header(header::ACCEPT, POST_ACCEPT),
+        );
+        let request = builder
+            .body(Body::from(message.to_string()))
+            .expect("valid request");
+        self.send(request).await
+    }
+
+    /// Send `initialize` followed by `notifications/initialized`.
+    ///
+    /// Records the session ID and negotiated protocol version so later
+    /// requests carry them. Returns the `initialize` response.
+    pub async fn initialize(&self) -> TestResponse {
+        self.initialize_with_capabilities(json!({})).await
+    }
+
+    /// Like [`initialize`](Self::initialize), declaring the given client `capabilities`.
+    pub async fn initialize_with_capabilities(&self, capabilities: Value) -> TestResponse {
+        let response = self
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": crate::LATEST_PROTOCOL_VERSION,
+                    "capabilities": capabilities,
+                    "clientInfo": { "name": "pmcp-test-client", "version": "1.0.0" },
+                }),
+            )
+            .await;
+
+        // Buffer the body so the negotiated version can be read and the
+        // response still handed back to the caller.
+        let status = response.status;
+        let headers = response.headers.clone();
+        let bytes = response.bytes().await;
+        let buffered = TestResponse {
+            status,
+            headers: headers.clone(),
+            body: Body::from(bytes.clone()),
+        };
+
+        if status.is_success() {
+            let messages = TestResponse {
+                status,
+                headers,
+                body: Body::from(bytes.clone()),
+            }
+            .messages()
+            .await;
+            if let Some(version) = messages
+                .first()
+                .and_then(|m| m["result"]["protocolVersion"].as_str())
+            {
+                *self.protocol_version.write() = Some(version.to_string());
+            }
+            self.notify("notifications/initialized", json!({})).await;
+        }
+
+        buffered
+    }
+
+    /// Send a JSON-RPC request with the next request ID.
+    pub async fn request(&self, method: &str, params: Value) -> TestResponse {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.post(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))
+        .await
+    }
+
+    /// Send a JSON-RPC notification.
+    pub async fn notify(&self, method: &str, params: Value) -> TestResponse {
+        self.post(json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+        .await
+    }
+
+    /// Open the standalone SSE stream (`GET /`).
+    ///
+    /// Use [`TestResponse::into_sse`] to read server-initiated messages.
+    pub async fn open_sse(&self) -> TestResponse {
+        let request = self
+            .session_headers(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/")
+                    .header(header::ACCEPT, TEXT_EVENT_STREAM),
+            )
+            .body(Body::empty())
+            .expect("valid request");
+        self.send(request).await
+    }
+
+    /// Terminate the session (`DELETE /`).
+    pub async fn close_session(&self) -> TestResponse {
+        let request = self
+            .session_headers(Request::builder().method(Method::DELETE).uri("/"))
+            .body(Body::empty())
+            .expect("valid request");
+        self.send(request).await
+    }
+
+    fn session_headers(
+        &self,
+        mut builder: axum::http::request::Builder,
+    ) -> axum::http::request::Builder {
+        if let Some(sid) = self.session_id.read().as_deref() {
+            builder = builder.header(MCP_SESSION_ID, sid);
+        }
+        if let Some(version) = self.protocol_version.read().as_deref() {
+            builder = builder.header(MCP_PROTOCOL_VERSION, version);
+        }
+        builder
+    }
+}
+
+/// HTTP response from a [`TestClient`] with JSON-RPC assertion helpers.
+pub struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Body,
+}
+
+impl std::fmt::Debug for TestResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TestResponse {
+    /// HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// A header value as a string, if present and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// Whether the body is an SSE stream.
+    pub fn is_sse(&self) -> bool {
+        self.header(header::CONTENT_TYPE.as_str())
+            .is_some_and(|ct| ct.starts_with(TEXT_EVENT_STREAM))
+    }
+
+    /// Read the whole body.
+    ///
+    /// Do not call this on the standalone SSE stream, which never ends; use
+    /// [`into_sse`](Self::into_sse) instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body fails to read.
+    pub async fn bytes(self) -> Bytes {
+        self.body
+            .collect()
+            .await
+            .expect("failed to read response body")
+            .to_bytes()
+    }
+
+    /// Read the whole body as UTF-8 text.
+    pub async fn text(self) -> String {
+        String::from_utf8_lossy(&self.bytes().await).into_owned()
+    }
+
+    /// Read the body as JSON.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body is not valid JSON.
+    pub async fn json(self) -> Value {
+        let text = self.text().await;
+        serde_json::from_str(&text)
+            .unwrap_or_else(|e| panic!("response body is not JSON ({}): {}", e, text))
+    }
+
+    /// All JSON-RPC messages in the body.
+    ///
+    /// Handles JSON bodies (a single message or a batch array) and SSE
+    /// bodies (one message per `data` field). Empty bodies yield no messages.
+    pub async fn messages(self) -> Vec<Value> {
+        if self.is_sse() {
+            let text = self.text().await;
+            return SseParser::new()
+                .feed(&text)
+                .into_iter()
+                .map(|event| parse_sse_data(&event))
+                .collect();
+        }
+
+        let bytes = self.bytes().await;
+        if bytes.is_empty() {
+            return Vec::new();
+        }
+        match serde_json::from_slice(&bytes) {
+            Ok(Value::Array(batch)) => batch,
+            Ok(message) => vec![message],
+            Err(e) => panic!(
+                "response body is not JSON ({}): {}",
+                e,
+                String::from_utf8_lossy(&bytes)
+            ),
+        }
+    }
+
+    /// The single JSON-RPC message in the body.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the body holds exactly one message.
+    pub async fn message(self) -> Value {
+        let mut messages = self.messages().await;
+        assert_eq!(
+            messages.len(),
+            1,
+            "expected exactly one JSON-RPC message, got {:?}",
+            messages
+        );
+        messages.remove(0)
+    }
+
+    /// Stream SSE events from the body as they arrive.
+    pub fn into_sse(self) -> SseEvents {
+        SseEvents {
+            stream: self.body.into_data_stream(),
+            parser: SseParser::new(),
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Assert the status code, printing the body on mismatch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status differs from `expected`.
+    pub async fn assert_status(self, expected: StatusCode) -> Self {
+        if self.status != expected {
+            let status = self.status;
+            panic!(
+                "expected HTTP {}, got {}: {}",
+                expected,
+                status,
+                self.text().await
+            );
+        }
+        self
+    }
+
+    /// Assert a 2xx status code, printing the body on failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status is not successful.
+    pub async fn assert_success(self) -> Self {
+        if !self.status.is_success() {
+            let status = self.status;
+            panic!("expected HTTP 2xx, got {}: {}", status, self.text().await);
+        }
+        self
+    }
+
+    /// Assert the body is a single successful JSON-RPC response and return its `result`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a non-2xx status, a missing `result`, or an `error` response.
+    pub async fn assert_result(self) -> Value {
+        let message = self.assert_success().await.message().await;
+        assert_jsonrpc_result(&message).clone()
+    }
+
+    /// Assert the body is a single JSON-RPC error with `code` and return the `error` object.
+    ///
+    /// Accepts any HTTP status, since transport-level errors (bad session,
+    /// bad headers) are reported as JSON-RPC errors with 4xx codes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body is not a JSON-RPC error with the given code.
+    pub async fn assert_error(self, code: i32) -> Value {
+        let message = self.message().await;
+        assert_jsonrpc_error(&message, code).clone()
+    }
+}
+
+/// Incremental reader for an SSE response body.
+pub struct SseEvents {
+    stream: BodyDataStream,
+    parser: SseParser,
+    /// Bytes after the last complete line, which may end mid UTF-8 character
+    buffer: Vec<u8>,
+    pending: VecDeque<SseEvent>,
+}
+
+impl std::fmt::Debug for SseEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SseEvents")
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SseEvents {
+    /// Next SSE event, or `None` when the stream ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body stream fails.
+    pub async fn next_event(&mut self) -> Option<SseEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            let chunk = self.stream.next().await?.expect("SSE stream failed");
+            self.buffer.extend_from_slice(&chunk);
+            // Decode only whole lines; a newline never splits a UTF-8 character
+            if let Some(end) = self.buffer.iter().rposition(|&b| b == b'\n') {
+                let lines: Vec<u8> = self.buffer.drain(..=end).collect();
+                self.pending
+                    .extend(self.parser.feed(&String::from_utf8_lossy(&lines)));
+            }
+        }
+    }
+
+    /// Next event, or `None` if nothing arrives within `timeout`.
+    pub async fn next_event_timeout(&mut self, timeout: Duration) -> Option<SseEvent> {
+        tokio::time::timeout(timeout, self.next_event())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Next event's `data` parsed as a JSON-RPC message.
+    pub async fn next_message(&mut self) -> Option<Value> {
+        self.next_event().await.map(|event| parse_sse_data(&event))
+    }
+
+    /// Next JSON-RPC message, or `None` if nothing arrives within `timeout`.
+    pub async fn next_message_timeout(&mut self, timeout: Duration) -> Option<Value> {
+        self.next_event_timeout(timeout)
+            .await
+            .map(|event| parse_sse_data(&event))
+    }
+}
+
+fn parse_sse_data(event: &SseEvent) -> Value {
+    serde_json::from_str(&event.data)
+        .unwrap_or_else(|e| panic!("SSE data is not JSON ({}): {}", e, event.data))
+}
+
+/// Assert `message` is a JSON-RPC success response and return its `result`.
+///
+/// # Panics
+///
+/// Panics if the message has an `error` or no `result`.
+pub fn assert_jsonrpc_result(message: &Value) -> &Value {
+    assert_eq!(
+        message["jsonrpc"], "2.0",
+        "not a JSON-RPC 2.0 message: {}",
+        message
+    );
+    assert!(
+        message.get("error").is_none(),
+        "expected JSON-RPC result, got error: {}",
+        message["error"]
+    );
+    message
+        .get("result")
+        .unwrap_or_else(|| panic!("JSON-RPC response has no result: {}", message))
+}
+
+/// Assert `message` is a JSON-RPC error with `code` and return the `error` object.
+///
+/// # Panics
+///
+/// Panics if the message has no `error` or the code differs.
+pub fn assert_jsonrpc_error(message: &Value, code: i32) -> &Value {
+    let error = message
+        .get("error")
+        .unwrap_or_else(|| panic!("expected JSON-RPC error {}, got: {}", code, message));
+    assert_eq!(
+        error["code"].as_i64(),
+        Some(i64::from(code)),
+        "expected JSON-RPC error code {}, got: {}",
+        code,
+        error
+    );
+    error
+}
@@ -4,8 +4,33 @@
 //! - OAuth token injection from server auth context
 //! - Request/response logging with tool metadata
 //! - Performance metrics collection
-//! - Authorization checks
+//! - Authorization checks and rate limiting
 //! - Argument validation and transformation
+//! - Response caching (by answering a call without running the handler)
+//!
+//! Middleware is registered with `tool_middleware` on the server builders and
+//! runs as an onion around every tool call: `on_request` hooks run in
+//! priority order, the handler runs, then `on_response` hooks run in reverse
+//! order. [`ToolMiddlewareChain::execute`] implements this lifecycle, so
+//! the same chain can wrap handlers outside the server as well.
+//!
+//! Use [`ToolFilter`] with [`FilteredMiddleware`] to apply a middleware to some
+//! tools only:
+//!
+//! ```rust
+//! use pmcp::server::tool_middleware::{FilteredMiddleware, ToolFilter, ToolMiddleware};
+//! use std::sync::Arc;
+//!
+//! struct AuditLog;
+//!
+//! impl ToolMiddleware for AuditLog {}
+//!
+//! let audit = FilteredMiddleware::new(
+//!     Arc::new(AuditLog),
+//!     ToolFilter::only(["delete_user", "refund_order"]),
+//! );
+//! # let _ = audit;
+//! ```
 //!
 //! # Security Best Practices
 //!
@@ -78,10 +103,12 @@
 
 use crate::error::{Error, Result};
 use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::ToolHandler;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tracing::Instrument;
 
 /// Context for tool middleware execution.
 ///
@@ -110,6 +137,15 @@ impl ToolContext {
         }
     }
 
+    /// Create the context for a call handled with `extra`, taking its
+    /// request and session IDs.
+    pub fn for_call(tool_name: impl Into<String>, extra: &RequestHandlerExtra) -> Self {
+        Self {
+            session_id: extra.session_id.clone(),
+            ..Self::new(tool_name, extra.request_id.clone())
+        }
+    }
+
     /// Set the session ID.
     pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
         self.session_id = Some(session_id.into());
@@ -147,9 +183,16 @@ impl ToolContext {
 /// Middleware runs in priority order (lower priority values execute first):
 /// 1. `should_execute()` - Check if middleware applies to this tool
 /// 2. `on_request()` - Before tool execution (can modify args/extra)
-/// 3. Tool execution
-/// 4. `on_response()` - After successful execution (can transform result)
-/// 5. `on_error()` - If tool execution fails (can transform error)
+/// 3. `intercept()` - Optionally answer the call without running the handler
+/// 4. Tool execution
+/// 5. `on_response()` - After execution, in reverse order (can transform result)
+/// 6. `on_error()` - Once, if the call or a response hook failed
+///
+/// `on_response` runs like the layers of an onion: the middleware that ran
+/// `on_request` first sees the result last. When a middleware intercepts or
+/// rejects the call, only the middleware that already ran `on_request` see
+/// the outcome in `on_response`. Middleware whose `should_execute` returns
+/// `false` see none of these hooks, `on_error` included.
 ///
 /// # Examples
 ///
@@ -243,6 +286,47 @@ pub trait ToolMiddleware: Send + Sync {
         Ok(())
     }
 
+    /// Called after this middleware's `on_request`, before the handler runs.
+    ///
+    /// Return `Some(value)` to answer the call with `value` without running
+    /// the handler or any later middleware, e.g. to serve a cached result.
+    /// Return an error to reject the call, as with `on_request`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::tool_middleware::{ToolMiddleware, ToolContext};
+    /// use pmcp::server::cancellation::RequestHandlerExtra;
+    /// use pmcp::Result;
+    /// use async_trait::async_trait;
+    /// use serde_json::{json, Value};
+    ///
+    /// struct Maintenance;
+    ///
+    /// #[async_trait]
+    /// impl ToolMiddleware for Maintenance {
+    ///     async fn intercept(
+    ///         &self,
+    ///         _tool_name: &str,
+    ///         _args: &Value,
+    ///         _extra: &RequestHandlerExtra,
+    ///         _context: &ToolContext,
+    ///     ) -> Result<Option<Value>> {
+    ///         Ok(Some(json!({"status": "down for maintenance"})))
+    ///     }
+    /// }
+    /// ```
+    async fn intercept(
+        &self,
+        tool_name: &str,
+        args: &Value,
+        extra: &RequestHandlerExtra,
+        context: &ToolContext,
+    ) -> Result<Option<Value>> {
+        let _ = (tool_name, args, extra, context);
+        Ok(None)
+    }
+
     /// Called after tool execution (success or failure).
     ///
     /// Can inspect or transform the result. This hook receives `&mut Result<Value>`,
@@ -360,9 +444,155 @@ pub trait ToolMiddleware: Send + Sync {
     }
 }
 
+/// Selects the tools a [`FilteredMiddleware`] applies to.
+#[derive(Clone)]
+pub enum ToolFilter {
+    /// Only the named tools.
+    Only(HashSet<String>),
+    /// Every tool except the named ones.
+    Except(HashSet<String>),
+    /// Tools whose name starts with the prefix.
+    Prefix(String),
+    /// Tools for which the predicate returns `true`.
+    Custom(Arc<dyn Fn(&ToolContext) -> bool + Send + Sync>),
+}
+
+impl ToolFilter {
+    /// Match only the named tools.
+    pub fn only<I, S>(tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Only(tools.into_iter().map(Into::into).collect())
+    }
+
+    /// Match every tool except the named ones.
+    pub fn except<I, S>(tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Except(tools.into_iter().map(Into::into).collect())
+    }
+
+    /// Match tools whose name starts with `prefix`.
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self::Prefix(prefix.into())
+    }
+
+    /// Match tools for which `predicate` returns `true`.
+    pub fn custom<F>(predicate: F) -> Self
+    where
+        F: Fn(&ToolContext) -> bool + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(predicate))
+    }
+
+    /// Returns `true` if the filter selects the tool in `context`.
+    pub fn matches(&self, context: &ToolContext) -> bool {
+        match self {
+            Self::Only(tools) => tools.contains(&context.tool_name),
+            Self::Except(tools) => !tools.contains(&context.tool_name),
+            Self::Prefix(prefix) => context.tool_name.starts_with(prefix.as_str()),
+            Self::Custom(predicate) => predicate(context),
+        }
+    }
+}
+
+impl std::fmt::Debug for ToolFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Only(tools) => f.debug_tuple("Only").field(tools).finish(),
+            Self::Except(tools) => f.debug_tuple("Except").field(tools).finish(),
+            Self::Prefix(prefix) => f.debug_tuple("Prefix").field(prefix).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Applies a middleware only to the tools selected by a [`ToolFilter`].
+///
+/// Every hook and the priority are delegated to the inner middleware; the
+/// filter is checked before the inner middleware's own `should_execute`.
+pub struct FilteredMiddleware {
+    inner: Arc<dyn ToolMiddleware>,
+    filter: ToolFilter,
+}
+
+impl FilteredMiddleware {
+    /// Wrap `middleware` so it only runs for tools matching `filter`.
+    pub fn new(middleware: Arc<dyn ToolMiddleware>, filter: ToolFilter) -> Self {
+        Self {
+            inner: middleware,
+            filter,
+        }
+    }
+
+    /// The filter selecting the tools this middleware runs for.
+    pub fn filter(&self) -> &ToolFilter {
+        &self.filter
+    }
+}
+
+impl std::fmt::Debug for FilteredMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilteredMiddleware")
+            .field("filter", &self.filter)
+            .field("priority", &self.inner.priority())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for FilteredMiddleware {
+    async fn on_request(
+        &self,
+        tool_name: &str,
+        args: &mut Value,
+        extra: &mut RequestHandlerExtra,
+        context: &ToolContext,
+    ) -> Result<()> {
+        self.inner.on_request(tool_name, args, extra, context).await
+    }
+
+    async fn intercept(
+        &self,
+        tool_name: &str,
+        args: &Value,
+        extra: &RequestHandlerExtra,
+        context: &ToolContext,
+    ) -> Result<Option<Value>> {
+        self.inner.intercept(tool_name, args, extra, context).await
+    }
+
+    async fn on_response(
+        &self,
+        tool_name: &str,
+        result: &mut Result<Value>,
+        context: &ToolContext,
+    ) -> Result<()> {
+        self.inner.on_response(tool_name, result, context).await
+    }
+
+    async fn on_error(&self, tool_name: &str, error: &Error, context: &ToolContext) -> Result<()> {
+        self.inner.on_error(tool_name, error, context).await
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    async fn should_execute(&self, context: &ToolContext) -> bool {
+        self.filter.matches(context) && self.inner.should_execute(context).await
+    }
+}
+
 /// Chain of tool middleware.
 ///
 /// Executes middleware in priority order for tool execution lifecycle hooks.
+/// Middleware with equal priority run in the order they were added.
+#[derive(Clone)]
 pub struct ToolMiddlewareChain {
     middlewares: Vec<Arc<dyn ToolMiddleware>>,
 }
@@ -384,6 +614,116 @@ impl ToolMiddlewareChain {
         self.middlewares.sort_by_key(|m| m.priority());
     }
 
+    /// Number of middleware in the chain.
+    pub fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Returns `true` if the chain has no middleware.
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    /// Run a tool call through the whole middleware lifecycle.
+    ///
+    /// For each applicable middleware in priority order, `on_request` runs and
+    /// then `intercept`. If a middleware intercepts the call, the handler and
+    /// the remaining middleware are skipped. Otherwise `handler` runs with the
+    /// (possibly modified) arguments and extra data. Then `on_response` runs
+    /// in reverse order for every middleware whose `on_request` succeeded.
+    ///
+    /// An error from `on_request` or `intercept` rejects the call: the handler
+    /// and the remaining middleware are skipped, and the middleware that
+    /// already entered see the rejection in `on_response`. An error from
+    /// `on_response` is logged, the remaining `on_response` hooks still run,
+    /// and the result as transformed so far is returned.
+    ///
+    /// Finally, if the call failed or an `on_response` hook failed, `on_error`
+    /// runs once for every middleware whose `on_request` was called, with the
+    /// call's error (or else the first hook error).
+    pub async fn execute(
+        &self,
+        tool_name: &str,
+        args: Value,
+        extra: RequestHandlerExtra,
+        context: &ToolContext,
+        handler: &dyn ToolHandler,
+    ) -> Result<Value> {
+        crate::server::observability::scope_call(self.run(tool_name, args, extra, context, handler))
+            .await
+    }
+
+    async fn run(
+        &self,
+        tool_name: &str,
+        mut args: Value,
+        mut extra: RequestHandlerExtra,
+        context: &ToolContext,
+        handler: &dyn ToolHandler,
+    ) -> Result<Value> {
+        let mut applicable = Vec::with_capacity(self.middlewares.len());
+        let mut entered = Vec::with_capacity(self.middlewares.len());
+        let mut outcome = None;
+        for middleware in &self.middlewares {
+            if !middleware.should_execute(context).await {
+                continue;
+            }
+            applicable.push(middleware);
+            if let Err(e) = middleware
+                .on_request(tool_name, &mut args, &mut extra, context)
+                .await
+            {
+                outcome = Some(Err(e));
+                break;
+            }
+            entered.push(middleware);
+            match middleware
+                .intercept(tool_name, &args, &extra, context)
+                .await
+            {
+                Ok(None) => {},
+                Ok(Some(value)) => {
+                    outcome = Some(Ok(value));
+                    break;
+                },
+                Err(e) => {
+                    outcome = Some(Err(e));
+                    break;
+                },
+            }
+        }
+
+        let mut result = match outcome {
+            Some(result) => result,
+            None => {
+                let span = crate::server::observability::tool_span(tool_name, &extra);
+                let trace = crate::server::observability::tool_trace(&extra);
+                let call = handler.handle(args, extra).instrument(span);
+                match trace {
+                    Some(trace) => trace.scope(call).await,
+                    None => call.await,
+                }
+            },
+        };
+
+        let mut hook_error = None;
+        for middleware in entered.into_iter().rev() {
+            if let Err(e) = middleware
+                .on_response(tool_name, &mut result, context)
+                .await
+            {
+                tracing::warn!("Tool response middleware processing failed: {}", e);
+                hook_error.get_or_insert(e);
+            }
+        }
+
+        if let Some(error) = result.as_ref().err().or(hook_error.as_ref()) {
+            notify_error(applicable, tool_name, error, context).await;
+        }
+
+        result
+    }
+
     /// Process request through all middleware.
     ///
     /// If any middleware returns an error:
@@ -433,19 +773,17 @@ impl ToolMiddlewareChain {
         Ok(())
     }
 
-    /// Handle error by calling `on_error` for all middleware.
+    /// Handle error by calling `on_error` for all applicable middleware.
     ///
     /// Errors from `on_error` itself are logged but don't propagate.
     async fn handle_error(&self, tool_name: &str, error: &Error, context: &ToolContext) {
+        let mut applicable = Vec::with_capacity(self.middlewares.len());
         for middleware in &self.middlewares {
-            if let Err(e) = middleware.on_error(tool_name, error, context).await {
-                tracing::error!(
-                    "Error in tool middleware on_error hook: {} (original error: {})",
-                    e,
-                    error
-                );
+            if middleware.should_execute(context).await {
+                applicable.push(middleware);
             }
         }
+        notify_error(applicable, tool_name, error, context).await;
     }
 
     /// Handle error from tool execution.
@@ -457,6 +795,24 @@ impl ToolMiddlewareChain {
     }
 }
 
+/// Call `on_error` on each middleware, logging errors from the hooks.
+async fn notify_error(
+    middlewares: Vec<&Arc<dyn ToolMiddleware>>,
+    tool_name: &str,
+    error: &Error,
+    context: &ToolContext,
+) {
+    for middleware in middlewares {
+        if let Err(e) = middleware.on_error(tool_name, error, context).await {
+            tracing::error!(
+                "Error in tool middleware on_error hook: {} (original error: {})",
+                e,
+                error
+            );
+        }
+    }
+}
+
 impl Default for ToolMiddlewareChain {
     fn default() -> Self {
         Self::new()
@@ -824,4 +1180,334 @@ mod tests {
             debug_output
         );
     }
+
+    /// Records hook calls in a shared log, optionally answering every call.
+    struct RecordingMiddleware {
+        name: &'static str,
+        priority: i32,
+        answer: Option<Value>,
+        fail_response: bool,
+        log: Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    impl RecordingMiddleware {
+        fn new(
+            name: &'static str,
+            priority: i32,
+            log: &Arc<parking_lot::Mutex<Vec<String>>>,
+        ) -> Self {
+            Self {
+                name,
+                priority,
+                answer: None,
+                fail_response: false,
+                log: log.clone(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ToolMiddleware for RecordingMiddleware {
+        async fn on_request(
+            &self,
+            _tool_name: &str,
+            _args: &mut Value,
+            _extra: &mut RequestHandlerExtra,
+            _context: &ToolContext,
+        ) -> Result<()> {
+            self.log.lock().push(format!("{}:request", self.name));
+            Ok(())
+        }
+
+        async fn intercept(
+            &self,
+            _tool_name: &str,
+            _args: &Value,
+            _extra: &RequestHandlerExtra,
+            _context: &ToolContext,
+        ) -> Result<Option<Value>> {
+            Ok(self.answer.clone())
+        }
+
+        async fn on_response(
+            &self,
+            _tool_name: &str,
+            _result: &mut Result<Value>,
+            _context: &ToolContext,
+        ) -> Result<()> {
+            self.log.lock().push(format!("{}:response", self.name));
+            if self.fail_response {
+                return Err(Error::internal("response hook failed"));
+            }
+            Ok(())
+        }
+
+        async fn on_error(
+            &self,
+            _tool_name: &str,
+            error: &Error,
+            _context: &ToolContext,
+        ) -> Result<()> {
+            self.log
+                .lock()
+                .push(format!("{}:error {}", self.name, error));
+            Ok(())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    struct LoggingTool {
+        log: Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl ToolHandler for LoggingTool {
+        async fn handle(&self, args: Value, _extra: RequestHandlerExtra) -> Result<Value> {
+            self.log.lock().push("handler".to_string());
+            Ok(args)
+        }
+    }
+
+    struct FailingTool;
+
+    #[async_trait]
+    impl ToolHandler for FailingTool {
+        async fn handle(&self, _args: Value, _extra: RequestHandlerExtra) -> Result<Value> {
+            Err(Error::internal("tool failed"))
+        }
+    }
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("req-1".to_string(), CancellationToken::new())
+    }
+
+    #[tokio::test]
+    async fn test_execute_runs_hooks_as_onion() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(RecordingMiddleware::new("logging", 90, &log)));
+        chain.add(Arc::new(RecordingMiddleware::new("auth", 10, &log)));
+
+        let tool = LoggingTool { log: log.clone() };
+        let result = chain
+            .execute(
+                "echo",
+                serde_json::json!({"x": 1}),
+                extra(),
+                &ToolContext::new("echo", "req-1"),
+                &tool,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result, serde_json::json!({"x": 1}));
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "auth:request",
+                "logging:request",
+                "handler",
+                "logging:response",
+                "auth:response",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_intercept_skips_handler_and_later_middleware() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut cache = RecordingMiddleware::new("cache", 20, &log);
+        cache.answer = Some(serde_json::json!({"cached": true}));
+
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(RecordingMiddleware::new("auth", 10, &log)));
+        chain.add(Arc::new(cache));
+        chain.add(Arc::new(RecordingMiddleware::new("logging", 90, &log)));
+
+        let tool = LoggingTool { log: log.clone() };
+        let result = chain
+            .execute(
+                "echo",
+                serde_json::json!({}),
+                extra(),
+                &ToolContext::new("echo", "req-1"),
+                &tool,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result, serde_json::json!({"cached": true}));
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "auth:request",
+                "cache:request",
+                "cache:response",
+                "auth:response",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejection_skips_handler() {
+        struct RateLimit;
+
+        #[async_trait]
+        impl ToolMiddleware for RateLimit {
+            async fn on_request(
+                &self,
+                _tool_name: &str,
+                _args: &mut Value,
+                _extra: &mut RequestHandlerExtra,
+                _context: &ToolContext,
+            ) -> Result<()> {
+                Err(Error::validation("Rate limit exceeded"))
+            }
+        }
+
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(RecordingMiddleware::new("auth", 10, &log)));
+        chain.add(Arc::new(RateLimit));
+        chain.add(Arc::new(RecordingMiddleware::new("logging", 90, &log)));
+
+        let tool = LoggingTool { log: log.clone() };
+        let result = chain
+            .execute(
+                "echo",
+                serde_json::json!({}),
+                extra(),
+                &ToolContext::new("echo", "req-1"),
+                &tool,
+            )
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("Rate limit"));
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "auth:request",
+                "auth:response",
+                "auth:error Validation error: Rate limit exceeded",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_response_failure_still_runs_outer_hooks() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut inner = RecordingMiddleware::new("inner", 50, &log);
+        inner.fail_response = true;
+
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(RecordingMiddleware::new("outer", 10, &log)));
+        chain.add(Arc::new(inner));
+
+        let tool = LoggingTool { log: log.clone() };
+        let result = chain
+            .execute(
+                "echo",
+                serde_json::json!({"x": 1}),
+                extra(),
+                &ToolContext::new("echo", "req-1"),
+                &tool,
+            )
+            .await;
+
+        assert_eq!(result.unwrap(), serde_json::json!({"x": 1}));
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "outer:request",
+                "inner:request",
+                "handler",
+                "inner:response",
+                "outer:response",
+                "outer:error Internal error: response hook failed",
+                "inner:error Internal error: response hook failed",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_middleware_skips_errors_of_other_tools() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(FilteredMiddleware::new(
+            Arc::new(RecordingMiddleware::new("audit", 50, &log)),
+            ToolFilter::only(["delete_user"]),
+        )));
+
+        for name in ["get_user", "delete_user"] {
+            let result = chain
+                .execute(
+                    name,
+                    serde_json::json!({}),
+                    extra(),
+                    &ToolContext::new(name, "req-1"),
+                    &FailingTool,
+                )
+                .await;
+            assert!(result.is_err());
+        }
+        chain
+            .handle_tool_error(
+                "get_user",
+                &Error::internal("late"),
+                &ToolContext::new("get_user", "req-2"),
+            )
+            .await;
+
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "audit:request",
+                "audit:response",
+                "audit:error Internal error: tool failed",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_middleware_applies_to_selected_tools() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut chain = ToolMiddlewareChain::new();
+        chain.add(Arc::new(FilteredMiddleware::new(
+            Arc::new(RecordingMiddleware::new("audit", 50, &log)),
+            ToolFilter::only(["delete_user"]),
+        )));
+
+        let tool = LoggingTool { log: log.clone() };
+        for name in ["get_user", "delete_user"] {
+            chain
+                .execute(
+                    name,
+                    serde_json::json!({}),
+                    extra(),
+                    &ToolContext::new(name, "req-1"),
+                    &tool,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            *log.lock(),
+            vec!["handler", "audit:request", "handler", "audit:response"]
+        );
+    }
+
+    #[test]
+    fn test_tool_filter_matching() {
+        let context = ToolContext::new("db_query", "req-1");
+
+        assert!(ToolFilter::only(["db_query"]).matches(&context));
+        assert!(!ToolFilter::except(["db_query"]).matches(&context));
+        assert!(ToolFilter::prefix("db_").matches(&context));
+        assert!(!ToolFilter::prefix("api_").matches(&context));
+        assert!(ToolFilter::custom(|ctx| ctx.request_id == "req-1").matches(&context));
+    }
 }
@@ -0,0 +1,280 @@
+//! Deadlines for tool handlers.
+//!
+//! A tool call that outlives its deadline is abandoned: the handler future
+//! is dropped, the request's cancellation token
+//! is cancelled so work the handler spawned can stop, a
+//! [`ServerEvent::ToolTimedOut`] is published, and the client receives a
+//! `REQUEST_TIMEOUT` (-32001) error naming the tool.
+//!
+//! The deadline covers the handler only. Tool middleware runs outside it and
+//! sees a timed-out call as that error in `on_response` and `on_error`, so
+//! logging, metrics, and observability record it like any other failure.
+//!
+//! Deadlines are configured on the server builder, with a default for every
+//! tool and overrides for individual tools:
+//!
+//! ```rust
+//! use pmcp::Server;
+//! use std::time::Duration;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("warehouse")
+//!     .version("1.0.0")
+//!     .default_tool_timeout(Duration::from_secs(30))
+//!     .tool_timeout("execute_query", Duration::from_secs(120))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Without a default, tools without an override run without a deadline.
+//! Workflow steps that call a tool run under the same deadline as a direct
+//! `tools/call`.
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::event_bus::{EventBus, ServerEvent};
+use crate::server::ToolHandler;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+#[cfg(feature = "workflow")]
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Per-tool handler deadlines.
+#[derive(Debug, Clone, Default)]
+pub struct ToolTimeouts {
+    default: Option<Duration>,
+    per_tool: HashMap<String, Duration>,
+}
+
+impl ToolTimeouts {
+    /// No deadlines.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the deadline for tools without an override.
+    #[must_use]
+    pub fn with_default(mut self, timeout: Duration) -> Self {
+        self.default = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for one tool, overriding the default.
+    #[must_use]
+    pub fn with_tool(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.per_tool.insert(name.into(), timeout);
+        self
+    }
+
+    /// The deadline for `name`, if any.
+    pub fn for_tool(&self, name: &str) -> Option<Duration> {
+        self.per_tool.get(name).copied().or(self.default)
+    }
+
+    /// Run a tool call under the deadline for `name`.
+    ///
+    /// On timeout the call is dropped, `token` is cancelled, and a
+    /// [`ServerEvent::ToolTimedOut`] is published on `events`.
+    pub(crate) async fn run<F, T>(
+        &self,
+        name: &str,
+        token: &CancellationToken,
+        events: &EventBus,
+        call: F,
+    ) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let Some(timeout) = self.for_tool(name) else {
+            return call.await;
+        };
+        if let Ok(result) = tokio::time::timeout(timeout, call).await {
+            return result;
+        }
+
+        token.cancel();
+        tracing::warn!(
+            tool = name,
+            timeout_ms = %timeout.as_millis(),
+            "Tool call timed out"
+        );
+        events.emit(ServerEvent::ToolTimedOut {
+            name: name.to_string(),
+            timeout,
+        });
+        Err(timeout_error(name, timeout))
+    }
+
+    /// Wrap `handler` so each call runs under the deadline for `name`.
+    ///
+    /// Passed to the tool middleware chain in place of the handler, so the
+    /// middleware observes timeouts.
+    pub(crate) fn guard<'a>(
+        &'a self,
+        name: &'a str,
+        events: &'a EventBus,
+        handler: &'a dyn ToolHandler,
+    ) -> TimedHandler<'a> {
+        TimedHandler {
+            timeouts: self,
+            name,
+            events,
+            handler,
+        }
+    }
+}
+
+/// A tool handler running under its deadline (see [`ToolTimeouts::guard`]).
+pub(crate) struct TimedHandler<'a> {
+    timeouts: &'a ToolTimeouts,
+    name: &'a str,
+    events: &'a EventBus,
+    handler: &'a dyn ToolHandler,
+}
+
+#[async_trait]
+impl ToolHandler for TimedHandler<'_> {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        let token = extra.cancellation_token.clone();
+        self.timeouts
+            .run(
+                self.name,
+                &token,
+                self.events,
+                self.handler.handle(args, extra),
+            )
+            .await
+    }
+}
+
+/// Deadlines filled in when the server is built.
+///
+/// Workflows capture their tool handlers at registration, before the
+/// builder's deadlines are final; the builder sets this cell in `build()`.
+#[cfg(feature = "workflow")]
+pub(crate) type DeferredToolTimeouts = Arc<OnceLock<ToolTimeouts>>;
+
+/// An owned tool handler running under deadlines fixed at build time.
+///
+/// Used for the handlers workflow steps call, so a step gets the same
+/// deadline and [`ServerEvent::ToolTimedOut`] as a direct `tools/call`.
+#[cfg(feature = "workflow")]
+pub(crate) struct DeferredTimedHandler {
+    name: String,
+    handler: Arc<dyn ToolHandler>,
+    timeouts: DeferredToolTimeouts,
+    events: EventBus,
+}
+
+#[cfg(feature = "workflow")]
+impl DeferredTimedHandler {
+    /// Wrap the handler registered as `name`.
+    pub(crate) fn new(
+        name: impl Into<String>,
+        handler: Arc<dyn ToolHandler>,
+        timeouts: DeferredToolTimeouts,
+        events: EventBus,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            handler,
+            timeouts,
+            events,
+        }
+    }
+}
+
+#[cfg(feature = "workflow")]
+#[async_trait]
+impl ToolHandler for DeferredTimedHandler {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        match self.timeouts.get() {
+            Some(timeouts) => {
+                timeouts
+                    .guard(&self.name, &self.events, self.handler.as_ref())
+                    .handle(args, extra)
+                    .await
+            },
+            None => self.handler.handle(args, extra).await,
+        }
+    }
+
+    fn metadata(&self) -> Option<crate::types::ToolInfo> {
+        self.handler.metadata()
+    }
+}
+
+/// The error returned to the client when a tool exceeds its deadline.
+fn timeout_error(name: &str, timeout: Duration) -> Error {
+    Error::protocol(
+        ErrorCode::REQUEST_TIMEOUT,
+        format!("Tool '{}' timed out after {}ms", name, timeout.as_millis()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_override_takes_precedence() {
+        let timeouts = ToolTimeouts::new()
+            .with_default(Duration::from_secs(30))
+            .with_tool("execute_query", Duration::from_secs(120));
+        assert_eq!(
+            timeouts.for_tool("execute_query"),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(timeouts.for_tool("echo"), Some(Duration::from_secs(30)));
+
+        let only_override = ToolTimeouts::new().with_tool("slow", Duration::from_secs(5));
+        assert_eq!(only_override.for_tool("echo"), None);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_cancels_call() {
+        let events = EventBus::new();
+        let timed_out = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let seen = timed_out.clone();
+        events.on_event(move |event| {
+            if let ServerEvent::ToolTimedOut { name, timeout } = event {
+                seen.lock().push((name.clone(), *timeout));
+            }
+        });
+
+        let timeouts = ToolTimeouts::new().with_default(Duration::from_millis(50));
+        let token = CancellationToken::new();
+        let err = timeouts
+            .run("stuck", &token, &events, async {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.error_code(), Some(ErrorCode::REQUEST_TIMEOUT));
+        assert!(err
+            .to_string()
+            .contains("Tool 'stuck' timed out after 50ms"));
+        assert!(token.is_cancelled());
+        assert_eq!(
+            *timed_out.lock(),
+            vec![("stuck".to_string(), Duration::from_millis(50))]
+        );
+
+        let token = CancellationToken::new();
+        let value = timeouts
+            .run("quick", &token, &events, async { Ok(7) })
+            .await
+            .unwrap();
+        assert_eq!(value, 7);
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,125 @@
+//! Negotiated HTTP compression for MCP servers.
+//!
+//! Compresses response bodies with gzip or zstd when the client's
+//! `Accept-Encoding` allows it, and decompresses request bodies sent with
+//! `Content-Encoding: gzip` or `zstd`.
+//!
+//! Small bodies are sent as-is (compression costs more than it saves), and
+//! SSE streams (`text/event-stream`) are never compressed: encoders buffer
+//! output, which would hold events back until the buffer fills.
+//!
+//! Decompressed request bodies are capped (16 MiB by default) so a small
+//! compressed payload cannot inflate without bound in memory.
+
+use axum::Router;
+use tower::ServiceBuilder;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::decompression::RequestDecompressionLayer;
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// Default minimum response size, in bytes, before compressing.
+const DEFAULT_MIN_SIZE: u16 = 1024;
+
+/// Default maximum request body size, in bytes, after decompression.
+const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+/// HTTP compression settings for the streamable HTTP server.
+///
+/// Set [`StreamableHttpServerConfig::compression`] to enable it; gzip and
+/// zstd are both offered by default.
+///
+/// [`StreamableHttpServerConfig::compression`]: crate::server::streamable_http_server::StreamableHttpServerConfig::compression
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pmcp::server::tower_layers::CompressionConfig;
+///
+/// // Only compress responses of 4 KiB or more, and only with zstd
+/// let compression = CompressionConfig::new().min_size(4096).without_gzip();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionConfig {
+    min_size: u16,
+    max_decompressed_size: usize,
+    gzip: bool,
+    zstd: bool,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            min_size: DEFAULT_MIN_SIZE,
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            gzip: true,
+            zstd: true,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Create a compression config with gzip and zstd enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only compress responses of at least `bytes` bytes (default 1024).
+    ///
+    /// Streaming bodies without a known size are always eligible.
+    #[must_use]
+    pub fn min_size(mut self, bytes: u16) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// Reject request bodies larger than `bytes` once decompressed
+    /// (default 16 MiB) with `413 Payload Too Large`.
+    ///
+    /// Applies to uncompressed request bodies as well.
+    #[must_use]
+    pub fn max_decompressed_size(mut self, bytes: usize) -> Self {
+        self.max_decompressed_size = bytes;
+        self
+    }
+
+    /// Do not offer or accept gzip.
+    #[must_use]
+    pub fn without_gzip(mut self) -> Self {
+        self.gzip = false;
+        self
+    }
+
+    /// Do not offer or accept zstd.
+    #[must_use]
+    pub fn without_zstd(mut self) -> Self {
+        self.zstd = false;
+        self
+    }
+
+    /// Wrap `router` with response compression and request decompression.
+    ///
+    /// The body limit sits inside the decompression layer, so it counts
+    /// decompressed bytes.
+    pub(crate) fn apply(&self, router: Router) -> Router {
+        let predicate = SizeAbove::new(self.min_size)
+            .and(NotForContentType::SSE)
+            .and(NotForContentType::IMAGES);
+
+        router.layer(
+            ServiceBuilder::new()
+                .layer(
+                    RequestDecompressionLayer::new()
+                        .gzip(self.gzip)
+                        .zstd(self.zstd),
+                )
+                .layer(RequestBodyLimitLayer::new(self.max_decompressed_size))
+                .layer(
+                    CompressionLayer::new()
+                        .gzip(self.gzip)
+                        .zstd(self.zstd)
+                        .compress_when(predicate),
+                ),
+        )
+    }
+}
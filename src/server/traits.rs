@@ -25,10 +25,7 @@ pub trait RequestHandler:
 pub trait ToolHandler: Send + Sync {
     /// List available tools.
     async fn list_tools(&self, _req: crate::types::ListToolsRequest) -> Result<ListToolsResult> {
-        Ok(ListToolsResult {
-            tools: vec![],
-            next_cursor: None,
-        })
+        Ok(ListToolsResult::new(vec![]))
     }
 
     /// Handle a tool call with the given arguments.
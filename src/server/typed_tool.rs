@@ -15,6 +15,8 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
 
+use super::argument_coercion::coerce_arguments;
+use super::argument_error::deserialize_arguments;
 use super::cancellation::RequestHandlerExtra;
 use super::ToolHandler;
 
@@ -35,6 +37,7 @@ where
     annotations: Option<ToolAnnotations>,
     ui_resource_uri: Option<String>,
     execution: Option<ToolExecution>,
+    lenient_input: bool,
     handler: F,
     _phantom: PhantomData<T>,
 }
@@ -77,6 +80,7 @@ where
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -91,6 +95,7 @@ where
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -240,6 +245,33 @@ where
         self.execution = Some(execution);
         self
     }
+
+    /// Accept string-encoded numbers and booleans (`"5"`, `"true"`) for fields
+    /// whose input schema declares a number or boolean.
+    ///
+    /// LLM clients often send stringly-typed arguments. In lenient-input mode they
+    /// are coerced to the schema types before deserialization; see
+    /// [`argument_coercion`](super::argument_coercion) for the exact rules.
+    ///
+    /// ```rust
+    /// use pmcp::server::typed_tool::TypedTool;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Args { limit: u32 }
+    ///
+    /// let tool = TypedTool::new_with_schema(
+    ///     "search",
+    ///     json!({ "type": "object", "properties": { "limit": { "type": "integer" } } }),
+    ///     |args: Args, _extra| Box::pin(async move { Ok(json!({ "limit": args.limit })) }),
+    /// )
+    /// .lenient_input();
+    /// ```
+    pub fn lenient_input(mut self) -> Self {
+        self.lenient_input = true;
+        self
+    }
 }
 
 #[async_trait]
@@ -251,10 +283,12 @@ where
         + Sync,
 {
     async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
-        // Deserialize and validate the arguments
-        let typed_args: T = serde_json::from_value(args).map_err(|e| {
-            crate::Error::Validation(format!("Invalid arguments for tool '{}': {}", self.name, e))
-        })?;
+        // Deserialize the arguments, reporting the failing field on error
+        let typed_args: T = deserialize_arguments(
+            &self.name,
+            prepare_arguments(args, &self.input_schema, self.lenient_input),
+            &self.input_schema,
+        )?;
 
         // Call the handler with the typed arguments
         (self.handler)(typed_args, extra).await
@@ -284,9 +318,11 @@ where
     name: String,
     description: Option<String>,
     input_schema: Value,
+    output_schema: Option<Value>,
     annotations: Option<ToolAnnotations>,
     ui_resource_uri: Option<String>,
     execution: Option<ToolExecution>,
+    lenient_input: bool,
     handler: F,
     _phantom: PhantomData<T>,
 }
@@ -301,6 +337,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("output_schema", &self.output_schema)
             .field("annotations", &self.annotations)
             .finish()
     }
@@ -322,9 +359,11 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            output_schema: None,
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -336,9 +375,11 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            output_schema: None,
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -350,6 +391,15 @@ where
         self
     }
 
+    /// Declare the JSON schema of this tool's result (`outputSchema`).
+    ///
+    /// Servers attach object results of tools with an output schema as
+    /// `structuredContent`, as the MCP spec requires.
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
     /// Set annotations for this tool.
     ///
     /// See [`TypedTool::with_annotations`] for detailed documentation.
@@ -418,6 +468,14 @@ where
         self.execution = Some(execution);
         self
     }
+
+    /// Accept string-encoded numbers and booleans for schema-typed fields.
+    ///
+    /// See [`TypedTool::lenient_input`] for detailed documentation.
+    pub fn lenient_input(mut self) -> Self {
+        self.lenient_input = true;
+        self
+    }
 }
 
 #[async_trait]
@@ -427,10 +485,12 @@ where
     F: Fn(T, RequestHandlerExtra) -> Result<Value> + Send + Sync,
 {
     async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
-        // Deserialize and validate the arguments
-        let typed_args: T = serde_json::from_value(args).map_err(|e| {
-            crate::Error::Validation(format!("Invalid arguments for tool '{}': {}", self.name, e))
-        })?;
+        // Deserialize the arguments, reporting the failing field on error
+        let typed_args: T = deserialize_arguments(
+            &self.name,
+            prepare_arguments(args, &self.input_schema, self.lenient_input),
+            &self.input_schema,
+        )?;
 
         // Call the handler with the typed arguments
         (self.handler)(typed_args, extra)
@@ -442,7 +502,7 @@ where
             title: None,
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
-            output_schema: None,
+            output_schema: self.output_schema.clone(),
             annotations: self.annotations.clone(),
             icons: None,
             _meta: crate::types::ui::build_ui_meta(self.ui_resource_uri.as_deref()),
@@ -451,6 +511,15 @@ where
     }
 }
 
+/// Coerce stringly-typed arguments to the input schema when lenient input is enabled.
+fn prepare_arguments(args: Value, input_schema: &Value, lenient_input: bool) -> Value {
+    if lenient_input {
+        coerce_arguments(args, input_schema)
+    } else {
+        args
+    }
+}
+
 /// Generate a JSON schema for a type using schemars.
 #[cfg(feature = "schema-generation")]
 fn generate_schema<T: JsonSchema>() -> Value {
@@ -469,6 +538,17 @@ fn generate_schema<T: JsonSchema>() -> Value {
     crate::server::schema_utils::normalize_schema(json_schema)
 }
 
+/// Generate an `outputSchema` for a tool result type.
+///
+/// Returns `None` unless the schema describes an object, since MCP requires
+/// `outputSchema` to have `"type": "object"`. This also leaves handlers that
+/// return untyped `serde_json::Value` without an output schema.
+#[cfg(feature = "schema-generation")]
+pub(crate) fn generate_output_schema<T: JsonSchema>() -> Option<Value> {
+    let schema = generate_schema::<T>();
+    (schema.get("type").and_then(Value::as_str) == Some("object")).then_some(schema)
+}
+
 /// Extension trait to add type-safe schema generation to `SimpleTool`.
 pub trait SimpleToolExt {
     /// Create a `SimpleTool` with schema generated from a type.
@@ -531,6 +611,7 @@ where
     annotations: Option<ToolAnnotations>,
     ui_resource_uri: Option<String>,
     execution: Option<ToolExecution>,
+    lenient_input: bool,
     handler: F,
     _phantom: PhantomData<(TIn, TOut)>,
 }
@@ -581,6 +662,7 @@ where
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -602,6 +684,7 @@ where
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -622,6 +705,7 @@ where
             annotations: None,
             ui_resource_uri: None,
             execution: None,
+            lenient_input: false,
             handler,
             _phantom: PhantomData,
         }
@@ -724,6 +808,14 @@ where
         self.execution = Some(execution);
         self
     }
+
+    /// Accept string-encoded numbers and booleans for schema-typed fields.
+    ///
+    /// See [`TypedTool::lenient_input`] for detailed documentation.
+    pub fn lenient_input(mut self) -> Self {
+        self.lenient_input = true;
+        self
+    }
 }
 
 #[async_trait]
@@ -736,9 +828,12 @@ where
         + Sync,
 {
     async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
-        // Parse the arguments to the input type
-        let typed_args: TIn = serde_json::from_value(args)
-            .map_err(|e| Error::Validation(format!("Invalid arguments: {}", e)))?;
+        // Parse the arguments to the input type, reporting the failing field on error
+        let typed_args: TIn = deserialize_arguments(
+            &self.name,
+            prepare_arguments(args, &self.input_schema, self.lenient_input),
+            &self.input_schema,
+        )?;
 
         // Call the handler
         let result = (self.handler)(typed_args, extra).await?;
@@ -849,6 +944,81 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_typed_sync_tool_reports_invalid_argument_pointer() {
+        #[derive(serde::Deserialize)]
+        struct Args {
+            #[allow(dead_code)]
+            count: u32,
+        }
+
+        let tool = TypedSyncTool::new_with_schema(
+            "counter",
+            json!({"type": "object", "properties": {"count": {"type": "integer"}}}),
+            |_args: Args, _extra| Ok(json!({})),
+        );
+        let extra = RequestHandlerExtra::new(
+            "test-request".to_string(),
+            tokio_util::sync::CancellationToken::new(),
+        );
+
+        let err = tool
+            .handle(json!({"count": "three"}), extra)
+            .await
+            .unwrap_err();
+        match err {
+            Error::Protocol {
+                code,
+                data: Some(data),
+                ..
+            } => {
+                assert_eq!(code, crate::ErrorCode::INVALID_PARAMS);
+                assert_eq!(data["pointer"], "/count");
+                assert_eq!(data["expected"], "integer");
+                assert_eq!(data["schema"], json!({"type": "integer"}));
+            },
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lenient_input_coerces_string_arguments() {
+        #[derive(serde::Deserialize)]
+        struct Args {
+            count: u32,
+            verbose: bool,
+        }
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "count": {"type": "integer"},
+                "verbose": {"type": "boolean"}
+            }
+        });
+        let handler =
+            |args: Args, _extra| Ok(json!({"count": args.count, "verbose": args.verbose}));
+        let extra = || {
+            RequestHandlerExtra::new(
+                "test-request".to_string(),
+                tokio_util::sync::CancellationToken::new(),
+            )
+        };
+
+        let strict = TypedSyncTool::new_with_schema("counter", schema.clone(), handler);
+        assert!(strict
+            .handle(json!({"count": "3", "verbose": "true"}), extra())
+            .await
+            .is_err());
+
+        let lenient = TypedSyncTool::new_with_schema("counter", schema, handler).lenient_input();
+        let result = lenient
+            .handle(json!({"count": "3", "verbose": "true"}), extra())
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"count": 3, "verbose": true}));
+    }
+
     #[test]
     fn test_typed_sync_tool_metadata_without_ui_has_no_meta() {
         let tool = TypedSyncTool::new_with_schema(
@@ -983,4 +1153,37 @@ mod tests {
         let info = tool.metadata().unwrap();
         assert!(info.execution.is_none());
     }
+
+    #[test]
+    fn test_typed_sync_tool_with_output_schema() {
+        let output_schema = json!({
+            "type": "object",
+            "properties": {"total": {"type": "integer"}}
+        });
+        let tool = TypedSyncTool::new_with_schema(
+            "sum",
+            json!({"type": "object"}),
+            |_args: serde_json::Value, _extra| Ok(json!({"total": 3})),
+        )
+        .with_output_schema(output_schema.clone());
+
+        let info = tool.metadata().unwrap();
+        assert_eq!(info.output_schema, Some(output_schema));
+    }
+
+    #[cfg(feature = "schema-generation")]
+    #[test]
+    fn test_generate_output_schema_requires_object() {
+        #[derive(serde::Serialize, JsonSchema)]
+        struct Totals {
+            total: u32,
+        }
+
+        let schema = generate_output_schema::<Totals>().expect("struct output has a schema");
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"].get("total").is_some());
+
+        assert!(generate_output_schema::<serde_json::Value>().is_none());
+        assert!(generate_output_schema::<Vec<String>>().is_none());
+    }
 }
@@ -138,10 +138,7 @@ impl WasmMcpServer {
     fn handle_list_tools(&self, _params: ListToolsRequest) -> Result<Value> {
         let tools: Vec<ToolInfo> = self.tool_infos.values().cloned().collect();
 
-        let result = ListToolsResult {
-            tools,
-            next_cursor: None,
-        };
+        let result = ListToolsResult::new(tools);
         serde_json::to_value(result).map_err(|e| Error::internal(&e.to_string()))
     }
 
@@ -243,8 +240,14 @@ impl WasmMcpServer {
         ))
     }
 
-    fn handle_list_prompts(&self, _params: ListPromptsRequest) -> Result<Value> {
-        let prompts: Vec<PromptInfo> = self.prompt_infos.values().cloned().collect();
+    fn handle_list_prompts(&self, params: ListPromptsRequest) -> Result<Value> {
+        let mut prompts: Vec<PromptInfo> = self
+            .prompt_infos
+            .values()
+            .filter(|info| params.matches(&info.name))
+            .cloned()
+            .collect();
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
 
         let result = ListPromptsResult {
             prompts,
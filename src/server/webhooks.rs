@@ -0,0 +1,597 @@
+//! Outbound webhooks for server lifecycle and task events.
+//!
+//! A [`WebhookSink`] posts [`WebhookEvent`]s as JSON to one or more HTTP
+//! endpoints, so operations can reach Slack, PagerDuty or an incident bot
+//! without glue code. Attached to a server's [`EventBus`], it forwards server
+//! starts, tool timeouts and task outcomes; deploy tooling and test runners
+//! post their own events with [`WebhookSink::send`].
+//!
+//! Each request carries the event name in [`EVENT_HEADER`]. Endpoints with a
+//! secret also get [`SIGNATURE_HEADER`], `sha256=<hex>` of the HMAC-SHA256 of
+//! the body, which receivers check with [`verify_signature`]. Failed
+//! deliveries (connection errors, `429` and `5xx` responses) are retried with
+//! exponential backoff.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pmcp::server::webhooks::{events, WebhookEndpoint, WebhookSink};
+//! use pmcp::Server;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("billing")
+//!     .version("1.0.0")
+//!     .build()?;
+//!
+//! let sink = WebhookSink::new()
+//!     .endpoint(
+//!         WebhookEndpoint::new("https://hooks.slack.com/services/T000/B000/XXXX")
+//!             .events([events::SERVER_STARTED, events::TASK_FAILED]),
+//!     )
+//!     .endpoint(
+//!         WebhookEndpoint::new("https://ops.example.com/mcp-events")
+//!             .secret(std::env::var("WEBHOOK_SECRET").unwrap_or_default()),
+//!     );
+//! sink.attach(server.events());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::error::{Error, Result};
+use crate::server::event_bus::{EventBus, ServerEvent};
+use crate::types::tasks::TaskStatus;
+
+/// Request header naming the event.
+pub const EVENT_HEADER: &str = "X-Pmcp-Event";
+
+/// Request header with the `sha256=<hex>` HMAC signature of the body.
+pub const SIGNATURE_HEADER: &str = "X-Pmcp-Signature";
+
+/// Default number of delivery attempts per endpoint, including the first.
+const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+/// Default wait before the first retry; doubled for each further retry.
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest wait between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Default timeout of a single delivery attempt.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Names of the events posted by the SDK and the pmcp tooling.
+pub mod events {
+    /// The server started serving a transport.
+    pub const SERVER_STARTED: &str = "server.started";
+    /// A tool call exceeded its deadline.
+    pub const TOOL_TIMED_OUT: &str = "tool.timed_out";
+    /// A task completed.
+    pub const TASK_COMPLETED: &str = "task.completed";
+    /// A task failed.
+    pub const TASK_FAILED: &str = "task.failed";
+    /// A task was cancelled.
+    pub const TASK_CANCELLED: &str = "task.cancelled";
+    /// `cargo pmcp deploy` finished a deployment.
+    pub const DEPLOY_COMPLETED: &str = "deploy.completed";
+    /// A test scenario (for example a scheduled run) failed.
+    pub const TEST_FAILED: &str = "test.failed";
+}
+
+/// The JSON body of a webhook request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Event name, such as [`events::TASK_FAILED`].
+    pub event: String,
+    /// When the event happened.
+    pub timestamp: DateTime<Utc>,
+    /// Event details.
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    /// An event that happened now.
+    pub fn new(event: impl Into<String>, data: Value) -> Self {
+        Self {
+            event: event.into(),
+            timestamp: Utc::now(),
+            data,
+        }
+    }
+
+    /// The webhook event for a server event, if it is one that is posted.
+    ///
+    /// Per-request events (sessions, tool calls, resource reads) and task
+    /// transitions to a non-terminal status are not posted.
+    pub fn from_server_event(event: &ServerEvent) -> Option<Self> {
+        match event {
+            ServerEvent::ServerStarted { server } => Some(Self::new(
+                events::SERVER_STARTED,
+                json!({ "name": server.name, "version": server.version }),
+            )),
+            ServerEvent::ToolTimedOut { name, timeout } => Some(Self::new(
+                events::TOOL_TIMED_OUT,
+                json!({ "tool": name, "timeout_ms": timeout.as_millis() }),
+            )),
+            ServerEvent::TaskTransitioned { task_id, status } => {
+                let event = match status {
+                    TaskStatus::Completed => events::TASK_COMPLETED,
+                    TaskStatus::Failed => events::TASK_FAILED,
+                    TaskStatus::Cancelled => events::TASK_CANCELLED,
+                    _ => return None,
+                };
+                Some(Self::new(event, json!({ "task_id": task_id })))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// An HTTP endpoint that receives webhooks.
+#[derive(Clone)]
+pub struct WebhookEndpoint {
+    url: String,
+    secret: Option<Arc<[u8]>>,
+    events: Vec<String>,
+}
+
+impl fmt::Debug for WebhookEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookEndpoint")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("events", &self.events)
+            .finish()
+    }
+}
+
+impl WebhookEndpoint {
+    /// An endpoint receiving every event, unsigned.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Sign request bodies with this secret.
+    #[must_use]
+    pub fn secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.secret = Some(Arc::from(secret.as_ref()));
+        self
+    }
+
+    /// Only post these events.
+    ///
+    /// A name ending in `.*` matches a whole group, such as `task.*`.
+    #[must_use]
+    pub fn events<I, S>(mut self, events: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.events.extend(events.into_iter().map(Into::into));
+        self
+    }
+
+    /// The endpoint URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Whether the endpoint receives `event`.
+    pub fn accepts(&self, event: &str) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|filter| match filter.strip_suffix('*') {
+                    Some(prefix) => event.starts_with(prefix),
+                    None => filter == event,
+                })
+    }
+}
+
+/// Posts webhook events to endpoints, retrying failed deliveries.
+///
+/// Cloning is cheap; clones share the HTTP client and endpoints.
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    endpoints: Arc<Vec<WebhookEndpoint>>,
+    client: reqwest::Client,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    request_timeout: Duration,
+}
+
+impl Default for WebhookSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookSink {
+    /// A sink without endpoints.
+    pub fn new() -> Self {
+        Self {
+            endpoints: Arc::new(Vec::new()),
+            client: reqwest::Client::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Add an endpoint.
+    #[must_use]
+    pub fn endpoint(mut self, endpoint: WebhookEndpoint) -> Self {
+        Arc::make_mut(&mut self.endpoints).push(endpoint);
+        self
+    }
+
+    /// Attempt each delivery up to `max_attempts` times (at least once),
+    /// waiting `initial_backoff` before the first retry and doubling the wait
+    /// after that, up to 30 seconds.
+    #[must_use]
+    pub fn retry(mut self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Give up on a single delivery attempt after `timeout` (default 10 seconds).
+    #[must_use]
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Post `event` to every endpoint that accepts it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the endpoints that still failed after their
+    /// retries. Deliveries to the other endpoints are not affected.
+    pub async fn send(&self, event: &WebhookEvent) -> Result<()> {
+        let body = serde_json::to_string(event)?;
+        let deliveries = self
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.accepts(&event.event))
+            .map(|endpoint| self.deliver(endpoint, &event.event, &body));
+        let failures: Vec<String> = futures::future::join_all(deliveries)
+            .await
+            .into_iter()
+            .filter_map(|result| result.err().map(|e| e.to_string()))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::internal(format!(
+                "Webhook delivery of '{}' failed: {}",
+                event.event,
+                failures.join("; ")
+            )))
+        }
+    }
+
+    /// Forward the server's events to the endpoints in the background.
+    ///
+    /// Deliveries run on their own tasks, so a slow endpoint does not hold
+    /// up later events. Failures are logged. The task ends when the event
+    /// bus is dropped.
+    pub fn attach(&self, events: &EventBus) -> tokio::task::JoinHandle<()> {
+        let mut receiver = events.subscribe();
+        let sink = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Webhook sink lagged, events skipped");
+                        continue;
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+                let Some(webhook) = WebhookEvent::from_server_event(&event) else {
+                    continue;
+                };
+                if !sink.endpoints.iter().any(|e| e.accepts(&webhook.event)) {
+                    continue;
+                }
+                let sink = sink.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = sink.send(&webhook).await {
+                        tracing::warn!("{}", e);
+                    }
+                });
+            }
+        })
+    }
+
+    async fn deliver(&self, endpoint: &WebhookEndpoint, event: &str, body: &str) -> Result<()> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let error = match self.post(endpoint, event, body).await {
+                Ok(()) => return Ok(()),
+                Err(Delivery::Rejected(error)) => return Err(error),
+                Err(Delivery::Retryable(error)) => error,
+            };
+            if attempt >= self.max_attempts {
+                return Err(error);
+            }
+            tracing::debug!(
+                url = endpoint.url(),
+                attempt,
+                "Webhook delivery failed, retrying: {}",
+                error
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            attempt += 1;
+        }
+    }
+
+    async fn post(
+        &self,
+        endpoint: &WebhookEndpoint,
+        event: &str,
+        body: &str,
+    ) -> std::result::Result<(), Delivery> {
+        let mut request = self
+            .client
+            .post(&endpoint.url)
+            .timeout(self.request_timeout)
+            .header("Content-Type", "application/json")
+            .header(EVENT_HEADER, event);
+        if let Some(secret) = &endpoint.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, body.as_bytes()));
+        }
+
+        let response = request.body(body.to_string()).send().await.map_err(|e| {
+            Delivery::Retryable(Error::internal(format!(
+                "Webhook {} unreachable: {}",
+                endpoint.url, e
+            )))
+        })?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let error = Error::internal(format!("Webhook {} returned {}", endpoint.url, status));
+        if status.is_server_error() || status.as_u16() == 429 {
+            Err(Delivery::Retryable(error))
+        } else {
+            Err(Delivery::Rejected(error))
+        }
+    }
+}
+
+/// Why a delivery attempt failed.
+enum Delivery {
+    /// Worth another attempt.
+    Retryable(Error),
+    /// The endpoint refused the request; retrying won't help.
+    Rejected(Error),
+}
+
+/// The [`SIGNATURE_HEADER`] value for `body`.
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac =
+        <HmacSha256 as KeyInit>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Check a [`SIGNATURE_HEADER`] value against the body a receiver got.
+///
+/// The comparison is constant-time.
+pub fn verify_signature(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let Some(hex) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return false;
+    }
+    let Ok(expected) = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<std::result::Result<Vec<u8>, _>>()
+    else {
+        return false;
+    };
+    let mut mac =
+        <HmacSha256 as KeyInit>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Implementation;
+    use parking_lot::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one connection per status in `statuses`, recording the requests
+    async fn serve(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then the body announced by Content-Length
+                loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw).to_string();
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= end + 4 + length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                seen.lock().push(String::from_utf8_lossy(&raw).to_string());
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let signature = sign(b"s3cret", b"{\"event\":\"x\"}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert!(verify_signature(
+            b"s3cret",
+            b"{\"event\":\"x\"}",
+            &signature
+        ));
+        assert!(!verify_signature(
+            b"other",
+            b"{\"event\":\"x\"}",
+            &signature
+        ));
+        assert!(!verify_signature(b"s3cret", b"{}", &signature));
+        assert!(!verify_signature(b"s3cret", b"{}", "sha256=zz"));
+        assert!(!verify_signature(b"s3cret", b"{}", "md5=00"));
+    }
+
+    #[test]
+    fn test_server_events_and_filters() {
+        let started = WebhookEvent::from_server_event(&ServerEvent::ServerStarted {
+            server: Implementation::new("billing", "1.2.0"),
+        })
+        .unwrap();
+        assert_eq!(started.event, events::SERVER_STARTED);
+        assert_eq!(
+            started.data,
+            json!({ "name": "billing", "version": "1.2.0" })
+        );
+
+        let failed = WebhookEvent::from_server_event(&ServerEvent::TaskTransitioned {
+            task_id: "t-1".to_string(),
+            status: TaskStatus::Failed,
+        })
+        .unwrap();
+        assert_eq!(failed.event, events::TASK_FAILED);
+        assert!(
+            WebhookEvent::from_server_event(&ServerEvent::TaskTransitioned {
+                task_id: "t-1".to_string(),
+                status: TaskStatus::Working,
+            })
+            .is_none()
+        );
+        assert!(
+            WebhookEvent::from_server_event(&ServerEvent::SessionClosed { session_id: None })
+                .is_none()
+        );
+
+        let tasks = WebhookEndpoint::new("http://localhost").events(["task.*", "deploy.completed"]);
+        assert!(tasks.accepts(events::TASK_FAILED));
+        assert!(tasks.accepts(events::DEPLOY_COMPLETED));
+        assert!(!tasks.accepts(events::SERVER_STARTED));
+        assert!(WebhookEndpoint::new("http://localhost").accepts(events::SERVER_STARTED));
+    }
+
+    #[tokio::test]
+    async fn test_send_signs_and_retries() {
+        let (url, requests) = serve(vec![503, 200]).await;
+        let sink = WebhookSink::new()
+            .endpoint(WebhookEndpoint::new(&url).secret("s3cret"))
+            .retry(3, Duration::from_millis(10));
+        let event = WebhookEvent::new(events::DEPLOY_COMPLETED, json!({ "url": "https://x" }));
+        sink.send(&event).await.unwrap();
+
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 2);
+        let request = &requests[1];
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        let head = head.to_lowercase();
+        assert!(head.contains("x-pmcp-event: deploy.completed"));
+        let signature = head
+            .lines()
+            .find_map(|l| l.strip_prefix("x-pmcp-signature: "))
+            .unwrap();
+        assert!(verify_signature(b"s3cret", body.as_bytes(), signature));
+        let posted: WebhookEvent = serde_json::from_str(body).unwrap();
+        assert_eq!(posted, event);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let (url, requests) = serve(vec![400, 200]).await;
+        let sink = WebhookSink::new()
+            .endpoint(WebhookEndpoint::new(&url))
+            .retry(3, Duration::from_millis(10));
+        let err = sink
+            .send(&WebhookEvent::new(events::TEST_FAILED, json!({})))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("400"), "{}", err);
+        assert_eq!(requests.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_attach_forwards_server_events() {
+        let (url, requests) = serve(vec![200]).await;
+        let sink = WebhookSink::new().endpoint(WebhookEndpoint::new(&url).events(["task.*"]));
+        let bus = EventBus::new();
+        sink.attach(&bus);
+
+        bus.emit(ServerEvent::SessionClosed { session_id: None });
+        bus.emit(ServerEvent::TaskTransitioned {
+            task_id: "t-9".to_string(),
+            status: TaskStatus::Failed,
+        });
+
+        for _ in 0..100 {
+            if !requests.lock().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].contains("\"task_id\":\"t-9\""));
+        assert!(requests[0].contains("\"event\":\"task.failed\""));
+    }
+}
@@ -0,0 +1,251 @@
+//! Server-side persistence for widget state.
+//!
+//! MCP Apps widgets are rendered inside host iframes that are torn down on
+//! reload, so any state the widget holds in memory is lost. A
+//! [`WidgetStateStore`] lets stateful hosts persist that state on the server,
+//! keyed by session and widget URI, so a reloaded widget can resume where it
+//! left off.
+//!
+//! The store is registered on the server builder and handed to every tool
+//! call and resource read through
+//! [`RequestHandlerExtra`](crate::server::cancellation::RequestHandlerExtra),
+//! which resolves the session key for the handler.
+//!
+//! # Backends
+//!
+//! - [`InMemoryWidgetStateStore`] - Thread-safe in-memory store for
+//!   development, testing and single-process servers.
+//! - `RedisWidgetStateStore` - Redis store for multi-instance deployments,
+//!   provided by the `pmcp-tasks` crate behind its `redis` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use pmcp::server::widget_state::{InMemoryWidgetStateStore, WidgetStateStore};
+//! use serde_json::json;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let store = InMemoryWidgetStateStore::new();
+//! store
+//!     .save("session-1", "ui://board/main.html", json!({"selected": 3}))
+//!     .await?;
+//! let state = store.load("session-1", "ui://board/main.html").await?;
+//! assert_eq!(state, Some(json!({"selected": 3})));
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// Default upper bound on the serialized size of a single widget state (64 KiB).
+pub const DEFAULT_MAX_STATE_BYTES: usize = 64 * 1024;
+
+/// Persists widget state keyed by session and widget URI.
+///
+/// Implementations must be `Send + Sync` for concurrent access from
+/// multiple request handlers. State is opaque JSON; the store never
+/// interprets it.
+#[async_trait]
+pub trait WidgetStateStore: Send + Sync {
+    /// Load the state saved for a widget in a session.
+    ///
+    /// Returns `None` if nothing was saved or the saved state has expired.
+    async fn load(&self, session_id: &str, widget_uri: &str) -> Result<Option<Value>>;
+
+    /// Save (replace) the state for a widget in a session.
+    async fn save(&self, session_id: &str, widget_uri: &str, state: Value) -> Result<()>;
+
+    /// Remove the state for a widget in a session.
+    ///
+    /// Returns `true` if state existed and was removed.
+    async fn clear(&self, session_id: &str, widget_uri: &str) -> Result<bool>;
+}
+
+/// Serialize widget state, rejecting payloads larger than `max_bytes`.
+///
+/// Shared by store implementations so every backend enforces the same limit
+/// and error shape.
+///
+/// # Errors
+///
+/// Returns a validation error if the serialized state exceeds `max_bytes`.
+pub fn encode_state(state: &Value, max_bytes: usize) -> Result<String> {
+    let encoded = serde_json::to_string(state)?;
+    if encoded.len() > max_bytes {
+        return Err(Error::validation(format!(
+            "widget state is {} bytes, exceeding the {max_bytes} byte limit",
+            encoded.len()
+        )));
+    }
+    Ok(encoded)
+}
+
+/// Stored widget state with its optional expiry.
+#[derive(Debug)]
+struct StateEntry {
+    state: Value,
+    expires_at: Option<Instant>,
+}
+
+impl StateEntry {
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| Instant::now() > at)
+    }
+}
+
+/// Thread-safe in-memory widget state store using [`DashMap`].
+///
+/// State lives for the lifetime of the process. For deployments with more
+/// than one server instance, use the Redis store from `pmcp-tasks`.
+///
+/// # Examples
+///
+/// ```
+/// use pmcp::server::widget_state::InMemoryWidgetStateStore;
+/// use std::time::Duration;
+///
+/// let store = InMemoryWidgetStateStore::new()
+///     .with_ttl(Duration::from_secs(3600))
+///     .with_max_state_bytes(16 * 1024);
+/// ```
+#[derive(Debug)]
+pub struct InMemoryWidgetStateStore {
+    entries: DashMap<(String, String), StateEntry>,
+    ttl: Option<Duration>,
+    max_state_bytes: usize,
+}
+
+impl InMemoryWidgetStateStore {
+    /// Create an in-memory store with no expiry and the default size limit.
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl: None,
+            max_state_bytes: DEFAULT_MAX_STATE_BYTES,
+        }
+    }
+
+    /// Expire saved state `ttl` after it was last written.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Override the maximum serialized size of a single widget state.
+    pub fn with_max_state_bytes(mut self, max_state_bytes: usize) -> Self {
+        self.max_state_bytes = max_state_bytes;
+        self
+    }
+
+    /// Drop all expired entries, returning how many were removed.
+    pub fn cleanup_expired(&self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired());
+        before - self.entries.len()
+    }
+
+    fn key(session_id: &str, widget_uri: &str) -> (String, String) {
+        (session_id.to_string(), widget_uri.to_string())
+    }
+}
+
+impl Default for InMemoryWidgetStateStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WidgetStateStore for InMemoryWidgetStateStore {
+    async fn load(&self, session_id: &str, widget_uri: &str) -> Result<Option<Value>> {
+        let key = Self::key(session_id, widget_uri);
+        let expired = match self.entries.get(&key) {
+            Some(entry) if !entry.is_expired() => return Ok(Some(entry.state.clone())),
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            self.entries.remove_if(&key, |_, entry| entry.is_expired());
+        }
+        Ok(None)
+    }
+
+    async fn save(&self, session_id: &str, widget_uri: &str, state: Value) -> Result<()> {
+        encode_state(&state, self.max_state_bytes)?;
+        self.entries.insert(
+            Self::key(session_id, widget_uri),
+            StateEntry {
+                state,
+                expires_at: self.ttl.map(|ttl| Instant::now() + ttl),
+            },
+        );
+        Ok(())
+    }
+
+    async fn clear(&self, session_id: &str, widget_uri: &str) -> Result<bool> {
+        Ok(self
+            .entries
+            .remove(&Self::key(session_id, widget_uri))
+            .is_some_and(|(_, entry)| !entry.is_expired()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const WIDGET: &str = "ui://board/main.html";
+
+    #[tokio::test]
+    async fn test_save_load_clear_roundtrip() {
+        let store = InMemoryWidgetStateStore::new();
+        assert_eq!(store.load("s1", WIDGET).await.unwrap(), None);
+
+        store.save("s1", WIDGET, json!({"page": 2})).await.unwrap();
+        assert_eq!(
+            store.load("s1", WIDGET).await.unwrap(),
+            Some(json!({"page": 2}))
+        );
+
+        // Other sessions and widgets are isolated.
+        assert_eq!(store.load("s2", WIDGET).await.unwrap(), None);
+        assert_eq!(store.load("s1", "ui://other.html").await.unwrap(), None);
+
+        assert!(store.clear("s1", WIDGET).await.unwrap());
+        assert!(!store.clear("s1", WIDGET).await.unwrap());
+        assert_eq!(store.load("s1", WIDGET).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_state_is_not_returned() {
+        let store = InMemoryWidgetStateStore::new().with_ttl(Duration::from_millis(1));
+        store.save("s1", WIDGET, json!(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(store.load("s1", WIDGET).await.unwrap(), None);
+        store.save("s2", WIDGET, json!(2)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(store.cleanup_expired(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_state_is_rejected() {
+        let store = InMemoryWidgetStateStore::new().with_max_state_bytes(16);
+        let err = store
+            .save(
+                "s1",
+                WIDGET,
+                json!({"notes": "far more than sixteen bytes"}),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("byte limit"));
+        assert_eq!(store.load("s1", WIDGET).await.unwrap(), None);
+    }
+}
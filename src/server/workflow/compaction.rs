@@ -0,0 +1,396 @@
+//! Conversation-trace compaction for workflow prompt results
+//!
+//! Workflow prompts return the full execution trace (intent, plan, guidance, tool calls,
+//! tool results and embedded resources). Hosts count every token of a prompt result, so
+//! long traces are expensive. Compactors rewrite the trace before it is returned and are
+//! selected per workflow with [`SequentialWorkflow::compact_trace`](super::SequentialWorkflow::compact_trace).
+//!
+//! # Example
+//!
+//! ```
+//! use pmcp::server::workflow::{SequentialWorkflow, WorkflowStep, ToolHandle};
+//! use pmcp::server::workflow::compaction::{
+//!     DedupeSessionResources, DropIntermediateToolOutputs, MergeConsecutiveGuidance,
+//! };
+//!
+//! let workflow = SequentialWorkflow::new("report", "Build a report")
+//!     .step(WorkflowStep::new("fetch", ToolHandle::new("fetch_rows")).bind("rows"))
+//!     .step(WorkflowStep::new("render", ToolHandle::new("render_report")))
+//!     .compact_trace(DropIntermediateToolOutputs)
+//!     .compact_trace(MergeConsecutiveGuidance)
+//!     .compact_trace(DedupeSessionResources::new());
+//!
+//! assert_eq!(workflow.trace_compactors().len(), 3);
+//! ```
+
+use crate::types::{Content, PromptMessage, Role};
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Prefix of the user message carrying a tool's raw output in a workflow trace.
+const TOOL_RESULT_PREFIX: &str = "Tool result:\n";
+
+/// Prefix of the user message embedding a fetched resource in a workflow trace.
+const RESOURCE_CONTENT_PREFIX: &str = "Resource content from ";
+
+/// Default number of sessions remembered by [`DedupeSessionResources`].
+const DEFAULT_SESSION_CAPACITY: usize = 1024;
+
+/// Rewrites a workflow conversation trace before it is returned to the client.
+///
+/// Compactors run in the order they were added to the workflow, each receiving the
+/// output of the previous one.
+pub trait TraceCompactor: Send + Sync + std::fmt::Debug {
+    /// Compact the trace.
+    ///
+    /// `session_id` identifies the client session when the transport provides one, so
+    /// compactors can remember what was already sent across prompt invocations.
+    fn compact(&self, messages: Vec<PromptMessage>, session_id: Option<&str>)
+        -> Vec<PromptMessage>;
+}
+
+/// Replaces all but the last tool result with a one-line summary.
+///
+/// Intermediate outputs are already bound and consumed server-side by later steps; the
+/// client usually only needs the final result.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropIntermediateToolOutputs;
+
+impl TraceCompactor for DropIntermediateToolOutputs {
+    fn compact(
+        &self,
+        mut messages: Vec<PromptMessage>,
+        _session_id: Option<&str>,
+    ) -> Vec<PromptMessage> {
+        let results: Vec<usize> = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| tool_result_body(message).is_some())
+            .map(|(index, _)| index)
+            .collect();
+
+        let Some((_, intermediate)) = results.split_last() else {
+            return messages;
+        };
+
+        for &index in intermediate {
+            let summary = tool_result_body(&messages[index]).map(summarize_tool_result);
+            if let Some(summary) = summary {
+                messages[index] = PromptMessage::user(Content::text(summary));
+            }
+        }
+
+        messages
+    }
+}
+
+/// Merges runs of consecutive assistant text messages into a single message.
+///
+/// The plan, per-step guidance and tool-call announcements are all assistant text; merging
+/// them removes the per-message overhead without losing any content.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeConsecutiveGuidance;
+
+impl TraceCompactor for MergeConsecutiveGuidance {
+    fn compact(
+        &self,
+        messages: Vec<PromptMessage>,
+        _session_id: Option<&str>,
+    ) -> Vec<PromptMessage> {
+        let mut merged: Vec<PromptMessage> = Vec::with_capacity(messages.len());
+
+        for message in messages {
+            if let Some(text) = assistant_text(&message) {
+                if let Some(previous) = merged.last_mut().and_then(assistant_text_mut) {
+                    previous.push_str("\n\n");
+                    previous.push_str(text);
+                    continue;
+                }
+            }
+            merged.push(message);
+        }
+
+        merged
+    }
+}
+
+/// Replaces embedded resources that were already sent earlier in the session with a
+/// short reference.
+///
+/// Resources are identified by URI and content, so a resource whose content changed is
+/// sent again. Without a session ID, duplicates are only removed within a single trace.
+/// The number of remembered sessions is bounded; the oldest session is forgotten first.
+#[derive(Debug)]
+pub struct DedupeSessionResources {
+    sessions: Mutex<IndexMap<String, HashSet<u64>>>,
+    capacity: usize,
+}
+
+impl DedupeSessionResources {
+    /// Create a deduplicator remembering up to 1024 sessions.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_SESSION_CAPACITY)
+    }
+
+    /// Create a deduplicator remembering up to `capacity` sessions.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            sessions: Mutex::new(IndexMap::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn dedupe(messages: Vec<PromptMessage>, seen: &mut HashSet<u64>) -> Vec<PromptMessage> {
+        messages
+            .into_iter()
+            .map(|message| match embedded_resource(&message) {
+                Some((uri, fingerprint)) if !seen.insert(fingerprint) => {
+                    PromptMessage::user(Content::text(format!(
+                        "Resource {} was already provided earlier in this session.",
+                        uri
+                    )))
+                },
+                _ => message,
+            })
+            .collect()
+    }
+}
+
+impl Default for DedupeSessionResources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TraceCompactor for DedupeSessionResources {
+    fn compact(
+        &self,
+        messages: Vec<PromptMessage>,
+        session_id: Option<&str>,
+    ) -> Vec<PromptMessage> {
+        let Some(session_id) = session_id else {
+            return Self::dedupe(messages, &mut HashSet::new());
+        };
+
+        let mut sessions = self.sessions.lock();
+        let mut seen = sessions.shift_remove(session_id).unwrap_or_default();
+        let messages = Self::dedupe(messages, &mut seen);
+
+        // Re-insert at the back so the least recently used session is evicted first
+        sessions.insert(session_id.to_string(), seen);
+        while sessions.len() > self.capacity {
+            sessions.shift_remove_index(0);
+        }
+
+        messages
+    }
+}
+
+/// Apply `compactors` to `messages` in order.
+pub(crate) fn apply(
+    compactors: &[std::sync::Arc<dyn TraceCompactor>],
+    messages: Vec<PromptMessage>,
+    session_id: Option<&str>,
+) -> Vec<PromptMessage> {
+    compactors.iter().fold(messages, |messages, compactor| {
+        compactor.compact(messages, session_id)
+    })
+}
+
+fn tool_result_body(message: &PromptMessage) -> Option<&str> {
+    match message {
+        PromptMessage {
+            role: Role::User,
+            content: Content::Text { text },
+        } => text.strip_prefix(TOOL_RESULT_PREFIX),
+        _ => None,
+    }
+}
+
+fn assistant_text(message: &PromptMessage) -> Option<&str> {
+    match message {
+        PromptMessage {
+            role: Role::Assistant,
+            content: Content::Text { text },
+        } => Some(text),
+        _ => None,
+    }
+}
+
+fn assistant_text_mut(message: &mut PromptMessage) -> Option<&mut String> {
+    match message {
+        PromptMessage {
+            role: Role::Assistant,
+            content: Content::Text { text },
+        } => Some(text),
+        _ => None,
+    }
+}
+
+/// URI and content fingerprint of a resource embedded in `message`, if any.
+fn embedded_resource(message: &PromptMessage) -> Option<(String, u64)> {
+    let (uri, body) = match &message.content {
+        Content::Resource { uri, text, .. } => (uri.clone(), text.clone().unwrap_or_default()),
+        Content::Text { text } if message.role == Role::User => {
+            let rest = text.strip_prefix(RESOURCE_CONTENT_PREFIX)?;
+            let (uri, body) = rest.split_once(":\n")?;
+            (uri.to_string(), body.to_string())
+        },
+        _ => return None,
+    };
+
+    let mut hasher = DefaultHasher::new();
+    uri.hash(&mut hasher);
+    body.hash(&mut hasher);
+    Some((uri, hasher.finish()))
+}
+
+fn summarize_tool_result(body: &str) -> String {
+    let shape = match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(map)) => {
+            let keys: Vec<&str> = map.keys().map(String::as_str).take(8).collect();
+            let more = if map.len() > keys.len() { ", ..." } else { "" };
+            format!(
+                "object with {} key{} ({}{})",
+                map.len(),
+                if map.len() == 1 { "" } else { "s" },
+                keys.join(", "),
+                more
+            )
+        },
+        Ok(Value::Array(items)) => format!(
+            "array of {} item{}",
+            items.len(),
+            if items.len() == 1 { "" } else { "s" }
+        ),
+        Ok(Value::String(s)) => format!("string of {} characters", s.chars().count()),
+        Ok(other) => other.to_string(),
+        Err(_) => format!("{} characters of text", body.chars().count()),
+    };
+
+    format!("Tool result omitted ({}, {} bytes).", shape, body.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(message: &PromptMessage) -> &str {
+        match &message.content {
+            Content::Text { text } => text,
+            other => panic!("expected text content, got {:?}", other),
+        }
+    }
+
+    fn tool_result(value: Value) -> PromptMessage {
+        PromptMessage::user(Content::text(format!(
+            "{}{}",
+            TOOL_RESULT_PREFIX,
+            serde_json::to_string_pretty(&value).unwrap()
+        )))
+    }
+
+    fn resource(uri: &str, body: &str) -> PromptMessage {
+        PromptMessage::user(Content::text(format!(
+            "{}{}:\n{}",
+            RESOURCE_CONTENT_PREFIX, uri, body
+        )))
+    }
+
+    #[test]
+    fn drop_intermediate_tool_outputs_keeps_last_result() {
+        let messages = vec![
+            PromptMessage::assistant(Content::text("Calling tool 'a'")),
+            tool_result(serde_json::json!({"rows": [1, 2, 3], "total": 3})),
+            PromptMessage::assistant(Content::text("Calling tool 'b'")),
+            tool_result(serde_json::json!(["x", "y"])),
+        ];
+
+        let compacted = DropIntermediateToolOutputs.compact(messages, None);
+
+        assert_eq!(compacted.len(), 4);
+        assert!(text(&compacted[1])
+            .starts_with("Tool result omitted (object with 2 keys (rows, total)"));
+        assert!(text(&compacted[3]).starts_with(TOOL_RESULT_PREFIX));
+    }
+
+    #[test]
+    fn drop_intermediate_tool_outputs_ignores_single_result() {
+        let messages = vec![tool_result(serde_json::json!({"ok": true}))];
+        let compacted = DropIntermediateToolOutputs.compact(messages, None);
+        assert!(text(&compacted[0]).starts_with(TOOL_RESULT_PREFIX));
+    }
+
+    #[test]
+    fn merge_consecutive_guidance_joins_assistant_runs() {
+        let messages = vec![
+            PromptMessage::user(Content::text("I want to do things.")),
+            PromptMessage::assistant(Content::text("Here's my plan")),
+            PromptMessage::assistant(Content::text("First, look around")),
+            PromptMessage::user(Content::text("Tool result:\n{}")),
+            PromptMessage::assistant(Content::text("Then summarize")),
+        ];
+
+        let compacted = MergeConsecutiveGuidance.compact(messages, None);
+
+        assert_eq!(compacted.len(), 4);
+        assert_eq!(text(&compacted[1]), "Here's my plan\n\nFirst, look around");
+        assert_eq!(text(&compacted[3]), "Then summarize");
+    }
+
+    #[test]
+    fn dedupe_resources_across_session_invocations() {
+        let dedupe = DedupeSessionResources::new();
+
+        let first = dedupe.compact(vec![resource("docs://guide", "body")], Some("s1"));
+        assert!(text(&first[0]).starts_with(RESOURCE_CONTENT_PREFIX));
+
+        let second = dedupe.compact(
+            vec![
+                resource("docs://guide", "body"),
+                resource("docs://guide", "changed body"),
+            ],
+            Some("s1"),
+        );
+        assert_eq!(
+            text(&second[0]),
+            "Resource docs://guide was already provided earlier in this session."
+        );
+        assert!(text(&second[1]).starts_with(RESOURCE_CONTENT_PREFIX));
+
+        // Other sessions are unaffected
+        let other = dedupe.compact(vec![resource("docs://guide", "body")], Some("s2"));
+        assert!(text(&other[0]).starts_with(RESOURCE_CONTENT_PREFIX));
+    }
+
+    #[test]
+    fn dedupe_resources_without_session_is_per_trace() {
+        let dedupe = DedupeSessionResources::new();
+        let messages = vec![
+            PromptMessage::user(Content::resource_with_text("ui://w", "<html>", "text/html")),
+            PromptMessage::user(Content::resource_with_text("ui://w", "<html>", "text/html")),
+        ];
+
+        let compacted = dedupe.compact(messages.clone(), None);
+        assert!(matches!(compacted[0].content, Content::Resource { .. }));
+        assert!(text(&compacted[1]).contains("already provided"));
+
+        // Nothing is remembered between traces without a session
+        let again = dedupe.compact(messages, None);
+        assert!(matches!(again[0].content, Content::Resource { .. }));
+    }
+
+    #[test]
+    fn dedupe_resources_evicts_oldest_session() {
+        let dedupe = DedupeSessionResources::with_capacity(1);
+        dedupe.compact(vec![resource("docs://a", "x")], Some("s1"));
+        dedupe.compact(vec![resource("docs://a", "x")], Some("s2"));
+
+        let again = dedupe.compact(vec![resource("docs://a", "x")], Some("s1"));
+        assert!(text(&again[0]).starts_with(RESOURCE_CONTENT_PREFIX));
+    }
+}
@@ -0,0 +1,302 @@
+//! Conditions over bound variables for workflow branching and loops
+//!
+//! A [`Condition`] is evaluated server-side against the workflow's prompt
+//! arguments and step bindings. Steps use it as a guard
+//! ([`WorkflowStep::branch_if`](super::WorkflowStep::branch_if)) or as the
+//! exit condition of a loop
+//! ([`WorkflowStep::repeat_until`](super::WorkflowStep::repeat_until)).
+//!
+//! # Example
+//!
+//! ```
+//! use pmcp::server::workflow::dsl::{field, prompt_arg};
+//! use pmcp::server::workflow::Condition;
+//! use serde_json::json;
+//!
+//! let finished = Condition::equals(field("job", "state"), json!("done"));
+//! let urgent = Condition::truthy(prompt_arg("urgent"))
+//!     .or(Condition::greater_than(field("ticket", "priority"), 2.0));
+//! let not_finished = !finished;
+//! ```
+
+use super::data_source::DataSource;
+use super::newtypes::BindingName;
+use serde_json::Value;
+use std::fmt;
+
+/// Boolean expression over prompt arguments and step bindings
+///
+/// Values that cannot be resolved (an unbound step, a missing field or an
+/// omitted optional argument) make every comparison false, so a condition
+/// never errors at execution time.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Condition {
+    /// The value resolves
+    Exists(DataSource),
+    /// The value resolves and is not `null`, `false`, `0`, `""`, `[]` or `{}`
+    Truthy(DataSource),
+    /// The value equals the given JSON value (numbers compare numerically)
+    Equals(DataSource, Value),
+    /// The value is a number (or numeric string) greater than the bound
+    GreaterThan(DataSource, f64),
+    /// The value is a number (or numeric string) less than the bound
+    LessThan(DataSource, f64),
+    /// Negation
+    Not(Box<Condition>),
+    /// All conditions hold
+    All(Vec<Condition>),
+    /// At least one condition holds
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// The value resolves
+    pub fn exists(source: DataSource) -> Self {
+        Self::Exists(source)
+    }
+
+    /// The value resolves and is truthy
+    pub fn truthy(source: DataSource) -> Self {
+        Self::Truthy(source)
+    }
+
+    /// The value equals `value`
+    pub fn equals(source: DataSource, value: Value) -> Self {
+        Self::Equals(source, value)
+    }
+
+    /// The value is greater than `bound`
+    pub fn greater_than(source: DataSource, bound: f64) -> Self {
+        Self::GreaterThan(source, bound)
+    }
+
+    /// The value is less than `bound`
+    pub fn less_than(source: DataSource, bound: f64) -> Self {
+        Self::LessThan(source, bound)
+    }
+
+    /// Both this condition and `other` hold
+    #[must_use]
+    pub fn and(self, other: Condition) -> Self {
+        match self {
+            Self::All(mut all) => {
+                all.push(other);
+                Self::All(all)
+            },
+            first => Self::All(vec![first, other]),
+        }
+    }
+
+    /// This condition or `other` holds
+    #[must_use]
+    pub fn or(self, other: Condition) -> Self {
+        match self {
+            Self::Any(mut any) => {
+                any.push(other);
+                Self::Any(any)
+            },
+            first => Self::Any(vec![first, other]),
+        }
+    }
+
+    /// Evaluate the condition, resolving data sources with `resolve`
+    ///
+    /// `resolve` returns `None` for values that are not available.
+    pub fn evaluate<F>(&self, resolve: &F) -> bool
+    where
+        F: Fn(&DataSource) -> Option<Value>,
+    {
+        match self {
+            Self::Exists(source) => resolve(source).is_some(),
+            Self::Truthy(source) => resolve(source).is_some_and(|v| is_truthy(&v)),
+            Self::Equals(source, expected) => {
+                resolve(source).is_some_and(|v| values_equal(&v, expected))
+            },
+            Self::GreaterThan(source, bound) => resolve(source)
+                .and_then(|v| as_number(&v))
+                .is_some_and(|n| n > *bound),
+            Self::LessThan(source, bound) => resolve(source)
+                .and_then(|v| as_number(&v))
+                .is_some_and(|n| n < *bound),
+            Self::Not(inner) => !inner.evaluate(resolve),
+            Self::All(all) => all.iter().all(|c| c.evaluate(resolve)),
+            Self::Any(any) => any.iter().any(|c| c.evaluate(resolve)),
+        }
+    }
+
+    /// All data sources referenced by the condition
+    pub fn data_sources(&self) -> Vec<&DataSource> {
+        match self {
+            Self::Exists(source)
+            | Self::Truthy(source)
+            | Self::Equals(source, _)
+            | Self::GreaterThan(source, _)
+            | Self::LessThan(source, _) => vec![source],
+            Self::Not(inner) => inner.data_sources(),
+            Self::All(all) | Self::Any(all) => {
+                all.iter().flat_map(Condition::data_sources).collect()
+            },
+        }
+    }
+
+    /// Bindings referenced by the condition
+    pub(crate) fn bindings(&self) -> impl Iterator<Item = BindingName> + '_ {
+        self.data_sources()
+            .into_iter()
+            .filter_map(|source| match source {
+                DataSource::StepOutput { step, .. } => Some(BindingName::new(step.as_str())),
+                _ => None,
+            })
+    }
+}
+
+impl std::ops::Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Not(inner) => *inner,
+            other => Self::Not(Box::new(other)),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, all: &[Condition], op: &str| {
+            write!(f, "(")?;
+            for (idx, condition) in all.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, " {} ", op)?;
+                }
+                write!(f, "{}", condition)?;
+            }
+            write!(f, ")")
+        };
+        match self {
+            Self::Exists(source) => write!(f, "{} is set", SourceDisplay(source)),
+            Self::Truthy(source) => write!(f, "{}", SourceDisplay(source)),
+            Self::Equals(source, value) => write!(f, "{} == {}", SourceDisplay(source), value),
+            Self::GreaterThan(source, bound) => write!(f, "{} > {}", SourceDisplay(source), bound),
+            Self::LessThan(source, bound) => write!(f, "{} < {}", SourceDisplay(source), bound),
+            Self::Not(inner) => write!(f, "not {}", inner),
+            Self::All(all) => join(f, all, "and"),
+            Self::Any(any) => join(f, any, "or"),
+        }
+    }
+}
+
+/// Renders a data source the way it reads in a condition
+struct SourceDisplay<'a>(&'a DataSource);
+
+impl fmt::Display for SourceDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            DataSource::PromptArg(name) => write!(f, "{{{}}}", name),
+            DataSource::StepOutput { step, field: None } => write!(f, "{}", step),
+            DataSource::StepOutput {
+                step,
+                field: Some(field),
+            } => write!(f, "{}.{}", step, field),
+            DataSource::Constant(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::workflow::dsl::{constant, field, prompt_arg};
+    use serde_json::json;
+
+    fn resolve(source: &DataSource) -> Option<Value> {
+        let bindings = json!({ "job": { "state": "done", "attempts": 3, "errors": [] } });
+        match source {
+            DataSource::PromptArg(name) if name.as_str() == "limit" => Some(json!("5")),
+            DataSource::PromptArg(_) => None,
+            DataSource::StepOutput { step, field } => {
+                let value = bindings.get(step.as_str())?;
+                match field {
+                    Some(field) => value.get(field).cloned(),
+                    None => Some(value.clone()),
+                }
+            },
+            DataSource::Constant(value) => Some(value.clone()),
+        }
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(Condition::exists(field("job", "state")).evaluate(&resolve));
+        assert!(!Condition::exists(field("job", "missing")).evaluate(&resolve));
+        assert!(Condition::equals(field("job", "state"), json!("done")).evaluate(&resolve));
+        assert!(Condition::equals(field("job", "attempts"), json!(3.0)).evaluate(&resolve));
+        assert!(Condition::greater_than(field("job", "attempts"), 2.0).evaluate(&resolve));
+        assert!(Condition::less_than(prompt_arg("limit"), 10.0).evaluate(&resolve));
+        assert!(!Condition::less_than(prompt_arg("absent"), 10.0).evaluate(&resolve));
+    }
+
+    #[test]
+    fn test_truthiness() {
+        assert!(Condition::truthy(field("job", "state")).evaluate(&resolve));
+        assert!(!Condition::truthy(field("job", "errors")).evaluate(&resolve));
+        assert!(!Condition::truthy(constant(json!(0))).evaluate(&resolve));
+        assert!(!Condition::truthy(prompt_arg("absent")).evaluate(&resolve));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let done = Condition::equals(field("job", "state"), json!("done"));
+        let failing = Condition::truthy(field("job", "errors"));
+
+        assert!(done.clone().and(!failing.clone()).evaluate(&resolve));
+        assert!(!done.clone().and(failing.clone()).evaluate(&resolve));
+        assert!(failing.clone().or(done.clone()).evaluate(&resolve));
+        assert_eq!(!!done.clone(), done);
+        assert_eq!(
+            done.clone().and(failing.clone()).and(done.clone()),
+            Condition::All(vec![done.clone(), failing.clone(), done])
+        );
+    }
+
+    #[test]
+    fn test_display_and_bindings() {
+        let condition = Condition::equals(field("job", "state"), json!("done"))
+            .or(!Condition::exists(prompt_arg("force")));
+        assert_eq!(
+            condition.to_string(),
+            r#"(job.state == "done" or not {force} is set)"#
+        );
+        assert_eq!(
+            condition.bindings().collect::<Vec<_>>(),
+            vec![BindingName::new("job")]
+        );
+    }
+}
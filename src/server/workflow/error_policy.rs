@@ -0,0 +1,167 @@
+//! Retry and error-handling policies for workflow steps
+//!
+//! A step's [`RetryPolicy`] decides whether a failed tool call is attempted
+//! again and how long to wait first; its [`OnError`] handler decides what
+//! happens once the attempts are used up. Every attempt is recorded in the
+//! execution trace.
+//!
+//! # Example
+//!
+//! ```
+//! use pmcp::server::workflow::{OnError, RetryPolicy, ToolHandle, WorkflowStep};
+//! use pmcp::ErrorCode;
+//! use std::time::Duration;
+//!
+//! let step = WorkflowStep::new("fetch", ToolHandle::new("fetch_prices"))
+//!     .retry(
+//!         RetryPolicy::exponential(3, Duration::from_millis(200), Duration::from_secs(2))
+//!             .retry_on(ErrorCode::REQUEST_TIMEOUT),
+//!     )
+//!     .on_error(OnError::fallback(
+//!         WorkflowStep::new("cached", ToolHandle::new("cached_prices")),
+//!     ))
+//!     .bind("prices");
+//! ```
+
+use super::workflow_step::WorkflowStep;
+use crate::error::{Error, ErrorCode};
+use std::time::Duration;
+
+/// How often a failed step is attempted and how long to wait in between
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    retry_on: Vec<ErrorCode>,
+}
+
+impl RetryPolicy {
+    /// Attempt the step up to `max_attempts` times (including the first) without waiting
+    pub fn new(max_attempts: u32) -> Self {
+        Self::fixed(max_attempts, Duration::ZERO)
+    }
+
+    /// Attempt the step up to `max_attempts` times, waiting `delay` between attempts
+    pub fn fixed(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay: delay,
+            max_delay: delay,
+            multiplier: 1.0,
+            retry_on: Vec::new(),
+        }
+    }
+
+    /// Attempt the step up to `max_attempts` times, doubling the wait from
+    /// `initial_delay` up to `max_delay`
+    pub fn exponential(max_attempts: u32, initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay,
+            max_delay,
+            multiplier: 2.0,
+            retry_on: Vec::new(),
+        }
+    }
+
+    /// Only retry errors with this code (chainable)
+    ///
+    /// Without any codes every error is retried. Errors without a JSON-RPC
+    /// code count as [`ErrorCode::INTERNAL_ERROR`].
+    #[must_use]
+    pub fn retry_on(mut self, code: ErrorCode) -> Self {
+        self.retry_on.push(code);
+        self
+    }
+
+    /// Maximum number of attempts, including the first
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Error codes that are retried; empty means all
+    pub fn retryable_codes(&self) -> &[ErrorCode] {
+        &self.retry_on
+    }
+
+    /// Whether `error` from attempt number `attempt` (starting at 1) is retried
+    pub fn should_retry(&self, error: &Error, attempt: u32) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+        let code = error.error_code().unwrap_or(ErrorCode::INTERNAL_ERROR);
+        self.retry_on.is_empty() || self.retry_on.contains(&code)
+    }
+
+    /// Wait before the attempt following attempt number `attempt`
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        Duration::try_from_secs_f64(delay)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// What a workflow does when a step fails after its retries
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub enum OnError {
+    /// Stop server-side execution and hand off to the client (the default)
+    #[default]
+    Abort,
+    /// Record the error and continue with the next step; the step's binding stays unset
+    Skip,
+    /// Run another tool step in its place
+    ///
+    /// The fallback's result is bound to the fallback's binding, or to the
+    /// failed step's binding if the fallback has none, so later steps can
+    /// use it either way. If the fallback fails too, execution stops.
+    Fallback(Box<WorkflowStep>),
+}
+
+impl OnError {
+    /// Run `step` when the step fails
+    pub fn fallback(step: WorkflowStep) -> Self {
+        Self::Fallback(Box::new(step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_retry_respects_attempts_and_codes() {
+        let policy = RetryPolicy::new(3);
+        let error = Error::internal("boom");
+        assert!(policy.should_retry(&error, 1));
+        assert!(policy.should_retry(&error, 2));
+        assert!(!policy.should_retry(&error, 3));
+
+        let timeouts_only = RetryPolicy::new(3).retry_on(ErrorCode::REQUEST_TIMEOUT);
+        assert!(timeouts_only.should_retry(&Error::timeout(100), 1));
+        assert!(!timeouts_only.should_retry(&error, 1));
+
+        let internal = RetryPolicy::new(2).retry_on(ErrorCode::INTERNAL_ERROR);
+        assert!(internal.should_retry(&error, 1));
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        let fixed = RetryPolicy::fixed(3, Duration::from_millis(100));
+        assert_eq!(fixed.delay_after(1), Duration::from_millis(100));
+        assert_eq!(fixed.delay_after(2), Duration::from_millis(100));
+
+        let exponential =
+            RetryPolicy::exponential(5, Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(exponential.delay_after(1), Duration::from_millis(100));
+        assert_eq!(exponential.delay_after(2), Duration::from_millis(200));
+        assert_eq!(exponential.delay_after(3), Duration::from_millis(350));
+        assert_eq!(exponential.delay_after(40), Duration::from_millis(350));
+
+        assert_eq!(RetryPolicy::new(2).delay_after(1), Duration::ZERO);
+    }
+}
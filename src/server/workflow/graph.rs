@@ -0,0 +1,639 @@
+//! Step graphs of workflows, exported as Mermaid or Graphviz DOT.
+//!
+//! [`SequentialWorkflow::graph`] describes a workflow as a [`WorkflowGraph`]:
+//! its steps in execution order, the tools they call, where each tool
+//! argument comes from, and the resources each step reads. The graph is
+//! serializable, and workflow prompts publish it in their `prompts/list`
+//! metadata under [`WORKFLOW_META_KEY`], which is how
+//! `cargo pmcp schema workflows` renders the workflows of a running server.
+//!
+//! In the rendered diagrams solid arrows are execution order, dotted arrows
+//! are data flow labelled with the receiving argument, and dashed arrows
+//! lead to error fallbacks.
+//!
+//! # Example
+//!
+//! ```
+//! use pmcp::server::workflow::{SequentialWorkflow, ToolHandle, WorkflowStep};
+//! use pmcp::server::workflow::dsl::{from_step, prompt_arg};
+//!
+//! let workflow = SequentialWorkflow::new("review", "Review a pull request")
+//!     .argument("pr", "Pull request number", true)
+//!     .step(
+//!         WorkflowStep::new("fetch", ToolHandle::new("get_diff"))
+//!             .arg("number", prompt_arg("pr"))
+//!             .bind("diff"),
+//!     )
+//!     .step(WorkflowStep::new("lint", ToolHandle::new("lint")).arg("patch", from_step("diff")));
+//!
+//! let mermaid = workflow.to_mermaid();
+//! assert!(mermaid.starts_with("flowchart TD"));
+//! assert!(mermaid.contains("s0 -.->|patch| s1"));
+//! ```
+
+use super::{
+    data_source::DataSource, error_policy::OnError, sequential::SequentialWorkflow,
+    workflow_step::WorkflowStep,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// `_meta` key under which workflow prompts publish their [`WorkflowGraph`].
+pub const WORKFLOW_META_KEY: &str = "pmcp/workflow";
+
+/// Text format of an exported workflow graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// A Mermaid `flowchart`, rendered by GitHub and most documentation sites
+    Mermaid,
+    /// A Graphviz `digraph`
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mermaid" => Ok(Self::Mermaid),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            other => Err(format!(
+                "Unknown graph format '{}', expected 'mermaid' or 'dot'",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mermaid => "mermaid",
+            Self::Dot => "dot",
+        })
+    }
+}
+
+/// The step graph of a workflow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowGraph {
+    /// Workflow (prompt) name
+    pub name: String,
+    /// Workflow description
+    pub description: String,
+    /// Prompt argument names
+    pub arguments: Vec<String>,
+    /// Steps in execution order
+    pub steps: Vec<GraphStep>,
+}
+
+/// One step of a [`WorkflowGraph`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphStep {
+    /// Step name
+    pub name: String,
+    /// Tool called by the step, as `server/tool` for foundation tools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Binding the step's output is stored under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binding: Option<String>,
+    /// Tool arguments and template variables, with where their values come from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<GraphInput>,
+    /// URIs of the resources the step reads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+    /// Indices of the steps that run immediately before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<usize>,
+    /// Condition under which the step runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_if: Option<String>,
+    /// Condition that ends the step's repetition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_until: Option<String>,
+    /// Step run in place of this one if it fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<Box<GraphStep>>,
+}
+
+/// A tool argument or template variable of a [`GraphStep`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphInput {
+    /// Argument or template variable name
+    pub name: String,
+    /// Where the value comes from
+    pub source: GraphSource,
+}
+
+/// Origin of a [`GraphInput`] value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum GraphSource {
+    /// A prompt argument
+    PromptArg {
+        /// Argument name
+        name: String,
+    },
+    /// The output of an earlier step
+    StepOutput {
+        /// Binding of the producing step
+        binding: String,
+        /// Field extracted from the output
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+    /// A constant
+    Constant {
+        /// The value
+        value: Value,
+    },
+}
+
+impl From<&DataSource> for GraphSource {
+    fn from(source: &DataSource) -> Self {
+        match source {
+            DataSource::PromptArg(name) => Self::PromptArg {
+                name: name.to_string(),
+            },
+            DataSource::StepOutput { step, field } => Self::StepOutput {
+                binding: step.to_string(),
+                field: field.clone(),
+            },
+            DataSource::Constant(value) => Self::Constant {
+                value: value.clone(),
+            },
+        }
+    }
+}
+
+impl GraphStep {
+    fn from_step(step: &WorkflowStep, after: Vec<usize>) -> Self {
+        let mut template_vars: Vec<_> = step.template_bindings().iter().collect();
+        template_vars.sort_by(|a, b| a.0.cmp(b.0));
+        let inputs = step
+            .arguments()
+            .iter()
+            .map(|(name, source)| (name.to_string(), source))
+            .chain(template_vars.into_iter().map(|(name, s)| (name.clone(), s)))
+            .map(|(name, source)| GraphInput {
+                name,
+                source: source.into(),
+            })
+            .collect();
+        Self {
+            name: step.name().to_string(),
+            tool: step.tool().map(ToString::to_string),
+            binding: step.binding().map(ToString::to_string),
+            inputs,
+            resources: step
+                .resources()
+                .iter()
+                .map(|r| r.uri().to_string())
+                .collect(),
+            after,
+            run_if: step.branch_condition().map(ToString::to_string),
+            repeat_until: step.repeat_condition().map(ToString::to_string),
+            fallback: match step.error_handler() {
+                OnError::Fallback(fallback) => {
+                    Some(Box::new(Self::from_step(fallback, Vec::new())))
+                },
+                _ => None,
+            },
+        }
+    }
+
+    fn label(&self) -> String {
+        let mut label = self.name.clone();
+        if let Some(tool) = &self.tool {
+            let _ = write!(label, "\ntool: {}", tool);
+        }
+        for input in &self.inputs {
+            if let GraphSource::Constant { value } = &input.source {
+                let _ = write!(label, "\n{} = {}", input.name, value);
+            }
+        }
+        if let Some(binding) = &self.binding {
+            let _ = write!(label, "\n→ {}", binding);
+        }
+        label
+    }
+}
+
+impl SequentialWorkflow {
+    /// Describe the workflow's steps, bindings and resources as a graph.
+    pub fn graph(&self) -> WorkflowGraph {
+        let steps = self.steps();
+        let graph_steps = (0..steps.len())
+            .map(|index| {
+                let after = match self.parallel_block(index) {
+                    Some(block) => self.predecessors(block.start),
+                    None => self.predecessors(index),
+                };
+                GraphStep::from_step(&steps[index], after)
+            })
+            .collect();
+        WorkflowGraph {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            arguments: self.arguments().keys().map(ToString::to_string).collect(),
+            steps: graph_steps,
+        }
+    }
+
+    /// Render the step graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        self.graph().to_mermaid()
+    }
+
+    /// Render the step graph as a Graphviz DOT digraph.
+    pub fn to_dot(&self) -> String {
+        self.graph().to_dot()
+    }
+
+    /// The steps that run immediately before the step at `index`: the
+    /// previous step, or every step of the parallel block ending there.
+    fn predecessors(&self, index: usize) -> Vec<usize> {
+        let Some(previous) = index.checked_sub(1) else {
+            return Vec::new();
+        };
+        match self.parallel_block(previous) {
+            Some(block) => block.collect(),
+            None => vec![previous],
+        }
+    }
+}
+
+/// A node or edge in renderer-neutral form.
+enum Element {
+    Node {
+        id: String,
+        label: String,
+        shape: Shape,
+    },
+    Edge {
+        from: String,
+        to: String,
+        label: Option<String>,
+        style: EdgeStyle,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Shape {
+    Step,
+    Argument,
+    Resource,
+}
+
+#[derive(Clone, Copy)]
+enum EdgeStyle {
+    Flow,
+    Data,
+    Fallback,
+}
+
+impl WorkflowGraph {
+    /// Render the graph in `format`.
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    /// Render the graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let escape = |s: &str| {
+            s.replace('"', "#quot;")
+                .replace('|', "#124;")
+                .replace('\n', "<br/>")
+        };
+        let mut out = String::from("flowchart TD\n");
+        for element in self.elements() {
+            let _ = match element {
+                Element::Node { id, label, shape } => {
+                    let (open, close) = match shape {
+                        Shape::Step => ("[\"", "\"]"),
+                        Shape::Argument => ("[/\"", "\"/]"),
+                        Shape::Resource => ("[(\"", "\")]"),
+                    };
+                    writeln!(out, "    {}{}{}{}", id, open, escape(&label), close)
+                },
+                Element::Edge {
+                    from,
+                    to,
+                    label,
+                    style,
+                } => {
+                    let arrow = match style {
+                        EdgeStyle::Flow => "-->",
+                        EdgeStyle::Data => "-.->",
+                        EdgeStyle::Fallback => "-. on error .->",
+                    };
+                    match label {
+                        Some(label) => {
+                            writeln!(out, "    {} {}|{}| {}", from, arrow, escape(&label), to)
+                        },
+                        None => writeln!(out, "    {} {} {}", from, arrow, to),
+                    }
+                },
+            };
+        }
+        out
+    }
+
+    /// Render the graph as a Graphviz DOT digraph.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| {
+            format!(
+                "\"{}\"",
+                s.replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n")
+            )
+        };
+        let mut out = format!("digraph {} {{\n", quote(&self.name));
+        out.push_str("    rankdir=TB;\n    node [shape=box];\n");
+        for element in self.elements() {
+            let _ = match element {
+                Element::Node { id, label, shape } => {
+                    let shape = match shape {
+                        Shape::Step => "box",
+                        Shape::Argument => "parallelogram",
+                        Shape::Resource => "cylinder",
+                    };
+                    writeln!(
+                        out,
+                        "    {} [label={}, shape={}];",
+                        id,
+                        quote(&label),
+                        shape
+                    )
+                },
+                Element::Edge {
+                    from,
+                    to,
+                    label,
+                    style,
+                } => {
+                    let mut attrs = Vec::new();
+                    match style {
+                        EdgeStyle::Flow => {},
+                        EdgeStyle::Data => attrs.push("style=dotted".to_string()),
+                        EdgeStyle::Fallback => {
+                            attrs.push("style=dashed".to_string());
+                            attrs.push(format!("label={}", quote("on error")));
+                        },
+                    }
+                    if let Some(label) = label {
+                        attrs.push(format!("label={}", quote(&label)));
+                    }
+                    if attrs.is_empty() {
+                        writeln!(out, "    {} -> {};", from, to)
+                    } else {
+                        writeln!(out, "    {} -> {} [{}];", from, to, attrs.join(", "))
+                    }
+                },
+            };
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let edge = |from: String, to: String, label: Option<String>, style| Element::Edge {
+            from,
+            to,
+            label,
+            style,
+        };
+
+        for (index, name) in self.arguments.iter().enumerate() {
+            nodes.push(Element::Node {
+                id: format!("a{}", index),
+                label: name.clone(),
+                shape: Shape::Argument,
+            });
+        }
+
+        let mut resources: Vec<&str> = Vec::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            let id = format!("s{}", index);
+            nodes.push(Element::Node {
+                id: id.clone(),
+                label: step.label(),
+                shape: Shape::Step,
+            });
+            for &previous in &step.after {
+                let label = step.run_if.as_ref().map(|c| format!("if {}", c));
+                edges.push(edge(
+                    format!("s{}", previous),
+                    id.clone(),
+                    label,
+                    EdgeStyle::Flow,
+                ));
+            }
+            if step.after.is_empty() {
+                if let Some(condition) = &step.run_if {
+                    nodes.push(Element::Node {
+                        id: format!("{}_if", id),
+                        label: format!("if {}", condition),
+                        shape: Shape::Argument,
+                    });
+                    edges.push(edge(
+                        format!("{}_if", id),
+                        id.clone(),
+                        None,
+                        EdgeStyle::Flow,
+                    ));
+                }
+            }
+            if let Some(condition) = &step.repeat_until {
+                let label = Some(format!("until {}", condition));
+                edges.push(edge(id.clone(), id.clone(), label, EdgeStyle::Flow));
+            }
+            self.input_edges(step, &id, index, &mut edges);
+            for uri in &step.resources {
+                let position = resources.iter().position(|r| r == uri).unwrap_or_else(|| {
+                    resources.push(uri);
+                    nodes.push(Element::Node {
+                        id: format!("r{}", resources.len() - 1),
+                        label: uri.clone(),
+                        shape: Shape::Resource,
+                    });
+                    resources.len() - 1
+                });
+                edges.push(edge(
+                    format!("r{}", position),
+                    id.clone(),
+                    None,
+                    EdgeStyle::Data,
+                ));
+            }
+            if let Some(fallback) = &step.fallback {
+                let fallback_id = format!("{}_fallback", id);
+                nodes.push(Element::Node {
+                    id: fallback_id.clone(),
+                    label: fallback.label(),
+                    shape: Shape::Step,
+                });
+                edges.push(edge(
+                    id.clone(),
+                    fallback_id.clone(),
+                    None,
+                    EdgeStyle::Fallback,
+                ));
+                self.input_edges(fallback, &fallback_id, index, &mut edges);
+            }
+        }
+
+        nodes.extend(edges);
+        nodes
+    }
+
+    /// Data edges into `step` from prompt arguments and the steps before `index`.
+    fn input_edges(&self, step: &GraphStep, id: &str, index: usize, edges: &mut Vec<Element>) {
+        for input in &step.inputs {
+            let from = match &input.source {
+                GraphSource::PromptArg { name } => self
+                    .arguments
+                    .iter()
+                    .position(|arg| arg == name)
+                    .map(|arg| format!("a{}", arg)),
+                GraphSource::StepOutput { binding, .. } => self.producer(binding, index),
+                GraphSource::Constant { .. } => None,
+            };
+            if let Some(from) = from {
+                let label = match &input.source {
+                    GraphSource::StepOutput {
+                        field: Some(field), ..
+                    } => format!("{} ← {}", input.name, field),
+                    _ => input.name.clone(),
+                };
+                edges.push(Element::Edge {
+                    from,
+                    to: id.to_string(),
+                    label: Some(label),
+                    style: EdgeStyle::Data,
+                });
+            }
+        }
+    }
+
+    /// The node of the last step before `index` that binds `binding`.
+    fn producer(&self, binding: &str, index: usize) -> Option<String> {
+        self.steps[..index]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, step)| {
+                if step.binding.as_deref() == Some(binding) {
+                    Some(format!("s{}", i))
+                } else {
+                    step.fallback
+                        .as_ref()
+                        .filter(|f| f.binding.as_deref() == Some(binding))
+                        .map(|_| format!("s{}_fallback", i))
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::workflow::dsl::{field, from_step, prompt_arg};
+    use crate::server::workflow::{Condition, ToolHandle};
+    use serde_json::json;
+
+    fn workflow() -> SequentialWorkflow {
+        SequentialWorkflow::new("trip", "Plan a trip")
+            .argument("city", "Destination", true)
+            .parallel([
+                WorkflowStep::new("weather", ToolHandle::new("forecast"))
+                    .arg("city", prompt_arg("city"))
+                    .bind("weather"),
+                WorkflowStep::new("hotels", ToolHandle::remote("travel", "search_hotels"))
+                    .arg("city", prompt_arg("city"))
+                    .arg("limit", DataSource::Constant(json!(3)))
+                    .bind("hotels"),
+            ])
+            .step(
+                WorkflowStep::new("plan", ToolHandle::new("plan_trip"))
+                    .arg("forecast", field("weather", "summary"))
+                    .arg("hotels", from_step("hotels"))
+                    .with_resource("docs://travel/policy")
+                    .unwrap()
+                    .branch_if(Condition::Truthy(from_step("hotels")))
+                    .on_error(OnError::fallback(WorkflowStep::new(
+                        "plan_offline",
+                        ToolHandle::new("offline_plan"),
+                    ))),
+            )
+    }
+
+    #[test]
+    fn graph_orders_parallel_blocks() {
+        let graph = workflow().graph();
+        let after: Vec<_> = graph.steps.iter().map(|s| s.after.clone()).collect();
+        assert_eq!(after, vec![vec![], vec![], vec![0, 1]]);
+        assert_eq!(graph.steps[1].tool.as_deref(), Some("travel/search_hotels"));
+        assert_eq!(graph.steps[2].resources, vec!["docs://travel/policy"]);
+        assert!(graph.steps[2].fallback.is_some());
+    }
+
+    #[test]
+    fn mermaid_shows_flow_data_and_fallbacks() {
+        let mermaid = workflow().to_mermaid();
+        assert!(mermaid.contains("a0[/\"city\"/]"), "{mermaid}");
+        assert!(mermaid.contains("a0 -.->|city| s0"), "{mermaid}");
+        assert!(mermaid.contains("s0 -->|if hotels| s2"), "{mermaid}");
+        assert!(
+            mermaid.contains("s0 -.->|forecast ← summary| s2"),
+            "{mermaid}"
+        );
+        assert!(mermaid.contains("limit = 3"), "{mermaid}");
+        assert!(
+            mermaid.contains("r0[(\"docs://travel/policy\")]"),
+            "{mermaid}"
+        );
+        assert!(
+            mermaid.contains("s2 -. on error .-> s2_fallback"),
+            "{mermaid}"
+        );
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let workflow = SequentialWorkflow::new("say \"hi\"", "Greets").step(
+            WorkflowStep::new("greet", ToolHandle::new("greet"))
+                .arg("text", DataSource::Constant(json!("a\"b"))),
+        );
+        let dot = workflow.to_dot();
+        assert!(dot.starts_with("digraph \"say \\\"hi\\\"\" {"), "{dot}");
+        assert!(
+            dot.contains(r#"label="greet\ntool: greet\ntext = \"a\\\"b\"""#),
+            "{dot}"
+        );
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn graph_round_trips_through_json() {
+        let graph = workflow().graph();
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(json["steps"][0]["inputs"][0]["source"]["kind"], "promptArg");
+        let back: WorkflowGraph = serde_json::from_value(json).unwrap();
+        assert_eq!(back, graph);
+        assert_eq!("DOT".parse::<GraphFormat>(), Ok(GraphFormat::Dot));
+    }
+}
@@ -6,9 +6,14 @@ use super::{error::WorkflowError, newtypes::Uri};
 use std::sync::Arc;
 
 /// Type-safe identifier for a tool
+///
+/// A handle names either a tool registered on this server or, with
+/// [`ToolHandle::remote`], a tool on a foundation server that is called
+/// through the server's [`FoundationClient`](crate::composition::FoundationClient).
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ToolHandle {
     name: Arc<str>,
+    server: Option<Arc<str>>,
 }
 
 impl ToolHandle {
@@ -16,6 +21,27 @@ impl ToolHandle {
     pub fn new(name: impl AsRef<str>) -> Self {
         Self {
             name: Arc::from(name.as_ref()),
+            server: None,
+        }
+    }
+
+    /// Create a handle for a tool on a foundation server
+    ///
+    /// `server` is the foundation server ID, as configured for the
+    /// `FoundationClient` (e.g. in `foundations.toml`).
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::ToolHandle;
+    ///
+    /// let add = ToolHandle::remote("calculator", "add");
+    /// assert_eq!(add.server(), Some("calculator"));
+    /// assert_eq!(add.name(), "add");
+    /// ```
+    pub fn remote(server: impl AsRef<str>, name: impl AsRef<str>) -> Self {
+        Self {
+            name: Arc::from(name.as_ref()),
+            server: Some(Arc::from(server.as_ref())),
         }
     }
 
@@ -23,11 +49,24 @@ impl ToolHandle {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Get the foundation server ID, if the tool is remote
+    pub fn server(&self) -> Option<&str> {
+        self.server.as_deref()
+    }
+
+    /// Whether the tool lives on a foundation server
+    pub fn is_remote(&self) -> bool {
+        self.server.is_some()
+    }
 }
 
 impl std::fmt::Display for ToolHandle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name)
+        match &self.server {
+            Some(server) => write!(f, "{}/{}", server, self.name),
+            None => write!(f, "{}", self.name),
+        }
     }
 }
 
@@ -127,6 +166,15 @@ mod tests {
         assert_eq!(format!("{}", handle), "greet");
     }
 
+    #[test]
+    fn test_remote_tool_handle() {
+        let handle = ToolHandle::remote("calculator", "add");
+        assert!(handle.is_remote());
+        assert_eq!(handle.server(), Some("calculator"));
+        assert_eq!(handle.to_string(), "calculator/add");
+        assert_ne!(handle, ToolHandle::new("add"));
+    }
+
     #[test]
     fn test_tool_handle_from_string() {
         let handle: ToolHandle = String::from("greet").into();
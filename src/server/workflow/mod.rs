@@ -25,10 +25,14 @@
 //! let message = InternalPromptMessage::new(Role::Assistant, tool);
 //! ```
 
+pub mod compaction;
+pub mod condition;
 pub mod conversion;
 pub mod data_source;
 pub mod dsl;
 pub mod error;
+pub mod error_policy;
+pub mod graph;
 pub mod handles;
 pub mod into_prompt_content;
 pub mod newtypes;
@@ -36,12 +40,17 @@ pub mod prompt_content;
 pub mod prompt_handler;
 pub mod sequential;
 pub mod task_prompt_handler;
+pub mod tool_schema;
 pub mod workflow_step;
 
 // Re-export commonly used types
+pub use compaction::TraceCompactor;
+pub use condition::Condition;
 pub use conversion::{ExpansionContext, ResourceInfo, ToolInfo};
 pub use data_source::DataSource;
 pub use error::WorkflowError;
+pub use error_policy::{OnError, RetryPolicy};
+pub use graph::{GraphFormat, WorkflowGraph};
 pub use handles::{ResourceHandle, ToolHandle};
 pub use into_prompt_content::IntoPromptContent;
 pub use newtypes::{ArgName, BindingName, StepName, Uri};
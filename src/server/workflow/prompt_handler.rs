@@ -23,7 +23,8 @@
 //! - Data flow via bindings between steps
 
 use super::{
-    conversion::ToolInfo, data_source::DataSource, newtypes::BindingName,
+    compaction, condition::Condition, conversion::ToolInfo, data_source::DataSource,
+    error::WorkflowError, error_policy::OnError, newtypes::BindingName,
     sequential::SequentialWorkflow, workflow_step::WorkflowStep,
 };
 use crate::error::Result;
@@ -36,6 +37,7 @@ use crate::types::{Content, GetPromptResult, PromptArgument, PromptInfo, PromptM
 use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::Arc;
 
 /// Stores step execution results (bindings) during workflow execution
@@ -60,6 +62,46 @@ impl ExecutionContext {
     }
 }
 
+/// Outcome of a step's `on_error` handler
+#[derive(Debug)]
+pub(crate) enum Recovery {
+    /// Stop server-side execution
+    Abort,
+    /// Continue without the step's result
+    Skipped,
+    /// A fallback step produced this result in place of the step
+    Recovered(Value),
+}
+
+/// Outcome of one step of a parallel block
+#[derive(Debug)]
+pub(crate) enum BlockOutcome {
+    /// The tool call produced this result
+    Completed(Value),
+    /// The step's branch condition did not hold
+    Skipped,
+    /// The tool call failed with this error and the step was skipped
+    SkippedOnError(crate::Error),
+    /// The tool call failed and a fallback step produced this result
+    Recovered(Value),
+    /// The tool call failed with this error and was not recovered
+    Failed(crate::Error),
+    /// The step's parameters cannot be resolved server-side
+    Unresolved,
+    /// The resolved parameters miss these required fields of the tool's schema
+    Incomplete(Vec<String>),
+}
+
+impl BlockOutcome {
+    /// Whether server-side execution stops after the block
+    pub(crate) fn stops_execution(&self) -> bool {
+        matches!(
+            self,
+            Self::Failed(_) | Self::Unresolved | Self::Incomplete(_)
+        )
+    }
+}
+
 /// `PromptHandler` implementation for `SequentialWorkflow`
 ///
 /// Executes workflow steps server-side during `prompts/get` and returns a conversation trace
@@ -100,6 +142,9 @@ pub struct WorkflowPromptHandler {
     tool_handlers: HashMap<Arc<str>, Arc<dyn ToolHandler>>,
     /// Resource handler for fetching resource content
     resource_handler: Option<Arc<dyn ResourceHandler>>,
+    /// Client for steps on remote tool handles
+    #[cfg(feature = "composition")]
+    foundation: Option<Arc<dyn crate::composition::FoundationClient>>,
 }
 
 impl std::fmt::Debug for WorkflowPromptHandler {
@@ -113,6 +158,7 @@ impl std::fmt::Debug for WorkflowPromptHandler {
                 &self.tool_handlers.keys().collect::<Vec<_>>(),
             )
             .field("resource_handler", &self.resource_handler.is_some())
+            .field("foundation", &self.has_foundation_client())
             .finish()
     }
 }
@@ -138,6 +184,8 @@ impl WorkflowPromptHandler {
             middleware_executor: None,
             tool_handlers,
             resource_handler,
+            #[cfg(feature = "composition")]
+            foundation: None,
         }
     }
 
@@ -164,6 +212,54 @@ impl WorkflowPromptHandler {
             middleware_executor: Some(middleware_executor),
             tool_handlers: HashMap::new(),
             resource_handler,
+            #[cfg(feature = "composition")]
+            foundation: None,
+        }
+    }
+
+    /// Call tools on [remote handles](super::ToolHandle::remote) through `client`
+    ///
+    /// Remote steps get the resolved arguments like local ones. A JSON text
+    /// result is parsed, so later steps can bind its fields; any other text
+    /// is bound as a string. Remote calls do not run the tool middleware.
+    #[cfg(feature = "composition")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "composition")))]
+    #[must_use]
+    pub fn with_foundation_client(
+        mut self,
+        client: Arc<dyn crate::composition::FoundationClient>,
+    ) -> Self {
+        self.foundation = Some(client);
+        self
+    }
+
+    /// Whether a foundation client is configured for remote steps
+    pub(crate) fn has_foundation_client(&self) -> bool {
+        #[cfg(feature = "composition")]
+        {
+            self.foundation.is_some()
+        }
+        #[cfg(not(feature = "composition"))]
+        {
+            false
+        }
+    }
+
+    /// Fail if the workflow has remote steps but no foundation client to run them
+    pub(crate) fn check_remote_steps(&self) -> std::result::Result<(), WorkflowError> {
+        if self.has_foundation_client() {
+            return Ok(());
+        }
+        match self.workflow.remote_step() {
+            Some(step) => Err(WorkflowError::InvalidMapping {
+                step: step.name().to_string(),
+                reason: format!(
+                    "Tool '{}' is on a foundation server, but no foundation client is configured; \
+                     call .foundation_client() on the builder before registering the workflow",
+                    step.tool().map(ToString::to_string).unwrap_or_default()
+                ),
+            }),
+            None => Ok(()),
         }
     }
 
@@ -402,6 +498,23 @@ impl WorkflowPromptHandler {
         Ok(())
     }
 
+    /// Build the prompt result, applying the workflow's trace compactors
+    pub(crate) fn finish(
+        &self,
+        messages: Vec<PromptMessage>,
+        extra: &RequestHandlerExtra,
+    ) -> GetPromptResult {
+        GetPromptResult {
+            description: Some(self.workflow.description().to_string()),
+            messages: compaction::apply(
+                self.workflow.trace_compactors(),
+                messages,
+                extra.session_id.as_deref(),
+            ),
+            _meta: None,
+        }
+    }
+
     /// Create user intent message from workflow description and arguments
     pub(crate) fn create_user_intent(&self, args: &HashMap<String, String>) -> PromptMessage {
         let description = self.workflow.description();
@@ -432,28 +545,43 @@ impl WorkflowPromptHandler {
         for (idx, step) in self.workflow.steps().iter().enumerate() {
             if let Some(tool_handle) = step.tool() {
                 // Tool execution step
-                let tool_info = self.tools.get(tool_handle.name()).ok_or_else(|| {
-                    crate::Error::Internal(format!(
-                        "Tool '{}' not found in registry",
-                        tool_handle.name()
-                    ))
-                })?;
+                let description = match tool_handle.server() {
+                    Some(server) => format!("Call on foundation server '{}'", server),
+                    None => self
+                        .tools
+                        .get(tool_handle.name())
+                        .ok_or_else(|| {
+                            crate::Error::Internal(format!(
+                                "Tool '{}' not found in registry",
+                                tool_handle.name()
+                            ))
+                        })?
+                        .description
+                        .clone(),
+                };
 
                 plan.push_str(&format!(
-                    "{}. {} - {}\n",
+                    "{}. {} - {}{}{}\n",
                     idx + 1,
                     tool_handle.name(),
-                    tool_info.description
+                    description,
+                    Self::plan_conditions(step),
+                    if self.workflow.parallel_block(idx).is_some() {
+                        " (in parallel)"
+                    } else {
+                        ""
+                    }
                 ));
             } else {
                 // Resource-only step
                 let resource_count = step.resources().len();
                 plan.push_str(&format!(
-                    "{}. {} - Fetch {} resource{}\n",
+                    "{}. {} - Fetch {} resource{}{}\n",
                     idx + 1,
                     step.name(),
                     resource_count,
-                    if resource_count == 1 { "" } else { "s" }
+                    if resource_count == 1 { "" } else { "s" },
+                    Self::plan_conditions(step)
                 ));
             }
         }
@@ -461,6 +589,80 @@ impl WorkflowPromptHandler {
         Ok(PromptMessage::assistant(Content::text(plan)))
     }
 
+    /// Describe a step's branch and loop conditions for the plan message
+    fn plan_conditions(step: &WorkflowStep) -> String {
+        let mut text = String::new();
+        if let Some(condition) = step.branch_condition() {
+            text.push_str(&format!(" (only if {})", condition));
+        }
+        if let Some(condition) = step.repeat_condition() {
+            text.push_str(&format!(
+                " (repeat until {}, at most {} times)",
+                condition,
+                step.iteration_limit()
+            ));
+        }
+        text
+    }
+
+    /// Evaluate a branch or loop condition against prompt arguments and bindings
+    pub(crate) fn condition_holds(
+        &self,
+        condition: &Condition,
+        args: &HashMap<String, String>,
+        ctx: &ExecutionContext,
+    ) -> bool {
+        condition.evaluate(&|source: &DataSource| match source {
+            DataSource::PromptArg(arg_name) => {
+                let value = args.get(arg_name.as_str())?;
+                match self
+                    .workflow
+                    .arguments()
+                    .get(arg_name)
+                    .and_then(|spec| spec.arg_type)
+                {
+                    Some(arg_type) => arg_type.parse_value(value).ok(),
+                    None => Some(Value::String(value.clone())),
+                }
+            },
+            DataSource::Constant(value) => Some(value.clone()),
+            DataSource::StepOutput { step, field } => {
+                let binding_value = ctx.get_binding(step)?;
+                match field {
+                    Some(field_name) => Self::navigate_json_path(binding_value, field_name)
+                        .ok()
+                        .cloned(),
+                    None => Some(binding_value.clone()),
+                }
+            },
+        })
+    }
+
+    /// Whether a step's `repeat_until` condition asks for another iteration
+    ///
+    /// Returns `false` for steps without an exit condition.
+    pub(crate) fn should_repeat(
+        &self,
+        step: &WorkflowStep,
+        args: &HashMap<String, String>,
+        ctx: &ExecutionContext,
+    ) -> bool {
+        step.repeat_condition()
+            .is_some_and(|condition| !self.condition_holds(condition, args, ctx))
+    }
+
+    /// Message recorded when a `repeat_until` step runs out of iterations
+    pub(crate) fn iterations_exhausted_message(step: &WorkflowStep) -> String {
+        format!(
+            "Step '{}' did not reach its exit condition ({}) after {} iteration{}",
+            step.name(),
+            step.repeat_condition()
+                .map_or_else(String::new, ToString::to_string),
+            step.iteration_limit(),
+            if step.iteration_limit() == 1 { "" } else { "s" }
+        )
+    }
+
     /// Create assistant message announcing the tool call with resolved parameters
     pub(crate) fn create_tool_call_announcement(
         &self,
@@ -559,6 +761,11 @@ impl WorkflowPromptHandler {
             ))
         })?;
 
+        // The foundation server validates arguments for remote tools
+        if tool_handle.is_remote() {
+            return Ok(Vec::new());
+        }
+
         // Get the tool info (includes schema)
         let tool_info = self.tools.get(tool_handle.name()).ok_or_else(|| {
             crate::Error::Internal(format!(
@@ -616,6 +823,12 @@ impl WorkflowPromptHandler {
         // Resolve parameters using bindings and arguments
         let params = self.resolve_tool_parameters(step, args, ctx)?;
 
+        if let Some(server) = tool_handle.server() {
+            return self
+                .execute_remote_tool(server, tool_handle.name(), params)
+                .await;
+        }
+
         // Debug: Check auth_context before passing to middleware executor
         tracing::debug!(
             "WorkflowPromptHandler.execute_tool_step() - Before clone: auth_context present: {}, has_token: {}",
@@ -647,6 +860,263 @@ impl WorkflowPromptHandler {
         handler.handle(params, extra.clone()).await
     }
 
+    /// Call a tool on a foundation server through the foundation client
+    #[cfg(feature = "composition")]
+    async fn execute_remote_tool(&self, server: &str, tool: &str, params: Value) -> Result<Value> {
+        let client = self.foundation.as_ref().ok_or_else(|| {
+            crate::Error::validation(format!(
+                "Tool '{}' is on foundation server '{}', but no foundation client is configured",
+                tool, server
+            ))
+        })?;
+        let text = client.call_tool(server, tool, &params).await?;
+        Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+    }
+
+    #[cfg(not(feature = "composition"))]
+    #[allow(clippy::unused_async)]
+    async fn execute_remote_tool(&self, server: &str, tool: &str, _params: Value) -> Result<Value> {
+        Err(crate::Error::validation(format!(
+            "Tool '{}' is on foundation server '{}'; remote workflow steps need the \
+             `composition` feature",
+            tool, server
+        )))
+    }
+
+    /// Execute a tool step, retrying failed attempts per the step's retry policy
+    ///
+    /// Each retried attempt and the wait before the next one are recorded in
+    /// `messages`. The error of the final attempt is returned.
+    pub(crate) async fn execute_with_retry(
+        &self,
+        step: &WorkflowStep,
+        args: &HashMap<String, String>,
+        ctx: &ExecutionContext,
+        extra: &RequestHandlerExtra,
+        messages: &mut Vec<PromptMessage>,
+    ) -> Result<Value> {
+        let mut attempt = 1;
+        loop {
+            let error = match self.execute_tool_step(step, args, ctx, extra).await {
+                Ok(result) => return Ok(result),
+                Err(e) => e,
+            };
+            let Some(policy) = step
+                .retry_policy()
+                .filter(|policy| policy.should_retry(&error, attempt))
+            else {
+                return Err(error);
+            };
+
+            let delay = policy.delay_after(attempt);
+            messages.push(PromptMessage::user(Content::text(format!(
+                "Error executing tool (attempt {} of {}): {}",
+                attempt,
+                policy.max_attempts(),
+                error
+            ))));
+            messages.push(PromptMessage::assistant(Content::text(format!(
+                "Retrying {} in {}ms...",
+                step.name(),
+                delay.as_millis()
+            ))));
+
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {},
+                () = extra.cancellation_token.cancelled() => return Err(error),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Apply the step's `on_error` handler after its final failed attempt
+    ///
+    /// A fallback step's announcement and result are recorded in `messages`,
+    /// and its result is bound to its own binding or, failing that, to the
+    /// failed step's binding.
+    pub(crate) async fn recover(
+        &self,
+        step: &WorkflowStep,
+        args: &HashMap<String, String>,
+        ctx: &mut ExecutionContext,
+        extra: &RequestHandlerExtra,
+        messages: &mut Vec<PromptMessage>,
+    ) -> Recovery {
+        let fallback = match step.error_handler() {
+            OnError::Skip => {
+                messages.push(PromptMessage::assistant(Content::text(format!(
+                    "Continuing without {}.",
+                    step.name()
+                ))));
+                return Recovery::Skipped;
+            },
+            OnError::Fallback(fallback) => fallback,
+            _ => return Recovery::Abort,
+        };
+
+        messages.push(PromptMessage::assistant(Content::text(format!(
+            "Falling back to {} instead of {}.",
+            fallback.name(),
+            step.name()
+        ))));
+        let Ok(announcement) = self.create_tool_call_announcement(fallback, args, ctx) else {
+            return Recovery::Abort;
+        };
+        let complete = self
+            .resolve_tool_parameters(fallback, args, ctx)
+            .and_then(|params| self.params_satisfy_tool_schema(fallback, &params))
+            .is_ok_and(|missing| missing.is_empty());
+        if !complete {
+            return Recovery::Abort;
+        }
+        messages.push(announcement);
+
+        match self
+            .execute_with_retry(fallback, args, ctx, extra, messages)
+            .await
+        {
+            Ok(result) => {
+                messages.push(PromptMessage::user(Content::text(format!(
+                    "Tool result:\n{}",
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|_| format!("{:?}", result))
+                ))));
+                if let Some(binding) = fallback.binding().or(step.binding()) {
+                    ctx.store_binding(binding.clone(), result.clone());
+                }
+                Recovery::Recovered(result)
+            },
+            Err(e) => {
+                messages.push(PromptMessage::user(Content::text(format!(
+                    "Error executing tool: {}",
+                    e
+                ))));
+                Recovery::Abort
+            },
+        }
+    }
+
+    /// Execute the steps of a parallel block concurrently
+    ///
+    /// Branch conditions and parameters are resolved first, against the
+    /// bindings from before the block. The resolvable tool calls then run at
+    /// the same time, each recording its retries in its own trace. Finally,
+    /// in declaration order, every step's messages are appended to `messages`,
+    /// results are bound, and failed steps go through their `on_error`
+    /// handler, so the trace does not depend on which call finished first.
+    pub(crate) async fn execute_parallel_block(
+        &self,
+        block: Range<usize>,
+        args: &HashMap<String, String>,
+        ctx: &mut ExecutionContext,
+        extra: &RequestHandlerExtra,
+        messages: &mut Vec<PromptMessage>,
+    ) -> Vec<BlockOutcome> {
+        let steps = &self.workflow.steps()[block];
+
+        // Messages leading up to each call, and the outcome of steps that don't run
+        let mut leads = Vec::with_capacity(steps.len());
+        let mut outcomes = Vec::with_capacity(steps.len());
+        for step in steps {
+            let mut lead = Vec::new();
+            let outcome = match step.branch_condition() {
+                Some(condition) if !self.condition_holds(condition, args, ctx) => {
+                    lead.push(PromptMessage::assistant(Content::text(format!(
+                        "Skipping {}: condition not met ({}).",
+                        step.name(),
+                        condition
+                    ))));
+                    Some(BlockOutcome::Skipped)
+                },
+                _ => {
+                    if let Some(guidance_template) = step.guidance() {
+                        let guidance_text = Self::substitute_arguments(guidance_template, args);
+                        lead.push(PromptMessage::assistant(Content::text(guidance_text)));
+                    }
+                    let missing = self
+                        .resolve_tool_parameters(step, args, ctx)
+                        .and_then(|params| self.params_satisfy_tool_schema(step, &params));
+                    match (self.create_tool_call_announcement(step, args, ctx), missing) {
+                        (Ok(announcement), Ok(missing)) if missing.is_empty() => {
+                            lead.push(announcement);
+                            None
+                        },
+                        (Ok(_), Ok(missing)) => Some(BlockOutcome::Incomplete(missing)),
+                        _ => Some(BlockOutcome::Unresolved),
+                    }
+                },
+            };
+            leads.push(lead);
+            outcomes.push(outcome);
+        }
+
+        let shared: &ExecutionContext = ctx;
+        let calls = steps
+            .iter()
+            .zip(&outcomes)
+            .map(|(step, outcome)| async move {
+                if outcome.is_some() {
+                    return None;
+                }
+                let mut trace = Vec::new();
+                let result = self
+                    .execute_with_retry(step, args, shared, extra, &mut trace)
+                    .await;
+                Some((result, trace))
+            });
+        let calls = futures::future::join_all(calls).await;
+
+        let mut finished = Vec::with_capacity(steps.len());
+        for (((step, lead), outcome), call) in steps.iter().zip(leads).zip(outcomes).zip(calls) {
+            messages.extend(lead);
+            let outcome = match (outcome, call) {
+                (Some(outcome), _) => outcome,
+                (None, Some((Ok(result), trace))) => {
+                    messages.extend(trace);
+                    messages.push(PromptMessage::user(Content::text(format!(
+                        "Tool result:\n{}",
+                        serde_json::to_string_pretty(&result)
+                            .unwrap_or_else(|_| format!("{:?}", result))
+                    ))));
+                    if let Some(binding) = step.binding() {
+                        ctx.store_binding(binding.clone(), result.clone());
+                    }
+                    BlockOutcome::Completed(result)
+                },
+                (None, Some((Err(e), trace))) => {
+                    messages.extend(trace);
+                    messages.push(PromptMessage::user(Content::text(format!(
+                        "Error executing tool: {}",
+                        e
+                    ))));
+                    match self.recover(step, args, ctx, extra, messages).await {
+                        Recovery::Abort => BlockOutcome::Failed(e),
+                        Recovery::Skipped => BlockOutcome::SkippedOnError(e),
+                        Recovery::Recovered(result) => BlockOutcome::Recovered(result),
+                    }
+                },
+                (None, None) => BlockOutcome::Unresolved,
+            };
+            finished.push(outcome);
+        }
+        finished
+    }
+
+    /// Progress message for a parallel block
+    pub(crate) fn parallel_progress_message(&self, block: Range<usize>) -> String {
+        let names: Vec<&str> = self.workflow.steps()[block.clone()]
+            .iter()
+            .map(|step| step.name().as_str())
+            .collect();
+        format!(
+            "Steps {}-{}/{}: {}",
+            block.start + 1,
+            block.end,
+            self.workflow.steps().len(),
+            names.join(", ")
+        )
+    }
+
     /// Resolve tool parameters from `DataSources` (prompt args, bindings, constants)
     pub(crate) fn resolve_tool_parameters(
         &self,
@@ -776,7 +1246,11 @@ impl PromptHandler for WorkflowPromptHandler {
         // 3️⃣ Execute workflow steps sequentially with progress reporting
         let total_steps = self.workflow.steps().len();
 
-        for (step_index, step) in self.workflow.steps().iter().enumerate() {
+        // Steps are walked by index so `repeat_until` steps can run again
+        let mut step_index = 0;
+        let mut iteration = 1;
+
+        while let Some(step) = self.workflow.steps().get(step_index) {
             // Check for cancellation before each step
             if extra.is_cancelled() {
                 tracing::warn!("Workflow cancelled at step: {}", step.name());
@@ -787,10 +1261,51 @@ impl PromptHandler for WorkflowPromptHandler {
                 )));
             }
 
+            // Run a block of independent steps concurrently
+            if let Some(block) = self.workflow.parallel_block(step_index) {
+                let progress_message = self.parallel_progress_message(block.clone());
+                if let Err(e) = extra
+                    .report_count(block.end, total_steps, Some(progress_message))
+                    .await
+                {
+                    tracing::warn!("Failed to report workflow progress: {}", e);
+                }
+                let outcomes = self
+                    .execute_parallel_block(
+                        block.clone(),
+                        &args,
+                        &mut execution_context,
+                        &extra,
+                        &mut messages,
+                    )
+                    .await;
+                if outcomes.iter().any(BlockOutcome::stops_execution) {
+                    break;
+                }
+                step_index = block.end;
+                continue;
+            }
+
+            // Skip steps whose branch condition does not hold
+            if let Some(condition) = step.branch_condition() {
+                if !self.condition_holds(condition, &args, &execution_context) {
+                    messages.push(PromptMessage::assistant(Content::text(format!(
+                        "Skipping {}: condition not met ({}).",
+                        step.name(),
+                        condition
+                    ))));
+                    step_index += 1;
+                    continue;
+                }
+            }
+
             // Report progress at the start of each step
             // Use the step name for a more descriptive message
-            let progress_message =
+            let mut progress_message =
                 format!("Step {}/{}: {}", step_index + 1, total_steps, step.name());
+            if iteration > 1 {
+                progress_message.push_str(&format!(" (iteration {})", iteration));
+            }
             if let Err(e) = extra
                 .report_count(step_index + 1, total_steps, Some(progress_message))
                 .await
@@ -818,11 +1333,7 @@ impl PromptHandler for WorkflowPromptHandler {
                     .is_err()
                 {
                     // Resource fetch failed - stop execution
-                    return Ok(GetPromptResult {
-                        description: Some(self.workflow.description().to_string()),
-                        messages,
-                        _meta: None,
-                    });
+                    return Ok(self.finish(messages, &extra));
                 }
             }
 
@@ -849,14 +1360,11 @@ impl PromptHandler for WorkflowPromptHandler {
                         .is_err()
                 {
                     // Resource fetch failed - stop execution
-                    return Ok(GetPromptResult {
-                        description: Some(self.workflow.description().to_string()),
-                        messages,
-                        _meta: None,
-                    });
+                    return Ok(self.finish(messages, &extra));
                 }
 
                 // Continue to next step
+                step_index += 1;
                 continue;
             }
 
@@ -887,7 +1395,7 @@ impl PromptHandler for WorkflowPromptHandler {
                     messages.push(announcement);
 
                     match self
-                        .execute_tool_step(step, &args, &execution_context, &extra)
+                        .execute_with_retry(step, &args, &execution_context, &extra, &mut messages)
                         .await
                     {
                         Ok(result) => {
@@ -920,14 +1428,33 @@ impl PromptHandler for WorkflowPromptHandler {
                                 // Resource fetch failed - stop execution
                                 break;
                             }
+
+                            // Run the step again until its exit condition holds
+                            if self.should_repeat(step, &args, &execution_context) {
+                                if iteration < step.iteration_limit() {
+                                    iteration += 1;
+                                    continue;
+                                }
+                                // Out of iterations - hand off to the client LLM
+                                messages.push(PromptMessage::assistant(Content::text(
+                                    Self::iterations_exhausted_message(step),
+                                )));
+                                break;
+                            }
                         },
                         Err(e) => {
-                            // Execution error - STOP with error
                             messages.push(PromptMessage::user(Content::text(format!(
                                 "Error executing tool: {}",
                                 e
                             ))));
-                            break; // Let LLM handle recovery
+                            // Skip or fall back if the step says so, otherwise
+                            // STOP and let the LLM handle recovery
+                            let recovery = self
+                                .recover(step, &args, &mut execution_context, &extra, &mut messages)
+                                .await;
+                            if matches!(recovery, Recovery::Abort) {
+                                break;
+                            }
                         },
                     }
                 },
@@ -939,6 +1466,9 @@ impl PromptHandler for WorkflowPromptHandler {
                     break; // Graceful handoff - return partial trace
                 },
             }
+
+            step_index += 1;
+            iteration = 1;
         }
 
         // Report final workflow completion
@@ -951,11 +1481,7 @@ impl PromptHandler for WorkflowPromptHandler {
             )
             .await;
 
-        Ok(GetPromptResult {
-            description: Some(self.workflow.description().to_string()),
-            messages,
-            _meta: None,
-        })
+        Ok(self.finish(messages, &extra))
     }
 
     fn metadata(&self) -> Option<PromptInfo> {
@@ -987,6 +1513,11 @@ impl PromptHandler for WorkflowPromptHandler {
         if let Some(args) = arguments {
             info = info.with_arguments(args);
         }
+        if let Ok(graph) = serde_json::to_value(self.workflow.graph()) {
+            let mut meta = serde_json::Map::new();
+            meta.insert(super::graph::WORKFLOW_META_KEY.to_string(), graph);
+            info = info.with_meta(meta);
+        }
         Some(info)
     }
 }
@@ -1061,6 +1592,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1101,6 +1640,415 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_trace_compaction_applied_to_result() {
+        use crate::server::workflow::compaction::{
+            DropIntermediateToolOutputs, MergeConsecutiveGuidance,
+        };
+
+        let workflow = SequentialWorkflow::new("count_pages", "count the pages")
+            .step(WorkflowStep::new("list_pages", ToolHandle::new("list_pages")).bind("pages"))
+            .step(
+                WorkflowStep::new("count", ToolHandle::new("count_pages"))
+                    .arg("pages", from_step("pages")),
+            )
+            .compact_trace(DropIntermediateToolOutputs)
+            .compact_trace(MergeConsecutiveGuidance);
+
+        let list_pages_tool = SimpleTool::new("list_pages", |_args, _extra| {
+            Box::pin(async move { Ok(serde_json::json!({"pages": ["Website", "Mobile"]})) })
+        })
+        .with_description("List all pages")
+        .with_schema(serde_json::json!({"type": "object"}));
+        let count_tool = SimpleTool::new("count_pages", |_args, _extra| {
+            Box::pin(async move { Ok(serde_json::json!({"count": 2})) })
+        })
+        .with_description("Count pages")
+        .with_schema(serde_json::json!({"type": "object"}));
+
+        let mut tools = HashMap::new();
+        let mut tool_handlers: HashMap<Arc<str>, Arc<dyn ToolHandler>> = HashMap::new();
+        let pipeline: [Arc<dyn ToolHandler>; 2] = [Arc::new(list_pages_tool), Arc::new(count_tool)];
+        for tool in pipeline {
+            let metadata = tool.metadata().unwrap();
+            tools.insert(
+                Arc::from(metadata.name.as_str()),
+                ToolInfo {
+                    name: metadata.name.clone(),
+                    description: metadata.description.unwrap_or_default(),
+                    input_schema: metadata.input_schema,
+                },
+            );
+            tool_handlers.insert(Arc::from(metadata.name.as_str()), tool);
+        }
+
+        let handler = WorkflowPromptHandler::new(workflow, tools, tool_handlers, None);
+        let result = handler
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .expect("Should execute successfully");
+
+        // intent, plan + first call (merged), summarized first result, second call, raw result
+        let texts: Vec<&str> = result
+            .messages
+            .iter()
+            .map(|m| match &m.content {
+                Content::Text { text } => text.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(texts.len(), 5, "{:#?}", texts);
+        assert!(texts[1].starts_with("Here's my plan"));
+        assert!(texts[1].contains("Calling tool 'list_pages'"));
+        assert!(texts[2].starts_with("Tool result omitted (object with 1 key (pages)"));
+        assert!(texts[3].contains("Calling tool 'count_pages'"));
+        assert!(texts[4].starts_with("Tool result:\n"));
+        assert!(texts[4].contains("\"count\": 2"));
+    }
+
+    /// Handler over a `poll_job` tool that reports "done" from the third call on,
+    /// plus `archive` and `report` tools that echo their step
+    fn polling_handler(workflow: SequentialWorkflow) -> WorkflowPromptHandler {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let poll_tool = SimpleTool::new("poll_job", move |_args, _extra| {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move {
+                let state = if call >= 3 { "done" } else { "running" };
+                Ok(json!({ "state": state, "call": call }))
+            })
+        })
+        .with_schema(json!({"type": "object"}));
+        let archive_tool = SimpleTool::new("archive", |_args, _extra| {
+            Box::pin(async move { Ok(json!({"archived": true})) })
+        })
+        .with_schema(json!({"type": "object"}));
+        let report_tool = SimpleTool::new("report", |_args, _extra| {
+            Box::pin(async move { Ok(json!({"reported": true})) })
+        })
+        .with_schema(json!({"type": "object"}));
+
+        let mut tools = HashMap::new();
+        let mut tool_handlers: HashMap<Arc<str>, Arc<dyn ToolHandler>> = HashMap::new();
+        let pipeline: [Arc<dyn ToolHandler>; 3] = [
+            Arc::new(poll_tool),
+            Arc::new(archive_tool),
+            Arc::new(report_tool),
+        ];
+        for tool in pipeline {
+            let metadata = tool.metadata().unwrap();
+            tools.insert(
+                Arc::from(metadata.name.as_str()),
+                ToolInfo {
+                    name: metadata.name.clone(),
+                    description: metadata.description.unwrap_or_default(),
+                    input_schema: metadata.input_schema,
+                },
+            );
+            tool_handlers.insert(Arc::from(metadata.name.as_str()), tool);
+        }
+
+        WorkflowPromptHandler::new(workflow, tools, tool_handlers, None)
+    }
+
+    fn message_texts(result: &GetPromptResult) -> Vec<&str> {
+        result
+            .messages
+            .iter()
+            .map(|m| match &m.content {
+                Content::Text { text } => text.as_str(),
+                _ => "",
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_repeat_until_and_branch_if() {
+        use crate::server::workflow::dsl::field;
+        use crate::server::workflow::Condition;
+
+        let done = Condition::equals(field("job", "state"), json!("done"));
+        let workflow = SequentialWorkflow::new("finish_job", "finish the job")
+            .step(
+                WorkflowStep::new("poll", ToolHandle::new("poll_job"))
+                    .bind("job")
+                    .repeat_until(done.clone()),
+            )
+            .step(WorkflowStep::new("archive", ToolHandle::new("archive")).branch_if(!done.clone()))
+            .step(WorkflowStep::new("report", ToolHandle::new("report")).branch_if(done));
+        workflow.validate().unwrap();
+
+        let handler = polling_handler(workflow);
+        let result = handler
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+
+        assert!(texts[1].contains("(repeat until job.state == \"done\", at most 5 times)"));
+        assert!(texts[1].contains("(only if not job.state == \"done\")"));
+        let polls = texts
+            .iter()
+            .filter(|t| t.contains("Calling tool 'poll_job'"))
+            .count();
+        assert_eq!(polls, 3, "{:#?}", texts);
+        assert!(texts.iter().any(|t| t.starts_with("Skipping archive")));
+        assert!(!texts.iter().any(|t| t.contains("Calling tool 'archive'")));
+        assert!(texts.iter().any(|t| t.contains("Calling tool 'report'")));
+    }
+
+    #[tokio::test]
+    async fn test_repeat_until_stops_after_max_iterations() {
+        use crate::server::workflow::dsl::field;
+        use crate::server::workflow::Condition;
+
+        let workflow = SequentialWorkflow::new("finish_job", "finish the job")
+            .step(
+                WorkflowStep::new("poll", ToolHandle::new("poll_job"))
+                    .bind("job")
+                    .repeat_until(Condition::equals(field("job", "state"), json!("done")))
+                    .max_iterations(2),
+            )
+            .step(WorkflowStep::new("report", ToolHandle::new("report")));
+
+        let handler = polling_handler(workflow);
+        let result = handler
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+
+        let last = texts.last().unwrap();
+        assert!(
+            last.starts_with("Step 'poll' did not reach its exit condition"),
+            "{:#?}",
+            texts
+        );
+        assert!(last.contains("after 2 iterations"));
+        assert!(!texts.iter().any(|t| t.contains("Calling tool 'report'")));
+    }
+
+    /// Handler over a `fetch` tool that fails its first `failures` calls, a
+    /// `cached` tool, and a `report` tool that echoes its arguments
+    fn flaky_handler(workflow: SequentialWorkflow, failures: usize) -> WorkflowPromptHandler {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fetch_tool = SimpleTool::new("fetch", move |_args, _extra| {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move {
+                if call <= failures {
+                    Err(crate::Error::internal(format!(
+                        "upstream down (call {})",
+                        call
+                    )))
+                } else {
+                    Ok(json!({ "source": "live" }))
+                }
+            })
+        })
+        .with_schema(json!({"type": "object"}));
+        let cached_tool = SimpleTool::new("cached", |_args, _extra| {
+            Box::pin(async move { Ok(json!({ "source": "cache" })) })
+        })
+        .with_schema(json!({"type": "object"}));
+        let report_tool =
+            SimpleTool::new("report", |args, _extra| Box::pin(async move { Ok(args) }))
+                .with_schema(json!({"type": "object"}));
+
+        let mut tools = HashMap::new();
+        let mut tool_handlers: HashMap<Arc<str>, Arc<dyn ToolHandler>> = HashMap::new();
+        let pipeline: [Arc<dyn ToolHandler>; 3] = [
+            Arc::new(fetch_tool),
+            Arc::new(cached_tool),
+            Arc::new(report_tool),
+        ];
+        for tool in pipeline {
+            let metadata = tool.metadata().unwrap();
+            tools.insert(
+                Arc::from(metadata.name.as_str()),
+                ToolInfo {
+                    name: metadata.name.clone(),
+                    description: metadata.description.unwrap_or_default(),
+                    input_schema: metadata.input_schema,
+                },
+            );
+            tool_handlers.insert(Arc::from(metadata.name.as_str()), tool);
+        }
+
+        WorkflowPromptHandler::new(workflow, tools, tool_handlers, None)
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_records_each_attempt() {
+        use crate::server::workflow::RetryPolicy;
+
+        let workflow = SequentialWorkflow::new("sync", "sync prices").step(
+            WorkflowStep::new("fetch", ToolHandle::new("fetch"))
+                .retry(RetryPolicy::fixed(3, std::time::Duration::from_millis(1)))
+                .bind("prices"),
+        );
+        let result = flaky_handler(workflow, 2)
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+
+        assert!(texts.iter().any(|t| t.starts_with(
+            "Error executing tool (attempt 1 of 3): Internal error: upstream down (call 1)"
+        )));
+        assert!(texts
+            .iter()
+            .any(|t| t.starts_with("Error executing tool (attempt 2 of 3)")));
+        assert_eq!(
+            texts
+                .iter()
+                .filter(|t| t.starts_with("Retrying fetch"))
+                .count(),
+            2
+        );
+        assert!(texts.last().unwrap().contains("\"source\": \"live\""));
+
+        // Out of attempts: the last error stops execution
+        let workflow = SequentialWorkflow::new("sync", "sync prices")
+            .step(WorkflowStep::new("fetch", ToolHandle::new("fetch")).retry(RetryPolicy::new(2)));
+        let result = flaky_handler(workflow, 5)
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+        assert_eq!(
+            *texts.last().unwrap(),
+            "Error executing tool: Internal error: upstream down (call 2)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error_skip_and_fallback() {
+        use crate::server::workflow::OnError;
+
+        let workflow = SequentialWorkflow::new("sync", "sync prices")
+            .step(WorkflowStep::new("fetch", ToolHandle::new("fetch")).on_error(OnError::Skip))
+            .step(WorkflowStep::new("report", ToolHandle::new("report")));
+        let result = flaky_handler(workflow, 1)
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+        assert!(texts.contains(&"Continuing without fetch."));
+        assert!(texts.iter().any(|t| t.contains("Calling tool 'report'")));
+
+        let workflow = SequentialWorkflow::new("sync", "sync prices")
+            .step(
+                WorkflowStep::new("fetch", ToolHandle::new("fetch"))
+                    .on_error(OnError::fallback(WorkflowStep::new(
+                        "from_cache",
+                        ToolHandle::new("cached"),
+                    )))
+                    .bind("prices"),
+            )
+            .step(
+                WorkflowStep::new("report", ToolHandle::new("report"))
+                    .arg("prices", from_step("prices")),
+            );
+        workflow.validate().unwrap();
+        let result = flaky_handler(workflow, 1)
+            .handle(HashMap::new(), RequestHandlerExtra::default())
+            .await
+            .unwrap();
+        let texts = message_texts(&result);
+        assert!(texts.contains(&"Falling back to from_cache instead of fetch."));
+        assert!(texts.iter().any(|t| t.contains("Calling tool 'cached'")));
+        // The fallback result is bound to the failed step's binding
+        assert!(texts.last().unwrap().contains("\"source\": \"cache\""));
+        assert!(texts.last().unwrap().contains("\"prices\""));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_block_runs_concurrently_with_ordered_trace() {
+        use crate::server::workflow::dsl::field;
+        use std::time::Duration;
+
+        // Both tools wait for each other, so the block only finishes if they run concurrently
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let slow_barrier = barrier.clone();
+        let slow_tool = SimpleTool::new("forecast", move |_args, _extra| {
+            let barrier = slow_barrier.clone();
+            Box::pin(async move {
+                barrier.wait().await;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(json!({ "summary": "sunny" }))
+            })
+        })
+        .with_schema(json!({"type": "object"}));
+        let fast_tool = SimpleTool::new("search_hotels", move |_args, _extra| {
+            let barrier = barrier.clone();
+            Box::pin(async move {
+                barrier.wait().await;
+                Ok(json!({ "name": "Grand" }))
+            })
+        })
+        .with_schema(json!({"type": "object"}));
+        let plan_tool = SimpleTool::new("plan_trip", |args, _extra| {
+            Box::pin(async move { Ok(args) })
+        })
+        .with_schema(json!({"type": "object"}));
+
+        let mut tools = HashMap::new();
+        let mut tool_handlers: HashMap<Arc<str>, Arc<dyn ToolHandler>> = HashMap::new();
+        let pipeline: [Arc<dyn ToolHandler>; 3] = [
+            Arc::new(slow_tool),
+            Arc::new(fast_tool),
+            Arc::new(plan_tool),
+        ];
+        for tool in pipeline {
+            let metadata = tool.metadata().unwrap();
+            tools.insert(
+                Arc::from(metadata.name.as_str()),
+                ToolInfo {
+                    name: metadata.name.clone(),
+                    description: metadata.description.unwrap_or_default(),
+                    input_schema: metadata.input_schema,
+                },
+            );
+            tool_handlers.insert(Arc::from(metadata.name.as_str()), tool);
+        }
+
+        let workflow = SequentialWorkflow::new("trip", "plan a trip")
+            .parallel([
+                WorkflowStep::new("weather", ToolHandle::new("forecast")).bind("weather"),
+                WorkflowStep::new("hotels", ToolHandle::new("search_hotels")).bind("hotels"),
+            ])
+            .step(
+                WorkflowStep::new("plan", ToolHandle::new("plan_trip"))
+                    .arg("forecast", field("weather", "summary"))
+                    .arg("hotel", field("hotels", "name")),
+            );
+        workflow.validate().unwrap();
+        let handler = WorkflowPromptHandler::new(workflow, tools, tool_handlers, None);
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            handler.handle(HashMap::new(), RequestHandlerExtra::default()),
+        )
+        .await
+        .expect("parallel steps did not run concurrently")
+        .unwrap();
+        let texts = message_texts(&result);
+
+        assert_eq!(texts[1].matches("(in parallel)").count(), 2, "{}", texts[1]);
+        // The slower step finishes last but is recorded first
+        let position = |needle: &str| texts.iter().position(|t| t.contains(needle)).unwrap();
+        assert!(position("Calling tool 'forecast'") < position("\"sunny\""));
+        assert!(position("\"sunny\"") < position("Calling tool 'search_hotels'"));
+        assert!(position("\"Grand\"") < position("Calling tool 'plan_trip'"));
+        // Both bindings are available after the block
+        let last = texts.last().unwrap();
+        assert!(last.contains("\"forecast\": \"sunny\""), "{}", last);
+        assert!(last.contains("\"hotel\": \"Grand\""), "{}", last);
+    }
+
     #[tokio::test]
     async fn test_complete_workflow_execution_with_bindings() {
         use crate::server::workflow::dsl::*;
@@ -1244,6 +2192,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1353,6 +2309,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1447,6 +2411,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1579,6 +2551,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1710,6 +2690,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1778,6 +2766,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1857,6 +2853,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -1954,6 +2958,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -2087,6 +3099,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -2229,6 +3249,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -2344,6 +3372,14 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             progress_reporter: None,
             task_request: None,
+            task_id: None,
+            widget_state: None,
+            kv_store: None,
+            elicitation: None,
+            roots: None,
+            sampling: None,
+            flags: Default::default(),
+            extensions: Default::default(),
         };
 
         let result = handler
@@ -2541,4 +3577,105 @@ mod tests {
             "object"
         );
     }
+
+    #[test]
+    fn test_remote_step_without_foundation_client_is_rejected() {
+        let workflow = SequentialWorkflow::new("quote", "Price an order").step(WorkflowStep::new(
+            "total",
+            ToolHandle::remote("calculator", "add"),
+        ));
+        let handler = WorkflowPromptHandler::new(workflow, HashMap::new(), HashMap::new(), None);
+
+        let err = handler.check_remote_steps().unwrap_err().to_string();
+        assert!(err.contains("calculator/add"), "{err}");
+    }
+
+    #[cfg(feature = "composition")]
+    #[tokio::test]
+    async fn test_remote_step_calls_foundation_client() {
+        use crate::composition::{
+            CompositionError, FoundationClient, PromptResult, ResourceContent,
+        };
+
+        struct Calculator;
+
+        #[async_trait]
+        impl FoundationClient for Calculator {
+            async fn call_tool(
+                &self,
+                server_id: &str,
+                tool_name: &str,
+                arguments: &Value,
+            ) -> std::result::Result<String, CompositionError> {
+                assert_eq!((server_id, tool_name), ("calculator", "add"));
+                let sum = arguments["a"].as_i64().unwrap() + arguments["b"].as_i64().unwrap();
+                Ok(json!({ "result": sum }).to_string())
+            }
+
+            async fn read_resource(
+                &self,
+                server_id: &str,
+                _uri: &str,
+            ) -> std::result::Result<ResourceContent, CompositionError> {
+                Err(CompositionError::ServerNotFound(server_id.to_string()))
+            }
+
+            async fn get_prompt(
+                &self,
+                server_id: &str,
+                _prompt_name: &str,
+                _arguments: &Value,
+            ) -> std::result::Result<PromptResult, CompositionError> {
+                Err(CompositionError::ServerNotFound(server_id.to_string()))
+            }
+
+            async fn is_available(&self, server_id: &str) -> bool {
+                server_id == "calculator"
+            }
+
+            fn foundation_ids(&self) -> Vec<String> {
+                vec!["calculator".to_string()]
+            }
+        }
+
+        let workflow = SequentialWorkflow::new("quote", "Price an order")
+            .typed_argument(
+                "qty",
+                "Quantity",
+                true,
+                crate::types::PromptArgumentType::Integer,
+            )
+            .step(
+                WorkflowStep::new("total", ToolHandle::remote("calculator", "add"))
+                    .arg("a", prompt_arg("qty"))
+                    .arg("b", DataSource::constant(json!(2)))
+                    .bind("total"),
+            );
+        let handler = WorkflowPromptHandler::new(workflow, HashMap::new(), HashMap::new(), None)
+            .with_foundation_client(Arc::new(Calculator));
+        handler.check_remote_steps().unwrap();
+
+        let args = HashMap::from([("qty".to_string(), "3".to_string())]);
+        let extra = RequestHandlerExtra::new("test".to_string(), Default::default());
+        let result = handler.handle(args, extra).await.unwrap();
+
+        let texts: Vec<&str> = result
+            .messages
+            .iter()
+            .filter_map(|m| match &m.content {
+                Content::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(
+            texts
+                .iter()
+                .any(|t| t.contains("foundation server 'calculator'")),
+            "{texts:?}"
+        );
+        assert!(
+            texts.iter().any(|t| t.contains("\"result\": 5")),
+            "{texts:?}"
+        );
+    }
 }
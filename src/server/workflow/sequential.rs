@@ -3,14 +3,20 @@
 //! Orchestrates multiple workflow steps in sequence with data flow validation.
 
 use super::{
+    compaction::TraceCompactor,
     error::WorkflowError,
+    error_policy::OnError,
+    handles::ToolHandle,
     newtypes::{ArgName, BindingName},
     prompt_content::InternalPromptMessage,
     workflow_step::WorkflowStep,
 };
-use crate::types::PromptArgumentType;
+use crate::types::{PromptArgumentType, ToolInfo};
 use indexmap::IndexMap;
 use smallvec::SmallVec;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// A sequential workflow that executes steps in order
 #[derive(Clone, Debug)]
@@ -33,6 +39,10 @@ pub struct SequentialWorkflow {
     /// wrapped in a [`TaskWorkflowPromptHandler`](super::TaskWorkflowPromptHandler)
     /// that creates a task on invocation.
     task_support: bool,
+    /// Compactors applied, in order, to the conversation trace before it is returned
+    compactors: Vec<Arc<dyn TraceCompactor>>,
+    /// Ranges of `steps` that execute concurrently
+    parallel_blocks: Vec<Range<usize>>,
 }
 
 /// Specification for a prompt argument
@@ -70,6 +80,8 @@ impl SequentialWorkflow {
             steps: SmallVec::new(),
             instructions: SmallVec::new(),
             task_support: false,
+            compactors: Vec::new(),
+            parallel_blocks: Vec::new(),
         }
     }
 
@@ -154,6 +166,54 @@ impl SequentialWorkflow {
         self
     }
 
+    /// Add a block of independent steps that execute concurrently (chainable)
+    ///
+    /// The steps run server-side at the same time. Their bindings are
+    /// available to the steps after the block, and their messages appear in
+    /// the conversation trace in the order the steps are listed here, however
+    /// the calls interleave. Steps in a block cannot reference each other's
+    /// bindings, fetch resources, or use `repeat_until`.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::{SequentialWorkflow, WorkflowStep, ToolHandle};
+    /// use pmcp::server::workflow::dsl::{field, prompt_arg};
+    ///
+    /// let workflow = SequentialWorkflow::new("trip", "plan a trip")
+    ///     .argument("city", "Destination", true)
+    ///     .parallel([
+    ///         WorkflowStep::new("weather", ToolHandle::new("forecast"))
+    ///             .arg("city", prompt_arg("city"))
+    ///             .bind("weather"),
+    ///         WorkflowStep::new("hotels", ToolHandle::new("search_hotels"))
+    ///             .arg("city", prompt_arg("city"))
+    ///             .bind("hotels"),
+    ///     ])
+    ///     .step(
+    ///         WorkflowStep::new("plan", ToolHandle::new("plan_trip"))
+    ///             .arg("forecast", field("weather", "summary"))
+    ///             .arg("hotel", field("hotels", "0.name")),
+    ///     );
+    ///
+    /// assert!(workflow.validate().is_ok());
+    /// assert_eq!(workflow.parallel_block(1), Some(0..2));
+    /// ```
+    #[must_use]
+    pub fn parallel(mut self, steps: impl IntoIterator<Item = WorkflowStep>) -> Self {
+        let start = self.steps.len();
+        self.steps.extend(steps);
+        self.parallel_blocks.push(start..self.steps.len());
+        self
+    }
+
+    /// Get the parallel block containing the step at `index`, if any
+    pub fn parallel_block(&self, index: usize) -> Option<Range<usize>> {
+        self.parallel_blocks
+            .iter()
+            .find(|block| block.contains(&index))
+            .cloned()
+    }
+
     /// Add an instruction message (chainable)
     ///
     /// # Example
@@ -201,6 +261,30 @@ impl SequentialWorkflow {
         self.task_support
     }
 
+    /// Add a trace compactor (chainable)
+    ///
+    /// Compactors rewrite the conversation trace returned by `prompts/get`, in the order
+    /// they were added. See [`compaction`](super::compaction) for the built-in strategies.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::SequentialWorkflow;
+    /// use pmcp::server::workflow::compaction::DropIntermediateToolOutputs;
+    ///
+    /// let workflow = SequentialWorkflow::new("workflow", "description")
+    ///     .compact_trace(DropIntermediateToolOutputs);
+    /// ```
+    #[must_use]
+    pub fn compact_trace(mut self, compactor: impl TraceCompactor + 'static) -> Self {
+        self.compactors.push(Arc::new(compactor));
+        self
+    }
+
+    /// Get trace compactors
+    pub fn trace_compactors(&self) -> &[Arc<dyn TraceCompactor>] {
+        &self.compactors
+    }
+
     /// Get workflow name
     pub fn name(&self) -> &str {
         &self.name
@@ -232,6 +316,7 @@ impl SequentialWorkflow {
     /// - All steps reference valid bindings
     /// - No circular dependencies
     /// - All prompt arguments referenced by steps are defined
+    /// - Steps in a parallel block are independent tool steps
     ///
     /// # Binding behavior
     /// Steps can only be referenced by their explicit binding names set via `.bind()`.
@@ -239,10 +324,24 @@ impl SequentialWorkflow {
     pub fn validate(&self) -> Result<(), WorkflowError> {
         let mut available_bindings = Vec::new();
 
+        for block in &self.parallel_blocks {
+            self.validate_parallel_block(block.clone())?;
+        }
+
+        // Bindings visible to the current step; steps in a parallel block
+        // only see the bindings from before the block
+        let mut visible = 0;
+
         // Validate each step in sequence
-        for step in &self.steps {
+        for (idx, step) in self.steps.iter().enumerate() {
+            if self
+                .parallel_block(idx)
+                .is_none_or(|block| block.start == idx)
+            {
+                visible = available_bindings.len();
+            }
             // Validate step can access required bindings
-            step.validate(&available_bindings)?;
+            step.validate(&available_bindings[..visible])?;
 
             // Add this step's binding to available bindings (if it has one)
             // Only explicit bindings can be referenced by later steps
@@ -253,7 +352,21 @@ impl SequentialWorkflow {
 
         // Validate all prompt arguments referenced in steps are defined
         for step in &self.steps {
-            for (_, source) in step.arguments() {
+            let condition_sources = step
+                .branch_condition()
+                .into_iter()
+                .chain(step.repeat_condition())
+                .flat_map(|condition| condition.data_sources());
+            let fallback_sources = match step.error_handler() {
+                OnError::Fallback(fallback) => Some(fallback.arguments().values()),
+                _ => None,
+            };
+            let sources = step
+                .arguments()
+                .values()
+                .chain(condition_sources)
+                .chain(fallback_sources.into_iter().flatten());
+            for source in sources {
                 if let super::data_source::DataSource::PromptArg(arg_name) = source {
                     if !self.arguments.contains_key(arg_name) {
                         return Err(WorkflowError::InvalidMapping {
@@ -268,6 +381,71 @@ impl SequentialWorkflow {
         Ok(())
     }
 
+    /// Validate the tool steps against the registered tools
+    ///
+    /// Checks that every tool step (and tool fallback) calls a tool in
+    /// `tools`, that each mapped argument is declared in the tool's input
+    /// schema, and that prompt arguments and constants have a compatible
+    /// JSON type. Steps on foundation servers are not checked. See
+    /// [`tool_schema`](super::tool_schema) for the rules.
+    pub fn validate_tools(&self, tools: &HashMap<String, ToolInfo>) -> Result<(), WorkflowError> {
+        for step in self.tool_steps() {
+            super::tool_schema::check_step(self, step, tools)?;
+        }
+        Ok(())
+    }
+
+    /// The steps and tool fallbacks, in order
+    pub(crate) fn tool_steps(&self) -> impl Iterator<Item = &WorkflowStep> {
+        self.steps.iter().flat_map(|step| {
+            let fallback = match step.error_handler() {
+                OnError::Fallback(fallback) => Some(fallback.as_ref()),
+                _ => None,
+            };
+            std::iter::once(step).chain(fallback)
+        })
+    }
+
+    /// The first step that calls a tool on a foundation server, if any
+    pub(crate) fn remote_step(&self) -> Option<&WorkflowStep> {
+        self.tool_steps()
+            .find(|step| step.tool().is_some_and(ToolHandle::is_remote))
+    }
+
+    fn validate_parallel_block(&self, block: Range<usize>) -> Result<(), WorkflowError> {
+        if block.is_empty() {
+            return Err(WorkflowError::InvalidMapping {
+                step: self.name.clone(),
+                reason: "Parallel blocks must contain at least one step".to_string(),
+            });
+        }
+        let mut bindings = Vec::new();
+        for step in &self.steps[block] {
+            let invalid = |reason: String| WorkflowError::InvalidMapping {
+                step: step.name().to_string(),
+                reason,
+            };
+            if step.is_resource_only() || !step.resources().is_empty() {
+                return Err(invalid(
+                    "Steps in a parallel block cannot fetch resources".to_string(),
+                ));
+            }
+            if step.repeat_condition().is_some() {
+                return Err(invalid(
+                    "Steps in a parallel block cannot use repeat_until".to_string(),
+                ));
+            }
+            if let Some(binding) = step.binding().filter(|b| bindings.contains(b)) {
+                return Err(invalid(format!(
+                    "Binding '{}' is bound twice in the parallel block",
+                    binding
+                )));
+            }
+            bindings.extend(step.binding());
+        }
+        Ok(())
+    }
+
     /// Get all bindings that will be available after executing all steps
     pub fn output_bindings(&self) -> Vec<BindingName> {
         self.steps
@@ -500,4 +678,51 @@ mod tests {
             WorkflowError::UnknownBinding { .. }
         ));
     }
+
+    #[test]
+    fn test_sequential_workflow_condition_references_undefined_argument() {
+        use crate::server::workflow::Condition;
+
+        let workflow = SequentialWorkflow::new("workflow", "description").step(
+            WorkflowStep::new("notify", ToolHandle::new("notify"))
+                .branch_if(Condition::truthy(prompt_arg("urgent"))),
+        );
+        assert!(matches!(
+            workflow.validate(),
+            Err(WorkflowError::InvalidMapping { .. })
+        ));
+
+        let workflow = workflow.argument("urgent", "Notify on-call", false);
+        assert!(workflow.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parallel_block_validation() {
+        let workflow = SequentialWorkflow::new("trip", "plan a trip")
+            .step(WorkflowStep::new("find", ToolHandle::new("find_city")).bind("city"))
+            .parallel([
+                WorkflowStep::new("weather", ToolHandle::new("forecast"))
+                    .arg("city", from_step("city"))
+                    .bind("weather"),
+                WorkflowStep::new("hotels", ToolHandle::new("search_hotels"))
+                    .arg("forecast", from_step("weather")),
+            ]);
+        assert_eq!(workflow.parallel_block(0), None);
+        assert_eq!(workflow.parallel_block(2), Some(1..3));
+        // Steps in a block cannot see each other's bindings
+        assert!(matches!(
+            workflow.validate(),
+            Err(WorkflowError::UnknownBinding { .. })
+        ));
+
+        let workflow = SequentialWorkflow::new("trip", "plan a trip").parallel([
+            WorkflowStep::new("weather", ToolHandle::new("forecast")).bind("data"),
+            WorkflowStep::new("hotels", ToolHandle::new("search_hotels")).bind("data"),
+        ]);
+        let err = workflow.validate().unwrap_err();
+        assert!(err.to_string().contains("bound twice"));
+
+        let workflow = SequentialWorkflow::new("trip", "plan a trip").parallel([]);
+        assert!(workflow.validate().is_err());
+    }
 }
@@ -6,12 +6,21 @@
 //!
 //! 1. Creates a task via the [`TaskRouter`]
 //! 2. Runs its own step loop using the inner handler's `pub(crate)` helpers
-//! 3. Accumulates step results in memory during execution
+//! 3. Checkpoints progress, step results, and bindings to the task after
+//!    each step
 //! 4. Classifies failures into typed [`PauseReason`] variants
-//! 5. Batch-writes all state (progress, results, pause reason) to the task store
+//! 5. Writes the final state (progress, results, pause reason) to the task store
 //! 6. Auto-completes the task when all steps succeed
 //! 7. Enriches the [`GetPromptResult`] with `_meta` containing task state
 //!
+//! # Resuming
+//!
+//! When the prompt is requested with `_task_id` in `_meta`, the handler
+//! loads that task instead of creating one. Steps the checkpoint records as
+//! completed or skipped are not run again, their bindings are restored, and
+//! execution continues from the first remaining step. This lets a workflow
+//! survive a server restart or Lambda timeout.
+//!
 //! The execution loop stops at the first unresolvable step without failing
 //! the task -- the task stays Working with completed steps having results
 //! and remaining steps staying Pending.
@@ -19,9 +28,9 @@
 //! # Graceful Degradation
 //!
 //! If task creation fails, the handler logs the error and falls back to
-//! returning the inner handler's result without `_meta`. If the batch write
-//! to the task store fails, the prompt result is returned anyway with `_meta`
-//! constructed from in-memory state.
+//! returning the inner handler's result without `_meta`. If a checkpoint
+//! write to the task store fails, execution continues and the prompt result
+//! is returned with `_meta` constructed from in-memory state.
 //!
 //! # Architecture Note
 //!
@@ -32,7 +41,8 @@
 //! maintain the `_workflow.*` convention.
 
 use super::data_source::DataSource;
-use super::prompt_handler::{ExecutionContext, WorkflowPromptHandler};
+use super::newtypes::BindingName;
+use super::prompt_handler::{BlockOutcome, ExecutionContext, Recovery, WorkflowPromptHandler};
 use super::sequential::SequentialWorkflow;
 use super::workflow_step::WorkflowStep;
 use crate::error::Result;
@@ -60,6 +70,11 @@ fn workflow_result_key(step_name: &str) -> String {
     format!("_workflow.result.{step_name}")
 }
 
+/// Builds the task variable key for a step output binding.
+fn workflow_binding_key(binding: &str) -> String {
+    format!("_workflow.binding.{binding}")
+}
+
 // === Step status (mirrors pmcp_tasks::types::workflow::StepStatus) ===
 
 /// Runtime outcome of a workflow step.
@@ -89,6 +104,30 @@ impl StepStatus {
             Self::Skipped => "skipped",
         }
     }
+
+    /// Parse the JSON string representation, treating unknown values as pending.
+    fn parse(status: &str) -> Self {
+        match status {
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "skipped" => Self::Skipped,
+            _ => Self::Pending,
+        }
+    }
+
+    /// Whether the step needs no further execution.
+    fn is_done(self) -> bool {
+        matches!(self, Self::Completed | Self::Skipped)
+    }
+}
+
+/// Execution state restored from a workflow task's variables.
+#[derive(Debug, Default)]
+struct Checkpoint {
+    /// Status of each step, in workflow order.
+    step_statuses: Vec<StepStatus>,
+    /// Outputs of completed steps, keyed by binding name.
+    bindings: Vec<(BindingName, Value)>,
 }
 
 // === Pause reason (mirrors pmcp_tasks::types::workflow::PauseReason) ===
@@ -502,14 +541,123 @@ impl TaskWorkflowPromptHandler {
     /// Resolve the owner ID from the request's auth context.
     ///
     /// Follows the same pattern as `ServerCore::resolve_task_owner`:
-    /// delegates to `TaskRouter::resolve_owner` with available auth fields.
+    /// delegates to `TaskRouter::resolve_owner_from_auth` with the auth
+    /// context and transport session.
     fn resolve_owner(&self, extra: &RequestHandlerExtra) -> String {
-        match &extra.auth_context {
-            Some(ctx) => {
-                self.task_router
-                    .resolve_owner(Some(&ctx.subject), ctx.client_id.as_deref(), None)
-            },
-            None => self.task_router.resolve_owner(None, None, None),
+        self.task_router
+            .resolve_owner_from_auth(extra.auth_context.as_ref(), extra.session_id.as_deref())
+    }
+
+    /// Restore the execution state of a task being resumed.
+    ///
+    /// `task` is the value returned by [`TaskRouter::get_workflow_task`]. The
+    /// task must still be running and its recorded progress must list the
+    /// same steps as this workflow. A completed step whose output was not
+    /// persisted is treated as pending so it runs again.
+    fn restore_checkpoint(&self, task_id: &str, task: &Value) -> Result<Checkpoint> {
+        let status = task.get("status").and_then(Value::as_str).unwrap_or("");
+        if !matches!(status, "working" | "input_required") {
+            return Err(crate::Error::invalid_params(format!(
+                "Task '{}' is {} and cannot be resumed",
+                task_id, status
+            )));
+        }
+
+        let variables = task.get("_meta").and_then(Value::as_object);
+        let variable = |key: &str| variables.and_then(|vars| vars.get(key));
+
+        let recorded_steps = variable(WORKFLOW_PROGRESS_KEY)
+            .and_then(|progress| progress.get("steps"))
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let steps = self.workflow.steps();
+        let same_steps = recorded_steps.len() == steps.len()
+            && recorded_steps.iter().zip(steps).all(|(recorded, step)| {
+                recorded.get("name") == Some(&Value::from(step.name().as_str()))
+            });
+        if !same_steps {
+            return Err(crate::Error::invalid_params(format!(
+                "Task '{}' does not belong to workflow '{}'",
+                task_id,
+                self.workflow.name()
+            )));
+        }
+
+        let mut checkpoint = Checkpoint::default();
+        for (recorded, step) in recorded_steps.iter().zip(steps) {
+            let mut status = recorded
+                .get("status")
+                .and_then(Value::as_str)
+                .map_or(StepStatus::Pending, StepStatus::parse);
+            if status == StepStatus::Completed {
+                if let Some(binding) = step.binding() {
+                    // Steps completed by a continuation tool call record only their result
+                    let value = variable(&workflow_binding_key(binding.as_str()))
+                        .or_else(|| variable(&workflow_result_key(step.name().as_str())));
+                    match value {
+                        Some(value) => checkpoint.bindings.push((binding.clone(), value.clone())),
+                        None => status = StepStatus::Pending,
+                    }
+                }
+            }
+            checkpoint.step_statuses.push(status);
+        }
+        Ok(checkpoint)
+    }
+
+    /// Persist the execution state reached so far.
+    ///
+    /// Writes the updated progress, the results in `new_results` together
+    /// with the bindings their steps produced, and the pause reason (clearing
+    /// any reason left by an earlier run). Failures are logged, not returned.
+    #[allow(clippy::too_many_arguments)]
+    async fn checkpoint(
+        &self,
+        task_id: &str,
+        owner_id: &str,
+        initial_progress: &Value,
+        step_statuses: &[StepStatus],
+        new_results: &[(String, Value)],
+        execution_context: &ExecutionContext,
+        pause_reason: Option<&PauseReason>,
+    ) {
+        let mut batch = serde_json::Map::new();
+        batch.insert(
+            WORKFLOW_PROGRESS_KEY.to_string(),
+            build_updated_progress(initial_progress, step_statuses),
+        );
+
+        for (step_name, result) in new_results {
+            batch.insert(workflow_result_key(step_name), result.clone());
+            let binding = self
+                .workflow
+                .steps()
+                .iter()
+                .find(|step| step.name().as_str() == step_name)
+                .and_then(WorkflowStep::binding);
+            if let Some(binding) = binding {
+                if let Some(value) = execution_context.get_binding(binding) {
+                    batch.insert(workflow_binding_key(binding.as_str()), value.clone());
+                }
+            }
+        }
+
+        batch.insert(
+            WORKFLOW_PAUSE_REASON_KEY.to_string(),
+            pause_reason.map_or(Value::Null, PauseReason::to_value),
+        );
+
+        if let Err(e) = self
+            .task_router
+            .set_task_variables(task_id, owner_id, Value::Object(batch))
+            .await
+        {
+            tracing::warn!(
+                "Failed to checkpoint task variables for workflow '{}': {}",
+                self.workflow.name(),
+                e
+            );
         }
     }
 }
@@ -604,9 +752,11 @@ impl PromptHandler for TaskWorkflowPromptHandler {
     /// Orchestration flow:
     /// 1. Resolve owner from auth context
     /// 2. Build initial progress from workflow definition
-    /// 3. Create task via task router (graceful degradation on failure)
-    /// 4. Run active step loop using inner handler's helpers
-    /// 5. Batch-write progress, results, and pause reason to task store
+    /// 3. Resume the task named in `_meta`, or create one via the task router
+    ///    (graceful degradation on failure)
+    /// 4. Run active step loop using inner handler's helpers, checkpointing
+    ///    after each step
+    /// 5. Write final progress, results, and pause reason to task store
     /// 6. Auto-complete if all steps succeeded
     /// 7. Enrich result with `_meta`
     async fn handle(
@@ -620,25 +770,35 @@ impl PromptHandler for TaskWorkflowPromptHandler {
         // 2. Build initial progress (typed)
         let initial_progress = self.build_initial_progress_typed();
 
-        // 3. Create task (graceful degradation on failure)
-        let task_id = match self
-            .task_router
-            .create_workflow_task(self.workflow.name(), &owner_id, initial_progress.clone())
-            .await
-        {
-            Ok(value) => value
-                .get("task")
-                .and_then(|t| t.get("taskId"))
-                .and_then(|v| v.as_str())
-                .map(String::from),
-            Err(e) => {
-                tracing::warn!(
-                    "Task creation failed for workflow '{}', proceeding without task tracking: {}",
-                    self.workflow.name(),
-                    e
-                );
-                None
-            },
+        // 3. Resume the requested task, or create one (graceful degradation on failure)
+        let mut checkpoint = Checkpoint::default();
+        let task_id = if let Some(task_id) = extra.task_id.clone() {
+            let task = self
+                .task_router
+                .get_workflow_task(&task_id, &owner_id)
+                .await?;
+            checkpoint = self.restore_checkpoint(&task_id, &task)?;
+            Some(task_id)
+        } else {
+            match self
+                .task_router
+                .create_workflow_task(self.workflow.name(), &owner_id, initial_progress.clone())
+                .await
+            {
+                Ok(value) => value
+                    .get("task")
+                    .and_then(|t| t.get("taskId"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                Err(e) => {
+                    tracing::warn!(
+                        "Task creation failed for workflow '{}', proceeding without task tracking: {}",
+                        self.workflow.name(),
+                        e
+                    );
+                    None
+                },
+            }
         };
 
         // If no task was created, delegate to inner handler (graceful degradation)
@@ -656,11 +816,39 @@ impl PromptHandler for TaskWorkflowPromptHandler {
         let mut step_statuses: Vec<StepStatus> = vec![StepStatus::Pending; step_count];
         let mut pause_reason: Option<PauseReason> = None;
 
+        // Steps finished before a resume are not run again
+        let mut restored = vec![false; step_count];
+        for (idx, status) in checkpoint.step_statuses.into_iter().enumerate() {
+            if status.is_done() {
+                step_statuses[idx] = status;
+                restored[idx] = true;
+            }
+        }
+        for (binding, value) in checkpoint.bindings {
+            execution_context.store_binding(binding, value);
+        }
+
+        // What has been written to the task so far
+        let mut checkpointed_statuses = step_statuses.clone();
+        let mut checkpointed_results = 0;
+
         // Add header messages
         messages.push(self.inner.create_user_intent(&args));
         messages.push(self.inner.create_assistant_plan()?);
 
-        for (idx, step) in self.workflow.steps().iter().enumerate() {
+        let restored_count = restored.iter().filter(|done| **done).count();
+        if restored_count > 0 {
+            messages.push(PromptMessage::assistant(Content::text(format!(
+                "Resuming workflow: {} of {} steps already completed.",
+                restored_count, step_count
+            ))));
+        }
+
+        // Steps are walked by index so `repeat_until` steps can run again
+        let mut idx = 0;
+        let mut iteration = 1;
+
+        while let Some(step) = self.workflow.steps().get(idx) {
             // Check cancellation
             if extra.is_cancelled() {
                 tracing::warn!("Workflow cancelled at step: {}", step.name());
@@ -671,8 +859,131 @@ impl PromptHandler for TaskWorkflowPromptHandler {
                 )));
             }
 
+            // Checkpoint what the previous step changed
+            if step_results.len() > checkpointed_results || step_statuses != checkpointed_statuses {
+                self.checkpoint(
+                    &task_id,
+                    &owner_id,
+                    &initial_progress,
+                    &step_statuses,
+                    &step_results[checkpointed_results..],
+                    &execution_context,
+                    None,
+                )
+                .await;
+                checkpointed_results = step_results.len();
+                checkpointed_statuses.clone_from(&step_statuses);
+            }
+
+            if restored[idx] {
+                idx += 1;
+                continue;
+            }
+
+            // Run a block of independent steps concurrently; a block that was
+            // partly completed before a resume runs its remaining steps in turn
+            if let Some(block) = self
+                .workflow
+                .parallel_block(idx)
+                .filter(|block| !block.clone().any(|i| restored[i]))
+            {
+                let progress_message = self.inner.parallel_progress_message(block.clone());
+                if let Err(e) = extra
+                    .report_count(block.end, total_steps, Some(progress_message))
+                    .await
+                {
+                    tracing::warn!("Failed to report workflow progress: {}", e);
+                }
+                let outcomes = self
+                    .inner
+                    .execute_parallel_block(
+                        block.clone(),
+                        &args,
+                        &mut execution_context,
+                        &extra,
+                        &mut messages,
+                    )
+                    .await;
+                for (step_idx, outcome) in block.clone().zip(outcomes) {
+                    let step = &self.workflow.steps()[step_idx];
+                    let step_name = step.name().to_string();
+                    let suggested_tool = step
+                        .tool()
+                        .map(|t| t.name().to_string())
+                        .unwrap_or_default();
+                    match outcome {
+                        BlockOutcome::Completed(result) | BlockOutcome::Recovered(result) => {
+                            step_results.push((step_name, result));
+                            step_statuses[step_idx] = StepStatus::Completed;
+                        },
+                        BlockOutcome::Skipped => {
+                            step_statuses[step_idx] = StepStatus::Skipped;
+                        },
+                        BlockOutcome::SkippedOnError(e) => {
+                            step_results
+                                .push((step_name, serde_json::json!({"error": e.to_string()})));
+                            step_statuses[step_idx] = StepStatus::Skipped;
+                        },
+                        BlockOutcome::Failed(e) => {
+                            step_results.push((
+                                step_name.clone(),
+                                serde_json::json!({"error": e.to_string()}),
+                            ));
+                            step_statuses[step_idx] = StepStatus::Failed;
+                            pause_reason.get_or_insert(PauseReason::ToolError {
+                                failed_step: step_name,
+                                error: e.to_string(),
+                                retryable: step.is_retryable(),
+                                suggested_tool,
+                            });
+                        },
+                        BlockOutcome::Unresolved => {
+                            pause_reason.get_or_insert_with(|| {
+                                classify_resolution_failure(
+                                    step,
+                                    self.workflow.steps(),
+                                    &step_statuses,
+                                )
+                            });
+                        },
+                        BlockOutcome::Incomplete(missing_fields) => {
+                            pause_reason.get_or_insert(PauseReason::SchemaMismatch {
+                                blocked_step: step_name,
+                                missing_fields,
+                                suggested_tool,
+                            });
+                        },
+                    }
+                }
+                if pause_reason.is_some() {
+                    break;
+                }
+                idx = block.end;
+                continue;
+            }
+
+            // Skip steps whose branch condition does not hold
+            if let Some(condition) = step.branch_condition() {
+                if !self
+                    .inner
+                    .condition_holds(condition, &args, &execution_context)
+                {
+                    messages.push(PromptMessage::assistant(Content::text(format!(
+                        "Skipping {}: condition not met ({}).",
+                        step.name(),
+                        condition
+                    ))));
+                    step_statuses[idx] = StepStatus::Skipped;
+                    idx += 1;
+                    continue;
+                }
+            }
+
             // Report progress
-            let progress_message = format!("Step {}/{}: {}", idx + 1, total_steps, step.name());
+            let mut progress_message = format!("Step {}/{}: {}", idx + 1, total_steps, step.name());
+            if iteration > 1 {
+                progress_message.push_str(&format!(" (iteration {})", iteration));
+            }
             if let Err(e) = extra
                 .report_count(idx + 1, total_steps, Some(progress_message))
                 .await
@@ -727,6 +1038,7 @@ impl PromptHandler for TaskWorkflowPromptHandler {
 
                 step_statuses[idx] = StepStatus::Completed;
                 step_results.push((step.name().to_string(), Value::Null));
+                idx += 1;
                 continue;
             }
 
@@ -795,7 +1107,13 @@ impl PromptHandler for TaskWorkflowPromptHandler {
 
                             match self
                                 .inner
-                                .execute_tool_step(step, &args, &execution_context, &extra)
+                                .execute_with_retry(
+                                    step,
+                                    &args,
+                                    &execution_context,
+                                    &extra,
+                                    &mut messages,
+                                )
                                 .await
                             {
                                 Ok(result) => {
@@ -827,6 +1145,31 @@ impl PromptHandler for TaskWorkflowPromptHandler {
                                     {
                                         break;
                                     }
+
+                                    if self.inner.should_repeat(step, &args, &execution_context) {
+                                        if iteration < step.iteration_limit() {
+                                            iteration += 1;
+                                            continue;
+                                        }
+                                        let error =
+                                            WorkflowPromptHandler::iterations_exhausted_message(
+                                                step,
+                                            );
+                                        messages.push(PromptMessage::assistant(Content::text(
+                                            error.clone(),
+                                        )));
+                                        step_statuses[idx] = StepStatus::Failed;
+                                        pause_reason = Some(PauseReason::ToolError {
+                                            failed_step: step.name().to_string(),
+                                            error,
+                                            retryable: step.is_retryable(),
+                                            suggested_tool: step
+                                                .tool()
+                                                .map(|t| t.name().to_string())
+                                                .unwrap_or_default(),
+                                        });
+                                        break;
+                                    }
                                 },
                                 Err(e) => {
                                     messages.push(PromptMessage::user(Content::text(format!(
@@ -835,29 +1178,57 @@ impl PromptHandler for TaskWorkflowPromptHandler {
                                     ))));
 
                                     let step_name = step.name().to_string();
-                                    step_results.push((
-                                        step_name.clone(),
-                                        serde_json::json!({"error": e.to_string()}),
-                                    ));
-                                    step_statuses[idx] = StepStatus::Failed;
-
-                                    let suggested_tool = step
-                                        .tool()
-                                        .map(|t| t.name().to_string())
-                                        .unwrap_or_default();
-                                    pause_reason = Some(PauseReason::ToolError {
-                                        failed_step: step_name,
-                                        error: e.to_string(),
-                                        retryable: step.is_retryable(),
-                                        suggested_tool,
-                                    });
-                                    break;
+                                    let recovery = self
+                                        .inner
+                                        .recover(
+                                            step,
+                                            &args,
+                                            &mut execution_context,
+                                            &extra,
+                                            &mut messages,
+                                        )
+                                        .await;
+                                    match recovery {
+                                        Recovery::Recovered(result) => {
+                                            step_results.push((step_name, result));
+                                            step_statuses[idx] = StepStatus::Completed;
+                                        },
+                                        Recovery::Skipped => {
+                                            step_results.push((
+                                                step_name,
+                                                serde_json::json!({"error": e.to_string()}),
+                                            ));
+                                            step_statuses[idx] = StepStatus::Skipped;
+                                        },
+                                        Recovery::Abort => {
+                                            step_results.push((
+                                                step_name.clone(),
+                                                serde_json::json!({"error": e.to_string()}),
+                                            ));
+                                            step_statuses[idx] = StepStatus::Failed;
+
+                                            let suggested_tool = step
+                                                .tool()
+                                                .map(|t| t.name().to_string())
+                                                .unwrap_or_default();
+                                            pause_reason = Some(PauseReason::ToolError {
+                                                failed_step: step_name,
+                                                error: e.to_string(),
+                                                retryable: step.is_retryable(),
+                                                suggested_tool,
+                                            });
+                                            break;
+                                        },
+                                    }
                                 },
                             }
                         },
                     }
                 },
             }
+
+            idx += 1;
+            iteration = 1;
         }
 
         // 4b. Append handoff message when execution paused
@@ -867,38 +1238,25 @@ impl PromptHandler for TaskWorkflowPromptHandler {
             messages.push(handoff);
         }
 
-        // 5. Batch write to task store
-        let updated_progress = build_updated_progress(&initial_progress, &step_statuses);
-
-        let mut batch: HashMap<String, Value> = HashMap::new();
-        batch.insert(WORKFLOW_PROGRESS_KEY.to_string(), updated_progress);
-
-        for (step_name, result) in &step_results {
-            batch.insert(workflow_result_key(step_name), result.clone());
-        }
-
-        if let Some(ref reason) = pause_reason {
-            batch.insert(WORKFLOW_PAUSE_REASON_KEY.to_string(), reason.to_value());
-        }
-
-        let batch_value = serde_json::to_value(&batch).unwrap_or_else(|_| serde_json::json!({}));
-
-        if let Err(e) = self
-            .task_router
-            .set_task_variables(&task_id, &owner_id, batch_value)
-            .await
-        {
-            tracing::warn!(
-                "Failed to batch-write task variables for workflow '{}': {}",
-                self.workflow.name(),
-                e
-            );
-        }
+        // 5. Write the final state to the task store
+        self.checkpoint(
+            &task_id,
+            &owner_id,
+            &initial_progress,
+            &step_statuses,
+            &step_results[checkpointed_results..],
+            &execution_context,
+            pause_reason.as_ref(),
+        )
+        .await;
 
         // 6. Auto-complete if all steps succeeded
         let mut task_status = "working";
-        let all_completed =
-            pause_reason.is_none() && step_statuses.iter().all(|s| *s == StepStatus::Completed);
+        // Steps skipped by a branch condition count as done
+        let all_completed = pause_reason.is_none()
+            && step_statuses
+                .iter()
+                .all(|s| matches!(s, StepStatus::Completed | StepStatus::Skipped));
 
         if all_completed {
             let completion_result = serde_json::json!({
@@ -949,11 +1307,7 @@ impl PromptHandler for TaskWorkflowPromptHandler {
             )
             .await;
 
-        let mut result = GetPromptResult {
-            description: Some(self.workflow.description().to_string()),
-            messages,
-            _meta: None,
-        };
+        let mut result = self.inner.finish(messages, &extra);
         result._meta = Some(meta);
 
         Ok(result)
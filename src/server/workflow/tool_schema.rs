@@ -0,0 +1,225 @@
+//! Checks of workflow argument mappings against tool input schemas.
+//!
+//! A workflow step maps prompt arguments, constants and earlier step
+//! outputs into a tool's arguments. The server builders run
+//! [`SequentialWorkflow::validate_tools`] from `build()`, so a mistyped
+//! argument name or a string fed into an integer parameter fails the build
+//! instead of the first `prompts/get`:
+//!
+//! - every tool step, including tool fallbacks, calls a registered tool,
+//! - every mapped argument is declared in the tool's `properties`, unless
+//!   the schema allows additional properties,
+//! - prompt arguments and constants have a JSON type the parameter accepts.
+//!   A prompt argument without a type hint is a string.
+//!
+//! Step outputs are not type-checked; their shape is only known at run time.
+//! Steps on [remote tool handles](super::ToolHandle::remote) are skipped, since
+//! the foundation server's schemas are not known at build time.
+
+use super::{
+    data_source::DataSource, error::WorkflowError, sequential::SequentialWorkflow,
+    workflow_step::WorkflowStep,
+};
+use crate::server::argument_error::resolve;
+use crate::types::{PromptArgumentType, ToolInfo};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Check one tool step's argument mappings against the registered tools.
+pub(crate) fn check_step(
+    workflow: &SequentialWorkflow,
+    step: &WorkflowStep,
+    tools: &HashMap<String, ToolInfo>,
+) -> Result<(), WorkflowError> {
+    let Some(handle) = step.tool().filter(|handle| !handle.is_remote()) else {
+        return Ok(());
+    };
+    let tool = tools
+        .get(handle.name())
+        .ok_or_else(|| WorkflowError::MissingTool {
+            workflow: workflow.name().to_string(),
+            tool: handle.name().to_string(),
+        })?;
+    let invalid = |reason: String| WorkflowError::InvalidMapping {
+        step: step.name().to_string(),
+        reason,
+    };
+
+    let root = &tool.input_schema;
+    let Some(schema) = resolve(root, root) else {
+        return Ok(());
+    };
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Ok(());
+    };
+    let open = schema
+        .get("additionalProperties")
+        .is_some_and(|extra| extra.as_bool().unwrap_or(true));
+
+    for (arg, source) in step.arguments() {
+        let Some(property) = properties.get(arg.as_str()) else {
+            if open {
+                continue;
+            }
+            return Err(invalid(format!(
+                "Tool '{}' has no argument '{}'",
+                tool.name, arg
+            )));
+        };
+        let Some(accepted) = resolve(root, property).and_then(json_types) else {
+            continue;
+        };
+        let (actual, origin) = match source {
+            DataSource::PromptArg(name) => {
+                let arg_type = workflow
+                    .arguments()
+                    .get(name)
+                    .and_then(|spec| spec.arg_type)
+                    .unwrap_or_default();
+                (
+                    prompt_arg_type(arg_type),
+                    format!("prompt argument '{}'", name),
+                )
+            },
+            DataSource::Constant(value) => (value_type(value), "the constant".to_string()),
+            DataSource::StepOutput { .. } => continue,
+        };
+        if !accepts(&accepted, actual) {
+            return Err(invalid(format!(
+                "Tool '{}' expects {} for argument '{}', but {} is {}",
+                tool.name,
+                accepted.join(" or "),
+                arg,
+                origin,
+                actual
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The JSON types a schema declares with `type`, if it declares any.
+fn json_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(vec![ty.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn accepts(accepted: &[&str], actual: &str) -> bool {
+    accepted.contains(&actual) || (actual == "integer" && accepted.contains(&"number"))
+}
+
+fn prompt_arg_type(arg_type: PromptArgumentType) -> &'static str {
+    match arg_type {
+        PromptArgumentType::String => "string",
+        PromptArgumentType::Number => "number",
+        PromptArgumentType::Integer => "integer",
+        PromptArgumentType::Boolean => "boolean",
+    }
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::workflow::{DataSource, ToolHandle};
+    use serde_json::json;
+
+    fn tools() -> HashMap<String, ToolInfo> {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer" },
+                "threshold": { "$ref": "#/$defs/Ratio" }
+            },
+            "additionalProperties": false,
+            "$defs": { "Ratio": { "type": "number" } }
+        });
+        HashMap::from([("search".to_string(), ToolInfo::new("search", None, schema))])
+    }
+
+    fn workflow(step: WorkflowStep) -> SequentialWorkflow {
+        SequentialWorkflow::new("find", "Find things")
+            .argument("q", "Query", true)
+            .typed_argument("n", "Count", false, PromptArgumentType::Integer)
+            .step(step)
+    }
+
+    fn check(step: WorkflowStep) -> Result<(), WorkflowError> {
+        let workflow = workflow(step);
+        workflow.validate_tools(&tools())
+    }
+
+    #[test]
+    fn accepts_matching_mappings() {
+        let step = WorkflowStep::new("search", ToolHandle::new("search"))
+            .arg("query", DataSource::prompt_arg("q"))
+            .arg("limit", DataSource::prompt_arg("n"))
+            .arg("threshold", DataSource::Constant(json!(1)));
+        check(step).unwrap();
+    }
+
+    #[test]
+    fn rejects_unregistered_tool() {
+        let step = WorkflowStep::new("search", ToolHandle::new("lookup"));
+        assert!(matches!(
+            check(step),
+            Err(WorkflowError::MissingTool { tool, .. }) if tool == "lookup"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_argument() {
+        let step = WorkflowStep::new("search", ToolHandle::new("search"))
+            .arg("qeury", DataSource::prompt_arg("q"));
+        let err = check(step).unwrap_err().to_string();
+        assert!(err.contains("has no argument 'qeury'"), "{err}");
+    }
+
+    #[test]
+    fn rejects_untyped_prompt_arg_for_integer() {
+        let step = WorkflowStep::new("search", ToolHandle::new("search"))
+            .arg("limit", DataSource::prompt_arg("q"));
+        let err = check(step).unwrap_err().to_string();
+        assert!(
+            err.contains("expects integer for argument 'limit', but prompt argument 'q' is string"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn rejects_constant_of_wrong_type() {
+        let step = WorkflowStep::new("search", ToolHandle::new("search"))
+            .arg("threshold", DataSource::Constant(json!("high")));
+        let err = check(step).unwrap_err().to_string();
+        assert!(err.contains("expects number"), "{err}");
+    }
+
+    #[test]
+    fn skips_remote_tools() {
+        let step = WorkflowStep::new("add", ToolHandle::remote("calculator", "add"))
+            .arg("a", DataSource::Constant(json!("one")));
+        check(step).unwrap();
+    }
+
+    #[test]
+    fn skips_step_outputs() {
+        let step = WorkflowStep::new("search", ToolHandle::new("search"))
+            .arg("limit", DataSource::from_step("previous"));
+        check(step).unwrap();
+    }
+}
@@ -3,8 +3,10 @@
 //! Provides a type-safe, ergonomic API for building workflow steps.
 
 use super::{
+    condition::Condition,
     data_source::DataSource,
     error::WorkflowError,
+    error_policy::{OnError, RetryPolicy},
     handles::{ResourceHandle, ToolHandle},
     newtypes::{ArgName, BindingName, StepName},
 };
@@ -79,8 +81,21 @@ pub struct WorkflowStep {
     ///
     /// Defaults to `false`.
     retryable: bool,
+    /// Guard evaluated before the step; the step is skipped when it is false
+    branch_if: Option<Condition>,
+    /// Exit condition evaluated after the step; the step repeats until it holds
+    repeat_until: Option<Condition>,
+    /// Upper bound on executions of a `repeat_until` step
+    max_iterations: usize,
+    /// How failed tool calls are retried
+    retry_policy: Option<RetryPolicy>,
+    /// What happens when the step fails after its retries
+    on_error: OnError,
 }
 
+/// Default upper bound on executions of a `repeat_until` step
+pub const DEFAULT_MAX_ITERATIONS: usize = 5;
+
 impl WorkflowStep {
     /// Create a new workflow step that executes a tool
     ///
@@ -103,6 +118,11 @@ impl WorkflowStep {
             resources: Vec::new(),
             template_bindings: HashMap::new(),
             retryable: false,
+            branch_if: None,
+            repeat_until: None,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            retry_policy: None,
+            on_error: OnError::Abort,
         }
     }
 
@@ -147,6 +167,11 @@ impl WorkflowStep {
             resources: Vec::new(),
             template_bindings: HashMap::new(),
             retryable: false,
+            branch_if: None,
+            repeat_until: None,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            retry_policy: None,
+            on_error: OnError::Abort,
         }
     }
 
@@ -360,12 +385,137 @@ impl WorkflowStep {
         self.retryable
     }
 
+    /// Only execute this step when `condition` holds (chainable)
+    ///
+    /// The condition is evaluated server-side before the step runs. When it
+    /// is false the step is skipped and its binding stays unset; two steps
+    /// with opposite guards form a decision point.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::{Condition, WorkflowStep, ToolHandle};
+    /// use pmcp::server::workflow::dsl::field;
+    ///
+    /// let needs_review = Condition::greater_than(field("diff", "lines"), 500.0);
+    /// let review = WorkflowStep::new("review", ToolHandle::new("request_review"))
+    ///     .branch_if(needs_review.clone());
+    /// let merge = WorkflowStep::new("merge", ToolHandle::new("merge"))
+    ///     .branch_if(!needs_review);
+    /// ```
+    #[must_use]
+    pub fn branch_if(mut self, condition: Condition) -> Self {
+        self.branch_if = Some(condition);
+        self
+    }
+
+    /// Repeat this step until `condition` holds (chainable)
+    ///
+    /// The condition is evaluated server-side after each execution, so it
+    /// may reference the step's own binding. The step runs at most
+    /// [`max_iterations`](Self::max_iterations) times; if the condition
+    /// still does not hold, execution stops and hands off to the client.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::{Condition, WorkflowStep, ToolHandle};
+    /// use pmcp::server::workflow::dsl::field;
+    /// use serde_json::json;
+    ///
+    /// let step = WorkflowStep::new("poll", ToolHandle::new("job_status"))
+    ///     .bind("job")
+    ///     .repeat_until(Condition::equals(field("job", "state"), json!("done")))
+    ///     .max_iterations(10);
+    ///
+    /// assert_eq!(step.iteration_limit(), 10);
+    /// ```
+    #[must_use]
+    pub fn repeat_until(mut self, condition: Condition) -> Self {
+        self.repeat_until = Some(condition);
+        self
+    }
+
+    /// Set the upper bound on executions of a `repeat_until` step (chainable)
+    ///
+    /// Defaults to [`DEFAULT_MAX_ITERATIONS`].
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Get the guard set via [`WorkflowStep::branch_if`]
+    pub fn branch_condition(&self) -> Option<&Condition> {
+        self.branch_if.as_ref()
+    }
+
+    /// Get the exit condition set via [`WorkflowStep::repeat_until`]
+    pub fn repeat_condition(&self) -> Option<&Condition> {
+        self.repeat_until.as_ref()
+    }
+
+    /// Get the upper bound on executions of a `repeat_until` step
+    pub fn iteration_limit(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Retry failed tool calls according to `policy` (chainable)
+    ///
+    /// Each attempt and the wait before the next one are recorded in the
+    /// execution trace.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::{RetryPolicy, WorkflowStep, ToolHandle};
+    /// use std::time::Duration;
+    ///
+    /// let step = WorkflowStep::new("deploy", ToolHandle::new("deploy_service"))
+    ///     .retry(RetryPolicy::fixed(3, Duration::from_millis(500)));
+    ///
+    /// assert_eq!(step.retry_policy().unwrap().max_attempts(), 3);
+    /// ```
+    #[must_use]
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Set what happens when the step fails after its retries (chainable)
+    ///
+    /// Defaults to [`OnError::Abort`], which stops server-side execution and
+    /// hands off to the client.
+    ///
+    /// # Example
+    /// ```
+    /// use pmcp::server::workflow::{OnError, WorkflowStep, ToolHandle};
+    ///
+    /// let step = WorkflowStep::new("enrich", ToolHandle::new("enrich_contact"))
+    ///     .on_error(OnError::Skip);
+    /// ```
+    #[must_use]
+    pub fn on_error(mut self, handler: OnError) -> Self {
+        self.on_error = handler;
+        self
+    }
+
+    /// Get the retry policy set via [`WorkflowStep::retry`]
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Get the error handler set via [`WorkflowStep::on_error`]
+    pub fn error_handler(&self) -> &OnError {
+        &self.on_error
+    }
+
     /// Validate the step
     ///
     /// Checks that:
     /// - All referenced bindings are available
     /// - Resource-only steps have at least one resource
     /// - Resource-only steps don't have tool arguments
+    /// - `repeat_until` is only used on tool steps with at least one iteration
+    /// - Retry policies allow at least one attempt
+    /// - Fallback steps are valid tool steps without a fallback of their own
     pub fn validate(&self, available_bindings: &[BindingName]) -> Result<(), WorkflowError> {
         // Validate resource-only steps
         if self.is_resource_only() {
@@ -423,6 +573,77 @@ impl WorkflowStep {
             }
         }
 
+        // Guards run before the step, so they only see earlier bindings
+        if let Some(condition) = &self.branch_if {
+            self.check_condition_bindings(condition, available_bindings, false)?;
+        }
+
+        // Exit conditions run after the step and may also see its own binding
+        if let Some(condition) = &self.repeat_until {
+            if self.is_resource_only() {
+                return Err(WorkflowError::InvalidMapping {
+                    step: self.name.to_string(),
+                    reason: "Resource-only steps cannot use repeat_until. Only tool steps produce new values between iterations.".to_string(),
+                });
+            }
+            if self.max_iterations == 0 {
+                return Err(WorkflowError::InvalidMapping {
+                    step: self.name.to_string(),
+                    reason: "max_iterations must be at least 1".to_string(),
+                });
+            }
+            self.check_condition_bindings(condition, available_bindings, true)?;
+        }
+
+        if self
+            .retry_policy
+            .as_ref()
+            .is_some_and(|p| p.max_attempts() == 0)
+        {
+            return Err(WorkflowError::InvalidMapping {
+                step: self.name.to_string(),
+                reason: "Retry policies must allow at least one attempt".to_string(),
+            });
+        }
+
+        // Fallbacks run in place of this step, so they see the same bindings
+        if let OnError::Fallback(fallback) = &self.on_error {
+            let reason = if self.is_resource_only() {
+                Some("Resource-only steps cannot have a fallback step")
+            } else if fallback.is_resource_only() {
+                Some("Fallback steps must call a tool")
+            } else if matches!(fallback.on_error, OnError::Fallback(_)) {
+                Some("Fallback steps cannot have a fallback of their own")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                return Err(WorkflowError::InvalidMapping {
+                    step: self.name.to_string(),
+                    reason: reason.to_string(),
+                });
+            }
+            fallback.validate(available_bindings)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_condition_bindings(
+        &self,
+        condition: &Condition,
+        available_bindings: &[BindingName],
+        own_binding: bool,
+    ) -> Result<(), WorkflowError> {
+        for binding in condition.bindings() {
+            let is_own = own_binding && self.binding.as_ref() == Some(&binding);
+            if !is_own && !available_bindings.contains(&binding) {
+                return Err(WorkflowError::UnknownBinding {
+                    step: self.name.to_string(),
+                    binding: binding.to_string(),
+                });
+            }
+        }
         Ok(())
     }
 }
@@ -776,4 +997,85 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<WorkflowStep>();
     }
+
+    #[test]
+    fn test_branch_if_validation() {
+        use crate::server::workflow::dsl::field;
+
+        let step = WorkflowStep::new("merge", ToolHandle::new("merge"))
+            .branch_if(Condition::truthy(field("review", "approved")));
+        assert!(step.branch_condition().is_some());
+        assert!(step.validate(&[BindingName::new("review")]).is_ok());
+
+        match step.validate(&[]) {
+            Err(WorkflowError::UnknownBinding { step, binding }) => {
+                assert_eq!(step, "merge");
+                assert_eq!(binding, "review");
+            },
+            _ => panic!("Expected UnknownBinding error"),
+        }
+    }
+
+    #[test]
+    fn test_repeat_until_validation() {
+        use crate::server::workflow::dsl::field;
+
+        let done = Condition::equals(field("job", "state"), json!("done"));
+        let step = WorkflowStep::new("poll", ToolHandle::new("job_status"))
+            .bind("job")
+            .repeat_until(done.clone());
+        assert_eq!(step.iteration_limit(), DEFAULT_MAX_ITERATIONS);
+        // The exit condition may reference the step's own binding
+        assert!(step.validate(&[]).is_ok());
+
+        let unbounded = step.clone().max_iterations(0);
+        assert!(matches!(
+            unbounded.validate(&[]),
+            Err(WorkflowError::InvalidMapping { .. })
+        ));
+
+        let resource_only = WorkflowStep::fetch_resources("fetch")
+            .with_resource("docs://guide")
+            .unwrap()
+            .repeat_until(Condition::truthy(field("other", "ok")));
+        match resource_only.validate(&[BindingName::new("other")]) {
+            Err(WorkflowError::InvalidMapping { reason, .. }) => {
+                assert!(reason.contains("repeat_until"));
+            },
+            _ => panic!("Expected InvalidMapping error"),
+        }
+    }
+
+    #[test]
+    fn test_error_policy_validation() {
+        let step = WorkflowStep::new("fetch", ToolHandle::new("fetch_prices"))
+            .retry(RetryPolicy::new(3))
+            .on_error(OnError::fallback(
+                WorkflowStep::new("cached", ToolHandle::new("cached_prices"))
+                    .arg("since", DataSource::from_step("last_sync")),
+            ));
+        assert!(matches!(step.error_handler(), OnError::Fallback(_)));
+        assert!(step.validate(&[BindingName::new("last_sync")]).is_ok());
+        assert!(matches!(
+            step.validate(&[]),
+            Err(WorkflowError::UnknownBinding { .. })
+        ));
+
+        let no_attempts =
+            WorkflowStep::new("fetch", ToolHandle::new("fetch")).retry(RetryPolicy::new(0));
+        assert!(no_attempts.validate(&[]).is_err());
+
+        let nested =
+            WorkflowStep::new("fetch", ToolHandle::new("fetch")).on_error(OnError::fallback(
+                WorkflowStep::new("cached", ToolHandle::new("cached")).on_error(OnError::fallback(
+                    WorkflowStep::new("x", ToolHandle::new("x")),
+                )),
+            ));
+        match nested.validate(&[]) {
+            Err(WorkflowError::InvalidMapping { reason, .. }) => {
+                assert!(reason.contains("fallback of their own"));
+            },
+            _ => panic!("Expected InvalidMapping error"),
+        }
+    }
 }
@@ -0,0 +1,412 @@
+//! Chunked framing for oversized stdio messages.
+//!
+//! stdio carries one JSON-RPC message per line, and some hosts read lines
+//! into fixed-size buffers, so a large tool result can break the connection.
+//! With chunking enabled on both ends (see
+//! [`StdioTransport::with_chunking`](crate::shared::StdioTransport::with_chunking)),
+//! a message longer than the peer's line limit is sent as a series of
+//! `notifications/pmcp/chunk` notifications that the receiving transport
+//! reassembles before handing the message on.
+//!
+//! # Negotiation
+//!
+//! Each side advertises the longest line it accepts under the experimental
+//! capability `pmcp/chunking` in its `initialize` request or result:
+//!
+//! ```json
+//! { "experimental": { "pmcp/chunking": { "maxLineBytes": 65536 } } }
+//! ```
+//!
+//! A side only chunks after it has seen the peer's advertisement, so peers
+//! without chunking support never receive chunk notifications.
+//!
+//! # Framing
+//!
+//! ```json
+//! { "jsonrpc": "2.0", "method": "notifications/pmcp/chunk",
+//!   "params": { "stream": 7, "index": 0, "total": 3, "data": "<base64>" } }
+//! ```
+//!
+//! `data` is a base64 slice of the serialized message; the message is
+//! complete once all `total` parts of a `stream` have arrived.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use base64::Engine;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+
+use crate::error::{Result, TransportError};
+use crate::shared::transport::TransportMessage;
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{ClientRequest, Request, RequestId};
+
+/// Experimental capability key under which each side advertises its line limit.
+pub const CHUNKING_CAPABILITY: &str = "pmcp/chunking";
+
+/// Method of the notifications carrying message chunks.
+pub const CHUNK_METHOD: &str = "notifications/pmcp/chunk";
+
+/// Smallest line limit a side may advertise.
+pub const MIN_LINE_BYTES: usize = 1024;
+
+/// Room left in each chunk line for the notification envelope.
+const ENVELOPE_BYTES: usize = 160;
+
+/// Upper bound on bytes buffered for incomplete messages.
+const MAX_PENDING_BYTES: usize = 64 * 1024 * 1024;
+
+/// Upper bound on the parts of one message (chunk lines are at least
+/// [`MIN_LINE_BYTES`] long).
+const MAX_PARTS: usize = MAX_PENDING_BYTES / 512;
+
+/// Chunking state of one stdio transport.
+#[derive(Debug)]
+pub(crate) struct StdioChunking {
+    /// Longest line this side accepts (advertised to the peer).
+    max_line_bytes: usize,
+    /// Longest line the peer accepts; `0` until the peer advertises.
+    peer_max_line_bytes: AtomicUsize,
+    /// ID of the `initialize` request this side sent (client), whose
+    /// response carries the server's advertisement.
+    sent_initialize: Mutex<Option<RequestId>>,
+    /// ID of the `initialize` request this side received (server), whose
+    /// response gets this side's advertisement.
+    received_initialize: Mutex<Option<RequestId>>,
+    next_stream: AtomicU64,
+    reassembler: Mutex<Reassembler>,
+}
+
+impl StdioChunking {
+    pub(crate) fn new(max_line_bytes: usize) -> Self {
+        Self {
+            max_line_bytes: max_line_bytes.max(MIN_LINE_BYTES),
+            peer_max_line_bytes: AtomicUsize::new(0),
+            sent_initialize: Mutex::new(None),
+            received_initialize: Mutex::new(None),
+            next_stream: AtomicU64::new(0),
+            reassembler: Mutex::new(Reassembler::default()),
+        }
+    }
+
+    fn advertisement(&self) -> Value {
+        json!({ "maxLineBytes": self.max_line_bytes })
+    }
+
+    /// Add this side's advertisement to an outgoing `initialize` request or
+    /// result.
+    pub(crate) fn on_send(&self, message: &mut TransportMessage) {
+        match message {
+            TransportMessage::Request {
+                id,
+                request: Request::Client(request),
+            } => {
+                if let ClientRequest::Initialize(params) = request.as_mut() {
+                    params
+                        .capabilities
+                        .experimental
+                        .get_or_insert_with(HashMap::new)
+                        .insert(CHUNKING_CAPABILITY.to_string(), self.advertisement());
+                    *self.sent_initialize.lock() = Some(id.clone());
+                }
+            },
+            TransportMessage::Response(response) => {
+                let mut received = self.received_initialize.lock();
+                if received.as_ref() != Some(&response.id) {
+                    return;
+                }
+                *received = None;
+                if let ResponsePayload::Result(result) = &mut response.payload {
+                    if let Some(capabilities) = result
+                        .get_mut("capabilities")
+                        .and_then(Value::as_object_mut)
+                    {
+                        let experimental = capabilities
+                            .entry("experimental")
+                            .or_insert_with(|| json!({}));
+                        if let Some(experimental) = experimental.as_object_mut() {
+                            experimental
+                                .insert(CHUNKING_CAPABILITY.to_string(), self.advertisement());
+                        }
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Record the peer's advertisement from an incoming `initialize` request
+    /// or result.
+    pub(crate) fn on_receive(&self, message: &TransportMessage) {
+        let advertised = match message {
+            TransportMessage::Request {
+                id,
+                request: Request::Client(request),
+            } => match request.as_ref() {
+                ClientRequest::Initialize(params) => {
+                    *self.received_initialize.lock() = Some(id.clone());
+                    params
+                        .capabilities
+                        .experimental
+                        .as_ref()
+                        .and_then(|e| e.get(CHUNKING_CAPABILITY))
+                        .cloned()
+                },
+                _ => None,
+            },
+            TransportMessage::Response(response) => {
+                let mut sent = self.sent_initialize.lock();
+                if sent.as_ref() != Some(&response.id) {
+                    return;
+                }
+                *sent = None;
+                match &response.payload {
+                    // `~1` escapes the `/` in the capability key.
+                    ResponsePayload::Result(result) => result
+                        .pointer("/capabilities/experimental/pmcp~1chunking")
+                        .cloned(),
+                    ResponsePayload::Error(_) => None,
+                }
+            },
+            _ => None,
+        };
+
+        if let Some(max) = advertised
+            .as_ref()
+            .and_then(|a| a.get("maxLineBytes"))
+            .and_then(Value::as_u64)
+        {
+            let max = usize::try_from(max).unwrap_or(usize::MAX);
+            self.peer_max_line_bytes
+                .store(max.max(MIN_LINE_BYTES), Ordering::Release);
+        }
+    }
+
+    /// Split a serialized message into the lines to write.
+    ///
+    /// Returns the message unchanged unless the peer supports chunking and
+    /// the message exceeds its line limit.
+    pub(crate) fn frame(&self, message: Vec<u8>) -> Vec<Vec<u8>> {
+        let peer_max = self.peer_max_line_bytes.load(Ordering::Acquire);
+        if peer_max == 0 || message.len() <= peer_max {
+            return vec![message];
+        }
+
+        // Base64 turns every 3 bytes into 4 characters.
+        let slice_bytes = (peer_max.saturating_sub(ENVELOPE_BYTES) / 4 * 3).max(3);
+        let stream = self.next_stream.fetch_add(1, Ordering::Relaxed);
+        let total = message.len().div_ceil(slice_bytes);
+        message
+            .chunks(slice_bytes)
+            .enumerate()
+            .map(|(index, slice)| {
+                let chunk = json!({
+                    "jsonrpc": "2.0",
+                    "method": CHUNK_METHOD,
+                    "params": {
+                        "stream": stream,
+                        "index": index,
+                        "total": total,
+                        "data": base64::engine::general_purpose::STANDARD.encode(slice),
+                    }
+                });
+                serde_json::to_vec(&chunk).unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Feed an incoming line through reassembly.
+    ///
+    /// Returns the line itself if it is not a chunk, the reassembled message
+    /// once its last chunk arrives, and `None` while chunks are outstanding.
+    pub(crate) fn accept_line(&self, line: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        if !is_chunk_line(&line) {
+            return Ok(Some(line));
+        }
+        let chunk: Value = serde_json::from_slice(&line)
+            .map_err(|e| TransportError::InvalidMessage(format!("Invalid chunk: {}", e)))?;
+        if chunk.get("method").and_then(Value::as_str) != Some(CHUNK_METHOD) {
+            return Ok(Some(line));
+        }
+        self.reassembler.lock().accept(&chunk["params"])
+    }
+}
+
+/// Cheap pre-check that avoids parsing every line as JSON.
+fn is_chunk_line(line: &[u8]) -> bool {
+    let head = &line[..line.len().min(128)];
+    head.windows(CHUNK_METHOD.len())
+        .any(|w| w == CHUNK_METHOD.as_bytes())
+}
+
+#[derive(Debug)]
+struct PartialMessage {
+    parts: Vec<Option<Vec<u8>>>,
+    received: usize,
+}
+
+/// Buffers chunks until every part of a message has arrived.
+#[derive(Debug, Default)]
+struct Reassembler {
+    streams: HashMap<u64, PartialMessage>,
+    pending_bytes: usize,
+}
+
+impl Reassembler {
+    fn accept(&mut self, params: &Value) -> Result<Option<Vec<u8>>> {
+        let invalid =
+            |reason: &str| TransportError::InvalidMessage(format!("Invalid chunk: {}", reason));
+        let field = |name: &str| {
+            params
+                .get(name)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| invalid(&format!("missing {}", name)))
+        };
+        let stream = field("stream")?;
+        let index = usize::try_from(field("index")?).map_err(|_| invalid("index too large"))?;
+        let total = usize::try_from(field("total")?).map_err(|_| invalid("total too large"))?;
+        let data = params
+            .get("data")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid("missing data"))?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| invalid(&e.to_string()))?;
+
+        if total == 0 || index >= total || total > MAX_PARTS {
+            return Err(invalid("index out of range").into());
+        }
+        if self.pending_bytes + data.len() > MAX_PENDING_BYTES {
+            if let Some(dropped) = self.streams.remove(&stream) {
+                self.pending_bytes -= buffered_bytes(&dropped);
+            }
+            return Err(invalid("message exceeds the reassembly limit").into());
+        }
+
+        let partial = self
+            .streams
+            .entry(stream)
+            .or_insert_with(|| PartialMessage {
+                parts: vec![None; total],
+                received: 0,
+            });
+        if partial.parts.len() != total {
+            return Err(invalid("inconsistent total").into());
+        }
+        if partial.parts[index].is_none() {
+            self.pending_bytes += data.len();
+            partial.parts[index] = Some(data);
+            partial.received += 1;
+        }
+        if partial.received < total {
+            return Ok(None);
+        }
+
+        let complete = self
+            .streams
+            .remove(&stream)
+            .expect("stream was just updated");
+        self.pending_bytes -= buffered_bytes(&complete);
+        Ok(Some(
+            complete.parts.into_iter().flatten().flatten().collect(),
+        ))
+    }
+}
+
+fn buffered_bytes(partial: &PartialMessage) -> usize {
+    partial.parts.iter().flatten().map(Vec::len).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClientCapabilities, Implementation, InitializeRequest};
+
+    fn initialize_request() -> TransportMessage {
+        TransportMessage::Request {
+            id: RequestId::Number(1),
+            request: Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+                protocol_version: "2025-06-18".to_string(),
+                capabilities: ClientCapabilities::default(),
+                client_info: Implementation::new("test", "1.0.0"),
+            }))),
+        }
+    }
+
+    fn initialize_response(experimental: Value) -> TransportMessage {
+        TransportMessage::Response(crate::types::JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": { "experimental": experimental },
+                "serverInfo": { "name": "server", "version": "1.0.0" }
+            })),
+        })
+    }
+
+    #[test]
+    fn negotiates_through_initialize() {
+        let client = StdioChunking::new(4096);
+        let server = StdioChunking::new(2048);
+
+        let mut request = initialize_request();
+        client.on_send(&mut request);
+        server.on_receive(&request);
+        assert_eq!(server.peer_max_line_bytes.load(Ordering::Acquire), 4096);
+
+        let mut response = initialize_response(json!({}));
+        server.on_send(&mut response);
+        client.on_receive(&response);
+        assert_eq!(client.peer_max_line_bytes.load(Ordering::Acquire), 2048);
+    }
+
+    #[test]
+    fn does_not_chunk_without_peer_support() {
+        let client = StdioChunking::new(4096);
+        let mut request = initialize_request();
+        client.on_send(&mut request);
+        client.on_receive(&initialize_response(json!({})));
+
+        let message = vec![b'x'; 10_000];
+        assert_eq!(client.frame(message.clone()), vec![message]);
+    }
+
+    #[test]
+    fn chunks_and_reassembles_large_messages() {
+        let sender = StdioChunking::new(MIN_LINE_BYTES);
+        sender
+            .peer_max_line_bytes
+            .store(MIN_LINE_BYTES, Ordering::Release);
+        let receiver = StdioChunking::new(MIN_LINE_BYTES);
+
+        let message = serde_json::to_vec(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "text": "é".repeat(5000) }
+        }))
+        .unwrap();
+        let lines = sender.frame(message.clone());
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= MIN_LINE_BYTES));
+
+        // Deliver out of order: reassembly must not depend on arrival order.
+        let mut lines = lines;
+        lines.reverse();
+        let mut assembled = None;
+        for line in lines {
+            assert!(assembled.is_none());
+            assembled = receiver.accept_line(line).unwrap();
+        }
+        assert_eq!(assembled, Some(message));
+        assert_eq!(receiver.reassembler.lock().pending_bytes, 0);
+    }
+
+    #[test]
+    fn passes_through_ordinary_lines() {
+        let receiver = StdioChunking::new(MIN_LINE_BYTES);
+        let line = br#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#.to_vec();
+        assert_eq!(receiver.accept_line(line.clone()).unwrap(), Some(line));
+    }
+}
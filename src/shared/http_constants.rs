@@ -16,6 +16,12 @@ pub const ACCEPT: &str = "Accept";
 /// HTTP Content-Type header name
 pub const CONTENT_TYPE: &str = "Content-Type";
 
+/// W3C Trace Context `traceparent` header name
+pub const TRACEPARENT: &str = "traceparent";
+
+/// W3C Trace Context `tracestate` header name
+pub const TRACESTATE: &str = "tracestate";
+
 // Content Types
 /// JSON content type value
 pub const APPLICATION_JSON: &str = "application/json";
@@ -18,6 +18,8 @@ pub mod sse_parser;
 #[cfg(feature = "sse")]
 pub mod sse_optimized;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod chunking;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod connection_pool;
 #[cfg(not(target_arch = "wasm32"))]
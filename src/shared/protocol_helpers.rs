@@ -255,6 +255,35 @@ fn client_request_to_jsonrpc(req: ClientRequest) -> (String, Option<Value>) {
         ClientRequest::TasksResult(params) => create_method_params("tasks/result", params),
         ClientRequest::TasksList(params) => create_method_params("tasks/list", params),
         ClientRequest::TasksCancel(params) => create_method_params("tasks/cancel", params),
+        ClientRequest::TasksProvideInput(params) => {
+            create_method_params("tasks/provide_input", params)
+        },
+    }
+}
+
+/// The JSON-RPC method name of a client request.
+#[cfg(feature = "streamable-http")]
+pub(crate) fn client_request_method(req: &ClientRequest) -> &'static str {
+    match req {
+        ClientRequest::Initialize(_) => "initialize",
+        ClientRequest::Ping => "ping",
+        ClientRequest::SetLoggingLevel { .. } => "logging/setLevel",
+        ClientRequest::ListTools(_) => "tools/list",
+        ClientRequest::CallTool(_) => "tools/call",
+        ClientRequest::ListPrompts(_) => "prompts/list",
+        ClientRequest::GetPrompt(_) => "prompts/get",
+        ClientRequest::ListResources(_) => "resources/list",
+        ClientRequest::ListResourceTemplates(_) => "resources/templates/list",
+        ClientRequest::ReadResource(_) => "resources/read",
+        ClientRequest::Subscribe(_) => "resources/subscribe",
+        ClientRequest::Unsubscribe(_) => "resources/unsubscribe",
+        ClientRequest::Complete(_) => "completion/complete",
+        ClientRequest::CreateMessage(_) => "sampling/createMessage",
+        ClientRequest::TasksGet(_) => "tasks/get",
+        ClientRequest::TasksResult(_) => "tasks/result",
+        ClientRequest::TasksList(_) => "tasks/list",
+        ClientRequest::TasksCancel(_) => "tasks/cancel",
+        ClientRequest::TasksProvideInput(_) => "tasks/provide_input",
     }
 }
 
@@ -756,7 +785,7 @@ mod tests {
         // Test all ClientRequest variants to ensure complete coverage
         let test_cases = vec![
             (
-                ClientRequest::ListPrompts(ListPromptsRequest { cursor: None }),
+                ClientRequest::ListPrompts(ListPromptsRequest::default()),
                 "prompts/list",
             ),
             (
@@ -776,10 +805,7 @@ mod tests {
                 "resources/templates/list",
             ),
             (
-                ClientRequest::ReadResource(ReadResourceRequest {
-                    uri: "test://uri".to_string(),
-                    _meta: None,
-                }),
+                ClientRequest::ReadResource(ReadResourceRequest::new("test://uri")),
                 "resources/read",
             ),
             (
@@ -803,6 +829,7 @@ mod tests {
                         name: "test".to_string(),
                         value: "val".to_string(),
                     },
+                    context: None,
                 }),
                 "completion/complete",
             ),
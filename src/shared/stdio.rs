@@ -4,6 +4,7 @@
 //! JSON-RPC messages as per the MCP specification.
 
 use crate::error::{Result, TransportError};
+use crate::shared::chunking::StdioChunking;
 use crate::shared::transport::{Transport, TransportMessage};
 use async_trait::async_trait;
 #[cfg(not(target_arch = "wasm32"))]
@@ -29,9 +30,11 @@ use tokio::sync::Mutex;
 /// ```
 #[derive(Debug)]
 pub struct StdioTransport {
-    stdin: Mutex<BufReader<tokio::io::Stdin>>,
+    /// Reader plus the bytes of a line whose read was cancelled part-way
+    stdin: Mutex<(BufReader<tokio::io::Stdin>, Vec<u8>)>,
     stdout: Mutex<tokio::io::Stdout>,
     closed: std::sync::atomic::AtomicBool,
+    chunking: Option<StdioChunking>,
 }
 
 impl StdioTransport {
@@ -47,11 +50,35 @@ impl StdioTransport {
     /// ```
     pub fn new() -> Self {
         Self {
-            stdin: Mutex::new(BufReader::new(tokio::io::stdin())),
+            stdin: Mutex::new((BufReader::new(tokio::io::stdin()), Vec::new())),
             stdout: Mutex::new(tokio::io::stdout()),
             closed: std::sync::atomic::AtomicBool::new(false),
+            chunking: None,
         }
     }
+
+    /// Split messages longer than the peer's line limit into chunks, and
+    /// accept lines of up to `max_line_bytes` from the peer.
+    ///
+    /// Chunking is negotiated during `initialize`: each side advertises its
+    /// limit and only chunks once the peer has advertised one, so enabling
+    /// it is safe against peers that do not support it. Enable it on both
+    /// the client and the server transport. See
+    /// [`chunking`](crate::shared::chunking) for the wire format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::shared::StdioTransport;
+    ///
+    /// // Keep every line under 64 KiB
+    /// let transport = StdioTransport::new().with_chunking(64 * 1024);
+    /// ```
+    #[must_use]
+    pub fn with_chunking(mut self, max_line_bytes: usize) -> Self {
+        self.chunking = Some(StdioChunking::new(max_line_bytes));
+        self
+    }
 }
 
 impl Default for StdioTransport {
@@ -67,8 +94,18 @@ impl Transport for StdioTransport {
             return Err(TransportError::ConnectionClosed.into());
         }
 
+        let Some(chunking) = &self.chunking else {
+            let json_bytes = Self::serialize_message(&message)?;
+            return self.write_message(&json_bytes).await;
+        };
+
+        let mut message = message;
+        chunking.on_send(&mut message);
         let json_bytes = Self::serialize_message(&message)?;
-        self.write_message(&json_bytes).await
+        for line in chunking.frame(json_bytes) {
+            self.write_message(&line).await?;
+        }
+        Ok(())
     }
 
     async fn receive(&mut self) -> Result<TransportMessage> {
@@ -76,8 +113,19 @@ impl Transport for StdioTransport {
             return Err(TransportError::ConnectionClosed.into());
         }
 
-        let buffer = self.read_line().await?;
-        Self::parse_message(&buffer)
+        let Some(chunking) = &self.chunking else {
+            let buffer = self.read_line().await?;
+            return Self::parse_message(&buffer);
+        };
+
+        loop {
+            let line = self.read_line().await?;
+            if let Some(buffer) = chunking.accept_line(line)? {
+                let message = Self::parse_message(&buffer)?;
+                chunking.on_receive(&message);
+                return Ok(message);
+            }
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -158,16 +206,20 @@ impl StdioTransport {
     }
 
     /// Read a line from stdin (newline-delimited JSON per MCP spec)
+    ///
+    /// Cancel-safe: `read_until` appends to the buffer kept next to the
+    /// reader, so a read cancelled part-way (e.g. by a server writing while
+    /// it waits for input) resumes where it stopped.
     async fn read_line(&self) -> Result<Vec<u8>> {
         let mut stdin = self.stdin.lock().await;
-        let mut line = String::new();
+        let (reader, pending) = &mut *stdin;
 
-        let bytes_read = stdin
-            .read_line(&mut line)
+        let bytes_read = reader
+            .read_until(b'\n', pending)
             .await
             .map_err(TransportError::from)?;
 
-        if bytes_read == 0 {
+        if bytes_read == 0 && pending.is_empty() {
             // EOF reached
             drop(stdin);
             self.closed
@@ -175,18 +227,20 @@ impl StdioTransport {
             return Err(TransportError::ConnectionClosed.into());
         }
 
-        // Remove trailing newline and return as bytes
-        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        let mut line = std::mem::take(pending);
+        drop(stdin);
+
+        // Remove trailing newline
+        while matches!(line.last(), Some(b'\n' | b'\r')) {
+            line.pop();
+        }
 
         // Skip empty lines (per MCP spec: messages are delimited by newlines)
         if line.is_empty() {
-            drop(stdin);
             return Err(TransportError::InvalidMessage("Empty line received".to_string()).into());
         }
 
-        let bytes = line.as_bytes().to_vec();
-        drop(stdin);
-        Ok(bytes)
+        Ok(line)
     }
 
     /// Parse JSON message and determine its type.
@@ -8,6 +8,7 @@ use crate::shared::{Transport, TransportMessage};
 use async_trait::async_trait;
 use bytes::Bytes;
 use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
 use hyper::{Method, Request, Response as HyperResponse, StatusCode};
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
@@ -16,6 +17,8 @@ use std::fmt::Debug;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::mpsc;
+use tower::ServiceExt;
+use tower_http::decompression::{Decompression, DecompressionBody};
 use url::Url;
 
 /// Options for sending messages over streamable HTTP transport.
@@ -52,48 +55,36 @@ pub struct SendOptions {
 
 /// Configuration for the `StreamableHttpTransport`.
 ///
+/// This struct is `#[non_exhaustive]`. Build it with
+/// [`StreamableHttpTransportConfigBuilder`] and set fields on the result to
+/// remain forward-compatible.
+///
 /// # Examples
 ///
 /// ```rust
-/// use pmcp::shared::streamable_http::StreamableHttpTransportConfig;
+/// use pmcp::shared::streamable_http::StreamableHttpTransportConfigBuilder;
 /// use url::Url;
 ///
 /// // Minimal configuration for stateless operation
-/// let config = StreamableHttpTransportConfig {
-///     url: Url::parse("http://localhost:8080").unwrap(),
-///     extra_headers: vec![],
-///     auth_provider: None,
-///     session_id: None,
-///     enable_json_response: false,
-///     on_resumption_token: None,
-///     http_middleware_chain: None,
-/// };
+/// let config =
+///     StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+///         .build();
 ///
 /// // Configuration with session for stateful operation
-/// let config = StreamableHttpTransportConfig {
-///     url: Url::parse("http://localhost:8080").unwrap(),
-///     extra_headers: vec![
-///         ("X-API-Key".to_string(), "secret".to_string()),
-///     ],
-///     auth_provider: None,
-///     session_id: Some("session-123".to_string()),
-///     enable_json_response: false,
-///     on_resumption_token: None,
-///     http_middleware_chain: None,
-/// };
+/// let config =
+///     StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+///         .with_header("X-API-Key", "secret")
+///         .with_session_id("session-123")
+///         .build();
 ///
 /// // Configuration for simple request/response (no streaming)
-/// let config = StreamableHttpTransportConfig {
-///     url: Url::parse("http://localhost:8080").unwrap(),
-///     extra_headers: vec![],
-///     auth_provider: None,
-///     session_id: None,
-///     enable_json_response: true,  // JSON instead of SSE
-///     on_resumption_token: None,
-///     http_middleware_chain: None,
-/// };
+/// let mut config =
+///     StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+///         .build();
+/// config.enable_json_response = true; // JSON instead of SSE
 /// ```
 #[derive(Clone)]
+#[non_exhaustive]
 pub struct StreamableHttpTransportConfig {
     /// The HTTP endpoint URL
     pub url: Url,
@@ -107,6 +98,8 @@ pub struct StreamableHttpTransportConfig {
     pub enable_json_response: bool,
     /// Callback when resumption token is received
     pub on_resumption_token: Option<Arc<dyn Fn(String) + Send + Sync>>,
+    /// Callback with the outcome of each resumed SSE stream
+    pub on_resume: Option<Arc<dyn Fn(ResumeOutcome) + Send + Sync>>,
     /// HTTP middleware chain for request/response transformation
     pub http_middleware_chain: Option<Arc<crate::client::http_middleware::HttpMiddlewareChain>>,
 }
@@ -120,6 +113,7 @@ impl Debug for StreamableHttpTransportConfig {
             .field("session_id", &self.session_id)
             .field("enable_json_response", &self.enable_json_response)
             .field("on_resumption_token", &self.on_resumption_token.is_some())
+            .field("on_resume", &self.on_resume.is_some())
             .field(
                 "http_middleware_chain",
                 &self.http_middleware_chain.is_some(),
@@ -160,6 +154,7 @@ pub struct StreamableHttpTransportConfigBuilder {
     session_id: Option<String>,
     enable_json_response: bool,
     on_resumption_token: Option<Arc<dyn Fn(String) + Send + Sync>>,
+    on_resume: Option<Arc<dyn Fn(ResumeOutcome) + Send + Sync>>,
     http_middleware_chain: Option<Arc<crate::client::http_middleware::HttpMiddlewareChain>>,
 }
 
@@ -172,6 +167,7 @@ impl Debug for StreamableHttpTransportConfigBuilder {
             .field("session_id", &self.session_id)
             .field("enable_json_response", &self.enable_json_response)
             .field("on_resumption_token", &self.on_resumption_token.is_some())
+            .field("on_resume", &self.on_resume.is_some())
             .field(
                 "http_middleware_chain",
                 &self.http_middleware_chain.is_some(),
@@ -190,6 +186,7 @@ impl StreamableHttpTransportConfigBuilder {
             session_id: None,
             enable_json_response: false,
             on_resumption_token: None,
+            on_resume: None,
             http_middleware_chain: None,
         }
     }
@@ -224,6 +221,16 @@ impl StreamableHttpTransportConfigBuilder {
         self
     }
 
+    /// Set callback for the outcome of resumed SSE streams.
+    ///
+    /// Called once per [`StreamableHttpTransport::start_sse`] with a
+    /// resumption token, after the replayed events have been delivered, so
+    /// the application can re-sync state when the replay was lossy.
+    pub fn on_resume(mut self, callback: Arc<dyn Fn(ResumeOutcome) + Send + Sync>) -> Self {
+        self.on_resume = Some(callback);
+        self
+    }
+
     /// Set the HTTP middleware chain for request/response transformation.
     ///
     /// HTTP middleware operates at the transport layer, before protocol processing.
@@ -270,11 +277,116 @@ impl StreamableHttpTransportConfigBuilder {
             session_id: self.session_id,
             enable_json_response: self.enable_json_response,
             on_resumption_token: self.on_resumption_token,
+            on_resume: self.on_resume,
             http_middleware_chain: self.http_middleware_chain,
         }
     }
 }
 
+/// How completely a resumed SSE stream replayed the events the client missed.
+///
+/// Servers that number events per session (such as pmcp's
+/// [`StreamableHttpServer`](crate::server::streamable_http_server::StreamableHttpServer))
+/// let the client spot events that were evicted from the server's event store
+/// before it reconnected. For other servers completeness cannot be checked.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::streamable_http::{ResumeOutcome, StreamableHttpTransportConfigBuilder};
+/// use std::sync::Arc;
+/// use url::Url;
+///
+/// let config = StreamableHttpTransportConfigBuilder::new(
+///         Url::parse("http://localhost:8080").unwrap()
+///     )
+///     .on_resume(Arc::new(|outcome: ResumeOutcome| {
+///         if outcome.is_lossy() {
+///             // Notifications were lost: refetch resource and tool lists.
+///         }
+///     }))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeOutcome {
+    /// Every event after the resumption token was replayed.
+    CaughtUp {
+        /// Number of events the server replayed
+        replayed: usize,
+    },
+    /// Some events after the resumption token are gone for good.
+    Lossy {
+        /// Number of events the server replayed
+        replayed: usize,
+        /// Number of events that were not replayed
+        missed: u64,
+    },
+    /// The server's event ids carry no sequence, so gaps cannot be detected.
+    Unverified {
+        /// Number of events the server replayed
+        replayed: usize,
+    },
+}
+
+impl ResumeOutcome {
+    /// Work out the outcome of resuming from `token` given the ids of the
+    /// replayed events, in the order they arrived.
+    fn from_replay<'a>(token: &str, replayed: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut count = 0;
+        let mut sequenced = 0;
+        let mut seen = std::collections::BTreeSet::new();
+        let resumed_from = parse_sequenced_event_id(token);
+        for id in replayed {
+            count += 1;
+            let Some((stream, seq)) = parse_sequenced_event_id(id) else {
+                continue;
+            };
+            sequenced += 1;
+            if let Some((token_stream, token_seq)) = resumed_from {
+                if stream == token_stream && seq > token_seq {
+                    seen.insert(seq);
+                }
+            }
+        }
+
+        let Some((_, token_seq)) = resumed_from else {
+            return Self::Unverified { replayed: count };
+        };
+        if sequenced < count {
+            return Self::Unverified { replayed: count };
+        }
+        let expected = seen.last().map_or(0, |last| last - token_seq);
+        let missed = expected - seen.len() as u64;
+        if missed == 0 {
+            Self::CaughtUp { replayed: count }
+        } else {
+            Self::Lossy {
+                replayed: count,
+                missed,
+            }
+        }
+    }
+
+    /// Returns `true` if events were lost while the client was disconnected.
+    pub fn is_lossy(&self) -> bool {
+        matches!(self, Self::Lossy { .. })
+    }
+}
+
+/// Build the id of the `seq`th event in `stream`.
+pub(crate) fn sequenced_event_id(stream: &str, seq: u64) -> String {
+    format!("{stream}:{seq}")
+}
+
+/// Split an id built by [`sequenced_event_id`] into its stream and sequence.
+pub(crate) fn parse_sequenced_event_id(id: &str) -> Option<(&str, u64)> {
+    let (stream, seq) = id.rsplit_once(':')?;
+    if stream.is_empty() || !seq.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((stream, seq.parse().ok()?))
+}
+
 /// A streamable HTTP transport for MCP.
 ///
 /// This transport supports both stateless and stateful operation modes:
@@ -285,6 +397,9 @@ impl StreamableHttpTransportConfigBuilder {
 ///
 /// HTTPS is supported via rustls with the ring crypto provider, which is compatible
 /// with AWS Lambda and other serverless environments.
+///
+/// Requests advertise `Accept-Encoding: gzip, zstd`, and compressed responses
+/// are decoded transparently.
 #[derive(Clone)]
 pub struct StreamableHttpTransport {
     config: Arc<RwLock<StreamableHttpTransportConfig>>,
@@ -450,11 +565,7 @@ impl StreamableHttpTransport {
         }
 
         // Send request
-        let response = self
-            .client
-            .request(request)
-            .await
-            .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))?;
+        let response = self.execute(request).await?;
 
         // Handle 405 (SSE not supported) gracefully
         if response.status() == StatusCode::METHOD_NOT_ALLOWED {
@@ -496,6 +607,7 @@ impl StreamableHttpTransport {
         // Start streaming task
         let sender = self.sender.clone();
         let on_resumption = self.config.read().on_resumption_token.clone();
+        let on_resume = self.config.read().on_resume.clone();
         let last_event_id = self.last_event_id.clone();
 
         let handle = tokio::spawn(async move {
@@ -504,7 +616,7 @@ impl StreamableHttpTransport {
 
             // Parse SSE events
             let events = sse_parser.feed(&body);
-            for event in events {
+            for event in &events {
                 // Update last event ID and notify callback
                 if let Some(id) = &event.id {
                     *last_event_id.write() = Some(id.clone());
@@ -523,6 +635,12 @@ impl StreamableHttpTransport {
                     }
                 }
             }
+
+            // Report whether the replay covered everything since the token
+            if let (Some(token), Some(callback)) = (&resumption_token, &on_resume) {
+                let replayed = events.iter().filter_map(|event| event.id.as_deref());
+                callback(ResumeOutcome::from_replay(token, replayed));
+            }
         });
 
         *self.abort_handle.write() = Some(handle);
@@ -670,6 +788,18 @@ impl StreamableHttpTransport {
         }
     }
 
+    /// Send a request, advertising gzip and zstd support and decoding
+    /// compressed response bodies.
+    async fn execute(
+        &self,
+        request: Request<Full<Bytes>>,
+    ) -> Result<HyperResponse<DecompressionBody<Incoming>>> {
+        Decompression::new(self.client.clone())
+            .oneshot(request)
+            .await
+            .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))
+    }
+
     /// Process response headers and extract session/protocol information
     fn process_response_headers(&self, response: &HyperResponse<impl hyper::body::Body>) {
         // Update session ID from response header
@@ -730,11 +860,7 @@ impl StreamableHttpTransport {
         );
 
         // Send request
-        let response = self
-            .client
-            .request(request)
-            .await
-            .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))?;
+        let response = self.execute(request).await?;
 
         // Process headers for session and protocol info
         self.process_response_headers(&response);
@@ -966,7 +1092,7 @@ impl Transport for StreamableHttpTransport {
                 .await?;
 
             // Send DELETE request (ignore 405 as per spec)
-            let response = self.client.request(request).await;
+            let response = self.execute(request).await;
             if let Ok(resp) = response {
                 if !resp.status().is_success() && resp.status() != StatusCode::METHOD_NOT_ALLOWED {
                     // Log error but don't fail close operation
@@ -994,3 +1120,70 @@ pub trait AuthProvider: Send + Sync + Debug {
     /// Returns an access token.
     async fn get_access_token(&self) -> Result<String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequenced_event_id_round_trip() {
+        let id = sequenced_event_id("session-1", 42);
+        assert_eq!(parse_sequenced_event_id(&id), Some(("session-1", 42)));
+        assert_eq!(parse_sequenced_event_id("a:b:7"), Some(("a:b", 7)));
+        assert_eq!(
+            parse_sequenced_event_id("0f8fad5b-d9cb-469f-a165-70867728950e"),
+            None
+        );
+        assert_eq!(parse_sequenced_event_id(":7"), None);
+        assert_eq!(parse_sequenced_event_id("s:+7"), None);
+    }
+
+    #[test]
+    fn test_resume_caught_up() {
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:4", "s:5", "s:6"]);
+        assert_eq!(outcome, ResumeOutcome::CaughtUp { replayed: 3 });
+        assert!(!outcome.is_lossy());
+
+        let outcome = ResumeOutcome::from_replay("s:3", []);
+        assert_eq!(outcome, ResumeOutcome::CaughtUp { replayed: 0 });
+    }
+
+    #[test]
+    fn test_resume_out_of_order_replay_is_not_a_gap() {
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:5", "s:4"]);
+        assert_eq!(outcome, ResumeOutcome::CaughtUp { replayed: 2 });
+    }
+
+    #[test]
+    fn test_resume_detects_evicted_events() {
+        // Events 4 and 5 were evicted before the client reconnected
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:6", "s:7"]);
+        assert_eq!(
+            outcome,
+            ResumeOutcome::Lossy {
+                replayed: 2,
+                missed: 2
+            }
+        );
+        assert!(outcome.is_lossy());
+
+        // A hole in the middle counts too
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:4", "s:6"]);
+        assert!(matches!(outcome, ResumeOutcome::Lossy { missed: 1, .. }));
+    }
+
+    #[test]
+    fn test_resume_ignores_already_seen_events() {
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:2", "s:3", "s:4"]);
+        assert_eq!(outcome, ResumeOutcome::CaughtUp { replayed: 3 });
+    }
+
+    #[test]
+    fn test_resume_unsequenced_ids_are_unverified() {
+        let outcome = ResumeOutcome::from_replay("evt-abc", ["evt-def"]);
+        assert_eq!(outcome, ResumeOutcome::Unverified { replayed: 1 });
+
+        let outcome = ResumeOutcome::from_replay("s:3", ["s:4", "evt-def"]);
+        assert_eq!(outcome, ResumeOutcome::Unverified { replayed: 2 });
+    }
+}
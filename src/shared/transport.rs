@@ -205,6 +205,10 @@ pub trait Transport: Send + Sync + Debug {
     ///
     /// This method should block until a complete message is available.
     /// It should handle any necessary buffering and framing internally.
+    ///
+    /// It should also be cancel-safe: `Server::run` drops a pending receive
+    /// to send responses and notifications, and calls it again afterwards.
+    /// Partially read input must be kept for the next call, not discarded.
     async fn receive(&mut self) -> Result<TransportMessage>;
 
     /// Close the transport.
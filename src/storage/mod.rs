@@ -0,0 +1,273 @@
+//! Persistent key-value storage for tool handlers.
+//!
+//! Small servers often need a little durable state, such as counters, user
+//! preferences, or cached API responses, without running a database.
+//! [`KvStore`] is a uniform byte-oriented API for that, with backends that
+//! can be swapped without touching handler code:
+//!
+//! - [`MemoryKvStore`] - In-process store for development and tests.
+//! - [`RedbKvStore`](redb::RedbKvStore) - Embedded single-file database,
+//!   behind the `storage-redb` feature.
+//! - [`RedisKvStore`](redis::RedisKvStore) - Redis, for multi-instance
+//!   deployments, behind the `storage-redis` feature.
+//!
+//! Register a store with `Server::builder().kv_store(...)` and reach it from
+//! handlers through
+//! [`RequestHandlerExtra::kv`](crate::server::cancellation::RequestHandlerExtra::kv).
+//! [`KvStoreExt`] adds JSON helpers on top of the raw byte API.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::storage::{KvStore, KvStoreExt, MemoryKvStore};
+//! use serde_json::json;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let store = MemoryKvStore::new();
+//!
+//! assert_eq!(store.increment("visits", 1).await?, 1);
+//! assert_eq!(store.increment("visits", 2).await?, 3);
+//!
+//! store.set_json("prefs:alice", &json!({"units": "metric"}), None).await?;
+//! let prefs: Option<serde_json::Value> = store.get_json("prefs:alice").await?;
+//! assert_eq!(prefs, Some(json!({"units": "metric"})));
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "storage-redb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "storage-redb")))]
+pub mod redb;
+#[cfg(feature = "storage-redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "storage-redis")))]
+pub mod redis;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// Byte-oriented key-value store shared by tool handlers.
+///
+/// Implementations must be `Send + Sync` for concurrent access from
+/// multiple request handlers. Counters written by
+/// [`increment`](Self::increment) are stored as decimal strings, so they can
+/// also be read with [`get`](Self::get).
+#[async_trait]
+pub trait KvStore: Send + Sync {
+    /// Get the value stored under `key`.
+    ///
+    /// Returns `None` if the key is missing or has expired.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, replacing any existing value.
+    ///
+    /// With a `ttl`, the key expires that long after this write.
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()>;
+
+    /// Remove `key`, returning `true` if it existed.
+    async fn delete(&self, key: &str) -> Result<bool>;
+
+    /// Atomically add `delta` to the counter at `key`, returning the new value.
+    ///
+    /// A missing key counts as zero. An existing expiry is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the existing value is not an integer.
+    async fn increment(&self, key: &str, delta: i64) -> Result<i64>;
+
+    /// List the live keys starting with `prefix`, in no particular order.
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>>;
+}
+
+/// JSON helpers for any [`KvStore`].
+#[async_trait]
+pub trait KvStoreExt: KvStore {
+    /// Get and deserialize the JSON value stored under `key`.
+    async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        match self.get(key).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Serialize `value` as JSON and store it under `key`.
+    async fn set_json<T: Serialize + Sync + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        self.set(key, serde_json::to_vec(value)?, ttl).await
+    }
+}
+
+impl<S: KvStore + ?Sized> KvStoreExt for S {}
+
+/// Parse a stored counter value.
+///
+/// Shared by backends so every store reports non-integer values the same way.
+pub(crate) fn parse_counter(key: &str, bytes: &[u8]) -> Result<i64> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| Error::validation(format!("value at '{key}' is not an integer")))
+}
+
+/// Add `delta` to `current`, rejecting overflow.
+pub(crate) fn add_counter(key: &str, current: i64, delta: i64) -> Result<i64> {
+    current
+        .checked_add(delta)
+        .ok_or_else(|| Error::validation(format!("counter at '{key}' would overflow")))
+}
+
+/// Stored value with its optional expiry.
+#[derive(Debug)]
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| Instant::now() > at)
+    }
+}
+
+/// Thread-safe in-memory store using [`DashMap`].
+///
+/// Values live for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemoryKvStore {
+    entries: DashMap<String, Entry>,
+}
+
+impl MemoryKvStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop all expired entries, returning how many were removed.
+    pub fn cleanup_expired(&self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired());
+        before - self.entries.len()
+    }
+}
+
+#[async_trait]
+impl KvStore for MemoryKvStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let expired = match self.entries.get(key) {
+            Some(entry) if !entry.is_expired() => return Ok(Some(entry.value.clone())),
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            self.entries.remove_if(key, |_, entry| entry.is_expired());
+        }
+        Ok(None)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                value,
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+            },
+        );
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        Ok(self
+            .entries
+            .remove(key)
+            .is_some_and(|(_, entry)| !entry.is_expired()))
+    }
+
+    async fn increment(&self, key: &str, delta: i64) -> Result<i64> {
+        let mut entry = self.entries.entry(key.to_string()).or_insert(Entry {
+            value: b"0".to_vec(),
+            expires_at: None,
+        });
+        if entry.is_expired() {
+            entry.value = b"0".to_vec();
+            entry.expires_at = None;
+        }
+        let next = add_counter(key, parse_counter(key, &entry.value)?, delta)?;
+        entry.value = next.to_string().into_bytes();
+        Ok(next)
+    }
+
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .entries
+            .iter()
+            .filter(|entry| entry.key().starts_with(prefix) && !entry.is_expired())
+            .map(|entry| entry.key().clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_set_get_delete() {
+        let store = MemoryKvStore::new();
+        store.set("a", b"1".to_vec(), None).await.unwrap();
+        assert_eq!(store.get("a").await.unwrap(), Some(b"1".to_vec()));
+        assert!(store.delete("a").await.unwrap());
+        assert!(!store.delete("a").await.unwrap());
+        assert_eq!(store.get("a").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expires_values() {
+        let store = MemoryKvStore::new();
+        store
+            .set("a", b"1".to_vec(), Some(Duration::from_millis(10)))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(store.get("a").await.unwrap(), None);
+        assert!(store.keys("").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_increment() {
+        let store = MemoryKvStore::new();
+        assert_eq!(store.increment("n", 5).await.unwrap(), 5);
+        assert_eq!(store.increment("n", -2).await.unwrap(), 3);
+        assert_eq!(store.get("n").await.unwrap(), Some(b"3".to_vec()));
+
+        store.set("s", b"abc".to_vec(), None).await.unwrap();
+        assert!(store.increment("s", 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keys_by_prefix_and_json() {
+        let store = MemoryKvStore::new();
+        store
+            .set_json("user:1", &json!({"name": "a"}), None)
+            .await
+            .unwrap();
+        store.set_json("user:2", &json!(2), None).await.unwrap();
+        store.set_json("other", &json!(3), None).await.unwrap();
+
+        let mut keys = store.keys("user:").await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["user:1", "user:2"]);
+        let user: Option<serde_json::Value> = store.get_json("user:1").await.unwrap();
+        assert_eq!(user, Some(json!({"name": "a"})));
+    }
+}
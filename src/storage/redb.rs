@@ -0,0 +1,284 @@
+//! Embedded [`KvStore`] backed by a single-file [redb](https://docs.rs/redb) database.
+//!
+//! Values are stored with an 8-byte big-endian expiry prefix (Unix epoch
+//! milliseconds, `0` for no expiry). Expired values are skipped on read and
+//! removed on the next write to the same key. redb is synchronous, so every
+//! operation runs on Tokio's blocking pool.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::storage::redb::RedbKvStore;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let store = RedbKvStore::open("data/server.redb")?;
+//! let server = pmcp::Server::builder()
+//!     .name("notes")
+//!     .version("1.0.0")
+//!     .kv_store(Arc::new(store))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use ::redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{add_counter, parse_counter, KvStore};
+use crate::error::{Error, Result};
+
+const TABLE: TableDefinition<'static, &str, &[u8]> = TableDefinition::new("pmcp_kv");
+
+/// Length of the expiry prefix on every stored value.
+const EXPIRY_BYTES: usize = 8;
+
+/// [`KvStore`] persisted in a local redb database file.
+///
+/// Cheap to clone; clones share the same database handle.
+#[derive(Clone)]
+pub struct RedbKvStore {
+    db: Arc<Database>,
+}
+
+impl std::fmt::Debug for RedbKvStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedbKvStore").finish_non_exhaustive()
+    }
+}
+
+impl RedbKvStore {
+    /// Open the database at `path`, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let db = Database::create(path).map_err(|e| {
+            Error::internal(format!(
+                "failed to open redb database {}: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Run `op` against the database on the blocking pool.
+    async fn blocking<T, F>(&self, op: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Database) -> std::result::Result<T, StoreError> + Send + 'static,
+    {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || op(&db))
+            .await
+            .map_err(|e| Error::internal(format!("redb task failed: {e}")))?
+            .map_err(StoreError::into_error)
+    }
+}
+
+/// Errors raised inside blocking redb operations.
+enum StoreError {
+    Backend(String),
+    Pmcp(Error),
+}
+
+impl StoreError {
+    fn into_error(self) -> Error {
+        match self {
+            Self::Backend(message) => Error::internal(format!("redb: {message}")),
+            Self::Pmcp(error) => error,
+        }
+    }
+}
+
+macro_rules! backend_errors {
+    ($($error:ty),*) => {
+        $(impl From<$error> for StoreError {
+            fn from(error: $error) -> Self {
+                Self::Backend(error.to_string())
+            }
+        })*
+    };
+}
+
+backend_errors!(
+    ::redb::DatabaseError,
+    ::redb::TransactionError,
+    ::redb::TableError,
+    ::redb::StorageError,
+    ::redb::CommitError
+);
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+fn encode(value: &[u8], ttl: Option<Duration>) -> Vec<u8> {
+    let expires_at = ttl.map_or(0, |ttl| {
+        now_millis().saturating_add(u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX))
+    });
+    encode_with_expiry(value, expires_at)
+}
+
+fn encode_with_expiry(value: &[u8], expires_at: u64) -> Vec<u8> {
+    let mut stored = Vec::with_capacity(EXPIRY_BYTES + value.len());
+    stored.extend_from_slice(&expires_at.to_be_bytes());
+    stored.extend_from_slice(value);
+    stored
+}
+
+/// Split a stored value into its expiry and payload, or `None` if expired.
+fn decode(stored: &[u8]) -> Option<(u64, &[u8])> {
+    if stored.len() < EXPIRY_BYTES {
+        return None;
+    }
+    let (expiry, value) = stored.split_at(EXPIRY_BYTES);
+    let expires_at = u64::from_be_bytes(expiry.try_into().ok()?);
+    (expires_at == 0 || expires_at > now_millis()).then_some((expires_at, value))
+}
+
+#[async_trait]
+impl KvStore for RedbKvStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let key = key.to_string();
+        self.blocking(move |db| {
+            let txn = db.begin_read()?;
+            let table = match txn.open_table(TABLE) {
+                Ok(table) => table,
+                Err(::redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+            let stored = table.get(key.as_str())?;
+            Ok(stored.and_then(|v| decode(v.value()).map(|(_, value)| value.to_vec())))
+        })
+        .await
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        let key = key.to_string();
+        self.blocking(move |db| {
+            let txn = db.begin_write()?;
+            {
+                let mut table = txn.open_table(TABLE)?;
+                table.insert(key.as_str(), encode(&value, ttl).as_slice())?;
+            }
+            txn.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        let key = key.to_string();
+        self.blocking(move |db| {
+            let txn = db.begin_write()?;
+            let existed = {
+                let mut table = txn.open_table(TABLE)?;
+                let removed = table.remove(key.as_str())?;
+                removed.is_some_and(|v| decode(v.value()).is_some())
+            };
+            txn.commit()?;
+            Ok(existed)
+        })
+        .await
+    }
+
+    async fn increment(&self, key: &str, delta: i64) -> Result<i64> {
+        let key = key.to_string();
+        self.blocking(move |db| {
+            // redb serializes write transactions, so read-modify-write is atomic
+            let txn = db.begin_write()?;
+            let next = {
+                let mut table = txn.open_table(TABLE)?;
+                let (expires_at, current) = match table.get(key.as_str())? {
+                    Some(stored) => match decode(stored.value()) {
+                        Some((expires_at, value)) => (
+                            expires_at,
+                            parse_counter(&key, value).map_err(StoreError::Pmcp)?,
+                        ),
+                        None => (0, 0),
+                    },
+                    None => (0, 0),
+                };
+                let next = add_counter(&key, current, delta).map_err(StoreError::Pmcp)?;
+                let stored = encode_with_expiry(next.to_string().as_bytes(), expires_at);
+                table.insert(key.as_str(), stored.as_slice())?;
+                next
+            };
+            txn.commit()?;
+            Ok(next)
+        })
+        .await
+    }
+
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let prefix = prefix.to_string();
+        self.blocking(move |db| {
+            let txn = db.begin_read()?;
+            let table = match txn.open_table(TABLE) {
+                Ok(table) => table,
+                Err(::redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+                Err(e) => return Err(e.into()),
+            };
+            let mut keys = Vec::new();
+            for entry in table.range(prefix.as_str()..)? {
+                let (key, value) = entry?;
+                let key = key.value();
+                if !key.starts_with(prefix.as_str()) {
+                    break;
+                }
+                if decode(value.value()).is_some() {
+                    keys.push(key.to_string());
+                }
+            }
+            Ok(keys)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_values_persist_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kv.redb");
+
+        {
+            let store = RedbKvStore::open(&path).unwrap();
+            store.set("user:1", b"alice".to_vec(), None).await.unwrap();
+            store.set("user:2", b"bob".to_vec(), None).await.unwrap();
+            assert_eq!(store.increment("visits", 2).await.unwrap(), 2);
+        }
+
+        let store = RedbKvStore::open(&path).unwrap();
+        assert_eq!(store.get("user:1").await.unwrap(), Some(b"alice".to_vec()));
+        assert_eq!(store.increment("visits", 1).await.unwrap(), 3);
+        let mut keys = store.keys("user:").await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["user:1", "user:2"]);
+        assert!(store.delete("user:1").await.unwrap());
+        assert_eq!(store.get("user:1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_values_are_hidden() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = RedbKvStore::open(dir.path().join("kv.redb")).unwrap();
+        assert_eq!(store.get("missing").await.unwrap(), None);
+
+        store
+            .set("a", b"1".to_vec(), Some(Duration::from_millis(10)))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(store.get("a").await.unwrap(), None);
+        assert!(store.keys("").await.unwrap().is_empty());
+        assert_eq!(store.increment("a", 1).await.unwrap(), 1);
+    }
+}
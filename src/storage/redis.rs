@@ -0,0 +1,184 @@
+//! [`KvStore`] backed by Redis, for servers running more than one instance.
+//!
+//! # Key Schema
+//!
+//! | Key Pattern | Type | Purpose |
+//! |-------------|------|---------|
+//! | `{prefix}:kv:{key}` | String | Stored value or counter |
+//!
+//! TTLs map to `SET ... PX`, and counters use `INCRBY`, which keeps any
+//! existing expiry.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::storage::redis::RedisKvStore;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let store = RedisKvStore::new("redis://127.0.0.1:6379")
+//!     .await?
+//!     .with_prefix("notes");
+//! let server = pmcp::Server::builder()
+//!     .name("notes")
+//!     .version("1.0.0")
+//!     .kv_store(Arc::new(store))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use ::redis::aio::MultiplexedConnection;
+use ::redis::AsyncCommands;
+use async_trait::async_trait;
+use std::time::Duration;
+
+use super::KvStore;
+use crate::error::{Error, Result};
+
+/// Redis [`KvStore`].
+///
+/// Holds a [`MultiplexedConnection`], which is cheap to clone; each method
+/// clones it for concurrent use.
+#[derive(Clone)]
+pub struct RedisKvStore {
+    conn: MultiplexedConnection,
+    key_prefix: String,
+}
+
+impl std::fmt::Debug for RedisKvStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisKvStore")
+            .field("key_prefix", &self.key_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedisKvStore {
+    /// Connect to Redis at `url`, using the key prefix `"pmcp"`.
+    pub async fn new(url: &str) -> Result<Self> {
+        let client = ::redis::Client::open(url).map_err(backend)?;
+        let conn = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(backend)?;
+        Ok(Self::with_connection(conn))
+    }
+
+    /// Create a store with a pre-built multiplexed connection.
+    pub fn with_connection(conn: MultiplexedConnection) -> Self {
+        Self {
+            conn,
+            key_prefix: "pmcp".to_string(),
+        }
+    }
+
+    /// Set a custom key prefix (builder pattern).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    fn redis_key(&self, key: &str) -> String {
+        format!("{}:kv:{}", self.key_prefix, key)
+    }
+}
+
+fn backend(error: ::redis::RedisError) -> Error {
+    Error::internal(format!("redis: {error}"))
+}
+
+/// Escape glob metacharacters so `prefix` matches literally in `SCAN MATCH`.
+fn escape_pattern(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[async_trait]
+impl KvStore for RedisKvStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut conn = self.conn.clone();
+        conn.get(self.redis_key(key)).await.map_err(backend)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        let mut cmd = ::redis::cmd("SET");
+        cmd.arg(self.redis_key(key)).arg(value);
+        if let Some(ttl) = ttl {
+            let millis = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+            cmd.arg("PX").arg(millis.max(1));
+        }
+        let () = cmd
+            .query_async(&mut self.conn.clone())
+            .await
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        let mut conn = self.conn.clone();
+        let removed: i64 = conn.del(self.redis_key(key)).await.map_err(backend)?;
+        Ok(removed > 0)
+    }
+
+    async fn increment(&self, key: &str, delta: i64) -> Result<i64> {
+        let mut conn = self.conn.clone();
+        conn.incr(self.redis_key(key), delta).await.map_err(|e| {
+            if e.to_string().contains("not an integer") {
+                Error::validation(format!("value at '{key}' is not an integer"))
+            } else {
+                backend(e)
+            }
+        })
+    }
+
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        let namespace = self.redis_key("");
+        let pattern = format!("{}*", escape_pattern(&self.redis_key(prefix)));
+        let mut cursor: u64 = 0;
+        let mut keys = Vec::new();
+        loop {
+            let (next, batch): (u64, Vec<String>) = ::redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut conn)
+                .await
+                .map_err(backend)?;
+            keys.extend(
+                batch
+                    .iter()
+                    .filter_map(|k| k.strip_prefix(&namespace))
+                    .map(String::from),
+            );
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        // SCAN may return a key more than once
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_pattern() {
+        assert_eq!(escape_pattern("user:1"), "user:1");
+        assert_eq!(escape_pattern("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+}
@@ -364,6 +364,20 @@ impl ClientCapabilities {
     pub fn supports_sampling(&self) -> bool {
         self.sampling.is_some()
     }
+
+    /// Check if the client exposes its roots with `roots/list`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::ClientCapabilities;
+    ///
+    /// assert!(ClientCapabilities::full().supports_roots());
+    /// assert!(!ClientCapabilities::minimal().supports_roots());
+    /// ```
+    pub fn supports_roots(&self) -> bool {
+        self.roots.is_some()
+    }
 }
 
 impl ServerCapabilities {
@@ -137,6 +137,50 @@ impl Content {
         }
     }
 
+    /// Create image content from raw image bytes, base64-encoding them.
+    ///
+    /// The bytes are sent as-is. Use [`Content::image_from_bytes_with`] (feature
+    /// `image`) to resize, convert or size-cap the image first.
+    ///
+    /// ```rust
+    /// use pmcp::types::Content;
+    ///
+    /// let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    /// let c = Content::image_from_bytes(&png, "image/png");
+    /// ```
+    pub fn image_from_bytes(bytes: &[u8], mime_type: impl Into<String>) -> Self {
+        use base64::Engine;
+
+        Self::image(
+            base64::engine::general_purpose::STANDARD.encode(bytes),
+            mime_type,
+        )
+    }
+
+    /// Create image content from raw image bytes, resized and converted to
+    /// satisfy `options`.
+    ///
+    /// See [`crate::utils::image::prepare`] for how dimensions, accepted
+    /// formats and the byte cap are applied.
+    ///
+    /// ```rust,no_run
+    /// use pmcp::types::Content;
+    /// use pmcp::utils::image::ImageOptions;
+    ///
+    /// # let screenshot: Vec<u8> = Vec::new();
+    /// let c = Content::image_from_bytes_with(&screenshot, &ImageOptions::thumbnail(512))?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    #[cfg(feature = "image")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+    pub fn image_from_bytes_with(
+        bytes: &[u8],
+        options: &crate::utils::image::ImageOptions,
+    ) -> crate::Result<Self> {
+        crate::utils::image::prepare(bytes, options)
+            .map(crate::utils::image::PreparedImage::into_content)
+    }
+
     /// Create a minimal resource reference (URI only).
     ///
     /// Use [`Content::resource_with_text`] when you have content to include.
@@ -425,6 +469,15 @@ mod tests {
         assert_eq!(json["text"], "Hello");
     }
 
+    #[test]
+    fn test_image_from_bytes_encodes_base64() {
+        let content = Content::image_from_bytes(b"\x89PNG", "image/png");
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["type"], "image");
+        assert_eq!(json["data"], "iVBORw==");
+        assert_eq!(json["mimeType"], "image/png");
+    }
+
     #[test]
     fn test_content_resource_meta_serialization() {
         let mut meta_map = serde_json::Map::new();
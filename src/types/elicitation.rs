@@ -56,6 +56,65 @@ pub struct ElicitResult {
     pub content: Option<HashMap<String, Value>>,
 }
 
+impl ElicitResult {
+    /// The user accepted and submitted `content`.
+    pub fn accept(content: HashMap<String, Value>) -> Self {
+        Self {
+            action: ElicitAction::Accept,
+            content: Some(content),
+        }
+    }
+
+    /// The user explicitly declined to provide the input.
+    pub fn decline() -> Self {
+        Self {
+            action: ElicitAction::Decline,
+            content: None,
+        }
+    }
+
+    /// The user dismissed the request without choosing.
+    pub fn cancel() -> Self {
+        Self {
+            action: ElicitAction::Cancel,
+            content: None,
+        }
+    }
+
+    /// Whether the user accepted the request.
+    pub fn is_accepted(&self) -> bool {
+        self.action == ElicitAction::Accept
+    }
+
+    /// Deserialize the submitted content.
+    ///
+    /// Returns `Ok(None)` when the user declined or cancelled, so handlers can
+    /// fall back or abort without inspecting the action themselves.
+    ///
+    /// ```rust
+    /// use pmcp::types::elicitation::ElicitResult;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Confirm { confirmed: bool }
+    ///
+    /// let accepted = ElicitResult::accept([("confirmed".to_string(), json!(true))].into());
+    /// assert!(accepted.content_as::<Confirm>().unwrap().unwrap().confirmed);
+    ///
+    /// assert!(ElicitResult::decline().content_as::<Confirm>().unwrap().is_none());
+    /// ```
+    pub fn content_as<T: serde::de::DeserializeOwned>(&self) -> crate::Result<Option<T>> {
+        if !self.is_accepted() {
+            return Ok(None);
+        }
+        let content = self.content.clone().unwrap_or_default();
+        serde_json::from_value(Value::Object(content.into_iter().collect()))
+            .map(Some)
+            .map_err(|e| crate::Error::validation(format!("Invalid elicitation content: {e}")))
+    }
+}
+
 /// Action taken by the user on an elicitation request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +107,13 @@ pub struct WidgetCSP {
     /// Restricts which domains can be used as the base URI for relative URLs.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub base_uri_domains: Option<Vec<String>>,
+
+    /// Origins allowed to embed the widget (frame-ancestors).
+    ///
+    /// Lets hosts refuse to render the widget inside pages other than the
+    /// listed origins. `'self'` and `'none'` are passed through as-is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_ancestors: Option<Vec<String>>,
 }
 
 impl WidgetCSP {
@@ -176,12 +183,30 @@ impl WidgetCSP {
         self
     }
 
+    /// Add an origin allowed to embed the widget (`frame-ancestors`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pmcp::types::mcp_apps::WidgetCSP;
+    ///
+    /// let csp = WidgetCSP::new()
+    ///     .connect("https://api.example.com")
+    ///     .frame_ancestor("https://chatgpt.com");
+    /// ```
+    pub fn frame_ancestor(mut self, origin: impl Into<String>) -> Self {
+        self.frame_ancestors
+            .get_or_insert_with(Vec::new)
+            .push(origin.into());
+        self
+    }
+
     /// Serialize as a spec-compatible JSON map (ext-apps `McpUiResourceCsp` shape).
     ///
     /// Uses camelCase field names per spec. `redirect_domains` is excluded
     /// because it is ChatGPT-specific and not part of the ext-apps spec.
     pub fn to_spec_map(&self) -> serde_json::Map<String, serde_json::Value> {
-        let mut csp_obj = serde_json::Map::with_capacity(4);
+        let mut csp_obj = serde_json::Map::with_capacity(5);
         if !self.connect_domains.is_empty() {
             csp_obj.insert(
                 "connectDomains".into(),
@@ -204,6 +229,11 @@ impl WidgetCSP {
                 csp_obj.insert("baseUriDomains".into(), serde_json::json!(base_uris));
             }
         }
+        if let Some(ancestors) = &self.frame_ancestors {
+            if !ancestors.is_empty() {
+                csp_obj.insert("frameAncestors".into(), serde_json::json!(ancestors));
+            }
+        }
         csp_obj
     }
 
@@ -214,6 +244,7 @@ impl WidgetCSP {
             && self.redirect_domains.as_ref().is_none_or(Vec::is_empty)
             && self.frame_domains.as_ref().is_none_or(Vec::is_empty)
             && self.base_uri_domains.as_ref().is_none_or(Vec::is_empty)
+            && self.frame_ancestors.as_ref().is_none_or(Vec::is_empty)
     }
 }
 
@@ -285,6 +316,17 @@ pub struct WidgetMeta {
         skip_serializing_if = "Option::is_none"
     )]
     pub description: Option<String>,
+
+    /// Iframe sandbox flags the widget needs (e.g. `allow-forms`).
+    ///
+    /// Hosts that honor this render the widget in an iframe whose `sandbox`
+    /// attribute grants only these tokens, so list every capability the
+    /// widget relies on and nothing more.
+    #[serde(
+        rename = "openai/widgetSandbox",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sandbox: Option<Vec<String>>,
 }
 
 impl WidgetMeta {
@@ -326,13 +368,34 @@ impl WidgetMeta {
         self
     }
 
+    /// Add an iframe sandbox flag the widget needs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pmcp::types::mcp_apps::WidgetMeta;
+    ///
+    /// let meta = WidgetMeta::new()
+    ///     .sandbox("allow-scripts")
+    ///     .sandbox("allow-forms");
+    /// ```
+    pub fn sandbox(mut self, flag: impl Into<String>) -> Self {
+        let flag = flag.into();
+        let flags = self.sandbox.get_or_insert_with(Vec::new);
+        if !flags.contains(&flag) {
+            flags.push(flag);
+        }
+        self
+    }
+
     /// Convert to a `serde_json::Map` for merging into resource `_meta`.
     ///
     /// Produces both flat `openai/*` keys (via serde) and a nested `"ui"` object
-    /// containing MCP standard equivalents (`prefersBorder`, `domain`, `csp`).
+    /// containing MCP standard equivalents (`prefersBorder`, `domain`, `csp`,
+    /// `sandbox`).
     ///
     /// The nested `ui.csp` uses spec field names (`connectDomains`, `resourceDomains`,
-    /// `frameDomains`, `baseUriDomains`). Note that `redirect_domains` is ChatGPT-specific
+    /// `frameDomains`, `baseUriDomains`, `frameAncestors`). Note that `redirect_domains` is ChatGPT-specific
     /// and only appears in the flat `openai/widgetCSP` key, not in nested `ui.csp`.
     pub fn to_meta_map(&self) -> serde_json::Map<String, serde_json::Value> {
         let mut map = match serde_json::to_value(self).ok() {
@@ -362,6 +425,9 @@ impl WidgetMeta {
                 ui_obj.insert("csp".to_string(), serde_json::Value::Object(csp_obj));
             }
         }
+        if let Some(flags) = self.sandbox.as_ref().filter(|f| !f.is_empty()) {
+            ui_obj.insert("sandbox".to_string(), serde_json::json!(flags));
+        }
         if !ui_obj.is_empty() {
             map.insert("ui".to_string(), serde_json::Value::Object(ui_obj));
         }
@@ -379,6 +445,7 @@ impl WidgetMeta {
             && self.domain.is_none()
             && self.csp.is_none()
             && self.description.is_none()
+            && self.sandbox.is_none()
     }
 }
 
@@ -1430,6 +1497,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_widget_meta_security_policy() {
+        let meta = WidgetMeta::new()
+            .csp(
+                WidgetCSP::new()
+                    .connect("https://api.example.com")
+                    .frame_ancestor("https://chatgpt.com"),
+            )
+            .sandbox("allow-scripts")
+            .sandbox("allow-forms")
+            .sandbox("allow-scripts");
+        assert!(!meta.is_empty());
+
+        let map = meta.to_meta_map();
+        assert_eq!(
+            map["openai/widgetSandbox"],
+            serde_json::json!(["allow-scripts", "allow-forms"])
+        );
+        assert_eq!(
+            map["openai/widgetCSP"]["frame_ancestors"][0],
+            "https://chatgpt.com"
+        );
+
+        let ui_obj = map.get("ui").expect("must have nested 'ui' key");
+        assert_eq!(ui_obj["csp"]["frameAncestors"][0], "https://chatgpt.com");
+        assert_eq!(
+            ui_obj["sandbox"],
+            serde_json::json!(["allow-scripts", "allow-forms"])
+        );
+    }
+
     #[test]
     fn test_widget_meta_dual_emit_all_fields() {
         let meta = WidgetMeta::new()
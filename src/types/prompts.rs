@@ -10,6 +10,34 @@ use super::content::{Content, Role};
 use super::protocol::Cursor;
 use super::protocol::RequestMeta;
 
+/// Separator between namespace segments in prompt names (`reporting/weekly`).
+pub const PROMPT_NAMESPACE_SEPARATOR: char = '/';
+
+/// Whether the prompt `name` is `namespace` itself or lies inside it.
+///
+/// `reporting` contains `reporting/weekly` and `reporting/q1/summary`, but
+/// not `reportings/weekly`. A trailing separator on `namespace` is ignored,
+/// and an empty namespace contains every prompt.
+///
+/// ```rust
+/// use pmcp::types::prompts::in_namespace;
+///
+/// assert!(in_namespace("reporting/weekly", "reporting"));
+/// assert!(in_namespace("reporting/weekly", "reporting/"));
+/// assert!(!in_namespace("reportings/weekly", "reporting"));
+/// assert!(in_namespace("admin/cleanup", ""));
+/// ```
+pub fn in_namespace(name: &str, namespace: &str) -> bool {
+    let namespace = namespace.trim_end_matches(PROMPT_NAMESPACE_SEPARATOR);
+    if namespace.is_empty() {
+        return true;
+    }
+    match name.strip_prefix(namespace) {
+        Some(rest) => rest.is_empty() || rest.starts_with(PROMPT_NAMESPACE_SEPARATOR),
+        None => false,
+    }
+}
+
 /// List prompts request.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +45,27 @@ pub struct ListPromptsRequest {
     /// Pagination cursor
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Cursor,
+    /// Only list prompts in this namespace (pmcp extension).
+    ///
+    /// Servers that do not support namespaces ignore the field and return
+    /// every prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+impl ListPromptsRequest {
+    /// Only list prompts in `namespace` (e.g. `reporting`).
+    pub fn with_prefix(mut self, namespace: impl Into<String>) -> Self {
+        self.prefix = Some(namespace.into());
+        self
+    }
+
+    /// Whether the prompt `name` passes this request's prefix filter.
+    pub fn matches(&self, name: &str) -> bool {
+        self.prefix
+            .as_deref()
+            .is_none_or(|prefix| in_namespace(name, prefix))
+    }
 }
 
 /// Prompt information.
@@ -78,6 +127,17 @@ impl PromptInfo {
         self
     }
 
+    /// Namespace of a hierarchical prompt name.
+    ///
+    /// `reporting/weekly` is in `reporting`, `admin/users/purge` is in
+    /// `admin/users`, and a name without a separator has no namespace.
+    pub fn namespace(&self) -> Option<&str> {
+        self.name
+            .rsplit_once(PROMPT_NAMESPACE_SEPARATOR)
+            .map(|(namespace, _)| namespace)
+            .filter(|namespace| !namespace.is_empty())
+    }
+
     /// Set the prompt description.
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
@@ -409,6 +469,40 @@ mod tests {
         assert_eq!(json["arguments"][0]["required"], true);
     }
 
+    #[test]
+    fn prompt_namespaces() {
+        assert_eq!(
+            PromptInfo::new("reporting/weekly").namespace(),
+            Some("reporting")
+        );
+        assert_eq!(
+            PromptInfo::new("admin/users/purge").namespace(),
+            Some("admin/users")
+        );
+        assert_eq!(PromptInfo::new("summarize").namespace(), None);
+
+        let request = ListPromptsRequest::default().with_prefix("admin");
+        assert!(request.matches("admin/cleanup"));
+        assert!(request.matches("admin/users/purge"));
+        assert!(!request.matches("administer"));
+        assert!(!request.matches("reporting/weekly"));
+        assert!(ListPromptsRequest::default().matches("reporting/weekly"));
+    }
+
+    #[test]
+    fn list_prompts_request_prefix_round_trip() {
+        let request = ListPromptsRequest::default().with_prefix("reporting");
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json, serde_json::json!({ "prefix": "reporting" }));
+
+        let plain: ListPromptsRequest = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(plain.prefix.is_none());
+        assert!(serde_json::to_value(&plain)
+            .unwrap()
+            .get("prefix")
+            .is_none());
+    }
+
     #[test]
     fn get_prompt_result_without_meta_omits_field() {
         let result = GetPromptResult::new(vec![], Some("Test".to_string()));
@@ -7,6 +7,7 @@ pub mod version;
 
 use crate::types::capabilities::{ClientCapabilities, ServerCapabilities};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Re-export version constants and negotiation function.
 pub use version::*;
@@ -322,9 +323,24 @@ pub struct RequestMeta {
     /// When present on a `tools/call` request, the server records the tool
     /// result against the referenced workflow task after normal execution.
     /// The tool call itself proceeds as normal; the recording is best-effort.
+    /// On a `prompts/get` request for a task-backed workflow, the workflow
+    /// resumes the referenced task from its last completed step.
     #[serde(skip_serializing_if = "Option::is_none", rename = "_task_id")]
     #[allow(clippy::pub_underscore_fields)]
     pub _task_id: Option<String>,
+
+    /// Byte range to read on a `resources/read` request (PMCP extension).
+    ///
+    /// See [`ReadResourceRequest::with_range`](super::resources::ReadResourceRequest::with_range).
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pmcp/range")]
+    pub range: Option<super::resources::ResourceRange>,
+
+    /// Etag of the copy the client already has, on a `resources/read`
+    /// request (PMCP extension).
+    ///
+    /// See [`ReadResourceRequest::with_if_none_match`](super::resources::ReadResourceRequest::with_if_none_match).
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pmcp/ifNoneMatch")]
+    pub if_none_match: Option<String>,
 }
 
 impl RequestMeta {
@@ -355,6 +371,18 @@ pub struct CompleteRequest {
     pub r#ref: CompletionReference,
     /// The argument to complete
     pub argument: CompletionArgument,
+    /// Values already resolved for other arguments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<CompletionContext>,
+}
+
+/// Additional context for a completion request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionContext {
+    /// Previously resolved argument values, keyed by argument name
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
 }
 
 /// Completion reference.
@@ -394,6 +422,13 @@ pub struct CompleteResult {
     pub completion: CompletionResult,
 }
 
+impl CompleteResult {
+    /// Create a completion response.
+    pub fn new(completion: CompletionResult) -> Self {
+        Self { completion }
+    }
+}
+
 /// Completion result.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -497,6 +532,9 @@ pub enum ClientRequest {
     /// Cancel a task (MCP 2025-11-25 Tasks).
     #[serde(rename = "tasks/cancel")]
     TasksCancel(crate::types::tasks::CancelTaskRequest),
+    /// Provide input to a task waiting in `input_required`.
+    #[serde(rename = "tasks/provide_input")]
+    TasksProvideInput(crate::types::tasks::ProvideTaskInputRequest),
 }
 
 /// Server request types.
@@ -557,6 +595,16 @@ mod tests {
         let json_str = r#"{"method": "tasks/cancel", "params": {"taskId": "abc"}}"#;
         let req: ClientRequest = serde_json::from_str(json_str).unwrap();
         assert!(matches!(req, ClientRequest::TasksCancel(_)));
+
+        let json_str = r#"{"method": "tasks/provide_input", "params": {"taskId": "abc", "action": "accept", "content": {"name": "x"}}}"#;
+        let req: ClientRequest = serde_json::from_str(json_str).unwrap();
+        match req {
+            ClientRequest::TasksProvideInput(params) => {
+                assert_eq!(params.task_id, "abc");
+                assert!(params.input.is_accepted());
+            },
+            other => panic!("expected TasksProvideInput, got {other:?}"),
+        }
     }
 
     #[test]
@@ -161,12 +161,90 @@ impl ListResourcesResult {
 pub struct ReadResourceRequest {
     /// Resource URI
     pub uri: String,
-    /// Request metadata (e.g., progress token)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Request metadata (e.g., progress token, byte range)
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none", default)]
     #[allow(clippy::pub_underscore_fields)] // _meta is part of MCP protocol spec
     pub _meta: Option<RequestMeta>,
 }
 
+impl ReadResourceRequest {
+    /// Create a request for the whole resource.
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            _meta: None,
+        }
+    }
+
+    /// Read `length` bytes starting at `offset` (to the end if `length` is `None`).
+    ///
+    /// Sent as `_meta["pmcp/range"]`.
+    pub fn with_range(mut self, offset: u64, length: Option<u64>) -> Self {
+        self._meta.get_or_insert_with(RequestMeta::new).range =
+            Some(ResourceRange { offset, length });
+        self
+    }
+
+    /// Return an empty, `notModified` result if the resource etag matches.
+    ///
+    /// Sent as `_meta["pmcp/ifNoneMatch"]`.
+    pub fn with_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self._meta
+            .get_or_insert_with(RequestMeta::new)
+            .if_none_match = Some(etag.into());
+        self
+    }
+
+    /// The requested byte range, if any.
+    pub fn range(&self) -> Option<ResourceRange> {
+        self._meta.as_ref()?.range
+    }
+
+    /// The etag the client already has, if any.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self._meta.as_ref()?.if_none_match.as_deref()
+    }
+}
+
+/// Byte range of a resource's UTF-8 text, for incremental reads of large
+/// resources such as logs or datasets.
+///
+/// Offsets count bytes. The server moves the slice boundaries to the nearest
+/// character boundaries, so the returned [`ResourceRangeInfo`] is authoritative
+/// for where the next range starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRange {
+    /// First byte to read
+    pub offset: u64,
+    /// Maximum number of bytes to read (to the end if absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+}
+
+/// `_meta` key under which range and etag information is returned.
+pub const RESOURCE_RANGE_META_KEY: &str = "pmcp/range";
+
+/// Range and validator metadata on a ranged or conditional read result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRangeInfo {
+    /// Byte offset of the returned slice
+    pub offset: u64,
+    /// Byte length of the returned slice
+    pub length: u64,
+    /// Byte length of the whole resource text
+    pub total_length: u64,
+    /// Offset of the next slice, absent once the end is reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+    /// Validator for the whole resource; changes whenever the content does
+    pub etag: String,
+    /// The etag matched `ifNoneMatch` and no contents were returned
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub not_modified: bool,
+}
+
 /// List resource templates request.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -356,12 +434,36 @@ pub struct ReadResourceResult {
         deserialize_with = "crate::types::content::resource_contents_serde::deserialize"
     )]
     pub contents: Vec<Content>,
+    /// Result metadata (e.g., range information for partial reads)
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none", default)]
+    #[allow(clippy::pub_underscore_fields)] // _meta is part of MCP protocol spec
+    pub _meta: Option<serde_json::Map<String, Value>>,
 }
 
 impl ReadResourceResult {
     /// Create a new read resource result.
     pub fn new(contents: Vec<Content>) -> Self {
-        Self { contents }
+        Self {
+            contents,
+            _meta: None,
+        }
+    }
+
+    /// Range and etag information, present on ranged or conditional reads.
+    pub fn range_info(&self) -> Option<ResourceRangeInfo> {
+        self._meta
+            .as_ref()?
+            .get(RESOURCE_RANGE_META_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Text of the first content item, if it has any.
+    pub fn text(&self) -> Option<&str> {
+        match self.contents.first()? {
+            Content::Text { text } => Some(text),
+            Content::Resource { text, .. } => text.as_deref(),
+            _ => None,
+        }
     }
 }
 
@@ -402,4 +504,58 @@ mod tests {
         assert_eq!(json["description"], "Access files by path");
         assert_eq!(json["mimeType"], "text/plain");
     }
+
+    #[test]
+    fn test_read_resource_request_range_serialization() {
+        let req = ReadResourceRequest::new("file:///var/log/app.log")
+            .with_range(1024, Some(4096))
+            .with_if_none_match("abc");
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["_meta"]["pmcp/range"]["offset"], 1024);
+        assert_eq!(json["_meta"]["pmcp/range"]["length"], 4096);
+        assert_eq!(json["_meta"]["pmcp/ifNoneMatch"], "abc");
+        let round_trip: ReadResourceRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            round_trip.range(),
+            Some(ResourceRange {
+                offset: 1024,
+                length: Some(4096)
+            })
+        );
+        assert_eq!(round_trip.if_none_match(), Some("abc"));
+
+        let plain = serde_json::to_value(ReadResourceRequest::new("file:///a")).unwrap();
+        assert!(plain.get("_meta").is_none());
+        let parsed: ReadResourceRequest =
+            serde_json::from_value(serde_json::json!({"uri": "file:///a"})).unwrap();
+        assert!(parsed.range().is_none() && parsed.if_none_match().is_none());
+    }
+
+    #[test]
+    fn test_read_resource_result_range_info() {
+        let mut result = ReadResourceResult::new(vec![Content::text("hello")]);
+        assert!(result.range_info().is_none());
+        assert_eq!(result.text(), Some("hello"));
+
+        let info = ResourceRangeInfo {
+            offset: 0,
+            length: 5,
+            total_length: 10,
+            next_offset: Some(5),
+            etag: "e1".to_string(),
+            not_modified: false,
+        };
+        let mut meta = serde_json::Map::new();
+        meta.insert(
+            RESOURCE_RANGE_META_KEY.to_string(),
+            serde_json::to_value(&info).unwrap(),
+        );
+        result._meta = Some(meta);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["_meta"]["pmcp/range"]["totalLength"], 10);
+        assert!(json["_meta"]["pmcp/range"].get("notModified").is_none());
+        let round_trip: ReadResourceResult = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.range_info(), Some(info));
+    }
 }
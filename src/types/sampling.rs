@@ -327,6 +327,54 @@ impl CreateMessageResult {
         self.stop_reason = Some(reason.into());
         self
     }
+
+    /// The generated text, or `None` if the model returned other content.
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            super::content::Content::Text { text } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Parse the generated text as JSON.
+    ///
+    /// A surrounding Markdown code fence, which models often add, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the content is not text or does not
+    /// parse as `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::{Content, CreateMessageResult};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Sentiment { score: f64 }
+    ///
+    /// let result = CreateMessageResult::new(
+    ///     Content::text("```json\n{\"score\": 0.8}\n```"),
+    ///     "claude-3-5-haiku",
+    /// );
+    /// assert_eq!(result.parse_json::<Sentiment>().unwrap().score, 0.8);
+    /// ```
+    pub fn parse_json<T: serde::de::DeserializeOwned>(&self) -> crate::Result<T> {
+        let text = self
+            .text()
+            .ok_or_else(|| crate::Error::validation("Sampling result is not text"))?
+            .trim();
+        let json = text
+            .strip_prefix("```")
+            .and_then(|fenced| fenced.strip_suffix("```"))
+            .map_or(text, |fenced| {
+                // Drop the info string (`json`) on the opening fence line
+                fenced.split_once('\n').map_or(fenced, |(_, body)| body)
+            });
+        serde_json::from_str(json)
+            .map_err(|e| crate::Error::validation(format!("Invalid sampling JSON: {e}")))
+    }
 }
 
 /// Sampling result with tool use support (MCP 2025-11-25).
@@ -537,6 +585,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_message_result_parse_json() {
+        #[derive(Deserialize)]
+        struct Answer {
+            value: u32,
+        }
+
+        let plain = CreateMessageResult::new(super::super::Content::text("{\"value\": 4}"), "m");
+        assert_eq!(plain.parse_json::<Answer>().unwrap().value, 4);
+
+        let fenced =
+            CreateMessageResult::new(super::super::Content::text("```\n{\"value\": 5}\n```"), "m");
+        assert_eq!(fenced.parse_json::<Answer>().unwrap().value, 5);
+
+        let prose = CreateMessageResult::new(super::super::Content::text("four"), "m");
+        assert!(prose.parse_json::<Answer>().is_err());
+    }
+
     #[test]
     fn sampling_message_content_text_roundtrip() {
         let content = SamplingMessageContent::Text {
@@ -309,6 +309,22 @@ pub struct CancelTaskRequest {
     pub result: Option<serde_json::Value>,
 }
 
+/// Provide task input request.
+///
+/// Answers the elicitation attached to a task in `input_required` status.
+/// The elicitation fields (`action`, `content`) are flattened next to
+/// `taskId`, exactly as the client would return them for an
+/// `elicitation/create` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvideTaskInputRequest {
+    /// Task ID waiting for input
+    pub task_id: String,
+    /// The user's answer to the task's elicitation
+    #[serde(flatten)]
+    pub input: crate::types::elicitation::ElicitResult,
+}
+
 /// Cancel task result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
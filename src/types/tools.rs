@@ -377,6 +377,28 @@ impl ToolInfo {
                 || meta.get("ui").and_then(|v| v.get("resourceUri")).is_some()
         })
     }
+
+    /// Widget URIs this tool's `_meta` points at, without duplicates.
+    ///
+    /// Collects `ui.resourceUri`, the legacy `ui/resourceUri` key, and
+    /// `openai/outputTemplate`.
+    pub fn widget_uris(&self) -> Vec<&str> {
+        let Some(meta) = self.widget_meta() else {
+            return Vec::new();
+        };
+        let mut uris: Vec<&str> = Vec::new();
+        let candidates = [
+            meta.get("ui").and_then(|ui| ui.get("resourceUri")),
+            meta.get(crate::types::ui::META_KEY_UI_RESOURCE_URI),
+            meta.get("openai/outputTemplate"),
+        ];
+        for uri in candidates.into_iter().flatten().filter_map(|v| v.as_str()) {
+            if !uris.contains(&uri) {
+                uris.push(uri);
+            }
+        }
+        uris
+    }
 }
 
 /// List tools request.
@@ -411,6 +433,13 @@ pub struct ListToolsResult {
     /// Pagination cursor for next page
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Cursor,
+    /// Schema definitions shared by several tools (PMCP extension).
+    ///
+    /// Tool schemas refer to them with `{"$ref": "#/definitions/<name>"}`.
+    /// Use [`inline_definitions`](Self::inline_definitions) to expand the
+    /// references for consumers that need self-contained schemas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definitions: Option<serde_json::Map<String, Value>>,
 }
 
 impl ListToolsResult {
@@ -419,6 +448,7 @@ impl ListToolsResult {
         Self {
             tools,
             next_cursor: None,
+            definitions: None,
         }
     }
 
@@ -427,6 +457,66 @@ impl ListToolsResult {
         self.next_cursor = Some(cursor.into());
         self
     }
+
+    /// Set the shared schema definitions.
+    pub fn with_definitions(mut self, definitions: serde_json::Map<String, Value>) -> Self {
+        self.definitions = Some(definitions);
+        self
+    }
+
+    /// Replace `#/definitions/` references in every tool schema with the
+    /// shared definition they point to, and drop [`definitions`](Self::definitions).
+    ///
+    /// References to unknown definitions are left as they are.
+    pub fn inline_definitions(&mut self) {
+        let Some(definitions) = self.definitions.take() else {
+            return;
+        };
+        for tool in &mut self.tools {
+            inline_refs(&mut tool.input_schema, &definitions, 0);
+            if let Some(output) = tool.output_schema.as_mut() {
+                inline_refs(output, &definitions, 0);
+            }
+        }
+    }
+}
+
+/// Maximum nesting of inlined definitions (guards recursive types).
+const MAX_INLINE_DEPTH: usize = 32;
+
+fn inline_refs(value: &mut Value, definitions: &serde_json::Map<String, Value>, depth: usize) {
+    if depth >= MAX_INLINE_DEPTH {
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            let definition = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix("#/definitions/"))
+                .and_then(|name| definitions.get(name))
+                .and_then(Value::as_object);
+            if let Some(definition) = definition {
+                map.remove("$ref");
+                for (key, val) in definition {
+                    map.entry(key.clone()).or_insert_with(|| val.clone());
+                }
+                for val in map.values_mut() {
+                    inline_refs(val, definitions, depth + 1);
+                }
+            } else {
+                for val in map.values_mut() {
+                    inline_refs(val, definitions, depth);
+                }
+            }
+        },
+        Value::Array(items) => {
+            for item in items {
+                inline_refs(item, definitions, depth);
+            }
+        },
+        _ => {},
+    }
 }
 
 /// Tool call request.
@@ -598,6 +688,20 @@ impl CallToolResult {
             self
         }
     }
+
+    /// Attach `structured_value` as `structuredContent` when the tool
+    /// declares an `outputSchema`.
+    ///
+    /// The MCP spec requires tools with an output schema to return
+    /// conforming structured results. Non-object values are left as text
+    /// only, since `structuredContent` must be a JSON object.
+    pub fn with_structured_output(self, info: &ToolInfo, structured_value: Value) -> Self {
+        if info.output_schema.is_some() && structured_value.is_object() {
+            self.with_structured_content(structured_value)
+        } else {
+            self
+        }
+    }
 }
 
 #[cfg(test)]
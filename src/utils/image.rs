@@ -0,0 +1,459 @@
+//! Image content preparation: resizing, format conversion and size caps.
+//!
+//! Servers returning charts or screenshots usually have raw image bytes in
+//! whatever format and resolution the renderer produced. Hosts, on the other
+//! hand, reject or silently drop images that are too large or in a format they
+//! do not accept. [`prepare`] bridges the two: it decodes the image, scales it
+//! down to fit [`ImageOptions`], picks the first accepted format whose encoding
+//! fits the byte cap, and shrinks further until it does.
+//!
+//! Most callers use [`Content::image_from_bytes_with`](crate::types::Content::image_from_bytes_with)
+//! rather than calling [`prepare`] directly.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::types::Content;
+//! use pmcp::utils::image::{ImageFormat, ImageOptions};
+//!
+//! # fn render_chart() -> Vec<u8> { Vec::new() }
+//! let png = render_chart();
+//!
+//! // A 256px thumbnail, as WebP if it fits in 64 KiB, PNG otherwise.
+//! let options = ImageOptions::thumbnail(256)
+//!     .with_formats(vec![ImageFormat::WebP, ImageFormat::Png])
+//!     .with_max_bytes(64 * 1024);
+//! let content = Content::image_from_bytes_with(&png, &options)?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use std::io::Cursor;
+
+use crate::error::{Error, Result};
+use crate::types::Content;
+
+/// JPEG qualities tried, best first, before the image is scaled down further.
+const JPEG_QUALITIES: [u8; 3] = [85, 70, 55];
+
+/// Factor applied to both dimensions each time no format fits the byte cap.
+const SHRINK_FACTOR: f64 = 0.75;
+
+/// Images are not shrunk below this size on their longest edge.
+const MIN_DIMENSION: u32 = 16;
+
+/// Output format for prepared images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    /// PNG (lossless).
+    Png,
+    /// JPEG (lossy, no transparency).
+    Jpeg,
+    /// WebP (encoded losslessly).
+    WebP,
+}
+
+impl ImageFormat {
+    /// MIME type for this format.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::WebP => "image/webp",
+        }
+    }
+
+    /// Look up a format by MIME type.
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type.trim().to_ascii_lowercase().as_str() {
+            "image/png" => Some(Self::Png),
+            "image/jpeg" | "image/jpg" => Some(Self::Jpeg),
+            "image/webp" => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    fn from_codec(format: ::image::ImageFormat) -> Option<Self> {
+        match format {
+            ::image::ImageFormat::Png => Some(Self::Png),
+            ::image::ImageFormat::Jpeg => Some(Self::Jpeg),
+            ::image::ImageFormat::WebP => Some(Self::WebP),
+            _ => None,
+        }
+    }
+}
+
+/// Limits an image must satisfy before it is returned to the host.
+///
+/// The defaults keep the image's dimensions, accept PNG and JPEG (PNG first),
+/// and cap the encoded data at 1 MiB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageOptions {
+    /// Maximum width in pixels.
+    pub max_width: Option<u32>,
+    /// Maximum height in pixels.
+    pub max_height: Option<u32>,
+    /// Maximum size of the base64-encoded `data` field, in bytes.
+    pub max_bytes: Option<usize>,
+    /// Accepted output formats, most preferred first.
+    pub formats: Vec<ImageFormat>,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            max_height: None,
+            max_bytes: Some(1024 * 1024),
+            formats: vec![ImageFormat::Png, ImageFormat::Jpeg],
+        }
+    }
+}
+
+impl ImageOptions {
+    /// Create options with the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options for a thumbnail fitting in a `size` x `size` square.
+    pub fn thumbnail(size: u32) -> Self {
+        Self::default().with_max_dimensions(size, size)
+    }
+
+    /// Limits matching what the given host accepts.
+    ///
+    /// Claude scales images down to 1568px on the longest edge and rejects
+    /// images over 5 MB; ChatGPT fits images within 2048x2048 and accepts up
+    /// to 20 MB. Other hosts get the conservative defaults.
+    #[cfg(feature = "mcp-apps")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mcp-apps")))]
+    pub fn for_host(host: crate::types::mcp_apps::HostType) -> Self {
+        use crate::types::mcp_apps::HostType;
+
+        let all = vec![ImageFormat::WebP, ImageFormat::Png, ImageFormat::Jpeg];
+        match host {
+            HostType::Claude => Self::default()
+                .with_max_dimensions(1568, 1568)
+                .with_max_bytes(5 * 1000 * 1000)
+                .with_formats(all),
+            HostType::ChatGpt => Self::default()
+                .with_max_dimensions(2048, 2048)
+                .with_max_bytes(20 * 1000 * 1000)
+                .with_formats(all),
+            HostType::Nanobot | HostType::McpJam | HostType::Generic => {
+                Self::default().with_max_dimensions(1568, 1568)
+            },
+        }
+    }
+
+    /// Scale images down to fit within `width` x `height`, keeping the aspect
+    /// ratio. Smaller images are never scaled up.
+    pub fn with_max_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.max_width = Some(width);
+        self.max_height = Some(height);
+        self
+    }
+
+    /// Cap the size of the base64-encoded data.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Remove the size cap.
+    pub fn without_max_bytes(mut self) -> Self {
+        self.max_bytes = None;
+        self
+    }
+
+    /// Set the accepted output formats, most preferred first.
+    pub fn with_formats(mut self, formats: Vec<ImageFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Accept only the formats whose MIME types appear in `mime_types`, in
+    /// that order. Unknown MIME types are ignored.
+    pub fn with_accepted_mime_types<I, S>(self, mime_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut formats = Vec::new();
+        for format in mime_types
+            .into_iter()
+            .filter_map(|m| ImageFormat::from_mime_type(m.as_ref()))
+        {
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        self.with_formats(formats)
+    }
+
+    fn fits(&self, encoded_len: usize) -> bool {
+        self.max_bytes
+            .is_none_or(|max| base64_len(encoded_len) <= max)
+    }
+}
+
+/// An image ready to be returned as [`Content::Image`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedImage {
+    /// Encoded image bytes.
+    pub data: Vec<u8>,
+    /// Format of `data`.
+    pub format: ImageFormat,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+impl PreparedImage {
+    /// Base64-encode the image into image content.
+    pub fn into_content(self) -> Content {
+        Content::image_from_bytes(&self.data, self.format.mime_type())
+    }
+}
+
+/// Decode `bytes` and re-encode them to satisfy `options`.
+///
+/// The source format is kept when it is accepted and the image already fits,
+/// in which case the original bytes are returned unchanged. Otherwise the
+/// accepted formats are tried in order, scaling the image down until one of
+/// them fits the byte cap.
+///
+/// # Errors
+///
+/// Returns a validation error if `bytes` is not a decodable image, no format
+/// is accepted, or the image cannot be made small enough.
+pub fn prepare(bytes: &[u8], options: &ImageOptions) -> Result<PreparedImage> {
+    if options.formats.is_empty() {
+        return Err(Error::validation("No accepted image formats"));
+    }
+
+    let source_format = ::image::guess_format(bytes)
+        .ok()
+        .and_then(ImageFormat::from_codec);
+    let decoded = ::image::load_from_memory(bytes)
+        .map_err(|e| Error::validation(format!("Unsupported image data: {}", e)))?;
+    let (width, height) = ::image::GenericImageView::dimensions(&decoded);
+
+    let (target_width, target_height) = fit_within(width, height, options);
+    if (target_width, target_height) == (width, height) {
+        if let Some(format) = source_format.filter(|f| options.formats.contains(f)) {
+            if options.fits(bytes.len()) {
+                return Ok(PreparedImage {
+                    data: bytes.to_vec(),
+                    format,
+                    width,
+                    height,
+                });
+            }
+        }
+    }
+
+    // Try the source format first when accepted, to avoid needless conversion.
+    let mut formats = options.formats.clone();
+    if let Some(position) = source_format.and_then(|f| formats.iter().position(|x| *x == f)) {
+        let preferred = formats.remove(position);
+        formats.insert(0, preferred);
+    }
+
+    let mut image = resize(&decoded, target_width, target_height);
+    loop {
+        let (width, height) = ::image::GenericImageView::dimensions(&image);
+        for format in &formats {
+            if let Some(data) = encode_within(&image, *format, options)? {
+                return Ok(PreparedImage {
+                    data,
+                    format: *format,
+                    width,
+                    height,
+                });
+            }
+        }
+
+        if width.max(height) <= MIN_DIMENSION {
+            return Err(Error::validation(format!(
+                "Image does not fit in {} bytes at any accepted size",
+                options.max_bytes.unwrap_or_default()
+            )));
+        }
+        let next_width = ((f64::from(width) * SHRINK_FACTOR) as u32).max(1);
+        let next_height = ((f64::from(height) * SHRINK_FACTOR) as u32).max(1);
+        image = resize(&image, next_width, next_height);
+    }
+}
+
+/// Scale `width` x `height` down to fit the options' dimensions, keeping the
+/// aspect ratio.
+fn fit_within(width: u32, height: u32, options: &ImageOptions) -> (u32, u32) {
+    let max_width = options.max_width.unwrap_or(width).max(1);
+    let max_height = options.max_height.unwrap_or(height).max(1);
+    if width <= max_width && height <= max_height {
+        return (width, height);
+    }
+    let scale =
+        (f64::from(max_width) / f64::from(width)).min(f64::from(max_height) / f64::from(height));
+    (
+        ((f64::from(width) * scale).round() as u32).clamp(1, max_width),
+        ((f64::from(height) * scale).round() as u32).clamp(1, max_height),
+    )
+}
+
+fn resize(image: &::image::DynamicImage, width: u32, height: u32) -> ::image::DynamicImage {
+    if ::image::GenericImageView::dimensions(image) == (width, height) {
+        return image.clone();
+    }
+    image.resize_exact(width, height, ::image::imageops::FilterType::Lanczos3)
+}
+
+/// Encode `image` as `format`, returning `None` if no encoding fits the cap.
+fn encode_within(
+    image: &::image::DynamicImage,
+    format: ImageFormat,
+    options: &ImageOptions,
+) -> Result<Option<Vec<u8>>> {
+    let encoded = match format {
+        ImageFormat::Png => vec![encode(image, ::image::ImageFormat::Png)?],
+        ImageFormat::WebP => {
+            let rgba = ::image::DynamicImage::ImageRgba8(image.to_rgba8());
+            vec![encode(&rgba, ::image::ImageFormat::WebP)?]
+        },
+        ImageFormat::Jpeg => {
+            let rgb = ::image::DynamicImage::ImageRgb8(image.to_rgb8());
+            let mut attempts = Vec::with_capacity(JPEG_QUALITIES.len());
+            for quality in JPEG_QUALITIES {
+                let mut data = Vec::new();
+                rgb.write_with_encoder(::image::codecs::jpeg::JpegEncoder::new_with_quality(
+                    &mut data, quality,
+                ))
+                .map_err(|e| Error::internal(format!("Failed to encode JPEG: {}", e)))?;
+                let fits = options.fits(data.len());
+                attempts.push(data);
+                if fits {
+                    break;
+                }
+            }
+            attempts
+        },
+    };
+    Ok(encoded.into_iter().find(|data| options.fits(data.len())))
+}
+
+fn encode(image: &::image::DynamicImage, format: ::image::ImageFormat) -> Result<Vec<u8>> {
+    let mut data = Cursor::new(Vec::new());
+    image
+        .write_to(&mut data, format)
+        .map_err(|e| Error::internal(format!("Failed to encode {:?}: {}", format, e)))?;
+    Ok(data.into_inner())
+}
+
+/// Length of `len` bytes once base64-encoded with padding.
+fn base64_len(len: usize) -> usize {
+    base64::encoded_len(len, true).unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    fn gradient_png(width: u32, height: u32) -> Vec<u8> {
+        let image = ::image::RgbaImage::from_fn(width, height, |x, y| {
+            ::image::Rgba([
+                (x * 7 % 256) as u8,
+                (y * 13 % 256) as u8,
+                ((x ^ y) % 256) as u8,
+                255,
+            ])
+        });
+        encode(
+            &::image::DynamicImage::ImageRgba8(image),
+            ::image::ImageFormat::Png,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fitting_image_passes_through() {
+        let png = gradient_png(32, 16);
+        let prepared = prepare(&png, &ImageOptions::new()).unwrap();
+
+        assert_eq!(prepared.format, ImageFormat::Png);
+        assert_eq!((prepared.width, prepared.height), (32, 16));
+        assert_eq!(prepared.data, png);
+    }
+
+    #[test]
+    fn test_thumbnail_keeps_aspect_ratio() {
+        let png = gradient_png(400, 200);
+        let prepared = prepare(&png, &ImageOptions::thumbnail(100)).unwrap();
+
+        assert_eq!((prepared.width, prepared.height), (100, 50));
+        let decoded = ::image::load_from_memory(&prepared.data).unwrap();
+        assert_eq!(::image::GenericImageView::dimensions(&decoded), (100, 50));
+    }
+
+    #[test]
+    fn test_converts_to_accepted_format() {
+        let png = gradient_png(24, 24);
+        let options = ImageOptions::new().with_accepted_mime_types(["image/webp"]);
+        let prepared = prepare(&png, &options).unwrap();
+
+        assert_eq!(prepared.format, ImageFormat::WebP);
+        assert_eq!(
+            ::image::guess_format(&prepared.data).unwrap(),
+            ::image::ImageFormat::WebP
+        );
+    }
+
+    #[test]
+    fn test_shrinks_to_fit_byte_cap() {
+        let png = gradient_png(512, 512);
+        let options = ImageOptions::new()
+            .with_formats(vec![ImageFormat::Png])
+            .with_max_bytes(8 * 1024);
+        let prepared = prepare(&png, &options).unwrap();
+
+        assert!(prepared.width < 512);
+        assert!(base64_len(prepared.data.len()) <= 8 * 1024);
+    }
+
+    #[test]
+    fn test_impossible_cap_is_an_error() {
+        let png = gradient_png(64, 64);
+        let options = ImageOptions::new().with_max_bytes(8);
+        assert!(prepare(&png, &options).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_image_data() {
+        assert!(prepare(b"not an image", &ImageOptions::new()).is_err());
+        assert!(prepare(
+            &gradient_png(4, 4),
+            &ImageOptions::new().with_formats(vec![])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_into_content_round_trips() {
+        let png = gradient_png(8, 8);
+        let content = prepare(&png, &ImageOptions::new()).unwrap().into_content();
+        match content {
+            Content::Image { data, mime_type } => {
+                assert_eq!(mime_type, "image/png");
+                assert_eq!(
+                    base64::engine::general_purpose::STANDARD
+                        .decode(data)
+                        .unwrap(),
+                    png
+                );
+            },
+            other => panic!("expected image content, got {:?}", other),
+        }
+    }
+}
@@ -6,6 +6,11 @@ pub mod batching;
 pub mod parallel_batch;
 pub mod validation;
 
+/// Resizing, format conversion and size caps for image content
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+pub mod image;
+
 #[cfg(feature = "simd")]
 /// SIMD-accelerated JSON parsing utilities for high-performance message processing
 pub mod json_simd;
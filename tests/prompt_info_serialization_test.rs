@@ -1,7 +1,6 @@
 //! Test `PromptInfo` serialization to debug the metadata issue
 
 use pmcp::types::{PromptArgument, PromptInfo};
-use pmcp::PromptHandler;
 
 #[test]
 fn test_prompt_info_serializes_all_fields() {
@@ -53,9 +52,11 @@ fn test_prompt_info_with_none_fields() {
     );
 }
 
+#[cfg(feature = "workflow")]
 #[test]
 fn test_workflow_prompt_info_round_trip() {
     use pmcp::server::workflow::{SequentialWorkflow, WorkflowPromptHandler};
+    use pmcp::PromptHandler;
     use std::collections::HashMap;
 
     let workflow = SequentialWorkflow::new("add_project_task", "Add a task to a Logseq project")
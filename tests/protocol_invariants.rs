@@ -221,14 +221,14 @@ proptest! {
             0 => ClientRequest::Ping,
             1 => ClientRequest::ListTools(ListToolsRequest { cursor: cursor.clone() }),
             2 => ClientRequest::CallTool(CallToolRequest::new(tool_name, args)),
-            3 => ClientRequest::ListPrompts(ListPromptsRequest { cursor: cursor.clone() }),
+            3 => ClientRequest::ListPrompts(ListPromptsRequest { cursor: cursor.clone(), prefix: None }),
             4 => ClientRequest::GetPrompt(GetPromptRequest {
                 name: prompt_name,
                 arguments: Default::default(),
                 _meta: None,
             }),
             5 => ClientRequest::ListResources(ListResourcesRequest { cursor }),
-            _ => ClientRequest::ReadResource(ReadResourceRequest { uri: resource_uri, _meta: None }),
+            _ => ClientRequest::ReadResource(ReadResourceRequest::new(resource_uri)),
         };
 
         let json = serde_json::to_value(&request).unwrap();
@@ -2,7 +2,9 @@
 mod session_validation_tests {
     use pmcp::server::streamable_http_server::StreamableHttpServer;
     use pmcp::server::Server;
-    use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+    use pmcp::shared::streamable_http::{
+        StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+    };
     use pmcp::shared::{Transport, TransportMessage};
     use pmcp::types::{
         ClientCapabilities, ClientRequest, Implementation, InitializeRequest, Request,
@@ -35,18 +37,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup first client and initialize successfully
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client1 = StreamableHttpTransport::new(client_config);
 
         // First initialization - should succeed
@@ -71,18 +68,14 @@ mod session_validation_tests {
             .expect("Session should be set after first init");
 
         // Setup second client with same session ID and attempt re-initialization
-        let client_config2 = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config2 = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: Some(session_id),
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .with_session_id(session_id)
+        .build();
         let mut client2 = StreamableHttpTransport::new(client_config2);
 
         // Second initialization with same session - should fail
@@ -98,18 +91,14 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup client with invalid session ID
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: Some("invalid-session-id".to_string()),
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .with_session_id("invalid-session-id")
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send non-init request with invalid session ID - should fail
@@ -130,18 +119,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup client without session ID
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send non-init request without session ID - should fail
@@ -162,18 +146,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup client transport
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Initialize
@@ -266,18 +245,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup client and initialize
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Initialize to get session and negotiated version
@@ -342,18 +316,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // Setup client
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Initialize
@@ -448,18 +417,13 @@ mod session_validation_tests {
 
         // Test 3: Missing session ID for non-init request
         // First initialize to establish that we're in stateful mode
-        let init_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let init_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut init_client = StreamableHttpTransport::new(init_config);
 
         let init_message = TransportMessage::Request {
@@ -480,18 +444,13 @@ mod session_validation_tests {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Now try a non-init request without session ID - should fail with JSON error
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None, // No session ID
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let ping_message = TransportMessage::Request {
@@ -504,18 +463,14 @@ mod session_validation_tests {
         assert!(result.is_err());
 
         // Test 4: Unknown session ID - should return JSON error with 404
-        let client_config2 = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config2 = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: Some("non-existent-session".to_string()),
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .with_session_id("non-existent-session")
+        .build();
         let mut client2 = StreamableHttpTransport::new(client_config2);
 
         let ping_message2 = TransportMessage::Request {
@@ -535,18 +490,13 @@ mod session_validation_tests {
         let (server_addr, server_task) = create_test_server().await?;
 
         // 1. Initialize - should succeed and return session ID
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -600,18 +550,14 @@ mod session_validation_tests {
         assert_eq!(delete_response.status().as_u16(), 200);
 
         // 4. Try to use deleted session - should fail
-        let client_config2 = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config2 = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: Some(session_id),
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .with_session_id(session_id)
+        .build();
         let mut client2 = StreamableHttpTransport::new(client_config2);
 
         let ping_message2 = TransportMessage::Request {
@@ -15,7 +15,9 @@ use pmcp::server::streamable_http_server::{
     InMemoryEventStore, StreamableHttpServer, StreamableHttpServerConfig,
 };
 use pmcp::server::Server;
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::types::capabilities::ServerCapabilities;
 use pmcp::ClientBuilder;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -86,15 +88,9 @@ async fn test_middleware_runs_on_sse_get() {
         .unwrap();
 
     let event_store = InMemoryEventStore::default();
-    let config = StreamableHttpServerConfig {
-        session_id_generator: Some(Box::new(|| uuid::Uuid::new_v4().to_string())),
-        enable_json_response: false, // Force SSE mode
-        event_store: Some(Arc::new(event_store)),
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let mut config = StreamableHttpServerConfig::default();
+    config.session_id_generator = Some(Box::new(|| uuid::Uuid::new_v4().to_string()));
+    config.event_store = Some(Arc::new(event_store));
 
     let server_instance = StreamableHttpServer::with_config(
         "127.0.0.1:0".parse().unwrap(),
@@ -110,15 +106,10 @@ async fn test_middleware_runs_on_sse_get() {
     http_chain.add(tracking_middleware.clone());
 
     // Create client with SSE transport and middleware
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false, // Use SSE
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
     let mut client = ClientBuilder::new(transport).build();
@@ -188,15 +179,7 @@ async fn test_middleware_with_multiple_http_methods() {
         .build()
         .unwrap();
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true, // JSON mode for simpler testing
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance = StreamableHttpServer::with_config(
         "127.0.0.1:0".parse().unwrap(),
@@ -214,15 +197,11 @@ async fn test_middleware_with_multiple_http_methods() {
     http_chain.add(method_tracker.clone());
 
     // Create client
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
     let mut client = ClientBuilder::new(transport).build();
@@ -280,15 +259,7 @@ async fn test_middleware_modifies_request_headers() {
         .build()
         .unwrap();
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance = StreamableHttpServer::with_config(
         "127.0.0.1:0".parse().unwrap(),
@@ -303,15 +274,11 @@ async fn test_middleware_modifies_request_headers() {
         header_value: "test-value-123".to_string(),
     }));
 
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
     let mut client = ClientBuilder::new(transport).build();
@@ -367,15 +334,7 @@ async fn test_middleware_response_processing() {
         .build()
         .unwrap();
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance = StreamableHttpServer::with_config(
         "127.0.0.1:0".parse().unwrap(),
@@ -388,15 +347,11 @@ async fn test_middleware_response_processing() {
     let mut http_chain = HttpMiddlewareChain::new();
     http_chain.add(response_tracker.clone());
 
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
     let mut client = ClientBuilder::new(transport).build();
@@ -0,0 +1,215 @@
+//! Tests for negotiated gzip/zstd compression on the streamable HTTP transport.
+
+#![cfg(feature = "streamable-http")]
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::header::{ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+use axum::http::{Method, Request, StatusCode};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::tower_layers::CompressionConfig;
+use pmcp::server::{Server, ToolHandler};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
+use pmcp::types::capabilities::ServerCapabilities;
+use pmcp::types::Content;
+use pmcp::{ClientBuilder, RequestHandlerExtra};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Returns `count` rows of query-like data.
+struct RowsTool;
+
+#[async_trait]
+impl ToolHandler for RowsTool {
+    async fn handle(&self, args: Value, _extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+        let count = args["count"].as_u64().unwrap_or(1);
+        let rows: Vec<Value> = (0..count)
+            .map(|i| json!({ "id": i, "region": "us-east-1", "revenue": i * 100 }))
+            .collect();
+        Ok(json!({ "rows": rows }))
+    }
+}
+
+fn http_server(config: StreamableHttpServerConfig) -> StreamableHttpServer {
+    let server = Server::builder()
+        .name("compression-test-server")
+        .version("1.0.0")
+        .capabilities(ServerCapabilities::tools_only())
+        .tool("rows", RowsTool)
+        .build()
+        .unwrap();
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        config,
+    )
+}
+
+fn compressed_stateless() -> StreamableHttpServerConfig {
+    StreamableHttpServerConfig::stateless().with_compression(CompressionConfig::new())
+}
+
+fn call_rows(count: u64) -> Value {
+    json!({ "name": "rows", "arguments": { "count": count } })
+}
+
+/// A gzip-compressed `ping` request padded to `padding` bytes of params.
+fn gzipped_ping(padding: usize) -> Request<Body> {
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "ping",
+        "params": { "padding": "a".repeat(padding) },
+    });
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(message.to_string().as_bytes()).unwrap();
+    Request::builder()
+        .method(Method::POST)
+        .uri("/")
+        .header(CONTENT_TYPE, "application/json")
+        .header(ACCEPT, "application/json, text/event-stream")
+        .header(CONTENT_ENCODING, "gzip")
+        .body(Body::from(encoder.finish().unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn large_json_response_is_compressed() {
+    for encoding in ["gzip", "zstd"] {
+        let client = http_server(compressed_stateless())
+            .test_client()
+            .with_header(ACCEPT_ENCODING, encoding);
+        client.initialize().await.assert_success().await;
+
+        let response = client
+            .request("tools/call", call_rows(500))
+            .await
+            .assert_success()
+            .await;
+        assert_eq!(response.header("content-encoding"), Some(encoding));
+    }
+}
+
+#[tokio::test]
+async fn small_response_is_not_compressed() {
+    let client = http_server(compressed_stateless())
+        .test_client()
+        .with_header(ACCEPT_ENCODING, "gzip");
+    client.initialize().await.assert_success().await;
+
+    let response = client.request("ping", json!({})).await;
+    assert_eq!(response.header("content-encoding"), None);
+    response.assert_result().await;
+}
+
+#[tokio::test]
+async fn compression_is_off_by_default() {
+    let client = http_server(StreamableHttpServerConfig::stateless())
+        .test_client()
+        .with_header(ACCEPT_ENCODING, "gzip");
+    client.initialize().await.assert_success().await;
+
+    let response = client.request("tools/call", call_rows(500)).await;
+    assert_eq!(response.header("content-encoding"), None);
+    response.assert_result().await;
+}
+
+#[tokio::test]
+async fn sse_responses_are_not_compressed() {
+    let config = StreamableHttpServerConfig::default().with_compression(CompressionConfig::new());
+    let client = http_server(config)
+        .test_client()
+        .with_header(ACCEPT_ENCODING, "gzip, zstd");
+    client.initialize().await.assert_success().await;
+
+    let response = client.request("tools/call", call_rows(500)).await;
+    assert!(response.is_sse());
+    assert_eq!(response.header("content-encoding"), None);
+    response.assert_result().await;
+}
+
+#[tokio::test]
+async fn disabled_encoding_is_not_used() {
+    let config = StreamableHttpServerConfig::stateless()
+        .with_compression(CompressionConfig::new().without_zstd());
+    let client = http_server(config)
+        .test_client()
+        .with_header(ACCEPT_ENCODING, "zstd");
+    client.initialize().await.assert_success().await;
+
+    let response = client.request("tools/call", call_rows(500)).await;
+    assert_eq!(response.header("content-encoding"), None);
+}
+
+#[tokio::test]
+async fn transport_decodes_compressed_responses() {
+    let (addr, handle) = http_server(compressed_stateless()).start().await.unwrap();
+
+    let transport = StreamableHttpTransport::new(
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .build(),
+    );
+    let mut client = ClientBuilder::new(transport).build();
+    client
+        .initialize(pmcp::ClientCapabilities::minimal())
+        .await
+        .unwrap();
+
+    let result = client
+        .call_tool("rows".to_string(), json!({ "count": 500 }))
+        .await
+        .unwrap();
+    let Some(Content::Text { text }) = result.content.first() else {
+        panic!("expected text content, got {:?}", result.content);
+    };
+    let value: Value = serde_json::from_str(text).unwrap();
+    assert_eq!(value["rows"].as_array().unwrap().len(), 500);
+    assert_eq!(value["rows"][499]["revenue"], 49_900);
+
+    drop(client);
+    handle.abort();
+}
+
+#[tokio::test]
+async fn compressed_request_is_decompressed() {
+    let client = http_server(compressed_stateless()).test_client();
+
+    client
+        .send(gzipped_ping(64 * 1024))
+        .await
+        .assert_result()
+        .await;
+}
+
+#[tokio::test]
+async fn oversized_decompressed_request_is_rejected() {
+    let config = StreamableHttpServerConfig::stateless()
+        .with_compression(CompressionConfig::new().max_decompressed_size(64 * 1024));
+    let client = http_server(config).test_client();
+
+    client
+        .send(gzipped_ping(1024 * 1024))
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE)
+        .await;
+}
+
+#[tokio::test]
+async fn decompressed_size_is_capped_by_default() {
+    let client = http_server(compressed_stateless()).test_client();
+
+    // About 32 KiB on the wire, 17 MiB once inflated
+    client
+        .send(gzipped_ping(17 * 1024 * 1024))
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE)
+        .await;
+}
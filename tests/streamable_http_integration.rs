@@ -6,7 +6,9 @@ use pmcp::server::streamable_http_server::{
     InMemoryEventStore, StreamableHttpServer, StreamableHttpServerConfig,
 };
 use pmcp::server::Server;
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::shared::{Transport, TransportMessage};
 use pmcp::types::capabilities::ServerCapabilities;
 use pmcp::types::{
@@ -33,15 +35,7 @@ async fn create_test_server() -> Arc<Mutex<Server>> {
 async fn test_streamable_http_stateless_mode() {
     // Start server in stateless mode
     let server = create_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None, // Stateless mode
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -49,15 +43,10 @@ async fn test_streamable_http_stateless_mode() {
     let (addr, handle) = server_instance.start().await.unwrap();
 
     // Create client transport
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .build();
 
     let mut transport = StreamableHttpTransport::new(client_config);
 
@@ -96,27 +85,23 @@ async fn test_streamable_http_stateful_mode() {
     let init_clone = session_initialized.clone();
     let closed_clone = session_closed.clone();
 
-    let config = StreamableHttpServerConfig {
-        session_id_generator: Some(Box::new(|| {
-            format!("test-session-{}", uuid::Uuid::new_v4())
-        })),
-        enable_json_response: false,
-        event_store: Some(Arc::new(InMemoryEventStore::default())),
-        on_session_initialized: Some(Box::new(move |_session_id| {
-            let init = init_clone.clone();
-            tokio::spawn(async move {
-                *init.lock().await = true;
-            });
-        })),
-        on_session_closed: Some(Box::new(move |_session_id| {
-            let closed = closed_clone.clone();
-            tokio::spawn(async move {
-                *closed.lock().await = true;
-            });
-        })),
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let mut config = StreamableHttpServerConfig::default();
+    config.session_id_generator = Some(Box::new(|| {
+        format!("test-session-{}", uuid::Uuid::new_v4())
+    }));
+    config.event_store = Some(Arc::new(InMemoryEventStore::default()));
+    config.on_session_initialized = Some(Box::new(move |_session_id| {
+        let init = init_clone.clone();
+        tokio::spawn(async move {
+            *init.lock().await = true;
+        });
+    }));
+    config.on_session_closed = Some(Box::new(move |_session_id| {
+        let closed = closed_clone.clone();
+        tokio::spawn(async move {
+            *closed.lock().await = true;
+        });
+    }));
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -124,15 +109,9 @@ async fn test_streamable_http_stateful_mode() {
     let (addr, handle) = server_instance.start().await.unwrap();
 
     // Create client transport
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .build();
 
     let mut transport = StreamableHttpTransport::new(client_config);
 
@@ -203,15 +182,7 @@ async fn test_sse_parser_integration() {
 #[tokio::test]
 async fn test_transport_send_receive_multiple() {
     let server = create_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -219,15 +190,10 @@ async fn test_transport_send_receive_multiple() {
     let (addr, handle) = server_instance.start().await.unwrap();
 
     // Create client transport
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .build();
 
     let mut transport = StreamableHttpTransport::new(client_config);
 
@@ -294,18 +260,12 @@ async fn test_transport_with_headers() {
     let (addr, handle) = server_instance.start().await.unwrap();
 
     // Create client transport with extra headers
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![
-            ("X-Custom-Header".to_string(), "custom-value".to_string()),
-            ("X-API-Key".to_string(), "test-key".to_string()),
-        ],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .with_header("X-Custom-Header", "custom-value")
+            .with_header("X-API-Key", "test-key")
+            .enable_json_response()
+            .build();
 
     let mut transport = StreamableHttpTransport::new(client_config);
 
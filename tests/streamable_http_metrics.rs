@@ -0,0 +1,99 @@
+//! Tests for the Prometheus `/metrics` route of the streamable HTTP server.
+
+#![cfg(feature = "streamable-http")]
+
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use pmcp::server::observability::PrometheusMetrics;
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::{Server, SimpleTool};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn http_server(metrics: Option<PrometheusMetrics>) -> StreamableHttpServer {
+    let server = Server::builder()
+        .name("metrics-test-server")
+        .version("1.0.0")
+        .tool(
+            "echo",
+            SimpleTool::new("echo", |args, _extra| Box::pin(async move { Ok(args) })),
+        )
+        .build()
+        .unwrap();
+    let mut config = StreamableHttpServerConfig::stateless();
+    config.metrics = metrics;
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        config,
+    )
+}
+
+fn metrics_request() -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn metrics_route_reports_requests_tools_and_errors() {
+    let metrics = PrometheusMetrics::new();
+    let client = http_server(Some(metrics.clone())).test_client();
+
+    client.initialize().await.assert_success().await;
+    client
+        .request("tools/call", json!({ "name": "echo", "arguments": {} }))
+        .await
+        .assert_result()
+        .await;
+    client
+        .request("prompts/get", json!({ "name": "missing" }))
+        .await
+        .assert_success()
+        .await;
+
+    let response = client.send(metrics_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response
+        .header("content-type")
+        .unwrap()
+        .starts_with("text/plain; version=0.0.4"));
+    let text = response.text().await;
+
+    assert!(text.contains("mcp_requests_total{method=\"initialize\"} 1"));
+    assert!(text.contains("mcp_requests_total{method=\"tools/call\"} 1"));
+    assert!(text.contains("mcp_tool_calls_total{tool=\"echo\"} 1"));
+    assert!(text.contains("mcp_request_errors_total{method=\"prompts/get\""));
+    assert!(text.contains("mcp_request_duration_seconds_count{method=\"tools/call\"} 1"));
+    assert!(text.contains("mcp_requests_in_flight 0"));
+    assert_eq!(metrics.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn unregistered_tool_names_share_the_unknown_label() {
+    let client = http_server(Some(PrometheusMetrics::new())).test_client();
+
+    client.initialize().await.assert_success().await;
+    for name in ["made-up-1", "made-up-2"] {
+        client
+            .request("tools/call", json!({ "name": name, "arguments": {} }))
+            .await
+            .assert_success()
+            .await;
+    }
+
+    let text = client.send(metrics_request()).await.text().await;
+    assert!(text.contains("mcp_tool_calls_total{tool=\"unknown\"} 2"));
+    assert!(!text.contains("made-up"));
+}
+
+#[tokio::test]
+async fn metrics_route_is_absent_by_default() {
+    let client = http_server(None).test_client();
+
+    let response = client.send(metrics_request()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -14,7 +14,7 @@ use pmcp::client::oauth_middleware::{BearerToken, OAuthClientMiddleware};
 use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
 use pmcp::server::{Server, ToolHandler};
 use pmcp::shared::streamable_http::{
-    AuthProvider, StreamableHttpTransport, StreamableHttpTransportConfig,
+    AuthProvider, StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
 };
 use pmcp::types::capabilities::ServerCapabilities;
 use pmcp::ClientBuilder;
@@ -55,15 +55,7 @@ async fn create_auth_test_server() -> Arc<Mutex<Server>> {
 async fn test_oauth_middleware_injects_token() {
     // Start server
     let server = create_auth_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -79,15 +71,11 @@ async fn test_oauth_middleware_injects_token() {
     http_chain.add(Arc::new(OAuthClientMiddleware::new(token)));
 
     // Create client with OAuth middleware, no auth_provider
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
 
@@ -123,15 +111,7 @@ async fn test_auth_provider_takes_precedence_over_oauth() {
 
     // Start server
     let server = create_auth_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -148,15 +128,12 @@ async fn test_auth_provider_takes_precedence_over_oauth() {
         token: "auth-provider-token-wins".to_string(),
     });
 
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: Some(auth_provider),
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .with_auth_provider(auth_provider)
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
 
@@ -183,15 +160,7 @@ async fn test_auth_provider_takes_precedence_over_oauth() {
 async fn test_oauth_token_expiry_triggers_error() {
     // Start server
     let server = create_auth_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -211,15 +180,11 @@ async fn test_oauth_token_expiry_triggers_error() {
     http_chain.add(Arc::new(OAuthClientMiddleware::new(expired_token)));
 
     // Create client with expired OAuth token
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
 
@@ -243,15 +208,7 @@ async fn test_oauth_token_expiry_triggers_error() {
 async fn test_multiple_requests_with_oauth() {
     // Start server
     let server = create_auth_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -264,15 +221,11 @@ async fn test_multiple_requests_with_oauth() {
     http_chain.add(Arc::new(OAuthClientMiddleware::new(token)));
 
     // Create client
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+            .enable_json_response()
+            .with_http_middleware(Arc::new(http_chain))
+            .build();
 
     let transport = StreamableHttpTransport::new(client_config);
 
@@ -300,15 +253,7 @@ async fn test_multiple_requests_with_oauth() {
 async fn test_oauth_with_case_insensitive_header_check() {
     // Start server
     let server = create_auth_test_server().await;
-    let config = StreamableHttpServerConfig {
-        session_id_generator: None,
-        enable_json_response: true,
-        event_store: None,
-        on_session_initialized: None,
-        on_session_closed: None,
-        http_middleware: None,
-        allowed_origins: None,
-    };
+    let config = StreamableHttpServerConfig::stateless();
 
     let server_instance =
         StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
@@ -321,21 +266,12 @@ async fn test_oauth_with_case_insensitive_header_check() {
     http_chain.add(Arc::new(OAuthClientMiddleware::new(token)));
 
     // Create client with extra headers that include authorization (different case)
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr)).unwrap(),
-        extra_headers: vec![
-            // This should be detected by OAuth middleware despite case difference
-            (
-                "AUTHORIZATION".to_string(),
-                "Bearer manual-token".to_string(),
-            ),
-        ],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: Some(Arc::new(http_chain)),
-    };
+    let client_config = StreamableHttpTransportConfigBuilder::new(Url::parse(&format!("http://{}", addr)).unwrap())
+        // This should be detected by OAuth middleware despite case difference
+        .with_header("AUTHORIZATION", "Bearer manual-token")
+        .enable_json_response()
+        .with_http_middleware(Arc::new(http_chain))
+        .build();
 
     let transport = StreamableHttpTransport::new(client_config);
 
@@ -0,0 +1,134 @@
+//! Tests for resource profiles on the streamable HTTP transport.
+
+#![cfg(feature = "streamable-http")]
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use pmcp::server::resource_profile::ResourceProfile;
+use pmcp::server::streamable_http_server::{
+    EventStore, InMemoryEventStore, StreamableHttpServer, StreamableHttpServerConfig,
+};
+use pmcp::shared::TransportMessage;
+use pmcp::types::{JSONRPCResponse, RequestId};
+use pmcp::Server;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn http_server(config: StreamableHttpServerConfig) -> StreamableHttpServer {
+    let server = Server::builder()
+        .name("resource-profile-test-server")
+        .version("1.0.0")
+        .build()
+        .unwrap();
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        config,
+    )
+}
+
+fn response_message(id: i64, payload: &str) -> TransportMessage {
+    TransportMessage::Response(JSONRPCResponse::success(
+        RequestId::Number(id),
+        json!({ "payload": payload }),
+    ))
+}
+
+#[tokio::test]
+async fn serverless_profile_is_stateless() {
+    let http = http_server(StreamableHttpServerConfig::from_profile(
+        ResourceProfile::serverless(),
+    ));
+    let client = http.test_client();
+
+    let response = client.initialize().await.assert_success().await;
+    assert!(response.header("mcp-session-id").is_none());
+    assert!(!response.is_sse());
+}
+
+#[tokio::test]
+async fn oversized_body_is_rejected() {
+    let profile = ResourceProfile::serverless().with_max_request_body_bytes(Some(1024));
+    let http = http_server(StreamableHttpServerConfig::from_profile(profile));
+    let client = http.test_client();
+
+    let response = client
+        .request("tools/list", json!({ "padding": "x".repeat(4096) }))
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn declared_content_length_over_limit_is_rejected() {
+    let profile = ResourceProfile::serverless().with_max_request_body_bytes(Some(1024));
+    let http = http_server(StreamableHttpServerConfig::from_profile(profile));
+    let client = http.test_client();
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json, text/event-stream")
+        .header(header::CONTENT_LENGTH, "1048576")
+        .body(Body::empty())
+        .unwrap();
+    let response = client.send(request).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn requests_over_concurrency_limit_are_shed() {
+    let profile = ResourceProfile::serverless().with_max_concurrent_requests(Some(0));
+    let http = http_server(StreamableHttpServerConfig::from_profile(profile));
+    let client = http.test_client();
+
+    let response = client.initialize().await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn standard_profile_keeps_sessions_and_event_store() {
+    let config = StreamableHttpServerConfig::from_profile(ResourceProfile::standard());
+    assert!(config.session_id_generator.is_some());
+    assert!(config.event_store.is_some());
+
+    let client = http_server(config).test_client();
+    let response = client.initialize().await.assert_success().await;
+    assert!(response.header("mcp-session-id").is_some());
+}
+
+#[tokio::test]
+async fn bounded_event_store_evicts_oldest_and_skips_large_events() {
+    let store = InMemoryEventStore::with_limits(Some(2), Some(256));
+
+    store
+        .store_event("s1", "e1", &response_message(1, "a"))
+        .await
+        .unwrap();
+    store
+        .store_event("s1", "e2", &response_message(2, "b"))
+        .await
+        .unwrap();
+    store
+        .store_event("s1", "e3", &response_message(3, "c"))
+        .await
+        .unwrap();
+    store
+        .store_event("s1", "e4", &response_message(4, &"x".repeat(1024)))
+        .await
+        .unwrap();
+
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.get_stream_for_event("e1").await.unwrap(), None);
+    assert_eq!(store.get_stream_for_event("e4").await.unwrap(), None);
+
+    let replayed: Vec<String> = store
+        .replay_events_after("e2")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(replayed, vec!["e3".to_string()]);
+}
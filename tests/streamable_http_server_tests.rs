@@ -2,7 +2,9 @@
 mod streamable_http_server_tests {
     use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
     use pmcp::server::Server;
-    use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+    use pmcp::shared::streamable_http::{
+        StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+    };
     use pmcp::shared::{Transport, TransportMessage};
     use pmcp::types::{
         ClientCapabilities, ClientRequest, Implementation, InitializeRequest, Request,
@@ -32,18 +34,13 @@ mod streamable_http_server_tests {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Setup client
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send initialization without session ID
@@ -92,18 +89,13 @@ mod streamable_http_server_tests {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Setup client without session
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None, // No session ID
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send non-init request without session ID - should fail
@@ -134,15 +126,8 @@ mod streamable_http_server_tests {
         ));
         let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
 
-        let config = StreamableHttpServerConfig {
-            session_id_generator: None, // Stateless mode
-            enable_json_response: false,
-            event_store: None,
-            on_session_initialized: None,
-            on_session_closed: None,
-            http_middleware: None,
-            allowed_origins: None,
-        };
+        let mut config = StreamableHttpServerConfig::stateless();
+        config.enable_json_response = false;
 
         let http_server = StreamableHttpServer::with_config(addr, server, config);
         let (server_addr, server_task) = http_server
@@ -151,18 +136,13 @@ mod streamable_http_server_tests {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Setup client
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send initialization
@@ -226,18 +206,13 @@ mod streamable_http_server_tests {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Setup client
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
                 Box::new(pmcp::Error::Internal(e.to_string()))
                     as Box<dyn std::error::Error + Send + Sync>
             })?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send initialization
@@ -267,4 +242,87 @@ mod streamable_http_server_tests {
         server_task.abort();
         Ok(())
     }
+
+    #[tokio::test]
+    #[allow(clippy::used_underscore_binding)] // _meta is part of MCP protocol spec
+    async fn test_stream_chunks_arrive_before_tool_response() -> Result<()> {
+        use pmcp::types::notifications::ProgressToken;
+        use pmcp::types::protocol::RequestMeta;
+        use pmcp::types::{CallToolRequest, Notification, ServerNotification};
+        use pmcp::SimpleTool;
+
+        let tool = SimpleTool::new("tail", |_args, extra| {
+            Box::pin(async move {
+                extra.stream_chunk("first").await?;
+                extra.stream_chunk("second").await?;
+                Ok(serde_json::json!({ "output": "first second" }))
+            })
+        });
+        let server = Arc::new(Mutex::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .tool("tail", tool)
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        ));
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let mut config = StreamableHttpServerConfig::stateless();
+        config.enable_json_response = false;
+        let http_server = StreamableHttpServer::with_config(addr, server, config);
+        let (server_addr, server_task) = http_server
+            .start()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+                Box::new(pmcp::Error::Internal(e.to_string()))
+                    as Box<dyn std::error::Error + Send + Sync>
+            })?,
+        )
+        .build();
+        let mut client = StreamableHttpTransport::new(client_config);
+
+        let mut call = CallToolRequest::new("tail", serde_json::json!({}));
+        call._meta = Some(
+            RequestMeta::new().with_progress_token(ProgressToken::String("tail-1".to_string())),
+        );
+        client
+            .send(TransportMessage::Request {
+                id: 1i64.into(),
+                request: Request::Client(Box::new(ClientRequest::CallTool(call))),
+            })
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let mut chunks = Vec::new();
+        loop {
+            let message = client
+                .receive()
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+            match message {
+                TransportMessage::Notification(
+                    Notification::Progress(progress)
+                    | Notification::Server(ServerNotification::Progress(progress)),
+                ) => {
+                    assert_eq!(
+                        progress.progress_token,
+                        ProgressToken::String("tail-1".to_string())
+                    );
+                    chunks.push(progress.message.unwrap_or_default());
+                },
+                TransportMessage::Response(response) => {
+                    assert!(response.result().is_some(), "tool call should succeed");
+                    break;
+                },
+                other => panic!("unexpected message: {other:?}"),
+            }
+        }
+        assert_eq!(chunks, vec!["first", "second"]);
+
+        server_task.abort();
+        Ok(())
+    }
 }
@@ -0,0 +1,137 @@
+//! Tests that the transport session of a streamable HTTP request reaches
+//! tool dispatch.
+
+#![cfg(feature = "streamable-http")]
+
+use pmcp::server::rate_limit::{Quota, RateLimitKey, RateLimiter};
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::widget_state::InMemoryWidgetStateStore;
+use pmcp::{Server, ServerBuilder, SimpleTool};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn http_server(server: Server) -> StreamableHttpServer {
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        StreamableHttpServerConfig::default(),
+    )
+}
+
+/// A server whose `whoami` tool returns the caller's session ID.
+fn server_builder() -> ServerBuilder {
+    Server::builder().name("sessions").version("1.0.0").tool(
+        "whoami",
+        SimpleTool::new("whoami", |_args, extra| {
+            Box::pin(async move { Ok(json!({ "session": extra.session_id })) })
+        }),
+    )
+}
+
+#[tokio::test]
+async fn tool_calls_carry_the_http_session_id() {
+    let server = server_builder().build().unwrap();
+    let client = http_server(server).test_client();
+    client.initialize().await.assert_success().await;
+
+    let result = client
+        .request("tools/call", json!({ "name": "whoami", "arguments": {} }))
+        .await
+        .assert_result()
+        .await;
+
+    let text = result["content"][0]["text"].as_str().unwrap();
+    let session = client.session_id().unwrap();
+    assert!(text.contains(&session), "{text} should name {session}");
+}
+
+#[tokio::test]
+async fn sessions_have_separate_rate_limit_buckets() {
+    let limiter = RateLimiter::new(Quota::per_minute(1)).keyed_by(RateLimitKey::Session);
+    let server = server_builder()
+        .tool_middleware(Arc::new(limiter))
+        .build()
+        .unwrap();
+    let http = http_server(server);
+    let first = http.test_client();
+    let second = http.test_client();
+    first.initialize().await.assert_success().await;
+    second.initialize().await.assert_success().await;
+    assert_ne!(first.session_id(), second.session_id());
+
+    let call = json!({ "name": "whoami", "arguments": {} });
+    first
+        .request("tools/call", call.clone())
+        .await
+        .assert_result()
+        .await;
+    first
+        .request("tools/call", call.clone())
+        .await
+        .assert_error(-32005)
+        .await;
+
+    second
+        .request("tools/call", call)
+        .await
+        .assert_result()
+        .await;
+}
+
+#[tokio::test]
+async fn anonymous_sessions_keep_separate_widget_state() {
+    let server = server_builder()
+        .widget_state_store(Arc::new(InMemoryWidgetStateStore::new()))
+        .tool(
+            "save_board",
+            SimpleTool::new("save_board", |args, extra| {
+                Box::pin(async move {
+                    extra
+                        .save_widget_state("ui://board", args["state"].clone())
+                        .await?;
+                    Ok(json!({}))
+                })
+            }),
+        )
+        .tool(
+            "load_board",
+            SimpleTool::new("load_board", |_args, extra| {
+                Box::pin(async move {
+                    let state = extra.load_widget_state("ui://board").await?;
+                    Ok(json!({ "state": state }))
+                })
+            }),
+        )
+        .build()
+        .unwrap();
+    let http = http_server(server);
+    let first = http.test_client();
+    let second = http.test_client();
+    first.initialize().await.assert_success().await;
+    second.initialize().await.assert_success().await;
+
+    let save = json!({ "name": "save_board", "arguments": { "state": { "selected": "e4" } } });
+    first
+        .request("tools/call", save)
+        .await
+        .assert_result()
+        .await;
+
+    let load = json!({ "name": "load_board", "arguments": {} });
+    let mine = first
+        .request("tools/call", load.clone())
+        .await
+        .assert_result()
+        .await;
+    let theirs = second
+        .request("tools/call", load)
+        .await
+        .assert_result()
+        .await;
+
+    let mine = mine["content"][0]["text"].as_str().unwrap();
+    let theirs = theirs["content"][0]["text"].as_str().unwrap();
+    assert!(mine.contains("e4"), "{mine} should hold the saved state");
+    assert!(!theirs.contains("e4"), "{theirs} should not see it");
+}
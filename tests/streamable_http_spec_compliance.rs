@@ -3,7 +3,9 @@
 mod spec_compliance_tests {
     use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
     use pmcp::server::Server;
-    use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+    use pmcp::shared::streamable_http::{
+        StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+    };
     use pmcp::shared::{Transport, TransportMessage};
     use pmcp::types::{
         ClientCapabilities, ClientRequest, Implementation, InitializeRequest, Request,
@@ -153,17 +155,12 @@ mod spec_compliance_tests {
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
         // First initialize to establish a session (in stateful mode)
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -230,17 +227,12 @@ mod spec_compliance_tests {
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
         // First initialize to establish we're testing non-init requests
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -304,17 +296,12 @@ mod spec_compliance_tests {
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
         // Initialize first
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -393,17 +380,12 @@ mod spec_compliance_tests {
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None, // No session ID initially
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -441,17 +423,12 @@ mod spec_compliance_tests {
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
         // Initialize to get session
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -510,15 +487,8 @@ mod spec_compliance_tests {
                 .map_err(box_err)?,
         ));
         let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
-        let config = StreamableHttpServerConfig {
-            session_id_generator: None, // Stateless mode
-            enable_json_response: false,
-            event_store: None,
-            on_session_initialized: None,
-            on_session_closed: None,
-            http_middleware: None,
-            allowed_origins: None,
-        };
+        let mut config = StreamableHttpServerConfig::stateless();
+        config.enable_json_response = false;
         let http_server = StreamableHttpServer::with_config(addr, server, config);
         http_server.start().await.map_err(box_err)
     }
@@ -527,17 +497,12 @@ mod spec_compliance_tests {
     async fn test_stateless_no_session_id_in_response() -> Result<()> {
         let (server_addr, server_task) = create_stateless_server().await?;
 
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -565,17 +530,12 @@ mod spec_compliance_tests {
     async fn test_stateless_reinitialize_allowed() -> Result<()> {
         let (server_addr, server_task) = create_stateless_server().await?;
 
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None,
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         let init_message = TransportMessage::Request {
@@ -612,17 +572,12 @@ mod spec_compliance_tests {
     async fn test_stateless_no_session_required() -> Result<()> {
         let (server_addr, server_task) = create_stateless_server().await?;
 
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: None, // No session ID
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send non-init request without session ID - should work in stateless mode
@@ -644,17 +599,13 @@ mod spec_compliance_tests {
     async fn test_stateless_ignores_arbitrary_session_id() -> Result<()> {
         let (server_addr, server_task) = create_stateless_server().await?;
 
-        let client_config = StreamableHttpTransportConfig {
-            url: Url::parse(&format!("http://{}", server_addr))
+        let client_config = StreamableHttpTransportConfigBuilder::new(
+            Url::parse(&format!("http://{}", server_addr))
                 .map_err(|e| pmcp::Error::Internal(e.to_string()))
                 .map_err(box_err)?,
-            extra_headers: vec![],
-            auth_provider: None,
-            session_id: Some("arbitrary-session-id".to_string()), // Arbitrary session ID
-            enable_json_response: false,
-            on_resumption_token: None,
-            http_middleware_chain: None,
-        };
+        )
+        .with_session_id("arbitrary-session-id")
+        .build();
         let mut client = StreamableHttpTransport::new(client_config);
 
         // Send request with arbitrary session ID - should be ignored in stateless mode
@@ -0,0 +1,109 @@
+//! Tests for resource subscriptions over streamable HTTP sessions.
+
+#![cfg(all(feature = "streamable-http", feature = "resources"))]
+
+use async_trait::async_trait;
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::subscriptions::{ResourceChangeNotifier, ResourceWatcher};
+use pmcp::Server;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Hands the notifier it is started with to the test.
+#[derive(Clone, Default)]
+struct TestWatcher(Arc<std::sync::Mutex<Option<ResourceChangeNotifier>>>);
+
+#[async_trait]
+impl ResourceWatcher for TestWatcher {
+    async fn start(&self, notifier: ResourceChangeNotifier) -> pmcp::Result<()> {
+        *self.0.lock().unwrap() = Some(notifier);
+        Ok(())
+    }
+}
+
+impl TestWatcher {
+    fn notify(&self, uri: &str) {
+        let notifier = self.0.lock().unwrap().clone().expect("watcher started");
+        assert!(notifier.notify(uri));
+    }
+}
+
+fn http_server(watcher: TestWatcher) -> (StreamableHttpServer, Arc<Mutex<Server>>) {
+    let server = Server::builder()
+        .name("subscriptions-test-server")
+        .version("1.0.0")
+        .resource_watcher(watcher)
+        .build()
+        .unwrap();
+    let server = Arc::new(Mutex::new(server));
+    // Answer requests inline so the standalone streams carry only notifications
+    let mut config = StreamableHttpServerConfig::default();
+    config.enable_json_response = true;
+    let http =
+        StreamableHttpServer::with_config("127.0.0.1:0".parse().unwrap(), server.clone(), config);
+    (http, server)
+}
+
+#[tokio::test]
+async fn changes_reach_only_subscribed_sessions() {
+    let watcher = TestWatcher::default();
+    let (http, _server) = http_server(watcher.clone());
+
+    let subscribed = http.test_client();
+    let other = http.test_client();
+    subscribed.initialize().await.assert_success().await;
+    other.initialize().await.assert_success().await;
+    let mut subscribed_events = subscribed
+        .open_sse()
+        .await
+        .assert_success()
+        .await
+        .into_sse();
+    let mut other_events = other.open_sse().await.assert_success().await.into_sse();
+
+    subscribed
+        .request("resources/subscribe", json!({ "uri": "file:///app.log" }))
+        .await
+        .assert_result()
+        .await;
+    watcher.notify("file:///app.log");
+
+    let message = subscribed_events
+        .next_message_timeout(Duration::from_secs(5))
+        .await
+        .expect("resource updated notification");
+    assert_eq!(message["method"], "notifications/resources/updated");
+    assert_eq!(message["params"]["uri"], "file:///app.log");
+
+    let message = other_events
+        .next_message_timeout(Duration::from_millis(200))
+        .await;
+    assert!(message.is_none(), "unexpected {message:?}");
+}
+
+#[tokio::test]
+async fn closed_sessions_are_unsubscribed() {
+    let (http, server) = http_server(TestWatcher::default());
+    let subscribers = || async {
+        server
+            .lock()
+            .await
+            .notify_resource_updated("file:///app.log".to_string())
+            .await
+            .unwrap()
+    };
+
+    let client = http.test_client();
+    client.initialize().await.assert_success().await;
+    client
+        .request("resources/subscribe", json!({ "uri": "file:///app.log" }))
+        .await
+        .assert_result()
+        .await;
+    assert_eq!(subscribers().await, 1);
+
+    client.close_session().await.assert_success().await;
+    assert_eq!(subscribers().await, 0);
+}
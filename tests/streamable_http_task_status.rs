@@ -0,0 +1,89 @@
+//! Tests for `notifications/tasks/status` pushed over the standalone SSE stream.
+
+#![cfg(feature = "streamable-http")]
+
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::task_store::{InMemoryTaskStore, TaskStore};
+use pmcp::Server;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn http_server(store: &InMemoryTaskStore) -> StreamableHttpServer {
+    let server = Server::builder()
+        .name("task-status-test-server")
+        .version("1.0.0")
+        .build()
+        .unwrap();
+    let mut config = StreamableHttpServerConfig::default();
+    config.task_status_updates = store.status_updates();
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn task_transitions_are_pushed_to_tasks_capable_sessions() {
+    let store = InMemoryTaskStore::new();
+    let http = http_server(&store);
+
+    let client = http.test_client();
+    client
+        .initialize_with_capabilities(json!({ "tasks": {} }))
+        .await
+        .assert_success()
+        .await;
+    let mut events = client.open_sse().await.assert_success().await.into_sse();
+
+    let task = store.create("local", None).await.unwrap();
+    store.cancel(&task.task_id, "local").await.unwrap();
+
+    for status in ["working", "cancelled"] {
+        let message = events
+            .next_message_timeout(Duration::from_secs(5))
+            .await
+            .expect("task status notification");
+        assert_eq!(message["method"], "notifications/tasks/status");
+        assert_eq!(message["params"]["task"]["taskId"], task.task_id.as_str());
+        assert_eq!(message["params"]["task"]["status"], status);
+    }
+}
+
+#[tokio::test]
+async fn sessions_without_tasks_capability_or_owner_get_nothing() {
+    let store = InMemoryTaskStore::new();
+    let http = http_server(&store);
+
+    let client = http.test_client();
+    client.initialize().await.assert_success().await;
+    let mut events = client.open_sse().await.assert_success().await.into_sse();
+
+    let capable = http.test_client();
+    capable
+        .initialize_with_capabilities(json!({ "tasks": {} }))
+        .await
+        .assert_success()
+        .await;
+    let mut capable_events = capable.open_sse().await.assert_success().await.into_sse();
+
+    // Tasks of another owner are not pushed to the local owner's session
+    store.create("someone-else", None).await.unwrap();
+    store.create("local", None).await.unwrap();
+
+    let message = capable_events
+        .next_message_timeout(Duration::from_secs(5))
+        .await
+        .expect("task status notification");
+    assert_eq!(message["method"], "notifications/tasks/status");
+    assert!(events
+        .next_message_timeout(Duration::from_millis(200))
+        .await
+        .is_none());
+    assert!(capable_events
+        .next_message_timeout(Duration::from_millis(200))
+        .await
+        .is_none());
+}
@@ -0,0 +1,109 @@
+//! Tests for the in-process `StreamableHttpServer::test_client()` harness.
+
+#![cfg(feature = "streamable-http")]
+
+use axum::http::StatusCode;
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::server::test_client::assert_jsonrpc_result;
+use pmcp::Server;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn http_server(config: StreamableHttpServerConfig) -> StreamableHttpServer {
+    let server = Server::builder()
+        .name("test-client-server")
+        .version("1.0.0")
+        .build()
+        .unwrap();
+    StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn initialize_establishes_session() {
+    let client = http_server(StreamableHttpServerConfig::default()).test_client();
+
+    let message = client
+        .initialize()
+        .await
+        .assert_success()
+        .await
+        .message()
+        .await;
+    let result = assert_jsonrpc_result(&message);
+
+    assert_eq!(result["serverInfo"]["name"], "test-client-server");
+    assert!(client.session_id().is_some());
+    assert_eq!(
+        client.protocol_version().as_deref(),
+        result["protocolVersion"].as_str()
+    );
+}
+
+#[tokio::test]
+async fn requests_reuse_session() {
+    let client = http_server(StreamableHttpServerConfig::default()).test_client();
+    client.initialize().await.assert_success().await;
+
+    let result = client
+        .request("ping", json!({}))
+        .await
+        .assert_result()
+        .await;
+    assert_eq!(result, json!({}));
+}
+
+#[tokio::test]
+async fn unknown_session_is_rejected() {
+    let client = http_server(StreamableHttpServerConfig::default()).test_client();
+    client.initialize().await.assert_success().await;
+    client.set_session_id(Some("not-a-session".to_string()));
+
+    let response = client
+        .request("ping", json!({}))
+        .await
+        .assert_status(StatusCode::NOT_FOUND)
+        .await;
+    response.assert_error(-32600).await;
+}
+
+#[tokio::test]
+async fn stateless_mode_returns_json() {
+    let client = http_server(StreamableHttpServerConfig::stateless()).test_client();
+
+    let response = client.initialize().await.assert_success().await;
+    assert!(!response.is_sse());
+    assert!(client.session_id().is_none());
+
+    client
+        .request("ping", json!({}))
+        .await
+        .assert_result()
+        .await;
+}
+
+#[tokio::test]
+async fn sse_stream_opens_and_session_closes() {
+    let client = http_server(StreamableHttpServerConfig::default()).test_client();
+    client.initialize().await.assert_success().await;
+
+    let response = client.open_sse().await.assert_success().await;
+    assert!(response.is_sse());
+    let mut events = response.into_sse();
+    assert!(events
+        .next_message_timeout(Duration::from_millis(50))
+        .await
+        .is_none());
+
+    client.close_session().await.assert_success().await;
+    client
+        .request("ping", json!({}))
+        .await
+        .assert_status(StatusCode::NOT_FOUND)
+        .await;
+}
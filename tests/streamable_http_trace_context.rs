@@ -0,0 +1,79 @@
+//! W3C trace context propagation between the foundation client and the
+//! streamable HTTP server.
+
+#![cfg(feature = "composition")]
+
+use pmcp::composition::{FoundationClient, McpFoundationClient};
+use pmcp::server::observability::TraceContext;
+use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+use pmcp::{Server, SimpleTool};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Start a server whose `whoami` tool reports the trace it runs in.
+async fn start_server() -> (String, tokio::task::JoinHandle<()>) {
+    let server = Server::builder()
+        .name("trace-test-server")
+        .version("1.0.0")
+        .tool(
+            "whoami",
+            SimpleTool::new("whoami", |_args, _extra| {
+                Box::pin(async move {
+                    let trace = TraceContext::current();
+                    Ok(json!({
+                        "trace_id": trace.as_ref().map(|t| t.trace_id.clone()),
+                        "span_id": trace.as_ref().map(|t| t.span_id.clone()),
+                        "tracestate": trace.and_then(|t| t.tracestate),
+                    }))
+                })
+            }),
+        )
+        .build()
+        .unwrap();
+    let (addr, handle) = StreamableHttpServer::with_config(
+        "127.0.0.1:0".parse().unwrap(),
+        Arc::new(Mutex::new(server)),
+        StreamableHttpServerConfig::stateless(),
+    )
+    .start()
+    .await
+    .unwrap();
+    (format!("http://{addr}"), handle)
+}
+
+async fn whoami(client: &McpFoundationClient) -> Value {
+    let text = client
+        .call_tool("remote", "whoami", &json!({}))
+        .await
+        .unwrap();
+    serde_json::from_str(&text).unwrap()
+}
+
+#[tokio::test]
+async fn current_trace_is_propagated_to_the_remote_tool() {
+    let (url, handle) = start_server().await;
+    let client = McpFoundationClient::for_server("remote", url);
+
+    let mut caller = TraceContext::new_root();
+    caller.tracestate = Some("vendor=abc".to_string());
+    let seen = caller.clone().scope(whoami(&client)).await;
+
+    assert_eq!(seen["trace_id"], json!(caller.trace_id));
+    assert_eq!(seen["span_id"], json!(caller.span_id));
+    assert_eq!(seen["tracestate"], json!("vendor=abc"));
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn calls_outside_a_trace_send_no_traceparent() {
+    let (url, handle) = start_server().await;
+    let client = McpFoundationClient::for_server("remote", url);
+
+    let seen = whoami(&client).await;
+
+    assert_eq!(seen["trace_id"], Value::Null);
+
+    handle.abort();
+}
@@ -3,7 +3,7 @@
 #![cfg(feature = "streamable-http")]
 
 use pmcp::shared::streamable_http::{
-    AuthProvider, SendOptions, StreamableHttpTransport, StreamableHttpTransportConfig,
+    AuthProvider, SendOptions, StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
 };
 use pmcp::shared::Transport;
 use pmcp::types::{ClientRequest, Request, RequestId};
@@ -42,19 +42,18 @@ fn test_send_options_with_values() {
 
 #[test]
 fn test_streamable_http_config_creation() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![("X-Custom".to_string(), "value".to_string())],
-        auth_provider: Some(Arc::new(MockAuthProvider {
-            token: "test-token".to_string(),
-        })),
-        session_id: Some("session-123".to_string()),
-        enable_json_response: true,
-        on_resumption_token: Some(Arc::new(|token| {
-            println!("Resumption token: {}", token);
-        })),
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .with_header("X-Custom", "value")
+            .with_auth_provider(Arc::new(MockAuthProvider {
+                token: "test-token".to_string(),
+            }))
+            .with_session_id("session-123")
+            .enable_json_response()
+            .on_resumption_token(Arc::new(|token| {
+                println!("Resumption token: {}", token);
+            }))
+            .build();
 
     assert_eq!(config.url.as_str(), "http://localhost:8080/");
     assert_eq!(config.extra_headers.len(), 1);
@@ -66,15 +65,9 @@ fn test_streamable_http_config_creation() {
 
 #[test]
 fn test_streamable_http_transport_creation() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .build();
 
     let transport = StreamableHttpTransport::new(config);
     assert!(transport.session_id().is_none());
@@ -85,15 +78,10 @@ fn test_streamable_http_transport_creation() {
 
 #[test]
 fn test_streamable_http_transport_session_management() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: Some("initial-session".to_string()),
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .with_session_id("initial-session")
+            .build();
 
     let transport = StreamableHttpTransport::new(config);
     assert_eq!(transport.session_id(), Some("initial-session".to_string()));
@@ -109,15 +97,9 @@ fn test_streamable_http_transport_session_management() {
 
 #[test]
 fn test_streamable_http_transport_protocol_version() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .build();
 
     let transport = StreamableHttpTransport::new(config);
     assert!(transport.protocol_version().is_none());
@@ -129,15 +111,9 @@ fn test_streamable_http_transport_protocol_version() {
 
 #[test]
 fn test_streamable_http_config_debug() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .build();
 
     let debug_str = format!("{:?}", config);
     assert!(debug_str.contains("StreamableHttpTransportConfig"));
@@ -147,15 +123,9 @@ fn test_streamable_http_config_debug() {
 
 #[test]
 fn test_streamable_http_transport_debug() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .build();
 
     let transport = StreamableHttpTransport::new(config);
     let debug_str = format!("{:?}", transport);
@@ -165,15 +135,12 @@ fn test_streamable_http_transport_debug() {
 
 #[test]
 fn test_streamable_http_config_clone() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![("X-Test".to_string(), "value".to_string())],
-        auth_provider: None,
-        session_id: Some("session-123".to_string()),
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .with_header("X-Test", "value")
+            .with_session_id("session-123")
+            .enable_json_response()
+            .build();
 
     let cloned = config.clone();
     assert_eq!(config.url, cloned.url);
@@ -184,15 +151,10 @@ fn test_streamable_http_config_clone() {
 
 #[test]
 fn test_streamable_http_transport_clone() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: Some("session-123".to_string()),
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .with_session_id("session-123")
+            .build();
 
     let transport = StreamableHttpTransport::new(config);
     let cloned = transport.clone();
@@ -201,15 +163,9 @@ fn test_streamable_http_transport_clone() {
 
 #[tokio::test]
 async fn test_streamable_http_transport_close() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .build();
 
     let mut transport = StreamableHttpTransport::new(config);
 
@@ -220,17 +176,12 @@ async fn test_streamable_http_transport_close() {
 
 #[tokio::test]
 async fn test_streamable_http_send_with_auth_provider() {
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse("http://localhost:8080").unwrap(),
-        extra_headers: vec![],
-        auth_provider: Some(Arc::new(MockAuthProvider {
-            token: "bearer-token-123".to_string(),
-        })),
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    let config =
+        StreamableHttpTransportConfigBuilder::new(Url::parse("http://localhost:8080").unwrap())
+            .with_auth_provider(Arc::new(MockAuthProvider {
+                token: "bearer-token-123".to_string(),
+            }))
+            .build();
 
     let mut transport = StreamableHttpTransport::new(config);
 
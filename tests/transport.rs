@@ -2,7 +2,9 @@
 
 use pmcp::server::streamable_http_server::StreamableHttpServer;
 use pmcp::server::Server;
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::shared::{Transport, TransportMessage};
 use pmcp::types::{ClientCapabilities, ClientRequest, Implementation, InitializeRequest, Request};
 // Use boxed error for tests to satisfy clippy
@@ -32,18 +34,13 @@ async fn test_streamable_http_transport_send_receive() -> Result<()> {
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
     // Setup the client transport
-    let client_config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
+    let client_config = StreamableHttpTransportConfigBuilder::new(
+        Url::parse(&format!("http://{}", server_addr)).map_err(|e| {
             Box::new(pmcp::Error::Internal(e.to_string()))
                 as Box<dyn std::error::Error + Send + Sync>
         })?,
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: false,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    )
+    .build();
     let mut client_transport = StreamableHttpTransport::new(client_config);
 
     // Create an Initialize request first (to get session ID)
@@ -3,11 +3,17 @@
 //! This test mimics exactly what mcp-tester does: start a server, connect a client,
 //! and verify the prompts/list response includes complete metadata.
 
-#![cfg(all(feature = "streamable-http", not(target_arch = "wasm32")))]
+#![cfg(all(
+    feature = "streamable-http",
+    feature = "workflow",
+    not(target_arch = "wasm32")
+))]
 
 use pmcp::server::streamable_http_server::StreamableHttpServer;
 use pmcp::server::workflow::{InternalPromptMessage, SequentialWorkflow};
-use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use pmcp::shared::streamable_http::{
+    StreamableHttpTransport, StreamableHttpTransportConfigBuilder,
+};
 use pmcp::types::Role;
 use pmcp::{Client, ClientCapabilities, Result, Server};
 use std::net::SocketAddr;
@@ -78,16 +84,12 @@ async fn test_workflow_prompt_metadata_over_http() -> Result<()> {
     sleep(Duration::from_millis(500)).await;
 
     // Create client
-    let config = StreamableHttpTransportConfig {
-        url: Url::parse(&format!("http://{}", addr))
+    let config = StreamableHttpTransportConfigBuilder::new(
+        Url::parse(&format!("http://{}", addr))
             .map_err(|e| pmcp::Error::Internal(e.to_string()))?,
-        extra_headers: vec![],
-        auth_provider: None,
-        session_id: None,
-        enable_json_response: true,
-        on_resumption_token: None,
-        http_middleware_chain: None,
-    };
+    )
+    .enable_json_response()
+    .build();
     let transport = StreamableHttpTransport::new(config);
     let mut client = Client::new(transport);
 